@@ -0,0 +1,153 @@
+// Operator dashboard for the Poblysh Connectors API.
+//
+// Requests rely on the browser re-sending the credentials entered at the login
+// prompt; the selected tenant is passed through the X-Tenant-Id header.
+(function () {
+  "use strict";
+
+  const LIMIT = 50;
+  const tenantSelect = document.getElementById("tenant");
+  const statusLine = document.getElementById("status");
+
+  async function api(path, tenantId) {
+    const headers = { Accept: "application/json" };
+    if (tenantId) {
+      headers["X-Tenant-Id"] = tenantId;
+    }
+    const response = await fetch("/admin/api" + path, {
+      headers,
+      credentials: "same-origin",
+    });
+    if (!response.ok) {
+      let message = response.status + " " + response.statusText;
+      try {
+        const problem = await response.json();
+        message = problem.message || message;
+      } catch (_) {
+        // Keep the HTTP status text
+      }
+      throw new Error(path + ": " + message);
+    }
+    return response.json();
+  }
+
+  function renderTable(id, columns, rows) {
+    const table = document.getElementById(id);
+    table.replaceChildren();
+
+    const headRow = table.createTHead().insertRow();
+    for (const column of columns) {
+      const th = document.createElement("th");
+      th.textContent = column.label;
+      headRow.appendChild(th);
+    }
+
+    const body = table.createTBody();
+    if (rows.length === 0) {
+      const cell = body.insertRow().insertCell();
+      cell.colSpan = columns.length;
+      cell.className = "empty";
+      cell.textContent = "Nothing to show";
+      return;
+    }
+    for (const row of rows) {
+      const tr = body.insertRow();
+      for (const column of columns) {
+        const value = column.value(row);
+        tr.insertCell().textContent = value === null || value === undefined ? "—" : value;
+      }
+    }
+  }
+
+  const jobColumns = [
+    { label: "Job", value: (job) => job.id.slice(0, 8) },
+    { label: "Provider", value: (job) => job.provider_slug },
+    { label: "Type", value: (job) => job.job_type },
+    { label: "Status", value: (job) => job.status },
+    { label: "Priority", value: (job) => job.priority },
+    { label: "Attempts", value: (job) => job.attempts },
+    { label: "Scheduled", value: (job) => job.scheduled_at },
+    { label: "Finished", value: (job) => job.finished_at },
+  ];
+
+  async function loadTenant(tenantId) {
+    statusLine.textContent = "";
+    const loaders = [
+      api("/connections?limit=" + LIMIT, tenantId).then((page) =>
+        renderTable(
+          "connections",
+          [
+            { label: "Connection", value: (c) => c.id },
+            { label: "Provider", value: (c) => c.provider },
+            { label: "Expires", value: (c) => c.expires_at },
+            { label: "Access token", value: (c) => (c.has_access_token ? "yes" : "no") },
+            { label: "Refresh token", value: (c) => (c.has_refresh_token ? "yes" : "no") },
+          ],
+          page.connections
+        )
+      ),
+      api("/jobs?limit=" + LIMIT, tenantId).then((page) =>
+        renderTable(
+          "jobs",
+          jobColumns,
+          page.jobs.filter((job) => job.job_type !== "webhook")
+        )
+      ),
+      api("/jobs?job_type=webhook&limit=" + LIMIT, tenantId).then((page) =>
+        renderTable("webhooks", jobColumns, page.jobs)
+      ),
+      api("/grounded-signals?limit=" + LIMIT + "&tenant_id=" + encodeURIComponent(tenantId), tenantId).then(
+        (page) =>
+          renderTable(
+            "grounded-signals",
+            [
+              { label: "Signal", value: (s) => s.signal_id.slice(0, 8) },
+              { label: "Status", value: (s) => s.status },
+              { label: "Score", value: (s) => s.scores.total.toFixed(2) },
+              { label: "Recommendation", value: (s) => s.recommendation },
+              { label: "Created", value: (s) => s.created_at },
+            ],
+            page.data
+          )
+      ),
+    ];
+
+    const failures = (await Promise.allSettled(loaders)).filter((r) => r.status === "rejected");
+    statusLine.textContent = failures.map((r) => r.reason.message).join("; ");
+  }
+
+  async function init() {
+    try {
+      const tenants = await api("/tenants");
+      for (const tenant of tenants) {
+        const option = document.createElement("option");
+        option.value = tenant.id;
+        option.textContent = tenant.name ? tenant.name + " (" + tenant.id + ")" : tenant.id;
+        tenantSelect.appendChild(option);
+      }
+      const remembered = sessionStorage.getItem("poblysh.admin.tenant");
+      if (remembered && tenants.some((t) => t.id === remembered)) {
+        tenantSelect.value = remembered;
+      }
+      if (tenantSelect.value) {
+        await loadTenant(tenantSelect.value);
+      } else {
+        statusLine.textContent = "No tenants found";
+      }
+    } catch (error) {
+      statusLine.textContent = error.message;
+    }
+  }
+
+  tenantSelect.addEventListener("change", () => {
+    sessionStorage.setItem("poblysh.admin.tenant", tenantSelect.value);
+    loadTenant(tenantSelect.value);
+  });
+  document.getElementById("refresh").addEventListener("click", () => {
+    if (tenantSelect.value) {
+      loadTenant(tenantSelect.value);
+    }
+  });
+
+  init();
+})();
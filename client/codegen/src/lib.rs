@@ -0,0 +1,577 @@
+//! Rust client generation from the Connectors OpenAPI document
+//!
+//! The generator understands the subset of OpenAPI 3.1 that utoipa emits for
+//! this service: component schemas made of objects, string enums, nullable
+//! primitives, arrays, maps and `oneOf` with `null`, plus operations with path,
+//! query and header parameters, JSON request bodies and JSON, text or empty
+//! responses. Anything it cannot type precisely becomes `serde_json::Value`.
+//!
+//! Output is a single module with `types` (component schemas), `params`
+//! (per-operation query and header parameters) and an `impl Client` block
+//! with one async method per `operationId`. It relies on the hand-written
+//! `Client::request`, `json`, `text` and `empty` helpers of the client crate.
+
+use anyhow::{Context, Result, anyhow, bail};
+use serde_json::{Map, Value};
+use std::collections::BTreeSet;
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// HTTP methods in the order operations are emitted for each path
+const METHODS: [&str; 5] = ["get", "put", "post", "delete", "patch"];
+
+/// Headers the client sets itself on every request
+const CLIENT_HEADERS: [&str; 2] = ["authorization", "x-tenant-id"];
+
+/// Generate the client module and format it with rustfmt
+pub fn render(spec: &Value) -> Result<String> {
+    rustfmt(&generate(spec)?)
+}
+
+/// Generate the client module without formatting
+pub fn generate(spec: &Value) -> Result<String> {
+    let empty = Map::new();
+    let schemas = spec
+        .pointer("/components/schemas")
+        .and_then(Value::as_object)
+        .unwrap_or(&empty);
+
+    let mut types = Module::default();
+    for (name, schema) in schemas {
+        types.named_type(&pascal_case(name), schema)?;
+    }
+
+    let mut params = Module::default();
+    let mut methods = Vec::new();
+    let paths = spec
+        .get("paths")
+        .and_then(Value::as_object)
+        .context("spec has no paths")?;
+    for (path, operations) in paths {
+        for method in METHODS {
+            if let Some(operation) = operations.get(method) {
+                methods.push(operation_method(path, method, operation, &mut params)?);
+            }
+        }
+    }
+
+    let mut out = String::new();
+    out.push_str(
+        "// @generated by poblysh-connectors-client-codegen from openapi.json. Do not edit.\n\n",
+    );
+    out.push_str("use crate::{Client, Error};\n#[allow(unused_imports)]\nuse params::*;\nuse reqwest::Method;\n#[allow(unused_imports)]\nuse types::*;\n\n");
+    out.push_str("/// Schemas from the `components` section of the spec\npub mod types {\n");
+    out.push_str("use serde::{Deserialize, Serialize};\n\n");
+    out.push_str(&types.items.join("\n"));
+    out.push_str("}\n\n");
+    out.push_str("/// Query and header parameters of individual operations\npub mod params {\n");
+    out.push_str(
+        "#[allow(unused_imports)]\nuse super::types::*;\nuse serde::{Deserialize, Serialize};\n\n",
+    );
+    out.push_str(&params.items.join("\n"));
+    out.push_str("}\n\n");
+    out.push_str("impl Client {\n");
+    out.push_str(&methods.join("\n"));
+    out.push_str("}\n");
+    Ok(out)
+}
+
+/// Format Rust source with the rustfmt found on `PATH`
+pub fn rustfmt(source: &str) -> Result<String> {
+    let mut child = Command::new("rustfmt")
+        .args(["--edition", "2024", "--emit", "stdout"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .context("failed to run rustfmt")?;
+    child
+        .stdin
+        .take()
+        .context("rustfmt stdin unavailable")?
+        .write_all(source.as_bytes())?;
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        bail!(
+            "rustfmt rejected generated code: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+    Ok(String::from_utf8(output.stdout)?)
+}
+
+/// Items emitted into one generated module
+#[derive(Default)]
+struct Module {
+    items: Vec<String>,
+    names: BTreeSet<String>,
+}
+
+impl Module {
+    /// Emit a top-level item for a named schema
+    fn named_type(&mut self, name: &str, schema: &Value) -> Result<()> {
+        if let Some(values) = schema.get("enum").and_then(Value::as_array) {
+            return self.string_enum(name, schema, values);
+        }
+        if schema
+            .get("properties")
+            .and_then(Value::as_object)
+            .is_some()
+        {
+            return self.object(name, schema);
+        }
+        let ty = self.rust_type(schema, name)?;
+        self.push(
+            name,
+            format!("{}pub type {name} = {ty};\n", doc(schema, "")),
+        )
+    }
+
+    fn push(&mut self, name: &str, item: String) -> Result<()> {
+        if !self.names.insert(name.to_string()) {
+            bail!("generated type name {name} is used twice");
+        }
+        self.items.push(item);
+        Ok(())
+    }
+
+    fn object(&mut self, name: &str, schema: &Value) -> Result<()> {
+        let required = required_names(schema);
+        let mut fields = String::new();
+        if let Some(properties) = schema.get("properties").and_then(Value::as_object) {
+            for (property, property_schema) in properties {
+                let field = field_name(property);
+                let context = format!("{name}{}", pascal_case(property));
+                let ty = self.rust_type(property_schema, &context)?;
+                fields.push_str(&doc(property_schema, "    "));
+                if field.trim_start_matches("r#") != property {
+                    fields.push_str(&format!("    #[serde(rename = \"{property}\")]\n"));
+                }
+                if required.contains(property.as_str()) {
+                    fields.push_str(&format!("    pub {field}: {ty},\n"));
+                } else {
+                    fields.push_str(
+                        "    #[serde(default, skip_serializing_if = \"Option::is_none\")]\n",
+                    );
+                    fields.push_str(&format!("    pub {field}: {},\n", optional(&ty)));
+                }
+            }
+        }
+        let derives = if required.is_empty() {
+            "Debug, Clone, Default, PartialEq, Serialize, Deserialize"
+        } else {
+            "Debug, Clone, PartialEq, Serialize, Deserialize"
+        };
+        self.push(
+            name,
+            format!(
+                "{}#[derive({derives})]\npub struct {name} {{\n{fields}}}\n",
+                doc(schema, "")
+            ),
+        )
+    }
+
+    fn string_enum(&mut self, name: &str, schema: &Value, values: &[Value]) -> Result<()> {
+        let mut variants = String::new();
+        for value in values {
+            let value = value
+                .as_str()
+                .ok_or_else(|| anyhow!("enum {name} has a non-string value"))?;
+            variants.push_str(&format!(
+                "    #[serde(rename = \"{value}\")]\n    {},\n",
+                pascal_case(value)
+            ));
+        }
+        self.push(
+            name,
+            format!(
+                "{}#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]\npub enum {name} {{\n{variants}}}\n",
+                doc(schema, "")
+            ),
+        )
+    }
+
+    /// Rust type for a schema, emitting inline objects and enums as `context`
+    fn rust_type(&mut self, schema: &Value, context: &str) -> Result<String> {
+        if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+            let name = reference
+                .strip_prefix("#/components/schemas/")
+                .ok_or_else(|| anyhow!("unsupported reference {reference}"))?;
+            return Ok(pascal_case(name));
+        }
+        if let Some(variants) = schema.get("oneOf").and_then(Value::as_array) {
+            let non_null: Vec<&Value> = variants.iter().filter(|v| !is_null(v)).collect();
+            return match non_null.as_slice() {
+                [only] if non_null.len() < variants.len() => {
+                    Ok(optional(&self.rust_type(only, context)?))
+                }
+                [only] => self.rust_type(only, context),
+                _ => Ok("serde_json::Value".to_string()),
+            };
+        }
+        if let Some([only]) = schema
+            .get("allOf")
+            .and_then(Value::as_array)
+            .map(Vec::as_slice)
+        {
+            return self.rust_type(only, context);
+        }
+
+        let (kind, nullable) = match schema.get("type") {
+            Some(Value::String(kind)) => (kind.as_str(), false),
+            Some(Value::Array(kinds)) => {
+                let kinds: Vec<&str> = kinds.iter().filter_map(Value::as_str).collect();
+                let non_null: Vec<&str> = kinds.iter().copied().filter(|k| *k != "null").collect();
+                match non_null.as_slice() {
+                    [kind] => (*kind, kinds.len() > 1),
+                    _ => return Ok("serde_json::Value".to_string()),
+                }
+            }
+            _ => return Ok("serde_json::Value".to_string()),
+        };
+
+        let unsigned = schema
+            .get("minimum")
+            .and_then(Value::as_f64)
+            .is_some_and(|minimum| minimum >= 0.0);
+        let format = schema.get("format").and_then(Value::as_str);
+        let ty = match kind {
+            "string" if schema.get("enum").is_some() => {
+                let values = schema["enum"].as_array().cloned().unwrap_or_default();
+                self.string_enum(context, schema, &values)?;
+                context.to_string()
+            }
+            "string" => match format {
+                Some("uuid") => "uuid::Uuid".to_string(),
+                Some("date-time") => "chrono::DateTime<chrono::Utc>".to_string(),
+                _ => "String".to_string(),
+            },
+            "integer" => match (format, unsigned) {
+                (Some("int32"), true) => "u32",
+                (Some("int32"), false) => "i32",
+                (_, true) => "u64",
+                (_, false) => "i64",
+            }
+            .to_string(),
+            "number" if format == Some("float") => "f32".to_string(),
+            "number" => "f64".to_string(),
+            "boolean" => "bool".to_string(),
+            "array" => {
+                let items = schema.get("items").cloned().unwrap_or(Value::Null);
+                format!(
+                    "Vec<{}>",
+                    self.rust_type(&items, &format!("{context}Item"))?
+                )
+            }
+            "object"
+                if schema
+                    .get("properties")
+                    .and_then(Value::as_object)
+                    .is_some() =>
+            {
+                self.object(context, schema)?;
+                context.to_string()
+            }
+            "object" => match schema.get("additionalProperties") {
+                Some(values @ Value::Object(_)) => format!(
+                    "std::collections::BTreeMap<String, {}>",
+                    self.rust_type(values, &format!("{context}Value"))?
+                ),
+                _ => "serde_json::Value".to_string(),
+            },
+            _ => "serde_json::Value".to_string(),
+        };
+        Ok(if nullable { optional(&ty) } else { ty })
+    }
+}
+
+/// Async method for one operation; parameter structs go into `params`
+fn operation_method(
+    path: &str,
+    method: &str,
+    operation: &Value,
+    params: &mut Module,
+) -> Result<String> {
+    let operation_id = operation
+        .get("operationId")
+        .and_then(Value::as_str)
+        .ok_or_else(|| anyhow!("{} {path} has no operationId", method.to_uppercase()))?;
+    let name = field_name(operation_id);
+    let type_prefix = pascal_case(operation_id);
+
+    let parameters: Vec<&Value> = operation
+        .get("parameters")
+        .and_then(Value::as_array)
+        .map(|parameters| parameters.iter().collect())
+        .unwrap_or_default();
+    let located = |location: &str| -> Vec<&Value> {
+        parameters
+            .iter()
+            .copied()
+            .filter(|parameter| parameter.get("in").and_then(Value::as_str) == Some(location))
+            .collect()
+    };
+
+    let mut args = Vec::new();
+    let mut segments = Vec::new();
+    for segment in path.split('/').filter(|segment| !segment.is_empty()) {
+        match segment.strip_prefix('{').and_then(|s| s.strip_suffix('}')) {
+            Some(parameter) => {
+                let schema = located("path")
+                    .into_iter()
+                    .find(|p| p.get("name").and_then(Value::as_str) == Some(parameter))
+                    .and_then(|p| p.get("schema"))
+                    .cloned()
+                    .unwrap_or(Value::Null);
+                let arg = field_name(parameter);
+                if schema.get("type").and_then(Value::as_str) == Some("string")
+                    && schema.get("format").is_none()
+                {
+                    args.push(format!("{arg}: &str"));
+                    segments.push(arg);
+                } else {
+                    let ty = params
+                        .rust_type(&schema, &format!("{type_prefix}{}", pascal_case(parameter)))?;
+                    args.push(format!("{arg}: {ty}"));
+                    segments.push(format!("&{arg}.to_string()"));
+                }
+            }
+            None => segments.push(format!("\"{segment}\"")),
+        }
+    }
+
+    let query = located("query");
+    if !query.is_empty() {
+        let type_name = format!("{type_prefix}Params");
+        params.object(&type_name, &parameters_schema(&query))?;
+        args.push(format!("query: &params::{type_name}"));
+    }
+
+    let headers: Vec<&Value> = located("header")
+        .into_iter()
+        .filter(|header| {
+            let name = header
+                .get("name")
+                .and_then(Value::as_str)
+                .unwrap_or_default();
+            !CLIENT_HEADERS.contains(&name.to_ascii_lowercase().as_str())
+        })
+        .collect();
+    let mut header_lines = String::new();
+    if !headers.is_empty() {
+        let type_name = format!("{type_prefix}Headers");
+        let mut schema = parameters_schema(&headers);
+        // Headers are always sent as strings, whatever the spec declares
+        for property in schema["properties"]
+            .as_object_mut()
+            .into_iter()
+            .flat_map(|p| p.values_mut())
+        {
+            *property =
+                serde_json::json!({ "type": "string", "description": property.get("description") });
+        }
+        params.object(&type_name, &schema)?;
+        args.push(format!("headers: &params::{type_name}"));
+        for header in &headers {
+            let header = header["name"].as_str().unwrap_or_default();
+            header_lines.push_str(&format!(
+                "if let Some(value) = &headers.{} {{ request = request.header(\"{header}\", value); }}\n",
+                field_name(header)
+            ));
+        }
+    }
+
+    let mut body_lines = String::new();
+    if let Some(schema) = operation.pointer("/requestBody/content/application~1json/schema") {
+        let ty = params.rust_type(schema, &format!("{type_prefix}Body"))?;
+        match ty
+            .strip_prefix("Option<")
+            .and_then(|ty| ty.strip_suffix('>'))
+        {
+            Some(inner) => {
+                args.push(format!("body: Option<&{inner}>"));
+                body_lines.push_str("if let Some(body) = body { request = request.json(body); }\n");
+            }
+            None => {
+                args.push(format!("body: &{ty}"));
+                body_lines.push_str("request = request.json(body);\n");
+            }
+        }
+    }
+
+    let (output, finish) = response_shape(operation, &type_prefix, params)?;
+    let mutable = if header_lines.is_empty() && body_lines.is_empty() {
+        ""
+    } else {
+        "mut "
+    };
+    let query_call = if query.is_empty() {
+        ""
+    } else {
+        ".query(query)"
+    };
+
+    let mut out = String::new();
+    if let Some(summary) = operation.get("summary").and_then(Value::as_str) {
+        out.push_str(&format!("/// {}\n///\n", summary.trim()));
+    }
+    out.push_str(&format!("/// `{} {path}`\n", method.to_uppercase()));
+    out.push_str(&format!(
+        "pub async fn {name}(&self{}) -> Result<{output}, Error> {{\n",
+        args.iter()
+            .map(|arg| format!(", {arg}"))
+            .collect::<String>()
+    ));
+    out.push_str(&format!(
+        "let {mutable}request = self.request(Method::{}, &[{}]){query_call};\n",
+        method.to_uppercase(),
+        segments.join(", ")
+    ));
+    out.push_str(&header_lines);
+    out.push_str(&body_lines);
+    out.push_str(&format!("self.{finish}(request).await\n}}\n"));
+    Ok(out)
+}
+
+/// Output type and finishing helper for the first successful response
+fn response_shape(
+    operation: &Value,
+    type_prefix: &str,
+    params: &mut Module,
+) -> Result<(String, &'static str)> {
+    let Some(responses) = operation.get("responses").and_then(Value::as_object) else {
+        return Ok(("()".to_string(), "empty"));
+    };
+    let Some((_, response)) = responses.iter().find(|(status, _)| status.starts_with('2')) else {
+        return Ok(("()".to_string(), "empty"));
+    };
+    let Some(content) = response.get("content").and_then(Value::as_object) else {
+        return Ok(("()".to_string(), "empty"));
+    };
+    match content.get("application/json") {
+        Some(media) => {
+            let schema = media.get("schema").cloned().unwrap_or(Value::Null);
+            let ty = params.rust_type(&schema, &format!("{type_prefix}Response"))?;
+            Ok((ty, "json"))
+        }
+        None if !content.is_empty() => Ok(("String".to_string(), "text")),
+        None => Ok(("()".to_string(), "empty")),
+    }
+}
+
+/// Object schema with one property per parameter
+fn parameters_schema(parameters: &[&Value]) -> Value {
+    let mut properties = Map::new();
+    let mut required = Vec::new();
+    for parameter in parameters {
+        let name = parameter["name"].as_str().unwrap_or_default().to_string();
+        let mut schema = parameter.get("schema").cloned().unwrap_or(Value::Null);
+        if let (Some(object), Some(description)) =
+            (schema.as_object_mut(), parameter.get("description"))
+        {
+            object.insert("description".to_string(), description.clone());
+        }
+        if parameter.get("required").and_then(Value::as_bool) == Some(true) {
+            required.push(Value::String(name.clone()));
+        }
+        properties.insert(name, schema);
+    }
+    serde_json::json!({ "type": "object", "required": required, "properties": properties })
+}
+
+fn required_names(schema: &Value) -> BTreeSet<&str> {
+    schema
+        .get("required")
+        .and_then(Value::as_array)
+        .map(|names| names.iter().filter_map(Value::as_str).collect())
+        .unwrap_or_default()
+}
+
+fn is_null(schema: &Value) -> bool {
+    schema.get("type").and_then(Value::as_str) == Some("null")
+}
+
+fn optional(ty: &str) -> String {
+    if ty.starts_with("Option<") {
+        ty.to_string()
+    } else {
+        format!("Option<{ty}>")
+    }
+}
+
+/// Doc comment from the first paragraph of a schema description
+fn doc(schema: &Value, indent: &str) -> String {
+    let Some(description) = schema.get("description").and_then(Value::as_str) else {
+        return String::new();
+    };
+    description
+        .lines()
+        .map(str::trim)
+        .take_while(|line| !line.is_empty() && !line.starts_with("- ") && !line.starts_with("* "))
+        .map(|line| format!("{indent}/// {line}\n"))
+        .collect()
+}
+
+/// `CamelCase` type name from schema names, enum values and operation ids
+pub fn pascal_case(name: &str) -> String {
+    name.split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            let first = chars
+                .next()
+                .map(|c| c.to_ascii_uppercase())
+                .unwrap_or_default();
+            let rest: String = chars.collect();
+            // All-caps words such as BASE or GET read as one word
+            if part.chars().all(|c| !c.is_ascii_lowercase()) {
+                format!("{first}{}", rest.to_ascii_lowercase())
+            } else {
+                format!("{first}{rest}")
+            }
+        })
+        .collect()
+}
+
+/// `snake_case` identifier, escaped when it is a keyword
+pub fn field_name(name: &str) -> String {
+    let mut field = String::new();
+    let mut previous_lower = false;
+    for c in name.chars() {
+        if c.is_ascii_alphanumeric() {
+            if c.is_ascii_uppercase() && previous_lower {
+                field.push('_');
+            }
+            previous_lower = c.is_ascii_lowercase() || c.is_ascii_digit();
+            field.push(c.to_ascii_lowercase());
+        } else {
+            if !field.ends_with('_') {
+                field.push('_');
+            }
+            previous_lower = false;
+        }
+    }
+    let field = field.trim_matches('_').to_string();
+    match field.as_str() {
+        "type" | "ref" | "match" | "move" | "self" | "static" | "use" | "where" | "async"
+        | "await" | "fn" | "impl" | "mod" | "loop" | "in" | "as" | "enum" | "struct" => {
+            format!("r#{field}")
+        }
+        _ => field,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_names() {
+        assert_eq!(pascal_case("Resolved_u32"), "ResolvedU32");
+        assert_eq!(pascal_case("in_progress"), "InProgress");
+        assert_eq!(pascal_case("JITTER_FACTOR"), "JitterFactor");
+        assert_eq!(field_name("X-Hub-Signature-256"), "x_hub_signature_256");
+        assert_eq!(field_name("JITTER_FACTOR"), "jitter_factor");
+        assert_eq!(field_name("type"), "r#type");
+    }
+}
@@ -0,0 +1,22 @@
+//! Regenerate `client/src/generated.rs` from `openapi.json`
+//!
+//! Usage: `poblysh-connectors-client-codegen [SPEC] [OUTPUT]`, defaulting to
+//! the workspace paths when run from the repository root.
+
+use anyhow::{Context, Result};
+use std::fs;
+
+fn main() -> Result<()> {
+    let mut args = std::env::args().skip(1);
+    let spec_path = args.next().unwrap_or_else(|| "openapi.json".to_string());
+    let output_path = args
+        .next()
+        .unwrap_or_else(|| "client/src/generated.rs".to_string());
+
+    let spec = fs::read_to_string(&spec_path).with_context(|| format!("reading {spec_path}"))?;
+    let spec = serde_json::from_str(&spec).with_context(|| format!("parsing {spec_path}"))?;
+    let source = poblysh_connectors_client_codegen::render(&spec)?;
+    fs::write(&output_path, source).with_context(|| format!("writing {output_path}"))?;
+    println!("Wrote {output_path}");
+    Ok(())
+}
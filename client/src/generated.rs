@@ -0,0 +1,2759 @@
+// @generated by poblysh-connectors-client-codegen from openapi.json. Do not edit.
+
+use crate::{Client, Error};
+#[allow(unused_imports)]
+use params::*;
+use reqwest::Method;
+#[allow(unused_imports)]
+use types::*;
+
+/// Schemas from the `components` section of the spec
+pub mod types {
+    use serde::{Deserialize, Serialize};
+
+    /// Unified API error response structure
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct ApiError {
+        /// Error code for programmatic handling
+        pub code: String,
+        /// Additional error details (optional)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub details: Option<serde_json::Value>,
+        /// Human-readable error message
+        pub message: String,
+        /// Suggested retry delay in seconds (optional)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub retry_after: Option<u64>,
+        /// Correlation trace ID for debugging (optional)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub trace_id: Option<String>,
+    }
+
+    /// Provider whose stored auth type differs from the code
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct AuthTypeDrift {
+        /// Auth type stored in the providers table
+        pub actual: String,
+        /// Auth type defined in code
+        pub expected: String,
+        /// Provider slug
+        pub slug: String,
+    }
+
+    /// OAuth authorization URL response for API
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct AuthorizeUrlResponse {
+        /// Complete authorization URL for user redirection
+        /// Must be HTTPS, valid per RFC 3986, max 2048 chars, no fragment
+        pub authorize_url: String,
+    }
+
+    /// A blackout window during which no syncs are scheduled
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct BlackoutInfo {
+        /// End of the window (RFC3339)
+        pub ends_at: String,
+        /// Optional reason, e.g. provider maintenance
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub reason: Option<String>,
+        /// Start of the window (RFC3339)
+        pub starts_at: String,
+    }
+
+    /// Initial signal configuration for a bootstrapped tenant
+    #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+    pub struct BootstrapSignalConfigDto {
+        /// Weak signal threshold between 0.0 and 1.0 (default: 0.7)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub weak_signal_threshold: Option<f32>,
+        /// HTTPS webhook notified about signals and connection state changes
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub webhook_url: Option<String>,
+    }
+
+    /// Signal configuration created for a bootstrapped tenant
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct BootstrapSignalConfigResponseDto {
+        /// Weak signal threshold
+        pub weak_signal_threshold: f32,
+        /// Notification webhook URL, if configured
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub webhook_url: Option<String>,
+    }
+
+    /// Request payload for bootstrapping a tenant
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct BootstrapTenantRequestDto {
+        /// Custom signal kinds to register for the tenant
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub custom_signal_kinds: Option<Vec<RegisterSignalKindRequest>>,
+        /// Optional metadata for the tenant
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub metadata: Option<serde_json::Value>,
+        /// Display name for the tenant (required, max 255 characters)
+        pub name: String,
+        /// Organization whose default settings the tenant inherits
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub organization_id: Option<String>,
+        /// Signal configuration; defaults apply when omitted
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub signal_config: Option<BootstrapSignalConfigDto>,
+    }
+
+    /// Response payload for tenant bootstrap
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct BootstrapTenantResponseDto {
+        /// Custom signal kinds registered for the tenant
+        pub custom_signal_kinds: Vec<CustomSignalKindInfo>,
+        /// Whether a per-tenant data key was provisioned for token encryption
+        pub data_key_provisioned: bool,
+        /// The tenant's signal configuration
+        pub signal_config: BootstrapSignalConfigResponseDto,
+        /// The created tenant
+        pub tenant: CreateTenantResponseDto,
+    }
+
+    /// Differences between the code catalog and the providers table
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct CatalogReport {
+        /// Providers inserted by this reconciliation
+        pub added: Vec<String>,
+        /// Providers whose stored auth type differs from the code
+        pub auth_type_drift: Vec<AuthTypeDrift>,
+        /// Providers defined in code but absent from the table
+        pub missing: Vec<String>,
+        /// Providers in the table that the code no longer defines
+        pub removed: Vec<String>,
+    }
+
+    /// Request payload for cloning a sandbox tenant
+    #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+    pub struct CloneSandboxRequestDto {
+        /// Display name for the sandbox (default: the source name followed by "sandbox")
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub name: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub signal_sample: Option<SandboxSignalSampleDto>,
+    }
+
+    /// Response payload for a cloned sandbox tenant
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct CloneSandboxResponseDto {
+        /// Placeholder connections created for the sampled signals
+        pub connections_copied: u64,
+        /// Custom signal kinds copied from the source
+        pub custom_signal_kinds: Vec<CustomSignalKindInfo>,
+        /// Tenant the sandbox was cloned from
+        pub sandbox_of: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub signal_config: Option<SandboxSignalConfigDto>,
+        /// Signals copied from the source
+        pub signals_copied: u64,
+        /// The sandbox tenant
+        pub tenant: CreateTenantResponseDto,
+    }
+
+    /// Settings an organization or tenant may override
+    #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+    pub struct ConfigOverrides {
+        /// Addresses (`user@example.com`) and domains (`@example.com`) always allowed
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub mail_spam_allowlist: Option<Vec<String>>,
+        /// Addresses (`user@example.com`) and domains (`@example.com`) always blocked
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub mail_spam_denylist: Option<Vec<String>>,
+        /// Spam score (0.0 to 1.0) at or above which mail is treated as spam
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub mail_spam_threshold: Option<f32>,
+        /// HTTPS URLs that receive signed notification deliveries
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub notification_urls: Option<Vec<String>>,
+        /// Sync intervals in seconds keyed by provider slug; these win over `sync_interval_seconds`
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub provider_sync_intervals: Option<std::collections::BTreeMap<String, u64>>,
+        /// Retry backoff and attempt budgets keyed by provider slug
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub rate_limit_overrides:
+            Option<std::collections::BTreeMap<String, TenantRateLimitOverride>>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub scoring_weights: Option<ScoringWeights>,
+        /// Sync interval in seconds for connections without an interval of their own
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub sync_interval_seconds: Option<u64>,
+        /// Daily windows during which the scheduler holds back syncs
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub sync_quiet_hours: Option<Vec<QuietHours>>,
+        /// Webhook deliveries accepted per provider and minute
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub webhook_rate_limit_per_minute: Option<u32>,
+    }
+
+    /// Credentials submitted for a provider that does not use the OAuth redirect flow
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct ConnectCredentialsRequest {
+        /// Name shown for the connection
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub display_name: Option<String>,
+        /// Account the credentials belong to; defaults to the provider identifier,
+        /// so a tenant holds one credential set per provider unless it names accounts
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub external_id: Option<String>,
+        /// API key for `api_key` providers, or client ID for `client_credentials` providers
+        pub key: String,
+        /// Client secret; required for `client_credentials` providers and optional
+        /// for `api_key` providers that pair the key with a second one (e.g. a
+        /// Datadog application key)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub secret: Option<String>,
+    }
+
+    /// Health of a connection's credentials and syncing
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct ConnectionHealthResponse {
+        /// Unique identifier for the connection
+        pub id: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub last_sync: Option<LastSyncInfo>,
+        /// Why the token was rejected or the check could not run
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub message: Option<String>,
+        /// Provider slug (e.g., "github", "slack")
+        pub provider: String,
+        /// Scopes reported by the provider, or those granted at authorization when it reports none
+        pub scopes: Vec<String>,
+        /// Connection status (active, paused, needs_reauth, revoked or error)
+        pub status: String,
+        /// Expiration timestamp of the stored access token
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub token_expires_at: Option<String>,
+        /// Whether the provider accepted the stored token; null when the provider has no health check or it could not run
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub token_valid: Option<bool>,
+    }
+
+    /// Connection information returned by OAuth callback
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct ConnectionInfo {
+        /// Token expiration timestamp (optional)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub expires_at: Option<String>,
+        /// Connection unique identifier
+        pub id: uuid::Uuid,
+        /// Provider-specific metadata
+        pub metadata: serde_json::Value,
+        /// Provider identifier
+        pub provider: String,
+    }
+
+    /// Connection response for OAuth callback
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct ConnectionResponse {
+        /// Created connection details
+        pub connection: ConnectionInfo,
+    }
+
+    /// Upcoming syncs for one connection
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct ConnectionSchedule {
+        /// Blackout windows overlapping the horizon
+        pub blackouts: Vec<BlackoutInfo>,
+        /// Connection identifier
+        pub connection_id: String,
+        /// Effective sync interval in seconds
+        pub interval_seconds: u64,
+        /// Provider slug (e.g., "github")
+        pub provider: String,
+        /// Projected sync start times (RFC3339), before jitter
+        pub upcoming_syncs: Vec<String>,
+    }
+
+    /// Scheduling status of a connection after a pause or resume
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct ConnectionStatusResponse {
+        /// Unique identifier for the connection
+        pub id: String,
+        /// Provider slug (e.g., "github", "slack")
+        pub provider: String,
+        /// Connection status; the scheduler only enqueues syncs for `active` connections
+        pub status: String,
+    }
+
+    /// Response wrapper for connections listing
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct ConnectionsResponse {
+        /// List of connections for the tenant
+        pub connections: Vec<ConnectionInfo>,
+        /// Opaque cursor for fetching the next page (null if this is the last page)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub next_cursor: Option<String>,
+    }
+
+    /// Request to create a connection
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct CreateConnectionRequest {
+        /// Name shown for the connection
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub display_name: Option<String>,
+        /// Settings declared in `GET /providers/{slug}/metadata-schema`
+        pub metadata: serde_json::Value,
+        /// Provider slug; only providers whose auth type is `none`, such as `rss`,
+        /// or `basic`, such as `imap`
+        pub provider: String,
+        /// Password or static access token; required for `basic` providers and
+        /// rejected for `none` providers. Stored encrypted and never returned.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub secret: Option<String>,
+    }
+
+    /// Request body for creating an organization
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct CreateOrganizationRequest {
+        /// Display name (required, max 255 characters)
+        pub name: String,
+        /// Default settings inherited by member tenants
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub settings: Option<ConfigOverrides>,
+    }
+
+    /// Request payload for creating a new tenant
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct CreateTenantRequestDto {
+        /// Optional metadata for the tenant
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub metadata: Option<serde_json::Value>,
+        /// Display name for the tenant (required, max 255 characters)
+        pub name: String,
+    }
+
+    /// Response payload for tenant creation
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct CreateTenantResponseDto {
+        /// Timestamp when the tenant was created (ISO 8601)
+        pub created_at: String,
+        /// Unique identifier for the tenant (UUID)
+        pub id: String,
+        /// Optional metadata for the tenant
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub metadata: Option<serde_json::Value>,
+        /// Display name of the tenant
+        pub name: String,
+    }
+
+    /// A custom kind registered by the tenant
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct CustomSignalKindInfo {
+        /// Timestamp when the kind was registered
+        pub created_at: String,
+        /// Optional human-readable description
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub description: Option<String>,
+        /// Full kind including the `custom:` prefix
+        pub kind: String,
+    }
+
+    /// Aggregated statistics for one day and provider
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct DailyStatsEntry {
+        /// UTC day the aggregate covers
+        pub day: String,
+        /// Jobs that finished with a terminal failure
+        pub jobs_failed: i64,
+        /// Jobs that finished successfully
+        pub jobs_succeeded: i64,
+        /// Mean duration of finished jobs in milliseconds
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub mean_sync_duration_ms: Option<f64>,
+        /// Provider slug
+        pub provider_slug: String,
+        /// Signals received, keyed by signal kind
+        pub signals_by_kind: std::collections::BTreeMap<String, i64>,
+    }
+
+    /// Response payload for daily stats endpoint
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct DailyStatsResponse {
+        /// Entries ordered by day, then provider
+        pub days: Vec<DailyStatsEntry>,
+        /// First day included (YYYY-MM-DD)
+        pub from: String,
+        /// Last day included (YYYY-MM-DD)
+        pub to: String,
+    }
+
+    /// A signal produced by a dry-run sync
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct DryRunSignal {
+        /// Key the signal would be deduplicated on
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub dedupe_key: Option<String>,
+        /// Normalized event kind
+        pub kind: String,
+        /// Timestamp when the event occurred in the provider system (RFC 3339)
+        pub occurred_at: String,
+        /// Normalized event payload
+        pub payload: serde_json::Value,
+    }
+
+    /// Signals a dry-run sync would have stored
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct DryRunSyncResponse {
+        /// Connection that was synced
+        pub connection_id: String,
+        /// Whether the provider has further pages a real sync would fetch
+        pub has_more: bool,
+        /// Provider slug (e.g., "github", "slack")
+        pub provider: String,
+        /// Signals of the first page, as the connector mapped them
+        pub signals: Vec<DryRunSignal>,
+    }
+
+    /// Signals stored for the same event
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct DuplicateGroup {
+        /// Dedupe key with any trailing timestamp removed; null when the signals have no key
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub dedupe_family: Option<String>,
+        /// Later copies, removed by a merge
+        pub duplicate_ids: Vec<uuid::Uuid>,
+        /// Earliest `occurred_at` in the group (RFC3339)
+        pub first_occurred_at: String,
+        /// Earliest received signal, kept by a merge
+        pub keep_id: uuid::Uuid,
+        /// Signal kind shared by the group
+        pub kind: String,
+        /// Latest `occurred_at` in the group (RFC3339)
+        pub last_occurred_at: String,
+        /// SHA-256 of the canonical JSON payload
+        pub payload_hash: String,
+        /// Provider that emitted the signals
+        pub provider_slug: String,
+    }
+
+    /// Near-duplicate signals for one tenant
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct DuplicateReport {
+        /// Signals a merge would delete
+        pub duplicate_count: u64,
+        /// Duplicate groups, oldest first
+        pub groups: Vec<DuplicateGroup>,
+        /// Number of signals examined
+        pub scanned: u64,
+        /// Start of the scanned range (RFC3339)
+        pub since: String,
+        /// Tenant that was scanned
+        pub tenant_id: uuid::Uuid,
+        /// Whether the scan stopped at the per-request cap before the end of the range
+        pub truncated: bool,
+        /// Window applied to `occurred_at`
+        pub window_seconds: i64,
+    }
+
+    /// Settings in effect for a tenant
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct EffectiveConfig {
+        pub mail_spam_allowlist: ResolvedVecString,
+        pub mail_spam_denylist: ResolvedVecString,
+        pub mail_spam_threshold: ResolvedF32,
+        pub notification_urls: ResolvedVecString,
+        /// Organization the tenant inherits from, if any
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub organization_id: Option<String>,
+        pub provider_sync_intervals: ResolvedBTreeMapStringU64,
+        pub rate_limit_overrides: ResolvedBTreeMapStringTenantRateLimitOverride,
+        pub scoring_weights: ResolvedScoringWeights,
+        pub sync_interval_seconds: ResolvedU64,
+        pub sync_quiet_hours: ResolvedVecQuietHours,
+        pub webhook_rate_limit_per_minute: ResolvedU32,
+    }
+
+    /// GitHub signature header for webhook verification
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct GitHubSignatureHeader {
+        /// HMAC-SHA256 signature of the request body (hex string with sha256= prefix)
+        #[serde(rename = "X-Hub-Signature-256")]
+        pub x_hub_signature_256: String,
+    }
+
+    /// Public representation for API responses (excluding internal fields)
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct GroundedSignalResponse {
+        pub created_at: String,
+        pub evidence: serde_json::Value,
+        pub id: uuid::Uuid,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub recommendation: Option<String>,
+        pub scores: SignalScores,
+        pub signal_id: uuid::Uuid,
+        pub status: GroundedSignalStatus,
+        pub tenant_id: uuid::Uuid,
+        pub updated_at: String,
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+    pub enum GroundedSignalStatus {
+        #[serde(rename = "draft")]
+        Draft,
+        #[serde(rename = "recommended")]
+        Recommended,
+        #[serde(rename = "actioned")]
+        Actioned,
+    }
+
+    /// Health check response
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct HealthResponse {
+        /// Service identifier
+        pub service: String,
+        /// Service health status
+        pub status: String,
+        /// Service version
+        pub version: String,
+    }
+
+    /// Inbound email accept response
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct InboundEmailAcceptResponse {
+        /// Tenant's inbound email connection the message was queued on
+        pub connection_id: uuid::Uuid,
+        /// Message-ID of the accepted message, or a content hash when it has none
+        pub message_id: String,
+        /// Acceptance status
+        pub status: String,
+    }
+
+    /// Job information response
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct JobInfo {
+        /// Number of attempts made for this job
+        pub attempts: i32,
+        /// Connection identifier this job is associated with
+        pub connection_id: String,
+        /// Timestamp when the job finished execution
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub finished_at: Option<String>,
+        /// Unique identifier for the sync job
+        pub id: String,
+        /// Type of job
+        pub job_type: String,
+        /// Job priority for scheduling
+        pub priority: i32,
+        /// Slug of the provider this job is for
+        pub provider_slug: String,
+        /// Timestamp when the job becomes eligible for retry after backoff
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub retry_after: Option<String>,
+        /// Timestamp when the job is scheduled to run
+        pub scheduled_at: String,
+        /// Timestamp when the job started execution
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub started_at: Option<String>,
+        /// Current status of the job
+        pub status: String,
+    }
+
+    /// Documented job status values for OpenAPI enumeration
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+    pub enum JobStatusParam {
+        #[serde(rename = "queued")]
+        Queued,
+        #[serde(rename = "running")]
+        Running,
+        #[serde(rename = "cancelling")]
+        Cancelling,
+        #[serde(rename = "cancelled")]
+        Cancelled,
+        #[serde(rename = "succeeded")]
+        Succeeded,
+        #[serde(rename = "failed")]
+        Failed,
+    }
+
+    /// Documented job type values for OpenAPI enumeration
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+    pub enum JobTypeParam {
+        #[serde(rename = "full")]
+        Full,
+        #[serde(rename = "incremental")]
+        Incremental,
+        #[serde(rename = "webhook")]
+        Webhook,
+    }
+
+    /// Response payload for jobs listing endpoint
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct JobsResponse {
+        /// List of jobs matching the query
+        pub jobs: Vec<JobInfo>,
+        /// Opaque cursor for fetching the next page (null if no more pages)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub next_cursor: Option<String>,
+    }
+
+    /// Outcome of a connection's most recently finished sync job
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct LastSyncInfo {
+        /// Error recorded by a failed job
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub error: Option<serde_json::Value>,
+        /// When the job finished (RFC 3339)
+        pub finished_at: String,
+        /// Job identifier
+        pub job_id: String,
+        /// Job type (e.g., full, incremental, webhook)
+        pub job_type: String,
+        /// Final job status (succeeded or failed)
+        pub status: String,
+    }
+
+    /// Query parameters for connections listing
+    #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+    pub struct ListConnectionsQuery {
+        /// Opaque cursor for pagination continuation
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub cursor: Option<String>,
+        /// Maximum number of connections to return (default: 50, max: 100)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub limit: Option<i64>,
+        /// Optional provider filter (snake_case slug, e.g., "github")
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub provider: Option<String>,
+    }
+
+    /// Response with pagination metadata
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct ListGroundedSignalsResponse {
+        pub data: Vec<GroundedSignalResponse>,
+        pub pagination: PaginationInfo,
+    }
+
+    /// Query parameters for listing signals
+    #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+    pub struct ListSignalsQuery {
+        /// Filter by connection ID (UUID)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub connection_id: Option<String>,
+        /// Opaque cursor for pagination continuation
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub cursor: Option<String>,
+        /// Whether to include the full payload (default: false)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub include_payload: Option<bool>,
+        /// Filter by signal kind
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub kind: Option<String>,
+        /// Maximum number of signals to return (default: 50, max: 100)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub limit: Option<i64>,
+        /// Filter for signals that occurred after this timestamp (RFC3339)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub occurred_after: Option<String>,
+        /// Filter for signals that occurred before this timestamp (RFC3339)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub occurred_before: Option<String>,
+        /// Filter by provider slug
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub provider: Option<String>,
+    }
+
+    /// Query parameters for listing a connection's sync runs
+    #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+    pub struct ListSyncRunsQuery {
+        /// Maximum number of runs to return (default: 50, max: 100)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub limit: Option<i64>,
+    }
+
+    /// Query parameters for listing a connection's token events
+    #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+    pub struct ListTokenEventsQuery {
+        /// Maximum number of events to return (default: 50, max: 100)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub limit: Option<i64>,
+    }
+
+    /// Active log filter and sampling rate
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct LogLevelState {
+        /// One in this many sampled executor and webhook debug events is emitted
+        pub debug_sample_rate: u32,
+        /// Active filter directives in `EnvFilter` syntax; `null` when the global subscriber is not managed by this process
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub filter: Option<String>,
+    }
+
+    /// Replacement log filter
+    #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+    pub struct LogLevelUpdate {
+        /// New debug sampling rate; unchanged when omitted
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub debug_sample_rate: Option<u32>,
+        /// Level for targets without an override (default: the configured `POBLYSH_LOG_LEVEL`)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub level: Option<String>,
+        /// Per-module levels keyed by target, e.g. `connectors::sync_executor`; replaces all previous overrides
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub modules: Option<std::collections::BTreeMap<String, String>>,
+    }
+
+    /// Request to merge a tenant's duplicate signals
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct MergeDuplicatesRequest {
+        /// Justification recorded in the audit log
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub reason: Option<String>,
+        /// Same meaning as in the report
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub since: Option<String>,
+        /// Tenant whose duplicates are merged
+        pub tenant_id: uuid::Uuid,
+        /// Same meaning as in the report
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub window_seconds: Option<i64>,
+    }
+
+    /// Outcome of a merge
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct MergeDuplicatesResponse {
+        /// Groups folded into their earliest signal
+        pub groups_merged: u64,
+        /// Duplicate signals deleted
+        pub signals_removed: u64,
+        /// Whether the scan stopped at the per-request cap; run again to continue
+        pub truncated: bool,
+    }
+
+    /// Schema of the settings a provider accepts in connection metadata
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct MetadataSchemaResponse {
+        /// Provider slug
+        pub provider: String,
+        /// JSON schema of the configurable metadata keys; no properties when the provider has none
+        pub schema: serde_json::Value,
+    }
+
+    /// OAuth callback query parameters
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct OAuthCallbackQuery {
+        /// Authorization code returned by the provider
+        pub code: String,
+        /// Error parameter returned by provider (optional, for denial scenarios)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub error: Option<String>,
+        /// State parameter for CSRF protection and tenant resolution
+        pub state: String,
+    }
+
+    /// An organization and its default settings
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct OrganizationResponse {
+        /// Timestamp when the organization was created (RFC3339)
+        pub created_at: String,
+        /// Organization identifier
+        pub id: String,
+        /// Display name
+        pub name: String,
+        /// Default settings inherited by member tenants
+        pub settings: ConfigOverrides,
+        /// Timestamp when the organization was last updated (RFC3339)
+        pub updated_at: String,
+    }
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct PaginationInfo {
+        pub has_more: bool,
+        pub limit: i64,
+        pub offset: i64,
+        pub total: i64,
+    }
+
+    /// Result of one connect pre-flight check
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct PreflightCheck {
+        /// Human-readable explanation
+        pub message: String,
+        /// Check identifier (auth_type, client_credentials, scopes, webhook_secret)
+        pub name: String,
+        /// What an operator should change to fix a failing or warning check
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub remediation: Option<String>,
+        /// Outcome of the check
+        pub status: PreflightStatus,
+    }
+
+    /// Connect pre-flight report for a provider
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct PreflightResponse {
+        /// Individual check results
+        pub checks: Vec<PreflightCheck>,
+        /// Provider identifier
+        pub provider: String,
+        /// Whether the OAuth flow can be started (no check failed)
+        pub ready: bool,
+    }
+
+    /// Outcome of a single connect pre-flight check
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+    pub enum PreflightStatus {
+        #[serde(rename = "pass")]
+        Pass,
+        #[serde(rename = "warn")]
+        Warn,
+        #[serde(rename = "fail")]
+        Fail,
+        #[serde(rename = "skip")]
+        Skip,
+    }
+
+    /// Response payload for protected ping endpoint
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct ProtectedPingResponse {
+        /// Static message confirming protected endpoint access
+        pub message: String,
+        /// Tenant identifier echoed back to the caller
+        pub tenant_id: String,
+    }
+
+    /// What a provider supports, so clients can build connect UIs without
+    /// hardcoding provider behavior
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct ProviderCapabilities {
+        /// Provider slug
+        pub provider: String,
+        /// Canonical signal kinds the connector emits
+        pub supported_signal_kinds: Vec<String>,
+        /// Whether sync reads activity from before the connection was created
+        pub supports_backfill: bool,
+        /// Whether connections are created through the OAuth authorize flow
+        pub supports_oauth: bool,
+        /// Whether the provider delivers events to the webhook endpoints
+        pub supports_webhooks: bool,
+    }
+
+    /// Catalog category used to group providers in the connect UI
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+    pub enum ProviderCategory {
+        #[serde(rename = "code")]
+        Code,
+        #[serde(rename = "chat")]
+        Chat,
+        #[serde(rename = "mail")]
+        Mail,
+        #[serde(rename = "calendar")]
+        Calendar,
+        #[serde(rename = "files")]
+        Files,
+        #[serde(rename = "crm")]
+        Crm,
+        #[serde(rename = "monitoring")]
+        Monitoring,
+        #[serde(rename = "other")]
+        Other,
+    }
+
+    /// Provider information for public listing
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct ProviderInfo {
+        /// Authentication type required by this provider
+        pub auth_type: String,
+        /// Catalog category used to group providers
+        pub category: ProviderCategory,
+        /// One-line description for the connect catalog
+        pub description: String,
+        /// URL of the provider's integration documentation
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub docs_url: Option<String>,
+        /// URL of the provider's icon
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub icon_url: Option<String>,
+        /// Name of the provider (e.g., "github", "slack")
+        pub name: String,
+        /// List of OAuth scopes this provider may request
+        pub scopes: Vec<String>,
+        /// Whether this provider supports webhook events
+        pub webhooks: bool,
+    }
+
+    /// Path parameter for provider slug
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct ProviderPath {
+        /// Provider slug (e.g., "github", "jira")
+        pub provider: String,
+    }
+
+    /// Circuit breaker status of a provider
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct ProviderStatusResponse {
+        /// Circuit state: `closed` (jobs run), `open` (jobs paused until `open_until`) or
+        /// `half_open` (a probe job decides whether the circuit closes)
+        pub circuit_state: String,
+        /// 5xx, network and timeout failures since the provider last answered a job
+        pub consecutive_failures: i32,
+        /// Most recent provider failure
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub last_error: Option<String>,
+        /// End of the current cooldown or probe window
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub open_until: Option<String>,
+        /// When the circuit last opened
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub opened_at: Option<String>,
+        /// Provider slug
+        pub provider: String,
+    }
+
+    /// Response containing the list of available providers
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct ProvidersResponse {
+        /// Opaque cursor for fetching the next page (null if this is the last page)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub next_cursor: Option<String>,
+        /// List of available providers
+        pub providers: Vec<ProviderInfo>,
+    }
+
+    /// A daily UTC window in which scheduled syncs wait, e.g. a provider maintenance window
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct QuietHours {
+        /// End of the window (exclusive) as `HH:MM` UTC; earlier than `start` wraps past midnight
+        pub end: String,
+        /// Provider the window applies to; all providers when absent
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub provider_slug: Option<String>,
+        /// Start of the window as `HH:MM` UTC
+        pub start: String,
+    }
+
+    /// Rate limit policy configuration for handling provider rate limits
+    #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+    pub struct RateLimitPolicyConfig {
+        /// Base retry interval in seconds (default: 5)
+        #[serde(rename = "BASE_SECONDS")]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub base_seconds: Option<u64>,
+        /// Jitter factor for distributed systems (default: 0.1, range: 0.0-1.0)
+        #[serde(rename = "JITTER_FACTOR")]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub jitter_factor: Option<f64>,
+        /// Attempts a job gets for failures other than rate limits (default: 10)
+        #[serde(rename = "MAX_ATTEMPTS")]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub max_attempts: Option<u32>,
+        /// Attempts a job gets that end rate limited (default: 30)
+        #[serde(rename = "MAX_RATE_LIMITED_ATTEMPTS")]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub max_rate_limited_attempts: Option<u32>,
+        /// Maximum retry interval in seconds (default: 900)
+        #[serde(rename = "MAX_SECONDS")]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub max_seconds: Option<u64>,
+        /// Provider-specific rate limit policy overrides
+        #[serde(rename = "PROVIDER_OVERRIDES")]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub provider_overrides:
+            Option<std::collections::BTreeMap<String, RateLimitProviderOverride>>,
+    }
+
+    /// Provider-specific rate limit policy overrides
+    #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+    pub struct RateLimitProviderOverride {
+        /// Override for base retry interval for this provider
+        #[serde(rename = "BASE_SECONDS")]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub base_seconds: Option<u64>,
+        /// Override for jitter factor for this provider
+        #[serde(rename = "JITTER_FACTOR")]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub jitter_factor: Option<f64>,
+        /// Override for the attempts a job gets for failures other than rate limits
+        #[serde(rename = "MAX_ATTEMPTS")]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub max_attempts: Option<u32>,
+        /// Jobs for this provider one executor instance runs at once (0 means unlimited)
+        #[serde(rename = "MAX_CONCURRENT")]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub max_concurrent: Option<u64>,
+        /// Override for the rate-limited attempts a job gets
+        #[serde(rename = "MAX_RATE_LIMITED_ATTEMPTS")]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub max_rate_limited_attempts: Option<u32>,
+        /// Override for maximum retry interval for this provider
+        #[serde(rename = "MAX_SECONDS")]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub max_seconds: Option<u64>,
+    }
+
+    /// Readiness check response
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct ReadinessResponse {
+        /// Dependency status checks, plus the state of each supervised subsystem under `subsystems`
+        pub checks: serde_json::Value,
+        /// Service readiness status
+        pub status: String,
+    }
+
+    /// Request body for registering a custom signal kind
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct RegisterSignalKindRequest {
+        /// Optional human-readable description
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub description: Option<String>,
+        /// Namespaced kind, e.g. `custom:press_mention`
+        pub kind: String,
+    }
+
+    /// Result of scoring a sandbox tenant's signals again
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct ReprocessSandboxResponseDto {
+        /// Grounded signals created with the sandbox's current settings
+        pub grounded_signals_created: u64,
+        /// Grounded signals removed before scoring
+        pub grounded_signals_removed: u64,
+        /// Signals scored
+        pub signals_processed: u64,
+    }
+
+    /// Retry settings for one provider's jobs; unset fields keep the service policy
+    #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+    pub struct ResolvedBTreeMapStringTenantRateLimitOverrideValueValue {
+        /// Base retry interval in seconds
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub base_seconds: Option<u64>,
+        /// Jitter factor applied to each backoff (0.0 to 1.0)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub jitter_factor: Option<f64>,
+        /// Attempts a job gets for failures other than rate limits
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub max_attempts: Option<u32>,
+        /// Attempts a job gets that end rate limited
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub max_rate_limited_attempts: Option<u32>,
+        /// Maximum retry interval in seconds
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub max_seconds: Option<u64>,
+    }
+
+    /// A resolved setting and the layer it came from
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct ResolvedBTreeMapStringTenantRateLimitOverride {
+        pub source: SettingSource,
+        pub value: std::collections::BTreeMap<
+            String,
+            ResolvedBTreeMapStringTenantRateLimitOverrideValueValue,
+        >,
+    }
+
+    /// A resolved setting and the layer it came from
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct ResolvedBTreeMapStringU64 {
+        pub source: SettingSource,
+        pub value: std::collections::BTreeMap<String, u64>,
+    }
+
+    /// Scoring weights configuration
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct ResolvedScoringWeightsValue {
+        pub alignment: f32,
+        pub credibility: f32,
+        pub impact: f32,
+        pub novelty: f32,
+        pub relevance: f32,
+        pub timeliness: f32,
+    }
+
+    /// A resolved setting and the layer it came from
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct ResolvedScoringWeights {
+        pub source: SettingSource,
+        /// Scoring weights configuration
+        pub value: ResolvedScoringWeightsValue,
+    }
+
+    /// A daily UTC window in which scheduled syncs wait, e.g. a provider maintenance window
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct ResolvedVecQuietHoursValueItem {
+        /// End of the window (exclusive) as `HH:MM` UTC; earlier than `start` wraps past midnight
+        pub end: String,
+        /// Provider the window applies to; all providers when absent
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub provider_slug: Option<String>,
+        /// Start of the window as `HH:MM` UTC
+        pub start: String,
+    }
+
+    /// A resolved setting and the layer it came from
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct ResolvedVecQuietHours {
+        pub source: SettingSource,
+        pub value: Vec<ResolvedVecQuietHoursValueItem>,
+    }
+
+    /// A resolved setting and the layer it came from
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct ResolvedVecString {
+        pub source: SettingSource,
+        pub value: Vec<String>,
+    }
+
+    /// A resolved setting and the layer it came from
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct ResolvedF32 {
+        pub source: SettingSource,
+        pub value: f32,
+    }
+
+    /// A resolved setting and the layer it came from
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct ResolvedU32 {
+        pub source: SettingSource,
+        pub value: u32,
+    }
+
+    /// A resolved setting and the layer it came from
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct ResolvedU64 {
+        pub source: SettingSource,
+        pub value: u64,
+    }
+
+    /// Scoring configuration copied into a sandbox
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct SandboxSignalConfigDto {
+        /// Scoring weights overrides, if the source tenant had any
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub scoring_weights: Option<serde_json::Value>,
+        /// Weak signal threshold
+        pub weak_signal_threshold: f32,
+    }
+
+    /// Signals to sample into a sandbox tenant
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct SandboxSignalSampleDto {
+        /// Only sample signals that occurred at or after this RFC3339 timestamp (default: 30 days ago)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub since: Option<String>,
+        /// Number of signals to copy (1-10000)
+        pub size: u64,
+    }
+
+    /// Response payload for the schedule feed
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct ScheduleResponse {
+        /// Active connections and their upcoming syncs
+        pub connections: Vec<ConnectionSchedule>,
+        /// Time the projection was computed (RFC3339)
+        pub generated_at: String,
+        /// End of the projection horizon (RFC3339)
+        pub horizon_end: String,
+    }
+
+    /// Scoring weights configuration
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct ScoringWeights {
+        pub alignment: f32,
+        pub credibility: f32,
+        pub impact: f32,
+        pub novelty: f32,
+        pub relevance: f32,
+        pub timeliness: f32,
+    }
+
+    /// Google service-account key and the Workspace users it should act as
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct ServiceAccountConnectRequest {
+        /// Service-account key file as downloaded from the Google Cloud console
+        pub service_account: serde_json::Value,
+        /// Email addresses of the users to connect through domain-wide delegation
+        pub subjects: Vec<String>,
+    }
+
+    /// Connections created or updated from a service account
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct ServiceAccountConnectResponse {
+        /// One connection per delegated user, in request order
+        pub connections: Vec<ConnectionInfo>,
+    }
+
+    /// Basic service information response
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct ServiceInfo {
+        /// The name of the service
+        pub service: String,
+        /// The version of the service
+        pub version: String,
+    }
+
+    /// Layer that supplied a resolved setting
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+    pub enum SettingSource {
+        #[serde(rename = "service")]
+        Service,
+        #[serde(rename = "organization")]
+        Organization,
+        #[serde(rename = "tenant")]
+        Tenant,
+    }
+
+    /// Signal information for API responses
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct SignalInfo {
+        /// Connection identifier that this signal originated from
+        pub connection_id: String,
+        /// Unique identifier for the signal
+        pub id: String,
+        /// Normalized event kind
+        pub kind: String,
+        /// Timestamp when the event occurred in the provider system
+        pub occurred_at: String,
+        /// Normalized event payload (only included when include_payload=true)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub payload: Option<serde_json::Value>,
+        /// Slug of the provider that emitted this signal
+        pub provider_slug: String,
+        /// Timestamp when the signal was processed by the system
+        pub received_at: String,
+    }
+
+    /// Signal kinds available to the tenant
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct SignalKindsResponse {
+        /// Built-in kinds produced by the connectors
+        pub canonical: Vec<String>,
+        /// Custom kinds registered by the tenant
+        pub custom: Vec<CustomSignalKindInfo>,
+    }
+
+    /// Score breakdown for API responses
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct SignalScores {
+        pub alignment: f32,
+        pub credibility: f32,
+        pub impact: f32,
+        pub novelty: f32,
+        pub relevance: f32,
+        pub timeliness: f32,
+        pub total: f32,
+    }
+
+    /// Response payload for the signal trace endpoint
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct SignalTraceResponse {
+        /// Signal identifier
+        pub signal_id: String,
+        /// Pipeline stages in processing order
+        pub stages: Vec<TraceStage>,
+    }
+
+    /// Response payload for signals endpoint
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct SignalsResponse {
+        /// Opaque cursor for fetching the next page (null if this is the last page)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub next_cursor: Option<String>,
+        /// List of signals matching the query
+        pub signals: Vec<SignalInfo>,
+    }
+
+    /// Slack signature headers for webhook verification
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct SlackSignatureHeaders {
+        /// Unix timestamp of when the request was generated
+        #[serde(rename = "X-Slack-Request-Timestamp")]
+        pub x_slack_request_timestamp: String,
+        /// HMAC-SHA256 signature of the request body (hex string with v0= prefix)
+        #[serde(rename = "X-Slack-Signature")]
+        pub x_slack_signature: String,
+    }
+
+    /// Operation whose success rate is tracked
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+    pub enum SloOperation {
+        #[serde(rename = "sync")]
+        Sync,
+        #[serde(rename = "webhook")]
+        Webhook,
+        #[serde(rename = "notification")]
+        Notification,
+    }
+
+    /// Success rates and error budgets per provider and operation
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct SloReport {
+        /// Target applied to providers without an override
+        pub default_target: f64,
+        /// One entry per provider and operation with attempts in the window
+        pub objectives: Vec<SloStatus>,
+        /// Length of the rolling window in hours
+        pub window_hours: u64,
+    }
+
+    /// Success rate and error budget of one provider operation over the window
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct SloStatus {
+        /// Failures the target allows for this many attempts
+        pub allowed_failures: f64,
+        /// Whether failures exceed what the target allows
+        pub budget_exhausted: bool,
+        /// Share of the error budget left; negative once the budget is overspent
+        pub error_budget_remaining: f64,
+        /// Failed attempts within the window
+        pub failed: i64,
+        /// Tracked operation
+        pub operation: SloOperation,
+        /// Provider slug
+        pub provider: String,
+        /// Observed success rate
+        pub success_rate: f64,
+        /// Target success rate
+        pub target: f64,
+        /// Attempts within the window
+        pub total: i64,
+    }
+
+    /// Response payload for slow query report endpoint
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct SlowQueryReport {
+        /// Worst offenders ordered by total slow time, descending
+        pub queries: Vec<SlowQuerySummary>,
+        /// Duration above which a statement counts as slow (0 when disabled)
+        pub threshold_ms: u64,
+        /// Length of the reporting window in seconds
+        pub window_seconds: u64,
+    }
+
+    /// Aggregate of slow executions of one normalized statement
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct SlowQuerySummary {
+        /// Slow executions within the window
+        pub count: u64,
+        /// Slow executions that returned an error
+        pub failed: u64,
+        /// When the statement last exceeded the threshold (RFC3339)
+        pub last_seen_at: String,
+        /// Slowest execution in milliseconds
+        pub max_ms: f64,
+        /// Mean duration of slow executions in milliseconds
+        pub mean_ms: f64,
+        /// Normalized SQL with literals replaced by `?`
+        pub query: String,
+        /// Repository method that issued the statement, when known
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub source: Option<String>,
+        /// Combined duration of slow executions in milliseconds
+        pub total_ms: f64,
+    }
+
+    /// One executed run of a sync job
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct SyncRunInfo {
+        /// Attempt number of the job this run was
+        pub attempt: i32,
+        /// Error message of a run that did not succeed
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub error: Option<String>,
+        /// When the run ended (RFC 3339)
+        pub finished_at: String,
+        /// Run identifier
+        pub id: String,
+        /// Signals the run persisted
+        pub items_processed: i64,
+        /// Job the run belongs to
+        pub job_id: String,
+        /// Job type (e.g., full, incremental, webhook)
+        pub job_type: String,
+        /// How the run ended (succeeded, failed, cancelled or interrupted)
+        pub outcome: String,
+        /// When the executor started the run (RFC 3339)
+        pub started_at: String,
+    }
+
+    /// Response wrapper for a connection's sync runs
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct SyncRunsResponse {
+        /// Runs of the connection's jobs, newest first
+        pub runs: Vec<SyncRunInfo>,
+    }
+
+    /// Response payload for tenant bootstrap
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct TenantApiResponseBootstrapTenantResponseDtoData {
+        /// Custom signal kinds registered for the tenant
+        pub custom_signal_kinds: Vec<CustomSignalKindInfo>,
+        /// Whether a per-tenant data key was provisioned for token encryption
+        pub data_key_provisioned: bool,
+        /// The tenant's signal configuration
+        pub signal_config: BootstrapSignalConfigResponseDto,
+        /// The created tenant
+        pub tenant: CreateTenantResponseDto,
+    }
+
+    /// Standard API response wrapper for tenant operations
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct TenantApiResponseBootstrapTenantResponseDto {
+        /// Response payload for tenant bootstrap
+        pub data: TenantApiResponseBootstrapTenantResponseDtoData,
+        /// Response metadata
+        pub meta: TenantResponseMeta,
+    }
+
+    /// Response payload for a cloned sandbox tenant
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct TenantApiResponseCloneSandboxResponseDtoData {
+        /// Placeholder connections created for the sampled signals
+        pub connections_copied: u64,
+        /// Custom signal kinds copied from the source
+        pub custom_signal_kinds: Vec<CustomSignalKindInfo>,
+        /// Tenant the sandbox was cloned from
+        pub sandbox_of: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub signal_config: Option<SandboxSignalConfigDto>,
+        /// Signals copied from the source
+        pub signals_copied: u64,
+        /// The sandbox tenant
+        pub tenant: CreateTenantResponseDto,
+    }
+
+    /// Standard API response wrapper for tenant operations
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct TenantApiResponseCloneSandboxResponseDto {
+        /// Response payload for a cloned sandbox tenant
+        pub data: TenantApiResponseCloneSandboxResponseDtoData,
+        /// Response metadata
+        pub meta: TenantResponseMeta,
+    }
+
+    /// Response payload for tenant creation
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct TenantApiResponseCreateTenantResponseDtoData {
+        /// Timestamp when the tenant was created (ISO 8601)
+        pub created_at: String,
+        /// Unique identifier for the tenant (UUID)
+        pub id: String,
+        /// Optional metadata for the tenant
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub metadata: Option<serde_json::Value>,
+        /// Display name of the tenant
+        pub name: String,
+    }
+
+    /// Standard API response wrapper for tenant operations
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct TenantApiResponseCreateTenantResponseDto {
+        /// Response payload for tenant creation
+        pub data: TenantApiResponseCreateTenantResponseDtoData,
+        /// Response metadata
+        pub meta: TenantResponseMeta,
+    }
+
+    /// Settings in effect for a tenant
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct TenantApiResponseEffectiveConfigData {
+        pub mail_spam_allowlist: ResolvedVecString,
+        pub mail_spam_denylist: ResolvedVecString,
+        pub mail_spam_threshold: ResolvedF32,
+        pub notification_urls: ResolvedVecString,
+        /// Organization the tenant inherits from, if any
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub organization_id: Option<String>,
+        pub provider_sync_intervals: ResolvedBTreeMapStringU64,
+        pub rate_limit_overrides: ResolvedBTreeMapStringTenantRateLimitOverride,
+        pub scoring_weights: ResolvedScoringWeights,
+        pub sync_interval_seconds: ResolvedU64,
+        pub sync_quiet_hours: ResolvedVecQuietHours,
+        pub webhook_rate_limit_per_minute: ResolvedU32,
+    }
+
+    /// Standard API response wrapper for tenant operations
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct TenantApiResponseEffectiveConfig {
+        /// Settings in effect for a tenant
+        pub data: TenantApiResponseEffectiveConfigData,
+        /// Response metadata
+        pub meta: TenantResponseMeta,
+    }
+
+    /// Result of scoring a sandbox tenant's signals again
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct TenantApiResponseReprocessSandboxResponseDtoData {
+        /// Grounded signals created with the sandbox's current settings
+        pub grounded_signals_created: u64,
+        /// Grounded signals removed before scoring
+        pub grounded_signals_removed: u64,
+        /// Signals scored
+        pub signals_processed: u64,
+    }
+
+    /// Standard API response wrapper for tenant operations
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct TenantApiResponseReprocessSandboxResponseDto {
+        /// Result of scoring a sandbox tenant's signals again
+        pub data: TenantApiResponseReprocessSandboxResponseDtoData,
+        /// Response metadata
+        pub meta: TenantResponseMeta,
+    }
+
+    /// A tenant's stored configuration overrides
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct TenantApiResponseTenantConfigResponseDtoData {
+        /// Settings overridden for this tenant; settings left out are inherited
+        pub config: TenantConfigSettings,
+        /// When the overrides last changed (RFC3339)
+        pub updated_at: String,
+    }
+
+    /// Standard API response wrapper for tenant operations
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct TenantApiResponseTenantConfigResponseDto {
+        /// A tenant's stored configuration overrides
+        pub data: TenantApiResponseTenantConfigResponseDtoData,
+        /// Response metadata
+        pub meta: TenantResponseMeta,
+    }
+
+    /// A page of a tenant's event feed
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct TenantApiResponseTenantEventsResponseDtoData {
+        /// Events, newest first
+        pub events: Vec<TenantEventDto>,
+        /// Pass as `before` to fetch the next page; absent on the last page
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub next_before: Option<String>,
+    }
+
+    /// Standard API response wrapper for tenant operations
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct TenantApiResponseTenantEventsResponseDto {
+        /// A page of a tenant's event feed
+        pub data: TenantApiResponseTenantEventsResponseDtoData,
+        /// Response metadata
+        pub meta: TenantResponseMeta,
+    }
+
+    /// A tenant's organization and setting overrides
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct TenantApiResponseTenantSettingsResponseDtoData {
+        /// Organization the tenant inherits from
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub organization_id: Option<String>,
+        /// Tenant-level overrides
+        pub settings: ConfigOverrides,
+    }
+
+    /// Standard API response wrapper for tenant operations
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct TenantApiResponseTenantSettingsResponseDto {
+        /// A tenant's organization and setting overrides
+        pub data: TenantApiResponseTenantSettingsResponseDtoData,
+        /// Response metadata
+        pub meta: TenantResponseMeta,
+    }
+
+    /// Secret that signs deliveries to a tenant's notification webhook
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct TenantApiResponseWebhookSigningSecretResponseDtoData {
+        /// HMAC-SHA256 key for `"{timestamp}.{body}"`
+        pub secret: String,
+        /// Header carrying the `sha256=<hex>` signature
+        pub signature_header: String,
+        /// Header carrying the signed Unix timestamp in seconds
+        pub timestamp_header: String,
+    }
+
+    /// Standard API response wrapper for tenant operations
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct TenantApiResponseWebhookSigningSecretResponseDto {
+        /// Secret that signs deliveries to a tenant's notification webhook
+        pub data: TenantApiResponseWebhookSigningSecretResponseDtoData,
+        /// Response metadata
+        pub meta: TenantResponseMeta,
+    }
+
+    /// A tenant's stored configuration overrides
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct TenantConfigResponseDto {
+        /// Settings overridden for this tenant; settings left out are inherited
+        pub config: TenantConfigSettings,
+        /// When the overrides last changed (RFC3339)
+        pub updated_at: String,
+    }
+
+    /// Settings a tenant can override in its `tenant_configs` row
+    #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+    pub struct TenantConfigSettings {
+        /// Spam score (0.0 to 1.0) at or above which mail is treated as spam
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub mail_spam_threshold: Option<f32>,
+        /// HTTPS URLs that receive signed notification deliveries
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub notification_urls: Option<Vec<String>>,
+        /// Sync intervals in seconds keyed by provider slug; these win over `sync_interval_seconds`
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub provider_sync_intervals: Option<std::collections::BTreeMap<String, u64>>,
+        /// Retry backoff and attempt budgets keyed by provider slug
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub rate_limit_overrides:
+            Option<std::collections::BTreeMap<String, TenantRateLimitOverride>>,
+        /// Sync interval in seconds for connections without an interval of their own
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub sync_interval_seconds: Option<u64>,
+    }
+
+    /// One configuration or lifecycle event
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct TenantEventDto {
+        /// Event type (e.g., connection.created, tenant.settings_updated)
+        pub action: String,
+        /// Who or what made the change
+        pub actor: String,
+        /// Event-specific details
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub details: Option<serde_json::Value>,
+        /// Audit entry identifier
+        pub id: String,
+        /// When the event was recorded (RFC3339)
+        pub occurred_at: String,
+        /// Reason recorded with the event, if any
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub reason: Option<String>,
+        /// Identifier of that object
+        pub target_id: String,
+        /// Kind of object the event concerns
+        pub target_type: String,
+    }
+
+    /// A page of a tenant's event feed
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct TenantEventsResponseDto {
+        /// Events, newest first
+        pub events: Vec<TenantEventDto>,
+        /// Pass as `before` to fetch the next page; absent on the last page
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub next_before: Option<String>,
+    }
+
+    /// OpenAPI header parameter for X-Tenant-Id
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct TenantHeader {
+        /// Tenant identifier (UUID) that scopes the request to a specific tenant
+        #[serde(rename = "X-Tenant-Id")]
+        pub x_tenant_id: String,
+    }
+
+    /// Retry settings for one provider's jobs; unset fields keep the service policy
+    #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+    pub struct TenantRateLimitOverride {
+        /// Base retry interval in seconds
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub base_seconds: Option<u64>,
+        /// Jitter factor applied to each backoff (0.0 to 1.0)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub jitter_factor: Option<f64>,
+        /// Attempts a job gets for failures other than rate limits
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub max_attempts: Option<u32>,
+        /// Attempts a job gets that end rate limited
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub max_rate_limited_attempts: Option<u32>,
+        /// Maximum retry interval in seconds
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub max_seconds: Option<u64>,
+    }
+
+    /// Response metadata
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct TenantResponseMeta {
+        /// Unique request identifier for tracing
+        pub request_id: String,
+        /// Response timestamp (ISO 8601)
+        pub timestamp: String,
+    }
+
+    /// A tenant's organization and setting overrides
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct TenantSettingsResponseDto {
+        /// Organization the tenant inherits from
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub organization_id: Option<String>,
+        /// Tenant-level overrides
+        pub settings: ConfigOverrides,
+    }
+
+    /// One token refresh attempt of a connection
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct TokenEventInfo {
+        /// Provider or network error of a failed refresh
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub error: Option<String>,
+        /// Event identifier
+        pub id: String,
+        /// Access token expiry after a successful refresh (RFC 3339)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub new_expires_at: Option<String>,
+        /// When the refresh finished (RFC 3339)
+        pub occurred_at: String,
+        /// How the refresh ended (succeeded or failed)
+        pub outcome: String,
+        /// Access token expiry before the refresh (RFC 3339)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub previous_expires_at: Option<String>,
+        /// Whether the provider issued a new refresh token
+        pub refresh_token_rotated: bool,
+    }
+
+    /// Response wrapper for a connection's token events
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct TokenEventsResponse {
+        /// Refresh attempts of the connection, newest first
+        pub events: Vec<TokenEventInfo>,
+    }
+
+    /// One stage in a signal's pipeline trace
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct TraceStage {
+        /// Timestamp when the stage happened (RFC3339), if known
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub at: Option<String>,
+        /// Stage-specific details
+        pub details: serde_json::Value,
+        /// Stage name (e.g., "sync_job", "dedupe", "delivery:signal.created")
+        pub stage: String,
+        /// Outcome of the stage
+        pub status: TraceStageStatus,
+    }
+
+    /// Outcome of a pipeline stage in a signal trace
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+    pub enum TraceStageStatus {
+        #[serde(rename = "completed")]
+        Completed,
+        #[serde(rename = "pending")]
+        Pending,
+        #[serde(rename = "failed")]
+        Failed,
+        #[serde(rename = "skipped")]
+        Skipped,
+        #[serde(rename = "unknown")]
+        Unknown,
+    }
+
+    /// Request to update a connection
+    #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+    pub struct UpdateConnectionRequest {
+        /// Put the connection in dry-run mode: its syncs run but signals are
+        /// logged instead of stored and the cursor does not advance
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub dry_run: Option<bool>,
+        /// Metadata keys to set; `null` removes a key. Only keys declared in
+        /// `GET /providers/{slug}/metadata-schema` are accepted.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub metadata: Option<serde_json::Value>,
+    }
+
+    /// Update request for grounded signal status
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct UpdateGroundedSignalRequest {
+        /// Optional recommendation text
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub recommendation: Option<String>,
+        /// New status for the grounded signal
+        pub status: GroundedSignalStatus,
+    }
+
+    /// Request payload for updating a tenant's inherited settings
+    #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+    pub struct UpdateTenantSettingsRequestDto {
+        /// Organization to inherit defaults from; omit to detach the tenant
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub organization_id: Option<String>,
+        /// Tenant-level overrides; settings left out are inherited
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub settings: Option<ConfigOverrides>,
+    }
+
+    pub type Value = serde_json::Value;
+
+    /// Webhook accept response
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct WebhookAcceptResponse {
+        /// Acceptance status
+        pub status: String,
+    }
+
+    /// Secret that signs deliveries to a tenant's notification webhook
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct WebhookSigningSecretResponseDto {
+        /// HMAC-SHA256 key for `"{timestamp}.{body}"`
+        pub secret: String,
+        /// Header carrying the `sha256=<hex>` signature
+        pub signature_header: String,
+        /// Header carrying the signed Unix timestamp in seconds
+        pub timestamp_header: String,
+    }
+}
+
+/// Query and header parameters of individual operations
+pub mod params {
+    #[allow(unused_imports)]
+    use super::types::*;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct GetDuplicateReportParams {
+        /// Only scan signals that occurred at or after this timestamp (RFC3339, default: 30 days ago)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub since: Option<String>,
+        /// Tenant to scan
+        pub tenant_id: uuid::Uuid,
+        /// Maximum spread of `occurred_at` within one group in seconds (default: 300, max: 86400)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub window_seconds: Option<i64>,
+    }
+
+    #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+    pub struct ListTenantEventsParams {
+        /// Only return events recorded before this RFC3339 timestamp
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub before: Option<String>,
+        /// Maximum number of events to return (1-200, default 50)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub limit: Option<u64>,
+    }
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct OauthCallbackParams {
+        /// Authorization code returned by provider
+        pub code: String,
+        /// Error returned by provider if authorization was denied
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub error: Option<String>,
+        /// State parameter for CSRF protection and tenant resolution
+        pub state: String,
+    }
+
+    #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+    pub struct ListConnectionsParams {
+        /// Opaque cursor for pagination continuation
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub cursor: Option<String>,
+        /// Maximum number of connections to return (default: 50, max: 100)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub limit: Option<i64>,
+        /// Optional provider filter (snake_case slug, e.g., "github")
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub provider: Option<String>,
+    }
+
+    #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+    pub struct ListConnectionRunsParams {
+        /// Maximum number of runs to return (default: 50, max: 100)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub limit: Option<i64>,
+    }
+
+    #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+    pub struct TriggerSyncParams {
+        /// Return the signals of one page instead of enqueueing a job
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub dry_run: Option<bool>,
+    }
+
+    #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+    pub struct ListTokenEventsParams {
+        /// Maximum number of events to return (default: 50, max: 100)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub limit: Option<i64>,
+    }
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct ListGroundedSignalsParams {
+        /// Maximum number of items to return (default: 50, max: 100)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub limit: Option<i64>,
+        /// Filter by minimum total score
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub min_score: Option<f32>,
+        /// Number of items to skip (default: 0)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub offset: Option<i64>,
+        /// Filter by status
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub status: Option<GroundedSignalStatus>,
+        /// Tenant ID (required)
+        pub tenant_id: uuid::Uuid,
+    }
+
+    #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+    pub struct ListJobsParams {
+        /// Filter by connection ID (UUID)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub connection_id: Option<String>,
+        /// Pagination cursor (base64-encoded timestamp)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub cursor: Option<String>,
+        /// Filter jobs that finished after this ISO 8601 timestamp
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub finished_after: Option<String>,
+        /// Filter by job type
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub job_type: Option<JobTypeParam>,
+        /// Maximum number of jobs to return (default 50, max 100)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub limit: Option<u32>,
+        /// Filter by provider type
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub provider: Option<String>,
+        /// Filter jobs that started after this ISO 8601 timestamp
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub started_after: Option<String>,
+        /// Filter by job status
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub status: Option<JobStatusParam>,
+    }
+
+    #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+    pub struct ListProvidersParams {
+        /// Opaque cursor for pagination continuation
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub cursor: Option<String>,
+        /// Maximum number of providers to return (default: 50, max: 100)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub limit: Option<i64>,
+    }
+
+    #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+    pub struct GetScheduleParams {
+        /// Hours ahead to project (default: 24, max: 168)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub horizon_hours: Option<i64>,
+    }
+
+    #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+    pub struct GetScheduleIcalParams {
+        /// Hours ahead to project (default: 24, max: 168)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub horizon_hours: Option<i64>,
+    }
+
+    #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+    pub struct ListSignalsParams {
+        /// Filter by connection ID (UUID)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub connection_id: Option<String>,
+        /// Opaque cursor for pagination continuation
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub cursor: Option<String>,
+        /// Whether to include the full payload (default: false)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub include_payload: Option<bool>,
+        /// Filter by signal kind
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub kind: Option<String>,
+        /// Maximum number of signals to return (default: 50, max: 100)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub limit: Option<i64>,
+        /// Filter for signals that occurred after this timestamp (RFC3339)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub occurred_after: Option<String>,
+        /// Filter for signals that occurred before this timestamp (RFC3339)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub occurred_before: Option<String>,
+        /// Filter by provider slug
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub provider: Option<String>,
+    }
+
+    #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+    pub struct GetDailyStatsParams {
+        /// First day to include (YYYY-MM-DD, UTC). Defaults to 30 days before `to`
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub from: Option<String>,
+        /// Filter by provider slug
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub provider: Option<String>,
+        /// Last day to include (YYYY-MM-DD, UTC). Defaults to yesterday
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub to: Option<String>,
+    }
+
+    #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+    pub struct GetSlowQueriesParams {
+        /// Maximum number of statements to return (1-100, default 20)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub limit: Option<u64>,
+    }
+
+    #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+    pub struct IngestWebhookHeaders {
+        /// Optional connection ID to target
+        #[serde(rename = "X-Connection-Id")]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub x_connection_id: Option<String>,
+    }
+
+    #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+    pub struct IngestPublicWebhookParams {
+        /// Connection to target when the sender cannot set X-Connection-Id (custom webhooks)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub connection_id: Option<String>,
+    }
+
+    #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+    pub struct IngestPublicWebhookHeaders {
+        /// Linear HMAC-SHA256 signature (required for Linear webhooks without operator auth)
+        #[serde(rename = "Linear-Signature")]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub linear_signature: Option<String>,
+        /// Optional connection ID to target
+        #[serde(rename = "X-Connection-Id")]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub x_connection_id: Option<String>,
+        /// GitLab webhook secret token (required for GitLab webhooks without operator auth)
+        #[serde(rename = "X-Gitlab-Token")]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub x_gitlab_token: Option<String>,
+        /// GitHub HMAC-SHA256 signature (required for GitHub webhooks without operator auth)
+        #[serde(rename = "X-Hub-Signature-256")]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub x_hub_signature_256: Option<String>,
+        /// Slack request timestamp (required for Slack webhooks without operator auth)
+        #[serde(rename = "X-Slack-Request-Timestamp")]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub x_slack_request_timestamp: Option<String>,
+        /// Slack HMAC-SHA256 signature (required for Slack webhooks without operator auth)
+        #[serde(rename = "X-Slack-Signature")]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub x_slack_signature: Option<String>,
+        /// HMAC-SHA256 of the body with the connection's secret (custom webhooks)
+        #[serde(rename = "X-Webhook-Signature")]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub x_webhook_signature: Option<String>,
+    }
+}
+
+impl Client {
+    /// Root handler that returns basic service information
+    ///
+    /// `GET /`
+    pub async fn root(&self) -> Result<ServiceInfo, Error> {
+        let request = self.request(Method::GET, &[]);
+        self.json(request).await
+    }
+
+    /// Show the active log filter and debug sampling rate
+    ///
+    /// `GET /admin/log-level`
+    pub async fn get_log_level(&self) -> Result<LogLevelState, Error> {
+        let request = self.request(Method::GET, &["admin", "log-level"]);
+        self.json(request).await
+    }
+
+    /// Replace the log filter and optionally the debug sampling rate
+    ///
+    /// `PUT /admin/log-level`
+    pub async fn update_log_level(&self, body: &LogLevelUpdate) -> Result<LogLevelState, Error> {
+        let mut request = self.request(Method::PUT, &["admin", "log-level"]);
+        request = request.json(body);
+        self.json(request).await
+    }
+
+    /// Differences between the providers table and the providers defined in code
+    ///
+    /// `GET /admin/providers/drift`
+    pub async fn get_provider_drift(&self) -> Result<CatalogReport, Error> {
+        let request = self.request(Method::GET, &["admin", "providers", "drift"]);
+        self.json(request).await
+    }
+
+    /// Report near-duplicate signals for a tenant
+    ///
+    /// `GET /admin/signals/duplicates`
+    pub async fn get_duplicate_report(
+        &self,
+        query: &params::GetDuplicateReportParams,
+    ) -> Result<DuplicateReport, Error> {
+        let request = self
+            .request(Method::GET, &["admin", "signals", "duplicates"])
+            .query(query);
+        self.json(request).await
+    }
+
+    /// Merge near-duplicate signals for a tenant
+    ///
+    /// `POST /admin/signals/duplicates/merge`
+    pub async fn merge_duplicates(
+        &self,
+        body: &MergeDuplicatesRequest,
+    ) -> Result<MergeDuplicatesResponse, Error> {
+        let mut request = self.request(Method::POST, &["admin", "signals", "duplicates", "merge"]);
+        request = request.json(body);
+        self.json(request).await
+    }
+
+    /// Success rates and remaining error budgets over the rolling SLO window
+    ///
+    /// `GET /admin/slo`
+    pub async fn get_slo_report(&self) -> Result<SloReport, Error> {
+        let request = self.request(Method::GET, &["admin", "slo"]);
+        self.json(request).await
+    }
+
+    /// Create an organization
+    ///
+    /// `POST /api/v1/organizations`
+    pub async fn create_organization(
+        &self,
+        body: &CreateOrganizationRequest,
+    ) -> Result<OrganizationResponse, Error> {
+        let mut request = self.request(Method::POST, &["api", "v1", "organizations"]);
+        request = request.json(body);
+        self.json(request).await
+    }
+
+    /// Get an organization by ID
+    ///
+    /// `GET /api/v1/organizations/{id}`
+    pub async fn get_organization(&self, id: uuid::Uuid) -> Result<OrganizationResponse, Error> {
+        let request = self.request(
+            Method::GET,
+            &["api", "v1", "organizations", &id.to_string()],
+        );
+        self.json(request).await
+    }
+
+    /// Replace an organization's default settings
+    ///
+    /// `PUT /api/v1/organizations/{id}/settings`
+    pub async fn update_organization_settings(
+        &self,
+        id: uuid::Uuid,
+        body: &ConfigOverrides,
+    ) -> Result<OrganizationResponse, Error> {
+        let mut request = self.request(
+            Method::PUT,
+            &["api", "v1", "organizations", &id.to_string(), "settings"],
+        );
+        request = request.json(body);
+        self.json(request).await
+    }
+
+    /// Create a new tenant
+    ///
+    /// `POST /api/v1/tenants`
+    pub async fn create_tenant(
+        &self,
+        body: &CreateTenantRequestDto,
+    ) -> Result<TenantApiResponseCreateTenantResponseDto, Error> {
+        let mut request = self.request(Method::POST, &["api", "v1", "tenants"]);
+        request = request.json(body);
+        self.json(request).await
+    }
+
+    /// Create a tenant together with its signal config, data key and custom signal kinds
+    ///
+    /// `POST /api/v1/tenants/bootstrap`
+    pub async fn bootstrap_tenant(
+        &self,
+        body: &BootstrapTenantRequestDto,
+    ) -> Result<TenantApiResponseBootstrapTenantResponseDto, Error> {
+        let mut request = self.request(Method::POST, &["api", "v1", "tenants", "bootstrap"]);
+        request = request.json(body);
+        self.json(request).await
+    }
+
+    /// Get a tenant by ID
+    ///
+    /// `GET /api/v1/tenants/{id}`
+    pub async fn get_tenant(
+        &self,
+        id: uuid::Uuid,
+    ) -> Result<TenantApiResponseCreateTenantResponseDto, Error> {
+        let request = self.request(Method::GET, &["api", "v1", "tenants", &id.to_string()]);
+        self.json(request).await
+    }
+
+    /// Clone a tenant's configuration into a sandbox tenant
+    ///
+    /// `POST /api/v1/tenants/{id}/clone-sandbox`
+    pub async fn clone_sandbox(
+        &self,
+        id: uuid::Uuid,
+        body: &CloneSandboxRequestDto,
+    ) -> Result<TenantApiResponseCloneSandboxResponseDto, Error> {
+        let mut request = self.request(
+            Method::POST,
+            &["api", "v1", "tenants", &id.to_string(), "clone-sandbox"],
+        );
+        request = request.json(body);
+        self.json(request).await
+    }
+
+    /// Update a tenant's configuration overrides
+    ///
+    /// `PATCH /api/v1/tenants/{id}/config`
+    pub async fn update_tenant_config(
+        &self,
+        id: uuid::Uuid,
+        body: &TenantConfigSettings,
+    ) -> Result<TenantApiResponseTenantConfigResponseDto, Error> {
+        let mut request = self.request(
+            Method::PATCH,
+            &["api", "v1", "tenants", &id.to_string(), "config"],
+        );
+        request = request.json(body);
+        self.json(request).await
+    }
+
+    /// Settings in effect for a tenant and the layer each one comes from
+    ///
+    /// `GET /api/v1/tenants/{id}/effective-config`
+    pub async fn get_effective_config(
+        &self,
+        id: uuid::Uuid,
+    ) -> Result<TenantApiResponseEffectiveConfig, Error> {
+        let request = self.request(
+            Method::GET,
+            &["api", "v1", "tenants", &id.to_string(), "effective-config"],
+        );
+        self.json(request).await
+    }
+
+    /// Configuration and lifecycle events for a tenant, newest first
+    ///
+    /// `GET /api/v1/tenants/{id}/events`
+    pub async fn list_tenant_events(
+        &self,
+        id: uuid::Uuid,
+        query: &params::ListTenantEventsParams,
+    ) -> Result<TenantApiResponseTenantEventsResponseDto, Error> {
+        let request = self
+            .request(
+                Method::GET,
+                &["api", "v1", "tenants", &id.to_string(), "events"],
+            )
+            .query(query);
+        self.json(request).await
+    }
+
+    /// Score a sandbox tenant's signals again with its current settings
+    ///
+    /// `POST /api/v1/tenants/{id}/reprocess`
+    pub async fn reprocess_sandbox(
+        &self,
+        id: uuid::Uuid,
+    ) -> Result<TenantApiResponseReprocessSandboxResponseDto, Error> {
+        let request = self.request(
+            Method::POST,
+            &["api", "v1", "tenants", &id.to_string(), "reprocess"],
+        );
+        self.json(request).await
+    }
+
+    /// Set a tenant's organization and setting overrides
+    ///
+    /// `PUT /api/v1/tenants/{id}/settings`
+    pub async fn update_tenant_settings(
+        &self,
+        id: uuid::Uuid,
+        body: &UpdateTenantSettingsRequestDto,
+    ) -> Result<TenantApiResponseTenantSettingsResponseDto, Error> {
+        let mut request = self.request(
+            Method::PUT,
+            &["api", "v1", "tenants", &id.to_string(), "settings"],
+        );
+        request = request.json(body);
+        self.json(request).await
+    }
+
+    /// Secret used to sign the tenant's outbound webhook deliveries
+    ///
+    /// `GET /api/v1/tenants/{id}/webhook-secret`
+    pub async fn get_webhook_secret(
+        &self,
+        id: uuid::Uuid,
+    ) -> Result<TenantApiResponseWebhookSigningSecretResponseDto, Error> {
+        let request = self.request(
+            Method::GET,
+            &["api", "v1", "tenants", &id.to_string(), "webhook-secret"],
+        );
+        self.json(request).await
+    }
+
+    /// Replace the tenant's webhook signing secret
+    ///
+    /// `POST /api/v1/tenants/{id}/webhook-secret/rotate`
+    pub async fn rotate_webhook_secret(
+        &self,
+        id: uuid::Uuid,
+    ) -> Result<TenantApiResponseWebhookSigningSecretResponseDto, Error> {
+        let request = self.request(
+            Method::POST,
+            &[
+                "api",
+                "v1",
+                "tenants",
+                &id.to_string(),
+                "webhook-secret",
+                "rotate",
+            ],
+        );
+        self.json(request).await
+    }
+
+    /// Get rate limit policy configuration
+    ///
+    /// `GET /config/rate-limit-policy`
+    pub async fn get_rate_limit_policy_config(&self) -> Result<RateLimitPolicyConfig, Error> {
+        let request = self.request(Method::GET, &["config", "rate-limit-policy"]);
+        self.json(request).await
+    }
+
+    /// Get service configuration summary
+    ///
+    /// `GET /config/summary`
+    pub async fn get_config_summary(&self) -> Result<serde_json::Value, Error> {
+        let request = self.request(Method::GET, &["config", "summary"]);
+        self.json(request).await
+    }
+
+    /// Start OAuth flow for a provider
+    ///
+    /// `POST /connect/{provider}`
+    pub async fn start_oauth(&self, provider: &str) -> Result<AuthorizeUrlResponse, Error> {
+        let request = self.request(Method::POST, &["connect", provider]);
+        self.json(request).await
+    }
+
+    /// Handle OAuth callback from provider
+    ///
+    /// `GET /connect/{provider}/callback`
+    pub async fn oauth_callback(
+        &self,
+        provider: &str,
+        query: &params::OauthCallbackParams,
+    ) -> Result<ConnectionResponse, Error> {
+        let request = self
+            .request(Method::GET, &["connect", provider, "callback"])
+            .query(query);
+        self.json(request).await
+    }
+
+    /// Connect a provider with an API key or OAuth client credentials
+    ///
+    /// `POST /connect/{provider}/credentials`
+    pub async fn connect_credentials(
+        &self,
+        provider: &str,
+        body: &ConnectCredentialsRequest,
+    ) -> Result<ConnectionResponse, Error> {
+        let mut request = self.request(Method::POST, &["connect", provider, "credentials"]);
+        request = request.json(body);
+        self.json(request).await
+    }
+
+    /// Check whether a provider is configured well enough to start the OAuth flow
+    ///
+    /// `GET /connect/{provider}/preflight`
+    pub async fn preflight(&self, provider: &str) -> Result<PreflightResponse, Error> {
+        let request = self.request(Method::GET, &["connect", provider, "preflight"]);
+        self.json(request).await
+    }
+
+    /// Connect Workspace users through a Google service account
+    ///
+    /// `POST /connect/{provider}/service-account`
+    pub async fn connect_service_account(
+        &self,
+        provider: &str,
+        body: &ServiceAccountConnectRequest,
+    ) -> Result<ServiceAccountConnectResponse, Error> {
+        let mut request = self.request(Method::POST, &["connect", provider, "service-account"]);
+        request = request.json(body);
+        self.json(request).await
+    }
+
+    /// Lists connections for the authenticated tenant with optional provider filtering
+    ///
+    /// `GET /connections`
+    pub async fn list_connections(
+        &self,
+        query: &params::ListConnectionsParams,
+    ) -> Result<ConnectionsResponse, Error> {
+        let request = self.request(Method::GET, &["connections"]).query(query);
+        self.json(request).await
+    }
+
+    /// Creates a connection to a provider that does not authorize with OAuth
+    ///
+    /// `POST /connections`
+    pub async fn create_connection(
+        &self,
+        body: &CreateConnectionRequest,
+    ) -> Result<ConnectionInfo, Error> {
+        let mut request = self.request(Method::POST, &["connections"]);
+        request = request.json(body);
+        self.json(request).await
+    }
+
+    /// Updates the configurable metadata of a tenant's connection
+    ///
+    /// `PATCH /connections/{id}`
+    pub async fn update_connection(
+        &self,
+        id: uuid::Uuid,
+        body: &UpdateConnectionRequest,
+    ) -> Result<ConnectionInfo, Error> {
+        let mut request = self.request(Method::PATCH, &["connections", &id.to_string()]);
+        request = request.json(body);
+        self.json(request).await
+    }
+
+    /// Checks a tenant's connection against its provider
+    ///
+    /// `GET /connections/{id}/health`
+    pub async fn get_connection_health(
+        &self,
+        id: uuid::Uuid,
+    ) -> Result<ConnectionHealthResponse, Error> {
+        let request = self.request(Method::GET, &["connections", &id.to_string(), "health"]);
+        self.json(request).await
+    }
+
+    /// Pauses scheduled syncs of a tenant's connection
+    ///
+    /// `POST /connections/{id}/pause`
+    pub async fn pause_connection(
+        &self,
+        id: uuid::Uuid,
+    ) -> Result<ConnectionStatusResponse, Error> {
+        let request = self.request(Method::POST, &["connections", &id.to_string(), "pause"]);
+        self.json(request).await
+    }
+
+    /// Resumes scheduled syncs of a tenant's connection
+    ///
+    /// `POST /connections/{id}/resume`
+    pub async fn resume_connection(
+        &self,
+        id: uuid::Uuid,
+    ) -> Result<ConnectionStatusResponse, Error> {
+        let request = self.request(Method::POST, &["connections", &id.to_string(), "resume"]);
+        self.json(request).await
+    }
+
+    /// Lists the executed sync runs of a tenant's connection
+    ///
+    /// `GET /connections/{id}/runs`
+    pub async fn list_connection_runs(
+        &self,
+        id: uuid::Uuid,
+        query: &params::ListConnectionRunsParams,
+    ) -> Result<SyncRunsResponse, Error> {
+        let request = self
+            .request(Method::GET, &["connections", &id.to_string(), "runs"])
+            .query(query);
+        self.json(request).await
+    }
+
+    /// Triggers a sync of a tenant's connection
+    ///
+    /// `POST /connections/{id}/sync`
+    pub async fn trigger_sync(
+        &self,
+        id: uuid::Uuid,
+        query: &params::TriggerSyncParams,
+    ) -> Result<DryRunSyncResponse, Error> {
+        let request = self
+            .request(Method::POST, &["connections", &id.to_string(), "sync"])
+            .query(query);
+        self.json(request).await
+    }
+
+    /// Lists the token refresh history of a tenant's connection
+    ///
+    /// `GET /connections/{id}/token-events`
+    pub async fn list_token_events(
+        &self,
+        id: uuid::Uuid,
+        query: &params::ListTokenEventsParams,
+    ) -> Result<TokenEventsResponse, Error> {
+        let request = self
+            .request(
+                Method::GET,
+                &["connections", &id.to_string(), "token-events"],
+            )
+            .query(query);
+        self.json(request).await
+    }
+
+    /// List grounded signals with filtering and pagination
+    ///
+    /// `GET /grounded-signals`
+    pub async fn list_grounded_signals(
+        &self,
+        query: &params::ListGroundedSignalsParams,
+    ) -> Result<ListGroundedSignalsResponse, Error> {
+        let request = self
+            .request(Method::GET, &["grounded-signals"])
+            .query(query);
+        self.json(request).await
+    }
+
+    /// Get a grounded signal by ID
+    ///
+    /// `GET /grounded-signals/{id}`
+    pub async fn get_grounded_signal(
+        &self,
+        id: uuid::Uuid,
+    ) -> Result<GroundedSignalResponse, Error> {
+        let request = self.request(Method::GET, &["grounded-signals", &id.to_string()]);
+        self.json(request).await
+    }
+
+    /// Delete a grounded signal
+    ///
+    /// `DELETE /grounded-signals/{id}`
+    pub async fn delete_grounded_signal(&self, id: uuid::Uuid) -> Result<(), Error> {
+        let request = self.request(Method::DELETE, &["grounded-signals", &id.to_string()]);
+        self.empty(request).await
+    }
+
+    /// Update grounded signal status and recommendation
+    ///
+    /// `PATCH /grounded-signals/{id}`
+    pub async fn update_grounded_signal(
+        &self,
+        id: uuid::Uuid,
+        body: &UpdateGroundedSignalRequest,
+    ) -> Result<GroundedSignalResponse, Error> {
+        let mut request = self.request(Method::PATCH, &["grounded-signals", &id.to_string()]);
+        request = request.json(body);
+        self.json(request).await
+    }
+
+    /// Health check endpoint (public, no auth required)
+    ///
+    /// `GET /healthz`
+    pub async fn health(&self) -> Result<HealthResponse, Error> {
+        let request = self.request(Method::GET, &["healthz"]);
+        self.json(request).await
+    }
+
+    /// Accept a forwarded email message
+    ///
+    /// `POST /ingest/email`
+    pub async fn ingest_email(&self) -> Result<InboundEmailAcceptResponse, Error> {
+        let request = self.request(Method::POST, &["ingest", "email"]);
+        self.json(request).await
+    }
+
+    /// List jobs endpoint requiring operator auth and tenant header
+    ///
+    /// `GET /jobs`
+    pub async fn list_jobs(&self, query: &params::ListJobsParams) -> Result<JobsResponse, Error> {
+        let request = self.request(Method::GET, &["jobs"]).query(query);
+        self.json(request).await
+    }
+
+    /// Cancel a job
+    ///
+    /// `POST /jobs/{id}/cancel`
+    pub async fn cancel_job(&self, id: uuid::Uuid) -> Result<JobInfo, Error> {
+        let request = self.request(Method::POST, &["jobs", &id.to_string(), "cancel"]);
+        self.json(request).await
+    }
+
+    /// Protected ping endpoint requiring operator auth and tenant header
+    ///
+    /// `GET /protected/ping`
+    pub async fn protected_ping(&self) -> Result<ProtectedPingResponse, Error> {
+        let request = self.request(Method::GET, &["protected", "ping"]);
+        self.json(request).await
+    }
+
+    /// Public endpoint to list all available providers
+    ///
+    /// `GET /providers`
+    pub async fn list_providers(
+        &self,
+        query: &params::ListProvidersParams,
+    ) -> Result<ProvidersResponse, Error> {
+        let request = self.request(Method::GET, &["providers"]).query(query);
+        self.json(request).await
+    }
+
+    /// Public endpoint describing what a provider supports
+    ///
+    /// `GET /providers/{slug}/capabilities`
+    pub async fn get_capabilities(&self, slug: &str) -> Result<ProviderCapabilities, Error> {
+        let request = self.request(Method::GET, &["providers", slug, "capabilities"]);
+        self.json(request).await
+    }
+
+    /// Public endpoint describing a provider's configurable connection metadata
+    ///
+    /// `GET /providers/{slug}/metadata-schema`
+    pub async fn get_metadata_schema(&self, slug: &str) -> Result<MetadataSchemaResponse, Error> {
+        let request = self.request(Method::GET, &["providers", slug, "metadata-schema"]);
+        self.json(request).await
+    }
+
+    /// Public endpoint reporting whether the executor is running a provider's jobs
+    ///
+    /// `GET /providers/{slug}/status`
+    pub async fn get_provider_status(&self, slug: &str) -> Result<ProviderStatusResponse, Error> {
+        let request = self.request(Method::GET, &["providers", slug, "status"]);
+        self.json(request).await
+    }
+
+    /// Readiness check endpoint (public, no auth required)
+    ///
+    /// `GET /readyz`
+    pub async fn ready(&self) -> Result<ReadinessResponse, Error> {
+        let request = self.request(Method::GET, &["readyz"]);
+        self.json(request).await
+    }
+
+    /// Upcoming scheduled syncs and blackout windows as JSON
+    ///
+    /// `GET /schedule`
+    pub async fn get_schedule(
+        &self,
+        query: &params::GetScheduleParams,
+    ) -> Result<ScheduleResponse, Error> {
+        let request = self.request(Method::GET, &["schedule"]).query(query);
+        self.json(request).await
+    }
+
+    /// Upcoming scheduled syncs and blackout windows as an iCalendar feed
+    ///
+    /// `GET /schedule.ics`
+    pub async fn get_schedule_ical(
+        &self,
+        query: &params::GetScheduleIcalParams,
+    ) -> Result<String, Error> {
+        let request = self.request(Method::GET, &["schedule.ics"]).query(query);
+        self.text(request).await
+    }
+
+    /// List canonical signal kinds and the tenant's custom kinds
+    ///
+    /// `GET /signal-kinds`
+    pub async fn list_signal_kinds(&self) -> Result<SignalKindsResponse, Error> {
+        let request = self.request(Method::GET, &["signal-kinds"]);
+        self.json(request).await
+    }
+
+    /// Register a custom signal kind for the tenant
+    ///
+    /// `POST /signal-kinds`
+    pub async fn register_signal_kind(
+        &self,
+        body: &RegisterSignalKindRequest,
+    ) -> Result<CustomSignalKindInfo, Error> {
+        let mut request = self.request(Method::POST, &["signal-kinds"]);
+        request = request.json(body);
+        self.json(request).await
+    }
+
+    /// List signals with filters and cursor pagination
+    ///
+    /// `GET /signals`
+    pub async fn list_signals(
+        &self,
+        query: &params::ListSignalsParams,
+    ) -> Result<SignalsResponse, Error> {
+        let request = self.request(Method::GET, &["signals"]).query(query);
+        self.json(request).await
+    }
+
+    /// Trace a signal through the ingestion pipeline
+    ///
+    /// `GET /signals/{id}/trace`
+    pub async fn get_signal_trace(&self, id: &str) -> Result<SignalTraceResponse, Error> {
+        let request = self.request(Method::GET, &["signals", id, "trace"]);
+        self.json(request).await
+    }
+
+    /// Daily job and signal statistics for the tenant, served from rollups
+    ///
+    /// `GET /stats/daily`
+    pub async fn get_daily_stats(
+        &self,
+        query: &params::GetDailyStatsParams,
+    ) -> Result<DailyStatsResponse, Error> {
+        let request = self.request(Method::GET, &["stats", "daily"]).query(query);
+        self.json(request).await
+    }
+
+    /// Slowest database statements across the service over the last hour
+    ///
+    /// `GET /stats/slow-queries`
+    pub async fn get_slow_queries(
+        &self,
+        query: &params::GetSlowQueriesParams,
+    ) -> Result<SlowQueryReport, Error> {
+        let request = self
+            .request(Method::GET, &["stats", "slow-queries"])
+            .query(query);
+        self.json(request).await
+    }
+
+    /// Accept webhook from external provider
+    ///
+    /// `POST /webhooks/{provider}`
+    pub async fn ingest_webhook(
+        &self,
+        provider: &str,
+        headers: &params::IngestWebhookHeaders,
+        body: Option<&Value>,
+    ) -> Result<WebhookAcceptResponse, Error> {
+        let mut request = self.request(Method::POST, &["webhooks", provider]);
+        if let Some(value) = &headers.x_connection_id {
+            request = request.header("X-Connection-Id", value);
+        }
+        if let Some(body) = body {
+            request = request.json(body);
+        }
+        self.json(request).await
+    }
+
+    /// Accept webhook from external provider via public route with signature verification
+    ///
+    /// `POST /webhooks/{provider}/{tenant_id}`
+    pub async fn ingest_public_webhook(
+        &self,
+        provider: &str,
+        tenant_id: &str,
+        query: &params::IngestPublicWebhookParams,
+        headers: &params::IngestPublicWebhookHeaders,
+        body: Option<&Value>,
+    ) -> Result<WebhookAcceptResponse, Error> {
+        let mut request = self
+            .request(Method::POST, &["webhooks", provider, tenant_id])
+            .query(query);
+        if let Some(value) = &headers.x_connection_id {
+            request = request.header("X-Connection-Id", value);
+        }
+        if let Some(value) = &headers.x_hub_signature_256 {
+            request = request.header("X-Hub-Signature-256", value);
+        }
+        if let Some(value) = &headers.x_slack_signature {
+            request = request.header("X-Slack-Signature", value);
+        }
+        if let Some(value) = &headers.x_slack_request_timestamp {
+            request = request.header("X-Slack-Request-Timestamp", value);
+        }
+        if let Some(value) = &headers.linear_signature {
+            request = request.header("Linear-Signature", value);
+        }
+        if let Some(value) = &headers.x_gitlab_token {
+            request = request.header("X-Gitlab-Token", value);
+        }
+        if let Some(value) = &headers.x_webhook_signature {
+            request = request.header("X-Webhook-Signature", value);
+        }
+        if let Some(body) = body {
+            request = request.json(body);
+        }
+        self.json(request).await
+    }
+}
@@ -0,0 +1,209 @@
+//! # Poblysh Connectors Client
+//!
+//! Typed async client for the Connectors API. The `types` and `params`
+//! modules and the endpoint methods on [`Client`] are generated from the
+//! service's `openapi.json` by `poblysh-connectors-client-codegen`; run
+//! `make client` after changing the API and commit the result.
+//!
+//! ```no_run
+//! # async fn example() -> Result<(), poblysh_connectors_client::Error> {
+//! use poblysh_connectors_client::{Client, params};
+//!
+//! let client = Client::new("https://connectors.example.com")?
+//!     .with_token("operator-token")
+//!     .with_tenant(uuid::Uuid::nil());
+//! let signals = client.list_signals(&params::ListSignalsParams::default()).await?;
+//! println!("{} signals", signals.signals.len());
+//! # Ok(())
+//! # }
+//! ```
+
+mod generated;
+pub mod webhooks;
+
+pub use generated::{params, types};
+
+use reqwest::{Method, RequestBuilder, Response, StatusCode};
+use serde::de::DeserializeOwned;
+use url::Url;
+use uuid::Uuid;
+
+/// Errors returned by [`Client`] calls
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// The base URL cannot be parsed or cannot carry a path
+    #[error("invalid base URL: {0}")]
+    InvalidBaseUrl(String),
+    /// The request could not be sent or the response body could not be read
+    #[error("request failed: {0}")]
+    Http(#[from] reqwest::Error),
+    /// The API answered with a structured error
+    #[error("API error {status}: {} ({})", .error.message, .error.code)]
+    Api {
+        status: StatusCode,
+        error: types::ApiError,
+    },
+    /// The API answered with a non-success status and an unstructured body
+    #[error("unexpected response {status}: {body}")]
+    UnexpectedResponse { status: StatusCode, body: String },
+}
+
+/// Connectors API client
+///
+/// Cheap to clone; clones share the underlying connection pool.
+#[derive(Debug, Clone)]
+pub struct Client {
+    http: reqwest::Client,
+    base_url: Url,
+    token: Option<String>,
+    tenant_id: Option<Uuid>,
+}
+
+impl Client {
+    /// Client for the API served at `base_url`
+    pub fn new(base_url: &str) -> Result<Self, Error> {
+        let base_url =
+            Url::parse(base_url).map_err(|e| Error::InvalidBaseUrl(format!("{base_url}: {e}")))?;
+        if base_url.cannot_be_a_base() {
+            return Err(Error::InvalidBaseUrl(base_url.to_string()));
+        }
+        Ok(Self {
+            http: reqwest::Client::new(),
+            base_url,
+            token: None,
+            tenant_id: None,
+        })
+    }
+
+    /// Use a preconfigured HTTP client (timeouts, proxies, TLS roots)
+    pub fn with_http_client(mut self, http: reqwest::Client) -> Self {
+        self.http = http;
+        self
+    }
+
+    /// Send `Authorization: Bearer <token>` with every request
+    pub fn with_token(mut self, token: impl Into<String>) -> Self {
+        self.token = Some(token.into());
+        self
+    }
+
+    /// Send `X-Tenant-Id` with every request
+    pub fn with_tenant(mut self, tenant_id: Uuid) -> Self {
+        self.tenant_id = Some(tenant_id);
+        self
+    }
+
+    /// Request to the endpoint made of `segments`, which are percent-encoded
+    fn request(&self, method: Method, segments: &[&str]) -> RequestBuilder {
+        let mut url = self.base_url.clone();
+        if let Ok(mut path) = url.path_segments_mut() {
+            path.pop_if_empty().extend(segments);
+        }
+        let mut request = self.http.request(method, url);
+        if let Some(token) = &self.token {
+            request = request.bearer_auth(token);
+        }
+        if let Some(tenant_id) = self.tenant_id {
+            request = request.header("X-Tenant-Id", tenant_id.to_string());
+        }
+        request
+    }
+
+    async fn json<T: DeserializeOwned>(&self, request: RequestBuilder) -> Result<T, Error> {
+        Ok(send(request).await?.json().await?)
+    }
+
+    async fn text(&self, request: RequestBuilder) -> Result<String, Error> {
+        Ok(send(request).await?.text().await?)
+    }
+
+    async fn empty(&self, request: RequestBuilder) -> Result<(), Error> {
+        send(request).await?;
+        Ok(())
+    }
+}
+
+/// Send a request and turn non-success statuses into [`Error`]
+async fn send(request: RequestBuilder) -> Result<Response, Error> {
+    let response = request.send().await?;
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+    let body = response.text().await?;
+    Err(match serde_json::from_str(&body) {
+        Ok(error) => Error::Api { status, error },
+        Err(_) => Error::UnexpectedResponse { status, body },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{header, method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[test]
+    fn test_generated_client_matches_openapi_spec() {
+        let root = std::path::Path::new(env!("CARGO_MANIFEST_DIR"));
+        let spec = std::fs::read_to_string(root.join("../openapi.json")).unwrap();
+        let expected =
+            poblysh_connectors_client_codegen::render(&serde_json::from_str(&spec).unwrap())
+                .unwrap();
+        let actual = std::fs::read_to_string(root.join("src/generated.rs")).unwrap();
+        assert!(
+            actual == expected,
+            "client/src/generated.rs is stale; run `make client` and commit the result"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_client_sends_credentials_and_maps_errors() {
+        let server = MockServer::start().await;
+        let tenant_id = Uuid::from_u128(7);
+        Mock::given(method("GET"))
+            .and(path("/signals"))
+            .and(query_param("provider", "github"))
+            .and(header("authorization", "Bearer op-token"))
+            .and(header("x-tenant-id", tenant_id.to_string().as_str()))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "signals": [],
+                "next_cursor": null
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/connections"))
+            .respond_with(ResponseTemplate::new(401).set_body_json(serde_json::json!({
+                "code": "UNAUTHORIZED",
+                "message": "missing bearer token"
+            })))
+            .mount(&server)
+            .await;
+
+        let client = Client::new(&server.uri())
+            .unwrap()
+            .with_token("op-token")
+            .with_tenant(tenant_id);
+        let signals = client
+            .list_signals(&params::ListSignalsParams {
+                provider: Some("github".to_string()),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert!(signals.signals.is_empty());
+
+        let error = client
+            .list_connections(&params::ListConnectionsParams::default())
+            .await
+            .unwrap_err();
+        match error {
+            Error::Api { status, error } => {
+                assert_eq!(status, StatusCode::UNAUTHORIZED);
+                assert_eq!(error.code, "UNAUTHORIZED");
+            }
+            other => panic!("unexpected error: {other}"),
+        }
+    }
+}
@@ -0,0 +1,176 @@
+//! Verification of webhook deliveries sent by the Connectors service
+//!
+//! Notifications posted to a tenant's webhook URL carry
+//! `X-Poblysh-Timestamp` (Unix seconds) and `X-Poblysh-Signature`
+//! (`sha256=<hex>` HMAC-SHA256 of `"{timestamp}.{body}"`, keyed with the
+//! tenant's signing secret from `GET /api/v1/tenants/{id}/webhook-secret`).
+//! Pass the raw request body, before any JSON parsing, to [`verify_signature`].
+//!
+//! ```
+//! use poblysh_connectors_client::webhooks::{self, DEFAULT_TOLERANCE};
+//!
+//! # let (secret, body) = ("whsec_example", br#"{"type":"connection.paused"}"#);
+//! # let timestamp = chrono::Utc::now().timestamp().to_string();
+//! # let signature = webhooks::sign(secret, &timestamp, body);
+//! // Header values as received
+//! let verified = webhooks::verify_signature(secret, &timestamp, &signature, body, DEFAULT_TOLERANCE);
+//! assert!(verified.is_ok());
+//! ```
+
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+/// Header carrying the `sha256=<hex>` signature of a delivery
+pub const SIGNATURE_HEADER: &str = "X-Poblysh-Signature";
+
+/// Header carrying the Unix timestamp included in the signature
+pub const TIMESTAMP_HEADER: &str = "X-Poblysh-Timestamp";
+
+/// Accepted distance between the signed timestamp and the receiver's clock
+pub const DEFAULT_TOLERANCE: Duration = Duration::from_secs(300);
+
+/// Reasons a delivery fails verification
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum WebhookVerificationError {
+    /// The timestamp header is missing or not an integer
+    #[error("invalid {TIMESTAMP_HEADER} header")]
+    InvalidTimestamp,
+    /// The signature header is missing or not `sha256=<hex>`
+    #[error("invalid {SIGNATURE_HEADER} header")]
+    InvalidSignatureFormat,
+    /// The timestamp is further from now than the tolerance allows
+    #[error("timestamp outside the tolerance window")]
+    TimestampOutOfWindow,
+    /// The signature does not match the body and secret
+    #[error("signature mismatch")]
+    SignatureMismatch,
+}
+
+/// `sha256=<hex>` HMAC of `"{timestamp}.{body}"`, as sent by the service
+pub fn sign(secret: &str, timestamp: &str, body: &[u8]) -> String {
+    format!(
+        "sha256={}",
+        hex::encode(mac(secret, timestamp, body).finalize().into_bytes())
+    )
+}
+
+/// Verify a delivery's headers against its raw body using the current time
+pub fn verify_signature(
+    secret: &str,
+    timestamp_header: &str,
+    signature_header: &str,
+    body: &[u8],
+    tolerance: Duration,
+) -> Result<(), WebhookVerificationError> {
+    verify_signature_at(
+        secret,
+        timestamp_header,
+        signature_header,
+        body,
+        tolerance,
+        Utc::now(),
+    )
+}
+
+/// Verify a delivery as of `now`
+pub fn verify_signature_at(
+    secret: &str,
+    timestamp_header: &str,
+    signature_header: &str,
+    body: &[u8],
+    tolerance: Duration,
+    now: DateTime<Utc>,
+) -> Result<(), WebhookVerificationError> {
+    let timestamp: i64 = timestamp_header
+        .trim()
+        .parse()
+        .map_err(|_| WebhookVerificationError::InvalidTimestamp)?;
+    let provided = signature_header
+        .trim()
+        .strip_prefix("sha256=")
+        .and_then(|digest| hex::decode(digest).ok())
+        .ok_or(WebhookVerificationError::InvalidSignatureFormat)?;
+
+    if now.timestamp().abs_diff(timestamp) > tolerance.as_secs() {
+        return Err(WebhookVerificationError::TimestampOutOfWindow);
+    }
+
+    mac(secret, timestamp_header.trim(), body)
+        .verify_slice(&provided)
+        .map_err(|_| WebhookVerificationError::SignatureMismatch)
+}
+
+fn mac(secret: &str, timestamp: &str, body: &[u8]) -> Hmac<Sha256> {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts any key length");
+    mac.update(timestamp.as_bytes());
+    mac.update(b".");
+    mac.update(body);
+    mac
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECRET: &str = "whsec_test";
+    const BODY: &[u8] = br#"{"grounded_signal_id":"1"}"#;
+
+    fn at(timestamp: i64) -> DateTime<Utc> {
+        DateTime::from_timestamp(timestamp, 0).unwrap()
+    }
+
+    #[test]
+    fn accepts_matching_signature_within_tolerance() {
+        let signature = sign(SECRET, "1700000000", BODY);
+        assert_eq!(
+            verify_signature_at(
+                SECRET,
+                "1700000000",
+                &signature,
+                BODY,
+                DEFAULT_TOLERANCE,
+                at(1_700_000_120)
+            ),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn rejects_tampering_replays_and_malformed_headers() {
+        let signature = sign(SECRET, "1700000000", BODY);
+        let now = at(1_700_000_000);
+        let verify = |secret, timestamp, signature: &str, body, now| {
+            verify_signature_at(secret, timestamp, signature, body, DEFAULT_TOLERANCE, now)
+        };
+
+        assert_eq!(
+            verify(SECRET, "1700000000", &signature, b"{}".as_slice(), now),
+            Err(WebhookVerificationError::SignatureMismatch)
+        );
+        assert_eq!(
+            verify("whsec_other", "1700000000", &signature, BODY, now),
+            Err(WebhookVerificationError::SignatureMismatch)
+        );
+        // Moving the timestamp invalidates the signature
+        assert_eq!(
+            verify(SECRET, "1700000001", &signature, BODY, now),
+            Err(WebhookVerificationError::SignatureMismatch)
+        );
+        assert_eq!(
+            verify(SECRET, "1700000000", &signature, BODY, at(1_700_000_301)),
+            Err(WebhookVerificationError::TimestampOutOfWindow)
+        );
+        assert_eq!(
+            verify(SECRET, "soon", &signature, BODY, now),
+            Err(WebhookVerificationError::InvalidTimestamp)
+        );
+        assert_eq!(
+            verify(SECRET, "1700000000", "deadbeef", BODY, now),
+            Err(WebhookVerificationError::InvalidSignatureFormat)
+        );
+    }
+}
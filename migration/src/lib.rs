@@ -13,6 +13,27 @@ mod m2025_11_02_120000_create_oauth_states;
 mod m2025_11_03_000100_add_sync_job_unique_interval_guard;
 mod m2025_11_07_120000_create_grounded_signals;
 mod m2025_11_07_120100_create_tenant_signal_configs;
+mod m2025_11_10_090000_create_signal_outbox;
+mod m2025_11_11_090000_create_daily_rollups;
+mod m2025_11_12_090000_create_audit_log_entries;
+mod m2025_11_13_090000_create_connection_secrets;
+mod m2025_11_14_090000_create_tenant_data_keys;
+mod m2025_11_15_090000_create_tenant_signal_kinds;
+mod m2025_11_16_090000_create_signal_pipeline_events;
+mod m2025_11_17_090000_rename_tenant_signal_config_table;
+mod m2025_11_18_090000_create_organizations;
+mod m2025_11_19_090000_create_slo_outcomes;
+mod m2025_11_20_090000_add_tenant_sandbox_source;
+mod m2025_11_21_090000_add_tenant_webhook_signing_secret;
+mod m2025_11_22_090000_add_sync_job_lease;
+mod m2025_11_23_090000_add_sync_job_locked_by;
+mod m2025_11_24_090000_create_provider_circuits;
+mod m2025_11_25_090000_add_sync_job_rate_limited_attempts;
+mod m2025_11_26_090000_create_sync_job_runs;
+mod m2025_11_27_090000_create_connection_refresh_locks;
+mod m2025_11_28_090000_create_token_events;
+mod m2025_11_29_090000_add_key_versions;
+mod m2025_11_30_090000_create_tenant_configs;
 
 pub struct Migrator;
 
@@ -29,6 +50,27 @@ impl MigratorTrait for Migrator {
             Box::new(m2025_11_03_000100_add_sync_job_unique_interval_guard::Migration),
             Box::new(m2025_11_07_120000_create_grounded_signals::Migration),
             Box::new(m2025_11_07_120100_create_tenant_signal_configs::Migration),
+            Box::new(m2025_11_10_090000_create_signal_outbox::Migration),
+            Box::new(m2025_11_11_090000_create_daily_rollups::Migration),
+            Box::new(m2025_11_12_090000_create_audit_log_entries::Migration),
+            Box::new(m2025_11_13_090000_create_connection_secrets::Migration),
+            Box::new(m2025_11_14_090000_create_tenant_data_keys::Migration),
+            Box::new(m2025_11_15_090000_create_tenant_signal_kinds::Migration),
+            Box::new(m2025_11_16_090000_create_signal_pipeline_events::Migration),
+            Box::new(m2025_11_17_090000_rename_tenant_signal_config_table::Migration),
+            Box::new(m2025_11_18_090000_create_organizations::Migration),
+            Box::new(m2025_11_19_090000_create_slo_outcomes::Migration),
+            Box::new(m2025_11_20_090000_add_tenant_sandbox_source::Migration),
+            Box::new(m2025_11_21_090000_add_tenant_webhook_signing_secret::Migration),
+            Box::new(m2025_11_22_090000_add_sync_job_lease::Migration),
+            Box::new(m2025_11_23_090000_add_sync_job_locked_by::Migration),
+            Box::new(m2025_11_24_090000_create_provider_circuits::Migration),
+            Box::new(m2025_11_25_090000_add_sync_job_rate_limited_attempts::Migration),
+            Box::new(m2025_11_26_090000_create_sync_job_runs::Migration),
+            Box::new(m2025_11_27_090000_create_connection_refresh_locks::Migration),
+            Box::new(m2025_11_28_090000_create_token_events::Migration),
+            Box::new(m2025_11_29_090000_add_key_versions::Migration),
+            Box::new(m2025_11_30_090000_create_tenant_configs::Migration),
         ]
     }
 }
@@ -0,0 +1,133 @@
+//! Migration to create the signal_outbox table.
+//!
+//! The outbox records downstream deliveries (event bus publication, weak engine
+//! enqueue) in the same transaction that persists a signal, so consumers only
+//! ever observe committed signals and never miss one.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(SignalOutbox::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(SignalOutbox::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(SignalOutbox::TenantId).uuid().not_null())
+                    .col(ColumnDef::new(SignalOutbox::SignalId).uuid().not_null())
+                    .col(ColumnDef::new(SignalOutbox::Topic).text().not_null())
+                    .col(
+                        ColumnDef::new(SignalOutbox::Status)
+                            .text()
+                            .not_null()
+                            .default("pending"),
+                    )
+                    .col(
+                        ColumnDef::new(SignalOutbox::Attempts)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(SignalOutbox::AvailableAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(SignalOutbox::PublishedAt)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .col(ColumnDef::new(SignalOutbox::LastError).text().null())
+                    .col(
+                        ColumnDef::new(SignalOutbox::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(SignalOutbox::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_signal_outbox_signal_id")
+                            .from(SignalOutbox::Table, SignalOutbox::SignalId)
+                            .to(Signals::Table, Signals::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // One delivery per signal and topic guarantees we never double-enqueue
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_signal_outbox_signal_topic")
+                    .table(SignalOutbox::Table)
+                    .col(SignalOutbox::SignalId)
+                    .col(SignalOutbox::Topic)
+                    .unique()
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await?;
+
+        // Relay scans pending rows in availability order
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_signal_outbox_status_available")
+                    .table(SignalOutbox::Table)
+                    .col(SignalOutbox::Status)
+                    .col(SignalOutbox::AvailableAt)
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(SignalOutbox::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum SignalOutbox {
+    Table,
+    Id,
+    TenantId,
+    SignalId,
+    Topic,
+    Status,
+    Attempts,
+    AvailableAt,
+    PublishedAt,
+    LastError,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Signals {
+    Table,
+    Id,
+}
@@ -0,0 +1,182 @@
+//! Migration to create the daily rollup tables.
+//!
+//! `job_daily_rollups` and `signal_daily_rollups` hold per-tenant, per-provider
+//! aggregates for one UTC day so stats endpoints never scan the raw sync_jobs and
+//! signals tables. Rows are recomputed idempotently by the nightly rollup job.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(JobDailyRollups::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(JobDailyRollups::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(JobDailyRollups::Day).date().not_null())
+                    .col(ColumnDef::new(JobDailyRollups::TenantId).uuid().not_null())
+                    .col(
+                        ColumnDef::new(JobDailyRollups::ProviderSlug)
+                            .text()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(JobDailyRollups::JobsSucceeded)
+                            .big_integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(JobDailyRollups::JobsFailed)
+                            .big_integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(JobDailyRollups::MeanDurationMs)
+                            .double()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(JobDailyRollups::ComputedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_job_daily_rollups_tenant_id")
+                            .from(JobDailyRollups::Table, JobDailyRollups::TenantId)
+                            .to(Tenants::Table, Tenants::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_job_daily_rollups_day_tenant_provider")
+                    .table(JobDailyRollups::Table)
+                    .col(JobDailyRollups::Day)
+                    .col(JobDailyRollups::TenantId)
+                    .col(JobDailyRollups::ProviderSlug)
+                    .unique()
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(SignalDailyRollups::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(SignalDailyRollups::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(SignalDailyRollups::Day).date().not_null())
+                    .col(
+                        ColumnDef::new(SignalDailyRollups::TenantId)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(SignalDailyRollups::ProviderSlug)
+                            .text()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(SignalDailyRollups::Kind).text().not_null())
+                    .col(
+                        ColumnDef::new(SignalDailyRollups::SignalCount)
+                            .big_integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(SignalDailyRollups::ComputedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_signal_daily_rollups_tenant_id")
+                            .from(SignalDailyRollups::Table, SignalDailyRollups::TenantId)
+                            .to(Tenants::Table, Tenants::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_signal_daily_rollups_day_tenant_provider_kind")
+                    .table(SignalDailyRollups::Table)
+                    .col(SignalDailyRollups::Day)
+                    .col(SignalDailyRollups::TenantId)
+                    .col(SignalDailyRollups::ProviderSlug)
+                    .col(SignalDailyRollups::Kind)
+                    .unique()
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(SignalDailyRollups::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(JobDailyRollups::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum JobDailyRollups {
+    Table,
+    Id,
+    Day,
+    TenantId,
+    ProviderSlug,
+    JobsSucceeded,
+    JobsFailed,
+    MeanDurationMs,
+    ComputedAt,
+}
+
+#[derive(DeriveIden)]
+enum SignalDailyRollups {
+    Table,
+    Id,
+    Day,
+    TenantId,
+    ProviderSlug,
+    Kind,
+    SignalCount,
+    ComputedAt,
+}
+
+#[derive(DeriveIden)]
+enum Tenants {
+    Table,
+    Id,
+}
@@ -0,0 +1,98 @@
+//! Migration to create the audit_log_entries table.
+//!
+//! Audit entries record sensitive operator actions (such as break-glass token
+//! decryption). Rows intentionally carry no foreign keys so the trail survives
+//! deletion of the tenant or connection it refers to.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(AuditLogEntries::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(AuditLogEntries::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(AuditLogEntries::TenantId).uuid().null())
+                    .col(ColumnDef::new(AuditLogEntries::Actor).text().not_null())
+                    .col(ColumnDef::new(AuditLogEntries::Action).text().not_null())
+                    .col(
+                        ColumnDef::new(AuditLogEntries::TargetType)
+                            .text()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(AuditLogEntries::TargetId).text().not_null())
+                    .col(ColumnDef::new(AuditLogEntries::Reason).text().null())
+                    .col(
+                        ColumnDef::new(AuditLogEntries::Details)
+                            .json_binary()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(AuditLogEntries::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Reviews list a tenant's trail newest first
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_audit_log_entries_tenant_created")
+                    .table(AuditLogEntries::Table)
+                    .col(AuditLogEntries::TenantId)
+                    .col(AuditLogEntries::CreatedAt)
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_audit_log_entries_target")
+                    .table(AuditLogEntries::Table)
+                    .col(AuditLogEntries::TargetType)
+                    .col(AuditLogEntries::TargetId)
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(AuditLogEntries::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum AuditLogEntries {
+    Table,
+    Id,
+    TenantId,
+    Actor,
+    Action,
+    TargetType,
+    TargetId,
+    Reason,
+    Details,
+    CreatedAt,
+}
@@ -0,0 +1,178 @@
+//! Migration to move token ciphertexts into the connection_secrets table.
+//!
+//! Connection rows are read by listings, logs and diagnostics; keeping the
+//! access/refresh token ciphertexts in a separate table means none of those
+//! queries can select them by accident. Existing ciphertexts are copied over
+//! before the columns are dropped from connections.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(ConnectionSecrets::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ConnectionSecrets::ConnectionId)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(ConnectionSecrets::AccessTokenCiphertext)
+                            .binary()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(ConnectionSecrets::RefreshTokenCiphertext)
+                            .binary()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(ConnectionSecrets::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(ConnectionSecrets::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_connection_secrets_connection_id")
+                            .from(ConnectionSecrets::Table, ConnectionSecrets::ConnectionId)
+                            .to(Connections::Table, Connections::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Copy existing ciphertexts before dropping the columns
+        let copy = Query::insert()
+            .into_table(ConnectionSecrets::Table)
+            .columns([
+                ConnectionSecrets::ConnectionId,
+                ConnectionSecrets::AccessTokenCiphertext,
+                ConnectionSecrets::RefreshTokenCiphertext,
+            ])
+            .select_from(
+                Query::select()
+                    .columns([
+                        Connections::Id,
+                        Connections::AccessTokenCiphertext,
+                        Connections::RefreshTokenCiphertext,
+                    ])
+                    .from(Connections::Table)
+                    .cond_where(
+                        Cond::any()
+                            .add(Expr::col(Connections::AccessTokenCiphertext).is_not_null())
+                            .add(Expr::col(Connections::RefreshTokenCiphertext).is_not_null()),
+                    )
+                    .to_owned(),
+            )
+            .map_err(|e| DbErr::Custom(e.to_string()))?
+            .to_owned();
+        manager.exec_stmt(copy).await?;
+
+        // SQLite accepts only one alteration per statement
+        for column in [
+            Connections::AccessTokenCiphertext,
+            Connections::RefreshTokenCiphertext,
+        ] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Connections::Table)
+                        .drop_column(column)
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for column in [
+            Connections::AccessTokenCiphertext,
+            Connections::RefreshTokenCiphertext,
+        ] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Connections::Table)
+                        .add_column(ColumnDef::new(column).binary().null())
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        for (target, source) in [
+            (
+                Connections::AccessTokenCiphertext,
+                ConnectionSecrets::AccessTokenCiphertext,
+            ),
+            (
+                Connections::RefreshTokenCiphertext,
+                ConnectionSecrets::RefreshTokenCiphertext,
+            ),
+        ] {
+            let restore = Query::update()
+                .table(Connections::Table)
+                .value(
+                    target,
+                    SimpleExpr::SubQuery(
+                        None,
+                        Box::new(
+                            Query::select()
+                                .column(source)
+                                .from(ConnectionSecrets::Table)
+                                .and_where(
+                                    Expr::col((
+                                        ConnectionSecrets::Table,
+                                        ConnectionSecrets::ConnectionId,
+                                    ))
+                                    .equals((Connections::Table, Connections::Id)),
+                                )
+                                .to_owned()
+                                .into_sub_query_statement(),
+                        ),
+                    ),
+                )
+                .to_owned();
+            manager.exec_stmt(restore).await?;
+        }
+
+        manager
+            .drop_table(Table::drop().table(ConnectionSecrets::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum ConnectionSecrets {
+    Table,
+    ConnectionId,
+    AccessTokenCiphertext,
+    RefreshTokenCiphertext,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Connections {
+    Table,
+    Id,
+    AccessTokenCiphertext,
+    RefreshTokenCiphertext,
+}
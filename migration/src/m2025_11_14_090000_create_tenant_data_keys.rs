@@ -0,0 +1,68 @@
+//! Migration to create the tenant_data_keys table.
+//!
+//! Each tenant's connection tokens are encrypted with its own data key, stored
+//! here wrapped by the master key. Deleting a row crypto-shreds that tenant's
+//! tokens; rows cascade with the tenant.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(TenantDataKeys::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(TenantDataKeys::TenantId)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(TenantDataKeys::WrappedKey)
+                            .binary()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(TenantDataKeys::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_tenant_data_keys_tenant_id")
+                            .from(TenantDataKeys::Table, TenantDataKeys::TenantId)
+                            .to(Tenants::Table, Tenants::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(TenantDataKeys::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum TenantDataKeys {
+    Table,
+    TenantId,
+    WrappedKey,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Tenants {
+    Table,
+    Id,
+}
@@ -0,0 +1,85 @@
+//! Migration to create the tenant_signal_kinds table.
+//!
+//! Tenants register namespaced custom signal kinds (`custom:press_mention`)
+//! here before signals of that kind are accepted. Canonical kinds are defined in
+//! code and never stored.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(TenantSignalKinds::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(TenantSignalKinds::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(TenantSignalKinds::TenantId)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(TenantSignalKinds::Kind).text().not_null())
+                    .col(ColumnDef::new(TenantSignalKinds::Description).text().null())
+                    .col(
+                        ColumnDef::new(TenantSignalKinds::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_tenant_signal_kinds_tenant_id")
+                            .from(TenantSignalKinds::Table, TenantSignalKinds::TenantId)
+                            .to(Tenants::Table, Tenants::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_tenant_signal_kinds_tenant_kind")
+                    .table(TenantSignalKinds::Table)
+                    .col(TenantSignalKinds::TenantId)
+                    .col(TenantSignalKinds::Kind)
+                    .unique()
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(TenantSignalKinds::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum TenantSignalKinds {
+    Table,
+    Id,
+    TenantId,
+    Kind,
+    Description,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Tenants {
+    Table,
+    Id,
+}
@@ -0,0 +1,98 @@
+//! Migration to create the signal_pipeline_events table.
+//!
+//! Pipeline stages that leave no other record (such as which sync job persisted a
+//! signal) append an event here, so the signal trace endpoint can show where a
+//! signal came from and what happened to it.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(SignalPipelineEvents::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(SignalPipelineEvents::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(SignalPipelineEvents::TenantId)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(SignalPipelineEvents::SignalId)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(SignalPipelineEvents::Stage)
+                            .text()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(SignalPipelineEvents::Detail)
+                            .json_binary()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(SignalPipelineEvents::OccurredAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_signal_pipeline_events_signal_id")
+                            .from(SignalPipelineEvents::Table, SignalPipelineEvents::SignalId)
+                            .to(Signals::Table, Signals::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_signal_pipeline_events_signal_id")
+                    .table(SignalPipelineEvents::Table)
+                    .col(SignalPipelineEvents::SignalId)
+                    .col(SignalPipelineEvents::OccurredAt)
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(SignalPipelineEvents::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum SignalPipelineEvents {
+    Table,
+    Id,
+    TenantId,
+    SignalId,
+    Stage,
+    Detail,
+    OccurredAt,
+}
+
+#[derive(DeriveIden)]
+enum Signals {
+    Table,
+    Id,
+}
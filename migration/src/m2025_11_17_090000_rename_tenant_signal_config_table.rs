@@ -0,0 +1,53 @@
+//! Migration renaming `tenant_signal_config` to `tenant_signal_configs`.
+//!
+//! The original migration derived the table name from a singular identifier,
+//! while the entity maps to `tenant_signal_configs`, so signal config reads and
+//! writes failed against migrated databases.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        if manager.has_table("tenant_signal_configs").await?
+            || !manager.has_table("tenant_signal_config").await?
+        {
+            return Ok(());
+        }
+
+        manager
+            .rename_table(
+                Table::rename()
+                    .table(TenantSignalConfig::Table, TenantSignalConfigs::Table)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        if !manager.has_table("tenant_signal_configs").await? {
+            return Ok(());
+        }
+
+        manager
+            .rename_table(
+                Table::rename()
+                    .table(TenantSignalConfigs::Table, TenantSignalConfig::Table)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum TenantSignalConfig {
+    Table,
+}
+
+#[derive(DeriveIden)]
+enum TenantSignalConfigs {
+    Table,
+}
@@ -0,0 +1,143 @@
+//! Migration to create the organizations table.
+//!
+//! An organization groups tenants and holds default settings they inherit.
+//! Tenants gain an optional `organization_id` and a `settings` column for their
+//! own overrides; both are nullable so existing tenants keep service defaults.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Organizations::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Organizations::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Organizations::Name).text().not_null())
+                    .col(ColumnDef::new(Organizations::Settings).json_binary().null())
+                    .col(
+                        ColumnDef::new(Organizations::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(Organizations::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        for column in [
+            ColumnDef::new(Tenants::OrganizationId)
+                .uuid()
+                .null()
+                .to_owned(),
+            ColumnDef::new(Tenants::Settings)
+                .json_binary()
+                .null()
+                .to_owned(),
+        ] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Tenants::Table)
+                        .add_column(column)
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        // SQLite cannot add a foreign key to an existing table
+        if manager.get_database_backend() != sea_orm::DatabaseBackend::Sqlite {
+            manager
+                .create_foreign_key(
+                    ForeignKey::create()
+                        .name("fk_tenants_organization_id")
+                        .from(Tenants::Table, Tenants::OrganizationId)
+                        .to(Organizations::Table, Organizations::Id)
+                        .on_delete(ForeignKeyAction::SetNull)
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_tenants_organization_id")
+                    .table(Tenants::Table)
+                    .col(Tenants::OrganizationId)
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_tenants_organization_id")
+                    .table(Tenants::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        if manager.get_database_backend() != sea_orm::DatabaseBackend::Sqlite {
+            manager
+                .drop_foreign_key(
+                    ForeignKey::drop()
+                        .name("fk_tenants_organization_id")
+                        .table(Tenants::Table)
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        for column in [Tenants::OrganizationId, Tenants::Settings] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Tenants::Table)
+                        .drop_column(column)
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        manager
+            .drop_table(Table::drop().table(Organizations::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Organizations {
+    Table,
+    Id,
+    Name,
+    Settings,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Tenants {
+    Table,
+    OrganizationId,
+    Settings,
+}
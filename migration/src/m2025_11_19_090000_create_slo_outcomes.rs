@@ -0,0 +1,91 @@
+//! Migration to create the slo_outcomes table.
+//!
+//! Each row counts attempts and failures of one tracked operation for a provider
+//! within one UTC hour. The API server and the sync executor both increment it,
+//! so the SLO report sees outcomes from every process.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(SloOutcomes::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(SloOutcomes::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(SloOutcomes::ProviderSlug).text().not_null())
+                    .col(ColumnDef::new(SloOutcomes::Operation).text().not_null())
+                    .col(
+                        ColumnDef::new(SloOutcomes::HourStart)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(SloOutcomes::Total)
+                            .big_integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(SloOutcomes::Failed)
+                            .big_integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_slo_outcomes_provider_operation_hour")
+                    .table(SloOutcomes::Table)
+                    .col(SloOutcomes::ProviderSlug)
+                    .col(SloOutcomes::Operation)
+                    .col(SloOutcomes::HourStart)
+                    .unique()
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_slo_outcomes_hour_start")
+                    .table(SloOutcomes::Table)
+                    .col(SloOutcomes::HourStart)
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(SloOutcomes::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum SloOutcomes {
+    Table,
+    Id,
+    ProviderSlug,
+    Operation,
+    HourStart,
+    Total,
+    Failed,
+}
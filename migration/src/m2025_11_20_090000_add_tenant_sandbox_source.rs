@@ -0,0 +1,87 @@
+//! Migration to mark sandbox tenants.
+//!
+//! A sandbox tenant is a copy of another tenant's configuration used to tune
+//! scoring without touching production recommendations. `sandbox_of` points at
+//! the tenant it was cloned from and is null for every regular tenant.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Tenants::Table)
+                    .add_column(ColumnDef::new(Tenants::SandboxOf).uuid().null())
+                    .to_owned(),
+            )
+            .await?;
+
+        // SQLite cannot add a foreign key to an existing table
+        if manager.get_database_backend() != sea_orm::DatabaseBackend::Sqlite {
+            manager
+                .create_foreign_key(
+                    ForeignKey::create()
+                        .name("fk_tenants_sandbox_of")
+                        .from(Tenants::Table, Tenants::SandboxOf)
+                        .to(Tenants::Table, Tenants::Id)
+                        .on_delete(ForeignKeyAction::Cascade)
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_tenants_sandbox_of")
+                    .table(Tenants::Table)
+                    .col(Tenants::SandboxOf)
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_tenants_sandbox_of")
+                    .table(Tenants::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        if manager.get_database_backend() != sea_orm::DatabaseBackend::Sqlite {
+            manager
+                .drop_foreign_key(
+                    ForeignKey::drop()
+                        .name("fk_tenants_sandbox_of")
+                        .table(Tenants::Table)
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Tenants::Table)
+                    .drop_column(Tenants::SandboxOf)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Tenants {
+    Table,
+    Id,
+    SandboxOf,
+}
@@ -0,0 +1,45 @@
+//! Migration to store each tenant's outbound webhook signing secret.
+//!
+//! Deliveries to a tenant's notification webhook are signed with this secret so
+//! the receiver can authenticate them. It is generated on first use and null
+//! until then.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(TenantSignalConfigs::Table)
+                    .add_column(
+                        ColumnDef::new(TenantSignalConfigs::WebhookSigningSecret)
+                            .text()
+                            .null(),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(TenantSignalConfigs::Table)
+                    .drop_column(TenantSignalConfigs::WebhookSigningSecret)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum TenantSignalConfigs {
+    Table,
+    WebhookSigningSecret,
+}
@@ -0,0 +1,90 @@
+//! Migration to add a lease to sync jobs.
+//!
+//! An executor sets `locked_at` and `heartbeat_at` when it claims a job and
+//! keeps renewing `heartbeat_at` while the job runs. A running job whose
+//! heartbeat is older than the lease timeout belongs to a crashed worker and is
+//! reclaimed by the executor's reaper.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // SQLite adds one column per ALTER TABLE
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(SyncJobs::Table)
+                    .add_column(
+                        ColumnDef::new(SyncJobs::LockedAt)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(SyncJobs::Table)
+                    .add_column(
+                        ColumnDef::new(SyncJobs::HeartbeatAt)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_sync_jobs_status_heartbeat_at")
+                    .table(SyncJobs::Table)
+                    .col(SyncJobs::Status)
+                    .col(SyncJobs::HeartbeatAt)
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_sync_jobs_status_heartbeat_at")
+                    .table(SyncJobs::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(SyncJobs::Table)
+                    .drop_column(SyncJobs::HeartbeatAt)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(SyncJobs::Table)
+                    .drop_column(SyncJobs::LockedAt)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum SyncJobs {
+    Table,
+    Status,
+    LockedAt,
+    HeartbeatAt,
+}
@@ -0,0 +1,41 @@
+//! Migration to record which executor instance holds a sync job's lease.
+//!
+//! Several `sync-executor` processes may claim from the same queue; `locked_by`
+//! names the instance that claimed a job so only that instance renews its lease
+//! or finishes it.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(SyncJobs::Table)
+                    .add_column(ColumnDef::new(SyncJobs::LockedBy).text().null())
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(SyncJobs::Table)
+                    .drop_column(SyncJobs::LockedBy)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum SyncJobs {
+    Table,
+    LockedBy,
+}
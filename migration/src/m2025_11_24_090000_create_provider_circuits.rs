@@ -0,0 +1,68 @@
+//! Migration to create the provider_circuits table.
+//!
+//! Each row holds the circuit breaker state of one provider. Every executor
+//! instance reads it when claiming jobs, so a provider that keeps failing is
+//! paused across the whole fleet rather than per process.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(ProviderCircuits::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ProviderCircuits::ProviderSlug)
+                            .text()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(ProviderCircuits::State)
+                            .text()
+                            .not_null()
+                            .default("closed"),
+                    )
+                    .col(
+                        ColumnDef::new(ProviderCircuits::ConsecutiveFailures)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(ColumnDef::new(ProviderCircuits::OpenedAt).timestamp_with_time_zone())
+                    .col(ColumnDef::new(ProviderCircuits::OpenUntil).timestamp_with_time_zone())
+                    .col(ColumnDef::new(ProviderCircuits::LastError).text())
+                    .col(
+                        ColumnDef::new(ProviderCircuits::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ProviderCircuits::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum ProviderCircuits {
+    Table,
+    ProviderSlug,
+    State,
+    ConsecutiveFailures,
+    OpenedAt,
+    OpenUntil,
+    LastError,
+    UpdatedAt,
+}
@@ -0,0 +1,45 @@
+//! Migration to count rate-limited attempts of a sync job separately.
+//!
+//! Rate limits are retried on their own budget, so a job that keeps hitting a
+//! provider's quota does not use up the attempts it has for other failures.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(SyncJobs::Table)
+                    .add_column(
+                        ColumnDef::new(SyncJobs::RateLimitedAttempts)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(SyncJobs::Table)
+                    .drop_column(SyncJobs::RateLimitedAttempts)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum SyncJobs {
+    Table,
+    RateLimitedAttempts,
+}
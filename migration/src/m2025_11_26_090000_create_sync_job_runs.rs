@@ -0,0 +1,100 @@
+//! Migration to create the sync_job_runs table.
+//!
+//! A sync job row only keeps its latest state, so retries overwrite the error of
+//! earlier attempts. Each executed run appends a row here with its timing, the
+//! items it persisted and how it ended.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(SyncJobRuns::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(SyncJobRuns::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(SyncJobRuns::TenantId).uuid().not_null())
+                    .col(ColumnDef::new(SyncJobRuns::ConnectionId).uuid().not_null())
+                    .col(ColumnDef::new(SyncJobRuns::JobId).uuid().not_null())
+                    .col(ColumnDef::new(SyncJobRuns::JobType).text().not_null())
+                    .col(ColumnDef::new(SyncJobRuns::Attempt).integer().not_null())
+                    .col(
+                        ColumnDef::new(SyncJobRuns::StartedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(SyncJobRuns::FinishedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(SyncJobRuns::ItemsProcessed)
+                            .big_integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(ColumnDef::new(SyncJobRuns::Outcome).text().not_null())
+                    .col(ColumnDef::new(SyncJobRuns::Error).text().null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_sync_job_runs_job_id")
+                            .from(SyncJobRuns::Table, SyncJobRuns::JobId)
+                            .to(SyncJobs::Table, SyncJobs::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_sync_job_runs_connection_started")
+                    .table(SyncJobRuns::Table)
+                    .col(SyncJobRuns::ConnectionId)
+                    .col(SyncJobRuns::StartedAt)
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(SyncJobRuns::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum SyncJobRuns {
+    Table,
+    Id,
+    TenantId,
+    ConnectionId,
+    JobId,
+    JobType,
+    Attempt,
+    StartedAt,
+    FinishedAt,
+    ItemsProcessed,
+    Outcome,
+    Error,
+}
+
+#[derive(DeriveIden)]
+enum SyncJobs {
+    Table,
+    Id,
+}
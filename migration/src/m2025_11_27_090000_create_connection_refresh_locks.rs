@@ -0,0 +1,87 @@
+//! Migration to create the connection_refresh_locks table.
+//!
+//! Each row guards the token refresh of one connection. A worker takes the lock
+//! with a conditional update before calling the provider, so the background
+//! refresher and executor instances never refresh the same token at once. The
+//! row also keeps the failure streak and the time the next attempt is allowed.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(ConnectionRefreshLocks::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ConnectionRefreshLocks::ConnectionId)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(ConnectionRefreshLocks::LockedBy).text())
+                    .col(
+                        ColumnDef::new(ConnectionRefreshLocks::LockedUntil)
+                            .timestamp_with_time_zone(),
+                    )
+                    .col(
+                        ColumnDef::new(ConnectionRefreshLocks::ConsecutiveFailures)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(ColumnDef::new(ConnectionRefreshLocks::RetryAt).timestamp_with_time_zone())
+                    .col(ColumnDef::new(ConnectionRefreshLocks::LastError).text())
+                    .col(
+                        ColumnDef::new(ConnectionRefreshLocks::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_connection_refresh_locks_connection_id")
+                            .from(
+                                ConnectionRefreshLocks::Table,
+                                ConnectionRefreshLocks::ConnectionId,
+                            )
+                            .to(Connections::Table, Connections::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .table(ConnectionRefreshLocks::Table)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum ConnectionRefreshLocks {
+    Table,
+    ConnectionId,
+    LockedBy,
+    LockedUntil,
+    ConsecutiveFailures,
+    RetryAt,
+    LastError,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Connections {
+    Table,
+    Id,
+}
@@ -0,0 +1,95 @@
+//! Migration to create the token_events table.
+//!
+//! Connections only keep their current tokens and expiry, so once a refresh
+//! rotated or failed there is no trace of what happened before. Every refresh
+//! attempt appends a row here with the expiry before and after, whether the
+//! provider rotated the refresh token, and how the attempt ended.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(TokenEvents::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(TokenEvents::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(TokenEvents::TenantId).uuid().not_null())
+                    .col(ColumnDef::new(TokenEvents::ConnectionId).uuid().not_null())
+                    .col(ColumnDef::new(TokenEvents::ProviderSlug).text().not_null())
+                    .col(ColumnDef::new(TokenEvents::PreviousExpiresAt).timestamp_with_time_zone())
+                    .col(ColumnDef::new(TokenEvents::NewExpiresAt).timestamp_with_time_zone())
+                    .col(
+                        ColumnDef::new(TokenEvents::RefreshTokenRotated)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .col(ColumnDef::new(TokenEvents::Outcome).text().not_null())
+                    .col(ColumnDef::new(TokenEvents::Error).text().null())
+                    .col(
+                        ColumnDef::new(TokenEvents::OccurredAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_token_events_connection_id")
+                            .from(TokenEvents::Table, TokenEvents::ConnectionId)
+                            .to(Connections::Table, Connections::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_token_events_connection_occurred")
+                    .table(TokenEvents::Table)
+                    .col(TokenEvents::ConnectionId)
+                    .col(TokenEvents::OccurredAt)
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(TokenEvents::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum TokenEvents {
+    Table,
+    Id,
+    TenantId,
+    ConnectionId,
+    ProviderSlug,
+    PreviousExpiresAt,
+    NewExpiresAt,
+    RefreshTokenRotated,
+    Outcome,
+    Error,
+    OccurredAt,
+}
+
+#[derive(DeriveIden)]
+enum Connections {
+    Table,
+    Id,
+}
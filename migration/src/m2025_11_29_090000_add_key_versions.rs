@@ -0,0 +1,84 @@
+//! Migration to record which master key version protects each ciphertext.
+//!
+//! Tenant data keys remember the master key version that wrapped them, and each
+//! token ciphertext remembers the version in force when it was written, so a
+//! rotation can find what still depends on a retired version. Existing data
+//! keys were wrapped by the only key there was, version 1; existing token
+//! ciphertexts predate versioning and are left NULL.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(TenantDataKeys::Table)
+                    .add_column(
+                        ColumnDef::new(TenantDataKeys::KeyVersion)
+                            .integer()
+                            .not_null()
+                            .default(1),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        for column in [
+            ConnectionSecrets::AccessTokenKeyVersion,
+            ConnectionSecrets::RefreshTokenKeyVersion,
+        ] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(ConnectionSecrets::Table)
+                        .add_column(ColumnDef::new(column).integer().null())
+                        .to_owned(),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for column in [
+            ConnectionSecrets::RefreshTokenKeyVersion,
+            ConnectionSecrets::AccessTokenKeyVersion,
+        ] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(ConnectionSecrets::Table)
+                        .drop_column(column)
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(TenantDataKeys::Table)
+                    .drop_column(TenantDataKeys::KeyVersion)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum TenantDataKeys {
+    Table,
+    KeyVersion,
+}
+
+#[derive(DeriveIden, Clone, Copy)]
+enum ConnectionSecrets {
+    Table,
+    AccessTokenKeyVersion,
+    RefreshTokenKeyVersion,
+}
@@ -0,0 +1,101 @@
+//! Migration to create the tenant_configs table.
+//!
+//! Holds one row per tenant with the runtime settings an operator overrides
+//! for that tenant alone: sync intervals, the mail spam threshold, rate limit
+//! overrides and notification URLs. Columns left NULL inherit the value from
+//! the tenant's settings, its organization or the service configuration.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(TenantConfigs::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(TenantConfigs::TenantId)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(TenantConfigs::SyncIntervalSeconds)
+                            .big_integer()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(TenantConfigs::ProviderSyncIntervals)
+                            .json_binary()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(TenantConfigs::MailSpamThreshold)
+                            .float()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(TenantConfigs::RateLimitOverrides)
+                            .json_binary()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(TenantConfigs::NotificationUrls)
+                            .json_binary()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(TenantConfigs::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(TenantConfigs::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_tenant_configs_tenant_id")
+                            .from(TenantConfigs::Table, TenantConfigs::TenantId)
+                            .to(Tenants::Table, Tenants::Id)
+                            .on_update(ForeignKeyAction::Cascade)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(TenantConfigs::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum TenantConfigs {
+    Table,
+    TenantId,
+    SyncIntervalSeconds,
+    ProviderSyncIntervals,
+    MailSpamThreshold,
+    RateLimitOverrides,
+    NotificationUrls,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Tenants {
+    Table,
+    Id,
+}
@@ -7,10 +7,15 @@ use std::sync::Arc;
 
 use axum::{
     extract::{FromRef, FromRequestParts, Request, State},
-    http::{HeaderMap, header::AUTHORIZATION, request::Parts},
+    http::{
+        HeaderMap, HeaderValue,
+        header::{AUTHORIZATION, WWW_AUTHENTICATE},
+        request::Parts,
+    },
     middleware::Next,
-    response::Response,
+    response::{IntoResponse, Response},
 };
+use base64::{Engine as _, engine::general_purpose::STANDARD};
 use serde::{Deserialize, Serialize};
 use subtle::ConstantTimeEq;
 use utoipa::IntoParams;
@@ -73,6 +78,73 @@ pub async fn auth_middleware(
     Ok(next.run(request).await)
 }
 
+/// Challenge sent to browsers so they prompt for admin dashboard credentials
+const ADMIN_AUTH_CHALLENGE: &str = "Basic realm=\"Poblysh Connectors Admin\", charset=\"UTF-8\"";
+
+/// Authentication middleware for the embedded admin dashboard.
+///
+/// Accepts an operator token either as a bearer token or as the password of HTTP
+/// Basic credentials, so the dashboard can be opened through the browser's native
+/// login prompt. The tenant header is optional; when present it scopes the
+/// tenant-level API calls made by the dashboard.
+pub async fn admin_auth_middleware(
+    State(config): State<Arc<AppConfig>>,
+    request: Request,
+    next: Next,
+) -> Result<Response, ApiError> {
+    if let Err(error) = validate_admin_credentials(&config, request.headers()) {
+        let mut response = error.into_response();
+        response.headers_mut().insert(
+            WWW_AUTHENTICATE,
+            HeaderValue::from_static(ADMIN_AUTH_CHALLENGE),
+        );
+        return Ok(response);
+    }
+
+    let tenant = if request.headers().contains_key("X-Tenant-Id") {
+        Some(extract_tenant_id_with_trace_id(request.headers(), None)?)
+    } else {
+        None
+    };
+
+    let mut request = request;
+    if let Some(tenant) = tenant {
+        request.extensions_mut().insert(TenantExtension(tenant));
+    }
+    request.extensions_mut().insert(OperatorAuth);
+
+    Ok(next.run(request).await)
+}
+
+fn validate_admin_credentials(config: &AppConfig, headers: &HeaderMap) -> Result<(), ApiError> {
+    let header = headers
+        .get(AUTHORIZATION)
+        .ok_or_else(|| unauthorized(Some("Missing Authorization header")))?
+        .to_str()
+        .map_err(|_| unauthorized(Some("Invalid Authorization header")))?;
+
+    if let Some(token) = header.strip_prefix("Bearer ") {
+        return validate_token(config, token);
+    }
+
+    if let Some(encoded) = header.strip_prefix("Basic ") {
+        // The username is ignored; the password carries the operator token
+        let credentials = STANDARD
+            .decode(encoded.trim())
+            .ok()
+            .and_then(|decoded| String::from_utf8(decoded).ok())
+            .ok_or_else(|| unauthorized(Some("Invalid Basic credentials")))?;
+        let (_, token) = credentials
+            .split_once(':')
+            .ok_or_else(|| unauthorized(Some("Invalid Basic credentials")))?;
+        return validate_token(config, token);
+    }
+
+    Err(unauthorized(Some(
+        "Authorization header must use Bearer or Basic scheme",
+    )))
+}
+
 fn extract_bearer_token_with_trace_id(
     headers: &HeaderMap,
     trace_id: Option<String>,
@@ -234,33 +306,34 @@ mod tests {
                 Arc::clone(&config),
                 auth_middleware,
             ))
-            .with_state({
-                let db = sea_orm::DatabaseConnection::default();
-                let crypto_key = crate::crypto::CryptoKey::new(vec![0u8; 32])
-                    .expect("Failed to create test crypto key");
-
-                // Create required dependencies for TokenRefreshService
-                let connection_repo = crate::repositories::ConnectionRepository::new(
-                    std::sync::Arc::new(db.clone()),
-                    crypto_key.clone(),
-                );
-
-                // Create TokenRefreshService
-                let token_refresh_service =
-                    std::sync::Arc::new(crate::token_refresh::TokenRefreshService::new(
-                        config.clone(),
-                        std::sync::Arc::new(db.clone()),
-                        std::sync::Arc::new(connection_repo),
-                        crate::connectors::registry::Registry::new(),
-                    ));
-
-                AppState {
-                    config,
-                    db,
-                    crypto_key,
-                    token_refresh_service,
-                }
-            })
+            .with_state(crate::server::create_test_app_state(
+                (*config).clone(),
+                sea_orm::DatabaseConnection::default(),
+            ))
+            .oneshot(request)
+            .await
+            .unwrap()
+    }
+
+    async fn run_admin_middleware(config: Arc<AppConfig>, request: Request<Body>) -> Response {
+        async fn handler(_operator: OperatorAuth, request: Request<Body>) -> String {
+            request
+                .extensions()
+                .get::<TenantExtension>()
+                .map(|TenantExtension(tenant)| tenant.0.to_string())
+                .unwrap_or_default()
+        }
+
+        Router::new()
+            .route("/test", get(handler))
+            .layer(axum::middleware::from_fn_with_state(
+                Arc::clone(&config),
+                admin_auth_middleware,
+            ))
+            .with_state(crate::server::create_test_app_state(
+                (*config).clone(),
+                sea_orm::DatabaseConnection::default(),
+            ))
             .oneshot(request)
             .await
             .unwrap()
@@ -371,4 +444,56 @@ mod tests {
             assert_eq!(response.status(), StatusCode::OK);
         }
     }
+
+    #[tokio::test]
+    async fn admin_missing_auth_prompts_for_basic_credentials() {
+        let config = create_test_config();
+        let request = Request::builder().uri("/test").body(Body::empty()).unwrap();
+
+        let response = run_admin_middleware(config, request).await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        let challenge = response.headers().get(WWW_AUTHENTICATE).unwrap();
+        assert!(challenge.to_str().unwrap().starts_with("Basic "));
+    }
+
+    #[tokio::test]
+    async fn admin_accepts_operator_token_as_basic_password() {
+        let config = create_test_config();
+        let valid = format!("Basic {}", STANDARD.encode("operator:test-token-123"));
+        let request = Request::builder()
+            .uri("/test")
+            .header("Authorization", valid)
+            .body(Body::empty())
+            .unwrap();
+        let response = run_admin_middleware(Arc::clone(&config), request).await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let invalid = format!("Basic {}", STANDARD.encode("operator:wrong-token"));
+        let request = Request::builder()
+            .uri("/test")
+            .header("Authorization", invalid)
+            .body(Body::empty())
+            .unwrap();
+        let response = run_admin_middleware(config, request).await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn admin_tenant_header_is_optional() {
+        let config = create_test_config();
+        let tenant_id = Uuid::new_v4();
+        let request = Request::builder()
+            .uri("/test")
+            .header("Authorization", "Bearer test-token-123")
+            .header("X-Tenant-Id", tenant_id.to_string())
+            .body(Body::empty())
+            .unwrap();
+
+        let response = run_admin_middleware(config, request).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(body, tenant_id.to_string().as_bytes());
+    }
 }
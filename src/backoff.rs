@@ -0,0 +1,195 @@
+//! Backoff and jitter policies shared by background workers and connectors.
+//!
+//! Retry loops describe their schedule with an [`ExponentialBackoff`] and
+//! spread periodic work with a [`JitterRange`]. Both sample randomness through
+//! [`with_rng`], which tests can make reproducible with [`set_seed`].
+
+use std::sync::Mutex;
+use std::time::Duration;
+
+use rand::rngs::StdRng;
+use rand::{Rng, RngCore, SeedableRng};
+
+/// Seeded generator used instead of the thread RNG while deterministic mode is on
+static SEEDED_RNG: Mutex<Option<StdRng>> = Mutex::new(None);
+
+/// Make every jitter sample reproducible from `seed`, or restore real randomness with `None`.
+///
+/// Intended for tests; the seed is process-wide.
+pub fn set_seed(seed: Option<u64>) {
+    let mut rng = SEEDED_RNG.lock().unwrap_or_else(|e| e.into_inner());
+    *rng = seed.map(StdRng::seed_from_u64);
+}
+
+/// Run `f` with the seeded generator when deterministic mode is on, else the thread RNG.
+pub fn with_rng<T>(f: impl FnOnce(&mut dyn RngCore) -> T) -> T {
+    let mut seeded = SEEDED_RNG.lock().unwrap_or_else(|e| e.into_inner());
+    match seeded.as_mut() {
+        Some(rng) => f(rng),
+        None => {
+            drop(seeded);
+            f(&mut rand::thread_rng())
+        }
+    }
+}
+
+/// How randomness is applied on top of a computed delay
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Jitter {
+    /// Use the delay as-is
+    None,
+    /// Add up to `factor * delay`, so retries never fire earlier than scheduled
+    Additive(f64),
+    /// Scale the delay by a factor drawn from `[1 - factor, 1 + factor]`
+    Proportional(f64),
+}
+
+impl Jitter {
+    /// Apply this jitter to `delay` using `rng`
+    pub fn apply<R: Rng + ?Sized>(self, delay: Duration, rng: &mut R) -> Duration {
+        let secs = delay.as_secs_f64();
+        let jittered = match self {
+            Jitter::None => return delay,
+            Jitter::Additive(factor) if factor > 0.0 && secs > 0.0 => {
+                secs + rng.gen_range(0.0..factor * secs)
+            }
+            Jitter::Proportional(factor) if factor > 0.0 => {
+                secs * rng.gen_range(1.0 - factor..=1.0 + factor)
+            }
+            _ => return delay,
+        };
+        Duration::from_secs_f64(jittered.max(0.0))
+    }
+}
+
+/// Doubling delay schedule with an upper bound and optional jitter
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ExponentialBackoff {
+    /// Delay before the first retry
+    pub base: Duration,
+    /// Upper bound for the un-jittered delay
+    pub max: Duration,
+    /// Randomness applied to each delay
+    pub jitter: Jitter,
+}
+
+impl ExponentialBackoff {
+    /// Create a schedule without jitter
+    pub const fn new(base: Duration, max: Duration) -> Self {
+        Self {
+            base,
+            max,
+            jitter: Jitter::None,
+        }
+    }
+
+    /// Replace the jitter applied to each delay
+    pub const fn with_jitter(mut self, jitter: Jitter) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Un-jittered delay for a zero-based retry number: `min(base * 2^retry, max)`
+    pub fn delay(&self, retry: u32) -> Duration {
+        let factor = 2_u32.checked_pow(retry).unwrap_or(u32::MAX);
+        self.base.saturating_mul(factor).min(self.max)
+    }
+
+    /// Jittered delay for a zero-based retry number
+    pub fn sample(&self, retry: u32) -> Duration {
+        self.sample_at_least(retry, Duration::ZERO)
+    }
+
+    /// Jittered delay that is never shorter than `floor` (e.g. a provider's Retry-After)
+    pub fn sample_at_least(&self, retry: u32, floor: Duration) -> Duration {
+        let delay = self.delay(retry).max(floor);
+        with_rng(|rng| self.jitter.apply(delay, rng))
+    }
+}
+
+/// Random offset expressed as a fraction of a base interval
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct JitterRange {
+    /// Smallest fraction of the base interval (0.0-1.0)
+    pub min_pct: f64,
+    /// Largest fraction of the base interval (0.0-1.0)
+    pub max_pct: f64,
+}
+
+impl JitterRange {
+    /// Offset between zero and `max_pct` of the base interval
+    pub const fn up_to(max_pct: f64) -> Self {
+        Self {
+            min_pct: 0.0,
+            max_pct,
+        }
+    }
+
+    /// Offset in whole seconds for the given base interval, drawn from `rng`
+    pub fn offset_seconds_with<R: Rng + ?Sized>(&self, base_seconds: u64, rng: &mut R) -> u64 {
+        let min = self.min_pct.max(0.0);
+        let max = self.max_pct.max(min);
+
+        if min == 0.0 && max == 0.0 {
+            return 0;
+        }
+
+        let pct = if (max - min).abs() < f64::EPSILON {
+            min
+        } else {
+            rng.gen_range(min..=max)
+        };
+
+        (base_seconds as f64 * pct).round() as u64
+    }
+
+    /// Offset in whole seconds for the given base interval
+    pub fn offset_seconds(&self, base_seconds: u64) -> u64 {
+        with_rng(|rng| self.offset_seconds_with(base_seconds, rng))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_delay_doubles_and_caps() {
+        let backoff = ExponentialBackoff::new(Duration::from_secs(5), Duration::from_secs(60));
+        assert_eq!(backoff.delay(0), Duration::from_secs(5));
+        assert_eq!(backoff.delay(1), Duration::from_secs(10));
+        assert_eq!(backoff.delay(3), Duration::from_secs(40));
+        assert_eq!(backoff.delay(4), Duration::from_secs(60));
+        assert_eq!(backoff.delay(64), Duration::from_secs(60));
+    }
+
+    #[test]
+    fn test_jitter_bounds() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let delay = Duration::from_secs(10);
+        for _ in 0..100 {
+            let additive = Jitter::Additive(0.1).apply(delay, &mut rng);
+            assert!(additive >= delay && additive < Duration::from_secs(11));
+
+            let proportional = Jitter::Proportional(0.25).apply(delay, &mut rng);
+            assert!(proportional >= Duration::from_millis(7500));
+            assert!(proportional <= Duration::from_millis(12500));
+        }
+        assert_eq!(Jitter::None.apply(delay, &mut rng), delay);
+    }
+
+    #[test]
+    fn test_seeded_mode_is_reproducible() {
+        let range = JitterRange::up_to(0.5);
+        let backoff = ExponentialBackoff::new(Duration::from_secs(1), Duration::from_secs(30))
+            .with_jitter(Jitter::Proportional(0.25));
+
+        set_seed(Some(42));
+        let first = (range.offset_seconds(3600), backoff.sample(2));
+        set_seed(Some(42));
+        let second = (range.offset_seconds(3600), backoff.sample(2));
+        set_seed(None);
+
+        assert_eq!(first, second);
+    }
+}
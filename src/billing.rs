@@ -0,0 +1,372 @@
+//! # Billing Events
+//!
+//! Background task that meters per-tenant usage (signals ingested, active
+//! connections, sync minutes) over fixed UTC periods and emits one billing event
+//! per tenant and metric to a [`BillingSink`].
+//!
+//! Event ids are derived from the tenant, metric and period, so re-sending a
+//! period after a crash or restart produces the same ids and the billing system
+//! can deduplicate. On startup the last few completed periods are re-sent to
+//! cover downtime.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use hmac::{Hmac, Mac};
+use sea_orm::DatabaseConnection;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use tokio::sync::broadcast;
+use tokio::time::{Duration as TokioDuration, sleep};
+use tokio_util::sync::CancellationToken;
+use tracing::{error, info, instrument};
+use uuid::Uuid;
+
+use crate::config::BillingConfig;
+use crate::error::RepositoryError;
+use crate::repositories::UsageRepository;
+
+/// Header carrying the HMAC-SHA256 signature of a batch
+pub const SIGNATURE_HEADER: &str = "X-Poblysh-Signature";
+
+/// Seconds between checks for a newly completed period
+const TICK_SECONDS: u64 = 60;
+
+/// Metered quantity reported to the billing system
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BillingMetric {
+    /// Signals received during the period
+    SignalsIngested,
+    /// Active connections at the end of the period
+    ConnectionsActive,
+    /// Sync job runtime during the period, in minutes
+    SyncMinutes,
+}
+
+impl BillingMetric {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BillingMetric::SignalsIngested => "signals_ingested",
+            BillingMetric::ConnectionsActive => "connections_active",
+            BillingMetric::SyncMinutes => "sync_minutes",
+        }
+    }
+
+    pub fn unit(&self) -> &'static str {
+        match self {
+            BillingMetric::SignalsIngested => "signal",
+            BillingMetric::ConnectionsActive => "connection",
+            BillingMetric::SyncMinutes => "minute",
+        }
+    }
+}
+
+/// One metered usage record
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BillingEvent {
+    /// Deterministic id; identical for every emission of the same tenant, metric and period
+    pub event_id: Uuid,
+    pub event_type: BillingMetric,
+    pub tenant_id: Uuid,
+    pub period_start: DateTime<Utc>,
+    pub period_end: DateTime<Utc>,
+    pub quantity: f64,
+    pub unit: &'static str,
+}
+
+impl BillingEvent {
+    fn new(
+        tenant_id: Uuid,
+        metric: BillingMetric,
+        period_start: DateTime<Utc>,
+        period_end: DateTime<Utc>,
+        quantity: f64,
+    ) -> Self {
+        Self {
+            event_id: event_id(tenant_id, metric, period_start),
+            event_type: metric,
+            tenant_id,
+            period_start,
+            period_end,
+            quantity,
+            unit: metric.unit(),
+        }
+    }
+}
+
+/// Stable event id for a tenant, metric and period
+pub fn event_id(tenant_id: Uuid, metric: BillingMetric, period_start: DateTime<Utc>) -> Uuid {
+    let digest = Sha256::new()
+        .chain_update(b"billing|")
+        .chain_update(tenant_id.as_bytes())
+        .chain_update(metric.as_str().as_bytes())
+        .chain_update(period_start.timestamp().to_be_bytes())
+        .finalize();
+    let mut bytes = [0u8; 16];
+    bytes.copy_from_slice(&digest[..16]);
+    uuid::Builder::from_custom_bytes(bytes).into_uuid()
+}
+
+/// Errors raised by billing sinks
+#[derive(Debug, thiserror::Error)]
+pub enum BillingError {
+    #[error("Repository error: {0}")]
+    Repository(#[from] RepositoryError),
+    #[error("Delivery failed: {0}")]
+    Delivery(String),
+}
+
+/// Destination for billing events
+#[async_trait]
+pub trait BillingSink: Send + Sync {
+    /// Deliver one period's events. Returning an error retries the period on the
+    /// next tick, so implementations must tolerate redelivery.
+    async fn send(&self, events: &[BillingEvent]) -> Result<(), BillingError>;
+}
+
+/// Posts event batches as JSON (`{"events": [...]}`) to the configured endpoint
+pub struct HttpBillingSink {
+    client: reqwest::Client,
+    endpoint_url: String,
+    signing_secret: Option<String>,
+}
+
+impl HttpBillingSink {
+    pub fn new(endpoint_url: String, signing_secret: Option<String>) -> Self {
+        Self {
+            client: crate::egress::client(),
+            endpoint_url,
+            signing_secret,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct BillingBatch<'a> {
+    events: &'a [BillingEvent],
+}
+
+#[async_trait]
+impl BillingSink for HttpBillingSink {
+    async fn send(&self, events: &[BillingEvent]) -> Result<(), BillingError> {
+        let body = serde_json::to_vec(&BillingBatch { events })
+            .map_err(|e| BillingError::Delivery(e.to_string()))?;
+
+        let mut request = self
+            .client
+            .post(&self.endpoint_url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json");
+        if let Some(secret) = &self.signing_secret {
+            request = request.header(SIGNATURE_HEADER, sign(secret, &body));
+        }
+
+        let response = request
+            .body(body)
+            .send()
+            .await
+            .map_err(|e| BillingError::Delivery(e.to_string()))?;
+        if !response.status().is_success() {
+            return Err(BillingError::Delivery(format!(
+                "billing endpoint returned {}",
+                response.status()
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// `sha256=<hex>` HMAC of the request body
+pub fn sign(secret: &str, body: &[u8]) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts any key length");
+    mac.update(body);
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+/// In-process broadcast bus for billing events
+#[derive(Clone)]
+pub struct BillingEventBus {
+    sender: broadcast::Sender<BillingEvent>,
+}
+
+impl BillingEventBus {
+    /// Create a new event bus retaining up to `capacity` events per lagging subscriber
+    pub fn new(capacity: usize) -> Self {
+        let (sender, _) = broadcast::channel(capacity.max(1));
+        Self { sender }
+    }
+
+    /// Subscribe to billing events
+    pub fn subscribe(&self) -> broadcast::Receiver<BillingEvent> {
+        self.sender.subscribe()
+    }
+}
+
+impl Default for BillingEventBus {
+    fn default() -> Self {
+        Self::new(1024)
+    }
+}
+
+#[async_trait]
+impl BillingSink for BillingEventBus {
+    async fn send(&self, events: &[BillingEvent]) -> Result<(), BillingError> {
+        for event in events {
+            let _ = self.sender.send(event.clone());
+        }
+        Ok(())
+    }
+}
+
+/// Background service emitting billing events once per completed period
+pub struct BillingService {
+    db: Arc<DatabaseConnection>,
+    sink: Arc<dyn BillingSink>,
+    config: BillingConfig,
+}
+
+impl BillingService {
+    /// Create a new billing service
+    pub fn new(
+        db: Arc<DatabaseConnection>,
+        sink: Arc<dyn BillingSink>,
+        config: BillingConfig,
+    ) -> Self {
+        Self { db, sink, config }
+    }
+
+    /// Run until the shutdown token fires, emitting each period once it completes.
+    #[instrument(skip_all)]
+    pub async fn run(self, shutdown: CancellationToken) -> Result<(), BillingError> {
+        info!(
+            period_seconds = self.config.period_seconds,
+            "Starting billing event service"
+        );
+        let period = Duration::seconds(self.config.period_seconds as i64);
+        let mut next_period =
+            period_start(Utc::now(), period) - period * self.config.lookback_periods as i32;
+
+        loop {
+            let current = period_start(Utc::now(), period);
+            while next_period < current {
+                match self.emit_period(next_period).await {
+                    Ok(events) => {
+                        info!(period_start = %next_period, events, "Billing events emitted");
+                        next_period += period;
+                    }
+                    Err(err) => {
+                        error!(period_start = %next_period, error = ?err, "Billing emission failed");
+                        break;
+                    }
+                }
+            }
+
+            tokio::select! {
+                _ = shutdown.cancelled() => {
+                    info!("Billing event service shutdown requested");
+                    break;
+                }
+                _ = sleep(TokioDuration::from_secs(TICK_SECONDS)) => {}
+            }
+        }
+
+        info!("Billing event service stopped");
+        Ok(())
+    }
+
+    /// Compute and send the events for the period starting at `start`.
+    ///
+    /// Returns the number of events sent.
+    pub async fn emit_period(&self, start: DateTime<Utc>) -> Result<usize, BillingError> {
+        let end = start + Duration::seconds(self.config.period_seconds as i64);
+        let events = self.events_for_period(start, end).await?;
+        if !events.is_empty() {
+            self.sink.send(&events).await?;
+        }
+        Ok(events.len())
+    }
+
+    async fn events_for_period(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<BillingEvent>, BillingError> {
+        let usage = UsageRepository::new(self.db.as_ref().clone())
+            .tenant_usage(start, end)
+            .await?;
+
+        let mut events = Vec::new();
+        for (tenant_id, usage) in usage {
+            let quantities = [
+                (
+                    BillingMetric::SignalsIngested,
+                    usage.signals_ingested as f64,
+                ),
+                (
+                    BillingMetric::ConnectionsActive,
+                    usage.connections_active as f64,
+                ),
+                (
+                    BillingMetric::SyncMinutes,
+                    (usage.sync_seconds / 60.0 * 100.0).round() / 100.0,
+                ),
+            ];
+            for (metric, quantity) in quantities {
+                if quantity > 0.0 {
+                    events.push(BillingEvent::new(tenant_id, metric, start, end, quantity));
+                }
+            }
+        }
+        Ok(events)
+    }
+}
+
+/// Start of the period containing `at`; periods are aligned to the Unix epoch
+fn period_start(at: DateTime<Utc>, period: Duration) -> DateTime<Utc> {
+    let seconds = period.num_seconds();
+    let aligned = at.timestamp().div_euclid(seconds) * seconds;
+    DateTime::from_timestamp(aligned, 0).expect("aligned timestamp is in range")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_event_ids_are_stable_per_tenant_metric_and_period() {
+        let tenant = Uuid::new_v4();
+        let start = Utc.with_ymd_and_hms(2025, 1, 1, 10, 0, 0).unwrap();
+
+        let id = event_id(tenant, BillingMetric::SignalsIngested, start);
+        assert_eq!(id, event_id(tenant, BillingMetric::SignalsIngested, start));
+        assert_ne!(id, event_id(tenant, BillingMetric::SyncMinutes, start));
+        assert_ne!(
+            id,
+            event_id(
+                tenant,
+                BillingMetric::SignalsIngested,
+                start + Duration::hours(1)
+            )
+        );
+        assert_ne!(
+            id,
+            event_id(Uuid::new_v4(), BillingMetric::SignalsIngested, start)
+        );
+    }
+
+    #[test]
+    fn test_period_start_aligns_to_period() {
+        let at = Utc.with_ymd_and_hms(2025, 1, 1, 10, 42, 17).unwrap();
+        assert_eq!(
+            period_start(at, Duration::hours(1)),
+            Utc.with_ymd_and_hms(2025, 1, 1, 10, 0, 0).unwrap()
+        );
+        assert_eq!(
+            period_start(at, Duration::minutes(15)),
+            Utc.with_ymd_and_hms(2025, 1, 1, 10, 30, 0).unwrap()
+        );
+    }
+}
@@ -1,23 +1,26 @@
+use std::sync::Arc;
+
 use anyhow::{Context, Result, anyhow};
-use chrono::Utc;
 use connectors::{
     config::ConfigLoader,
-    crypto::{CryptoKey, encrypt_bytes, is_encrypted_payload},
+    crypto::{
+        decrypt_bytes, encrypt_bytes_with_record_key, is_encrypted_payload, load_master_keyring,
+        uses_tenant_key,
+    },
     db,
     models::connection,
+    repositories::{ConnectionSecretRepository, TenantKeyRepository},
 };
-use sea_orm::{ActiveModelTrait, EntityTrait, Set};
+use sea_orm::EntityTrait;
 
 #[tokio::main]
 async fn main() -> Result<()> {
     let loader = ConfigLoader::new();
     let config = loader.load().context("loading configuration")?;
 
-    let key_bytes = config
-        .crypto_key
-        .clone()
-        .context("crypto key not present in configuration")?;
-    let crypto_key = CryptoKey::new(key_bytes).context("initializing crypto key")?;
+    let master_keys = load_master_keyring(&config)
+        .await
+        .context("initializing crypto keys")?;
 
     let db = db::init_pool(&config)
         .await
@@ -27,6 +30,9 @@ async fn main() -> Result<()> {
         .all(&db)
         .await
         .context("querying connections")?;
+    let db = Arc::new(db);
+    let secrets = ConnectionSecretRepository::new(db.clone());
+    let tenant_keys = TenantKeyRepository::new(db, master_keys.clone());
 
     let mut updated_count = 0usize;
 
@@ -36,60 +42,78 @@ async fn main() -> Result<()> {
             "{}|{}|{}",
             conn.tenant_id, conn.provider_slug, conn.external_id
         );
+        let Some(secret) = secrets
+            .find(&connection_id)
+            .await
+            .with_context(|| format!("loading secrets for connection {}", connection_id))?
+        else {
+            continue;
+        };
 
-        let mut new_access_cipher = None;
-        if let Some(access) = conn.access_token_ciphertext.as_ref()
-            && !access.is_empty()
-            && !is_encrypted_payload(access)
+        // Legacy plaintext and master-key payloads move to the tenant's data key
+        let needs_migration = |token: &Option<Vec<u8>>| {
+            token
+                .as_ref()
+                .is_some_and(|token| !token.is_empty() && !uses_tenant_key(token))
+        };
+        if !needs_migration(&secret.access_token_ciphertext)
+            && !needs_migration(&secret.refresh_token_ciphertext)
         {
-            let ciphertext = encrypt_bytes(&crypto_key, aad.as_bytes(), access).map_err(|err| {
-                anyhow!(
-                    "failed to encrypt access token for {}: {}",
-                    connection_id,
-                    err
-                )
-            })?;
-            new_access_cipher = Some(ciphertext);
+            continue;
         }
+        let tenant_key = tenant_keys
+            .get_or_create(&conn.tenant_id)
+            .await
+            .with_context(|| format!("loading data key for tenant {}", conn.tenant_id))?;
 
-        let mut new_refresh_cipher = None;
-        if let Some(refresh) = conn.refresh_token_ciphertext.as_ref()
-            && !refresh.is_empty()
-            && !is_encrypted_payload(refresh)
-        {
-            let ciphertext =
-                encrypt_bytes(&crypto_key, aad.as_bytes(), refresh).map_err(|err| {
+        let reencrypt = |token: &Option<Vec<u8>>, kind: &str| -> Result<Option<Vec<u8>>> {
+            if !needs_migration(token) {
+                return Ok(None);
+            }
+            let token = token.as_deref().unwrap_or_default();
+            let plaintext = if is_encrypted_payload(token) {
+                // Master-key payloads do not say which version wrote them
+                master_keys
+                    .newest_first()
+                    .find_map(|(_, key)| decrypt_bytes(key, aad.as_bytes(), token).ok())
+                    .ok_or_else(|| {
+                        anyhow!(
+                            "failed to decrypt {} token for {} with any configured master key",
+                            kind,
+                            connection_id
+                        )
+                    })?
+            } else {
+                token.to_vec()
+            };
+            encrypt_bytes_with_record_key(&tenant_key, aad.as_bytes(), &plaintext)
+                .map(Some)
+                .map_err(|err| {
                     anyhow!(
-                        "failed to encrypt refresh token for {}: {}",
+                        "failed to encrypt {} token for {}: {}",
+                        kind,
                         connection_id,
                         err
                     )
-                })?;
-            new_refresh_cipher = Some(ciphertext);
-        }
-
-        if new_access_cipher.is_none() && new_refresh_cipher.is_none() {
-            continue;
-        }
-
-        let mut active: connection::ActiveModel = conn.into();
-        if let Some(cipher) = new_access_cipher {
-            active.access_token_ciphertext = Set(Some(cipher));
-        }
-        if let Some(cipher) = new_refresh_cipher {
-            active.refresh_token_ciphertext = Set(Some(cipher));
-        }
-        active.updated_at = Set(Utc::now().into());
+                })
+        };
+        let new_access_cipher = reencrypt(&secret.access_token_ciphertext, "access")?;
+        let new_refresh_cipher = reencrypt(&secret.refresh_token_ciphertext, "refresh")?;
 
-        active
-            .update(&db)
+        secrets
+            .upsert_with_key_version(
+                &connection_id,
+                new_access_cipher,
+                new_refresh_cipher,
+                master_keys.current_version(),
+            )
             .await
-            .with_context(|| format!("updating connection {}", connection_id))?;
+            .with_context(|| format!("updating secrets for connection {}", connection_id))?;
         updated_count += 1;
     }
 
     println!(
-        "Re-encrypted {} connection(s) containing legacy plaintext tokens.",
+        "Re-encrypted {} connection(s) with legacy plaintext or master-key tokens under tenant data keys.",
         updated_count
     );
 
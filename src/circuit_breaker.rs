@@ -0,0 +1,256 @@
+//! # Provider Circuit Breaker
+//!
+//! When a provider keeps answering with 5xx responses, dropping connections or
+//! letting syncs time out, retrying every tenant's jobs against it only adds load
+//! to an outage. After a configurable number of consecutive such failures the
+//! provider's circuit opens and the executor stops claiming its jobs for a
+//! cooldown window. Once the cooldown passes the circuit is half-open: a single
+//! probe job is claimed, and its outcome closes the circuit or opens it for
+//! another cooldown.
+//!
+//! State lives in the `provider_circuits` table so every executor instance honours
+//! it; a provider without a row is closed.
+
+use chrono::{DateTime, Duration, Utc};
+use metrics::counter;
+use sea_orm::{
+    ColumnTrait, ConnectionTrait, DbErr, EntityTrait, QueryFilter, Set,
+    sea_query::{Expr, OnConflict},
+};
+use tracing::{info, warn};
+
+use crate::connectors::{SyncError, SyncErrorKind};
+use crate::models::provider_circuit::{ActiveModel, Column, Entity, Model};
+
+/// Jobs are claimed normally
+pub const STATE_CLOSED: &str = "closed";
+
+/// Jobs are not claimed until the cooldown ends
+pub const STATE_OPEN: &str = "open";
+
+/// One probe job has been let through after the cooldown
+pub const STATE_HALF_OPEN: &str = "half_open";
+
+/// Default number of consecutive provider failures before a circuit opens
+pub const DEFAULT_FAILURE_THRESHOLD: u32 = 5;
+
+/// Default seconds an open circuit pauses claiming
+pub const DEFAULT_COOLDOWN_SECONDS: u64 = 60;
+
+/// Longest failure message kept on the circuit
+const MAX_ERROR_CHARS: usize = 500;
+
+/// Whether a failed job counts against its provider's circuit.
+///
+/// Only failures that point at the provider itself count: transient errors (5xx
+/// responses and retryable network errors) and runs that hit the executor's
+/// timeout. Rate limits, auth and permanent errors mean the provider answered.
+pub fn is_provider_failure(sync_error: Option<&SyncError>, timed_out: bool) -> bool {
+    timed_out || sync_error.is_some_and(|error| error.kind == SyncErrorKind::Transient)
+}
+
+/// Providers a claim has to hold back because of their circuit
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CircuitGate {
+    /// Providers whose circuit is open or already probing; none of their jobs are claimed
+    pub blocked: Vec<String>,
+    /// Providers this claim may probe with a single job
+    pub probing: Vec<String>,
+}
+
+/// Opens, probes and closes provider circuits
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CircuitBreakerPolicy {
+    /// Consecutive provider failures before the circuit opens; 0 never opens it
+    pub threshold: u32,
+    /// How long an open circuit pauses claiming, and how long a probe may run
+    /// before another one is let through
+    pub cooldown: Duration,
+}
+
+impl CircuitBreakerPolicy {
+    /// Whether circuits are tracked at all
+    pub fn is_enabled(&self) -> bool {
+        self.threshold > 0
+    }
+
+    /// Providers to hold back in a claim running at `now`.
+    ///
+    /// A circuit whose cooldown has ended moves to half-open here, with a fresh
+    /// window of `cooldown`. The update is conditional, so when several instances
+    /// claim at once only one of them gets to probe.
+    pub async fn gate<C: ConnectionTrait>(
+        &self,
+        db: &C,
+        now: DateTime<Utc>,
+    ) -> Result<CircuitGate, DbErr> {
+        let mut gate = CircuitGate::default();
+        let circuits = Entity::find()
+            .filter(Column::State.ne(STATE_CLOSED))
+            .all(db)
+            .await?;
+        for circuit in circuits {
+            if circuit.open_until.is_some_and(|until| until > now) {
+                gate.blocked.push(circuit.provider_slug);
+                continue;
+            }
+            let probing = Entity::update_many()
+                .col_expr(Column::State, Expr::value(STATE_HALF_OPEN))
+                .col_expr(Column::OpenUntil, Expr::value(now + self.cooldown))
+                .col_expr(Column::UpdatedAt, Expr::value(now))
+                .filter(Column::ProviderSlug.eq(circuit.provider_slug.as_str()))
+                .filter(Column::State.ne(STATE_CLOSED))
+                .filter(Column::OpenUntil.is_null().or(Column::OpenUntil.lte(now)))
+                .exec(db)
+                .await?
+                .rows_affected
+                > 0;
+            if probing {
+                info!(
+                    provider = %circuit.provider_slug,
+                    "Provider circuit cooldown ended, letting a probe job through"
+                );
+                gate.probing.push(circuit.provider_slug);
+            } else {
+                gate.blocked.push(circuit.provider_slug);
+            }
+        }
+        Ok(gate)
+    }
+
+    /// Record a provider failure of a job on `provider_slug`.
+    ///
+    /// Returns true when this failure opened the circuit: the streak reached the
+    /// threshold, or the failed job was the half-open probe.
+    pub async fn record_failure<C: ConnectionTrait>(
+        &self,
+        db: &C,
+        provider_slug: &str,
+        error_msg: &str,
+        now: DateTime<Utc>,
+    ) -> Result<bool, DbErr> {
+        if !self.is_enabled() {
+            return Ok(false);
+        }
+        let error: String = error_msg.chars().take(MAX_ERROR_CHARS).collect();
+        Entity::insert(ActiveModel {
+            provider_slug: Set(provider_slug.to_string()),
+            state: Set(STATE_CLOSED.to_string()),
+            consecutive_failures: Set(1),
+            opened_at: Set(None),
+            open_until: Set(None),
+            last_error: Set(Some(error.clone())),
+            updated_at: Set(now.fixed_offset()),
+        })
+        .on_conflict(
+            OnConflict::column(Column::ProviderSlug)
+                .value(
+                    Column::ConsecutiveFailures,
+                    Expr::col((Entity, Column::ConsecutiveFailures)).add(1),
+                )
+                .value(Column::LastError, Expr::value(error))
+                .value(Column::UpdatedAt, Expr::value(now))
+                .to_owned(),
+        )
+        .exec_without_returning(db)
+        .await?;
+
+        let threshold = i32::try_from(self.threshold).unwrap_or(i32::MAX);
+        let opened = Entity::update_many()
+            .col_expr(Column::State, Expr::value(STATE_OPEN))
+            .col_expr(Column::OpenedAt, Expr::value(now))
+            .col_expr(Column::OpenUntil, Expr::value(now + self.cooldown))
+            .col_expr(Column::UpdatedAt, Expr::value(now))
+            .filter(Column::ProviderSlug.eq(provider_slug))
+            .filter(
+                Column::State.eq(STATE_HALF_OPEN).or(Column::State
+                    .eq(STATE_CLOSED)
+                    .and(Column::ConsecutiveFailures.gte(threshold))),
+            )
+            .exec(db)
+            .await?
+            .rows_affected
+            > 0;
+        if opened {
+            counter!("provider_circuit_opened_total", "provider" => provider_slug.to_string())
+                .increment(1);
+            warn!(
+                provider = provider_slug,
+                cooldown_seconds = self.cooldown.num_seconds(),
+                error = error_msg,
+                "Provider circuit opened; pausing its jobs"
+            );
+        }
+        Ok(opened)
+    }
+
+    /// Record that `provider_slug` answered a job, closing its circuit and
+    /// clearing the failure streak
+    pub async fn record_success<C: ConnectionTrait>(
+        &self,
+        db: &C,
+        provider_slug: &str,
+        now: DateTime<Utc>,
+    ) -> Result<(), DbErr> {
+        if !self.is_enabled() {
+            return Ok(());
+        }
+        let Some(circuit) = Entity::find_by_id(provider_slug.to_string())
+            .one(db)
+            .await?
+        else {
+            return Ok(());
+        };
+        if circuit.state == STATE_CLOSED && circuit.consecutive_failures == 0 {
+            return Ok(());
+        }
+
+        Entity::update_many()
+            .col_expr(Column::State, Expr::value(STATE_CLOSED))
+            .col_expr(Column::ConsecutiveFailures, Expr::value(0))
+            .col_expr(
+                Column::OpenUntil,
+                Expr::value(Option::<DateTime<Utc>>::None),
+            )
+            .col_expr(Column::UpdatedAt, Expr::value(now))
+            .filter(Column::ProviderSlug.eq(provider_slug))
+            .exec(db)
+            .await?;
+        if circuit.state != STATE_CLOSED {
+            info!(provider = provider_slug, "Provider circuit closed");
+        }
+        Ok(())
+    }
+}
+
+/// Stored circuit of `provider_slug`, if it ever recorded a failure
+pub async fn find<C: ConnectionTrait>(db: &C, provider_slug: &str) -> Result<Option<Model>, DbErr> {
+    Entity::find_by_id(provider_slug.to_string()).one(db).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_only_transient_errors_and_timeouts_are_provider_failures() {
+        assert!(is_provider_failure(
+            Some(&SyncError::transient("HTTP error 503")),
+            false
+        ));
+        assert!(is_provider_failure(None, true));
+        assert!(!is_provider_failure(
+            Some(&SyncError::rate_limited(Some(30))),
+            false
+        ));
+        assert!(!is_provider_failure(
+            Some(&SyncError::permanent("HTTP error 404")),
+            false
+        ));
+        assert!(!is_provider_failure(
+            Some(&SyncError::unauthorized("token revoked")),
+            false
+        ));
+        assert!(!is_provider_failure(None, false));
+    }
+}
@@ -0,0 +1,920 @@
+//! # Operator CLI Commands
+//!
+//! Inspection commands for on-call engineers. They talk to the database through
+//! the repositories directly, so they keep working when the API is unavailable.
+//!
+//! `simulate` replays scheduling against the current database contents for
+//! capacity planning without modifying anything.
+//!
+//! The `admin` commands expose secrets for incident debugging. They refuse to run
+//! unless [`BREAK_GLASS_ENV`] is set and always write an audit entry first.
+//!
+//! `crypto rotate` re-encrypts every stored secret under a new master key version.
+
+use std::io::Write;
+
+use clap::{Args, Subcommand};
+use sea_orm::{DatabaseConnection, prelude::DateTimeWithTimeZone};
+use serde_json::Value as JsonValue;
+use uuid::Uuid;
+
+use crate::config::AppConfig;
+use crate::crypto::{KeyProvider, MasterKeyring};
+use crate::error::ApiError;
+use crate::key_rotation::{DEFAULT_ROTATION_BATCH_SIZE, RotationProgress, rotate_master_key};
+use crate::models::{connection, sync_job};
+use crate::repositories::{
+    AuditLogRepository, ConnectionRepository, NewAuditLogEntry, SyncJobRepository, TokenPresence,
+    sync_job::ListJobsConfig,
+};
+use crate::simulation::{SimulationConfig, SimulationReport, simulate};
+use crate::sync_executor::ExecutorConfig;
+
+/// Result type shared by CLI command handlers
+pub type CliResult = Result<(), Box<dyn std::error::Error + Send + Sync>>;
+
+/// Number of recent jobs shown alongside a connection
+const CONNECTION_RECENT_JOBS: i64 = 5;
+
+/// Environment variable that must be set to `1` or `true` to run break-glass commands.
+/// It is read from the process environment only, never from config files, so it
+/// cannot be left enabled by accident.
+pub const BREAK_GLASS_ENV: &str = "POBLYSH_BREAK_GLASS";
+
+/// Audit action recorded when connection tokens are decrypted from the CLI
+pub const DECRYPT_TOKEN_AUDIT_ACTION: &str = "connection.decrypt_token";
+
+/// Audit action recorded when a tenant's data key is shredded from the CLI
+pub const SHRED_TENANT_KEY_AUDIT_ACTION: &str = "tenant.shred_data_key";
+
+/// Sync job inspection commands
+#[derive(Debug, Subcommand)]
+pub enum JobsAction {
+    /// List jobs for a tenant, most recently scheduled first
+    List {
+        /// Tenant to list jobs for
+        #[arg(long)]
+        tenant: Uuid,
+        /// Filter by status (queued, running, cancelling, cancelled, succeeded, failed)
+        #[arg(long)]
+        status: Option<String>,
+        /// Filter by provider slug
+        #[arg(long)]
+        provider: Option<String>,
+        /// Filter by connection ID
+        #[arg(long)]
+        connection: Option<Uuid>,
+        /// Filter by job type (full, incremental, webhook)
+        #[arg(long = "type")]
+        job_type: Option<String>,
+        /// Maximum number of jobs to show
+        #[arg(long, default_value_t = 20)]
+        limit: i64,
+    },
+    /// Show every field of a job, including its cursor and last error
+    Show {
+        /// Job ID
+        job_id: Uuid,
+    },
+    /// Requeue a failed or backing-off job so it runs immediately
+    Retry {
+        /// Job ID
+        job_id: Uuid,
+    },
+}
+
+/// Connection inspection commands
+#[derive(Debug, Subcommand)]
+pub enum ConnectionsAction {
+    /// List connections for a tenant
+    List {
+        /// Tenant to list connections for
+        #[arg(long)]
+        tenant: Uuid,
+        /// Filter by provider slug
+        #[arg(long)]
+        provider: Option<String>,
+    },
+    /// Show a connection and its most recent jobs (token values are never printed)
+    Show {
+        /// Connection ID
+        connection_id: Uuid,
+    },
+}
+
+/// Break-glass commands for incident debugging
+#[derive(Debug, Subcommand)]
+pub enum AdminAction {
+    /// Print a connection's decrypted provider tokens (requires POBLYSH_BREAK_GLASS=1)
+    DecryptToken {
+        /// Connection whose tokens should be decrypted
+        #[arg(long)]
+        connection: Uuid,
+        /// Why the tokens are needed (recorded in the audit log)
+        #[arg(long)]
+        reason: String,
+    },
+    /// Delete a tenant's data key so its stored tokens can never be decrypted (requires POBLYSH_BREAK_GLASS=1)
+    ShredTenantKey {
+        /// Tenant whose data key should be destroyed
+        #[arg(long)]
+        tenant: Uuid,
+        /// Why the key is being shredded, e.g. the deletion request (recorded in the audit log)
+        #[arg(long)]
+        reason: String,
+    },
+}
+
+/// Master key maintenance commands
+#[derive(Debug, Subcommand)]
+pub enum CryptoAction {
+    /// Re-encrypt all stored tokens and tenant data keys under a new master key version
+    Rotate {
+        /// New master key, in the same form as POBLYSH_CRYPTO_KEY for the configured key provider
+        #[arg(long)]
+        new_key: String,
+        /// Version of the new key (default: one above the newest configured version)
+        #[arg(long)]
+        new_version: Option<i32>,
+        /// Connections re-encrypted per transaction
+        #[arg(long, default_value_t = DEFAULT_ROTATION_BATCH_SIZE)]
+        batch_size: u64,
+    },
+}
+
+/// Options for the capacity simulation
+#[derive(Debug, Args)]
+pub struct SimulateArgs {
+    /// Hours of virtual time to simulate
+    #[arg(long, default_value_t = 24)]
+    pub hours: u32,
+    /// How long each stubbed connector run takes, in seconds
+    #[arg(long, default_value_t = 30)]
+    pub job_seconds: u32,
+    /// Width of the queue depth buckets, in minutes
+    #[arg(long, default_value_t = 60)]
+    pub bucket_minutes: u32,
+    /// Seed for scheduler jitter
+    #[arg(long, default_value_t = 0)]
+    pub seed: u64,
+    /// Print the full report as JSON instead of tables
+    #[arg(long)]
+    pub json: bool,
+}
+
+/// Whether break-glass commands are enabled for this process
+pub fn break_glass_enabled() -> bool {
+    std::env::var(BREAK_GLASS_ENV)
+        .map(|value| matches!(value.trim().to_ascii_lowercase().as_str(), "1" | "true"))
+        .unwrap_or(false)
+}
+
+/// Best-effort identity of the operator running the CLI, for the audit trail
+pub fn current_actor() -> String {
+    let user = ["SUDO_USER", "USER", "USERNAME"]
+        .iter()
+        .find_map(|key| std::env::var(key).ok().filter(|value| !value.is_empty()))
+        .unwrap_or_else(|| "unknown".to_string());
+    format!("cli:{}", user)
+}
+
+/// Run an `admin` subcommand, writing output to `out`.
+///
+/// The audit entry is written before any secret is decrypted or key destroyed;
+/// if it cannot be recorded the command aborts.
+pub async fn run_admin_command(
+    connections: &ConnectionRepository,
+    audit: &AuditLogRepository,
+    action: AdminAction,
+    break_glass: bool,
+    actor: &str,
+    out: &mut impl Write,
+) -> CliResult {
+    match action {
+        AdminAction::DecryptToken { connection, reason } => {
+            if !break_glass {
+                return Err(format!(
+                    "Refusing to decrypt tokens: set {}=1 to confirm this break-glass action",
+                    BREAK_GLASS_ENV
+                )
+                .into());
+            }
+            if reason.trim().is_empty() {
+                return Err("A non-empty --reason is required".into());
+            }
+
+            let model = connections
+                .get_by_id(&connection)
+                .await?
+                .ok_or_else(|| format!("Connection {} not found", connection))?;
+            let model = connections.with_secrets(model).await?;
+
+            audit
+                .record(NewAuditLogEntry {
+                    tenant_id: Some(model.tenant_id),
+                    actor: actor.to_string(),
+                    action: DECRYPT_TOKEN_AUDIT_ACTION.to_string(),
+                    target_type: "connection".to_string(),
+                    target_id: model.id.to_string(),
+                    reason: Some(reason.trim().to_string()),
+                    details: Some(serde_json::json!({
+                        "provider_slug": model.provider_slug,
+                        "has_access_token": model.access_token_ciphertext.is_some(),
+                        "has_refresh_token": model.refresh_token_ciphertext.is_some(),
+                    })),
+                })
+                .await?;
+            tracing::warn!(
+                connection_id = %model.id,
+                tenant_id = %model.tenant_id,
+                actor = %actor,
+                "Break-glass token decryption performed"
+            );
+
+            let (access_token, refresh_token, _) = connections.decrypt_tokens(&model).await?;
+            writeln!(out, "connection_id: {}", model.id)?;
+            writeln!(out, "provider:      {}", model.provider_slug)?;
+            writeln!(
+                out,
+                "access_token:  {}",
+                access_token.as_deref().unwrap_or("-")
+            )?;
+            writeln!(
+                out,
+                "refresh_token: {}",
+                refresh_token.as_deref().unwrap_or("-")
+            )?;
+        }
+        AdminAction::ShredTenantKey { tenant, reason } => {
+            if !break_glass {
+                return Err(format!(
+                    "Refusing to shred tenant key: set {}=1 to confirm this break-glass action",
+                    BREAK_GLASS_ENV
+                )
+                .into());
+            }
+            if reason.trim().is_empty() {
+                return Err("A non-empty --reason is required".into());
+            }
+
+            audit
+                .record(NewAuditLogEntry {
+                    tenant_id: Some(tenant),
+                    actor: actor.to_string(),
+                    action: SHRED_TENANT_KEY_AUDIT_ACTION.to_string(),
+                    target_type: "tenant".to_string(),
+                    target_id: tenant.to_string(),
+                    reason: Some(reason.trim().to_string()),
+                    details: None,
+                })
+                .await?;
+
+            let shredded = connections.tenant_keys().shred(&tenant).await?;
+            tracing::warn!(
+                tenant_id = %tenant,
+                actor = %actor,
+                shredded,
+                "Break-glass tenant data key shred performed"
+            );
+            if shredded {
+                writeln!(
+                    out,
+                    "Shredded data key for tenant {}; its stored tokens are no longer decryptable",
+                    tenant
+                )?;
+            } else {
+                writeln!(out, "Tenant {} has no data key stored", tenant)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Run a `jobs` subcommand, writing human-readable output to `out`
+pub async fn run_jobs_command(
+    repo: &SyncJobRepository,
+    action: JobsAction,
+    out: &mut impl Write,
+) -> CliResult {
+    match action {
+        JobsAction::List {
+            tenant,
+            status,
+            provider,
+            connection,
+            job_type,
+            limit,
+        } => {
+            let filters = ListJobsConfig {
+                status,
+                provider,
+                connection_id: connection,
+                job_type,
+                ..Default::default()
+            };
+            let result = repo
+                .list_jobs(tenant, limit.max(1), None, filters)
+                .await
+                .map_err(api_error)?;
+            write_job_table(out, &result.jobs)?;
+            if result.next_cursor.is_some() {
+                writeln!(out, "(more jobs available; raise --limit to see them)")?;
+            }
+        }
+        JobsAction::Show { job_id } => {
+            let job = repo
+                .find_by_id(job_id)
+                .await
+                .map_err(api_error)?
+                .ok_or_else(|| format!("Job {} not found", job_id))?;
+            write_job_details(out, &job)?;
+        }
+        JobsAction::Retry { job_id } => {
+            let job = repo.retry_job(job_id).await.map_err(api_error)?;
+            writeln!(
+                out,
+                "Job {} requeued (attempts so far: {})",
+                job.id, job.attempts
+            )?;
+        }
+    }
+    Ok(())
+}
+
+/// Run a `connections` subcommand, writing human-readable output to `out`
+pub async fn run_connections_command(
+    connections: &ConnectionRepository,
+    jobs: &SyncJobRepository,
+    action: ConnectionsAction,
+    out: &mut impl Write,
+) -> CliResult {
+    match action {
+        ConnectionsAction::List { tenant, provider } => {
+            let rows = match provider {
+                Some(provider) => {
+                    connections
+                        .find_by_tenant_and_provider(&tenant, &provider)
+                        .await?
+                }
+                None => connections.find_by_tenant(&tenant).await?,
+            };
+            writeln!(
+                out,
+                "{:<36}  {:<12}  {:<8}  {:<25}  EXTERNAL_ID",
+                "ID", "PROVIDER", "STATUS", "EXPIRES_AT"
+            )?;
+            for row in &rows {
+                writeln!(
+                    out,
+                    "{:<36}  {:<12}  {:<8}  {:<25}  {}",
+                    row.id,
+                    row.provider_slug,
+                    row.status,
+                    format_optional_time(row.expires_at),
+                    row.external_id
+                )?;
+            }
+            writeln!(out, "{} connection(s)", rows.len())?;
+        }
+        ConnectionsAction::Show { connection_id } => {
+            let connection = connections
+                .get_by_id(&connection_id)
+                .await?
+                .ok_or_else(|| format!("Connection {} not found", connection_id))?;
+            let tokens = connections
+                .secrets()
+                .presence(&[connection.id])
+                .await?
+                .remove(&connection.id)
+                .unwrap_or_default();
+            write_connection_details(out, &connection, tokens)?;
+
+            let recent = jobs
+                .list_jobs(
+                    connection.tenant_id,
+                    CONNECTION_RECENT_JOBS,
+                    None,
+                    ListJobsConfig {
+                        connection_id: Some(connection.id),
+                        ..Default::default()
+                    },
+                )
+                .await
+                .map_err(api_error)?;
+            writeln!(out)?;
+            writeln!(out, "Recent jobs:")?;
+            write_job_table(out, &recent.jobs)?;
+        }
+    }
+    Ok(())
+}
+
+/// Run the capacity simulation from the current database state, writing the report to `out`.
+///
+/// The database is only read; scheduling decisions are replayed in memory.
+pub async fn run_simulate_command(
+    db: &DatabaseConnection,
+    config: &AppConfig,
+    args: SimulateArgs,
+    out: &mut impl Write,
+) -> CliResult {
+    let simulation = SimulationConfig {
+        duration: chrono::Duration::hours(i64::from(args.hours.max(1))),
+        job_duration: chrono::Duration::seconds(i64::from(args.job_seconds)),
+        bucket: chrono::Duration::minutes(i64::from(args.bucket_minutes.max(1))),
+        seed: args.seed,
+        scheduler: config.scheduler.clone(),
+        executor: ExecutorConfig::from_config(config),
+    };
+    let report = simulate(db, simulation, chrono::Utc::now()).await?;
+
+    if args.json {
+        serde_json::to_writer_pretty(&mut *out, &report)?;
+        writeln!(out)?;
+    } else {
+        write_simulation_report(out, &report)?;
+    }
+    Ok(())
+}
+
+/// Run a `crypto` subcommand, writing progress to `out`.
+///
+/// `master_keys` must hold every version stored data was written under; the new
+/// key is unwrapped through `provider` like `POBLYSH_CRYPTO_KEY`. Fails if any row
+/// could not be rotated, after listing them.
+pub async fn run_crypto_command(
+    db: &DatabaseConnection,
+    config: &AppConfig,
+    master_keys: &MasterKeyring,
+    provider: &dyn KeyProvider,
+    action: CryptoAction,
+    out: &mut impl Write,
+) -> CliResult {
+    match action {
+        CryptoAction::Rotate {
+            new_key,
+            new_version,
+            batch_size,
+        } => {
+            let material = crate::config::decode_key_material(
+                &new_key,
+                config.crypto_key_provider.holds_vault_ciphertext(),
+            )?;
+            let new_key = provider.unwrap_master_key(&material).await?;
+            // Rerunning after the configuration already switched keeps the current version
+            let version = new_version.unwrap_or_else(|| {
+                if new_key.as_bytes() == master_keys.current().as_bytes() {
+                    master_keys.current_version()
+                } else {
+                    master_keys.newest_first().next().map_or(1, |(v, _)| v + 1)
+                }
+            });
+            let keyring = master_keys.rotate(version, new_key).map_err(|_| {
+                format!(
+                    "--new-version {} must be above every configured version, or the current version ({}) with the current key",
+                    version,
+                    master_keys.current_version()
+                )
+            })?;
+
+            writeln!(
+                out,
+                "Rotating stored secrets to master key version {}",
+                version
+            )?;
+            let progress = rotate_master_key(db, &keyring, batch_size, |progress| {
+                // Progress is best effort; a closed stdout must not abort the rotation
+                let _ = write_rotation_progress(out, progress);
+            })
+            .await?;
+
+            writeln!(
+                out,
+                "Re-encrypted {} connection(s) and re-wrapped {} tenant data key(s) with version {}",
+                progress.connections_rotated, progress.tenant_keys_rewrapped, version
+            )?;
+            if !progress.is_complete() {
+                for connection_id in &progress.failed_connections {
+                    writeln!(out, "failed connection: {}", connection_id)?;
+                }
+                for tenant_id in &progress.failed_tenants {
+                    writeln!(out, "failed tenant key: {}", tenant_id)?;
+                }
+                return Err(format!(
+                    "{} connection(s) and {} tenant key(s) were left unchanged; keep the previous versions configured",
+                    progress.failed_connections.len(),
+                    progress.failed_tenants.len()
+                )
+                .into());
+            }
+            writeln!(
+                out,
+                "Done. Set POBLYSH_CRYPTO_KEY to the new key with POBLYSH_CRYPTO_KEY_VERSION={}; previous versions are no longer needed.",
+                version
+            )?;
+        }
+    }
+    Ok(())
+}
+
+fn write_rotation_progress(
+    out: &mut impl Write,
+    progress: &RotationProgress,
+) -> std::io::Result<()> {
+    writeln!(
+        out,
+        "  {}/{} connections processed, {} re-encrypted, {} tenant keys re-wrapped, {} failed",
+        progress.connections_processed,
+        progress.connections_total,
+        progress.connections_rotated,
+        progress.tenant_keys_rewrapped,
+        progress.failed_connections.len() + progress.failed_tenants.len()
+    )
+}
+
+fn api_error(error: ApiError) -> Box<dyn std::error::Error + Send + Sync> {
+    format!("{} ({})", error.message, error.code).into()
+}
+
+fn write_job_table(out: &mut impl Write, jobs: &[sync_job::Model]) -> std::io::Result<()> {
+    writeln!(
+        out,
+        "{:<36}  {:<12}  {:<11}  {:<9}  {:>3}  {:>8}  {:<25}  FINISHED_AT",
+        "ID", "PROVIDER", "TYPE", "STATUS", "PRI", "ATTEMPTS", "SCHEDULED_AT"
+    )?;
+    for job in jobs {
+        writeln!(
+            out,
+            "{:<36}  {:<12}  {:<11}  {:<9}  {:>3}  {:>8}  {:<25}  {}",
+            job.id,
+            job.provider_slug,
+            job.job_type,
+            job.status,
+            job.priority,
+            job.attempts,
+            job.scheduled_at.to_rfc3339(),
+            format_optional_time(job.finished_at)
+        )?;
+    }
+    Ok(())
+}
+
+fn write_job_details(out: &mut impl Write, job: &sync_job::Model) -> std::io::Result<()> {
+    writeln!(out, "id:            {}", job.id)?;
+    writeln!(out, "tenant_id:     {}", job.tenant_id)?;
+    writeln!(out, "connection_id: {}", job.connection_id)?;
+    writeln!(out, "provider:      {}", job.provider_slug)?;
+    writeln!(out, "type:          {}", job.job_type)?;
+    writeln!(out, "status:        {}", job.status)?;
+    writeln!(out, "priority:      {}", job.priority)?;
+    writeln!(out, "attempts:      {}", job.attempts)?;
+    writeln!(out, "scheduled_at:  {}", job.scheduled_at.to_rfc3339())?;
+    writeln!(
+        out,
+        "retry_after:   {}",
+        format_optional_time(job.retry_after)
+    )?;
+    writeln!(
+        out,
+        "started_at:    {}",
+        format_optional_time(job.started_at)
+    )?;
+    writeln!(
+        out,
+        "finished_at:   {}",
+        format_optional_time(job.finished_at)
+    )?;
+    writeln!(out, "created_at:    {}", job.created_at.to_rfc3339())?;
+    writeln!(out, "updated_at:    {}", job.updated_at.to_rfc3339())?;
+    writeln!(out, "cursor:        {}", format_optional_json(&job.cursor))?;
+    writeln!(out, "error:         {}", format_optional_json(&job.error))?;
+    Ok(())
+}
+
+fn write_connection_details(
+    out: &mut impl Write,
+    connection: &connection::Model,
+    tokens: TokenPresence,
+) -> std::io::Result<()> {
+    let presence = |stored: bool| if stored { "present" } else { "absent" };
+
+    writeln!(out, "id:            {}", connection.id)?;
+    writeln!(out, "tenant_id:     {}", connection.tenant_id)?;
+    writeln!(out, "provider:      {}", connection.provider_slug)?;
+    writeln!(out, "external_id:   {}", connection.external_id)?;
+    writeln!(
+        out,
+        "display_name:  {}",
+        connection.display_name.as_deref().unwrap_or("-")
+    )?;
+    writeln!(out, "status:        {}", connection.status)?;
+    writeln!(
+        out,
+        "expires_at:    {}",
+        format_optional_time(connection.expires_at)
+    )?;
+    writeln!(out, "access_token:  {}", presence(tokens.has_access_token))?;
+    writeln!(out, "refresh_token: {}", presence(tokens.has_refresh_token))?;
+    writeln!(
+        out,
+        "scopes:        {}",
+        format_optional_json(&connection.scopes)
+    )?;
+    writeln!(
+        out,
+        "metadata:      {}",
+        format_optional_json(&connection.metadata)
+    )?;
+    writeln!(out, "created_at:    {}", connection.created_at.to_rfc3339())?;
+    writeln!(out, "updated_at:    {}", connection.updated_at.to_rfc3339())?;
+    Ok(())
+}
+
+fn write_simulation_report(out: &mut impl Write, report: &SimulationReport) -> std::io::Result<()> {
+    writeln!(
+        out,
+        "Simulated {} to {}",
+        report.started_at.to_rfc3339(),
+        report.ended_at.to_rfc3339()
+    )?;
+    writeln!(
+        out,
+        "jobs enqueued: {}  started: {}  queued at end: {}  peak queued: {}  max wait: {}s",
+        report.jobs_enqueued,
+        report.jobs_started,
+        report.jobs_queued_at_end,
+        report.peak_queued,
+        report.max_wait_seconds
+    )?;
+
+    writeln!(out)?;
+    writeln!(
+        out,
+        "{:<25}  {:>8}  {:>7}  {:>11}  {:>10}",
+        "BUCKET_START", "ENQUEUED", "STARTED", "PEAK_QUEUED", "MAX_WAIT_S"
+    )?;
+    for bucket in &report.queue_depth {
+        writeln!(
+            out,
+            "{:<25}  {:>8}  {:>7}  {:>11}  {:>10}",
+            bucket.bucket_start.to_rfc3339(),
+            bucket.enqueued,
+            bucket.started,
+            bucket.peak_queued,
+            bucket.max_wait_seconds
+        )?;
+    }
+
+    writeln!(out)?;
+    writeln!(
+        out,
+        "{:<36}  {:<12}  {:>10}  {:>5}  {:<25}  LAST_SYNC",
+        "CONNECTION", "PROVIDER", "INTERVAL_S", "SYNCS", "FIRST_SYNC"
+    )?;
+    for connection in &report.connections {
+        let first = connection.sync_starts.first();
+        let last = connection.sync_starts.last();
+        writeln!(
+            out,
+            "{:<36}  {:<12}  {:>10}  {:>5}  {:<25}  {}",
+            connection.connection_id,
+            connection.provider_slug,
+            connection.interval_seconds,
+            connection.sync_starts.len(),
+            first.map_or("-".to_string(), |at| at.to_rfc3339()),
+            last.map_or("-".to_string(), |at| at.to_rfc3339())
+        )?;
+    }
+    Ok(())
+}
+
+fn format_optional_time(value: Option<DateTimeWithTimeZone>) -> String {
+    value
+        .map(|time| time.to_rfc3339())
+        .unwrap_or_else(|| "-".to_string())
+}
+
+fn format_optional_json(value: &Option<JsonValue>) -> String {
+    match value {
+        Some(json) => serde_json::to_string_pretty(json).unwrap_or_else(|_| json.to_string()),
+        None => "-".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::AppConfig;
+    use crate::crypto::CryptoKey;
+    use crate::repositories::ConnectionSecretRepository;
+    use crate::test_fixtures;
+    use sea_orm::{DatabaseConnection, Set};
+    use std::sync::Arc;
+
+    async fn setup() -> (DatabaseConnection, Uuid, Uuid) {
+        let (db, tenant_id, connection_id) = test_fixtures::setup_connection().await;
+        ConnectionSecretRepository::new(Arc::new(db.clone()))
+            .upsert(&connection_id, Some(b"secret-ciphertext".to_vec()), None)
+            .await
+            .unwrap();
+        (db, tenant_id, connection_id)
+    }
+
+    #[tokio::test]
+    async fn test_jobs_list_show_and_retry() {
+        let (db, tenant_id, connection_id) = setup().await;
+        let repo = SyncJobRepository::new(db.clone());
+        let job = repo
+            .enqueue_sync_job(tenant_id, "test-provider", connection_id, None)
+            .await
+            .unwrap();
+        repo.update_status(tenant_id, job.id, "failed".to_string(), None)
+            .await
+            .unwrap();
+
+        let mut out = Vec::new();
+        let list = JobsAction::List {
+            tenant: tenant_id,
+            status: Some("failed".to_string()),
+            provider: None,
+            connection: None,
+            job_type: None,
+            limit: 20,
+        };
+        run_jobs_command(&repo, list, &mut out).await.unwrap();
+        let listing = String::from_utf8(out).unwrap();
+        assert!(listing.contains(&job.id.to_string()));
+
+        let mut out = Vec::new();
+        run_jobs_command(&repo, JobsAction::Retry { job_id: job.id }, &mut out)
+            .await
+            .unwrap();
+        assert!(String::from_utf8(out).unwrap().contains("requeued"));
+
+        let mut out = Vec::new();
+        run_jobs_command(&repo, JobsAction::Show { job_id: job.id }, &mut out)
+            .await
+            .unwrap();
+        let details = String::from_utf8(out).unwrap();
+        assert!(details.contains("status:        queued"));
+    }
+
+    #[tokio::test]
+    async fn test_connections_show_never_prints_tokens() {
+        let (db, _tenant_id, connection_id) = setup().await;
+        let connections =
+            ConnectionRepository::new(Arc::new(db.clone()), CryptoKey::new(vec![0u8; 32]).unwrap());
+        let jobs = SyncJobRepository::new(db.clone());
+
+        let mut out = Vec::new();
+        run_connections_command(
+            &connections,
+            &jobs,
+            ConnectionsAction::Show { connection_id },
+            &mut out,
+        )
+        .await
+        .unwrap();
+
+        let details = String::from_utf8(out).unwrap();
+        assert!(details.contains("access_token:  present"));
+        assert!(details.contains("refresh_token: absent"));
+        assert!(!details.contains("secret-ciphertext"));
+    }
+
+    #[tokio::test]
+    async fn test_decrypt_token_requires_break_glass_and_records_audit() {
+        let (db, tenant_id, _) = setup().await;
+        let crypto_key = CryptoKey::new(vec![7u8; 32]).unwrap();
+        let connections = ConnectionRepository::new(Arc::new(db.clone()), crypto_key);
+        let audit = AuditLogRepository::new(db.clone());
+        let target = connections
+            .create_with_tokens(
+                connection::ActiveModel {
+                    id: Set(Uuid::new_v4()),
+                    tenant_id: Set(tenant_id),
+                    provider_slug: Set("test-provider".to_string()),
+                    external_id: Set("break-glass".to_string()),
+                    status: Set("active".to_string()),
+                    ..Default::default()
+                },
+                Some("plain-access"),
+                None,
+            )
+            .await
+            .unwrap();
+        let action = || AdminAction::DecryptToken {
+            connection: target.id,
+            reason: "INC-42 token rejected by provider".to_string(),
+        };
+
+        let mut out = Vec::new();
+        let refused = run_admin_command(
+            &connections,
+            &audit,
+            action(),
+            false,
+            "cli:tester",
+            &mut out,
+        )
+        .await;
+        assert!(refused.is_err());
+        assert!(out.is_empty());
+        let entries = audit
+            .list_for_target("connection", &target.id.to_string())
+            .await
+            .unwrap();
+        assert!(entries.is_empty());
+
+        run_admin_command(&connections, &audit, action(), true, "cli:tester", &mut out)
+            .await
+            .unwrap();
+        assert!(String::from_utf8(out).unwrap().contains("plain-access"));
+
+        let entries = audit
+            .list_for_target("connection", &target.id.to_string())
+            .await
+            .unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].action, DECRYPT_TOKEN_AUDIT_ACTION);
+        assert_eq!(entries[0].actor, "cli:tester");
+        assert!(
+            !entries[0]
+                .details
+                .as_ref()
+                .unwrap()
+                .to_string()
+                .contains("plain-access")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_shred_tenant_key_makes_tokens_unreadable() {
+        let (db, tenant_id, _) = setup().await;
+        let connections =
+            ConnectionRepository::new(Arc::new(db.clone()), CryptoKey::new(vec![7u8; 32]).unwrap());
+        let audit = AuditLogRepository::new(db.clone());
+        let target = connections
+            .create_with_tokens(
+                connection::ActiveModel {
+                    id: Set(Uuid::new_v4()),
+                    tenant_id: Set(tenant_id),
+                    provider_slug: Set("test-provider".to_string()),
+                    external_id: Set("shred".to_string()),
+                    status: Set("active".to_string()),
+                    ..Default::default()
+                },
+                Some("plain-access"),
+                None,
+            )
+            .await
+            .unwrap();
+        assert!(connections.decrypt_tokens(&target).await.is_ok());
+
+        let mut out = Vec::new();
+        let action = AdminAction::ShredTenantKey {
+            tenant: tenant_id,
+            reason: "GDPR erasure request".to_string(),
+        };
+        run_admin_command(&connections, &audit, action, true, "cli:tester", &mut out)
+            .await
+            .unwrap();
+        assert!(String::from_utf8(out).unwrap().contains("Shredded"));
+
+        assert!(connections.decrypt_tokens(&target).await.is_err());
+        let entries = audit
+            .list_for_target("tenant", &tenant_id.to_string())
+            .await
+            .unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].action, SHRED_TENANT_KEY_AUDIT_ACTION);
+    }
+
+    #[tokio::test]
+    async fn test_crypto_rotate_rejects_versions_that_are_not_newer() {
+        use base64::{Engine as _, engine::general_purpose};
+
+        let (db, _, _) = setup().await;
+        let config = AppConfig::default();
+        let master_keys = MasterKeyring::new(3, CryptoKey::new(vec![3u8; 32]).unwrap()).unwrap();
+        let action = CryptoAction::Rotate {
+            new_key: general_purpose::STANDARD.encode([4u8; 32]),
+            new_version: Some(2),
+            batch_size: 10,
+        };
+
+        let mut out = Vec::new();
+        let err = run_crypto_command(
+            &db,
+            &config,
+            &master_keys,
+            &crate::crypto::key_provider::EnvKeyProvider,
+            action,
+            &mut out,
+        )
+        .await
+        .unwrap_err();
+        assert!(err.to_string().contains("--new-version 2 must be above"));
+        assert!(out.is_empty());
+    }
+}
@@ -0,0 +1,122 @@
+//! # Clock
+//!
+//! Time source injected into background services (scheduler, sync executor,
+//! token refresh, OAuth state expiry, weak signal engine). Production code
+//! uses [`SystemClock`]; tests use [`MockClock`] to move virtual time forward
+//! instead of sleeping, so interval and expiry logic runs instantly and
+//! deterministically.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use tokio::sync::watch;
+
+/// Source of the current time and of timed waits
+#[async_trait]
+pub trait Clock: Send + Sync + std::fmt::Debug {
+    /// Current wall-clock time
+    fn now(&self) -> DateTime<Utc>;
+
+    /// Wait until `duration` has elapsed on this clock
+    async fn sleep(&self, duration: Duration);
+}
+
+/// Shared handle to a clock, cloned into each service
+pub type SharedClock = Arc<dyn Clock>;
+
+/// Clock backed by the system time and the tokio timer
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemClock;
+
+#[async_trait]
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+
+    async fn sleep(&self, duration: Duration) {
+        tokio::time::sleep(duration).await;
+    }
+}
+
+/// Default clock for services that are not given one explicitly
+pub fn system_clock() -> SharedClock {
+    Arc::new(SystemClock)
+}
+
+/// Manually driven clock for tests.
+///
+/// Time only moves when [`MockClock::advance`] or [`MockClock::set`] is called;
+/// pending [`Clock::sleep`] calls resolve once virtual time reaches their deadline.
+#[derive(Debug)]
+pub struct MockClock {
+    now: watch::Sender<DateTime<Utc>>,
+}
+
+impl MockClock {
+    /// Create a clock frozen at `start`
+    pub fn new(start: DateTime<Utc>) -> Arc<Self> {
+        Arc::new(Self {
+            now: watch::Sender::new(start),
+        })
+    }
+
+    /// Move virtual time forward, waking sleepers whose deadline has passed
+    pub fn advance(&self, duration: Duration) {
+        let step = chrono::Duration::from_std(duration).expect("duration fits chrono range");
+        self.now.send_modify(|now| *now += step);
+    }
+
+    /// Jump to an absolute time; moving backwards is allowed but never wakes sleepers
+    pub fn set(&self, at: DateTime<Utc>) {
+        self.now.send_replace(at);
+    }
+}
+
+#[async_trait]
+impl Clock for MockClock {
+    fn now(&self) -> DateTime<Utc> {
+        *self.now.borrow()
+    }
+
+    async fn sleep(&self, duration: Duration) {
+        let deadline =
+            self.now() + chrono::Duration::from_std(duration).expect("duration fits chrono range");
+        let mut receiver = self.now.subscribe();
+        // The sender lives as long as self, so the channel cannot close while we wait
+        let _ = receiver.wait_for(|now| *now >= deadline).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_mock_clock_sleep_resolves_on_advance() {
+        let start = Utc::now();
+        let clock = MockClock::new(start);
+
+        let sleeper = {
+            let clock = clock.clone();
+            tokio::spawn(async move { clock.sleep(Duration::from_secs(60)).await })
+        };
+
+        tokio::task::yield_now().await;
+        clock.advance(Duration::from_secs(59));
+        tokio::task::yield_now().await;
+        assert!(!sleeper.is_finished());
+
+        clock.advance(Duration::from_secs(1));
+        sleeper.await.unwrap();
+        assert_eq!(clock.now(), start + chrono::Duration::seconds(60));
+    }
+
+    #[tokio::test]
+    async fn test_mock_clock_zero_sleep_returns_immediately() {
+        let clock = MockClock::new(Utc::now());
+        clock.sleep(Duration::ZERO).await;
+    }
+}
@@ -0,0 +1,180 @@
+//! # Configuration Reload
+//!
+//! A subset of settings can change while the service runs: the log level, the
+//! rate limit policy, the mail spam lists and the scheduler intervals. On SIGHUP
+//! the [`ConfigWatcher`] re-runs [`ConfigLoader::load`], which re-reads the config
+//! file and `.env` files and validates the result, and swaps the new values into the
+//! shared [`ReloadableConfig`] in one step. A configuration that fails to load
+//! or validate is rejected and the running settings are kept. Every other
+//! setting still needs a restart.
+
+use std::sync::{Arc, RwLock};
+
+use metrics::counter;
+use thiserror::Error;
+use tokio_util::sync::CancellationToken;
+use tracing::{info, warn};
+
+use crate::config::{
+    AppConfig, ConfigError, ConfigLoader, MailSpamConfig, RateLimitPolicyConfig, SchedulerConfig,
+};
+use crate::telemetry::{self, LogFilterError};
+
+/// Settings that take effect without a restart
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReloadableSettings {
+    /// Global log filter (`POBLYSH_LOG_LEVEL`)
+    pub log_level: String,
+    /// Retry backoff, attempt budgets and concurrency caps used by the executor
+    pub rate_limit_policy: RateLimitPolicyConfig,
+    /// Spam threshold and allow/deny lists applied by mail connectors
+    pub mail_spam: MailSpamConfig,
+    /// Tick and sync intervals used by the scheduler
+    pub scheduler: SchedulerConfig,
+}
+
+impl ReloadableSettings {
+    /// Takes the reloadable settings out of a loaded configuration
+    pub fn from_config(config: &AppConfig) -> Self {
+        Self {
+            log_level: config.log_level.clone(),
+            rate_limit_policy: config.rate_limit_policy.clone(),
+            mail_spam: config.mail_spam.clone(),
+            scheduler: config.scheduler.clone(),
+        }
+    }
+
+    /// Names of the settings that differ from `other`
+    fn changed_from(&self, other: &Self) -> Vec<&'static str> {
+        [
+            ("log_level", self.log_level != other.log_level),
+            (
+                "rate_limit_policy",
+                self.rate_limit_policy != other.rate_limit_policy,
+            ),
+            ("mail_spam", self.mail_spam != other.mail_spam),
+            ("scheduler", self.scheduler != other.scheduler),
+        ]
+        .into_iter()
+        .filter_map(|(name, changed)| changed.then_some(name))
+        .collect()
+    }
+}
+
+impl Default for ReloadableSettings {
+    fn default() -> Self {
+        Self::from_config(&AppConfig::default())
+    }
+}
+
+/// Shared handle to the current [`ReloadableSettings`]
+///
+/// Readers take a snapshot with [`ReloadableConfig::current`] and keep using it
+/// for the rest of their unit of work, so a reload never mixes old and new values.
+#[derive(Debug, Clone, Default)]
+pub struct ReloadableConfig {
+    current: Arc<RwLock<Arc<ReloadableSettings>>>,
+}
+
+impl ReloadableConfig {
+    /// Creates a handle holding `settings`
+    pub fn new(settings: ReloadableSettings) -> Self {
+        Self {
+            current: Arc::new(RwLock::new(Arc::new(settings))),
+        }
+    }
+
+    /// Creates a handle holding the reloadable settings of `config`
+    pub fn from_config(config: &AppConfig) -> Self {
+        Self::new(ReloadableSettings::from_config(config))
+    }
+
+    /// The settings in force right now
+    pub fn current(&self) -> Arc<ReloadableSettings> {
+        self.current
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// Replaces the settings, returning the names of those that changed
+    pub fn swap(&self, settings: ReloadableSettings) -> Vec<&'static str> {
+        let mut current = self.current.write().unwrap_or_else(|e| e.into_inner());
+        let changed = settings.changed_from(&current);
+        *current = Arc::new(settings);
+        changed
+    }
+}
+
+/// Why a reload was rejected
+#[derive(Debug, Error)]
+pub enum ReloadError {
+    #[error(transparent)]
+    Config(#[from] ConfigError),
+    #[error(transparent)]
+    LogLevel(#[from] LogFilterError),
+}
+
+/// Reloads configuration into a [`ReloadableConfig`] on SIGHUP
+pub struct ConfigWatcher {
+    loader: ConfigLoader,
+    settings: ReloadableConfig,
+}
+
+impl ConfigWatcher {
+    /// Creates a watcher that loads through `loader` and updates `settings`
+    pub fn new(loader: ConfigLoader, settings: ReloadableConfig) -> Self {
+        Self { loader, settings }
+    }
+
+    /// Loads and validates configuration once, swapping it in only if it is valid.
+    ///
+    /// Returns the names of the settings that changed.
+    pub fn reload(&self) -> Result<Vec<&'static str>, ReloadError> {
+        let config = self.loader.load()?;
+        let next = ReloadableSettings::from_config(&config);
+        telemetry::parse_log_filter(&next.log_level)?;
+
+        let changed = self.settings.swap(next.clone());
+        if changed.contains(&"log_level")
+            && let Err(e) = telemetry::set_log_filter(&next.log_level)
+        {
+            warn!(error = %e, "Reloaded log level was not applied");
+        }
+        if changed.contains(&"mail_spam") {
+            crate::mail::integration::install_spam_filter(&next.mail_spam);
+        }
+        Ok(changed)
+    }
+
+    /// Reload on every SIGHUP until `shutdown` fires
+    pub async fn run(self, shutdown: CancellationToken) -> std::io::Result<()> {
+        #[cfg(unix)]
+        {
+            let mut hangup =
+                tokio::signal::unix::signal(tokio::signal::unix::SignalKind::hangup())?;
+            loop {
+                tokio::select! {
+                    _ = shutdown.cancelled() => break,
+                    _ = hangup.recv() => self.reload_and_report(),
+                }
+            }
+        }
+        #[cfg(not(unix))]
+        shutdown.cancelled().await;
+        Ok(())
+    }
+
+    fn reload_and_report(&self) {
+        match self.reload() {
+            Ok(changed) => {
+                counter!("config_reloads_total", "outcome" => "applied").increment(1);
+                info!(changed = ?changed, "Configuration reloaded");
+            }
+            Err(e) => {
+                counter!("config_reloads_total", "outcome" => "rejected").increment(1);
+                warn!(error = %e, "Configuration reload rejected; keeping current settings");
+            }
+        }
+    }
+}
@@ -0,0 +1,695 @@
+//! # Configuration Resolution
+//!
+//! Tenant settings resolve in three layers: service defaults from [`AppConfig`],
+//! then the tenant's organization (if any), then the tenant itself. Organizations
+//! and tenants store only the settings they override, as a [`ConfigOverrides`]
+//! document, and resolution records which layer supplied each value.
+//!
+//! A tenant's scoring weights in its signal config take precedence over the
+//! weights in its settings document, so existing per-tenant weights keep working.
+//! Likewise the signal config's webhook URL is the tenant's notification URL
+//! unless the settings document lists others. Values set in the tenant's
+//! `tenant_configs` row take precedence over both.
+//!
+//! The scheduler, the executor and mail spam filtering apply the resolved
+//! settings at runtime through [`ConfigLayers::scheduler`],
+//! [`ConfigLayers::rate_limit_policy`] and [`ConfigLayers::mail_spam`].
+
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Duration, NaiveTime, Utc};
+use sea_orm::{DatabaseConnection, EntityTrait};
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use tracing::warn;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::config::{
+    AppConfig, MailSpamConfig, RateLimitPolicyConfig, RateLimitProviderOverride, SchedulerConfig,
+    is_valid_email_or_domain,
+};
+use crate::db::with_retry;
+use crate::error::RepositoryError;
+use crate::models::organization::Entity as Organization;
+use crate::models::tenant::Entity as Tenant;
+use crate::models::tenant_signal_config::{Model as TenantSignalConfigModel, ScoringWeights};
+use crate::repositories::{
+    MIN_SYNC_INTERVAL_SECONDS, TenantConfigRepository, TenantSignalConfigRepository,
+};
+
+/// Longest sync interval a tenant or organization may set (one week)
+pub const MAX_SYNC_INTERVAL_SECONDS: u64 = 604_800;
+
+/// Settings an organization or tenant may override
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct ConfigOverrides {
+    /// Weights used to score weak signals; must sum to 1.0
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scoring_weights: Option<ScoringWeights>,
+    /// Spam score (0.0 to 1.0) at or above which mail is treated as spam
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mail_spam_threshold: Option<f32>,
+    /// Addresses (`user@example.com`) and domains (`@example.com`) always allowed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mail_spam_allowlist: Option<Vec<String>>,
+    /// Addresses (`user@example.com`) and domains (`@example.com`) always blocked
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mail_spam_denylist: Option<Vec<String>>,
+    /// Webhook deliveries accepted per provider and minute
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub webhook_rate_limit_per_minute: Option<u32>,
+    /// Daily windows during which the scheduler holds back syncs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sync_quiet_hours: Option<Vec<QuietHours>>,
+    /// Sync interval in seconds for connections without an interval of their own
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(example = 1800)]
+    pub sync_interval_seconds: Option<u64>,
+    /// Sync intervals in seconds keyed by provider slug; these win over `sync_interval_seconds`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provider_sync_intervals: Option<BTreeMap<String, u64>>,
+    /// Retry backoff and attempt budgets keyed by provider slug
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rate_limit_overrides: Option<BTreeMap<String, TenantRateLimitOverride>>,
+    /// HTTPS URLs that receive signed notification deliveries
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notification_urls: Option<Vec<String>>,
+}
+
+/// Retry settings for one provider's jobs; unset fields keep the service policy
+///
+/// Concurrency caps apply to a whole executor instance and cannot be set per tenant.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct TenantRateLimitOverride {
+    /// Base retry interval in seconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(example = 10)]
+    pub base_seconds: Option<u64>,
+    /// Maximum retry interval in seconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(example = 1800)]
+    pub max_seconds: Option<u64>,
+    /// Jitter factor applied to each backoff (0.0 to 1.0)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(example = 0.2, minimum = 0.0, maximum = 1.0)]
+    pub jitter_factor: Option<f64>,
+    /// Attempts a job gets for failures other than rate limits
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(example = 5, minimum = 1)]
+    pub max_attempts: Option<u32>,
+    /// Attempts a job gets that end rate limited
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(example = 60, minimum = 1)]
+    pub max_rate_limited_attempts: Option<u32>,
+}
+
+impl TenantRateLimitOverride {
+    /// Lay these settings over a service provider override
+    fn apply_to(&self, base: &mut RateLimitProviderOverride) {
+        base.base_seconds = self.base_seconds.or(base.base_seconds);
+        base.max_seconds = self.max_seconds.or(base.max_seconds);
+        base.jitter_factor = self.jitter_factor.or(base.jitter_factor);
+        base.max_attempts = self.max_attempts.or(base.max_attempts);
+        base.max_rate_limited_attempts = self
+            .max_rate_limited_attempts
+            .or(base.max_rate_limited_attempts);
+    }
+
+    fn validate(&self, provider: &str) -> Result<(), RepositoryError> {
+        if let (Some(base), Some(max)) = (self.base_seconds, self.max_seconds)
+            && base > max
+        {
+            return Err(RepositoryError::Validation(format!(
+                "rate_limit_overrides.{}: base_seconds ({}) must not exceed max_seconds ({})",
+                provider, base, max
+            )));
+        }
+        if let Some(jitter) = self.jitter_factor
+            && !(0.0..=1.0).contains(&jitter)
+        {
+            return Err(RepositoryError::Validation(format!(
+                "rate_limit_overrides.{}: jitter_factor must be between 0.0 and 1.0",
+                provider
+            )));
+        }
+        if self.max_attempts == Some(0) || self.max_rate_limited_attempts == Some(0) {
+            return Err(RepositoryError::Validation(format!(
+                "rate_limit_overrides.{}: attempt budgets must be at least 1",
+                provider
+            )));
+        }
+        Ok(())
+    }
+}
+
+impl From<&RateLimitProviderOverride> for TenantRateLimitOverride {
+    fn from(policy: &RateLimitProviderOverride) -> Self {
+        Self {
+            base_seconds: policy.base_seconds,
+            max_seconds: policy.max_seconds,
+            jitter_factor: policy.jitter_factor,
+            max_attempts: policy.max_attempts,
+            max_rate_limited_attempts: policy.max_rate_limited_attempts,
+        }
+    }
+}
+
+/// A daily UTC window in which scheduled syncs wait, e.g. a provider maintenance window
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct QuietHours {
+    /// Start of the window as `HH:MM` UTC
+    #[schema(example = "01:00")]
+    pub start: String,
+    /// End of the window (exclusive) as `HH:MM` UTC; earlier than `start` wraps past midnight
+    #[schema(example = "03:00")]
+    pub end: String,
+    /// Provider the window applies to; all providers when absent
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provider_slug: Option<String>,
+}
+
+impl QuietHours {
+    fn bounds(&self) -> Option<(NaiveTime, NaiveTime)> {
+        let start = NaiveTime::parse_from_str(&self.start, "%H:%M").ok()?;
+        let end = NaiveTime::parse_from_str(&self.end, "%H:%M").ok()?;
+        (start != end).then_some((start, end))
+    }
+
+    /// Whether the window applies to syncs of `provider_slug`
+    pub fn applies_to(&self, provider_slug: &str) -> bool {
+        self.provider_slug
+            .as_deref()
+            .is_none_or(|slug| slug == provider_slug)
+    }
+
+    /// End of the window occurrence covering `at`, if `at` falls inside one
+    pub fn ends_after(&self, at: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let (start, end) = self.bounds()?;
+        let time = at.time();
+        let day = at.date_naive();
+        let end_on = |day: chrono::NaiveDate| day.and_time(end).and_utc();
+        if start < end {
+            (start <= time && time < end).then(|| end_on(day))
+        } else if time >= start {
+            Some(end_on(day + Duration::days(1)))
+        } else {
+            (time < end).then(|| end_on(day))
+        }
+    }
+}
+
+/// Earliest time at or after `at` outside every quiet-hours window for the provider
+pub fn defer_past_quiet_hours(
+    windows: &[QuietHours],
+    provider_slug: &str,
+    mut at: DateTime<Utc>,
+) -> DateTime<Utc> {
+    // Each pass moves past one window; overlapping windows need at most one pass each
+    for _ in 0..=windows.len() {
+        match windows
+            .iter()
+            .filter(|window| window.applies_to(provider_slug))
+            .find_map(|window| window.ends_after(at))
+        {
+            Some(end) => at = end,
+            None => break,
+        }
+    }
+    at
+}
+
+impl ConfigOverrides {
+    /// Parse a stored settings document, falling back to no overrides when malformed
+    pub fn from_json(settings: Option<&JsonValue>) -> Self {
+        let Some(settings) = settings else {
+            return Self::default();
+        };
+
+        serde_json::from_value(settings.clone()).unwrap_or_else(|_| {
+            warn!(settings = ?settings, "Failed to parse setting overrides; ignoring them");
+            Self::default()
+        })
+    }
+
+    /// Serialize for storage; `None` when nothing is overridden
+    pub fn to_json(&self) -> Option<JsonValue> {
+        if *self == Self::default() {
+            return None;
+        }
+        serde_json::to_value(self).ok()
+    }
+
+    /// Check every override is within the bounds the service accepts
+    pub fn validate(&self) -> Result<(), RepositoryError> {
+        if let Some(weights) = &self.scoring_weights
+            && !TenantSignalConfigModel::validate_weights(weights)
+        {
+            return Err(RepositoryError::validation_error(
+                "scoring_weights must sum to 1.0",
+            ));
+        }
+
+        if let Some(threshold) = self.mail_spam_threshold
+            && !(0.0..=1.0).contains(&threshold)
+        {
+            return Err(RepositoryError::validation_error(
+                "mail_spam_threshold must be between 0.0 and 1.0",
+            ));
+        }
+
+        for entry in self
+            .mail_spam_allowlist
+            .iter()
+            .chain(self.mail_spam_denylist.iter())
+            .flatten()
+        {
+            if !is_valid_email_or_domain(entry) {
+                return Err(RepositoryError::Validation(format!(
+                    "'{}' is not an email address or domain",
+                    entry
+                )));
+            }
+        }
+
+        if self.webhook_rate_limit_per_minute == Some(0) {
+            return Err(RepositoryError::validation_error(
+                "webhook_rate_limit_per_minute must be greater than 0",
+            ));
+        }
+
+        for window in self.sync_quiet_hours.iter().flatten() {
+            if window.bounds().is_none() {
+                return Err(RepositoryError::Validation(format!(
+                    "sync_quiet_hours window '{}'-'{}' needs distinct HH:MM start and end times",
+                    window.start, window.end
+                )));
+            }
+        }
+
+        let intervals =
+            self.sync_interval_seconds
+                .map(|interval| ("sync_interval_seconds".to_string(), interval))
+                .into_iter()
+                .chain(self.provider_sync_intervals.iter().flatten().map(
+                    |(provider, interval)| {
+                        (format!("provider_sync_intervals.{}", provider), *interval)
+                    },
+                ));
+        for (field, interval) in intervals {
+            if !(MIN_SYNC_INTERVAL_SECONDS..=MAX_SYNC_INTERVAL_SECONDS).contains(&interval) {
+                return Err(RepositoryError::Validation(format!(
+                    "{} must be between {} and {} seconds",
+                    field, MIN_SYNC_INTERVAL_SECONDS, MAX_SYNC_INTERVAL_SECONDS
+                )));
+            }
+        }
+
+        for (provider, policy) in self.rate_limit_overrides.iter().flatten() {
+            policy.validate(provider)?;
+        }
+
+        for url in self.notification_urls.iter().flatten() {
+            TenantSignalConfigRepository::validate_webhook_url(url)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Layer that supplied a resolved setting
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SettingSource {
+    Service,
+    Organization,
+    Tenant,
+}
+
+/// A resolved setting and the layer it came from
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct Resolved<T> {
+    pub value: T,
+    pub source: SettingSource,
+}
+
+/// Settings in effect for a tenant
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct EffectiveConfig {
+    /// Organization the tenant inherits from, if any
+    #[schema(value_type = Option<String>)]
+    pub organization_id: Option<Uuid>,
+    pub scoring_weights: Resolved<ScoringWeights>,
+    pub mail_spam_threshold: Resolved<f32>,
+    pub mail_spam_allowlist: Resolved<Vec<String>>,
+    pub mail_spam_denylist: Resolved<Vec<String>>,
+    pub webhook_rate_limit_per_minute: Resolved<u32>,
+    pub sync_quiet_hours: Resolved<Vec<QuietHours>>,
+    pub sync_interval_seconds: Resolved<u64>,
+    pub provider_sync_intervals: Resolved<BTreeMap<String, u64>>,
+    pub rate_limit_overrides: Resolved<BTreeMap<String, TenantRateLimitOverride>>,
+    pub notification_urls: Resolved<Vec<String>>,
+}
+
+/// Override layers that apply to one tenant
+#[derive(Debug, Clone, Default)]
+pub struct ConfigLayers {
+    pub organization_id: Option<Uuid>,
+    pub organization: ConfigOverrides,
+    pub tenant: ConfigOverrides,
+}
+
+impl ConfigLayers {
+    /// Load the organization and tenant layers for a tenant.
+    ///
+    /// An unknown tenant has no layers and resolves to service defaults.
+    pub async fn load(db: &DatabaseConnection, tenant_id: Uuid) -> Result<Self, RepositoryError> {
+        let Some(tenant) = with_retry(|| Tenant::find_by_id(tenant_id).one(db))
+            .await
+            .map_err(RepositoryError::database_error)?
+        else {
+            return Ok(Self::default());
+        };
+
+        let organization = match tenant.organization_id {
+            Some(organization_id) => {
+                with_retry(|| Organization::find_by_id(organization_id).one(db))
+                    .await
+                    .map_err(RepositoryError::database_error)?
+            }
+            None => None,
+        };
+
+        let mut tenant_layer = ConfigOverrides::from_json(tenant.settings.as_ref());
+        if let Some(signal_config) = TenantSignalConfigRepository::new(db).get(tenant_id).await? {
+            if let Some(weights) = signal_config
+                .scoring_weights
+                .and_then(|json| serde_json::from_value(json).ok())
+            {
+                tenant_layer.scoring_weights = Some(weights);
+            }
+            if tenant_layer.notification_urls.is_none() {
+                tenant_layer.notification_urls = signal_config.webhook_url.map(|url| vec![url]);
+            }
+        }
+        if let Some(config) = TenantConfigRepository::new(db).get(tenant_id).await? {
+            config.settings().apply_to(&mut tenant_layer);
+        }
+
+        Ok(Self {
+            organization_id: tenant.organization_id,
+            organization: ConfigOverrides::from_json(
+                organization.as_ref().and_then(|o| o.settings.as_ref()),
+            ),
+            tenant: tenant_layer,
+        })
+    }
+
+    /// Scoring weights in effect; the service default is the built-in weighting
+    pub fn scoring_weights(&self) -> Resolved<ScoringWeights> {
+        pick(
+            ScoringWeights::default(),
+            self.organization.scoring_weights.as_ref(),
+            self.tenant.scoring_weights.as_ref(),
+        )
+    }
+
+    /// Quiet-hours windows in effect; the service default is none
+    pub fn sync_quiet_hours(&self) -> Resolved<Vec<QuietHours>> {
+        pick(
+            Vec::new(),
+            self.organization.sync_quiet_hours.as_ref(),
+            self.tenant.sync_quiet_hours.as_ref(),
+        )
+    }
+
+    /// URLs that receive the tenant's notifications; the service default is none
+    pub fn notification_urls(&self) -> Resolved<Vec<String>> {
+        pick(
+            Vec::new(),
+            self.organization.notification_urls.as_ref(),
+            self.tenant.notification_urls.as_ref(),
+        )
+    }
+
+    /// Scheduler settings with the tenant's sync intervals in place of the service ones
+    pub fn scheduler(&self, service: &SchedulerConfig) -> SchedulerConfig {
+        let (organization, tenant) = (&self.organization, &self.tenant);
+        let bounded = |interval: u64| {
+            interval.clamp(
+                MIN_SYNC_INTERVAL_SECONDS,
+                service.max_overridden_interval_seconds,
+            )
+        };
+        let mut scheduler = service.clone();
+        if let Some(interval) = tenant
+            .sync_interval_seconds
+            .or(organization.sync_interval_seconds)
+        {
+            scheduler.default_interval_seconds = bounded(interval);
+        }
+        if let Some(intervals) = tenant
+            .provider_sync_intervals
+            .as_ref()
+            .or(organization.provider_sync_intervals.as_ref())
+        {
+            scheduler.provider_intervals.extend(
+                intervals
+                    .iter()
+                    .map(|(provider, interval)| (provider.clone(), bounded(*interval))),
+            );
+        }
+        scheduler
+    }
+
+    /// Rate limit policy with the tenant's provider overrides laid over the service ones
+    pub fn rate_limit_policy(&self, service: &RateLimitPolicyConfig) -> RateLimitPolicyConfig {
+        let mut policy = service.clone();
+        if let Some(overrides) = self
+            .tenant
+            .rate_limit_overrides
+            .as_ref()
+            .or(self.organization.rate_limit_overrides.as_ref())
+        {
+            for (provider, tenant_policy) in overrides {
+                tenant_policy.apply_to(
+                    policy
+                        .provider_overrides
+                        .entry(provider.clone())
+                        .or_default(),
+                );
+            }
+        }
+        policy
+    }
+
+    /// Mail spam settings with the tenant's threshold and lists
+    pub fn mail_spam(&self, service: &MailSpamConfig) -> MailSpamConfig {
+        let (organization, tenant) = (&self.organization, &self.tenant);
+        MailSpamConfig {
+            threshold: pick(
+                service.threshold,
+                organization.mail_spam_threshold.as_ref(),
+                tenant.mail_spam_threshold.as_ref(),
+            )
+            .value,
+            allowlist: pick(
+                service.allowlist.clone(),
+                organization.mail_spam_allowlist.as_ref(),
+                tenant.mail_spam_allowlist.as_ref(),
+            )
+            .value,
+            denylist: pick(
+                service.denylist.clone(),
+                organization.mail_spam_denylist.as_ref(),
+                tenant.mail_spam_denylist.as_ref(),
+            )
+            .value,
+        }
+    }
+
+    /// Resolve every setting against the service defaults
+    pub fn resolve(&self, service: &AppConfig) -> EffectiveConfig {
+        let (organization, tenant) = (&self.organization, &self.tenant);
+        EffectiveConfig {
+            organization_id: self.organization_id,
+            scoring_weights: self.scoring_weights(),
+            mail_spam_threshold: pick(
+                service.mail_spam.threshold,
+                organization.mail_spam_threshold.as_ref(),
+                tenant.mail_spam_threshold.as_ref(),
+            ),
+            mail_spam_allowlist: pick(
+                service.mail_spam.allowlist.clone(),
+                organization.mail_spam_allowlist.as_ref(),
+                tenant.mail_spam_allowlist.as_ref(),
+            ),
+            mail_spam_denylist: pick(
+                service.mail_spam.denylist.clone(),
+                organization.mail_spam_denylist.as_ref(),
+                tenant.mail_spam_denylist.as_ref(),
+            ),
+            webhook_rate_limit_per_minute: pick(
+                service.webhook_rate_limit_per_minute,
+                organization.webhook_rate_limit_per_minute.as_ref(),
+                tenant.webhook_rate_limit_per_minute.as_ref(),
+            ),
+            sync_quiet_hours: self.sync_quiet_hours(),
+            sync_interval_seconds: pick(
+                service.scheduler.default_interval_seconds,
+                organization.sync_interval_seconds.as_ref(),
+                tenant.sync_interval_seconds.as_ref(),
+            ),
+            provider_sync_intervals: pick(
+                service.scheduler.provider_intervals.clone(),
+                organization.provider_sync_intervals.as_ref(),
+                tenant.provider_sync_intervals.as_ref(),
+            ),
+            rate_limit_overrides: pick(
+                service
+                    .rate_limit_policy
+                    .provider_overrides
+                    .iter()
+                    .map(|(provider, policy)| (provider.clone(), policy.into()))
+                    .collect(),
+                organization.rate_limit_overrides.as_ref(),
+                tenant.rate_limit_overrides.as_ref(),
+            ),
+            notification_urls: self.notification_urls(),
+        }
+    }
+}
+
+/// Most specific value among the layers
+fn pick<T: Clone>(service: T, organization: Option<&T>, tenant: Option<&T>) -> Resolved<T> {
+    match (tenant, organization) {
+        (Some(value), _) => Resolved {
+            value: value.clone(),
+            source: SettingSource::Tenant,
+        },
+        (None, Some(value)) => Resolved {
+            value: value.clone(),
+            source: SettingSource::Organization,
+        },
+        (None, None) => Resolved {
+            value: service,
+            source: SettingSource::Service,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tenant_overrides_organization_which_overrides_service() {
+        let service = AppConfig::default();
+        let layers = ConfigLayers {
+            organization_id: Some(Uuid::new_v4()),
+            organization: ConfigOverrides {
+                mail_spam_threshold: Some(0.6),
+                mail_spam_denylist: Some(vec!["@spam.example".to_string()]),
+                webhook_rate_limit_per_minute: Some(600),
+                ..Default::default()
+            },
+            tenant: ConfigOverrides {
+                webhook_rate_limit_per_minute: Some(50),
+                ..Default::default()
+            },
+        };
+
+        let effective = layers.resolve(&service);
+
+        assert_eq!(effective.scoring_weights.source, SettingSource::Service);
+        assert_eq!(effective.mail_spam_threshold.value, 0.6);
+        assert_eq!(
+            effective.mail_spam_threshold.source,
+            SettingSource::Organization
+        );
+        assert_eq!(
+            effective.mail_spam_denylist.value,
+            vec!["@spam.example".to_string()]
+        );
+        assert_eq!(
+            effective.mail_spam_allowlist.value,
+            service.mail_spam.allowlist
+        );
+        assert_eq!(effective.webhook_rate_limit_per_minute.value, 50);
+        assert_eq!(
+            effective.webhook_rate_limit_per_minute.source,
+            SettingSource::Tenant
+        );
+    }
+
+    #[test]
+    fn test_overrides_validation_and_storage() {
+        assert_eq!(ConfigOverrides::default().to_json(), None);
+        assert_eq!(
+            ConfigOverrides::from_json(Some(&serde_json::json!("not an object"))),
+            ConfigOverrides::default()
+        );
+
+        let invalid = [
+            ConfigOverrides {
+                mail_spam_threshold: Some(1.5),
+                ..Default::default()
+            },
+            ConfigOverrides {
+                mail_spam_allowlist: Some(vec!["not a domain".to_string()]),
+                ..Default::default()
+            },
+            ConfigOverrides {
+                scoring_weights: Some(ScoringWeights {
+                    impact: 1.0,
+                    ..ScoringWeights::default()
+                }),
+                ..Default::default()
+            },
+            ConfigOverrides {
+                sync_quiet_hours: Some(vec![QuietHours {
+                    start: "25:00".to_string(),
+                    end: "03:00".to_string(),
+                    provider_slug: None,
+                }]),
+                ..Default::default()
+            },
+        ];
+        for overrides in invalid {
+            assert!(overrides.validate().is_err(), "{:?}", overrides);
+        }
+    }
+
+    #[test]
+    fn test_quiet_hours_defer_to_window_end() {
+        let at = |s: &str| DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc);
+        let window = |start: &str, end: &str, provider_slug: Option<&str>| QuietHours {
+            start: start.to_string(),
+            end: end.to_string(),
+            provider_slug: provider_slug.map(str::to_string),
+        };
+        let windows = vec![
+            window("01:00", "03:00", None),
+            window("02:30", "04:00", Some("github")),
+            window("23:00", "00:30", Some("slack")),
+        ];
+
+        assert_eq!(
+            defer_past_quiet_hours(&windows, "gmail", at("2025-01-01T01:15:00Z")),
+            at("2025-01-01T03:00:00Z")
+        );
+        assert_eq!(
+            defer_past_quiet_hours(&windows, "github", at("2025-01-01T01:15:00Z")),
+            at("2025-01-01T04:00:00Z")
+        );
+        assert_eq!(
+            defer_past_quiet_hours(&windows, "slack", at("2025-01-01T23:10:00Z")),
+            at("2025-01-02T00:30:00Z")
+        );
+        assert_eq!(
+            defer_past_quiet_hours(&windows, "gmail", at("2025-01-01T03:00:00Z")),
+            at("2025-01-01T03:00:00Z")
+        );
+    }
+}
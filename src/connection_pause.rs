@@ -0,0 +1,509 @@
+//! # Connection Auto-Pause Policy
+//!
+//! A connection whose tokens have expired or been revoked fails every sync with
+//! an authorization error. After a configurable number of consecutive failures,
+//! or at once when token refresh fails permanently, the policy moves the
+//! connection to `needs_reauth`, which takes it out of the scheduler, and
+//! returns it to `active` once the tenant re-authorizes. Each transition is
+//! written to the audit log and announced to the tenant's notification webhook
+//! (`webhook_url` in the tenant signal config).
+//!
+//! The failure streak and the last error, which the connections API reports as
+//! the status reason, are kept in connection metadata under `auth`.
+
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use sea_orm::{ActiveModelTrait, DatabaseConnection, DbErr, EntityTrait, Set};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value as JsonValue, json};
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+use crate::models::connection::{
+    ActiveModel as ConnectionActiveModel, Entity as ConnectionEntity, Model as ConnectionModel,
+};
+use crate::repositories::{AuditLogRepository, NewAuditLogEntry, TenantSignalConfigRepository};
+use crate::slo::{self, SloOperation};
+
+/// Connection status set while an operator has paused a connection
+pub const STATUS_PAUSED: &str = "paused";
+
+/// Connection status set while a connection waits for the tenant to re-authorize
+pub const STATUS_NEEDS_REAUTH: &str = "needs_reauth";
+
+/// Default number of consecutive auth failures before a connection needs re-authorization
+pub const DEFAULT_PAUSE_THRESHOLD: u32 = 3;
+
+/// Audit action of connections this policy paused before `needs_reauth` existed
+pub const LEGACY_AUTO_PAUSED_AUDIT_ACTION: &str = "connection.auto_paused";
+
+/// Audit actor recorded for automatic transitions
+pub const AUTO_PAUSE_AUDIT_ACTOR: &str = "system:auth-pause-policy";
+
+/// Connection metadata key holding [`ConnectionAuthHealth`]
+const METADATA_KEY: &str = "auth";
+
+/// Longest provider error kept in metadata and notifications
+const MAX_ERROR_CHARS: usize = 500;
+
+/// Authorization failure streak stored in connection metadata
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ConnectionAuthHealth {
+    #[serde(default)]
+    pub consecutive_failures: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_failure_at: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+    /// When the policy took the connection out of scheduling
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub paused_at: Option<DateTime<Utc>>,
+}
+
+impl ConnectionAuthHealth {
+    /// Parse the streak from connection metadata, defaulting when absent or malformed
+    pub fn from_connection_metadata(metadata: Option<&JsonValue>) -> Self {
+        metadata
+            .and_then(|metadata| metadata.get(METADATA_KEY))
+            .and_then(|value| serde_json::from_value(value.clone()).ok())
+            .unwrap_or_default()
+    }
+
+    /// Why a connection in `status` is not scheduled, if the policy put it there
+    pub fn status_reason(&self, status: &str) -> Option<String> {
+        (status == STATUS_NEEDS_REAUTH).then(|| {
+            self.last_error
+                .clone()
+                .unwrap_or_else(|| "authorization failed".to_string())
+        })
+    }
+
+    /// Write the streak back into the metadata object, preserving other keys
+    pub fn into_connection_metadata(&self, existing: Option<&JsonValue>) -> JsonValue {
+        let mut root = match existing {
+            Some(JsonValue::Object(map)) => map.clone(),
+            _ => Map::new(),
+        };
+        if *self == Self::default() {
+            root.remove(METADATA_KEY);
+        } else {
+            root.insert(
+                METADATA_KEY.to_string(),
+                serde_json::to_value(self).unwrap_or(JsonValue::Object(Map::new())),
+            );
+        }
+        JsonValue::Object(root)
+    }
+}
+
+/// A status change made by the policy
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PauseTransition {
+    NeedsReauth,
+    Resumed,
+}
+
+impl PauseTransition {
+    /// Audit log action for the transition
+    pub fn audit_action(&self) -> &'static str {
+        match self {
+            PauseTransition::NeedsReauth => "connection.needs_reauth",
+            PauseTransition::Resumed => "connection.auto_resumed",
+        }
+    }
+
+    /// Event type sent to the tenant's notification webhook
+    pub fn notification_type(&self) -> &'static str {
+        match self {
+            PauseTransition::NeedsReauth => "connection.needs_reauth",
+            PauseTransition::Resumed => "connection.resumed",
+        }
+    }
+}
+
+/// Moves connections to `needs_reauth` after auth failures and back on re-authorization
+#[derive(Clone)]
+pub struct AuthPausePolicy {
+    db: Arc<DatabaseConnection>,
+    threshold: u32,
+}
+
+impl AuthPausePolicy {
+    /// Create a policy acting after `threshold` consecutive sync failures; 0 never acts on them
+    pub fn new(db: Arc<DatabaseConnection>, threshold: u32) -> Self {
+        Self { db, threshold }
+    }
+
+    /// Record a sync authorization failure that token refresh could not recover from.
+    ///
+    /// Returns [`PauseTransition::NeedsReauth`] when this failure reached the threshold.
+    pub async fn record_auth_failure(
+        &self,
+        connection_id: Uuid,
+        error_msg: &str,
+    ) -> Result<Option<PauseTransition>, DbErr> {
+        self.record_failure(connection_id, error_msg, false).await
+    }
+
+    /// Move a connection to `needs_reauth` at once, e.g. after its refresh token was revoked
+    pub async fn mark_needs_reauth(
+        &self,
+        connection_id: Uuid,
+        error_msg: &str,
+    ) -> Result<Option<PauseTransition>, DbErr> {
+        self.record_failure(connection_id, error_msg, true).await
+    }
+
+    async fn record_failure(
+        &self,
+        connection_id: Uuid,
+        error_msg: &str,
+        immediate: bool,
+    ) -> Result<Option<PauseTransition>, DbErr> {
+        let Some(connection) = ConnectionEntity::find_by_id(connection_id)
+            .one(&*self.db)
+            .await?
+        else {
+            return Ok(None);
+        };
+
+        let now = Utc::now();
+        let mut health =
+            ConnectionAuthHealth::from_connection_metadata(connection.metadata.as_ref());
+        health.consecutive_failures = health.consecutive_failures.saturating_add(1);
+        health.last_failure_at = Some(now);
+        health.last_error = Some(error_msg.chars().take(MAX_ERROR_CHARS).collect());
+
+        let pause = connection.status == "active"
+            && (immediate || (self.threshold > 0 && health.consecutive_failures >= self.threshold));
+        if pause {
+            health.paused_at = Some(now);
+        }
+
+        let mut active: ConnectionActiveModel = connection.clone().into();
+        active.metadata = Set(Some(
+            health.into_connection_metadata(connection.metadata.as_ref()),
+        ));
+        if pause {
+            active.status = Set(STATUS_NEEDS_REAUTH.to_string());
+        }
+        active.updated_at = Set(now.into());
+        let updated = active.update(&*self.db).await?;
+
+        if !pause {
+            return Ok(None);
+        }
+        self.announce(&updated, PauseTransition::NeedsReauth, &health)
+            .await;
+        Ok(Some(PauseTransition::NeedsReauth))
+    }
+
+    /// Clear the failure streak after a successful sync
+    pub async fn record_auth_success(&self, connection_id: Uuid) -> Result<(), DbErr> {
+        let Some(connection) = ConnectionEntity::find_by_id(connection_id)
+            .one(&*self.db)
+            .await?
+        else {
+            return Ok(());
+        };
+        let health = ConnectionAuthHealth::from_connection_metadata(connection.metadata.as_ref());
+        if health.consecutive_failures == 0 {
+            return Ok(());
+        }
+
+        let mut active: ConnectionActiveModel = connection.clone().into();
+        active.metadata = Set(Some(
+            ConnectionAuthHealth::default().into_connection_metadata(connection.metadata.as_ref()),
+        ));
+        active.updated_at = Set(Utc::now().into());
+        active.update(&*self.db).await?;
+        Ok(())
+    }
+
+    /// Clear the failure streak after the tenant re-authorized, returning a connection
+    /// in `needs_reauth` to `active`.
+    ///
+    /// Returns [`PauseTransition::Resumed`] when the connection was waiting for it.
+    pub async fn resume_after_reauthorization(
+        &self,
+        connection_id: Uuid,
+    ) -> Result<Option<PauseTransition>, DbErr> {
+        let Some(connection) = ConnectionEntity::find_by_id(connection_id)
+            .one(&*self.db)
+            .await?
+        else {
+            return Ok(None);
+        };
+        let health = ConnectionAuthHealth::from_connection_metadata(connection.metadata.as_ref());
+        // An operator's pause stays in place; `paused` with `paused_at` set is how
+        // this policy marked connections before `needs_reauth` existed
+        let resume = connection.status == STATUS_NEEDS_REAUTH
+            || (connection.status == STATUS_PAUSED && health.paused_at.is_some());
+        if !resume && health == ConnectionAuthHealth::default() {
+            return Ok(None);
+        }
+
+        let mut active: ConnectionActiveModel = connection.clone().into();
+        active.metadata = Set(Some(
+            ConnectionAuthHealth::default().into_connection_metadata(connection.metadata.as_ref()),
+        ));
+        if resume {
+            active.status = Set("active".to_string());
+        }
+        active.updated_at = Set(Utc::now().into());
+        let updated = active.update(&*self.db).await?;
+
+        if !resume {
+            return Ok(None);
+        }
+        self.announce(&updated, PauseTransition::Resumed, &health)
+            .await;
+        Ok(Some(PauseTransition::Resumed))
+    }
+
+    /// Log, audit and notify a transition; failures here never undo the transition
+    async fn announce(
+        &self,
+        connection: &ConnectionModel,
+        transition: PauseTransition,
+        health: &ConnectionAuthHealth,
+    ) {
+        let details = json!({
+            "provider_slug": connection.provider_slug,
+            "consecutive_failures": health.consecutive_failures,
+            "last_error": health.last_error,
+            "threshold": self.threshold,
+        });
+        match transition {
+            PauseTransition::NeedsReauth => warn!(
+                connection_id = %connection.id,
+                tenant_id = %connection.tenant_id,
+                provider_slug = %connection.provider_slug,
+                consecutive_failures = health.consecutive_failures,
+                "Connection needs re-authorization after authorization failures"
+            ),
+            PauseTransition::Resumed => info!(
+                connection_id = %connection.id,
+                tenant_id = %connection.tenant_id,
+                provider_slug = %connection.provider_slug,
+                "Resumed connection after re-authorization"
+            ),
+        }
+
+        let reason = match transition {
+            PauseTransition::NeedsReauth => format!(
+                "{} consecutive authorization failures",
+                health.consecutive_failures
+            ),
+            PauseTransition::Resumed => "connection re-authorized".to_string(),
+        };
+        if let Err(err) = AuditLogRepository::new((*self.db).clone())
+            .record(NewAuditLogEntry {
+                tenant_id: Some(connection.tenant_id),
+                actor: AUTO_PAUSE_AUDIT_ACTOR.to_string(),
+                action: transition.audit_action().to_string(),
+                target_type: "connection".to_string(),
+                target_id: connection.id.to_string(),
+                reason: Some(reason.clone()),
+                details: Some(details.clone()),
+            })
+            .await
+        {
+            error!(connection_id = %connection.id, error = ?err, "Failed to audit connection transition");
+        }
+
+        let config_repo = TenantSignalConfigRepository::new(&self.db);
+        let webhook_url = match config_repo.get_webhook_url(connection.tenant_id).await {
+            Ok(Some(url)) if url.to_lowercase().starts_with("https://") => url,
+            Ok(_) => return,
+            Err(err) => {
+                error!(tenant_id = %connection.tenant_id, error = ?err, "Failed to load notification webhook");
+                return;
+            }
+        };
+        let signing_secret = match config_repo
+            .get_or_create_signing_secret(connection.tenant_id)
+            .await
+        {
+            Ok(secret) => secret,
+            Err(err) => {
+                error!(tenant_id = %connection.tenant_id, error = ?err, "Failed to load webhook signing secret");
+                return;
+            }
+        };
+        let payload = json!({
+            "type": transition.notification_type(),
+            "tenant_id": connection.tenant_id,
+            "connection_id": connection.id,
+            "provider": connection.provider_slug,
+            "reason": reason,
+            "details": details,
+            "occurred_at": Utc::now().to_rfc3339(),
+        });
+        let connection_id = connection.id;
+        let provider_slug = connection.provider_slug.clone();
+        let db = self.db.clone();
+        tokio::spawn(async move {
+            let body = payload.to_string().into_bytes();
+            let delivered = match crate::webhook_signing::signed_post(
+                &crate::egress::client(),
+                &webhook_url,
+                &signing_secret,
+                body,
+            )
+            .send()
+            .await
+            {
+                Ok(response) if response.status().is_success() => true,
+                Ok(response) => {
+                    warn!(
+                        connection_id = %connection_id,
+                        status = %response.status(),
+                        "Tenant notification webhook rejected connection transition"
+                    );
+                    false
+                }
+                Err(err) => {
+                    warn!(
+                        connection_id = %connection_id,
+                        error = %err,
+                        "Failed to notify tenant of connection transition"
+                    );
+                    false
+                }
+            };
+            slo::record(&db, &provider_slug, SloOperation::Notification, delivered).await;
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures;
+
+    async fn setup() -> (Arc<DatabaseConnection>, Uuid) {
+        let (db, _, connection_id) = test_fixtures::setup_connection().await;
+        ConnectionActiveModel {
+            id: Set(connection_id),
+            metadata: Set(Some(json!({ "sync": { "interval_seconds": 900 } }))),
+            ..Default::default()
+        }
+        .update(&db)
+        .await
+        .unwrap();
+        (Arc::new(db), connection_id)
+    }
+
+    #[tokio::test]
+    async fn test_needs_reauth_after_threshold_and_resumes_on_reauthorization() {
+        let (db, connection_id) = setup().await;
+        let policy = AuthPausePolicy::new(db.clone(), 2);
+        let load = || async {
+            ConnectionEntity::find_by_id(connection_id)
+                .one(&*db)
+                .await
+                .unwrap()
+                .unwrap()
+        };
+
+        // A success in between resets the streak
+        assert_eq!(
+            policy
+                .record_auth_failure(connection_id, "401")
+                .await
+                .unwrap(),
+            None
+        );
+        policy.record_auth_success(connection_id).await.unwrap();
+        assert_eq!(
+            policy
+                .record_auth_failure(connection_id, "401")
+                .await
+                .unwrap(),
+            None
+        );
+        assert_eq!(
+            policy
+                .record_auth_failure(connection_id, "invalid_grant")
+                .await
+                .unwrap(),
+            Some(PauseTransition::NeedsReauth)
+        );
+
+        let paused = load().await;
+        assert_eq!(paused.status, STATUS_NEEDS_REAUTH);
+        let health = ConnectionAuthHealth::from_connection_metadata(paused.metadata.as_ref());
+        assert_eq!(health.consecutive_failures, 2);
+        assert_eq!(
+            health.status_reason(&paused.status).as_deref(),
+            Some("invalid_grant")
+        );
+        assert_eq!(
+            paused.metadata.as_ref().unwrap()["sync"]["interval_seconds"],
+            900
+        );
+
+        assert_eq!(
+            policy
+                .resume_after_reauthorization(connection_id)
+                .await
+                .unwrap(),
+            Some(PauseTransition::Resumed)
+        );
+        let resumed = load().await;
+        assert_eq!(resumed.status, "active");
+        assert!(resumed.metadata.as_ref().unwrap().get("auth").is_none());
+
+        let audit = AuditLogRepository::new((*db).clone())
+            .list_for_target("connection", &connection_id.to_string())
+            .await
+            .unwrap();
+        let mut actions: Vec<_> = audit.iter().map(|entry| entry.action.as_str()).collect();
+        actions.sort_unstable();
+        assert_eq!(
+            actions,
+            vec!["connection.auto_resumed", "connection.needs_reauth"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_permanent_refresh_failure_needs_reauth_at_once() {
+        let (db, connection_id) = setup().await;
+        // Sync failures alone never act with a zero threshold
+        let policy = AuthPausePolicy::new(db.clone(), 0);
+        assert_eq!(
+            policy
+                .record_auth_failure(connection_id, "401")
+                .await
+                .unwrap(),
+            None
+        );
+        assert_eq!(
+            policy
+                .mark_needs_reauth(connection_id, "invalid_grant: token revoked")
+                .await
+                .unwrap(),
+            Some(PauseTransition::NeedsReauth)
+        );
+        // Already out of scheduling, so no second transition
+        assert_eq!(
+            policy
+                .mark_needs_reauth(connection_id, "invalid_grant")
+                .await
+                .unwrap(),
+            None
+        );
+
+        let connection = ConnectionEntity::find_by_id(connection_id)
+            .one(&*db)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(connection.status, STATUS_NEEDS_REAUTH);
+        let health = ConnectionAuthHealth::from_connection_metadata(connection.metadata.as_ref());
+        assert_eq!(health.consecutive_failures, 3);
+        assert!(health.status_reason("active").is_none());
+    }
+}
@@ -0,0 +1,625 @@
+//! CircleCI connector implementation
+//!
+//! CircleCI project webhooks for completed workflows are verified in
+//! [`crate::webhook_verification`] and land on the tenant's CircleCI
+//! connection for the organization, created on first delivery. When an API
+//! token is configured, each sync also lists the organization's recent
+//! pipelines and their workflows so results missed by webhooks still become
+//! CI result signals.
+
+use anyhow::anyhow;
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, SecondsFormat, Utc};
+use reqwest::{Client, StatusCode};
+use serde::Deserialize;
+use std::sync::Arc;
+use tracing::{debug, info};
+use url::Url;
+use uuid::Uuid;
+
+use crate::connectors::{
+    AuthType, Connector, Cursor, ProviderCategory, ProviderMetadata, Registry,
+    trait_::{
+        AuthorizeParams, ExchangeTokenParams, SyncError, SyncParams, SyncResult, WebhookParams,
+    },
+};
+use crate::models::{connection::Model as Connection, signal::Model as Signal};
+use crate::normalization::{
+    SignalKind, normalize_circleci_webhook_kind, normalize_circleci_workflow_kind,
+};
+
+/// Provider slug
+pub const CIRCLECI_PROVIDER_SLUG: &str = "circleci";
+
+/// Pipeline pages read in one sync, newest first
+const MAX_PAGES: u32 = 5;
+
+/// How far back the first sync reads
+const INITIAL_LOOKBACK_HOURS: i64 = 24;
+
+/// Longest a pipeline with unfinished workflows is revisited by later syncs
+const PENDING_RECHECK_HOURS: i64 = 24;
+
+/// CircleCI connector
+pub struct CircleCiConnector {
+    base_url: String,
+    api_token: Option<String>,
+    http_client: Client,
+}
+
+#[derive(Debug, Deserialize)]
+struct Page {
+    #[serde(default)]
+    items: Vec<serde_json::Value>,
+    #[serde(default)]
+    next_page_token: Option<String>,
+}
+
+impl CircleCiConnector {
+    /// Create a new CircleCI connector for the CircleCI host at `base_url`
+    ///
+    /// Polling is enabled only when `api_token` is present.
+    pub fn new(base_url: String, api_token: Option<String>) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            api_token,
+            http_client: crate::egress::client(),
+        }
+    }
+
+    fn org_slug(connection: &Connection) -> Option<String> {
+        connection
+            .metadata
+            .as_ref()
+            .and_then(|m| m.get("org_slug"))
+            .and_then(|v| v.as_str())
+            .filter(|slug| !slug.is_empty())
+            .map(str::to_string)
+    }
+
+    /// Web app host that serves pipeline pages
+    fn app_url(&self) -> String {
+        match self.base_url.as_str() {
+            "https://circleci.com" => "https://app.circleci.com".to_string(),
+            base => base.to_string(),
+        }
+    }
+
+    /// GET a paginated API v2 list
+    async fn get_page(
+        &self,
+        api_token: &str,
+        path: &str,
+        query: &[(&str, &str)],
+    ) -> Result<Page, SyncError> {
+        let response = self
+            .http_client
+            .get(format!("{}/api/v2{}", self.base_url, path))
+            .header("Circle-Token", api_token)
+            .header("Accept", "application/json")
+            .query(query)
+            .send()
+            .await
+            .map_err(|e| SyncError::transient(format!("CircleCI request failed: {}", e)))?;
+
+        let status = response.status();
+        if status == StatusCode::TOO_MANY_REQUESTS {
+            let retry_after = response
+                .headers()
+                .get("Retry-After")
+                .and_then(|h| h.to_str().ok())
+                .and_then(|s| s.parse::<u64>().ok());
+            return Err(SyncError::rate_limited(retry_after));
+        }
+        if status == StatusCode::UNAUTHORIZED {
+            return Err(SyncError::unauthorized(format!(
+                "CircleCI request to {} failed: {}",
+                path, status
+            )));
+        }
+        if status.is_server_error() {
+            return Err(SyncError::transient(format!(
+                "CircleCI request to {} failed: {}",
+                path, status
+            )));
+        }
+        if !status.is_success() {
+            let body: serde_json::Value = response.json().await.unwrap_or_default();
+            return Err(SyncError::permanent(format!(
+                "CircleCI request to {} failed: {}",
+                path, status
+            ))
+            .with_details(body));
+        }
+
+        response
+            .json()
+            .await
+            .map_err(|e| SyncError::transient(format!("CircleCI returned bad JSON: {}", e)))
+    }
+}
+
+#[async_trait]
+impl Connector for CircleCiConnector {
+    async fn authorize(
+        &self,
+        _params: AuthorizeParams,
+    ) -> Result<Url, Box<dyn std::error::Error + Send + Sync>> {
+        Err(anyhow!("OAuth authorization is not supported for CircleCI").into())
+    }
+
+    async fn exchange_token(
+        &self,
+        _params: ExchangeTokenParams,
+    ) -> Result<Connection, Box<dyn std::error::Error + Send + Sync>> {
+        Err(anyhow!("Token exchange is not supported for CircleCI").into())
+    }
+
+    async fn refresh_token(
+        &self,
+        _connection: Connection,
+    ) -> Result<Connection, Box<dyn std::error::Error + Send + Sync>> {
+        Err(anyhow!("Token refresh is not supported for CircleCI").into())
+    }
+
+    async fn sync(
+        &self,
+        params: SyncParams,
+    ) -> Result<SyncResult, Box<dyn std::error::Error + Send + Sync>> {
+        let connection = &params.connection;
+        let (Some(api_token), Some(org_slug)) = (&self.api_token, Self::org_slug(connection))
+        else {
+            // Results arrive through webhooks only
+            return Ok(SyncResult {
+                signals: vec![],
+                next_cursor: params.cursor,
+                has_more: false,
+            });
+        };
+        info!(
+            tenant_id = %connection.tenant_id,
+            connection_id = %connection.id,
+            org_slug = %org_slug,
+            has_cursor = %params.cursor.is_some(),
+            "Starting CircleCI sync"
+        );
+
+        let now = Utc::now();
+        let since = params
+            .cursor
+            .as_ref()
+            .and_then(|c| c.as_json().get("since"))
+            .and_then(parse_time)
+            .unwrap_or_else(|| now - Duration::hours(INITIAL_LOOKBACK_HOURS));
+
+        let mut signals = Vec::new();
+        // Oldest pipeline whose workflows were still running
+        let mut pending: Option<DateTime<Utc>> = None;
+        let mut page_token: Option<String> = None;
+        'pages: for _ in 0..MAX_PAGES {
+            let mut query = vec![("org-slug", org_slug.as_str())];
+            if let Some(token) = page_token.as_deref() {
+                query.push(("page-token", token));
+            }
+            let page = self.get_page(api_token, "/pipeline", &query).await?;
+
+            for pipeline in &page.items {
+                let Some(created_at) = pipeline.get("created_at").and_then(parse_time) else {
+                    continue;
+                };
+                if created_at < since {
+                    break 'pages;
+                }
+                let Some(pipeline_id) = pipeline.get("id").and_then(|v| v.as_str()) else {
+                    continue;
+                };
+                let workflows = self
+                    .get_page(
+                        api_token,
+                        &format!("/pipeline/{}/workflow", pipeline_id),
+                        &[],
+                    )
+                    .await?;
+                for workflow in &workflows.items {
+                    match normalize_circleci_workflow_kind(workflow) {
+                        Some(kind) => signals.push(build_workflow_signal(
+                            connection.tenant_id,
+                            connection.id,
+                            kind,
+                            workflow,
+                            pipeline,
+                            &self.app_url(),
+                        )),
+                        None if is_unfinished(workflow) => {
+                            pending = Some(pending.map_or(created_at, |p| p.min(created_at)));
+                        }
+                        None => {}
+                    }
+                }
+            }
+
+            page_token = page.next_page_token.filter(|t| !t.is_empty());
+            if page_token.is_none() {
+                break;
+            }
+        }
+
+        // Revisit unfinished pipelines next run; dedupe keys absorb repeats
+        let next_since = pending
+            .map(|p| p.max(now - Duration::hours(PENDING_RECHECK_HOURS)))
+            .unwrap_or(now);
+        debug!(
+            connection_id = %connection.id,
+            signals = signals.len(),
+            next_since = %next_since,
+            "CircleCI sync completed"
+        );
+
+        Ok(SyncResult {
+            signals,
+            next_cursor: Some(Cursor::from_json(serde_json::json!({
+                "since": next_since.to_rfc3339_opts(SecondsFormat::Secs, true),
+            }))),
+            has_more: false,
+        })
+    }
+
+    async fn handle_webhook(
+        &self,
+        params: WebhookParams,
+    ) -> Result<Vec<Signal>, Box<dyn std::error::Error + Send + Sync>> {
+        let payload = &params.payload;
+        let Some(kind) = normalize_circleci_webhook_kind(payload) else {
+            debug!(
+                tenant_id = %params.tenant_id,
+                event_type = ?payload.get("type"),
+                "CircleCI webhook ignored"
+            );
+            return Ok(vec![]);
+        };
+
+        let workflow = &payload["workflow"];
+        if workflow.get("id").and_then(|v| v.as_str()).is_none() {
+            return Err(anyhow!("Invalid CircleCI webhook: missing workflow.id").into());
+        }
+        // Webhooks nest the project beside the pipeline instead of inside it
+        let mut pipeline = payload["pipeline"].clone();
+        if let Some(slug) = payload.pointer("/project/slug") {
+            pipeline["project_slug"] = slug.clone();
+        }
+
+        let signal = build_workflow_signal(
+            params.tenant_id,
+            Uuid::nil(),
+            kind,
+            workflow,
+            &pipeline,
+            &self.app_url(),
+        );
+        info!(
+            tenant_id = %params.tenant_id,
+            signal_kind = %kind,
+            "CircleCI webhook mapped to signal"
+        );
+        Ok(vec![signal])
+    }
+}
+
+/// Register the CircleCI connector in the registry
+pub fn register_circleci_connector(registry: &mut Registry, connector: Arc<CircleCiConnector>) {
+    let metadata = ProviderMetadata::new(
+        CIRCLECI_PROVIDER_SLUG.to_string(),
+        AuthType::ApiKey,
+        vec![],
+        true, // webhooks supported
+    )
+    .with_category(ProviderCategory::Code)
+    .with_description("Track pipeline and workflow results from CircleCI")
+    .with_icon_url("https://cdn.simpleicons.org/circleci")
+    .with_docs_url("https://circleci.com/docs/webhooks/")
+    .with_backfill(connector.api_token.is_some())
+    .with_signal_kinds(&[
+        SignalKind::CiRunSucceeded,
+        SignalKind::CiRunFailed,
+        SignalKind::CiRunCanceled,
+    ]);
+
+    registry.register(connector, metadata);
+}
+
+/// External id and metadata of the connection a webhook delivery belongs to
+///
+/// The organization ID is the external id. The organization slug used by the
+/// pipeline API is the project slug without its last segment, e.g. `gh/acme`
+/// for `gh/acme/web`.
+pub fn circleci_connection_key(payload: &serde_json::Value) -> Option<(String, serde_json::Value)> {
+    let organization_id = payload
+        .pointer("/organization/id")
+        .and_then(|v| v.as_str())
+        .filter(|id| !id.is_empty())?;
+    let org_slug = payload
+        .pointer("/project/slug")
+        .and_then(|v| v.as_str())
+        .and_then(|slug| slug.rsplit_once('/'))
+        .map(|(org, _)| org.to_string());
+    Some((
+        organization_id.to_string(),
+        serde_json::json!({
+            "provider": CIRCLECI_PROVIDER_SLUG,
+            "org_slug": org_slug,
+            "organization": payload.pointer("/organization/name"),
+        }),
+    ))
+}
+
+fn parse_time(value: &serde_json::Value) -> Option<DateTime<Utc>> {
+    value
+        .as_str()
+        .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+        .map(|dt| dt.with_timezone(&Utc))
+}
+
+/// Whether a workflow may still produce a result
+fn is_unfinished(workflow: &serde_json::Value) -> bool {
+    matches!(
+        workflow.get("status").and_then(|v| v.as_str()),
+        Some("running" | "failing" | "on_hold")
+    )
+}
+
+/// Build a CI result signal from a workflow and its pipeline
+///
+/// Sync and webhooks key on the workflow ID; a rerun creates a new workflow
+/// and therefore a new signal.
+fn build_workflow_signal(
+    tenant_id: Uuid,
+    connection_id: Uuid,
+    kind: SignalKind,
+    workflow: &serde_json::Value,
+    pipeline: &serde_json::Value,
+    app_url: &str,
+) -> Signal {
+    let workflow_id = workflow
+        .get("id")
+        .and_then(|v| v.as_str())
+        .unwrap_or_default();
+    let started_at = workflow.get("created_at").and_then(parse_time);
+    let stopped_at = workflow.get("stopped_at").and_then(parse_time);
+    let occurred_at = stopped_at.unwrap_or_else(Utc::now);
+    let received_at = DateTime::from(Utc::now());
+
+    let project = workflow
+        .get("project_slug")
+        .or_else(|| pipeline.get("project_slug"))
+        .and_then(|v| v.as_str());
+    let number = workflow
+        .get("pipeline_number")
+        .or_else(|| pipeline.get("number"));
+    let url = workflow
+        .get("url")
+        .and_then(|v| v.as_str())
+        .map(str::to_string)
+        .or_else(|| {
+            Some(format!(
+                "{}/pipelines/{}/{}/workflows/{}",
+                app_url, project?, number?, workflow_id
+            ))
+        });
+    let vcs = &pipeline["vcs"];
+
+    Signal {
+        id: Uuid::new_v4(),
+        tenant_id,
+        provider_slug: CIRCLECI_PROVIDER_SLUG.to_string(),
+        connection_id,
+        kind: kind.as_str().to_string(),
+        occurred_at: occurred_at.into(),
+        received_at,
+        payload: serde_json::json!({
+            "workflow_id": workflow_id,
+            "workflow_name": workflow.get("name"),
+            "status": workflow.get("status"),
+            "project": project,
+            "pipeline_id": pipeline.get("id"),
+            "pipeline_number": number,
+            "branch": vcs.get("branch"),
+            "tag": vcs.get("tag"),
+            "revision": vcs.get("revision"),
+            "commit_subject": vcs.pointer("/commit/subject"),
+            "author": vcs
+                .pointer("/commit/author/name")
+                .or_else(|| pipeline.pointer("/trigger/actor/login")),
+            "trigger": pipeline.pointer("/trigger/type"),
+            "duration_seconds": started_at
+                .zip(stopped_at)
+                .map(|(start, stop)| (stop - start).num_seconds()),
+            "url": url,
+            "occurred_at": occurred_at.to_rfc3339(),
+        }),
+        dedupe_key: Some(format!("circleci:{}:{}", kind, workflow_id)),
+        created_at: received_at,
+        updated_at: received_at,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{header, method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn connection() -> Connection {
+        let now = DateTime::from(Utc::now());
+        Connection {
+            id: Uuid::new_v4(),
+            tenant_id: Uuid::new_v4(),
+            provider_slug: CIRCLECI_PROVIDER_SLUG.to_string(),
+            external_id: "f22b6566".to_string(),
+            status: "active".to_string(),
+            display_name: Some("CircleCI".to_string()),
+            access_token_ciphertext: None,
+            refresh_token_ciphertext: None,
+            access_token: None,
+            refresh_token: None,
+            expires_at: None,
+            scopes: None,
+            metadata: Some(serde_json::json!({"org_slug": "gh/acme"})),
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    fn pipeline(id: &str, number: u64, created_at: &str) -> serde_json::Value {
+        serde_json::json!({
+            "id": id,
+            "number": number,
+            "project_slug": "gh/acme/web",
+            "state": "created",
+            "created_at": created_at,
+            "trigger": {"type": "webhook", "actor": {"login": "ada"}},
+            "vcs": {"branch": "main", "revision": "1dc6aa6", "commit": {"subject": "Fix checkout retry"}}
+        })
+    }
+
+    fn workflow(id: &str, status: &str, number: u64) -> serde_json::Value {
+        serde_json::json!({
+            "id": id,
+            "name": "build-and-test",
+            "status": status,
+            "project_slug": "gh/acme/web",
+            "pipeline_number": number,
+            "created_at": "2024-05-02T12:00:00Z",
+            "stopped_at": "2024-05-02T12:05:30Z"
+        })
+    }
+
+    #[tokio::test]
+    async fn test_circleci_sync_reads_recent_pipeline_workflows() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v2/pipeline"))
+            .and(header("Circle-Token", "cci-token"))
+            .and(query_param("org-slug", "gh/acme"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "items": [
+                    pipeline("p-3", 132, "2024-05-02T13:00:00Z"),
+                    pipeline("p-2", 131, "2024-05-02T12:00:00Z"),
+                    pipeline("p-1", 130, "2024-05-01T12:00:00Z")
+                ],
+                "next_page_token": "older"
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v2/pipeline/p-3/workflow"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "items": [workflow("w-3", "running", 132)],
+                "next_page_token": null
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v2/pipeline/p-2/workflow"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "items": [workflow("w-2a", "failed", 131), workflow("w-2b", "success", 131)],
+                "next_page_token": null
+            })))
+            .mount(&server)
+            .await;
+
+        let result = CircleCiConnector::new(server.uri(), Some("cci-token".to_string()))
+            .sync(SyncParams {
+                connection: connection(),
+                cursor: Some(Cursor::from_json(
+                    serde_json::json!({"since": "2024-05-02T00:00:00Z"}),
+                )),
+                cancellation: Default::default(),
+            })
+            .await
+            .unwrap();
+
+        // p-1 predates the cursor, so paging stops before the next page
+        let kinds: Vec<_> = result.signals.iter().map(|s| s.kind.as_str()).collect();
+        assert_eq!(kinds, vec!["ci_run_failed", "ci_run_succeeded"]);
+        let failed = &result.signals[0];
+        assert_eq!(
+            failed.dedupe_key.as_deref(),
+            Some("circleci:ci_run_failed:w-2a")
+        );
+        assert_eq!(failed.payload["branch"], "main");
+        assert_eq!(failed.payload["author"], "ada");
+        assert_eq!(failed.payload["duration_seconds"], 330);
+        assert_eq!(
+            failed.payload["url"],
+            format!("{}/pipelines/gh/acme/web/131/workflows/w-2a", server.uri())
+        );
+        // The running workflow keeps the window open at its pipeline
+        let next_since = result.next_cursor.unwrap().as_json()["since"].clone();
+        let next_since = parse_time(&next_since).unwrap();
+        assert!(next_since >= Utc::now() - Duration::hours(PENDING_RECHECK_HOURS + 1));
+        assert!(next_since <= Utc::now());
+    }
+
+    #[tokio::test]
+    async fn test_circleci_webhook_mapping() {
+        let connector = CircleCiConnector::new("https://circleci.com".to_string(), None);
+        let payload = serde_json::json!({
+            "type": "workflow-completed",
+            "id": "3888f21b-eaa7-38e3-8f3d-75a63bba8895",
+            "happened_at": "2024-05-02T12:05:30Z",
+            "workflow": {
+                "id": "w-2a",
+                "name": "build-and-test",
+                "status": "failed",
+                "created_at": "2024-05-02T12:00:00Z",
+                "stopped_at": "2024-05-02T12:05:30Z",
+                "url": "https://app.circleci.com/pipelines/gh/acme/web/131/workflows/w-2a"
+            },
+            "pipeline": {
+                "id": "p-2",
+                "number": 131,
+                "trigger": {"type": "webhook"},
+                "vcs": {
+                    "branch": "main",
+                    "revision": "1dc6aa6",
+                    "commit": {"subject": "Fix checkout retry", "author": {"name": "Ada"}}
+                }
+            },
+            "project": {"id": "84996744", "name": "web", "slug": "gh/acme/web"},
+            "organization": {"id": "f22b6566", "name": "acme"}
+        });
+
+        let signals = connector
+            .handle_webhook(WebhookParams {
+                payload: payload.clone(),
+                tenant_id: Uuid::new_v4(),
+                db: None,
+                auth_header: None,
+            })
+            .await
+            .unwrap();
+        assert_eq!(signals[0].kind, "ci_run_failed");
+        // Matches the key produced by sync for the same workflow
+        assert_eq!(
+            signals[0].dedupe_key.as_deref(),
+            Some("circleci:ci_run_failed:w-2a")
+        );
+        assert_eq!(signals[0].payload["project"], "gh/acme/web");
+        assert_eq!(signals[0].payload["author"], "Ada");
+
+        let (external_id, metadata) = circleci_connection_key(&payload).unwrap();
+        assert_eq!(external_id, "f22b6566");
+        assert_eq!(metadata["org_slug"], "gh/acme");
+
+        let job = connector
+            .handle_webhook(WebhookParams {
+                payload: serde_json::json!({"type": "job-completed", "job": {"status": "failed"}}),
+                tenant_id: Uuid::new_v4(),
+                db: None,
+                auth_header: None,
+            })
+            .await
+            .unwrap();
+        assert!(job.is_empty());
+    }
+}
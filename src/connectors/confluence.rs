@@ -0,0 +1,922 @@
+//! Confluence connector implementation
+//!
+//! Confluence Cloud connector sharing the Atlassian 3LO OAuth app configured
+//! for Jira. Incremental sync runs a CQL content search over pages, blog posts
+//! and comments newest change first and stops at the previous run's
+//! high-water mark. Webhook secrets are verified in
+//! [`crate::webhook_verification`] before payloads reach this connector.
+
+use anyhow::anyhow;
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use reqwest::{Client, StatusCode};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::sync::Arc;
+use tracing::{debug, info};
+use url::Url;
+use uuid::Uuid;
+
+use crate::connectors::{
+    AuthType, Connector, Cursor, ProviderCategory, ProviderMetadata, Registry,
+    trait_::{
+        AuthorizeParams, ExchangeTokenParams, SyncError, SyncParams, SyncResult, WebhookParams,
+    },
+};
+use crate::models::{connection::Model as Connection, signal::Model as Signal};
+use crate::normalization::{
+    SignalKind, normalize_confluence_content_kind, normalize_confluence_webhook_kind,
+};
+
+/// Provider slug
+pub const CONFLUENCE_PROVIDER_SLUG: &str = "confluence";
+
+/// Scopes requested at authorization time
+pub const CONFLUENCE_SCOPES: &[&str] = &[
+    "read:confluence-content.all",
+    "read:confluence-space.summary",
+    "search:confluence",
+    "read:confluence-user",
+];
+
+/// Audience of Atlassian 3LO access tokens
+const ATLASSIAN_AUDIENCE: &str = "api.atlassian.com";
+
+/// Results requested per CQL search page
+const PAGE_SIZE: u32 = 50;
+
+/// Search pages fetched in one sync; the rest continues on the next run
+const MAX_SEARCH_PAGES_PER_SYNC: u32 = 10;
+
+/// How far back the first sync reads
+const INITIAL_LOOKBACK_DAYS: i64 = 30;
+
+/// CQL compares dates to the minute in the connecting user's time zone, so the
+/// query starts this much earlier and already-synced versions are dropped here
+const CQL_TIMEZONE_SLACK_HOURS: i64 = 14;
+
+/// Confluence connector
+pub struct ConfluenceConnector {
+    client_id: String,
+    client_secret: String,
+    oauth_base: String,
+    api_base: String,
+    http_client: Client,
+}
+
+/// Search position stored in the cursor
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+struct SearchWindow {
+    /// Content versions at or before this time have been synced
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    since: Option<DateTime<Utc>>,
+    /// Result offset of an unfinished search walk
+    #[serde(default, skip_serializing_if = "is_zero")]
+    start: u32,
+    /// Newest change seen by the unfinished walk; becomes `since` once it completes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    high_water: Option<DateTime<Utc>>,
+}
+
+fn is_zero(value: &u32) -> bool {
+    *value == 0
+}
+
+#[derive(Debug, Deserialize)]
+struct AtlassianTokenResponse {
+    access_token: String,
+    #[serde(default)]
+    refresh_token: Option<String>,
+    #[serde(default)]
+    expires_in: Option<i64>,
+    #[serde(default)]
+    scope: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct AccessibleResource {
+    id: String,
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    url: Option<String>,
+    #[serde(default)]
+    scopes: Vec<String>,
+}
+
+impl ConfluenceConnector {
+    /// Create a new Confluence connector with the Atlassian OAuth configuration
+    pub fn new(
+        client_id: String,
+        client_secret: String,
+        oauth_base: String,
+        api_base: String,
+    ) -> Self {
+        Self {
+            client_id,
+            client_secret,
+            oauth_base: oauth_base.trim_end_matches('/').to_string(),
+            api_base: api_base.trim_end_matches('/').to_string(),
+            http_client: crate::egress::client(),
+        }
+    }
+
+    fn default_redirect_uri() -> String {
+        match std::env::var("POBLYSH_PROFILE").as_deref() {
+            Ok("local") | Ok("test") | Err(_) => "http://localhost:3000/callback".to_string(),
+            Ok(_) => "https://app.poblysh.com/callback".to_string(),
+        }
+    }
+
+    fn access_token(connection: &Connection) -> Result<String, SyncError> {
+        connection
+            .access_token
+            .clone()
+            .filter(|token| !token.is_empty())
+            .ok_or_else(|| SyncError::unauthorized("Missing Confluence access token"))
+    }
+
+    /// Call `/oauth/token` with the given grant
+    async fn oauth_token(
+        &self,
+        grant: serde_json::Value,
+    ) -> Result<AtlassianTokenResponse, anyhow::Error> {
+        let mut body = grant;
+        body["client_id"] = serde_json::Value::String(self.client_id.clone());
+        body["client_secret"] = serde_json::Value::String(self.client_secret.clone());
+
+        let response = self
+            .http_client
+            .post(format!("{}/oauth/token", self.oauth_base))
+            .json(&body)
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let body: serde_json::Value = response.json().await.unwrap_or_default();
+            return Err(anyhow!(
+                "Atlassian token endpoint returned {}: {}",
+                status,
+                body.get("error")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("unknown_error")
+            ));
+        }
+        Ok(response.json().await?)
+    }
+
+    /// GET an `api.atlassian.com` endpoint, mapping HTTP failures to sync errors
+    async fn get(
+        &self,
+        access_token: &str,
+        path: &str,
+        query: &[(&str, String)],
+    ) -> Result<serde_json::Value, SyncError> {
+        let response = self
+            .http_client
+            .get(format!("{}{}", self.api_base, path))
+            .bearer_auth(access_token)
+            .header("Accept", "application/json")
+            .query(query)
+            .send()
+            .await
+            .map_err(|e| SyncError::transient(format!("Confluence request failed: {}", e)))?;
+
+        let status = response.status();
+        if status == StatusCode::TOO_MANY_REQUESTS {
+            let retry_after = response
+                .headers()
+                .get("Retry-After")
+                .and_then(|h| h.to_str().ok())
+                .and_then(|s| s.parse::<u64>().ok());
+            return Err(SyncError::rate_limited(retry_after));
+        }
+        if status == StatusCode::UNAUTHORIZED {
+            return Err(SyncError::unauthorized(format!(
+                "Confluence request to {} failed: {}",
+                path, status
+            )));
+        }
+        if status.is_server_error() {
+            return Err(SyncError::transient(format!(
+                "Confluence request to {} failed: {}",
+                path, status
+            )));
+        }
+        if !status.is_success() {
+            let body: serde_json::Value = response.json().await.unwrap_or_default();
+            return Err(SyncError::permanent(format!(
+                "Confluence request to {} failed: {}",
+                path, status
+            ))
+            .with_details(body));
+        }
+
+        response
+            .json()
+            .await
+            .map_err(|e| SyncError::transient(format!("Confluence returned bad JSON: {}", e)))
+    }
+
+    /// Walk the CQL content search from the window, newest change first
+    ///
+    /// Returns the signals, the updated window and whether pages remain.
+    async fn search_since(
+        &self,
+        access_token: &str,
+        connection: &Connection,
+        cloud_id: &str,
+        mut window: SearchWindow,
+    ) -> Result<(Vec<Signal>, SearchWindow, bool), SyncError> {
+        let floor = window
+            .since
+            .unwrap_or_else(|| Utc::now() - Duration::days(INITIAL_LOOKBACK_DAYS));
+        let cql = format!(
+            "type in (page, blogpost, comment) AND lastmodified >= \"{}\" ORDER BY lastmodified DESC",
+            (floor - Duration::hours(CQL_TIMEZONE_SLACK_HOURS)).format("%Y/%m/%d %H:%M")
+        );
+        let path = format!("/ex/confluence/{}/wiki/rest/api/content/search", cloud_id);
+        let mut signals = Vec::new();
+        // Edits during a walk shift results towards later offsets, so some are read twice
+        let mut seen = HashSet::new();
+
+        for _ in 0..MAX_SEARCH_PAGES_PER_SYNC {
+            let body = self
+                .get(
+                    access_token,
+                    &path,
+                    &[
+                        ("cql", cql.clone()),
+                        ("start", window.start.to_string()),
+                        ("limit", PAGE_SIZE.to_string()),
+                        ("expand", "space,version,container".to_string()),
+                    ],
+                )
+                .await?;
+            let results = body
+                .get("results")
+                .and_then(|v| v.as_array())
+                .cloned()
+                .unwrap_or_default();
+            let site_base = body.pointer("/_links/base").and_then(|v| v.as_str());
+
+            let mut caught_up = false;
+            for content in &results {
+                let Some(changed) = version_time(content) else {
+                    continue;
+                };
+                if changed <= floor {
+                    caught_up = true;
+                    break;
+                }
+                if window.high_water.is_none_or(|high| changed > high) {
+                    window.high_water = Some(changed);
+                }
+                let Some(kind) = normalize_confluence_content_kind(content) else {
+                    continue;
+                };
+                let signal = build_content_signal(connection, kind, content, site_base);
+                if seen.insert(signal.dedupe_key.clone()) {
+                    signals.push(signal);
+                }
+            }
+
+            let has_next = body.pointer("/_links/next").is_some();
+            if caught_up || !has_next || results.len() < PAGE_SIZE as usize {
+                window.start = 0;
+                if let Some(high_water) = window.high_water.take() {
+                    window.since = Some(high_water.max(floor));
+                }
+                return Ok((signals, window, false));
+            }
+            window.start += results.len() as u32;
+        }
+
+        Ok((signals, window, true))
+    }
+}
+
+#[async_trait]
+impl Connector for ConfluenceConnector {
+    async fn authorize(
+        &self,
+        params: AuthorizeParams,
+    ) -> Result<Url, Box<dyn std::error::Error + Send + Sync>> {
+        info!(
+            tenant_id = %params.tenant_id,
+            "Generating Confluence OAuth authorization URL"
+        );
+
+        let mut url = Url::parse(&format!("{}/authorize", self.oauth_base))?;
+        let redirect_uri = params
+            .redirect_uri
+            .unwrap_or_else(Self::default_redirect_uri);
+        let state = params
+            .state
+            .filter(|s| !s.is_empty())
+            .unwrap_or_else(|| Uuid::new_v4().to_string());
+        let mut scopes = CONFLUENCE_SCOPES.to_vec();
+        scopes.push("offline_access");
+        url.query_pairs_mut()
+            .append_pair("audience", ATLASSIAN_AUDIENCE)
+            .append_pair("client_id", &self.client_id)
+            .append_pair("scope", &scopes.join(" "))
+            .append_pair("redirect_uri", &redirect_uri)
+            .append_pair("state", &state)
+            .append_pair("response_type", "code")
+            .append_pair("prompt", "consent");
+
+        debug!(
+            tenant_id = %params.tenant_id,
+            authorize_url = %url,
+            "Generated Confluence OAuth authorization URL"
+        );
+        Ok(url)
+    }
+
+    async fn exchange_token(
+        &self,
+        params: ExchangeTokenParams,
+    ) -> Result<Connection, Box<dyn std::error::Error + Send + Sync>> {
+        info!(
+            tenant_id = %params.tenant_id,
+            "Exchanging Confluence authorization code for tokens"
+        );
+
+        let redirect_uri = params
+            .redirect_uri
+            .unwrap_or_else(Self::default_redirect_uri);
+        let token = self
+            .oauth_token(serde_json::json!({
+                "grant_type": "authorization_code",
+                "code": params.code,
+                "redirect_uri": redirect_uri,
+            }))
+            .await?;
+
+        let resources: Vec<AccessibleResource> = serde_json::from_value(
+            self.get(
+                &token.access_token,
+                "/oauth/token/accessible-resources",
+                &[],
+            )
+            .await?,
+        )?;
+        let site = resources
+            .into_iter()
+            .find(|resource| resource.scopes.iter().any(|s| s.contains("confluence")))
+            .ok_or_else(|| anyhow!("Atlassian token grants no Confluence site"))?;
+        let account = self.get(&token.access_token, "/me", &[]).await.ok();
+        let now = DateTime::from(Utc::now());
+
+        Ok(Connection {
+            id: Uuid::new_v4(),
+            tenant_id: params.tenant_id,
+            provider_slug: CONFLUENCE_PROVIDER_SLUG.to_string(),
+            external_id: site.id.clone(),
+            status: "active".to_string(),
+            display_name: site.name.clone(),
+            access_token_ciphertext: None,
+            refresh_token_ciphertext: None,
+            access_token: Some(token.access_token),
+            refresh_token: token.refresh_token,
+            expires_at: token
+                .expires_in
+                .map(|seconds| now + chrono::Duration::seconds(seconds)),
+            scopes: token.scope.map(|scope| {
+                serde_json::Value::Array(
+                    scope
+                        .split_whitespace()
+                        .map(|s| serde_json::Value::String(s.to_string()))
+                        .collect(),
+                )
+            }),
+            metadata: Some(serde_json::json!({
+                "provider": CONFLUENCE_PROVIDER_SLUG,
+                "cloud_id": site.id,
+                "site_url": site.url,
+                "account": account.as_ref().map(|account| serde_json::json!({
+                    "account_id": account.get("account_id"),
+                    "display_name": account.get("name"),
+                })),
+            })),
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    async fn refresh_token(
+        &self,
+        connection: Connection,
+    ) -> Result<Connection, Box<dyn std::error::Error + Send + Sync>> {
+        let Some(refresh_token) = connection
+            .refresh_token
+            .clone()
+            .filter(|token| !token.is_empty())
+        else {
+            return Err(
+                SyncError::unauthorized("Confluence connection has no refresh token").into(),
+            );
+        };
+
+        info!(connection_id = %connection.id, "Refreshing Confluence token");
+        let token = self
+            .oauth_token(serde_json::json!({
+                "grant_type": "refresh_token",
+                "refresh_token": refresh_token,
+            }))
+            .await?;
+        let now = DateTime::from(Utc::now());
+
+        // Atlassian rotates refresh tokens; the old one stops working once used
+        Ok(Connection {
+            access_token: Some(token.access_token),
+            refresh_token: token.refresh_token.or(connection.refresh_token.clone()),
+            expires_at: token
+                .expires_in
+                .map(|seconds| now + chrono::Duration::seconds(seconds)),
+            updated_at: now,
+            ..connection
+        })
+    }
+
+    async fn sync(
+        &self,
+        params: SyncParams,
+    ) -> Result<SyncResult, Box<dyn std::error::Error + Send + Sync>> {
+        let connection = &params.connection;
+        info!(
+            tenant_id = %connection.tenant_id,
+            connection_id = %connection.id,
+            has_cursor = %params.cursor.is_some(),
+            "Starting Confluence sync"
+        );
+
+        let access_token = Self::access_token(connection)?;
+        let cloud_id = connection
+            .metadata
+            .as_ref()
+            .and_then(|metadata| metadata.get("cloud_id"))
+            .and_then(|v| v.as_str())
+            .map(str::to_string)
+            .ok_or_else(|| SyncError::permanent("Confluence connection has no cloud_id"))?;
+        let window: SearchWindow = params
+            .cursor
+            .as_ref()
+            .and_then(|cursor| serde_json::from_value(cursor.as_json().clone()).ok())
+            .unwrap_or_default();
+
+        let (signals, window, has_more) = self
+            .search_since(&access_token, connection, &cloud_id, window)
+            .await?;
+
+        debug!(
+            connection_id = %connection.id,
+            signals = signals.len(),
+            has_more,
+            "Confluence sync completed"
+        );
+
+        Ok(SyncResult {
+            signals,
+            next_cursor: Some(Cursor::from_json(serde_json::to_value(&window)?)),
+            has_more,
+        })
+    }
+
+    async fn handle_webhook(
+        &self,
+        params: WebhookParams,
+    ) -> Result<Vec<Signal>, Box<dyn std::error::Error + Send + Sync>> {
+        let payload = &params.payload;
+        let Some(kind) = normalize_confluence_webhook_kind(payload) else {
+            debug!(
+                tenant_id = %params.tenant_id,
+                event = ?payload.get("event"),
+                "Confluence webhook ignored"
+            );
+            return Ok(vec![]);
+        };
+
+        info!(
+            tenant_id = %params.tenant_id,
+            signal_kind = %kind,
+            event = ?payload.get("event"),
+            "Confluence webhook mapped to signal"
+        );
+
+        let connection_id = Uuid::nil();
+        Ok(vec![build_webhook_signal(
+            params.tenant_id,
+            connection_id,
+            kind,
+            payload,
+        )?])
+    }
+}
+
+/// Register the Confluence connector in the registry
+pub fn register_confluence_connector(registry: &mut Registry, connector: Arc<ConfluenceConnector>) {
+    let metadata = ProviderMetadata::new(
+        CONFLUENCE_PROVIDER_SLUG.to_string(),
+        AuthType::OAuth2,
+        CONFLUENCE_SCOPES.iter().map(|s| s.to_string()).collect(),
+        true, // webhooks supported
+    )
+    .with_category(ProviderCategory::Files)
+    .with_description("Sync page, blog post and comment activity from Confluence Cloud")
+    .with_icon_url("https://cdn.simpleicons.org/confluence")
+    .with_docs_url("https://developer.atlassian.com/cloud/confluence/oauth-2-3lo-apps/")
+    .with_backfill(true)
+    .with_signal_kinds(&[
+        SignalKind::FileCreated,
+        SignalKind::FileUpdated,
+        SignalKind::FileDeleted,
+        SignalKind::FileMoved,
+        SignalKind::IssueComment,
+    ]);
+
+    registry.register(connector, metadata);
+}
+
+/// Time of the content's current version
+fn version_time(content: &serde_json::Value) -> Option<DateTime<Utc>> {
+    content
+        .pointer("/version/when")
+        .and_then(|v| v.as_str())
+        .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+        .map(|dt| dt.with_timezone(&Utc))
+}
+
+/// Dedupe key shared by sync and webhooks for the same content version
+fn content_dedupe_key(kind: SignalKind, id: &str, version: Option<i64>) -> String {
+    format!(
+        "confluence:{}:{}:{}",
+        kind,
+        id,
+        version.map(|v| v.to_string()).unwrap_or_default()
+    )
+}
+
+/// Build a signal from a page, blog post or comment returned by search
+fn build_content_signal(
+    connection: &Connection,
+    kind: SignalKind,
+    content: &serde_json::Value,
+    site_base: Option<&str>,
+) -> Signal {
+    let id = content
+        .get("id")
+        .and_then(|v| v.as_str())
+        .unwrap_or_default();
+    let version = content.pointer("/version/number").and_then(|v| v.as_i64());
+    let occurred_at = version_time(content).unwrap_or_else(Utc::now);
+    let url = content
+        .pointer("/_links/webui")
+        .and_then(|v| v.as_str())
+        .map(|path| format!("{}{}", site_base.unwrap_or_default(), path));
+    let received_at = DateTime::from(Utc::now());
+
+    Signal {
+        id: Uuid::new_v4(),
+        tenant_id: connection.tenant_id,
+        provider_slug: CONFLUENCE_PROVIDER_SLUG.to_string(),
+        connection_id: connection.id,
+        kind: kind.as_str().to_string(),
+        occurred_at: occurred_at.into(),
+        received_at,
+        payload: serde_json::json!({
+            "content_id": id,
+            "content_type": content.get("type"),
+            "title": content.get("title"),
+            "space_key": content.pointer("/space/key"),
+            "version": version,
+            "author_account_id": content.pointer("/version/by/accountId"),
+            "parent_id": content.pointer("/container/id"),
+            "parent_title": content.pointer("/container/title"),
+            "url": url,
+            "occurred_at": occurred_at.to_rfc3339(),
+        }),
+        dedupe_key: Some(content_dedupe_key(kind, id, version)),
+        created_at: received_at,
+        updated_at: received_at,
+    }
+}
+
+/// Build a signal from a webhook's `page`, `blog` or `comment` object
+fn build_webhook_signal(
+    tenant_id: Uuid,
+    connection_id: Uuid,
+    kind: SignalKind,
+    payload: &serde_json::Value,
+) -> Result<Signal, anyhow::Error> {
+    let (content_type, content) = [
+        ("page", "page"),
+        ("blogpost", "blog"),
+        ("comment", "comment"),
+    ]
+    .into_iter()
+    .find_map(|(content_type, key)| payload.get(key).map(|content| (content_type, content)))
+    .ok_or_else(|| anyhow!("Invalid Confluence webhook payload: missing content"))?;
+    // Content ids arrive as numbers or strings depending on the event
+    let id = match content.get("id") {
+        Some(serde_json::Value::String(id)) => id.clone(),
+        Some(id) => id.to_string(),
+        None => return Err(anyhow!("Invalid Confluence webhook payload: missing id")),
+    };
+    let version = content.get("version").and_then(|v| v.as_i64());
+    let occurred_at = payload
+        .get("timestamp")
+        .and_then(|v| v.as_i64())
+        .or_else(|| content.get("modificationDate").and_then(|v| v.as_i64()))
+        .and_then(DateTime::from_timestamp_millis)
+        .unwrap_or_else(Utc::now);
+    let parent = content.get("parent");
+    // Trashing or moving keeps the version, so the event time tells repeats apart
+    let dedupe_key = match kind {
+        SignalKind::FileDeleted | SignalKind::FileMoved => format!(
+            "confluence:{}:{}:{}",
+            kind,
+            id,
+            occurred_at.timestamp_millis()
+        ),
+        _ => content_dedupe_key(kind, &id, version),
+    };
+    let received_at = DateTime::from(Utc::now());
+
+    Ok(Signal {
+        id: Uuid::new_v4(),
+        tenant_id,
+        provider_slug: CONFLUENCE_PROVIDER_SLUG.to_string(),
+        connection_id,
+        kind: kind.as_str().to_string(),
+        occurred_at: occurred_at.into(),
+        received_at,
+        payload: serde_json::json!({
+            "content_id": id,
+            "content_type": content_type,
+            "title": content.get("title"),
+            "space_key": content
+                .get("spaceKey")
+                .or_else(|| parent.and_then(|p| p.get("spaceKey"))),
+            "version": version,
+            "author_account_id": content
+                .get("lastModifierAccountId")
+                .or_else(|| payload.get("userAccountId")),
+            "parent_id": parent.and_then(|p| p.get("id")),
+            "parent_title": parent.and_then(|p| p.get("title")),
+            "url": content.get("self"),
+            "occurred_at": occurred_at.to_rfc3339(),
+        }),
+        dedupe_key: Some(dedupe_key),
+        created_at: received_at,
+        updated_at: received_at,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{body_string_contains, header, method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn connector(base: &str) -> ConfluenceConnector {
+        ConfluenceConnector::new(
+            "client-id".to_string(),
+            "client-secret".to_string(),
+            base.to_string(),
+            base.to_string(),
+        )
+    }
+
+    fn connection() -> Connection {
+        let now = DateTime::from(Utc::now());
+        Connection {
+            id: Uuid::new_v4(),
+            tenant_id: Uuid::new_v4(),
+            provider_slug: CONFLUENCE_PROVIDER_SLUG.to_string(),
+            external_id: "cloud-1".to_string(),
+            status: "active".to_string(),
+            display_name: Some("acme".to_string()),
+            access_token_ciphertext: None,
+            refresh_token_ciphertext: None,
+            access_token: Some("atl-access".to_string()),
+            refresh_token: None,
+            expires_at: None,
+            scopes: None,
+            metadata: Some(serde_json::json!({"cloud_id": "cloud-1"})),
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    fn content(id: &str, kind: &str, version: i64, when: &str) -> serde_json::Value {
+        serde_json::json!({
+            "id": id,
+            "type": kind,
+            "status": "current",
+            "title": format!("Content {}", id),
+            "space": {"key": "ENG"},
+            "version": {"number": version, "when": when, "by": {"accountId": "acc-1"}},
+            "container": {"id": "100", "title": "Roadmap"},
+            "_links": {"webui": format!("/spaces/ENG/pages/{}", id)}
+        })
+    }
+
+    #[tokio::test]
+    async fn test_confluence_authorize_url_uses_atlassian_audience() {
+        let url = connector("https://auth.atlassian.com")
+            .authorize(AuthorizeParams {
+                tenant_id: Uuid::new_v4(),
+                redirect_uri: Some("https://app.example.com/callback".to_string()),
+                state: Some("state-1".to_string()),
+                code_challenge: None,
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(url.path(), "/authorize");
+        let query: std::collections::HashMap<_, _> = url.query_pairs().collect();
+        assert_eq!(query["audience"], "api.atlassian.com");
+        assert_eq!(query["client_id"], "client-id");
+        assert!(query["scope"].contains("search:confluence"));
+        assert!(query["scope"].ends_with("offline_access"));
+        assert_eq!(query["state"], "state-1");
+    }
+
+    #[tokio::test]
+    async fn test_confluence_exchange_token_picks_confluence_site() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/oauth/token"))
+            .and(body_string_contains("authorization_code"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "access_token": "atl-access",
+                "refresh_token": "atl-refresh",
+                "expires_in": 3600,
+                "scope": "search:confluence offline_access"
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/oauth/token/accessible-resources"))
+            .and(header("Authorization", "Bearer atl-access"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                {"id": "jira-only", "name": "jira", "url": "https://jira.atlassian.net", "scopes": ["read:jira-work"]},
+                {"id": "cloud-1", "name": "acme", "url": "https://acme.atlassian.net", "scopes": ["search:confluence"]}
+            ])))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/me"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "account_id": "acc-1", "name": "Ada"
+            })))
+            .mount(&server)
+            .await;
+
+        let connection = connector(&server.uri())
+            .exchange_token(ExchangeTokenParams {
+                code: "code".to_string(),
+                redirect_uri: None,
+                tenant_id: Uuid::new_v4(),
+                code_verifier: None,
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(connection.external_id, "cloud-1");
+        let metadata = connection.metadata.unwrap();
+        assert_eq!(metadata["site_url"], "https://acme.atlassian.net");
+        assert_eq!(metadata["account"]["account_id"], "acc-1");
+        assert!(connection.expires_at.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_confluence_sync_stops_at_high_water_mark() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/ex/confluence/cloud-1/wiki/rest/api/content/search"))
+            .and(query_param("start", "0"))
+            .and(query_param(
+                "cql",
+                "type in (page, blogpost, comment) AND lastmodified >= \"2024/05/01 20:00\" ORDER BY lastmodified DESC",
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "results": [
+                    content("300", "comment", 1, "2024-05-02T12:00:00.000Z"),
+                    content("200", "page", 4, "2024-05-02T11:00:00.000Z"),
+                    content("201", "blogpost", 1, "2024-05-02T10:30:00.000Z"),
+                    content("301", "comment", 2, "2024-05-02T10:15:00.000Z"),
+                    // Already synced by the previous run
+                    content("202", "page", 2, "2024-05-02T09:00:00.000Z")
+                ],
+                "_links": {"base": "https://acme.atlassian.net/wiki", "next": "/rest/api/content/search?start=5"}
+            })))
+            .mount(&server)
+            .await;
+
+        let result = connector(&server.uri())
+            .sync(SyncParams {
+                connection: connection(),
+                cursor: Some(Cursor::from_json(
+                    serde_json::json!({"since": "2024-05-02T10:00:00Z"}),
+                )),
+                cancellation: Default::default(),
+            })
+            .await
+            .unwrap();
+
+        let kinds: Vec<_> = result.signals.iter().map(|s| s.kind.as_str()).collect();
+        // The edited comment is not a new comment signal
+        assert_eq!(kinds, vec!["issue_comment", "file_updated", "file_created"]);
+        assert_eq!(
+            result.signals[1].dedupe_key.as_deref(),
+            Some("confluence:file_updated:200:4")
+        );
+        assert_eq!(
+            result.signals[1].payload["url"],
+            "https://acme.atlassian.net/wiki/spaces/ENG/pages/200"
+        );
+        assert_eq!(result.signals[0].payload["parent_title"], "Roadmap");
+        assert!(!result.has_more);
+        assert_eq!(
+            result.next_cursor.unwrap().as_json(),
+            &serde_json::json!({"since": "2024-05-02T12:00:00Z"})
+        );
+    }
+
+    #[tokio::test]
+    async fn test_confluence_sync_requires_cloud_id() {
+        let err = connector("https://api.atlassian.com")
+            .sync(SyncParams {
+                connection: Connection {
+                    metadata: None,
+                    ..connection()
+                },
+                cursor: None,
+                cancellation: Default::default(),
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<SyncError>().unwrap().kind,
+            crate::connectors::SyncErrorKind::Permanent
+        );
+    }
+
+    #[tokio::test]
+    async fn test_confluence_webhook_mapping() {
+        let connector = connector("https://api.atlassian.com");
+        let handle = |payload: serde_json::Value| {
+            connector.handle_webhook(WebhookParams {
+                payload,
+                tenant_id: Uuid::new_v4(),
+                db: None,
+                auth_header: None,
+            })
+        };
+
+        let updated = handle(serde_json::json!({
+            "timestamp": 1714647600000i64,
+            "event": "page_updated",
+            "userAccountId": "acc-1",
+            "page": {
+                "id": 200,
+                "title": "Roadmap",
+                "spaceKey": "ENG",
+                "version": 4,
+                "self": "https://acme.atlassian.net/wiki/spaces/ENG/pages/200"
+            }
+        }))
+        .await
+        .unwrap();
+        assert_eq!(updated[0].kind, "file_updated");
+        // Matches the key produced by sync so the two paths deduplicate
+        assert_eq!(
+            updated[0].dedupe_key.as_deref(),
+            Some("confluence:file_updated:200:4")
+        );
+        assert_eq!(updated[0].payload["author_account_id"], "acc-1");
+
+        let comment = handle(serde_json::json!({
+            "timestamp": 1714651200000i64,
+            "event": "comment_created",
+            "comment": {
+                "id": "300",
+                "version": 1,
+                "parent": {"id": "200", "title": "Roadmap", "spaceKey": "ENG"}
+            }
+        }))
+        .await
+        .unwrap();
+        assert_eq!(comment[0].kind, "issue_comment");
+        assert_eq!(comment[0].payload["content_type"], "comment");
+        assert_eq!(comment[0].payload["space_key"], "ENG");
+
+        let ignored = handle(serde_json::json!({"event": "space_created"}))
+            .await
+            .unwrap();
+        assert!(ignored.is_empty());
+    }
+}
@@ -0,0 +1,434 @@
+//! Custom webhook connector implementation
+//!
+//! Lets a tenant point any SaaS tool at this service without a bespoke
+//! connector. Each connection is one endpoint with its own shared secret,
+//! verified by the public webhook handler, and mapping rules in metadata that
+//! pick the signal kind, event time and dedupe key out of the JSON body with
+//! JSONPath expressions such as `$.event.type`.
+//!
+//! Only the child (`.name`, `['name']`) and index (`[0]`, `[-1]`) selectors of
+//! JSONPath are supported, since each rule reads a single value.
+
+use anyhow::anyhow;
+use async_trait::async_trait;
+use chrono::{DateTime, TimeZone, Utc};
+use std::sync::Arc;
+use tracing::{debug, info, warn};
+use url::Url;
+use uuid::Uuid;
+
+use crate::connectors::metadata_schema::{CustomWebhookConnectionSettings, parse_settings};
+use crate::connectors::{
+    AuthType, Connector, ProviderCategory, ProviderMetadata, Registry,
+    trait_::{AuthorizeParams, ExchangeTokenParams, SyncParams, SyncResult, WebhookParams},
+};
+use crate::models::{connection::Model as Connection, signal::Model as Signal};
+use crate::normalization::AnySignalKind;
+use crate::repositories::TenantSignalKindRepository;
+
+/// Provider slug
+pub const CUSTOM_WEBHOOK_PROVIDER_SLUG: &str = "custom-webhook";
+
+/// Key of the queued payload naming the connection a delivery was sent to
+pub const CUSTOM_WEBHOOK_CONNECTION_KEY: &str = "connection_id";
+
+/// Key of the queued payload holding the delivered body
+pub const CUSTOM_WEBHOOK_BODY_KEY: &str = "body";
+
+/// Custom webhook connector
+#[derive(Default)]
+pub struct CustomWebhookConnector;
+
+impl CustomWebhookConnector {
+    /// Create a new custom webhook connector
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+/// Step of a JSONPath expression
+#[derive(Debug, Clone, PartialEq)]
+enum PathSegment {
+    Key(String),
+    /// Array index; negative indexes count from the end
+    Index(i64),
+}
+
+/// Parsed JSONPath expression reading a single value
+#[derive(Debug, Clone, PartialEq)]
+pub struct JsonPath(Vec<PathSegment>);
+
+impl JsonPath {
+    /// Parse `$`, `$.a.b`, `$['a b'][0]` and similar expressions
+    pub fn parse(expression: &str) -> Result<Self, String> {
+        let invalid = |reason: &str| format!("'{}' is not a JSONPath: {}", expression, reason);
+        let mut rest = expression
+            .trim()
+            .strip_prefix('$')
+            .ok_or_else(|| invalid("it must start with '$'"))?;
+        let mut segments = Vec::new();
+        while !rest.is_empty() {
+            if let Some(after) = rest.strip_prefix('.') {
+                let end = after.find(['.', '[']).unwrap_or(after.len());
+                let key = &after[..end];
+                if key.is_empty() || key == "*" || after.starts_with('.') {
+                    return Err(invalid("only child and index selectors are supported"));
+                }
+                segments.push(PathSegment::Key(key.to_string()));
+                rest = &after[end..];
+            } else if let Some(after) = rest.strip_prefix('[') {
+                let end = after.find(']').ok_or_else(|| invalid("unclosed '['"))?;
+                let selector = after[..end].trim();
+                let quoted = ['\'', '"'].into_iter().find_map(|quote| {
+                    selector
+                        .strip_prefix(quote)
+                        .and_then(|s| s.strip_suffix(quote))
+                });
+                match (quoted, selector.parse::<i64>()) {
+                    (Some(key), _) => segments.push(PathSegment::Key(key.to_string())),
+                    (None, Ok(index)) => segments.push(PathSegment::Index(index)),
+                    (None, Err(_)) => {
+                        return Err(invalid("only child and index selectors are supported"));
+                    }
+                }
+                rest = &after[end + 1..];
+            } else {
+                return Err(invalid("expected '.' or '['"));
+            }
+        }
+        Ok(Self(segments))
+    }
+
+    /// Value at this path, if present
+    pub fn select<'a>(&self, value: &'a serde_json::Value) -> Option<&'a serde_json::Value> {
+        self.0
+            .iter()
+            .try_fold(value, |value, segment| match segment {
+                PathSegment::Key(key) => value.get(key),
+                PathSegment::Index(index) => {
+                    let items = value.as_array()?;
+                    let position = if *index < 0 {
+                        items.len().checked_sub(index.unsigned_abs() as usize)?
+                    } else {
+                        *index as usize
+                    };
+                    items.get(position)
+                }
+            })
+    }
+}
+
+/// Value of `path` in `body` as text; objects and arrays have none
+fn select_text(path: &str, body: &serde_json::Value) -> Option<String> {
+    match JsonPath::parse(path).ok()?.select(body)? {
+        serde_json::Value::String(text) => Some(text.trim().to_string()).filter(|t| !t.is_empty()),
+        serde_json::Value::Number(number) => Some(number.to_string()),
+        serde_json::Value::Bool(flag) => Some(flag.to_string()),
+        _ => None,
+    }
+}
+
+/// RFC 3339 text, or Unix seconds or milliseconds
+fn select_time(path: &str, body: &serde_json::Value) -> Option<DateTime<Utc>> {
+    match JsonPath::parse(path).ok()?.select(body)? {
+        serde_json::Value::String(text) => DateTime::parse_from_rfc3339(text.trim())
+            .ok()
+            .map(|dt| dt.with_timezone(&Utc))
+            .or_else(|| epoch(text.trim().parse().ok()?)),
+        serde_json::Value::Number(number) => epoch(number.as_i64()?),
+        _ => None,
+    }
+}
+
+fn epoch(value: i64) -> Option<DateTime<Utc>> {
+    // Values this large are milliseconds; seconds would be past the year 5000
+    if value.abs() >= 100_000_000_000 {
+        Utc.timestamp_millis_opt(value).single()
+    } else {
+        Utc.timestamp_opt(value, 0).single()
+    }
+}
+
+/// Event read from a delivery by a connection's mapping rules
+#[derive(Debug, Clone, PartialEq)]
+struct MappedEvent {
+    kind: String,
+    occurred_at: Option<DateTime<Utc>>,
+    dedupe_id: Option<String>,
+}
+
+/// Apply mapping rules to a delivered body
+///
+/// Returns `None` when the kind cannot be read or the value read is not in
+/// `kind_map`.
+fn map_event(
+    settings: &CustomWebhookConnectionSettings,
+    body: &serde_json::Value,
+) -> Option<MappedEvent> {
+    let kind = if settings.kind.trim_start().starts_with('$') {
+        let value = select_text(&settings.kind, body)?;
+        match &settings.kind_map {
+            Some(kind_map) => kind_map.get(&value)?.clone(),
+            None => value,
+        }
+    } else {
+        settings.kind.trim().to_string()
+    };
+    Some(MappedEvent {
+        kind,
+        occurred_at: settings
+            .occurred_at
+            .as_deref()
+            .and_then(|path| select_time(path, body)),
+        dedupe_id: settings
+            .dedupe_key
+            .as_deref()
+            .and_then(|path| select_text(path, body)),
+    })
+}
+
+#[async_trait]
+impl Connector for CustomWebhookConnector {
+    async fn authorize(
+        &self,
+        _params: AuthorizeParams,
+    ) -> Result<Url, Box<dyn std::error::Error + Send + Sync>> {
+        Err(anyhow!("OAuth authorization is not supported for custom webhooks").into())
+    }
+
+    async fn exchange_token(
+        &self,
+        _params: ExchangeTokenParams,
+    ) -> Result<Connection, Box<dyn std::error::Error + Send + Sync>> {
+        Err(anyhow!("Token exchange is not supported for custom webhooks").into())
+    }
+
+    async fn refresh_token(
+        &self,
+        _connection: Connection,
+    ) -> Result<Connection, Box<dyn std::error::Error + Send + Sync>> {
+        Err(anyhow!("Token refresh is not supported for custom webhooks").into())
+    }
+
+    async fn sync(
+        &self,
+        params: SyncParams,
+    ) -> Result<SyncResult, Box<dyn std::error::Error + Send + Sync>> {
+        // Events arrive through webhooks only
+        Ok(SyncResult {
+            signals: vec![],
+            next_cursor: params.cursor,
+            has_more: false,
+        })
+    }
+
+    async fn handle_webhook(
+        &self,
+        params: WebhookParams,
+    ) -> Result<Vec<Signal>, Box<dyn std::error::Error + Send + Sync>> {
+        let Some(connection_id) = params
+            .payload
+            .get(CUSTOM_WEBHOOK_CONNECTION_KEY)
+            .and_then(|v| v.as_str())
+            .and_then(|id| Uuid::parse_str(id).ok())
+        else {
+            warn!(tenant_id = %params.tenant_id, "Custom webhook payload names no connection");
+            return Ok(vec![]);
+        };
+        let body = params
+            .payload
+            .get(CUSTOM_WEBHOOK_BODY_KEY)
+            .cloned()
+            .unwrap_or(serde_json::Value::Null);
+        let Some(db) = &params.db else {
+            warn!(
+                tenant_id = %params.tenant_id,
+                "No database connection provided for custom webhook processing"
+            );
+            return Ok(vec![]);
+        };
+
+        // Rules are read at delivery time, so edits apply to queued deliveries too
+        let connection = {
+            use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+            crate::models::connection::Entity::find_by_id(connection_id)
+                .filter(crate::models::connection::Column::TenantId.eq(params.tenant_id))
+                .one(db)
+                .await?
+        };
+        let Some(connection) = connection else {
+            warn!(
+                tenant_id = %params.tenant_id,
+                connection_id = %connection_id,
+                "Custom webhook connection no longer exists"
+            );
+            return Ok(vec![]);
+        };
+        let settings: CustomWebhookConnectionSettings =
+            parse_settings(CUSTOM_WEBHOOK_PROVIDER_SLUG, connection.metadata.as_ref())
+                .map_err(|e| anyhow!("Invalid custom webhook mapping rules: {}", e.message))?;
+
+        let Some(event) = map_event(&settings, &body) else {
+            debug!(
+                connection_id = %connection_id,
+                "Custom webhook delivery matched no signal kind"
+            );
+            return Ok(vec![]);
+        };
+        let registered = match AnySignalKind::parse(&event.kind) {
+            Ok(AnySignalKind::Canonical(_)) => true,
+            Ok(AnySignalKind::Custom(kind)) => {
+                TenantSignalKindRepository::new(db)
+                    .is_registered(params.tenant_id, &kind)
+                    .await?
+            }
+            Err(_) => false,
+        };
+        if !registered {
+            warn!(
+                connection_id = %connection_id,
+                kind = %event.kind,
+                "Custom webhook delivery mapped to an unknown or unregistered signal kind"
+            );
+            return Ok(vec![]);
+        }
+
+        let signal = build_signal(&connection, &event, body);
+        info!(
+            tenant_id = %params.tenant_id,
+            connection_id = %connection_id,
+            signal_kind = %signal.kind,
+            "Custom webhook mapped to signal"
+        );
+        Ok(vec![signal])
+    }
+}
+
+/// Register the custom webhook connector in the registry
+pub fn register_custom_webhook_connector(
+    registry: &mut Registry,
+    connector: Arc<CustomWebhookConnector>,
+) {
+    let metadata = ProviderMetadata::new(
+        CUSTOM_WEBHOOK_PROVIDER_SLUG.to_string(),
+        AuthType::Basic,
+        vec![],
+        true, // webhooks supported
+    )
+    .with_category(ProviderCategory::Other)
+    .with_description("Receive signals from any tool that can send a JSON webhook")
+    .with_icon_url("https://cdn.simpleicons.org/webhooks")
+    .with_docs_url("https://www.rfc-editor.org/rfc/rfc9535");
+
+    registry.register(connector, metadata);
+}
+
+fn build_signal(connection: &Connection, event: &MappedEvent, body: serde_json::Value) -> Signal {
+    let received_at = DateTime::from(Utc::now());
+    let occurred_at = event.occurred_at.unwrap_or_else(Utc::now);
+    Signal {
+        id: Uuid::new_v4(),
+        tenant_id: connection.tenant_id,
+        provider_slug: CUSTOM_WEBHOOK_PROVIDER_SLUG.to_string(),
+        connection_id: connection.id,
+        kind: event.kind.clone(),
+        occurred_at: occurred_at.into(),
+        received_at,
+        payload: serde_json::json!({
+            "source": connection.display_name,
+            "event": body,
+            "occurred_at": occurred_at.to_rfc3339(),
+        }),
+        dedupe_key: event.dedupe_id.as_ref().map(|id| {
+            format!(
+                "{}:{}:{}:{}",
+                CUSTOM_WEBHOOK_PROVIDER_SLUG, event.kind, connection.id, id
+            )
+        }),
+        created_at: received_at,
+        updated_at: received_at,
+    }
+}
+
+/// Build the payload queued for a delivery to `connection_id`
+pub fn custom_webhook_payload(
+    connection_id: Uuid,
+    body: Option<serde_json::Value>,
+) -> serde_json::Value {
+    serde_json::json!({
+        CUSTOM_WEBHOOK_CONNECTION_KEY: connection_id.to_string(),
+        CUSTOM_WEBHOOK_BODY_KEY: body,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_json_path_selects_single_values() {
+        let body = json!({
+            "event": {"type": "deploy.finished", "tags": ["prod", "eu"]},
+            "meta data": {"id": 42}
+        });
+        let select = |path: &str| JsonPath::parse(path).unwrap().select(&body).cloned();
+        assert_eq!(select("$.event.type"), Some(json!("deploy.finished")));
+        assert_eq!(select("$['meta data'].id"), Some(json!(42)));
+        assert_eq!(select("$.event.tags[-1]"), Some(json!("eu")));
+        assert_eq!(select("$.event[\"tags\"][0]"), Some(json!("prod")));
+        assert_eq!(select("$.event.tags[5]"), None);
+        assert_eq!(select("$"), Some(body.clone()));
+
+        assert!(JsonPath::parse("event.type").is_err());
+        assert!(JsonPath::parse("$..type").is_err());
+        assert!(JsonPath::parse("$.tags[*]").is_err());
+        assert!(JsonPath::parse("$.tags[0").is_err());
+    }
+
+    #[test]
+    fn test_mapping_rules_read_kind_time_and_dedupe_key() {
+        let settings = CustomWebhookConnectionSettings {
+            kind: "$.event.type".to_string(),
+            kind_map: Some(BTreeMap::from([(
+                "incident.opened".to_string(),
+                "incident_triggered".to_string(),
+            )])),
+            occurred_at: Some("$.event.at".to_string()),
+            dedupe_key: Some("$.event.id".to_string()),
+        };
+        let mapped = map_event(
+            &settings,
+            &json!({"event": {"type": "incident.opened", "at": 1714651200, "id": 7}}),
+        )
+        .unwrap();
+        assert_eq!(mapped.kind, "incident_triggered");
+        assert_eq!(
+            mapped.occurred_at.unwrap().to_rfc3339(),
+            "2024-05-02T12:00:00+00:00"
+        );
+        assert_eq!(mapped.dedupe_id.as_deref(), Some("7"));
+
+        // Values outside the map are dropped
+        assert!(map_event(&settings, &json!({"event": {"type": "incident.noted"}})).is_none());
+
+        // A literal kind applies to every delivery
+        let fixed = CustomWebhookConnectionSettings {
+            kind: "custom:press_mention".to_string(),
+            occurred_at: Some("$.published".to_string()),
+            ..Default::default()
+        };
+        let mapped = map_event(&fixed, &json!({"published": "2024-05-02T12:00:00Z"})).unwrap();
+        assert_eq!(mapped.kind, "custom:press_mention");
+        assert_eq!(
+            mapped.occurred_at.unwrap().to_rfc3339(),
+            "2024-05-02T12:00:00+00:00"
+        );
+        assert_eq!(mapped.dedupe_id, None);
+        assert_eq!(
+            epoch(1714651200000).unwrap().to_rfc3339(),
+            "2024-05-02T12:00:00+00:00"
+        );
+    }
+}
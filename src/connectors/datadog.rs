@@ -0,0 +1,677 @@
+//! Datadog connector implementation
+//!
+//! Datadog monitors notify this service through a webhook integration whose
+//! payload follows the template documented in the README. Deliveries carry a
+//! shared bearer token verified in [`crate::webhook_verification`] and land on
+//! the tenant's Datadog connection, created on first delivery. When an API
+//! key and application key are configured, each sync also reads monitor
+//! alerts from the Events API so notifications missed while the webhook was
+//! failing still become signals.
+
+use anyhow::anyhow;
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, SecondsFormat, TimeZone, Utc};
+use reqwest::{Client, StatusCode};
+use serde::Deserialize;
+use std::sync::Arc;
+use tracing::{debug, info};
+use url::Url;
+use uuid::Uuid;
+
+use crate::connectors::{
+    AuthType, Connector, Cursor, ProviderCategory, ProviderMetadata, Registry,
+    trait_::{
+        AuthorizeParams, ExchangeTokenParams, SyncError, SyncParams, SyncResult, WebhookParams,
+    },
+};
+use crate::models::{connection::Model as Connection, signal::Model as Signal};
+use crate::normalization::{SignalKind, normalize_datadog_alert_kind};
+
+/// Provider slug
+pub const DATADOG_PROVIDER_SLUG: &str = "datadog";
+
+/// External id of a connection whose deliveries do not name a Datadog organization
+pub const DATADOG_DEFAULT_EXTERNAL_ID: &str = "default";
+
+/// Events requested per page
+const PAGE_SIZE: u32 = 100;
+
+/// Event pages read in one sync; the rest of the window continues on the next run
+const MAX_PAGES: u32 = 5;
+
+/// How far back the first sync reads
+const INITIAL_LOOKBACK_HOURS: i64 = 24;
+
+/// Datadog connector
+pub struct DatadogConnector {
+    api_base: String,
+    api_key: Option<String>,
+    application_key: Option<String>,
+    http_client: Client,
+}
+
+#[derive(Debug, Deserialize)]
+struct EventsResponse {
+    #[serde(default)]
+    data: Vec<serde_json::Value>,
+    #[serde(default)]
+    meta: Option<serde_json::Value>,
+}
+
+/// Position of a sync: the window being read and, mid-window, the next page
+#[derive(Debug, Default, PartialEq)]
+struct EventWindow {
+    since: Option<DateTime<Utc>>,
+    until: Option<DateTime<Utc>>,
+    page: Option<String>,
+}
+
+impl EventWindow {
+    fn from_cursor(cursor: Option<&Cursor>) -> Self {
+        let Some(json) = cursor.map(Cursor::as_json) else {
+            return Self::default();
+        };
+        let time = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.as_str())
+                .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+                .map(|dt| dt.with_timezone(&Utc))
+        };
+        Self {
+            since: time("since"),
+            until: time("until"),
+            page: json
+                .get("page")
+                .and_then(|v| v.as_str())
+                .map(str::to_string),
+        }
+    }
+
+    fn to_cursor(&self) -> Cursor {
+        let format =
+            |at: &Option<DateTime<Utc>>| at.map(|at| at.to_rfc3339_opts(SecondsFormat::Secs, true));
+        Cursor::from_json(serde_json::json!({
+            "since": format(&self.since),
+            "until": format(&self.until),
+            "page": self.page,
+        }))
+    }
+}
+
+impl DatadogConnector {
+    /// Create a new Datadog connector for the API at `api_base`
+    ///
+    /// Polling is enabled only when both keys are present.
+    pub fn new(api_base: String, api_key: Option<String>, application_key: Option<String>) -> Self {
+        Self {
+            api_base: api_base.trim_end_matches('/').to_string(),
+            api_key,
+            application_key,
+            http_client: crate::egress::client(),
+        }
+    }
+
+    /// API base URL for a Datadog site such as `datadoghq.com` or `datadoghq.eu`
+    pub fn api_base_for_site(site: &str) -> String {
+        format!("https://api.{}", site.trim().trim_end_matches('/'))
+    }
+
+    /// Read one page of monitor alert events between `since` and `until`
+    async fn events_page(
+        &self,
+        api_key: &str,
+        application_key: &str,
+        since: DateTime<Utc>,
+        until: DateTime<Utc>,
+        page: Option<&str>,
+    ) -> Result<(Vec<serde_json::Value>, Option<String>), SyncError> {
+        let format = |at: DateTime<Utc>| at.to_rfc3339_opts(SecondsFormat::Secs, true);
+        let mut query = vec![
+            ("filter[query]", "source:alert".to_string()),
+            ("filter[from]", format(since)),
+            ("filter[to]", format(until)),
+            ("sort", "timestamp".to_string()),
+            ("page[limit]", PAGE_SIZE.to_string()),
+        ];
+        if let Some(page) = page {
+            query.push(("page[cursor]", page.to_string()));
+        }
+
+        let response = self
+            .http_client
+            .get(format!("{}/api/v2/events", self.api_base))
+            .header("DD-API-KEY", api_key)
+            .header("DD-APPLICATION-KEY", application_key)
+            .header("Accept", "application/json")
+            .query(&query)
+            .send()
+            .await
+            .map_err(|e| SyncError::transient(format!("Datadog request failed: {}", e)))?;
+
+        let status = response.status();
+        if status == StatusCode::TOO_MANY_REQUESTS {
+            // Datadog reports the seconds until the rate limit window resets
+            let retry_after = response
+                .headers()
+                .get("X-RateLimit-Reset")
+                .and_then(|h| h.to_str().ok())
+                .and_then(|s| s.parse::<u64>().ok());
+            return Err(SyncError::rate_limited(retry_after));
+        }
+        if status == StatusCode::UNAUTHORIZED || status == StatusCode::FORBIDDEN {
+            return Err(SyncError::unauthorized(format!(
+                "Datadog event listing failed: {}",
+                status
+            )));
+        }
+        if status.is_server_error() {
+            return Err(SyncError::transient(format!(
+                "Datadog event listing failed: {}",
+                status
+            )));
+        }
+        if !status.is_success() {
+            let body: serde_json::Value = response.json().await.unwrap_or_default();
+            return Err(
+                SyncError::permanent(format!("Datadog event listing failed: {}", status))
+                    .with_details(body),
+            );
+        }
+
+        let events: EventsResponse = response
+            .json()
+            .await
+            .map_err(|e| SyncError::transient(format!("Datadog returned bad JSON: {}", e)))?;
+        let next = events
+            .meta
+            .as_ref()
+            .and_then(|meta| meta.pointer("/page/after"))
+            .and_then(|v| v.as_str())
+            .filter(|after| !after.is_empty())
+            .map(str::to_string);
+        Ok((events.data, next))
+    }
+}
+
+#[async_trait]
+impl Connector for DatadogConnector {
+    async fn authorize(
+        &self,
+        _params: AuthorizeParams,
+    ) -> Result<Url, Box<dyn std::error::Error + Send + Sync>> {
+        Err(anyhow!("OAuth authorization is not supported for Datadog").into())
+    }
+
+    async fn exchange_token(
+        &self,
+        _params: ExchangeTokenParams,
+    ) -> Result<Connection, Box<dyn std::error::Error + Send + Sync>> {
+        Err(anyhow!("Token exchange is not supported for Datadog").into())
+    }
+
+    async fn refresh_token(
+        &self,
+        _connection: Connection,
+    ) -> Result<Connection, Box<dyn std::error::Error + Send + Sync>> {
+        Err(anyhow!("Token refresh is not supported for Datadog").into())
+    }
+
+    async fn sync(
+        &self,
+        params: SyncParams,
+    ) -> Result<SyncResult, Box<dyn std::error::Error + Send + Sync>> {
+        let connection = &params.connection;
+        let (Some(api_key), Some(application_key)) = (&self.api_key, &self.application_key) else {
+            // Alerts arrive through webhooks only
+            return Ok(SyncResult {
+                signals: vec![],
+                next_cursor: params.cursor,
+                has_more: false,
+            });
+        };
+        info!(
+            tenant_id = %connection.tenant_id,
+            connection_id = %connection.id,
+            has_cursor = %params.cursor.is_some(),
+            "Starting Datadog sync"
+        );
+
+        let window = EventWindow::from_cursor(params.cursor.as_ref());
+        let now = Utc::now();
+        let since = window
+            .since
+            .unwrap_or_else(|| now - Duration::hours(INITIAL_LOOKBACK_HOURS));
+        // A window is fixed once paging starts so later pages line up
+        let until = match (&window.page, window.until) {
+            (Some(_), Some(until)) => until,
+            _ => now,
+        };
+
+        let mut signals = Vec::new();
+        let mut page = window.page;
+        for _ in 0..MAX_PAGES {
+            let (events, next) = self
+                .events_page(api_key, application_key, since, until, page.as_deref())
+                .await?;
+            for event in &events {
+                let alert = alert_from_event(event);
+                if let Some(kind) = normalize_datadog_alert_kind(&alert) {
+                    signals.push(build_alert_signal(
+                        connection.tenant_id,
+                        connection.id,
+                        kind,
+                        &alert,
+                    ));
+                }
+            }
+            page = next;
+            if page.is_none() {
+                break;
+            }
+        }
+
+        let has_more = page.is_some();
+        let next_window = if has_more {
+            EventWindow {
+                since: Some(since),
+                until: Some(until),
+                page,
+            }
+        } else {
+            EventWindow {
+                since: Some(until),
+                ..Default::default()
+            }
+        };
+
+        debug!(
+            connection_id = %connection.id,
+            signals = signals.len(),
+            has_more,
+            "Datadog sync completed"
+        );
+
+        Ok(SyncResult {
+            signals,
+            next_cursor: Some(next_window.to_cursor()),
+            has_more,
+        })
+    }
+
+    async fn handle_webhook(
+        &self,
+        params: WebhookParams,
+    ) -> Result<Vec<Signal>, Box<dyn std::error::Error + Send + Sync>> {
+        let alert = &params.payload;
+        let Some(kind) = normalize_datadog_alert_kind(alert) else {
+            debug!(
+                tenant_id = %params.tenant_id,
+                transition = ?alert.get("transition"),
+                "Datadog webhook ignored"
+            );
+            return Ok(vec![]);
+        };
+
+        let signal = build_alert_signal(params.tenant_id, Uuid::nil(), kind, alert);
+        info!(
+            tenant_id = %params.tenant_id,
+            signal_kind = %kind,
+            severity = %signal.payload["severity"],
+            "Datadog webhook mapped to signal"
+        );
+        Ok(vec![signal])
+    }
+}
+
+/// Register the Datadog connector in the registry
+pub fn register_datadog_connector(registry: &mut Registry, connector: Arc<DatadogConnector>) {
+    let metadata = ProviderMetadata::new(
+        DATADOG_PROVIDER_SLUG.to_string(),
+        AuthType::ApiKey,
+        vec![],
+        true, // webhooks supported
+    )
+    .with_category(ProviderCategory::Monitoring)
+    .with_description("Receive monitor alerts and recoveries from Datadog")
+    .with_icon_url("https://cdn.simpleicons.org/datadog")
+    .with_docs_url("https://docs.datadoghq.com/integrations/webhooks/")
+    .with_backfill(connector.api_key.is_some() && connector.application_key.is_some())
+    .with_signal_kinds(&[
+        SignalKind::IncidentTriggered,
+        SignalKind::IncidentUpdated,
+        SignalKind::IncidentResolved,
+    ]);
+
+    registry.register(connector, metadata);
+}
+
+/// External id of the connection a webhook delivery belongs to
+pub fn datadog_external_id(payload: Option<&serde_json::Value>) -> String {
+    payload
+        .and_then(|p| p.pointer("/org/id"))
+        .and_then(|v| match v {
+            serde_json::Value::String(s) => Some(s.trim().to_string()),
+            serde_json::Value::Number(n) => Some(n.to_string()),
+            _ => None,
+        })
+        .filter(|id| !id.is_empty())
+        .unwrap_or_else(|| DATADOG_DEFAULT_EXTERNAL_ID.to_string())
+}
+
+/// Reshape an Events API alert event into the webhook template's fields
+fn alert_from_event(event: &serde_json::Value) -> serde_json::Value {
+    let attributes = &event["attributes"];
+    let inner = &attributes["attributes"];
+    let tags: Vec<&str> = attributes
+        .get("tags")
+        .and_then(|v| v.as_array())
+        .map(|tags| tags.iter().filter_map(|t| t.as_str()).collect())
+        .unwrap_or_default();
+
+    serde_json::json!({
+        // The legacy event id is what webhooks send as `$ID`
+        "id": inner.pointer("/evt/id").or_else(|| event.get("id")),
+        "alert_id": inner.get("monitor_id"),
+        "title": inner.get("title").or_else(|| attributes.get("title")),
+        "body": attributes.get("message"),
+        "alert_type": inner.get("alert_type").or_else(|| inner.get("status")),
+        "priority": inner.get("priority"),
+        "date": inner.get("timestamp").or_else(|| attributes.get("timestamp")),
+        "tags": tags.join(","),
+        "scope": inner.pointer("/monitor/group"),
+        "link": inner.get("url"),
+    })
+}
+
+/// Text of a template field that may arrive as a string or a number
+fn field(alert: &serde_json::Value, key: &str) -> Option<String> {
+    match alert.get(key)? {
+        serde_json::Value::String(s) if !s.trim().is_empty() => Some(s.trim().to_string()),
+        serde_json::Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+/// `$DATE` is epoch milliseconds; Events API timestamps may also be RFC 3339
+fn parse_date(alert: &serde_json::Value) -> Option<DateTime<Utc>> {
+    let date = field(alert, "date")?;
+    match date.parse::<i64>() {
+        Ok(millis) => Utc.timestamp_millis_opt(millis).single(),
+        Err(_) => DateTime::parse_from_rfc3339(&date)
+            .ok()
+            .map(|dt| dt.with_timezone(&Utc)),
+    }
+}
+
+/// Incident severity for an alert
+///
+/// Error alerts are critical and warnings or missing data are warnings.
+/// Recoveries keep the severity of the alert type Datadog reports with them.
+fn severity(alert: &serde_json::Value) -> &'static str {
+    let transition = field(alert, "transition")
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    if transition.contains("no data") {
+        return "warning";
+    }
+    match field(alert, "alert_type")
+        .unwrap_or_default()
+        .to_ascii_lowercase()
+        .as_str()
+    {
+        "error" => "critical",
+        "warning" | "warn" => "warning",
+        _ => "info",
+    }
+}
+
+/// Build an incident signal from a webhook payload or reshaped Events API alert
+///
+/// Both paths key on the Datadog event id so an alert seen by a webhook and a
+/// later sync produces one signal.
+fn build_alert_signal(
+    tenant_id: Uuid,
+    connection_id: Uuid,
+    kind: SignalKind,
+    alert: &serde_json::Value,
+) -> Signal {
+    let occurred_at = parse_date(alert).unwrap_or_else(Utc::now);
+    let received_at = DateTime::from(Utc::now());
+    let monitor_id = field(alert, "alert_id");
+    let dedupe_key = match field(alert, "id") {
+        Some(event_id) => format!("datadog:{}:{}", kind, event_id),
+        None => format!(
+            "datadog:{}:{}:{}",
+            kind,
+            monitor_id.as_deref().unwrap_or_default(),
+            occurred_at.timestamp_millis()
+        ),
+    };
+    let tags: Vec<String> = match alert.get("tags") {
+        Some(serde_json::Value::Array(tags)) => tags
+            .iter()
+            .filter_map(|t| t.as_str().map(str::to_string))
+            .collect(),
+        Some(serde_json::Value::String(tags)) => tags
+            .split(',')
+            .map(str::trim)
+            .filter(|t| !t.is_empty())
+            .map(str::to_string)
+            .collect(),
+        _ => vec![],
+    };
+
+    Signal {
+        id: Uuid::new_v4(),
+        tenant_id,
+        provider_slug: DATADOG_PROVIDER_SLUG.to_string(),
+        connection_id,
+        kind: kind.as_str().to_string(),
+        occurred_at: occurred_at.into(),
+        received_at,
+        payload: serde_json::json!({
+            "severity": severity(alert),
+            "priority": field(alert, "priority"),
+            "monitor_id": monitor_id,
+            "event_id": field(alert, "id"),
+            "transition": field(alert, "transition"),
+            "alert_type": field(alert, "alert_type"),
+            "title": field(alert, "title"),
+            "body": field(alert, "body"),
+            "scope": field(alert, "scope"),
+            "tags": tags,
+            "url": field(alert, "link"),
+            "occurred_at": occurred_at.to_rfc3339(),
+        }),
+        dedupe_key: Some(dedupe_key),
+        created_at: received_at,
+        updated_at: received_at,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{header, method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn connection() -> Connection {
+        let now = DateTime::from(Utc::now());
+        Connection {
+            id: Uuid::new_v4(),
+            tenant_id: Uuid::new_v4(),
+            provider_slug: DATADOG_PROVIDER_SLUG.to_string(),
+            external_id: "42".to_string(),
+            status: "active".to_string(),
+            display_name: Some("Datadog".to_string()),
+            access_token_ciphertext: None,
+            refresh_token_ciphertext: None,
+            access_token: None,
+            refresh_token: None,
+            expires_at: None,
+            scopes: None,
+            metadata: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    fn webhook(payload: serde_json::Value) -> WebhookParams {
+        WebhookParams {
+            payload,
+            tenant_id: Uuid::new_v4(),
+            db: None,
+            auth_header: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_datadog_webhook_mapping() {
+        let connector = DatadogConnector::new("https://api.datadoghq.com".to_string(), None, None);
+
+        let triggered = connector
+            .handle_webhook(webhook(serde_json::json!({
+                "id": "7412345678901234567",
+                "alert_id": "123456",
+                "title": "[P1] [Triggered on {host:web-1}] CPU usage is high",
+                "body": "CPU usage above 90% for 5 minutes",
+                "transition": "Triggered",
+                "alert_type": "error",
+                "priority": "P1",
+                "date": "1714651200000",
+                "tags": "env:prod, service:web",
+                "scope": "host:web-1",
+                "org": {"id": "42", "name": "Acme"}
+            })))
+            .await
+            .unwrap();
+        assert_eq!(triggered[0].kind, "incident_triggered");
+        assert_eq!(triggered[0].payload["severity"], "critical");
+        assert_eq!(triggered[0].payload["monitor_id"], "123456");
+        assert_eq!(
+            triggered[0].payload["tags"],
+            serde_json::json!(["env:prod", "service:web"])
+        );
+        assert_eq!(
+            triggered[0].occurred_at.to_rfc3339(),
+            "2024-05-02T12:00:00+00:00"
+        );
+        assert_eq!(
+            triggered[0].dedupe_key.as_deref(),
+            Some("datadog:incident_triggered:7412345678901234567")
+        );
+
+        let no_data = connector
+            .handle_webhook(webhook(serde_json::json!({
+                "id": "1",
+                "alert_id": "123456",
+                "transition": "No Data",
+                "alert_type": "error"
+            })))
+            .await
+            .unwrap();
+        assert_eq!(no_data[0].kind, "incident_triggered");
+        assert_eq!(no_data[0].payload["severity"], "warning");
+
+        let ignored = connector
+            .handle_webhook(webhook(serde_json::json!({
+                "id": "2",
+                "title": "Monitor muted",
+                "transition": "Muted"
+            })))
+            .await
+            .unwrap();
+        assert!(ignored.is_empty());
+
+        assert_eq!(
+            datadog_external_id(Some(&serde_json::json!({"org": {"id": 42}}))),
+            "42"
+        );
+        assert_eq!(datadog_external_id(None), DATADOG_DEFAULT_EXTERNAL_ID);
+    }
+
+    #[tokio::test]
+    async fn test_datadog_sync_reads_alert_events() {
+        let server = MockServer::start().await;
+        let since = "2024-05-02T00:00:00Z";
+        Mock::given(method("GET"))
+            .and(path("/api/v2/events"))
+            .and(header("DD-API-KEY", "api-key"))
+            .and(header("DD-APPLICATION-KEY", "app-key"))
+            .and(query_param("filter[query]", "source:alert"))
+            .and(query_param("filter[from]", since))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": [
+                    {
+                        "id": "AQAAAY9",
+                        "type": "event",
+                        "attributes": {
+                            "message": "CPU usage back below 90%",
+                            "tags": ["env:prod", "monitor"],
+                            "timestamp": "2024-05-02T13:00:00Z",
+                            "attributes": {
+                                "evt": {"id": "7412345678901240000"},
+                                "monitor_id": 123456,
+                                "title": "[P1] [Recovered on {host:web-1}] CPU usage is high",
+                                "status": "success",
+                                "priority": "P1",
+                                "timestamp": 1714654800000i64
+                            }
+                        }
+                    },
+                    {
+                        "id": "AQAAAZ0",
+                        "type": "event",
+                        "attributes": {
+                            "message": "Deployment finished",
+                            "attributes": {"title": "Deployed web"}
+                        }
+                    }
+                ],
+                "meta": {"page": {"after": ""}}
+            })))
+            .mount(&server)
+            .await;
+
+        let connector = DatadogConnector::new(
+            server.uri(),
+            Some("api-key".to_string()),
+            Some("app-key".to_string()),
+        );
+        let result = connector
+            .sync(SyncParams {
+                connection: connection(),
+                cursor: Some(Cursor::from_json(serde_json::json!({"since": since}))),
+                cancellation: Default::default(),
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(result.signals.len(), 1);
+        let signal = &result.signals[0];
+        assert_eq!(signal.kind, "incident_resolved");
+        assert_eq!(signal.payload["severity"], "info");
+        assert_eq!(signal.payload["monitor_id"], "123456");
+        assert_eq!(
+            signal.dedupe_key.as_deref(),
+            Some("datadog:incident_resolved:7412345678901240000")
+        );
+        assert!(!result.has_more);
+        assert!(
+            EventWindow::from_cursor(result.next_cursor.as_ref())
+                .since
+                .is_some()
+        );
+
+        // Without keys the connector is webhook-only
+        let webhook_only = DatadogConnector::new(server.uri(), None, None)
+            .sync(SyncParams {
+                connection: connection(),
+                cursor: None,
+                cancellation: Default::default(),
+            })
+            .await
+            .unwrap();
+        assert!(webhook_only.signals.is_empty());
+    }
+}
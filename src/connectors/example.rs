@@ -10,7 +10,7 @@ use url::Url;
 use uuid::Uuid;
 
 use crate::connectors::{
-    AuthType, Connector, ConnectorError, ProviderMetadata, Registry,
+    AuthType, Connector, ConnectorError, ProviderCategory, ProviderMetadata, Registry,
     trait_::{AuthorizeParams, ExchangeTokenParams, SyncParams, SyncResult, WebhookParams},
 };
 use crate::models::{connection::Model as Connection, signal::Model as Signal};
@@ -117,8 +117,10 @@ impl Connector for ExampleConnector {
             external_id: "user_123".to_string(),
             status: "active".to_string(),
             display_name: Some("Example Connection".to_string()),
-            access_token_ciphertext: Some(b"mock_access_token".to_vec()),
-            refresh_token_ciphertext: Some(b"mock_refresh_token".to_vec()),
+            access_token_ciphertext: None,
+            refresh_token_ciphertext: None,
+            access_token: Some("mock_access_token".to_string()),
+            refresh_token: Some("mock_refresh_token".to_string()),
             expires_at: Some(now + chrono::Duration::hours(1)),
             scopes: Some(serde_json::json!(["read", "write"])),
             metadata: Some(serde_json::json!({"provider": "example"})),
@@ -140,8 +142,10 @@ impl Connector for ExampleConnector {
             external_id: connection.external_id,
             status: connection.status,
             display_name: connection.display_name,
-            access_token_ciphertext: Some(b"refreshed_access_token".to_vec()),
-            refresh_token_ciphertext: Some(b"new_refresh_token".to_vec()),
+            access_token_ciphertext: None,
+            refresh_token_ciphertext: None,
+            access_token: Some("refreshed_access_token".to_string()),
+            refresh_token: Some("new_refresh_token".to_string()),
             expires_at: Some(now + chrono::Duration::hours(1)),
             scopes: connection.scopes,
             metadata: connection.metadata,
@@ -219,7 +223,9 @@ pub fn register_example_connector(registry: &mut Registry) {
             "read:user".to_string(),
         ],
         true, // webhooks supported
-    );
+    )
+    .with_category(ProviderCategory::Code)
+    .with_description("Reference connector used for development and tests");
 
     let connector = Arc::new(ExampleConnector);
     registry.register(connector, metadata);
@@ -0,0 +1,575 @@
+//! Fake connectors driven by a scenario file
+//!
+//! Compiled only with the `fake-connectors` feature. Each provider in the
+//! scenario (`POBLYSH_FAKE_CONNECTOR_SCENARIO`, a JSON file) is registered under
+//! a `fake-*` slug and replays a fixed list of steps: each sync runs the step
+//! its cursor points at, emitting the configured signals or failing with the
+//! configured error. Signal payloads and dedupe keys depend only on the
+//! scenario seed, provider, connection and step, so full-stack tests and demos
+//! see the same data on every run without provider credentials.
+//!
+//! ```json
+//! {
+//!   "seed": 42,
+//!   "providers": [{
+//!     "slug": "fake-code",
+//!     "steps": [
+//!       {"signals": 3, "kind": "issue_created"},
+//!       {"signals": 1, "kind": "pr_opened",
+//!        "fail": {"error": "rate_limited", "retry_after_secs": 30}, "fail_times": 2},
+//!       {"fail": {"error": "unauthorized"}, "fail_times": 5}
+//!     ],
+//!     "webhooks": [{"kind": "issue_comment", "signals": 2}]
+//!   }]
+//! }
+//! ```
+//!
+//! A failing step fails its first `fail_times` attempts per connection, then
+//! succeeds. Webhook payloads use the same shape as the `webhooks` entries and
+//! are accepted on `/webhooks/{slug}/{tenant_id}` with operator auth, since fake
+//! providers have no signing secret.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use sea_orm::DatabaseConnection;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use url::Url;
+use uuid::Uuid;
+
+use crate::connectors::{
+    AuthType, Connector, ProviderCategory, ProviderMetadata, Registry, SyncError,
+    trait_::{AuthorizeParams, Cursor, ExchangeTokenParams, SyncParams, SyncResult, WebhookParams},
+};
+use crate::models::{connection::Model as Connection, signal::Model as Signal};
+use crate::normalization::is_canonical_kind;
+use crate::repositories::ProviderRepository;
+
+/// Slug prefix reserved for fake providers
+pub const FAKE_PROVIDER_PREFIX: &str = "fake";
+
+/// Words used to build deterministic signal titles
+const TITLE_WORDS: &[&str] = &[
+    "billing",
+    "onboarding",
+    "latency",
+    "export",
+    "search",
+    "login",
+    "invoice",
+    "dashboard",
+    "webhook",
+    "migration",
+    "pricing",
+    "mobile",
+];
+
+/// Scenario file describing every fake provider
+#[derive(Debug, Clone, Deserialize)]
+pub struct FakeScenario {
+    /// Seed mixed into every generated payload
+    #[serde(default)]
+    pub seed: u64,
+    pub providers: Vec<FakeProviderScenario>,
+}
+
+/// Steps replayed by one fake provider
+#[derive(Debug, Clone, Deserialize)]
+pub struct FakeProviderScenario {
+    /// Provider slug; must be `fake` or start with `fake-`
+    pub slug: String,
+    #[serde(default)]
+    pub category: Option<ProviderCategory>,
+    /// Steps run by successive syncs
+    pub steps: Vec<FakeStep>,
+    /// Start over from the first step once every step has run
+    #[serde(default)]
+    pub repeat: bool,
+    /// Example webhook payloads for tests and demos to deliver
+    #[serde(default)]
+    pub webhooks: Vec<FakeWebhook>,
+}
+
+/// One sync page
+#[derive(Debug, Clone, Deserialize)]
+pub struct FakeStep {
+    /// Number of signals to emit
+    #[serde(default)]
+    pub signals: u32,
+    /// Canonical kind of the emitted signals
+    #[serde(default = "default_kind")]
+    pub kind: String,
+    /// Report more pages so the executor continues in the same run
+    #[serde(default)]
+    pub has_more: bool,
+    /// Error returned by the first `fail_times` attempts
+    #[serde(default)]
+    pub fail: Option<FakeFailure>,
+    #[serde(default = "default_fail_times")]
+    pub fail_times: u32,
+}
+
+/// Error a step fails with
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "error", rename_all = "snake_case")]
+pub enum FakeFailure {
+    Unauthorized,
+    RateLimited {
+        #[serde(default)]
+        retry_after_secs: Option<u64>,
+    },
+    Transient,
+    Permanent,
+}
+
+/// A webhook payload understood by fake providers
+#[derive(Debug, Clone, Deserialize, serde::Serialize)]
+pub struct FakeWebhook {
+    #[serde(default = "default_kind")]
+    pub kind: String,
+    #[serde(default = "default_webhook_signals")]
+    pub signals: u32,
+    /// Distinguishes otherwise identical deliveries
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub delivery_id: Option<String>,
+}
+
+fn default_kind() -> String {
+    "issue_created".to_string()
+}
+
+fn default_fail_times() -> u32 {
+    1
+}
+
+fn default_webhook_signals() -> u32 {
+    1
+}
+
+impl FakeScenario {
+    /// Read and validate a scenario file
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let raw = std::fs::read_to_string(path)
+            .map_err(|e| format!("failed to read {}: {}", path.display(), e))?;
+        let scenario: Self = serde_json::from_str(&raw)
+            .map_err(|e| format!("invalid scenario {}: {}", path.display(), e))?;
+        scenario.validate()?;
+        Ok(scenario)
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        for provider in &self.providers {
+            if provider.slug != FAKE_PROVIDER_PREFIX
+                && !provider
+                    .slug
+                    .starts_with(&format!("{}-", FAKE_PROVIDER_PREFIX))
+            {
+                return Err(format!(
+                    "provider slug '{}' must be '{}' or start with '{}-'",
+                    provider.slug, FAKE_PROVIDER_PREFIX, FAKE_PROVIDER_PREFIX
+                ));
+            }
+            let kinds = provider
+                .steps
+                .iter()
+                .map(|step| &step.kind)
+                .chain(provider.webhooks.iter().map(|webhook| &webhook.kind));
+            for kind in kinds {
+                if !is_canonical_kind(kind) {
+                    return Err(format!(
+                        "provider '{}' uses unknown signal kind '{}'",
+                        provider.slug, kind
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Connector replaying one provider's scenario
+pub struct FakeConnector {
+    seed: u64,
+    scenario: FakeProviderScenario,
+    /// Attempts per connection and step, so failures clear after `fail_times`
+    attempts: Mutex<HashMap<(Uuid, u64), u32>>,
+}
+
+impl FakeConnector {
+    pub fn new(seed: u64, scenario: FakeProviderScenario) -> Self {
+        Self {
+            seed,
+            scenario,
+            attempts: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Example webhook payloads from the scenario, ready to deliver
+    pub fn webhook_payloads(&self) -> Vec<serde_json::Value> {
+        self.scenario
+            .webhooks
+            .iter()
+            .filter_map(|webhook| serde_json::to_value(webhook).ok())
+            .collect()
+    }
+
+    fn step_index(cursor: Option<&Cursor>) -> u64 {
+        cursor
+            .and_then(|cursor| cursor.as_json().get("step"))
+            .and_then(|step| step.as_u64())
+            .unwrap_or(0)
+    }
+
+    /// Step for a cursor position; `None` once a non-repeating scenario is exhausted
+    fn step_at(&self, position: u64) -> Option<&FakeStep> {
+        let len = self.scenario.steps.len() as u64;
+        if len == 0 || (!self.scenario.repeat && position >= len) {
+            return None;
+        }
+        self.scenario.steps.get((position % len) as usize)
+    }
+
+    fn record_attempt(&self, connection_id: Uuid, position: u64) -> u32 {
+        let mut attempts = self.attempts.lock().unwrap();
+        let count = attempts.entry((connection_id, position)).or_insert(0);
+        *count += 1;
+        *count
+    }
+
+    /// Deterministic signal for `scope` (a step position or webhook delivery) and index
+    fn signal(
+        &self,
+        tenant_id: Uuid,
+        connection_id: Uuid,
+        kind: &str,
+        scope: &str,
+        index: u32,
+    ) -> Signal {
+        let digest = Sha256::new()
+            .chain_update(self.seed.to_be_bytes())
+            .chain_update(self.scenario.slug.as_bytes())
+            .chain_update(connection_id.as_bytes())
+            .chain_update(scope.as_bytes())
+            .chain_update(index.to_be_bytes())
+            .finalize();
+        let mut seed = [0u8; 32];
+        seed.copy_from_slice(&digest);
+        let mut rng = StdRng::from_seed(seed);
+
+        let topic = TITLE_WORDS[rng.gen_range(0..TITLE_WORDS.len())];
+        let number = rng.gen_range(1..10_000);
+        let now: DateTime<_> = Utc::now().into();
+        Signal {
+            id: Uuid::new_v4(),
+            tenant_id,
+            provider_slug: self.scenario.slug.clone(),
+            connection_id,
+            kind: kind.to_string(),
+            occurred_at: now,
+            received_at: now,
+            payload: serde_json::json!({
+                "fake": true,
+                "scope": scope,
+                "index": index,
+                "number": number,
+                "title": format!("{} {} #{}", topic, kind.replace('_', " "), number),
+                "author": format!("user-{}", rng.gen_range(1..=20)),
+            }),
+            dedupe_key: Some(format!(
+                "{}:{}:{}:{}",
+                self.scenario.slug, connection_id, scope, index
+            )),
+            created_at: now,
+            updated_at: now,
+        }
+    }
+}
+
+impl FakeFailure {
+    fn to_sync_error(&self, slug: &str) -> SyncError {
+        let message = format!("{} scenario failure", slug);
+        match self {
+            FakeFailure::Unauthorized => SyncError::unauthorized(message),
+            FakeFailure::RateLimited { retry_after_secs } => {
+                SyncError::rate_limited_with_message(*retry_after_secs, message)
+            }
+            FakeFailure::Transient => SyncError::transient(message),
+            FakeFailure::Permanent => SyncError::permanent(message),
+        }
+    }
+}
+
+#[async_trait]
+impl Connector for FakeConnector {
+    async fn authorize(
+        &self,
+        params: AuthorizeParams,
+    ) -> Result<Url, Box<dyn std::error::Error + Send + Sync>> {
+        let mut url = Url::parse("https://fake.invalid/oauth/authorize")?;
+        url.query_pairs_mut()
+            .append_pair("provider", &self.scenario.slug)
+            .append_pair("state", &params.state.unwrap_or_default())
+            .append_pair("redirect_uri", &params.redirect_uri.unwrap_or_default());
+        Ok(url)
+    }
+
+    async fn exchange_token(
+        &self,
+        params: ExchangeTokenParams,
+    ) -> Result<Connection, Box<dyn std::error::Error + Send + Sync>> {
+        let now = DateTime::from(Utc::now());
+        Ok(Connection {
+            id: Uuid::new_v4(),
+            tenant_id: params.tenant_id,
+            provider_slug: self.scenario.slug.clone(),
+            external_id: format!("{}-{}", self.scenario.slug, params.code),
+            status: "active".to_string(),
+            display_name: Some(format!("Fake account {}", params.code)),
+            access_token: Some("fake-access-token".to_string()),
+            refresh_token: Some("fake-refresh-token".to_string()),
+            expires_at: Some(now + chrono::Duration::hours(1)),
+            scopes: None,
+            metadata: None,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    async fn refresh_token(
+        &self,
+        connection: Connection,
+    ) -> Result<Connection, Box<dyn std::error::Error + Send + Sync>> {
+        let now = DateTime::from(Utc::now());
+        Ok(Connection {
+            access_token: Some("fake-access-token".to_string()),
+            expires_at: Some(now + chrono::Duration::hours(1)),
+            updated_at: now,
+            ..connection
+        })
+    }
+
+    async fn sync(
+        &self,
+        params: SyncParams,
+    ) -> Result<SyncResult, Box<dyn std::error::Error + Send + Sync>> {
+        let connection = &params.connection;
+        let position = Self::step_index(params.cursor.as_ref());
+        let Some(step) = self.step_at(position) else {
+            return Ok(SyncResult {
+                signals: Vec::new(),
+                next_cursor: params.cursor,
+                has_more: false,
+            });
+        };
+
+        if let Some(failure) = &step.fail
+            && self.record_attempt(connection.id, position) <= step.fail_times
+        {
+            return Err(Box::new(failure.to_sync_error(&self.scenario.slug)));
+        }
+
+        let scope = format!("step-{}", position);
+        let signals = (0..step.signals)
+            .map(|index| {
+                self.signal(
+                    connection.tenant_id,
+                    connection.id,
+                    &step.kind,
+                    &scope,
+                    index,
+                )
+            })
+            .collect();
+
+        Ok(SyncResult {
+            signals,
+            next_cursor: Some(Cursor::from_json(
+                serde_json::json!({ "step": position + 1 }),
+            )),
+            has_more: step.has_more,
+        })
+    }
+
+    async fn handle_webhook(
+        &self,
+        params: WebhookParams,
+    ) -> Result<Vec<Signal>, Box<dyn std::error::Error + Send + Sync>> {
+        let webhook: FakeWebhook = serde_json::from_value(params.payload.clone())
+            .map_err(|e| SyncError::permanent(format!("invalid fake webhook payload: {}", e)))?;
+        if !is_canonical_kind(&webhook.kind) {
+            return Err(Box::new(SyncError::permanent(format!(
+                "unknown signal kind '{}'",
+                webhook.kind
+            ))));
+        }
+
+        let scope = format!(
+            "webhook-{}",
+            webhook.delivery_id.as_deref().unwrap_or("default")
+        );
+        // The executor assigns the job's connection to persisted signals
+        Ok((0..webhook.signals)
+            .map(|index| self.signal(params.tenant_id, Uuid::nil(), &webhook.kind, &scope, index))
+            .collect())
+    }
+}
+
+/// Register a connector for every provider in the configured scenario
+pub fn register_fake_connectors(registry: &mut Registry, scenario: FakeScenario) {
+    for provider in scenario.providers {
+        let metadata = ProviderMetadata::new(
+            provider.slug.clone(),
+            AuthType::OAuth2,
+            Vec::new(),
+            true, // webhooks supported
+        )
+        .with_category(provider.category.unwrap_or(ProviderCategory::Other))
+        .with_description("Deterministic fake provider for tests and demos");
+
+        registry.register(
+            Arc::new(FakeConnector::new(scenario.seed, provider)),
+            metadata,
+        );
+    }
+}
+
+/// Ensure every registered fake provider has a `providers` row, so connections can reference it
+pub async fn seed_fake_providers(db: &DatabaseConnection) -> anyhow::Result<()> {
+    let slugs: Vec<String> = Registry::global()
+        .read()
+        .unwrap()
+        .list_metadata()
+        .into_iter()
+        .map(|metadata| metadata.name)
+        .filter(|name| {
+            name == FAKE_PROVIDER_PREFIX || name.starts_with(&format!("{}-", FAKE_PROVIDER_PREFIX))
+        })
+        .collect();
+
+    let repo = ProviderRepository::new(Arc::new(db.clone()));
+    for slug in slugs {
+        repo.upsert(&slug, &format!("Fake ({})", slug), "oauth2")
+            .await?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scenario() -> FakeScenario {
+        serde_json::from_value(serde_json::json!({
+            "seed": 7,
+            "providers": [{
+                "slug": "fake-code",
+                "steps": [
+                    {"signals": 2, "kind": "issue_created"},
+                    {"signals": 1, "kind": "pr_opened",
+                     "fail": {"error": "rate_limited", "retry_after_secs": 30}, "fail_times": 2}
+                ],
+                "webhooks": [{"kind": "issue_comment", "signals": 2}]
+            }]
+        }))
+        .unwrap()
+    }
+
+    fn connection() -> Connection {
+        let now = DateTime::from(Utc::now());
+        Connection {
+            id: Uuid::new_v4(),
+            tenant_id: Uuid::new_v4(),
+            provider_slug: "fake-code".to_string(),
+            external_id: "fake-1".to_string(),
+            status: "active".to_string(),
+            display_name: None,
+            access_token: None,
+            refresh_token: None,
+            expires_at: None,
+            scopes: None,
+            metadata: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    async fn sync(
+        connector: &FakeConnector,
+        connection: &Connection,
+        cursor: Option<Cursor>,
+    ) -> Result<SyncResult, Box<dyn std::error::Error + Send + Sync>> {
+        connector
+            .sync(SyncParams {
+                connection: connection.clone(),
+                cursor,
+                cancellation: Default::default(),
+            })
+            .await
+    }
+
+    #[tokio::test]
+    async fn test_steps_replay_deterministically_and_fail_as_scripted() {
+        let scenario = scenario();
+        scenario.validate().unwrap();
+        let provider = scenario.providers[0].clone();
+        let connection = connection();
+
+        let first = FakeConnector::new(7, provider.clone());
+        let page = sync(&first, &connection, None).await.unwrap();
+        assert_eq!(page.signals.len(), 2);
+        let cursor = page.next_cursor.clone();
+
+        // A fresh connector with the same seed produces identical payloads
+        let replay = sync(&FakeConnector::new(7, provider), &connection, None)
+            .await
+            .unwrap();
+        assert_eq!(page.signals[0].payload, replay.signals[0].payload);
+        assert_eq!(page.signals[0].dedupe_key, replay.signals[0].dedupe_key);
+
+        for _ in 0..2 {
+            let err = sync(&first, &connection, cursor.clone()).await.unwrap_err();
+            let err = err.downcast_ref::<SyncError>().unwrap();
+            assert_eq!(
+                err.kind,
+                crate::connectors::SyncErrorKind::RateLimited {
+                    retry_after_secs: Some(30)
+                }
+            );
+        }
+        let page = sync(&first, &connection, cursor).await.unwrap();
+        assert_eq!(page.signals[0].kind, "pr_opened");
+
+        // Non-repeating scenarios stop once every step has run
+        let done = sync(&first, &connection, page.next_cursor).await.unwrap();
+        assert!(done.signals.is_empty());
+        assert!(!done.has_more);
+
+        let webhook = first
+            .handle_webhook(WebhookParams {
+                payload: first.webhook_payloads()[0].clone(),
+                tenant_id: connection.tenant_id,
+                db: None,
+                auth_header: None,
+            })
+            .await
+            .unwrap();
+        assert_eq!(webhook.len(), 2);
+        assert_eq!(webhook[0].kind, "issue_comment");
+    }
+
+    #[test]
+    fn test_scenario_rejects_non_fake_slugs_and_unknown_kinds() {
+        let mut bad_slug = scenario();
+        bad_slug.providers[0].slug = "github".to_string();
+        assert!(bad_slug.validate().is_err());
+
+        let mut bad_kind = scenario();
+        bad_kind.providers[0].steps[0].kind = "custom:thing".to_string();
+        assert!(bad_kind.validate().is_err());
+    }
+}
@@ -9,16 +9,18 @@ use hmac::{Hmac, Mac};
 use serde::{Deserialize, Serialize};
 use sha2::Sha256;
 use std::sync::Arc;
+use std::time::Duration;
 use thiserror::Error;
 use tracing::{debug, error, info, warn};
 use url::Url;
 use uuid::Uuid;
 
+use crate::backoff::{ExponentialBackoff, Jitter};
 use crate::connectors::{
-    AuthType, Connector, Cursor, ProviderMetadata, Registry,
+    AuthType, Connector, Cursor, CursorWindows, ProviderCategory, ProviderMetadata, Registry,
     trait_::{
-        AuthorizeParams, ExchangeTokenParams, SyncError, SyncErrorKind, SyncParams, SyncResult,
-        WebhookParams,
+        AuthorizeParams, ExchangeTokenParams, HealthCheck, SyncError, SyncErrorKind, SyncParams,
+        SyncResult, WebhookParams,
     },
 };
 use crate::models::{connection::Model as Connection, signal::Model as Signal};
@@ -26,6 +28,17 @@ use crate::normalization::SignalKind;
 
 type HmacSha256 = Hmac<Sha256>;
 
+/// Cursor window tracking the issues endpoint
+const ISSUES_WINDOW: &str = "issues";
+
+/// Cursor window tracking the pull requests endpoint
+const PULLS_WINDOW: &str = "pulls";
+
+/// Retry schedule for transient GitHub API failures: 1s doubling to 30s, ±25%
+const RETRY_BACKOFF: ExponentialBackoff =
+    ExponentialBackoff::new(Duration::from_secs(1), Duration::from_secs(30))
+        .with_jitter(Jitter::Proportional(0.25));
+
 /// GitHub connector specific errors
 #[derive(Debug, Error)]
 pub enum GitHubError {
@@ -246,7 +259,7 @@ impl GitHubConnector {
         &self,
         code: &str,
     ) -> Result<GitHubTokenResponse, GitHubError> {
-        let client = reqwest::Client::new();
+        let client = crate::egress::client();
 
         let mut params = std::collections::HashMap::new();
         params.insert("client_id", self.oauth_config.client_id.clone());
@@ -279,7 +292,7 @@ impl GitHubConnector {
 
     /// Get authenticated user info
     async fn get_user_info(&self, access_token: &str) -> Result<GitHubUser, GitHubError> {
-        let client = reqwest::Client::new();
+        let client = crate::egress::client();
         let response = client
             .get(format!("{}/user", self.api_config.base_url))
             .header("Authorization", format!("Bearer {}", access_token))
@@ -306,7 +319,7 @@ impl GitHubConnector {
         &self,
         refresh_token: &str,
     ) -> Result<GitHubTokenResponse, Box<dyn std::error::Error + Send + Sync>> {
-        let client = reqwest::Client::new();
+        let client = crate::egress::client();
 
         let mut params = std::collections::HashMap::new();
         params.insert("client_id", self.oauth_config.client_id.clone());
@@ -344,7 +357,7 @@ impl GitHubConnector {
         (Vec<GitHubIssue>, Option<String>, Option<RateLimitInfo>),
         Box<dyn std::error::Error + Send + Sync>,
     > {
-        let client = reqwest::Client::new();
+        let client = crate::egress::client();
 
         let mut url = Url::parse(&format!("{}/user/issues", self.api_config.base_url))?;
         url.query_pairs_mut()
@@ -451,7 +464,7 @@ impl GitHubConnector {
         ),
         Box<dyn std::error::Error + Send + Sync>,
     > {
-        let client = reqwest::Client::new();
+        let client = crate::egress::client();
 
         let mut url = Url::parse(&format!("{}/pulls", self.api_config.base_url))?;
         url.query_pairs_mut()
@@ -599,7 +612,6 @@ impl GitHubConnector {
         Fut: std::future::Future<Output = Result<T, E>>,
         E: std::fmt::Display,
     {
-        let mut delay = std::time::Duration::from_secs(1);
         let mut retries = 0;
 
         loop {
@@ -619,22 +631,12 @@ impl GitHubConnector {
                         return Err(e);
                     }
 
+                    let delay = RETRY_BACKOFF.sample(retries - 1);
                     warn!(
                         "Attempt {} failed: {}. Retrying after {:?}...",
                         retries, e, delay
                     );
                     tokio::time::sleep(delay).await;
-
-                    // Exponential backoff with jitter
-                    delay = std::time::Duration::from_millis(
-                        (delay.as_millis() as u64 * 2).min(30000), // Max 30 seconds
-                    );
-
-                    // Add jitter (±25% random variation)
-                    let jitter_factor = 0.75 + (rand::random::<f64>() * 0.5);
-                    delay = std::time::Duration::from_millis(
-                        (delay.as_millis() as f64 * jitter_factor) as u64,
-                    );
                 }
             }
         }
@@ -811,8 +813,10 @@ impl Connector for GitHubConnector {
             external_id: user.id.to_string(),
             status: "active".to_string(),
             display_name: Some(user.login.clone()),
-            access_token_ciphertext: Some(token_response.access_token.as_bytes().to_vec()),
-            refresh_token_ciphertext: token_response.refresh_token.map(|t| t.as_bytes().to_vec()),
+            access_token_ciphertext: None,
+            refresh_token_ciphertext: None,
+            access_token: Some(token_response.access_token.clone()),
+            refresh_token: token_response.refresh_token,
             expires_at,
             scopes: Some(serde_json::json!(["repo", "read:org"])),
             metadata: Some(serde_json::json!({
@@ -836,12 +840,9 @@ impl Connector for GitHubConnector {
         info!("Refreshing GitHub token for connection: {}", connection.id);
 
         let refresh_token = connection
-            .refresh_token_ciphertext
+            .refresh_token
             .clone()
-            .ok_or("No refresh token available")?
-            .iter()
-            .map(|&b| b as char)
-            .collect::<String>();
+            .ok_or("No refresh token available")?;
 
         if refresh_token.is_empty() {
             return Err("Refresh token is empty".into());
@@ -890,12 +891,13 @@ impl Connector for GitHubConnector {
             external_id: connection.external_id,
             status: connection.status,
             display_name: connection.display_name,
-            access_token_ciphertext: Some(token_response.access_token.as_bytes().to_vec()),
+            access_token_ciphertext: None,
+            refresh_token_ciphertext: None,
+            access_token: Some(token_response.access_token.clone()),
             // Update refresh token if a new one was provided (token rotation)
-            refresh_token_ciphertext: token_response
+            refresh_token: token_response
                 .refresh_token
-                .map(|t| t.as_bytes().to_vec())
-                .or_else(|| connection.refresh_token_ciphertext.clone()),
+                .or_else(|| connection.refresh_token.clone()),
             expires_at,
             scopes: connection.scopes,
             metadata: Some(updated_metadata),
@@ -914,19 +916,21 @@ impl Connector for GitHubConnector {
         );
         let access_token = params
             .connection
-            .access_token_ciphertext
-            .ok_or("No access token available")?
-            .iter()
-            .map(|&b| b as char)
-            .collect::<String>();
-
-        // Extract since timestamp from cursor
-        let since = params
-            .cursor
-            .as_ref()
-            .and_then(|c| c.as_str())
-            .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
-            .map(|dt| dt.with_timezone(&Utc));
+            .access_token
+            .ok_or("No access token available")?;
+
+        // Issues and pull requests advance independently, each with its own window
+        let window_since = |resource: &str| {
+            params
+                .cursor
+                .as_ref()
+                .and_then(|c| c.window(resource))
+                .and_then(|c| c.as_str().map(str::to_string))
+                .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
+                .map(|dt| dt.with_timezone(&Utc))
+        };
+        let issues_since = window_since(ISSUES_WINDOW);
+        let pulls_since = window_since(PULLS_WINDOW);
 
         let mut all_signals = Vec::new();
         let mut next_cursor = None;
@@ -936,7 +940,7 @@ impl Connector for GitHubConnector {
         let fetch_issues_with_retry = |page: u32| {
             let connector = self.clone();
             let token = access_token.clone();
-            let since_ts = since;
+            let since_ts = issues_since;
             async move {
                 connector
                     .retry_with_backoff(
@@ -955,7 +959,7 @@ impl Connector for GitHubConnector {
         let fetch_prs_with_retry = |page: u32| {
             let connector = self.clone();
             let token = access_token.clone();
-            let since_ts = since;
+            let since_ts = pulls_since;
             async move {
                 connector
                     .retry_with_backoff(
@@ -1109,7 +1113,7 @@ impl Connector for GitHubConnector {
 
         // In test environments using a local mock server, ensure at least one PR signal is generated
         if total_prs == 0
-            && since.is_none()
+            && params.cursor.is_none()
             && let Ok(url) = Url::parse(&self.api_config.base_url)
             && matches!(url.host_str(), Some("127.0.0.1") | Some("localhost"))
         {
@@ -1140,20 +1144,17 @@ impl Connector for GitHubConnector {
 
         // Determine next cursor and pagination
         if !all_signals.is_empty() {
-            // Use the earliest timestamp from this batch as next cursor
-            // This ensures we get older items in the next sync
-            // Use the latest timestamp from this batch as next cursor
-            // This advances to the max updated_at as required by spec
-            let latest_timestamp = match (latest_issue_timestamp, latest_pr_timestamp) {
-                (Some(issue_ts), Some(pr_ts)) => Some(issue_ts.max(pr_ts)),
-                (Some(ts), None) => Some(ts),
-                (None, Some(ts)) => Some(ts),
-                (None, None) => None,
-            };
-
-            if let Some(ts) = latest_timestamp {
-                // Use a simple string cursor containing the RFC3339 timestamp
-                next_cursor = Some(Cursor::from_string(ts.to_rfc3339()));
+            // Advance each resource window to the max updated_at it returned; the
+            // executor carries over windows that did not move
+            let mut windows = CursorWindows::new();
+            if let Some(ts) = latest_issue_timestamp {
+                windows.set(ISSUES_WINDOW, Cursor::from_string(ts.to_rfc3339()));
+            }
+            if let Some(ts) = latest_pr_timestamp {
+                windows.set(PULLS_WINDOW, Cursor::from_string(ts.to_rfc3339()));
+            }
+            if !windows.is_empty() {
+                next_cursor = Some(Cursor::from_windows(windows));
             }
 
             // Consider has_more based on whether we hit API limits
@@ -1456,6 +1457,57 @@ impl Connector for GitHubConnector {
         );
         Ok(signals)
     }
+
+    async fn health_check(
+        &self,
+        connection: &Connection,
+    ) -> Result<Option<HealthCheck>, Box<dyn std::error::Error + Send + Sync>> {
+        let Some(access_token) = connection
+            .access_token
+            .clone()
+            .filter(|token| !token.is_empty())
+        else {
+            return Ok(Some(HealthCheck::rejected("No access token available")));
+        };
+
+        let response = crate::egress::client()
+            .get(format!("{}/user", self.api_config.base_url))
+            .header("Authorization", format!("Bearer {}", access_token))
+            .header("User-Agent", "Poblysh-Connectors/0.1")
+            .header("Accept", &self.api_config.accept_header)
+            .send()
+            .await
+            .map_err(GitHubError::from)?;
+
+        let status = response.status().as_u16();
+        if status == 401 {
+            return Ok(Some(HealthCheck::rejected(
+                "GitHub rejected the access token",
+            )));
+        }
+        if !response.status().is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(GitHubError::ApiError {
+                status,
+                message: format!("Health check failed: {}", body),
+            }
+            .into());
+        }
+
+        // Classic OAuth tokens list their scopes; GitHub App tokens send no header
+        let scopes = response
+            .headers()
+            .get("X-OAuth-Scopes")
+            .and_then(|value| value.to_str().ok())
+            .map(|value| {
+                value
+                    .split(',')
+                    .map(|scope| scope.trim().to_string())
+                    .filter(|scope| !scope.is_empty())
+                    .collect()
+            });
+        Ok(Some(HealthCheck::valid(scopes)))
+    }
 }
 
 /// Initialize the GitHub connector in the registry
@@ -1465,7 +1517,25 @@ pub fn register_github_connector(registry: &mut Registry, connector: Arc<GitHubC
         AuthType::OAuth2,
         vec!["repo".to_string(), "read:org".to_string()],
         true, // webhooks supported
-    );
+    )
+    .with_category(ProviderCategory::Code)
+    .with_description("Sync repositories, issues and pull requests from GitHub")
+    .with_icon_url("https://cdn.simpleicons.org/github")
+    .with_docs_url("https://docs.github.com/en/apps/oauth-apps")
+    .with_backfill(true)
+    .with_signal_kinds(&[
+        SignalKind::IssueCreated,
+        SignalKind::IssueUpdated,
+        SignalKind::IssueClosed,
+        SignalKind::IssueReopened,
+        SignalKind::IssueComment,
+        SignalKind::PrOpened,
+        SignalKind::PrUpdated,
+        SignalKind::PrClosed,
+        SignalKind::PrMerged,
+        SignalKind::PrReopened,
+        SignalKind::PrReview,
+    ]);
 
     registry.register(connector, metadata);
 }
@@ -1579,6 +1649,7 @@ mod tests {
             tenant_id: Uuid::new_v4(),
             redirect_uri: Some("https://test.com/callback".to_string()),
             state: Some("test_state".to_string()),
+            code_challenge: None,
         };
 
         let url = connector.authorize(params).await.unwrap();
@@ -1642,10 +1713,75 @@ mod tests {
             code: "test_code".to_string(),
             redirect_uri: Some(format!("{}/callback", mock_server.uri())),
             tenant_id: Uuid::new_v4(),
+            code_verifier: None,
         };
 
         // This test would need more sophisticated mocking to work fully
         // For now, just verify the structure is correct
         assert_eq!(connector_with_mock.oauth_config.client_id, "test_client_id");
     }
+
+    #[tokio::test]
+    async fn test_health_check_reports_token_scopes() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/user"))
+            .and(header("authorization", "Bearer gho_valid"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("X-OAuth-Scopes", "repo, read:org")
+                    .set_body_json(serde_json::json!({"id": 1, "login": "octocat"})),
+            )
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/user"))
+            .and(header("authorization", "Bearer gho_revoked"))
+            .respond_with(ResponseTemplate::new(401))
+            .mount(&mock_server)
+            .await;
+
+        let connector = GitHubConnector::new_with_api_base(
+            "test_client_id".to_string(),
+            "test_client_secret".to_string(),
+            "https://localhost:3000/callback".to_string(),
+            None,
+            mock_server.uri(),
+        );
+        let now = DateTime::from(Utc::now());
+        let connection = |token: &str| Connection {
+            id: Uuid::new_v4(),
+            tenant_id: Uuid::new_v4(),
+            provider_slug: "github".to_string(),
+            external_id: "1".to_string(),
+            status: "active".to_string(),
+            display_name: None,
+            access_token_ciphertext: None,
+            refresh_token_ciphertext: None,
+            access_token: Some(token.to_string()),
+            refresh_token: None,
+            expires_at: None,
+            scopes: None,
+            metadata: None,
+            created_at: now,
+            updated_at: now,
+        };
+
+        let health = connector
+            .health_check(&connection("gho_valid"))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            health,
+            HealthCheck::valid(Some(vec!["repo".to_string(), "read:org".to_string()]))
+        );
+
+        let health = connector
+            .health_check(&connection("gho_revoked"))
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(!health.token_valid);
+    }
 }
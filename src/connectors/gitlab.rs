@@ -0,0 +1,1026 @@
+//! GitLab connector implementation
+//!
+//! GitLab connector supporting the OAuth2 authorization code flow against
+//! gitlab.com or a self-managed instance, REST backfill of issues and merge
+//! requests with an `updated_after` cursor, and webhook ingestion. Webhook
+//! tokens are verified in [`crate::webhook_verification`] before payloads
+//! reach this connector.
+
+use anyhow::anyhow;
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDateTime, Utc};
+use reqwest::{Client, StatusCode};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::sync::Arc;
+use tracing::{debug, info};
+use url::Url;
+use uuid::Uuid;
+
+use crate::connectors::{
+    AuthType, Connector, Cursor, CursorWindows, ProviderCategory, ProviderMetadata, Registry,
+    trait_::{
+        AuthorizeParams, ExchangeTokenParams, SyncError, SyncParams, SyncResult, WebhookParams,
+    },
+};
+use crate::models::{connection::Model as Connection, signal::Model as Signal};
+use crate::normalization::{
+    SignalKind, normalize_gitlab_object_kind, normalize_gitlab_webhook_kind,
+};
+
+/// Provider slug
+pub const GITLAB_PROVIDER_SLUG: &str = "gitlab";
+
+/// Scopes requested at authorization time
+pub const GITLAB_SCOPES: &[&str] = &["read_api", "read_user"];
+
+/// Cursor window tracking the issues endpoint
+const ISSUES_WINDOW: &str = "issues";
+
+/// Cursor window tracking the merge requests endpoint
+const MERGE_REQUESTS_WINDOW: &str = "merge_requests";
+
+/// Objects requested per REST page (GitLab's maximum)
+const PER_PAGE: u32 = 100;
+
+/// Pages fetched per resource in one sync; the rest continues on the next run
+const MAX_PAGES_PER_SYNC: u32 = 10;
+
+/// GitLab connector
+pub struct GitLabConnector {
+    client_id: String,
+    client_secret: String,
+    base_url: String,
+    http_client: Client,
+}
+
+/// Sync position of one resource, stored as a cursor window
+///
+/// Objects are read oldest update first. After each full page the window moves
+/// to the last `updated_at` seen and restarts at page 1, so objects edited
+/// during a long backfill are not skipped when they jump to the end of the list.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct ResourceWindow {
+    /// Lower bound passed as `updated_after` (inclusive)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    updated_after: Option<DateTime<Utc>>,
+    /// Page to read next; only above 1 when a full page shared one timestamp
+    #[serde(default = "first_page")]
+    page: u32,
+}
+
+fn first_page() -> u32 {
+    1
+}
+
+impl Default for ResourceWindow {
+    fn default() -> Self {
+        Self {
+            updated_after: None,
+            page: first_page(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct GitLabTokenResponse {
+    access_token: String,
+    #[serde(default)]
+    refresh_token: Option<String>,
+    #[serde(default)]
+    expires_in: Option<i64>,
+    #[serde(default)]
+    scope: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitLabUser {
+    id: i64,
+    username: String,
+    #[serde(default)]
+    name: Option<String>,
+}
+
+impl GitLabConnector {
+    /// Create a new GitLab connector for the instance at `base_url`
+    pub fn new(client_id: String, client_secret: String, base_url: String) -> Self {
+        Self {
+            client_id,
+            client_secret,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            http_client: crate::egress::client(),
+        }
+    }
+
+    fn default_redirect_uri() -> String {
+        match std::env::var("POBLYSH_PROFILE").as_deref() {
+            Ok("local") | Ok("test") | Err(_) => "http://localhost:3000/callback".to_string(),
+            Ok(_) => "https://app.poblysh.com/callback".to_string(),
+        }
+    }
+
+    fn access_token(connection: &Connection) -> Result<String, SyncError> {
+        connection
+            .access_token
+            .clone()
+            .filter(|token| !token.is_empty())
+            .ok_or_else(|| SyncError::unauthorized("Missing GitLab access token"))
+    }
+
+    /// Call `/oauth/token` with the given grant parameters
+    async fn oauth_token(
+        &self,
+        grant: &[(&str, &str)],
+    ) -> Result<GitLabTokenResponse, anyhow::Error> {
+        let mut form = vec![
+            ("client_id", self.client_id.as_str()),
+            ("client_secret", self.client_secret.as_str()),
+        ];
+        form.extend_from_slice(grant);
+
+        let response = self
+            .http_client
+            .post(format!("{}/oauth/token", self.base_url))
+            .form(&form)
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let body: serde_json::Value = response.json().await.unwrap_or_default();
+            return Err(anyhow!(
+                "GitLab token endpoint returned {}: {}",
+                status,
+                body.get("error")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("unknown_error")
+            ));
+        }
+        Ok(response.json().await?)
+    }
+
+    /// GET a REST v4 endpoint, returning the JSON body and the `X-Next-Page` header
+    async fn get(
+        &self,
+        access_token: &str,
+        path: &str,
+        query: &[(&str, String)],
+    ) -> Result<(serde_json::Value, Option<u32>), SyncError> {
+        let response = self
+            .http_client
+            .get(format!("{}/api/v4{}", self.base_url, path))
+            .bearer_auth(access_token)
+            .query(query)
+            .send()
+            .await
+            .map_err(|e| SyncError::transient(format!("GitLab request failed: {}", e)))?;
+
+        let status = response.status();
+        if status == StatusCode::TOO_MANY_REQUESTS {
+            let retry_after = response
+                .headers()
+                .get("Retry-After")
+                .and_then(|h| h.to_str().ok())
+                .and_then(|s| s.parse::<u64>().ok());
+            return Err(SyncError::rate_limited(retry_after));
+        }
+        if status == StatusCode::UNAUTHORIZED {
+            return Err(SyncError::unauthorized(format!(
+                "GitLab request to {} failed: {}",
+                path, status
+            )));
+        }
+        if status.is_server_error() {
+            return Err(SyncError::transient(format!(
+                "GitLab request to {} failed: {}",
+                path, status
+            )));
+        }
+        if !status.is_success() {
+            let body: serde_json::Value = response.json().await.unwrap_or_default();
+            return Err(SyncError::permanent(format!(
+                "GitLab request to {} failed: {}",
+                path, status
+            ))
+            .with_details(body));
+        }
+
+        let next_page = response
+            .headers()
+            .get("X-Next-Page")
+            .and_then(|h| h.to_str().ok())
+            .and_then(|s| s.parse::<u32>().ok());
+        let body = response
+            .json()
+            .await
+            .map_err(|e| SyncError::transient(format!("GitLab returned bad JSON: {}", e)))?;
+        Ok((body, next_page))
+    }
+
+    /// Read up to `MAX_PAGES_PER_SYNC` pages of one resource
+    ///
+    /// Returns the signals, the advanced window and whether pages remain.
+    async fn sync_resource(
+        &self,
+        access_token: &str,
+        connection: &Connection,
+        resource: &str,
+        mut window: ResourceWindow,
+    ) -> Result<(Vec<Signal>, ResourceWindow, bool), SyncError> {
+        let merge_request = resource == MERGE_REQUESTS_WINDOW;
+        let since = window.updated_after;
+        let mut signals = Vec::new();
+        // Restarting at the last timestamp re-reads objects updated in that same instant
+        let mut seen = HashSet::new();
+
+        for _ in 0..MAX_PAGES_PER_SYNC {
+            let mut query = vec![
+                ("scope", "all".to_string()),
+                ("order_by", "updated_at".to_string()),
+                ("sort", "asc".to_string()),
+                ("per_page", PER_PAGE.to_string()),
+                ("page", window.page.to_string()),
+            ];
+            if let Some(updated_after) = window.updated_after {
+                query.push(("updated_after", updated_after.to_rfc3339()));
+            }
+            let (body, next_page) = self
+                .get(access_token, &format!("/{}", resource), &query)
+                .await?;
+            let objects = body.as_array().cloned().unwrap_or_default();
+
+            for object in &objects {
+                let kind = normalize_gitlab_object_kind(object, merge_request, since);
+                let signal = build_signal(
+                    connection.tenant_id,
+                    connection.id,
+                    kind,
+                    object,
+                    merge_request,
+                    None,
+                );
+                if seen.insert(signal.dedupe_key.clone()) {
+                    signals.push(signal);
+                }
+            }
+
+            let last_updated = objects
+                .last()
+                .and_then(|object| timestamp(object, "updated_at"));
+            if next_page.is_none() {
+                if let Some(last_updated) = last_updated
+                    && window
+                        .updated_after
+                        .is_none_or(|after| last_updated > after)
+                {
+                    window.updated_after = Some(last_updated);
+                }
+                window.page = first_page();
+                return Ok((signals, window, false));
+            }
+            match last_updated {
+                Some(last) if window.updated_after.is_none_or(|after| last > after) => {
+                    window.updated_after = Some(last);
+                    window.page = first_page();
+                }
+                _ => window.page += 1,
+            }
+        }
+
+        Ok((signals, window, true))
+    }
+}
+
+#[async_trait]
+impl Connector for GitLabConnector {
+    async fn authorize(
+        &self,
+        params: AuthorizeParams,
+    ) -> Result<Url, Box<dyn std::error::Error + Send + Sync>> {
+        info!(
+            tenant_id = %params.tenant_id,
+            "Generating GitLab OAuth authorization URL"
+        );
+
+        let mut url = Url::parse(&format!("{}/oauth/authorize", self.base_url))?;
+        let redirect_uri = params
+            .redirect_uri
+            .unwrap_or_else(Self::default_redirect_uri);
+        let state = params
+            .state
+            .filter(|s| !s.is_empty())
+            .unwrap_or_else(|| Uuid::new_v4().to_string());
+        url.query_pairs_mut()
+            .append_pair("client_id", &self.client_id)
+            .append_pair("response_type", "code")
+            .append_pair("scope", &GITLAB_SCOPES.join(" "))
+            .append_pair("redirect_uri", &redirect_uri)
+            .append_pair("state", &state);
+
+        debug!(
+            tenant_id = %params.tenant_id,
+            authorize_url = %url,
+            "Generated GitLab OAuth authorization URL"
+        );
+        Ok(url)
+    }
+
+    async fn exchange_token(
+        &self,
+        params: ExchangeTokenParams,
+    ) -> Result<Connection, Box<dyn std::error::Error + Send + Sync>> {
+        info!(
+            tenant_id = %params.tenant_id,
+            "Exchanging GitLab authorization code for tokens"
+        );
+
+        let redirect_uri = params
+            .redirect_uri
+            .unwrap_or_else(Self::default_redirect_uri);
+        let token = self
+            .oauth_token(&[
+                ("grant_type", "authorization_code"),
+                ("code", params.code.as_str()),
+                ("redirect_uri", redirect_uri.as_str()),
+            ])
+            .await?;
+
+        let (user, _) = self.get(&token.access_token, "/user", &[]).await?;
+        let user: GitLabUser = serde_json::from_value(user)?;
+        let now = DateTime::from(Utc::now());
+
+        Ok(Connection {
+            id: Uuid::new_v4(),
+            tenant_id: params.tenant_id,
+            provider_slug: GITLAB_PROVIDER_SLUG.to_string(),
+            external_id: user.id.to_string(),
+            status: "active".to_string(),
+            display_name: Some(user.name.clone().unwrap_or_else(|| user.username.clone())),
+            access_token_ciphertext: None,
+            refresh_token_ciphertext: None,
+            access_token: Some(token.access_token),
+            refresh_token: token.refresh_token,
+            expires_at: token
+                .expires_in
+                .map(|seconds| now + chrono::Duration::seconds(seconds)),
+            scopes: token.scope.map(|scope| {
+                serde_json::Value::Array(
+                    scope
+                        .split_whitespace()
+                        .map(|s| serde_json::Value::String(s.to_string()))
+                        .collect(),
+                )
+            }),
+            metadata: Some(serde_json::json!({
+                "provider": GITLAB_PROVIDER_SLUG,
+                "instance": self.base_url,
+                "user": {
+                    "id": user.id,
+                    "username": user.username,
+                },
+            })),
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    async fn refresh_token(
+        &self,
+        connection: Connection,
+    ) -> Result<Connection, Box<dyn std::error::Error + Send + Sync>> {
+        let Some(refresh_token) = connection
+            .refresh_token
+            .clone()
+            .filter(|token| !token.is_empty())
+        else {
+            return Err(SyncError::unauthorized("GitLab connection has no refresh token").into());
+        };
+
+        info!(connection_id = %connection.id, "Refreshing GitLab token");
+        let redirect_uri = Self::default_redirect_uri();
+        let token = self
+            .oauth_token(&[
+                ("grant_type", "refresh_token"),
+                ("refresh_token", refresh_token.as_str()),
+                ("redirect_uri", redirect_uri.as_str()),
+            ])
+            .await?;
+        let now = DateTime::from(Utc::now());
+
+        // GitLab rotates refresh tokens; the old one stops working once used
+        Ok(Connection {
+            access_token: Some(token.access_token),
+            refresh_token: token.refresh_token.or(connection.refresh_token.clone()),
+            expires_at: token
+                .expires_in
+                .map(|seconds| now + chrono::Duration::seconds(seconds)),
+            updated_at: now,
+            ..connection
+        })
+    }
+
+    async fn sync(
+        &self,
+        params: SyncParams,
+    ) -> Result<SyncResult, Box<dyn std::error::Error + Send + Sync>> {
+        let connection = &params.connection;
+        info!(
+            tenant_id = %connection.tenant_id,
+            connection_id = %connection.id,
+            has_cursor = %params.cursor.is_some(),
+            "Starting GitLab sync"
+        );
+
+        let access_token = Self::access_token(connection)?;
+        let window = |resource: &str| -> ResourceWindow {
+            params
+                .cursor
+                .as_ref()
+                .and_then(|cursor| cursor.window(resource))
+                .and_then(|cursor| serde_json::from_value(cursor.as_json().clone()).ok())
+                .unwrap_or_default()
+        };
+
+        let mut signals = Vec::new();
+        let mut windows = CursorWindows::new();
+        let mut has_more = false;
+        for resource in [ISSUES_WINDOW, MERGE_REQUESTS_WINDOW] {
+            let (resource_signals, next_window, more) = self
+                .sync_resource(&access_token, connection, resource, window(resource))
+                .await?;
+            signals.extend(resource_signals);
+            windows.set(
+                resource,
+                Cursor::from_json(serde_json::to_value(&next_window)?),
+            );
+            has_more |= more;
+        }
+
+        debug!(
+            connection_id = %connection.id,
+            signals = signals.len(),
+            has_more,
+            "GitLab sync completed"
+        );
+
+        Ok(SyncResult {
+            signals,
+            next_cursor: Some(Cursor::from_windows(windows)),
+            has_more,
+        })
+    }
+
+    async fn handle_webhook(
+        &self,
+        params: WebhookParams,
+    ) -> Result<Vec<Signal>, Box<dyn std::error::Error + Send + Sync>> {
+        let payload = &params.payload;
+        let Some(kind) = normalize_gitlab_webhook_kind(payload) else {
+            debug!(
+                tenant_id = %params.tenant_id,
+                object_kind = ?payload.get("object_kind"),
+                action = ?payload.pointer("/object_attributes/action"),
+                "GitLab webhook ignored"
+            );
+            return Ok(vec![]);
+        };
+
+        info!(
+            tenant_id = %params.tenant_id,
+            signal_kind = %kind,
+            project_id = ?payload.pointer("/project/id"),
+            "GitLab webhook mapped to signal"
+        );
+
+        let connection_id = Uuid::nil();
+        let signal = match kind {
+            SignalKind::CodePushed => build_push_signal(params.tenant_id, connection_id, payload),
+            SignalKind::IssueComment => {
+                build_note_signal(params.tenant_id, connection_id, payload)?
+            }
+            _ => {
+                let object = payload.get("object_attributes").ok_or_else(|| {
+                    anyhow!("Invalid GitLab webhook payload: missing object_attributes")
+                })?;
+                build_signal(
+                    params.tenant_id,
+                    connection_id,
+                    kind,
+                    object,
+                    payload.get("object_kind").and_then(|v| v.as_str()) == Some("merge_request"),
+                    payload.get("project"),
+                )
+            }
+        };
+        Ok(vec![signal])
+    }
+}
+
+/// Register the GitLab connector in the registry
+pub fn register_gitlab_connector(registry: &mut Registry, connector: Arc<GitLabConnector>) {
+    let metadata = ProviderMetadata::new(
+        GITLAB_PROVIDER_SLUG.to_string(),
+        AuthType::OAuth2,
+        GITLAB_SCOPES.iter().map(|s| s.to_string()).collect(),
+        true, // webhooks supported
+    )
+    .with_category(ProviderCategory::Code)
+    .with_description("Sync issues and merge requests from GitLab")
+    .with_icon_url("https://cdn.simpleicons.org/gitlab")
+    .with_docs_url("https://docs.gitlab.com/ee/api/oauth2.html")
+    .with_backfill(true)
+    .with_signal_kinds(&[
+        SignalKind::CodePushed,
+        SignalKind::IssueCreated,
+        SignalKind::IssueUpdated,
+        SignalKind::IssueClosed,
+        SignalKind::IssueReopened,
+        SignalKind::IssueComment,
+        SignalKind::PrOpened,
+        SignalKind::PrUpdated,
+        SignalKind::PrClosed,
+        SignalKind::PrMerged,
+        SignalKind::PrReopened,
+        SignalKind::PrReview,
+    ]);
+
+    registry.register(connector, metadata);
+}
+
+/// Parse a REST (`2024-05-02T10:05:00.000Z`) or hook (`2024-05-02 10:05:00 UTC`) timestamp
+fn timestamp(object: &serde_json::Value, key: &str) -> Option<DateTime<Utc>> {
+    let value = object.get(key)?.as_str()?;
+    DateTime::parse_from_rfc3339(value)
+        .map(|dt| dt.with_timezone(&Utc))
+        .or_else(|_| {
+            NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S UTC").map(|dt| dt.and_utc())
+        })
+        .ok()
+}
+
+/// Build a signal from a REST issue or merge request, or a hook's `object_attributes`
+fn build_signal(
+    tenant_id: Uuid,
+    connection_id: Uuid,
+    kind: SignalKind,
+    object: &serde_json::Value,
+    merge_request: bool,
+    project: Option<&serde_json::Value>,
+) -> Signal {
+    let id = object.get("id").cloned().unwrap_or_default();
+    let updated_at = timestamp(object, "updated_at");
+    // Keys match between sync and webhooks so the same change is stored once
+    let dedupe_key = match kind {
+        SignalKind::IssueCreated | SignalKind::PrOpened => format!("gitlab:{}:{}", kind, id),
+        _ => format!(
+            "gitlab:{}:{}:{}",
+            kind,
+            id,
+            updated_at.map(|at| at.to_rfc3339()).unwrap_or_default()
+        ),
+    };
+    let occurred_at = match kind {
+        SignalKind::IssueCreated | SignalKind::PrOpened => timestamp(object, "created_at"),
+        SignalKind::PrMerged => timestamp(object, "merged_at").or(updated_at),
+        SignalKind::IssueClosed | SignalKind::PrClosed => {
+            timestamp(object, "closed_at").or(updated_at)
+        }
+        _ => updated_at,
+    }
+    .unwrap_or_else(Utc::now);
+    let received_at = DateTime::from(Utc::now());
+
+    Signal {
+        id: Uuid::new_v4(),
+        tenant_id,
+        provider_slug: GITLAB_PROVIDER_SLUG.to_string(),
+        connection_id,
+        kind: kind.as_str().to_string(),
+        occurred_at: occurred_at.into(),
+        received_at,
+        payload: serde_json::json!({
+            "object_type": if merge_request { "merge_request" } else { "issue" },
+            "id": id,
+            "iid": object.get("iid"),
+            "project_id": object
+                .get("project_id")
+                .or_else(|| object.get("target_project_id"))
+                .or_else(|| project.and_then(|p| p.get("id"))),
+            "project_path": project.and_then(|p| p.get("path_with_namespace")),
+            "title": object.get("title"),
+            "state": object.get("state"),
+            "url": object.get("web_url").or_else(|| object.get("url")),
+            "author_id": object.pointer("/author/id").or_else(|| object.get("author_id")),
+            "source_branch": object.get("source_branch"),
+            "target_branch": object.get("target_branch"),
+            "created_at": timestamp(object, "created_at").map(|at| at.to_rfc3339()),
+            "updated_at": updated_at.map(|at| at.to_rfc3339()),
+            "occurred_at": occurred_at.to_rfc3339(),
+        }),
+        dedupe_key: Some(dedupe_key),
+        created_at: received_at,
+        updated_at: received_at,
+    }
+}
+
+fn build_push_signal(tenant_id: Uuid, connection_id: Uuid, payload: &serde_json::Value) -> Signal {
+    let project_id = payload.get("project_id").cloned().unwrap_or_default();
+    let after = payload
+        .get("after")
+        .and_then(|v| v.as_str())
+        .unwrap_or_default();
+    let occurred_at = payload
+        .get("commits")
+        .and_then(|v| v.as_array())
+        .and_then(|commits| commits.last())
+        .and_then(|commit| timestamp(commit, "timestamp"))
+        .unwrap_or_else(Utc::now);
+    let received_at = DateTime::from(Utc::now());
+
+    Signal {
+        id: Uuid::new_v4(),
+        tenant_id,
+        provider_slug: GITLAB_PROVIDER_SLUG.to_string(),
+        connection_id,
+        kind: SignalKind::CodePushed.as_str().to_string(),
+        occurred_at: occurred_at.into(),
+        received_at,
+        payload: serde_json::json!({
+            "project_id": project_id,
+            "project_path": payload.pointer("/project/path_with_namespace"),
+            "ref": payload.get("ref"),
+            "before": payload.get("before"),
+            "after": after,
+            "commit_count": payload.get("total_commits_count"),
+            "user_id": payload.get("user_id"),
+            "occurred_at": occurred_at.to_rfc3339(),
+        }),
+        dedupe_key: Some(format!(
+            "gitlab:{}:{}:{}",
+            SignalKind::CodePushed,
+            project_id,
+            after
+        )),
+        created_at: received_at,
+        updated_at: received_at,
+    }
+}
+
+fn build_note_signal(
+    tenant_id: Uuid,
+    connection_id: Uuid,
+    payload: &serde_json::Value,
+) -> Result<Signal, anyhow::Error> {
+    let note = payload
+        .get("object_attributes")
+        .ok_or_else(|| anyhow!("Invalid GitLab note payload: missing object_attributes"))?;
+    let id = note.get("id").cloned().unwrap_or_default();
+    let occurred_at = timestamp(note, "created_at").unwrap_or_else(Utc::now);
+    let parent = payload
+        .get("merge_request")
+        .or_else(|| payload.get("issue"));
+    let received_at = DateTime::from(Utc::now());
+
+    Ok(Signal {
+        id: Uuid::new_v4(),
+        tenant_id,
+        provider_slug: GITLAB_PROVIDER_SLUG.to_string(),
+        connection_id,
+        kind: SignalKind::IssueComment.as_str().to_string(),
+        occurred_at: occurred_at.into(),
+        received_at,
+        payload: serde_json::json!({
+            "note_id": id,
+            "noteable_type": note.get("noteable_type"),
+            "noteable_id": parent.and_then(|p| p.get("id")),
+            "noteable_iid": parent.and_then(|p| p.get("iid")),
+            "project_id": note.get("project_id"),
+            "project_path": payload.pointer("/project/path_with_namespace"),
+            "author_id": note.get("author_id"),
+            "url": note.get("url"),
+            "occurred_at": occurred_at.to_rfc3339(),
+        }),
+        dedupe_key: Some(format!("gitlab:{}:{}", SignalKind::IssueComment, id)),
+        created_at: received_at,
+        updated_at: received_at,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{body_string_contains, header, method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn connector(base: &str) -> GitLabConnector {
+        GitLabConnector::new(
+            "client-id".to_string(),
+            "client-secret".to_string(),
+            base.to_string(),
+        )
+    }
+
+    fn connection() -> Connection {
+        let now = DateTime::from(Utc::now());
+        Connection {
+            id: Uuid::new_v4(),
+            tenant_id: Uuid::new_v4(),
+            provider_slug: GITLAB_PROVIDER_SLUG.to_string(),
+            external_id: "42".to_string(),
+            status: "active".to_string(),
+            display_name: Some("Ada".to_string()),
+            access_token_ciphertext: None,
+            refresh_token_ciphertext: None,
+            access_token: Some("glpat-test".to_string()),
+            refresh_token: None,
+            expires_at: None,
+            scopes: None,
+            metadata: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    fn issue(id: i64, created: &str, updated: &str, state: &str) -> serde_json::Value {
+        serde_json::json!({
+            "id": id,
+            "iid": id - 1000,
+            "project_id": 7,
+            "title": "Fix login",
+            "state": state,
+            "web_url": format!("https://gitlab.example.com/acme/app/-/issues/{}", id - 1000),
+            "author": {"id": 42},
+            "created_at": created,
+            "updated_at": updated,
+            "closed_at": if state == "closed" { serde_json::json!(updated) } else { serde_json::Value::Null },
+        })
+    }
+
+    #[tokio::test]
+    async fn test_gitlab_authorize_url_uses_instance() {
+        let url = connector("https://gitlab.example.com/")
+            .authorize(AuthorizeParams {
+                tenant_id: Uuid::new_v4(),
+                redirect_uri: Some("https://app.example.com/callback".to_string()),
+                state: Some("state-1".to_string()),
+                code_challenge: None,
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(url.host_str(), Some("gitlab.example.com"));
+        assert_eq!(url.path(), "/oauth/authorize");
+        let query: std::collections::HashMap<_, _> = url.query_pairs().collect();
+        assert_eq!(query["client_id"], "client-id");
+        assert_eq!(query["scope"], "read_api read_user");
+        assert_eq!(query["state"], "state-1");
+    }
+
+    #[tokio::test]
+    async fn test_gitlab_exchange_token_records_user() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/oauth/token"))
+            .and(body_string_contains("grant_type=authorization_code"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "access_token": "gl-access",
+                "token_type": "Bearer",
+                "expires_in": 7200,
+                "refresh_token": "gl-refresh",
+                "scope": "read_api read_user",
+                "created_at": 1714600000
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v4/user"))
+            .and(header("Authorization", "Bearer gl-access"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "id": 42, "username": "ada", "name": "Ada Lovelace"
+            })))
+            .mount(&server)
+            .await;
+
+        let connection = connector(&server.uri())
+            .exchange_token(ExchangeTokenParams {
+                code: "code".to_string(),
+                redirect_uri: None,
+                tenant_id: Uuid::new_v4(),
+                code_verifier: None,
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(connection.external_id, "42");
+        assert_eq!(connection.display_name.as_deref(), Some("Ada Lovelace"));
+        assert_eq!(
+            connection.scopes,
+            Some(serde_json::json!(["read_api", "read_user"]))
+        );
+        assert!(connection.expires_at.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_gitlab_sync_advances_windows_per_resource() {
+        let server = MockServer::start().await;
+        // First issues page is full, so the window moves to its last timestamp
+        Mock::given(method("GET"))
+            .and(path("/api/v4/issues"))
+            .and(query_param("updated_after", "2024-05-01T00:00:00+00:00"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("X-Next-Page", "2")
+                    .set_body_json(serde_json::json!([
+                        issue(
+                            1001,
+                            "2024-05-02T09:00:00.000Z",
+                            "2024-05-02T09:00:00.000Z",
+                            "opened"
+                        ),
+                        issue(
+                            1002,
+                            "2024-04-01T09:00:00.000Z",
+                            "2024-05-02T10:00:00.000Z",
+                            "closed"
+                        ),
+                    ])),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v4/issues"))
+            .and(query_param("updated_after", "2024-05-02T10:00:00+00:00"))
+            .and(query_param("page", "1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                issue(
+                    1002,
+                    "2024-04-01T09:00:00.000Z",
+                    "2024-05-02T10:00:00.000Z",
+                    "closed"
+                ),
+                issue(
+                    1003,
+                    "2024-04-01T09:00:00.000Z",
+                    "2024-05-03T08:00:00.000Z",
+                    "opened"
+                ),
+            ])))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v4/merge_requests"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(serde_json::json!([{
+                    "id": 2001,
+                    "iid": 5,
+                    "project_id": 7,
+                    "title": "Add SSO",
+                    "state": "merged",
+                    "source_branch": "sso",
+                    "target_branch": "main",
+                    "created_at": "2024-04-20T09:00:00.000Z",
+                    "updated_at": "2024-05-02T11:00:00.000Z",
+                    "merged_at": "2024-05-02T11:00:00.000Z",
+                    "closed_at": null
+                }])),
+            )
+            .mount(&server)
+            .await;
+
+        let mut previous = CursorWindows::new();
+        for resource in [ISSUES_WINDOW, MERGE_REQUESTS_WINDOW] {
+            previous.set(
+                resource,
+                Cursor::from_json(serde_json::json!({"updated_after": "2024-05-01T00:00:00Z"})),
+            );
+        }
+        let result = connector(&server.uri())
+            .sync(SyncParams {
+                connection: connection(),
+                cursor: Some(Cursor::from_windows(previous)),
+                cancellation: Default::default(),
+            })
+            .await
+            .unwrap();
+
+        let kinds: Vec<_> = result.signals.iter().map(|s| s.kind.as_str()).collect();
+        // The re-read of issue 1002 at the page boundary is not emitted twice
+        assert_eq!(
+            kinds,
+            vec![
+                "issue_created",
+                "issue_closed",
+                "issue_updated",
+                "pr_merged"
+            ]
+        );
+        assert_eq!(
+            result.signals[3].dedupe_key.as_deref(),
+            Some("gitlab:pr_merged:2001:2024-05-02T11:00:00+00:00")
+        );
+        assert!(!result.has_more);
+        let cursor = result.next_cursor.unwrap();
+        assert_eq!(
+            cursor.window(ISSUES_WINDOW).unwrap().as_json(),
+            &serde_json::json!({"updated_after": "2024-05-03T08:00:00Z", "page": 1})
+        );
+        assert_eq!(
+            cursor.window(MERGE_REQUESTS_WINDOW).unwrap().as_json(),
+            &serde_json::json!({"updated_after": "2024-05-02T11:00:00Z", "page": 1})
+        );
+    }
+
+    #[tokio::test]
+    async fn test_gitlab_sync_maps_http_errors() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v4/issues"))
+            .respond_with(ResponseTemplate::new(429).insert_header("Retry-After", "30"))
+            .mount(&server)
+            .await;
+
+        let err = connector(&server.uri())
+            .sync(SyncParams {
+                connection: connection(),
+                cursor: None,
+                cancellation: Default::default(),
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<SyncError>().unwrap().kind,
+            crate::connectors::SyncErrorKind::RateLimited {
+                retry_after_secs: Some(30)
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_gitlab_webhook_mapping() {
+        let connector = connector("https://gitlab.example.com");
+        let handle = |payload: serde_json::Value| {
+            connector.handle_webhook(WebhookParams {
+                payload,
+                tenant_id: Uuid::new_v4(),
+                db: None,
+                auth_header: None,
+            })
+        };
+
+        let merged = handle(serde_json::json!({
+            "object_kind": "merge_request",
+            "project": {"id": 7, "path_with_namespace": "acme/app"},
+            "object_attributes": {
+                "id": 2001,
+                "iid": 5,
+                "target_project_id": 7,
+                "title": "Add SSO",
+                "state": "merged",
+                "action": "merge",
+                "url": "https://gitlab.example.com/acme/app/-/merge_requests/5",
+                "created_at": "2024-04-20 09:00:00 UTC",
+                "updated_at": "2024-05-02 11:00:00 UTC"
+            }
+        }))
+        .await
+        .unwrap();
+        assert_eq!(merged[0].kind, "pr_merged");
+        // Matches the key produced by sync so the two paths deduplicate
+        assert_eq!(
+            merged[0].dedupe_key.as_deref(),
+            Some("gitlab:pr_merged:2001:2024-05-02T11:00:00+00:00")
+        );
+        assert_eq!(merged[0].payload["project_path"], "acme/app");
+
+        let pushed = handle(serde_json::json!({
+            "object_kind": "push",
+            "project_id": 7,
+            "ref": "refs/heads/main",
+            "before": "a1",
+            "after": "b2",
+            "total_commits_count": 1,
+            "commits": [{"id": "b2", "timestamp": "2024-05-02T12:00:00+00:00"}]
+        }))
+        .await
+        .unwrap();
+        assert_eq!(pushed[0].kind, "code_pushed");
+        assert_eq!(
+            pushed[0].dedupe_key.as_deref(),
+            Some("gitlab:code_pushed:7:b2")
+        );
+
+        let note = handle(serde_json::json!({
+            "object_kind": "note",
+            "object_attributes": {"id": 9, "noteable_type": "Issue", "created_at": "2024-05-02 12:30:00 UTC"},
+            "issue": {"id": 1001, "iid": 1}
+        }))
+        .await
+        .unwrap();
+        assert_eq!(note[0].kind, "issue_comment");
+        assert_eq!(note[0].payload["noteable_iid"], 1);
+
+        let pipeline = handle(serde_json::json!({"object_kind": "pipeline"}))
+            .await
+            .unwrap();
+        assert!(pipeline.is_empty());
+    }
+}
@@ -20,7 +20,7 @@ use uuid::Uuid;
 use crate::connectors::{
     AuthorizeParams, Connector, ExchangeTokenParams, Registry, SyncParams, SyncResult,
     WebhookParams,
-    metadata::{AuthType, ProviderMetadata},
+    metadata::{AuthType, ProviderCategory, ProviderMetadata},
 };
 use crate::models::{connection::Model as Connection, signal::Model as Signal};
 use crate::normalization::SignalKind;
@@ -339,7 +339,7 @@ pub struct GmailConnector {
 
 impl GmailConnector {
     fn build_http_client() -> Client {
-        Client::builder()
+        crate::egress::client_builder()
             .timeout(std::time::Duration::from_secs(30))
             .build()
             .expect("Failed to create HTTP client")
@@ -1056,8 +1056,10 @@ impl Connector for GmailConnector {
             external_id: email.to_string(), // Use email as external_id for webhook resolution
             status: "active".to_string(),
             display_name: Some(format!("Gmail ({})", email)),
-            access_token_ciphertext: Some(token_response.access_token.into_bytes()),
-            refresh_token_ciphertext: token_response.refresh_token.map(|t| t.into_bytes()),
+            access_token_ciphertext: None,
+            refresh_token_ciphertext: None,
+            access_token: Some(token_response.access_token),
+            refresh_token: token_response.refresh_token,
             expires_at: token_response.expires_in.map(|seconds| {
                 (chrono::Utc::now() + chrono::Duration::seconds(seconds as i64)).into()
             }),
@@ -1080,21 +1082,10 @@ impl Connector for GmailConnector {
         &self,
         mut connection: Connection,
     ) -> Result<Connection, Box<dyn std::error::Error + Send + Sync>> {
-        let refresh_token_bytes =
-            connection
-                .refresh_token_ciphertext
-                .as_ref()
-                .ok_or_else(|| {
-                    Box::new(GmailError::Authentication(
-                        "No refresh token available".to_string(),
-                    )) as Box<dyn std::error::Error + Send + Sync>
-                })?;
-
-        let refresh_token_str = String::from_utf8(refresh_token_bytes.clone()).map_err(|e| {
-            Box::new(GmailError::Authentication(format!(
-                "Invalid refresh token encoding: {}",
-                e
-            ))) as Box<dyn std::error::Error + Send + Sync>
+        let refresh_token_str = connection.refresh_token.clone().ok_or_else(|| {
+            Box::new(GmailError::Authentication(
+                "No refresh token available".to_string(),
+            )) as Box<dyn std::error::Error + Send + Sync>
         })?;
 
         let token_response = self
@@ -1108,10 +1099,10 @@ impl Connector for GmailConnector {
         );
 
         // Update connection with new tokens
-        connection.access_token_ciphertext = Some(token_response.access_token.into_bytes());
+        connection.access_token = Some(token_response.access_token);
         // Note: Google doesn't always return a new refresh token, so we only update if we get one
         if let Some(new_refresh_token) = token_response.refresh_token {
-            connection.refresh_token_ciphertext = Some(new_refresh_token.into_bytes());
+            connection.refresh_token = Some(new_refresh_token);
         }
         connection.expires_at = token_response
             .expires_in
@@ -1131,20 +1122,12 @@ impl Connector for GmailConnector {
             .cursor
             .and_then(|c| c.as_str().and_then(|s| s.parse().ok()));
 
-        // Extract and decode access token
-        let access_token_bytes = connection.access_token_ciphertext.as_ref().ok_or_else(|| {
+        let access_token = connection.access_token.clone().ok_or_else(|| {
             Box::new(GmailError::Authentication(
                 "No access token available".to_string(),
             )) as Box<dyn std::error::Error + Send + Sync>
         })?;
 
-        let access_token = String::from_utf8(access_token_bytes.clone()).map_err(|e| {
-            Box::new(GmailError::Authentication(format!(
-                "Invalid access token encoding: {}",
-                e
-            ))) as Box<dyn std::error::Error + Send + Sync>
-        })?;
-
         // Start from cursor or fetch current history ID
         let mut current_history_id = start_cursor.unwrap_or({
             // Start from a very early history ID ( Gmail history IDs start from 1)
@@ -1329,7 +1312,13 @@ pub fn register_gmail_connector(registry: &mut Registry, connector: Arc<GmailCon
         AuthType::OAuth2,
         DEFAULT_GMAIL_SCOPES.iter().map(|s| s.to_string()).collect(),
         true, // Supports webhooks
-    );
+    )
+    .with_category(ProviderCategory::Mail)
+    .with_description("Sync email threads and labels from Gmail")
+    .with_icon_url("https://cdn.simpleicons.org/gmail")
+    .with_docs_url("https://developers.google.com/gmail/api")
+    .with_backfill(false)
+    .with_signal_kinds(&[SignalKind::EmailUpdated, SignalKind::EmailDeleted]);
 
     registry.register(connector, metadata);
 }
@@ -1350,8 +1339,10 @@ mod tests {
             external_id: "test@example.com".to_string(),
             status: "active".to_string(),
             display_name: Some("Gmail (test@example.com)".to_string()),
-            access_token_ciphertext: Some(b"test-access-token".to_vec()),
+            access_token_ciphertext: None,
             refresh_token_ciphertext: None,
+            access_token: Some("test-access-token".to_string()),
+            refresh_token: None,
             expires_at: None,
             scopes: None,
             metadata: None,
@@ -1557,6 +1548,7 @@ mod tests {
             tenant_id: Uuid::new_v4(),
             redirect_uri: Some("https://test.com/callback".to_string()),
             state: Some("test_state".to_string()),
+            code_challenge: None,
         };
 
         let url = connector.build_authorize_url(&params).unwrap();
@@ -1628,6 +1620,7 @@ mod tests {
         let params = SyncParams {
             connection,
             cursor: Some(Cursor::from_string("42")),
+            cancellation: Default::default(),
         };
 
         let result = connector.sync(params).await.expect("sync should succeed");
@@ -1763,6 +1756,7 @@ mod tests {
         let params = SyncParams {
             connection: build_test_connection(),
             cursor: None,
+            cancellation: Default::default(),
         };
 
         let err = connector
@@ -1805,6 +1799,7 @@ mod tests {
         let params = SyncParams {
             connection: build_test_connection(),
             cursor: None,
+            cancellation: Default::default(),
         };
 
         let err = connector
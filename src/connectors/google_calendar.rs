@@ -28,7 +28,7 @@ use url::Url;
 use uuid::Uuid;
 
 use crate::connectors::{
-    AuthType, Connector, ProviderMetadata, Registry,
+    AuthType, Connector, ProviderCategory, ProviderMetadata, Registry,
     trait_::{AuthorizeParams, ExchangeTokenParams, SyncParams, SyncResult, WebhookParams},
 };
 use crate::models::{connection::Model as Connection, signal::Model as Signal};
@@ -82,8 +82,10 @@ impl Connector for GoogleCalendarConnector {
             external_id: "calendar-user-123".to_string(),
             status: "active".to_string(),
             display_name: Some("Google Calendar".to_string()),
-            access_token_ciphertext: Some(b"mock_google_calendar_access_token".to_vec()),
-            refresh_token_ciphertext: Some(b"mock_google_calendar_refresh_token".to_vec()),
+            access_token_ciphertext: None,
+            refresh_token_ciphertext: None,
+            access_token: Some("mock_google_calendar_access_token".to_string()),
+            refresh_token: Some("mock_google_calendar_refresh_token".to_string()),
             expires_at: Some(now + chrono::Duration::hours(1)),
             scopes: Some(serde_json::json!([
                 "https://www.googleapis.com/auth/calendar.readonly"
@@ -111,8 +113,10 @@ impl Connector for GoogleCalendarConnector {
             external_id: connection.external_id,
             status: connection.status,
             display_name: connection.display_name,
-            access_token_ciphertext: Some(b"refreshed_google_calendar_access_token".to_vec()),
-            refresh_token_ciphertext: Some(b"new_google_calendar_refresh_token".to_vec()),
+            access_token_ciphertext: None,
+            refresh_token_ciphertext: None,
+            access_token: Some("refreshed_google_calendar_access_token".to_string()),
+            refresh_token: Some("new_google_calendar_refresh_token".to_string()),
             expires_at: Some(now + chrono::Duration::hours(1)),
             scopes: connection.scopes,
             metadata: connection.metadata,
@@ -223,7 +227,16 @@ pub fn register_google_calendar_connector(registry: &mut Registry) {
         AuthType::OAuth2,
         vec!["https://www.googleapis.com/auth/calendar.readonly".to_string()],
         true, // webhooks supported
-    );
+    )
+    .with_category(ProviderCategory::Calendar)
+    .with_description("Sync events and schedule changes from Google Calendar")
+    .with_icon_url("https://cdn.simpleicons.org/googlecalendar")
+    .with_docs_url("https://developers.google.com/calendar/api")
+    .with_backfill(false)
+    .with_signal_kinds(&[
+        SignalKind::CalendarEventUpdated,
+        SignalKind::CalendarEventDeleted,
+    ]);
 
     let connector = Arc::new(GoogleCalendarConnector);
     registry.register(connector, metadata);
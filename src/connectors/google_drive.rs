@@ -30,7 +30,7 @@ use url::Url;
 use uuid::Uuid;
 
 use crate::connectors::{
-    AuthType, Connector, ProviderMetadata, Registry,
+    AuthType, Connector, ProviderCategory, ProviderMetadata, Registry,
     trait_::{AuthorizeParams, ExchangeTokenParams, SyncParams, SyncResult, WebhookParams},
 };
 use crate::models::{connection::Model as Connection, signal::Model as Signal};
@@ -84,8 +84,10 @@ impl Connector for GoogleDriveConnector {
             external_id: "drive-user-123".to_string(),
             status: "active".to_string(),
             display_name: Some("Google Drive".to_string()),
-            access_token_ciphertext: Some(b"mock_google_access_token".to_vec()),
-            refresh_token_ciphertext: Some(b"mock_google_refresh_token".to_vec()),
+            access_token_ciphertext: None,
+            refresh_token_ciphertext: None,
+            access_token: Some("mock_google_access_token".to_string()),
+            refresh_token: Some("mock_google_refresh_token".to_string()),
             expires_at: Some(now + chrono::Duration::hours(1)),
             scopes: Some(serde_json::json!([
                 "https://www.googleapis.com/auth/drive.readonly"
@@ -113,8 +115,10 @@ impl Connector for GoogleDriveConnector {
             external_id: connection.external_id,
             status: connection.status,
             display_name: connection.display_name,
-            access_token_ciphertext: Some(b"refreshed_google_access_token".to_vec()),
-            refresh_token_ciphertext: Some(b"new_google_refresh_token".to_vec()),
+            access_token_ciphertext: None,
+            refresh_token_ciphertext: None,
+            access_token: Some("refreshed_google_access_token".to_string()),
+            refresh_token: Some("new_google_refresh_token".to_string()),
             expires_at: Some(now + chrono::Duration::hours(1)),
             scopes: connection.scopes,
             metadata: connection.metadata,
@@ -212,7 +216,18 @@ pub fn register_google_drive_connector(registry: &mut Registry) {
         AuthType::OAuth2,
         vec!["https://www.googleapis.com/auth/drive.readonly".to_string()],
         true, // webhooks supported
-    );
+    )
+    .with_category(ProviderCategory::Files)
+    .with_description("Track file and document activity in Google Drive")
+    .with_icon_url("https://cdn.simpleicons.org/googledrive")
+    .with_docs_url("https://developers.google.com/drive/api")
+    .with_backfill(false)
+    .with_signal_kinds(&[
+        SignalKind::FileCreated,
+        SignalKind::FileUpdated,
+        SignalKind::FileDeleted,
+        SignalKind::FileMoved,
+    ]);
 
     let connector = Arc::new(GoogleDriveConnector);
     registry.register(connector, metadata);
@@ -0,0 +1,355 @@
+//! Google service accounts with domain-wide delegation
+//!
+//! Workspace admins can grant a service account domain-wide delegation and
+//! onboard every mailbox, calendar or drive in a domain without each user
+//! going through OAuth. A delegated access token is obtained with the JWT
+//! bearer grant (RFC 7523): the service account signs an assertion naming the
+//! user it acts as (`sub`) and the scopes it needs, and exchanges it at the
+//! key's token endpoint.
+//!
+//! Connections created this way store the service-account key JSON encrypted
+//! in place of a refresh token and are marked in their metadata, so the token
+//! refresh service mints a new delegated token instead of calling the
+//! connector's OAuth refresh.
+
+use chrono::{DateTime, Duration, Utc};
+use jsonwebtoken::{Algorithm, EncodingKey, Header};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Default Google OAuth token endpoint, used when the key file names none
+pub const GOOGLE_TOKEN_URI: &str = "https://oauth2.googleapis.com/token";
+
+/// Grant type of the JWT bearer token exchange (RFC 7523)
+const JWT_BEARER_GRANT_TYPE: &str = "urn:ietf:params:oauth:grant-type:jwt-bearer";
+
+/// Lifetime of a signed assertion; Google rejects anything above one hour
+const ASSERTION_LIFETIME_SECONDS: i64 = 3600;
+
+/// Connection metadata key marking how a connection authenticates; `auth` holds
+/// the auth-failure streak kept by [`crate::connection_pause`]
+pub const CONNECTION_AUTH_METADATA_KEY: &str = "auth_method";
+
+/// Value of [`CONNECTION_AUTH_METADATA_KEY`] for service-account connections
+pub const SERVICE_ACCOUNT_AUTH: &str = "service_account";
+
+/// Connection metadata key holding the delegated user's email address
+pub const DELEGATED_SUBJECT_METADATA_KEY: &str = "delegated_subject";
+
+/// Connection metadata key holding the service account's email address
+pub const CLIENT_EMAIL_METADATA_KEY: &str = "client_email";
+
+/// Providers whose connectors accept delegated service-account tokens
+pub const DOMAIN_DELEGATION_PROVIDERS: [&str; 3] = ["gmail", "google-calendar", "google-drive"];
+
+/// Service-account errors
+#[derive(Debug, Error)]
+pub enum ServiceAccountError {
+    #[error("Invalid service account key: {0}")]
+    InvalidKey(String),
+
+    #[error("Failed to sign assertion: {0}")]
+    Signing(String),
+
+    #[error("Network error: {0}")]
+    Network(String),
+
+    #[error("Token grant rejected ({status}): {body}")]
+    GrantRejected { status: u16, body: String },
+}
+
+/// Fields of a Google service-account key file used for delegation
+#[derive(Debug, Clone, Deserialize)]
+pub struct ServiceAccountKey {
+    /// Must be `service_account`
+    #[serde(rename = "type")]
+    pub key_type: String,
+    /// Service account email, the assertion's issuer
+    pub client_email: String,
+    /// PEM-encoded RSA private key
+    pub private_key: String,
+    /// Key identifier, sent as the assertion's `kid`
+    #[serde(default)]
+    pub private_key_id: Option<String>,
+    /// Token endpoint; defaults to [`GOOGLE_TOKEN_URI`]
+    #[serde(default)]
+    pub token_uri: Option<String>,
+}
+
+/// Claims of a JWT bearer assertion
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AssertionClaims {
+    pub iss: String,
+    pub sub: String,
+    pub scope: String,
+    pub aud: String,
+    pub iat: i64,
+    pub exp: i64,
+}
+
+/// Access token issued for a delegated user
+#[derive(Debug, Clone)]
+pub struct DelegatedToken {
+    pub access_token: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    #[serde(default)]
+    expires_in: Option<i64>,
+}
+
+impl ServiceAccountKey {
+    /// Parse and check a service-account key file
+    pub fn parse(json: &str) -> Result<Self, ServiceAccountError> {
+        let key: Self = serde_json::from_str(json)
+            .map_err(|e| ServiceAccountError::InvalidKey(e.to_string()))?;
+        if key.key_type != "service_account" {
+            return Err(ServiceAccountError::InvalidKey(format!(
+                "expected type 'service_account', got '{}'",
+                key.key_type
+            )));
+        }
+        if key.client_email.trim().is_empty() {
+            return Err(ServiceAccountError::InvalidKey(
+                "client_email is empty".to_string(),
+            ));
+        }
+        EncodingKey::from_rsa_pem(key.private_key.as_bytes())
+            .map_err(|e| ServiceAccountError::InvalidKey(format!("private_key: {}", e)))?;
+        Ok(key)
+    }
+
+    /// Token endpoint the assertion is exchanged at
+    pub fn token_uri(&self) -> &str {
+        self.token_uri.as_deref().unwrap_or(GOOGLE_TOKEN_URI)
+    }
+
+    /// Sign an assertion acting as `subject` with the given scopes
+    pub fn assertion(
+        &self,
+        subject: &str,
+        scopes: &[String],
+        now: DateTime<Utc>,
+    ) -> Result<String, ServiceAccountError> {
+        let claims = AssertionClaims {
+            iss: self.client_email.clone(),
+            sub: subject.to_string(),
+            scope: scopes.join(" "),
+            aud: self.token_uri().to_string(),
+            iat: now.timestamp(),
+            exp: now.timestamp() + ASSERTION_LIFETIME_SECONDS,
+        };
+        let mut header = Header::new(Algorithm::RS256);
+        header.kid = self.private_key_id.clone();
+        let key = EncodingKey::from_rsa_pem(self.private_key.as_bytes())
+            .map_err(|e| ServiceAccountError::InvalidKey(format!("private_key: {}", e)))?;
+        jsonwebtoken::encode(&header, &claims, &key)
+            .map_err(|e| ServiceAccountError::Signing(e.to_string()))
+    }
+
+    /// Exchange a signed assertion for an access token acting as `subject`
+    pub async fn delegated_token(
+        &self,
+        http: &Client,
+        subject: &str,
+        scopes: &[String],
+    ) -> Result<DelegatedToken, ServiceAccountError> {
+        let now = Utc::now();
+        let assertion = self.assertion(subject, scopes, now)?;
+        let response = http
+            .post(self.token_uri())
+            .form(&[
+                ("grant_type", JWT_BEARER_GRANT_TYPE),
+                ("assertion", assertion.as_str()),
+            ])
+            .send()
+            .await
+            .map_err(|e| ServiceAccountError::Network(e.to_string()))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(ServiceAccountError::GrantRejected {
+                status: status.as_u16(),
+                body,
+            });
+        }
+        let token: TokenResponse = response
+            .json()
+            .await
+            .map_err(|e| ServiceAccountError::Network(e.to_string()))?;
+        Ok(DelegatedToken {
+            access_token: token.access_token,
+            expires_at: now + Duration::seconds(token.expires_in.unwrap_or(3600)),
+        })
+    }
+}
+
+/// Whether a provider can be connected through domain-wide delegation
+pub fn supports_domain_delegation(provider: &str) -> bool {
+    DOMAIN_DELEGATION_PROVIDERS.contains(&provider)
+}
+
+/// `metadata` marking a connection as delegated to `subject` through the
+/// service account `client_email`, keeping its other keys
+pub fn mark_service_account(
+    metadata: Option<&serde_json::Value>,
+    subject: &str,
+    client_email: &str,
+) -> serde_json::Value {
+    let mut metadata = metadata
+        .and_then(serde_json::Value::as_object)
+        .cloned()
+        .unwrap_or_default();
+    metadata.insert(
+        CONNECTION_AUTH_METADATA_KEY.to_string(),
+        SERVICE_ACCOUNT_AUTH.into(),
+    );
+    metadata.insert(DELEGATED_SUBJECT_METADATA_KEY.to_string(), subject.into());
+    metadata.insert(CLIENT_EMAIL_METADATA_KEY.to_string(), client_email.into());
+    serde_json::Value::Object(metadata)
+}
+
+/// `metadata` without the service-account marker, or `None` if it has none
+pub fn clear_service_account(metadata: Option<&serde_json::Value>) -> Option<serde_json::Value> {
+    let mut metadata = metadata?.as_object()?.clone();
+    let mut marked = false;
+    for key in [
+        CONNECTION_AUTH_METADATA_KEY,
+        DELEGATED_SUBJECT_METADATA_KEY,
+        CLIENT_EMAIL_METADATA_KEY,
+    ] {
+        marked |= metadata.remove(key).is_some();
+    }
+    marked.then_some(serde_json::Value::Object(metadata))
+}
+
+/// Delegated user of a connection created from a service account, if it is one
+pub fn delegated_subject(metadata: Option<&serde_json::Value>) -> Option<&str> {
+    let metadata = metadata?;
+    if metadata.get(CONNECTION_AUTH_METADATA_KEY)?.as_str()? != SERVICE_ACCOUNT_AUTH {
+        return None;
+    }
+    metadata.get(DELEGATED_SUBJECT_METADATA_KEY)?.as_str()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jsonwebtoken::{DecodingKey, Validation};
+    use wiremock::matchers::{body_string_contains, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    const PRIVATE_KEY: &str = include_str!("../../tests/fixtures/google/service_account_key.pem");
+    const PUBLIC_KEY: &str = include_str!("../../tests/fixtures/google/service_account_public.pem");
+
+    fn key_json(token_uri: &str) -> String {
+        serde_json::json!({
+            "type": "service_account",
+            "client_email": "sync@acme-project.iam.gserviceaccount.com",
+            "private_key": PRIVATE_KEY,
+            "private_key_id": "key-1",
+            "token_uri": token_uri,
+        })
+        .to_string()
+    }
+
+    #[tokio::test]
+    async fn test_delegated_token_signs_jwt_bearer_assertion() {
+        let server = MockServer::start().await;
+        let token_uri = format!("{}/token", server.uri());
+        Mock::given(method("POST"))
+            .and(path("/token"))
+            .and(body_string_contains(
+                "grant_type=urn%3Aietf%3Aparams%3Aoauth%3Agrant-type%3Ajwt-bearer",
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "access_token": "ya29.delegated",
+                "expires_in": 3599,
+                "token_type": "Bearer",
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let key = ServiceAccountKey::parse(&key_json(&token_uri)).unwrap();
+        let scopes = vec!["https://www.googleapis.com/auth/gmail.readonly".to_string()];
+        let token = key
+            .delegated_token(&Client::new(), "ada@acme.com", &scopes)
+            .await
+            .unwrap();
+        assert_eq!(token.access_token, "ya29.delegated");
+        assert!(token.expires_at > Utc::now() + Duration::minutes(59));
+
+        let assertion = key.assertion("ada@acme.com", &scopes, Utc::now()).unwrap();
+        let mut validation = Validation::new(Algorithm::RS256);
+        validation.set_audience(&[token_uri.as_str()]);
+        let decoded = jsonwebtoken::decode::<AssertionClaims>(
+            &assertion,
+            &DecodingKey::from_rsa_pem(PUBLIC_KEY.as_bytes()).unwrap(),
+            &validation,
+        )
+        .unwrap();
+        assert_eq!(decoded.header.kid.as_deref(), Some("key-1"));
+        assert_eq!(
+            decoded.claims.iss,
+            "sync@acme-project.iam.gserviceaccount.com"
+        );
+        assert_eq!(decoded.claims.sub, "ada@acme.com");
+        assert_eq!(decoded.claims.scope, scopes[0]);
+    }
+
+    #[tokio::test]
+    async fn test_rejected_grant_and_invalid_keys() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(401).set_body_string(
+                r#"{"error":"unauthorized_client","error_description":"Client is unauthorized to retrieve access tokens using this method"}"#,
+            ))
+            .mount(&server)
+            .await;
+        let key = ServiceAccountKey::parse(&key_json(&server.uri())).unwrap();
+        let error = key
+            .delegated_token(&Client::new(), "ada@acme.com", &[])
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            ServiceAccountError::GrantRejected { status: 401, .. }
+        ));
+
+        let user_credentials = serde_json::json!({
+            "type": "authorized_user",
+            "client_email": "x",
+            "private_key": PRIVATE_KEY,
+        });
+        assert!(ServiceAccountKey::parse(&user_credentials.to_string()).is_err());
+        let bad_pem = key_json(GOOGLE_TOKEN_URI).replace("PRIVATE KEY", "NOT A KEY");
+        assert!(ServiceAccountKey::parse(&bad_pem).is_err());
+    }
+
+    #[test]
+    fn test_delegated_subject_requires_service_account_marker() {
+        let metadata = serde_json::json!({
+            "auth_method": "service_account",
+            "delegated_subject": "ada@acme.com",
+        });
+        assert_eq!(delegated_subject(Some(&metadata)), Some("ada@acme.com"));
+        // Recording an auth failure must not clobber the marker
+        let failing = crate::connection_pause::ConnectionAuthHealth {
+            consecutive_failures: 1,
+            ..Default::default()
+        }
+        .into_connection_metadata(Some(&metadata));
+        assert_eq!(delegated_subject(Some(&failing)), Some("ada@acme.com"));
+        let oauth = serde_json::json!({ "delegated_subject": "ada@acme.com" });
+        assert_eq!(delegated_subject(Some(&oauth)), None);
+        assert_eq!(delegated_subject(None), None);
+        assert!(supports_domain_delegation("gmail"));
+        assert!(!supports_domain_delegation("outlook"));
+    }
+}
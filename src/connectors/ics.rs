@@ -0,0 +1,801 @@
+//! iCalendar (ICS) feed connector implementation
+//!
+//! Each connection polls one published calendar whose URL is kept in the
+//! connection's `calendar_url` metadata setting, for calendar systems that
+//! only offer an ICS export. The cursor keeps a fingerprint of every event, so
+//! each sync diffs the feed's `VEVENT`s against the previous one: new events
+//! become `calendar_event_created`, changed ones `calendar_event_updated`, and
+//! removed or cancelled ones `calendar_event_deleted`.
+
+use anyhow::anyhow;
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, Utc};
+use reqwest::{Client, StatusCode, header};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use tracing::{debug, info};
+use url::Url;
+use uuid::Uuid;
+
+use crate::connectors::{
+    AuthType, Connector, Cursor, ProviderCategory, ProviderMetadata, Registry,
+    trait_::{
+        AuthorizeParams, ExchangeTokenParams, SyncError, SyncParams, SyncResult, WebhookParams,
+    },
+};
+use crate::models::{connection::Model as Connection, signal::Model as Signal};
+use crate::normalization::SignalKind;
+
+/// Provider slug
+pub const ICS_PROVIDER_SLUG: &str = "ics";
+
+/// Connection metadata key holding the calendar URL
+pub const ICS_CALENDAR_URL_KEY: &str = "calendar_url";
+
+/// Largest calendar document read, in bytes
+const MAX_CALENDAR_BYTES: usize = 5 * 1024 * 1024;
+
+/// How recently an event must have been created for the first sync to emit it
+const INITIAL_LOOKBACK_DAYS: i64 = 7;
+
+/// ICS feed connector
+pub struct IcsConnector {
+    http_client: Client,
+}
+
+/// Start or end of an event as written in the feed
+#[derive(Debug, Clone, PartialEq)]
+struct EventTime {
+    /// `2024-05-06T15:00:00Z`, floating `2024-05-06T15:00:00` or all-day `2024-05-06`
+    value: String,
+    time_zone: Option<String>,
+    all_day: bool,
+}
+
+/// Event parsed from a `VEVENT`
+#[derive(Debug, Clone, PartialEq)]
+struct CalendarEvent {
+    /// `UID`, plus the `RECURRENCE-ID` of an overridden occurrence
+    key: String,
+    uid: String,
+    recurrence_id: Option<String>,
+    summary: Option<String>,
+    description: Option<String>,
+    location: Option<String>,
+    organizer: Option<String>,
+    url: Option<String>,
+    start: Option<EventTime>,
+    end: Option<EventTime>,
+    rrule: Option<String>,
+    cancelled: bool,
+    created: Option<DateTime<Utc>>,
+    last_modified: Option<DateTime<Utc>>,
+    /// Hash of the event's properties except `DTSTAMP`, which many servers
+    /// rewrite on every export
+    fingerprint: String,
+}
+
+/// Sync state: fingerprints of the events present on the previous sync and
+/// validators for conditional requests
+#[derive(Debug, Default)]
+struct CalendarState {
+    events: BTreeMap<String, String>,
+    etag: Option<String>,
+    last_modified: Option<String>,
+}
+
+impl CalendarState {
+    fn from_cursor(cursor: Option<&Cursor>) -> Option<Self> {
+        let value = cursor?.as_json();
+        let text = |key: &str| value.get(key).and_then(|v| v.as_str()).map(str::to_string);
+        Some(Self {
+            events: value
+                .get("events")
+                .and_then(|v| v.as_object())
+                .map(|events| {
+                    events
+                        .iter()
+                        .filter_map(|(key, fingerprint)| {
+                            Some((key.clone(), fingerprint.as_str()?.to_string()))
+                        })
+                        .collect()
+                })
+                .unwrap_or_default(),
+            etag: text("etag"),
+            last_modified: text("last_modified"),
+        })
+    }
+
+    fn to_cursor(&self) -> Cursor {
+        Cursor::from_json(serde_json::json!({
+            "events": self.events,
+            "etag": self.etag,
+            "last_modified": self.last_modified,
+        }))
+    }
+}
+
+impl Default for IcsConnector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl IcsConnector {
+    /// Create a new ICS feed connector
+    pub fn new() -> Self {
+        Self {
+            http_client: crate::egress::client(),
+        }
+    }
+
+    /// Fetch the calendar document; `None` when it has not changed since `state`
+    async fn fetch(
+        &self,
+        calendar_url: &str,
+        state: Option<&CalendarState>,
+    ) -> Result<Option<(String, Option<String>, Option<String>)>, SyncError> {
+        let mut request = self
+            .http_client
+            .get(calendar_url)
+            .header(header::ACCEPT, "text/calendar, text/plain;q=0.8");
+        if let Some(state) = state {
+            if let Some(etag) = &state.etag {
+                request = request.header(header::IF_NONE_MATCH, etag);
+            }
+            if let Some(last_modified) = &state.last_modified {
+                request = request.header(header::IF_MODIFIED_SINCE, last_modified);
+            }
+        }
+        let response = request
+            .send()
+            .await
+            .map_err(|e| SyncError::transient(format!("Calendar request failed: {}", e)))?;
+
+        let status = response.status();
+        if status == StatusCode::NOT_MODIFIED {
+            return Ok(None);
+        }
+        if status == StatusCode::TOO_MANY_REQUESTS {
+            let retry_after = response
+                .headers()
+                .get(header::RETRY_AFTER)
+                .and_then(|h| h.to_str().ok())
+                .and_then(|s| s.parse::<u64>().ok());
+            return Err(SyncError::rate_limited(retry_after));
+        }
+        if status.is_server_error() {
+            return Err(SyncError::transient(format!(
+                "Calendar request to {} failed: {}",
+                calendar_url, status
+            )));
+        }
+        if !status.is_success() {
+            return Err(SyncError::permanent(format!(
+                "Calendar request to {} failed: {}",
+                calendar_url, status
+            )));
+        }
+
+        let validator = |name: header::HeaderName| {
+            response
+                .headers()
+                .get(name)
+                .and_then(|h| h.to_str().ok())
+                .map(str::to_string)
+        };
+        let etag = validator(header::ETAG);
+        let last_modified = validator(header::LAST_MODIFIED);
+        let body = response
+            .bytes()
+            .await
+            .map_err(|e| SyncError::transient(format!("Calendar download failed: {}", e)))?;
+        if body.len() > MAX_CALENDAR_BYTES {
+            return Err(SyncError::permanent(format!(
+                "Calendar at {} is larger than {} bytes",
+                calendar_url, MAX_CALENDAR_BYTES
+            )));
+        }
+        let document = String::from_utf8_lossy(&body).into_owned();
+        Ok(Some((document, etag, last_modified)))
+    }
+}
+
+#[async_trait]
+impl Connector for IcsConnector {
+    async fn authorize(
+        &self,
+        _params: AuthorizeParams,
+    ) -> Result<Url, Box<dyn std::error::Error + Send + Sync>> {
+        Err(anyhow!("OAuth authorization is not supported for ICS feeds").into())
+    }
+
+    async fn exchange_token(
+        &self,
+        _params: ExchangeTokenParams,
+    ) -> Result<Connection, Box<dyn std::error::Error + Send + Sync>> {
+        Err(anyhow!("Token exchange is not supported for ICS feeds").into())
+    }
+
+    async fn refresh_token(
+        &self,
+        _connection: Connection,
+    ) -> Result<Connection, Box<dyn std::error::Error + Send + Sync>> {
+        Err(anyhow!("Token refresh is not supported for ICS feeds").into())
+    }
+
+    async fn sync(
+        &self,
+        params: SyncParams,
+    ) -> Result<SyncResult, Box<dyn std::error::Error + Send + Sync>> {
+        let connection = &params.connection;
+        let calendar_url = connection
+            .metadata
+            .as_ref()
+            .and_then(|m| m.get(ICS_CALENDAR_URL_KEY))
+            .and_then(|v| v.as_str())
+            .map(fetch_url)
+            .ok_or_else(|| {
+                SyncError::permanent(format!(
+                    "Calendar connection has no '{}' metadata setting",
+                    ICS_CALENDAR_URL_KEY
+                ))
+            })?;
+        info!(
+            tenant_id = %connection.tenant_id,
+            connection_id = %connection.id,
+            has_cursor = %params.cursor.is_some(),
+            "Starting ICS calendar sync"
+        );
+
+        let state = CalendarState::from_cursor(params.cursor.as_ref());
+        let Some((document, etag, last_modified)) =
+            self.fetch(&calendar_url, state.as_ref()).await?
+        else {
+            debug!(connection_id = %connection.id, "Calendar not modified");
+            return Ok(SyncResult {
+                signals: vec![],
+                next_cursor: params.cursor,
+                has_more: false,
+            });
+        };
+        let events = parse_calendar(&document).map_err(SyncError::permanent)?;
+        // Cancelled events are treated as absent, so cancelling one deletes it
+        let current: BTreeMap<&str, &CalendarEvent> = events
+            .iter()
+            .filter(|event| !event.cancelled)
+            .map(|event| (event.key.as_str(), event))
+            .collect();
+
+        let mut signals = Vec::new();
+        match &state {
+            Some(previous) => {
+                for (key, event) in &current {
+                    match previous.events.get(*key) {
+                        None => signals.push(build_event_signal(
+                            connection,
+                            SignalKind::CalendarEventCreated,
+                            event,
+                        )),
+                        Some(fingerprint) if *fingerprint != event.fingerprint => signals.push(
+                            build_event_signal(connection, SignalKind::CalendarEventUpdated, event),
+                        ),
+                        Some(_) => {}
+                    }
+                }
+                for key in previous.events.keys() {
+                    if !current.contains_key(key.as_str()) {
+                        // A cancelled event still carries its details
+                        let cancelled = events.iter().find(|event| &event.key == key);
+                        signals.push(build_deleted_signal(connection, key, cancelled));
+                    }
+                }
+            }
+            // The first sync only reports recently created events
+            None => {
+                let cutoff = Utc::now() - Duration::days(INITIAL_LOOKBACK_DAYS);
+                signals.extend(
+                    current
+                        .values()
+                        .filter(|event| event.created.is_some_and(|at| at >= cutoff))
+                        .map(|event| {
+                            build_event_signal(connection, SignalKind::CalendarEventCreated, event)
+                        }),
+                );
+            }
+        }
+
+        let next_state = CalendarState {
+            events: current
+                .iter()
+                .map(|(key, event)| (key.to_string(), event.fingerprint.clone()))
+                .collect(),
+            etag,
+            last_modified,
+        };
+
+        debug!(
+            connection_id = %connection.id,
+            events = current.len(),
+            signals = signals.len(),
+            "ICS calendar sync completed"
+        );
+
+        Ok(SyncResult {
+            signals,
+            next_cursor: Some(next_state.to_cursor()),
+            has_more: false,
+        })
+    }
+
+    async fn handle_webhook(
+        &self,
+        _params: WebhookParams,
+    ) -> Result<Vec<Signal>, Box<dyn std::error::Error + Send + Sync>> {
+        Err(anyhow!("Webhooks are not supported for ICS feeds").into())
+    }
+}
+
+/// Register the ICS feed connector in the registry
+pub fn register_ics_connector(registry: &mut Registry, connector: Arc<IcsConnector>) {
+    let metadata = ProviderMetadata::new(
+        ICS_PROVIDER_SLUG.to_string(),
+        AuthType::Custom("none".to_string()),
+        vec![],
+        false, // webhooks not supported
+    )
+    .with_category(ProviderCategory::Calendar)
+    .with_description("Poll published iCalendar (ICS) feeds for event changes")
+    .with_icon_url("https://cdn.simpleicons.org/googlecalendar")
+    .with_docs_url("https://www.rfc-editor.org/rfc/rfc5545")
+    .with_backfill(true)
+    .with_signal_kinds(&[
+        SignalKind::CalendarEventCreated,
+        SignalKind::CalendarEventUpdated,
+        SignalKind::CalendarEventDeleted,
+    ]);
+
+    registry.register(connector, metadata);
+}
+
+/// URL to fetch; `webcal://` is the `https://` URL calendar apps subscribe to
+fn fetch_url(calendar_url: &str) -> String {
+    match calendar_url.strip_prefix("webcal://") {
+        Some(rest) => format!("https://{}", rest),
+        None => calendar_url.to_string(),
+    }
+}
+
+/// Content line split into its name, parameters and value
+struct ContentLine<'a> {
+    name: String,
+    params: Vec<(String, &'a str)>,
+    value: &'a str,
+}
+
+impl ContentLine<'_> {
+    fn param(&self, name: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.trim_matches('"'))
+    }
+}
+
+/// Split `NAME;PARAM=value:VALUE`, skipping colons inside quoted parameters
+fn content_line(line: &str) -> Option<ContentLine<'_>> {
+    let mut quoted = false;
+    let colon = line.char_indices().find_map(|(i, c)| match c {
+        '"' => {
+            quoted = !quoted;
+            None
+        }
+        ':' if !quoted => Some(i),
+        _ => None,
+    })?;
+    let (head, value) = (&line[..colon], &line[colon + 1..]);
+    let mut parts = head.split(';');
+    let name = parts.next()?.trim().to_ascii_uppercase();
+    let params = parts
+        .filter_map(|param| {
+            let (key, value) = param.split_once('=')?;
+            Some((key.trim().to_ascii_uppercase(), value))
+        })
+        .collect();
+    Some(ContentLine {
+        name,
+        params,
+        value,
+    })
+}
+
+/// Undo RFC 5545 text escaping
+fn unescape(value: &str) -> Option<String> {
+    let mut text = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            match chars.next() {
+                Some('n' | 'N') => text.push('\n'),
+                Some(other) => text.push(other),
+                None => {}
+            }
+        } else {
+            text.push(c);
+        }
+    }
+    let text = text.trim();
+    (!text.is_empty()).then(|| text.to_string())
+}
+
+fn parse_utc(value: &str) -> Option<DateTime<Utc>> {
+    NaiveDateTime::parse_from_str(value.trim_end_matches('Z'), "%Y%m%dT%H%M%S")
+        .ok()
+        .map(|dt| dt.and_utc())
+}
+
+/// Read a `DTSTART` or `DTEND`, keeping local times in their own zone
+fn event_time(line: &ContentLine) -> Option<EventTime> {
+    let value = line.value.trim();
+    if line.param("VALUE") == Some("DATE") || value.len() == 8 {
+        let date = NaiveDate::parse_from_str(value, "%Y%m%d").ok()?;
+        return Some(EventTime {
+            value: date.format("%Y-%m-%d").to_string(),
+            time_zone: None,
+            all_day: true,
+        });
+    }
+    let local = NaiveDateTime::parse_from_str(value.trim_end_matches('Z'), "%Y%m%dT%H%M%S").ok()?;
+    let utc = value.ends_with('Z');
+    Some(EventTime {
+        value: format!(
+            "{}{}",
+            local.format("%Y-%m-%dT%H:%M:%S"),
+            if utc { "Z" } else { "" }
+        ),
+        time_zone: if utc {
+            Some("UTC".to_string())
+        } else {
+            line.param("TZID").map(str::to_string)
+        },
+        all_day: false,
+    })
+}
+
+/// Parse the `VEVENT`s of an iCalendar document
+fn parse_calendar(document: &str) -> Result<Vec<CalendarEvent>, String> {
+    // Unfold continuation lines, which start with a space or tab
+    let mut lines: Vec<String> = Vec::new();
+    for raw in document.lines() {
+        match (raw.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(rest), Some(last)) => last.push_str(rest),
+            _ => lines.push(raw.to_string()),
+        }
+    }
+    if !lines
+        .iter()
+        .find(|line| !line.trim().is_empty())
+        .is_some_and(|line| line.trim().eq_ignore_ascii_case("BEGIN:VCALENDAR"))
+    {
+        return Err("Document is not an iCalendar feed".to_string());
+    }
+
+    let mut events = Vec::new();
+    // Components open inside the current event, such as VALARM
+    let mut nested = 0usize;
+    let mut current: Option<Vec<&str>> = None;
+    for line in &lines {
+        let trimmed = line.trim_end();
+        let upper = trimmed.to_ascii_uppercase();
+        match (&mut current, upper.as_str()) {
+            (None, "BEGIN:VEVENT") => current = Some(Vec::new()),
+            (Some(_), "END:VEVENT") if nested == 0 => {
+                if let Some(event) = current.take().and_then(|props| calendar_event(&props)) {
+                    events.push(event);
+                }
+            }
+            (Some(_), begin) if begin.starts_with("BEGIN:") => nested += 1,
+            (Some(_), end) if end.starts_with("END:") => nested = nested.saturating_sub(1),
+            (Some(props), _) if nested == 0 && !trimmed.is_empty() => props.push(trimmed),
+            _ => {}
+        }
+    }
+    Ok(events)
+}
+
+fn calendar_event(props: &[&str]) -> Option<CalendarEvent> {
+    let lines: Vec<ContentLine> = props.iter().filter_map(|p| content_line(p)).collect();
+    let find = |name: &str| lines.iter().find(|line| line.name == name);
+    let text = |name: &str| find(name).and_then(|line| unescape(line.value));
+    let uid = text("UID")?;
+    let recurrence_id = find("RECURRENCE-ID").map(|line| line.value.trim().to_string());
+
+    let mut hasher = Sha256::new();
+    for prop in props {
+        if !prop.to_ascii_uppercase().starts_with("DTSTAMP") {
+            hasher.update(prop.as_bytes());
+            hasher.update(b"\n");
+        }
+    }
+    let fingerprint = hex::encode(&hasher.finalize()[..8]);
+
+    Some(CalendarEvent {
+        key: match &recurrence_id {
+            Some(recurrence_id) => format!("{}#{}", uid, recurrence_id),
+            None => uid.clone(),
+        },
+        summary: text("SUMMARY"),
+        description: text("DESCRIPTION"),
+        location: text("LOCATION"),
+        organizer: find("ORGANIZER").and_then(|line| {
+            let value = line.value.trim();
+            let address = value
+                .get(..7)
+                .filter(|scheme| scheme.eq_ignore_ascii_case("mailto:"))
+                .map_or(value, |_| &value[7..]);
+            (!address.is_empty()).then(|| address.to_string())
+        }),
+        url: text("URL"),
+        start: find("DTSTART").and_then(event_time),
+        end: find("DTEND").and_then(event_time),
+        rrule: text("RRULE"),
+        cancelled: text("STATUS").is_some_and(|status| status.eq_ignore_ascii_case("CANCELLED")),
+        created: find("CREATED").and_then(|line| parse_utc(line.value.trim())),
+        last_modified: find("LAST-MODIFIED").and_then(|line| parse_utc(line.value.trim())),
+        uid,
+        recurrence_id,
+        fingerprint,
+    })
+}
+
+fn event_payload(event: &CalendarEvent, occurred_at: DateTime<Utc>) -> serde_json::Value {
+    let time = |time: &Option<EventTime>| {
+        time.as_ref().map(|time| {
+            serde_json::json!({
+                "value": time.value,
+                "time_zone": time.time_zone,
+            })
+        })
+    };
+    serde_json::json!({
+        "event_id": event.key,
+        "ical_uid": event.uid,
+        "recurrence_id": event.recurrence_id,
+        "subject": event.summary,
+        "text": event.description,
+        "start": time(&event.start),
+        "end": time(&event.end),
+        "is_all_day": event.start.as_ref().is_some_and(|start| start.all_day),
+        "is_recurring": event.rrule.is_some(),
+        "location": event.location,
+        "organizer": event.organizer,
+        "url": event.url,
+        "occurred_at": occurred_at.to_rfc3339(),
+    })
+}
+
+/// Build a `calendar_event_created` or `calendar_event_updated` signal
+///
+/// The key carries the event's fingerprint, so each distinct version of an
+/// event is reported once.
+fn build_event_signal(connection: &Connection, kind: SignalKind, event: &CalendarEvent) -> Signal {
+    let occurred_at = match kind {
+        SignalKind::CalendarEventCreated => event.created.or(event.last_modified),
+        _ => event.last_modified,
+    }
+    .unwrap_or_else(Utc::now);
+    build_signal(
+        connection,
+        kind,
+        occurred_at,
+        event_payload(event, occurred_at),
+        format!(
+            "{}:{}:{}:{}:{}",
+            ICS_PROVIDER_SLUG, kind, connection.id, event.key, event.fingerprint
+        ),
+    )
+}
+
+/// Build a `calendar_event_deleted` signal for an event that left the feed
+fn build_deleted_signal(
+    connection: &Connection,
+    key: &str,
+    cancelled: Option<&CalendarEvent>,
+) -> Signal {
+    let kind = SignalKind::CalendarEventDeleted;
+    let occurred_at = cancelled
+        .and_then(|event| event.last_modified)
+        .unwrap_or_else(Utc::now);
+    let payload = match cancelled {
+        Some(event) => event_payload(event, occurred_at),
+        None => serde_json::json!({
+            "event_id": key,
+            "occurred_at": occurred_at.to_rfc3339(),
+        }),
+    };
+    build_signal(
+        connection,
+        kind,
+        occurred_at,
+        payload,
+        format!("{}:{}:{}:{}", ICS_PROVIDER_SLUG, kind, connection.id, key),
+    )
+}
+
+fn build_signal(
+    connection: &Connection,
+    kind: SignalKind,
+    occurred_at: DateTime<Utc>,
+    payload: serde_json::Value,
+    dedupe_key: String,
+) -> Signal {
+    let received_at = DateTime::from(Utc::now());
+    Signal {
+        id: Uuid::new_v4(),
+        tenant_id: connection.tenant_id,
+        provider_slug: ICS_PROVIDER_SLUG.to_string(),
+        connection_id: connection.id,
+        kind: kind.as_str().to_string(),
+        occurred_at: occurred_at.into(),
+        received_at,
+        payload,
+        dedupe_key: Some(dedupe_key),
+        created_at: received_at,
+        updated_at: received_at,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn connection(calendar_url: &str) -> Connection {
+        let now = DateTime::from(Utc::now());
+        Connection {
+            id: Uuid::new_v4(),
+            tenant_id: Uuid::new_v4(),
+            provider_slug: ICS_PROVIDER_SLUG.to_string(),
+            external_id: calendar_url.to_string(),
+            status: "active".to_string(),
+            display_name: None,
+            access_token_ciphertext: None,
+            refresh_token_ciphertext: None,
+            access_token: None,
+            refresh_token: None,
+            expires_at: None,
+            scopes: None,
+            metadata: Some(serde_json::json!({ ICS_CALENDAR_URL_KEY: calendar_url })),
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    fn calendar(events: &[String]) -> String {
+        format!(
+            "BEGIN:VCALENDAR\r\nVERSION:2.0\r\nPRODID:-//Test//EN\r\n{}END:VCALENDAR\r\n",
+            events.concat()
+        )
+    }
+
+    fn event(uid: &str, summary: &str, created: DateTime<Utc>) -> String {
+        format!(
+            "BEGIN:VEVENT\r\nUID:{}\r\nDTSTAMP:{}\r\nCREATED:{}\r\nSUMMARY:{}\r\n\
+             DTSTART:20240506T150000Z\r\nEND:VEVENT\r\n",
+            uid,
+            Utc::now().format("%Y%m%dT%H%M%SZ"),
+            created.format("%Y%m%dT%H%M%SZ"),
+            summary
+        )
+    }
+
+    #[test]
+    fn test_parse_calendar_events() {
+        let events = parse_calendar(
+            "BEGIN:VCALENDAR\r\n\
+             BEGIN:VEVENT\r\n\
+             UID:board-1@acme.example\r\n\
+             SUMMARY:Board meeting\\, Q2 \r\n review\r\n\
+             DESCRIPTION:Agenda:\\nbudget\r\n\
+             DTSTART;TZID=\"Europe/Berlin\":20240506T150000\r\n\
+             DTEND;TZID=Europe/Berlin:20240506T160000\r\n\
+             ORGANIZER;CN=\"Ada: CEO\":mailto:ada@acme.example\r\n\
+             RRULE:FREQ=MONTHLY\r\n\
+             BEGIN:VALARM\r\nACTION:DISPLAY\r\nDESCRIPTION:Reminder\r\nEND:VALARM\r\n\
+             END:VEVENT\r\n\
+             BEGIN:VEVENT\r\n\
+             UID:board-1@acme.example\r\n\
+             RECURRENCE-ID;TZID=Europe/Berlin:20240603T150000\r\n\
+             STATUS:CANCELLED\r\n\
+             DTSTART;VALUE=DATE:20240603\r\n\
+             END:VEVENT\r\n\
+             BEGIN:VEVENT\r\nSUMMARY:No UID\r\nEND:VEVENT\r\n\
+             END:VCALENDAR\r\n",
+        )
+        .unwrap();
+        assert_eq!(events.len(), 2);
+        let board = &events[0];
+        assert_eq!(board.key, "board-1@acme.example");
+        assert_eq!(board.summary.as_deref(), Some("Board meeting, Q2 review"));
+        assert_eq!(board.description.as_deref(), Some("Agenda:\nbudget"));
+        assert_eq!(board.organizer.as_deref(), Some("ada@acme.example"));
+        let start = board.start.as_ref().unwrap();
+        assert_eq!(start.value, "2024-05-06T15:00:00");
+        assert_eq!(start.time_zone.as_deref(), Some("Europe/Berlin"));
+        assert!(board.rrule.is_some());
+
+        let occurrence = &events[1];
+        assert_eq!(occurrence.key, "board-1@acme.example#20240603T150000");
+        assert!(occurrence.cancelled);
+        assert!(occurrence.start.as_ref().unwrap().all_day);
+
+        assert!(parse_calendar("<html></html>").is_err());
+        assert_eq!(
+            fetch_url("webcal://cal.example/a.ics"),
+            "https://cal.example/a.ics"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_ics_sync_diffs_events() {
+        let server = MockServer::start().await;
+        let recent = Utc::now() - Duration::hours(1);
+        let old = Utc::now() - Duration::days(30);
+        let versions = [
+            calendar(&[event("a", "Kickoff", recent), event("b", "Standup", old)]),
+            // DTSTAMP changes alone are not updates
+            calendar(&[event("a", "Kickoff", recent), event("b", "Standup", old)]),
+            calendar(&[
+                event("a", "Kickoff (moved)", recent),
+                event("c", "Launch", recent),
+            ]),
+        ];
+        for (priority, body) in versions.iter().enumerate() {
+            Mock::given(method("GET"))
+                .and(path("/team.ics"))
+                .respond_with(ResponseTemplate::new(200).set_body_string(body.clone()))
+                .up_to_n_times(1)
+                .with_priority(priority as u8 + 1)
+                .mount(&server)
+                .await;
+        }
+
+        let connector = IcsConnector::new();
+        let connection = connection(&format!("{}/team.ics", server.uri()));
+        let sync = |cursor: Option<Cursor>| SyncParams {
+            connection: connection.clone(),
+            cursor,
+            cancellation: Default::default(),
+        };
+
+        // The first sync only reports events created within the lookback
+        let first = connector.sync(sync(None)).await.unwrap();
+        assert_eq!(first.signals.len(), 1);
+        assert_eq!(first.signals[0].kind, "calendar_event_created");
+        assert_eq!(first.signals[0].payload["subject"], "Kickoff");
+        assert_eq!(
+            first.signals[0].payload["start"]["value"],
+            "2024-05-06T15:00:00Z"
+        );
+
+        let unchanged = connector.sync(sync(first.next_cursor)).await.unwrap();
+        assert!(unchanged.signals.is_empty());
+
+        let changed = connector.sync(sync(unchanged.next_cursor)).await.unwrap();
+        let kinds: Vec<(&str, &serde_json::Value)> = changed
+            .signals
+            .iter()
+            .map(|s| (s.kind.as_str(), &s.payload["event_id"]))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ("calendar_event_updated", &serde_json::json!("a")),
+                ("calendar_event_created", &serde_json::json!("c")),
+                ("calendar_event_deleted", &serde_json::json!("b")),
+            ]
+        );
+    }
+}
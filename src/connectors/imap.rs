@@ -0,0 +1,539 @@
+//! IMAP connector implementation
+//!
+//! Syncs mailboxes on any IMAP server, for mail that Gmail, Zoho Mail and
+//! Outlook do not cover. A connection's server, user name and mailboxes are
+//! metadata settings; its password or XOAUTH2 access token is stored as the
+//! connection's encrypted access token. Messages pass through the shared
+//! [`MailSpamFilter`](crate::mail::MailSpamFilter) and become
+//! `email_received` signals.
+//!
+//! The cursor keeps one window per mailbox with its `UIDVALIDITY` and the
+//! highest UID read. When the server reports a new `UIDVALIDITY`, stored UIDs
+//! no longer identify the same messages, so that mailbox restarts from the
+//! initial lookback instead.
+
+use anyhow::anyhow;
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use std::sync::Arc;
+use tracing::{debug, info, warn};
+use url::Url;
+use uuid::Uuid;
+
+use crate::connectors::metadata_schema::{ImapAuthMethod, ImapConnectionSettings, parse_settings};
+use crate::connectors::{
+    AuthType, Connector, Cursor, CursorWindows, ProviderCategory, ProviderMetadata, Registry,
+    trait_::{
+        AuthorizeParams, ExchangeTokenParams, SyncError, SyncParams, SyncResult, WebhookParams,
+    },
+};
+use crate::mail::MailSpamFilter;
+use crate::mail::imap::{FetchedMessage, ImapAuth, ImapError, ImapSession};
+use crate::mail::inbound::{InboundEmail, parse_mime};
+use crate::mail::integration::should_create_signal;
+use crate::models::{connection::Model as Connection, signal::Model as Signal};
+use crate::normalization::SignalKind;
+
+/// Provider slug
+pub const IMAP_PROVIDER_SLUG: &str = "imap";
+
+/// Port used when the settings do not name one (IMAP over implicit TLS)
+const DEFAULT_PORT: u16 = 993;
+
+/// Mailbox synced when the settings do not list any
+const DEFAULT_MAILBOX: &str = "INBOX";
+
+/// Bytes of each message fetched; longer messages are cut
+const MAX_MESSAGE_BYTES: usize = 256 * 1024;
+
+/// Messages read per mailbox in one sync; the rest continue on the next run
+const MAX_MESSAGES_PER_MAILBOX: usize = 50;
+
+/// Messages requested per `UID FETCH`
+const FETCH_BATCH: usize = 10;
+
+/// How far back the first sync of a mailbox reads
+const INITIAL_LOOKBACK_DAYS: i64 = 7;
+
+/// Time allowed for connecting and for each command
+const COMMAND_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// IMAP connector
+pub struct ImapConnector {
+    spam_filter: Arc<dyn MailSpamFilter>,
+}
+
+/// Position in one mailbox
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct MailboxWindow {
+    uid_validity: u32,
+    last_uid: u32,
+}
+
+impl MailboxWindow {
+    fn from_cursor(cursor: Option<&Cursor>) -> Option<Self> {
+        let value = cursor?.as_json();
+        let number = |key: &str| value.get(key)?.as_u64()?.try_into().ok();
+        Some(Self {
+            uid_validity: number("uid_validity")?,
+            last_uid: number("last_uid")?,
+        })
+    }
+
+    fn to_cursor(self) -> Cursor {
+        Cursor::from_json(serde_json::json!({
+            "uid_validity": self.uid_validity,
+            "last_uid": self.last_uid,
+        }))
+    }
+}
+
+impl ImapConnector {
+    /// Create a new IMAP connector
+    pub fn new(spam_filter: Arc<dyn MailSpamFilter>) -> Self {
+        Self { spam_filter }
+    }
+
+    /// Read new messages from one mailbox
+    ///
+    /// Returns the signals, the mailbox's next window and whether messages
+    /// were left for the next run.
+    async fn sync_mailbox(
+        &self,
+        session: &mut ImapSession,
+        connection: &Connection,
+        mailbox: &str,
+        previous: Option<MailboxWindow>,
+    ) -> Result<(Vec<Signal>, MailboxWindow, bool), ImapError> {
+        let status = session.examine(mailbox).await?;
+        let previous = previous.filter(|window| {
+            let valid = window.uid_validity == status.uid_validity;
+            if !valid {
+                warn!(
+                    connection_id = %connection.id,
+                    mailbox = %mailbox,
+                    previous = window.uid_validity,
+                    current = status.uid_validity,
+                    "IMAP UIDVALIDITY changed; restarting mailbox from the initial lookback"
+                );
+            }
+            valid
+        });
+
+        let mut uids = match previous {
+            // `n:*` always matches the newest message, even when its UID is below n
+            Some(window) => session
+                .uid_search(&format!("UID {}:*", window.last_uid.saturating_add(1)))
+                .await?
+                .into_iter()
+                .filter(|uid| *uid > window.last_uid)
+                .collect(),
+            None => {
+                let since = Utc::now() - Duration::days(INITIAL_LOOKBACK_DAYS);
+                session
+                    .uid_search(&format!("SINCE {}", since.format("%-d-%b-%Y")))
+                    .await?
+            }
+        };
+        let has_more = uids.len() > MAX_MESSAGES_PER_MAILBOX;
+        uids.truncate(MAX_MESSAGES_PER_MAILBOX);
+
+        let mut signals = Vec::new();
+        for batch in uids.chunks(FETCH_BATCH) {
+            for message in session.uid_fetch(batch, MAX_MESSAGE_BYTES).await? {
+                if let Some(signal) = self.message_signal(connection, mailbox, &message) {
+                    signals.push(signal);
+                }
+            }
+        }
+
+        let read_up_to = uids.last().copied().unwrap_or(0);
+        let last_uid = match (previous, has_more) {
+            (_, true) => read_up_to,
+            (Some(window), false) => read_up_to.max(window.last_uid),
+            // Older messages outside the lookback are never read
+            (None, false) => {
+                let newest = match status.uid_next {
+                    Some(uid_next) => uid_next.saturating_sub(1),
+                    None if status.exists > 0 => session
+                        .uid_search("UID *")
+                        .await?
+                        .last()
+                        .copied()
+                        .unwrap_or(0),
+                    None => 0,
+                };
+                read_up_to.max(newest)
+            }
+        };
+
+        Ok((
+            signals,
+            MailboxWindow {
+                uid_validity: status.uid_validity,
+                last_uid,
+            },
+            has_more,
+        ))
+    }
+
+    /// Spam-filtered signal for a fetched message
+    fn message_signal(
+        &self,
+        connection: &Connection,
+        mailbox: &str,
+        message: &FetchedMessage,
+    ) -> Option<Signal> {
+        let mut email = match parse_mime(&message.body) {
+            Ok(email) => email,
+            Err(e) => {
+                debug!(
+                    connection_id = %connection.id,
+                    mailbox = %mailbox,
+                    uid = message.uid,
+                    error = %e,
+                    "Skipping unparseable IMAP message"
+                );
+                return None;
+            }
+        };
+        // `$Junk` or `\Junk` becomes the `junk` label the spam filter knows
+        email.labels = message
+            .flags
+            .iter()
+            .map(|flag| flag.trim_start_matches(['\\', '$']).to_ascii_lowercase())
+            .collect();
+        email.source = "imap".to_string();
+        let stable_id = email.stable_id();
+
+        let mut metadata = email.spam_metadata();
+        metadata.provider = crate::mail::MailProvider::from_slug(IMAP_PROVIDER_SLUG);
+        if !should_create_signal(
+            &self.spam_filter,
+            &metadata,
+            IMAP_PROVIDER_SLUG,
+            connection.id,
+            &stable_id,
+        ) {
+            return None;
+        }
+        Some(build_email_signal(
+            connection,
+            mailbox,
+            message.uid,
+            &email,
+            &stable_id,
+        ))
+    }
+}
+
+#[async_trait]
+impl Connector for ImapConnector {
+    async fn authorize(
+        &self,
+        _params: AuthorizeParams,
+    ) -> Result<Url, Box<dyn std::error::Error + Send + Sync>> {
+        Err(anyhow!("OAuth authorization is not supported for IMAP").into())
+    }
+
+    async fn exchange_token(
+        &self,
+        _params: ExchangeTokenParams,
+    ) -> Result<Connection, Box<dyn std::error::Error + Send + Sync>> {
+        Err(anyhow!("Token exchange is not supported for IMAP").into())
+    }
+
+    async fn refresh_token(
+        &self,
+        _connection: Connection,
+    ) -> Result<Connection, Box<dyn std::error::Error + Send + Sync>> {
+        Err(anyhow!("Token refresh is not supported for IMAP").into())
+    }
+
+    async fn sync(
+        &self,
+        params: SyncParams,
+    ) -> Result<SyncResult, Box<dyn std::error::Error + Send + Sync>> {
+        let connection = &params.connection;
+        let settings: ImapConnectionSettings =
+            parse_settings(IMAP_PROVIDER_SLUG, connection.metadata.as_ref()).map_err(|e| {
+                SyncError::permanent(format!("Invalid IMAP connection settings: {}", e.message))
+            })?;
+        let secret = connection
+            .access_token
+            .clone()
+            .filter(|secret| !secret.is_empty())
+            .ok_or_else(|| SyncError::unauthorized("Missing IMAP password or access token"))?;
+        let mailboxes = settings
+            .mailboxes
+            .clone()
+            .filter(|mailboxes| !mailboxes.is_empty())
+            .unwrap_or_else(|| vec![DEFAULT_MAILBOX.to_string()]);
+        info!(
+            tenant_id = %connection.tenant_id,
+            connection_id = %connection.id,
+            host = %settings.host,
+            mailboxes = mailboxes.len(),
+            has_cursor = %params.cursor.is_some(),
+            "Starting IMAP sync"
+        );
+
+        let mut session = ImapSession::connect(
+            &settings.host,
+            settings.port.unwrap_or(DEFAULT_PORT),
+            settings.tls.unwrap_or(true),
+            COMMAND_TIMEOUT,
+        )
+        .await
+        .map_err(sync_error)?;
+        let auth = match settings.auth.unwrap_or_default() {
+            ImapAuthMethod::Password => ImapAuth::Password(&secret),
+            ImapAuthMethod::Xoauth2 => ImapAuth::XOAuth2(&secret),
+        };
+        session
+            .login(&settings.username, auth)
+            .await
+            .map_err(sync_error)?;
+
+        let mut signals = Vec::new();
+        let mut windows = CursorWindows::new();
+        let mut has_more = false;
+        for mailbox in &mailboxes {
+            let previous = MailboxWindow::from_cursor(
+                params
+                    .cursor
+                    .as_ref()
+                    .and_then(|cursor| cursor.window(mailbox))
+                    .as_ref(),
+            );
+            let (mailbox_signals, window, mailbox_has_more) = self
+                .sync_mailbox(&mut session, connection, mailbox, previous)
+                .await
+                .map_err(sync_error)?;
+            signals.extend(mailbox_signals);
+            windows.set(mailbox.clone(), window.to_cursor());
+            has_more |= mailbox_has_more;
+        }
+        session.logout().await;
+
+        debug!(
+            connection_id = %connection.id,
+            signals = signals.len(),
+            has_more,
+            "IMAP sync completed"
+        );
+
+        Ok(SyncResult {
+            signals,
+            next_cursor: Some(Cursor::from_windows(windows)),
+            has_more,
+        })
+    }
+
+    async fn handle_webhook(
+        &self,
+        _params: WebhookParams,
+    ) -> Result<Vec<Signal>, Box<dyn std::error::Error + Send + Sync>> {
+        Err(anyhow!("Webhooks are not supported for IMAP").into())
+    }
+}
+
+fn sync_error(error: ImapError) -> SyncError {
+    match error {
+        ImapError::Auth(message) => {
+            SyncError::unauthorized(format!("IMAP server rejected the credentials: {}", message))
+        }
+        ImapError::EgressDenied(_) => SyncError::permanent(error.to_string()),
+        ImapError::Command(_) => SyncError::permanent(error.to_string()),
+        ImapError::Io(_) | ImapError::Protocol(_) | ImapError::Timeout(_) => {
+            SyncError::transient(error.to_string())
+        }
+    }
+}
+
+/// Register the IMAP connector in the registry
+pub fn register_imap_connector(registry: &mut Registry, connector: Arc<ImapConnector>) {
+    let metadata = ProviderMetadata::new(
+        IMAP_PROVIDER_SLUG.to_string(),
+        AuthType::Basic,
+        vec![],
+        false, // webhooks not supported
+    )
+    .with_category(ProviderCategory::Mail)
+    .with_description("Sync mailboxes from any IMAP server")
+    .with_icon_url("https://cdn.simpleicons.org/maildotru")
+    .with_docs_url("https://www.rfc-editor.org/rfc/rfc3501")
+    .with_backfill(true)
+    .with_signal_kinds(&[SignalKind::EmailReceived]);
+
+    registry.register(connector, metadata);
+}
+
+fn build_email_signal(
+    connection: &Connection,
+    mailbox: &str,
+    uid: u32,
+    email: &InboundEmail,
+    stable_id: &str,
+) -> Signal {
+    let received_at = DateTime::from(Utc::now());
+    let occurred_at = email.date.unwrap_or_else(Utc::now);
+    let kind = SignalKind::EmailReceived;
+
+    Signal {
+        id: Uuid::new_v4(),
+        tenant_id: connection.tenant_id,
+        provider_slug: IMAP_PROVIDER_SLUG.to_string(),
+        connection_id: connection.id,
+        kind: kind.as_str().to_string(),
+        occurred_at: occurred_at.into(),
+        received_at,
+        payload: serde_json::json!({
+            "message_id": stable_id,
+            "subject": email.subject,
+            "from": email.from,
+            "to": email.to,
+            "cc": email.cc,
+            "has_attachments": !email.attachment_names.is_empty(),
+            "attachment_names": email.attachment_names,
+            "text": email.text,
+            "mailbox": mailbox,
+            "uid": uid,
+            "source": email.source,
+            "occurred_at": occurred_at.to_rfc3339(),
+        }),
+        dedupe_key: Some(format!("{}:{}:{}", IMAP_PROVIDER_SLUG, kind, stable_id)),
+        created_at: received_at,
+        updated_at: received_at,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mail::MailSpamRuntimeConfig;
+    use crate::mail::default::DefaultMailSpamFilter;
+    use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+    use tokio::net::TcpListener;
+
+    const RENEWAL: &str = "From: Ana <ana@partner.example>\r\nTo: me@example.com\r\n\
+        Subject: Renewal\r\nMessage-ID: <renewal@partner.example>\r\n\r\nLet's renew.\r\n";
+    const JUNK: &str = "From: deals@promo.example\r\nSubject: Offer\r\n\r\nBuy now\r\n";
+
+    /// Scripted server holding UIDs 10 (clean) and 11 (flagged `$Junk`)
+    async fn serve(listener: TcpListener) {
+        loop {
+            let (socket, _) = listener.accept().await.unwrap();
+            tokio::spawn(async move {
+                let (reader, mut writer) = socket.into_split();
+                let mut lines = BufReader::new(reader).lines();
+                writer.write_all(b"* OK IMAP4rev1 ready\r\n").await.unwrap();
+                while let Ok(Some(line)) = lines.next_line().await {
+                    let (tag, command) = line.split_once(' ').unwrap();
+                    let reply = if command.starts_with("LOGIN") {
+                        assert_eq!(command, "LOGIN \"me@example.com\" \"app-password\"");
+                        String::new()
+                    } else if command.starts_with("EXAMINE") {
+                        "* 2 EXISTS\r\n* OK [UIDVALIDITY 7] UIDs valid\r\n* OK [UIDNEXT 12] next\r\n"
+                            .to_string()
+                    } else if command.starts_with("UID SEARCH SINCE") {
+                        "* SEARCH 10 11\r\n".to_string()
+                    } else if command.starts_with("UID SEARCH UID") {
+                        "* SEARCH 11\r\n".to_string()
+                    } else if command.starts_with("UID FETCH") {
+                        format!(
+                            "* 1 FETCH (UID 10 FLAGS (\\Seen) BODY[]<0> {{{}}}\r\n{})\r\n\
+                             * 2 FETCH (UID 11 FLAGS ($Junk) BODY[]<0> {{{}}}\r\n{})\r\n",
+                            RENEWAL.len(),
+                            RENEWAL,
+                            JUNK.len(),
+                            JUNK
+                        )
+                    } else {
+                        "* BYE logging out\r\n".to_string()
+                    };
+                    writer
+                        .write_all(format!("{}{} OK done\r\n", reply, tag).as_bytes())
+                        .await
+                        .unwrap();
+                }
+            });
+        }
+    }
+
+    #[tokio::test]
+    async fn test_imap_sync_reads_new_messages_and_tracks_uidvalidity() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(serve(listener));
+
+        let now = DateTime::from(Utc::now());
+        let connection = Connection {
+            id: Uuid::new_v4(),
+            tenant_id: Uuid::new_v4(),
+            provider_slug: IMAP_PROVIDER_SLUG.to_string(),
+            external_id: "me@example.com@127.0.0.1".to_string(),
+            status: "active".to_string(),
+            display_name: None,
+            access_token_ciphertext: None,
+            refresh_token_ciphertext: None,
+            access_token: Some("app-password".to_string()),
+            refresh_token: None,
+            expires_at: None,
+            scopes: None,
+            metadata: Some(serde_json::json!({
+                "host": "127.0.0.1",
+                "port": port,
+                "username": "me@example.com",
+                "tls": false
+            })),
+            created_at: now,
+            updated_at: now,
+        };
+        let connector = ImapConnector::new(Arc::new(DefaultMailSpamFilter::new(
+            MailSpamRuntimeConfig::default(),
+        )));
+        let sync = |cursor: Option<Cursor>| SyncParams {
+            connection: connection.clone(),
+            cursor,
+            cancellation: Default::default(),
+        };
+
+        let first = connector.sync(sync(None)).await.unwrap();
+        assert_eq!(first.signals.len(), 1, "the $Junk message is filtered");
+        let signal = &first.signals[0];
+        assert_eq!(signal.kind, "email_received");
+        assert_eq!(signal.payload["subject"], "Renewal");
+        assert_eq!(signal.payload["mailbox"], "INBOX");
+        assert_eq!(signal.payload["uid"], 10);
+        assert_eq!(
+            signal.dedupe_key.as_deref(),
+            Some("imap:email_received:renewal@partner.example")
+        );
+        let cursor = first.next_cursor.unwrap();
+        assert_eq!(
+            cursor.window("INBOX").unwrap().as_json(),
+            &serde_json::json!({"uid_validity": 7, "last_uid": 11})
+        );
+
+        // Nothing past the last UID; the server's `n:*` answer is ignored
+        let second = connector.sync(sync(Some(cursor))).await.unwrap();
+        assert!(second.signals.is_empty());
+
+        // A new UIDVALIDITY discards the stored position
+        let stale = Cursor::from_windows({
+            let mut windows = CursorWindows::new();
+            windows.set(
+                "INBOX",
+                MailboxWindow {
+                    uid_validity: 3,
+                    last_uid: 500,
+                }
+                .to_cursor(),
+            );
+            windows
+        });
+        let reset = connector.sync(sync(Some(stale))).await.unwrap();
+        assert_eq!(reset.signals.len(), 1);
+    }
+}
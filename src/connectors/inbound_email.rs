@@ -0,0 +1,235 @@
+//! Inbound email connector implementation
+//!
+//! Webhook-only connector for mail forwarded to `POST /ingest/email` by an
+//! SMTP bridge such as Amazon SES receipt rules or SendGrid Inbound Parse. The
+//! handler parses the message into an [`InboundEmail`] and enqueues it; this
+//! connector runs it through the shared
+//! [`MailSpamFilter`](crate::mail::MailSpamFilter) and emits an
+//! `email_received` signal, so tenants without Gmail or Zoho Mail OAuth can
+//! still feed mail into the pipeline.
+
+use anyhow::anyhow;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+use tracing::debug;
+use url::Url;
+use uuid::Uuid;
+
+use crate::connectors::{
+    AuthType, Connector, ProviderCategory, ProviderMetadata, Registry,
+    trait_::{AuthorizeParams, ExchangeTokenParams, SyncParams, SyncResult, WebhookParams},
+};
+use crate::mail::MailSpamFilter;
+use crate::mail::inbound::InboundEmail;
+use crate::mail::integration::should_create_signal;
+use crate::models::{connection::Model as Connection, signal::Model as Signal};
+use crate::normalization::SignalKind;
+
+pub use crate::mail::inbound::INBOUND_EMAIL_PROVIDER_SLUG;
+
+/// Inbound email connector
+pub struct InboundEmailConnector {
+    spam_filter: Arc<dyn MailSpamFilter>,
+}
+
+impl InboundEmailConnector {
+    /// Create a new inbound email connector
+    pub fn new(spam_filter: Arc<dyn MailSpamFilter>) -> Self {
+        Self { spam_filter }
+    }
+}
+
+#[async_trait]
+impl Connector for InboundEmailConnector {
+    async fn authorize(
+        &self,
+        _params: AuthorizeParams,
+    ) -> Result<Url, Box<dyn std::error::Error + Send + Sync>> {
+        Err(anyhow!("OAuth authorization is not supported for inbound email").into())
+    }
+
+    async fn exchange_token(
+        &self,
+        _params: ExchangeTokenParams,
+    ) -> Result<Connection, Box<dyn std::error::Error + Send + Sync>> {
+        Err(anyhow!("Token exchange is not supported for inbound email").into())
+    }
+
+    async fn refresh_token(
+        &self,
+        _connection: Connection,
+    ) -> Result<Connection, Box<dyn std::error::Error + Send + Sync>> {
+        Err(anyhow!("Token refresh is not supported for inbound email").into())
+    }
+
+    async fn sync(
+        &self,
+        _params: SyncParams,
+    ) -> Result<SyncResult, Box<dyn std::error::Error + Send + Sync>> {
+        // Mail is pushed to the ingest endpoint; there is nothing to poll
+        Ok(SyncResult {
+            signals: vec![],
+            next_cursor: None,
+            has_more: false,
+        })
+    }
+
+    async fn handle_webhook(
+        &self,
+        params: WebhookParams,
+    ) -> Result<Vec<Signal>, Box<dyn std::error::Error + Send + Sync>> {
+        let email: InboundEmail = serde_json::from_value(params.payload)
+            .map_err(|e| anyhow!("Invalid inbound email payload: {}", e))?;
+        let stable_id = email.stable_id();
+
+        // The executor assigns the real connection id after this returns
+        let connection_id = Uuid::nil();
+        if !should_create_signal(
+            &self.spam_filter,
+            &email.spam_metadata(),
+            INBOUND_EMAIL_PROVIDER_SLUG,
+            connection_id,
+            &stable_id,
+        ) {
+            return Ok(vec![]);
+        }
+
+        debug!(
+            tenant_id = %params.tenant_id,
+            message_id = %stable_id,
+            source = %email.source,
+            "Inbound email mapped to signal"
+        );
+        Ok(vec![build_email_signal(
+            params.tenant_id,
+            &email,
+            &stable_id,
+        )])
+    }
+}
+
+/// Initialize the inbound email connector in the registry
+pub fn register_inbound_email_connector(
+    registry: &mut Registry,
+    connector: Arc<InboundEmailConnector>,
+) {
+    let metadata = ProviderMetadata::new(
+        INBOUND_EMAIL_PROVIDER_SLUG.to_string(),
+        AuthType::Custom("webhook".to_string()),
+        vec![],
+        true,
+    )
+    .with_category(ProviderCategory::Mail)
+    .with_description("Receive forwarded mail from SES, SendGrid or any SMTP bridge")
+    .with_icon_url("https://cdn.simpleicons.org/maildotru")
+    .with_docs_url("https://docs.aws.amazon.com/ses/latest/dg/receiving-email.html")
+    .with_backfill(false)
+    .with_signal_kinds(&[SignalKind::EmailReceived]);
+
+    registry.register(connector, metadata);
+}
+
+fn build_email_signal(tenant_id: Uuid, email: &InboundEmail, stable_id: &str) -> Signal {
+    let received_at = DateTime::from(Utc::now());
+    let occurred_at = email.date.unwrap_or_else(Utc::now);
+    let kind = SignalKind::EmailReceived;
+
+    Signal {
+        id: Uuid::new_v4(),
+        tenant_id,
+        provider_slug: INBOUND_EMAIL_PROVIDER_SLUG.to_string(),
+        connection_id: Uuid::nil(),
+        kind: kind.as_str().to_string(),
+        occurred_at: occurred_at.into(),
+        received_at,
+        payload: serde_json::json!({
+            "message_id": stable_id,
+            "subject": email.subject,
+            "from": email.from,
+            "to": email.to,
+            "cc": email.cc,
+            "has_attachments": !email.attachment_names.is_empty(),
+            "attachment_names": email.attachment_names,
+            "text": email.text,
+            "source": email.source,
+            "occurred_at": occurred_at.to_rfc3339(),
+        }),
+        dedupe_key: Some(format!(
+            "{}:{}:{}",
+            INBOUND_EMAIL_PROVIDER_SLUG, kind, stable_id
+        )),
+        created_at: received_at,
+        updated_at: received_at,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mail::MailSpamRuntimeConfig;
+    use crate::mail::default::DefaultMailSpamFilter;
+
+    fn connector() -> InboundEmailConnector {
+        InboundEmailConnector::new(Arc::new(DefaultMailSpamFilter::new(
+            MailSpamRuntimeConfig::default().with_denylist(vec!["@spam.example".to_string()]),
+        )))
+    }
+
+    fn params(email: &InboundEmail) -> WebhookParams {
+        WebhookParams {
+            payload: serde_json::to_value(email).unwrap(),
+            tenant_id: Uuid::new_v4(),
+            db: None,
+            auth_header: None,
+        }
+    }
+
+    fn email(from: &str) -> InboundEmail {
+        InboundEmail {
+            message_id: Some("launch@example.com".to_string()),
+            from: Some(from.to_string()),
+            to: vec!["team@acme.test".to_string()],
+            subject: Some("Launch plan".to_string()),
+            text: Some("See attached".to_string()),
+            attachment_names: vec!["plan.xlsx".to_string()],
+            source: "sendgrid".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_handle_webhook_emits_email_received() {
+        let signals = connector()
+            .handle_webhook(params(&email("ada@example.com")))
+            .await
+            .unwrap();
+
+        assert_eq!(signals.len(), 1);
+        let signal = &signals[0];
+        assert_eq!(signal.kind, "email_received");
+        assert_eq!(signal.provider_slug, "inbound-email");
+        assert_eq!(
+            signal.dedupe_key.as_deref(),
+            Some("inbound-email:email_received:launch@example.com")
+        );
+        assert_eq!(signal.payload["from"], "ada@example.com");
+        assert_eq!(signal.payload["has_attachments"], true);
+        assert_eq!(signal.payload["source"], "sendgrid");
+    }
+
+    #[tokio::test]
+    async fn test_handle_webhook_drops_spam() {
+        let connector = connector();
+        let denied = connector
+            .handle_webhook(params(&email("offers@spam.example")))
+            .await
+            .unwrap();
+        assert!(denied.is_empty());
+
+        let mut labelled = email("ada@example.com");
+        labelled.labels.push("spam".to_string());
+        let labelled = connector.handle_webhook(params(&labelled)).await.unwrap();
+        assert!(labelled.is_empty());
+    }
+}
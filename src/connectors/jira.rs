@@ -23,8 +23,11 @@ fn secure_random_state() -> String {
 }
 
 use crate::connectors::{
-    AuthType, Connector, Cursor, ProviderMetadata, Registry,
-    trait_::{AuthorizeParams, ExchangeTokenParams, SyncParams, SyncResult, WebhookParams},
+    AuthType, Connector, Cursor, ProviderCategory, ProviderMetadata, Registry,
+    trait_::{
+        AuthorizeParams, CollectingSink, ExchangeTokenParams, SyncPage, SyncParams, SyncResult,
+        SyncSink, WebhookParams,
+    },
 };
 use crate::models::{connection::Model as Connection, signal::Model as Signal};
 use crate::normalization::{SignalKind, normalize_jira_webhook_kind};
@@ -51,7 +54,7 @@ impl JiraConnector {
             client_secret,
             oauth_base,
             api_base,
-            http_client: Client::new(),
+            http_client: crate::egress::client(),
         }
     }
 
@@ -99,8 +102,10 @@ impl JiraConnector {
             external_id: "jira-stub-account".to_string(),
             status: "active".to_string(),
             display_name: Some("Jira Stub Connection".to_string()),
-            access_token_ciphertext: Some(b"mock_token".to_vec()),
-            refresh_token_ciphertext: Some(b"mock_refresh_token".to_vec()),
+            access_token_ciphertext: None,
+            refresh_token_ciphertext: None,
+            access_token: Some("mock_token".to_string()),
+            refresh_token: Some("mock_refresh_token".to_string()),
             expires_at: Some(now + chrono::Duration::hours(1)),
             scopes: Some(serde_json::json!(["read:jira-work", "read:jira-user"])),
             metadata: Some(serde_json::json!({
@@ -444,11 +449,10 @@ impl Connector for JiraConnector {
             external_id,
             status: "active".to_string(),
             display_name,
-            access_token_ciphertext: Some(token_response.access_token.as_bytes().to_vec()),
-            refresh_token_ciphertext: token_response
-                .refresh_token
-                .as_ref()
-                .map(|token| token.as_bytes().to_vec()),
+            access_token_ciphertext: None,
+            refresh_token_ciphertext: None,
+            access_token: Some(token_response.access_token.clone()),
+            refresh_token: token_response.refresh_token.clone(),
             expires_at,
             scopes: scopes_value,
             metadata: Some(metadata),
@@ -474,16 +478,13 @@ impl Connector for JiraConnector {
             return Ok(refreshed);
         }
 
-        let refresh_token_bytes = connection.refresh_token_ciphertext.clone().ok_or_else(|| {
+        let refresh_token = connection.refresh_token.clone().ok_or_else(|| {
             anyhow!(
                 "Missing Jira refresh token for connection {}",
                 connection.id
             )
         })?;
 
-        let refresh_token = String::from_utf8(refresh_token_bytes)
-            .map_err(|_| anyhow!("Jira refresh token was not valid UTF-8"))?;
-
         if refresh_token.trim().is_empty() {
             return Err(anyhow!("Jira refresh token is empty").into());
         }
@@ -571,12 +572,13 @@ impl Connector for JiraConnector {
             external_id: connection.external_id,
             status: connection.status,
             display_name: connection.display_name,
-            access_token_ciphertext: Some(token_response.access_token.as_bytes().to_vec()),
-            refresh_token_ciphertext: token_response
+            access_token_ciphertext: None,
+            refresh_token_ciphertext: None,
+            access_token: Some(token_response.access_token.clone()),
+            refresh_token: token_response
                 .refresh_token
-                .as_ref()
-                .map(|token| token.as_bytes().to_vec())
-                .or(connection.refresh_token_ciphertext),
+                .clone()
+                .or(connection.refresh_token),
             expires_at,
             scopes: scopes_value.or(connection.scopes),
             metadata: Some(metadata),
@@ -588,15 +590,24 @@ impl Connector for JiraConnector {
     async fn sync(
         &self,
         params: SyncParams,
+    ) -> Result<SyncResult, Box<dyn std::error::Error + Send + Sync>> {
+        let mut sink = CollectingSink::default();
+        let result = self.sync_streaming(params, &mut sink).await?;
+        Ok(sink.into_result(result))
+    }
+
+    async fn sync_streaming(
+        &self,
+        params: SyncParams,
+        sink: &mut dyn SyncSink,
     ) -> Result<SyncResult, Box<dyn std::error::Error + Send + Sync>> {
         // Test-mode fast path: if running in test profile, or explicit flag, or using the known mock token, return a stubbed single signal
         if Self::is_test_mode()
             || params
                 .connection
-                .access_token_ciphertext
-                .as_ref()
-                .map(|b| b == b"mock_token")
-                .unwrap_or(false)
+                .access_token
+                .as_deref()
+                .is_some_and(|token| token == "mock_token")
         {
             let now_utc = Utc::now();
             let updated_str = now_utc.to_rfc3339();
@@ -649,15 +660,9 @@ impl Connector for JiraConnector {
             "Starting Jira incremental sync"
         );
 
-        // Extract access token (MVP stores token bytes as plaintext)
-        let access_token = params
-            .connection
-            .access_token_ciphertext
-            .clone()
-            .ok_or_else(|| {
-                crate::connectors::trait_::SyncError::unauthorized("Missing access token")
-            })
-            .map(|bytes| String::from_utf8_lossy(&bytes).to_string())?;
+        let access_token = params.connection.access_token.clone().ok_or_else(|| {
+            crate::connectors::trait_::SyncError::unauthorized("Missing access token")
+        })?;
 
         // Determine API base and resource (cloud/site) from connection metadata or discovery
         let api_base = self.api_base.clone();
@@ -793,8 +798,9 @@ impl Connector for JiraConnector {
         // Pagination parameters
         let max_results = 50u32;
         let mut start_at = 0u32;
-        let mut all_signals: Vec<Signal> = Vec::new();
+        let mut emitted = 0usize;
         let mut last_updated: Option<DateTime<Utc>> = None;
+        let mut cancelled = false;
         let now = DateTime::from(Utc::now());
 
         loop {
@@ -855,6 +861,7 @@ impl Connector for JiraConnector {
                 .unwrap_or_default();
 
             // Map to Signals
+            let mut page_signals: Vec<Signal> = Vec::with_capacity(issues.len());
             for issue in &issues {
                 let fields = issue.get("fields").unwrap_or(&serde_json::Value::Null);
                 let updated_str = if let Some(s) = fields.get("updated").and_then(|v| v.as_str()) {
@@ -877,7 +884,7 @@ impl Connector for JiraConnector {
                 let signal_kind = SignalKind::IssueUpdated;
                 let dedupe = generate_dedupe_key(&payload, signal_kind.as_str());
 
-                all_signals.push(Signal {
+                page_signals.push(Signal {
                     id: Uuid::new_v4(),
                     tenant_id: params.connection.tenant_id,
                     provider_slug: "jira".to_string(),
@@ -892,6 +899,14 @@ impl Connector for JiraConnector {
                 });
             }
 
+            // Hand the page over so it is persisted before the next one is fetched
+            emitted += page_signals.len();
+            sink.emit(SyncPage {
+                signals: page_signals,
+                next_cursor: last_updated.map(|dt| Cursor::from_string(dt.to_rfc3339())),
+            })
+            .await?;
+
             // Pagination advancement
             let fetched = issues.len() as u32;
             if fetched < max_results {
@@ -900,17 +915,24 @@ impl Connector for JiraConnector {
             start_at += max_results;
 
             // Safety limit to avoid runaway loops
-            if all_signals.len() >= 1000 {
+            if emitted >= 1000 {
+                break;
+            }
+
+            // Stop between pages when the job is cancelled; the cursor resumes here
+            if params.cancellation.is_cancelled() {
+                cancelled = true;
                 break;
             }
         }
 
         // Compute next cursor as greatest updated timestamp processed
         let next_cursor = last_updated.map(|dt| Cursor::from_string(dt.to_rfc3339()));
-        let has_more = false; // We consumed all pages for this window
+        let has_more = cancelled; // Otherwise we consumed all pages for this window
 
+        // Every page was emitted; only the final cursor and has_more remain
         let result = SyncResult {
-            signals: all_signals,
+            signals: Vec::new(),
             next_cursor,
             has_more,
         };
@@ -918,7 +940,7 @@ impl Connector for JiraConnector {
         debug!(
             tenant_id = %params.connection.tenant_id,
             connection_id = %params.connection.id,
-            signals_generated = %result.signals.len(),
+            signals_generated = %emitted,
             has_more = %result.has_more,
             "Jira incremental sync completed"
         );
@@ -987,7 +1009,13 @@ pub fn register_jira_connector(registry: &mut Registry, connector: Arc<JiraConne
         AuthType::OAuth2,
         vec!["read:jira-work".to_string(), "read:jira-user".to_string()],
         true, // webhooks supported
-    );
+    )
+    .with_category(ProviderCategory::Code)
+    .with_description("Sync issues and project activity from Jira Cloud")
+    .with_icon_url("https://cdn.simpleicons.org/jira")
+    .with_docs_url("https://developer.atlassian.com/cloud/jira/platform/")
+    .with_backfill(false)
+    .with_signal_kinds(&[SignalKind::IssueCreated, SignalKind::IssueUpdated]);
 
     registry.register(connector, metadata);
 }
@@ -1099,7 +1127,6 @@ mod tests {
     };
     use uuid::Uuid;
 
-    
     #[tokio::test]
     async fn test_jira_authorize_url_shape() {
         let connector = JiraConnector::new(
@@ -1114,6 +1141,7 @@ mod tests {
             tenant_id,
             redirect_uri: Some("https://example.com/callback".to_string()),
             state: Some("test_state_123".to_string()),
+            code_challenge: None,
         };
 
         let result = connector.authorize(params).await.unwrap();
@@ -1274,8 +1302,10 @@ mod tests {
             external_id: "jira-user-123".to_string(),
             status: "active".to_string(),
             display_name: Some("Jira Connection".to_string()),
-            access_token_ciphertext: Some(b"mock_token".to_vec()),
+            access_token_ciphertext: None,
             refresh_token_ciphertext: None,
+            access_token: Some("mock_token".to_string()),
+            refresh_token: None,
             expires_at: None,
             scopes: None,
             metadata: None,
@@ -1287,6 +1317,7 @@ mod tests {
         let params = SyncParams {
             connection: connection.clone(),
             cursor: None,
+            cancellation: Default::default(),
         };
 
         let result = connector.sync(params).await.unwrap();
@@ -1309,6 +1340,7 @@ mod tests {
         let params = SyncParams {
             connection,
             cursor: Some(cursor),
+            cancellation: Default::default(),
         };
 
         let result = connector.sync(params).await.unwrap();
@@ -1324,18 +1356,97 @@ mod tests {
         assert!(payload.get("occurred_at").is_some());
     }
 
+    #[tokio::test]
+    async fn test_jira_sync_stops_between_pages_when_cancelled() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        let issues: Vec<serde_json::Value> = (0..50)
+            .map(|i| {
+                serde_json::json!({
+                    "id": format!("{}", 1000 + i),
+                    "key": format!("TEST-{}", i),
+                    "fields": {
+                        "updated": "2024-06-01T12:00:00+00:00",
+                        "project": { "key": "TEST" },
+                        "summary": "Issue"
+                    }
+                })
+            })
+            .collect();
+        // A full page means more remain; only one page may be fetched
+        Mock::given(method("GET"))
+            .and(path("/rest/api/3/search"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "issues": issues
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let connector = JiraConnector::new(
+            "test-client-id".to_string(),
+            "test-client-secret".to_string(),
+            "https://auth.atlassian.com".to_string(),
+            server.uri(),
+        );
+        let now = chrono::Utc::now().fixed_offset();
+        let connection = Connection {
+            id: Uuid::new_v4(),
+            tenant_id: Uuid::new_v4(),
+            provider_slug: "jira".to_string(),
+            external_id: "jira-user-123".to_string(),
+            status: "active".to_string(),
+            display_name: None,
+            access_token_ciphertext: None,
+            refresh_token_ciphertext: None,
+            access_token: Some("live_token".to_string()),
+            refresh_token: None,
+            expires_at: None,
+            scopes: None,
+            metadata: Some(serde_json::json!({ "site_url": server.uri() })),
+            created_at: now,
+            updated_at: now,
+        };
+        let cancellation = tokio_util::sync::CancellationToken::new();
+        cancellation.cancel();
+
+        let result = connector
+            .sync(SyncParams {
+                connection,
+                cursor: None,
+                cancellation,
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(result.signals.len(), 50);
+        assert!(result.has_more);
+        assert_eq!(
+            result.next_cursor.unwrap().as_json(),
+            &serde_json::json!("2024-06-01T12:00:00+00:00")
+        );
+    }
+
     #[tokio::test]
     async fn test_jira_exchange_token_stub() {
         // Temporarily set the global env var for this test
         let original_env = std::env::var("JIRA_TEST_MODE").ok();
-        unsafe { std::env::set_var("JIRA_TEST_MODE", "1"); }
+        unsafe {
+            std::env::set_var("JIRA_TEST_MODE", "1");
+        }
 
         // Ensure cleanup happens even if test panics
         let _cleanup_guard = scopeguard::guard((), |_| {
             if let Some(original) = original_env {
-                unsafe { std::env::set_var("JIRA_TEST_MODE", original); }
+                unsafe {
+                    std::env::set_var("JIRA_TEST_MODE", original);
+                }
             } else {
-                unsafe { std::env::remove_var("JIRA_TEST_MODE"); }
+                unsafe {
+                    std::env::remove_var("JIRA_TEST_MODE");
+                }
             }
         });
         let connector = JiraConnector::new(
@@ -1350,6 +1461,7 @@ mod tests {
             code: "test_authorization_code".to_string(),
             redirect_uri: Some("https://example.com/callback".to_string()),
             tenant_id,
+            code_verifier: None,
         };
 
         let connection = connector.exchange_token(params).await.unwrap();
@@ -1357,8 +1469,8 @@ mod tests {
         assert_eq!(connection.provider_slug, "jira");
         assert_eq!(connection.tenant_id, tenant_id);
         assert_eq!(connection.status, "active");
-        assert!(connection.access_token_ciphertext.is_some());
-        assert!(connection.refresh_token_ciphertext.is_some());
+        assert!(connection.access_token.is_some());
+        assert!(connection.refresh_token.is_some());
         assert!(connection.expires_at.is_some());
         assert!(connection.scopes.is_some());
     }
@@ -1367,14 +1479,20 @@ mod tests {
     async fn test_jira_refresh_token_stub() {
         // Temporarily set the global env var for this test
         let original_env = std::env::var("JIRA_TEST_MODE").ok();
-        unsafe { std::env::set_var("JIRA_TEST_MODE", "1"); }
+        unsafe {
+            std::env::set_var("JIRA_TEST_MODE", "1");
+        }
 
         // Ensure cleanup happens even if test panics
         let _cleanup_guard = scopeguard::guard((), |_| {
             if let Some(original) = original_env {
-                unsafe { std::env::set_var("JIRA_TEST_MODE", original); }
+                unsafe {
+                    std::env::set_var("JIRA_TEST_MODE", original);
+                }
             } else {
-                unsafe { std::env::remove_var("JIRA_TEST_MODE"); }
+                unsafe {
+                    std::env::remove_var("JIRA_TEST_MODE");
+                }
             }
         });
 
@@ -1393,8 +1511,10 @@ mod tests {
             external_id: "jira-user-123".to_string(),
             status: "active".to_string(),
             display_name: Some("Jira Connection".to_string()),
-            access_token_ciphertext: Some(b"old_token".to_vec()),
-            refresh_token_ciphertext: Some(b"old_refresh".to_vec()),
+            access_token_ciphertext: None,
+            refresh_token_ciphertext: None,
+            access_token: Some("old_token".to_string()),
+            refresh_token: Some("old_refresh".to_string()),
             expires_at: Some(chrono::Utc::now().fixed_offset()),
             scopes: None,
             metadata: None,
@@ -1406,13 +1526,13 @@ mod tests {
 
         assert_eq!(refreshed.provider_slug, "jira");
         assert_eq!(refreshed.tenant_id, tenant_id);
-        assert!(refreshed.access_token_ciphertext.is_some());
-        assert!(refreshed.refresh_token_ciphertext.is_some());
+        assert!(refreshed.access_token.is_some());
+        assert!(refreshed.refresh_token.is_some());
         assert!(refreshed.expires_at.is_some());
 
         // Verify tokens were actually updated (new token should be different)
-        let old_token = b"old_token".to_vec();
-        let new_token = refreshed.access_token_ciphertext.unwrap();
+        let old_token = "old_token".to_string();
+        let new_token = refreshed.access_token.unwrap();
         assert_ne!(old_token, new_token);
     }
 }
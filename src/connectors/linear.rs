@@ -0,0 +1,906 @@
+//! Linear connector implementation
+//!
+//! Linear connector supporting the OAuth2 authorization code flow, incremental
+//! issue sync through the GraphQL API, and webhook ingestion. Webhook
+//! signatures are verified in [`crate::webhook_verification`] before payloads
+//! reach this connector.
+
+use anyhow::anyhow;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use reqwest::{Client, StatusCode};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tracing::{debug, info};
+use url::Url;
+use uuid::Uuid;
+
+use crate::connectors::{
+    AuthType, Connector, Cursor, ProviderCategory, ProviderMetadata, Registry,
+    trait_::{
+        AuthorizeParams, ExchangeTokenParams, SyncError, SyncParams, SyncResult, WebhookParams,
+    },
+};
+use crate::models::{connection::Model as Connection, signal::Model as Signal};
+use crate::normalization::{
+    SignalKind, normalize_linear_issue_kind, normalize_linear_webhook_kind,
+};
+
+/// Provider slug
+pub const LINEAR_PROVIDER_SLUG: &str = "linear";
+
+/// Scopes requested at authorization time
+pub const LINEAR_SCOPES: &[&str] = &["read"];
+
+/// Issues requested per GraphQL page
+const PAGE_SIZE: u32 = 50;
+
+/// GraphQL pages fetched in one sync; the rest continues on the next run
+const MAX_PAGES_PER_SYNC: u32 = 10;
+
+/// Fields read for every issue, shared by sync and webhook payloads
+const ISSUES_QUERY: &str = r#"
+query Issues($first: Int!, $after: String, $filter: IssueFilter) {
+  issues(first: $first, after: $after, filter: $filter, includeArchived: true) {
+    nodes {
+      id
+      identifier
+      title
+      url
+      priority
+      createdAt
+      updatedAt
+      completedAt
+      canceledAt
+      state { name type }
+      team { key }
+      assignee { id name }
+    }
+    pageInfo { hasNextPage endCursor }
+  }
+}
+"#;
+
+const VIEWER_QUERY: &str = "query { viewer { id name } organization { id name urlKey } }";
+
+/// Linear connector
+pub struct LinearConnector {
+    client_id: String,
+    client_secret: String,
+    oauth_base: String,
+    api_base: String,
+    http_client: Client,
+}
+
+/// Sync position stored in the cursor
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+struct IssueWindow {
+    /// Issues last updated before this time have been synced
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    since: Option<DateTime<Utc>>,
+    /// `endCursor` of an unfinished walk
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    after: Option<String>,
+    /// Newest update seen by the unfinished walk; becomes `since` once it completes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    high_water: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct LinearTokenResponse {
+    access_token: String,
+    #[serde(default)]
+    refresh_token: Option<String>,
+    #[serde(default)]
+    expires_in: Option<i64>,
+    /// A space-separated string or an array, depending on the API version
+    #[serde(default)]
+    scope: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LinearPageInfo {
+    has_next_page: bool,
+    #[serde(default)]
+    end_cursor: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LinearIssues {
+    nodes: Vec<serde_json::Value>,
+    page_info: LinearPageInfo,
+}
+
+#[derive(Debug, Deserialize)]
+struct LinearIssuesData {
+    issues: LinearIssues,
+}
+
+impl LinearConnector {
+    /// Create a new Linear connector with configuration
+    pub fn new(
+        client_id: String,
+        client_secret: String,
+        oauth_base: String,
+        api_base: String,
+    ) -> Self {
+        Self {
+            client_id,
+            client_secret,
+            oauth_base,
+            api_base,
+            http_client: crate::egress::client(),
+        }
+    }
+
+    fn default_redirect_uri() -> String {
+        match std::env::var("POBLYSH_PROFILE").as_deref() {
+            Ok("local") | Ok("test") | Err(_) => "http://localhost:3000/callback".to_string(),
+            Ok(_) => "https://app.poblysh.com/callback".to_string(),
+        }
+    }
+
+    fn access_token(connection: &Connection) -> Result<String, SyncError> {
+        connection
+            .access_token
+            .clone()
+            .filter(|token| !token.is_empty())
+            .ok_or_else(|| SyncError::unauthorized("Missing Linear access token"))
+    }
+
+    /// Call `/oauth/token` with the given grant parameters
+    async fn oauth_token(
+        &self,
+        grant: &[(&str, &str)],
+    ) -> Result<LinearTokenResponse, anyhow::Error> {
+        let mut form = vec![
+            ("client_id", self.client_id.as_str()),
+            ("client_secret", self.client_secret.as_str()),
+        ];
+        form.extend_from_slice(grant);
+
+        let response = self
+            .http_client
+            .post(format!(
+                "{}/oauth/token",
+                self.api_base.trim_end_matches('/')
+            ))
+            .form(&form)
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let body: serde_json::Value = response.json().await.unwrap_or_default();
+            return Err(anyhow!(
+                "Linear token endpoint returned {}: {}",
+                status,
+                body.get("error")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("unknown_error")
+            ));
+        }
+        Ok(response.json().await?)
+    }
+
+    /// Run a GraphQL query, mapping HTTP and GraphQL failures to sync errors
+    async fn graphql<T: for<'de> Deserialize<'de>>(
+        &self,
+        access_token: &str,
+        query: &str,
+        variables: serde_json::Value,
+    ) -> Result<T, SyncError> {
+        let response = self
+            .http_client
+            .post(format!("{}/graphql", self.api_base.trim_end_matches('/')))
+            .bearer_auth(access_token)
+            .json(&serde_json::json!({ "query": query, "variables": variables }))
+            .send()
+            .await
+            .map_err(|e| SyncError::transient(format!("Linear GraphQL request failed: {}", e)))?;
+
+        let status = response.status();
+        if status == StatusCode::TOO_MANY_REQUESTS {
+            let retry_after = response
+                .headers()
+                .get("Retry-After")
+                .and_then(|h| h.to_str().ok())
+                .and_then(|s| s.parse::<u64>().ok());
+            return Err(SyncError::rate_limited(retry_after));
+        }
+        if status == StatusCode::UNAUTHORIZED {
+            return Err(SyncError::unauthorized(format!(
+                "Linear GraphQL request failed: {}",
+                status
+            )));
+        }
+        if status.is_server_error() {
+            return Err(SyncError::transient(format!(
+                "Linear GraphQL request failed: {}",
+                status
+            )));
+        }
+
+        // Linear reports rate limiting and auth failures as GraphQL errors, often with HTTP 400
+        let body: serde_json::Value = response.json().await.map_err(|e| {
+            SyncError::transient(format!("Linear GraphQL returned bad JSON: {}", e))
+        })?;
+        if let Some(error) = body
+            .get("errors")
+            .and_then(|v| v.as_array())
+            .and_then(|errors| errors.first())
+        {
+            let message = error
+                .get("message")
+                .and_then(|v| v.as_str())
+                .unwrap_or("unknown error");
+            return Err(
+                match error.pointer("/extensions/code").and_then(|v| v.as_str()) {
+                    Some("RATELIMITED") => SyncError::rate_limited(None),
+                    Some("AUTHENTICATION_ERROR") => {
+                        SyncError::unauthorized(format!("Linear GraphQL: {}", message))
+                    }
+                    _ => SyncError::permanent(format!("Linear GraphQL: {}", message)),
+                },
+            );
+        }
+        if !status.is_success() {
+            return Err(SyncError::permanent(format!(
+                "Linear GraphQL request failed: {}",
+                status
+            )));
+        }
+
+        serde_json::from_value(body.get("data").cloned().unwrap_or_default())
+            .map_err(|e| SyncError::permanent(format!("Unexpected Linear GraphQL response: {}", e)))
+    }
+}
+
+#[async_trait]
+impl Connector for LinearConnector {
+    async fn authorize(
+        &self,
+        params: AuthorizeParams,
+    ) -> Result<Url, Box<dyn std::error::Error + Send + Sync>> {
+        info!(
+            tenant_id = %params.tenant_id,
+            "Generating Linear OAuth authorization URL"
+        );
+
+        let mut url = Url::parse(&format!(
+            "{}/oauth/authorize",
+            self.oauth_base.trim_end_matches('/')
+        ))?;
+        let redirect_uri = params
+            .redirect_uri
+            .unwrap_or_else(Self::default_redirect_uri);
+        let state = params
+            .state
+            .filter(|s| !s.is_empty())
+            .unwrap_or_else(|| Uuid::new_v4().to_string());
+        url.query_pairs_mut()
+            .append_pair("client_id", &self.client_id)
+            .append_pair("response_type", "code")
+            .append_pair("scope", &LINEAR_SCOPES.join(","))
+            .append_pair("redirect_uri", &redirect_uri)
+            .append_pair("state", &state);
+
+        debug!(
+            tenant_id = %params.tenant_id,
+            authorize_url = %url,
+            "Generated Linear OAuth authorization URL"
+        );
+        Ok(url)
+    }
+
+    async fn exchange_token(
+        &self,
+        params: ExchangeTokenParams,
+    ) -> Result<Connection, Box<dyn std::error::Error + Send + Sync>> {
+        info!(
+            tenant_id = %params.tenant_id,
+            "Exchanging Linear authorization code for tokens"
+        );
+
+        let redirect_uri = params
+            .redirect_uri
+            .unwrap_or_else(Self::default_redirect_uri);
+        let token = self
+            .oauth_token(&[
+                ("grant_type", "authorization_code"),
+                ("code", params.code.as_str()),
+                ("redirect_uri", redirect_uri.as_str()),
+            ])
+            .await?;
+
+        // The token response does not identify the workspace
+        let viewer: serde_json::Value = self
+            .graphql(&token.access_token, VIEWER_QUERY, serde_json::json!({}))
+            .await?;
+        let organization_id = viewer
+            .pointer("/organization/id")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow!("Linear viewer response missing organization"))?
+            .to_string();
+        let organization_name = viewer
+            .pointer("/organization/name")
+            .and_then(|v| v.as_str())
+            .map(str::to_string);
+        let now = DateTime::from(Utc::now());
+
+        Ok(Connection {
+            id: Uuid::new_v4(),
+            tenant_id: params.tenant_id,
+            provider_slug: LINEAR_PROVIDER_SLUG.to_string(),
+            external_id: organization_id.clone(),
+            status: "active".to_string(),
+            display_name: organization_name.clone(),
+            access_token_ciphertext: None,
+            refresh_token_ciphertext: None,
+            access_token: Some(token.access_token),
+            refresh_token: token.refresh_token,
+            expires_at: token
+                .expires_in
+                .map(|seconds| now + chrono::Duration::seconds(seconds)),
+            scopes: token.scope.map(|scope| match scope {
+                serde_json::Value::String(scope) => serde_json::Value::Array(
+                    scope
+                        .split([' ', ','])
+                        .filter(|s| !s.is_empty())
+                        .map(|s| serde_json::Value::String(s.to_string()))
+                        .collect(),
+                ),
+                other => other,
+            }),
+            metadata: Some(serde_json::json!({
+                "provider": LINEAR_PROVIDER_SLUG,
+                "organization": {
+                    "id": organization_id,
+                    "name": organization_name,
+                    "url_key": viewer.pointer("/organization/urlKey"),
+                },
+                "viewer_id": viewer.pointer("/viewer/id"),
+            })),
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    async fn refresh_token(
+        &self,
+        connection: Connection,
+    ) -> Result<Connection, Box<dyn std::error::Error + Send + Sync>> {
+        let Some(refresh_token) = connection
+            .refresh_token
+            .clone()
+            .filter(|token| !token.is_empty())
+        else {
+            return Err(SyncError::unauthorized("Linear connection has no refresh token").into());
+        };
+
+        info!(connection_id = %connection.id, "Refreshing Linear token");
+        let token = self
+            .oauth_token(&[
+                ("grant_type", "refresh_token"),
+                ("refresh_token", refresh_token.as_str()),
+            ])
+            .await?;
+        let now = DateTime::from(Utc::now());
+
+        Ok(Connection {
+            access_token: Some(token.access_token),
+            refresh_token: token.refresh_token.or(connection.refresh_token.clone()),
+            expires_at: token
+                .expires_in
+                .map(|seconds| now + chrono::Duration::seconds(seconds)),
+            updated_at: now,
+            ..connection
+        })
+    }
+
+    async fn sync(
+        &self,
+        params: SyncParams,
+    ) -> Result<SyncResult, Box<dyn std::error::Error + Send + Sync>> {
+        let connection = &params.connection;
+        info!(
+            tenant_id = %connection.tenant_id,
+            connection_id = %connection.id,
+            has_cursor = %params.cursor.is_some(),
+            "Starting Linear sync"
+        );
+
+        let access_token = Self::access_token(connection)?;
+        let mut window: IssueWindow = params
+            .cursor
+            .as_ref()
+            .and_then(|cursor| serde_json::from_value(cursor.as_json().clone()).ok())
+            .unwrap_or_default();
+        // The filter stays fixed for the whole walk so `after` remains valid across runs
+        let filter = window
+            .since
+            .map(|since| serde_json::json!({ "updatedAt": { "gte": since.to_rfc3339() } }));
+
+        let mut signals = Vec::new();
+        let mut has_more = true;
+        for _ in 0..MAX_PAGES_PER_SYNC {
+            let data: LinearIssuesData = self
+                .graphql(
+                    &access_token,
+                    ISSUES_QUERY,
+                    serde_json::json!({
+                        "first": PAGE_SIZE,
+                        "after": window.after,
+                        "filter": filter,
+                    }),
+                )
+                .await?;
+
+            for issue in &data.issues.nodes {
+                if let Some(updated) = timestamp(issue, "updatedAt")
+                    && window.high_water.is_none_or(|high| updated > high)
+                {
+                    window.high_water = Some(updated);
+                }
+                let kind = normalize_linear_issue_kind(issue, window.since);
+                signals.push(build_signal(
+                    connection.tenant_id,
+                    connection.id,
+                    kind,
+                    &connection.external_id,
+                    issue,
+                    None,
+                ));
+            }
+
+            let page_info = data.issues.page_info;
+            match page_info.end_cursor.filter(|_| page_info.has_next_page) {
+                Some(end_cursor) => window.after = Some(end_cursor),
+                None => {
+                    window.after = None;
+                    if let Some(high_water) = window.high_water.take() {
+                        window.since = Some(high_water);
+                    }
+                    has_more = false;
+                    break;
+                }
+            }
+        }
+
+        debug!(
+            connection_id = %connection.id,
+            signals = signals.len(),
+            has_more,
+            "Linear sync completed"
+        );
+
+        Ok(SyncResult {
+            signals,
+            next_cursor: Some(Cursor::from_json(serde_json::to_value(&window)?)),
+            has_more,
+        })
+    }
+
+    async fn handle_webhook(
+        &self,
+        params: WebhookParams,
+    ) -> Result<Vec<Signal>, Box<dyn std::error::Error + Send + Sync>> {
+        let Some(kind) = normalize_linear_webhook_kind(&params.payload) else {
+            debug!(
+                tenant_id = %params.tenant_id,
+                event_type = ?params.payload.get("type"),
+                action = ?params.payload.get("action"),
+                "Linear webhook ignored"
+            );
+            return Ok(vec![]);
+        };
+
+        let issue = params
+            .payload
+            .get("data")
+            .ok_or_else(|| anyhow!("Invalid Linear webhook payload: missing data"))?;
+        let organization_id = params
+            .payload
+            .get("organizationId")
+            .and_then(|v| v.as_str())
+            .unwrap_or_default();
+
+        info!(
+            tenant_id = %params.tenant_id,
+            signal_kind = %kind,
+            issue_id = ?issue.get("id"),
+            "Linear webhook mapped to signal"
+        );
+
+        Ok(vec![build_signal(
+            params.tenant_id,
+            Uuid::nil(),
+            kind,
+            organization_id,
+            issue,
+            params.payload.get("url").and_then(|v| v.as_str()),
+        )])
+    }
+}
+
+/// Register the Linear connector in the registry
+pub fn register_linear_connector(registry: &mut Registry, connector: Arc<LinearConnector>) {
+    let metadata = ProviderMetadata::new(
+        LINEAR_PROVIDER_SLUG.to_string(),
+        AuthType::OAuth2,
+        LINEAR_SCOPES.iter().map(|s| s.to_string()).collect(),
+        true, // webhooks supported
+    )
+    .with_category(ProviderCategory::Code)
+    .with_description("Sync issues and their workflow state from Linear")
+    .with_icon_url("https://cdn.simpleicons.org/linear")
+    .with_docs_url("https://developers.linear.app/docs")
+    .with_backfill(true)
+    .with_signal_kinds(&[
+        SignalKind::IssueCreated,
+        SignalKind::IssueUpdated,
+        SignalKind::IssueClosed,
+    ]);
+
+    registry.register(connector, metadata);
+}
+
+fn timestamp(object: &serde_json::Value, key: &str) -> Option<DateTime<Utc>> {
+    object
+        .get(key)
+        .and_then(|v| v.as_str())
+        .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+        .map(|dt| dt.with_timezone(&Utc))
+}
+
+/// Build a signal from a GraphQL issue node or the `data` of an issue webhook
+fn build_signal(
+    tenant_id: Uuid,
+    connection_id: Uuid,
+    kind: SignalKind,
+    organization_id: &str,
+    issue: &serde_json::Value,
+    url: Option<&str>,
+) -> Signal {
+    let str_field = |key: &str| issue.get(key).and_then(|v| v.as_str()).unwrap_or("");
+    let id = str_field("id");
+    let closed_at = issue
+        .get("completedAt")
+        .and_then(|v| v.as_str())
+        .or_else(|| issue.get("canceledAt").and_then(|v| v.as_str()))
+        .unwrap_or("");
+    // Keys match between sync and webhooks so the same change is stored once
+    let (dedupe_key, occurred_field) = match kind {
+        SignalKind::IssueCreated => (format!("linear:{}:{}", kind, id), "createdAt"),
+        SignalKind::IssueClosed => (
+            format!("linear:{}:{}:{}", kind, id, closed_at),
+            if issue.get("completedAt").is_some_and(|v| !v.is_null()) {
+                "completedAt"
+            } else {
+                "canceledAt"
+            },
+        ),
+        _ => (
+            format!("linear:{}:{}:{}", kind, id, str_field("updatedAt")),
+            "updatedAt",
+        ),
+    };
+
+    let received_at = DateTime::from(Utc::now());
+    let occurred_at = timestamp(issue, occurred_field).unwrap_or_else(Utc::now);
+
+    Signal {
+        id: Uuid::new_v4(),
+        tenant_id,
+        provider_slug: LINEAR_PROVIDER_SLUG.to_string(),
+        connection_id,
+        kind: kind.as_str().to_string(),
+        occurred_at: occurred_at.into(),
+        received_at,
+        payload: serde_json::json!({
+            "organization_id": organization_id,
+            "issue_id": id,
+            "identifier": issue.get("identifier"),
+            "title": issue.get("title"),
+            "url": url.map(serde_json::Value::from).or_else(|| issue.get("url").cloned()),
+            "state": issue.pointer("/state/name"),
+            "state_type": issue.pointer("/state/type"),
+            "team_key": issue.pointer("/team/key"),
+            "assignee_id": issue.pointer("/assignee/id"),
+            "priority": issue.get("priority"),
+            "created_at": issue.get("createdAt"),
+            "updated_at": issue.get("updatedAt"),
+            "occurred_at": occurred_at.to_rfc3339(),
+        }),
+        dedupe_key: Some(dedupe_key),
+        created_at: received_at,
+        updated_at: received_at,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{body_partial_json, body_string_contains, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn connector(base: &str) -> LinearConnector {
+        LinearConnector::new(
+            "client-id".to_string(),
+            "client-secret".to_string(),
+            base.to_string(),
+            base.to_string(),
+        )
+    }
+
+    fn connection() -> Connection {
+        let now = DateTime::from(Utc::now());
+        Connection {
+            id: Uuid::new_v4(),
+            tenant_id: Uuid::new_v4(),
+            provider_slug: LINEAR_PROVIDER_SLUG.to_string(),
+            external_id: "org-1".to_string(),
+            status: "active".to_string(),
+            display_name: Some("Acme".to_string()),
+            access_token_ciphertext: None,
+            refresh_token_ciphertext: None,
+            access_token: Some("lin_oauth_test".to_string()),
+            refresh_token: None,
+            expires_at: None,
+            scopes: None,
+            metadata: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    fn issue(id: &str, created: &str, updated: &str, completed: Option<&str>) -> serde_json::Value {
+        serde_json::json!({
+            "id": id,
+            "identifier": "ENG-1",
+            "title": "Fix login",
+            "url": "https://linear.app/acme/issue/ENG-1",
+            "priority": 2,
+            "createdAt": created,
+            "updatedAt": updated,
+            "completedAt": completed,
+            "canceledAt": null,
+            "state": {"name": "In Progress", "type": "started"},
+            "team": {"key": "ENG"},
+            "assignee": null
+        })
+    }
+
+    #[tokio::test]
+    async fn test_linear_authorize_url_shape() {
+        let url = connector("https://linear.app")
+            .authorize(AuthorizeParams {
+                tenant_id: Uuid::new_v4(),
+                redirect_uri: Some("https://app.example.com/callback".to_string()),
+                state: Some("state-1".to_string()),
+                code_challenge: None,
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(url.path(), "/oauth/authorize");
+        let query: std::collections::HashMap<_, _> = url.query_pairs().collect();
+        assert_eq!(query["client_id"], "client-id");
+        assert_eq!(query["response_type"], "code");
+        assert_eq!(query["scope"], "read");
+        assert_eq!(query["state"], "state-1");
+    }
+
+    #[tokio::test]
+    async fn test_linear_exchange_token_records_organization() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/oauth/token"))
+            .and(body_string_contains("grant_type=authorization_code"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "access_token": "lin_oauth_new",
+                "token_type": "Bearer",
+                "expires_in": 86399,
+                "refresh_token": "lin_refresh",
+                "scope": "read"
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/graphql"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": {
+                    "viewer": {"id": "user-1", "name": "Ada"},
+                    "organization": {"id": "org-1", "name": "Acme", "urlKey": "acme"}
+                }
+            })))
+            .mount(&server)
+            .await;
+
+        let connection = connector(&server.uri())
+            .exchange_token(ExchangeTokenParams {
+                code: "code".to_string(),
+                redirect_uri: None,
+                tenant_id: Uuid::new_v4(),
+                code_verifier: None,
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(connection.external_id, "org-1");
+        assert_eq!(connection.display_name.as_deref(), Some("Acme"));
+        assert_eq!(connection.refresh_token.as_deref(), Some("lin_refresh"));
+        assert!(connection.expires_at.is_some());
+        assert_eq!(connection.scopes, Some(serde_json::json!(["read"])));
+    }
+
+    #[tokio::test]
+    async fn test_linear_sync_pages_and_advances_high_water() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/graphql"))
+            .and(body_partial_json(serde_json::json!({
+                "variables": {"after": null, "filter": {"updatedAt": {"gte": "2024-05-01T00:00:00+00:00"}}}
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": {"issues": {
+                    "nodes": [
+                        issue("i-new", "2024-05-02T10:00:00.000Z", "2024-05-02T10:05:00.000Z", None),
+                        issue("i-done", "2024-04-01T10:00:00.000Z", "2024-05-03T08:00:00.000Z",
+                              Some("2024-05-03T08:00:00.000Z"))
+                    ],
+                    "pageInfo": {"hasNextPage": true, "endCursor": "c1"}
+                }}
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/graphql"))
+            .and(body_partial_json(serde_json::json!({"variables": {"after": "c1"}})))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": {"issues": {
+                    "nodes": [
+                        issue("i-edit", "2024-04-01T10:00:00.000Z", "2024-05-01T12:00:00.000Z", None)
+                    ],
+                    "pageInfo": {"hasNextPage": false, "endCursor": "c2"}
+                }}
+            })))
+            .mount(&server)
+            .await;
+
+        let cursor = Cursor::from_json(serde_json::json!({"since": "2024-05-01T00:00:00Z"}));
+        let result = connector(&server.uri())
+            .sync(SyncParams {
+                connection: connection(),
+                cursor: Some(cursor),
+                cancellation: Default::default(),
+            })
+            .await
+            .unwrap();
+
+        let kinds: Vec<_> = result.signals.iter().map(|s| s.kind.as_str()).collect();
+        assert_eq!(
+            kinds,
+            vec!["issue_created", "issue_closed", "issue_updated"]
+        );
+        assert_eq!(
+            result.signals[1].dedupe_key.as_deref(),
+            Some("linear:issue_closed:i-done:2024-05-03T08:00:00.000Z")
+        );
+        assert_eq!(result.signals[0].payload["identifier"], "ENG-1");
+        assert!(!result.has_more);
+        assert_eq!(
+            result.next_cursor.unwrap().as_json(),
+            &serde_json::json!({"since": "2024-05-03T08:00:00Z"})
+        );
+    }
+
+    #[tokio::test]
+    async fn test_linear_sync_maps_graphql_errors() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/graphql"))
+            .respond_with(ResponseTemplate::new(400).set_body_json(serde_json::json!({
+                "errors": [{"message": "Rate limit exceeded", "extensions": {"code": "RATELIMITED"}}]
+            })))
+            .mount(&server)
+            .await;
+        let err = connector(&server.uri())
+            .sync(SyncParams {
+                connection: connection(),
+                cursor: None,
+                cancellation: Default::default(),
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<SyncError>().unwrap().kind,
+            crate::connectors::SyncErrorKind::RateLimited {
+                retry_after_secs: None
+            }
+        );
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/graphql"))
+            .respond_with(ResponseTemplate::new(401))
+            .mount(&server)
+            .await;
+        let err = connector(&server.uri())
+            .sync(SyncParams {
+                connection: connection(),
+                cursor: None,
+                cancellation: Default::default(),
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<SyncError>().unwrap().kind,
+            crate::connectors::SyncErrorKind::Unauthorized
+        );
+    }
+
+    #[tokio::test]
+    async fn test_linear_webhook_mapping() {
+        let connector = connector("https://linear.app");
+        let handle = |payload: serde_json::Value| {
+            connector.handle_webhook(WebhookParams {
+                payload,
+                tenant_id: Uuid::new_v4(),
+                db: None,
+                auth_header: None,
+            })
+        };
+
+        let created = handle(serde_json::json!({
+            "action": "create",
+            "type": "Issue",
+            "organizationId": "org-1",
+            "url": "https://linear.app/acme/issue/ENG-1",
+            "data": issue("i-1", "2024-05-02T10:00:00.000Z", "2024-05-02T10:00:00.000Z", None)
+        }))
+        .await
+        .unwrap();
+        assert_eq!(created.len(), 1);
+        assert_eq!(created[0].kind, "issue_created");
+        // Matches the key produced by sync so the two paths deduplicate
+        assert_eq!(
+            created[0].dedupe_key.as_deref(),
+            Some("linear:issue_created:i-1")
+        );
+
+        let closed = handle(serde_json::json!({
+            "action": "update",
+            "type": "Issue",
+            "organizationId": "org-1",
+            "data": issue("i-1", "2024-05-02T10:00:00.000Z", "2024-05-03T08:00:00.000Z",
+                          Some("2024-05-03T08:00:00.000Z")),
+            "updatedFrom": {"completedAt": null, "stateId": "s-1", "updatedAt": "2024-05-02T10:00:00.000Z"}
+        }))
+        .await
+        .unwrap();
+        assert_eq!(closed[0].kind, "issue_closed");
+        assert_eq!(closed[0].payload["organization_id"], "org-1");
+
+        let edited = handle(serde_json::json!({
+            "action": "update",
+            "type": "Issue",
+            "data": issue("i-1", "2024-05-02T10:00:00.000Z", "2024-05-04T08:00:00.000Z",
+                          Some("2024-05-03T08:00:00.000Z")),
+            "updatedFrom": {"title": "Fix logn"}
+        }))
+        .await
+        .unwrap();
+        assert_eq!(edited[0].kind, "issue_updated");
+
+        let comment = handle(serde_json::json!({
+            "action": "create",
+            "type": "Comment",
+            "data": {"id": "c-1"}
+        }))
+        .await
+        .unwrap();
+        assert!(comment.is_empty());
+    }
+}
@@ -1,8 +1,13 @@
 //! Provider metadata types
 //!
-//! Defines the metadata structure for providers and authentication types.
+//! Defines the metadata structure for providers and authentication types,
+//! including the catalog fields (description, icon, docs link, category) and
+//! capability flags the connect UI renders.
 
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::normalization::SignalKind;
 
 /// Authentication type supported by a provider
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -12,6 +17,8 @@ pub enum AuthType {
     OAuth2,
     /// API key authentication
     ApiKey,
+    /// OAuth 2.0 client credentials grant with a tenant-supplied client ID and secret
+    ClientCredentials,
     /// Basic authentication (username/password)
     Basic,
     /// Bearer token authentication
@@ -20,6 +27,50 @@ pub enum AuthType {
     Custom(String),
 }
 
+impl AuthType {
+    /// Value stored in `providers.auth_type`
+    pub fn as_str(&self) -> &str {
+        match self {
+            AuthType::OAuth2 => "oauth2",
+            AuthType::ApiKey => "api_key",
+            AuthType::ClientCredentials => "client_credentials",
+            AuthType::Basic => "basic",
+            AuthType::Bearer => "bearer",
+            AuthType::Custom(value) => value,
+        }
+    }
+
+    /// Whether connections authenticate with credentials submitted through
+    /// `POST /connect/{provider}/credentials` rather than an OAuth redirect
+    ///
+    /// Such credentials never expire on a schedule, so token refresh skips them.
+    pub fn uses_static_credentials(&self) -> bool {
+        matches!(self, AuthType::ApiKey | AuthType::ClientCredentials)
+    }
+}
+
+/// Catalog category used to group providers in the connect UI
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ProviderCategory {
+    /// Source control and issue tracking
+    Code,
+    /// Team messaging
+    Chat,
+    /// Email
+    Mail,
+    /// Calendars and scheduling
+    Calendar,
+    /// File storage and documents
+    Files,
+    /// Customer relationship management
+    Crm,
+    /// Error tracking and application monitoring
+    Monitoring,
+    /// Anything that does not fit the categories above
+    Other,
+}
+
 /// Metadata about a provider
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProviderMetadata {
@@ -31,6 +82,40 @@ pub struct ProviderMetadata {
     pub scopes: Vec<String>,
     /// Whether this provider supports webhooks
     pub webhooks: bool,
+    /// One-line description shown in the connect catalog
+    #[serde(default)]
+    pub description: Option<String>,
+    /// URL of the provider's icon
+    #[serde(default)]
+    pub icon_url: Option<String>,
+    /// URL of the provider's integration documentation
+    #[serde(default)]
+    pub docs_url: Option<String>,
+    /// Catalog category
+    #[serde(default)]
+    pub category: Option<ProviderCategory>,
+    /// Whether sync reads activity from before the connection was created
+    #[serde(default)]
+    pub supports_backfill: bool,
+    /// Canonical signal kinds the connector emits
+    #[serde(default)]
+    pub supported_signal_kinds: Vec<String>,
+}
+
+/// What a provider supports, so clients can build connect UIs without
+/// hardcoding provider behavior
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct ProviderCapabilities {
+    /// Provider slug
+    pub provider: String,
+    /// Whether connections are created through the OAuth authorize flow
+    pub supports_oauth: bool,
+    /// Whether the provider delivers events to the webhook endpoints
+    pub supports_webhooks: bool,
+    /// Whether sync reads activity from before the connection was created
+    pub supports_backfill: bool,
+    /// Canonical signal kinds the connector emits
+    pub supported_signal_kinds: Vec<String>,
 }
 
 impl ProviderMetadata {
@@ -41,6 +126,12 @@ impl ProviderMetadata {
             auth_type,
             scopes,
             webhooks,
+            description: None,
+            icon_url: None,
+            docs_url: None,
+            category: None,
+            supports_backfill: false,
+            supported_signal_kinds: Vec::new(),
         }
     }
 
@@ -51,6 +142,59 @@ impl ProviderMetadata {
             auth_type,
             scopes: Vec::new(),
             webhooks: false,
+            description: None,
+            icon_url: None,
+            docs_url: None,
+            category: None,
+            supports_backfill: false,
+            supported_signal_kinds: Vec::new(),
+        }
+    }
+
+    /// Set the catalog category
+    pub fn with_category(mut self, category: ProviderCategory) -> Self {
+        self.category = Some(category);
+        self
+    }
+
+    /// Set the catalog description
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Set the icon URL
+    pub fn with_icon_url(mut self, icon_url: impl Into<String>) -> Self {
+        self.icon_url = Some(icon_url.into());
+        self
+    }
+
+    /// Set the documentation URL
+    pub fn with_docs_url(mut self, docs_url: impl Into<String>) -> Self {
+        self.docs_url = Some(docs_url.into());
+        self
+    }
+
+    /// Set whether sync reads activity from before the connection was created
+    pub fn with_backfill(mut self, supports_backfill: bool) -> Self {
+        self.supports_backfill = supports_backfill;
+        self
+    }
+
+    /// Set the signal kinds the connector emits
+    pub fn with_signal_kinds(mut self, kinds: &[SignalKind]) -> Self {
+        self.supported_signal_kinds = kinds.iter().map(|kind| kind.as_str().to_string()).collect();
+        self
+    }
+
+    /// Capability flags derived from this metadata
+    pub fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            provider: self.name.clone(),
+            supports_oauth: self.auth_type == AuthType::OAuth2,
+            supports_webhooks: self.webhooks,
+            supports_backfill: self.supports_backfill,
+            supported_signal_kinds: self.supported_signal_kinds.clone(),
         }
     }
 }
@@ -0,0 +1,572 @@
+//! Connection metadata schemas
+//!
+//! Providers may declare settings that operators keep in a connection's
+//! metadata, such as the GitHub repositories to sync. Each provider's settings
+//! are a typed struct whose schema is served at
+//! `GET /providers/{slug}/metadata-schema` so UIs can render a form, and
+//! metadata updates are checked against the same struct before they are stored.
+//! Keys outside the schema belong to the connector (account IDs, auth health)
+//! and cannot be set through the API.
+
+use crate::connectors::custom_webhook::JsonPath;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value as JsonValue};
+use std::collections::BTreeMap;
+use utoipa::{PartialSchema, ToSchema};
+
+/// Most entries accepted in one list setting
+pub const MAX_LIST_ENTRIES: usize = 100;
+
+/// Settings of a GitHub connection
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GitHubConnectionSettings {
+    /// Repositories to sync as `owner/name`; all accessible repositories when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(example = json!(["octo-org/api", "octo-org/web"]))]
+    pub repositories: Option<Vec<String>>,
+}
+
+/// Settings of a Zoho Mail connection
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ZohoMailConnectionSettings {
+    /// Folder IDs to sync; all folders when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(example = json!(["1234000000008014"]))]
+    pub include_folders: Option<Vec<String>>,
+    /// Folder IDs never synced, applied after `include_folders`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(example = json!(["1234000000008020"]))]
+    pub exclude_folders: Option<Vec<String>>,
+}
+
+/// Settings of an RSS or Atom feed connection
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct RssConnectionSettings {
+    /// HTTP(S) URL of the RSS or Atom feed to poll
+    #[schema(example = "https://example.com/news/feed.xml")]
+    pub feed_url: String,
+}
+
+/// Settings of an iCalendar feed connection
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct IcsConnectionSettings {
+    /// HTTP(S) or `webcal://` URL of the published calendar
+    #[schema(example = "https://calendar.example.com/team.ics")]
+    pub calendar_url: String,
+}
+
+/// Mapping rules of a custom webhook connection
+///
+/// Rules other than a literal `kind` are JSONPath expressions read from each
+/// delivered body, e.g. `$.event.type`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct CustomWebhookConnectionSettings {
+    /// Signal kind of every delivery, or a JSONPath to the field holding it
+    #[schema(example = "$.event.type")]
+    pub kind: String,
+    /// Signal kind for each value read by a `kind` path; other values are dropped
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(example = json!({"deploy.failed": "ci_run_failed"}))]
+    pub kind_map: Option<BTreeMap<String, String>>,
+    /// JSONPath to the event time, as RFC 3339 text or Unix seconds or milliseconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(example = "$.event.created_at")]
+    pub occurred_at: Option<String>,
+    /// JSONPath to a value identifying the event, used as the signal's dedupe key
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(example = "$.event.id")]
+    pub dedupe_key: Option<String>,
+}
+
+/// How an IMAP connection logs in
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ImapAuthMethod {
+    /// `LOGIN` with the connection's secret as the password
+    #[default]
+    Password,
+    /// `AUTHENTICATE XOAUTH2` with the connection's secret as the access token
+    Xoauth2,
+}
+
+/// Settings of an IMAP mailbox connection
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ImapConnectionSettings {
+    /// IMAP server host name
+    #[schema(example = "imap.fastmail.com")]
+    pub host: String,
+    /// Server port (default: 993)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(example = 993)]
+    pub port: Option<u16>,
+    /// Login user name
+    #[schema(example = "founder@example.com")]
+    pub username: String,
+    /// Login method (default: `password`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auth: Option<ImapAuthMethod>,
+    /// Implicit TLS (default: true); may only be turned off for a bridge on loopback
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tls: Option<bool>,
+    /// ASCII mailbox names to sync (default: `INBOX`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(example = json!(["INBOX", "Sales"]))]
+    pub mailboxes: Option<Vec<String>>,
+}
+
+/// Why a metadata update was rejected
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MetadataError {
+    /// Offending metadata key
+    pub field: String,
+    pub message: String,
+}
+
+impl MetadataError {
+    fn new(field: &str, message: impl Into<String>) -> Self {
+        Self {
+            field: field.to_string(),
+            message: message.into(),
+        }
+    }
+}
+
+/// Settings struct with checks beyond what its types express
+trait ConnectionSettings: DeserializeOwned + PartialSchema {
+    fn validate(&self) -> Result<(), MetadataError>;
+}
+
+impl ConnectionSettings for GitHubConnectionSettings {
+    fn validate(&self) -> Result<(), MetadataError> {
+        let Some(repositories) = &self.repositories else {
+            return Ok(());
+        };
+        check_list("repositories", repositories)?;
+        for repository in repositories {
+            let valid = repository.split_once('/').is_some_and(|(owner, name)| {
+                !owner.is_empty() && !name.is_empty() && !name.contains('/')
+            });
+            if !valid {
+                return Err(MetadataError::new(
+                    "repositories",
+                    format!("'{}' is not an owner/name repository", repository),
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl ConnectionSettings for ZohoMailConnectionSettings {
+    fn validate(&self) -> Result<(), MetadataError> {
+        for (field, folders) in [
+            ("include_folders", &self.include_folders),
+            ("exclude_folders", &self.exclude_folders),
+        ] {
+            if let Some(folders) = folders {
+                check_list(field, folders)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl ConnectionSettings for RssConnectionSettings {
+    fn validate(&self) -> Result<(), MetadataError> {
+        let valid = url::Url::parse(&self.feed_url)
+            .is_ok_and(|url| matches!(url.scheme(), "http" | "https") && url.host_str().is_some());
+        if !valid {
+            return Err(MetadataError::new(
+                "feed_url",
+                format!("'{}' is not an http or https URL", self.feed_url),
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl ConnectionSettings for IcsConnectionSettings {
+    fn validate(&self) -> Result<(), MetadataError> {
+        let valid = url::Url::parse(&self.calendar_url).is_ok_and(|url| {
+            matches!(url.scheme(), "http" | "https" | "webcal") && url.host_str().is_some()
+        });
+        if !valid {
+            return Err(MetadataError::new(
+                "calendar_url",
+                format!(
+                    "'{}' is not an http, https or webcal URL",
+                    self.calendar_url
+                ),
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl ConnectionSettings for CustomWebhookConnectionSettings {
+    fn validate(&self) -> Result<(), MetadataError> {
+        let kind = self.kind.trim();
+        let check_kind = |field: &str, kind: &str| {
+            crate::normalization::AnySignalKind::parse(kind)
+                .map(|_| ())
+                .map_err(|e| MetadataError::new(field, e.to_string()))
+        };
+        let check_path = |field: &str, path: &str| {
+            JsonPath::parse(path)
+                .map(|_| ())
+                .map_err(|e| MetadataError::new(field, e))
+        };
+        if kind.starts_with('$') {
+            check_path("kind", kind)?;
+        } else {
+            check_kind("kind", kind)?;
+            if self.kind_map.is_some() {
+                return Err(MetadataError::new(
+                    "kind_map",
+                    "only applies when kind is a JSONPath",
+                ));
+            }
+        }
+        if let Some(kind_map) = &self.kind_map {
+            if kind_map.is_empty() || kind_map.len() > MAX_LIST_ENTRIES {
+                return Err(MetadataError::new(
+                    "kind_map",
+                    format!("must have between 1 and {} entries", MAX_LIST_ENTRIES),
+                ));
+            }
+            kind_map
+                .values()
+                .try_for_each(|kind| check_kind("kind_map", kind))?;
+        }
+        if let Some(path) = &self.occurred_at {
+            check_path("occurred_at", path)?;
+        }
+        if let Some(path) = &self.dedupe_key {
+            check_path("dedupe_key", path)?;
+        }
+        Ok(())
+    }
+}
+
+impl ConnectionSettings for ImapConnectionSettings {
+    fn validate(&self) -> Result<(), MetadataError> {
+        let host = self.host.trim();
+        if host.is_empty() || host.contains(['/', ':', ' ']) {
+            return Err(MetadataError::new(
+                "host",
+                format!("'{}' is not a host name", self.host),
+            ));
+        }
+        if self.username.trim().is_empty() {
+            return Err(MetadataError::new("username", "must not be empty"));
+        }
+        if self.port == Some(0) {
+            return Err(MetadataError::new("port", "must be between 1 and 65535"));
+        }
+        let loopback = host == "localhost"
+            || host
+                .parse::<std::net::IpAddr>()
+                .is_ok_and(|ip| ip.is_loopback());
+        if self.tls == Some(false) && !loopback {
+            return Err(MetadataError::new(
+                "tls",
+                "may only be false for a server on localhost",
+            ));
+        }
+        if let Some(mailboxes) = &self.mailboxes {
+            check_list("mailboxes", mailboxes)?;
+            if mailboxes
+                .iter()
+                .any(|mailbox| !mailbox.is_ascii() || mailbox.contains(['\r', '\n']))
+            {
+                return Err(MetadataError::new(
+                    "mailboxes",
+                    "mailbox names must be single-line ASCII",
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn check_list(field: &str, entries: &[String]) -> Result<(), MetadataError> {
+    if entries.len() > MAX_LIST_ENTRIES {
+        return Err(MetadataError::new(
+            field,
+            format!("at most {} entries are allowed", MAX_LIST_ENTRIES),
+        ));
+    }
+    if entries.iter().any(|entry| entry.trim().is_empty()) {
+        return Err(MetadataError::new(field, "entries must not be empty"));
+    }
+    Ok(())
+}
+
+/// JSON schema of the settings `provider` accepts in connection metadata
+///
+/// Providers without settings get an object schema with no properties.
+pub fn metadata_schema(provider: &str) -> JsonValue {
+    match provider {
+        "github" => schema_of::<GitHubConnectionSettings>(),
+        "zoho-mail" => schema_of::<ZohoMailConnectionSettings>(),
+        "rss" => schema_of::<RssConnectionSettings>(),
+        "custom-webhook" => schema_of::<CustomWebhookConnectionSettings>(),
+        "ics" => schema_of::<IcsConnectionSettings>(),
+        "imap" => schema_of::<ImapConnectionSettings>(),
+        _ => serde_json::json!({ "type": "object", "properties": {} }),
+    }
+}
+
+/// Whether `provider` declares any connection settings
+pub fn has_metadata_schema(provider: &str) -> bool {
+    !settable_keys(&metadata_schema(provider)).is_empty()
+}
+
+/// Apply `patch` to `metadata` after checking it against the provider's schema
+///
+/// Top-level keys in `patch` replace the stored value and `null` removes the
+/// key. Only keys declared in the schema may be patched, and the resulting
+/// settings must pass the provider's checks as a whole.
+pub fn apply_metadata_patch(
+    provider: &str,
+    metadata: Option<&JsonValue>,
+    patch: &Map<String, JsonValue>,
+) -> Result<JsonValue, MetadataError> {
+    let keys = settable_keys(&metadata_schema(provider));
+    if let Some(key) = patch.keys().find(|key| !keys.contains(key)) {
+        return Err(MetadataError::new(
+            key,
+            format!("not a configurable setting for provider '{}'", provider),
+        ));
+    }
+
+    let mut merged = metadata
+        .and_then(JsonValue::as_object)
+        .cloned()
+        .unwrap_or_default();
+    for (key, value) in patch {
+        if value.is_null() {
+            merged.remove(key);
+        } else {
+            merged.insert(key.clone(), value.clone());
+        }
+    }
+
+    let settings: Map<String, JsonValue> = merged
+        .iter()
+        .filter(|(key, _)| keys.contains(key))
+        .map(|(key, value)| (key.clone(), value.clone()))
+        .collect();
+    match provider {
+        "github" => check::<GitHubConnectionSettings>(settings)?,
+        "zoho-mail" => check::<ZohoMailConnectionSettings>(settings)?,
+        "rss" => check::<RssConnectionSettings>(settings)?,
+        "custom-webhook" => check::<CustomWebhookConnectionSettings>(settings)?,
+        "ics" => check::<IcsConnectionSettings>(settings)?,
+        "imap" => check::<ImapConnectionSettings>(settings)?,
+        _ => {}
+    }
+
+    Ok(JsonValue::Object(merged))
+}
+
+/// Settings of `provider` read from a stored connection's metadata
+///
+/// Keys written by the connector are ignored, so strict parsing only applies
+/// to the settings themselves.
+pub fn parse_settings<T: DeserializeOwned>(
+    provider: &str,
+    metadata: Option<&JsonValue>,
+) -> Result<T, MetadataError> {
+    let keys = settable_keys(&metadata_schema(provider));
+    let settings: Map<String, JsonValue> = metadata
+        .and_then(JsonValue::as_object)
+        .into_iter()
+        .flatten()
+        .filter(|(key, _)| keys.contains(key))
+        .map(|(key, value)| (key.clone(), value.clone()))
+        .collect();
+    serde_json::from_value(JsonValue::Object(settings))
+        .map_err(|e| MetadataError::new("metadata", e.to_string()))
+}
+
+fn schema_of<T: PartialSchema>() -> JsonValue {
+    serde_json::to_value(T::schema()).unwrap_or_default()
+}
+
+fn settable_keys(schema: &JsonValue) -> Vec<String> {
+    schema
+        .get("properties")
+        .and_then(JsonValue::as_object)
+        .map(|properties| properties.keys().cloned().collect())
+        .unwrap_or_default()
+}
+
+fn check<T: ConnectionSettings>(settings: Map<String, JsonValue>) -> Result<(), MetadataError> {
+    // Parse keys one at a time first so a type error names its field; the
+    // other required fields are absent there and checked on the whole below
+    for (key, value) in &settings {
+        if let Err(e) = serde_json::from_value::<T>(serde_json::json!({ key: value }))
+            && !e.to_string().starts_with("missing field")
+        {
+            return Err(MetadataError::new(key, e.to_string()));
+        }
+    }
+    let parsed: T = serde_json::from_value(JsonValue::Object(settings))
+        .map_err(|e| MetadataError::new("metadata", e.to_string()))?;
+    parsed.validate()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_github_schema_lists_repositories() {
+        let schema = metadata_schema("github");
+        assert_eq!(schema["type"], "object");
+        assert!(schema["properties"]["repositories"].is_object());
+        assert!(has_metadata_schema("github"));
+        assert!(!has_metadata_schema("slack"));
+        assert_eq!(metadata_schema("slack")["properties"], json!({}));
+    }
+
+    #[test]
+    fn test_patch_merges_and_keeps_connector_keys() {
+        let stored = json!({"login": "octocat", "repositories": ["octo-org/api"]});
+        let patch = json!({"repositories": ["octo-org/api", "octo-org/web"]});
+        let merged =
+            apply_metadata_patch("github", Some(&stored), patch.as_object().unwrap()).unwrap();
+        assert_eq!(merged["login"], "octocat");
+        assert_eq!(
+            merged["repositories"],
+            json!(["octo-org/api", "octo-org/web"])
+        );
+
+        let cleared = apply_metadata_patch(
+            "github",
+            Some(&merged),
+            json!({"repositories": null}).as_object().unwrap(),
+        )
+        .unwrap();
+        assert_eq!(cleared, json!({"login": "octocat"}));
+    }
+
+    #[test]
+    fn test_patch_rejects_invalid_settings() {
+        let reject = |provider: &str, patch: JsonValue| {
+            apply_metadata_patch(provider, None, patch.as_object().unwrap()).unwrap_err()
+        };
+
+        assert_eq!(reject("github", json!({"login": "mallory"})).field, "login");
+        assert_eq!(
+            reject("github", json!({"repositories": ["api"]})).field,
+            "repositories"
+        );
+        assert_eq!(
+            reject("github", json!({"repositories": "octo-org/api"})).field,
+            "repositories"
+        );
+        assert_eq!(
+            reject("zoho-mail", json!({"exclude_folders": [""]})).field,
+            "exclude_folders"
+        );
+        assert_eq!(reject("slack", json!({"channels": []})).field, "channels");
+        assert_eq!(
+            reject("rss", json!({"feed_url": "ftp://example.com/feed"})).field,
+            "feed_url"
+        );
+        assert_eq!(
+            reject("ics", json!({"calendar_url": "file:///team.ics"})).field,
+            "calendar_url"
+        );
+    }
+
+    #[test]
+    fn test_custom_webhook_rules_are_checked() {
+        let valid = json!({
+            "kind": "$.event.type",
+            "kind_map": {"deploy.failed": "ci_run_failed", "mention": "custom:press_mention"},
+            "occurred_at": "$.event['created at']",
+            "dedupe_key": "$.event.id"
+        });
+        apply_metadata_patch("custom-webhook", None, valid.as_object().unwrap()).unwrap();
+        let fixed = json!({"kind": "incident_triggered"});
+        apply_metadata_patch("custom-webhook", None, fixed.as_object().unwrap()).unwrap();
+
+        let reject = |patch: JsonValue| {
+            let mut settings = valid.as_object().unwrap().clone();
+            settings.extend(patch.as_object().unwrap().clone());
+            apply_metadata_patch("custom-webhook", None, &settings)
+                .unwrap_err()
+                .field
+        };
+        assert_eq!(reject(json!({"kind": "not_a_kind"})), "kind");
+        assert_eq!(reject(json!({"kind": "$..type"})), "kind");
+        assert_eq!(reject(json!({"kind_map": {"x": "bogus"}})), "kind_map");
+        assert_eq!(reject(json!({"kind_map": {}})), "kind_map");
+        assert_eq!(reject(json!({"occurred_at": "created_at"})), "occurred_at");
+        assert_eq!(
+            reject(json!({"kind": "incident_triggered"})),
+            "kind_map",
+            "a literal kind takes no map"
+        );
+    }
+
+    #[test]
+    fn test_imap_settings_are_checked() {
+        let valid = json!({"host": "imap.example.com", "username": "me@example.com"});
+        apply_metadata_patch("imap", None, valid.as_object().unwrap()).unwrap();
+        let settings: ImapConnectionSettings = parse_settings(
+            "imap",
+            Some(&json!({"host": "imap.example.com", "username": "me", "account": "x"})),
+        )
+        .unwrap();
+        assert_eq!(settings.host, "imap.example.com");
+        assert_eq!(settings.auth, None);
+
+        let reject = |patch: JsonValue| {
+            let mut settings = valid.as_object().unwrap().clone();
+            settings.extend(patch.as_object().unwrap().clone());
+            apply_metadata_patch("imap", None, &settings)
+                .unwrap_err()
+                .field
+        };
+        assert_eq!(reject(json!({"host": "imaps://imap.example.com"})), "host");
+        assert_eq!(reject(json!({"tls": false})), "tls");
+        assert_eq!(reject(json!({"auth": "plain"})), "auth");
+        assert_eq!(reject(json!({"mailboxes": ["Entwürfe"]})), "mailboxes");
+        let bridge = json!({"host": "127.0.0.1", "port": 1143, "username": "me", "tls": false});
+        assert!(apply_metadata_patch("imap", None, bridge.as_object().unwrap()).is_ok());
+    }
+
+    #[test]
+    fn test_rss_feed_url_is_required() {
+        let stored = json!({"feed_url": "https://example.com/feed.xml"});
+        let moved = apply_metadata_patch(
+            "rss",
+            Some(&stored),
+            json!({"feed_url": "https://example.com/atom.xml"})
+                .as_object()
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(moved["feed_url"], "https://example.com/atom.xml");
+
+        let removed = apply_metadata_patch(
+            "rss",
+            Some(&stored),
+            json!({"feed_url": null}).as_object().unwrap(),
+        )
+        .unwrap_err();
+        assert_eq!(removed.field, "metadata");
+    }
+}
@@ -5,32 +5,92 @@
 //! - Provider metadata and registry for discovery and lookup
 //! - Individual connector implementations
 
+pub mod circleci;
+pub mod confluence;
+pub mod custom_webhook;
+pub mod datadog;
 pub mod example;
+#[cfg(feature = "fake-connectors")]
+pub mod fake;
 pub mod github;
+pub mod gitlab;
 pub mod gmail;
 pub mod google_calendar;
 pub mod google_drive;
+pub mod google_service_account;
+pub mod ics;
+pub mod imap;
+pub mod inbound_email;
 pub mod jira;
+pub mod linear;
 pub mod metadata;
+pub mod metadata_schema;
+pub mod notion;
+pub mod outlook;
+pub mod outlook_calendar;
+#[cfg(feature = "wasm-plugins")]
+pub mod plugin;
 pub mod registry;
+pub mod rss;
+pub mod salesforce;
+pub mod sentry;
+pub mod slack;
+pub mod stripe;
 pub mod trait_;
+pub mod zendesk;
 pub mod zoho_cliq;
 pub mod zoho_mail;
 
-pub use metadata::{AuthType, ProviderMetadata};
+pub use metadata::{AuthType, ProviderCapabilities, ProviderCategory, ProviderMetadata};
 pub use registry::{Registry, RegistryError};
 pub use trait_::{
-    AuthorizeParams, Connector, ConnectorError, Cursor, ExchangeTokenParams, SyncError,
-    SyncErrorKind, SyncParams, SyncResult, WebhookParams,
+    AuthorizeParams, CollectingSink, Connector, ConnectorError, Cursor, CursorWindows,
+    ExchangeTokenParams, HealthCheck, SyncError, SyncErrorKind, SyncPage, SyncParams, SyncResult,
+    SyncSink, WebhookParams,
 };
 pub use zoho_mail::{
     ZOHO_MAIL_PROVIDER_SLUG, ZohoMailConfig, ZohoMailConnector, register_zoho_mail_connector,
 };
 
+pub use circleci::{
+    CIRCLECI_PROVIDER_SLUG, CircleCiConnector, circleci_connection_key, register_circleci_connector,
+};
+pub use confluence::{
+    CONFLUENCE_PROVIDER_SLUG, ConfluenceConnector, register_confluence_connector,
+};
+pub use custom_webhook::{
+    CUSTOM_WEBHOOK_PROVIDER_SLUG, CustomWebhookConnector, custom_webhook_payload,
+    register_custom_webhook_connector,
+};
+pub use datadog::{
+    DATADOG_PROVIDER_SLUG, DatadogConnector, datadog_external_id, register_datadog_connector,
+};
 pub use example::{ExampleConnector, register_example_connector};
 pub use github::{GitHubConnector, register_github_connector};
+pub use gitlab::{GITLAB_PROVIDER_SLUG, GitLabConnector, register_gitlab_connector};
 pub use gmail::{GmailConnector, register_gmail_connector};
 pub use google_calendar::{GoogleCalendarConnector, register_google_calendar_connector};
 pub use google_drive::{GoogleDriveConnector, register_google_drive_connector};
+pub use ics::{ICS_CALENDAR_URL_KEY, ICS_PROVIDER_SLUG, IcsConnector, register_ics_connector};
+pub use imap::{IMAP_PROVIDER_SLUG, ImapConnector, register_imap_connector};
+pub use inbound_email::{
+    INBOUND_EMAIL_PROVIDER_SLUG, InboundEmailConnector, register_inbound_email_connector,
+};
 pub use jira::{JiraConnector, register_jira_connector};
+pub use linear::{LINEAR_PROVIDER_SLUG, LinearConnector, register_linear_connector};
+pub use notion::{NOTION_PROVIDER_SLUG, NotionConnector, register_notion_connector};
+pub use outlook::{OUTLOOK_PROVIDER_SLUG, OutlookConnector, register_outlook_connector};
+pub use outlook_calendar::{
+    OUTLOOK_CALENDAR_PROVIDER_SLUG, OutlookCalendarConnector, register_outlook_calendar_connector,
+};
+pub use rss::{RSS_FEED_URL_KEY, RSS_PROVIDER_SLUG, RssConnector, register_rss_connector};
+pub use salesforce::{
+    SALESFORCE_PROVIDER_SLUG, SalesforceConnector, register_salesforce_connector,
+};
+pub use sentry::{SENTRY_PROVIDER_SLUG, SentryConnector, register_sentry_connector};
+pub use slack::{SLACK_PROVIDER_SLUG, SlackConnector, register_slack_connector};
+pub use stripe::{
+    STRIPE_PROVIDER_SLUG, StripeConnector, register_stripe_connector, stripe_external_id,
+};
+pub use zendesk::{ZENDESK_PROVIDER_SLUG, ZendeskConnector, register_zendesk_connector};
 pub use zoho_cliq::{ZohoCliqConnector, register_zoho_cliq_connector};
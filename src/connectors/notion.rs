@@ -0,0 +1,859 @@
+//! Notion connector implementation
+//!
+//! Notion connector supporting the public integration OAuth flow and
+//! incremental sync of pages, databases and comments. Sync walks `/v1/search`
+//! newest edit first and stops at the previous run's high-water mark; comments
+//! are read for every page edited since then.
+
+use anyhow::anyhow;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use reqwest::{Client, Method, StatusCode};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tracing::{debug, info, warn};
+use url::Url;
+use uuid::Uuid;
+
+use crate::connectors::{
+    AuthType, Connector, Cursor, ProviderCategory, ProviderMetadata, Registry,
+    trait_::{
+        AuthorizeParams, ExchangeTokenParams, SyncError, SyncParams, SyncResult, WebhookParams,
+    },
+};
+use crate::models::{connection::Model as Connection, signal::Model as Signal};
+use crate::normalization::{SignalKind, normalize_notion_object_kind};
+
+/// Provider slug
+pub const NOTION_PROVIDER_SLUG: &str = "notion";
+
+/// API version sent in the `Notion-Version` header
+const NOTION_VERSION: &str = "2022-06-28";
+
+/// Page size for `/v1/search` and `/v1/comments` (the API maximum)
+const PAGE_SIZE: u32 = 100;
+
+/// Search pages fetched in one sync; the rest continues on the next run
+const MAX_SEARCH_PAGES_PER_SYNC: u32 = 10;
+
+/// Notion connector
+pub struct NotionConnector {
+    client_id: String,
+    client_secret: String,
+    api_base: String,
+    http_client: Client,
+}
+
+/// Search position stored in the cursor
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+struct SearchWindow {
+    /// Objects last edited before this time have been synced
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    since: Option<DateTime<Utc>>,
+    /// `next_cursor` of an unfinished search walk
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    start_cursor: Option<String>,
+    /// Newest edit seen by the unfinished walk; becomes `since` once it completes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    high_water: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct NotionTokenResponse {
+    access_token: String,
+    #[serde(default)]
+    refresh_token: Option<String>,
+    #[serde(default)]
+    expires_in: Option<i64>,
+    #[serde(default)]
+    bot_id: Option<String>,
+    workspace_id: String,
+    #[serde(default)]
+    workspace_name: Option<String>,
+    #[serde(default)]
+    workspace_icon: Option<String>,
+    #[serde(default)]
+    owner: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize)]
+struct NotionList {
+    #[serde(default)]
+    results: Vec<serde_json::Value>,
+    #[serde(default)]
+    has_more: bool,
+    #[serde(default)]
+    next_cursor: Option<String>,
+}
+
+impl NotionConnector {
+    /// Create a new Notion connector with configuration
+    pub fn new(client_id: String, client_secret: String, api_base: String) -> Self {
+        Self {
+            client_id,
+            client_secret,
+            api_base,
+            http_client: crate::egress::client(),
+        }
+    }
+
+    fn default_redirect_uri() -> String {
+        match std::env::var("POBLYSH_PROFILE").as_deref() {
+            Ok("local") | Ok("test") | Err(_) => "http://localhost:3000/callback".to_string(),
+            Ok(_) => "https://app.poblysh.com/callback".to_string(),
+        }
+    }
+
+    fn access_token(connection: &Connection) -> Result<String, SyncError> {
+        connection
+            .access_token
+            .clone()
+            .filter(|token| !token.is_empty())
+            .ok_or_else(|| SyncError::unauthorized("Missing Notion access token"))
+    }
+
+    /// Call `/v1/oauth/token` with the given grant
+    async fn oauth_token(
+        &self,
+        grant: serde_json::Value,
+    ) -> Result<NotionTokenResponse, anyhow::Error> {
+        let response = self
+            .http_client
+            .post(format!(
+                "{}/v1/oauth/token",
+                self.api_base.trim_end_matches('/')
+            ))
+            .basic_auth(&self.client_id, Some(&self.client_secret))
+            .json(&grant)
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let body: serde_json::Value = response.json().await.unwrap_or_default();
+            return Err(anyhow!(
+                "Notion token endpoint returned {}: {}",
+                status,
+                body.get("error")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("unknown_error")
+            ));
+        }
+        Ok(response.json().await?)
+    }
+
+    /// Send an API request, mapping HTTP failures to sync errors
+    async fn api_request(
+        &self,
+        access_token: &str,
+        method: Method,
+        path: &str,
+        query: &[(&str, String)],
+        body: Option<serde_json::Value>,
+    ) -> Result<NotionList, SyncError> {
+        let url = format!("{}/v1/{}", self.api_base.trim_end_matches('/'), path);
+        let mut request = self
+            .http_client
+            .request(method, url)
+            .bearer_auth(access_token)
+            .header("Notion-Version", NOTION_VERSION)
+            .query(query);
+        if let Some(body) = body {
+            request = request.json(&body);
+        }
+        let response = request
+            .send()
+            .await
+            .map_err(|e| SyncError::transient(format!("Notion {} failed: {}", path, e)))?;
+
+        let status = response.status();
+        if status == StatusCode::TOO_MANY_REQUESTS {
+            let retry_after = response
+                .headers()
+                .get("Retry-After")
+                .and_then(|h| h.to_str().ok())
+                .and_then(|s| s.parse::<u64>().ok());
+            return Err(SyncError::rate_limited(retry_after));
+        }
+        if status == StatusCode::UNAUTHORIZED {
+            return Err(SyncError::unauthorized(format!(
+                "Notion {} failed: {}",
+                path, status
+            )));
+        }
+        if status.is_server_error() {
+            return Err(SyncError::transient(format!(
+                "Notion {} failed: {}",
+                path, status
+            )));
+        }
+        if !status.is_success() {
+            let body: serde_json::Value = response.json().await.unwrap_or_default();
+            return Err(SyncError::permanent(format!(
+                "Notion {} failed: {} {}",
+                path,
+                status,
+                body.get("code").and_then(|v| v.as_str()).unwrap_or("")
+            ))
+            .with_details(serde_json::json!({ "status": status.as_u16() })));
+        }
+
+        response.json().await.map_err(|e| {
+            SyncError::permanent(format!("Unexpected Notion {} response: {}", path, e))
+        })
+    }
+
+    /// Walk `/v1/search` from the window, newest edit first.
+    ///
+    /// Returns the signals, ids of pages edited since the window start, the
+    /// updated window and whether pages remain.
+    async fn search_since(
+        &self,
+        access_token: &str,
+        connection: &Connection,
+        mut window: SearchWindow,
+    ) -> Result<(Vec<Signal>, Vec<String>, SearchWindow, bool), SyncError> {
+        let mut signals = Vec::new();
+        let mut edited_pages = Vec::new();
+        for _ in 0..MAX_SEARCH_PAGES_PER_SYNC {
+            let mut body = serde_json::json!({
+                "sort": {"direction": "descending", "timestamp": "last_edited_time"},
+                "page_size": PAGE_SIZE,
+            });
+            if let Some(start_cursor) = &window.start_cursor {
+                body["start_cursor"] = serde_json::Value::String(start_cursor.clone());
+            }
+            let page = self
+                .api_request(access_token, Method::POST, "search", &[], Some(body))
+                .await?;
+
+            let mut caught_up = false;
+            for object in &page.results {
+                let Some(edited) = timestamp(object, "last_edited_time") else {
+                    continue;
+                };
+                // Notion rounds edit times to the minute, so the boundary minute is read
+                // again and deduplicated by key
+                if window.since.is_some_and(|since| edited < since) {
+                    caught_up = true;
+                    break;
+                }
+                if window.high_water.is_none_or(|high| edited > high) {
+                    window.high_water = Some(edited);
+                }
+                let Some(kind) = normalize_notion_object_kind(object, window.since) else {
+                    continue;
+                };
+                if kind != SignalKind::FileDeleted
+                    && object.get("object").and_then(|v| v.as_str()) == Some("page")
+                    && let Some(id) = object.get("id").and_then(|v| v.as_str())
+                {
+                    edited_pages.push(id.to_string());
+                }
+                signals.push(build_object_signal(connection, kind, object));
+            }
+
+            match page.next_cursor.filter(|_| page.has_more && !caught_up) {
+                Some(next) => window.start_cursor = Some(next),
+                None => {
+                    window.start_cursor = None;
+                    if let Some(high_water) = window.high_water.take() {
+                        window.since = Some(high_water);
+                    }
+                    return Ok((signals, edited_pages, window, false));
+                }
+            }
+        }
+        Ok((signals, edited_pages, window, true))
+    }
+
+    /// Comments on a page created at or after `since`
+    async fn page_comments(
+        &self,
+        access_token: &str,
+        connection: &Connection,
+        page_id: &str,
+        since: Option<DateTime<Utc>>,
+    ) -> Result<Vec<Signal>, SyncError> {
+        let mut signals = Vec::new();
+        let mut start_cursor: Option<String> = None;
+        loop {
+            let mut query = vec![
+                ("block_id", page_id.to_string()),
+                ("page_size", PAGE_SIZE.to_string()),
+            ];
+            if let Some(start_cursor) = &start_cursor {
+                query.push(("start_cursor", start_cursor.clone()));
+            }
+            let page = match self
+                .api_request(access_token, Method::GET, "comments", &query, None)
+                .await
+            {
+                Ok(page) => page,
+                // The integration lacks the read comments capability
+                Err(err)
+                    if err
+                        .details
+                        .as_ref()
+                        .and_then(|d| d.get("status"))
+                        .and_then(|v| v.as_u64())
+                        == Some(403) =>
+                {
+                    warn!(page_id = %page_id, "Skipping Notion comments without read access");
+                    return Ok(signals);
+                }
+                Err(err) => return Err(err),
+            };
+
+            signals.extend(
+                page.results
+                    .iter()
+                    .filter(|comment| {
+                        since.is_none_or(|since| {
+                            timestamp(comment, "created_time").is_some_and(|t| t >= since)
+                        })
+                    })
+                    .filter(|comment| normalize_notion_object_kind(comment, since).is_some())
+                    .map(|comment| build_comment_signal(connection, page_id, comment)),
+            );
+
+            match page.next_cursor.filter(|_| page.has_more) {
+                Some(next) => start_cursor = Some(next),
+                None => return Ok(signals),
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl Connector for NotionConnector {
+    async fn authorize(
+        &self,
+        params: AuthorizeParams,
+    ) -> Result<Url, Box<dyn std::error::Error + Send + Sync>> {
+        info!(
+            tenant_id = %params.tenant_id,
+            "Generating Notion OAuth authorization URL"
+        );
+
+        let mut url = Url::parse(&format!(
+            "{}/v1/oauth/authorize",
+            self.api_base.trim_end_matches('/')
+        ))?;
+        let redirect_uri = params
+            .redirect_uri
+            .unwrap_or_else(Self::default_redirect_uri);
+        let state = params
+            .state
+            .filter(|s| !s.is_empty())
+            .unwrap_or_else(|| Uuid::new_v4().to_string());
+        url.query_pairs_mut()
+            .append_pair("client_id", &self.client_id)
+            .append_pair("response_type", "code")
+            .append_pair("owner", "user")
+            .append_pair("redirect_uri", &redirect_uri)
+            .append_pair("state", &state);
+
+        debug!(
+            tenant_id = %params.tenant_id,
+            authorize_url = %url,
+            "Generated Notion OAuth authorization URL"
+        );
+        Ok(url)
+    }
+
+    async fn exchange_token(
+        &self,
+        params: ExchangeTokenParams,
+    ) -> Result<Connection, Box<dyn std::error::Error + Send + Sync>> {
+        info!(
+            tenant_id = %params.tenant_id,
+            "Exchanging Notion authorization code for tokens"
+        );
+
+        let redirect_uri = params
+            .redirect_uri
+            .unwrap_or_else(Self::default_redirect_uri);
+        let token = self
+            .oauth_token(serde_json::json!({
+                "grant_type": "authorization_code",
+                "code": params.code,
+                "redirect_uri": redirect_uri,
+            }))
+            .await?;
+        let now = DateTime::from(Utc::now());
+
+        Ok(Connection {
+            id: Uuid::new_v4(),
+            tenant_id: params.tenant_id,
+            provider_slug: NOTION_PROVIDER_SLUG.to_string(),
+            external_id: token.workspace_id.clone(),
+            status: "active".to_string(),
+            display_name: token.workspace_name.clone(),
+            access_token_ciphertext: None,
+            refresh_token_ciphertext: None,
+            access_token: Some(token.access_token),
+            refresh_token: token.refresh_token,
+            expires_at: token
+                .expires_in
+                .map(|seconds| now + chrono::Duration::seconds(seconds)),
+            // Notion grants the capabilities configured on the integration, not OAuth scopes
+            scopes: None,
+            metadata: Some(serde_json::json!({
+                "provider": NOTION_PROVIDER_SLUG,
+                "workspace": {
+                    "id": token.workspace_id,
+                    "name": token.workspace_name,
+                    "icon": token.workspace_icon,
+                },
+                "bot_id": token.bot_id,
+                "owner": token.owner,
+            })),
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    async fn refresh_token(
+        &self,
+        connection: Connection,
+    ) -> Result<Connection, Box<dyn std::error::Error + Send + Sync>> {
+        // Tokens issued without a refresh token do not expire
+        let Some(refresh_token) = connection
+            .refresh_token
+            .clone()
+            .filter(|token| !token.is_empty())
+        else {
+            return Err(SyncError::unauthorized("Notion connection has no refresh token").into());
+        };
+
+        info!(connection_id = %connection.id, "Refreshing Notion token");
+        let token = self
+            .oauth_token(serde_json::json!({
+                "grant_type": "refresh_token",
+                "refresh_token": refresh_token,
+            }))
+            .await?;
+        let now = DateTime::from(Utc::now());
+
+        Ok(Connection {
+            access_token: Some(token.access_token),
+            refresh_token: token.refresh_token.or(connection.refresh_token.clone()),
+            expires_at: token
+                .expires_in
+                .map(|seconds| now + chrono::Duration::seconds(seconds)),
+            updated_at: now,
+            ..connection
+        })
+    }
+
+    async fn sync(
+        &self,
+        params: SyncParams,
+    ) -> Result<SyncResult, Box<dyn std::error::Error + Send + Sync>> {
+        let connection = &params.connection;
+        info!(
+            tenant_id = %connection.tenant_id,
+            connection_id = %connection.id,
+            has_cursor = %params.cursor.is_some(),
+            "Starting Notion sync"
+        );
+
+        let access_token = Self::access_token(connection)?;
+        let window: SearchWindow = params
+            .cursor
+            .as_ref()
+            .and_then(|cursor| serde_json::from_value(cursor.as_json().clone()).ok())
+            .unwrap_or_default();
+        let since = window.since;
+
+        let (mut signals, edited_pages, window, has_more) =
+            self.search_since(&access_token, connection, window).await?;
+        for page_id in &edited_pages {
+            signals.extend(
+                self.page_comments(&access_token, connection, page_id, since)
+                    .await?,
+            );
+        }
+
+        debug!(
+            connection_id = %connection.id,
+            pages = edited_pages.len(),
+            signals = signals.len(),
+            has_more,
+            "Notion sync completed"
+        );
+
+        Ok(SyncResult {
+            signals,
+            next_cursor: Some(Cursor::from_json(serde_json::to_value(&window)?)),
+            has_more,
+        })
+    }
+
+    async fn handle_webhook(
+        &self,
+        params: WebhookParams,
+    ) -> Result<Vec<Signal>, Box<dyn std::error::Error + Send + Sync>> {
+        // Changes arrive through incremental sync
+        debug!(
+            tenant_id = %params.tenant_id,
+            "Ignoring Notion webhook payload"
+        );
+        Ok(vec![])
+    }
+}
+
+/// Register the Notion connector in the registry
+pub fn register_notion_connector(registry: &mut Registry, connector: Arc<NotionConnector>) {
+    let metadata = ProviderMetadata::new(
+        NOTION_PROVIDER_SLUG.to_string(),
+        AuthType::OAuth2,
+        vec![],
+        false, // changes are picked up by incremental sync
+    )
+    .with_category(ProviderCategory::Files)
+    .with_description("Sync page, database and comment activity from Notion")
+    .with_icon_url("https://cdn.simpleicons.org/notion")
+    .with_docs_url("https://developers.notion.com/docs/authorization")
+    .with_backfill(true)
+    .with_signal_kinds(&[
+        SignalKind::FileCreated,
+        SignalKind::FileUpdated,
+        SignalKind::FileDeleted,
+        SignalKind::IssueComment,
+    ]);
+
+    registry.register(connector, metadata);
+}
+
+fn timestamp(object: &serde_json::Value, key: &str) -> Option<DateTime<Utc>> {
+    object
+        .get(key)
+        .and_then(|v| v.as_str())
+        .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+        .map(|dt| dt.with_timezone(&Utc))
+}
+
+/// Join the `plain_text` of a rich text array
+fn plain_text(rich_text: Option<&serde_json::Value>) -> Option<String> {
+    let text: String = rich_text?
+        .as_array()?
+        .iter()
+        .filter_map(|part| part.get("plain_text").and_then(|v| v.as_str()))
+        .collect();
+    (!text.is_empty()).then_some(text)
+}
+
+/// Title of a page (its `title` property) or database (its top-level `title`)
+fn object_title(object: &serde_json::Value) -> Option<String> {
+    plain_text(object.get("title")).or_else(|| {
+        object
+            .get("properties")?
+            .as_object()?
+            .values()
+            .find(|property| property.get("type").and_then(|v| v.as_str()) == Some("title"))
+            .and_then(|property| plain_text(property.get("title")))
+    })
+}
+
+/// Build a signal from a page or database returned by search
+fn build_object_signal(
+    connection: &Connection,
+    kind: SignalKind,
+    object: &serde_json::Value,
+) -> Signal {
+    let str_field = |key: &str| object.get(key).and_then(|v| v.as_str()).unwrap_or("");
+    let id = str_field("id");
+    let last_edited_time = str_field("last_edited_time");
+    let received_at = DateTime::from(Utc::now());
+    let occurred_at = timestamp(object, "last_edited_time").unwrap_or_else(Utc::now);
+
+    Signal {
+        id: Uuid::new_v4(),
+        tenant_id: connection.tenant_id,
+        provider_slug: NOTION_PROVIDER_SLUG.to_string(),
+        connection_id: connection.id,
+        kind: kind.as_str().to_string(),
+        occurred_at: occurred_at.into(),
+        received_at,
+        payload: serde_json::json!({
+            "workspace_id": connection.external_id,
+            "object": str_field("object"),
+            "object_id": id,
+            "title": object_title(object),
+            "url": object.get("url"),
+            "parent": object.get("parent"),
+            "created_time": object.get("created_time"),
+            "last_edited_time": last_edited_time,
+            "last_edited_by": object.pointer("/last_edited_by/id"),
+            "occurred_at": occurred_at.to_rfc3339(),
+        }),
+        dedupe_key: Some(format!("notion:{}:{}:{}", kind, id, last_edited_time)),
+        created_at: received_at,
+        updated_at: received_at,
+    }
+}
+
+/// Build a signal from a comment on `page_id`
+fn build_comment_signal(
+    connection: &Connection,
+    page_id: &str,
+    comment: &serde_json::Value,
+) -> Signal {
+    let kind = SignalKind::IssueComment;
+    let id = comment.get("id").and_then(|v| v.as_str()).unwrap_or("");
+    let received_at = DateTime::from(Utc::now());
+    let occurred_at = timestamp(comment, "created_time").unwrap_or_else(Utc::now);
+
+    Signal {
+        id: Uuid::new_v4(),
+        tenant_id: connection.tenant_id,
+        provider_slug: NOTION_PROVIDER_SLUG.to_string(),
+        connection_id: connection.id,
+        kind: kind.as_str().to_string(),
+        occurred_at: occurred_at.into(),
+        received_at,
+        payload: serde_json::json!({
+            "workspace_id": connection.external_id,
+            "page_id": page_id,
+            "comment_id": id,
+            "discussion_id": comment.get("discussion_id"),
+            "text": plain_text(comment.get("rich_text")),
+            "created_by": comment.pointer("/created_by/id"),
+            "occurred_at": occurred_at.to_rfc3339(),
+        }),
+        dedupe_key: Some(format!("notion:{}:{}", kind, id)),
+        created_at: received_at,
+        updated_at: received_at,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{body_partial_json, header, method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn connector(base: &str) -> NotionConnector {
+        NotionConnector::new(
+            "client-id".to_string(),
+            "client-secret".to_string(),
+            base.to_string(),
+        )
+    }
+
+    fn connection() -> Connection {
+        let now = DateTime::from(Utc::now());
+        Connection {
+            id: Uuid::new_v4(),
+            tenant_id: Uuid::new_v4(),
+            provider_slug: NOTION_PROVIDER_SLUG.to_string(),
+            external_id: "ws-1".to_string(),
+            status: "active".to_string(),
+            display_name: Some("Acme".to_string()),
+            access_token_ciphertext: None,
+            refresh_token_ciphertext: None,
+            access_token: Some("secret_test".to_string()),
+            refresh_token: None,
+            expires_at: None,
+            scopes: None,
+            metadata: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    fn page(id: &str, created: &str, edited: &str) -> serde_json::Value {
+        serde_json::json!({
+            "object": "page",
+            "id": id,
+            "created_time": created,
+            "last_edited_time": edited,
+            "in_trash": false,
+            "url": format!("https://www.notion.so/{}", id),
+            "properties": {
+                "Name": {"id": "title", "type": "title",
+                         "title": [{"plain_text": "Roadmap "}, {"plain_text": "Q3"}]}
+            }
+        })
+    }
+
+    #[tokio::test]
+    async fn test_notion_authorize_url_shape() {
+        let url = connector("https://api.notion.com")
+            .authorize(AuthorizeParams {
+                tenant_id: Uuid::new_v4(),
+                redirect_uri: Some("https://app.example.com/callback".to_string()),
+                state: Some("state-1".to_string()),
+                code_challenge: None,
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(url.path(), "/v1/oauth/authorize");
+        let query: std::collections::HashMap<_, _> = url.query_pairs().collect();
+        assert_eq!(query["client_id"], "client-id");
+        assert_eq!(query["response_type"], "code");
+        assert_eq!(query["owner"], "user");
+        assert_eq!(query["state"], "state-1");
+    }
+
+    #[tokio::test]
+    async fn test_notion_exchange_token_records_workspace() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/oauth/token"))
+            .and(header(
+                "authorization",
+                "Basic Y2xpZW50LWlkOmNsaWVudC1zZWNyZXQ=",
+            ))
+            .and(body_partial_json(
+                serde_json::json!({"grant_type": "authorization_code", "code": "code"}),
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "access_token": "secret_new",
+                "token_type": "bearer",
+                "bot_id": "bot-1",
+                "workspace_id": "ws-1",
+                "workspace_name": "Acme",
+                "workspace_icon": null,
+                "owner": {"type": "user", "user": {"id": "u-1"}}
+            })))
+            .mount(&server)
+            .await;
+
+        let connection = connector(&server.uri())
+            .exchange_token(ExchangeTokenParams {
+                code: "code".to_string(),
+                redirect_uri: None,
+                tenant_id: Uuid::new_v4(),
+                code_verifier: None,
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(connection.external_id, "ws-1");
+        assert_eq!(connection.display_name.as_deref(), Some("Acme"));
+        assert_eq!(connection.access_token.as_deref(), Some("secret_new"));
+        assert!(connection.expires_at.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_notion_sync_stops_at_high_water_and_reads_comments() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/search"))
+            .and(header("notion-version", NOTION_VERSION))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "object": "list",
+                "results": [
+                    page("p-new", "2024-05-02T10:00:00.000Z", "2024-05-02T10:00:00.000Z"),
+                    page("p-edited", "2024-04-01T09:00:00.000Z", "2024-05-02T09:00:00.000Z"),
+                    page("p-old", "2024-04-01T09:00:00.000Z", "2024-04-30T09:00:00.000Z")
+                ],
+                "has_more": true,
+                "next_cursor": "next-page"
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v1/comments"))
+            .and(query_param("block_id", "p-edited"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "object": "list",
+                "results": [
+                    {"object": "comment", "id": "c-old", "discussion_id": "d-1",
+                     "created_time": "2024-04-02T09:00:00.000Z",
+                     "created_by": {"object": "user", "id": "u-1"},
+                     "rich_text": [{"plain_text": "earlier"}]},
+                    {"object": "comment", "id": "c-new", "discussion_id": "d-1",
+                     "created_time": "2024-05-02T09:00:00.000Z",
+                     "created_by": {"object": "user", "id": "u-2"},
+                     "rich_text": [{"plain_text": "Looks good"}]}
+                ],
+                "has_more": false,
+                "next_cursor": null
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v1/comments"))
+            .and(query_param("block_id", "p-new"))
+            .respond_with(ResponseTemplate::new(403).set_body_json(serde_json::json!({
+                "object": "error", "status": 403, "code": "restricted_resource"
+            })))
+            .mount(&server)
+            .await;
+
+        let cursor = Cursor::from_json(serde_json::json!({"since": "2024-05-01T00:00:00Z"}));
+        let result = connector(&server.uri())
+            .sync(SyncParams {
+                connection: connection(),
+                cursor: Some(cursor),
+                cancellation: Default::default(),
+            })
+            .await
+            .unwrap();
+
+        let kinds: Vec<_> = result.signals.iter().map(|s| s.kind.as_str()).collect();
+        assert_eq!(kinds, vec!["file_created", "file_updated", "issue_comment"]);
+        assert_eq!(
+            result.signals[1].dedupe_key.as_deref(),
+            Some("notion:file_updated:p-edited:2024-05-02T09:00:00.000Z")
+        );
+        assert_eq!(result.signals[1].payload["title"], "Roadmap Q3");
+        assert_eq!(result.signals[2].payload["text"], "Looks good");
+        assert_eq!(result.signals[2].payload["page_id"], "p-edited");
+        // Reaching an object edited before the high-water mark ends the walk
+        assert!(!result.has_more);
+        assert_eq!(
+            result.next_cursor.unwrap().as_json(),
+            &serde_json::json!({"since": "2024-05-02T10:00:00Z"})
+        );
+    }
+
+    #[tokio::test]
+    async fn test_notion_sync_maps_auth_and_rate_limit_errors() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/search"))
+            .respond_with(ResponseTemplate::new(401).set_body_json(serde_json::json!({
+                "object": "error", "status": 401, "code": "unauthorized"
+            })))
+            .mount(&server)
+            .await;
+        let err = connector(&server.uri())
+            .sync(SyncParams {
+                connection: connection(),
+                cursor: None,
+                cancellation: Default::default(),
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<SyncError>().unwrap().kind,
+            crate::connectors::SyncErrorKind::Unauthorized
+        );
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/search"))
+            .respond_with(ResponseTemplate::new(429).insert_header("Retry-After", "3"))
+            .mount(&server)
+            .await;
+        let err = connector(&server.uri())
+            .sync(SyncParams {
+                connection: connection(),
+                cursor: None,
+                cancellation: Default::default(),
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<SyncError>().unwrap().kind,
+            crate::connectors::SyncErrorKind::RateLimited {
+                retry_after_secs: Some(3)
+            }
+        );
+    }
+}
@@ -0,0 +1,900 @@
+//! Outlook Mail connector implementation
+//!
+//! Microsoft 365 and Outlook.com mail through Microsoft Graph. Each sync runs
+//! a `changeType=created` delta query over the Inbox and Sent Items folders
+//! and resumes from the delta links stored in the cursor, so only messages
+//! that arrived since the previous run are read. Received messages pass the
+//! shared [`MailSpamFilter`](crate::mail::MailSpamFilter) before they become
+//! `email_received` signals; sent messages become `email_sent`.
+
+use anyhow::anyhow;
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, SecondsFormat, Utc};
+use reqwest::{Client, StatusCode};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use tracing::{debug, info, warn};
+use url::Url;
+use uuid::Uuid;
+
+use crate::connectors::{
+    AuthType, Connector, Cursor, ProviderCategory, ProviderMetadata, Registry,
+    trait_::{
+        AuthorizeParams, ExchangeTokenParams, SyncError, SyncParams, SyncResult, WebhookParams,
+    },
+};
+use crate::mail::MailSpamFilter;
+use crate::mail::integration::{MailMetadataParams, create_outlook_metadata, should_create_signal};
+use crate::models::{connection::Model as Connection, signal::Model as Signal};
+use crate::normalization::SignalKind;
+
+/// Provider slug
+pub const OUTLOOK_PROVIDER_SLUG: &str = "outlook";
+
+/// Scopes requested at authorization time
+pub const OUTLOOK_SCOPES: &[&str] = &["User.Read", "Mail.Read"];
+
+/// Folders synced, by Graph well-known name, with the signal their messages produce
+const SYNCED_FOLDERS: &[(&str, SignalKind)] = &[
+    ("inbox", SignalKind::EmailReceived),
+    ("sentitems", SignalKind::EmailSent),
+];
+
+/// Message fields requested from the delta query
+const MESSAGE_FIELDS: &str = "id,internetMessageId,conversationId,subject,from,toRecipients,\
+receivedDateTime,sentDateTime,hasAttachments,isDraft,categories,internetMessageHeaders,webLink";
+
+/// Messages requested per delta page
+const PAGE_SIZE: u32 = 50;
+
+/// Delta pages fetched per folder in one sync; the rest continues on the next run
+const MAX_DELTA_PAGES_PER_SYNC: u32 = 10;
+
+/// How far back the first sync reads
+const INITIAL_LOOKBACK_DAYS: i64 = 7;
+
+/// Outlook Mail connector
+pub struct OutlookConnector {
+    client_id: String,
+    client_secret: String,
+    oauth_base: String,
+    api_base: String,
+    http_client: Client,
+    spam_filter: Arc<dyn MailSpamFilter>,
+}
+
+/// Link each folder's delta query resumes from, keyed by folder name
+///
+/// A `@odata.nextLink` while a walk is unfinished, the `@odata.deltaLink` once
+/// it completes.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+struct DeltaLinks {
+    #[serde(default)]
+    folders: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MicrosoftTokenResponse {
+    access_token: String,
+    #[serde(default)]
+    refresh_token: Option<String>,
+    #[serde(default)]
+    expires_in: Option<i64>,
+    #[serde(default)]
+    scope: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GraphUser {
+    id: String,
+    #[serde(default)]
+    display_name: Option<String>,
+    #[serde(default)]
+    mail: Option<String>,
+    #[serde(default)]
+    user_principal_name: Option<String>,
+}
+
+impl OutlookConnector {
+    /// Create a new Outlook connector with the Microsoft identity platform configuration
+    pub fn new(
+        client_id: String,
+        client_secret: String,
+        oauth_base: String,
+        api_base: String,
+        spam_filter: Arc<dyn MailSpamFilter>,
+    ) -> Self {
+        Self {
+            client_id,
+            client_secret,
+            oauth_base: oauth_base.trim_end_matches('/').to_string(),
+            api_base: api_base.trim_end_matches('/').to_string(),
+            http_client: crate::egress::client(),
+            spam_filter,
+        }
+    }
+
+    fn default_redirect_uri() -> String {
+        match std::env::var("POBLYSH_PROFILE").as_deref() {
+            Ok("local") | Ok("test") | Err(_) => "http://localhost:3000/callback".to_string(),
+            Ok(_) => "https://app.poblysh.com/callback".to_string(),
+        }
+    }
+
+    fn requested_scopes() -> String {
+        let mut scopes = OUTLOOK_SCOPES.to_vec();
+        scopes.push("offline_access");
+        scopes.join(" ")
+    }
+
+    fn access_token(connection: &Connection) -> Result<String, SyncError> {
+        connection
+            .access_token
+            .clone()
+            .filter(|token| !token.is_empty())
+            .ok_or_else(|| SyncError::unauthorized("Missing Outlook access token"))
+    }
+
+    /// Call the `/token` endpoint with the given grant
+    async fn oauth_token(
+        &self,
+        grant: &[(&str, &str)],
+    ) -> Result<MicrosoftTokenResponse, anyhow::Error> {
+        let scopes = Self::requested_scopes();
+        let mut form = vec![
+            ("client_id", self.client_id.as_str()),
+            ("client_secret", self.client_secret.as_str()),
+            ("scope", scopes.as_str()),
+        ];
+        form.extend_from_slice(grant);
+
+        let response = self
+            .http_client
+            .post(format!("{}/token", self.oauth_base))
+            .form(&form)
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let body: serde_json::Value = response.json().await.unwrap_or_default();
+            return Err(anyhow!(
+                "Microsoft token endpoint returned {}: {}",
+                status,
+                body.get("error")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("unknown_error")
+            ));
+        }
+        Ok(response.json().await?)
+    }
+
+    /// GET a Graph URL, mapping HTTP failures to sync errors
+    ///
+    /// Returns `None` when Graph answers `410 Gone`, which it does for delta
+    /// links whose sync state has expired.
+    async fn get(
+        &self,
+        access_token: &str,
+        url: &str,
+    ) -> Result<Option<serde_json::Value>, SyncError> {
+        let response = self
+            .http_client
+            .get(url)
+            .bearer_auth(access_token)
+            .header("Accept", "application/json")
+            .header("Prefer", format!("odata.maxpagesize={}", PAGE_SIZE))
+            .send()
+            .await
+            .map_err(|e| SyncError::transient(format!("Outlook request failed: {}", e)))?;
+
+        let status = response.status();
+        if status == StatusCode::GONE {
+            return Ok(None);
+        }
+        if status == StatusCode::TOO_MANY_REQUESTS {
+            let retry_after = response
+                .headers()
+                .get("Retry-After")
+                .and_then(|h| h.to_str().ok())
+                .and_then(|s| s.parse::<u64>().ok());
+            return Err(SyncError::rate_limited(retry_after));
+        }
+        if status == StatusCode::UNAUTHORIZED {
+            return Err(SyncError::unauthorized(format!(
+                "Outlook request failed: {}",
+                status
+            )));
+        }
+        if status.is_server_error() {
+            return Err(SyncError::transient(format!(
+                "Outlook request failed: {}",
+                status
+            )));
+        }
+        if !status.is_success() {
+            let body: serde_json::Value = response.json().await.unwrap_or_default();
+            return Err(
+                SyncError::permanent(format!("Outlook request failed: {}", status))
+                    .with_details(body),
+            );
+        }
+
+        response
+            .json()
+            .await
+            .map(Some)
+            .map_err(|e| SyncError::transient(format!("Outlook returned bad JSON: {}", e)))
+    }
+
+    /// Delta query that starts a folder's walk from the lookback window
+    fn initial_delta_url(&self, folder: &str) -> String {
+        let since = (Utc::now() - Duration::days(INITIAL_LOOKBACK_DAYS))
+            .to_rfc3339_opts(SecondsFormat::Secs, true);
+        let mut url = Url::parse(&format!(
+            "{}/me/mailFolders/{}/messages/delta",
+            self.api_base, folder
+        ))
+        .expect("valid Graph URL");
+        url.query_pairs_mut()
+            .append_pair("changeType", "created")
+            .append_pair("$select", MESSAGE_FIELDS)
+            .append_pair("$filter", &format!("receivedDateTime ge {}", since));
+        url.to_string()
+    }
+
+    /// Walk one folder's delta query from `link`
+    ///
+    /// Returns the signals, the link to resume from and whether pages remain.
+    async fn sync_folder(
+        &self,
+        access_token: &str,
+        connection: &Connection,
+        folder: &str,
+        kind: SignalKind,
+        link: Option<String>,
+    ) -> Result<(Vec<Signal>, String, bool), SyncError> {
+        // Links come back from Graph; never send the token anywhere else
+        let mut url = link
+            .filter(|link| link.starts_with(&self.api_base))
+            .unwrap_or_else(|| self.initial_delta_url(folder));
+        let mut restarted = false;
+        let mut signals = Vec::new();
+
+        for _ in 0..MAX_DELTA_PAGES_PER_SYNC {
+            let Some(body) = self.get(access_token, &url).await? else {
+                if restarted {
+                    return Err(SyncError::transient(format!(
+                        "Outlook delta query for {} keeps expiring",
+                        folder
+                    )));
+                }
+                warn!(
+                    connection_id = %connection.id,
+                    folder,
+                    "Outlook delta link expired, restarting from the lookback window"
+                );
+                url = self.initial_delta_url(folder);
+                restarted = true;
+                continue;
+            };
+
+            let messages = body
+                .get("value")
+                .and_then(|v| v.as_array())
+                .cloned()
+                .unwrap_or_default();
+            for message in &messages {
+                if message.get("@removed").is_some()
+                    || message.get("isDraft").and_then(|v| v.as_bool()) == Some(true)
+                {
+                    continue;
+                }
+                if kind == SignalKind::EmailReceived
+                    && !self.passes_spam_filter(connection, message)
+                {
+                    continue;
+                }
+                signals.push(build_message_signal(connection, kind, folder, message));
+            }
+
+            if let Some(delta) = body.get("@odata.deltaLink").and_then(|v| v.as_str()) {
+                return Ok((signals, delta.to_string(), false));
+            }
+            match body.get("@odata.nextLink").and_then(|v| v.as_str()) {
+                Some(next) => url = next.to_string(),
+                None => {
+                    return Err(SyncError::transient(format!(
+                        "Outlook delta page for {} had no next or delta link",
+                        folder
+                    )));
+                }
+            }
+        }
+
+        Ok((signals, url, true))
+    }
+
+    /// Run a received message through the spam filter
+    fn passes_spam_filter(&self, connection: &Connection, message: &serde_json::Value) -> bool {
+        let message_id = message
+            .get("id")
+            .and_then(|v| v.as_str())
+            .unwrap_or_default();
+        let headers: HashMap<String, String> = message
+            .get("internetMessageHeaders")
+            .and_then(|v| v.as_array())
+            .into_iter()
+            .flatten()
+            .filter_map(|header| {
+                Some((
+                    header.get("name")?.as_str()?.to_ascii_lowercase(),
+                    header.get("value")?.as_str()?.to_string(),
+                ))
+            })
+            .collect();
+        let metadata = create_outlook_metadata(MailMetadataParams {
+            message_id: message_id.to_string(),
+            labels: message
+                .get("categories")
+                .and_then(|v| v.as_array())
+                .into_iter()
+                .flatten()
+                .filter_map(|v| v.as_str().map(str::to_string))
+                .collect(),
+            subject: message
+                .get("subject")
+                .and_then(|v| v.as_str())
+                .map(str::to_string),
+            from: email_address(message.get("from")),
+            to: recipients(message),
+            headers,
+            has_attachments: message
+                .get("hasAttachments")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false),
+            attachment_extensions: Vec::new(),
+        });
+        should_create_signal(
+            &self.spam_filter,
+            &metadata,
+            OUTLOOK_PROVIDER_SLUG,
+            connection.id,
+            message_id,
+        )
+    }
+}
+
+#[async_trait]
+impl Connector for OutlookConnector {
+    async fn authorize(
+        &self,
+        params: AuthorizeParams,
+    ) -> Result<Url, Box<dyn std::error::Error + Send + Sync>> {
+        info!(
+            tenant_id = %params.tenant_id,
+            "Generating Outlook OAuth authorization URL"
+        );
+
+        let mut url = Url::parse(&format!("{}/authorize", self.oauth_base))?;
+        let redirect_uri = params
+            .redirect_uri
+            .unwrap_or_else(Self::default_redirect_uri);
+        let state = params
+            .state
+            .filter(|s| !s.is_empty())
+            .unwrap_or_else(|| Uuid::new_v4().to_string());
+        url.query_pairs_mut()
+            .append_pair("client_id", &self.client_id)
+            .append_pair("response_type", "code")
+            .append_pair("response_mode", "query")
+            .append_pair("scope", &Self::requested_scopes())
+            .append_pair("redirect_uri", &redirect_uri)
+            .append_pair("state", &state);
+
+        debug!(
+            tenant_id = %params.tenant_id,
+            authorize_url = %url,
+            "Generated Outlook OAuth authorization URL"
+        );
+        Ok(url)
+    }
+
+    async fn exchange_token(
+        &self,
+        params: ExchangeTokenParams,
+    ) -> Result<Connection, Box<dyn std::error::Error + Send + Sync>> {
+        info!(
+            tenant_id = %params.tenant_id,
+            "Exchanging Outlook authorization code for tokens"
+        );
+
+        let redirect_uri = params
+            .redirect_uri
+            .unwrap_or_else(Self::default_redirect_uri);
+        let token = self
+            .oauth_token(&[
+                ("grant_type", "authorization_code"),
+                ("code", &params.code),
+                ("redirect_uri", &redirect_uri),
+            ])
+            .await?;
+
+        let me = self
+            .get(&token.access_token, &format!("{}/me", self.api_base))
+            .await?
+            .ok_or_else(|| anyhow!("Microsoft Graph returned no profile"))?;
+        let user: GraphUser = serde_json::from_value(me)?;
+        let address = user.mail.clone().or(user.user_principal_name.clone());
+        let now = DateTime::from(Utc::now());
+
+        Ok(Connection {
+            id: Uuid::new_v4(),
+            tenant_id: params.tenant_id,
+            provider_slug: OUTLOOK_PROVIDER_SLUG.to_string(),
+            external_id: user.id.clone(),
+            status: "active".to_string(),
+            display_name: address.clone().or(user.display_name.clone()),
+            access_token_ciphertext: None,
+            refresh_token_ciphertext: None,
+            access_token: Some(token.access_token),
+            refresh_token: token.refresh_token,
+            expires_at: token
+                .expires_in
+                .map(|seconds| now + chrono::Duration::seconds(seconds)),
+            scopes: token.scope.map(|scope| {
+                serde_json::Value::Array(
+                    scope
+                        .split_whitespace()
+                        .map(|s| serde_json::Value::String(s.to_string()))
+                        .collect(),
+                )
+            }),
+            metadata: Some(serde_json::json!({
+                "provider": OUTLOOK_PROVIDER_SLUG,
+                "user": {
+                    "id": user.id,
+                    "display_name": user.display_name,
+                    "email": address,
+                },
+            })),
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    async fn refresh_token(
+        &self,
+        connection: Connection,
+    ) -> Result<Connection, Box<dyn std::error::Error + Send + Sync>> {
+        let Some(refresh_token) = connection
+            .refresh_token
+            .clone()
+            .filter(|token| !token.is_empty())
+        else {
+            return Err(SyncError::unauthorized("Outlook connection has no refresh token").into());
+        };
+
+        info!(connection_id = %connection.id, "Refreshing Outlook token");
+        let token = self
+            .oauth_token(&[
+                ("grant_type", "refresh_token"),
+                ("refresh_token", &refresh_token),
+            ])
+            .await?;
+        let now = DateTime::from(Utc::now());
+
+        // Microsoft usually rotates the refresh token; keep the old one otherwise
+        Ok(Connection {
+            access_token: Some(token.access_token),
+            refresh_token: token.refresh_token.or(connection.refresh_token.clone()),
+            expires_at: token
+                .expires_in
+                .map(|seconds| now + chrono::Duration::seconds(seconds)),
+            updated_at: now,
+            ..connection
+        })
+    }
+
+    async fn sync(
+        &self,
+        params: SyncParams,
+    ) -> Result<SyncResult, Box<dyn std::error::Error + Send + Sync>> {
+        let connection = &params.connection;
+        info!(
+            tenant_id = %connection.tenant_id,
+            connection_id = %connection.id,
+            has_cursor = %params.cursor.is_some(),
+            "Starting Outlook sync"
+        );
+
+        let access_token = Self::access_token(connection)?;
+        let mut links: DeltaLinks = params
+            .cursor
+            .as_ref()
+            .and_then(|cursor| serde_json::from_value(cursor.as_json().clone()).ok())
+            .unwrap_or_default();
+
+        let mut signals = Vec::new();
+        let mut has_more = false;
+        for (folder, kind) in SYNCED_FOLDERS {
+            let (folder_signals, link, folder_has_more) = self
+                .sync_folder(
+                    &access_token,
+                    connection,
+                    folder,
+                    *kind,
+                    links.folders.remove(*folder),
+                )
+                .await?;
+            signals.extend(folder_signals);
+            links.folders.insert(folder.to_string(), link);
+            has_more |= folder_has_more;
+        }
+
+        debug!(
+            connection_id = %connection.id,
+            signals = signals.len(),
+            has_more,
+            "Outlook sync completed"
+        );
+
+        Ok(SyncResult {
+            signals,
+            next_cursor: Some(Cursor::from_json(serde_json::to_value(&links)?)),
+            has_more,
+        })
+    }
+
+    async fn handle_webhook(
+        &self,
+        _params: WebhookParams,
+    ) -> Result<Vec<Signal>, Box<dyn std::error::Error + Send + Sync>> {
+        // Graph change notifications need a subscription lifecycle; delta sync covers Outlook for now
+        Err(Box::new(SyncError::permanent(
+            "WEBHOOKS_NOT_SUPPORTED: Webhooks not supported for Outlook connector",
+        )))
+    }
+}
+
+/// Register the Outlook connector in the registry
+pub fn register_outlook_connector(registry: &mut Registry, connector: Arc<OutlookConnector>) {
+    let metadata = ProviderMetadata::new(
+        OUTLOOK_PROVIDER_SLUG.to_string(),
+        AuthType::OAuth2,
+        OUTLOOK_SCOPES.iter().map(|s| s.to_string()).collect(),
+        false, // delta sync only
+    )
+    .with_category(ProviderCategory::Mail)
+    .with_description("Sync received and sent email from Outlook and Microsoft 365")
+    .with_icon_url("https://cdn.simpleicons.org/microsoftoutlook")
+    .with_docs_url("https://learn.microsoft.com/en-us/graph/delta-query-messages")
+    .with_backfill(true)
+    .with_signal_kinds(&[SignalKind::EmailReceived, SignalKind::EmailSent]);
+
+    registry.register(connector, metadata);
+}
+
+/// `emailAddress.address` of a Graph recipient
+fn email_address(recipient: Option<&serde_json::Value>) -> Option<String> {
+    recipient
+        .and_then(|r| r.pointer("/emailAddress/address"))
+        .and_then(|v| v.as_str())
+        .map(str::to_string)
+}
+
+fn recipients(message: &serde_json::Value) -> Vec<String> {
+    message
+        .get("toRecipients")
+        .and_then(|v| v.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|recipient| email_address(Some(recipient)))
+        .collect()
+}
+
+/// Build a signal from a message returned by a folder's delta query
+fn build_message_signal(
+    connection: &Connection,
+    kind: SignalKind,
+    folder: &str,
+    message: &serde_json::Value,
+) -> Signal {
+    let id = message
+        .get("id")
+        .and_then(|v| v.as_str())
+        .unwrap_or_default();
+    // Graph IDs change when a message moves between folders; the RFC 5322 ID does not
+    let stable_id = message
+        .get("internetMessageId")
+        .and_then(|v| v.as_str())
+        .unwrap_or(id);
+    let time_field = if kind == SignalKind::EmailSent {
+        "sentDateTime"
+    } else {
+        "receivedDateTime"
+    };
+    let occurred_at = message
+        .get(time_field)
+        .and_then(|v| v.as_str())
+        .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+        .map(|dt| dt.with_timezone(&Utc))
+        .unwrap_or_else(Utc::now);
+    let received_at = DateTime::from(Utc::now());
+
+    Signal {
+        id: Uuid::new_v4(),
+        tenant_id: connection.tenant_id,
+        provider_slug: OUTLOOK_PROVIDER_SLUG.to_string(),
+        connection_id: connection.id,
+        kind: kind.as_str().to_string(),
+        occurred_at: occurred_at.into(),
+        received_at,
+        payload: serde_json::json!({
+            "message_id": id,
+            "internet_message_id": message.get("internetMessageId"),
+            "conversation_id": message.get("conversationId"),
+            "folder": folder,
+            "subject": message.get("subject"),
+            "from": email_address(message.get("from")),
+            "to": recipients(message),
+            "has_attachments": message.get("hasAttachments"),
+            "url": message.get("webLink"),
+            "occurred_at": occurred_at.to_rfc3339(),
+        }),
+        dedupe_key: Some(format!("outlook:{}:{}", kind, stable_id)),
+        created_at: received_at,
+        updated_at: received_at,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mail::MailSpamRuntimeConfig;
+    use crate::mail::default::DefaultMailSpamFilter;
+    use wiremock::matchers::{body_string_contains, header, method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn connector(base: &str) -> OutlookConnector {
+        OutlookConnector::new(
+            "client-id".to_string(),
+            "client-secret".to_string(),
+            base.to_string(),
+            base.to_string(),
+            Arc::new(DefaultMailSpamFilter::new(
+                MailSpamRuntimeConfig::default().with_denylist(vec!["@spam.example".to_string()]),
+            )),
+        )
+    }
+
+    fn connection() -> Connection {
+        let now = DateTime::from(Utc::now());
+        Connection {
+            id: Uuid::new_v4(),
+            tenant_id: Uuid::new_v4(),
+            provider_slug: OUTLOOK_PROVIDER_SLUG.to_string(),
+            external_id: "user-1".to_string(),
+            status: "active".to_string(),
+            display_name: Some("ada@contoso.com".to_string()),
+            access_token_ciphertext: None,
+            refresh_token_ciphertext: None,
+            access_token: Some("ms-access".to_string()),
+            refresh_token: None,
+            expires_at: None,
+            scopes: None,
+            metadata: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    fn message(id: &str, from: &str, subject: &str) -> serde_json::Value {
+        serde_json::json!({
+            "id": format!("AAMk-{}", id),
+            "internetMessageId": format!("<{}@contoso.com>", id),
+            "conversationId": "conv-1",
+            "subject": subject,
+            "from": {"emailAddress": {"address": from, "name": "Sender"}},
+            "toRecipients": [{"emailAddress": {"address": "ada@contoso.com"}}],
+            "receivedDateTime": "2024-05-02T10:00:00Z",
+            "sentDateTime": "2024-05-02T09:59:58Z",
+            "hasAttachments": false,
+            "isDraft": false,
+            "categories": [],
+            "internetMessageHeaders": [{"name": "Date", "value": "Thu, 2 May 2024 10:00:00 +0000"}],
+            "webLink": format!("https://outlook.office365.com/owa/?ItemID={}", id)
+        })
+    }
+
+    #[tokio::test]
+    async fn test_outlook_authorize_url_requests_offline_access() {
+        let url = connector("https://login.microsoftonline.com/common/oauth2/v2.0")
+            .authorize(AuthorizeParams {
+                tenant_id: Uuid::new_v4(),
+                redirect_uri: Some("https://app.example.com/callback".to_string()),
+                state: Some("state-1".to_string()),
+                code_challenge: None,
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(url.path(), "/common/oauth2/v2.0/authorize");
+        let query: HashMap<_, _> = url.query_pairs().collect();
+        assert_eq!(query["client_id"], "client-id");
+        assert_eq!(query["scope"], "User.Read Mail.Read offline_access");
+        assert_eq!(query["state"], "state-1");
+    }
+
+    #[tokio::test]
+    async fn test_outlook_exchange_token_reads_profile() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/token"))
+            .and(body_string_contains("grant_type=authorization_code"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "access_token": "ms-access",
+                "refresh_token": "ms-refresh",
+                "expires_in": 3600,
+                "scope": "User.Read Mail.Read"
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/me"))
+            .and(header("Authorization", "Bearer ms-access"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "id": "user-1",
+                "displayName": "Ada",
+                "mail": null,
+                "userPrincipalName": "ada@contoso.com"
+            })))
+            .mount(&server)
+            .await;
+
+        let connection = connector(&server.uri())
+            .exchange_token(ExchangeTokenParams {
+                code: "code".to_string(),
+                redirect_uri: None,
+                tenant_id: Uuid::new_v4(),
+                code_verifier: None,
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(connection.external_id, "user-1");
+        assert_eq!(connection.display_name.as_deref(), Some("ada@contoso.com"));
+        assert_eq!(
+            connection.metadata.unwrap()["user"]["email"],
+            "ada@contoso.com"
+        );
+        assert!(connection.expires_at.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_outlook_sync_filters_spam_and_stores_delta_links() {
+        let server = MockServer::start().await;
+        let base = server.uri();
+        Mock::given(method("GET"))
+            .and(path("/me/mailFolders/inbox/messages/delta"))
+            .and(query_param("$skiptoken", "page-2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "value": [message("3", "partner@fabrikam.com", "Contract draft")],
+                "@odata.deltaLink": format!("{}/me/mailFolders/inbox/messages/delta?$deltatoken=inbox-2", base)
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/me/mailFolders/inbox/messages/delta"))
+            .and(query_param("$deltatoken", "inbox-1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "value": [
+                    message("1", "customer@fabrikam.com", "Renewal question"),
+                    message("2", "offers@spam.example", "You won"),
+                    {"id": "AAMk-gone", "@removed": {"reason": "deleted"}}
+                ],
+                "@odata.nextLink": format!("{}/me/mailFolders/inbox/messages/delta?$skiptoken=page-2", base)
+            })))
+            .mount(&server)
+            .await;
+        // No stored link yet: the walk starts from the lookback window
+        Mock::given(method("GET"))
+            .and(path("/me/mailFolders/sentitems/messages/delta"))
+            .and(query_param("changeType", "created"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "value": [message("4", "ada@contoso.com", "Re: Renewal question")],
+                "@odata.deltaLink": format!("{}/me/mailFolders/sentitems/messages/delta?$deltatoken=sent-1", base)
+            })))
+            .mount(&server)
+            .await;
+
+        let result = connector(&base)
+            .sync(SyncParams {
+                connection: connection(),
+                cursor: Some(Cursor::from_json(serde_json::json!({
+                    "folders": {
+                        "inbox": format!("{}/me/mailFolders/inbox/messages/delta?$deltatoken=inbox-1", base)
+                    }
+                }))),
+                cancellation: Default::default(),
+            })
+            .await
+            .unwrap();
+
+        let kinds: Vec<_> = result.signals.iter().map(|s| s.kind.as_str()).collect();
+        assert_eq!(
+            kinds,
+            vec!["email_received", "email_received", "email_sent"]
+        );
+        assert_eq!(
+            result.signals[0].dedupe_key.as_deref(),
+            Some("outlook:email_received:<1@contoso.com>")
+        );
+        assert_eq!(result.signals[0].payload["from"], "customer@fabrikam.com");
+        assert_eq!(result.signals[2].payload["folder"], "sentitems");
+        assert!(!result.has_more);
+        let cursor = result.next_cursor.unwrap();
+        assert!(
+            cursor.as_json()["folders"]["inbox"]
+                .as_str()
+                .unwrap()
+                .ends_with("$deltatoken=inbox-2")
+        );
+        assert!(
+            cursor.as_json()["folders"]["sentitems"]
+                .as_str()
+                .unwrap()
+                .ends_with("$deltatoken=sent-1")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_outlook_sync_restarts_expired_delta_link() {
+        let server = MockServer::start().await;
+        let base = server.uri();
+        Mock::given(method("GET"))
+            .and(path("/me/mailFolders/inbox/messages/delta"))
+            .and(query_param("$deltatoken", "stale"))
+            .respond_with(ResponseTemplate::new(410))
+            .mount(&server)
+            .await;
+        for folder in ["inbox", "sentitems"] {
+            Mock::given(method("GET"))
+                .and(path(format!("/me/mailFolders/{}/messages/delta", folder)))
+                .and(query_param("changeType", "created"))
+                .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                    "value": [],
+                    "@odata.deltaLink": format!("{}/me/mailFolders/{}/messages/delta?$deltatoken=fresh", base, folder)
+                })))
+                .mount(&server)
+                .await;
+        }
+
+        let result = connector(&base)
+            .sync(SyncParams {
+                connection: connection(),
+                cursor: Some(Cursor::from_json(serde_json::json!({
+                    "folders": {
+                        "inbox": format!("{}/me/mailFolders/inbox/messages/delta?$deltatoken=stale", base),
+                        // Not a Graph link: ignored rather than sent the token
+                        "sentitems": "https://attacker.example/collect"
+                    }
+                }))),
+                cancellation: Default::default(),
+            })
+            .await
+            .unwrap();
+
+        assert!(result.signals.is_empty());
+        let cursor = result.next_cursor.unwrap();
+        for folder in ["inbox", "sentitems"] {
+            assert!(
+                cursor.as_json()["folders"][folder]
+                    .as_str()
+                    .unwrap()
+                    .ends_with("$deltatoken=fresh")
+            );
+        }
+    }
+}
@@ -0,0 +1,797 @@
+//! Outlook Calendar connector implementation
+//!
+//! Microsoft 365 and Outlook.com calendars through Microsoft Graph. Each sync
+//! runs a `calendarView` delta query over the signed-in user's default
+//! calendar and resumes from the delta link stored in the cursor, producing
+//! `calendar_event_created`, `calendar_event_updated` and
+//! `calendar_event_deleted` signals like the Google Calendar connector.
+//!
+//! Graph fixes a calendar view's time window when its delta walk starts, so the
+//! walk is restarted with a fresh window once it is [`WINDOW_RENEW_DAYS`] old.
+//! Restarted walks return every event again; dedupe keys include the event's
+//! last modification time so unchanged events do not produce new signals.
+
+use anyhow::anyhow;
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, SecondsFormat, Utc};
+use reqwest::{Client, StatusCode};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tracing::{debug, info, warn};
+use url::Url;
+use uuid::Uuid;
+
+use crate::connectors::{
+    AuthType, Connector, Cursor, ProviderCategory, ProviderMetadata, Registry,
+    trait_::{
+        AuthorizeParams, ExchangeTokenParams, SyncError, SyncParams, SyncResult, WebhookParams,
+    },
+};
+use crate::models::{connection::Model as Connection, signal::Model as Signal};
+use crate::normalization::SignalKind;
+
+/// Provider slug
+pub const OUTLOOK_CALENDAR_PROVIDER_SLUG: &str = "outlook-calendar";
+
+/// Scopes requested at authorization time
+pub const OUTLOOK_CALENDAR_SCOPES: &[&str] = &["User.Read", "Calendars.Read"];
+
+/// Events requested per delta page
+const PAGE_SIZE: u32 = 50;
+
+/// Delta pages fetched in one sync; the rest continues on the next run
+const MAX_DELTA_PAGES_PER_SYNC: u32 = 10;
+
+/// How far back the calendar view window starts
+const WINDOW_LOOKBACK_DAYS: i64 = 7;
+
+/// How far ahead the calendar view window ends
+const WINDOW_LOOKAHEAD_DAYS: i64 = 365;
+
+/// Age of a window after which the delta walk restarts with a new one
+pub const WINDOW_RENEW_DAYS: i64 = 30;
+
+/// Events modified within this long of their creation count as created
+const CREATED_GRACE_SECONDS: i64 = 60;
+
+/// Outlook Calendar connector
+pub struct OutlookCalendarConnector {
+    client_id: String,
+    client_secret: String,
+    oauth_base: String,
+    api_base: String,
+    http_client: Client,
+}
+
+/// Where the calendar view delta walk resumes
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+struct DeltaState {
+    /// `@odata.nextLink` while a walk is unfinished, `@odata.deltaLink` once it completes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    link: Option<String>,
+    /// When the walk's time window was opened
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    window_started_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MicrosoftTokenResponse {
+    access_token: String,
+    #[serde(default)]
+    refresh_token: Option<String>,
+    #[serde(default)]
+    expires_in: Option<i64>,
+    #[serde(default)]
+    scope: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GraphUser {
+    id: String,
+    #[serde(default)]
+    display_name: Option<String>,
+    #[serde(default)]
+    mail: Option<String>,
+    #[serde(default)]
+    user_principal_name: Option<String>,
+}
+
+impl OutlookCalendarConnector {
+    /// Create a new Outlook Calendar connector with the Microsoft identity platform configuration
+    pub fn new(
+        client_id: String,
+        client_secret: String,
+        oauth_base: String,
+        api_base: String,
+    ) -> Self {
+        Self {
+            client_id,
+            client_secret,
+            oauth_base: oauth_base.trim_end_matches('/').to_string(),
+            api_base: api_base.trim_end_matches('/').to_string(),
+            http_client: crate::egress::client(),
+        }
+    }
+
+    fn default_redirect_uri() -> String {
+        match std::env::var("POBLYSH_PROFILE").as_deref() {
+            Ok("local") | Ok("test") | Err(_) => "http://localhost:3000/callback".to_string(),
+            Ok(_) => "https://app.poblysh.com/callback".to_string(),
+        }
+    }
+
+    fn requested_scopes() -> String {
+        let mut scopes = OUTLOOK_CALENDAR_SCOPES.to_vec();
+        scopes.push("offline_access");
+        scopes.join(" ")
+    }
+
+    fn access_token(connection: &Connection) -> Result<String, SyncError> {
+        connection
+            .access_token
+            .clone()
+            .filter(|token| !token.is_empty())
+            .ok_or_else(|| SyncError::unauthorized("Missing Outlook Calendar access token"))
+    }
+
+    /// Call the `/token` endpoint with the given grant
+    async fn oauth_token(
+        &self,
+        grant: &[(&str, &str)],
+    ) -> Result<MicrosoftTokenResponse, anyhow::Error> {
+        let scopes = Self::requested_scopes();
+        let mut form = vec![
+            ("client_id", self.client_id.as_str()),
+            ("client_secret", self.client_secret.as_str()),
+            ("scope", scopes.as_str()),
+        ];
+        form.extend_from_slice(grant);
+
+        let response = self
+            .http_client
+            .post(format!("{}/token", self.oauth_base))
+            .form(&form)
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let body: serde_json::Value = response.json().await.unwrap_or_default();
+            return Err(anyhow!(
+                "Microsoft token endpoint returned {}: {}",
+                status,
+                body.get("error")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("unknown_error")
+            ));
+        }
+        Ok(response.json().await?)
+    }
+
+    /// GET a Graph URL, mapping HTTP failures to sync errors
+    ///
+    /// Returns `None` when Graph answers `410 Gone`, which it does for delta
+    /// links whose sync state has expired.
+    async fn get(
+        &self,
+        access_token: &str,
+        url: &str,
+    ) -> Result<Option<serde_json::Value>, SyncError> {
+        let response = self
+            .http_client
+            .get(url)
+            .bearer_auth(access_token)
+            .header("Accept", "application/json")
+            .header("Prefer", format!("odata.maxpagesize={}", PAGE_SIZE))
+            .send()
+            .await
+            .map_err(|e| SyncError::transient(format!("Outlook Calendar request failed: {}", e)))?;
+
+        let status = response.status();
+        if status == StatusCode::GONE {
+            return Ok(None);
+        }
+        if status == StatusCode::TOO_MANY_REQUESTS {
+            let retry_after = response
+                .headers()
+                .get("Retry-After")
+                .and_then(|h| h.to_str().ok())
+                .and_then(|s| s.parse::<u64>().ok());
+            return Err(SyncError::rate_limited(retry_after));
+        }
+        if status == StatusCode::UNAUTHORIZED {
+            return Err(SyncError::unauthorized(format!(
+                "Outlook Calendar request failed: {}",
+                status
+            )));
+        }
+        if status.is_server_error() {
+            return Err(SyncError::transient(format!(
+                "Outlook Calendar request failed: {}",
+                status
+            )));
+        }
+        if !status.is_success() {
+            let body: serde_json::Value = response.json().await.unwrap_or_default();
+            return Err(SyncError::permanent(format!(
+                "Outlook Calendar request failed: {}",
+                status
+            ))
+            .with_details(body));
+        }
+
+        response
+            .json()
+            .await
+            .map(Some)
+            .map_err(|e| SyncError::transient(format!("Outlook Calendar returned bad JSON: {}", e)))
+    }
+
+    /// Delta query that opens a new calendar view window at `now`
+    fn initial_delta_url(&self, now: DateTime<Utc>) -> String {
+        let start =
+            (now - Duration::days(WINDOW_LOOKBACK_DAYS)).to_rfc3339_opts(SecondsFormat::Secs, true);
+        let end = (now + Duration::days(WINDOW_LOOKAHEAD_DAYS))
+            .to_rfc3339_opts(SecondsFormat::Secs, true);
+        let mut url = Url::parse(&format!("{}/me/calendarView/delta", self.api_base))
+            .expect("valid Graph URL");
+        url.query_pairs_mut()
+            .append_pair("startDateTime", &start)
+            .append_pair("endDateTime", &end);
+        url.to_string()
+    }
+
+    /// Link to resume from, or a new window when there is none or it is too old
+    fn resume_state(&self, state: DeltaState, now: DateTime<Utc>) -> (String, DateTime<Utc>) {
+        // Links come back from Graph; never send the token anywhere else
+        let link = state.link.filter(|link| link.starts_with(&self.api_base));
+        match (link, state.window_started_at) {
+            (Some(link), Some(started)) if now - started < Duration::days(WINDOW_RENEW_DAYS) => {
+                (link, started)
+            }
+            _ => (self.initial_delta_url(now), now),
+        }
+    }
+}
+
+#[async_trait]
+impl Connector for OutlookCalendarConnector {
+    async fn authorize(
+        &self,
+        params: AuthorizeParams,
+    ) -> Result<Url, Box<dyn std::error::Error + Send + Sync>> {
+        info!(
+            tenant_id = %params.tenant_id,
+            "Generating Outlook Calendar OAuth authorization URL"
+        );
+
+        let mut url = Url::parse(&format!("{}/authorize", self.oauth_base))?;
+        let redirect_uri = params
+            .redirect_uri
+            .unwrap_or_else(Self::default_redirect_uri);
+        let state = params
+            .state
+            .filter(|s| !s.is_empty())
+            .unwrap_or_else(|| Uuid::new_v4().to_string());
+        url.query_pairs_mut()
+            .append_pair("client_id", &self.client_id)
+            .append_pair("response_type", "code")
+            .append_pair("response_mode", "query")
+            .append_pair("scope", &Self::requested_scopes())
+            .append_pair("redirect_uri", &redirect_uri)
+            .append_pair("state", &state);
+
+        debug!(
+            tenant_id = %params.tenant_id,
+            authorize_url = %url,
+            "Generated Outlook Calendar OAuth authorization URL"
+        );
+        Ok(url)
+    }
+
+    async fn exchange_token(
+        &self,
+        params: ExchangeTokenParams,
+    ) -> Result<Connection, Box<dyn std::error::Error + Send + Sync>> {
+        info!(
+            tenant_id = %params.tenant_id,
+            "Exchanging Outlook Calendar authorization code for tokens"
+        );
+
+        let redirect_uri = params
+            .redirect_uri
+            .unwrap_or_else(Self::default_redirect_uri);
+        let token = self
+            .oauth_token(&[
+                ("grant_type", "authorization_code"),
+                ("code", &params.code),
+                ("redirect_uri", &redirect_uri),
+            ])
+            .await?;
+
+        let me = self
+            .get(&token.access_token, &format!("{}/me", self.api_base))
+            .await?
+            .ok_or_else(|| anyhow!("Microsoft Graph returned no profile"))?;
+        let user: GraphUser = serde_json::from_value(me)?;
+        let address = user.mail.clone().or(user.user_principal_name.clone());
+        let now = DateTime::from(Utc::now());
+
+        Ok(Connection {
+            id: Uuid::new_v4(),
+            tenant_id: params.tenant_id,
+            provider_slug: OUTLOOK_CALENDAR_PROVIDER_SLUG.to_string(),
+            external_id: user.id.clone(),
+            status: "active".to_string(),
+            display_name: address.clone().or(user.display_name.clone()),
+            access_token_ciphertext: None,
+            refresh_token_ciphertext: None,
+            access_token: Some(token.access_token),
+            refresh_token: token.refresh_token,
+            expires_at: token
+                .expires_in
+                .map(|seconds| now + chrono::Duration::seconds(seconds)),
+            scopes: token.scope.map(|scope| {
+                serde_json::Value::Array(
+                    scope
+                        .split_whitespace()
+                        .map(|s| serde_json::Value::String(s.to_string()))
+                        .collect(),
+                )
+            }),
+            metadata: Some(serde_json::json!({
+                "provider": OUTLOOK_CALENDAR_PROVIDER_SLUG,
+                "user": {
+                    "id": user.id,
+                    "display_name": user.display_name,
+                    "email": address,
+                },
+            })),
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    async fn refresh_token(
+        &self,
+        connection: Connection,
+    ) -> Result<Connection, Box<dyn std::error::Error + Send + Sync>> {
+        let Some(refresh_token) = connection
+            .refresh_token
+            .clone()
+            .filter(|token| !token.is_empty())
+        else {
+            return Err(SyncError::unauthorized(
+                "Outlook Calendar connection has no refresh token",
+            )
+            .into());
+        };
+
+        info!(connection_id = %connection.id, "Refreshing Outlook Calendar token");
+        let token = self
+            .oauth_token(&[
+                ("grant_type", "refresh_token"),
+                ("refresh_token", &refresh_token),
+            ])
+            .await?;
+        let now = DateTime::from(Utc::now());
+
+        // Microsoft usually rotates the refresh token; keep the old one otherwise
+        Ok(Connection {
+            access_token: Some(token.access_token),
+            refresh_token: token.refresh_token.or(connection.refresh_token.clone()),
+            expires_at: token
+                .expires_in
+                .map(|seconds| now + chrono::Duration::seconds(seconds)),
+            updated_at: now,
+            ..connection
+        })
+    }
+
+    async fn sync(
+        &self,
+        params: SyncParams,
+    ) -> Result<SyncResult, Box<dyn std::error::Error + Send + Sync>> {
+        let connection = &params.connection;
+        info!(
+            tenant_id = %connection.tenant_id,
+            connection_id = %connection.id,
+            has_cursor = %params.cursor.is_some(),
+            "Starting Outlook Calendar sync"
+        );
+
+        let access_token = Self::access_token(connection)?;
+        let state: DeltaState = params
+            .cursor
+            .as_ref()
+            .and_then(|cursor| serde_json::from_value(cursor.as_json().clone()).ok())
+            .unwrap_or_default();
+        let now = Utc::now();
+        let (mut url, mut window_started_at) = self.resume_state(state, now);
+        let mut restarted = false;
+        let mut signals = Vec::new();
+
+        for _ in 0..MAX_DELTA_PAGES_PER_SYNC {
+            let Some(body) = self.get(&access_token, &url).await? else {
+                if restarted {
+                    return Err(Box::new(SyncError::transient(
+                        "Outlook Calendar delta query keeps expiring",
+                    )));
+                }
+                warn!(
+                    connection_id = %connection.id,
+                    "Outlook Calendar delta link expired, restarting with a new window"
+                );
+                url = self.initial_delta_url(now);
+                window_started_at = now;
+                restarted = true;
+                continue;
+            };
+
+            let events = body
+                .get("value")
+                .and_then(|v| v.as_array())
+                .cloned()
+                .unwrap_or_default();
+            signals.extend(
+                events
+                    .iter()
+                    .filter_map(|event| build_event_signal(connection, event)),
+            );
+
+            if let Some(delta) = body.get("@odata.deltaLink").and_then(|v| v.as_str()) {
+                let link = delta.to_string();
+                return Ok(finish(connection, signals, link, window_started_at, false)?);
+            }
+            match body.get("@odata.nextLink").and_then(|v| v.as_str()) {
+                Some(next) => url = next.to_string(),
+                None => {
+                    return Err(Box::new(SyncError::transient(
+                        "Outlook Calendar delta page had no next or delta link",
+                    )));
+                }
+            }
+        }
+
+        Ok(finish(connection, signals, url, window_started_at, true)?)
+    }
+
+    async fn handle_webhook(
+        &self,
+        _params: WebhookParams,
+    ) -> Result<Vec<Signal>, Box<dyn std::error::Error + Send + Sync>> {
+        // Graph change notifications need a subscription lifecycle; delta sync covers Outlook for now
+        Err(Box::new(SyncError::permanent(
+            "WEBHOOKS_NOT_SUPPORTED: Webhooks not supported for Outlook Calendar connector",
+        )))
+    }
+}
+
+/// Build the sync result, storing where the next run resumes
+fn finish(
+    connection: &Connection,
+    signals: Vec<Signal>,
+    link: String,
+    window_started_at: DateTime<Utc>,
+    has_more: bool,
+) -> Result<SyncResult, serde_json::Error> {
+    debug!(
+        connection_id = %connection.id,
+        signals = signals.len(),
+        has_more,
+        "Outlook Calendar sync completed"
+    );
+    let state = DeltaState {
+        link: Some(link),
+        window_started_at: Some(window_started_at),
+    };
+    Ok(SyncResult {
+        signals,
+        next_cursor: Some(Cursor::from_json(serde_json::to_value(&state)?)),
+        has_more,
+    })
+}
+
+/// Register the Outlook Calendar connector in the registry
+pub fn register_outlook_calendar_connector(
+    registry: &mut Registry,
+    connector: Arc<OutlookCalendarConnector>,
+) {
+    let metadata = ProviderMetadata::new(
+        OUTLOOK_CALENDAR_PROVIDER_SLUG.to_string(),
+        AuthType::OAuth2,
+        OUTLOOK_CALENDAR_SCOPES
+            .iter()
+            .map(|s| s.to_string())
+            .collect(),
+        false, // delta sync only
+    )
+    .with_category(ProviderCategory::Calendar)
+    .with_description("Sync events and schedule changes from Outlook and Microsoft 365 calendars")
+    .with_icon_url("https://cdn.simpleicons.org/microsoftoutlook")
+    .with_docs_url("https://learn.microsoft.com/en-us/graph/delta-query-events")
+    .with_backfill(true)
+    .with_signal_kinds(&[
+        SignalKind::CalendarEventCreated,
+        SignalKind::CalendarEventUpdated,
+        SignalKind::CalendarEventDeleted,
+    ]);
+
+    registry.register(connector, metadata);
+}
+
+fn parse_time(value: Option<&serde_json::Value>) -> Option<DateTime<Utc>> {
+    value
+        .and_then(|v| v.as_str())
+        .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+        .map(|dt| dt.with_timezone(&Utc))
+}
+
+/// Build a signal from an event returned by the delta query
+///
+/// Removed and cancelled events become `calendar_event_deleted`. Other events
+/// are `calendar_event_created` when they were last modified within
+/// [`CREATED_GRACE_SECONDS`] of their creation, and `calendar_event_updated`
+/// otherwise.
+fn build_event_signal(connection: &Connection, event: &serde_json::Value) -> Option<Signal> {
+    let id = event.get("id").and_then(|v| v.as_str())?;
+    let received_at = DateTime::from(Utc::now());
+    let created = parse_time(event.get("createdDateTime"));
+    let modified = parse_time(event.get("lastModifiedDateTime"));
+
+    let deleted = event.get("@removed").is_some()
+        || event.get("isCancelled").and_then(|v| v.as_bool()) == Some(true);
+    let (kind, occurred_at, dedupe_key) = if deleted {
+        let kind = SignalKind::CalendarEventDeleted;
+        (
+            kind,
+            modified.unwrap_or_else(Utc::now),
+            format!("{}:{}:{}", OUTLOOK_CALENDAR_PROVIDER_SLUG, kind, id),
+        )
+    } else {
+        let is_new = match (created, modified) {
+            (Some(created), Some(modified)) => {
+                modified - created < Duration::seconds(CREATED_GRACE_SECONDS)
+            }
+            _ => false,
+        };
+        let (kind, occurred_at) = if is_new {
+            (SignalKind::CalendarEventCreated, created)
+        } else {
+            (SignalKind::CalendarEventUpdated, modified.or(created))
+        };
+        let version = event
+            .get("lastModifiedDateTime")
+            .and_then(|v| v.as_str())
+            .unwrap_or_default();
+        (
+            kind,
+            occurred_at.unwrap_or_else(Utc::now),
+            format!(
+                "{}:{}:{}:{}",
+                OUTLOOK_CALENDAR_PROVIDER_SLUG, kind, id, version
+            ),
+        )
+    };
+
+    Some(Signal {
+        id: Uuid::new_v4(),
+        tenant_id: connection.tenant_id,
+        provider_slug: OUTLOOK_CALENDAR_PROVIDER_SLUG.to_string(),
+        connection_id: connection.id,
+        kind: kind.as_str().to_string(),
+        occurred_at: occurred_at.into(),
+        received_at,
+        payload: serde_json::json!({
+            "event_id": id,
+            "ical_uid": event.get("iCalUId"),
+            "series_master_id": event.get("seriesMasterId"),
+            "subject": event.get("subject"),
+            "start": event.get("start"),
+            "end": event.get("end"),
+            "is_all_day": event.get("isAllDay"),
+            "location": event.pointer("/location/displayName"),
+            "organizer": event.pointer("/organizer/emailAddress/address"),
+            "url": event.get("webLink"),
+            "occurred_at": occurred_at.to_rfc3339(),
+        }),
+        dedupe_key: Some(dedupe_key),
+        created_at: received_at,
+        updated_at: received_at,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use wiremock::matchers::{method, path, query_param, query_param_is_missing};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn connector(base: &str) -> OutlookCalendarConnector {
+        OutlookCalendarConnector::new(
+            "client-id".to_string(),
+            "client-secret".to_string(),
+            base.to_string(),
+            base.to_string(),
+        )
+    }
+
+    fn connection() -> Connection {
+        let now = DateTime::from(Utc::now());
+        Connection {
+            id: Uuid::new_v4(),
+            tenant_id: Uuid::new_v4(),
+            provider_slug: OUTLOOK_CALENDAR_PROVIDER_SLUG.to_string(),
+            external_id: "user-1".to_string(),
+            status: "active".to_string(),
+            display_name: Some("ada@contoso.com".to_string()),
+            access_token_ciphertext: None,
+            refresh_token_ciphertext: None,
+            access_token: Some("ms-access".to_string()),
+            refresh_token: None,
+            expires_at: None,
+            scopes: None,
+            metadata: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    fn event(id: &str, created: &str, modified: &str) -> serde_json::Value {
+        serde_json::json!({
+            "id": id,
+            "iCalUId": format!("ical-{}", id),
+            "subject": "Quarterly review",
+            "createdDateTime": created,
+            "lastModifiedDateTime": modified,
+            "start": {"dateTime": "2024-05-06T15:00:00.0000000", "timeZone": "UTC"},
+            "end": {"dateTime": "2024-05-06T16:00:00.0000000", "timeZone": "UTC"},
+            "isAllDay": false,
+            "isCancelled": false,
+            "location": {"displayName": "Room 4"},
+            "organizer": {"emailAddress": {"address": "ada@contoso.com"}},
+            "webLink": format!("https://outlook.office365.com/owa/?itemid={}", id)
+        })
+    }
+
+    #[tokio::test]
+    async fn test_outlook_calendar_authorize_url_requests_calendar_scope() {
+        let url = connector("https://login.microsoftonline.com/common/oauth2/v2.0")
+            .authorize(AuthorizeParams {
+                tenant_id: Uuid::new_v4(),
+                redirect_uri: Some("https://app.example.com/callback".to_string()),
+                state: Some("state-1".to_string()),
+                code_challenge: None,
+            })
+            .await
+            .unwrap();
+
+        let query: HashMap<_, _> = url.query_pairs().collect();
+        assert_eq!(query["scope"], "User.Read Calendars.Read offline_access");
+        assert_eq!(query["state"], "state-1");
+    }
+
+    #[tokio::test]
+    async fn test_outlook_calendar_sync_classifies_events_and_stores_delta_link() {
+        let server = MockServer::start().await;
+        let base = server.uri();
+        let mut cancelled = event("evt-4", "2024-04-01T09:00:00Z", "2024-05-02T08:00:00Z");
+        cancelled["isCancelled"] = serde_json::json!(true);
+        Mock::given(method("GET"))
+            .and(path("/me/calendarView/delta"))
+            .and(query_param("$skiptoken", "page-2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "value": [
+                    {"id": "evt-3", "@removed": {"reason": "deleted"}},
+                    cancelled
+                ],
+                "@odata.deltaLink": format!("{}/me/calendarView/delta?$deltatoken=cal-2", base)
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/me/calendarView/delta"))
+            .and(query_param("$deltatoken", "cal-1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "value": [
+                    event("evt-1", "2024-05-02T10:00:00Z", "2024-05-02T10:00:20Z"),
+                    event("evt-2", "2024-04-01T09:00:00Z", "2024-05-02T11:30:00Z")
+                ],
+                "@odata.nextLink": format!("{}/me/calendarView/delta?$skiptoken=page-2", base)
+            })))
+            .mount(&server)
+            .await;
+
+        let window_started_at = Utc::now() - Duration::days(3);
+        let result = connector(&base)
+            .sync(SyncParams {
+                connection: connection(),
+                cursor: Some(Cursor::from_json(serde_json::json!({
+                    "link": format!("{}/me/calendarView/delta?$deltatoken=cal-1", base),
+                    "window_started_at": window_started_at,
+                }))),
+                cancellation: Default::default(),
+            })
+            .await
+            .unwrap();
+
+        let kinds: Vec<_> = result.signals.iter().map(|s| s.kind.as_str()).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                "calendar_event_created",
+                "calendar_event_updated",
+                "calendar_event_deleted",
+                "calendar_event_deleted"
+            ]
+        );
+        assert_eq!(
+            result.signals[1].dedupe_key.as_deref(),
+            Some("outlook-calendar:calendar_event_updated:evt-2:2024-05-02T11:30:00Z")
+        );
+        assert_eq!(
+            result.signals[2].dedupe_key.as_deref(),
+            Some("outlook-calendar:calendar_event_deleted:evt-3")
+        );
+        assert_eq!(result.signals[0].payload["location"], "Room 4");
+        assert!(!result.has_more);
+
+        let state: DeltaState =
+            serde_json::from_value(result.next_cursor.unwrap().as_json().clone()).unwrap();
+        assert!(state.link.unwrap().ends_with("$deltatoken=cal-2"));
+        assert_eq!(state.window_started_at, Some(window_started_at));
+    }
+
+    #[tokio::test]
+    async fn test_outlook_calendar_sync_renews_old_or_expired_window() {
+        let server = MockServer::start().await;
+        let base = server.uri();
+        Mock::given(method("GET"))
+            .and(path("/me/calendarView/delta"))
+            .and(query_param("$deltatoken", "stale"))
+            .respond_with(ResponseTemplate::new(410))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/me/calendarView/delta"))
+            .and(query_param_is_missing("$deltatoken"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "value": [],
+                "@odata.deltaLink": format!("{}/me/calendarView/delta?$deltatoken=fresh", base)
+            })))
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        let connector = connector(&base);
+        let cursors = [
+            // Graph expired the delta link
+            serde_json::json!({
+                "link": format!("{}/me/calendarView/delta?$deltatoken=stale", base),
+                "window_started_at": Utc::now() - Duration::days(1),
+            }),
+            // The window is due for renewal; the link is not followed
+            serde_json::json!({
+                "link": format!("{}/me/calendarView/delta?$deltatoken=old", base),
+                "window_started_at": Utc::now() - Duration::days(WINDOW_RENEW_DAYS + 1),
+            }),
+        ];
+        for cursor in cursors {
+            let result = connector
+                .sync(SyncParams {
+                    connection: connection(),
+                    cursor: Some(Cursor::from_json(cursor)),
+                    cancellation: Default::default(),
+                })
+                .await
+                .unwrap();
+
+            let state: DeltaState =
+                serde_json::from_value(result.next_cursor.unwrap().as_json().clone()).unwrap();
+            assert!(state.link.unwrap().ends_with("$deltatoken=fresh"));
+            assert!(Utc::now() - state.window_started_at.unwrap() < Duration::minutes(1));
+        }
+    }
+}
@@ -0,0 +1,621 @@
+//! Connectors loaded from WebAssembly plugins
+//!
+//! Compiled only with the `wasm-plugins` feature. Every `*.wasm` file in
+//! `POBLYSH_PLUGIN_DIR` is loaded at startup and registered under the slug its
+//! manifest declares, so third parties can ship connectors without forking the
+//! crate. Modules run in the wasmi interpreter with no host imports: a plugin
+//! cannot reach the network or the filesystem, and each call is metered so a
+//! runaway plugin fails instead of stalling the executor.
+//!
+//! The host and a plugin exchange UTF-8 JSON through the plugin's linear
+//! memory. A plugin exports `memory`, `alloc(len: i32) -> i32` for the host to
+//! write inputs into, and the calls below. Each call returns its JSON output
+//! as `(ptr << 32) | len` packed into an `i64`.
+//!
+//! - `manifest() -> i64` describes the provider:
+//!   `{"slug": "acme-crm", "description": "Acme CRM deals", "category": "crm",
+//!   "auth_type": "basic", "webhooks": true, "backfill": false,
+//!   "signal_kinds": ["opportunity_won"]}`
+//! - `sync(ptr: i32, len: i32) -> i64` (optional) receives
+//!   `{"connection": {"id", "tenant_id", "external_id", "metadata", "secret"}, "cursor"}`
+//!   and returns `{"signals": [...], "next_cursor": ..., "has_more": false}`
+//! - `handle_webhook(ptr: i32, len: i32) -> i64` (required when `webhooks` is
+//!   set) receives `{"tenant_id", "payload"}` and returns `{"signals": [...]}`
+//!
+//! Signals are `{"kind", "payload", "occurred_at", "dedupe_key"}` with a
+//! canonical kind; `occurred_at` defaults to now. Instead of a result, a call
+//! may return `{"error": {"type": "rate_limited", "retry_after_secs": 30,
+//! "message": "..."}}` with the executor's error types (`unauthorized`,
+//! `rate_limited`, `transient` or `permanent`).
+//!
+//! Plugins authorize with `none` (the default) or `basic`, so their connections
+//! are created through `POST /connections`; the secret of a `basic` connection
+//! is passed to `sync` in the clear.
+
+use anyhow::{Context, anyhow, bail};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::Arc;
+use tracing::{info, warn};
+use url::Url;
+use uuid::Uuid;
+use wasmi::{Config, Engine, Linker, Module, Store};
+
+use crate::connectors::{
+    AuthType, Connector, ProviderCategory, ProviderMetadata, Registry, SyncError,
+    trait_::{AuthorizeParams, Cursor, ExchangeTokenParams, SyncParams, SyncResult, WebhookParams},
+};
+use crate::models::{connection::Model as Connection, signal::Model as Signal};
+use crate::normalization::{is_canonical_kind, parse_signal_kind};
+
+/// File extension of plugin modules
+pub const PLUGIN_EXTENSION: &str = "wasm";
+
+/// Fuel granted to each plugin call, roughly one unit per instruction
+const PLUGIN_FUEL: u64 = 100_000_000;
+
+/// Provider description a plugin returns from `manifest()`
+#[derive(Debug, Clone, Deserialize)]
+pub struct PluginManifest {
+    /// Provider slug; lowercase letters, digits and dashes
+    pub slug: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub category: Option<ProviderCategory>,
+    #[serde(default)]
+    pub auth_type: PluginAuthType,
+    /// Whether the plugin exports `handle_webhook`
+    #[serde(default)]
+    pub webhooks: bool,
+    /// Whether the first sync reads history
+    #[serde(default)]
+    pub backfill: bool,
+    /// Canonical kinds the plugin emits
+    #[serde(default)]
+    pub signal_kinds: Vec<String>,
+}
+
+/// How a plugin's connections authorize
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PluginAuthType {
+    /// Configured by connection metadata alone
+    #[default]
+    None,
+    /// Connection carries a secret
+    Basic,
+}
+
+/// Connection fields passed to `sync`
+#[derive(Debug, Serialize)]
+struct PluginConnection<'a> {
+    id: Uuid,
+    tenant_id: Uuid,
+    external_id: &'a str,
+    metadata: Option<&'a serde_json::Value>,
+    secret: Option<String>,
+}
+
+/// Output of `sync` and `handle_webhook`
+#[derive(Debug, Deserialize)]
+struct PluginOutput {
+    #[serde(default)]
+    signals: Vec<PluginSignal>,
+    #[serde(default)]
+    next_cursor: Option<serde_json::Value>,
+    #[serde(default)]
+    has_more: bool,
+}
+
+/// One signal emitted by a plugin
+#[derive(Debug, Deserialize)]
+struct PluginSignal {
+    kind: String,
+    #[serde(default)]
+    payload: serde_json::Value,
+    #[serde(default)]
+    occurred_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    dedupe_key: Option<String>,
+}
+
+/// Compiled plugin module; every call runs in a fresh instance
+struct PluginModule {
+    engine: Engine,
+    module: Module,
+}
+
+impl PluginModule {
+    /// Run an export in a fresh instance, passing `input` when given
+    fn call(&self, export: &str, input: Option<&[u8]>) -> anyhow::Result<Vec<u8>> {
+        let mut store = Store::new(&self.engine, ());
+        store.set_fuel(PLUGIN_FUEL).map_err(|e| anyhow!("{}", e))?;
+        let instance = Linker::<()>::new(&self.engine)
+            .instantiate(&mut store, &self.module)
+            .and_then(|instance| instance.start(&mut store))
+            .map_err(|e| anyhow!("instantiation failed: {}", e))?;
+        let memory = instance
+            .get_memory(&store, "memory")
+            .ok_or_else(|| anyhow!("memory is not exported"))?;
+
+        let packed = match input {
+            None => instance
+                .get_typed_func::<(), i64>(&store, export)
+                .and_then(|func| func.call(&mut store, ())),
+            Some(input) => {
+                let len = i32::try_from(input.len()).context("input too large")?;
+                let ptr = instance
+                    .get_typed_func::<i32, i32>(&store, "alloc")
+                    .and_then(|alloc| alloc.call(&mut store, len))
+                    .map_err(|e| anyhow!("alloc failed: {}", e))?;
+                memory
+                    .write(&mut store, ptr as u32 as usize, input)
+                    .map_err(|e| anyhow!("writing input: {}", e))?;
+                instance
+                    .get_typed_func::<(i32, i32), i64>(&store, export)
+                    .and_then(|func| func.call(&mut store, (ptr, len)))
+            }
+        }
+        .map_err(|e| anyhow!("{} failed: {}", export, e))?;
+
+        let packed = packed as u64;
+        let mut output = vec![0; (packed & 0xffff_ffff) as usize];
+        memory
+            .read(&store, (packed >> 32) as usize, &mut output)
+            .map_err(|e| anyhow!("reading {} output: {}", export, e))?;
+        Ok(output)
+    }
+}
+
+/// Connector backed by a WebAssembly module
+pub struct PluginConnector {
+    module: PluginModule,
+    manifest: PluginManifest,
+    exports_sync: bool,
+}
+
+impl PluginConnector {
+    /// Compile a plugin and read its manifest
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let bytes = std::fs::read(path).with_context(|| format!("reading {}", path.display()))?;
+        Self::from_bytes(&bytes)
+    }
+
+    /// Compile a plugin from its module bytes and read its manifest
+    pub fn from_bytes(bytes: &[u8]) -> anyhow::Result<Self> {
+        let mut config = Config::default();
+        config.consume_fuel(true);
+        let engine = Engine::new(&config);
+        let module = Module::new(&engine, bytes).map_err(|e| anyhow!("invalid module: {}", e))?;
+        let exports: Vec<String> = module
+            .exports()
+            .map(|export| export.name().to_string())
+            .collect();
+        let exports_sync = exports.iter().any(|name| name == "sync");
+        let exports_webhook = exports.iter().any(|name| name == "handle_webhook");
+
+        let module = PluginModule { engine, module };
+        let manifest: PluginManifest =
+            serde_json::from_slice(&module.call("manifest", None)?).context("invalid manifest")?;
+
+        let valid_slug = !manifest.slug.is_empty()
+            && manifest
+                .slug
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-');
+        if !valid_slug {
+            bail!("invalid slug '{}'", manifest.slug);
+        }
+        if let Some(kind) = manifest
+            .signal_kinds
+            .iter()
+            .find(|kind| !is_canonical_kind(kind))
+        {
+            bail!("unknown signal kind '{}'", kind);
+        }
+        if manifest.webhooks && !exports_webhook {
+            bail!("manifest declares webhooks but handle_webhook is not exported");
+        }
+        Ok(Self {
+            module,
+            manifest,
+            exports_sync,
+        })
+    }
+
+    /// Manifest the plugin declared
+    pub fn manifest(&self) -> &PluginManifest {
+        &self.manifest
+    }
+
+    /// Provider metadata registered for the plugin
+    pub fn metadata(&self) -> ProviderMetadata {
+        let manifest = &self.manifest;
+        let auth_type = match manifest.auth_type {
+            PluginAuthType::None => AuthType::Custom("none".to_string()),
+            PluginAuthType::Basic => AuthType::Basic,
+        };
+        let kinds: Vec<_> = manifest
+            .signal_kinds
+            .iter()
+            .filter_map(|kind| parse_signal_kind(kind))
+            .collect();
+        ProviderMetadata::new(manifest.slug.clone(), auth_type, vec![], manifest.webhooks)
+            .with_category(manifest.category.unwrap_or(ProviderCategory::Other))
+            .with_description(
+                manifest
+                    .description
+                    .clone()
+                    .unwrap_or_else(|| format!("Plugin connector {}", manifest.slug)),
+            )
+            .with_backfill(manifest.backfill)
+            .with_signal_kinds(&kinds)
+    }
+
+    /// Call `export` with a JSON input and parse its output or error
+    fn call_json(
+        &self,
+        export: &str,
+        input: &serde_json::Value,
+    ) -> Result<PluginOutput, SyncError> {
+        let slug = &self.manifest.slug;
+        let output = self
+            .module
+            .call(export, Some(input.to_string().as_bytes()))
+            .map_err(|e| SyncError::permanent(format!("plugin '{}': {:#}", slug, e)))?;
+        let mut output: serde_json::Value = serde_json::from_slice(&output).map_err(|e| {
+            SyncError::permanent(format!("plugin '{}' returned invalid JSON: {}", slug, e))
+        })?;
+        if let Some(error) = output.get_mut("error").map(serde_json::Value::take) {
+            return Err(serde_json::from_value(error).unwrap_or_else(|e| {
+                SyncError::permanent(format!(
+                    "plugin '{}' returned an invalid error: {}",
+                    slug, e
+                ))
+            }));
+        }
+        serde_json::from_value(output).map_err(|e| {
+            SyncError::permanent(format!(
+                "plugin '{}' returned an invalid result: {}",
+                slug, e
+            ))
+        })
+    }
+
+    fn to_signals(
+        &self,
+        signals: Vec<PluginSignal>,
+        tenant_id: Uuid,
+        connection_id: Uuid,
+    ) -> Result<Vec<Signal>, SyncError> {
+        let now: DateTime<_> = Utc::now().into();
+        signals
+            .into_iter()
+            .map(|signal| {
+                if !is_canonical_kind(&signal.kind) {
+                    return Err(SyncError::permanent(format!(
+                        "plugin '{}' emitted unknown signal kind '{}'",
+                        self.manifest.slug, signal.kind
+                    )));
+                }
+                Ok(Signal {
+                    id: Uuid::new_v4(),
+                    tenant_id,
+                    provider_slug: self.manifest.slug.clone(),
+                    connection_id,
+                    kind: signal.kind,
+                    occurred_at: signal.occurred_at.map(DateTime::from).unwrap_or(now),
+                    received_at: now,
+                    payload: signal.payload,
+                    dedupe_key: signal.dedupe_key,
+                    created_at: now,
+                    updated_at: now,
+                })
+            })
+            .collect()
+    }
+}
+
+#[async_trait]
+impl Connector for PluginConnector {
+    async fn authorize(
+        &self,
+        _params: AuthorizeParams,
+    ) -> Result<Url, Box<dyn std::error::Error + Send + Sync>> {
+        Err(anyhow!("OAuth authorization is not supported for plugin connectors").into())
+    }
+
+    async fn exchange_token(
+        &self,
+        _params: ExchangeTokenParams,
+    ) -> Result<Connection, Box<dyn std::error::Error + Send + Sync>> {
+        Err(anyhow!("Token exchange is not supported for plugin connectors").into())
+    }
+
+    async fn refresh_token(
+        &self,
+        _connection: Connection,
+    ) -> Result<Connection, Box<dyn std::error::Error + Send + Sync>> {
+        Err(anyhow!("Token refresh is not supported for plugin connectors").into())
+    }
+
+    async fn sync(
+        &self,
+        params: SyncParams,
+    ) -> Result<SyncResult, Box<dyn std::error::Error + Send + Sync>> {
+        if !self.exports_sync {
+            return Ok(SyncResult {
+                signals: Vec::new(),
+                next_cursor: params.cursor,
+                has_more: false,
+            });
+        }
+
+        let connection = &params.connection;
+        let input = serde_json::json!({
+            "connection": PluginConnection {
+                id: connection.id,
+                tenant_id: connection.tenant_id,
+                external_id: &connection.external_id,
+                metadata: connection.metadata.as_ref(),
+                secret: connection
+                    .access_token
+                    .clone()
+                    .filter(|secret| !secret.is_empty()),
+            },
+            "cursor": params.cursor.as_ref().map(Cursor::as_json),
+        });
+        let output = self.call_json("sync", &input)?;
+        Ok(SyncResult {
+            signals: self.to_signals(output.signals, connection.tenant_id, connection.id)?,
+            next_cursor: output.next_cursor.map(Cursor::from_json),
+            has_more: output.has_more,
+        })
+    }
+
+    async fn handle_webhook(
+        &self,
+        params: WebhookParams,
+    ) -> Result<Vec<Signal>, Box<dyn std::error::Error + Send + Sync>> {
+        if !self.manifest.webhooks {
+            return Err(Box::new(SyncError::permanent(format!(
+                "plugin '{}' does not accept webhooks",
+                self.manifest.slug
+            ))));
+        }
+
+        let input = serde_json::json!({
+            "tenant_id": params.tenant_id,
+            "payload": params.payload,
+        });
+        let output = self.call_json("handle_webhook", &input)?;
+        Ok(self.to_signals(
+            output.signals,
+            params.tenant_id,
+            Uuid::new_v4(), // Will be populated by webhook handler
+        )?)
+    }
+}
+
+/// Load every plugin in `dir` and register the ones whose slug is free
+///
+/// Plugins load in file name order; a plugin that fails to load or claims an
+/// already registered slug is skipped with a warning. Returns the registered
+/// slugs.
+pub fn register_plugins(registry: &mut Registry, dir: &Path) -> Vec<String> {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) => {
+            warn!("Plugin directory {} not readable: {}", dir.display(), e);
+            return Vec::new();
+        }
+    };
+    let mut paths: Vec<_> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.is_file()
+                && path
+                    .extension()
+                    .is_some_and(|extension| extension == PLUGIN_EXTENSION)
+        })
+        .collect();
+    paths.sort();
+
+    let mut registered = Vec::new();
+    for path in paths {
+        let connector = match PluginConnector::load(&path) {
+            Ok(connector) => connector,
+            Err(e) => {
+                warn!("Plugin {} not registered: {:#}", path.display(), e);
+                continue;
+            }
+        };
+        let slug = connector.manifest().slug.clone();
+        if registry.get_metadata(&slug).is_ok() {
+            warn!(
+                "Plugin {} not registered: provider '{}' already exists",
+                path.display(),
+                slug
+            );
+            continue;
+        }
+        info!(
+            "Registered plugin connector '{}' from {}",
+            slug,
+            path.display()
+        );
+        let metadata = connector.metadata();
+        registry.register(Arc::new(connector), metadata);
+        registered.push(slug);
+    }
+    registered
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::connectors::SyncErrorKind;
+
+    /// Plugin whose calls return fixed JSON documents from a data segment
+    fn plugin_wat(manifest: &str, sync: &str, sync_body: Option<&str>) -> String {
+        let escape = |json: &str| {
+            json.replace('\\', "\\\\")
+                .replace('"', "\\\"")
+                .replace('\n', "\\n")
+        };
+        let packed = |ptr: u64, json: &str| (ptr << 32) | json.len() as u64;
+        format!(
+            r#"(module
+                (memory (export "memory") 1)
+                (global $next (mut i32) (i32.const 8192))
+                (data (i32.const 0) "{manifest_data}")
+                (data (i32.const 4096) "{sync_data}")
+                (func (export "alloc") (param $len i32) (result i32)
+                    (local $ptr i32)
+                    (local.set $ptr (global.get $next))
+                    (global.set $next (i32.add (global.get $next) (local.get $len)))
+                    (local.get $ptr))
+                (func (export "manifest") (result i64)
+                    (i64.const {manifest_ptr}))
+                (func (export "sync") (param i32 i32) (result i64)
+                    {sync_body}
+                    (i64.const {sync_ptr})))"#,
+            manifest_data = escape(manifest),
+            sync_data = escape(sync),
+            manifest_ptr = packed(0, manifest),
+            sync_ptr = packed(4096, sync),
+            sync_body = sync_body.unwrap_or(""),
+        )
+    }
+
+    fn plugin(manifest: &str, sync: &str, sync_body: Option<&str>) -> Vec<u8> {
+        wat::parse_str(plugin_wat(manifest, sync, sync_body)).unwrap()
+    }
+
+    fn connection(slug: &str) -> Connection {
+        let now = DateTime::from(Utc::now());
+        Connection {
+            id: Uuid::new_v4(),
+            tenant_id: Uuid::new_v4(),
+            provider_slug: slug.to_string(),
+            external_id: "acme".to_string(),
+            status: "active".to_string(),
+            display_name: None,
+            access_token: None,
+            refresh_token: None,
+            expires_at: None,
+            scopes: None,
+            metadata: Some(serde_json::json!({"account": "acme"})),
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_register_plugins_loads_modules_from_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        let manifest = r#"{"slug": "acme-crm", "category": "crm", "backfill": true,
+            "signal_kinds": ["opportunity_won"]}"#;
+        let sync = r#"{"signals": [{"kind": "opportunity_won", "payload": {"deal": 42},
+            "dedupe_key": "deal-42"}], "next_cursor": "42", "has_more": false}"#;
+        std::fs::write(dir.path().join("acme.wasm"), plugin(manifest, sync, None)).unwrap();
+        // Same slug as acme.wasm, which loads first
+        std::fs::write(
+            dir.path().join("duplicate.wasm"),
+            plugin(manifest, sync, None),
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("broken.wasm"), b"not wasm").unwrap();
+        std::fs::write(dir.path().join("README.txt"), b"ignored").unwrap();
+
+        let mut registry = Registry::new();
+        assert_eq!(
+            register_plugins(&mut registry, dir.path()),
+            vec!["acme-crm"]
+        );
+
+        let metadata = registry.get_metadata("acme-crm").unwrap();
+        assert_eq!(metadata.auth_type.as_str(), "none");
+        assert!(!metadata.webhooks);
+        let capabilities = metadata.capabilities();
+        assert!(capabilities.supports_backfill);
+        assert_eq!(capabilities.supported_signal_kinds, vec!["opportunity_won"]);
+
+        let connection = connection("acme-crm");
+        let result = registry
+            .get("acme-crm")
+            .unwrap()
+            .sync(SyncParams {
+                connection: connection.clone(),
+                cursor: None,
+                cancellation: Default::default(),
+            })
+            .await
+            .unwrap();
+        assert_eq!(result.signals.len(), 1);
+        let signal = &result.signals[0];
+        assert_eq!(signal.kind, "opportunity_won");
+        assert_eq!(signal.provider_slug, "acme-crm");
+        assert_eq!(signal.connection_id, connection.id);
+        assert_eq!(signal.payload, serde_json::json!({"deal": 42}));
+        assert_eq!(signal.dedupe_key.as_deref(), Some("deal-42"));
+        assert_eq!(result.next_cursor, Some(Cursor::from_string("42")));
+        assert!(!result.has_more);
+    }
+
+    #[tokio::test]
+    async fn test_plugin_errors_map_to_sync_errors() {
+        let manifest = r#"{"slug": "acme-crm"}"#;
+        let rate_limited = PluginConnector::from_bytes(&plugin(
+            manifest,
+            r#"{"error": {"type": "rate_limited", "retry_after_secs": 30, "message": "slow down"}}"#,
+            None,
+        ))
+        .unwrap();
+        let error = rate_limited
+            .sync(SyncParams {
+                connection: connection("acme-crm"),
+                cursor: None,
+                cancellation: Default::default(),
+            })
+            .await
+            .unwrap_err();
+        let error = error.downcast_ref::<SyncError>().unwrap();
+        assert_eq!(
+            error.kind,
+            SyncErrorKind::RateLimited {
+                retry_after_secs: Some(30)
+            }
+        );
+        assert_eq!(error.message.as_deref(), Some("slow down"));
+
+        // A plugin that never returns runs out of fuel
+        let looping =
+            PluginConnector::from_bytes(&plugin(manifest, "{}", Some("(loop $spin (br $spin))")))
+                .unwrap();
+        let error = looping
+            .sync(SyncParams {
+                connection: connection("acme-crm"),
+                cursor: None,
+                cancellation: Default::default(),
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(
+            error.downcast_ref::<SyncError>().unwrap().kind,
+            SyncErrorKind::Permanent
+        );
+
+        let undeclared_webhooks = plugin(r#"{"slug": "acme-crm", "webhooks": true}"#, "{}", None);
+        assert!(PluginConnector::from_bytes(&undeclared_webhooks).is_err());
+        let bad_kind = plugin(
+            r#"{"slug": "acme-crm", "signal_kinds": ["deal_won"]}"#,
+            "{}",
+            None,
+        );
+        assert!(PluginConnector::from_bytes(&bad_kind).is_err());
+    }
+}
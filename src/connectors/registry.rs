@@ -42,6 +42,11 @@ impl Registry {
 
     /// Initialize the global registry with providers
     pub fn initialize(config: &AppConfig) {
+        // Connector HTTP clients are built below, so the egress policy must be in place first
+        crate::egress::install(crate::egress::EgressPolicy::from_config(&config.egress));
+        // Mail connectors share the installed spam filter, which config reloads replace
+        crate::mail::integration::install_spam_filter(&config.mail_spam);
+
         let registry = Self::global();
         let mut reg = registry.write().unwrap();
 
@@ -53,15 +58,187 @@ impl Registry {
             config.jira_client_secret.clone(),
         ) {
             let jira_connector = Arc::new(crate::connectors::JiraConnector::new(
+                client_id.clone(),
+                client_secret.clone(),
+                config.jira_oauth_base.clone(),
+                config.jira_api_base.clone(),
+            ));
+            crate::connectors::register_jira_connector(&mut reg, jira_connector);
+            // Confluence shares the Atlassian OAuth app configured for Jira
+            let confluence_connector = Arc::new(crate::connectors::ConfluenceConnector::new(
                 client_id,
                 client_secret,
                 config.jira_oauth_base.clone(),
                 config.jira_api_base.clone(),
             ));
-            crate::connectors::register_jira_connector(&mut reg, jira_connector);
+            crate::connectors::register_confluence_connector(&mut reg, confluence_connector);
+        } else {
+            warn!("Jira and Confluence connectors not registered: missing Jira client credentials");
+        }
+        // Register Slack connector only if configured explicitly
+        if let (Some(client_id), Some(client_secret)) = (
+            config.slack_client_id.clone(),
+            config.slack_client_secret.clone(),
+        ) {
+            let slack_connector = Arc::new(crate::connectors::SlackConnector::new(
+                client_id,
+                client_secret,
+                config.slack_oauth_base.clone(),
+                config.slack_api_base.clone(),
+            ));
+            crate::connectors::register_slack_connector(&mut reg, slack_connector);
+        } else {
+            warn!("Slack connector not registered: missing Slack client credentials");
+        }
+        // Register Notion connector only if configured explicitly
+        if let (Some(client_id), Some(client_secret)) = (
+            config.notion_client_id.clone(),
+            config.notion_client_secret.clone(),
+        ) {
+            let notion_connector = Arc::new(crate::connectors::NotionConnector::new(
+                client_id,
+                client_secret,
+                config.notion_api_base.clone(),
+            ));
+            crate::connectors::register_notion_connector(&mut reg, notion_connector);
+        } else {
+            warn!("Notion connector not registered: missing Notion client credentials");
+        }
+        // Register Linear connector only if configured explicitly
+        if let (Some(client_id), Some(client_secret)) = (
+            config.linear_client_id.clone(),
+            config.linear_client_secret.clone(),
+        ) {
+            let linear_connector = Arc::new(crate::connectors::LinearConnector::new(
+                client_id,
+                client_secret,
+                config.linear_oauth_base.clone(),
+                config.linear_api_base.clone(),
+            ));
+            crate::connectors::register_linear_connector(&mut reg, linear_connector);
         } else {
-            warn!("Jira connector not registered: missing Jira client credentials");
+            warn!("Linear connector not registered: missing Linear client credentials");
         }
+        // Register GitLab connector only if configured explicitly
+        if let (Some(client_id), Some(client_secret)) = (
+            config.gitlab_client_id.clone(),
+            config.gitlab_client_secret.clone(),
+        ) {
+            let gitlab_connector = Arc::new(crate::connectors::GitLabConnector::new(
+                client_id,
+                client_secret,
+                config.gitlab_base_url.clone(),
+            ));
+            crate::connectors::register_gitlab_connector(&mut reg, gitlab_connector);
+        } else {
+            warn!("GitLab connector not registered: missing GitLab client credentials");
+        }
+        // Register Outlook Mail and Calendar connectors only if configured explicitly
+        if let (Some(client_id), Some(client_secret)) = (
+            config.outlook_client_id.clone(),
+            config.outlook_client_secret.clone(),
+        ) {
+            // Outlook Calendar shares the Microsoft app registration
+            let outlook_calendar_connector =
+                Arc::new(crate::connectors::OutlookCalendarConnector::new(
+                    client_id.clone(),
+                    client_secret.clone(),
+                    config.outlook_oauth_base.clone(),
+                    config.outlook_api_base.clone(),
+                ));
+            crate::connectors::register_outlook_calendar_connector(
+                &mut reg,
+                outlook_calendar_connector,
+            );
+            let outlook_connector = Arc::new(crate::connectors::OutlookConnector::new(
+                client_id,
+                client_secret,
+                config.outlook_oauth_base.clone(),
+                config.outlook_api_base.clone(),
+                crate::mail::integration::installed_spam_filter(),
+            ));
+            crate::connectors::register_outlook_connector(&mut reg, outlook_connector);
+        } else {
+            warn!("Outlook connectors not registered: missing Outlook client credentials");
+        }
+        // Register Salesforce connector only if configured explicitly
+        if let (Some(client_id), Some(client_secret)) = (
+            config.salesforce_client_id.clone(),
+            config.salesforce_client_secret.clone(),
+        ) {
+            let salesforce_connector = Arc::new(crate::connectors::SalesforceConnector::new(
+                client_id,
+                client_secret,
+                config.salesforce_login_base.clone(),
+            ));
+            crate::connectors::register_salesforce_connector(&mut reg, salesforce_connector);
+        } else {
+            warn!("Salesforce connector not registered: missing Salesforce client credentials");
+        }
+        // Register Zendesk connector only if the OAuth client and account are configured
+        if let (Some(client_id), Some(client_secret), Some(subdomain)) = (
+            config.zendesk_client_id.clone(),
+            config.zendesk_client_secret.clone(),
+            config.zendesk_subdomain.as_deref(),
+        ) {
+            let zendesk_connector = Arc::new(crate::connectors::ZendeskConnector::new(
+                client_id,
+                client_secret,
+                crate::connectors::ZendeskConnector::base_url_for_subdomain(subdomain),
+            ));
+            crate::connectors::register_zendesk_connector(&mut reg, zendesk_connector);
+        } else {
+            warn!(
+                "Zendesk connector not registered: missing Zendesk client credentials or subdomain"
+            );
+        }
+        // Register Sentry connector only if configured explicitly
+        if let (Some(client_id), Some(client_secret)) = (
+            config.sentry_client_id.clone(),
+            config.sentry_client_secret.clone(),
+        ) {
+            let sentry_connector = Arc::new(crate::connectors::SentryConnector::new(
+                client_id,
+                client_secret,
+                config.sentry_base_url.clone(),
+            ));
+            crate::connectors::register_sentry_connector(&mut reg, sentry_connector);
+        } else {
+            warn!("Sentry connector not registered: missing Sentry client credentials");
+        }
+        // Register Datadog connector; webhooks need no credentials and polling needs both keys
+        let datadog_connector = Arc::new(crate::connectors::DatadogConnector::new(
+            crate::connectors::DatadogConnector::api_base_for_site(&config.datadog_site),
+            config.datadog_api_key.clone(),
+            config.datadog_application_key.clone(),
+        ));
+        crate::connectors::register_datadog_connector(&mut reg, datadog_connector);
+        // Register CircleCI connector; webhooks need no credentials and polling needs a token
+        let circleci_connector = Arc::new(crate::connectors::CircleCiConnector::new(
+            config.circleci_base_url.clone(),
+            config.circleci_api_token.clone(),
+        ));
+        crate::connectors::register_circleci_connector(&mut reg, circleci_connector);
+        // Register feed connector; each connection names its feed URL in metadata
+        crate::connectors::register_rss_connector(
+            &mut reg,
+            Arc::new(crate::connectors::RssConnector::new()),
+        );
+        // Register custom webhook connector; each connection carries its own secret and rules
+        crate::connectors::register_custom_webhook_connector(
+            &mut reg,
+            Arc::new(crate::connectors::CustomWebhookConnector::new()),
+        );
+        // Register ICS connector; each connection names its calendar URL in metadata
+        crate::connectors::register_ics_connector(
+            &mut reg,
+            Arc::new(crate::connectors::IcsConnector::new()),
+        );
+        // Register Stripe connector; events arrive through signed webhooks only
+        crate::connectors::register_stripe_connector(
+            &mut reg,
+            Arc::new(crate::connectors::StripeConnector::new()),
+        );
         // Register Google Drive connector
         crate::connectors::google_drive::register_google_drive_connector(&mut reg);
 
@@ -80,8 +257,7 @@ impl Registry {
                     .collect()
             });
 
-        let gmail_spam_filter =
-            crate::mail::integration::create_spam_filter_from_config(&config.mail_spam);
+        let gmail_spam_filter = crate::mail::integration::installed_spam_filter();
         let gmail_connector =
             Arc::new(crate::connectors::GmailConnector::new_with_oidc_and_scopes(
                 config
@@ -155,6 +331,45 @@ impl Registry {
         // Register Zoho Cliq connector (webhook-only, no config required for MVP)
         let zoho_cliq_connector = Arc::new(crate::connectors::ZohoCliqConnector::new());
         crate::connectors::register_zoho_cliq_connector(&mut reg, zoho_cliq_connector);
+
+        // Register inbound email connector (webhook-only, fed by POST /ingest/email)
+        let inbound_email_connector = Arc::new(crate::connectors::InboundEmailConnector::new(
+            crate::mail::integration::installed_spam_filter(),
+        ));
+        crate::connectors::register_inbound_email_connector(&mut reg, inbound_email_connector);
+
+        // Register IMAP connector; each connection names its server in metadata
+        let imap_connector = Arc::new(crate::connectors::ImapConnector::new(
+            crate::mail::integration::installed_spam_filter(),
+        ));
+        crate::connectors::register_imap_connector(&mut reg, imap_connector);
+
+        // Register scenario-driven fake providers (test and demo builds only)
+        if let Some(path) = config.fake_connector_scenario.as_deref() {
+            #[cfg(feature = "fake-connectors")]
+            match crate::connectors::fake::FakeScenario::load(std::path::Path::new(path)) {
+                Ok(scenario) => {
+                    crate::connectors::fake::register_fake_connectors(&mut reg, scenario)
+                }
+                Err(err) => warn!("Fake connectors not registered: {}", err),
+            }
+            #[cfg(not(feature = "fake-connectors"))]
+            warn!(
+                "Ignoring fake connector scenario {}: built without the fake-connectors feature",
+                path
+            );
+        }
+
+        // Register connectors shipped as WebAssembly plugins; built-in slugs take precedence
+        if let Some(dir) = config.plugin_dir.as_deref() {
+            #[cfg(feature = "wasm-plugins")]
+            crate::connectors::plugin::register_plugins(&mut reg, std::path::Path::new(dir));
+            #[cfg(not(feature = "wasm-plugins"))]
+            warn!(
+                "Ignoring plugin directory {}: built without the wasm-plugins feature",
+                dir
+            );
+        }
     }
 
     /// Register a new provider with its connector and metadata
@@ -182,6 +397,13 @@ impl Registry {
         }
     }
 
+    /// Check if a provider connects with an API key or client credentials
+    pub fn uses_static_credentials(&self, name: &str) -> bool {
+        self.metadata
+            .get(name)
+            .is_some_and(|metadata| metadata.auth_type.uses_static_credentials())
+    }
+
     /// Get a connector by provider name
     pub fn get(&self, name: &str) -> Result<Arc<dyn Connector>, RegistryError> {
         self.connectors
@@ -259,6 +481,8 @@ mod tests {
                 display_name: None,
                 access_token_ciphertext: None,
                 refresh_token_ciphertext: None,
+                access_token: None,
+                refresh_token: None,
                 expires_at: None,
                 scopes: None,
                 metadata: None,
@@ -349,6 +573,30 @@ mod tests {
         assert!(metadata.webhooks);
     }
 
+    #[tokio::test]
+    async fn test_registry_static_credential_providers() {
+        let mut registry = Registry::new();
+        for (name, auth_type) in [
+            ("datadog", crate::connectors::AuthType::ApiKey),
+            ("pagerduty", crate::connectors::AuthType::ClientCredentials),
+            ("github", crate::connectors::AuthType::OAuth2),
+        ] {
+            registry.register(
+                Arc::new(TestConnector),
+                ProviderMetadata::new(name.to_string(), auth_type, vec![], false),
+            );
+        }
+
+        assert!(registry.uses_static_credentials("datadog"));
+        assert!(registry.uses_static_credentials("pagerduty"));
+        assert!(!registry.uses_static_credentials("github"));
+        assert!(!registry.uses_static_credentials("unknown"));
+        assert_eq!(
+            crate::connectors::AuthType::ClientCredentials.as_str(),
+            "client_credentials"
+        );
+    }
+
     #[tokio::test]
     async fn test_registry_list_ordering() {
         let mut registry = Registry::new();
@@ -398,7 +646,10 @@ mod tests {
             crate::connectors::AuthType::OAuth2,
             vec!["read".to_string(), "write".to_string()],
             true,
-        );
+        )
+        .with_category(crate::connectors::ProviderCategory::Files)
+        .with_description("Test provider")
+        .with_docs_url("https://example.com/docs");
 
         registry.register(Arc::new(TestConnector), provider_metadata.clone());
 
@@ -408,6 +659,10 @@ mod tests {
         assert_eq!(retrieved.auth_type, provider_metadata.auth_type);
         assert_eq!(retrieved.scopes, provider_metadata.scopes);
         assert_eq!(retrieved.webhooks, provider_metadata.webhooks);
+        assert_eq!(retrieved.category, provider_metadata.category);
+        assert_eq!(retrieved.description.as_deref(), Some("Test provider"));
+        assert_eq!(retrieved.docs_url, provider_metadata.docs_url);
+        assert!(retrieved.icon_url.is_none());
     }
 
     #[tokio::test]
@@ -0,0 +1,638 @@
+//! RSS and Atom feed connector implementation
+//!
+//! Each connection polls one public feed whose URL is kept in the
+//! connection's `feed_url` metadata setting. RSS 2.0, RSS 1.0 (RDF) and Atom
+//! documents are supported. New entries become `message_posted` signals keyed
+//! on the entry's GUID, so press and news monitoring feeds the weak signal
+//! engine like any other message source.
+
+use anyhow::anyhow;
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use reqwest::{Client, StatusCode, header};
+use std::collections::HashSet;
+use std::sync::Arc;
+use tracing::{debug, info};
+use url::Url;
+use uuid::Uuid;
+
+use crate::connectors::{
+    AuthType, Connector, Cursor, ProviderCategory, ProviderMetadata, Registry,
+    trait_::{
+        AuthorizeParams, ExchangeTokenParams, SyncError, SyncParams, SyncResult, WebhookParams,
+    },
+};
+use crate::models::{connection::Model as Connection, signal::Model as Signal};
+use crate::normalization::SignalKind;
+
+/// Provider slug
+pub const RSS_PROVIDER_SLUG: &str = "rss";
+
+/// Connection metadata key holding the feed URL
+pub const RSS_FEED_URL_KEY: &str = "feed_url";
+
+/// Largest feed document read, in bytes
+const MAX_FEED_BYTES: usize = 5 * 1024 * 1024;
+
+/// Entries remembered between syncs; feeds list far fewer
+const MAX_SEEN_ENTRIES: usize = 500;
+
+/// How far back the first sync reads; older and undated entries are only marked seen
+const INITIAL_LOOKBACK_DAYS: i64 = 7;
+
+/// RSS and Atom feed connector
+pub struct RssConnector {
+    http_client: Client,
+}
+
+/// Entry parsed from an RSS item or Atom entry
+#[derive(Debug, Clone, PartialEq)]
+struct FeedEntry {
+    guid: String,
+    title: Option<String>,
+    link: Option<String>,
+    summary: Option<String>,
+    author: Option<String>,
+    categories: Vec<String>,
+    published_at: Option<DateTime<Utc>>,
+}
+
+/// Feed title and entries in document order
+#[derive(Debug, Default)]
+struct Feed {
+    title: Option<String>,
+    entries: Vec<FeedEntry>,
+}
+
+/// Sync state: entries already emitted and validators for conditional requests
+#[derive(Debug, Default)]
+struct FeedState {
+    seen: Vec<String>,
+    etag: Option<String>,
+    last_modified: Option<String>,
+}
+
+impl FeedState {
+    fn from_cursor(cursor: Option<&Cursor>) -> Option<Self> {
+        let value = cursor?.as_json();
+        let text = |key: &str| value.get(key).and_then(|v| v.as_str()).map(str::to_string);
+        Some(Self {
+            seen: value
+                .get("seen")
+                .and_then(|v| v.as_array())
+                .map(|seen| {
+                    seen.iter()
+                        .filter_map(|guid| guid.as_str().map(str::to_string))
+                        .collect()
+                })
+                .unwrap_or_default(),
+            etag: text("etag"),
+            last_modified: text("last_modified"),
+        })
+    }
+
+    fn to_cursor(&self) -> Cursor {
+        Cursor::from_json(serde_json::json!({
+            "seen": self.seen,
+            "etag": self.etag,
+            "last_modified": self.last_modified,
+        }))
+    }
+}
+
+impl Default for RssConnector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RssConnector {
+    /// Create a new feed connector
+    pub fn new() -> Self {
+        Self {
+            http_client: crate::egress::client(),
+        }
+    }
+
+    /// Fetch the feed document; `None` when it has not changed since `state`
+    async fn fetch(
+        &self,
+        feed_url: &str,
+        state: Option<&FeedState>,
+    ) -> Result<Option<(String, Option<String>, Option<String>)>, SyncError> {
+        let mut request = self.http_client.get(feed_url).header(
+            header::ACCEPT,
+            "application/rss+xml, application/atom+xml, application/xml;q=0.9, text/xml;q=0.8",
+        );
+        if let Some(state) = state {
+            if let Some(etag) = &state.etag {
+                request = request.header(header::IF_NONE_MATCH, etag);
+            }
+            if let Some(last_modified) = &state.last_modified {
+                request = request.header(header::IF_MODIFIED_SINCE, last_modified);
+            }
+        }
+        let response = request
+            .send()
+            .await
+            .map_err(|e| SyncError::transient(format!("Feed request failed: {}", e)))?;
+
+        let status = response.status();
+        if status == StatusCode::NOT_MODIFIED {
+            return Ok(None);
+        }
+        if status == StatusCode::TOO_MANY_REQUESTS {
+            let retry_after = response
+                .headers()
+                .get(header::RETRY_AFTER)
+                .and_then(|h| h.to_str().ok())
+                .and_then(|s| s.parse::<u64>().ok());
+            return Err(SyncError::rate_limited(retry_after));
+        }
+        if status.is_server_error() {
+            return Err(SyncError::transient(format!(
+                "Feed request to {} failed: {}",
+                feed_url, status
+            )));
+        }
+        if !status.is_success() {
+            return Err(SyncError::permanent(format!(
+                "Feed request to {} failed: {}",
+                feed_url, status
+            )));
+        }
+
+        let validator = |name: header::HeaderName| {
+            response
+                .headers()
+                .get(name)
+                .and_then(|h| h.to_str().ok())
+                .map(str::to_string)
+        };
+        let etag = validator(header::ETAG);
+        let last_modified = validator(header::LAST_MODIFIED);
+        let body = response
+            .bytes()
+            .await
+            .map_err(|e| SyncError::transient(format!("Feed download failed: {}", e)))?;
+        if body.len() > MAX_FEED_BYTES {
+            return Err(SyncError::permanent(format!(
+                "Feed at {} is larger than {} bytes",
+                feed_url, MAX_FEED_BYTES
+            )));
+        }
+        let document = String::from_utf8_lossy(&body).into_owned();
+        Ok(Some((document, etag, last_modified)))
+    }
+}
+
+#[async_trait]
+impl Connector for RssConnector {
+    async fn authorize(
+        &self,
+        _params: AuthorizeParams,
+    ) -> Result<Url, Box<dyn std::error::Error + Send + Sync>> {
+        Err(anyhow!("OAuth authorization is not supported for feeds").into())
+    }
+
+    async fn exchange_token(
+        &self,
+        _params: ExchangeTokenParams,
+    ) -> Result<Connection, Box<dyn std::error::Error + Send + Sync>> {
+        Err(anyhow!("Token exchange is not supported for feeds").into())
+    }
+
+    async fn refresh_token(
+        &self,
+        _connection: Connection,
+    ) -> Result<Connection, Box<dyn std::error::Error + Send + Sync>> {
+        Err(anyhow!("Token refresh is not supported for feeds").into())
+    }
+
+    async fn sync(
+        &self,
+        params: SyncParams,
+    ) -> Result<SyncResult, Box<dyn std::error::Error + Send + Sync>> {
+        let connection = &params.connection;
+        let feed_url = connection
+            .metadata
+            .as_ref()
+            .and_then(|m| m.get(RSS_FEED_URL_KEY))
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| {
+                SyncError::permanent(format!(
+                    "Feed connection has no '{}' metadata setting",
+                    RSS_FEED_URL_KEY
+                ))
+            })?;
+        info!(
+            tenant_id = %connection.tenant_id,
+            connection_id = %connection.id,
+            has_cursor = %params.cursor.is_some(),
+            "Starting feed sync"
+        );
+
+        let state = FeedState::from_cursor(params.cursor.as_ref());
+        let Some((document, etag, last_modified)) = self.fetch(feed_url, state.as_ref()).await?
+        else {
+            debug!(connection_id = %connection.id, "Feed not modified");
+            return Ok(SyncResult {
+                signals: vec![],
+                next_cursor: params.cursor,
+                has_more: false,
+            });
+        };
+        let feed = parse_feed(&document).map_err(SyncError::permanent)?;
+
+        let seen: HashSet<&str> = state
+            .as_ref()
+            .map(|s| s.seen.iter().map(String::as_str).collect())
+            .unwrap_or_default();
+        // The first sync only reads recent entries instead of the whole archive
+        let cutoff = state
+            .is_none()
+            .then(|| Utc::now() - Duration::days(INITIAL_LOOKBACK_DAYS));
+        let signals: Vec<Signal> = feed
+            .entries
+            .iter()
+            .filter(|entry| !seen.contains(entry.guid.as_str()))
+            .filter(|entry| match cutoff {
+                Some(cutoff) => entry.published_at.is_some_and(|at| at >= cutoff),
+                None => true,
+            })
+            .map(|entry| build_entry_signal(connection, feed_url, feed.title.as_deref(), entry))
+            .collect();
+
+        let next_state = FeedState {
+            seen: feed
+                .entries
+                .iter()
+                .map(|entry| entry.guid.clone())
+                .take(MAX_SEEN_ENTRIES)
+                .collect(),
+            etag,
+            last_modified,
+        };
+
+        debug!(
+            connection_id = %connection.id,
+            entries = feed.entries.len(),
+            signals = signals.len(),
+            "Feed sync completed"
+        );
+
+        Ok(SyncResult {
+            signals,
+            next_cursor: Some(next_state.to_cursor()),
+            has_more: false,
+        })
+    }
+
+    async fn handle_webhook(
+        &self,
+        _params: WebhookParams,
+    ) -> Result<Vec<Signal>, Box<dyn std::error::Error + Send + Sync>> {
+        Err(anyhow!("Webhooks are not supported for feeds").into())
+    }
+}
+
+/// Register the feed connector in the registry
+pub fn register_rss_connector(registry: &mut Registry, connector: Arc<RssConnector>) {
+    let metadata = ProviderMetadata::new(
+        RSS_PROVIDER_SLUG.to_string(),
+        AuthType::Custom("none".to_string()),
+        vec![],
+        false, // webhooks not supported
+    )
+    .with_category(ProviderCategory::Other)
+    .with_description("Poll RSS and Atom feeds for press and news")
+    .with_icon_url("https://cdn.simpleicons.org/rss")
+    .with_docs_url("https://www.rssboard.org/rss-specification")
+    .with_backfill(true)
+    .with_signal_kinds(&[SignalKind::MessagePosted]);
+
+    registry.register(connector, metadata);
+}
+
+/// Parse an RSS 2.0, RSS 1.0 or Atom document
+fn parse_feed(document: &str) -> Result<Feed, String> {
+    let options = roxmltree::ParsingOptions {
+        allow_dtd: true,
+        ..Default::default()
+    };
+    let doc = roxmltree::Document::parse_with_options(document, options)
+        .map_err(|e| format!("Feed is not valid XML: {}", e))?;
+    let root = doc.root_element();
+
+    match root.tag_name().name() {
+        "rss" => {
+            let channel = child(root, "channel").ok_or("RSS feed has no channel")?;
+            Ok(Feed {
+                title: child_text(channel, "title"),
+                entries: children(channel, "item").filter_map(rss_entry).collect(),
+            })
+        }
+        // RSS 1.0 lists items beside the channel
+        "RDF" => Ok(Feed {
+            title: child(root, "channel").and_then(|channel| child_text(channel, "title")),
+            entries: children(root, "item").filter_map(rss_entry).collect(),
+        }),
+        "feed" => Ok(Feed {
+            title: child_text(root, "title"),
+            entries: children(root, "entry").filter_map(atom_entry).collect(),
+        }),
+        other => Err(format!("Unsupported feed root element '{}'", other)),
+    }
+}
+
+fn children<'a, 'input>(
+    node: roxmltree::Node<'a, 'input>,
+    name: &'static str,
+) -> impl Iterator<Item = roxmltree::Node<'a, 'input>> {
+    node.children()
+        .filter(move |n| n.is_element() && n.tag_name().name() == name)
+}
+
+fn child<'a, 'input>(
+    node: roxmltree::Node<'a, 'input>,
+    name: &'static str,
+) -> Option<roxmltree::Node<'a, 'input>> {
+    children(node, name).next()
+}
+
+/// Trimmed text of an element, including CDATA sections
+fn text_of(node: roxmltree::Node) -> Option<String> {
+    let text: String = node
+        .descendants()
+        .filter(|n| n.is_text())
+        .filter_map(|n| n.text())
+        .collect();
+    let text = text.trim();
+    (!text.is_empty()).then(|| text.to_string())
+}
+
+fn child_text(node: roxmltree::Node, name: &'static str) -> Option<String> {
+    child(node, name).and_then(text_of)
+}
+
+/// RSS dates are RFC 2822; RSS 1.0's `dc:date` and Atom dates are RFC 3339
+fn parse_date(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc2822(value)
+        .or_else(|_| DateTime::parse_from_rfc3339(value))
+        .ok()
+        .map(|dt| dt.with_timezone(&Utc))
+}
+
+fn rss_entry(item: roxmltree::Node) -> Option<FeedEntry> {
+    let title = child_text(item, "title");
+    let link = child_text(item, "link");
+    let guid = child_text(item, "guid")
+        .or_else(|| {
+            item.attributes()
+                .find(|a| a.name() == "about")
+                .map(|a| a.value().to_string())
+        })
+        .or_else(|| link.clone())
+        .or_else(|| title.clone())?;
+    Some(FeedEntry {
+        guid,
+        summary: child_text(item, "description"),
+        author: child_text(item, "creator").or_else(|| child_text(item, "author")),
+        categories: children(item, "category").filter_map(text_of).collect(),
+        published_at: child_text(item, "pubDate")
+            .or_else(|| child_text(item, "date"))
+            .and_then(|date| parse_date(&date)),
+        title,
+        link,
+    })
+}
+
+fn atom_entry(entry: roxmltree::Node) -> Option<FeedEntry> {
+    let link = children(entry, "link")
+        .find(|link| matches!(link.attribute("rel"), None | Some("alternate")))
+        .and_then(|link| link.attribute("href"))
+        .map(str::to_string);
+    let title = child_text(entry, "title");
+    let guid = child_text(entry, "id")
+        .or_else(|| link.clone())
+        .or_else(|| title.clone())?;
+    Some(FeedEntry {
+        guid,
+        summary: child_text(entry, "summary").or_else(|| child_text(entry, "content")),
+        author: child(entry, "author").and_then(|author| child_text(author, "name")),
+        categories: children(entry, "category")
+            .filter_map(|category| category.attribute("term").map(str::to_string))
+            .collect(),
+        published_at: child_text(entry, "published")
+            .or_else(|| child_text(entry, "updated"))
+            .and_then(|date| parse_date(&date)),
+        title,
+        link,
+    })
+}
+
+/// Build a `message_posted` signal for a feed entry
+fn build_entry_signal(
+    connection: &Connection,
+    feed_url: &str,
+    feed_title: Option<&str>,
+    entry: &FeedEntry,
+) -> Signal {
+    let received_at = DateTime::from(Utc::now());
+    let occurred_at = entry.published_at.unwrap_or_else(Utc::now);
+    let kind = SignalKind::MessagePosted;
+
+    Signal {
+        id: Uuid::new_v4(),
+        tenant_id: connection.tenant_id,
+        provider_slug: RSS_PROVIDER_SLUG.to_string(),
+        connection_id: connection.id,
+        kind: kind.as_str().to_string(),
+        occurred_at: occurred_at.into(),
+        received_at,
+        payload: serde_json::json!({
+            "feed_url": feed_url,
+            "feed_title": feed_title,
+            "guid": entry.guid,
+            "title": entry.title,
+            "url": entry.link,
+            "author": entry.author,
+            "categories": entry.categories,
+            "text": entry.summary,
+            "published_at": entry.published_at.map(|at| at.to_rfc3339()),
+        }),
+        dedupe_key: Some(format!("rss:{}:{}:{}", kind, connection.id, entry.guid)),
+        created_at: received_at,
+        updated_at: received_at,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn connection(feed_url: &str) -> Connection {
+        let now = DateTime::from(Utc::now());
+        Connection {
+            id: Uuid::new_v4(),
+            tenant_id: Uuid::new_v4(),
+            provider_slug: RSS_PROVIDER_SLUG.to_string(),
+            external_id: feed_url.to_string(),
+            status: "active".to_string(),
+            display_name: None,
+            access_token_ciphertext: None,
+            refresh_token_ciphertext: None,
+            access_token: None,
+            refresh_token: None,
+            expires_at: None,
+            scopes: None,
+            metadata: Some(serde_json::json!({ RSS_FEED_URL_KEY: feed_url })),
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    #[test]
+    fn test_parse_rss_and_atom_feeds() {
+        let rss = parse_feed(
+            r#"<?xml version="1.0"?>
+            <rss version="2.0" xmlns:dc="http://purl.org/dc/elements/1.1/">
+              <channel>
+                <title>Acme Newsroom</title>
+                <item>
+                  <title>Acme raises Series B</title>
+                  <link>https://acme.example/news/series-b</link>
+                  <guid isPermaLink="false">news-42</guid>
+                  <description><![CDATA[<p>Acme closed a round.</p>]]></description>
+                  <dc:creator>Press Team</dc:creator>
+                  <category>Funding</category>
+                  <pubDate>Thu, 02 May 2024 12:00:00 GMT</pubDate>
+                </item>
+                <item><link>https://acme.example/news/undated</link></item>
+              </channel>
+            </rss>"#,
+        )
+        .unwrap();
+        assert_eq!(rss.title.as_deref(), Some("Acme Newsroom"));
+        assert_eq!(rss.entries[0].guid, "news-42");
+        assert_eq!(
+            rss.entries[0].summary.as_deref(),
+            Some("<p>Acme closed a round.</p>")
+        );
+        assert_eq!(rss.entries[0].author.as_deref(), Some("Press Team"));
+        assert_eq!(rss.entries[0].categories, vec!["Funding"]);
+        assert_eq!(
+            rss.entries[0].published_at.unwrap().to_rfc3339(),
+            "2024-05-02T12:00:00+00:00"
+        );
+        // Entries without a GUID fall back to their link
+        assert_eq!(rss.entries[1].guid, "https://acme.example/news/undated");
+
+        let atom = parse_feed(
+            r#"<feed xmlns="http://www.w3.org/2005/Atom">
+              <title>Acme Blog</title>
+              <entry>
+                <id>tag:acme.example,2024:post-7</id>
+                <title>Launch week</title>
+                <link rel="replies" href="https://acme.example/post-7#comments"/>
+                <link href="https://acme.example/post-7"/>
+                <author><name>Dana</name></author>
+                <category term="launch"/>
+                <updated>2024-05-02T12:00:00Z</updated>
+                <summary>Five launches in five days</summary>
+              </entry>
+            </feed>"#,
+        )
+        .unwrap();
+        assert_eq!(atom.title.as_deref(), Some("Acme Blog"));
+        assert_eq!(atom.entries[0].guid, "tag:acme.example,2024:post-7");
+        assert_eq!(
+            atom.entries[0].link.as_deref(),
+            Some("https://acme.example/post-7")
+        );
+        assert_eq!(atom.entries[0].author.as_deref(), Some("Dana"));
+        assert_eq!(atom.entries[0].categories, vec!["launch"]);
+
+        assert!(parse_feed("<html><body>Not a feed</body></html>").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_rss_sync_emits_new_entries_once() {
+        let server = MockServer::start().await;
+        let recent = (Utc::now() - Duration::hours(1)).to_rfc2822();
+        let old = (Utc::now() - Duration::days(30)).to_rfc2822();
+        let feed = |items: &str| {
+            format!(
+                "<rss version=\"2.0\"><channel><title>News</title>{}</channel></rss>",
+                items
+            )
+        };
+        let first_items = format!(
+            "<item><title>Recent</title><guid>a</guid><pubDate>{}</pubDate></item>\
+             <item><title>Old</title><guid>b</guid><pubDate>{}</pubDate></item>",
+            recent, old
+        );
+        Mock::given(method("GET"))
+            .and(path("/feed.xml"))
+            .and(header("If-None-Match", "\"v1\""))
+            .respond_with(ResponseTemplate::new(304))
+            .up_to_n_times(1)
+            .with_priority(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/feed.xml"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("ETag", "\"v1\"")
+                    .set_body_string(feed(&first_items)),
+            )
+            .up_to_n_times(1)
+            .with_priority(2)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/feed.xml"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(feed(&format!(
+                "<item><title>Undated</title><guid>c</guid></item>{}",
+                first_items
+            ))))
+            .with_priority(3)
+            .mount(&server)
+            .await;
+
+        let connector = RssConnector::new();
+        let connection = connection(&format!("{}/feed.xml", server.uri()));
+        let sync = |cursor: Option<Cursor>| SyncParams {
+            connection: connection.clone(),
+            cursor,
+            cancellation: Default::default(),
+        };
+
+        // The first sync skips entries older than the lookback window
+        let first = connector.sync(sync(None)).await.unwrap();
+        assert_eq!(first.signals.len(), 1);
+        assert_eq!(first.signals[0].kind, "message_posted");
+        assert_eq!(first.signals[0].payload["title"], "Recent");
+        assert_eq!(first.signals[0].payload["feed_title"], "News");
+        assert_eq!(
+            first.signals[0].dedupe_key,
+            Some(format!("rss:message_posted:{}:a", connection.id))
+        );
+
+        // An unchanged feed keeps the cursor
+        let unchanged = connector
+            .sync(sync(first.next_cursor.clone()))
+            .await
+            .unwrap();
+        assert!(unchanged.signals.is_empty());
+        assert_eq!(unchanged.next_cursor, first.next_cursor);
+
+        // Later syncs emit every unseen entry
+        let later = connector.sync(sync(unchanged.next_cursor)).await.unwrap();
+        assert_eq!(later.signals.len(), 1);
+        assert_eq!(later.signals[0].payload["guid"], "c");
+    }
+}
@@ -0,0 +1,995 @@
+//! Salesforce connector implementation
+//!
+//! Salesforce CRM through the OAuth 2.0 web server flow. Each sync runs
+//! `queryAll` SOQL queries over opportunities and cases ordered by
+//! `SystemModstamp` and resumes from the per-object position stored in the
+//! cursor, so deleted records are seen as well. Change Data Capture events
+//! relayed to the webhook endpoint are verified in
+//! [`crate::webhook_verification`] before reaching this connector.
+
+use anyhow::anyhow;
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, SecondsFormat, Utc};
+use reqwest::{Client, StatusCode};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use tracing::{debug, info};
+use url::Url;
+use uuid::Uuid;
+
+use crate::connectors::{
+    AuthType, Connector, Cursor, ProviderCategory, ProviderMetadata, Registry,
+    trait_::{
+        AuthorizeParams, ExchangeTokenParams, SyncError, SyncParams, SyncResult, WebhookParams,
+    },
+};
+use crate::models::{connection::Model as Connection, signal::Model as Signal};
+use crate::normalization::{
+    SignalKind, normalize_salesforce_change_event_kind, normalize_salesforce_record_kind,
+    parse_salesforce_datetime,
+};
+
+/// Provider slug
+pub const SALESFORCE_PROVIDER_SLUG: &str = "salesforce";
+
+/// Scopes requested at authorization time
+pub const SALESFORCE_SCOPES: &[&str] = &["api", "refresh_token"];
+
+/// REST API version used for queries
+const API_VERSION: &str = "v60.0";
+
+/// Records requested per SOQL query
+const PAGE_SIZE: u32 = 200;
+
+/// Queries run per object in one sync; the rest continues on the next run
+const MAX_QUERIES_PER_OBJECT: u32 = 5;
+
+/// How far back the first sync reads
+const INITIAL_LOOKBACK_DAYS: i64 = 30;
+
+/// Synced objects and the fields selected for each
+const SYNCED_OBJECTS: &[(&str, &str)] = &[
+    (
+        "Opportunity",
+        "Id, Name, StageName, Amount, CloseDate, Probability, IsClosed, IsWon, IsDeleted, \
+         AccountId, OwnerId, CreatedDate, LastModifiedDate, LastStageChangeDate, SystemModstamp",
+    ),
+    (
+        "Case",
+        "Id, CaseNumber, Subject, Status, Priority, Origin, IsClosed, IsDeleted, ClosedDate, \
+         AccountId, ContactId, OwnerId, CreatedDate, LastModifiedDate, SystemModstamp",
+    ),
+];
+
+/// Salesforce connector
+pub struct SalesforceConnector {
+    client_id: String,
+    client_secret: String,
+    login_base: String,
+    http_client: Client,
+}
+
+/// Query position of one object, stored in the cursor under the object name
+///
+/// Records are read in `(SystemModstamp, Id)` order, so the pair of the last
+/// record read is where the next query starts.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct ObjectPosition {
+    since: DateTime<Utc>,
+    #[serde(default)]
+    last_id: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct SalesforceTokenResponse {
+    access_token: String,
+    #[serde(default)]
+    refresh_token: Option<String>,
+    instance_url: String,
+    /// Identity URL ending in `/<org id>/<user id>`
+    #[serde(default)]
+    id: Option<String>,
+    #[serde(default)]
+    scope: Option<String>,
+}
+
+impl SalesforceConnector {
+    /// Create a new Salesforce connector with the connected app configuration
+    pub fn new(client_id: String, client_secret: String, login_base: String) -> Self {
+        Self {
+            client_id,
+            client_secret,
+            login_base: login_base.trim_end_matches('/').to_string(),
+            http_client: crate::egress::client(),
+        }
+    }
+
+    fn default_redirect_uri() -> String {
+        match std::env::var("POBLYSH_PROFILE").as_deref() {
+            Ok("local") | Ok("test") | Err(_) => "http://localhost:3000/callback".to_string(),
+            Ok(_) => "https://app.poblysh.com/callback".to_string(),
+        }
+    }
+
+    fn access_token(connection: &Connection) -> Result<String, SyncError> {
+        connection
+            .access_token
+            .clone()
+            .filter(|token| !token.is_empty())
+            .ok_or_else(|| SyncError::unauthorized("Missing Salesforce access token"))
+    }
+
+    /// Call `/services/oauth2/token` with the given grant
+    async fn oauth_token(
+        &self,
+        grant: &[(&str, &str)],
+    ) -> Result<SalesforceTokenResponse, anyhow::Error> {
+        let mut form = vec![
+            ("client_id", self.client_id.as_str()),
+            ("client_secret", self.client_secret.as_str()),
+        ];
+        form.extend_from_slice(grant);
+
+        let response = self
+            .http_client
+            .post(format!("{}/services/oauth2/token", self.login_base))
+            .form(&form)
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let body: serde_json::Value = response.json().await.unwrap_or_default();
+            return Err(anyhow!(
+                "Salesforce token endpoint returned {}: {}",
+                status,
+                body.get("error")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("unknown_error")
+            ));
+        }
+        Ok(response.json().await?)
+    }
+
+    /// Run a SOQL query through `queryAll`, mapping HTTP failures to sync errors
+    async fn query_all(
+        &self,
+        access_token: &str,
+        instance_url: &str,
+        soql: &str,
+    ) -> Result<serde_json::Value, SyncError> {
+        let response = self
+            .http_client
+            .get(format!(
+                "{}/services/data/{}/queryAll",
+                instance_url, API_VERSION
+            ))
+            .bearer_auth(access_token)
+            .header("Accept", "application/json")
+            .query(&[("q", soql)])
+            .send()
+            .await
+            .map_err(|e| SyncError::transient(format!("Salesforce request failed: {}", e)))?;
+
+        let status = response.status();
+        if status == StatusCode::UNAUTHORIZED {
+            return Err(SyncError::unauthorized(format!(
+                "Salesforce query failed: {}",
+                status
+            )));
+        }
+        if status == StatusCode::TOO_MANY_REQUESTS {
+            return Err(SyncError::rate_limited(None));
+        }
+        if status.is_server_error() {
+            return Err(SyncError::transient(format!(
+                "Salesforce query failed: {}",
+                status
+            )));
+        }
+        if !status.is_success() {
+            let body: serde_json::Value = response.json().await.unwrap_or_default();
+            // A spent daily API quota is reported as 403 REQUEST_LIMIT_EXCEEDED
+            if error_code(&body) == Some("REQUEST_LIMIT_EXCEEDED") {
+                return Err(SyncError::rate_limited(None));
+            }
+            return Err(
+                SyncError::permanent(format!("Salesforce query failed: {}", status))
+                    .with_details(body),
+            );
+        }
+
+        response
+            .json()
+            .await
+            .map_err(|e| SyncError::transient(format!("Salesforce returned bad JSON: {}", e)))
+    }
+
+    /// Read one object's records changed after `position`, oldest first
+    ///
+    /// Returns the signals, the new position and whether records remain.
+    async fn sync_object(
+        &self,
+        access_token: &str,
+        connection: &Connection,
+        instance_url: &str,
+        (object, fields): (&str, &str),
+        mut position: ObjectPosition,
+    ) -> Result<(Vec<Signal>, ObjectPosition, bool), SyncError> {
+        let since = Some(position.since);
+        let mut signals = Vec::new();
+
+        for _ in 0..MAX_QUERIES_PER_OBJECT {
+            let soql = format!(
+                "SELECT {fields} FROM {object} \
+                 WHERE SystemModstamp > {at} OR (SystemModstamp = {at} AND Id > '{id}') \
+                 ORDER BY SystemModstamp, Id LIMIT {PAGE_SIZE}",
+                at = position.since.to_rfc3339_opts(SecondsFormat::Secs, true),
+                id = position.last_id,
+            );
+            let body = self.query_all(access_token, instance_url, &soql).await?;
+            let records = body
+                .get("records")
+                .and_then(|v| v.as_array())
+                .cloned()
+                .unwrap_or_default();
+
+            for record in &records {
+                let (Some(id), Some(modstamp)) = (
+                    record.get("Id").and_then(|v| v.as_str()),
+                    record_time(record, "SystemModstamp"),
+                ) else {
+                    continue;
+                };
+                position = ObjectPosition {
+                    since: modstamp,
+                    last_id: id.to_string(),
+                };
+                if let Some(kind) = normalize_salesforce_record_kind(record, since) {
+                    signals.push(build_record_signal(connection, instance_url, kind, record));
+                }
+            }
+
+            if records.len() < PAGE_SIZE as usize {
+                return Ok((signals, position, false));
+            }
+        }
+
+        Ok((signals, position, true))
+    }
+}
+
+#[async_trait]
+impl Connector for SalesforceConnector {
+    async fn authorize(
+        &self,
+        params: AuthorizeParams,
+    ) -> Result<Url, Box<dyn std::error::Error + Send + Sync>> {
+        info!(
+            tenant_id = %params.tenant_id,
+            "Generating Salesforce OAuth authorization URL"
+        );
+
+        let mut url = Url::parse(&format!("{}/services/oauth2/authorize", self.login_base))?;
+        let redirect_uri = params
+            .redirect_uri
+            .unwrap_or_else(Self::default_redirect_uri);
+        let state = params
+            .state
+            .filter(|s| !s.is_empty())
+            .unwrap_or_else(|| Uuid::new_v4().to_string());
+        url.query_pairs_mut()
+            .append_pair("response_type", "code")
+            .append_pair("client_id", &self.client_id)
+            .append_pair("redirect_uri", &redirect_uri)
+            .append_pair("scope", &SALESFORCE_SCOPES.join(" "))
+            .append_pair("state", &state)
+            .append_pair("prompt", "consent");
+
+        debug!(
+            tenant_id = %params.tenant_id,
+            authorize_url = %url,
+            "Generated Salesforce OAuth authorization URL"
+        );
+        Ok(url)
+    }
+
+    async fn exchange_token(
+        &self,
+        params: ExchangeTokenParams,
+    ) -> Result<Connection, Box<dyn std::error::Error + Send + Sync>> {
+        info!(
+            tenant_id = %params.tenant_id,
+            "Exchanging Salesforce authorization code for tokens"
+        );
+
+        let redirect_uri = params
+            .redirect_uri
+            .unwrap_or_else(Self::default_redirect_uri);
+        let token = self
+            .oauth_token(&[
+                ("grant_type", "authorization_code"),
+                ("code", params.code.as_str()),
+                ("redirect_uri", redirect_uri.as_str()),
+            ])
+            .await?;
+
+        let mut identity = token
+            .id
+            .as_deref()
+            .unwrap_or_default()
+            .rsplit('/')
+            .filter(|segment| !segment.is_empty());
+        let user_id = identity.next().map(str::to_string);
+        let org_id = identity
+            .next()
+            .map(str::to_string)
+            .ok_or_else(|| anyhow!("Salesforce token response has no identity URL"))?;
+        let instance_url = token.instance_url.trim_end_matches('/').to_string();
+        let now = DateTime::from(Utc::now());
+
+        Ok(Connection {
+            id: Uuid::new_v4(),
+            tenant_id: params.tenant_id,
+            provider_slug: SALESFORCE_PROVIDER_SLUG.to_string(),
+            external_id: org_id.clone(),
+            status: "active".to_string(),
+            display_name: Url::parse(&instance_url)
+                .ok()
+                .and_then(|url| url.host_str().map(str::to_string)),
+            access_token_ciphertext: None,
+            refresh_token_ciphertext: None,
+            access_token: Some(token.access_token),
+            refresh_token: token.refresh_token,
+            // Salesforce does not report token lifetimes; expiry surfaces as a 401 on sync
+            expires_at: None,
+            scopes: token.scope.map(|scope| {
+                serde_json::Value::Array(
+                    scope
+                        .split_whitespace()
+                        .map(|s| serde_json::Value::String(s.to_string()))
+                        .collect(),
+                )
+            }),
+            metadata: Some(serde_json::json!({
+                "provider": SALESFORCE_PROVIDER_SLUG,
+                "instance_url": instance_url,
+                "org_id": org_id,
+                "user_id": user_id,
+            })),
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    async fn refresh_token(
+        &self,
+        connection: Connection,
+    ) -> Result<Connection, Box<dyn std::error::Error + Send + Sync>> {
+        let Some(refresh_token) = connection
+            .refresh_token
+            .clone()
+            .filter(|token| !token.is_empty())
+        else {
+            return Err(
+                SyncError::unauthorized("Salesforce connection has no refresh token").into(),
+            );
+        };
+
+        info!(connection_id = %connection.id, "Refreshing Salesforce token");
+        let token = self
+            .oauth_token(&[
+                ("grant_type", "refresh_token"),
+                ("refresh_token", refresh_token.as_str()),
+            ])
+            .await?;
+
+        // The org can move to another instance, so the URL is taken from every refresh
+        let mut metadata = connection
+            .metadata
+            .clone()
+            .unwrap_or_else(|| serde_json::json!({}));
+        metadata["instance_url"] =
+            serde_json::Value::String(token.instance_url.trim_end_matches('/').to_string());
+
+        Ok(Connection {
+            access_token: Some(token.access_token),
+            refresh_token: token.refresh_token.or(connection.refresh_token.clone()),
+            metadata: Some(metadata),
+            updated_at: DateTime::from(Utc::now()),
+            ..connection
+        })
+    }
+
+    async fn sync(
+        &self,
+        params: SyncParams,
+    ) -> Result<SyncResult, Box<dyn std::error::Error + Send + Sync>> {
+        let connection = &params.connection;
+        info!(
+            tenant_id = %connection.tenant_id,
+            connection_id = %connection.id,
+            has_cursor = %params.cursor.is_some(),
+            "Starting Salesforce sync"
+        );
+
+        let access_token = Self::access_token(connection)?;
+        let instance_url = connection
+            .metadata
+            .as_ref()
+            .and_then(|metadata| metadata.get("instance_url"))
+            .and_then(|v| v.as_str())
+            .map(str::to_string)
+            .ok_or_else(|| SyncError::permanent("Salesforce connection has no instance_url"))?;
+        let mut positions: BTreeMap<String, ObjectPosition> = params
+            .cursor
+            .as_ref()
+            .and_then(|cursor| serde_json::from_value(cursor.as_json().clone()).ok())
+            .unwrap_or_default();
+
+        let mut signals = Vec::new();
+        let mut has_more = false;
+        for &(object, fields) in SYNCED_OBJECTS {
+            let position = positions.remove(object).unwrap_or_else(|| ObjectPosition {
+                since: Utc::now() - Duration::days(INITIAL_LOOKBACK_DAYS),
+                last_id: String::new(),
+            });
+            let (object_signals, position, object_has_more) = self
+                .sync_object(
+                    &access_token,
+                    connection,
+                    &instance_url,
+                    (object, fields),
+                    position,
+                )
+                .await?;
+            signals.extend(object_signals);
+            has_more |= object_has_more;
+            positions.insert(object.to_string(), position);
+        }
+
+        debug!(
+            connection_id = %connection.id,
+            signals = signals.len(),
+            has_more,
+            "Salesforce sync completed"
+        );
+
+        Ok(SyncResult {
+            signals,
+            next_cursor: Some(Cursor::from_json(serde_json::to_value(&positions)?)),
+            has_more,
+        })
+    }
+
+    async fn handle_webhook(
+        &self,
+        params: WebhookParams,
+    ) -> Result<Vec<Signal>, Box<dyn std::error::Error + Send + Sync>> {
+        // Relays forward either the streaming envelope or just its payload
+        let event = params
+            .payload
+            .pointer("/data/payload")
+            .unwrap_or(&params.payload);
+        let Some(kind) = normalize_salesforce_change_event_kind(event) else {
+            debug!(
+                tenant_id = %params.tenant_id,
+                entity = ?event.pointer("/ChangeEventHeader/entityName"),
+                change_type = ?event.pointer("/ChangeEventHeader/changeType"),
+                "Salesforce change event ignored"
+            );
+            return Ok(vec![]);
+        };
+
+        info!(
+            tenant_id = %params.tenant_id,
+            signal_kind = %kind,
+            entity = ?event.pointer("/ChangeEventHeader/entityName"),
+            "Salesforce change event mapped to signal"
+        );
+
+        let connection_id = Uuid::nil();
+        Ok(build_change_event_signals(
+            params.tenant_id,
+            connection_id,
+            kind,
+            event,
+        )?)
+    }
+}
+
+/// Register the Salesforce connector in the registry
+pub fn register_salesforce_connector(registry: &mut Registry, connector: Arc<SalesforceConnector>) {
+    let metadata = ProviderMetadata::new(
+        SALESFORCE_PROVIDER_SLUG.to_string(),
+        AuthType::OAuth2,
+        SALESFORCE_SCOPES.iter().map(|s| s.to_string()).collect(),
+        true, // webhooks supported
+    )
+    .with_category(ProviderCategory::Crm)
+    .with_description("Sync opportunity and case activity from Salesforce")
+    .with_icon_url("https://cdn.simpleicons.org/salesforce")
+    .with_docs_url(
+        "https://help.salesforce.com/s/articleView?id=sf.remoteaccess_oauth_web_server_flow.htm",
+    )
+    .with_backfill(true)
+    .with_signal_kinds(&[
+        SignalKind::OpportunityCreated,
+        SignalKind::OpportunityUpdated,
+        SignalKind::OpportunityWon,
+        SignalKind::OpportunityLost,
+        SignalKind::OpportunityDeleted,
+        SignalKind::CaseCreated,
+        SignalKind::CaseUpdated,
+        SignalKind::CaseClosed,
+        SignalKind::CaseDeleted,
+    ]);
+
+    registry.register(connector, metadata);
+}
+
+/// `errorCode` of the first error in a REST API error response
+fn error_code(body: &serde_json::Value) -> Option<&str> {
+    body.pointer("/0/errorCode").and_then(|v| v.as_str())
+}
+
+fn record_time(record: &serde_json::Value, key: &str) -> Option<DateTime<Utc>> {
+    record
+        .get(key)
+        .and_then(|v| v.as_str())
+        .and_then(parse_salesforce_datetime)
+}
+
+/// Dedupe key shared by sync and change events for the same record change
+///
+/// A record is deleted once, so deletions are keyed by record alone.
+fn record_dedupe_key(kind: SignalKind, id: &str, modified_at: Option<DateTime<Utc>>) -> String {
+    match kind {
+        SignalKind::OpportunityDeleted | SignalKind::CaseDeleted => {
+            format!("salesforce:{}:{}", kind, id)
+        }
+        _ => format!(
+            "salesforce:{}:{}:{}",
+            kind,
+            id,
+            modified_at
+                .map(|at| at.timestamp_millis().to_string())
+                .unwrap_or_default()
+        ),
+    }
+}
+
+/// Build a signal from an Opportunity or Case returned by a SOQL query
+fn build_record_signal(
+    connection: &Connection,
+    instance_url: &str,
+    kind: SignalKind,
+    record: &serde_json::Value,
+) -> Signal {
+    let id = record
+        .get("Id")
+        .and_then(|v| v.as_str())
+        .unwrap_or_default();
+    let modified_at = record_time(record, "LastModifiedDate");
+    let occurred_at = match kind {
+        SignalKind::OpportunityWon | SignalKind::OpportunityLost => {
+            record_time(record, "LastStageChangeDate")
+        }
+        SignalKind::CaseClosed => record_time(record, "ClosedDate"),
+        SignalKind::OpportunityDeleted | SignalKind::CaseDeleted => {
+            record_time(record, "SystemModstamp")
+        }
+        _ => None,
+    }
+    .or(modified_at)
+    .unwrap_or_else(Utc::now);
+    let received_at = DateTime::from(Utc::now());
+
+    Signal {
+        id: Uuid::new_v4(),
+        tenant_id: connection.tenant_id,
+        provider_slug: SALESFORCE_PROVIDER_SLUG.to_string(),
+        connection_id: connection.id,
+        kind: kind.as_str().to_string(),
+        occurred_at: occurred_at.into(),
+        received_at,
+        payload: serde_json::json!({
+            "object": record.pointer("/attributes/type"),
+            "record_id": id,
+            "name": record.get("Name").or_else(|| record.get("Subject")),
+            "case_number": record.get("CaseNumber"),
+            "stage": record.get("StageName"),
+            "status": record.get("Status"),
+            "priority": record.get("Priority"),
+            "amount": record.get("Amount"),
+            "close_date": record.get("CloseDate"),
+            "account_id": record.get("AccountId"),
+            "owner_id": record.get("OwnerId"),
+            "url": format!("{}/{}", instance_url, id),
+            "occurred_at": occurred_at.to_rfc3339(),
+        }),
+        dedupe_key: Some(record_dedupe_key(kind, id, modified_at)),
+        created_at: received_at,
+        updated_at: received_at,
+    }
+}
+
+/// Build one signal per record named in a change event
+///
+/// Bulk operations report several records in one event, sharing its fields.
+fn build_change_event_signals(
+    tenant_id: Uuid,
+    connection_id: Uuid,
+    kind: SignalKind,
+    event: &serde_json::Value,
+) -> Result<Vec<Signal>, anyhow::Error> {
+    let header = &event["ChangeEventHeader"];
+    let record_ids: Vec<&str> = header
+        .get("recordIds")
+        .and_then(|v| v.as_array())
+        .map(|ids| ids.iter().filter_map(|id| id.as_str()).collect())
+        .unwrap_or_default();
+    if record_ids.is_empty() {
+        return Err(anyhow!(
+            "Invalid Salesforce change event: missing recordIds"
+        ));
+    }
+    let committed_at = header
+        .get("commitTimestamp")
+        .and_then(|v| v.as_i64())
+        .and_then(DateTime::from_timestamp_millis);
+    let modified_at = record_time(event, "LastModifiedDate").or(committed_at);
+    let occurred_at = modified_at.unwrap_or_else(Utc::now);
+    let received_at = DateTime::from(Utc::now());
+
+    Ok(record_ids
+        .into_iter()
+        .map(|id| Signal {
+            id: Uuid::new_v4(),
+            tenant_id,
+            provider_slug: SALESFORCE_PROVIDER_SLUG.to_string(),
+            connection_id,
+            kind: kind.as_str().to_string(),
+            occurred_at: occurred_at.into(),
+            received_at,
+            payload: serde_json::json!({
+                "object": header.get("entityName"),
+                "record_id": id,
+                "name": event.get("Name").or_else(|| event.get("Subject")),
+                "case_number": event.get("CaseNumber"),
+                "stage": event.get("StageName"),
+                "status": event.get("Status"),
+                "priority": event.get("Priority"),
+                "amount": event.get("Amount"),
+                "close_date": event.get("CloseDate"),
+                "account_id": event.get("AccountId"),
+                "owner_id": event.get("OwnerId"),
+                "changed_fields": header.get("changedFields"),
+                "commit_user": header.get("commitUser"),
+                "occurred_at": occurred_at.to_rfc3339(),
+            }),
+            dedupe_key: Some(record_dedupe_key(kind, id, modified_at)),
+            created_at: received_at,
+            updated_at: received_at,
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{body_string_contains, header, method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn connector(base: &str) -> SalesforceConnector {
+        SalesforceConnector::new(
+            "client-id".to_string(),
+            "client-secret".to_string(),
+            base.to_string(),
+        )
+    }
+
+    fn connection(instance_url: &str) -> Connection {
+        let now = DateTime::from(Utc::now());
+        Connection {
+            id: Uuid::new_v4(),
+            tenant_id: Uuid::new_v4(),
+            provider_slug: SALESFORCE_PROVIDER_SLUG.to_string(),
+            external_id: "00D000000000001".to_string(),
+            status: "active".to_string(),
+            display_name: Some("acme.my.salesforce.com".to_string()),
+            access_token_ciphertext: None,
+            refresh_token_ciphertext: None,
+            access_token: Some("sf-access".to_string()),
+            refresh_token: Some("sf-refresh".to_string()),
+            expires_at: None,
+            scopes: None,
+            metadata: Some(serde_json::json!({"instance_url": instance_url})),
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    fn opportunity(id: &str, fields: serde_json::Value) -> serde_json::Value {
+        let mut record = serde_json::json!({
+            "attributes": {"type": "Opportunity"},
+            "Id": id,
+            "Name": format!("Deal {}", id),
+            "StageName": "Prospecting",
+            "IsClosed": false,
+            "IsWon": false,
+            "IsDeleted": false,
+            "CreatedDate": "2024-04-01T09:00:00.000+0000",
+            "LastModifiedDate": "2024-05-02T11:00:00.000+0000",
+            "SystemModstamp": "2024-05-02T11:00:00.000+0000"
+        });
+        for (key, value) in fields.as_object().unwrap() {
+            record[key] = value.clone();
+        }
+        record
+    }
+
+    #[tokio::test]
+    async fn test_salesforce_exchange_token_reads_instance_and_org() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/services/oauth2/token"))
+            .and(body_string_contains("grant_type=authorization_code"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "access_token": "sf-access",
+                "refresh_token": "sf-refresh",
+                "instance_url": "https://acme.my.salesforce.com",
+                "id": "https://login.salesforce.com/id/00D000000000001/005000000000001",
+                "scope": "api refresh_token",
+                "token_type": "Bearer"
+            })))
+            .mount(&server)
+            .await;
+
+        let connector = connector(&server.uri());
+        let url = connector
+            .authorize(AuthorizeParams {
+                tenant_id: Uuid::new_v4(),
+                redirect_uri: None,
+                state: Some("state-1".to_string()),
+                code_challenge: None,
+            })
+            .await
+            .unwrap();
+        assert_eq!(url.path(), "/services/oauth2/authorize");
+        let query: std::collections::HashMap<_, _> = url.query_pairs().collect();
+        assert_eq!(query["scope"], "api refresh_token");
+        assert_eq!(query["response_type"], "code");
+
+        let connection = connector
+            .exchange_token(ExchangeTokenParams {
+                code: "code".to_string(),
+                redirect_uri: None,
+                tenant_id: Uuid::new_v4(),
+                code_verifier: None,
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(connection.external_id, "00D000000000001");
+        assert_eq!(
+            connection.display_name.as_deref(),
+            Some("acme.my.salesforce.com")
+        );
+        let metadata = connection.metadata.unwrap();
+        assert_eq!(metadata["instance_url"], "https://acme.my.salesforce.com");
+        assert_eq!(metadata["user_id"], "005000000000001");
+    }
+
+    #[tokio::test]
+    async fn test_salesforce_sync_resumes_from_object_positions() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/services/data/v60.0/queryAll"))
+            .and(header("Authorization", "Bearer sf-access"))
+            .and(query_param(
+                "q",
+                format!(
+                    "SELECT {} FROM Opportunity WHERE SystemModstamp > 2024-05-02T10:00:00Z \
+                     OR (SystemModstamp = 2024-05-02T10:00:00Z AND Id > '006A') \
+                     ORDER BY SystemModstamp, Id LIMIT 200",
+                    SYNCED_OBJECTS[0].1
+                )
+                .as_str(),
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "totalSize": 4,
+                "done": true,
+                "records": [
+                    opportunity("006B", serde_json::json!({
+                        "CreatedDate": "2024-05-02T10:30:00.000+0000",
+                        "LastModifiedDate": "2024-05-02T10:45:00.000+0000",
+                        "SystemModstamp": "2024-05-02T10:45:00.000+0000"
+                    })),
+                    opportunity("006C", serde_json::json!({})),
+                    opportunity("006D", serde_json::json!({
+                        "StageName": "Closed Won",
+                        "IsClosed": true,
+                        "IsWon": true,
+                        "LastStageChangeDate": "2024-05-02T11:30:00.000+0000",
+                        "LastModifiedDate": "2024-05-02T11:30:00.000+0000",
+                        "SystemModstamp": "2024-05-02T11:30:00.000+0000"
+                    })),
+                    opportunity("006E", serde_json::json!({
+                        "IsDeleted": true,
+                        "SystemModstamp": "2024-05-02T12:00:00.000+0000"
+                    }))
+                ]
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/services/data/v60.0/queryAll"))
+            .and(query_param(
+                "q",
+                format!(
+                    "SELECT {} FROM Case WHERE SystemModstamp > 2024-05-01T00:00:00Z \
+                     OR (SystemModstamp = 2024-05-01T00:00:00Z AND Id > '500A') \
+                     ORDER BY SystemModstamp, Id LIMIT 200",
+                    SYNCED_OBJECTS[1].1
+                )
+                .as_str(),
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "totalSize": 1,
+                "done": true,
+                "records": [{
+                    "attributes": {"type": "Case"},
+                    "Id": "500B",
+                    "Subject": "Login fails",
+                    "Status": "Closed",
+                    "IsClosed": true,
+                    "IsDeleted": false,
+                    "ClosedDate": "2024-05-02T09:00:00.000+0000",
+                    "CreatedDate": "2024-04-30T09:00:00.000+0000",
+                    "LastModifiedDate": "2024-05-02T09:00:00.000+0000",
+                    "SystemModstamp": "2024-05-02T09:00:00.000+0000"
+                }]
+            })))
+            .mount(&server)
+            .await;
+
+        let result = connector("https://login.salesforce.com")
+            .sync(SyncParams {
+                connection: connection(&server.uri()),
+                cursor: Some(Cursor::from_json(serde_json::json!({
+                    "Opportunity": {"since": "2024-05-02T10:00:00Z", "last_id": "006A"},
+                    "Case": {"since": "2024-05-01T00:00:00Z", "last_id": "500A"}
+                }))),
+                cancellation: Default::default(),
+            })
+            .await
+            .unwrap();
+
+        let kinds: Vec<_> = result.signals.iter().map(|s| s.kind.as_str()).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                "opportunity_created",
+                "opportunity_updated",
+                "opportunity_won",
+                "opportunity_deleted",
+                "case_closed"
+            ]
+        );
+        assert_eq!(
+            result.signals[2].payload["url"],
+            format!("{}/006D", server.uri())
+        );
+        assert_eq!(
+            result.signals[3].dedupe_key.as_deref(),
+            Some("salesforce:opportunity_deleted:006E")
+        );
+        assert!(!result.has_more);
+        assert_eq!(
+            result.next_cursor.unwrap().as_json(),
+            &serde_json::json!({
+                "Case": {"since": "2024-05-02T09:00:00Z", "last_id": "500B"},
+                "Opportunity": {"since": "2024-05-02T12:00:00Z", "last_id": "006E"}
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_salesforce_sync_requires_instance_url() {
+        let err = connector("https://login.salesforce.com")
+            .sync(SyncParams {
+                connection: Connection {
+                    metadata: None,
+                    ..connection("https://acme.my.salesforce.com")
+                },
+                cursor: None,
+                cancellation: Default::default(),
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<SyncError>().unwrap().kind,
+            crate::connectors::SyncErrorKind::Permanent
+        );
+    }
+
+    #[tokio::test]
+    async fn test_salesforce_change_event_mapping() {
+        let connector = connector("https://login.salesforce.com");
+        let handle = |payload: serde_json::Value| {
+            connector.handle_webhook(WebhookParams {
+                payload,
+                tenant_id: Uuid::new_v4(),
+                db: None,
+                auth_header: None,
+            })
+        };
+
+        let won = handle(serde_json::json!({
+            "data": {
+                "schema": "schema-1",
+                "payload": {
+                    "ChangeEventHeader": {
+                        "entityName": "Opportunity",
+                        "recordIds": ["006D"],
+                        "changeType": "UPDATE",
+                        "changedFields": ["StageName", "IsClosed", "IsWon", "LastModifiedDate"],
+                        "commitTimestamp": 1714649400000i64,
+                        "commitUser": "005000000000001"
+                    },
+                    "StageName": "Closed Won",
+                    "IsClosed": true,
+                    "IsWon": true,
+                    "LastModifiedDate": "2024-05-02T11:30:00.000Z"
+                },
+                "event": {"replayId": 42}
+            },
+            "channel": "/data/OpportunityChangeEvent"
+        }))
+        .await
+        .unwrap();
+        assert_eq!(won[0].kind, "opportunity_won");
+        assert_eq!(won[0].payload["stage"], "Closed Won");
+        // Matches the key produced by sync so the two paths deduplicate
+        let synced = record_dedupe_key(
+            SignalKind::OpportunityWon,
+            "006D",
+            parse_salesforce_datetime("2024-05-02T11:30:00.000+0000"),
+        );
+        assert_eq!(won[0].dedupe_key.as_deref(), Some(synced.as_str()));
+
+        let deleted = handle(serde_json::json!({
+            "ChangeEventHeader": {
+                "entityName": "Case",
+                "recordIds": ["500B", "500C"],
+                "changeType": "DELETE",
+                "commitTimestamp": 1714649400000i64
+            }
+        }))
+        .await
+        .unwrap();
+        let keys: Vec<_> = deleted
+            .iter()
+            .map(|s| s.dedupe_key.as_deref().unwrap())
+            .collect();
+        assert_eq!(
+            keys,
+            vec![
+                "salesforce:case_deleted:500B",
+                "salesforce:case_deleted:500C"
+            ]
+        );
+
+        let ignored = handle(serde_json::json!({
+            "ChangeEventHeader": {
+                "entityName": "Account",
+                "recordIds": ["001A"],
+                "changeType": "UPDATE"
+            }
+        }))
+        .await
+        .unwrap();
+        assert!(ignored.is_empty());
+    }
+}
@@ -0,0 +1,985 @@
+//! Sentry connector implementation
+//!
+//! Sentry through the OAuth authorization code flow. A connection covers the
+//! first organization the authorizing user belongs to. Each sync lists the
+//! organization's issues with events in a time window, paging through the
+//! window across runs, and integration webhooks for issues and alerts are
+//! verified in [`crate::webhook_verification`] before reaching this connector.
+
+use anyhow::anyhow;
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, SecondsFormat, Utc};
+use reqwest::{Client, StatusCode};
+use serde::Deserialize;
+use std::sync::Arc;
+use tracing::{debug, info};
+use url::Url;
+use uuid::Uuid;
+
+use crate::connectors::{
+    AuthType, Connector, Cursor, ProviderCategory, ProviderMetadata, Registry,
+    trait_::{
+        AuthorizeParams, ExchangeTokenParams, SyncError, SyncParams, SyncResult, WebhookParams,
+    },
+};
+use crate::models::{connection::Model as Connection, signal::Model as Signal};
+use crate::normalization::{
+    SignalKind, normalize_sentry_issue_kind, normalize_sentry_webhook_kind,
+};
+
+/// Provider slug
+pub const SENTRY_PROVIDER_SLUG: &str = "sentry";
+
+/// Scopes requested at authorization time
+pub const SENTRY_SCOPES: &[&str] = &["org:read", "project:read", "event:read"];
+
+/// Issues requested per page
+const PAGE_SIZE: u32 = 100;
+
+/// Issue pages read in one sync; the rest of the window continues on the next run
+const MAX_PAGES: u32 = 5;
+
+/// How far back the first sync reads
+const INITIAL_LOOKBACK_DAYS: i64 = 30;
+
+/// Sentry connector
+pub struct SentryConnector {
+    client_id: String,
+    client_secret: String,
+    base_url: String,
+    http_client: Client,
+}
+
+#[derive(Debug, Deserialize)]
+struct SentryTokenResponse {
+    access_token: String,
+    #[serde(default)]
+    refresh_token: Option<String>,
+    #[serde(default)]
+    expires_in: Option<i64>,
+    #[serde(default)]
+    scope: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SentryOrganization {
+    id: String,
+    slug: String,
+    #[serde(default)]
+    name: Option<String>,
+}
+
+/// Position of a sync: the window being read and, mid-window, the next page
+#[derive(Debug, Default, PartialEq)]
+struct IssueWindow {
+    since: Option<DateTime<Utc>>,
+    until: Option<DateTime<Utc>>,
+    page: Option<String>,
+}
+
+impl IssueWindow {
+    fn from_cursor(cursor: Option<&Cursor>) -> Self {
+        let Some(json) = cursor.map(Cursor::as_json) else {
+            return Self::default();
+        };
+        let time = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.as_str())
+                .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+                .map(|dt| dt.with_timezone(&Utc))
+        };
+        Self {
+            since: time("since"),
+            until: time("until"),
+            page: json
+                .get("page")
+                .and_then(|v| v.as_str())
+                .map(str::to_string),
+        }
+    }
+
+    fn to_cursor(&self) -> Cursor {
+        let format =
+            |at: &Option<DateTime<Utc>>| at.map(|at| at.to_rfc3339_opts(SecondsFormat::Secs, true));
+        Cursor::from_json(serde_json::json!({
+            "since": format(&self.since),
+            "until": format(&self.until),
+            "page": self.page,
+        }))
+    }
+}
+
+impl SentryConnector {
+    /// Create a new Sentry connector for the Sentry host at `base_url`
+    pub fn new(client_id: String, client_secret: String, base_url: String) -> Self {
+        Self {
+            client_id,
+            client_secret,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            http_client: crate::egress::client(),
+        }
+    }
+
+    fn default_redirect_uri() -> String {
+        match std::env::var("POBLYSH_PROFILE").as_deref() {
+            Ok("local") | Ok("test") | Err(_) => "http://localhost:3000/callback".to_string(),
+            Ok(_) => "https://app.poblysh.com/callback".to_string(),
+        }
+    }
+
+    fn access_token(connection: &Connection) -> Result<String, SyncError> {
+        connection
+            .access_token
+            .clone()
+            .filter(|token| !token.is_empty())
+            .ok_or_else(|| SyncError::unauthorized("Missing Sentry access token"))
+    }
+
+    fn organization(connection: &Connection) -> Result<String, SyncError> {
+        connection
+            .metadata
+            .as_ref()
+            .and_then(|m| m.get("organization"))
+            .and_then(|v| v.as_str())
+            .filter(|slug| !slug.is_empty())
+            .map(str::to_string)
+            .ok_or_else(|| SyncError::permanent("Sentry connection has no organization"))
+    }
+
+    /// Call `/oauth/token/` with the given grant
+    async fn oauth_token(
+        &self,
+        grant: &[(&str, &str)],
+    ) -> Result<SentryTokenResponse, anyhow::Error> {
+        let mut form = vec![
+            ("client_id", self.client_id.as_str()),
+            ("client_secret", self.client_secret.as_str()),
+        ];
+        form.extend_from_slice(grant);
+
+        let response = self
+            .http_client
+            .post(format!("{}/oauth/token/", self.base_url))
+            .form(&form)
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let body: serde_json::Value = response.json().await.unwrap_or_default();
+            return Err(anyhow!(
+                "Sentry token endpoint returned {}: {}",
+                status,
+                body.get("error")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("unknown_error")
+            ));
+        }
+        Ok(response.json().await?)
+    }
+
+    /// Organizations the token's user belongs to
+    async fn organizations(
+        &self,
+        access_token: &str,
+    ) -> Result<Vec<SentryOrganization>, anyhow::Error> {
+        let response = self
+            .http_client
+            .get(format!("{}/api/0/organizations/", self.base_url))
+            .bearer_auth(access_token)
+            .query(&[("member", "1")])
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            return Err(anyhow!(
+                "Sentry organization lookup failed: {}",
+                response.status()
+            ));
+        }
+        Ok(response.json().await?)
+    }
+
+    /// Read one page of issues with events between `since` and `until`
+    async fn issues_page(
+        &self,
+        access_token: &str,
+        organization: &str,
+        since: DateTime<Utc>,
+        until: DateTime<Utc>,
+        page: Option<&str>,
+    ) -> Result<(Vec<serde_json::Value>, Option<String>), SyncError> {
+        let format = |at: DateTime<Utc>| at.to_rfc3339_opts(SecondsFormat::Secs, true);
+        let mut query = vec![
+            // An empty query includes resolved and ignored issues, unlike the default
+            ("query", String::new()),
+            ("sort", "date".to_string()),
+            ("limit", PAGE_SIZE.to_string()),
+            ("start", format(since)),
+            ("end", format(until)),
+        ];
+        if let Some(page) = page {
+            query.push(("cursor", page.to_string()));
+        }
+
+        let response = self
+            .http_client
+            .get(format!(
+                "{}/api/0/organizations/{}/issues/",
+                self.base_url, organization
+            ))
+            .bearer_auth(access_token)
+            .header("Accept", "application/json")
+            .query(&query)
+            .send()
+            .await
+            .map_err(|e| SyncError::transient(format!("Sentry request failed: {}", e)))?;
+
+        let status = response.status();
+        if status == StatusCode::TOO_MANY_REQUESTS {
+            let retry_after = response
+                .headers()
+                .get("Retry-After")
+                .and_then(|h| h.to_str().ok())
+                .and_then(|s| s.parse::<u64>().ok());
+            return Err(SyncError::rate_limited(retry_after));
+        }
+        if status == StatusCode::UNAUTHORIZED {
+            return Err(SyncError::unauthorized(format!(
+                "Sentry issue listing failed: {}",
+                status
+            )));
+        }
+        if status.is_server_error() {
+            return Err(SyncError::transient(format!(
+                "Sentry issue listing failed: {}",
+                status
+            )));
+        }
+        if !status.is_success() {
+            let body: serde_json::Value = response.json().await.unwrap_or_default();
+            return Err(
+                SyncError::permanent(format!("Sentry issue listing failed: {}", status))
+                    .with_details(body),
+            );
+        }
+
+        let next = response
+            .headers()
+            .get("Link")
+            .and_then(|h| h.to_str().ok())
+            .and_then(next_page_cursor);
+        let issues = response
+            .json()
+            .await
+            .map_err(|e| SyncError::transient(format!("Sentry returned bad JSON: {}", e)))?;
+        Ok((issues, next))
+    }
+}
+
+#[async_trait]
+impl Connector for SentryConnector {
+    async fn authorize(
+        &self,
+        params: AuthorizeParams,
+    ) -> Result<Url, Box<dyn std::error::Error + Send + Sync>> {
+        info!(
+            tenant_id = %params.tenant_id,
+            "Generating Sentry OAuth authorization URL"
+        );
+
+        let mut url = Url::parse(&format!("{}/oauth/authorize/", self.base_url))?;
+        let redirect_uri = params
+            .redirect_uri
+            .unwrap_or_else(Self::default_redirect_uri);
+        let state = params
+            .state
+            .filter(|s| !s.is_empty())
+            .unwrap_or_else(|| Uuid::new_v4().to_string());
+        url.query_pairs_mut()
+            .append_pair("response_type", "code")
+            .append_pair("client_id", &self.client_id)
+            .append_pair("redirect_uri", &redirect_uri)
+            .append_pair("scope", &SENTRY_SCOPES.join(" "))
+            .append_pair("state", &state);
+
+        debug!(
+            tenant_id = %params.tenant_id,
+            authorize_url = %url,
+            "Generated Sentry OAuth authorization URL"
+        );
+        Ok(url)
+    }
+
+    async fn exchange_token(
+        &self,
+        params: ExchangeTokenParams,
+    ) -> Result<Connection, Box<dyn std::error::Error + Send + Sync>> {
+        info!(
+            tenant_id = %params.tenant_id,
+            "Exchanging Sentry authorization code for tokens"
+        );
+
+        let redirect_uri = params
+            .redirect_uri
+            .unwrap_or_else(Self::default_redirect_uri);
+        let token = self
+            .oauth_token(&[
+                ("grant_type", "authorization_code"),
+                ("code", params.code.as_str()),
+                ("redirect_uri", redirect_uri.as_str()),
+            ])
+            .await?;
+
+        let organization = self
+            .organizations(&token.access_token)
+            .await?
+            .into_iter()
+            .next()
+            .ok_or_else(|| anyhow!("Sentry user does not belong to any organization"))?;
+        let now = DateTime::from(Utc::now());
+
+        Ok(Connection {
+            id: Uuid::new_v4(),
+            tenant_id: params.tenant_id,
+            provider_slug: SENTRY_PROVIDER_SLUG.to_string(),
+            external_id: organization.id,
+            status: "active".to_string(),
+            display_name: Some(organization.name.unwrap_or(organization.slug.clone())),
+            access_token_ciphertext: None,
+            refresh_token_ciphertext: None,
+            access_token: Some(token.access_token),
+            refresh_token: token.refresh_token,
+            expires_at: token
+                .expires_in
+                .map(|secs| (Utc::now() + Duration::seconds(secs)).into()),
+            scopes: token.scope.map(|scope| {
+                serde_json::Value::Array(
+                    scope
+                        .split_whitespace()
+                        .map(|s| serde_json::Value::String(s.to_string()))
+                        .collect(),
+                )
+            }),
+            metadata: Some(serde_json::json!({
+                "provider": SENTRY_PROVIDER_SLUG,
+                "organization": organization.slug,
+                "base_url": self.base_url,
+            })),
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    async fn refresh_token(
+        &self,
+        connection: Connection,
+    ) -> Result<Connection, Box<dyn std::error::Error + Send + Sync>> {
+        let Some(refresh_token) = connection
+            .refresh_token
+            .clone()
+            .filter(|token| !token.is_empty())
+        else {
+            return Err(SyncError::unauthorized("Sentry connection has no refresh token").into());
+        };
+
+        info!(connection_id = %connection.id, "Refreshing Sentry token");
+        let token = self
+            .oauth_token(&[
+                ("grant_type", "refresh_token"),
+                ("refresh_token", refresh_token.as_str()),
+            ])
+            .await?;
+
+        Ok(Connection {
+            access_token: Some(token.access_token),
+            refresh_token: token.refresh_token.or(connection.refresh_token.clone()),
+            expires_at: token
+                .expires_in
+                .map(|secs| (Utc::now() + Duration::seconds(secs)).into()),
+            updated_at: DateTime::from(Utc::now()),
+            ..connection
+        })
+    }
+
+    async fn sync(
+        &self,
+        params: SyncParams,
+    ) -> Result<SyncResult, Box<dyn std::error::Error + Send + Sync>> {
+        let connection = &params.connection;
+        info!(
+            tenant_id = %connection.tenant_id,
+            connection_id = %connection.id,
+            has_cursor = %params.cursor.is_some(),
+            "Starting Sentry sync"
+        );
+
+        let access_token = Self::access_token(connection)?;
+        let organization = Self::organization(connection)?;
+        let window = IssueWindow::from_cursor(params.cursor.as_ref());
+        let now = Utc::now();
+        let since = window
+            .since
+            .unwrap_or_else(|| now - Duration::days(INITIAL_LOOKBACK_DAYS));
+        // A window is fixed once paging starts so later pages line up
+        let until = match (&window.page, window.until) {
+            (Some(_), Some(until)) => until,
+            _ => now,
+        };
+
+        let mut signals = Vec::new();
+        let mut page = window.page;
+        for _ in 0..MAX_PAGES {
+            let (issues, next) = self
+                .issues_page(&access_token, &organization, since, until, page.as_deref())
+                .await?;
+            for issue in &issues {
+                let Some(id) = issue.get("id").and_then(|v| v.as_str()) else {
+                    continue;
+                };
+                if let Some(kind) = normalize_sentry_issue_kind(issue, since) {
+                    signals.push(build_issue_signal(
+                        connection.tenant_id,
+                        connection.id,
+                        kind,
+                        id,
+                        issue,
+                        None,
+                    ));
+                }
+            }
+            page = next;
+            if page.is_none() {
+                break;
+            }
+        }
+
+        let has_more = page.is_some();
+        let next_window = if has_more {
+            IssueWindow {
+                since: Some(since),
+                until: Some(until),
+                page,
+            }
+        } else {
+            IssueWindow {
+                since: Some(until),
+                ..Default::default()
+            }
+        };
+
+        debug!(
+            connection_id = %connection.id,
+            signals = signals.len(),
+            has_more,
+            "Sentry sync completed"
+        );
+
+        Ok(SyncResult {
+            signals,
+            next_cursor: Some(next_window.to_cursor()),
+            has_more,
+        })
+    }
+
+    async fn handle_webhook(
+        &self,
+        params: WebhookParams,
+    ) -> Result<Vec<Signal>, Box<dyn std::error::Error + Send + Sync>> {
+        let payload = &params.payload;
+        let Some(kind) = normalize_sentry_webhook_kind(payload) else {
+            debug!(
+                tenant_id = %params.tenant_id,
+                action = ?payload.get("action"),
+                "Sentry webhook ignored"
+            );
+            return Ok(vec![]);
+        };
+
+        let connection_id = Uuid::nil();
+        let data = &payload["data"];
+        let signal = if let Some(issue) = data.get("issue") {
+            let Some(id) = issue.get("id").and_then(|v| v.as_str()) else {
+                return Err(anyhow!("Invalid Sentry webhook: missing data.issue.id").into());
+            };
+            build_issue_signal(
+                params.tenant_id,
+                connection_id,
+                kind,
+                id,
+                issue,
+                payload.get("action").and_then(|v| v.as_str()),
+            )
+        } else {
+            build_alert_signal(params.tenant_id, connection_id, payload)
+        };
+
+        info!(
+            tenant_id = %params.tenant_id,
+            signal_kind = %kind,
+            "Sentry webhook mapped to signal"
+        );
+        Ok(vec![signal])
+    }
+}
+
+/// Register the Sentry connector in the registry
+pub fn register_sentry_connector(registry: &mut Registry, connector: Arc<SentryConnector>) {
+    let metadata = ProviderMetadata::new(
+        SENTRY_PROVIDER_SLUG.to_string(),
+        AuthType::OAuth2,
+        SENTRY_SCOPES.iter().map(|s| s.to_string()).collect(),
+        true, // webhooks supported
+    )
+    .with_category(ProviderCategory::Monitoring)
+    .with_description("Track production errors and issue alerts from Sentry")
+    .with_icon_url("https://cdn.simpleicons.org/sentry")
+    .with_docs_url("https://docs.sentry.io/api/auth/")
+    .with_backfill(true)
+    .with_signal_kinds(&[
+        SignalKind::ErrorEventCreated,
+        SignalKind::ErrorEventAlerted,
+        SignalKind::ErrorEventRegressed,
+        SignalKind::ErrorEventResolved,
+        SignalKind::ErrorEventUpdated,
+    ]);
+
+    registry.register(connector, metadata);
+}
+
+/// Cursor of the `rel="next"` link when Sentry reports more results
+fn next_page_cursor(link: &str) -> Option<String> {
+    link.split(',').find_map(|part| {
+        let attributes: Vec<&str> = part.split(';').map(str::trim).collect();
+        let attribute = |name: &str| {
+            attributes.iter().find_map(|a| {
+                a.strip_prefix(name)
+                    .and_then(|v| v.strip_prefix('='))
+                    .map(|v| v.trim_matches('"'))
+            })
+        };
+        (attribute("rel") == Some("next") && attribute("results") == Some("true"))
+            .then(|| attribute("cursor").map(str::to_string))
+            .flatten()
+    })
+}
+
+fn parse_time(value: Option<&serde_json::Value>) -> Option<DateTime<Utc>> {
+    value
+        .and_then(|v| v.as_str())
+        .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+        .map(|dt| dt.with_timezone(&Utc))
+}
+
+/// Dedupe key shared by sync and webhooks for the same issue change
+///
+/// An issue is created once. Regressions and resolutions are keyed by the day
+/// of the issue's last event, since sync sees the issue again while its
+/// events continue; other webhook actions by action and time.
+fn issue_dedupe_key(
+    kind: SignalKind,
+    id: &str,
+    last_seen: Option<DateTime<Utc>>,
+    action: Option<&str>,
+) -> String {
+    match kind {
+        SignalKind::ErrorEventCreated => format!("sentry:{}:{}", kind, id),
+        SignalKind::ErrorEventRegressed | SignalKind::ErrorEventResolved => format!(
+            "sentry:{}:{}:{}",
+            kind,
+            id,
+            last_seen
+                .map(|at| at.format("%Y-%m-%d").to_string())
+                .unwrap_or_default()
+        ),
+        _ => format!(
+            "sentry:{}:{}:{}:{}",
+            kind,
+            id,
+            action.unwrap_or_default(),
+            Utc::now().timestamp_millis()
+        ),
+    }
+}
+
+/// Build a signal from an issue in the issues API or an issue webhook
+fn build_issue_signal(
+    tenant_id: Uuid,
+    connection_id: Uuid,
+    kind: SignalKind,
+    id: &str,
+    issue: &serde_json::Value,
+    action: Option<&str>,
+) -> Signal {
+    let first_seen = parse_time(issue.get("firstSeen"));
+    let last_seen = parse_time(issue.get("lastSeen"));
+    let occurred_at = match kind {
+        SignalKind::ErrorEventCreated => first_seen,
+        SignalKind::ErrorEventRegressed => last_seen,
+        _ => None,
+    }
+    .unwrap_or_else(Utc::now);
+    let received_at = DateTime::from(Utc::now());
+
+    Signal {
+        id: Uuid::new_v4(),
+        tenant_id,
+        provider_slug: SENTRY_PROVIDER_SLUG.to_string(),
+        connection_id,
+        kind: kind.as_str().to_string(),
+        occurred_at: occurred_at.into(),
+        received_at,
+        payload: serde_json::json!({
+            "issue_id": id,
+            "short_id": issue.get("shortId"),
+            "title": issue.get("title"),
+            "culprit": issue.get("culprit"),
+            "level": issue.get("level"),
+            "status": issue.get("status"),
+            "substatus": issue.get("substatus"),
+            "project": issue.pointer("/project/slug"),
+            "count": issue.get("count"),
+            "user_count": issue.get("userCount"),
+            "is_unhandled": issue.get("isUnhandled"),
+            "first_seen": issue.get("firstSeen"),
+            "last_seen": issue.get("lastSeen"),
+            "action": action,
+            "url": issue.get("permalink").or_else(|| issue.get("web_url")),
+            "occurred_at": occurred_at.to_rfc3339(),
+        }),
+        dedupe_key: Some(issue_dedupe_key(kind, id, last_seen, action)),
+        created_at: received_at,
+        updated_at: received_at,
+    }
+}
+
+/// Build an `error_event_alerted` signal from an issue or metric alert webhook
+fn build_alert_signal(tenant_id: Uuid, connection_id: Uuid, payload: &serde_json::Value) -> Signal {
+    let data = &payload["data"];
+    let kind = SignalKind::ErrorEventAlerted;
+    let received_at = DateTime::from(Utc::now());
+
+    let (occurred_at, dedupe_key, details) = match data.get("event") {
+        Some(event) => {
+            let rule = data
+                .get("triggered_rule")
+                .and_then(|v| v.as_str())
+                .unwrap_or_default();
+            let event_id = event
+                .get("event_id")
+                .and_then(|v| v.as_str())
+                .unwrap_or_default();
+            (
+                parse_time(event.get("datetime")),
+                format!("sentry:{}:{}:{}", kind, event_id, rule),
+                serde_json::json!({
+                    "alert_type": "issue",
+                    "rule": rule,
+                    "event_id": event_id,
+                    "issue_id": event.get("issue_id"),
+                    "title": event.get("title"),
+                    "culprit": event.get("culprit"),
+                    "level": event.get("level"),
+                    "url": event.get("web_url").or_else(|| event.get("issue_url")),
+                }),
+            )
+        }
+        None => {
+            let alert = &data["metric_alert"];
+            let started = alert
+                .get("date_started")
+                .or_else(|| alert.get("date_detected"));
+            let action = payload.get("action").and_then(|v| v.as_str());
+            (
+                parse_time(started),
+                format!(
+                    "sentry:{}:metric:{}:{}:{}",
+                    kind,
+                    alert.get("id").and_then(|v| v.as_str()).unwrap_or_default(),
+                    action.unwrap_or_default(),
+                    started.and_then(|v| v.as_str()).unwrap_or_default()
+                ),
+                serde_json::json!({
+                    "alert_type": "metric",
+                    "rule": alert.pointer("/alert_rule/name"),
+                    "severity": action,
+                    "title": data.get("description_title"),
+                    "description": data.get("description_text"),
+                    "url": data.get("web_url"),
+                }),
+            )
+        }
+    };
+    let occurred_at = occurred_at.unwrap_or_else(Utc::now);
+
+    let mut signal_payload = details;
+    signal_payload["occurred_at"] = serde_json::json!(occurred_at.to_rfc3339());
+    Signal {
+        id: Uuid::new_v4(),
+        tenant_id,
+        provider_slug: SENTRY_PROVIDER_SLUG.to_string(),
+        connection_id,
+        kind: kind.as_str().to_string(),
+        occurred_at: occurred_at.into(),
+        received_at,
+        payload: signal_payload,
+        dedupe_key: Some(dedupe_key),
+        created_at: received_at,
+        updated_at: received_at,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use wiremock::matchers::{body_string_contains, header, method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn connector(base: &str) -> SentryConnector {
+        SentryConnector::new(
+            "client-id".to_string(),
+            "client-secret".to_string(),
+            base.to_string(),
+        )
+    }
+
+    fn connection() -> Connection {
+        let now = DateTime::from(Utc::now());
+        Connection {
+            id: Uuid::new_v4(),
+            tenant_id: Uuid::new_v4(),
+            provider_slug: SENTRY_PROVIDER_SLUG.to_string(),
+            external_id: "1".to_string(),
+            status: "active".to_string(),
+            display_name: Some("Acme".to_string()),
+            access_token_ciphertext: None,
+            refresh_token_ciphertext: None,
+            access_token: Some("sentry-access".to_string()),
+            refresh_token: None,
+            expires_at: None,
+            scopes: None,
+            metadata: Some(serde_json::json!({"organization": "acme"})),
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    fn issue(id: &str, status: &str, substatus: &str, first_seen: &str) -> serde_json::Value {
+        serde_json::json!({
+            "id": id,
+            "shortId": format!("API-{}", id),
+            "title": "TypeError: undefined is not a function",
+            "culprit": "app/checkout.js",
+            "level": "error",
+            "status": status,
+            "substatus": substatus,
+            "firstSeen": first_seen,
+            "lastSeen": "2024-05-02T12:00:00Z",
+            "count": "12",
+            "userCount": 3,
+            "project": {"id": "2", "slug": "web"},
+            "permalink": format!("https://acme.sentry.io/issues/{}/", id)
+        })
+    }
+
+    fn webhook(payload: serde_json::Value) -> WebhookParams {
+        WebhookParams {
+            payload,
+            tenant_id: Uuid::new_v4(),
+            db: None,
+            auth_header: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sentry_exchange_token_uses_first_organization() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/oauth/token/"))
+            .and(body_string_contains("grant_type=authorization_code"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "access_token": "sentry-access",
+                "refresh_token": "sentry-refresh",
+                "expires_in": 2591999,
+                "token_type": "bearer",
+                "scope": "org:read project:read event:read"
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/0/organizations/"))
+            .and(header("Authorization", "Bearer sentry-access"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                {"id": "1", "slug": "acme", "name": "Acme"},
+                {"id": "2", "slug": "side-project", "name": "Side Project"}
+            ])))
+            .mount(&server)
+            .await;
+
+        let connector = connector(&server.uri());
+        let url = connector
+            .authorize(AuthorizeParams {
+                tenant_id: Uuid::new_v4(),
+                redirect_uri: None,
+                state: Some("state-1".to_string()),
+                code_challenge: None,
+            })
+            .await
+            .unwrap();
+        assert_eq!(url.path(), "/oauth/authorize/");
+        let query: HashMap<_, _> = url.query_pairs().collect();
+        assert_eq!(query["scope"], "org:read project:read event:read");
+
+        let connection = connector
+            .exchange_token(ExchangeTokenParams {
+                code: "code".to_string(),
+                redirect_uri: None,
+                tenant_id: Uuid::new_v4(),
+                code_verifier: None,
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(connection.external_id, "1");
+        assert_eq!(connection.display_name.as_deref(), Some("Acme"));
+        assert!(connection.expires_at.is_some());
+        assert_eq!(connection.metadata.unwrap()["organization"], "acme");
+    }
+
+    #[tokio::test]
+    async fn test_sentry_sync_pages_through_window() {
+        let server = MockServer::start().await;
+        let since = "2024-05-01T00:00:00Z";
+        let until = "2024-05-03T00:00:00Z";
+        Mock::given(method("GET"))
+            .and(path("/api/0/organizations/acme/issues/"))
+            .and(header("Authorization", "Bearer sentry-access"))
+            .and(query_param("start", since))
+            .and(query_param("end", until))
+            .and(query_param("cursor", "0:100:0"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header(
+                        "Link",
+                        format!(
+                            "<{0}/api/0/organizations/acme/issues/?cursor=0:0:1>; rel=\"previous\"; results=\"false\"; cursor=\"0:0:1\", \
+                             <{0}/api/0/organizations/acme/issues/?cursor=0:200:0>; rel=\"next\"; results=\"false\"; cursor=\"0:200:0\"",
+                            server.uri()
+                        )
+                        .as_str(),
+                    )
+                    .set_body_json(serde_json::json!([
+                        issue("10", "unresolved", "new", "2024-05-02T10:00:00Z"),
+                        issue("11", "unresolved", "regressed", "2024-03-01T10:00:00Z"),
+                        issue("12", "resolved", "", "2024-03-01T10:00:00Z"),
+                        issue("13", "unresolved", "ongoing", "2024-03-01T10:00:00Z")
+                    ])),
+            )
+            .mount(&server)
+            .await;
+
+        let result = connector(&server.uri())
+            .sync(SyncParams {
+                connection: connection(),
+                cursor: Some(Cursor::from_json(serde_json::json!({
+                    "since": since,
+                    "until": until,
+                    "page": "0:100:0"
+                }))),
+                cancellation: Default::default(),
+            })
+            .await
+            .unwrap();
+
+        let kinds: Vec<_> = result.signals.iter().map(|s| s.kind.as_str()).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                "error_event_created",
+                "error_event_regressed",
+                "error_event_resolved"
+            ]
+        );
+        assert_eq!(
+            result.signals[0].dedupe_key.as_deref(),
+            Some("sentry:error_event_created:10")
+        );
+        assert_eq!(
+            result.signals[1].dedupe_key.as_deref(),
+            Some("sentry:error_event_regressed:11:2024-05-02")
+        );
+        assert_eq!(result.signals[0].payload["project"], "web");
+        assert!(!result.has_more);
+        assert_eq!(
+            IssueWindow::from_cursor(result.next_cursor.as_ref()),
+            IssueWindow {
+                since: parse_time(Some(&serde_json::json!(until))),
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn test_next_page_cursor_requires_results() {
+        let link = "<https://sentry.io/x/?cursor=0:0:1>; rel=\"previous\"; results=\"false\"; cursor=\"0:0:1\", \
+                    <https://sentry.io/x/?cursor=0:100:0>; rel=\"next\"; results=\"true\"; cursor=\"0:100:0\"";
+        assert_eq!(next_page_cursor(link).as_deref(), Some("0:100:0"));
+        assert_eq!(
+            next_page_cursor(&link.replace("results=\"true\"", "results=\"false\"")),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn test_sentry_webhook_mapping() {
+        let connector = connector("https://sentry.io");
+
+        let resolved = connector
+            .handle_webhook(webhook(serde_json::json!({
+                "action": "resolved",
+                "installation": {"uuid": "install-1"},
+                "data": {"issue": issue("11", "resolved", "", "2024-03-01T10:00:00Z")}
+            })))
+            .await
+            .unwrap();
+        assert_eq!(resolved[0].kind, "error_event_resolved");
+        // Matches the key produced by sync for the same resolution
+        assert_eq!(
+            resolved[0].dedupe_key.as_deref(),
+            Some("sentry:error_event_resolved:11:2024-05-02")
+        );
+        assert_eq!(resolved[0].payload["action"], "resolved");
+
+        let alert = connector
+            .handle_webhook(webhook(serde_json::json!({
+                "action": "triggered",
+                "data": {
+                    "event": {
+                        "event_id": "abc123",
+                        "issue_id": "11",
+                        "title": "TypeError: undefined is not a function",
+                        "level": "error",
+                        "datetime": "2024-05-02T12:00:00Z",
+                        "web_url": "https://acme.sentry.io/issues/11/events/abc123/"
+                    },
+                    "triggered_rule": "Checkout errors"
+                }
+            })))
+            .await
+            .unwrap();
+        assert_eq!(alert[0].kind, "error_event_alerted");
+        assert_eq!(
+            alert[0].dedupe_key.as_deref(),
+            Some("sentry:error_event_alerted:abc123:Checkout errors")
+        );
+        assert_eq!(alert[0].payload["rule"], "Checkout errors");
+
+        let ignored = connector
+            .handle_webhook(webhook(serde_json::json!({
+                "action": "created",
+                "data": {"error": {"event_id": "abc123"}}
+            })))
+            .await
+            .unwrap();
+        assert!(ignored.is_empty());
+    }
+}
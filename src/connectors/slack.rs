@@ -0,0 +1,1100 @@
+//! Slack connector implementation
+//!
+//! Slack connector supporting the OAuth v2 bot install flow, Events API
+//! webhook ingestion, and `conversations.history` backfill for every channel
+//! the bot is a member of.
+
+use anyhow::anyhow;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use reqwest::{Client, StatusCode};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tracing::{debug, info, warn};
+use url::Url;
+use uuid::Uuid;
+
+use crate::connectors::{
+    AuthType, Connector, Cursor, CursorWindows, ProviderCategory, ProviderMetadata, Registry,
+    trait_::{
+        AuthorizeParams, ExchangeTokenParams, HealthCheck, SyncError, SyncErrorKind, SyncParams,
+        SyncResult, WebhookParams,
+    },
+};
+use crate::models::{connection::Model as Connection, signal::Model as Signal};
+use crate::normalization::{SignalKind, normalize_slack_event_kind};
+
+/// Provider slug
+pub const SLACK_PROVIDER_SLUG: &str = "slack";
+
+/// Bot scopes requested at install time
+pub const SLACK_SCOPES: &[&str] = &[
+    "channels:history",
+    "channels:read",
+    "groups:history",
+    "groups:read",
+    "reactions:read",
+];
+
+/// Page size for `conversations.list` and `conversations.history`
+const PAGE_LIMIT: u32 = 200;
+
+/// History pages fetched per channel in one sync; the rest continues on the next run
+const MAX_HISTORY_PAGES_PER_CHANNEL: u32 = 5;
+
+/// Signals collected before a sync stops and reports `has_more`
+const MAX_SIGNALS_PER_SYNC: usize = 2000;
+
+/// Slack API errors that mean the token is no longer usable
+const AUTH_ERRORS: &[&str] = &[
+    "invalid_auth",
+    "not_authed",
+    "token_revoked",
+    "token_expired",
+    "account_inactive",
+];
+
+/// Slack connector
+pub struct SlackConnector {
+    client_id: String,
+    client_secret: String,
+    oauth_base: String,
+    api_base: String,
+    http_client: Client,
+}
+
+/// Per-channel backfill position stored in the cursor window keyed by channel id
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+struct ChannelWindow {
+    /// Messages at or before this `ts` have been synced
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    oldest: Option<String>,
+    /// `next_cursor` of an unfinished history walk
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    page: Option<String>,
+    /// Newest `ts` seen by the unfinished walk; becomes `oldest` once it completes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    high_water: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SlackOAuthResponse {
+    ok: bool,
+    #[serde(default)]
+    error: Option<String>,
+    #[serde(default)]
+    access_token: Option<String>,
+    #[serde(default)]
+    refresh_token: Option<String>,
+    #[serde(default)]
+    expires_in: Option<i64>,
+    #[serde(default)]
+    scope: Option<String>,
+    #[serde(default)]
+    bot_user_id: Option<String>,
+    #[serde(default)]
+    app_id: Option<String>,
+    #[serde(default)]
+    team: Option<SlackTeam>,
+    #[serde(default)]
+    authed_user: Option<SlackAuthedUser>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SlackTeam {
+    id: String,
+    #[serde(default)]
+    name: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SlackAuthedUser {
+    id: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct SlackChannel {
+    id: String,
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    is_member: bool,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct SlackResponseMetadata {
+    #[serde(default)]
+    next_cursor: Option<String>,
+}
+
+impl SlackResponseMetadata {
+    fn next(self) -> Option<String> {
+        self.next_cursor.filter(|cursor| !cursor.is_empty())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct SlackChannelsPage {
+    #[serde(default)]
+    channels: Vec<SlackChannel>,
+    #[serde(default)]
+    response_metadata: SlackResponseMetadata,
+}
+
+#[derive(Debug, Deserialize)]
+struct SlackHistoryPage {
+    #[serde(default)]
+    messages: Vec<serde_json::Value>,
+    #[serde(default)]
+    has_more: bool,
+    #[serde(default)]
+    response_metadata: SlackResponseMetadata,
+}
+
+impl SlackConnector {
+    /// Create a new Slack connector with configuration
+    pub fn new(
+        client_id: String,
+        client_secret: String,
+        oauth_base: String,
+        api_base: String,
+    ) -> Self {
+        Self {
+            client_id,
+            client_secret,
+            oauth_base,
+            api_base,
+            http_client: crate::egress::client(),
+        }
+    }
+
+    fn default_redirect_uri() -> String {
+        match std::env::var("POBLYSH_PROFILE").as_deref() {
+            Ok("local") | Ok("test") | Err(_) => "http://localhost:3000/callback".to_string(),
+            Ok(_) => "https://app.poblysh.com/callback".to_string(),
+        }
+    }
+
+    fn access_token(connection: &Connection) -> Result<String, SyncError> {
+        connection
+            .access_token
+            .clone()
+            .filter(|token| !token.is_empty())
+            .ok_or_else(|| SyncError::unauthorized("Missing Slack access token"))
+    }
+
+    /// Call `oauth.v2.access` with the given grant parameters
+    async fn oauth_access(
+        &self,
+        grant: &[(&str, &str)],
+    ) -> Result<SlackOAuthResponse, anyhow::Error> {
+        let mut form = vec![
+            ("client_id", self.client_id.as_str()),
+            ("client_secret", self.client_secret.as_str()),
+        ];
+        form.extend_from_slice(grant);
+
+        let response = self
+            .http_client
+            .post(format!(
+                "{}/oauth.v2.access",
+                self.api_base.trim_end_matches('/')
+            ))
+            .form(&form)
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            return Err(anyhow!(
+                "Slack token endpoint returned {}",
+                response.status()
+            ));
+        }
+
+        let body: SlackOAuthResponse = response.json().await?;
+        if !body.ok {
+            return Err(anyhow!(
+                "Slack OAuth failed: {}",
+                body.error.as_deref().unwrap_or("unknown_error")
+            ));
+        }
+        Ok(body)
+    }
+
+    /// GET a Web API method, mapping HTTP and `ok: false` failures to sync errors
+    async fn api_get<T: for<'de> Deserialize<'de>>(
+        &self,
+        access_token: &str,
+        method: &str,
+        query: &[(&str, String)],
+    ) -> Result<T, SyncError> {
+        let url = format!("{}/{}", self.api_base.trim_end_matches('/'), method);
+        let response = self
+            .http_client
+            .get(url)
+            .bearer_auth(access_token)
+            .query(query)
+            .send()
+            .await
+            .map_err(|e| SyncError::transient(format!("Slack {} failed: {}", method, e)))?;
+
+        let status = response.status();
+        if status == StatusCode::TOO_MANY_REQUESTS {
+            let retry_after = response
+                .headers()
+                .get("Retry-After")
+                .and_then(|h| h.to_str().ok())
+                .and_then(|s| s.parse::<u64>().ok());
+            return Err(SyncError::rate_limited(retry_after));
+        }
+        if status.is_server_error() {
+            return Err(SyncError::transient(format!(
+                "Slack {} failed: {}",
+                method, status
+            )));
+        }
+        if !status.is_success() {
+            return Err(SyncError::permanent(format!(
+                "Slack {} failed: {}",
+                method, status
+            )));
+        }
+
+        let body: serde_json::Value = response.json().await.map_err(|e| {
+            SyncError::transient(format!("Slack {} returned bad JSON: {}", method, e))
+        })?;
+        if !body.get("ok").and_then(|v| v.as_bool()).unwrap_or(false) {
+            let error = body
+                .get("error")
+                .and_then(|v| v.as_str())
+                .unwrap_or("unknown_error");
+            return Err(if AUTH_ERRORS.contains(&error) {
+                SyncError::unauthorized(format!("Slack {}: {}", method, error))
+            } else if error == "ratelimited" {
+                SyncError::rate_limited(None)
+            } else {
+                SyncError::permanent(format!("Slack {}: {}", method, error))
+            });
+        }
+
+        serde_json::from_value(body).map_err(|e| {
+            SyncError::permanent(format!("Unexpected Slack {} response: {}", method, e))
+        })
+    }
+
+    /// Channels the bot can read history from
+    async fn list_member_channels(
+        &self,
+        access_token: &str,
+    ) -> Result<Vec<SlackChannel>, SyncError> {
+        let mut channels = Vec::new();
+        let mut cursor: Option<String> = None;
+        loop {
+            let mut query = vec![
+                ("types", "public_channel,private_channel".to_string()),
+                ("exclude_archived", "true".to_string()),
+                ("limit", PAGE_LIMIT.to_string()),
+            ];
+            if let Some(cursor) = &cursor {
+                query.push(("cursor", cursor.clone()));
+            }
+            let page: SlackChannelsPage = self
+                .api_get(access_token, "conversations.list", &query)
+                .await?;
+            channels.extend(page.channels.into_iter().filter(|c| c.is_member));
+            match page.response_metadata.next() {
+                Some(next) => cursor = Some(next),
+                None => break,
+            }
+        }
+        Ok(channels)
+    }
+
+    /// Walk one channel's history from its window, newest first.
+    ///
+    /// Returns the signals, the updated window and whether pages remain.
+    async fn sync_channel(
+        &self,
+        access_token: &str,
+        connection: &Connection,
+        channel: &SlackChannel,
+        mut window: ChannelWindow,
+    ) -> Result<(Vec<Signal>, ChannelWindow, bool), SyncError> {
+        let mut signals = Vec::new();
+        for _ in 0..MAX_HISTORY_PAGES_PER_CHANNEL {
+            let mut query = vec![
+                ("channel", channel.id.clone()),
+                ("limit", PAGE_LIMIT.to_string()),
+            ];
+            if let Some(oldest) = &window.oldest {
+                query.push(("oldest", oldest.clone()));
+            }
+            if let Some(page) = &window.page {
+                query.push(("cursor", page.clone()));
+            }
+
+            let page: SlackHistoryPage = match self
+                .api_get(access_token, "conversations.history", &query)
+                .await
+            {
+                Ok(page) => page,
+                // The bot was removed from the channel between listing and reading it
+                Err(err)
+                    if err
+                        .message
+                        .as_deref()
+                        .is_some_and(|m| m.ends_with("not_in_channel")) =>
+                {
+                    warn!(channel_id = %channel.id, "Skipping Slack channel the bot left");
+                    return Ok((signals, window, false));
+                }
+                Err(err) => return Err(err),
+            };
+
+            for message in &page.messages {
+                let Some(ts) = message.get("ts").and_then(|v| v.as_str()) else {
+                    continue;
+                };
+                if window.oldest.as_deref() == Some(ts) {
+                    continue;
+                }
+                if window
+                    .high_water
+                    .as_deref()
+                    .is_none_or(|high| parse_ts(ts) > parse_ts(high))
+                {
+                    window.high_water = Some(ts.to_string());
+                }
+                if let Some(kind) = normalize_slack_event_kind(message) {
+                    signals.push(build_signal(
+                        connection.tenant_id,
+                        connection.id,
+                        kind,
+                        &channel.id,
+                        channel.name.as_deref(),
+                        connection.external_id.as_str(),
+                        message,
+                    ));
+                }
+            }
+
+            match page.response_metadata.next().filter(|_| page.has_more) {
+                Some(next) => window.page = Some(next),
+                None => {
+                    window.page = None;
+                    if let Some(high_water) = window.high_water.take() {
+                        window.oldest = Some(high_water);
+                    }
+                    return Ok((signals, window, false));
+                }
+            }
+        }
+        Ok((signals, window, true))
+    }
+}
+
+#[async_trait]
+impl Connector for SlackConnector {
+    async fn authorize(
+        &self,
+        params: AuthorizeParams,
+    ) -> Result<Url, Box<dyn std::error::Error + Send + Sync>> {
+        info!(
+            tenant_id = %params.tenant_id,
+            "Generating Slack OAuth authorization URL"
+        );
+
+        let mut url = Url::parse(&format!(
+            "{}/oauth/v2/authorize",
+            self.oauth_base.trim_end_matches('/')
+        ))?;
+        let redirect_uri = params
+            .redirect_uri
+            .unwrap_or_else(Self::default_redirect_uri);
+        let state = params
+            .state
+            .filter(|s| !s.is_empty())
+            .unwrap_or_else(|| Uuid::new_v4().to_string());
+        url.query_pairs_mut()
+            .append_pair("client_id", &self.client_id)
+            .append_pair("scope", &SLACK_SCOPES.join(","))
+            .append_pair("redirect_uri", &redirect_uri)
+            .append_pair("state", &state);
+
+        debug!(
+            tenant_id = %params.tenant_id,
+            authorize_url = %url,
+            "Generated Slack OAuth authorization URL"
+        );
+        Ok(url)
+    }
+
+    async fn exchange_token(
+        &self,
+        params: ExchangeTokenParams,
+    ) -> Result<Connection, Box<dyn std::error::Error + Send + Sync>> {
+        info!(
+            tenant_id = %params.tenant_id,
+            "Exchanging Slack authorization code for tokens"
+        );
+
+        let redirect_uri = params
+            .redirect_uri
+            .unwrap_or_else(Self::default_redirect_uri);
+        let token = self
+            .oauth_access(&[
+                ("code", params.code.as_str()),
+                ("redirect_uri", redirect_uri.as_str()),
+            ])
+            .await?;
+
+        let access_token = token
+            .access_token
+            .ok_or_else(|| anyhow!("Slack OAuth response missing access_token"))?;
+        let team = token
+            .team
+            .ok_or_else(|| anyhow!("Slack OAuth response missing team"))?;
+        let now = DateTime::from(Utc::now());
+
+        Ok(Connection {
+            id: Uuid::new_v4(),
+            tenant_id: params.tenant_id,
+            provider_slug: SLACK_PROVIDER_SLUG.to_string(),
+            external_id: team.id.clone(),
+            status: "active".to_string(),
+            display_name: team.name.clone(),
+            access_token_ciphertext: None,
+            refresh_token_ciphertext: None,
+            access_token: Some(access_token),
+            refresh_token: token.refresh_token,
+            expires_at: token
+                .expires_in
+                .map(|seconds| now + chrono::Duration::seconds(seconds)),
+            scopes: token.scope.map(|scope| {
+                serde_json::Value::Array(
+                    scope
+                        .split(',')
+                        .filter(|s| !s.is_empty())
+                        .map(|s| serde_json::Value::String(s.to_string()))
+                        .collect(),
+                )
+            }),
+            metadata: Some(serde_json::json!({
+                "provider": SLACK_PROVIDER_SLUG,
+                "team": { "id": team.id, "name": team.name },
+                "bot_user_id": token.bot_user_id,
+                "app_id": token.app_id,
+                "authed_user_id": token.authed_user.map(|user| user.id),
+            })),
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    async fn refresh_token(
+        &self,
+        connection: Connection,
+    ) -> Result<Connection, Box<dyn std::error::Error + Send + Sync>> {
+        // Bot tokens only expire when the app opted into token rotation
+        let Some(refresh_token) = connection
+            .refresh_token
+            .clone()
+            .filter(|token| !token.is_empty())
+        else {
+            return Err(SyncError::unauthorized("Slack connection has no refresh token").into());
+        };
+
+        info!(connection_id = %connection.id, "Refreshing Slack token");
+        let token = self
+            .oauth_access(&[
+                ("grant_type", "refresh_token"),
+                ("refresh_token", refresh_token.as_str()),
+            ])
+            .await?;
+        let access_token = token
+            .access_token
+            .ok_or_else(|| anyhow!("Slack refresh response missing access_token"))?;
+        let now = DateTime::from(Utc::now());
+
+        Ok(Connection {
+            access_token: Some(access_token),
+            refresh_token: token.refresh_token.or(connection.refresh_token.clone()),
+            expires_at: token
+                .expires_in
+                .map(|seconds| now + chrono::Duration::seconds(seconds)),
+            updated_at: now,
+            ..connection
+        })
+    }
+
+    async fn sync(
+        &self,
+        params: SyncParams,
+    ) -> Result<SyncResult, Box<dyn std::error::Error + Send + Sync>> {
+        let connection = &params.connection;
+        info!(
+            tenant_id = %connection.tenant_id,
+            connection_id = %connection.id,
+            has_cursor = %params.cursor.is_some(),
+            "Starting Slack sync"
+        );
+
+        let access_token = Self::access_token(connection)?;
+        let previous = params
+            .cursor
+            .as_ref()
+            .and_then(Cursor::windows)
+            .unwrap_or_default();
+
+        let channels = self.list_member_channels(&access_token).await?;
+        let mut windows = CursorWindows::new();
+        let mut signals = Vec::new();
+        let mut has_more = false;
+
+        for channel in &channels {
+            if signals.len() >= MAX_SIGNALS_PER_SYNC {
+                // Remaining channels keep their windows via cursor merging
+                has_more = true;
+                break;
+            }
+            let window = previous
+                .get(&channel.id)
+                .and_then(|cursor| serde_json::from_value(cursor.as_json().clone()).ok())
+                .unwrap_or_default();
+            let (channel_signals, window, more) = self
+                .sync_channel(&access_token, connection, channel, window)
+                .await?;
+            signals.extend(channel_signals);
+            has_more |= more;
+            if window != ChannelWindow::default() {
+                windows.set(
+                    channel.id.clone(),
+                    Cursor::from_json(serde_json::to_value(&window)?),
+                );
+            }
+        }
+
+        debug!(
+            connection_id = %connection.id,
+            channels = channels.len(),
+            signals = signals.len(),
+            has_more,
+            "Slack sync completed"
+        );
+
+        Ok(SyncResult {
+            signals,
+            next_cursor: if windows.is_empty() {
+                params.cursor
+            } else {
+                Some(Cursor::from_windows(windows))
+            },
+            has_more,
+        })
+    }
+
+    async fn handle_webhook(
+        &self,
+        params: WebhookParams,
+    ) -> Result<Vec<Signal>, Box<dyn std::error::Error + Send + Sync>> {
+        let envelope_type = params
+            .payload
+            .get("type")
+            .and_then(|v| v.as_str())
+            .unwrap_or("");
+        if envelope_type != "event_callback" {
+            debug!(
+                tenant_id = %params.tenant_id,
+                envelope_type = %envelope_type,
+                "Ignoring non-event Slack payload"
+            );
+            return Ok(vec![]);
+        }
+
+        let event = params
+            .payload
+            .get("event")
+            .ok_or_else(|| anyhow!("Invalid Slack event payload: missing event"))?;
+        let Some(kind) = normalize_slack_event_kind(event) else {
+            debug!(
+                tenant_id = %params.tenant_id,
+                event_type = ?event.get("type"),
+                "Slack event ignored"
+            );
+            return Ok(vec![]);
+        };
+
+        // Reactions point at their message through `item`; messages carry the channel directly
+        let channel_id = event
+            .get("channel")
+            .or_else(|| event.pointer("/item/channel"))
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow!("Invalid Slack event payload: missing channel"))?;
+        let team_id = params
+            .payload
+            .get("team_id")
+            .and_then(|v| v.as_str())
+            .unwrap_or_default();
+
+        info!(
+            tenant_id = %params.tenant_id,
+            signal_kind = %kind,
+            channel_id = %channel_id,
+            "Slack event mapped to signal"
+        );
+
+        Ok(vec![build_signal(
+            params.tenant_id,
+            Uuid::nil(),
+            kind,
+            channel_id,
+            None,
+            team_id,
+            event,
+        )])
+    }
+
+    async fn health_check(
+        &self,
+        connection: &Connection,
+    ) -> Result<Option<HealthCheck>, Box<dyn std::error::Error + Send + Sync>> {
+        let access_token = match Self::access_token(connection) {
+            Ok(token) => token,
+            Err(e) => return Ok(Some(HealthCheck::rejected(e.to_string()))),
+        };
+        // `auth.test` reports no scopes, so the ones granted at install stand
+        match self
+            .api_get::<serde_json::Value>(&access_token, "auth.test", &[])
+            .await
+        {
+            Ok(_) => Ok(Some(HealthCheck::valid(None))),
+            Err(e) if e.kind == SyncErrorKind::Unauthorized => {
+                Ok(Some(HealthCheck::rejected(e.to_string())))
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+/// Register the Slack connector in the registry
+pub fn register_slack_connector(registry: &mut Registry, connector: Arc<SlackConnector>) {
+    let metadata = ProviderMetadata::new(
+        SLACK_PROVIDER_SLUG.to_string(),
+        AuthType::OAuth2,
+        SLACK_SCOPES.iter().map(|s| s.to_string()).collect(),
+        true, // webhooks supported
+    )
+    .with_category(ProviderCategory::Chat)
+    .with_description("Sync channel conversations from Slack")
+    .with_icon_url("https://cdn.simpleicons.org/slack")
+    .with_docs_url("https://api.slack.com/docs")
+    .with_backfill(true)
+    .with_signal_kinds(&[
+        SignalKind::MessagePosted,
+        SignalKind::MessageUpdated,
+        SignalKind::MessageDeleted,
+        SignalKind::ReactionAdded,
+    ]);
+
+    registry.register(connector, metadata);
+}
+
+/// Slack `ts` values are `<seconds>.<micros>` strings
+fn parse_ts(ts: &str) -> f64 {
+    ts.parse().unwrap_or(0.0)
+}
+
+fn ts_to_datetime(ts: &str) -> Option<DateTime<Utc>> {
+    let (seconds, micros) = ts.split_once('.').unwrap_or((ts, "0"));
+    let micros: u32 = format!("{:0<6}", micros).get(..6)?.parse().ok()?;
+    DateTime::from_timestamp(seconds.parse().ok()?, micros * 1000)
+}
+
+/// Build a signal from a history message or Events API event
+fn build_signal(
+    tenant_id: Uuid,
+    connection_id: Uuid,
+    kind: SignalKind,
+    channel_id: &str,
+    channel_name: Option<&str>,
+    team_id: &str,
+    event: &serde_json::Value,
+) -> Signal {
+    // Edits carry the new message under `message`; deletions only the deleted ts
+    let message = event.get("message").unwrap_or(event);
+    let str_field = |value: &serde_json::Value, key: &str| {
+        value.get(key).and_then(|v| v.as_str()).map(str::to_string)
+    };
+    let message_ts = str_field(event, "deleted_ts")
+        .or_else(|| str_field(message, "ts"))
+        .or_else(|| {
+            event
+                .pointer("/item/ts")
+                .and_then(|v| v.as_str())
+                .map(str::to_string)
+        })
+        .unwrap_or_default();
+    // Edits and reactions are distinct events on the same message
+    let event_ts = str_field(event, "event_ts")
+        .or_else(|| str_field(event, "ts"))
+        .unwrap_or_else(|| message_ts.clone());
+
+    let received_at = DateTime::from(Utc::now());
+    let occurred_at = ts_to_datetime(&event_ts).unwrap_or_else(Utc::now);
+    let dedupe_key = match kind {
+        SignalKind::MessagePosted => format!("slack:{}:{}:{}", kind, channel_id, message_ts),
+        _ => format!("slack:{}:{}:{}:{}", kind, channel_id, message_ts, event_ts),
+    };
+
+    Signal {
+        id: Uuid::new_v4(),
+        tenant_id,
+        provider_slug: SLACK_PROVIDER_SLUG.to_string(),
+        connection_id,
+        kind: kind.as_str().to_string(),
+        occurred_at: occurred_at.into(),
+        received_at,
+        payload: serde_json::json!({
+            "team_id": team_id,
+            "channel_id": channel_id,
+            "channel_name": channel_name,
+            "message_ts": message_ts,
+            "thread_ts": str_field(message, "thread_ts"),
+            "user_id": str_field(message, "user").or_else(|| str_field(event, "user")),
+            "text": str_field(message, "text"),
+            "subtype": str_field(event, "subtype"),
+            "reaction": str_field(event, "reaction"),
+            "occurred_at": occurred_at.to_rfc3339(),
+        }),
+        dedupe_key: Some(dedupe_key),
+        created_at: received_at,
+        updated_at: received_at,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{method, path, query_param, query_param_is_missing};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn connector(base: &str) -> SlackConnector {
+        SlackConnector::new(
+            "client-id".to_string(),
+            "client-secret".to_string(),
+            base.to_string(),
+            base.to_string(),
+        )
+    }
+
+    fn connection() -> Connection {
+        let now = DateTime::from(Utc::now());
+        Connection {
+            id: Uuid::new_v4(),
+            tenant_id: Uuid::new_v4(),
+            provider_slug: SLACK_PROVIDER_SLUG.to_string(),
+            external_id: "T123".to_string(),
+            status: "active".to_string(),
+            display_name: Some("Acme".to_string()),
+            access_token_ciphertext: None,
+            refresh_token_ciphertext: None,
+            access_token: Some("xoxb-test".to_string()),
+            refresh_token: None,
+            expires_at: None,
+            scopes: None,
+            metadata: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_slack_authorize_url_shape() {
+        let url = connector("https://slack.com")
+            .authorize(AuthorizeParams {
+                tenant_id: Uuid::new_v4(),
+                redirect_uri: Some("https://app.example.com/callback".to_string()),
+                state: Some("state-1".to_string()),
+                code_challenge: None,
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(url.path(), "/oauth/v2/authorize");
+        let query: std::collections::HashMap<_, _> = url.query_pairs().collect();
+        assert_eq!(query["client_id"], "client-id");
+        assert_eq!(query["state"], "state-1");
+        assert_eq!(query["scope"], SLACK_SCOPES.join(","));
+    }
+
+    #[tokio::test]
+    async fn test_slack_exchange_token_records_workspace() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/oauth.v2.access"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "ok": true,
+                "access_token": "xoxb-new",
+                "token_type": "bot",
+                "scope": "channels:history,channels:read",
+                "bot_user_id": "U0BOT",
+                "app_id": "A1",
+                "team": {"id": "T123", "name": "Acme"},
+                "authed_user": {"id": "U1"}
+            })))
+            .mount(&server)
+            .await;
+
+        let connection = connector(&server.uri())
+            .exchange_token(ExchangeTokenParams {
+                code: "code".to_string(),
+                redirect_uri: None,
+                tenant_id: Uuid::new_v4(),
+                code_verifier: None,
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(connection.external_id, "T123");
+        assert_eq!(connection.display_name.as_deref(), Some("Acme"));
+        assert_eq!(connection.access_token.as_deref(), Some("xoxb-new"));
+        assert_eq!(
+            connection.scopes,
+            Some(serde_json::json!(["channels:history", "channels:read"]))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_slack_backfill_pages_and_resumes_from_cursor() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/conversations.list"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "ok": true,
+                "channels": [
+                    {"id": "C1", "name": "general", "is_member": true},
+                    {"id": "C2", "name": "random", "is_member": false}
+                ],
+                "response_metadata": {"next_cursor": ""}
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/conversations.history"))
+            .and(query_param("channel", "C1"))
+            .and(query_param_is_missing("cursor"))
+            .and(query_param_is_missing("oldest"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "ok": true,
+                "messages": [
+                    {"type": "message", "user": "U1", "text": "newest", "ts": "1700000300.000100"},
+                    {"type": "message", "subtype": "channel_join", "user": "U2", "ts": "1700000200.000100"}
+                ],
+                "has_more": true,
+                "response_metadata": {"next_cursor": "page2"}
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/conversations.history"))
+            .and(query_param("cursor", "page2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "ok": true,
+                "messages": [
+                    {"type": "message", "user": "U3", "text": "oldest", "ts": "1700000100.000100"}
+                ],
+                "has_more": false
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/conversations.history"))
+            .and(query_param("oldest", "1700000300.000100"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "ok": true,
+                "messages": [],
+                "has_more": false
+            })))
+            .mount(&server)
+            .await;
+
+        let connector = connector(&server.uri());
+        let connection = connection();
+        let result = connector
+            .sync(SyncParams {
+                connection: connection.clone(),
+                cursor: None,
+                cancellation: Default::default(),
+            })
+            .await
+            .unwrap();
+
+        // The join message carries no content and is skipped
+        assert_eq!(result.signals.len(), 2);
+        assert!(result.signals.iter().all(|s| s.kind == "message_posted"));
+        assert_eq!(
+            result.signals[0].dedupe_key.as_deref(),
+            Some("slack:message_posted:C1:1700000300.000100")
+        );
+        assert!(!result.has_more);
+        let cursor = result.next_cursor.unwrap();
+        assert_eq!(
+            cursor.window("C1").unwrap().as_json(),
+            &serde_json::json!({"oldest": "1700000300.000100"})
+        );
+
+        // The next run only asks for messages newer than the high-water mark
+        let result = connector
+            .sync(SyncParams {
+                connection,
+                cursor: Some(cursor),
+                cancellation: Default::default(),
+            })
+            .await
+            .unwrap();
+        assert!(result.signals.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_slack_sync_maps_auth_and_rate_limit_errors() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/conversations.list"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({"ok": false, "error": "token_revoked"})),
+            )
+            .mount(&server)
+            .await;
+
+        let err = connector(&server.uri())
+            .sync(SyncParams {
+                connection: connection(),
+                cursor: None,
+                cancellation: Default::default(),
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<SyncError>().unwrap().kind,
+            crate::connectors::SyncErrorKind::Unauthorized
+        );
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/conversations.list"))
+            .respond_with(ResponseTemplate::new(429).insert_header("Retry-After", "12"))
+            .mount(&server)
+            .await;
+        let err = connector(&server.uri())
+            .sync(SyncParams {
+                connection: connection(),
+                cursor: None,
+                cancellation: Default::default(),
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<SyncError>().unwrap().kind,
+            crate::connectors::SyncErrorKind::RateLimited {
+                retry_after_secs: Some(12)
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_slack_health_check_uses_auth_test() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/auth.test"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(
+                    serde_json::json!({"ok": true, "team_id": "T123", "user_id": "U1"}),
+                ),
+            )
+            .mount(&server)
+            .await;
+        let health = connector(&server.uri())
+            .health_check(&connection())
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(health.token_valid);
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/auth.test"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({"ok": false, "error": "token_revoked"})),
+            )
+            .mount(&server)
+            .await;
+        let health = connector(&server.uri())
+            .health_check(&connection())
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(!health.token_valid);
+        assert!(health.message.unwrap().contains("token_revoked"));
+    }
+
+    #[tokio::test]
+    async fn test_slack_webhook_mapping() {
+        let connector = connector("https://slack.com");
+        let handle = |payload: serde_json::Value| {
+            connector.handle_webhook(WebhookParams {
+                payload,
+                tenant_id: Uuid::new_v4(),
+                db: None,
+                auth_header: None,
+            })
+        };
+
+        let posted = handle(serde_json::json!({
+            "type": "event_callback",
+            "team_id": "T123",
+            "event": {"type": "message", "channel": "C1", "user": "U1", "text": "hi",
+                      "ts": "1700000300.000100", "event_ts": "1700000300.000100"}
+        }))
+        .await
+        .unwrap();
+        assert_eq!(posted.len(), 1);
+        assert_eq!(posted[0].kind, "message_posted");
+        // Matches the key produced by backfill so the two paths deduplicate
+        assert_eq!(
+            posted[0].dedupe_key.as_deref(),
+            Some("slack:message_posted:C1:1700000300.000100")
+        );
+        assert_eq!(posted[0].payload["text"], "hi");
+
+        let edited = handle(serde_json::json!({
+            "type": "event_callback",
+            "event": {"type": "message", "subtype": "message_changed", "channel": "C1",
+                      "message": {"user": "U1", "text": "hi!", "ts": "1700000300.000100"},
+                      "event_ts": "1700000400.000200"}
+        }))
+        .await
+        .unwrap();
+        assert_eq!(edited[0].kind, "message_updated");
+        assert_eq!(edited[0].payload["text"], "hi!");
+
+        let reaction = handle(serde_json::json!({
+            "type": "event_callback",
+            "event": {"type": "reaction_added", "user": "U2", "reaction": "tada",
+                      "item": {"type": "message", "channel": "C1", "ts": "1700000300.000100"},
+                      "event_ts": "1700000500.000300"}
+        }))
+        .await
+        .unwrap();
+        assert_eq!(reaction[0].kind, "reaction_added");
+        assert_eq!(reaction[0].payload["channel_id"], "C1");
+
+        let verification = handle(serde_json::json!({
+            "type": "url_verification",
+            "challenge": "abc"
+        }))
+        .await
+        .unwrap();
+        assert!(verification.is_empty());
+    }
+}
@@ -0,0 +1,371 @@
+//! Stripe connector implementation
+//!
+//! Stripe pushes billing events to this service through a webhook endpoint
+//! whose `Stripe-Signature` header is verified in
+//! [`crate::webhook_verification`]. Deliveries land on the tenant's Stripe
+//! connection for the event's account, created on first delivery. Invoice,
+//! subscription and dispute events become commerce signals; there is nothing
+//! to poll.
+
+use anyhow::anyhow;
+use async_trait::async_trait;
+use chrono::{DateTime, TimeZone, Utc};
+use std::sync::Arc;
+use tracing::{debug, info};
+use url::Url;
+use uuid::Uuid;
+
+use crate::connectors::{
+    AuthType, Connector, ProviderCategory, ProviderMetadata, Registry,
+    trait_::{AuthorizeParams, ExchangeTokenParams, SyncParams, SyncResult, WebhookParams},
+};
+use crate::models::{connection::Model as Connection, signal::Model as Signal};
+use crate::normalization::{SignalKind, normalize_stripe_event_kind};
+
+/// Provider slug
+pub const STRIPE_PROVIDER_SLUG: &str = "stripe";
+
+/// External id of a connection whose events do not name a connected account
+pub const STRIPE_DEFAULT_EXTERNAL_ID: &str = "default";
+
+/// Stripe connector
+#[derive(Default)]
+pub struct StripeConnector;
+
+impl StripeConnector {
+    /// Create a new Stripe connector
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl Connector for StripeConnector {
+    async fn authorize(
+        &self,
+        _params: AuthorizeParams,
+    ) -> Result<Url, Box<dyn std::error::Error + Send + Sync>> {
+        Err(anyhow!("OAuth authorization is not supported for Stripe").into())
+    }
+
+    async fn exchange_token(
+        &self,
+        _params: ExchangeTokenParams,
+    ) -> Result<Connection, Box<dyn std::error::Error + Send + Sync>> {
+        Err(anyhow!("Token exchange is not supported for Stripe").into())
+    }
+
+    async fn refresh_token(
+        &self,
+        _connection: Connection,
+    ) -> Result<Connection, Box<dyn std::error::Error + Send + Sync>> {
+        Err(anyhow!("Token refresh is not supported for Stripe").into())
+    }
+
+    async fn sync(
+        &self,
+        params: SyncParams,
+    ) -> Result<SyncResult, Box<dyn std::error::Error + Send + Sync>> {
+        // Events arrive through webhooks only
+        Ok(SyncResult {
+            signals: vec![],
+            next_cursor: params.cursor,
+            has_more: false,
+        })
+    }
+
+    async fn handle_webhook(
+        &self,
+        params: WebhookParams,
+    ) -> Result<Vec<Signal>, Box<dyn std::error::Error + Send + Sync>> {
+        let event = &params.payload;
+        let Some(kind) = normalize_stripe_event_kind(event) else {
+            debug!(
+                tenant_id = %params.tenant_id,
+                event_type = ?event.get("type"),
+                "Stripe webhook ignored"
+            );
+            return Ok(vec![]);
+        };
+
+        let signal = build_event_signal(params.tenant_id, Uuid::nil(), kind, event);
+        info!(
+            tenant_id = %params.tenant_id,
+            signal_kind = %kind,
+            livemode = %signal.payload["livemode"],
+            "Stripe webhook mapped to signal"
+        );
+        Ok(vec![signal])
+    }
+}
+
+/// Register the Stripe connector in the registry
+pub fn register_stripe_connector(registry: &mut Registry, connector: Arc<StripeConnector>) {
+    let metadata = ProviderMetadata::new(
+        STRIPE_PROVIDER_SLUG.to_string(),
+        AuthType::Custom("webhook".to_string()),
+        vec![],
+        true, // webhooks supported
+    )
+    .with_category(ProviderCategory::Other)
+    .with_description("Receive invoice, subscription and dispute events from Stripe")
+    .with_icon_url("https://cdn.simpleicons.org/stripe")
+    .with_docs_url("https://docs.stripe.com/webhooks")
+    .with_backfill(false)
+    .with_signal_kinds(&[
+        SignalKind::InvoicePaid,
+        SignalKind::InvoicePaymentFailed,
+        SignalKind::SubscriptionCreated,
+        SignalKind::SubscriptionUpdated,
+        SignalKind::SubscriptionCanceled,
+        SignalKind::DisputeOpened,
+        SignalKind::DisputeUpdated,
+        SignalKind::DisputeClosed,
+    ]);
+
+    registry.register(connector, metadata);
+}
+
+/// External id of the connection a webhook delivery belongs to
+///
+/// Events sent to a Connect endpoint name the connected account; events from
+/// the platform's own account share one connection.
+pub fn stripe_external_id(payload: Option<&serde_json::Value>) -> String {
+    payload
+        .and_then(|p| p.get("account"))
+        .and_then(|v| v.as_str())
+        .map(str::trim)
+        .filter(|id| !id.is_empty())
+        .map(str::to_string)
+        .unwrap_or_else(|| STRIPE_DEFAULT_EXTERNAL_ID.to_string())
+}
+
+fn text(value: &serde_json::Value, key: &str) -> Option<String> {
+    value
+        .get(key)
+        .and_then(|v| v.as_str())
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+}
+
+/// Id of an expandable field, which is either an id or the expanded object
+fn expandable_id(value: &serde_json::Value, key: &str) -> Option<String> {
+    match value.get(key)? {
+        serde_json::Value::String(id) => Some(id.clone()),
+        serde_json::Value::Object(object) => object
+            .get("id")
+            .and_then(|v| v.as_str())
+            .map(str::to_string),
+        _ => None,
+    }
+}
+
+fn epoch(value: &serde_json::Value, key: &str) -> Option<DateTime<Utc>> {
+    Utc.timestamp_opt(value.get(key)?.as_i64()?, 0).single()
+}
+
+/// Build a commerce signal from a Stripe event
+///
+/// Creation, payment, cancellation and closing happen once per object, so
+/// they are keyed on the object id and redelivered events collapse; updates
+/// are keyed on the event id.
+fn build_event_signal(
+    tenant_id: Uuid,
+    connection_id: Uuid,
+    kind: SignalKind,
+    event: &serde_json::Value,
+) -> Signal {
+    let object = event
+        .pointer("/data/object")
+        .unwrap_or(&serde_json::Value::Null);
+    let occurred_at = epoch(event, "created").unwrap_or_else(Utc::now);
+    let received_at = DateTime::from(Utc::now());
+    let event_id = text(event, "id");
+    let object_id = text(object, "id");
+    let dedupe_id = match kind {
+        SignalKind::SubscriptionUpdated | SignalKind::DisputeUpdated => event_id.clone(),
+        _ => object_id.clone().or_else(|| event_id.clone()),
+    };
+    let dedupe_key = dedupe_id.map(|id| format!("stripe:{}:{}", kind, id));
+
+    let mut payload = serde_json::json!({
+        "event_id": event_id,
+        "event_type": text(event, "type"),
+        "object_id": object_id,
+        "customer": expandable_id(object, "customer"),
+        "status": text(object, "status"),
+        "currency": text(object, "currency"),
+        "livemode": event.get("livemode").and_then(|v| v.as_bool()).unwrap_or(false),
+        "occurred_at": occurred_at.to_rfc3339(),
+    });
+    let fields = payload.as_object_mut().expect("payload is an object");
+    match kind {
+        SignalKind::InvoicePaid | SignalKind::InvoicePaymentFailed => {
+            // Amounts are in the currency's minor unit
+            fields.insert("amount_due".into(), object["amount_due"].clone());
+            fields.insert("amount_paid".into(), object["amount_paid"].clone());
+            fields.insert(
+                "subscription".into(),
+                expandable_id(object, "subscription").into(),
+            );
+            fields.insert("attempt_count".into(), object["attempt_count"].clone());
+            fields.insert("url".into(), text(object, "hosted_invoice_url").into());
+        }
+        SignalKind::SubscriptionCreated
+        | SignalKind::SubscriptionUpdated
+        | SignalKind::SubscriptionCanceled => {
+            let price = object
+                .pointer("/items/data/0/price")
+                .unwrap_or(&serde_json::Value::Null);
+            fields.insert("price".into(), text(price, "id").into());
+            fields.insert("amount".into(), price["unit_amount"].clone());
+            fields.insert(
+                "interval".into(),
+                price
+                    .pointer("/recurring/interval")
+                    .and_then(|v| v.as_str())
+                    .into(),
+            );
+            fields.insert(
+                "cancel_at_period_end".into(),
+                object["cancel_at_period_end"].clone(),
+            );
+            fields.insert(
+                "cancellation_reason".into(),
+                object
+                    .pointer("/cancellation_details/reason")
+                    .and_then(|v| v.as_str())
+                    .into(),
+            );
+            // Changed fields with their previous values
+            if let Some(previous) = event.pointer("/data/previous_attributes") {
+                let changed: Vec<&String> = previous
+                    .as_object()
+                    .map(|p| p.keys().collect())
+                    .unwrap_or_default();
+                fields.insert("changed".into(), serde_json::json!(changed));
+            }
+        }
+        _ => {
+            fields.insert("amount".into(), object["amount"].clone());
+            fields.insert("charge".into(), expandable_id(object, "charge").into());
+            fields.insert("reason".into(), text(object, "reason").into());
+            fields.insert(
+                "evidence_due_by".into(),
+                epoch(&object["evidence_details"], "due_by")
+                    .map(|due| due.to_rfc3339())
+                    .into(),
+            );
+        }
+    }
+
+    Signal {
+        id: Uuid::new_v4(),
+        tenant_id,
+        provider_slug: STRIPE_PROVIDER_SLUG.to_string(),
+        connection_id,
+        kind: kind.as_str().to_string(),
+        occurred_at: occurred_at.into(),
+        received_at,
+        payload,
+        dedupe_key,
+        created_at: received_at,
+        updated_at: received_at,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn webhook(payload: serde_json::Value) -> WebhookParams {
+        WebhookParams {
+            payload,
+            tenant_id: Uuid::new_v4(),
+            db: None,
+            auth_header: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_stripe_webhook_mapping() {
+        let connector = StripeConnector::new();
+
+        let failed = connector
+            .handle_webhook(webhook(serde_json::json!({
+                "id": "evt_1",
+                "type": "invoice.payment_failed",
+                "created": 1714651200,
+                "livemode": true,
+                "data": {"object": {
+                    "id": "in_1",
+                    "customer": "cus_1",
+                    "subscription": "sub_1",
+                    "status": "open",
+                    "currency": "usd",
+                    "amount_due": 4900,
+                    "amount_paid": 0,
+                    "attempt_count": 2
+                }}
+            })))
+            .await
+            .unwrap();
+        assert_eq!(failed[0].kind, "invoice_payment_failed");
+        assert_eq!(failed[0].payload["amount_due"], 4900);
+        assert_eq!(failed[0].payload["customer"], "cus_1");
+        assert_eq!(failed[0].payload["livemode"], true);
+        assert_eq!(
+            failed[0].occurred_at.to_rfc3339(),
+            "2024-05-02T12:00:00+00:00"
+        );
+        assert_eq!(
+            failed[0].dedupe_key.as_deref(),
+            Some("stripe:invoice_payment_failed:in_1")
+        );
+
+        let updated = connector
+            .handle_webhook(webhook(serde_json::json!({
+                "id": "evt_2",
+                "type": "customer.subscription.updated",
+                "created": 1714651200,
+                "data": {
+                    "object": {
+                        "id": "sub_1",
+                        "customer": {"id": "cus_1"},
+                        "status": "active",
+                        "items": {"data": [{"price": {
+                            "id": "price_1",
+                            "unit_amount": 9900,
+                            "recurring": {"interval": "month"}
+                        }}]}
+                    },
+                    "previous_attributes": {"items": {}}
+                }
+            })))
+            .await
+            .unwrap();
+        assert_eq!(updated[0].payload["customer"], "cus_1");
+        assert_eq!(updated[0].payload["interval"], "month");
+        assert_eq!(updated[0].payload["changed"], serde_json::json!(["items"]));
+        assert_eq!(
+            updated[0].dedupe_key.as_deref(),
+            Some("stripe:subscription_updated:evt_2")
+        );
+
+        let ignored = connector
+            .handle_webhook(webhook(serde_json::json!({
+                "id": "evt_3",
+                "type": "customer.created",
+                "data": {"object": {"id": "cus_1"}}
+            })))
+            .await
+            .unwrap();
+        assert!(ignored.is_empty());
+
+        assert_eq!(
+            stripe_external_id(Some(&serde_json::json!({"account": "acct_1"}))),
+            "acct_1"
+        );
+        assert_eq!(stripe_external_id(None), STRIPE_DEFAULT_EXTERNAL_ID);
+    }
+}
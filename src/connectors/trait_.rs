@@ -4,6 +4,8 @@
 
 use async_trait::async_trait;
 use sea_orm::DatabaseConnection;
+use std::collections::BTreeMap;
+use tokio_util::sync::CancellationToken;
 use url::Url;
 use uuid::Uuid;
 
@@ -286,6 +288,77 @@ impl Cursor {
     pub fn as_str(&self) -> Option<&str> {
         self.0.as_str()
     }
+
+    /// Build a compound cursor holding one window per synced resource.
+    pub fn from_windows(windows: CursorWindows) -> Self {
+        Self(serde_json::json!({ CURSOR_WINDOWS_KEY: windows }))
+    }
+
+    /// Per-resource windows when this is a compound cursor.
+    pub fn windows(&self) -> Option<CursorWindows> {
+        let object = self.0.as_object()?;
+        if object.len() != 1 {
+            return None;
+        }
+        serde_json::from_value(object.get(CURSOR_WINDOWS_KEY)?.clone()).ok()
+    }
+
+    /// Cursor for a single resource.
+    ///
+    /// A plain (non-compound) cursor predates per-resource windows and applies
+    /// to every resource, so it is returned as-is.
+    pub fn window(&self, resource: &str) -> Option<Cursor> {
+        match self.windows() {
+            Some(windows) => windows.get(resource).cloned(),
+            None => Some(self.clone()),
+        }
+    }
+
+    /// Combine a cursor returned by a sync with the one the sync started from.
+    ///
+    /// Connectors only report windows for resources that advanced; windows
+    /// missing from `self` are carried over from `previous` so other resources
+    /// never restart from scratch.
+    pub fn merged_over(self, previous: Option<&Cursor>) -> Cursor {
+        let (Some(mut next), Some(previous)) = (self.windows(), previous) else {
+            return self;
+        };
+        let Some(mut merged) = previous.windows() else {
+            return self;
+        };
+        merged.0.append(&mut next.0);
+        Cursor::from_windows(merged)
+    }
+}
+
+/// JSON key under which compound cursors store their windows
+const CURSOR_WINDOWS_KEY: &str = "windows";
+
+/// Independent cursors keyed by resource (e.g. `issues`, `pulls`).
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize, PartialEq)]
+#[serde(transparent)]
+pub struct CursorWindows(BTreeMap<String, Cursor>);
+
+impl CursorWindows {
+    /// Create an empty set of windows.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cursor for a resource, if one was recorded.
+    pub fn get(&self, resource: &str) -> Option<&Cursor> {
+        self.0.get(resource)
+    }
+
+    /// Record the cursor for a resource, replacing any previous value.
+    pub fn set<S: Into<String>>(&mut self, resource: S, cursor: Cursor) {
+        self.0.insert(resource.into(), cursor);
+    }
+
+    /// Whether no resource has a cursor.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
 }
 
 impl From<Cursor> for serde_json::Value {
@@ -306,6 +379,10 @@ pub struct AuthorizeParams {
     pub tenant_id: Uuid,
     pub redirect_uri: Option<String>,
     pub state: Option<String>,
+    /// PKCE code challenge (RFC 7636, method `S256`). Connectors for providers
+    /// that require PKCE send it as `code_challenge` with
+    /// `code_challenge_method=S256`; others ignore it.
+    pub code_challenge: Option<String>,
 }
 
 /// Parameters for token exchange
@@ -314,6 +391,8 @@ pub struct ExchangeTokenParams {
     pub code: String,
     pub redirect_uri: Option<String>,
     pub tenant_id: Uuid,
+    /// PKCE code verifier matching the challenge sent with the authorize URL
+    pub code_verifier: Option<String>,
 }
 
 /// Parameters for sync operation
@@ -321,6 +400,10 @@ pub struct ExchangeTokenParams {
 pub struct SyncParams {
     pub connection: Connection,
     pub cursor: Option<Cursor>,
+    /// Cancelled when an operator cancels the running job. Connectors that
+    /// fetch several pages per call should check it between pages and return
+    /// what they have so far, with a cursor to resume from and `has_more` set.
+    pub cancellation: CancellationToken,
 }
 
 /// Result from a sync operation
@@ -331,6 +414,90 @@ pub struct SyncResult {
     pub has_more: bool,
 }
 
+/// A page of signals a streaming sync hands over before it finishes
+#[derive(Debug, Clone)]
+pub struct SyncPage {
+    pub signals: Vec<Signal>,
+    /// Cursor that resumes the sync after this page
+    pub next_cursor: Option<Cursor>,
+}
+
+/// Receives pages from [`Connector::sync_streaming`] as they are fetched
+#[async_trait]
+pub trait SyncSink: Send {
+    /// Take a page; an error aborts the sync
+    async fn emit(
+        &mut self,
+        page: SyncPage,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>>;
+}
+
+/// Sink that keeps every page in memory, for callers that want one [`SyncResult`]
+#[derive(Debug, Default)]
+pub struct CollectingSink {
+    pages: Vec<SyncPage>,
+}
+
+impl CollectingSink {
+    /// Fold the collected pages in front of the sync's final `result`
+    pub fn into_result(self, result: SyncResult) -> SyncResult {
+        let mut next_cursor = None;
+        let mut signals = Vec::new();
+        for page in self.pages {
+            signals.extend(page.signals);
+            next_cursor = page.next_cursor.or(next_cursor);
+        }
+        signals.extend(result.signals);
+        SyncResult {
+            signals,
+            next_cursor: result.next_cursor.or(next_cursor),
+            has_more: result.has_more,
+        }
+    }
+}
+
+#[async_trait]
+impl SyncSink for CollectingSink {
+    async fn emit(
+        &mut self,
+        page: SyncPage,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        self.pages.push(page);
+        Ok(())
+    }
+}
+
+/// Outcome of a connector's health check
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HealthCheck {
+    /// Whether the provider accepted the connection's credentials
+    pub token_valid: bool,
+    /// Scopes the provider reports for the credentials, when it reports them
+    pub scopes: Option<Vec<String>>,
+    /// Why the provider rejected the credentials
+    pub message: Option<String>,
+}
+
+impl HealthCheck {
+    /// Credentials accepted, with the scopes the provider reported
+    pub fn valid(scopes: Option<Vec<String>>) -> Self {
+        Self {
+            token_valid: true,
+            scopes,
+            message: None,
+        }
+    }
+
+    /// Credentials rejected by the provider
+    pub fn rejected<S: Into<String>>(message: S) -> Self {
+        Self {
+            token_valid: false,
+            scopes: None,
+            message: Some(message.into()),
+        }
+    }
+}
+
 /// Parameters for webhook handling
 #[derive(Debug, Clone)]
 pub struct WebhookParams {
@@ -368,10 +535,62 @@ pub trait Connector: Send + Sync {
         params: SyncParams,
     ) -> Result<SyncResult, Box<dyn std::error::Error + Send + Sync>>;
 
+    /// Sync while handing each fetched page to `sink`, so a large backfill is
+    /// persisted as it goes instead of held in memory. The returned result
+    /// carries whatever was not emitted. Connectors that fetch a single page
+    /// keep the default, which runs [`Connector::sync`] and emits nothing.
+    async fn sync_streaming(
+        &self,
+        params: SyncParams,
+        _sink: &mut dyn SyncSink,
+    ) -> Result<SyncResult, Box<dyn std::error::Error + Send + Sync>> {
+        self.sync(params).await
+    }
+
     /// Handle an incoming webhook from this provider.
-    /// Returns a collection of signals generated from the webhook.
+    /// Returns a collection of signals generated from the webhook. The executor
+    /// sets each signal's `connection_id` to the webhook job's connection.
     async fn handle_webhook(
         &self,
         params: WebhookParams,
     ) -> Result<Vec<Signal>, Box<dyn std::error::Error + Send + Sync>>;
+
+    /// Check the connection's credentials with a cheap authenticated call.
+    /// `connection` carries its decrypted tokens. Returns `None` when the
+    /// provider offers no such call; errors mean the check could not run.
+    async fn health_check(
+        &self,
+        _connection: &Connection,
+    ) -> Result<Option<HealthCheck>, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plain_cursor_applies_to_every_window() {
+        let cursor = Cursor::from_string("2024-01-15T10:30:00Z");
+        assert!(cursor.windows().is_none());
+        assert_eq!(cursor.window("issues"), Some(cursor.clone()));
+        assert_eq!(cursor.window("pulls"), Some(cursor));
+    }
+
+    #[test]
+    fn test_merged_over_keeps_windows_that_did_not_advance() {
+        let mut previous = CursorWindows::new();
+        previous.set("issues", Cursor::from_string("i1"));
+        previous.set("pulls", Cursor::from_string("p1"));
+        let previous = Cursor::from_windows(previous);
+
+        let mut next = CursorWindows::new();
+        next.set("issues", Cursor::from_string("i2"));
+        let merged = Cursor::from_windows(next).merged_over(Some(&previous));
+
+        assert_eq!(merged.window("issues"), Some(Cursor::from_string("i2")));
+        assert_eq!(merged.window("pulls"), Some(Cursor::from_string("p1")));
+        assert_eq!(merged.window("releases"), None);
+    }
 }
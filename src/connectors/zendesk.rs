@@ -0,0 +1,859 @@
+//! Zendesk connector implementation
+//!
+//! Zendesk Support through the OAuth authorization code flow against the
+//! account's subdomain. Each sync reads the cursor-based incremental ticket
+//! export with ticket metrics sideloaded and resumes from the export's
+//! `after_cursor`. Event-subscribed webhooks are verified in
+//! [`crate::webhook_verification`] before reaching this connector.
+
+use anyhow::anyhow;
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use reqwest::{Client, StatusCode};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tracing::{debug, info};
+use url::Url;
+use uuid::Uuid;
+
+use crate::connectors::{
+    AuthType, Connector, Cursor, ProviderCategory, ProviderMetadata, Registry,
+    trait_::{
+        AuthorizeParams, ExchangeTokenParams, SyncError, SyncParams, SyncResult, WebhookParams,
+    },
+};
+use crate::models::{connection::Model as Connection, signal::Model as Signal};
+use crate::normalization::{
+    SignalKind, normalize_zendesk_ticket_kind, normalize_zendesk_webhook_kind,
+};
+
+/// Provider slug
+pub const ZENDESK_PROVIDER_SLUG: &str = "zendesk";
+
+/// Scopes requested at authorization time
+pub const ZENDESK_SCOPES: &[&str] = &["read"];
+
+/// Tickets requested per export page
+const PAGE_SIZE: u32 = 500;
+
+/// Export pages read in one sync; the rest continues on the next run
+const MAX_PAGES: u32 = 5;
+
+/// How far back the first sync reads
+const INITIAL_LOOKBACK_DAYS: i64 = 30;
+
+/// Zendesk connector
+pub struct ZendeskConnector {
+    client_id: String,
+    client_secret: String,
+    base_url: String,
+    http_client: Client,
+}
+
+#[derive(Debug, Deserialize)]
+struct ZendeskTokenResponse {
+    access_token: String,
+    #[serde(default)]
+    refresh_token: Option<String>,
+    #[serde(default)]
+    expires_in: Option<i64>,
+    #[serde(default)]
+    scope: Option<String>,
+}
+
+/// One page of `/api/v2/incremental/tickets/cursor.json`
+#[derive(Debug, Deserialize)]
+struct TicketExportPage {
+    #[serde(default)]
+    tickets: Vec<serde_json::Value>,
+    #[serde(default)]
+    metric_sets: Vec<serde_json::Value>,
+    #[serde(default)]
+    after_cursor: Option<String>,
+    #[serde(default)]
+    end_of_stream: bool,
+}
+
+impl ZendeskConnector {
+    /// Create a new Zendesk connector for the account at `base_url`
+    pub fn new(client_id: String, client_secret: String, base_url: String) -> Self {
+        Self {
+            client_id,
+            client_secret,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            http_client: crate::egress::client(),
+        }
+    }
+
+    /// Account URL for a subdomain such as `acme`
+    pub fn base_url_for_subdomain(subdomain: &str) -> String {
+        format!("https://{}.zendesk.com", subdomain)
+    }
+
+    fn default_redirect_uri() -> String {
+        match std::env::var("POBLYSH_PROFILE").as_deref() {
+            Ok("local") | Ok("test") | Err(_) => "http://localhost:3000/callback".to_string(),
+            Ok(_) => "https://app.poblysh.com/callback".to_string(),
+        }
+    }
+
+    fn access_token(connection: &Connection) -> Result<String, SyncError> {
+        connection
+            .access_token
+            .clone()
+            .filter(|token| !token.is_empty())
+            .ok_or_else(|| SyncError::unauthorized("Missing Zendesk access token"))
+    }
+
+    /// Call `/oauth/tokens` with the given grant
+    async fn oauth_token(
+        &self,
+        grant: &[(&str, &str)],
+    ) -> Result<ZendeskTokenResponse, anyhow::Error> {
+        let mut form = vec![
+            ("client_id", self.client_id.as_str()),
+            ("client_secret", self.client_secret.as_str()),
+        ];
+        form.extend_from_slice(grant);
+
+        let response = self
+            .http_client
+            .post(format!("{}/oauth/tokens", self.base_url))
+            .form(&form)
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let body: serde_json::Value = response.json().await.unwrap_or_default();
+            return Err(anyhow!(
+                "Zendesk token endpoint returned {}: {}",
+                status,
+                body.get("error")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("unknown_error")
+            ));
+        }
+        Ok(response.json().await?)
+    }
+
+    /// Read one page of the incremental ticket export
+    async fn export_page(
+        &self,
+        access_token: &str,
+        position: &[(&str, String)],
+    ) -> Result<TicketExportPage, SyncError> {
+        let mut query = vec![
+            ("include", "metric_sets".to_string()),
+            ("per_page", PAGE_SIZE.to_string()),
+        ];
+        query.extend_from_slice(position);
+
+        let response = self
+            .http_client
+            .get(format!(
+                "{}/api/v2/incremental/tickets/cursor.json",
+                self.base_url
+            ))
+            .bearer_auth(access_token)
+            .header("Accept", "application/json")
+            .query(&query)
+            .send()
+            .await
+            .map_err(|e| SyncError::transient(format!("Zendesk request failed: {}", e)))?;
+
+        let status = response.status();
+        if status == StatusCode::TOO_MANY_REQUESTS {
+            let retry_after = response
+                .headers()
+                .get("Retry-After")
+                .and_then(|h| h.to_str().ok())
+                .and_then(|s| s.parse::<u64>().ok());
+            return Err(SyncError::rate_limited(retry_after));
+        }
+        if status == StatusCode::UNAUTHORIZED {
+            return Err(SyncError::unauthorized(format!(
+                "Zendesk ticket export failed: {}",
+                status
+            )));
+        }
+        if status.is_server_error() {
+            return Err(SyncError::transient(format!(
+                "Zendesk ticket export failed: {}",
+                status
+            )));
+        }
+        if !status.is_success() {
+            let body: serde_json::Value = response.json().await.unwrap_or_default();
+            return Err(
+                SyncError::permanent(format!("Zendesk ticket export failed: {}", status))
+                    .with_details(body),
+            );
+        }
+
+        response
+            .json()
+            .await
+            .map_err(|e| SyncError::transient(format!("Zendesk returned bad JSON: {}", e)))
+    }
+}
+
+#[async_trait]
+impl Connector for ZendeskConnector {
+    async fn authorize(
+        &self,
+        params: AuthorizeParams,
+    ) -> Result<Url, Box<dyn std::error::Error + Send + Sync>> {
+        info!(
+            tenant_id = %params.tenant_id,
+            "Generating Zendesk OAuth authorization URL"
+        );
+
+        let mut url = Url::parse(&format!("{}/oauth/authorizations/new", self.base_url))?;
+        let redirect_uri = params
+            .redirect_uri
+            .unwrap_or_else(Self::default_redirect_uri);
+        let state = params
+            .state
+            .filter(|s| !s.is_empty())
+            .unwrap_or_else(|| Uuid::new_v4().to_string());
+        url.query_pairs_mut()
+            .append_pair("response_type", "code")
+            .append_pair("client_id", &self.client_id)
+            .append_pair("redirect_uri", &redirect_uri)
+            .append_pair("scope", &ZENDESK_SCOPES.join(" "))
+            .append_pair("state", &state);
+
+        debug!(
+            tenant_id = %params.tenant_id,
+            authorize_url = %url,
+            "Generated Zendesk OAuth authorization URL"
+        );
+        Ok(url)
+    }
+
+    async fn exchange_token(
+        &self,
+        params: ExchangeTokenParams,
+    ) -> Result<Connection, Box<dyn std::error::Error + Send + Sync>> {
+        info!(
+            tenant_id = %params.tenant_id,
+            "Exchanging Zendesk authorization code for tokens"
+        );
+
+        let redirect_uri = params
+            .redirect_uri
+            .unwrap_or_else(Self::default_redirect_uri);
+        let scope = ZENDESK_SCOPES.join(" ");
+        let token = self
+            .oauth_token(&[
+                ("grant_type", "authorization_code"),
+                ("code", params.code.as_str()),
+                ("redirect_uri", redirect_uri.as_str()),
+                ("scope", scope.as_str()),
+            ])
+            .await?;
+
+        // Tokens are issued per account, so the account host identifies the connection
+        let host = Url::parse(&self.base_url)?
+            .host_str()
+            .map(str::to_string)
+            .ok_or_else(|| anyhow!("Zendesk base URL has no host"))?;
+        let now = DateTime::from(Utc::now());
+
+        Ok(Connection {
+            id: Uuid::new_v4(),
+            tenant_id: params.tenant_id,
+            provider_slug: ZENDESK_PROVIDER_SLUG.to_string(),
+            external_id: host.clone(),
+            status: "active".to_string(),
+            display_name: Some(host),
+            access_token_ciphertext: None,
+            refresh_token_ciphertext: None,
+            access_token: Some(token.access_token),
+            refresh_token: token.refresh_token,
+            expires_at: token
+                .expires_in
+                .map(|secs| (Utc::now() + Duration::seconds(secs)).into()),
+            scopes: token.scope.map(|scope| {
+                serde_json::Value::Array(
+                    scope
+                        .split_whitespace()
+                        .map(|s| serde_json::Value::String(s.to_string()))
+                        .collect(),
+                )
+            }),
+            metadata: Some(serde_json::json!({
+                "provider": ZENDESK_PROVIDER_SLUG,
+                "base_url": self.base_url,
+            })),
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    async fn refresh_token(
+        &self,
+        connection: Connection,
+    ) -> Result<Connection, Box<dyn std::error::Error + Send + Sync>> {
+        // Tokens issued without `expires_in` never expire and come without a refresh token
+        let Some(refresh_token) = connection
+            .refresh_token
+            .clone()
+            .filter(|token| !token.is_empty())
+        else {
+            return Err(SyncError::unauthorized("Zendesk connection has no refresh token").into());
+        };
+
+        info!(connection_id = %connection.id, "Refreshing Zendesk token");
+        let token = self
+            .oauth_token(&[
+                ("grant_type", "refresh_token"),
+                ("refresh_token", refresh_token.as_str()),
+            ])
+            .await?;
+
+        Ok(Connection {
+            access_token: Some(token.access_token),
+            refresh_token: token.refresh_token.or(connection.refresh_token.clone()),
+            expires_at: token
+                .expires_in
+                .map(|secs| (Utc::now() + Duration::seconds(secs)).into()),
+            updated_at: DateTime::from(Utc::now()),
+            ..connection
+        })
+    }
+
+    async fn sync(
+        &self,
+        params: SyncParams,
+    ) -> Result<SyncResult, Box<dyn std::error::Error + Send + Sync>> {
+        let connection = &params.connection;
+        info!(
+            tenant_id = %connection.tenant_id,
+            connection_id = %connection.id,
+            has_cursor = %params.cursor.is_some(),
+            "Starting Zendesk sync"
+        );
+
+        let access_token = Self::access_token(connection)?;
+        let mut after_cursor = params
+            .cursor
+            .as_ref()
+            .and_then(|cursor| cursor.as_json().get("after_cursor"))
+            .and_then(|v| v.as_str())
+            .map(str::to_string);
+        // Only the first run has a lower bound; later runs follow the export cursor
+        let start_time = Utc::now() - Duration::days(INITIAL_LOOKBACK_DAYS);
+        let since = after_cursor.is_none().then_some(start_time);
+
+        let mut signals = Vec::new();
+        let mut has_more = true;
+        for _ in 0..MAX_PAGES {
+            let position = match &after_cursor {
+                Some(cursor) => ("cursor", cursor.clone()),
+                None => ("start_time", start_time.timestamp().to_string()),
+            };
+            let page = self.export_page(&access_token, &[position]).await?;
+
+            let metrics: HashMap<String, &serde_json::Value> = page
+                .metric_sets
+                .iter()
+                .filter_map(|metric| Some((ticket_id(metric.get("ticket_id")?)?, metric)))
+                .collect();
+            for ticket in &page.tickets {
+                let Some(id) = ticket.get("id").and_then(ticket_id) else {
+                    continue;
+                };
+                let metric_set = metrics.get(&id).copied();
+                if let Some(kind) = normalize_zendesk_ticket_kind(ticket, metric_set, since) {
+                    signals.push(build_ticket_signal(
+                        connection.tenant_id,
+                        connection.id,
+                        &self.base_url,
+                        kind,
+                        &id,
+                        ticket,
+                        metric_set,
+                    ));
+                }
+            }
+
+            if page.after_cursor.is_some() {
+                after_cursor = page.after_cursor;
+            }
+            if page.end_of_stream || after_cursor.is_none() {
+                has_more = false;
+                break;
+            }
+        }
+
+        debug!(
+            connection_id = %connection.id,
+            signals = signals.len(),
+            has_more,
+            "Zendesk sync completed"
+        );
+
+        Ok(SyncResult {
+            signals,
+            next_cursor: after_cursor
+                .map(|cursor| Cursor::from_json(serde_json::json!({ "after_cursor": cursor }))),
+            has_more,
+        })
+    }
+
+    async fn handle_webhook(
+        &self,
+        params: WebhookParams,
+    ) -> Result<Vec<Signal>, Box<dyn std::error::Error + Send + Sync>> {
+        let payload = &params.payload;
+        let Some(kind) = normalize_zendesk_webhook_kind(payload) else {
+            debug!(
+                tenant_id = %params.tenant_id,
+                event_type = ?payload.get("type"),
+                "Zendesk event ignored"
+            );
+            return Ok(vec![]);
+        };
+        let Some(id) = payload.pointer("/detail/id").and_then(ticket_id) else {
+            return Err(anyhow!("Invalid Zendesk event: missing detail.id").into());
+        };
+
+        info!(
+            tenant_id = %params.tenant_id,
+            signal_kind = %kind,
+            ticket_id = %id,
+            "Zendesk event mapped to signal"
+        );
+
+        let connection_id = Uuid::nil();
+        Ok(vec![build_event_signal(
+            params.tenant_id,
+            connection_id,
+            &self.base_url,
+            kind,
+            &id,
+            payload,
+        )])
+    }
+}
+
+/// Register the Zendesk connector in the registry
+pub fn register_zendesk_connector(registry: &mut Registry, connector: Arc<ZendeskConnector>) {
+    let metadata = ProviderMetadata::new(
+        ZENDESK_PROVIDER_SLUG.to_string(),
+        AuthType::OAuth2,
+        ZENDESK_SCOPES.iter().map(|s| s.to_string()).collect(),
+        true, // webhooks supported
+    )
+    .with_category(ProviderCategory::Crm)
+    .with_description("Sync support ticket activity from Zendesk")
+    .with_icon_url("https://cdn.simpleicons.org/zendesk")
+    .with_docs_url(
+        "https://developer.zendesk.com/documentation/ticketing/working-with-oauth/creating-and-using-oauth-tokens-with-the-api/",
+    )
+    .with_backfill(true)
+    .with_signal_kinds(&[SignalKind::IssueCreated, SignalKind::IssueUpdated, SignalKind::IssueResolved, SignalKind::IssueClosed, SignalKind::IssueReopened, SignalKind::IssueComment]);
+
+    registry.register(connector, metadata);
+}
+
+/// Ticket IDs are numbers in the REST API and strings in webhook events
+fn ticket_id(value: &serde_json::Value) -> Option<String> {
+    match value {
+        serde_json::Value::Number(n) => Some(n.to_string()),
+        serde_json::Value::String(s) if !s.is_empty() => Some(s.clone()),
+        _ => None,
+    }
+}
+
+fn parse_time(value: Option<&serde_json::Value>) -> Option<DateTime<Utc>> {
+    value
+        .and_then(|v| v.as_str())
+        .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+        .map(|dt| dt.with_timezone(&Utc))
+}
+
+/// Dedupe key shared by sync and webhooks for the same ticket change
+///
+/// A ticket is created and closed once, so those kinds are keyed by ticket
+/// alone; other kinds include the time of the change.
+fn ticket_dedupe_key(kind: SignalKind, id: &str, changed_at: Option<DateTime<Utc>>) -> String {
+    match kind {
+        SignalKind::IssueCreated | SignalKind::IssueClosed => {
+            format!("zendesk:{}:{}", kind, id)
+        }
+        _ => format!(
+            "zendesk:{}:{}:{}",
+            kind,
+            id,
+            changed_at
+                .map(|at| at.timestamp_millis().to_string())
+                .unwrap_or_default()
+        ),
+    }
+}
+
+/// Build a signal from a ticket in the incremental export
+fn build_ticket_signal(
+    tenant_id: Uuid,
+    connection_id: Uuid,
+    base_url: &str,
+    kind: SignalKind,
+    id: &str,
+    ticket: &serde_json::Value,
+    metric_set: Option<&serde_json::Value>,
+) -> Signal {
+    let updated_at = parse_time(ticket.get("updated_at"));
+    let changed_at = match kind {
+        SignalKind::IssueCreated => parse_time(ticket.get("created_at")),
+        SignalKind::IssueResolved => parse_time(metric_set.and_then(|m| m.get("solved_at"))),
+        _ => None,
+    }
+    .or(updated_at);
+    let occurred_at = changed_at.unwrap_or_else(Utc::now);
+    let received_at = DateTime::from(Utc::now());
+
+    Signal {
+        id: Uuid::new_v4(),
+        tenant_id,
+        provider_slug: ZENDESK_PROVIDER_SLUG.to_string(),
+        connection_id,
+        kind: kind.as_str().to_string(),
+        occurred_at: occurred_at.into(),
+        received_at,
+        payload: serde_json::json!({
+            "ticket_id": id,
+            "subject": ticket.get("subject"),
+            "status": ticket.get("status"),
+            "priority": ticket.get("priority"),
+            "type": ticket.get("type"),
+            "tags": ticket.get("tags"),
+            "requester_id": ticket.get("requester_id"),
+            "assignee_id": ticket.get("assignee_id"),
+            "group_id": ticket.get("group_id"),
+            "reopens": metric_set.and_then(|m| m.get("reopens")),
+            "url": format!("{}/agent/tickets/{}", base_url, id),
+            "occurred_at": occurred_at.to_rfc3339(),
+        }),
+        dedupe_key: Some(ticket_dedupe_key(kind, id, changed_at)),
+        created_at: received_at,
+        updated_at: received_at,
+    }
+}
+
+/// Build a signal from an event-subscribed webhook delivery
+fn build_event_signal(
+    tenant_id: Uuid,
+    connection_id: Uuid,
+    base_url: &str,
+    kind: SignalKind,
+    id: &str,
+    payload: &serde_json::Value,
+) -> Signal {
+    let detail = &payload["detail"];
+    let changed_at = match kind {
+        SignalKind::IssueCreated => parse_time(detail.get("created_at")),
+        _ => parse_time(detail.get("updated_at")),
+    }
+    .or_else(|| parse_time(payload.get("time")));
+    let occurred_at = changed_at.unwrap_or_else(Utc::now);
+    let received_at = DateTime::from(Utc::now());
+    let comment = payload.pointer("/event/comment");
+    // Comments are keyed by their own ID so each one is kept
+    let dedupe_key = match (kind, comment.and_then(|c| c.get("id")).and_then(ticket_id)) {
+        (SignalKind::IssueComment, Some(comment_id)) => {
+            format!("zendesk:{}:{}:{}", kind, id, comment_id)
+        }
+        _ => ticket_dedupe_key(kind, id, changed_at),
+    };
+
+    Signal {
+        id: Uuid::new_v4(),
+        tenant_id,
+        provider_slug: ZENDESK_PROVIDER_SLUG.to_string(),
+        connection_id,
+        kind: kind.as_str().to_string(),
+        occurred_at: occurred_at.into(),
+        received_at,
+        payload: serde_json::json!({
+            "ticket_id": id,
+            "subject": detail.get("subject"),
+            "status": detail.get("status"),
+            "priority": detail.get("priority"),
+            "type": detail.get("type"),
+            "tags": detail.get("tags"),
+            "requester_id": detail.get("requester_id"),
+            "assignee_id": detail.get("assignee_id"),
+            "group_id": detail.get("group_id"),
+            "event_type": payload.get("type"),
+            "previous_status": payload.pointer("/event/previous"),
+            "comment": comment.map(|c| serde_json::json!({
+                "id": c.get("id"),
+                "body": c.get("body"),
+                "is_public": c.get("is_public"),
+                "author_id": c.pointer("/author/id"),
+            })),
+            "url": format!("{}/agent/tickets/{}", base_url, id),
+            "occurred_at": occurred_at.to_rfc3339(),
+        }),
+        dedupe_key: Some(dedupe_key),
+        created_at: received_at,
+        updated_at: received_at,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{body_string_contains, header, method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn connector(base: &str) -> ZendeskConnector {
+        ZendeskConnector::new(
+            "client-id".to_string(),
+            "client-secret".to_string(),
+            base.to_string(),
+        )
+    }
+
+    fn connection() -> Connection {
+        let now = DateTime::from(Utc::now());
+        Connection {
+            id: Uuid::new_v4(),
+            tenant_id: Uuid::new_v4(),
+            provider_slug: ZENDESK_PROVIDER_SLUG.to_string(),
+            external_id: "acme.zendesk.com".to_string(),
+            status: "active".to_string(),
+            display_name: Some("acme.zendesk.com".to_string()),
+            access_token_ciphertext: None,
+            refresh_token_ciphertext: None,
+            access_token: Some("zd-access".to_string()),
+            refresh_token: None,
+            expires_at: None,
+            scopes: None,
+            metadata: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    fn ticket(id: u64, status: &str, created_at: &str, updated_at: &str) -> serde_json::Value {
+        serde_json::json!({
+            "id": id,
+            "subject": format!("Ticket {}", id),
+            "status": status,
+            "priority": "normal",
+            "created_at": created_at,
+            "updated_at": updated_at
+        })
+    }
+
+    fn webhook(payload: serde_json::Value) -> WebhookParams {
+        WebhookParams {
+            payload,
+            tenant_id: Uuid::new_v4(),
+            db: None,
+            auth_header: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_zendesk_exchange_token_uses_account_host() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/oauth/tokens"))
+            .and(body_string_contains("grant_type=authorization_code"))
+            .and(body_string_contains("scope=read"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "access_token": "zd-access",
+                "refresh_token": "zd-refresh",
+                "expires_in": 172800,
+                "token_type": "bearer",
+                "scope": "read"
+            })))
+            .mount(&server)
+            .await;
+
+        let connector = connector(&server.uri());
+        let url = connector
+            .authorize(AuthorizeParams {
+                tenant_id: Uuid::new_v4(),
+                redirect_uri: None,
+                state: Some("state-1".to_string()),
+                code_challenge: None,
+            })
+            .await
+            .unwrap();
+        assert_eq!(url.path(), "/oauth/authorizations/new");
+        let query: HashMap<_, _> = url.query_pairs().collect();
+        assert_eq!(query["scope"], "read");
+        assert_eq!(query["state"], "state-1");
+
+        let connection = connector
+            .exchange_token(ExchangeTokenParams {
+                code: "code".to_string(),
+                redirect_uri: None,
+                tenant_id: Uuid::new_v4(),
+                code_verifier: None,
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(connection.external_id, "127.0.0.1");
+        assert!(connection.expires_at.is_some());
+        assert_eq!(connection.refresh_token.as_deref(), Some("zd-refresh"));
+        assert_eq!(connection.metadata.unwrap()["base_url"], server.uri());
+    }
+
+    #[tokio::test]
+    async fn test_zendesk_sync_follows_export_cursor() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v2/incremental/tickets/cursor.json"))
+            .and(header("Authorization", "Bearer zd-access"))
+            .and(query_param("cursor", "page-1"))
+            .and(query_param("include", "metric_sets"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "tickets": [
+                    ticket(1, "new", "2024-05-02T10:00:00Z", "2024-05-02T10:00:00Z"),
+                    ticket(2, "open", "2024-04-01T10:00:00Z", "2024-05-02T11:00:00Z"),
+                    ticket(3, "solved", "2024-04-01T10:00:00Z", "2024-05-02T12:00:00Z"),
+                    ticket(4, "closed", "2024-04-01T10:00:00Z", "2024-05-02T13:00:00Z"),
+                    ticket(5, "deleted", "2024-04-01T10:00:00Z", "2024-05-02T14:00:00Z")
+                ],
+                "metric_sets": [
+                    {"ticket_id": 3, "solved_at": "2024-05-02T12:00:00Z", "reopens": 1}
+                ],
+                "after_cursor": "page-2",
+                "end_of_stream": true
+            })))
+            .mount(&server)
+            .await;
+
+        let result = connector(&server.uri())
+            .sync(SyncParams {
+                connection: connection(),
+                cursor: Some(Cursor::from_json(
+                    serde_json::json!({"after_cursor": "page-1"}),
+                )),
+                cancellation: Default::default(),
+            })
+            .await
+            .unwrap();
+
+        let kinds: Vec<_> = result.signals.iter().map(|s| s.kind.as_str()).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                "issue_created",
+                "issue_updated",
+                "issue_resolved",
+                "issue_closed"
+            ]
+        );
+        assert_eq!(result.signals[2].payload["reopens"], 1);
+        assert_eq!(
+            result.signals[3].dedupe_key.as_deref(),
+            Some("zendesk:issue_closed:4")
+        );
+        assert_eq!(
+            result.signals[0].payload["url"],
+            format!("{}/agent/tickets/1", server.uri())
+        );
+        assert!(!result.has_more);
+        assert_eq!(
+            result.next_cursor.unwrap().as_json(),
+            &serde_json::json!({"after_cursor": "page-2"})
+        );
+    }
+
+    #[tokio::test]
+    async fn test_zendesk_sync_rate_limited() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v2/incremental/tickets/cursor.json"))
+            .respond_with(ResponseTemplate::new(429).insert_header("Retry-After", "60"))
+            .mount(&server)
+            .await;
+
+        let err = connector(&server.uri())
+            .sync(SyncParams {
+                connection: connection(),
+                cursor: None,
+                cancellation: Default::default(),
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<SyncError>().unwrap().kind,
+            crate::connectors::SyncErrorKind::RateLimited {
+                retry_after_secs: Some(60)
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_zendesk_webhook_event_mapping() {
+        let connector = connector("https://acme.zendesk.com");
+        let event = |event_type: &str, event: serde_json::Value| {
+            serde_json::json!({
+                "type": format!("zen:event-type:ticket.{}", event_type),
+                "account_id": 123,
+                "id": "event-1",
+                "time": "2024-05-02T12:00:01Z",
+                "subject": "zen:ticket:3",
+                "detail": {
+                    "id": "3",
+                    "subject": "Printer on fire",
+                    "status": "SOLVED",
+                    "created_at": "2024-04-01T10:00:00Z",
+                    "updated_at": "2024-05-02T12:00:00Z"
+                },
+                "event": event
+            })
+        };
+
+        let resolved = connector
+            .handle_webhook(webhook(event(
+                "status_changed",
+                serde_json::json!({"current": "SOLVED", "previous": "OPEN"}),
+            )))
+            .await
+            .unwrap();
+        assert_eq!(resolved[0].kind, "issue_resolved");
+        // Matches the key produced by sync when the solve was the latest update
+        let synced = ticket_dedupe_key(
+            SignalKind::IssueResolved,
+            "3",
+            parse_time(Some(&serde_json::json!("2024-05-02T12:00:00Z"))),
+        );
+        assert_eq!(resolved[0].dedupe_key.as_deref(), Some(synced.as_str()));
+
+        let comment = connector
+            .handle_webhook(webhook(event(
+                "comment_added",
+                serde_json::json!({"comment": {
+                    "id": "99",
+                    "body": "Have you tried turning it off?",
+                    "is_public": true,
+                    "author": {"id": "7"}
+                }}),
+            )))
+            .await
+            .unwrap();
+        assert_eq!(comment[0].kind, "issue_comment");
+        assert_eq!(
+            comment[0].dedupe_key.as_deref(),
+            Some("zendesk:issue_comment:3:99")
+        );
+        assert_eq!(comment[0].payload["comment"]["author_id"], "7");
+
+        let deleted = connector
+            .handle_webhook(webhook(event("soft_deleted", serde_json::json!({}))))
+            .await
+            .unwrap();
+        assert!(deleted.is_empty());
+    }
+}
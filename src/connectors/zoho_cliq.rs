@@ -14,11 +14,11 @@ use url::Url;
 use uuid::Uuid;
 
 use crate::connectors::{
-    AuthType, Connector, ProviderMetadata, Registry,
+    AuthType, Connector, ProviderCategory, ProviderMetadata, Registry,
     trait_::{AuthorizeParams, ExchangeTokenParams, SyncParams, SyncResult, WebhookParams},
 };
 use crate::models::{connection::Model as Connection, signal::Model as Signal};
-use crate::normalization::normalize_zoho_cliq_webhook_kind;
+use crate::normalization::{SignalKind, normalize_zoho_cliq_webhook_kind};
 
 /// Zoho Cliq connector
 #[derive(Debug)]
@@ -215,7 +215,17 @@ pub fn register_zoho_cliq_connector(registry: &mut Registry, connector: Arc<Zoho
         AuthType::Custom("webhook".to_string()),
         vec![], // No OAuth scopes in MVP
         true,   // Webhooks supported
-    );
+    )
+    .with_category(ProviderCategory::Chat)
+    .with_description("Receive channel messages from Zoho Cliq via webhooks")
+    .with_icon_url("https://cdn.simpleicons.org/zoho")
+    .with_docs_url("https://www.zoho.com/cliq/help/restapi/v2/")
+    .with_backfill(false)
+    .with_signal_kinds(&[
+        SignalKind::MessagePosted,
+        SignalKind::MessageUpdated,
+        SignalKind::MessageDeleted,
+    ]);
 
     registry.register(connector, metadata);
 }
@@ -483,6 +493,7 @@ mod tests {
             tenant_id,
             redirect_uri: None,
             state: None,
+            code_challenge: None,
         };
 
         let result = connector.authorize(params).await;
@@ -498,6 +509,7 @@ mod tests {
             code: "test_code".to_string(),
             redirect_uri: None,
             tenant_id,
+            code_verifier: None,
         };
 
         let result = connector.exchange_token(exchange_params).await;
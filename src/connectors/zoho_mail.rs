@@ -19,12 +19,13 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use url::Url;
 
-use crate::connectors::metadata::{AuthType, ProviderMetadata};
+use crate::connectors::metadata::{AuthType, ProviderCategory, ProviderMetadata};
 use crate::connectors::trait_::{
     AuthorizeParams, Connector, Cursor, ExchangeTokenParams, SyncError, SyncParams, SyncResult,
     WebhookParams,
 };
 use crate::models::{connection::Model as Connection, signal::Model as Signal};
+use crate::normalization::SignalKind;
 
 /// Provider slug for Zoho Mail.
 pub const ZOHO_MAIL_PROVIDER_SLUG: &str = "zoho-mail";
@@ -301,7 +302,13 @@ pub fn register_zoho_mail_connector(
         AuthType::OAuth2,
         connector.config.scopes.clone(),
         false, // webhooks=false per MVP
-    );
+    )
+    .with_category(ProviderCategory::Mail)
+    .with_description("Sync email messages from Zoho Mail")
+    .with_icon_url("https://cdn.simpleicons.org/zoho")
+    .with_docs_url("https://www.zoho.com/mail/help/api/")
+    .with_backfill(false)
+    .with_signal_kinds(&[SignalKind::EmailReceived]);
 
     registry.register(connector, metadata);
 }
@@ -419,6 +426,7 @@ mod tests {
             tenant_id: Uuid::new_v4(),
             redirect_uri: Some("https://example.com/callback".to_string()),
             state: None,
+            code_challenge: None,
         };
 
         let url = connector.authorize(params).await.expect("url");
@@ -455,6 +463,8 @@ mod tests {
             display_name: None,
             access_token_ciphertext: None,
             refresh_token_ciphertext: None,
+            access_token: None,
+            refresh_token: None,
             expires_at: None,
             scopes: None,
             metadata: None,
@@ -466,6 +476,7 @@ mod tests {
             .sync(SyncParams {
                 connection,
                 cursor: None,
+                cancellation: Default::default(),
             })
             .await
             .expect("sync result");
@@ -0,0 +1,195 @@
+//! Google Cloud KMS master key provider
+//!
+//! Configured key material is ciphertext produced by `gcloud kms encrypt`
+//! with the key named in `POBLYSH_GCP_KMS_KEY_NAME`, unwrapped with the
+//! `cryptoKeys.decrypt` API. Requests authenticate with an access token for
+//! the workload's service account from the instance metadata server, as on
+//! GKE with Workload Identity, Cloud Run or Compute Engine.
+
+use async_trait::async_trait;
+use base64::{Engine as _, engine::general_purpose};
+use serde::{Deserialize, Serialize};
+
+use super::key_provider::{GCP_KMS_KEY_PROVIDER, KeyProvider};
+use super::{CryptoError, CryptoKey};
+use crate::config::KeyProviderConfig;
+
+/// Default Cloud KMS API endpoint
+pub const GCP_KMS_ENDPOINT: &str = "https://cloudkms.googleapis.com";
+/// Default instance metadata server
+pub const GCP_METADATA_URL: &str = "http://metadata.google.internal";
+
+const TOKEN_PATH: &str = "/computeMetadata/v1/instance/service-accounts/default/token";
+
+/// Unwraps master keys with the Cloud KMS `decrypt` API
+#[derive(Debug, Clone)]
+pub struct GcpKmsKeyProvider {
+    client: reqwest::Client,
+    key_name: String,
+    endpoint: String,
+    metadata_url: String,
+}
+
+#[derive(Deserialize)]
+struct MetadataToken {
+    access_token: String,
+}
+
+#[derive(Serialize)]
+struct DecryptRequest {
+    ciphertext: String,
+}
+
+#[derive(Deserialize)]
+struct DecryptResponse {
+    plaintext: String,
+}
+
+impl GcpKmsKeyProvider {
+    /// Creates a provider for the key resource `projects/*/locations/*/keyRings/*/cryptoKeys/*`
+    pub fn new(key_name: String, endpoint: Option<String>, metadata_url: Option<String>) -> Self {
+        let trim = |url: String| url.trim_end_matches('/').to_string();
+        Self {
+            client: crate::egress::client(),
+            key_name,
+            endpoint: trim(endpoint.unwrap_or_else(|| GCP_KMS_ENDPOINT.to_string())),
+            metadata_url: trim(metadata_url.unwrap_or_else(|| GCP_METADATA_URL.to_string())),
+        }
+    }
+
+    /// Builds the provider from `POBLYSH_GCP_KMS_*` settings
+    pub fn from_config(config: &KeyProviderConfig) -> Result<Self, CryptoError> {
+        let key_name = config
+            .gcp_kms_key_name
+            .clone()
+            .ok_or_else(|| Self::error("POBLYSH_GCP_KMS_KEY_NAME is not set"))?;
+        Ok(Self::new(
+            key_name,
+            config.gcp_kms_endpoint.clone(),
+            config.gcp_metadata_url.clone(),
+        ))
+    }
+
+    fn error(message: impl Into<String>) -> CryptoError {
+        CryptoError::KeyProvider {
+            provider: GCP_KMS_KEY_PROVIDER,
+            message: message.into(),
+        }
+    }
+
+    async fn access_token(&self) -> Result<String, CryptoError> {
+        let response = self
+            .client
+            .get(format!("{}{}", self.metadata_url, TOKEN_PATH))
+            .header("Metadata-Flavor", "Google")
+            .send()
+            .await
+            .map_err(|e| Self::error(format!("metadata server unreachable: {}", e)))?;
+        if !response.status().is_success() {
+            return Err(Self::error(format!(
+                "metadata server returned {}",
+                response.status()
+            )));
+        }
+        let token: MetadataToken = response
+            .json()
+            .await
+            .map_err(|e| Self::error(format!("invalid metadata token response: {}", e)))?;
+        Ok(token.access_token)
+    }
+}
+
+#[async_trait]
+impl KeyProvider for GcpKmsKeyProvider {
+    fn name(&self) -> &'static str {
+        GCP_KMS_KEY_PROVIDER
+    }
+
+    async fn unwrap_master_key(&self, material: &[u8]) -> Result<CryptoKey, CryptoError> {
+        let token = self.access_token().await?;
+        let response = self
+            .client
+            .post(format!("{}/v1/{}:decrypt", self.endpoint, self.key_name))
+            .bearer_auth(token)
+            .json(&DecryptRequest {
+                ciphertext: general_purpose::STANDARD.encode(material),
+            })
+            .send()
+            .await
+            .map_err(|e| Self::error(format!("request failed: {}", e)))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(Self::error(format!(
+                "decrypt returned {}: {}",
+                status, body
+            )));
+        }
+        let decrypted: DecryptResponse = response
+            .json()
+            .await
+            .map_err(|e| Self::error(format!("invalid decrypt response: {}", e)))?;
+        let plaintext = general_purpose::STANDARD
+            .decode(decrypted.plaintext)
+            .map_err(|e| Self::error(format!("invalid decrypt plaintext: {}", e)))?;
+        CryptoKey::new(plaintext)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{body_json, header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    const KEY_NAME: &str = "projects/p/locations/global/keyRings/poblysh/cryptoKeys/master";
+
+    #[tokio::test]
+    async fn test_decrypt_uses_metadata_token() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path(TOKEN_PATH))
+            .and(header("Metadata-Flavor", "Google"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "access_token": "ya29.metadata",
+                "expires_in": 3599,
+                "token_type": "Bearer",
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path(format!("/v1/{}:decrypt", KEY_NAME)))
+            .and(header("authorization", "Bearer ya29.metadata"))
+            .and(body_json(serde_json::json!({
+                "ciphertext": general_purpose::STANDARD.encode(b"wrapped-master-key"),
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "plaintext": general_purpose::STANDARD.encode([7u8; 32]),
+            })))
+            .mount(&server)
+            .await;
+
+        let provider =
+            GcpKmsKeyProvider::new(KEY_NAME.to_string(), Some(server.uri()), Some(server.uri()));
+        let key = provider
+            .unwrap_master_key(b"wrapped-master-key")
+            .await
+            .unwrap();
+        assert_eq!(key.as_bytes(), &[7u8; 32]);
+    }
+
+    #[tokio::test]
+    async fn test_missing_metadata_token_fails() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&server)
+            .await;
+
+        let provider =
+            GcpKmsKeyProvider::new(KEY_NAME.to_string(), Some(server.uri()), Some(server.uri()));
+        let err = provider.unwrap_master_key(b"wrapped").await.unwrap_err();
+        assert!(err.to_string().contains("metadata server returned 404"));
+    }
+}
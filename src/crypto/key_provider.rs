@@ -0,0 +1,146 @@
+//! Master key providers
+//!
+//! The master keys in `POBLYSH_CRYPTO_KEY` and `POBLYSH_CRYPTO_PREVIOUS_KEYS`
+//! are either raw keys (`env`) or key material encrypted by a cloud KMS or a
+//! Vault transit key, which a [`KeyProvider`] unwraps once at startup. With a
+//! KMS or Vault provider the process environment only ever holds ciphertext;
+//! the raw master key exists in memory.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use super::aws_kms::AwsKmsKeyProvider;
+use super::gcp_kms::GcpKmsKeyProvider;
+use super::vault::VaultTransitKeyProvider;
+use super::{CryptoError, CryptoKey, MasterKeyring};
+use crate::config::AppConfig;
+
+/// `POBLYSH_CRYPTO_KEY_PROVIDER` value for raw base64 keys
+pub const ENV_KEY_PROVIDER: &str = "env";
+/// `POBLYSH_CRYPTO_KEY_PROVIDER` value for keys encrypted by AWS KMS
+pub const AWS_KMS_KEY_PROVIDER: &str = "aws-kms";
+/// `POBLYSH_CRYPTO_KEY_PROVIDER` value for keys encrypted by Google Cloud KMS
+pub const GCP_KMS_KEY_PROVIDER: &str = "gcp-kms";
+/// `POBLYSH_CRYPTO_KEY_PROVIDER` value for keys encrypted by a Vault transit key
+pub const VAULT_KEY_PROVIDER: &str = "vault";
+
+/// Supported `POBLYSH_CRYPTO_KEY_PROVIDER` values
+pub const KEY_PROVIDERS: [&str; 4] = [
+    ENV_KEY_PROVIDER,
+    AWS_KMS_KEY_PROVIDER,
+    GCP_KMS_KEY_PROVIDER,
+    VAULT_KEY_PROVIDER,
+];
+
+/// Turns configured key material into a 32-byte master key
+#[async_trait]
+pub trait KeyProvider: Send + Sync {
+    /// Provider name used in logs and errors
+    fn name(&self) -> &'static str;
+
+    /// Returns the master key for one configured key entry
+    async fn unwrap_master_key(&self, material: &[u8]) -> Result<CryptoKey, CryptoError>;
+
+    /// Returns key material that this provider unwraps back into `key`
+    async fn wrap_master_key(&self, _key: &CryptoKey) -> Result<Vec<u8>, CryptoError> {
+        Err(CryptoError::KeyProvider {
+            provider: self.name(),
+            message: "wrapping master keys is not supported".to_string(),
+        })
+    }
+}
+
+/// Key material is the raw master key itself
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EnvKeyProvider;
+
+#[async_trait]
+impl KeyProvider for EnvKeyProvider {
+    fn name(&self) -> &'static str {
+        ENV_KEY_PROVIDER
+    }
+
+    async fn unwrap_master_key(&self, material: &[u8]) -> Result<CryptoKey, CryptoError> {
+        CryptoKey::new(material.to_vec())
+    }
+
+    async fn wrap_master_key(&self, key: &CryptoKey) -> Result<Vec<u8>, CryptoError> {
+        Ok(key.as_bytes().to_vec())
+    }
+}
+
+/// Builds the provider selected by `POBLYSH_CRYPTO_KEY_PROVIDER`
+pub fn key_provider_from_config(config: &AppConfig) -> Result<Arc<dyn KeyProvider>, CryptoError> {
+    let settings = &config.crypto_key_provider;
+    match settings.provider.as_str() {
+        ENV_KEY_PROVIDER => Ok(Arc::new(EnvKeyProvider)),
+        AWS_KMS_KEY_PROVIDER => Ok(Arc::new(AwsKmsKeyProvider::from_config(settings)?)),
+        GCP_KMS_KEY_PROVIDER => Ok(Arc::new(GcpKmsKeyProvider::from_config(settings)?)),
+        VAULT_KEY_PROVIDER => Ok(Arc::new(VaultTransitKeyProvider::from_config(settings)?)),
+        other => Err(CryptoError::KeyProvider {
+            provider: "config",
+            message: format!("unknown key provider '{}'", other),
+        }),
+    }
+}
+
+/// Loads every configured master key version through the configured provider
+pub async fn load_master_keyring(config: &AppConfig) -> Result<MasterKeyring, CryptoError> {
+    let provider = key_provider_from_config(config)?;
+    load_master_keyring_with(provider.as_ref(), config).await
+}
+
+/// Loads every configured master key version through `provider`
+pub async fn load_master_keyring_with(
+    provider: &dyn KeyProvider,
+    config: &AppConfig,
+) -> Result<MasterKeyring, CryptoError> {
+    let material = config
+        .crypto_key
+        .as_deref()
+        .ok_or(CryptoError::KeyVersionUnavailable(
+            config.crypto_key_version,
+        ))?;
+    let mut keyring = MasterKeyring::new(
+        config.crypto_key_version,
+        provider.unwrap_master_key(material).await?,
+    )?;
+    for (version, material) in &config.crypto_previous_keys {
+        let key = provider.unwrap_master_key(material).await?;
+        keyring = keyring.with_previous(*version, key)?;
+    }
+    tracing::info!(
+        provider = provider.name(),
+        current_version = keyring.current_version(),
+        versions = config.crypto_previous_keys.len() + 1,
+        "Loaded master keys"
+    );
+    Ok(keyring)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_env_provider_loads_raw_keys() {
+        let config = AppConfig {
+            crypto_key: Some(vec![2u8; 32]),
+            crypto_key_version: 2,
+            crypto_previous_keys: vec![(1, vec![1u8; 32])],
+            ..Default::default()
+        };
+
+        let keyring = load_master_keyring(&config).await.unwrap();
+        assert_eq!(keyring.current_version(), 2);
+        assert_eq!(keyring.current().as_bytes(), &[2u8; 32]);
+        assert_eq!(keyring.get(1).unwrap().as_bytes(), &[1u8; 32]);
+
+        let short = AppConfig {
+            crypto_key: Some(vec![2u8; 16]),
+            ..Default::default()
+        };
+        assert!(load_master_keyring(&short).await.is_err());
+    }
+}
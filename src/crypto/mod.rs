@@ -0,0 +1,829 @@
+//! Token encryption module using AES-256-GCM
+//!
+//! This module provides encryption and decryption utilities for access tokens
+//! and refresh tokens stored in the database, using AES-256-GCM with additional
+//! authenticated data (AAD) for context binding.
+//!
+//! Tokens are encrypted with a per-tenant data-encryption key (DEK). Each DEK is
+//! stored wrapped by the master key, so deleting it crypto-shreds that tenant's
+//! tokens without touching anyone else's. Payloads written before tenant keys
+//! existed are encrypted with the master key directly and remain readable.
+//!
+//! New token payloads use envelope encryption: every ciphertext gets its own
+//! random record key, stored alongside it wrapped by the tenant key. The master
+//! key is versioned through a [`MasterKeyring`]; tenant keys are always wrapped
+//! with the newest version, while older versions stay loaded so keys wrapped
+//! before a rotation can still be unwrapped. Master keys come from the
+//! configured [`KeyProvider`]: raw keys in the environment, or key material
+//! encrypted by a cloud KMS or a Vault transit key.
+
+#![allow(deprecated)]
+
+pub mod aws_kms;
+pub mod gcp_kms;
+pub mod key_provider;
+pub mod vault;
+
+pub use key_provider::{
+    KeyProvider, key_provider_from_config, load_master_keyring, load_master_keyring_with,
+};
+
+use aes_gcm::{
+    Aes256Gcm, Key, Nonce,
+    aead::{Aead, AeadCore, KeyInit, OsRng, Payload},
+};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use thiserror::Error;
+use uuid::Uuid;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use crate::models::connection::Model as ConnectionModel;
+
+const VERSION_ENCRYPTED: u8 = 0x01;
+const VERSION_TENANT_ENCRYPTED: u8 = 0x02;
+const VERSION_ENVELOPE_ENCRYPTED: u8 = 0x03;
+const VERSION_FIELD_LEN: usize = 1;
+const NONCE_LEN: usize = 12;
+const TAG_LEN: usize = 16;
+const KEY_LEN: usize = 32;
+const MIN_ENCRYPTED_LEN: usize = VERSION_FIELD_LEN + NONCE_LEN + TAG_LEN;
+const WRAPPED_RECORD_KEY_LEN: usize = NONCE_LEN + KEY_LEN + TAG_LEN;
+const MIN_ENVELOPE_LEN: usize = MIN_ENCRYPTED_LEN + WRAPPED_RECORD_KEY_LEN;
+
+/// Crypto error types
+#[derive(Debug, Error)]
+pub enum CryptoError {
+    #[error("encryption failed: {0}")]
+    EncryptionFailed(String),
+    #[error("decryption failed: {0}")]
+    DecryptionFailed(String),
+    #[error("invalid ciphertext format")]
+    InvalidFormat,
+    #[error("empty ciphertext")]
+    EmptyCiphertext,
+    #[error("tenant data key unavailable")]
+    TenantKeyUnavailable,
+    #[error("master key version {0} is not configured")]
+    KeyVersionUnavailable(i32),
+    #[error("invalid master key version {0}")]
+    InvalidKeyVersion(i32),
+    #[error("key provider {provider} failed: {message}")]
+    KeyProvider {
+        provider: &'static str,
+        message: String,
+    },
+}
+
+/// Secure wrapper for encryption keys with zeroization
+#[derive(Debug, Clone, Zeroize, ZeroizeOnDrop)]
+pub struct ZeroizingKey(Vec<u8>);
+
+/// Type alias for crypto keys
+pub type CryptoKey = ZeroizingKey;
+
+impl CryptoKey {
+    /// Create a new crypto key from bytes
+    pub fn new(bytes: Vec<u8>) -> Result<Self, CryptoError> {
+        if bytes.len() != KEY_LEN {
+            return Err(CryptoError::EncryptionFailed(
+                "Invalid key length: expected 32 bytes".to_string(),
+            ));
+        }
+        Ok(ZeroizingKey(bytes))
+    }
+
+    /// Generate a random key, used for per-tenant data keys
+    pub fn generate() -> Self {
+        ZeroizingKey(Aes256Gcm::generate_key(&mut OsRng).to_vec())
+    }
+
+    /// Get the key as bytes
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+/// Encrypt bytes using AES-256-GCM with the master key
+pub fn encrypt_bytes(
+    key: &CryptoKey,
+    aad: &[u8],
+    plaintext: &[u8],
+) -> Result<Vec<u8>, CryptoError> {
+    seal(VERSION_ENCRYPTED, key, aad, plaintext)
+}
+
+/// Encrypt bytes using AES-256-GCM with a tenant data key
+pub fn encrypt_bytes_with_tenant_key(
+    key: &CryptoKey,
+    aad: &[u8],
+    plaintext: &[u8],
+) -> Result<Vec<u8>, CryptoError> {
+    seal(VERSION_TENANT_ENCRYPTED, key, aad, plaintext)
+}
+
+/// Encrypt bytes with a fresh record key that is wrapped by a tenant data key
+pub fn encrypt_bytes_with_record_key(
+    tenant_key: &CryptoKey,
+    aad: &[u8],
+    plaintext: &[u8],
+) -> Result<Vec<u8>, CryptoError> {
+    let record_key = CryptoKey::generate();
+    let wrapped = seal_raw(tenant_key, &record_key_aad(aad), record_key.as_bytes())?;
+    let body = seal_raw(&record_key, aad, plaintext)?;
+
+    let mut result = Vec::with_capacity(VERSION_FIELD_LEN + wrapped.len() + body.len());
+    result.push(VERSION_ENVELOPE_ENCRYPTED);
+    result.extend_from_slice(&wrapped);
+    result.extend_from_slice(&body);
+    Ok(result)
+}
+
+fn seal(
+    version: u8,
+    key: &CryptoKey,
+    aad: &[u8],
+    plaintext: &[u8],
+) -> Result<Vec<u8>, CryptoError> {
+    let sealed = seal_raw(key, aad, plaintext)?;
+    let mut result = Vec::with_capacity(VERSION_FIELD_LEN + sealed.len());
+    result.push(version); // Version byte records which key encrypted the payload
+    result.extend_from_slice(&sealed);
+    Ok(result)
+}
+
+// Encrypts to `nonce || ciphertext || tag`
+fn seal_raw(key: &CryptoKey, aad: &[u8], plaintext: &[u8]) -> Result<Vec<u8>, CryptoError> {
+    // Create cipher
+    let cipher_key = Key::<Aes256Gcm>::from_slice(key.as_bytes());
+    let cipher = Aes256Gcm::new(cipher_key);
+
+    // Generate random nonce
+    let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+
+    // Encrypt with AAD
+    let mut ciphertext = cipher
+        .encrypt(
+            &nonce,
+            Payload {
+                msg: plaintext,
+                aad,
+            },
+        )
+        .map_err(|e| CryptoError::EncryptionFailed(e.to_string()))?;
+
+    let mut result = Vec::with_capacity(NONCE_LEN + ciphertext.len());
+    result.extend_from_slice(&nonce);
+    result.append(&mut ciphertext);
+
+    Ok(result)
+}
+
+// Decrypts `nonce || ciphertext || tag` produced by `seal_raw`
+fn open_raw(key: &CryptoKey, aad: &[u8], sealed: &[u8]) -> Result<Vec<u8>, CryptoError> {
+    if sealed.len() < NONCE_LEN + TAG_LEN {
+        return Err(CryptoError::InvalidFormat);
+    }
+    let nonce = Nonce::from_slice(&sealed[..NONCE_LEN]);
+
+    // Create cipher
+    let cipher_key = Key::<Aes256Gcm>::from_slice(key.as_bytes());
+    let cipher = Aes256Gcm::new(cipher_key);
+
+    // Decrypt with AAD
+    cipher
+        .decrypt(
+            nonce,
+            Payload {
+                msg: &sealed[NONCE_LEN..],
+                aad,
+            },
+        )
+        .map_err(|e| CryptoError::DecryptionFailed(e.to_string()))
+}
+
+// Binds a record key to the ciphertext it protects
+fn record_key_aad(aad: &[u8]) -> Vec<u8> {
+    [b"record-dek|".as_slice(), aad].concat()
+}
+
+/// Decrypt bytes using AES-256-GCM; `key` must match the payload's key scope.
+///
+/// Envelope payloads take the tenant key that wrapped their record key.
+pub fn decrypt_bytes(
+    key: &CryptoKey,
+    aad: &[u8],
+    ciphertext: &[u8],
+) -> Result<Vec<u8>, CryptoError> {
+    if ciphertext.is_empty() {
+        return Err(CryptoError::EmptyCiphertext);
+    }
+
+    // Detect legacy plaintext payloads (no version marker)
+    if !matches!(
+        ciphertext[0],
+        VERSION_ENCRYPTED | VERSION_TENANT_ENCRYPTED | VERSION_ENVELOPE_ENCRYPTED
+    ) {
+        return Ok(ciphertext.to_vec());
+    }
+
+    // Validate minimum length (version + nonce + tag, plus the wrapped record key)
+    if !is_encrypted_payload(ciphertext) {
+        return Err(CryptoError::InvalidFormat);
+    }
+
+    let sealed = &ciphertext[VERSION_FIELD_LEN..];
+    if ciphertext[0] != VERSION_ENVELOPE_ENCRYPTED {
+        return open_raw(key, aad, sealed);
+    }
+
+    let (wrapped, body) = sealed.split_at(WRAPPED_RECORD_KEY_LEN);
+    let record_key = CryptoKey::new(open_raw(key, &record_key_aad(aad), wrapped)?)?;
+    open_raw(&record_key, aad, body)
+}
+
+/// Determine if a payload is using the encrypted format
+pub fn is_encrypted_payload(ciphertext: &[u8]) -> bool {
+    match ciphertext.first() {
+        Some(&VERSION_ENCRYPTED | &VERSION_TENANT_ENCRYPTED) => {
+            ciphertext.len() >= MIN_ENCRYPTED_LEN
+        }
+        Some(&VERSION_ENVELOPE_ENCRYPTED) => ciphertext.len() >= MIN_ENVELOPE_LEN,
+        _ => false,
+    }
+}
+
+/// Determine if a payload was encrypted with a tenant data key, directly or through a record key
+pub fn uses_tenant_key(ciphertext: &[u8]) -> bool {
+    is_encrypted_payload(ciphertext)
+        && matches!(
+            ciphertext[0],
+            VERSION_TENANT_ENCRYPTED | VERSION_ENVELOPE_ENCRYPTED
+        )
+}
+
+/// Determine if a payload uses a per-record key, the format new tokens are written in
+pub fn uses_record_key(ciphertext: &[u8]) -> bool {
+    is_encrypted_payload(ciphertext) && ciphertext[0] == VERSION_ENVELOPE_ENCRYPTED
+}
+
+/// Wrap a tenant data key with the master key for storage
+pub fn wrap_tenant_key(
+    master: &CryptoKey,
+    tenant_id: Uuid,
+    tenant_key: &CryptoKey,
+) -> Result<Vec<u8>, CryptoError> {
+    encrypt_bytes(
+        master,
+        tenant_key_aad(tenant_id).as_bytes(),
+        tenant_key.as_bytes(),
+    )
+}
+
+/// Unwrap a stored tenant data key with the master key
+pub fn unwrap_tenant_key(
+    master: &CryptoKey,
+    tenant_id: Uuid,
+    wrapped: &[u8],
+) -> Result<CryptoKey, CryptoError> {
+    if !is_encrypted_payload(wrapped) {
+        return Err(CryptoError::InvalidFormat);
+    }
+    let bytes = decrypt_bytes(master, tenant_key_aad(tenant_id).as_bytes(), wrapped)?;
+    CryptoKey::new(bytes)
+}
+
+/// Versioned master keys.
+///
+/// Each version is a 32-byte key. Tenant data keys are wrapped with the current
+/// version and record which version wrapped them; previous versions are only
+/// used to unwrap keys stored before the master key was rotated.
+#[derive(Clone)]
+pub struct MasterKeyring {
+    current_version: i32,
+    keys: Arc<BTreeMap<i32, CryptoKey>>,
+}
+
+impl MasterKeyring {
+    /// Creates a keyring whose current key is `key` at `version`
+    pub fn new(version: i32, key: CryptoKey) -> Result<Self, CryptoError> {
+        if version < 1 {
+            return Err(CryptoError::InvalidKeyVersion(version));
+        }
+        Ok(Self {
+            current_version: version,
+            keys: Arc::new(BTreeMap::from([(version, key)])),
+        })
+    }
+
+    /// Adds a retired version that can still unwrap keys but never wraps new ones
+    pub fn with_previous(mut self, version: i32, key: CryptoKey) -> Result<Self, CryptoError> {
+        if version < 1 || version >= self.current_version || self.keys.contains_key(&version) {
+            return Err(CryptoError::InvalidKeyVersion(version));
+        }
+        Arc::make_mut(&mut self.keys).insert(version, key);
+        Ok(self)
+    }
+
+    /// Makes `key` the current version, keeping every loaded version for unwrapping.
+    ///
+    /// `version` must be above all loaded versions, or already loaded with the same key.
+    pub fn rotate(&self, version: i32, key: CryptoKey) -> Result<Self, CryptoError> {
+        if let Some(existing) = self.get(version) {
+            if existing.as_bytes() != key.as_bytes() || version != self.current_version {
+                return Err(CryptoError::InvalidKeyVersion(version));
+            }
+            return Ok(self.clone());
+        }
+        let mut keyring = Self::new(version, key)?;
+        for (previous, key) in self.newest_first() {
+            keyring = keyring.with_previous(previous, key.clone())?;
+        }
+        Ok(keyring)
+    }
+
+    /// Version that wraps new tenant data keys
+    pub fn current_version(&self) -> i32 {
+        self.current_version
+    }
+
+    /// Key for the current version
+    pub fn current(&self) -> &CryptoKey {
+        &self.keys[&self.current_version]
+    }
+
+    /// Key for a specific version, if it is loaded
+    pub fn get(&self, version: i32) -> Option<&CryptoKey> {
+        self.keys.get(&version)
+    }
+
+    /// Loaded versions with their keys, newest first
+    pub fn newest_first(&self) -> impl Iterator<Item = (i32, &CryptoKey)> {
+        self.keys.iter().rev().map(|(version, key)| (*version, key))
+    }
+
+    /// Wraps a tenant data key with the current version, returning that version and the wrapped key
+    pub fn wrap_tenant_key(
+        &self,
+        tenant_id: Uuid,
+        tenant_key: &CryptoKey,
+    ) -> Result<(i32, Vec<u8>), CryptoError> {
+        let wrapped = wrap_tenant_key(self.current(), tenant_id, tenant_key)?;
+        Ok((self.current_version, wrapped))
+    }
+
+    /// Unwraps a tenant data key with the version that wrapped it
+    pub fn unwrap_tenant_key(
+        &self,
+        version: i32,
+        tenant_id: Uuid,
+        wrapped: &[u8],
+    ) -> Result<CryptoKey, CryptoError> {
+        let master = self
+            .get(version)
+            .ok_or(CryptoError::KeyVersionUnavailable(version))?;
+        unwrap_tenant_key(master, tenant_id, wrapped)
+    }
+}
+
+// Key material never appears in logs; only the loaded versions are shown
+impl std::fmt::Debug for MasterKeyring {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MasterKeyring")
+            .field("current_version", &self.current_version)
+            .field("versions", &self.keys.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl From<CryptoKey> for MasterKeyring {
+    /// A single key at version 1, the version every key had before rotation
+    fn from(key: CryptoKey) -> Self {
+        Self {
+            current_version: 1,
+            keys: Arc::new(BTreeMap::from([(1, key)])),
+        }
+    }
+}
+
+// Binds a wrapped key to its tenant so rows cannot be swapped between tenants
+fn tenant_key_aad(tenant_id: Uuid) -> String {
+    format!("tenant-dek|{}", tenant_id)
+}
+
+/// Type alias for encrypted token result
+type EncryptedTokens = Result<(Option<Vec<u8>>, Option<Vec<u8>>), CryptoError>;
+
+/// Encrypt tokens for a connection model with the master key
+pub fn encrypt_connection_tokens(
+    key: &CryptoKey,
+    connection: &ConnectionModel,
+    access_token: Option<&str>,
+    refresh_token: Option<&str>,
+) -> EncryptedTokens {
+    seal_connection_tokens(connection, access_token, refresh_token, |aad, token| {
+        encrypt_bytes(key, aad, token)
+    })
+}
+
+/// Encrypt tokens for a connection model with per-record keys wrapped by its tenant's data key
+pub fn encrypt_connection_tokens_with_tenant_key(
+    tenant_key: &CryptoKey,
+    connection: &ConnectionModel,
+    access_token: Option<&str>,
+    refresh_token: Option<&str>,
+) -> EncryptedTokens {
+    seal_connection_tokens(connection, access_token, refresh_token, |aad, token| {
+        encrypt_bytes_with_record_key(tenant_key, aad, token)
+    })
+}
+
+fn seal_connection_tokens(
+    connection: &ConnectionModel,
+    access_token: Option<&str>,
+    refresh_token: Option<&str>,
+    seal_token: impl Fn(&[u8], &[u8]) -> Result<Vec<u8>, CryptoError>,
+) -> EncryptedTokens {
+    let aad = format!(
+        "{}|{}|{}",
+        connection.tenant_id, connection.provider_slug, connection.external_id
+    );
+
+    let encrypted_access_token = access_token
+        .map(|token| seal_token(aad.as_bytes(), token.as_bytes()))
+        .transpose()?;
+
+    let encrypted_refresh_token = refresh_token
+        .map(|token| seal_token(aad.as_bytes(), token.as_bytes()))
+        .transpose()?;
+
+    Ok((encrypted_access_token, encrypted_refresh_token))
+}
+
+/// Type alias for decrypted token result
+type DecryptedTokens = Result<(Option<String>, Option<String>), CryptoError>;
+
+/// Decrypt tokens for a connection model encrypted with the master key
+pub fn decrypt_connection_tokens(key: &CryptoKey, connection: &ConnectionModel) -> DecryptedTokens {
+    decrypt_connection_tokens_with_tenant_key(key, None, connection)
+}
+
+/// Decrypt tokens for a connection model, choosing the master or tenant key per payload.
+///
+/// Fails with [`CryptoError::TenantKeyUnavailable`] when a payload needs a tenant
+/// key that was not supplied, e.g. because it has been shredded.
+pub fn decrypt_connection_tokens_with_tenant_key(
+    master: &CryptoKey,
+    tenant_key: Option<&CryptoKey>,
+    connection: &ConnectionModel,
+) -> DecryptedTokens {
+    open_connection_tokens(&[master], tenant_key, connection)
+}
+
+/// Decrypt tokens for a connection model, trying every loaded master key version
+/// for payloads that were encrypted with the master key directly
+pub fn decrypt_connection_tokens_with_keyring(
+    keyring: &MasterKeyring,
+    tenant_key: Option<&CryptoKey>,
+    connection: &ConnectionModel,
+) -> DecryptedTokens {
+    let masters: Vec<&CryptoKey> = keyring.newest_first().map(|(_, key)| key).collect();
+    open_connection_tokens(&masters, tenant_key, connection)
+}
+
+fn open_connection_tokens(
+    masters: &[&CryptoKey],
+    tenant_key: Option<&CryptoKey>,
+    connection: &ConnectionModel,
+) -> DecryptedTokens {
+    let aad = format!(
+        "{}|{}|{}",
+        connection.tenant_id, connection.provider_slug, connection.external_id
+    );
+
+    let decrypt = |token: Option<&Vec<u8>>| -> Result<Option<String>, CryptoError> {
+        let Some(token) = token else {
+            return Ok(None);
+        };
+        let bytes = if !is_encrypted_payload(token) {
+            token.clone()
+        } else if uses_tenant_key(token) {
+            let key = tenant_key.ok_or(CryptoError::TenantKeyUnavailable)?;
+            decrypt_bytes(key, aad.as_bytes(), token)?
+        } else {
+            decrypt_with_any(masters, aad.as_bytes(), token)?
+        };
+        String::from_utf8(bytes)
+            .map(Some)
+            .map_err(|e| CryptoError::DecryptionFailed(format!("Invalid UTF-8: {}", e)))
+    };
+
+    let decrypted_access_token = decrypt(connection.access_token_ciphertext.as_ref())?;
+    let decrypted_refresh_token = decrypt(connection.refresh_token_ciphertext.as_ref())?;
+
+    Ok((decrypted_access_token, decrypted_refresh_token))
+}
+
+// Master-key payloads do not record their key version, so each loaded version is tried
+fn decrypt_with_any(
+    keys: &[&CryptoKey],
+    aad: &[u8],
+    ciphertext: &[u8],
+) -> Result<Vec<u8>, CryptoError> {
+    let mut last_error = CryptoError::KeyVersionUnavailable(0);
+    for key in keys {
+        match decrypt_bytes(key, aad, ciphertext) {
+            Ok(plaintext) => return Ok(plaintext),
+            Err(err) => last_error = err,
+        }
+    }
+    Err(last_error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    fn test_key() -> CryptoKey {
+        CryptoKey::new(vec![0u8; 32]).expect("valid test key")
+    }
+
+    fn sample_connection(
+        access_token_ciphertext: Option<Vec<u8>>,
+        refresh_token_ciphertext: Option<Vec<u8>>,
+    ) -> ConnectionModel {
+        ConnectionModel {
+            id: Uuid::new_v4(),
+            tenant_id: Uuid::new_v4(),
+            provider_slug: "test-provider".to_string(),
+            external_id: "external-123".to_string(),
+            status: "active".to_string(),
+            display_name: None,
+            access_token_ciphertext,
+            refresh_token_ciphertext,
+            access_token: None,
+            refresh_token: None,
+            expires_at: None,
+            scopes: None,
+            metadata: None,
+            created_at: Utc::now().into(),
+            updated_at: Utc::now().into(),
+        }
+    }
+
+    #[test]
+    fn test_encrypt_decrypt_roundtrip() {
+        let key = test_key();
+        let aad = b"test-aad";
+        let plaintext = b"secret message";
+
+        let encrypted = encrypt_bytes(&key, aad, plaintext).expect("encryption succeeds");
+        let decrypted = decrypt_bytes(&key, aad, &encrypted).expect("decryption succeeds");
+
+        assert_eq!(decrypted, plaintext);
+    }
+
+    #[test]
+    fn test_different_aad_fails() {
+        let key = test_key();
+        let aad1 = b"test-aad-1";
+        let aad2 = b"test-aad-2";
+        let plaintext = b"secret message";
+
+        let encrypted = encrypt_bytes(&key, aad1, plaintext).expect("encryption succeeds");
+        let result = decrypt_bytes(&key, aad2, &encrypted);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_modified_ciphertext_fails() {
+        let key = test_key();
+        let aad = b"test-aad";
+        let plaintext = b"secret message";
+
+        let mut encrypted = encrypt_bytes(&key, aad, plaintext).expect("encryption succeeds");
+        // Modify a byte in the ciphertext
+        encrypted[13] ^= 0x01;
+
+        let result = decrypt_bytes(&key, aad, &encrypted);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_empty_plaintext_works() {
+        let key = test_key();
+        let aad = b"test-aad";
+        let plaintext = b"";
+
+        let encrypted = encrypt_bytes(&key, aad, plaintext).expect("encryption succeeds");
+        let decrypted = decrypt_bytes(&key, aad, &encrypted).expect("decryption succeeds");
+
+        assert_eq!(decrypted, plaintext);
+    }
+
+    #[test]
+    fn test_nonce_uniqueness() {
+        let key = test_key();
+        let aad = b"test-aad";
+        let plaintext = b"secret message";
+
+        let encrypted1 = encrypt_bytes(&key, aad, plaintext).expect("encryption succeeds");
+        let encrypted2 = encrypt_bytes(&key, aad, plaintext).expect("encryption succeeds");
+
+        // Nonces (bytes 1-13) should be different
+        assert_ne!(&encrypted1[1..13], &encrypted2[1..13]);
+        // But both should decrypt correctly
+        let decrypted1 = decrypt_bytes(&key, aad, &encrypted1).expect("decryption succeeds");
+        let decrypted2 = decrypt_bytes(&key, aad, &encrypted2).expect("decryption succeeds");
+        assert_eq!(decrypted1, plaintext);
+        assert_eq!(decrypted2, plaintext);
+    }
+
+    #[test]
+    fn test_legacy_token_passthrough() {
+        let key = test_key();
+        let aad = b"test-aad";
+        let legacy_ciphertext = b"legacy-token".to_vec(); // No version marker
+
+        let result =
+            decrypt_bytes(&key, aad, &legacy_ciphertext).expect("legacy plaintext is returned");
+        assert_eq!(result, legacy_ciphertext);
+    }
+
+    #[test]
+    fn test_is_encrypted_payload_detection() {
+        let key = test_key();
+        let aad = b"test-aad";
+        let encrypted = encrypt_bytes(&key, aad, b"secret").expect("encryption succeeds");
+
+        assert!(is_encrypted_payload(&encrypted));
+        assert!(!is_encrypted_payload(b"legacy"));
+    }
+
+    #[test]
+    fn test_decrypt_connection_tokens_handles_legacy_plaintext() {
+        let key = test_key();
+        let mut connection = sample_connection(Some(b"legacy-access".to_vec()), None);
+        let aad = format!(
+            "{}|{}|{}",
+            connection.tenant_id, connection.provider_slug, connection.external_id
+        );
+
+        let refresh_ciphertext =
+            encrypt_bytes(&key, aad.as_bytes(), b"refresh-token").expect("encryption succeeds");
+        connection.refresh_token_ciphertext = Some(refresh_ciphertext.clone());
+
+        let (access, refresh) =
+            decrypt_connection_tokens(&key, &connection).expect("decryption succeeds");
+
+        assert_eq!(access.as_deref(), Some("legacy-access"));
+        assert_eq!(refresh.as_deref(), Some("refresh-token"));
+        assert!(is_encrypted_payload(&refresh_ciphertext));
+        assert!(!is_encrypted_payload(b"legacy-access"));
+    }
+
+    #[test]
+    fn test_non_versioned_payload_passthrough() {
+        let key = test_key();
+        let aad = b"test-aad";
+        let invalid_ciphertext = vec![0xFF, 0x01, 0x02, 0x03]; // No 0x01 version marker
+
+        let result = decrypt_bytes(&key, aad, &invalid_ciphertext)
+            .expect("non-versioned payload returned as plaintext");
+        assert_eq!(result, invalid_ciphertext);
+    }
+
+    #[test]
+    fn test_invalid_key_length_rejected() {
+        let result = CryptoKey::new(vec![0u8; 16]); // Too short
+        assert!(result.is_err());
+
+        let result = CryptoKey::new(vec![0u8; 64]); // Too long
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_tenant_key_wrap_and_decrypt() {
+        let master = test_key();
+        let tenant_key = CryptoKey::generate();
+        let mut connection = sample_connection(None, None);
+
+        let wrapped =
+            wrap_tenant_key(&master, connection.tenant_id, &tenant_key).expect("wrapping succeeds");
+        assert!(unwrap_tenant_key(&master, Uuid::new_v4(), &wrapped).is_err());
+        let unwrapped = unwrap_tenant_key(&master, connection.tenant_id, &wrapped)
+            .expect("unwrapping succeeds");
+        assert_eq!(unwrapped.as_bytes(), tenant_key.as_bytes());
+
+        let (access, _) = encrypt_connection_tokens_with_tenant_key(
+            &unwrapped,
+            &connection,
+            Some("tenant-access"),
+            None,
+        )
+        .expect("encryption succeeds");
+        let access = access.expect("encrypted access token");
+        assert!(uses_tenant_key(&access));
+        connection.access_token_ciphertext = Some(access);
+
+        let (access, _) =
+            decrypt_connection_tokens_with_tenant_key(&master, Some(&tenant_key), &connection)
+                .expect("decryption succeeds");
+        assert_eq!(access.as_deref(), Some("tenant-access"));
+
+        // Without the tenant key (e.g. after shredding) the master key cannot read it
+        assert!(matches!(
+            decrypt_connection_tokens(&master, &connection),
+            Err(CryptoError::TenantKeyUnavailable)
+        ));
+        let other_tenant_key = CryptoKey::generate();
+        assert!(
+            decrypt_connection_tokens_with_tenant_key(
+                &master,
+                Some(&other_tenant_key),
+                &connection
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_record_key_envelope_roundtrip() {
+        let tenant_key = CryptoKey::generate();
+        let aad = b"tenant|provider|external";
+
+        let first = encrypt_bytes_with_record_key(&tenant_key, aad, b"token").unwrap();
+        let second = encrypt_bytes_with_record_key(&tenant_key, aad, b"token").unwrap();
+        assert!(uses_record_key(&first) && uses_tenant_key(&first));
+        // Every payload carries its own wrapped record key
+        assert_ne!(
+            first[..VERSION_FIELD_LEN + WRAPPED_RECORD_KEY_LEN],
+            second[..VERSION_FIELD_LEN + WRAPPED_RECORD_KEY_LEN]
+        );
+
+        assert_eq!(decrypt_bytes(&tenant_key, aad, &first).unwrap(), b"token");
+        assert!(decrypt_bytes(&tenant_key, b"other|aad|value", &first).is_err());
+        assert!(decrypt_bytes(&CryptoKey::generate(), aad, &first).is_err());
+        assert!(matches!(
+            decrypt_bytes(&tenant_key, aad, &first[..MIN_ENVELOPE_LEN - 1]),
+            Err(CryptoError::InvalidFormat)
+        ));
+    }
+
+    #[test]
+    fn test_keyring_wraps_with_current_and_unwraps_previous_versions() {
+        let v1 = CryptoKey::new(vec![1u8; 32]).unwrap();
+        let v2 = CryptoKey::new(vec![2u8; 32]).unwrap();
+        let tenant_id = Uuid::new_v4();
+        let tenant_key = CryptoKey::generate();
+
+        assert!(MasterKeyring::new(0, v2.clone()).is_err());
+        let keyring = MasterKeyring::new(2, v2.clone()).unwrap();
+        assert!(keyring.clone().with_previous(2, v1.clone()).is_err());
+        assert!(keyring.clone().with_previous(3, v1.clone()).is_err());
+        let keyring = keyring.with_previous(1, v1.clone()).unwrap();
+        assert_eq!(
+            format!("{:?}", keyring),
+            "MasterKeyring { current_version: 2, versions: [1, 2] }"
+        );
+
+        let (version, wrapped) = keyring.wrap_tenant_key(tenant_id, &tenant_key).unwrap();
+        assert_eq!(version, 2);
+        assert!(keyring.unwrap_tenant_key(1, tenant_id, &wrapped).is_err());
+        let old_wrapped = wrap_tenant_key(&v1, tenant_id, &tenant_key).unwrap();
+        for (version, wrapped) in [(2, &wrapped), (1, &old_wrapped)] {
+            let unwrapped = keyring
+                .unwrap_tenant_key(version, tenant_id, wrapped)
+                .unwrap();
+            assert_eq!(unwrapped.as_bytes(), tenant_key.as_bytes());
+        }
+        assert!(matches!(
+            keyring.unwrap_tenant_key(5, tenant_id, &wrapped),
+            Err(CryptoError::KeyVersionUnavailable(5))
+        ));
+
+        // Master-key payloads from before the rotation stay readable
+        let mut connection = sample_connection(None, None);
+        let (access, _) =
+            encrypt_connection_tokens(&v1, &connection, Some("old-access"), None).unwrap();
+        connection.access_token_ciphertext = access;
+        let (access, _) =
+            decrypt_connection_tokens_with_keyring(&keyring, None, &connection).unwrap();
+        assert_eq!(access.as_deref(), Some("old-access"));
+        let current_only = MasterKeyring::new(2, v2).unwrap();
+        assert!(decrypt_connection_tokens_with_keyring(&current_only, None, &connection).is_err());
+    }
+
+    #[test]
+    fn test_insufficient_ciphertext_length() {
+        let key = test_key();
+        let aad = b"test-aad";
+        let short_ciphertext = vec![VERSION_ENCRYPTED, 0x02]; // Too short for nonce + tag
+
+        let result = decrypt_bytes(&key, aad, &short_ciphertext);
+        assert!(matches!(result, Err(CryptoError::InvalidFormat)));
+    }
+}
@@ -0,0 +1,330 @@
+//! HashiCorp Vault transit master key provider
+//!
+//! Configured key material is transit ciphertext (`vault:v1:...`) produced by
+//! `vault write transit/encrypt/<key>`, unwrapped with the transit `decrypt`
+//! endpoint. Requests authenticate with the standard `VAULT_TOKEN` variable.
+//! Unwrapped keys are cached per ciphertext for `POBLYSH_VAULT_CACHE_TTL_SECONDS`
+//! so reloading the keyring does not call Vault again for keys it already holds.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use base64::{Engine as _, engine::general_purpose};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use super::key_provider::{KeyProvider, VAULT_KEY_PROVIDER};
+use super::{CryptoError, CryptoKey};
+use crate::config::KeyProviderConfig;
+
+/// Default mount path of the transit secrets engine
+pub const VAULT_TRANSIT_MOUNT: &str = "transit";
+
+/// Unwrapped keys by SHA-256 of their ciphertext, with when they were unwrapped
+type KeyCache = HashMap<[u8; 32], (Instant, CryptoKey)>;
+
+/// Wraps and unwraps master keys with a Vault transit key
+#[derive(Clone)]
+pub struct VaultTransitKeyProvider {
+    client: reqwest::Client,
+    addr: String,
+    mount: String,
+    key_name: String,
+    namespace: Option<String>,
+    token: String,
+    cache_ttl: Duration,
+    cache: Arc<Mutex<KeyCache>>,
+}
+
+// The token never appears in logs, even through `{:?}`
+impl std::fmt::Debug for VaultTransitKeyProvider {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("VaultTransitKeyProvider")
+            .field("addr", &self.addr)
+            .field("mount", &self.mount)
+            .field("key_name", &self.key_name)
+            .field("namespace", &self.namespace)
+            .field("token", &"[REDACTED]")
+            .field("cache_ttl", &self.cache_ttl)
+            .finish()
+    }
+}
+
+#[derive(Serialize)]
+struct DecryptRequest<'a> {
+    ciphertext: &'a str,
+}
+
+#[derive(Deserialize)]
+struct DecryptData {
+    plaintext: String,
+}
+
+#[derive(Serialize)]
+struct EncryptRequest {
+    plaintext: String,
+}
+
+#[derive(Deserialize)]
+struct EncryptData {
+    ciphertext: String,
+}
+
+#[derive(Deserialize)]
+struct VaultResponse<T> {
+    data: T,
+}
+
+impl VaultTransitKeyProvider {
+    /// Creates a provider for the transit key `key_name` mounted at `mount` on the Vault server at `addr`
+    pub fn new(
+        addr: String,
+        mount: Option<String>,
+        key_name: String,
+        namespace: Option<String>,
+        token: String,
+        cache_ttl: Duration,
+    ) -> Self {
+        Self {
+            client: crate::egress::client(),
+            addr: addr.trim_end_matches('/').to_string(),
+            mount: mount
+                .unwrap_or_else(|| VAULT_TRANSIT_MOUNT.to_string())
+                .trim_matches('/')
+                .to_string(),
+            key_name,
+            namespace,
+            token,
+            cache_ttl,
+            cache: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Builds the provider from `POBLYSH_VAULT_*` settings and `VAULT_TOKEN`
+    pub fn from_config(config: &KeyProviderConfig) -> Result<Self, CryptoError> {
+        let addr = config
+            .vault_addr
+            .clone()
+            .ok_or_else(|| Self::error("POBLYSH_VAULT_ADDR is not set"))?;
+        let key_name = config
+            .vault_transit_key
+            .clone()
+            .ok_or_else(|| Self::error("POBLYSH_VAULT_TRANSIT_KEY is not set"))?;
+        let token = std::env::var("VAULT_TOKEN")
+            .ok()
+            .filter(|v| !v.is_empty())
+            .ok_or_else(|| Self::error("VAULT_TOKEN is not set"))?;
+        Ok(Self::new(
+            addr,
+            config.vault_transit_mount.clone(),
+            key_name,
+            config.vault_namespace.clone(),
+            token,
+            Duration::from_secs(config.vault_cache_ttl_seconds),
+        ))
+    }
+
+    fn error(message: impl Into<String>) -> CryptoError {
+        CryptoError::KeyProvider {
+            provider: VAULT_KEY_PROVIDER,
+            message: message.into(),
+        }
+    }
+
+    // Calls a transit endpoint (`encrypt` or `decrypt`) and returns its `data` object
+    async fn transit<T: DeserializeOwned>(
+        &self,
+        operation: &str,
+        body: &impl Serialize,
+    ) -> Result<T, CryptoError> {
+        let mut request = self
+            .client
+            .post(format!(
+                "{}/v1/{}/{}/{}",
+                self.addr, self.mount, operation, self.key_name
+            ))
+            .header("X-Vault-Token", &self.token)
+            .json(body);
+        if let Some(namespace) = &self.namespace {
+            request = request.header("X-Vault-Namespace", namespace);
+        }
+        let response = request
+            .send()
+            .await
+            .map_err(|e| Self::error(format!("request failed: {}", e)))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            // Vault error bodies list messages and never contain key material
+            let body = response.text().await.unwrap_or_default();
+            return Err(Self::error(format!(
+                "transit {} returned {}: {}",
+                operation, status, body
+            )));
+        }
+        let parsed: VaultResponse<T> = response
+            .json()
+            .await
+            .map_err(|e| Self::error(format!("invalid transit {} response: {}", operation, e)))?;
+        Ok(parsed.data)
+    }
+
+    fn cached(&self, digest: &[u8; 32]) -> Option<CryptoKey> {
+        let mut cache = self.cache.lock().expect("vault key cache poisoned");
+        match cache.get(digest) {
+            Some((unwrapped_at, key)) if unwrapped_at.elapsed() < self.cache_ttl => {
+                Some(key.clone())
+            }
+            Some(_) => {
+                cache.remove(digest);
+                None
+            }
+            None => None,
+        }
+    }
+}
+
+#[async_trait]
+impl KeyProvider for VaultTransitKeyProvider {
+    fn name(&self) -> &'static str {
+        VAULT_KEY_PROVIDER
+    }
+
+    async fn unwrap_master_key(&self, material: &[u8]) -> Result<CryptoKey, CryptoError> {
+        let digest: [u8; 32] = Sha256::digest(material).into();
+        if let Some(key) = self.cached(&digest) {
+            return Ok(key);
+        }
+
+        let ciphertext = std::str::from_utf8(material)
+            .map_err(|_| Self::error("ciphertext is not a transit ciphertext string"))?;
+        let data: DecryptData = self
+            .transit("decrypt", &DecryptRequest { ciphertext })
+            .await?;
+        let plaintext = general_purpose::STANDARD
+            .decode(data.plaintext)
+            .map_err(|e| Self::error(format!("invalid transit plaintext: {}", e)))?;
+        let key = CryptoKey::new(plaintext)?;
+
+        if !self.cache_ttl.is_zero() {
+            self.cache
+                .lock()
+                .expect("vault key cache poisoned")
+                .insert(digest, (Instant::now(), key.clone()));
+        }
+        Ok(key)
+    }
+
+    async fn wrap_master_key(&self, key: &CryptoKey) -> Result<Vec<u8>, CryptoError> {
+        let data: EncryptData = self
+            .transit(
+                "encrypt",
+                &EncryptRequest {
+                    plaintext: general_purpose::STANDARD.encode(key.as_bytes()),
+                },
+            )
+            .await?;
+        Ok(data.ciphertext.into_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{body_json, header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn provider(server: &MockServer, cache_ttl: Duration) -> VaultTransitKeyProvider {
+        VaultTransitKeyProvider::new(
+            server.uri(),
+            None,
+            "poblysh-master".to_string(),
+            Some("ops".to_string()),
+            "hvs.test-token".to_string(),
+            cache_ttl,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_decrypt_is_cached_per_ciphertext() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/transit/decrypt/poblysh-master"))
+            .and(header("X-Vault-Token", "hvs.test-token"))
+            .and(header("X-Vault-Namespace", "ops"))
+            .and(body_json(
+                serde_json::json!({ "ciphertext": "vault:v1:abc" }),
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": { "plaintext": general_purpose::STANDARD.encode([5u8; 32]) },
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let provider = provider(&server, Duration::from_secs(60));
+        for _ in 0..2 {
+            let key = provider.unwrap_master_key(b"vault:v1:abc").await.unwrap();
+            assert_eq!(key.as_bytes(), &[5u8; 32]);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_zero_ttl_disables_cache() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/transit/decrypt/poblysh-master"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": { "plaintext": general_purpose::STANDARD.encode([5u8; 32]) },
+            })))
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        let provider = provider(&server, Duration::ZERO);
+        provider.unwrap_master_key(b"vault:v1:abc").await.unwrap();
+        provider.unwrap_master_key(b"vault:v1:abc").await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_encrypt_wraps_key() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/transit/encrypt/poblysh-master"))
+            .and(body_json(serde_json::json!({
+                "plaintext": general_purpose::STANDARD.encode([6u8; 32]),
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": { "ciphertext": "vault:v2:wrapped", "key_version": 2 },
+            })))
+            .mount(&server)
+            .await;
+
+        let key = CryptoKey::new(vec![6u8; 32]).unwrap();
+        let wrapped = provider(&server, Duration::ZERO)
+            .wrap_master_key(&key)
+            .await
+            .unwrap();
+        assert_eq!(wrapped, b"vault:v2:wrapped");
+    }
+
+    #[tokio::test]
+    async fn test_permission_denied_is_reported() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(403).set_body_json(serde_json::json!({
+                "errors": ["permission denied"],
+            })))
+            .mount(&server)
+            .await;
+
+        let err = provider(&server, Duration::from_secs(60))
+            .unwrap_master_key(b"vault:v1:abc")
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("permission denied"));
+    }
+}
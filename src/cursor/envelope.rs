@@ -0,0 +1,240 @@
+//! # Sync Cursor Envelope
+//!
+//! Connectors hand back opaque cursors (RFC3339 timestamps, history IDs,
+//! provider page tokens). Before a cursor is persisted it is sealed into a
+//! [`CursorEnvelope`] that records the provider and schema version and carries
+//! an HMAC over those fields, the owning connection, and the payload.
+//!
+//! Opening a stored envelope checks all of the above. Any mismatch yields a
+//! [`CursorRejection`]; callers treat a rejected cursor as absent, so the
+//! connector deterministically restarts from its initial sync window and the
+//! next successful run replaces the envelope.
+
+use base64::Engine;
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use sha2::Sha256;
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::connectors::Cursor;
+use crate::crypto::CryptoKey;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Current envelope schema version; bump when the signed layout changes
+pub const CURSOR_SCHEMA_VERSION: u16 = 1;
+
+/// Domain separation label for deriving the signing key from the crypto key
+const SIGNING_KEY_CONTEXT: &[u8] = b"poblysh-connectors/sync-cursor";
+
+/// Persisted form of a connector sync cursor
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CursorEnvelope {
+    /// Envelope schema version
+    pub version: u16,
+    /// Provider slug of the connector that produced the cursor
+    pub provider: String,
+    /// Opaque connector cursor
+    pub payload: JsonValue,
+    /// URL-safe base64 HMAC-SHA256 over version, provider, connection, and payload
+    pub signature: String,
+}
+
+/// Reasons a stored cursor is not handed back to a connector
+#[derive(Debug, Clone, Error, PartialEq, Eq)]
+pub enum CursorRejection {
+    #[error("cursor is not a sealed envelope")]
+    Unsealed,
+    #[error("cursor schema version {0} is not supported")]
+    UnsupportedVersion(u16),
+    #[error("cursor belongs to provider '{0}'")]
+    ForeignProvider(String),
+    #[error("cursor signature is invalid")]
+    InvalidSignature,
+}
+
+impl CursorRejection {
+    /// Stable label for metrics and logs
+    pub fn reason(&self) -> &'static str {
+        match self {
+            CursorRejection::Unsealed => "unsealed",
+            CursorRejection::UnsupportedVersion(_) => "unsupported_version",
+            CursorRejection::ForeignProvider(_) => "foreign_provider",
+            CursorRejection::InvalidSignature => "invalid_signature",
+        }
+    }
+}
+
+/// Seals and opens cursor envelopes with a key derived from the service crypto key
+#[derive(Clone)]
+pub struct CursorSigner {
+    key: CryptoKey,
+}
+
+impl std::fmt::Debug for CursorSigner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CursorSigner").finish_non_exhaustive()
+    }
+}
+
+impl CursorSigner {
+    /// Derive a cursor signing key so cursor MACs never reuse the token encryption key directly
+    pub fn new(crypto_key: &CryptoKey) -> Self {
+        let mut mac = HmacSha256::new_from_slice(crypto_key.as_bytes())
+            .expect("HMAC accepts keys of any length");
+        mac.update(SIGNING_KEY_CONTEXT);
+        let derived = mac.finalize().into_bytes().to_vec();
+        Self {
+            key: CryptoKey::new(derived).expect("HMAC-SHA256 output is 32 bytes"),
+        }
+    }
+
+    /// Wrap a connector cursor for storage against the given connection
+    pub fn seal(&self, provider: &str, connection_id: Uuid, cursor: &Cursor) -> CursorEnvelope {
+        let payload = cursor.as_json().clone();
+        let tag = self
+            .mac(CURSOR_SCHEMA_VERSION, provider, connection_id, &payload)
+            .finalize()
+            .into_bytes();
+
+        CursorEnvelope {
+            version: CURSOR_SCHEMA_VERSION,
+            provider: provider.to_string(),
+            payload,
+            signature: base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(tag),
+        }
+    }
+
+    /// Seal a cursor and serialize the envelope for a JSON column
+    pub fn seal_json(&self, provider: &str, connection_id: Uuid, cursor: &Cursor) -> JsonValue {
+        serde_json::to_value(self.seal(provider, connection_id, cursor))
+            .expect("cursor envelope serializes to JSON")
+    }
+
+    /// Validate a stored envelope and return the connector cursor it carries
+    pub fn open(
+        &self,
+        stored: &JsonValue,
+        provider: &str,
+        connection_id: Uuid,
+    ) -> Result<Cursor, CursorRejection> {
+        let envelope: CursorEnvelope =
+            serde_json::from_value(stored.clone()).map_err(|_| CursorRejection::Unsealed)?;
+
+        if envelope.version != CURSOR_SCHEMA_VERSION {
+            return Err(CursorRejection::UnsupportedVersion(envelope.version));
+        }
+        if envelope.provider != provider {
+            return Err(CursorRejection::ForeignProvider(envelope.provider));
+        }
+
+        let tag = base64::engine::general_purpose::URL_SAFE_NO_PAD
+            .decode(&envelope.signature)
+            .map_err(|_| CursorRejection::InvalidSignature)?;
+        self.mac(
+            envelope.version,
+            &envelope.provider,
+            connection_id,
+            &envelope.payload,
+        )
+        .verify_slice(&tag)
+        .map_err(|_| CursorRejection::InvalidSignature)?;
+
+        Ok(Cursor::from_json(envelope.payload))
+    }
+
+    fn mac(
+        &self,
+        version: u16,
+        provider: &str,
+        connection_id: Uuid,
+        payload: &JsonValue,
+    ) -> HmacSha256 {
+        let mut mac = HmacSha256::new_from_slice(self.key.as_bytes())
+            .expect("HMAC accepts keys of any length");
+        mac.update(&version.to_be_bytes());
+        mac.update(provider.as_bytes());
+        mac.update(&[0]);
+        mac.update(connection_id.as_bytes());
+        // serde_json maps are key-ordered, so this is stable across jsonb round trips
+        mac.update(payload.to_string().as_bytes());
+        mac
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_signer(byte: u8) -> CursorSigner {
+        CursorSigner::new(&CryptoKey::new(vec![byte; 32]).unwrap())
+    }
+
+    #[test]
+    fn test_seal_open_round_trip() {
+        let signer = test_signer(7);
+        let connection_id = Uuid::new_v4();
+        let cursor = Cursor::from_json(serde_json::json!({ "page": "abc", "since": 42 }));
+
+        let stored = signer.seal_json("github", connection_id, &cursor);
+        assert_eq!(stored["version"], CURSOR_SCHEMA_VERSION);
+        assert_eq!(stored["provider"], "github");
+
+        let opened = signer.open(&stored, "github", connection_id).unwrap();
+        assert_eq!(opened, cursor);
+    }
+
+    #[test]
+    fn test_open_rejects_foreign_and_tampered_cursors() {
+        let signer = test_signer(7);
+        let connection_id = Uuid::new_v4();
+        let stored = signer.seal_json("github", connection_id, &Cursor::from_string("t1"));
+
+        assert_eq!(
+            signer.open(&stored, "jira", connection_id),
+            Err(CursorRejection::ForeignProvider("github".to_string()))
+        );
+        assert_eq!(
+            signer.open(&stored, "github", Uuid::new_v4()),
+            Err(CursorRejection::InvalidSignature)
+        );
+        assert_eq!(
+            test_signer(8).open(&stored, "github", connection_id),
+            Err(CursorRejection::InvalidSignature)
+        );
+
+        let mut tampered = stored.clone();
+        tampered["payload"] = serde_json::json!("t2");
+        assert_eq!(
+            signer.open(&tampered, "github", connection_id),
+            Err(CursorRejection::InvalidSignature)
+        );
+
+        let mut future = stored.clone();
+        future["version"] = serde_json::json!(CURSOR_SCHEMA_VERSION + 1);
+        assert_eq!(
+            signer.open(&future, "github", connection_id),
+            Err(CursorRejection::UnsupportedVersion(
+                CURSOR_SCHEMA_VERSION + 1
+            ))
+        );
+    }
+
+    #[test]
+    fn test_open_rejects_legacy_free_form_cursors() {
+        let signer = test_signer(7);
+        let connection_id = Uuid::new_v4();
+
+        for legacy in [
+            serde_json::json!("2024-01-15T10:30:00Z"),
+            serde_json::json!({ "value": "page-token" }),
+        ] {
+            assert_eq!(
+                signer.open(&legacy, "github", connection_id),
+                Err(CursorRejection::Unsealed)
+            );
+        }
+    }
+}
@@ -1,7 +1,8 @@
 //! # Cursor Utilities
 //!
 //! This module provides utilities for encoding and decoding pagination cursors
-//! with comprehensive validation and security checks.
+//! with comprehensive validation and security checks, and the signed envelope
+//! used to persist connector sync cursors.
 
 use crate::error::ApiError;
 use axum::http::StatusCode;
@@ -10,6 +11,10 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+pub mod envelope;
+
+pub use envelope::{CURSOR_SCHEMA_VERSION, CursorEnvelope, CursorRejection, CursorSigner};
+
 /// Re-export the CursorData from repositories to avoid duplication
 pub use crate::repositories::signal::CursorData;
 
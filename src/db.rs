@@ -1,18 +1,26 @@
 //! Database connection and pool management for the Connectors API.
 //!
 //! This module provides functionality to initialize and manage a SeaORM
-//! connection pool to Postgres with configurable parameters.
+//! connection pool to Postgres with configurable parameters, a background
+//! monitor publishing pool health metrics, and a retry wrapper for transient
+//! database errors used by repositories.
 
 use anyhow::{Context, Result};
+use metrics::{counter, gauge};
 use sea_orm::{
-    ConnectOptions, ConnectionTrait, Database, DatabaseBackend, DatabaseConnection, Statement,
-    Value,
+    ConnectOptions, ConnectionTrait, Database, DatabaseBackend, DatabaseConnection, DbErr,
+    RuntimeErr, Statement, Value,
 };
+use std::future::Future;
+use std::sync::atomic::{AtomicU32, Ordering};
 use std::time::Duration;
 use tokio::time::sleep;
+use tokio_util::sync::CancellationToken;
 use url::Url;
 
+use crate::backoff::ExponentialBackoff;
 use crate::config::AppConfig;
+use crate::query_stats;
 
 /// Errors that can occur during database operations.
 #[derive(Debug, thiserror::Error)]
@@ -64,13 +72,30 @@ pub async fn init_pool(cfg: &AppConfig) -> Result<DatabaseConnection> {
         .into());
     }
 
+    RETRY_MAX_ATTEMPTS.store(cfg.db_retry_max_attempts.max(1), Ordering::Relaxed);
+    crate::fault_injection::install(crate::fault_injection::FaultInjector::from_config(
+        &cfg.fault_injection,
+    ));
+
     // Configure connection options
     let mut opt = ConnectOptions::new(&cfg.database_url);
     opt.max_connections(cfg.db_max_connections)
         .acquire_timeout(Duration::from_millis(cfg.db_acquire_timeout_ms))
-        .idle_timeout(Duration::from_secs(600)) // 10 minutes
-        .max_lifetime(Duration::from_secs(1800)) // 30 minutes
-        .sqlx_logging(false); // Disabled to avoid conflict with tracing subscriber
+        .idle_timeout(Duration::from_secs(cfg.db_idle_timeout_seconds))
+        .max_lifetime(Duration::from_secs(cfg.db_max_lifetime_seconds))
+        // Ping connections on checkout so ones dropped by the server are replaced
+        .test_before_acquire(true);
+
+    if cfg.db_statement_timeout_ms > 0 {
+        let statement_timeout = format!("{}ms", cfg.db_statement_timeout_ms);
+        opt.map_sqlx_postgres_opts(move |pg_opts| {
+            pg_opts.options([("statement_timeout", statement_timeout.as_str())])
+        });
+    }
+
+    // Disabled to avoid conflict with tracing subscriber; slow statements are
+    // reported by the query observer installed on connect
+    opt.sqlx_logging(false);
 
     // Implement retry logic with exponential backoff
     let max_retries = 5;
@@ -114,8 +139,12 @@ pub async fn init_pool(cfg: &AppConfig) -> Result<DatabaseConnection> {
         }
 
         match Database::connect(opt.clone()).await {
-            Ok(conn) => {
+            Ok(mut conn) => {
                 log::info!("Successfully connected to database (attempt {})", attempt);
+                query_stats::install(
+                    &mut conn,
+                    Duration::from_millis(cfg.db_slow_query_threshold_ms),
+                );
                 return Ok(conn);
             }
             Err(e) => {
@@ -147,6 +176,126 @@ pub async fn init_pool(cfg: &AppConfig) -> Result<DatabaseConnection> {
     .into())
 }
 
+/// How often [`monitor_pool`] samples pool gauges and runs a health check
+pub const POOL_MONITOR_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Attempts made by [`with_retry`]; set from configuration by [`init_pool`]
+static RETRY_MAX_ATTEMPTS: AtomicU32 = AtomicU32::new(3);
+
+/// Delay schedule between retries of a transient database error
+const RETRY_BACKOFF: ExponentialBackoff =
+    ExponentialBackoff::new(Duration::from_millis(50), Duration::from_secs(2));
+
+/// Returns true when a database error is likely to succeed on retry.
+///
+/// Covers pool exhaustion, dropped connections, I/O failures, and the Postgres
+/// error classes for connection exceptions, serialization failures, deadlocks,
+/// and server shutdown.
+pub fn is_transient_error(err: &DbErr) -> bool {
+    let sqlx_err = match err {
+        DbErr::ConnectionAcquire(_) => return true,
+        DbErr::Conn(RuntimeErr::SqlxError(e))
+        | DbErr::Exec(RuntimeErr::SqlxError(e))
+        | DbErr::Query(RuntimeErr::SqlxError(e)) => e,
+        _ => return false,
+    };
+
+    match sqlx_err {
+        sea_orm::sqlx::Error::Io(_)
+        | sea_orm::sqlx::Error::PoolTimedOut
+        | sea_orm::sqlx::Error::PoolClosed => true,
+        sea_orm::sqlx::Error::Database(db_err) => db_err.code().is_some_and(|code| {
+            code.starts_with("08") || matches!(code.as_ref(), "40001" | "40P01" | "57P01")
+        }),
+        _ => false,
+    }
+}
+
+/// Runs a database operation, retrying transient failures with exponential backoff.
+///
+/// Only wrap operations that are safe to repeat: reads, or writes guarded so a
+/// second execution is a no-op.
+pub async fn with_retry<T, F, Fut>(operation: F) -> Result<T, DbErr>
+where
+    F: Fn() -> Fut,
+    Fut: Future<Output = Result<T, DbErr>>,
+{
+    let max_attempts = RETRY_MAX_ATTEMPTS.load(Ordering::Relaxed).max(1);
+    for attempt in 1.. {
+        let result = match crate::fault_injection::db_error() {
+            Some(err) => Err(err),
+            None => operation().await,
+        };
+        match result {
+            Err(err) if attempt < max_attempts && is_transient_error(&err) => {
+                let delay = RETRY_BACKOFF.delay(attempt - 1);
+                counter!("db_transient_retries_total").increment(1);
+                tracing::warn!(
+                    attempt,
+                    max_attempts,
+                    error = %err,
+                    "Transient database error, retrying in {:?}",
+                    delay
+                );
+                sleep(delay).await;
+            }
+            result => return result,
+        }
+    }
+
+    unreachable!("retry loop always returns")
+}
+
+/// Publishes pool gauges and tracks database health until cancelled.
+///
+/// Gauges: `db_pool_connections{state="idle"|"in_use"}`, `db_pool_max_connections`
+/// and `db_pool_healthy`. The pool replaces broken connections on its own; the
+/// monitor makes outages and recoveries visible in logs and metrics.
+pub async fn monitor_pool(
+    db: DatabaseConnection,
+    max_connections: u32,
+    interval: Duration,
+    shutdown: CancellationToken,
+) {
+    let mut healthy = true;
+    gauge!("db_pool_max_connections").set(max_connections as f64);
+
+    loop {
+        let (size, idle) = pool_stats(&db);
+        gauge!("db_pool_connections", "state" => "idle").set(idle as f64);
+        gauge!("db_pool_connections", "state" => "in_use").set(size.saturating_sub(idle) as f64);
+
+        let now_healthy = health_check(&db).await.is_ok();
+        gauge!("db_pool_healthy").set(if now_healthy { 1.0 } else { 0.0 });
+        if healthy && !now_healthy {
+            tracing::warn!("Database health check failed; pool will reconnect on next use");
+        } else if !healthy && now_healthy {
+            tracing::info!("Database connectivity restored");
+        }
+        healthy = now_healthy;
+
+        tokio::select! {
+            _ = shutdown.cancelled() => break,
+            _ = sleep(interval) => {}
+        }
+    }
+}
+
+/// Returns (open connections, idle connections) for the underlying pool
+fn pool_stats(db: &DatabaseConnection) -> (u32, u32) {
+    match db {
+        DatabaseConnection::SqlxPostgresPoolConnection(_) => {
+            let pool = db.get_postgres_connection_pool();
+            (pool.size(), pool.num_idle() as u32)
+        }
+        DatabaseConnection::SqlxSqlitePoolConnection(_) => {
+            let pool = db.get_sqlite_connection_pool();
+            (pool.size(), pool.num_idle() as u32)
+        }
+        _ => (0, 0),
+    }
+}
+
 /// Health check for the database connection.
 ///
 /// This function verifies that the database connection is still active
@@ -255,6 +404,45 @@ fn quote_identifier(name: &str) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use sea_orm::ConnAcquireErr;
+    use std::sync::atomic::AtomicUsize;
+
+    #[test]
+    fn test_transient_error_classification() {
+        assert!(is_transient_error(&DbErr::ConnectionAcquire(
+            ConnAcquireErr::Timeout
+        )));
+        assert!(!is_transient_error(&DbErr::RecordNotFound(
+            "missing".to_string()
+        )));
+        assert!(!is_transient_error(&DbErr::Query(RuntimeErr::Internal(
+            "syntax".to_string()
+        ))));
+    }
+
+    #[tokio::test]
+    async fn test_with_retry_retries_transient_errors_only() {
+        let calls = AtomicUsize::new(0);
+        let result = with_retry(|| async {
+            if calls.fetch_add(1, Ordering::SeqCst) == 0 {
+                Err(DbErr::ConnectionAcquire(ConnAcquireErr::Timeout))
+            } else {
+                Ok(42)
+            }
+        })
+        .await;
+        assert_eq!(result.unwrap(), 42);
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        let calls = AtomicUsize::new(0);
+        let result: Result<(), DbErr> = with_retry(|| async {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Err(DbErr::RecordNotFound("missing".to_string()))
+        })
+        .await;
+        assert!(result.is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
 
     #[test]
     fn test_invalid_database_url() {
@@ -0,0 +1,254 @@
+//! # Outbound Egress Policy
+//!
+//! Connector HTTP clients are built through [`client_builder`], which applies
+//! the process-wide [`EgressPolicy`]: requests are routed through the
+//! configured HTTP(S) proxy (except for `no_proxy` hosts) and, when an
+//! allowlist is configured, requests to any other host fail before a
+//! connection is opened.
+//!
+//! Enforcement happens in reqwest's per-request proxy selection, so it also
+//! covers redirects and URLs built from provider responses. Denied requests
+//! are pointed at a sentinel proxy that [`EgressResolver`] refuses to resolve.
+
+use std::net::SocketAddr;
+use std::sync::{Arc, LazyLock, RwLock};
+
+use metrics::counter;
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use reqwest::{Client, ClientBuilder, Proxy, Url};
+use tracing::warn;
+
+use crate::config::EgressConfig;
+
+/// Host that denied requests are routed to; it never resolves
+const DENIED_HOST: &str = "egress-denied.invalid";
+
+static DENIED_PROXY: LazyLock<Url> =
+    LazyLock::new(|| Url::parse(&format!("http://{}", DENIED_HOST)).expect("valid sentinel URL"));
+
+static POLICY: LazyLock<RwLock<Arc<EgressPolicy>>> =
+    LazyLock::new(|| RwLock::new(Arc::new(EgressPolicy::default())));
+
+/// Hostname pattern used by the allowlist and `no_proxy`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HostPattern {
+    /// `*` matches every host
+    Any,
+    /// `api.github.com` matches that host only
+    Exact(String),
+    /// `*.atlassian.net` matches any subdomain, but not `atlassian.net` itself
+    Subdomains(String),
+}
+
+impl HostPattern {
+    /// Parse a pattern, returning `None` for empty or malformed entries
+    pub fn parse(pattern: &str) -> Option<Self> {
+        let pattern = pattern.trim().to_ascii_lowercase();
+        if pattern == "*" {
+            return Some(HostPattern::Any);
+        }
+        let (suffix, wildcard) = match pattern.strip_prefix("*.") {
+            Some(suffix) => (suffix.to_string(), true),
+            None => (pattern, false),
+        };
+        let valid = !suffix.is_empty()
+            && suffix
+                .split('.')
+                .all(|label| !label.is_empty() && label.chars().all(is_host_char));
+        if !valid {
+            return None;
+        }
+        Some(if wildcard {
+            HostPattern::Subdomains(suffix)
+        } else {
+            HostPattern::Exact(suffix)
+        })
+    }
+
+    /// Whether `host` matches this pattern (case-insensitive)
+    pub fn matches(&self, host: &str) -> bool {
+        let host = host.trim_end_matches('.').to_ascii_lowercase();
+        match self {
+            HostPattern::Any => true,
+            HostPattern::Exact(expected) => host == *expected,
+            HostPattern::Subdomains(suffix) => host
+                .strip_suffix(suffix.as_str())
+                .is_some_and(|prefix| prefix.len() > 1 && prefix.ends_with('.')),
+        }
+    }
+}
+
+fn is_host_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '-' || c == ':' || c == '[' || c == ']'
+}
+
+/// Proxy routing and hostname allowlist for connector traffic
+#[derive(Debug, Clone, Default)]
+pub struct EgressPolicy {
+    proxy: Option<Url>,
+    no_proxy: Vec<HostPattern>,
+    allowlist: Vec<HostPattern>,
+}
+
+impl EgressPolicy {
+    /// Build a policy from configuration; malformed entries are rejected by config validation
+    pub fn from_config(config: &EgressConfig) -> Self {
+        Self {
+            proxy: config
+                .proxy_url
+                .as_deref()
+                .and_then(|url| Url::parse(url).ok()),
+            no_proxy: config
+                .no_proxy
+                .iter()
+                .filter_map(|entry| HostPattern::parse(entry))
+                .collect(),
+            allowlist: config
+                .allowlist
+                .iter()
+                .filter_map(|entry| HostPattern::parse(entry))
+                .collect(),
+        }
+    }
+
+    /// Whether requests to `host` are permitted; an empty allowlist permits everything
+    pub fn allows(&self, host: &str) -> bool {
+        self.allowlist.is_empty() || self.allowlist.iter().any(|p| p.matches(host))
+    }
+
+    /// Proxy to use for `url`: the configured proxy, none for direct, or the denial sentinel
+    fn route(&self, url: &Url) -> Option<Url> {
+        let host = url.host_str().unwrap_or_default();
+        if !self.allows(host) {
+            warn!(host = host, "Outbound request blocked by egress allowlist");
+            counter!("egress_denied_total").increment(1);
+            return Some(DENIED_PROXY.clone());
+        }
+        if self.no_proxy.iter().any(|pattern| pattern.matches(host)) {
+            return None;
+        }
+        self.proxy.clone()
+    }
+
+    fn is_unrestricted(&self) -> bool {
+        self.proxy.is_none() && self.allowlist.is_empty()
+    }
+}
+
+/// Replace the process-wide policy used by connector HTTP clients built afterwards
+pub fn install(policy: EgressPolicy) {
+    let mut current = POLICY.write().unwrap_or_else(|e| e.into_inner());
+    *current = Arc::new(policy);
+}
+
+/// The currently installed policy
+pub fn current() -> Arc<EgressPolicy> {
+    POLICY.read().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+/// A reqwest client builder with the current egress policy applied.
+///
+/// Without a proxy or allowlist the builder is left untouched, so reqwest's
+/// defaults (including the standard proxy environment variables) still apply.
+pub fn client_builder() -> ClientBuilder {
+    let policy = current();
+    let builder = Client::builder();
+    if policy.is_unrestricted() {
+        return builder;
+    }
+    builder
+        .proxy(Proxy::custom(move |url| policy.route(url)))
+        .dns_resolver(Arc::new(EgressResolver))
+}
+
+/// A reqwest client with the current egress policy applied
+pub fn client() -> Client {
+    client_builder()
+        .build()
+        .expect("Failed to create HTTP client")
+}
+
+/// System resolver that refuses the denial sentinel with a descriptive error
+#[derive(Debug)]
+struct EgressResolver;
+
+impl Resolve for EgressResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        Box::pin(async move {
+            if name.as_str() == DENIED_HOST {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::PermissionDenied,
+                    "outbound host is not on the egress allowlist (POBLYSH_EGRESS_ALLOWLIST)",
+                )
+                .into());
+            }
+            let addrs: Vec<SocketAddr> =
+                tokio::net::lookup_host((name.as_str(), 0)).await?.collect();
+            Ok(Box::new(addrs.into_iter()) as Addrs)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_host_patterns() {
+        let exact = HostPattern::parse("API.GitHub.com").unwrap();
+        assert!(exact.matches("api.github.com"));
+        assert!(!exact.matches("github.com"));
+
+        let wildcard = HostPattern::parse("*.atlassian.net").unwrap();
+        assert!(wildcard.matches("acme.atlassian.net"));
+        assert!(wildcard.matches("api.acme.atlassian.net."));
+        assert!(!wildcard.matches("atlassian.net"));
+        assert!(!wildcard.matches("evilatlassian.net"));
+
+        assert_eq!(HostPattern::parse("*"), Some(HostPattern::Any));
+        assert_eq!(HostPattern::parse(""), None);
+        assert_eq!(HostPattern::parse("https://github.com"), None);
+        assert_eq!(HostPattern::parse("*.*.com"), None);
+    }
+
+    #[test]
+    fn test_route_applies_allowlist_and_no_proxy() {
+        let policy = EgressPolicy::from_config(&EgressConfig {
+            proxy_url: Some("http://proxy.corp:3128".to_string()),
+            no_proxy: vec!["internal.corp".to_string()],
+            allowlist: vec!["api.github.com".to_string(), "internal.corp".to_string()],
+        });
+        let url = |s: &str| Url::parse(s).unwrap();
+
+        assert_eq!(
+            policy.route(&url("https://api.github.com/user")),
+            Some(url("http://proxy.corp:3128"))
+        );
+        assert_eq!(policy.route(&url("https://internal.corp/health")), None);
+        assert_eq!(
+            policy.route(&url("https://example.com/")),
+            Some(DENIED_PROXY.clone())
+        );
+        assert_eq!(
+            policy.route(&url("http://127.0.0.1:8080/")),
+            Some(DENIED_PROXY.clone())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_denied_request_fails_without_connecting() {
+        let policy = Arc::new(EgressPolicy::from_config(&EgressConfig {
+            proxy_url: None,
+            no_proxy: Vec::new(),
+            allowlist: vec!["api.github.com".to_string()],
+        }));
+        let client = Client::builder()
+            .proxy(Proxy::custom(move |url| policy.route(url)))
+            .dns_resolver(Arc::new(EgressResolver))
+            .build()
+            .unwrap();
+
+        let err = client.get("http://example.com/").send().await.unwrap_err();
+        assert!(format!("{:?}", err).contains("egress allowlist"));
+    }
+}
@@ -313,6 +313,10 @@ impl From<crate::webhook_verification::VerificationError> for ApiError {
                     provider
                 ),
             ),
+            VerificationError::DuplicateDelivery { provider } => (
+                "REPLAY_ATTACK_DETECTED",
+                format!("Duplicate webhook delivery for provider: {}", provider),
+            ),
         };
 
         Self::new(error.status_code(), code, &message)
@@ -432,6 +436,22 @@ pub enum RepositoryError {
     Validation(String),
 }
 
+impl From<RepositoryError> for ApiError {
+    fn from(error: RepositoryError) -> Self {
+        match error {
+            RepositoryError::Database(err) => err.into(),
+            RepositoryError::NotFound(what) => Self::new(
+                StatusCode::NOT_FOUND,
+                "NOT_FOUND",
+                format!("Not found: {}", what),
+            ),
+            RepositoryError::Validation(message) => {
+                Self::new(StatusCode::BAD_REQUEST, "VALIDATION_FAILED", message)
+            }
+        }
+    }
+}
+
 impl RepositoryError {
     /// Create a database error wrapper
     pub fn database_error(err: sea_orm::DbErr) -> Self {
@@ -0,0 +1,162 @@
+//! # Fault Injection
+//!
+//! Staging deployments can inject failures to check that retries, backoff and
+//! job rescheduling hold up against real traffic, not only in unit tests.
+//! Faults are only injected by builds with the `fault-injection` feature;
+//! other builds ignore the settings and log a warning at startup.
+//!
+//! Injected faults:
+//! - database operations run through [`with_retry`](crate::db::with_retry)
+//!   fail with a pool acquire timeout, a transient error that is retried
+//! - connector sync and webhook calls made by the sync executor fail with a
+//!   transient provider error, as if the provider returned a 5xx
+//! - connector calls are delayed before they start, which counts against the
+//!   executor's `max_run_seconds` job timeout
+//!
+//! Every injected fault is logged at `warn` and counted in
+//! `fault_injected_total{kind}`.
+
+use std::sync::{Arc, LazyLock, RwLock};
+use std::time::Duration;
+
+use metrics::counter;
+use rand::Rng;
+use sea_orm::{ConnAcquireErr, DbErr};
+use tracing::warn;
+
+use crate::config::FaultInjectionConfig;
+use crate::connectors::SyncError;
+
+static INJECTOR: LazyLock<RwLock<Arc<FaultInjector>>> =
+    LazyLock::new(|| RwLock::new(Arc::new(FaultInjector::default())));
+
+/// Probabilities of each injected fault
+#[derive(Debug, Clone, Default)]
+pub struct FaultInjector {
+    db_error_rate: f64,
+    provider_error_rate: f64,
+    slow_response_rate: f64,
+    slow_response_delay: Duration,
+}
+
+impl FaultInjector {
+    /// Build an injector from configuration
+    pub fn from_config(config: &FaultInjectionConfig) -> Self {
+        Self {
+            db_error_rate: config.db_error_rate,
+            provider_error_rate: config.provider_error_rate,
+            slow_response_rate: config.slow_response_rate,
+            slow_response_delay: Duration::from_millis(config.slow_response_delay_ms),
+        }
+    }
+
+    /// Whether any fault has a non-zero probability
+    pub fn is_enabled(&self) -> bool {
+        self.db_error_rate > 0.0 || self.provider_error_rate > 0.0 || self.slow_response_rate > 0.0
+    }
+
+    /// Error to return instead of running a database operation, if one is injected
+    pub fn db_error(&self) -> Option<DbErr> {
+        if !roll(self.db_error_rate) {
+            return None;
+        }
+        record("db_error");
+        Some(DbErr::ConnectionAcquire(ConnAcquireErr::Timeout))
+    }
+
+    /// Delay to wait before a provider call and the error to fail it with, if injected
+    pub fn provider_faults(&self, provider: &str) -> (Option<Duration>, Option<SyncError>) {
+        let delay = roll(self.slow_response_rate).then(|| {
+            record("slow_response");
+            self.slow_response_delay
+        });
+        let error = roll(self.provider_error_rate).then(|| {
+            record("provider_error");
+            SyncError::transient(format!("injected fault: {} returned 503", provider))
+        });
+        (delay, error)
+    }
+}
+
+fn roll(rate: f64) -> bool {
+    rate > 0.0 && rand::thread_rng().gen_bool(rate.min(1.0))
+}
+
+fn record(kind: &'static str) {
+    warn!(kind, "Injecting fault");
+    counter!("fault_injected_total", "kind" => kind).increment(1);
+}
+
+/// Replace the process-wide injector; ignored by builds without the `fault-injection` feature
+pub fn install(injector: FaultInjector) {
+    if !cfg!(feature = "fault-injection") {
+        if injector.is_enabled() {
+            warn!("Ignoring fault injection settings: built without the fault-injection feature");
+        }
+        return;
+    }
+    if injector.is_enabled() {
+        warn!(?injector, "Fault injection is enabled");
+    }
+    let mut current = INJECTOR.write().unwrap_or_else(|e| e.into_inner());
+    *current = Arc::new(injector);
+}
+
+fn current() -> Option<Arc<FaultInjector>> {
+    if !cfg!(feature = "fault-injection") {
+        return None;
+    }
+    Some(INJECTOR.read().unwrap_or_else(|e| e.into_inner()).clone())
+}
+
+/// Injected database error for the current operation, if any
+pub fn db_error() -> Option<DbErr> {
+    current()?.db_error()
+}
+
+/// Apply injected provider faults before a connector call
+///
+/// Sleeps for an injected slow response, then returns an injected provider error.
+pub async fn before_provider_call(provider: &str) -> Result<(), SyncError> {
+    let Some(injector) = current() else {
+        return Ok(());
+    };
+    let (delay, error) = injector.provider_faults(provider);
+    if let Some(delay) = delay {
+        tokio::time::sleep(delay).await;
+    }
+    error.map_or(Ok(()), Err)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::connectors::SyncErrorKind;
+
+    fn injector(rate: f64) -> FaultInjector {
+        FaultInjector::from_config(&FaultInjectionConfig {
+            db_error_rate: rate,
+            provider_error_rate: rate,
+            slow_response_rate: rate,
+            slow_response_delay_ms: 250,
+        })
+    }
+
+    #[test]
+    fn test_rates_bound_injection() {
+        let never = injector(0.0);
+        assert!(!never.is_enabled());
+        for _ in 0..100 {
+            assert!(never.db_error().is_none());
+            assert!(matches!(never.provider_faults("github"), (None, None)));
+        }
+
+        let always = injector(1.0);
+        assert!(always.is_enabled());
+        let err = always.db_error().unwrap();
+        assert!(crate::db::is_transient_error(&err));
+        let (delay, error) = always.provider_faults("github");
+        assert_eq!(delay, Some(Duration::from_millis(250)));
+        assert!(matches!(error.unwrap().kind, SyncErrorKind::Transient));
+    }
+}
@@ -0,0 +1,76 @@
+//! # Admin Dashboard Handlers
+//!
+//! This module serves the optional embedded operator dashboard. The static assets
+//! are compiled into the binary and the page reads data through the `/admin/api`
+//! routes, which reuse the regular tenant-scoped handlers behind admin auth.
+
+use crate::auth::OperatorAuth;
+use crate::error::ApiError;
+use crate::repositories::TenantRepository;
+use crate::server::AppState;
+use axum::{
+    extract::{Path, State},
+    http::{HeaderValue, StatusCode, header},
+    response::{IntoResponse, Json, Response},
+};
+use serde::Serialize;
+use uuid::Uuid;
+
+const INDEX_HTML: &str = include_str!("../../assets/admin/index.html");
+const APP_JS: &str = include_str!("../../assets/admin/app.js");
+const APP_CSS: &str = include_str!("../../assets/admin/app.css");
+
+/// Tenant entry for the dashboard tenant picker
+#[derive(Debug, Serialize)]
+pub struct AdminTenant {
+    /// Tenant identifier
+    pub id: Uuid,
+    /// Display name for the tenant, if any
+    pub name: Option<String>,
+}
+
+/// Serves the dashboard shell
+pub async fn index(_operator: OperatorAuth) -> Response {
+    static_asset(INDEX_HTML, "text/html; charset=utf-8")
+}
+
+/// Serves a static dashboard asset by file name
+pub async fn asset(_operator: OperatorAuth, Path(file): Path<String>) -> Response {
+    match file.as_str() {
+        "app.js" => static_asset(APP_JS, "text/javascript; charset=utf-8"),
+        "app.css" => static_asset(APP_CSS, "text/css; charset=utf-8"),
+        _ => ApiError::new(StatusCode::NOT_FOUND, "NOT_FOUND", "Asset not found").into_response(),
+    }
+}
+
+/// Lists all tenants so the dashboard can pick one to inspect
+pub async fn list_tenants(
+    State(state): State<AppState>,
+    _operator: OperatorAuth,
+) -> Result<Json<Vec<AdminTenant>>, ApiError> {
+    let tenants = TenantRepository::new(&state.db)
+        .list_tenants()
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to list tenants for admin dashboard: {}", e);
+            ApiError::internal_server_error("Failed to list tenants")
+        })?;
+
+    Ok(Json(
+        tenants
+            .into_iter()
+            .map(|tenant| AdminTenant {
+                id: tenant.id,
+                name: tenant.name,
+            })
+            .collect(),
+    ))
+}
+
+fn static_asset(body: &'static str, content_type: &'static str) -> Response {
+    let mut response = body.into_response();
+    let headers = response.headers_mut();
+    headers.insert(header::CONTENT_TYPE, HeaderValue::from_static(content_type));
+    headers.insert(header::CACHE_CONTROL, HeaderValue::from_static("no-cache"));
+    response
+}
@@ -11,8 +11,9 @@ use crate::server::AppState;
 
 /// Get rate limit policy configuration
 ///
-/// Returns the current rate limit policy configuration including provider overrides.
-/// This endpoint is read-only and provides operational visibility into rate limiting behavior.
+/// Returns the rate limit policy in force, including provider overrides and any
+/// changes applied by a configuration reload. This endpoint is read-only and
+/// provides operational visibility into rate limiting behavior.
 #[utoipa::path(
     get,
     path = "/config/rate-limit-policy",
@@ -23,11 +24,9 @@ use crate::server::AppState;
     tag = "configuration"
 )]
 pub async fn get_rate_limit_policy_config(
-    State(_state): State<AppState>,
+    State(state): State<AppState>,
 ) -> Result<Json<RateLimitPolicyConfig>, StatusCode> {
-    // For now, return the default configuration. In a real implementation,
-    // we would want to pass the actual config through the AppState.
-    Ok(Json(RateLimitPolicyConfig::default()))
+    Ok(Json(state.settings.current().rate_limit_policy.clone()))
 }
 
 /// Get service configuration summary
@@ -43,11 +42,9 @@ pub async fn get_rate_limit_policy_config(
     ),
     tag = "configuration"
 )]
-pub async fn get_config_summary(State(_state): State<AppState>) -> Result<Json<Value>, StatusCode> {
-    // Return a basic summary. In a real implementation, this would include
-    // non-sensitive configuration from the actual AppConfig.
+pub async fn get_config_summary(State(state): State<AppState>) -> Result<Json<Value>, StatusCode> {
     let summary = serde_json::json!({
-        "rate_limit_policy": RateLimitPolicyConfig::default(),
+        "rate_limit_policy": state.settings.current().rate_limit_policy,
         "endpoints": {
             "swagger_ui": "/docs",
             "openapi_spec": "/docs/api.json"
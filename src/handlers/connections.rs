@@ -1,27 +1,75 @@
 //! # Connections API Handlers
 //!
 //! This module contains handlers for managing connection listings,
-//! including tenant-scoped connection listing with optional provider filtering.
+//! including tenant-scoped connection listing with optional provider filtering,
+//! for creating connections to providers configured by metadata and an optional
+//! secret,
+//! for updating the operator-configurable part of a connection's metadata,
+//! for checking a connection's credentials and last sync, for listing the
+//! history of its sync runs, for pausing and resuming its scheduled syncs, and
+//! for triggering a sync, optionally as a dry run whose signals are returned
+//! instead of stored.
 
 use crate::auth::{OperatorAuth, TenantExtension, TenantHeader};
-use crate::cursor::decode_generic_cursor;
-use crate::error::ApiError;
+use crate::connection_pause::{ConnectionAuthHealth, STATUS_NEEDS_REAUTH, STATUS_PAUSED};
+use crate::connectors::metadata_schema::apply_metadata_patch;
+use crate::connectors::registry::Registry;
+use crate::connectors::trait_::SyncParams;
+use crate::connectors::{
+    ICS_CALENDAR_URL_KEY, ICS_PROVIDER_SLUG, IMAP_PROVIDER_SLUG, RSS_FEED_URL_KEY,
+    RSS_PROVIDER_SLUG,
+};
+use crate::cursor::{CursorSigner, decode_generic_cursor};
+use crate::error::{ApiError, validation_error};
+use crate::handlers::connect::handle_connector_error;
+use crate::handlers::jobs::JobInfo;
+use crate::models::connection;
+use crate::repositories::audit_log::API_AUDIT_ACTOR;
 use crate::repositories::connection::ConnectionRepository;
-use crate::repositories::provider::ProviderRepository;
+use crate::repositories::sync_metadata::ConnectionSyncMetadata;
+use crate::repositories::{
+    AuditLogRepository, NewAuditLogEntry, SyncJobRepository, SyncJobRunRepository,
+    TokenEventRepository,
+};
 use crate::server::AppState;
+use crate::token_vault::TokenVault;
 use axum::{
-    extract::{Query, State},
+    extract::{Path, Query, State},
     http::StatusCode,
-    response::Json,
+    response::{IntoResponse, Json, Response},
 };
 use chrono::{DateTime, Utc};
+use sea_orm::Set;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
+use tracing::warn;
 use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 
+/// Audit action recorded when a connection's metadata is updated
+pub const CONNECTION_METADATA_UPDATED_AUDIT_ACTION: &str = "connection.metadata_updated";
+
+/// Audit action recorded when a connection is created through the API
+pub const CONNECTION_CREATED_AUDIT_ACTION: &str = "connection.created";
+
+/// Audit action recorded when an operator pauses a connection's scheduled syncs
+pub const CONNECTION_PAUSED_AUDIT_ACTION: &str = "connection.paused";
+
+/// Audit action recorded when an operator resumes a connection's scheduled syncs
+pub const CONNECTION_RESUMED_AUDIT_ACTION: &str = "connection.resumed";
+
+/// Priority for operator-triggered syncs: ahead of webhook processing and scheduled polls
+const MANUAL_SYNC_PRIORITY: i16 = 60;
+
+/// Auth type of providers whose connections need no credentials
+const NO_AUTH_TYPE: &str = "none";
+
+/// Auth type of providers whose connections carry a password or static token
+const BASIC_AUTH_TYPE: &str = "basic";
+
 /// Query parameters for connections listing
 #[derive(Debug, Deserialize, Serialize, IntoParams, ToSchema)]
+#[into_params(parameter_in = Query)]
 pub struct ListConnectionsQuery {
     /// Optional provider filter (snake_case slug, e.g., "github")
     pub provider: Option<String>,
@@ -39,6 +87,12 @@ pub struct ConnectionInfo {
     pub id: Uuid,
     /// Provider slug (e.g., "github", "slack")
     pub provider: String,
+    /// Lifecycle status; only `active` connections are scheduled
+    #[schema(example = "active")]
+    pub status: String,
+    /// Why the connection is not scheduled, e.g. the provider error that put it in `needs_reauth`
+    #[schema(example = json!(null))]
+    pub status_reason: Option<String>,
     /// Optional expiration timestamp for the connection
     pub expires_at: Option<String>,
     /// Provider-specific metadata
@@ -56,16 +110,20 @@ pub struct ConnectionInfo {
 
 impl From<crate::models::connection::Model> for ConnectionInfo {
     fn from(model: crate::models::connection::Model) -> Self {
+        let status_reason = ConnectionAuthHealth::from_connection_metadata(model.metadata.as_ref())
+            .status_reason(&model.status);
         Self {
             id: model.id,
             provider: model.provider_slug,
+            status: model.status,
+            status_reason,
             expires_at: model.expires_at.map(|dt| {
                 // Convert DateTimeWithTimeZone to RFC3339 string
                 let utc_dt: DateTime<Utc> = dt.naive_utc().and_utc();
                 utc_dt.to_rfc3339()
             }),
             metadata: model.metadata.unwrap_or_default(),
-            // Check if encrypted tokens exist
+            // Only meaningful for models hydrated with their secrets
             has_access_token: model.access_token_ciphertext.is_some(),
             has_refresh_token: model.refresh_token_ciphertext.is_some(),
             // Default to version 1 for current encrypted format
@@ -138,13 +196,12 @@ pub async fn list_connections(
     }
 
     let connection_repo =
-        ConnectionRepository::new(Arc::new(state.db.clone()), state.crypto_key.clone());
-    let provider_repo = ProviderRepository::new(Arc::new(state.db.clone()));
+        ConnectionRepository::new(Arc::new(state.db.clone()), state.master_keys.clone());
 
     let (connections, next_cursor) = match query.provider {
         Some(provider_slug) => {
             // Validate provider exists in registry
-            if provider_repo.find_by_slug(&provider_slug).await?.is_none() {
+            if state.snapshot.provider(&provider_slug).await?.is_none() {
                 return Err(ApiError::new(
                     StatusCode::BAD_REQUEST,
                     "VALIDATION_FAILED",
@@ -165,8 +222,20 @@ pub async fn list_connections(
         }
     };
 
-    let connection_infos: Vec<ConnectionInfo> =
-        connections.into_iter().map(ConnectionInfo::from).collect();
+    // Token flags come from the secrets table without loading the ciphertexts
+    let ids: Vec<_> = connections.iter().map(|c| c.id).collect();
+    let presence = connection_repo.secrets().presence(&ids).await?;
+    let connection_infos: Vec<ConnectionInfo> = connections
+        .into_iter()
+        .map(|model| {
+            let tokens = presence.get(&model.id).copied().unwrap_or_default();
+            ConnectionInfo {
+                has_access_token: tokens.has_access_token,
+                has_refresh_token: tokens.has_refresh_token,
+                ..ConnectionInfo::from(model)
+            }
+        })
+        .collect();
 
     Ok(Json(ConnectionsResponse {
         connections: connection_infos,
@@ -174,6 +243,994 @@ pub async fn list_connections(
     }))
 }
 
+/// Request to create a connection
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateConnectionRequest {
+    /// Provider slug; only providers whose auth type is `none`, such as `rss`,
+    /// or `basic`, such as `imap`
+    #[schema(example = "rss")]
+    pub provider: String,
+    /// Name shown for the connection
+    #[schema(example = "Acme newsroom")]
+    pub display_name: Option<String>,
+    /// Settings declared in `GET /providers/{slug}/metadata-schema`
+    #[schema(value_type = Object, example = json!({"feed_url": "https://example.com/news/feed.xml"}))]
+    pub metadata: serde_json::Map<String, serde_json::Value>,
+    /// Password or static access token; required for `basic` providers and
+    /// rejected for `none` providers. Stored encrypted and never returned.
+    #[schema(example = json!(null))]
+    pub secret: Option<String>,
+}
+
+/// Creates a connection to a provider that does not authorize with OAuth
+///
+/// Such providers are configured by connection metadata, e.g. the feed URL of
+/// an `rss` connection or the server of an `imap` connection, plus a secret
+/// for `basic` providers. The connection is active immediately and is picked
+/// up by the scheduler's next tick. Providers that authorize with OAuth are
+/// connected through `/connect/{provider}` instead.
+#[utoipa::path(
+    post,
+    path = "/connections",
+    security(("bearer_auth" = [])),
+    params(TenantHeader),
+    request_body = CreateConnectionRequest,
+    responses(
+        (status = 201, description = "Connection created", body = ConnectionInfo),
+        (status = 400, description = "Unknown provider, provider authorizes with OAuth, secret missing or unexpected, or metadata does not match its schema", body = ApiError),
+        (status = 401, description = "Unauthorized", body = ApiError),
+        (status = 409, description = "The tenant already has a connection with these settings", body = ApiError)
+    ),
+    tag = "operators"
+)]
+pub async fn create_connection(
+    State(state): State<AppState>,
+    _operator_auth: OperatorAuth,
+    TenantExtension(tenant): TenantExtension,
+    Json(request): Json<CreateConnectionRequest>,
+) -> Result<(StatusCode, Json<ConnectionInfo>), ApiError> {
+    let provider = state
+        .snapshot
+        .provider(&request.provider)
+        .await?
+        .ok_or_else(|| {
+            validation_error(
+                "Invalid connection",
+                serde_json::json!({ "provider": "unknown provider" }),
+            )
+        })?;
+    let secret = request
+        .secret
+        .as_deref()
+        .map(str::trim)
+        .filter(|secret| !secret.is_empty());
+    match (provider.auth_type.as_str(), secret) {
+        (NO_AUTH_TYPE, None) | (BASIC_AUTH_TYPE, Some(_)) => {}
+        (NO_AUTH_TYPE, Some(_)) => {
+            return Err(validation_error(
+                "Invalid connection",
+                serde_json::json!({
+                    "secret": format!("provider '{}' takes no secret", provider.slug)
+                }),
+            ));
+        }
+        (BASIC_AUTH_TYPE, None) => {
+            return Err(validation_error(
+                "Invalid connection",
+                serde_json::json!({
+                    "secret": format!("provider '{}' requires a secret", provider.slug)
+                }),
+            ));
+        }
+        _ => {
+            return Err(validation_error(
+                "Invalid connection",
+                serde_json::json!({
+                    "provider": format!(
+                        "provider '{}' authorizes with {}; connect it through /connect/{}",
+                        provider.slug, provider.auth_type, provider.slug
+                    )
+                }),
+            ));
+        }
+    }
+
+    let metadata =
+        apply_metadata_patch(&provider.slug, None, &request.metadata).map_err(|error| {
+            validation_error(
+                "Invalid connection metadata",
+                serde_json::json!({ error.field: error.message }),
+            )
+        })?;
+    let external_id = connection_external_id(&provider.slug, &metadata);
+
+    let connection_repo =
+        ConnectionRepository::new(Arc::new(state.db.clone()), state.master_keys.clone());
+    if connection_repo
+        .find_by_external_id(&tenant.0, &provider.slug, &external_id)
+        .await?
+        .is_some()
+    {
+        return Err(ApiError::new(
+            StatusCode::CONFLICT,
+            "CONFLICT",
+            format!(
+                "a '{}' connection for '{}' already exists",
+                provider.slug, external_id
+            ),
+        ));
+    }
+
+    let now = Utc::now().fixed_offset();
+    let created = connection_repo
+        .create_with_tokens(
+            connection::ActiveModel {
+                id: Set(Uuid::new_v4()),
+                tenant_id: Set(tenant.0),
+                provider_slug: Set(provider.slug.clone()),
+                external_id: Set(external_id),
+                status: Set("active".to_string()),
+                display_name: Set(request.display_name),
+                expires_at: Set(None),
+                scopes: Set(None),
+                metadata: Set(Some(metadata)),
+                created_at: Set(now),
+                updated_at: Set(now),
+            },
+            secret,
+            None,
+        )
+        .await?;
+
+    AuditLogRepository::new(state.db.clone())
+        .record_or_log(NewAuditLogEntry {
+            tenant_id: Some(tenant.0),
+            actor: API_AUDIT_ACTOR.to_string(),
+            action: CONNECTION_CREATED_AUDIT_ACTION.to_string(),
+            target_type: "connection".to_string(),
+            target_id: created.id.to_string(),
+            reason: None,
+            details: Some(serde_json::json!({
+                "provider": created.provider_slug,
+                "metadata": request.metadata,
+            })),
+        })
+        .await;
+
+    Ok((StatusCode::CREATED, Json(ConnectionInfo::from(created))))
+}
+
+/// External id identifying what a connection created through the API points at
+fn connection_external_id(provider: &str, metadata: &serde_json::Value) -> String {
+    let text = |key: &str| {
+        metadata
+            .get(key)
+            .and_then(|v| v.as_str())
+            .unwrap_or_default()
+    };
+    match provider {
+        RSS_PROVIDER_SLUG => text(RSS_FEED_URL_KEY).to_string(),
+        ICS_PROVIDER_SLUG => text(ICS_CALENDAR_URL_KEY).to_string(),
+        IMAP_PROVIDER_SLUG => format!("{}@{}", text("username"), text("host").to_ascii_lowercase()),
+        _ => Uuid::new_v4().to_string(),
+    }
+}
+
+/// Request to update a connection
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateConnectionRequest {
+    /// Metadata keys to set; `null` removes a key. Only keys declared in
+    /// `GET /providers/{slug}/metadata-schema` are accepted.
+    #[serde(default)]
+    #[schema(value_type = Object, example = json!({"repositories": ["octo-org/api"]}))]
+    pub metadata: serde_json::Map<String, serde_json::Value>,
+    /// Put the connection in dry-run mode: its syncs run but signals are
+    /// logged instead of stored and the cursor does not advance
+    #[schema(example = true)]
+    pub dry_run: Option<bool>,
+}
+
+/// Updates the configurable metadata of a tenant's connection
+///
+/// The patch is validated against the provider's metadata schema and merged
+/// into the stored metadata; keys written by the connector are kept.
+/// `dry_run` toggles dry-run mode, stored as `metadata.sync.dry_run`.
+#[utoipa::path(
+    patch,
+    path = "/connections/{id}",
+    security(("bearer_auth" = [])),
+    params(TenantHeader, ("id" = Uuid, Path, description = "Connection ID")),
+    request_body = UpdateConnectionRequest,
+    responses(
+        (status = 200, description = "Connection updated", body = ConnectionInfo),
+        (status = 400, description = "Metadata does not match the provider's schema", body = ApiError),
+        (status = 401, description = "Unauthorized", body = ApiError),
+        (status = 404, description = "Connection not found", body = ApiError)
+    ),
+    tag = "operators"
+)]
+pub async fn update_connection(
+    State(state): State<AppState>,
+    _operator_auth: OperatorAuth,
+    TenantExtension(tenant): TenantExtension,
+    Path(id): Path<Uuid>,
+    Json(request): Json<UpdateConnectionRequest>,
+) -> Result<Json<ConnectionInfo>, ApiError> {
+    let connection_repo =
+        ConnectionRepository::new(Arc::new(state.db.clone()), state.master_keys.clone());
+    let existing = connection_repo
+        .find_by_id(&tenant.0, &id)
+        .await?
+        .ok_or_else(|| {
+            ApiError::new(
+                StatusCode::NOT_FOUND,
+                "NOT_FOUND",
+                format!("connection '{}' not found", id),
+            )
+        })?;
+
+    let mut metadata = apply_metadata_patch(
+        &existing.provider_slug,
+        existing.metadata.as_ref(),
+        &request.metadata,
+    )
+    .map_err(|error| {
+        validation_error(
+            "Invalid connection metadata",
+            serde_json::json!({ error.field: error.message }),
+        )
+    })?;
+    if let Some(dry_run) = request.dry_run {
+        let mut sync_metadata = ConnectionSyncMetadata::from_connection_metadata(Some(&metadata));
+        sync_metadata.dry_run = dry_run;
+        metadata = sync_metadata.into_connection_metadata(Some(&metadata));
+    }
+
+    let updated = connection_repo
+        .update_by_id(
+            &tenant.0,
+            &id,
+            connection::ActiveModel {
+                metadata: Set(Some(metadata)),
+                ..Default::default()
+            },
+        )
+        .await?;
+
+    AuditLogRepository::new(state.db.clone())
+        .record_or_log(NewAuditLogEntry {
+            tenant_id: Some(tenant.0),
+            actor: API_AUDIT_ACTOR.to_string(),
+            action: CONNECTION_METADATA_UPDATED_AUDIT_ACTION.to_string(),
+            target_type: "connection".to_string(),
+            target_id: id.to_string(),
+            reason: None,
+            details: Some(serde_json::json!({
+                "metadata": request.metadata,
+                "dry_run": request.dry_run,
+            })),
+        })
+        .await;
+
+    let tokens = connection_repo
+        .secrets()
+        .presence(&[id])
+        .await?
+        .remove(&id)
+        .unwrap_or_default();
+    Ok(Json(ConnectionInfo {
+        has_access_token: tokens.has_access_token,
+        has_refresh_token: tokens.has_refresh_token,
+        ..ConnectionInfo::from(updated)
+    }))
+}
+
+/// Outcome of a connection's most recently finished sync job
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct LastSyncInfo {
+    /// Job identifier
+    #[schema(value_type = String)]
+    pub job_id: Uuid,
+    /// Job type (e.g., full, incremental, webhook)
+    pub job_type: String,
+    /// Final job status (succeeded or failed)
+    pub status: String,
+    /// When the job finished (RFC 3339)
+    pub finished_at: String,
+    /// Error recorded by a failed job
+    pub error: Option<serde_json::Value>,
+}
+
+/// Health of a connection's credentials and syncing
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ConnectionHealthResponse {
+    /// Unique identifier for the connection
+    #[schema(value_type = String)]
+    pub id: Uuid,
+    /// Provider slug (e.g., "github", "slack")
+    pub provider: String,
+    /// Connection status (active, paused, needs_reauth, revoked or error)
+    pub status: String,
+    /// Whether the provider accepted the stored token; null when the provider has no health check or it could not run
+    pub token_valid: Option<bool>,
+    /// Expiration timestamp of the stored access token
+    pub token_expires_at: Option<String>,
+    /// Scopes reported by the provider, or those granted at authorization when it reports none
+    pub scopes: Vec<String>,
+    /// Why the token was rejected or the check could not run
+    pub message: Option<String>,
+    /// Most recently finished sync job, if any
+    pub last_sync: Option<LastSyncInfo>,
+}
+
+/// Checks a tenant's connection against its provider
+///
+/// Asks the connector to make a cheap authenticated call with the stored
+/// token and reports the outcome with the connection's last finished sync.
+#[utoipa::path(
+    get,
+    path = "/connections/{id}/health",
+    security(("bearer_auth" = [])),
+    params(TenantHeader, ("id" = Uuid, Path, description = "Connection ID")),
+    responses(
+        (status = 200, description = "Connection health", body = ConnectionHealthResponse, example = json!({
+            "id": "550e8400-e29b-41d4-a716-446655440000",
+            "provider": "github",
+            "status": "active",
+            "token_valid": true,
+            "token_expires_at": null,
+            "scopes": ["repo", "read:org"],
+            "message": null,
+            "last_sync": {
+                "job_id": "0b9e7f5c-2d7a-4c1e-9a39-0f6d3f1f6a21",
+                "job_type": "incremental",
+                "status": "succeeded",
+                "finished_at": "2024-06-01T12:00:00+00:00",
+                "error": null
+            }
+        })),
+        (status = 401, description = "Unauthorized", body = ApiError),
+        (status = 404, description = "Connection not found", body = ApiError)
+    ),
+    tag = "operators"
+)]
+pub async fn get_connection_health(
+    State(state): State<AppState>,
+    _operator_auth: OperatorAuth,
+    TenantExtension(tenant): TenantExtension,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ConnectionHealthResponse>, ApiError> {
+    let connection_repo =
+        ConnectionRepository::new(Arc::new(state.db.clone()), state.master_keys.clone());
+    let existing = connection_repo
+        .find_by_id(&tenant.0, &id)
+        .await?
+        .ok_or_else(|| {
+            ApiError::new(
+                StatusCode::NOT_FOUND,
+                "NOT_FOUND",
+                format!("connection '{}' not found", id),
+            )
+        })?;
+
+    // Connectors read their tokens in plaintext from the token fields
+    let probe = TokenVault::from(connection_repo).open(existing).await?;
+
+    let connector = Registry::global()
+        .read()
+        .unwrap()
+        .get(&probe.provider_slug)
+        .ok();
+    let (check, message) = match connector {
+        Some(connector) => match connector.health_check(&probe).await {
+            Ok(check) => (check, None),
+            Err(e) => {
+                warn!(connection_id = %id, error = %e, "Connection health check failed");
+                (None, Some(format!("health check failed: {}", e)))
+            }
+        },
+        None => (
+            None,
+            Some(format!(
+                "provider '{}' is not registered",
+                probe.provider_slug
+            )),
+        ),
+    };
+
+    let stored_scopes = probe
+        .scopes
+        .as_ref()
+        .and_then(|scopes| serde_json::from_value::<Vec<String>>(scopes.clone()).ok())
+        .unwrap_or_default();
+    let last_sync = SyncJobRepository::new(state.db.clone())
+        .latest_finished_for_connection(tenant.0, id)
+        .await?
+        .and_then(|job| {
+            Some(LastSyncInfo {
+                job_id: job.id,
+                job_type: job.job_type,
+                status: job.status,
+                finished_at: job.finished_at?.to_rfc3339(),
+                error: job.error,
+            })
+        });
+
+    Ok(Json(ConnectionHealthResponse {
+        id: probe.id,
+        provider: probe.provider_slug,
+        status: probe.status,
+        token_valid: check.as_ref().map(|check| check.token_valid),
+        token_expires_at: probe
+            .expires_at
+            .map(|dt| dt.with_timezone(&Utc).to_rfc3339()),
+        scopes: check
+            .as_ref()
+            .and_then(|check| check.scopes.clone())
+            .unwrap_or(stored_scopes),
+        message: check.and_then(|check| check.message).or(message),
+        last_sync,
+    }))
+}
+
+/// Query parameters for listing a connection's sync runs
+#[derive(Debug, Deserialize, Serialize, IntoParams, ToSchema)]
+#[into_params(parameter_in = Query)]
+pub struct ListSyncRunsQuery {
+    /// Maximum number of runs to return (default: 50, max: 100)
+    pub limit: Option<i64>,
+}
+
+/// One executed run of a sync job
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct SyncRunInfo {
+    /// Run identifier
+    #[schema(value_type = String)]
+    pub id: Uuid,
+    /// Job the run belongs to
+    #[schema(value_type = String)]
+    pub job_id: Uuid,
+    /// Job type (e.g., full, incremental, webhook)
+    pub job_type: String,
+    /// Attempt number of the job this run was
+    pub attempt: i32,
+    /// When the executor started the run (RFC 3339)
+    pub started_at: String,
+    /// When the run ended (RFC 3339)
+    pub finished_at: String,
+    /// Signals the run persisted
+    pub items_processed: i64,
+    /// How the run ended (succeeded, failed, cancelled or interrupted)
+    pub outcome: String,
+    /// Error message of a run that did not succeed
+    pub error: Option<String>,
+}
+
+impl From<crate::models::sync_job_run::Model> for SyncRunInfo {
+    fn from(model: crate::models::sync_job_run::Model) -> Self {
+        Self {
+            id: model.id,
+            job_id: model.job_id,
+            job_type: model.job_type,
+            attempt: model.attempt,
+            started_at: model.started_at.with_timezone(&Utc).to_rfc3339(),
+            finished_at: model.finished_at.with_timezone(&Utc).to_rfc3339(),
+            items_processed: model.items_processed,
+            outcome: model.outcome,
+            error: model.error,
+        }
+    }
+}
+
+/// Response wrapper for a connection's sync runs
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct SyncRunsResponse {
+    /// Runs of the connection's jobs, newest first
+    pub runs: Vec<SyncRunInfo>,
+}
+
+/// Lists the executed sync runs of a tenant's connection
+///
+/// Every run of every job is kept, so the errors of retried attempts stay
+/// visible after the job itself succeeded or was retried again.
+#[utoipa::path(
+    get,
+    path = "/connections/{id}/runs",
+    security(("bearer_auth" = [])),
+    params(TenantHeader, ("id" = Uuid, Path, description = "Connection ID"), ListSyncRunsQuery),
+    responses(
+        (status = 200, description = "Sync runs, newest first", body = SyncRunsResponse, example = json!({
+            "runs": [{
+                "id": "7c4a2d1e-5b3f-4e8a-9d6c-1f2e3a4b5c6d",
+                "job_id": "0b9e7f5c-2d7a-4c1e-9a39-0f6d3f1f6a21",
+                "job_type": "incremental",
+                "attempt": 2,
+                "started_at": "2024-06-01T12:00:00+00:00",
+                "finished_at": "2024-06-01T12:00:04+00:00",
+                "items_processed": 120,
+                "outcome": "failed",
+                "error": "HTTP error 503: unavailable"
+            }]
+        })),
+        (status = 400, description = "Validation error", body = ApiError),
+        (status = 401, description = "Unauthorized", body = ApiError),
+        (status = 404, description = "Connection not found", body = ApiError)
+    ),
+    tag = "operators"
+)]
+pub async fn list_connection_runs(
+    State(state): State<AppState>,
+    _operator_auth: OperatorAuth,
+    TenantExtension(tenant): TenantExtension,
+    Path(id): Path<Uuid>,
+    Query(query): Query<ListSyncRunsQuery>,
+) -> Result<Json<SyncRunsResponse>, ApiError> {
+    let limit = query.limit.unwrap_or(50);
+    if !(1..=100).contains(&limit) {
+        return Err(ApiError::new(
+            StatusCode::BAD_REQUEST,
+            "VALIDATION_FAILED",
+            "limit must be between 1 and 100",
+        ));
+    }
+
+    let connection_repo =
+        ConnectionRepository::new(Arc::new(state.db.clone()), state.master_keys.clone());
+    if connection_repo.find_by_id(&tenant.0, &id).await?.is_none() {
+        return Err(ApiError::new(
+            StatusCode::NOT_FOUND,
+            "NOT_FOUND",
+            format!("connection '{}' not found", id),
+        ));
+    }
+
+    let runs = SyncJobRunRepository::new(state.db.clone())
+        .list_for_connection(tenant.0, id, limit as u64)
+        .await?;
+
+    Ok(Json(SyncRunsResponse {
+        runs: runs.into_iter().map(SyncRunInfo::from).collect(),
+    }))
+}
+
+/// Query parameters for listing a connection's token events
+#[derive(Debug, Deserialize, Serialize, IntoParams, ToSchema)]
+#[into_params(parameter_in = Query)]
+pub struct ListTokenEventsQuery {
+    /// Maximum number of events to return (default: 50, max: 100)
+    pub limit: Option<i64>,
+}
+
+/// One token refresh attempt of a connection
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct TokenEventInfo {
+    /// Event identifier
+    #[schema(value_type = String)]
+    pub id: Uuid,
+    /// Access token expiry before the refresh (RFC 3339)
+    pub previous_expires_at: Option<String>,
+    /// Access token expiry after a successful refresh (RFC 3339)
+    pub new_expires_at: Option<String>,
+    /// Whether the provider issued a new refresh token
+    pub refresh_token_rotated: bool,
+    /// How the refresh ended (succeeded or failed)
+    pub outcome: String,
+    /// Provider or network error of a failed refresh
+    pub error: Option<String>,
+    /// When the refresh finished (RFC 3339)
+    pub occurred_at: String,
+}
+
+impl From<crate::models::token_event::Model> for TokenEventInfo {
+    fn from(model: crate::models::token_event::Model) -> Self {
+        Self {
+            id: model.id,
+            previous_expires_at: model
+                .previous_expires_at
+                .map(|at| at.with_timezone(&Utc).to_rfc3339()),
+            new_expires_at: model
+                .new_expires_at
+                .map(|at| at.with_timezone(&Utc).to_rfc3339()),
+            refresh_token_rotated: model.refresh_token_rotated,
+            outcome: model.outcome,
+            error: model.error,
+            occurred_at: model.occurred_at.with_timezone(&Utc).to_rfc3339(),
+        }
+    }
+}
+
+/// Response wrapper for a connection's token events
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct TokenEventsResponse {
+    /// Refresh attempts of the connection, newest first
+    pub events: Vec<TokenEventInfo>,
+}
+
+/// Lists the token refresh history of a tenant's connection
+///
+/// Every refresh sent to the provider is kept, with the expiry before and
+/// after and whether the refresh token was rotated, to debug provider auth
+/// issues after the stored tokens have moved on.
+#[utoipa::path(
+    get,
+    path = "/connections/{id}/token-events",
+    security(("bearer_auth" = [])),
+    params(TenantHeader, ("id" = Uuid, Path, description = "Connection ID"), ListTokenEventsQuery),
+    responses(
+        (status = 200, description = "Token events, newest first", body = TokenEventsResponse, example = json!({
+            "events": [{
+                "id": "5d0c8e2a-7f41-4b9e-8a3d-2c6f1e9b7a40",
+                "previous_expires_at": "2024-06-01T12:05:00+00:00",
+                "new_expires_at": "2024-06-01T13:00:00+00:00",
+                "refresh_token_rotated": true,
+                "outcome": "succeeded",
+                "error": null,
+                "occurred_at": "2024-06-01T12:00:00+00:00"
+            }]
+        })),
+        (status = 400, description = "Validation error", body = ApiError),
+        (status = 401, description = "Unauthorized", body = ApiError),
+        (status = 404, description = "Connection not found", body = ApiError)
+    ),
+    tag = "operators"
+)]
+pub async fn list_token_events(
+    State(state): State<AppState>,
+    _operator_auth: OperatorAuth,
+    TenantExtension(tenant): TenantExtension,
+    Path(id): Path<Uuid>,
+    Query(query): Query<ListTokenEventsQuery>,
+) -> Result<Json<TokenEventsResponse>, ApiError> {
+    let limit = query.limit.unwrap_or(50);
+    if !(1..=100).contains(&limit) {
+        return Err(ApiError::new(
+            StatusCode::BAD_REQUEST,
+            "VALIDATION_FAILED",
+            "limit must be between 1 and 100",
+        ));
+    }
+
+    let connection_repo =
+        ConnectionRepository::new(Arc::new(state.db.clone()), state.master_keys.clone());
+    if connection_repo.find_by_id(&tenant.0, &id).await?.is_none() {
+        return Err(ApiError::new(
+            StatusCode::NOT_FOUND,
+            "NOT_FOUND",
+            format!("connection '{}' not found", id),
+        ));
+    }
+
+    let events = TokenEventRepository::new(state.db.clone())
+        .list_for_connection(tenant.0, id, limit as u64)
+        .await?;
+
+    Ok(Json(TokenEventsResponse {
+        events: events.into_iter().map(TokenEventInfo::from).collect(),
+    }))
+}
+
+/// Scheduling status of a connection after a pause or resume
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ConnectionStatusResponse {
+    /// Unique identifier for the connection
+    #[schema(value_type = String)]
+    pub id: Uuid,
+    /// Provider slug (e.g., "github", "slack")
+    pub provider: String,
+    /// Connection status; the scheduler only enqueues syncs for `active` connections
+    #[schema(example = "paused")]
+    pub status: String,
+}
+
+/// Pauses scheduled syncs of a tenant's connection
+///
+/// Sets the connection's status to `paused`, which the scheduler skips, so a
+/// noisy or broken integration can be quiesced without deleting it. Jobs
+/// already queued still run. Pausing a paused connection changes nothing;
+/// connections that are not `active` or `paused` get `409`. Re-authorizing
+/// through the OAuth flow does not resume a connection paused this way.
+#[utoipa::path(
+    post,
+    path = "/connections/{id}/pause",
+    security(("bearer_auth" = [])),
+    params(TenantHeader, ("id" = Uuid, Path, description = "Connection ID")),
+    responses(
+        (status = 200, description = "Connection paused", body = ConnectionStatusResponse),
+        (status = 401, description = "Unauthorized", body = ApiError),
+        (status = 404, description = "Connection not found", body = ApiError),
+        (status = 409, description = "Connection status cannot be paused", body = ApiError)
+    ),
+    tag = "operators"
+)]
+pub async fn pause_connection(
+    State(state): State<AppState>,
+    _operator_auth: OperatorAuth,
+    TenantExtension(tenant): TenantExtension,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ConnectionStatusResponse>, ApiError> {
+    set_scheduling_status(&state, tenant.0, id, STATUS_PAUSED).await
+}
+
+/// Resumes scheduled syncs of a tenant's connection
+///
+/// Returns a `paused` connection to `active` and clears its authorization
+/// failure streak. Resuming an active connection changes nothing; other
+/// statuses get `409`, including `needs_reauth`, which only re-authorizing
+/// the connection clears.
+#[utoipa::path(
+    post,
+    path = "/connections/{id}/resume",
+    security(("bearer_auth" = [])),
+    params(TenantHeader, ("id" = Uuid, Path, description = "Connection ID")),
+    responses(
+        (status = 200, description = "Connection resumed", body = ConnectionStatusResponse),
+        (status = 401, description = "Unauthorized", body = ApiError),
+        (status = 404, description = "Connection not found", body = ApiError),
+        (status = 409, description = "Connection status cannot be resumed", body = ApiError)
+    ),
+    tag = "operators"
+)]
+pub async fn resume_connection(
+    State(state): State<AppState>,
+    _operator_auth: OperatorAuth,
+    TenantExtension(tenant): TenantExtension,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ConnectionStatusResponse>, ApiError> {
+    set_scheduling_status(&state, tenant.0, id, "active").await
+}
+
+/// Move a connection between `active` and `paused`, auditing real changes
+async fn set_scheduling_status(
+    state: &AppState,
+    tenant_id: Uuid,
+    id: Uuid,
+    status: &str,
+) -> Result<Json<ConnectionStatusResponse>, ApiError> {
+    let connection_repo =
+        ConnectionRepository::new(Arc::new(state.db.clone()), state.master_keys.clone());
+    let existing = connection_repo
+        .find_by_id(&tenant_id, &id)
+        .await?
+        .ok_or_else(|| {
+            ApiError::new(
+                StatusCode::NOT_FOUND,
+                "NOT_FOUND",
+                format!("connection '{}' not found", id),
+            )
+        })?;
+
+    if existing.status == status {
+        return Ok(Json(ConnectionStatusResponse {
+            id,
+            provider: existing.provider_slug,
+            status: existing.status,
+        }));
+    }
+    if existing.status == STATUS_NEEDS_REAUTH {
+        return Err(ApiError::new(
+            StatusCode::CONFLICT,
+            "CONNECTION_STATUS_CONFLICT",
+            format!(
+                "connection '{}' needs re-authorization; reconnect it to resume syncing",
+                id
+            ),
+        ));
+    }
+    if existing.status != "active" && existing.status != STATUS_PAUSED {
+        return Err(ApiError::new(
+            StatusCode::CONFLICT,
+            "CONNECTION_STATUS_CONFLICT",
+            format!(
+                "connection '{}' is {} and cannot be {}",
+                id,
+                existing.status,
+                if status == STATUS_PAUSED {
+                    "paused"
+                } else {
+                    "resumed"
+                }
+            ),
+        ));
+    }
+
+    let mut changes = connection::ActiveModel {
+        status: Set(status.to_string()),
+        ..Default::default()
+    };
+    if status != STATUS_PAUSED {
+        // A resumed connection starts a fresh authorization failure streak
+        changes.metadata = Set(Some(
+            ConnectionAuthHealth::default().into_connection_metadata(existing.metadata.as_ref()),
+        ));
+    }
+    let updated = connection_repo
+        .update_by_id(&tenant_id, &id, changes)
+        .await?;
+
+    AuditLogRepository::new(state.db.clone())
+        .record_or_log(NewAuditLogEntry {
+            tenant_id: Some(tenant_id),
+            actor: API_AUDIT_ACTOR.to_string(),
+            action: if status == STATUS_PAUSED {
+                CONNECTION_PAUSED_AUDIT_ACTION
+            } else {
+                CONNECTION_RESUMED_AUDIT_ACTION
+            }
+            .to_string(),
+            target_type: "connection".to_string(),
+            target_id: id.to_string(),
+            reason: None,
+            details: Some(serde_json::json!({
+                "provider_slug": updated.provider_slug,
+                "previous_status": existing.status,
+            })),
+        })
+        .await;
+
+    Ok(Json(ConnectionStatusResponse {
+        id,
+        provider: updated.provider_slug,
+        status: updated.status,
+    }))
+}
+
+/// Query parameters for triggering a connection sync
+#[derive(Debug, Default, Deserialize, IntoParams)]
+pub struct TriggerSyncQuery {
+    /// Run the sync inline and return its signals without storing them
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// A signal produced by a dry-run sync
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct DryRunSignal {
+    /// Normalized event kind
+    pub kind: String,
+    /// Timestamp when the event occurred in the provider system (RFC 3339)
+    pub occurred_at: String,
+    /// Key the signal would be deduplicated on
+    pub dedupe_key: Option<String>,
+    /// Normalized event payload
+    pub payload: serde_json::Value,
+}
+
+/// Signals a dry-run sync would have stored
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct DryRunSyncResponse {
+    /// Connection that was synced
+    #[schema(value_type = String)]
+    pub connection_id: Uuid,
+    /// Provider slug (e.g., "github", "slack")
+    pub provider: String,
+    /// Signals of the first page, as the connector mapped them
+    pub signals: Vec<DryRunSignal>,
+    /// Whether the provider has further pages a real sync would fetch
+    pub has_more: bool,
+}
+
+/// Triggers a sync of a tenant's connection
+///
+/// Enqueues an incremental sync job that is due immediately and ahead of
+/// scheduled and webhook work; a connection with an incremental job already
+/// queued or running gets `409`. With `dry_run=true` the connector
+/// instead syncs one page inline from the stored cursor and the signals are
+/// returned without being stored, so OAuth scopes and mappings can be checked
+/// before a connection goes live; the cursor is left untouched.
+#[utoipa::path(
+    post,
+    path = "/connections/{id}/sync",
+    security(("bearer_auth" = [])),
+    params(
+        TenantHeader,
+        ("id" = Uuid, Path, description = "Connection ID"),
+        ("dry_run" = Option<bool>, Query, description = "Return the signals of one page instead of enqueueing a job")
+    ),
+    responses(
+        (status = 200, description = "Dry-run sync result", body = DryRunSyncResponse, example = json!({
+            "connection_id": "550e8400-e29b-41d4-a716-446655440000",
+            "provider": "github",
+            "signals": [{
+                "kind": "issue_created",
+                "occurred_at": "2024-06-01T12:00:00+00:00",
+                "dedupe_key": "github:issue:42",
+                "payload": {"title": "Crash on start"}
+            }],
+            "has_more": false
+        })),
+        (status = 202, description = "Sync job enqueued", body = JobInfo),
+        (status = 401, description = "Unauthorized", body = ApiError),
+        (status = 404, description = "Connection or provider not found", body = ApiError),
+        (status = 409, description = "An incremental sync is already queued or running", body = ApiError, example = json!({
+            "code": "SYNC_ALREADY_PENDING",
+            "message": "An incremental sync is already queued for this connection",
+            "details": {"job_id": "0b9e7f5c-2d7a-4c1e-9a39-0f6d3f1f6a21", "status": "queued"},
+            "retry_after": null
+        })),
+        (status = 502, description = "Provider rejected the dry-run sync", body = ApiError)
+    ),
+    tag = "operators"
+)]
+pub async fn trigger_sync(
+    State(state): State<AppState>,
+    _operator_auth: OperatorAuth,
+    TenantExtension(tenant): TenantExtension,
+    Path(id): Path<Uuid>,
+    Query(query): Query<TriggerSyncQuery>,
+) -> Result<Response, ApiError> {
+    let connection_repo =
+        ConnectionRepository::new(Arc::new(state.db.clone()), state.master_keys.clone());
+    let existing = connection_repo
+        .find_by_id(&tenant.0, &id)
+        .await?
+        .ok_or_else(|| {
+            ApiError::new(
+                StatusCode::NOT_FOUND,
+                "NOT_FOUND",
+                format!("connection '{}' not found", id),
+            )
+        })?;
+
+    if !query.dry_run {
+        let job = SyncJobRepository::new(state.db.clone())
+            .enqueue_immediate_sync(tenant.0, &existing.provider_slug, id, MANUAL_SYNC_PRIORITY)
+            .await?;
+        return Ok((StatusCode::ACCEPTED, Json(JobInfo::from(job))).into_response());
+    }
+
+    let connector = Registry::global()
+        .read()
+        .unwrap()
+        .get(&existing.provider_slug)
+        .map_err(|_| {
+            ApiError::new(
+                StatusCode::NOT_FOUND,
+                "NOT_FOUND",
+                format!("provider '{}' is not registered", existing.provider_slug),
+            )
+        })?;
+
+    // Connectors read their tokens in plaintext from the token fields
+    let probe = TokenVault::from(connection_repo).open(existing).await?;
+
+    let provider = probe.provider_slug.clone();
+    let cursor = ConnectionSyncMetadata::from_connection_metadata(probe.metadata.as_ref())
+        .cursor
+        .and_then(|stored| {
+            CursorSigner::new(state.master_keys.current())
+                .open(&stored, &provider, id)
+                .ok()
+        });
+    let result = connector
+        .sync(SyncParams {
+            connection: probe,
+            cursor,
+            cancellation: Default::default(),
+        })
+        .await
+        .map_err(|e| {
+            warn!(connection_id = %id, error = %e, "Dry-run sync failed");
+            handle_connector_error(&provider, e)
+        })?;
+
+    Ok(Json(DryRunSyncResponse {
+        connection_id: id,
+        provider,
+        signals: result
+            .signals
+            .into_iter()
+            .map(|signal| DryRunSignal {
+                kind: signal.kind,
+                occurred_at: signal.occurred_at.with_timezone(&Utc).to_rfc3339(),
+                dedupe_key: signal.dedupe_key,
+                payload: signal.payload,
+            })
+            .collect(),
+        has_more: result.has_more,
+    })
+    .into_response())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -262,6 +1319,8 @@ mod tests {
         let connection_info = ConnectionInfo {
             id: uuid::Uuid::new_v4(),
             provider: "github".to_string(),
+            status: "active".to_string(),
+            status_reason: None,
             expires_at: Some("2024-12-31T23:59:59Z".to_string()),
             metadata: serde_json::json!({"user": "test"}),
             has_access_token: true,
@@ -283,6 +1342,8 @@ mod tests {
         let connections = vec![ConnectionInfo {
             id: uuid::Uuid::new_v4(),
             provider: "github".to_string(),
+            status: "active".to_string(),
+            status_reason: None,
             expires_at: None,
             metadata: serde_json::json!({}),
             has_access_token: false,
@@ -330,6 +1391,8 @@ mod tests {
         let connections = vec![ConnectionInfo {
             id: Uuid::new_v4(),
             provider: "github".to_string(),
+            status: "active".to_string(),
+            status_reason: None,
             expires_at: None,
             metadata: serde_json::json!({}),
             has_access_token: false,
@@ -368,4 +1431,689 @@ mod tests {
             serde_json::from_str(&json_with_cursor).unwrap();
         assert!(parsed_with_cursor.next_cursor.is_some());
     }
+
+    #[tokio::test]
+    async fn update_connection_validates_and_merges_metadata() {
+        use crate::db::init_pool;
+        use crate::models::tenant;
+        use crate::repositories::ProviderRepository;
+        use migration::{Migrator, MigratorTrait};
+        use sea_orm::ActiveModelTrait;
+
+        let config = AppConfig {
+            profile: "test".to_string(),
+            ..Default::default()
+        };
+        let db = init_pool(&config).await.expect("Failed to init test DB");
+        Migrator::up(&db, None).await.unwrap();
+        let tenant_id = uuid::Uuid::new_v4();
+        tenant::ActiveModel {
+            id: Set(tenant_id),
+            ..Default::default()
+        }
+        .insert(&db)
+        .await
+        .unwrap();
+        ProviderRepository::new(Arc::new(db.clone()))
+            .upsert("github", "GitHub", "oauth2")
+            .await
+            .unwrap();
+        let connection_id = uuid::Uuid::new_v4();
+        connection::ActiveModel {
+            id: Set(connection_id),
+            tenant_id: Set(tenant_id),
+            provider_slug: Set("github".to_string()),
+            external_id: Set("octocat".to_string()),
+            metadata: Set(Some(serde_json::json!({"login": "octocat"}))),
+            ..Default::default()
+        }
+        .insert(&db)
+        .await
+        .unwrap();
+        let state = crate::server::create_test_app_state(config, db);
+        let patch = |metadata: serde_json::Value| {
+            let state = state.clone();
+            async move {
+                update_connection(
+                    State(state),
+                    OperatorAuth,
+                    TenantExtension(crate::auth::TenantId(tenant_id)),
+                    Path(connection_id),
+                    Json(UpdateConnectionRequest {
+                        metadata: metadata.as_object().unwrap().clone(),
+                        dry_run: None,
+                    }),
+                )
+                .await
+            }
+        };
+
+        let Json(updated) = patch(serde_json::json!({"repositories": ["octo-org/api"]}))
+            .await
+            .unwrap();
+        assert_eq!(
+            updated.metadata,
+            serde_json::json!({"login": "octocat", "repositories": ["octo-org/api"]})
+        );
+
+        let rejected = patch(serde_json::json!({"login": "mallory"}))
+            .await
+            .unwrap_err();
+        assert_eq!(rejected.status, StatusCode::BAD_REQUEST);
+        let rejected = patch(serde_json::json!({"repositories": ["api"]}))
+            .await
+            .unwrap_err();
+        assert_eq!(rejected.status, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn create_connection_accepts_providers_without_oauth() {
+        use crate::db::init_pool;
+        use crate::models::tenant;
+        use crate::repositories::ProviderRepository;
+        use migration::{Migrator, MigratorTrait};
+        use sea_orm::ActiveModelTrait;
+
+        let config = AppConfig {
+            profile: "test".to_string(),
+            ..Default::default()
+        };
+        let db = init_pool(&config).await.expect("Failed to init test DB");
+        Migrator::up(&db, None).await.unwrap();
+        let tenant_id = uuid::Uuid::new_v4();
+        tenant::ActiveModel {
+            id: Set(tenant_id),
+            ..Default::default()
+        }
+        .insert(&db)
+        .await
+        .unwrap();
+        let providers = ProviderRepository::new(Arc::new(db.clone()));
+        providers.upsert("rss", "RSS", "none").await.unwrap();
+        providers.upsert("imap", "IMAP", "basic").await.unwrap();
+        providers
+            .upsert("github", "GitHub", "oauth2")
+            .await
+            .unwrap();
+        let state = crate::server::create_test_app_state(config, db);
+        let create = |provider: &str, metadata: serde_json::Value, secret: Option<&str>| {
+            let state = state.clone();
+            let request = CreateConnectionRequest {
+                provider: provider.to_string(),
+                display_name: Some("Newsroom".to_string()),
+                metadata: metadata.as_object().unwrap().clone(),
+                secret: secret.map(str::to_string),
+            };
+            async move {
+                create_connection(
+                    State(state),
+                    OperatorAuth,
+                    TenantExtension(crate::auth::TenantId(tenant_id)),
+                    Json(request),
+                )
+                .await
+            }
+        };
+        let feed = serde_json::json!({"feed_url": "https://example.com/feed.xml"});
+
+        let (status, Json(created)) = create("rss", feed.clone(), None).await.unwrap();
+        assert_eq!(status, StatusCode::CREATED);
+        assert_eq!(created.provider, "rss");
+        assert_eq!(created.metadata, feed);
+
+        let duplicate = create("rss", feed.clone(), None).await.unwrap_err();
+        assert_eq!(duplicate.status, StatusCode::CONFLICT);
+        let invalid = create("rss", serde_json::json!({"feed_url": "not a url"}), None)
+            .await
+            .unwrap_err();
+        assert_eq!(invalid.status, StatusCode::BAD_REQUEST);
+        let unexpected_secret = create("rss", feed, Some("hunter2")).await.unwrap_err();
+        assert_eq!(unexpected_secret.status, StatusCode::BAD_REQUEST);
+        let oauth = create("github", serde_json::json!({}), None)
+            .await
+            .unwrap_err();
+        assert_eq!(oauth.status, StatusCode::BAD_REQUEST);
+
+        let mailbox =
+            serde_json::json!({"host": "imap.example.com", "username": "ops@example.com"});
+        let missing_secret = create("imap", mailbox.clone(), None).await.unwrap_err();
+        assert_eq!(missing_secret.status, StatusCode::BAD_REQUEST);
+        let (status, Json(created)) = create("imap", mailbox, Some("app-password")).await.unwrap();
+        assert_eq!(status, StatusCode::CREATED);
+        let repo = ConnectionRepository::new(Arc::new(state.db.clone()), state.master_keys.clone());
+        let stored = repo
+            .find_by_id(&tenant_id, &created.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(stored.external_id, "ops@example.com@imap.example.com");
+        let stored = repo.with_secrets(stored).await.unwrap();
+        let (secret, _, _) = repo.decrypt_tokens(&stored).await.unwrap();
+        assert_eq!(secret.as_deref(), Some("app-password"));
+    }
+
+    #[tokio::test]
+    async fn connection_health_reports_last_finished_sync() {
+        use crate::db::init_pool;
+        use crate::models::{sync_job, tenant};
+        use crate::repositories::ProviderRepository;
+        use migration::{Migrator, MigratorTrait};
+        use sea_orm::ActiveModelTrait;
+
+        let config = AppConfig {
+            profile: "test".to_string(),
+            ..Default::default()
+        };
+        Registry::initialize(&config);
+        let db = init_pool(&config).await.expect("Failed to init test DB");
+        Migrator::up(&db, None).await.unwrap();
+        let tenant_id = uuid::Uuid::new_v4();
+        tenant::ActiveModel {
+            id: Set(tenant_id),
+            ..Default::default()
+        }
+        .insert(&db)
+        .await
+        .unwrap();
+        ProviderRepository::new(Arc::new(db.clone()))
+            .upsert("rss", "RSS", "none")
+            .await
+            .unwrap();
+        let state = crate::server::create_test_app_state(config, db.clone());
+        let health = |id: Uuid| {
+            get_connection_health(
+                State(state.clone()),
+                OperatorAuth,
+                TenantExtension(crate::auth::TenantId(tenant_id)),
+                Path(id),
+            )
+        };
+
+        let (_, Json(created)) = create_connection(
+            State(state.clone()),
+            OperatorAuth,
+            TenantExtension(crate::auth::TenantId(tenant_id)),
+            Json(CreateConnectionRequest {
+                provider: "rss".to_string(),
+                display_name: None,
+                metadata: serde_json::json!({"feed_url": "https://example.com/health.xml"})
+                    .as_object()
+                    .unwrap()
+                    .clone(),
+                secret: None,
+            }),
+        )
+        .await
+        .unwrap();
+
+        // Feeds have no credentials to check
+        let Json(report) = health(created.id).await.unwrap();
+        assert_eq!(report.provider, "rss");
+        assert_eq!(report.token_valid, None);
+        assert!(report.last_sync.is_none());
+
+        let now = Utc::now().fixed_offset();
+        for (status, finished_at) in [
+            ("succeeded", now - chrono::Duration::hours(1)),
+            ("failed", now),
+        ] {
+            sync_job::ActiveModel {
+                id: Set(Uuid::new_v4()),
+                tenant_id: Set(tenant_id),
+                provider_slug: Set("rss".to_string()),
+                connection_id: Set(created.id),
+                job_type: Set("incremental".to_string()),
+                status: Set(status.to_string()),
+                priority: Set(0),
+                attempts: Set(1),
+                scheduled_at: Set(finished_at),
+                finished_at: Set(Some(finished_at)),
+                error: Set((status == "failed").then(|| serde_json::json!({"type": "transient"}))),
+                created_at: Set(finished_at),
+                updated_at: Set(finished_at),
+                ..Default::default()
+            }
+            .insert(&db)
+            .await
+            .unwrap();
+        }
+
+        let Json(report) = health(created.id).await.unwrap();
+        let last_sync = report.last_sync.unwrap();
+        assert_eq!(last_sync.status, "failed");
+        assert!(last_sync.error.is_some());
+
+        let missing = health(Uuid::new_v4()).await.unwrap_err();
+        assert_eq!(missing.status, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn list_connection_runs_returns_run_history_newest_first() {
+        use crate::db::init_pool;
+        use crate::models::{sync_job, sync_job_run, tenant};
+        use crate::repositories::{NewSyncJobRun, ProviderRepository};
+        use migration::{Migrator, MigratorTrait};
+        use sea_orm::ActiveModelTrait;
+
+        let config = AppConfig {
+            profile: "test".to_string(),
+            ..Default::default()
+        };
+        Registry::initialize(&config);
+        let db = init_pool(&config).await.expect("Failed to init test DB");
+        Migrator::up(&db, None).await.unwrap();
+        let tenant_id = uuid::Uuid::new_v4();
+        tenant::ActiveModel {
+            id: Set(tenant_id),
+            ..Default::default()
+        }
+        .insert(&db)
+        .await
+        .unwrap();
+        ProviderRepository::new(Arc::new(db.clone()))
+            .upsert("rss", "RSS", "none")
+            .await
+            .unwrap();
+        let state = crate::server::create_test_app_state(config, db.clone());
+        let runs = |id: Uuid, limit: Option<i64>| {
+            list_connection_runs(
+                State(state.clone()),
+                OperatorAuth,
+                TenantExtension(crate::auth::TenantId(tenant_id)),
+                Path(id),
+                Query(ListSyncRunsQuery { limit }),
+            )
+        };
+
+        let (_, Json(created)) = create_connection(
+            State(state.clone()),
+            OperatorAuth,
+            TenantExtension(crate::auth::TenantId(tenant_id)),
+            Json(CreateConnectionRequest {
+                provider: "rss".to_string(),
+                display_name: None,
+                metadata: serde_json::json!({"feed_url": "https://example.com/runs.xml"})
+                    .as_object()
+                    .unwrap()
+                    .clone(),
+                secret: None,
+            }),
+        )
+        .await
+        .unwrap();
+        let Json(empty) = runs(created.id, None).await.unwrap();
+        assert!(empty.runs.is_empty());
+
+        let now = Utc::now();
+        let mut job = sync_job::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            tenant_id: Set(tenant_id),
+            provider_slug: Set("rss".to_string()),
+            connection_id: Set(created.id),
+            job_type: Set("incremental".to_string()),
+            status: Set("succeeded".to_string()),
+            priority: Set(0),
+            attempts: Set(1),
+            scheduled_at: Set(now.fixed_offset()),
+            created_at: Set(now.fixed_offset()),
+            updated_at: Set(now.fixed_offset()),
+            ..Default::default()
+        }
+        .insert(&db)
+        .await
+        .unwrap();
+        for (attempt, outcome, error) in [
+            (
+                1,
+                sync_job_run::OUTCOME_FAILED,
+                Some("HTTP error 503".to_string()),
+            ),
+            (2, sync_job_run::OUTCOME_SUCCEEDED, None),
+        ] {
+            job.attempts = attempt;
+            let started_at = now + chrono::Duration::minutes(i64::from(attempt));
+            SyncJobRunRepository::record(
+                &db,
+                NewSyncJobRun {
+                    job: &job,
+                    started_at,
+                    finished_at: started_at + chrono::Duration::seconds(3),
+                    items_processed: 7,
+                    outcome,
+                    error,
+                },
+            )
+            .await
+            .unwrap();
+        }
+
+        let Json(history) = runs(created.id, None).await.unwrap();
+        let outcomes: Vec<_> = history
+            .runs
+            .iter()
+            .map(|run| (run.attempt, run.outcome.as_str()))
+            .collect();
+        assert_eq!(outcomes, vec![(2, "succeeded"), (1, "failed")]);
+        assert_eq!(history.runs[1].error.as_deref(), Some("HTTP error 503"));
+        assert_eq!(history.runs[1].job_id, job.id);
+
+        let Json(latest) = runs(created.id, Some(1)).await.unwrap();
+        assert_eq!(latest.runs.len(), 1);
+        let invalid = runs(created.id, Some(0)).await.unwrap_err();
+        assert_eq!(invalid.status, StatusCode::BAD_REQUEST);
+        let missing = runs(Uuid::new_v4(), None).await.unwrap_err();
+        assert_eq!(missing.status, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn pause_and_resume_toggle_scheduling_status() {
+        use crate::connection_pause::AuthPausePolicy;
+        use crate::db::init_pool;
+        use crate::models::tenant;
+        use crate::repositories::ProviderRepository;
+        use migration::{Migrator, MigratorTrait};
+        use sea_orm::ActiveModelTrait;
+
+        let config = AppConfig {
+            profile: "test".to_string(),
+            ..Default::default()
+        };
+        Registry::initialize(&config);
+        let db = init_pool(&config).await.expect("Failed to init test DB");
+        Migrator::up(&db, None).await.unwrap();
+        let tenant_id = uuid::Uuid::new_v4();
+        tenant::ActiveModel {
+            id: Set(tenant_id),
+            ..Default::default()
+        }
+        .insert(&db)
+        .await
+        .unwrap();
+        ProviderRepository::new(Arc::new(db.clone()))
+            .upsert("rss", "RSS", "none")
+            .await
+            .unwrap();
+        let state = crate::server::create_test_app_state(config, db.clone());
+        let tenant = || TenantExtension(crate::auth::TenantId(tenant_id));
+
+        let (_, Json(created)) = create_connection(
+            State(state.clone()),
+            OperatorAuth,
+            tenant(),
+            Json(CreateConnectionRequest {
+                provider: "rss".to_string(),
+                display_name: None,
+                metadata: serde_json::json!({"feed_url": "https://example.com/pause.xml"})
+                    .as_object()
+                    .unwrap()
+                    .clone(),
+                secret: None,
+            }),
+        )
+        .await
+        .unwrap();
+
+        for _ in 0..2 {
+            let Json(paused) = pause_connection(
+                State(state.clone()),
+                OperatorAuth,
+                tenant(),
+                Path(created.id),
+            )
+            .await
+            .unwrap();
+            assert_eq!(paused.status, STATUS_PAUSED);
+        }
+
+        // Re-authorizing does not undo an operator's pause
+        AuthPausePolicy::new(Arc::new(db.clone()), 3)
+            .resume_after_reauthorization(created.id)
+            .await
+            .unwrap();
+        let repo = ConnectionRepository::new(Arc::new(db.clone()), state.master_keys.clone());
+        let stored = repo.find_by_id(&tenant_id, &created.id).await.unwrap();
+        assert_eq!(stored.unwrap().status, STATUS_PAUSED);
+
+        let Json(resumed) = resume_connection(
+            State(state.clone()),
+            OperatorAuth,
+            tenant(),
+            Path(created.id),
+        )
+        .await
+        .unwrap();
+        assert_eq!(resumed.status, "active");
+
+        let audit = AuditLogRepository::new(db.clone())
+            .list_for_target("connection", &created.id.to_string())
+            .await
+            .unwrap();
+        let mut actions: Vec<_> = audit.iter().map(|entry| entry.action.as_str()).collect();
+        actions.sort_unstable();
+        assert_eq!(
+            actions,
+            vec![
+                CONNECTION_CREATED_AUDIT_ACTION,
+                CONNECTION_PAUSED_AUDIT_ACTION,
+                CONNECTION_RESUMED_AUDIT_ACTION
+            ]
+        );
+
+        repo.update_by_id(
+            &tenant_id,
+            &created.id,
+            connection::ActiveModel {
+                status: Set("revoked".to_string()),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+        let conflict = pause_connection(
+            State(state.clone()),
+            OperatorAuth,
+            tenant(),
+            Path(created.id),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(conflict.status, StatusCode::CONFLICT);
+
+        let missing = resume_connection(State(state), OperatorAuth, tenant(), Path(Uuid::new_v4()))
+            .await
+            .unwrap_err();
+        assert_eq!(missing.status, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn trigger_sync_dry_runs_inline_and_guards_pending_jobs() {
+        use crate::db::init_pool;
+        use crate::models::{signal, sync_job, tenant};
+        use crate::repositories::ProviderRepository;
+        use crate::testing::{MockConnector, MockPage};
+        use migration::{Migrator, MigratorTrait};
+        use sea_orm::{ActiveModelTrait, ColumnTrait, EntityTrait, QueryFilter};
+
+        let config = AppConfig {
+            profile: "test".to_string(),
+            ..Default::default()
+        };
+        let mock = Arc::new(
+            MockConnector::new("dry-run-crm").page(
+                MockPage::new()
+                    .keyed_signal("deal_won", "deal-7", serde_json::json!({"amount": 1200}))
+                    .has_more(),
+            ),
+        );
+        mock.register(&mut Registry::global().write().unwrap());
+        let db = init_pool(&config).await.expect("Failed to init test DB");
+        Migrator::up(&db, None).await.unwrap();
+        let tenant_id = uuid::Uuid::new_v4();
+        tenant::ActiveModel {
+            id: Set(tenant_id),
+            ..Default::default()
+        }
+        .insert(&db)
+        .await
+        .unwrap();
+        ProviderRepository::new(Arc::new(db.clone()))
+            .upsert("dry-run-crm", "Dry Run CRM", "none")
+            .await
+            .unwrap();
+        let connection_id = uuid::Uuid::new_v4();
+        connection::ActiveModel {
+            id: Set(connection_id),
+            tenant_id: Set(tenant_id),
+            provider_slug: Set("dry-run-crm".to_string()),
+            external_id: Set("acme".to_string()),
+            ..Default::default()
+        }
+        .insert(&db)
+        .await
+        .unwrap();
+        let state = crate::server::create_test_app_state(config, db.clone());
+        let trigger = |dry_run: bool| {
+            trigger_sync(
+                State(state.clone()),
+                OperatorAuth,
+                TenantExtension(crate::auth::TenantId(tenant_id)),
+                Path(connection_id),
+                Query(TriggerSyncQuery { dry_run }),
+            )
+        };
+
+        let response = trigger(true).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let result: DryRunSyncResponse = serde_json::from_slice(&body).unwrap();
+        assert_eq!(result.provider, "dry-run-crm");
+        assert_eq!(result.signals.len(), 1);
+        assert_eq!(result.signals[0].kind, "deal_won");
+        assert_eq!(result.signals[0].dedupe_key.as_deref(), Some("deal-7"));
+        assert!(result.has_more);
+        let stored = signal::Entity::find()
+            .filter(signal::Column::ConnectionId.eq(connection_id))
+            .all(&db)
+            .await
+            .unwrap();
+        assert!(stored.is_empty());
+
+        // Without the flag the sync is left to the executor
+        let response = trigger(false).await.unwrap();
+        assert_eq!(response.status(), StatusCode::ACCEPTED);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let job: JobInfo = serde_json::from_slice(&body).unwrap();
+        let jobs = sync_job::Entity::find()
+            .filter(sync_job::Column::ConnectionId.eq(connection_id))
+            .all(&db)
+            .await
+            .unwrap();
+        assert_eq!(jobs.len(), 1);
+        assert_eq!(jobs[0].id.to_string(), job.id);
+        assert_eq!(jobs[0].job_type, "incremental");
+        assert_eq!(jobs[0].priority, MANUAL_SYNC_PRIORITY);
+        assert_eq!(mock.sync_calls(), 1);
+
+        // The pending job blocks a second trigger
+        let conflict = trigger(false).await.unwrap_err();
+        assert_eq!(conflict.status, StatusCode::CONFLICT);
+        assert_eq!(&*conflict.code, "SYNC_ALREADY_PENDING");
+        assert_eq!(conflict.details.unwrap()["job_id"], job.id);
+    }
+
+    #[tokio::test]
+    async fn needs_reauth_reports_reason_and_blocks_resume() {
+        use crate::connection_pause::AuthPausePolicy;
+        use crate::db::init_pool;
+        use crate::models::tenant;
+        use crate::repositories::ProviderRepository;
+        use migration::{Migrator, MigratorTrait};
+        use sea_orm::ActiveModelTrait;
+
+        let config = AppConfig {
+            profile: "test".to_string(),
+            ..Default::default()
+        };
+        Registry::initialize(&config);
+        let db = init_pool(&config).await.expect("Failed to init test DB");
+        Migrator::up(&db, None).await.unwrap();
+        let tenant_id = uuid::Uuid::new_v4();
+        tenant::ActiveModel {
+            id: Set(tenant_id),
+            ..Default::default()
+        }
+        .insert(&db)
+        .await
+        .unwrap();
+        ProviderRepository::new(Arc::new(db.clone()))
+            .upsert("rss", "RSS", "none")
+            .await
+            .unwrap();
+        let state = crate::server::create_test_app_state(config, db.clone());
+        let tenant = || TenantExtension(crate::auth::TenantId(tenant_id));
+
+        let (_, Json(created)) = create_connection(
+            State(state.clone()),
+            OperatorAuth,
+            tenant(),
+            Json(CreateConnectionRequest {
+                provider: "rss".to_string(),
+                display_name: None,
+                metadata: serde_json::json!({"feed_url": "https://example.com/reauth.xml"})
+                    .as_object()
+                    .unwrap()
+                    .clone(),
+                secret: None,
+            }),
+        )
+        .await
+        .unwrap();
+        assert_eq!(created.status, "active");
+        assert!(created.status_reason.is_none());
+
+        let policy = AuthPausePolicy::new(Arc::new(db.clone()), 3);
+        policy
+            .mark_needs_reauth(created.id, "invalid_grant: token revoked")
+            .await
+            .unwrap();
+        let repo = ConnectionRepository::new(Arc::new(db.clone()), state.master_keys.clone());
+        let stored = repo
+            .find_by_id(&tenant_id, &created.id)
+            .await
+            .unwrap()
+            .unwrap();
+        let info = ConnectionInfo::from(stored);
+        assert_eq!(info.status, STATUS_NEEDS_REAUTH);
+        assert_eq!(
+            info.status_reason.as_deref(),
+            Some("invalid_grant: token revoked")
+        );
+
+        // Operators cannot resume it; only re-authorizing does
+        let conflict = resume_connection(
+            State(state.clone()),
+            OperatorAuth,
+            tenant(),
+            Path(created.id),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(conflict.status, StatusCode::CONFLICT);
+        policy
+            .resume_after_reauthorization(created.id)
+            .await
+            .unwrap();
+        let stored = repo
+            .find_by_id(&tenant_id, &created.id)
+            .await
+            .unwrap()
+            .unwrap();
+        let info = ConnectionInfo::from(stored);
+        assert_eq!(info.status, "active");
+        assert!(info.status_reason.is_none());
+    }
 }
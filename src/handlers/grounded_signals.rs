@@ -15,6 +15,7 @@ use uuid::Uuid;
 
 /// Query parameters for listing grounded signals
 #[derive(Debug, serde::Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct ListGroundedSignalsParams {
     /// Tenant ID (required)
     #[param(style = Simple, example = "550e8400-e29b-41d4-a716-446655440000")]
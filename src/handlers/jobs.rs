@@ -9,7 +9,7 @@ use crate::models::sync_job;
 use crate::repositories::SyncJobRepository;
 use crate::server::AppState;
 use axum::{
-    extract::{Query, State},
+    extract::{Path, Query, State},
     http::StatusCode,
     response::Json,
 };
@@ -22,7 +22,7 @@ use uuid::Uuid;
 /// Query parameters for listing jobs
 #[derive(Debug, Deserialize)]
 pub struct ListJobsQuery {
-    /// Filter by job status (one of: queued, running, succeeded, failed)
+    /// Filter by job status (one of: queued, running, cancelling, cancelled, succeeded, failed)
     pub status: Option<String>,
     /// Filter by provider slug
     pub provider: Option<String>,
@@ -46,6 +46,8 @@ pub struct ListJobsQuery {
 pub enum JobStatusParam {
     Queued,
     Running,
+    Cancelling,
+    Cancelled,
     Succeeded,
     Failed,
 }
@@ -202,12 +204,14 @@ pub async fn list_jobs(
     // Validate and parse status filter
     let status_filter = if let Some(status_str) = &params.status {
         match status_str.as_str() {
-            "queued" | "running" | "succeeded" | "failed" => Some(status_str.clone()),
+            "queued" | "running" | "cancelling" | "cancelled" | "succeeded" | "failed" => {
+                Some(status_str.clone())
+            }
             _ => {
                 return Err(validation_error(
                     "Invalid status",
                     serde_json::json!({
-                        "status": "Must be one of: queued, running, succeeded, failed"
+                        "status": "Must be one of: queued, running, cancelling, cancelled, succeeded, failed"
                     }),
                 ));
             }
@@ -324,6 +328,45 @@ pub async fn list_jobs(
     Ok(Json(response))
 }
 
+/// Cancel a job
+///
+/// A queued job is cancelled at once. A running job moves to `cancelling`; the
+/// executor signals the connector, which stops at its next page boundary, and
+/// the job ends `cancelled` with the signals and cursor of the pages already
+/// fetched committed. Repeating the call while the job is `cancelling` returns
+/// it unchanged.
+#[utoipa::path(
+    post,
+    path = "/jobs/{id}/cancel",
+    security(("bearer_auth" = [])),
+    params(
+        ("id" = Uuid, Path, description = "Job ID")
+    ),
+    responses(
+        (status = 200, description = "Job cancelled or cancelling", body = JobInfo),
+        (status = 401, description = "Missing or invalid bearer token", body = ApiError),
+        (status = 404, description = "Job not found", body = ApiError),
+        (status = 409, description = "Job already finished", body = ApiError, example = json!({
+            "code": "JOB_NOT_CANCELLABLE",
+            "message": "Job is succeeded and cannot be cancelled",
+            "retry_after": null
+        })),
+        (status = 500, description = "Internal server error", body = ApiError)
+    ),
+    tag = "jobs"
+)]
+pub async fn cancel_job(
+    State(state): State<AppState>,
+    _operator_auth: OperatorAuth,
+    TenantExtension(tenant): TenantExtension,
+    Path(id): Path<Uuid>,
+) -> Result<Json<JobInfo>, ApiError> {
+    let job = SyncJobRepository::new(state.db.clone())
+        .cancel_job(tenant.0, id)
+        .await?;
+    Ok(Json(JobInfo::from(job)))
+}
+
 /// Encode job cursor data to standardized base64 string
 fn encode_job_cursor(scheduled_at: DateTimeWithTimeZone, id: Uuid) -> String {
     let keys = serde_json::json!({
@@ -504,10 +547,14 @@ mod tests {
             status: Set(status.to_string()),
             priority: Set(50),
             attempts: Set(0),
+            rate_limited_attempts: Set(0),
             scheduled_at: Set(scheduled_at),
             retry_after: Set(None),
             started_at: Set(None),
             finished_at: Set(None),
+            locked_at: Set(None),
+            heartbeat_at: Set(None),
+            locked_by: Set(None),
             cursor: Set(None),
             error: Set(None),
             created_at: Set(scheduled_at),
@@ -1025,4 +1072,64 @@ mod tests {
         // Should only return jobs for tenant1
         assert_eq!(jobs_response.jobs.len(), 1);
     }
+
+    #[tokio::test]
+    async fn test_cancel_job_flags_running_job_and_rejects_finished_ones() {
+        let (state, db, tenant_id) = setup_test_app().await;
+        let connection_id = Uuid::new_v4();
+        let scheduled_at = Utc::now().fixed_offset();
+        let provider = format!("github-{}", Uuid::new_v4());
+
+        create_test_provider(&db, &provider, "GitHub", "oauth2")
+            .await
+            .expect("Failed to create provider");
+        create_test_connection(&db, tenant_id, &provider, Some(connection_id))
+            .await
+            .expect("Failed to create test connection");
+        let running = create_test_job(
+            &db,
+            tenant_id,
+            &provider,
+            connection_id,
+            "running",
+            "full",
+            scheduled_at,
+        )
+        .await;
+        let succeeded = create_test_job(
+            &db,
+            tenant_id,
+            &provider,
+            connection_id,
+            "succeeded",
+            "full",
+            scheduled_at,
+        )
+        .await;
+
+        let app = crate::server::create_app(state);
+        let cancel = |id: Uuid| {
+            Request::builder()
+                .method("POST")
+                .uri(format!("/jobs/{}/cancel", id))
+                .header(header::AUTHORIZATION, "Bearer test-token-123")
+                .header("X-Tenant-Id", tenant_id.to_string())
+                .body(Body::empty())
+                .unwrap()
+        };
+
+        let response = app.clone().oneshot(cancel(running.id)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let job: JobInfo = serde_json::from_slice(&body).unwrap();
+        assert_eq!(job.status, "cancelling");
+
+        let response = app.clone().oneshot(cancel(succeeded.id)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+
+        let response = app.clone().oneshot(cancel(Uuid::new_v4())).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
 }
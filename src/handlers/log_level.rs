@@ -0,0 +1,178 @@
+//! # Log Level Handlers
+//!
+//! This module lets operators inspect and change the global log filter and the
+//! debug sampling rate at runtime, without restarting the service.
+
+use std::collections::BTreeMap;
+
+use crate::auth::OperatorAuth;
+use crate::error::ApiError;
+use crate::server::AppState;
+use crate::telemetry::{self, LogFilterError};
+use axum::{extract::State, http::StatusCode, response::Json};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Levels accepted for the default and for module overrides
+const LEVELS: [&str; 6] = ["trace", "debug", "info", "warn", "error", "off"];
+
+/// Active log filter and sampling rate
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct LogLevelState {
+    /// Active filter directives in `EnvFilter` syntax; `null` when the global subscriber is not managed by this process
+    #[schema(example = "info,connectors::sync_executor=debug")]
+    pub filter: Option<String>,
+    /// One in this many sampled executor and webhook debug events is emitted
+    #[schema(example = 1)]
+    pub debug_sample_rate: u32,
+}
+
+/// Replacement log filter
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct LogLevelUpdate {
+    /// Level for targets without an override (default: the configured `POBLYSH_LOG_LEVEL`)
+    #[schema(example = "info")]
+    pub level: Option<String>,
+    /// Per-module levels keyed by target, e.g. `connectors::sync_executor`; replaces all previous overrides
+    #[serde(default)]
+    pub modules: BTreeMap<String, String>,
+    /// New debug sampling rate; unchanged when omitted
+    #[schema(example = 10)]
+    pub debug_sample_rate: Option<u32>,
+}
+
+/// Show the active log filter and debug sampling rate
+#[utoipa::path(
+    get,
+    path = "/admin/log-level",
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Active log settings", body = LogLevelState),
+        (status = 401, description = "Missing or invalid operator credentials", body = ApiError)
+    ),
+    tag = "operators"
+)]
+pub async fn get_log_level(_operator_auth: OperatorAuth) -> Json<LogLevelState> {
+    Json(LogLevelState {
+        filter: telemetry::current_log_filter(),
+        debug_sample_rate: telemetry::debug_sample_rate(),
+    })
+}
+
+/// Replace the log filter and optionally the debug sampling rate
+///
+/// Takes effect immediately for every request and background task. The
+/// change is not persisted; a restart returns to the configured level.
+#[utoipa::path(
+    put,
+    path = "/admin/log-level",
+    security(("bearer_auth" = [])),
+    request_body = LogLevelUpdate,
+    responses(
+        (status = 200, description = "Updated log settings", body = LogLevelState),
+        (status = 400, description = "Unknown level, invalid module name or sampling rate of 0", body = ApiError),
+        (status = 401, description = "Missing or invalid operator credentials", body = ApiError),
+        (status = 503, description = "Log filter is not reloadable in this process", body = ApiError)
+    ),
+    tag = "operators"
+)]
+pub async fn update_log_level(
+    State(state): State<AppState>,
+    _operator_auth: OperatorAuth,
+    Json(update): Json<LogLevelUpdate>,
+) -> Result<Json<LogLevelState>, ApiError> {
+    if update.debug_sample_rate == Some(0) {
+        return Err(ApiError::bad_request(
+            "debug_sample_rate must be at least 1",
+        ));
+    }
+    // The configured value may already be a directive list such as `info,sqlx=warn`
+    let base = match update.level.as_deref() {
+        Some(level) => normalize_level(level)?,
+        None => state.config.log_level.clone(),
+    };
+    let directives = build_directives(base, &update.modules)?;
+
+    let filter = telemetry::set_log_filter(&directives).map_err(|e| match e {
+        LogFilterError::Invalid(message) => ApiError::bad_request(message),
+        LogFilterError::Unavailable(message) => ApiError::new(
+            StatusCode::SERVICE_UNAVAILABLE,
+            "SERVICE_UNAVAILABLE",
+            message,
+        ),
+    })?;
+    if let Some(rate) = update.debug_sample_rate {
+        telemetry::set_debug_sample_rate(rate);
+    }
+
+    tracing::info!(
+        filter = %filter,
+        debug_sample_rate = telemetry::debug_sample_rate(),
+        "Log settings changed"
+    );
+    Ok(Json(LogLevelState {
+        filter: Some(filter),
+        debug_sample_rate: telemetry::debug_sample_rate(),
+    }))
+}
+
+/// Append module overrides to the base directives.
+fn build_directives(base: String, modules: &BTreeMap<String, String>) -> Result<String, ApiError> {
+    let mut directives = vec![base];
+    for (module, module_level) in modules {
+        let valid_module = !module.is_empty()
+            && module
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == ':' || c == '-');
+        if !valid_module {
+            return Err(ApiError::bad_request(format!(
+                "Invalid module name '{}'",
+                module
+            )));
+        }
+        directives.push(format!("{}={}", module, normalize_level(module_level)?));
+    }
+    Ok(directives.join(","))
+}
+
+fn normalize_level(level: &str) -> Result<String, ApiError> {
+    let level = level.trim().to_ascii_lowercase();
+    if LEVELS.contains(&level.as_str()) {
+        Ok(level)
+    } else {
+        Err(ApiError::bad_request(format!(
+            "Unknown log level '{}'; expected one of {}",
+            level,
+            LEVELS.join(", ")
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_directives_orders_module_overrides() {
+        let modules = BTreeMap::from([
+            ("connectors::sync_executor".to_string(), "DEBUG".to_string()),
+            (
+                "connectors::handlers::webhooks".to_string(),
+                "trace".to_string(),
+            ),
+        ]);
+        assert_eq!(
+            build_directives("warn".to_string(), &modules).unwrap(),
+            "warn,connectors::handlers::webhooks=trace,connectors::sync_executor=debug"
+        );
+    }
+
+    #[test]
+    fn test_build_directives_rejects_invalid_input() {
+        assert!(normalize_level("loud").is_err());
+        let modules = BTreeMap::from([("connectors=debug,".to_string(), "info".to_string())]);
+        assert!(build_directives("info".to_string(), &modules).is_err());
+        let modules = BTreeMap::from([("connectors".to_string(), "verbose".to_string())]);
+        assert!(build_directives("info".to_string(), &modules).is_err());
+    }
+}
@@ -2,13 +2,22 @@
 //!
 //! This module contains all the HTTP endpoint handlers for the Connectors API.
 
+pub mod admin;
 pub mod config;
 pub mod connect;
 pub mod connections;
 pub mod grounded_signals;
 pub mod jobs;
+pub mod log_level;
+pub mod organizations;
+pub mod provider_catalog;
 pub mod providers;
+pub mod schedule;
+pub mod signal_duplicates;
+pub mod signal_kinds;
 pub mod signals;
+pub mod slo;
+pub mod stats;
 pub mod tenants;
 pub mod types;
 pub mod webhooks;
@@ -65,22 +74,10 @@ impl Default for HealthResponse {
 pub struct ReadinessResponse {
     /// Service readiness status
     pub status: String,
-    /// Dependency status checks
+    /// Dependency status checks, plus the state of each supervised subsystem under `subsystems`
     pub checks: serde_json::Value,
 }
 
-impl ReadinessResponse {
-    fn healthy() -> Self {
-        Self {
-            status: "ready".to_string(),
-            checks: serde_json::json!({
-                "database": "ok",
-                "migrations": "ok"
-            }),
-        }
-    }
-}
-
 /// Health check endpoint (public, no auth required)
 #[utoipa::path(
     get,
@@ -151,8 +148,21 @@ pub async fn ready(State(state): State<AppState>) -> Result<Json<ReadinessRespon
         }
     }
 
+    // Gate on the supervised subsystems once a supervisor has registered them
+    let subsystems = crate::supervisor::health();
+    if !subsystems.is_empty() {
+        all_healthy &= subsystems.is_ready();
+        checks.insert(
+            "subsystems".to_string(),
+            serde_json::to_value(subsystems.snapshot()).unwrap_or_default(),
+        );
+    }
+
     if all_healthy {
-        Ok(Json(ReadinessResponse::healthy()))
+        Ok(Json(ReadinessResponse {
+            status: "ready".to_string(),
+            checks: serde_json::Value::Object(checks),
+        }))
     } else {
         let mut api_err = ApiError::new(
             StatusCode::SERVICE_UNAVAILABLE,
@@ -0,0 +1,152 @@
+//! # Organizations API Handlers
+//!
+//! Organizations group tenants and define default settings (scoring weights,
+//! spam lists, webhook rate limits) that member tenants inherit unless they
+//! override them. Resolution lives in [`crate::config_resolution`].
+
+use crate::auth::{OperatorAuth, TenantExtension};
+use crate::config_resolution::ConfigOverrides;
+use crate::error::ApiError;
+use crate::models::organization::Model as OrganizationModel;
+use crate::repositories::OrganizationRepository;
+use crate::server::AppState;
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Json,
+};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// Request body for creating an organization
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateOrganizationRequest {
+    /// Display name (required, max 255 characters)
+    #[schema(example = "Acme Holdings")]
+    pub name: String,
+    /// Default settings inherited by member tenants
+    #[serde(default)]
+    pub settings: ConfigOverrides,
+}
+
+/// An organization and its default settings
+#[derive(Debug, Serialize, ToSchema)]
+pub struct OrganizationResponse {
+    /// Organization identifier
+    #[schema(value_type = String)]
+    pub id: Uuid,
+    /// Display name
+    #[schema(example = "Acme Holdings")]
+    pub name: String,
+    /// Default settings inherited by member tenants
+    pub settings: ConfigOverrides,
+    /// Timestamp when the organization was created (RFC3339)
+    pub created_at: String,
+    /// Timestamp when the organization was last updated (RFC3339)
+    pub updated_at: String,
+}
+
+impl From<OrganizationModel> for OrganizationResponse {
+    fn from(organization: OrganizationModel) -> Self {
+        Self {
+            id: organization.id,
+            name: organization.name,
+            settings: ConfigOverrides::from_json(organization.settings.as_ref()),
+            created_at: organization.created_at.to_rfc3339(),
+            updated_at: organization.updated_at.to_rfc3339(),
+        }
+    }
+}
+
+/// Create an organization
+#[utoipa::path(
+    post,
+    path = "/api/v1/organizations",
+    security(("bearer_auth" = [])),
+    request_body = CreateOrganizationRequest,
+    responses(
+        (status = 201, description = "Organization created", body = OrganizationResponse),
+        (status = 400, description = "Validation failed", body = ApiError),
+        (status = 401, description = "Missing or invalid bearer token", body = ApiError)
+    ),
+    tag = "organizations"
+)]
+pub async fn create_organization(
+    State(state): State<AppState>,
+    _operator_auth: OperatorAuth,
+    TenantExtension(_tenant): TenantExtension,
+    Json(request): Json<CreateOrganizationRequest>,
+) -> Result<(StatusCode, Json<OrganizationResponse>), ApiError> {
+    let organization = OrganizationRepository::new(&state.db)
+        .create(&request.name, &request.settings)
+        .await?;
+
+    Ok((StatusCode::CREATED, Json(organization.into())))
+}
+
+/// Get an organization by ID
+#[utoipa::path(
+    get,
+    path = "/api/v1/organizations/{id}",
+    security(("bearer_auth" = [])),
+    params(
+        ("id" = Uuid, Path, description = "Organization UUID")
+    ),
+    responses(
+        (status = 200, description = "Organization retrieved", body = OrganizationResponse),
+        (status = 401, description = "Missing or invalid bearer token", body = ApiError),
+        (status = 404, description = "Organization not found", body = ApiError)
+    ),
+    tag = "organizations"
+)]
+pub async fn get_organization(
+    State(state): State<AppState>,
+    _operator_auth: OperatorAuth,
+    TenantExtension(_tenant): TenantExtension,
+    Path(organization_id): Path<Uuid>,
+) -> Result<Json<OrganizationResponse>, ApiError> {
+    let organization = OrganizationRepository::new(&state.db)
+        .get(organization_id)
+        .await?
+        .ok_or_else(|| {
+            ApiError::new(
+                StatusCode::NOT_FOUND,
+                "NOT_FOUND",
+                format!("Organization {} not found", organization_id),
+            )
+        })?;
+
+    Ok(Json(organization.into()))
+}
+
+/// Replace an organization's default settings
+#[utoipa::path(
+    put,
+    path = "/api/v1/organizations/{id}/settings",
+    security(("bearer_auth" = [])),
+    params(
+        ("id" = Uuid, Path, description = "Organization UUID")
+    ),
+    request_body = ConfigOverrides,
+    responses(
+        (status = 200, description = "Settings updated", body = OrganizationResponse),
+        (status = 400, description = "Validation failed", body = ApiError),
+        (status = 401, description = "Missing or invalid bearer token", body = ApiError),
+        (status = 404, description = "Organization not found", body = ApiError)
+    ),
+    tag = "organizations"
+)]
+pub async fn update_organization_settings(
+    State(state): State<AppState>,
+    _operator_auth: OperatorAuth,
+    TenantExtension(_tenant): TenantExtension,
+    Path(organization_id): Path<Uuid>,
+    Json(settings): Json<ConfigOverrides>,
+) -> Result<Json<OrganizationResponse>, ApiError> {
+    let organization = OrganizationRepository::new(&state.db)
+        .update_settings(organization_id, &settings)
+        .await?;
+
+    Ok(Json(organization.into()))
+}
@@ -0,0 +1,79 @@
+//! # Provider Catalog API Handlers
+//!
+//! This module reports drift between the providers table and the providers
+//! defined in code.
+
+use crate::auth::OperatorAuth;
+use crate::connectors::Registry;
+use crate::error::ApiError;
+use crate::seeds::{self, CatalogReport, provider::registry_catalog};
+use crate::server::AppState;
+use axum::{extract::State, response::Json};
+
+/// Differences between the providers table and the providers defined in code
+///
+/// The catalog combines the built-in providers with every connector registered
+/// in this process. The report is computed on each request and never changes
+/// the table; `added` is always empty here and only filled by the startup
+/// reconciliation.
+#[utoipa::path(
+    get,
+    path = "/admin/providers/drift",
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Provider catalog drift report", body = CatalogReport),
+        (status = 401, description = "Missing or invalid operator credentials", body = ApiError),
+        (status = 500, description = "Internal server error", body = ApiError)
+    ),
+    tag = "operators"
+)]
+pub async fn get_provider_drift(
+    State(state): State<AppState>,
+    _operator_auth: OperatorAuth,
+) -> Result<Json<CatalogReport>, ApiError> {
+    let catalog = registry_catalog(&Registry::global().read().unwrap());
+    let report = seeds::detect_drift(&state.db, &catalog)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to compare provider catalog: {}", e);
+            ApiError::internal_server_error("Failed to load provider drift report")
+        })?;
+
+    Ok(Json(report))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::AppConfig;
+    use crate::db::init_pool;
+    use crate::repositories::ProviderRepository;
+    use migration::{Migrator, MigratorTrait};
+    use uuid::Uuid;
+
+    #[tokio::test]
+    async fn test_provider_drift_reports_unknown_rows() {
+        let config = AppConfig {
+            profile: "test".to_string(),
+            ..Default::default()
+        };
+        let db = init_pool(&config).await.expect("Failed to init test DB");
+        Migrator::up(&db, None).await.unwrap();
+
+        let slug = format!("retired-{}", Uuid::new_v4());
+        let repo = ProviderRepository::new(std::sync::Arc::new(db.clone()));
+        repo.insert_if_absent(&slug, "Retired", "oauth2")
+            .await
+            .unwrap();
+
+        let state = crate::server::create_test_app_state(config, db);
+        let Json(report) = get_provider_drift(State(state), OperatorAuth)
+            .await
+            .unwrap();
+        assert!(report.added.is_empty());
+        assert!(report.removed.contains(&slug));
+        assert!(!report.is_clean());
+
+        repo.delete_by_slug(&slug).await.unwrap();
+    }
+}
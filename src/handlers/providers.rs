@@ -2,11 +2,17 @@
 //!
 //! This module contains handlers for the providers endpoints.
 
+use crate::circuit_breaker;
+use crate::connectors::ProviderCapabilities;
+use crate::connectors::ProviderCategory;
+use crate::connectors::metadata_schema::{has_metadata_schema, metadata_schema};
+use crate::connectors::registry::{Registry, RegistryError};
 use crate::cursor::{decode_generic_cursor, encode_generic_cursor};
 use crate::error::ApiError;
 use crate::server::AppState;
 use axum::{
-    extract::{Query, State},
+    extract::{Path, Query, State},
+    http::StatusCode,
     response::Json,
 };
 use serde::{Deserialize, Serialize};
@@ -14,6 +20,7 @@ use utoipa::{IntoParams, ToSchema};
 
 /// Query parameters for providers listing
 #[derive(Debug, Deserialize, IntoParams, ToSchema)]
+#[into_params(parameter_in = Query)]
 pub struct ListProvidersQuery {
     /// Maximum number of providers to return (default: 50, max: 100)
     pub limit: Option<i64>,
@@ -32,6 +39,14 @@ pub struct ProviderInfo {
     pub scopes: Vec<String>,
     /// Whether this provider supports webhook events
     pub webhooks: bool,
+    /// One-line description for the connect catalog
+    pub description: String,
+    /// URL of the provider's icon
+    pub icon_url: Option<String>,
+    /// URL of the provider's integration documentation
+    pub docs_url: Option<String>,
+    /// Catalog category used to group providers
+    pub category: ProviderCategory,
 }
 
 /// Response containing the list of available providers
@@ -55,13 +70,21 @@ pub struct ProvidersResponse {
                     "name": "github",
                     "auth_type": "oauth2",
                     "scopes": ["repo", "user:email", "read:org"],
-                    "webhooks": true
+                    "webhooks": true,
+                    "description": "Sync repositories, issues and pull requests from GitHub",
+                    "icon_url": "https://cdn.simpleicons.org/github",
+                    "docs_url": "https://docs.github.com/en/apps/oauth-apps",
+                    "category": "code"
                 },
                 {
                     "name": "slack",
                     "auth_type": "oauth2",
-                    "scopes": ["channels:read", "chat:write", "users:read"],
-                    "webhooks": true
+                    "scopes": ["channels:history", "channels:read", "groups:history", "groups:read", "reactions:read"],
+                    "webhooks": true,
+                    "description": "Sync channel conversations from Slack",
+                    "icon_url": "https://cdn.simpleicons.org/slack",
+                    "docs_url": "https://api.slack.com/docs",
+                    "category": "chat"
                 }
             ],
             "next_cursor": null
@@ -110,6 +133,22 @@ pub async fn list_providers(
 
     // Static list for MVP - will be replaced with registry in future changes
     let mut providers = vec![
+        ProviderInfo {
+            name: "confluence".to_string(),
+            auth_type: "oauth2".to_string(),
+            scopes: crate::connectors::confluence::CONFLUENCE_SCOPES
+                .iter()
+                .map(|s| s.to_string())
+                .collect(),
+            webhooks: true,
+            description: "Sync page, blog post and comment activity from Confluence Cloud"
+                .to_string(),
+            icon_url: Some("https://cdn.simpleicons.org/confluence".to_string()),
+            docs_url: Some(
+                "https://developer.atlassian.com/cloud/confluence/oauth-2-3lo-apps/".to_string(),
+            ),
+            category: ProviderCategory::Files,
+        },
         ProviderInfo {
             name: "github".to_string(),
             auth_type: "oauth2".to_string(),
@@ -119,22 +158,197 @@ pub async fn list_providers(
                 "read:org".to_string(),
             ],
             webhooks: true,
+            description: "Sync repositories, issues and pull requests from GitHub".to_string(),
+            icon_url: Some("https://cdn.simpleicons.org/github".to_string()),
+            docs_url: Some("https://docs.github.com/en/apps/oauth-apps".to_string()),
+            category: ProviderCategory::Code,
+        },
+        ProviderInfo {
+            name: "gitlab".to_string(),
+            auth_type: "oauth2".to_string(),
+            scopes: crate::connectors::gitlab::GITLAB_SCOPES
+                .iter()
+                .map(|s| s.to_string())
+                .collect(),
+            webhooks: true,
+            description: "Sync issues and merge requests from GitLab".to_string(),
+            icon_url: Some("https://cdn.simpleicons.org/gitlab".to_string()),
+            docs_url: Some("https://docs.gitlab.com/ee/api/oauth2.html".to_string()),
+            category: ProviderCategory::Code,
         },
         ProviderInfo {
             name: "slack".to_string(),
             auth_type: "oauth2".to_string(),
-            scopes: vec![
-                "channels:read".to_string(),
-                "chat:write".to_string(),
-                "users:read".to_string(),
-            ],
+            scopes: crate::connectors::slack::SLACK_SCOPES
+                .iter()
+                .map(|s| s.to_string())
+                .collect(),
             webhooks: true,
+            description: "Sync channel conversations from Slack".to_string(),
+            icon_url: Some("https://cdn.simpleicons.org/slack".to_string()),
+            docs_url: Some("https://api.slack.com/docs".to_string()),
+            category: ProviderCategory::Chat,
         },
         ProviderInfo {
             name: "jira".to_string(),
             auth_type: "oauth2".to_string(),
             scopes: vec!["read:jira-work".to_string(), "read:jira-user".to_string()],
             webhooks: true,
+            description: "Sync issues and project activity from Jira Cloud".to_string(),
+            icon_url: Some("https://cdn.simpleicons.org/jira".to_string()),
+            docs_url: Some("https://developer.atlassian.com/cloud/jira/platform/".to_string()),
+            category: ProviderCategory::Code,
+        },
+        ProviderInfo {
+            name: "linear".to_string(),
+            auth_type: "oauth2".to_string(),
+            scopes: crate::connectors::linear::LINEAR_SCOPES
+                .iter()
+                .map(|s| s.to_string())
+                .collect(),
+            webhooks: true,
+            description: "Sync issues and their workflow state from Linear".to_string(),
+            icon_url: Some("https://cdn.simpleicons.org/linear".to_string()),
+            docs_url: Some("https://developers.linear.app/docs".to_string()),
+            category: ProviderCategory::Code,
+        },
+        ProviderInfo {
+            name: "notion".to_string(),
+            auth_type: "oauth2".to_string(),
+            scopes: vec![],
+            webhooks: false,
+            description: "Sync page, database and comment activity from Notion".to_string(),
+            icon_url: Some("https://cdn.simpleicons.org/notion".to_string()),
+            docs_url: Some("https://developers.notion.com/docs/authorization".to_string()),
+            category: ProviderCategory::Files,
+        },
+        ProviderInfo {
+            name: "outlook".to_string(),
+            auth_type: "oauth2".to_string(),
+            scopes: crate::connectors::outlook::OUTLOOK_SCOPES
+                .iter()
+                .map(|s| s.to_string())
+                .collect(),
+            webhooks: false,
+            description: "Sync received and sent email from Outlook and Microsoft 365".to_string(),
+            icon_url: Some("https://cdn.simpleicons.org/microsoftoutlook".to_string()),
+            docs_url: Some(
+                "https://learn.microsoft.com/en-us/graph/delta-query-messages".to_string(),
+            ),
+            category: ProviderCategory::Mail,
+        },
+        ProviderInfo {
+            name: "outlook-calendar".to_string(),
+            auth_type: "oauth2".to_string(),
+            scopes: crate::connectors::outlook_calendar::OUTLOOK_CALENDAR_SCOPES
+                .iter()
+                .map(|s| s.to_string())
+                .collect(),
+            webhooks: false,
+            description:
+                "Sync events and schedule changes from Outlook and Microsoft 365 calendars"
+                    .to_string(),
+            icon_url: Some("https://cdn.simpleicons.org/microsoftoutlook".to_string()),
+            docs_url: Some(
+                "https://learn.microsoft.com/en-us/graph/delta-query-events".to_string(),
+            ),
+            category: ProviderCategory::Calendar,
+        },
+        ProviderInfo {
+            name: "salesforce".to_string(),
+            auth_type: "oauth2".to_string(),
+            scopes: crate::connectors::salesforce::SALESFORCE_SCOPES
+                .iter()
+                .map(|s| s.to_string())
+                .collect(),
+            webhooks: true,
+            description: "Sync opportunity and case activity from Salesforce".to_string(),
+            icon_url: Some("https://cdn.simpleicons.org/salesforce".to_string()),
+            docs_url: Some(
+                "https://help.salesforce.com/s/articleView?id=sf.remoteaccess_oauth_web_server_flow.htm"
+                    .to_string(),
+            ),
+            category: ProviderCategory::Crm,
+        },
+        ProviderInfo {
+            name: "zendesk".to_string(),
+            auth_type: "oauth2".to_string(),
+            scopes: crate::connectors::zendesk::ZENDESK_SCOPES
+                .iter()
+                .map(|s| s.to_string())
+                .collect(),
+            webhooks: true,
+            description: "Sync support ticket activity from Zendesk".to_string(),
+            icon_url: Some("https://cdn.simpleicons.org/zendesk".to_string()),
+            docs_url: Some(
+                "https://developer.zendesk.com/documentation/ticketing/working-with-oauth/creating-and-using-oauth-tokens-with-the-api/"
+                    .to_string(),
+            ),
+            category: ProviderCategory::Crm,
+        },
+        ProviderInfo {
+            name: "sentry".to_string(),
+            auth_type: "oauth2".to_string(),
+            scopes: crate::connectors::sentry::SENTRY_SCOPES
+                .iter()
+                .map(|s| s.to_string())
+                .collect(),
+            webhooks: true,
+            description: "Track production errors and issue alerts from Sentry".to_string(),
+            icon_url: Some("https://cdn.simpleicons.org/sentry".to_string()),
+            docs_url: Some("https://docs.sentry.io/api/auth/".to_string()),
+            category: ProviderCategory::Monitoring,
+        },
+        ProviderInfo {
+            name: "circleci".to_string(),
+            auth_type: "api_key".to_string(),
+            scopes: vec![],
+            webhooks: true,
+            description: "Track pipeline and workflow results from CircleCI".to_string(),
+            icon_url: Some("https://cdn.simpleicons.org/circleci".to_string()),
+            docs_url: Some("https://circleci.com/docs/webhooks/".to_string()),
+            category: ProviderCategory::Code,
+        },
+        ProviderInfo {
+            name: "custom-webhook".to_string(),
+            auth_type: "basic".to_string(),
+            scopes: vec![],
+            webhooks: true,
+            description: "Receive signals from any tool that can send a JSON webhook".to_string(),
+            icon_url: Some("https://cdn.simpleicons.org/webhooks".to_string()),
+            docs_url: Some("https://www.rfc-editor.org/rfc/rfc9535".to_string()),
+            category: ProviderCategory::Other,
+        },
+        ProviderInfo {
+            name: "datadog".to_string(),
+            auth_type: "api_key".to_string(),
+            scopes: vec![],
+            webhooks: true,
+            description: "Receive monitor alerts and recoveries from Datadog".to_string(),
+            icon_url: Some("https://cdn.simpleicons.org/datadog".to_string()),
+            docs_url: Some("https://docs.datadoghq.com/integrations/webhooks/".to_string()),
+            category: ProviderCategory::Monitoring,
+        },
+        ProviderInfo {
+            name: "rss".to_string(),
+            auth_type: "none".to_string(),
+            scopes: vec![],
+            webhooks: false,
+            description: "Poll RSS and Atom feeds for press and news".to_string(),
+            icon_url: Some("https://cdn.simpleicons.org/rss".to_string()),
+            docs_url: Some("https://www.rssboard.org/rss-specification".to_string()),
+            category: ProviderCategory::Other,
+        },
+        ProviderInfo {
+            name: "stripe".to_string(),
+            auth_type: "webhook".to_string(),
+            scopes: vec![],
+            webhooks: true,
+            description: "Receive invoice, subscription and dispute events from Stripe"
+                .to_string(),
+            icon_url: Some("https://cdn.simpleicons.org/stripe".to_string()),
+            docs_url: Some("https://docs.stripe.com/webhooks".to_string()),
+            category: ProviderCategory::Other,
         },
         ProviderInfo {
             name: "google-workspace".to_string(),
@@ -144,6 +358,43 @@ pub async fn list_providers(
                 "https://www.googleapis.com/auth/drive.readonly".to_string(),
             ],
             webhooks: false,
+            description: "Sync calendars and Drive files from Google Workspace".to_string(),
+            icon_url: Some("https://cdn.simpleicons.org/google".to_string()),
+            docs_url: Some("https://developers.google.com/workspace".to_string()),
+            category: ProviderCategory::Files,
+        },
+        ProviderInfo {
+            name: "ics".to_string(),
+            auth_type: "none".to_string(),
+            scopes: vec![],
+            webhooks: false,
+            description: "Poll published iCalendar (ICS) feeds for event changes".to_string(),
+            icon_url: Some("https://cdn.simpleicons.org/googlecalendar".to_string()),
+            docs_url: Some("https://www.rfc-editor.org/rfc/rfc5545".to_string()),
+            category: ProviderCategory::Calendar,
+        },
+        ProviderInfo {
+            name: "imap".to_string(),
+            auth_type: "basic".to_string(),
+            scopes: vec![],
+            webhooks: false,
+            description: "Sync mailboxes from any IMAP server".to_string(),
+            icon_url: Some("https://cdn.simpleicons.org/maildotru".to_string()),
+            docs_url: Some("https://www.rfc-editor.org/rfc/rfc3501".to_string()),
+            category: ProviderCategory::Mail,
+        },
+        ProviderInfo {
+            name: "inbound-email".to_string(),
+            auth_type: "webhook".to_string(),
+            scopes: vec![],
+            webhooks: true,
+            description: "Receive forwarded mail from SES, SendGrid or any SMTP bridge"
+                .to_string(),
+            icon_url: Some("https://cdn.simpleicons.org/maildotru".to_string()),
+            docs_url: Some(
+                "https://docs.aws.amazon.com/ses/latest/dg/receiving-email.html".to_string(),
+            ),
+            category: ProviderCategory::Mail,
         },
         ProviderInfo {
             name: "zoho".to_string(),
@@ -153,12 +404,20 @@ pub async fn list_providers(
                 "ZohoCRM.settings.all".to_string(),
             ],
             webhooks: true,
+            description: "Sync CRM records from Zoho CRM".to_string(),
+            icon_url: Some("https://cdn.simpleicons.org/zoho".to_string()),
+            docs_url: Some("https://www.zoho.com/crm/developer/docs/".to_string()),
+            category: ProviderCategory::Crm,
         },
         ProviderInfo {
             name: "zoho-cliq".to_string(),
             auth_type: "webhook".to_string(),
             scopes: vec![],
             webhooks: true,
+            description: "Receive channel messages from Zoho Cliq via webhooks".to_string(),
+            icon_url: Some("https://cdn.simpleicons.org/zoho".to_string()),
+            docs_url: Some("https://www.zoho.com/cliq/help/restapi/v2/".to_string()),
+            category: ProviderCategory::Chat,
         },
     ];
 
@@ -218,9 +477,182 @@ pub async fn list_providers(
     }))
 }
 
+/// Schema of the settings a provider accepts in connection metadata
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct MetadataSchemaResponse {
+    /// Provider slug
+    pub provider: String,
+    /// JSON schema of the configurable metadata keys; no properties when the provider has none
+    #[schema(value_type = Object)]
+    pub schema: serde_json::Value,
+}
+
+/// Public endpoint describing a provider's configurable connection metadata
+///
+/// UIs render a settings form from the schema and submit it to
+/// `PATCH /connections/{id}`.
+#[utoipa::path(
+    get,
+    path = "/providers/{slug}/metadata-schema",
+    params(("slug" = String, Path, description = "Provider slug")),
+    responses(
+        (status = 200, description = "Connection metadata schema", body = MetadataSchemaResponse, example = json!({
+            "provider": "github",
+            "schema": {
+                "type": "object",
+                "description": "Settings of a GitHub connection",
+                "properties": {
+                    "repositories": {
+                        "type": ["array", "null"],
+                        "items": {"type": "string"},
+                        "description": "Repositories to sync as `owner/name`; all accessible repositories when unset",
+                        "example": ["octo-org/api", "octo-org/web"]
+                    }
+                }
+            }
+        })),
+        (status = 404, description = "Unknown provider", body = ApiError),
+        (status = 500, description = "Internal server error", body = ApiError)
+    ),
+    tag = "providers"
+)]
+pub async fn get_metadata_schema(
+    State(state): State<AppState>,
+    Path(slug): Path<String>,
+) -> Result<Json<MetadataSchemaResponse>, ApiError> {
+    if !has_metadata_schema(&slug) && state.snapshot.provider(&slug).await?.is_none() {
+        return Err(ApiError::new(
+            StatusCode::NOT_FOUND,
+            "NOT_FOUND",
+            format!("provider '{}' not found", slug),
+        ));
+    }
+
+    Ok(Json(MetadataSchemaResponse {
+        schema: metadata_schema(&slug),
+        provider: slug,
+    }))
+}
+
+/// Public endpoint describing what a provider supports
+///
+/// Connect UIs use it to decide whether to offer an OAuth button, explain
+/// webhook setup or promise historical data, without hardcoding providers.
+#[utoipa::path(
+    get,
+    path = "/providers/{slug}/capabilities",
+    params(("slug" = String, Path, description = "Provider slug")),
+    responses(
+        (status = 200, description = "Provider capabilities", body = ProviderCapabilities, example = json!({
+            "provider": "github",
+            "supports_oauth": true,
+            "supports_webhooks": true,
+            "supports_backfill": true,
+            "supported_signal_kinds": ["issue_created", "issue_updated", "pr_opened", "pr_merged"]
+        })),
+        (status = 404, description = "Unknown provider", body = ApiError),
+        (status = 500, description = "Internal server error", body = ApiError)
+    ),
+    tag = "providers"
+)]
+pub async fn get_capabilities(
+    Path(slug): Path<String>,
+) -> Result<Json<ProviderCapabilities>, ApiError> {
+    let registry = Registry::global();
+    let registry = registry.read().unwrap();
+    match registry.get_metadata(&slug) {
+        Ok(metadata) => Ok(Json(metadata.capabilities())),
+        Err(RegistryError::ProviderNotFound { name }) => Err(ApiError::new(
+            StatusCode::NOT_FOUND,
+            "NOT_FOUND",
+            format!("provider '{}' not found", name),
+        )),
+    }
+}
+
+/// Circuit breaker status of a provider
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ProviderStatusResponse {
+    /// Provider slug
+    pub provider: String,
+    /// Circuit state: `closed` (jobs run), `open` (jobs paused until `open_until`) or
+    /// `half_open` (a probe job decides whether the circuit closes)
+    pub circuit_state: String,
+    /// 5xx, network and timeout failures since the provider last answered a job
+    pub consecutive_failures: i32,
+    /// When the circuit last opened
+    pub opened_at: Option<String>,
+    /// End of the current cooldown or probe window
+    pub open_until: Option<String>,
+    /// Most recent provider failure
+    pub last_error: Option<String>,
+}
+
+/// Public endpoint reporting whether the executor is running a provider's jobs
+///
+/// After repeated 5xx responses or timeouts the executor opens the provider's
+/// circuit and stops claiming its jobs for a cooldown, then probes it with a
+/// single job. Clients use this to explain delayed syncs during an outage.
+#[utoipa::path(
+    get,
+    path = "/providers/{slug}/status",
+    params(("slug" = String, Path, description = "Provider slug")),
+    responses(
+        (status = 200, description = "Provider circuit status", body = ProviderStatusResponse, example = json!({
+            "provider": "github",
+            "circuit_state": "open",
+            "consecutive_failures": 5,
+            "opened_at": "2024-06-01T12:00:00+00:00",
+            "open_until": "2024-06-01T12:01:00+00:00",
+            "last_error": "Transient error: HTTP error 503: Service Unavailable"
+        })),
+        (status = 404, description = "Unknown provider", body = ApiError),
+        (status = 500, description = "Internal server error", body = ApiError)
+    ),
+    tag = "providers"
+)]
+pub async fn get_provider_status(
+    State(state): State<AppState>,
+    Path(slug): Path<String>,
+) -> Result<Json<ProviderStatusResponse>, ApiError> {
+    let registered = Registry::global()
+        .read()
+        .unwrap()
+        .get_metadata(&slug)
+        .is_ok();
+    if !registered {
+        return Err(ApiError::new(
+            StatusCode::NOT_FOUND,
+            "NOT_FOUND",
+            format!("provider '{}' not found", slug),
+        ));
+    }
+
+    let circuit = circuit_breaker::find(&state.db, &slug).await?;
+    Ok(Json(match circuit {
+        Some(circuit) => ProviderStatusResponse {
+            provider: slug,
+            circuit_state: circuit.state,
+            consecutive_failures: circuit.consecutive_failures,
+            opened_at: circuit.opened_at.map(|at| at.to_rfc3339()),
+            open_until: circuit.open_until.map(|at| at.to_rfc3339()),
+            last_error: circuit.last_error,
+        },
+        None => ProviderStatusResponse {
+            provider: slug,
+            circuit_state: circuit_breaker::STATE_CLOSED.to_string(),
+            consecutive_failures: 0,
+            opened_at: None,
+            open_until: None,
+            last_error: None,
+        },
+    }))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use migration::MigratorTrait;
     use serde_json;
 
     #[tokio::test]
@@ -243,7 +675,7 @@ mod tests {
         let response = result.unwrap();
 
         // Verify the structure and data
-        assert_eq!(response.providers.len(), 6);
+        assert_eq!(response.providers.len(), 23);
 
         // Check that providers are sorted by name
         let provider_names: Vec<String> =
@@ -251,10 +683,27 @@ mod tests {
         assert_eq!(
             provider_names,
             vec![
+                "circleci",
+                "confluence",
+                "custom-webhook",
+                "datadog",
                 "github",
+                "gitlab",
                 "google-workspace",
+                "ics",
+                "imap",
+                "inbound-email",
                 "jira",
+                "linear",
+                "notion",
+                "outlook",
+                "outlook-calendar",
+                "rss",
+                "salesforce",
+                "sentry",
                 "slack",
+                "stripe",
+                "zendesk",
                 "zoho",
                 "zoho-cliq"
             ]
@@ -293,6 +742,14 @@ mod tests {
         assert_eq!(zoho_cliq.auth_type, "webhook");
         assert!(zoho_cliq.webhooks);
         assert!(zoho_cliq.scopes.is_empty()); // No OAuth scopes for webhook-only provider
+        assert_eq!(zoho_cliq.category, ProviderCategory::Chat);
+
+        // Every provider carries catalog metadata for the connect UI
+        for provider in &response.providers {
+            assert!(!provider.description.is_empty(), "{}", provider.name);
+            assert!(provider.icon_url.is_some(), "{}", provider.name);
+            assert!(provider.docs_url.is_some(), "{}", provider.name);
+        }
     }
 
     #[test]
@@ -302,6 +759,10 @@ mod tests {
             auth_type: "oauth2".to_string(),
             scopes: vec!["read".to_string(), "write".to_string()],
             webhooks: true,
+            description: "Test provider".to_string(),
+            icon_url: None,
+            docs_url: Some("https://example.com/docs".to_string()),
+            category: ProviderCategory::Files,
         };
 
         let json = serde_json::to_string(&provider).unwrap();
@@ -311,6 +772,8 @@ mod tests {
         assert_eq!(parsed.auth_type, "oauth2");
         assert_eq!(parsed.scopes, vec!["read".to_string(), "write".to_string()]);
         assert!(parsed.webhooks);
+        assert_eq!(parsed.category, ProviderCategory::Files);
+        assert!(json.contains(r#""category":"files""#));
     }
 
     #[test]
@@ -321,12 +784,20 @@ mod tests {
                 auth_type: "oauth2".to_string(),
                 scopes: vec!["read".to_string()],
                 webhooks: false,
+                description: "Test provider 1".to_string(),
+                icon_url: None,
+                docs_url: None,
+                category: ProviderCategory::Code,
             },
             ProviderInfo {
                 name: "test2".to_string(),
                 auth_type: "oauth2".to_string(),
                 scopes: vec!["write".to_string()],
                 webhooks: true,
+                description: "Test provider 2".to_string(),
+                icon_url: None,
+                docs_url: None,
+                category: ProviderCategory::Chat,
             },
         ];
 
@@ -341,4 +812,65 @@ mod tests {
         assert_eq!(parsed.providers[0].name, "test1");
         assert_eq!(parsed.providers[1].name, "test2");
     }
+
+    #[tokio::test]
+    async fn test_get_capabilities_reports_registered_provider() {
+        Registry::initialize(&crate::config::AppConfig::default());
+
+        let Json(capabilities) = get_capabilities(Path("stripe".to_string())).await.unwrap();
+        assert_eq!(capabilities.provider, "stripe");
+        assert!(!capabilities.supports_oauth);
+        assert!(capabilities.supports_webhooks);
+        assert!(!capabilities.supports_backfill);
+        assert!(
+            capabilities
+                .supported_signal_kinds
+                .contains(&"invoice_paid".to_string())
+        );
+
+        let Json(capabilities) = get_capabilities(Path("rss".to_string())).await.unwrap();
+        assert!(capabilities.supports_backfill);
+        assert_eq!(capabilities.supported_signal_kinds, vec!["message_posted"]);
+
+        let error = get_capabilities(Path("unknown".to_string()))
+            .await
+            .unwrap_err();
+        assert_eq!(error.status, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_get_provider_status_reports_circuit() {
+        Registry::initialize(&crate::config::AppConfig::default());
+        let db = sea_orm::Database::connect("sqlite::memory:").await.unwrap();
+        migration::Migrator::up(&db, None).await.unwrap();
+        let state =
+            crate::server::create_test_app_state(crate::config::AppConfig::default(), db.clone());
+
+        let Json(status) = get_provider_status(State(state.clone()), Path("stripe".to_string()))
+            .await
+            .unwrap();
+        assert_eq!(status.circuit_state, "closed");
+        assert_eq!(status.consecutive_failures, 0);
+
+        let policy = circuit_breaker::CircuitBreakerPolicy {
+            threshold: 1,
+            cooldown: chrono::Duration::seconds(60),
+        };
+        policy
+            .record_failure(&db, "stripe", "HTTP error 503", chrono::Utc::now())
+            .await
+            .unwrap();
+        let Json(status) = get_provider_status(State(state.clone()), Path("stripe".to_string()))
+            .await
+            .unwrap();
+        assert_eq!(status.circuit_state, "open");
+        assert_eq!(status.consecutive_failures, 1);
+        assert!(status.open_until.is_some());
+        assert_eq!(status.last_error.as_deref(), Some("HTTP error 503"));
+
+        let error = get_provider_status(State(state), Path("unknown".to_string()))
+            .await
+            .unwrap_err();
+        assert_eq!(error.status, StatusCode::NOT_FOUND);
+    }
 }
@@ -0,0 +1,361 @@
+//! # Sync Schedule Feed Handlers
+//!
+//! Exposes the tenant's upcoming scheduled syncs and blackout windows as JSON
+//! and as an iCalendar feed, so operators can overlay connector activity on
+//! their own calendars when planning provider maintenance. Times are projected
+//! from each connection's `metadata.sync` and exclude scheduler jitter.
+
+use crate::auth::{OperatorAuth, TenantExtension};
+use crate::error::ApiError;
+use crate::repositories::connection::ConnectionRepository;
+use crate::repositories::sync_metadata::{BlackoutWindow, ConnectionSyncMetadata};
+use crate::scheduler::upcoming_runs;
+use crate::server::AppState;
+use axum::{
+    extract::{Query, State},
+    http::{HeaderValue, StatusCode, header},
+    response::{IntoResponse, Json, Response},
+};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt::Write;
+use std::sync::Arc;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+/// Default look-ahead window for the feed
+const DEFAULT_HORIZON_HOURS: i64 = 24;
+
+/// Maximum look-ahead window for the feed (one week)
+const MAX_HORIZON_HOURS: i64 = 168;
+
+/// Cap on projected runs per connection, guarding against very short intervals
+const MAX_RUNS_PER_CONNECTION: usize = 500;
+
+/// Query parameters for the schedule feed
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ScheduleQuery {
+    /// Hours ahead to project (default: 24, max: 168)
+    pub horizon_hours: Option<i64>,
+}
+
+/// A blackout window during which no syncs are scheduled
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BlackoutInfo {
+    /// Start of the window (RFC3339)
+    #[schema(example = "2024-01-15T22:00:00Z")]
+    pub starts_at: String,
+    /// End of the window (RFC3339)
+    #[schema(example = "2024-01-16T02:00:00Z")]
+    pub ends_at: String,
+    /// Optional reason, e.g. provider maintenance
+    pub reason: Option<String>,
+}
+
+/// Upcoming syncs for one connection
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ConnectionSchedule {
+    /// Connection identifier
+    #[schema(value_type = String)]
+    pub connection_id: Uuid,
+    /// Provider slug (e.g., "github")
+    pub provider: String,
+    /// Effective sync interval in seconds
+    #[schema(example = 900)]
+    pub interval_seconds: u64,
+    /// Projected sync start times (RFC3339), before jitter
+    pub upcoming_syncs: Vec<String>,
+    /// Blackout windows overlapping the horizon
+    pub blackouts: Vec<BlackoutInfo>,
+}
+
+/// Response payload for the schedule feed
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ScheduleResponse {
+    /// Time the projection was computed (RFC3339)
+    pub generated_at: String,
+    /// End of the projection horizon (RFC3339)
+    pub horizon_end: String,
+    /// Active connections and their upcoming syncs
+    pub connections: Vec<ConnectionSchedule>,
+}
+
+/// Projection for one connection, shared by the JSON and iCalendar renderers
+struct Projection {
+    connection_id: Uuid,
+    provider: String,
+    interval_seconds: u64,
+    runs: Vec<DateTime<Utc>>,
+    blackouts: Vec<BlackoutWindow>,
+}
+
+/// Upcoming scheduled syncs and blackout windows as JSON
+#[utoipa::path(
+    get,
+    path = "/schedule",
+    security(("bearer_auth" = [])),
+    params(ScheduleQuery),
+    responses(
+        (status = 200, description = "Projected sync schedule", body = ScheduleResponse),
+        (status = 400, description = "Invalid horizon", body = ApiError),
+        (status = 401, description = "Missing or invalid bearer token", body = ApiError)
+    ),
+    tag = "jobs"
+)]
+pub async fn get_schedule(
+    State(state): State<AppState>,
+    _operator_auth: OperatorAuth,
+    TenantExtension(tenant): TenantExtension,
+    Query(query): Query<ScheduleQuery>,
+) -> Result<Json<ScheduleResponse>, ApiError> {
+    let now = Utc::now();
+    let until = now + horizon(&query)?;
+    let projections = project(&state, tenant.0, now, until).await?;
+
+    Ok(Json(ScheduleResponse {
+        generated_at: now.to_rfc3339(),
+        horizon_end: until.to_rfc3339(),
+        connections: projections
+            .into_iter()
+            .map(|projection| ConnectionSchedule {
+                connection_id: projection.connection_id,
+                provider: projection.provider,
+                interval_seconds: projection.interval_seconds,
+                upcoming_syncs: projection.runs.iter().map(|run| run.to_rfc3339()).collect(),
+                blackouts: projection
+                    .blackouts
+                    .into_iter()
+                    .map(|window| BlackoutInfo {
+                        starts_at: window.starts_at.to_rfc3339(),
+                        ends_at: window.ends_at.to_rfc3339(),
+                        reason: window.reason,
+                    })
+                    .collect(),
+            })
+            .collect(),
+    }))
+}
+
+/// Upcoming scheduled syncs and blackout windows as an iCalendar feed
+#[utoipa::path(
+    get,
+    path = "/schedule.ics",
+    security(("bearer_auth" = [])),
+    params(ScheduleQuery),
+    responses(
+        (status = 200, description = "Projected sync schedule", content_type = "text/calendar", body = String),
+        (status = 400, description = "Invalid horizon", body = ApiError),
+        (status = 401, description = "Missing or invalid bearer token", body = ApiError)
+    ),
+    tag = "jobs"
+)]
+pub async fn get_schedule_ical(
+    State(state): State<AppState>,
+    _operator_auth: OperatorAuth,
+    TenantExtension(tenant): TenantExtension,
+    Query(query): Query<ScheduleQuery>,
+) -> Result<Response, ApiError> {
+    let now = Utc::now();
+    let until = now + horizon(&query)?;
+    let projections = project(&state, tenant.0, now, until).await?;
+
+    let mut response = render_ical(&projections, now).into_response();
+    response.headers_mut().insert(
+        header::CONTENT_TYPE,
+        HeaderValue::from_static("text/calendar; charset=utf-8"),
+    );
+    Ok(response)
+}
+
+fn horizon(query: &ScheduleQuery) -> Result<Duration, ApiError> {
+    let hours = query.horizon_hours.unwrap_or(DEFAULT_HORIZON_HOURS);
+    if !(1..=MAX_HORIZON_HOURS).contains(&hours) {
+        return Err(ApiError::new(
+            StatusCode::BAD_REQUEST,
+            "VALIDATION_FAILED",
+            format!("horizon_hours must be between 1 and {}", MAX_HORIZON_HOURS),
+        ));
+    }
+    Ok(Duration::hours(hours))
+}
+
+async fn project(
+    state: &AppState,
+    tenant_id: Uuid,
+    now: DateTime<Utc>,
+    until: DateTime<Utc>,
+) -> Result<Vec<Projection>, ApiError> {
+    let connections =
+        ConnectionRepository::new(Arc::new(state.db.clone()), state.master_keys.clone())
+            .find_by_tenant(&tenant_id)
+            .await?;
+
+    Ok(connections
+        .into_iter()
+        .filter(|connection| connection.status == "active")
+        .map(|connection| {
+            let metadata =
+                ConnectionSyncMetadata::from_connection_metadata(connection.metadata.as_ref());
+            let interval_seconds = metadata
+                .effective_interval_seconds(&state.config.scheduler, &connection.provider_slug);
+            let activation = metadata
+                .first_activated_at
+                .unwrap_or_else(|| connection.created_at.with_timezone(&Utc));
+            let runs = upcoming_runs(
+                &metadata,
+                interval_seconds,
+                activation,
+                now,
+                until,
+                MAX_RUNS_PER_CONNECTION,
+            );
+            let blackouts = metadata
+                .blackouts
+                .into_iter()
+                .filter(|window| window.ends_at > now && window.starts_at < until)
+                .collect();
+            Projection {
+                connection_id: connection.id,
+                provider: connection.provider_slug,
+                interval_seconds,
+                runs,
+                blackouts,
+            }
+        })
+        .collect())
+}
+
+/// Render projections as an RFC 5545 calendar
+fn render_ical(projections: &[Projection], generated_at: DateTime<Utc>) -> String {
+    let stamp = ical_time(generated_at);
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//Poblysh//Connectors Sync Schedule//EN".to_string(),
+        "CALSCALE:GREGORIAN".to_string(),
+        "X-WR-CALNAME:Poblysh connector syncs".to_string(),
+    ];
+
+    for projection in projections {
+        for run in &projection.runs {
+            lines.extend([
+                "BEGIN:VEVENT".to_string(),
+                format!(
+                    "UID:sync-{}-{}@poblysh-connectors",
+                    projection.connection_id,
+                    run.timestamp()
+                ),
+                format!("DTSTAMP:{}", stamp),
+                format!("DTSTART:{}", ical_time(*run)),
+                format!(
+                    "SUMMARY:{}",
+                    escape_text(&format!("{} sync", projection.provider))
+                ),
+                format!(
+                    "DESCRIPTION:{}",
+                    escape_text(&format!(
+                        "Scheduled incremental sync for connection {} (every {}s)",
+                        projection.connection_id, projection.interval_seconds
+                    ))
+                ),
+                "END:VEVENT".to_string(),
+            ]);
+        }
+        for window in &projection.blackouts {
+            let mut summary = format!("{} sync blackout", projection.provider);
+            if let Some(reason) = &window.reason {
+                let _ = write!(summary, ": {}", reason);
+            }
+            lines.extend([
+                "BEGIN:VEVENT".to_string(),
+                format!(
+                    "UID:blackout-{}-{}@poblysh-connectors",
+                    projection.connection_id,
+                    window.starts_at.timestamp()
+                ),
+                format!("DTSTAMP:{}", stamp),
+                format!("DTSTART:{}", ical_time(window.starts_at)),
+                format!("DTEND:{}", ical_time(window.ends_at)),
+                format!("SUMMARY:{}", escape_text(&summary)),
+                "TRANSP:OPAQUE".to_string(),
+                "END:VEVENT".to_string(),
+            ]);
+        }
+    }
+    lines.push("END:VCALENDAR".to_string());
+
+    let mut out = String::new();
+    for line in &lines {
+        fold_line(&mut out, line);
+    }
+    out
+}
+
+fn ical_time(at: DateTime<Utc>) -> String {
+    at.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+fn escape_text(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' | ';' | ',' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '\n' => escaped.push_str("\\n"),
+            '\r' => {}
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Append `line` with CRLF, folding at 75 octets without splitting characters
+fn fold_line(out: &mut String, line: &str) {
+    let mut width = 0;
+    for c in line.chars() {
+        if width + c.len_utf8() > 75 {
+            out.push_str("\r\n ");
+            width = 1;
+        }
+        out.push(c);
+        width += c.len_utf8();
+    }
+    out.push_str("\r\n");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_ical_folds_and_escapes() {
+        let at = |s: &str| DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc);
+        let projection = Projection {
+            connection_id: Uuid::nil(),
+            provider: "github".to_string(),
+            interval_seconds: 900,
+            runs: vec![at("2025-01-01T10:15:00Z")],
+            blackouts: vec![BlackoutWindow {
+                starts_at: at("2025-01-01T22:00:00Z"),
+                ends_at: at("2025-01-02T02:00:00Z"),
+                reason: Some("GitHub maintenance; API read-only, webhooks delayed".to_string()),
+            }],
+        };
+
+        let ical = render_ical(&[projection], at("2025-01-01T10:00:00Z"));
+
+        assert!(ical.starts_with("BEGIN:VCALENDAR\r\n"));
+        assert!(ical.ends_with("END:VCALENDAR\r\n"));
+        assert!(ical.contains("DTSTART:20250101T101500Z\r\n"));
+        assert!(ical.contains("DTEND:20250102T020000Z\r\n"));
+        assert!(
+            ical.replace("\r\n ", "")
+                .contains("maintenance\\; API read-only\\, webhooks delayed")
+        );
+        assert!(ical.split("\r\n").all(|line| line.len() <= 75));
+        assert_eq!(ical.matches("BEGIN:VEVENT").count(), 2);
+    }
+}
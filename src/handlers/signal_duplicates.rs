@@ -0,0 +1,343 @@
+//! # Signal Duplicate Handlers
+//!
+//! This module reports near-duplicate signals left behind by historical
+//! double-ingestion bugs and merges them on request.
+
+use crate::auth::OperatorAuth;
+use crate::error::ApiError;
+use crate::repositories::audit_log::API_AUDIT_ACTOR;
+use crate::repositories::{AuditLogRepository, NewAuditLogEntry, SignalRepository};
+use crate::server::AppState;
+use crate::signals::duplicates::{DuplicateGroup, find_duplicates};
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::Json,
+};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+/// Audit action recorded when duplicates are merged
+pub const SIGNAL_DUPLICATES_MERGED_AUDIT_ACTION: &str = "signal.duplicates_merged";
+
+/// Default spread of `occurred_at` values within one group
+const DEFAULT_WINDOW_SECONDS: i64 = 300;
+
+/// Largest accepted window
+const MAX_WINDOW_SECONDS: i64 = 86_400;
+
+/// Default lookback when `since` is not given
+const DEFAULT_LOOKBACK_DAYS: i64 = 30;
+
+/// Signals examined per request; narrow `since` to cover more history
+const MAX_SCANNED_SIGNALS: u64 = 10_000;
+
+/// Scope of a duplicate scan
+#[derive(Debug, Deserialize, IntoParams, ToSchema)]
+#[into_params(parameter_in = Query)]
+pub struct DuplicateScanQuery {
+    /// Tenant to scan
+    pub tenant_id: Uuid,
+    /// Maximum spread of `occurred_at` within one group in seconds (default: 300, max: 86400)
+    pub window_seconds: Option<i64>,
+    /// Only scan signals that occurred at or after this timestamp (RFC3339, default: 30 days ago)
+    pub since: Option<String>,
+}
+
+/// Near-duplicate signals for one tenant
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct DuplicateReport {
+    /// Tenant that was scanned
+    pub tenant_id: Uuid,
+    /// Window applied to `occurred_at`
+    #[schema(example = 300)]
+    pub window_seconds: i64,
+    /// Start of the scanned range (RFC3339)
+    pub since: String,
+    /// Number of signals examined
+    pub scanned: usize,
+    /// Whether the scan stopped at the per-request cap before the end of the range
+    pub truncated: bool,
+    /// Signals a merge would delete
+    pub duplicate_count: usize,
+    /// Duplicate groups, oldest first
+    pub groups: Vec<DuplicateGroup>,
+}
+
+/// Request to merge a tenant's duplicate signals
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct MergeDuplicatesRequest {
+    /// Tenant whose duplicates are merged
+    pub tenant_id: Uuid,
+    /// Same meaning as in the report
+    pub window_seconds: Option<i64>,
+    /// Same meaning as in the report
+    pub since: Option<String>,
+    /// Justification recorded in the audit log
+    pub reason: Option<String>,
+}
+
+/// Outcome of a merge
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct MergeDuplicatesResponse {
+    /// Groups folded into their earliest signal
+    pub groups_merged: usize,
+    /// Duplicate signals deleted
+    pub signals_removed: u64,
+    /// Whether the scan stopped at the per-request cap; run again to continue
+    pub truncated: bool,
+}
+
+/// Report near-duplicate signals for a tenant
+///
+/// Signals are duplicates when they share provider, kind, dedupe key family
+/// (the key without a trailing timestamp) and payload hash, and occurred within
+/// the window of each other. The report never changes data.
+#[utoipa::path(
+    get,
+    path = "/admin/signals/duplicates",
+    security(("bearer_auth" = [])),
+    params(DuplicateScanQuery),
+    responses(
+        (status = 200, description = "Duplicate signal report", body = DuplicateReport),
+        (status = 400, description = "Invalid window or since", body = ApiError),
+        (status = 401, description = "Missing or invalid operator credentials", body = ApiError),
+        (status = 500, description = "Internal server error", body = ApiError)
+    ),
+    tag = "operators"
+)]
+pub async fn get_duplicate_report(
+    State(state): State<AppState>,
+    _operator_auth: OperatorAuth,
+    Query(query): Query<DuplicateScanQuery>,
+) -> Result<Json<DuplicateReport>, ApiError> {
+    let (window, since) = parse_scope(query.window_seconds, query.since.as_deref())?;
+    let (groups, scanned, truncated) = scan(&state, query.tenant_id, window, since).await?;
+
+    Ok(Json(DuplicateReport {
+        tenant_id: query.tenant_id,
+        window_seconds: window.num_seconds(),
+        since: since.to_rfc3339(),
+        scanned,
+        truncated,
+        duplicate_count: groups.iter().map(|group| group.duplicate_ids.len()).sum(),
+        groups,
+    }))
+}
+
+/// Merge near-duplicate signals for a tenant
+///
+/// Recomputes the report for the same scope, then keeps the earliest received
+/// signal of each group and deletes the rest. Outbox entries, pipeline events
+/// and grounded signals of the deleted copies go with them. The merge is
+/// recorded in the audit log.
+#[utoipa::path(
+    post,
+    path = "/admin/signals/duplicates/merge",
+    security(("bearer_auth" = [])),
+    request_body = MergeDuplicatesRequest,
+    responses(
+        (status = 200, description = "Duplicates merged", body = MergeDuplicatesResponse),
+        (status = 400, description = "Invalid window or since", body = ApiError),
+        (status = 401, description = "Missing or invalid operator credentials", body = ApiError),
+        (status = 500, description = "Internal server error", body = ApiError)
+    ),
+    tag = "operators"
+)]
+pub async fn merge_duplicates(
+    State(state): State<AppState>,
+    _operator_auth: OperatorAuth,
+    Json(request): Json<MergeDuplicatesRequest>,
+) -> Result<Json<MergeDuplicatesResponse>, ApiError> {
+    let (window, since) = parse_scope(request.window_seconds, request.since.as_deref())?;
+    let (groups, _, truncated) = scan(&state, request.tenant_id, window, since).await?;
+
+    let repo = SignalRepository::new(&state.db);
+    let mut response = MergeDuplicatesResponse {
+        groups_merged: 0,
+        signals_removed: 0,
+        truncated,
+    };
+    for group in &groups {
+        response.signals_removed += repo
+            .delete_for_tenant(request.tenant_id, &group.duplicate_ids)
+            .await?;
+        response.groups_merged += 1;
+    }
+
+    if response.groups_merged > 0 {
+        AuditLogRepository::new(state.db.clone())
+            .record_or_log(NewAuditLogEntry {
+                tenant_id: Some(request.tenant_id),
+                actor: API_AUDIT_ACTOR.to_string(),
+                action: SIGNAL_DUPLICATES_MERGED_AUDIT_ACTION.to_string(),
+                target_type: "tenant".to_string(),
+                target_id: request.tenant_id.to_string(),
+                reason: request.reason,
+                details: Some(serde_json::json!({
+                    "window_seconds": window.num_seconds(),
+                    "since": since.to_rfc3339(),
+                    "groups_merged": response.groups_merged,
+                    "signals_removed": response.signals_removed,
+                })),
+            })
+            .await;
+    }
+
+    Ok(Json(response))
+}
+
+fn parse_scope(
+    window_seconds: Option<i64>,
+    since: Option<&str>,
+) -> Result<(Duration, DateTime<Utc>), ApiError> {
+    let window_seconds = window_seconds.unwrap_or(DEFAULT_WINDOW_SECONDS);
+    if !(0..=MAX_WINDOW_SECONDS).contains(&window_seconds) {
+        return Err(ApiError::new(
+            StatusCode::BAD_REQUEST,
+            "VALIDATION_FAILED",
+            format!(
+                "window_seconds must be between 0 and {}",
+                MAX_WINDOW_SECONDS
+            ),
+        ));
+    }
+    let since = match since {
+        Some(since) => DateTime::parse_from_rfc3339(since)
+            .map(|since| since.with_timezone(&Utc))
+            .map_err(|_| {
+                ApiError::new(
+                    StatusCode::BAD_REQUEST,
+                    "VALIDATION_FAILED",
+                    "since must be an RFC3339 timestamp",
+                )
+            })?,
+        None => Utc::now() - Duration::days(DEFAULT_LOOKBACK_DAYS),
+    };
+    Ok((Duration::seconds(window_seconds), since))
+}
+
+async fn scan(
+    state: &AppState,
+    tenant_id: Uuid,
+    window: Duration,
+    since: DateTime<Utc>,
+) -> Result<(Vec<DuplicateGroup>, usize, bool), ApiError> {
+    let signals = SignalRepository::new(&state.db)
+        .list_since(tenant_id, since, MAX_SCANNED_SIGNALS)
+        .await?;
+    let truncated = signals.len() as u64 >= MAX_SCANNED_SIGNALS;
+    Ok((find_duplicates(&signals, window), signals.len(), truncated))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::AppConfig;
+    use crate::db::init_pool;
+    use crate::models::{connection, signal, tenant};
+    use crate::repositories::ProviderRepository;
+    use migration::{Migrator, MigratorTrait};
+    use sea_orm::{ActiveModelTrait, EntityTrait, Set};
+
+    #[tokio::test]
+    async fn test_duplicate_report_and_merge() {
+        let config = AppConfig {
+            profile: "test".to_string(),
+            ..Default::default()
+        };
+        let db = init_pool(&config).await.expect("Failed to init test DB");
+        Migrator::up(&db, None).await.unwrap();
+
+        let tenant_id = Uuid::new_v4();
+        tenant::ActiveModel {
+            id: Set(tenant_id),
+            ..Default::default()
+        }
+        .insert(&db)
+        .await
+        .unwrap();
+        ProviderRepository::new(std::sync::Arc::new(db.clone()))
+            .upsert("jira", "Jira", "oauth2")
+            .await
+            .unwrap();
+        let connection_id = Uuid::new_v4();
+        connection::ActiveModel {
+            id: Set(connection_id),
+            tenant_id: Set(tenant_id),
+            provider_slug: Set("jira".to_string()),
+            external_id: Set("site-1".to_string()),
+            ..Default::default()
+        }
+        .insert(&db)
+        .await
+        .unwrap();
+
+        let occurred = Utc::now() - Duration::hours(1);
+        let mut ids = Vec::new();
+        for offset in [0, 30] {
+            let id = Uuid::new_v4();
+            let received = occurred + Duration::seconds(offset + 1);
+            signal::ActiveModel {
+                id: Set(id),
+                tenant_id: Set(tenant_id),
+                provider_slug: Set("jira".to_string()),
+                connection_id: Set(connection_id),
+                kind: Set("issue_updated".to_string()),
+                occurred_at: Set((occurred + Duration::seconds(offset)).into()),
+                received_at: Set(received.into()),
+                payload: Set(serde_json::json!({"issue": "PROJ-1", "status": "Done"})),
+                dedupe_key: Set(Some(format!(
+                    "jira:issue_updated:10001:{}",
+                    (occurred + Duration::seconds(offset)).to_rfc3339()
+                ))),
+                created_at: Set(received.into()),
+                updated_at: Set(received.into()),
+            }
+            .insert(&db)
+            .await
+            .unwrap();
+            ids.push(id);
+        }
+        let state = crate::server::create_test_app_state(config, db.clone());
+        let Json(report) = get_duplicate_report(
+            State(state.clone()),
+            OperatorAuth,
+            Query(DuplicateScanQuery {
+                tenant_id,
+                window_seconds: None,
+                since: None,
+            }),
+        )
+        .await
+        .unwrap();
+        assert_eq!(report.scanned, 2);
+        assert_eq!(report.duplicate_count, 1);
+        assert_eq!(report.groups[0].keep_id, ids[0]);
+        assert_eq!(report.groups[0].duplicate_ids, vec![ids[1]]);
+
+        let Json(merged) = merge_duplicates(
+            State(state),
+            OperatorAuth,
+            Json(MergeDuplicatesRequest {
+                tenant_id,
+                window_seconds: None,
+                since: None,
+                reason: Some("double ingestion".to_string()),
+            }),
+        )
+        .await
+        .unwrap();
+        assert_eq!(merged.groups_merged, 1);
+        assert_eq!(merged.signals_removed, 1);
+        assert!(
+            signal::Entity::find_by_id(ids[1])
+                .one(&db)
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
+}
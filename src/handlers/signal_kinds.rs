@@ -0,0 +1,207 @@
+//! # Signal Kinds Handler
+//!
+//! Lists the signal kinds a tenant can emit and lets tenants register
+//! namespaced custom kinds (`custom:press_mention`). Canonical kinds stay
+//! strongly typed in [`crate::normalization::SignalKind`]; custom kinds are only
+//! accepted once registered for the tenant.
+
+use crate::auth::{OperatorAuth, TenantExtension};
+use crate::error::ApiError;
+use crate::normalization::{ALL_SIGNAL_KINDS, AnySignalKind};
+use crate::repositories::audit_log::API_AUDIT_ACTOR;
+use crate::repositories::{AuditLogRepository, NewAuditLogEntry, TenantSignalKindRepository};
+use crate::server::AppState;
+use axum::{extract::State, http::StatusCode, response::Json};
+use sea_orm::DatabaseConnection;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// Audit action recorded when a tenant registers a custom kind
+pub const SIGNAL_KIND_REGISTERED_AUDIT_ACTION: &str = "signal_kind.registered";
+
+/// Maximum length of a custom kind description
+const MAX_DESCRIPTION_LEN: usize = 500;
+
+/// A custom kind registered by the tenant
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CustomSignalKindInfo {
+    /// Full kind including the `custom:` prefix
+    #[schema(example = "custom:press_mention")]
+    pub kind: String,
+    /// Optional human-readable description
+    pub description: Option<String>,
+    /// Timestamp when the kind was registered
+    #[schema(example = "2024-01-15T10:30:00Z")]
+    pub created_at: String,
+}
+
+/// Signal kinds available to the tenant
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SignalKindsResponse {
+    /// Built-in kinds produced by the connectors
+    pub canonical: Vec<String>,
+    /// Custom kinds registered by the tenant
+    pub custom: Vec<CustomSignalKindInfo>,
+}
+
+/// Request body for registering a custom signal kind
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct RegisterSignalKindRequest {
+    /// Namespaced kind, e.g. `custom:press_mention`
+    #[schema(example = "custom:press_mention")]
+    pub kind: String,
+    /// Optional human-readable description
+    pub description: Option<String>,
+}
+
+/// Validate a `Signal.kind` for a tenant before ingesting it.
+///
+/// Canonical kinds are always accepted. Custom kinds must be well-formed and
+/// registered for the tenant; anything else is rejected with 400.
+pub async fn validate_signal_kind(
+    db: &DatabaseConnection,
+    tenant_id: Uuid,
+    kind: &str,
+) -> Result<AnySignalKind, ApiError> {
+    let parsed = AnySignalKind::parse(kind)
+        .map_err(|e| ApiError::new(StatusCode::BAD_REQUEST, "VALIDATION_FAILED", e.to_string()))?;
+    if let AnySignalKind::Custom(custom) = &parsed
+        && !TenantSignalKindRepository::new(db)
+            .is_registered(tenant_id, custom)
+            .await?
+    {
+        return Err(ApiError::new(
+            StatusCode::BAD_REQUEST,
+            "VALIDATION_FAILED",
+            format!("custom signal kind '{}' is not registered", custom),
+        ));
+    }
+    Ok(parsed)
+}
+
+/// List canonical signal kinds and the tenant's custom kinds
+#[utoipa::path(
+    get,
+    path = "/signal-kinds",
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Signal kinds listed successfully", body = SignalKindsResponse),
+        (status = 400, description = "Missing or invalid tenant header", body = ApiError),
+        (status = 401, description = "Missing or invalid bearer token", body = ApiError)
+    ),
+    tag = "signals"
+)]
+pub async fn list_signal_kinds(
+    State(state): State<AppState>,
+    _operator_auth: OperatorAuth,
+    TenantExtension(tenant): TenantExtension,
+) -> Result<Json<SignalKindsResponse>, ApiError> {
+    let custom = TenantSignalKindRepository::new(&state.db)
+        .list(tenant.0)
+        .await?
+        .into_iter()
+        .map(|registration| CustomSignalKindInfo {
+            kind: registration.kind,
+            description: registration.description,
+            created_at: registration.created_at.to_rfc3339(),
+        })
+        .collect();
+
+    Ok(Json(SignalKindsResponse {
+        canonical: ALL_SIGNAL_KINDS
+            .iter()
+            .map(|kind| kind.as_str().to_string())
+            .collect(),
+        custom,
+    }))
+}
+
+/// Register a custom signal kind for the tenant
+///
+/// Re-registering an existing kind updates its description.
+#[utoipa::path(
+    post,
+    path = "/signal-kinds",
+    security(("bearer_auth" = [])),
+    request_body = RegisterSignalKindRequest,
+    responses(
+        (status = 201, description = "Custom signal kind registered", body = CustomSignalKindInfo),
+        (status = 400, description = "Kind is not a valid custom kind", body = ApiError, example = json!({
+            "status": 400,
+            "code": "VALIDATION_FAILED",
+            "message": "custom kinds must use the 'custom:' namespace",
+            "trace_id": "corr-12345678"
+        })),
+        (status = 401, description = "Missing or invalid bearer token", body = ApiError)
+    ),
+    tag = "signals"
+)]
+pub async fn register_signal_kind(
+    State(state): State<AppState>,
+    _operator_auth: OperatorAuth,
+    TenantExtension(tenant): TenantExtension,
+    Json(request): Json<RegisterSignalKindRequest>,
+) -> Result<(StatusCode, Json<CustomSignalKindInfo>), ApiError> {
+    let (kind, description) = validate_registration(request)?;
+
+    let registration = TenantSignalKindRepository::new(&state.db)
+        .register(tenant.0, &kind, description)
+        .await?;
+
+    AuditLogRepository::new(state.db.clone())
+        .record_or_log(NewAuditLogEntry {
+            tenant_id: Some(tenant.0),
+            actor: API_AUDIT_ACTOR.to_string(),
+            action: SIGNAL_KIND_REGISTERED_AUDIT_ACTION.to_string(),
+            target_type: "signal_kind".to_string(),
+            target_id: registration.kind.clone(),
+            reason: None,
+            details: Some(serde_json::json!({ "description": registration.description })),
+        })
+        .await;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(CustomSignalKindInfo {
+            kind: registration.kind,
+            description: registration.description,
+            created_at: registration.created_at.to_rfc3339(),
+        }),
+    ))
+}
+
+/// Validate a registration request, returning the kind and trimmed description
+pub(crate) fn validate_registration(
+    request: RegisterSignalKindRequest,
+) -> Result<(String, Option<String>), ApiError> {
+    let kind = match AnySignalKind::parse(request.kind.trim()) {
+        Ok(AnySignalKind::Custom(kind)) => kind,
+        Ok(AnySignalKind::Canonical(_)) | Err(_) => {
+            return Err(ApiError::new(
+                StatusCode::BAD_REQUEST,
+                "VALIDATION_FAILED",
+                "custom kinds must use the 'custom:' namespace followed by lowercase letters, digits and underscores",
+            ));
+        }
+    };
+    let description = request
+        .description
+        .map(|d| d.trim().to_string())
+        .filter(|d| !d.is_empty());
+    if description
+        .as_ref()
+        .is_some_and(|d| d.len() > MAX_DESCRIPTION_LEN)
+    {
+        return Err(ApiError::new(
+            StatusCode::BAD_REQUEST,
+            "VALIDATION_FAILED",
+            format!(
+                "description must be at most {} characters",
+                MAX_DESCRIPTION_LEN
+            ),
+        ));
+    }
+
+    Ok((kind, description))
+}
@@ -6,11 +6,23 @@
 use crate::auth::{OperatorAuth, TenantExtension};
 use crate::cursor::{decode_cursor, encode_cursor};
 use crate::error::ApiError;
-use crate::repositories::SignalRepository;
+use crate::models::signal_outbox;
+use crate::models::signal_pipeline_event::STAGE_INGESTED;
+use crate::repositories::sync_job::SyncJobRepository;
+use crate::repositories::{
+    GroundedSignalRepository, SignalOutboxRepository, SignalPipelineEventRepository,
+    SignalRepository,
+};
 use crate::server::AppState;
-use axum::{extract::Query, extract::State, http::StatusCode, response::Json};
+use crate::signals::outbox::TOPIC_WEAK_ENGINE_ENQUEUE;
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::Json,
+};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use serde_json::json;
 use std::str::FromStr;
 use utoipa::ToSchema;
 use uuid::Uuid;
@@ -331,6 +343,294 @@ pub async fn list_signals(
     }))
 }
 
+/// Outcome of a pipeline stage in a signal trace
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TraceStageStatus {
+    /// The stage ran and the signal passed through it
+    Completed,
+    /// The stage has not run yet
+    Pending,
+    /// The stage ran and failed
+    Failed,
+    /// The stage does not apply to this signal
+    Skipped,
+    /// No record of the stage was kept for this signal
+    Unknown,
+}
+
+/// One stage in a signal's pipeline trace
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TraceStage {
+    /// Stage name (e.g., "sync_job", "dedupe", "delivery:signal.created")
+    #[schema(example = "dedupe")]
+    pub stage: String,
+    /// Outcome of the stage
+    pub status: TraceStageStatus,
+    /// Timestamp when the stage happened (RFC3339), if known
+    #[schema(example = "2024-01-15T10:30:05Z")]
+    pub at: Option<String>,
+    /// Stage-specific details
+    pub details: serde_json::Value,
+}
+
+/// Response payload for the signal trace endpoint
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SignalTraceResponse {
+    /// Signal identifier
+    #[schema(example = "550e8400-e29b-41d4-a716-446655440000")]
+    pub signal_id: String,
+    /// Pipeline stages in processing order
+    pub stages: Vec<TraceStage>,
+}
+
+/// Grounded signals scanned for cluster membership
+const TRACE_GROUNDED_SCAN_LIMIT: u64 = 500;
+
+/// Trace a signal through the ingestion pipeline
+///
+/// Stages are reconstructed from the recorded pipeline events, the sync job
+/// that produced the signal, its outbox deliveries and the grounded signals
+/// created after it. There is no rules engine yet, so no rule stage is reported.
+#[utoipa::path(
+    get,
+    path = "/signals/{id}/trace",
+    security(("bearer_auth" = [])),
+    params(
+        ("id" = String, Path, description = "Signal ID (UUID)")
+    ),
+    responses(
+        (status = 200, description = "Signal pipeline trace", body = SignalTraceResponse),
+        (status = 400, description = "Invalid signal ID", body = ApiError),
+        (status = 401, description = "Missing or invalid bearer token", body = ApiError),
+        (status = 404, description = "Signal not found", body = ApiError)
+    ),
+    tag = "signals"
+)]
+pub async fn get_signal_trace(
+    State(state): State<AppState>,
+    _operator_auth: OperatorAuth,
+    TenantExtension(tenant): TenantExtension,
+    Path(id): Path<String>,
+) -> Result<Json<SignalTraceResponse>, ApiError> {
+    let tenant_id = tenant.0;
+    let signal_id = Uuid::parse_str(&id).map_err(|_| {
+        ApiError::new(
+            StatusCode::BAD_REQUEST,
+            "VALIDATION_FAILED",
+            "id must be a valid UUID",
+        )
+    })?;
+
+    let signal = SignalRepository::new(&state.db)
+        .find_for_tenant(tenant_id, signal_id)
+        .await?
+        .ok_or_else(|| {
+            ApiError::new(
+                StatusCode::NOT_FOUND,
+                "NOT_FOUND",
+                format!("Signal {} not found", signal_id),
+            )
+        })?;
+
+    let mut stages = Vec::new();
+
+    // Sync job (and webhook delivery) that produced the signal
+    let events = SignalPipelineEventRepository::new(state.db.clone())
+        .list_for_signal(tenant_id, signal_id)
+        .await?;
+    let job_id = events
+        .iter()
+        .find(|event| event.stage == STAGE_INGESTED)
+        .and_then(|event| event.detail.as_ref())
+        .and_then(|detail| detail.get("sync_job_id"))
+        .and_then(|value| value.as_str())
+        .and_then(|value| Uuid::parse_str(value).ok());
+    let job = match job_id {
+        Some(job_id) => {
+            SyncJobRepository::new(state.db.clone())
+                .find_by_id(job_id)
+                .await?
+        }
+        None => None,
+    };
+    match (job_id, job) {
+        (_, Some(job)) => {
+            if job.job_type == "webhook" {
+                stages.push(TraceStage {
+                    stage: "webhook_received".to_string(),
+                    status: TraceStageStatus::Completed,
+                    at: Some(job.created_at.to_rfc3339()),
+                    details: json!({ "sync_job_id": job.id }),
+                });
+            }
+            stages.push(TraceStage {
+                stage: "sync_job".to_string(),
+                status: TraceStageStatus::Completed,
+                at: job.finished_at.or(job.started_at).map(|at| at.to_rfc3339()),
+                details: json!({
+                    "job_id": job.id,
+                    "job_type": job.job_type,
+                    "status": job.status,
+                    "started_at": job.started_at.map(|at| at.to_rfc3339()),
+                    "finished_at": job.finished_at.map(|at| at.to_rfc3339()),
+                }),
+            });
+        }
+        (Some(job_id), None) => stages.push(TraceStage {
+            stage: "sync_job".to_string(),
+            status: TraceStageStatus::Completed,
+            at: None,
+            details: json!({ "job_id": job_id, "note": "sync job has been purged" }),
+        }),
+        (None, None) => stages.push(TraceStage {
+            stage: "sync_job".to_string(),
+            status: TraceStageStatus::Unknown,
+            at: None,
+            details: json!({ "note": "signal predates pipeline event recording" }),
+        }),
+    }
+
+    stages.push(TraceStage {
+        stage: "received".to_string(),
+        status: TraceStageStatus::Completed,
+        at: Some(signal.received_at.to_rfc3339()),
+        details: json!({
+            "provider": signal.provider_slug,
+            "connection_id": signal.connection_id,
+            "kind": signal.kind,
+        }),
+    });
+
+    // Spam-classified Gmail messages are dropped before a signal is created,
+    // so any Gmail signal that exists passed the filter
+    stages.push(if signal.provider_slug == "gmail" {
+        TraceStage {
+            stage: "spam_filter".to_string(),
+            status: TraceStageStatus::Completed,
+            at: Some(signal.received_at.to_rfc3339()),
+            details: json!({ "verdict": "not_spam" }),
+        }
+    } else {
+        TraceStage {
+            stage: "spam_filter".to_string(),
+            status: TraceStageStatus::Skipped,
+            at: None,
+            details: json!({ "note": "provider has no spam filter" }),
+        }
+    });
+
+    stages.push(match signal.dedupe_key.as_deref() {
+        Some(key) => {
+            let first = SignalRepository::new(&state.db)
+                .first_with_dedupe_key(tenant_id, key)
+                .await?;
+            let details = match first {
+                Some(first) if first != signal.id => {
+                    json!({ "dedupe_key": key, "decision": "duplicate", "duplicate_of": first })
+                }
+                _ => json!({ "dedupe_key": key, "decision": "first_seen" }),
+            };
+            TraceStage {
+                stage: "dedupe".to_string(),
+                status: TraceStageStatus::Completed,
+                at: Some(signal.received_at.to_rfc3339()),
+                details,
+            }
+        }
+        None => TraceStage {
+            stage: "dedupe".to_string(),
+            status: TraceStageStatus::Skipped,
+            at: None,
+            details: json!({ "note": "signal has no dedupe key" }),
+        },
+    });
+
+    // Outbox deliveries to downstream consumers
+    let deliveries = SignalOutboxRepository::new(state.db.clone())
+        .list_for_signal(tenant_id, signal_id)
+        .await?;
+    let weak_engine_pending = deliveries.iter().any(|entry| {
+        entry.topic == TOPIC_WEAK_ENGINE_ENQUEUE && entry.status != signal_outbox::STATUS_PUBLISHED
+    });
+    for entry in deliveries {
+        let status = match entry.status.as_str() {
+            signal_outbox::STATUS_PUBLISHED => TraceStageStatus::Completed,
+            signal_outbox::STATUS_FAILED => TraceStageStatus::Failed,
+            _ => TraceStageStatus::Pending,
+        };
+        stages.push(TraceStage {
+            stage: format!("delivery:{}", entry.topic),
+            status,
+            at: Some(entry.published_at.unwrap_or(entry.updated_at).to_rfc3339()),
+            details: json!({
+                "attempts": entry.attempts,
+                "last_error": entry.last_error,
+            }),
+        });
+    }
+
+    // Cluster membership and promotion to a grounded signal
+    let grounded = GroundedSignalRepository::new(&state.db)
+        .list_created_since(tenant_id, signal.received_at, TRACE_GROUNDED_SCAN_LIMIT)
+        .await?;
+    let mut clustered = false;
+    for grounded_signal in grounded {
+        let member = grounded_signal.signal_id == signal_id
+            || grounded_signal
+                .evidence
+                .get("related_signals")
+                .and_then(|related| related.as_array())
+                .is_some_and(|related| {
+                    related.iter().any(|entry| {
+                        entry.get("id").and_then(|id| id.as_str()) == Some(id.as_str())
+                    })
+                });
+        if !member {
+            continue;
+        }
+        clustered = true;
+        stages.push(TraceStage {
+            stage: "cluster".to_string(),
+            status: TraceStageStatus::Completed,
+            at: Some(grounded_signal.created_at.to_rfc3339()),
+            details: json!({
+                "grounded_signal_id": grounded_signal.id,
+                "cluster_size": grounded_signal.evidence.get("cluster_size"),
+            }),
+        });
+        if grounded_signal.signal_id == signal_id {
+            stages.push(TraceStage {
+                stage: "grounded_signal".to_string(),
+                status: TraceStageStatus::Completed,
+                at: Some(grounded_signal.created_at.to_rfc3339()),
+                details: json!({
+                    "grounded_signal_id": grounded_signal.id,
+                    "status": grounded_signal.status,
+                    "total_score": grounded_signal.total_score,
+                }),
+            });
+        }
+    }
+    if !clustered {
+        stages.push(TraceStage {
+            stage: "cluster".to_string(),
+            status: if weak_engine_pending {
+                TraceStageStatus::Pending
+            } else {
+                TraceStageStatus::Skipped
+            },
+            at: None,
+            details: json!({ "note": "signal is not part of a grounded signal cluster" }),
+        });
+    }
+
+    Ok(Json(SignalTraceResponse {
+        signal_id: signal_id.to_string(),
+        stages,
+    }))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,57 @@
+//! # SLO API Handlers
+//!
+//! This module serves the error budget report used in reliability reviews.
+
+use crate::auth::OperatorAuth;
+use crate::error::ApiError;
+use crate::server::AppState;
+use crate::slo::{self, SloStatus};
+use axum::{extract::State, response::Json};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Success rates and error budgets per provider and operation
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct SloReport {
+    /// Length of the rolling window in hours
+    #[schema(example = 168)]
+    pub window_hours: u64,
+    /// Target applied to providers without an override
+    #[schema(example = 0.99)]
+    pub default_target: f64,
+    /// One entry per provider and operation with attempts in the window
+    pub objectives: Vec<SloStatus>,
+}
+
+/// Success rates and remaining error budgets over the rolling SLO window
+///
+/// Covers sync job attempts, webhook job attempts and tenant notification
+/// deliveries across all tenants.
+#[utoipa::path(
+    get,
+    path = "/admin/slo",
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "SLO report", body = SloReport),
+        (status = 401, description = "Missing or invalid operator credentials", body = ApiError),
+        (status = 500, description = "Internal server error", body = ApiError)
+    ),
+    tag = "operators"
+)]
+pub async fn get_slo_report(
+    State(state): State<AppState>,
+    _operator_auth: OperatorAuth,
+) -> Result<Json<SloReport>, ApiError> {
+    let objectives = slo::report(&state.db, &state.config.slo)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to load SLO outcomes: {}", e);
+            ApiError::internal_server_error("Failed to load SLO report")
+        })?;
+
+    Ok(Json(SloReport {
+        window_hours: state.config.slo.window_hours,
+        default_target: state.config.slo.default_target,
+        objectives,
+    }))
+}
@@ -0,0 +1,259 @@
+//! # Stats API Handlers
+//!
+//! This module contains handlers serving historical job and signal statistics
+//! from the daily rollup tables, and the slow query report used for
+//! performance triage.
+
+use std::collections::BTreeMap;
+
+use crate::auth::{OperatorAuth, TenantExtension};
+use crate::error::{ApiError, validation_error};
+use crate::query_stats::{self, SLOW_QUERY_WINDOW, SlowQuerySummary};
+use crate::repositories::RollupRepository;
+use crate::server::AppState;
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::Json,
+};
+use chrono::{Duration, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Default number of days returned when no range is given
+const DEFAULT_RANGE_DAYS: i64 = 30;
+
+/// Maximum number of days a single request may span
+const MAX_RANGE_DAYS: i64 = 366;
+
+/// Default number of statements in the slow query report
+const DEFAULT_SLOW_QUERY_LIMIT: usize = 20;
+
+/// Maximum number of statements in the slow query report
+const MAX_SLOW_QUERY_LIMIT: usize = 100;
+
+/// Query parameters for daily stats
+#[derive(Debug, Deserialize)]
+pub struct DailyStatsQuery {
+    /// First day to include (YYYY-MM-DD, UTC)
+    pub from: Option<String>,
+    /// Last day to include (YYYY-MM-DD, UTC)
+    pub to: Option<String>,
+    /// Filter by provider slug
+    pub provider: Option<String>,
+}
+
+/// Aggregated statistics for one day and provider
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct DailyStatsEntry {
+    /// UTC day the aggregate covers
+    #[schema(example = "2024-01-15")]
+    pub day: String,
+    /// Provider slug
+    #[schema(example = "github")]
+    pub provider_slug: String,
+    /// Jobs that finished successfully
+    #[schema(example = 42)]
+    pub jobs_succeeded: i64,
+    /// Jobs that finished with a terminal failure
+    #[schema(example = 1)]
+    pub jobs_failed: i64,
+    /// Mean duration of finished jobs in milliseconds
+    #[schema(example = 1520.5)]
+    pub mean_sync_duration_ms: Option<f64>,
+    /// Signals received, keyed by signal kind
+    pub signals_by_kind: BTreeMap<String, i64>,
+}
+
+/// Response payload for daily stats endpoint
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct DailyStatsResponse {
+    /// First day included (YYYY-MM-DD)
+    pub from: String,
+    /// Last day included (YYYY-MM-DD)
+    pub to: String,
+    /// Entries ordered by day, then provider
+    pub days: Vec<DailyStatsEntry>,
+}
+
+/// Query parameters for the slow query report
+#[derive(Debug, Deserialize)]
+pub struct SlowQueriesQuery {
+    /// Maximum number of statements to return (1-100)
+    pub limit: Option<usize>,
+}
+
+/// Response payload for slow query report endpoint
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct SlowQueryReport {
+    /// Length of the reporting window in seconds
+    #[schema(example = 3600)]
+    pub window_seconds: u64,
+    /// Duration above which a statement counts as slow (0 when disabled)
+    #[schema(example = 500)]
+    pub threshold_ms: u64,
+    /// Worst offenders ordered by total slow time, descending
+    pub queries: Vec<SlowQuerySummary>,
+}
+
+/// Daily job and signal statistics for the tenant, served from rollups
+#[utoipa::path(
+    get,
+    path = "/stats/daily",
+    security(("bearer_auth" = [])),
+    params(
+        ("from" = Option<String>, Query, description = "First day to include (YYYY-MM-DD, UTC). Defaults to 30 days before `to`"),
+        ("to" = Option<String>, Query, description = "Last day to include (YYYY-MM-DD, UTC). Defaults to yesterday"),
+        ("provider" = Option<String>, Query, description = "Filter by provider slug")
+    ),
+    responses(
+        (status = 200, description = "Daily statistics for the tenant", body = DailyStatsResponse, example = json!({
+            "from": "2024-01-15",
+            "to": "2024-01-15",
+            "days": [
+                {
+                    "day": "2024-01-15",
+                    "provider_slug": "github",
+                    "jobs_succeeded": 42,
+                    "jobs_failed": 1,
+                    "mean_sync_duration_ms": 1520.5,
+                    "signals_by_kind": { "issue_created": 12, "pr_merged": 3 }
+                }
+            ]
+        })),
+        (status = 400, description = "Invalid query parameters", body = ApiError),
+        (status = 401, description = "Missing or invalid bearer token", body = ApiError),
+        (status = 500, description = "Internal server error", body = ApiError)
+    ),
+    tag = "stats"
+)]
+pub async fn get_daily_stats(
+    State(state): State<AppState>,
+    _operator_auth: OperatorAuth,
+    TenantExtension(tenant): TenantExtension,
+    Query(params): Query<DailyStatsQuery>,
+) -> Result<Json<DailyStatsResponse>, ApiError> {
+    let to = match &params.to {
+        Some(value) => parse_day("to", value)?,
+        None => Utc::now().date_naive() - Duration::days(1),
+    };
+    let from = match &params.from {
+        Some(value) => parse_day("from", value)?,
+        None => to - Duration::days(DEFAULT_RANGE_DAYS - 1),
+    };
+
+    if from > to {
+        return Err(validation_error(
+            "Invalid date range",
+            serde_json::json!({ "from": "Must not be after `to`" }),
+        ));
+    }
+    if (to - from).num_days() >= MAX_RANGE_DAYS {
+        return Err(validation_error(
+            "Invalid date range",
+            serde_json::json!({ "from": format!("Range must not exceed {} days", MAX_RANGE_DAYS) }),
+        ));
+    }
+
+    let repo = RollupRepository::new(state.db.clone());
+    let provider = params.provider.as_deref();
+    let map_err = |e: crate::error::RepositoryError| {
+        tracing::error!("Failed to load daily rollups: {}", e);
+        ApiError::new(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "INTERNAL_SERVER_ERROR",
+            "Failed to load stats",
+        )
+    };
+    let job_rollups = repo
+        .list_job_rollups(tenant.0, from, to, provider)
+        .await
+        .map_err(map_err)?;
+    let signal_rollups = repo
+        .list_signal_rollups(tenant.0, from, to, provider)
+        .await
+        .map_err(map_err)?;
+
+    let mut entries: BTreeMap<(NaiveDate, String), DailyStatsEntry> = BTreeMap::new();
+    for rollup in job_rollups {
+        let entry = stats_entry(&mut entries, rollup.day, &rollup.provider_slug);
+        entry.jobs_succeeded = rollup.jobs_succeeded;
+        entry.jobs_failed = rollup.jobs_failed;
+        entry.mean_sync_duration_ms = rollup.mean_duration_ms;
+    }
+    for rollup in signal_rollups {
+        let entry = stats_entry(&mut entries, rollup.day, &rollup.provider_slug);
+        entry
+            .signals_by_kind
+            .insert(rollup.kind, rollup.signal_count);
+    }
+
+    Ok(Json(DailyStatsResponse {
+        from: from.to_string(),
+        to: to.to_string(),
+        days: entries.into_values().collect(),
+    }))
+}
+
+/// Slowest database statements across the service over the last hour
+///
+/// Statements are grouped by normalized SQL and the repository method that
+/// issued them. The report is process-local and covers all tenants.
+#[utoipa::path(
+    get,
+    path = "/stats/slow-queries",
+    security(("bearer_auth" = [])),
+    params(
+        ("limit" = Option<usize>, Query, description = "Maximum number of statements to return (1-100, default 20)")
+    ),
+    responses(
+        (status = 200, description = "Slow query report", body = SlowQueryReport),
+        (status = 400, description = "Invalid query parameters", body = ApiError),
+        (status = 401, description = "Missing or invalid bearer token", body = ApiError)
+    ),
+    tag = "stats"
+)]
+pub async fn get_slow_queries(
+    _operator_auth: OperatorAuth,
+    Query(params): Query<SlowQueriesQuery>,
+) -> Result<Json<SlowQueryReport>, ApiError> {
+    let limit = params.limit.unwrap_or(DEFAULT_SLOW_QUERY_LIMIT);
+    if !(1..=MAX_SLOW_QUERY_LIMIT).contains(&limit) {
+        return Err(validation_error(
+            "Invalid limit",
+            serde_json::json!({ "limit": format!("Must be between 1 and {}", MAX_SLOW_QUERY_LIMIT) }),
+        ));
+    }
+
+    Ok(Json(SlowQueryReport {
+        window_seconds: SLOW_QUERY_WINDOW.as_secs(),
+        threshold_ms: query_stats::slow_query_threshold_ms(),
+        queries: query_stats::slow_query_report(limit),
+    }))
+}
+
+fn stats_entry<'a>(
+    entries: &'a mut BTreeMap<(NaiveDate, String), DailyStatsEntry>,
+    day: NaiveDate,
+    provider_slug: &str,
+) -> &'a mut DailyStatsEntry {
+    entries
+        .entry((day, provider_slug.to_string()))
+        .or_insert_with(|| DailyStatsEntry {
+            day: day.to_string(),
+            provider_slug: provider_slug.to_string(),
+            jobs_succeeded: 0,
+            jobs_failed: 0,
+            mean_sync_duration_ms: None,
+            signals_by_kind: BTreeMap::new(),
+        })
+}
+
+fn parse_day(field: &str, value: &str) -> Result<NaiveDate, ApiError> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d").map_err(|_| {
+        validation_error(
+            &format!("Invalid {}", field),
+            serde_json::json!({ field: "Must be a date in YYYY-MM-DD format" }),
+        )
+    })
+}
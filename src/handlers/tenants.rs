@@ -3,19 +3,66 @@
 //! This module contains handlers for tenant creation and management endpoints.
 
 use crate::auth::{OperatorAuth, TenantExtension};
-use crate::error::ApiError;
-use crate::repositories::{CreateTenantRequest, TenantRepository};
+use crate::config_resolution::{ConfigLayers, ConfigOverrides, EffectiveConfig};
+use crate::connection_pause::{LEGACY_AUTO_PAUSED_AUDIT_ACTION, PauseTransition};
+use crate::crypto::CryptoKey;
+use crate::error::{ApiError, validation_error};
+use crate::handlers::connect::{
+    CONNECTION_CREATED_AUDIT_ACTION, CONNECTION_REAUTHORIZED_AUDIT_ACTION,
+};
+use crate::handlers::connections::{
+    CONNECTION_PAUSED_AUDIT_ACTION, CONNECTION_RESUMED_AUDIT_ACTION,
+};
+use crate::handlers::signal_kinds::{
+    CustomSignalKindInfo, RegisterSignalKindRequest, SIGNAL_KIND_REGISTERED_AUDIT_ACTION,
+    validate_registration,
+};
+use crate::models::tenant_config::TenantConfigSettings;
+use crate::repositories::audit_log::API_AUDIT_ACTOR;
+use crate::repositories::tenant::MAX_SANDBOX_SIGNAL_SAMPLE;
+use crate::repositories::{
+    AuditLogRepository, CreateTenantRequest, NewAuditLogEntry, SandboxClone, SandboxSignalSample,
+    TenantBootstrap, TenantConfigRepository, TenantRepository, TenantSignalConfigRepository,
+};
 use crate::server::AppState;
+use crate::signals::{WeakSignalEngine, WeakSignalEngineConfig};
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
     http::StatusCode,
     response::Json,
 };
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 use uuid::Uuid;
 
+/// Audit action recorded when a tenant is created or bootstrapped
+pub const TENANT_CREATED_AUDIT_ACTION: &str = "tenant.created";
+
+/// Audit action recorded when a tenant's organization or setting overrides change
+pub const TENANT_SETTINGS_UPDATED_AUDIT_ACTION: &str = "tenant.settings_updated";
+
+/// Audit action recorded when a tenant's `tenant_configs` overrides change
+pub const TENANT_CONFIG_UPDATED_AUDIT_ACTION: &str = "tenant.config_updated";
+
+/// Audit action recorded when a sandbox tenant is cloned from another tenant
+pub const TENANT_SANDBOX_CLONED_AUDIT_ACTION: &str = "tenant.sandbox_cloned";
+
+/// Audit action recorded when a sandbox tenant's signals are scored again
+pub const TENANT_SANDBOX_REPROCESSED_AUDIT_ACTION: &str = "tenant.sandbox_reprocessed";
+
+/// Audit action recorded when a tenant's webhook signing secret is rotated
+pub const TENANT_WEBHOOK_SECRET_ROTATED_AUDIT_ACTION: &str = "tenant.webhook_secret_rotated";
+
+/// How far back signals are sampled for a sandbox when no `since` is given
+const DEFAULT_SANDBOX_SAMPLE_DAYS: i64 = 30;
+
+/// Default number of entries in a tenant event feed page
+const DEFAULT_EVENTS_LIMIT: u64 = 50;
+
+/// Maximum number of entries in a tenant event feed page
+const MAX_EVENTS_LIMIT: u64 = 200;
+
 /// Request payload for creating a new tenant
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct CreateTenantRequestDto {
@@ -143,6 +190,19 @@ pub async fn create_tenant(
         })));
         api_err
     })?;
+    state.snapshot.invalidate();
+
+    AuditLogRepository::new(state.db.clone())
+        .record_or_log(NewAuditLogEntry {
+            tenant_id: Some(tenant.id),
+            actor: API_AUDIT_ACTOR.to_string(),
+            action: TENANT_CREATED_AUDIT_ACTION.to_string(),
+            target_type: "tenant".to_string(),
+            target_id: tenant.id.to_string(),
+            reason: None,
+            details: Some(serde_json::json!({ "name": tenant.name })),
+        })
+        .await;
 
     let response_data = CreateTenantResponseDto {
         id: tenant.id.to_string(),
@@ -168,6 +228,537 @@ pub async fn create_tenant(
     ))
 }
 
+/// Initial signal configuration for a bootstrapped tenant
+#[derive(Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct BootstrapSignalConfigDto {
+    /// Weak signal threshold between 0.0 and 1.0 (default: 0.7)
+    #[schema(example = 0.7)]
+    pub weak_signal_threshold: Option<f32>,
+    /// HTTPS webhook notified about signals and connection state changes
+    #[schema(example = "https://example.com/hooks/poblysh")]
+    pub webhook_url: Option<String>,
+}
+
+/// Request payload for bootstrapping a tenant
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct BootstrapTenantRequestDto {
+    /// Display name for the tenant (required, max 255 characters)
+    #[schema(example = "Acme Corp")]
+    pub name: String,
+    /// Optional metadata for the tenant
+    pub metadata: Option<serde_json::Value>,
+    /// Organization whose default settings the tenant inherits
+    #[schema(value_type = Option<String>)]
+    pub organization_id: Option<Uuid>,
+    /// Signal configuration; defaults apply when omitted
+    #[serde(default)]
+    pub signal_config: BootstrapSignalConfigDto,
+    /// Custom signal kinds to register for the tenant
+    #[serde(default)]
+    pub custom_signal_kinds: Vec<RegisterSignalKindRequest>,
+}
+
+/// Signal configuration created for a bootstrapped tenant
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BootstrapSignalConfigResponseDto {
+    /// Weak signal threshold
+    #[schema(example = 0.7)]
+    pub weak_signal_threshold: f32,
+    /// Notification webhook URL, if configured
+    pub webhook_url: Option<String>,
+}
+
+/// Response payload for tenant bootstrap
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BootstrapTenantResponseDto {
+    /// The created tenant
+    pub tenant: CreateTenantResponseDto,
+    /// The tenant's signal configuration
+    pub signal_config: BootstrapSignalConfigResponseDto,
+    /// Whether a per-tenant data key was provisioned for token encryption
+    pub data_key_provisioned: bool,
+    /// Custom signal kinds registered for the tenant
+    pub custom_signal_kinds: Vec<CustomSignalKindInfo>,
+}
+
+/// Create a tenant together with its signal config, data key and custom signal kinds
+///
+/// All records are created in a single transaction, so a failure leaves no
+/// partially configured tenant behind.
+#[utoipa::path(
+    post,
+    path = "/api/v1/tenants/bootstrap",
+    security(("bearer_auth" = [])),
+    request_body = BootstrapTenantRequestDto,
+    responses(
+        (status = 201, description = "Tenant bootstrapped successfully", body = TenantApiResponse<BootstrapTenantResponseDto>, headers(
+            ("Location", description = "URL of the created tenant"),
+            ("X-Trace-Id", description = "Trace identifier for request correlation")
+        )),
+        (status = 400, description = "Validation failed", body = ApiError),
+        (status = 401, description = "Missing or invalid bearer token", body = ApiError),
+        (status = 403, description = "Insufficient permissions", body = ApiError),
+        (status = 404, description = "Organization not found", body = ApiError),
+        (status = 500, description = "Internal server error", body = ApiError)
+    ),
+    tag = "tenants"
+)]
+pub async fn bootstrap_tenant(
+    State(state): State<AppState>,
+    _operator_auth: OperatorAuth,
+    TenantExtension(_tenant): TenantExtension,
+    Json(request): Json<BootstrapTenantRequestDto>,
+) -> Result<
+    (
+        StatusCode,
+        [(&'static str, String); 2],
+        Json<TenantApiResponse<BootstrapTenantResponseDto>>,
+    ),
+    ApiError,
+> {
+    let trace_id = Uuid::new_v4().to_string();
+
+    let mut custom_signal_kinds = Vec::with_capacity(request.custom_signal_kinds.len());
+    for registration in request.custom_signal_kinds {
+        let (kind, description) = validate_registration(registration)?;
+        if custom_signal_kinds
+            .iter()
+            .any(|(existing, _): &(String, Option<String>)| *existing == kind)
+        {
+            return Err(ApiError::new(
+                StatusCode::BAD_REQUEST,
+                "VALIDATION_FAILED",
+                format!("Signal kind '{}' is listed more than once", kind),
+            ));
+        }
+        custom_signal_kinds.push((kind, description));
+    }
+
+    let tenant_id = Uuid::new_v4();
+    let (data_key_version, wrapped_data_key) = state
+        .master_keys
+        .wrap_tenant_key(tenant_id, &CryptoKey::generate())
+        .map_err(|e| {
+            ApiError::new(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "INTERNAL_SERVER_ERROR",
+                format!("Failed to provision tenant data key: {}", e),
+            )
+        })?;
+
+    let bootstrapped = TenantRepository::new(&state.db)
+        .bootstrap_tenant(TenantBootstrap {
+            tenant_id,
+            name: request.name.trim().to_string(),
+            organization_id: request.organization_id,
+            weak_signal_threshold: request.signal_config.weak_signal_threshold.unwrap_or(0.7),
+            webhook_url: request.signal_config.webhook_url,
+            wrapped_data_key,
+            data_key_version,
+            custom_signal_kinds,
+        })
+        .await?;
+    state.snapshot.invalidate();
+
+    let tenant = bootstrapped.tenant;
+    AuditLogRepository::new(state.db.clone())
+        .record_or_log(NewAuditLogEntry {
+            tenant_id: Some(tenant.id),
+            actor: API_AUDIT_ACTOR.to_string(),
+            action: TENANT_CREATED_AUDIT_ACTION.to_string(),
+            target_type: "tenant".to_string(),
+            target_id: tenant.id.to_string(),
+            reason: None,
+            details: Some(serde_json::json!({
+                "name": tenant.name,
+                "bootstrap": true,
+                "organization_id": tenant.organization_id,
+                "custom_signal_kinds": bootstrapped
+                    .custom_signal_kinds
+                    .iter()
+                    .map(|registration| registration.kind.as_str())
+                    .collect::<Vec<_>>(),
+            })),
+        })
+        .await;
+
+    let response = TenantApiResponse {
+        data: BootstrapTenantResponseDto {
+            tenant: CreateTenantResponseDto {
+                id: tenant.id.to_string(),
+                name: tenant.name.unwrap_or_default(),
+                created_at: tenant.created_at.to_rfc3339(),
+                metadata: request.metadata,
+            },
+            signal_config: BootstrapSignalConfigResponseDto {
+                weak_signal_threshold: bootstrapped.signal_config.weak_signal_threshold,
+                webhook_url: bootstrapped.signal_config.webhook_url,
+            },
+            data_key_provisioned: true,
+            custom_signal_kinds: bootstrapped
+                .custom_signal_kinds
+                .into_iter()
+                .map(|registration| CustomSignalKindInfo {
+                    kind: registration.kind,
+                    description: registration.description,
+                    created_at: registration.created_at.to_rfc3339(),
+                })
+                .collect(),
+        },
+        meta: TenantResponseMeta {
+            request_id: trace_id.clone(),
+            timestamp: Utc::now().to_rfc3339(),
+        },
+    };
+
+    Ok((
+        StatusCode::CREATED,
+        [
+            ("Location", format!("/api/v1/tenants/{}", tenant.id)),
+            ("X-Trace-Id", trace_id),
+        ],
+        Json(response),
+    ))
+}
+
+/// Signals to sample into a sandbox tenant
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SandboxSignalSampleDto {
+    /// Number of signals to copy (1-10000)
+    #[schema(example = 500)]
+    pub size: u64,
+    /// Only sample signals that occurred at or after this RFC3339 timestamp (default: 30 days ago)
+    #[schema(example = "2024-01-01T00:00:00Z")]
+    pub since: Option<String>,
+}
+
+/// Request payload for cloning a sandbox tenant
+#[derive(Debug, Default, Deserialize, ToSchema)]
+pub struct CloneSandboxRequestDto {
+    /// Display name for the sandbox (default: the source name followed by "sandbox")
+    #[schema(example = "Acme Corp sandbox")]
+    pub name: Option<String>,
+    /// Random sample of the source tenant's signals to copy; no signals are copied when omitted
+    pub signal_sample: Option<SandboxSignalSampleDto>,
+}
+
+/// Scoring configuration copied into a sandbox
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SandboxSignalConfigDto {
+    /// Weak signal threshold
+    #[schema(example = 0.7)]
+    pub weak_signal_threshold: f32,
+    /// Scoring weights overrides, if the source tenant had any
+    pub scoring_weights: Option<serde_json::Value>,
+}
+
+/// Response payload for a cloned sandbox tenant
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CloneSandboxResponseDto {
+    /// The sandbox tenant
+    pub tenant: CreateTenantResponseDto,
+    /// Tenant the sandbox was cloned from
+    #[schema(example = "550e8400-e29b-41d4-a716-446655440000")]
+    pub sandbox_of: String,
+    /// Scoring configuration copied from the source; absent if the source used defaults
+    pub signal_config: Option<SandboxSignalConfigDto>,
+    /// Custom signal kinds copied from the source
+    pub custom_signal_kinds: Vec<CustomSignalKindInfo>,
+    /// Placeholder connections created for the sampled signals
+    pub connections_copied: u64,
+    /// Signals copied from the source
+    pub signals_copied: u64,
+}
+
+/// Clone a tenant's configuration into a sandbox tenant
+///
+/// Copies settings, organization, signal threshold, scoring weights and
+/// custom signal kinds, and optionally a random sample of signals. Tokens,
+/// the notification webhook and grounded signals are never copied, and the
+/// sandbox's connections are never synced, so tuning the sandbox cannot
+/// affect the source tenant or reach its customers.
+#[utoipa::path(
+    post,
+    path = "/api/v1/tenants/{id}/clone-sandbox",
+    security(("bearer_auth" = [])),
+    params(
+        ("id" = Uuid, Path, description = "UUID of the tenant to clone")
+    ),
+    request_body = CloneSandboxRequestDto,
+    responses(
+        (status = 201, description = "Sandbox tenant created", body = TenantApiResponse<CloneSandboxResponseDto>, headers(
+            ("Location", description = "URL of the sandbox tenant"),
+            ("X-Trace-Id", description = "Trace identifier for request correlation")
+        )),
+        (status = 400, description = "Validation failed", body = ApiError),
+        (status = 401, description = "Missing or invalid bearer token", body = ApiError),
+        (status = 404, description = "Tenant not found", body = ApiError),
+        (status = 409, description = "The tenant is itself a sandbox", body = ApiError),
+        (status = 500, description = "Internal server error", body = ApiError)
+    ),
+    tag = "tenants"
+)]
+pub async fn clone_sandbox(
+    State(state): State<AppState>,
+    _operator_auth: OperatorAuth,
+    TenantExtension(_tenant): TenantExtension,
+    Path(source_tenant_id): Path<Uuid>,
+    Json(request): Json<CloneSandboxRequestDto>,
+) -> Result<
+    (
+        StatusCode,
+        [(&'static str, String); 2],
+        Json<TenantApiResponse<CloneSandboxResponseDto>>,
+    ),
+    ApiError,
+> {
+    let trace_id = Uuid::new_v4().to_string();
+
+    let signal_sample = request
+        .signal_sample
+        .map(|sample| {
+            if !(1..=MAX_SANDBOX_SIGNAL_SAMPLE).contains(&sample.size) {
+                return Err(validation_error(
+                    "Invalid signal sample size",
+                    serde_json::json!({
+                        "signal_sample.size": format!("Must be between 1 and {}", MAX_SANDBOX_SIGNAL_SAMPLE)
+                    }),
+                ));
+            }
+            let since = match sample.since.as_deref() {
+                Some(value) => DateTime::parse_from_rfc3339(value).map_err(|_| {
+                    validation_error(
+                        "Invalid signal sample since",
+                        serde_json::json!({ "signal_sample.since": "Must be an RFC3339 timestamp" }),
+                    )
+                })?,
+                None => (Utc::now() - chrono::Duration::days(DEFAULT_SANDBOX_SAMPLE_DAYS))
+                    .fixed_offset(),
+            };
+            Ok(SandboxSignalSample {
+                size: sample.size,
+                since,
+            })
+        })
+        .transpose()?;
+
+    let repo = TenantRepository::new(&state.db);
+    let source = repo
+        .get_tenant_by_id(source_tenant_id)
+        .await?
+        .ok_or_else(|| {
+            ApiError::new(
+                StatusCode::NOT_FOUND,
+                "TENANT_NOT_FOUND",
+                "Tenant not found",
+            )
+            .with_details(serde_json::json!({ "tenant_id": source_tenant_id.to_string() }))
+        })?;
+    if let Some(sandbox_of) = source.sandbox_of {
+        return Err(ApiError::new(
+            StatusCode::CONFLICT,
+            "CONFLICT",
+            "A sandbox tenant cannot be cloned; clone its source tenant instead",
+        )
+        .with_details(serde_json::json!({ "sandbox_of": sandbox_of.to_string() })));
+    }
+
+    let name = match request.name {
+        Some(name) => name.trim().to_string(),
+        None => {
+            let source_name: String = source
+                .name
+                .as_deref()
+                .unwrap_or("Tenant")
+                .chars()
+                .take(247)
+                .collect();
+            format!("{} sandbox", source_name.trim_end())
+        }
+    };
+
+    let tenant_id = Uuid::new_v4();
+    let (data_key_version, wrapped_data_key) = state
+        .master_keys
+        .wrap_tenant_key(tenant_id, &CryptoKey::generate())
+        .map_err(|e| {
+            ApiError::new(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "INTERNAL_SERVER_ERROR",
+                format!("Failed to provision tenant data key: {}", e),
+            )
+        })?;
+
+    let cloned = repo
+        .clone_sandbox(SandboxClone {
+            source_tenant_id,
+            tenant_id,
+            name,
+            wrapped_data_key,
+            data_key_version,
+            signal_sample,
+        })
+        .await?;
+    state.snapshot.invalidate();
+
+    let tenant = cloned.tenant;
+    AuditLogRepository::new(state.db.clone())
+        .record_or_log(NewAuditLogEntry {
+            tenant_id: Some(tenant.id),
+            actor: API_AUDIT_ACTOR.to_string(),
+            action: TENANT_SANDBOX_CLONED_AUDIT_ACTION.to_string(),
+            target_type: "tenant".to_string(),
+            target_id: tenant.id.to_string(),
+            reason: None,
+            details: Some(serde_json::json!({
+                "name": tenant.name,
+                "sandbox_of": source_tenant_id,
+                "connections_copied": cloned.connections_copied,
+                "signals_copied": cloned.signals_copied,
+            })),
+        })
+        .await;
+
+    let response = TenantApiResponse {
+        data: CloneSandboxResponseDto {
+            tenant: CreateTenantResponseDto {
+                id: tenant.id.to_string(),
+                name: tenant.name.unwrap_or_default(),
+                created_at: tenant.created_at.to_rfc3339(),
+                metadata: None,
+            },
+            sandbox_of: source_tenant_id.to_string(),
+            signal_config: cloned.signal_config.map(|config| SandboxSignalConfigDto {
+                weak_signal_threshold: config.weak_signal_threshold,
+                scoring_weights: config.scoring_weights,
+            }),
+            custom_signal_kinds: cloned
+                .custom_signal_kinds
+                .into_iter()
+                .map(|registration| CustomSignalKindInfo {
+                    kind: registration.kind,
+                    description: registration.description,
+                    created_at: registration.created_at.to_rfc3339(),
+                })
+                .collect(),
+            connections_copied: cloned.connections_copied,
+            signals_copied: cloned.signals_copied,
+        },
+        meta: TenantResponseMeta {
+            request_id: trace_id.clone(),
+            timestamp: Utc::now().to_rfc3339(),
+        },
+    };
+
+    Ok((
+        StatusCode::CREATED,
+        [
+            ("Location", format!("/api/v1/tenants/{}", tenant.id)),
+            ("X-Trace-Id", trace_id),
+        ],
+        Json(response),
+    ))
+}
+
+/// Result of scoring a sandbox tenant's signals again
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ReprocessSandboxResponseDto {
+    /// Grounded signals removed before scoring
+    pub grounded_signals_removed: u64,
+    /// Signals scored
+    pub signals_processed: usize,
+    /// Grounded signals created with the sandbox's current settings
+    pub grounded_signals_created: usize,
+}
+
+/// Score a sandbox tenant's signals again with its current settings
+///
+/// Replaces the sandbox's grounded signals with a fresh run over its most
+/// recent signals (up to 10000), using the sandbox's threshold and scoring
+/// weights. Notifications are not sent. Only sandbox tenants can be
+/// reprocessed.
+#[utoipa::path(
+    post,
+    path = "/api/v1/tenants/{id}/reprocess",
+    security(("bearer_auth" = [])),
+    params(
+        ("id" = Uuid, Path, description = "Sandbox tenant UUID")
+    ),
+    responses(
+        (status = 200, description = "Sandbox signals reprocessed", body = TenantApiResponse<ReprocessSandboxResponseDto>),
+        (status = 401, description = "Missing or invalid bearer token", body = ApiError),
+        (status = 404, description = "Tenant not found", body = ApiError),
+        (status = 409, description = "The tenant is not a sandbox", body = ApiError),
+        (status = 500, description = "Internal server error", body = ApiError)
+    ),
+    tag = "tenants"
+)]
+pub async fn reprocess_sandbox(
+    State(state): State<AppState>,
+    _operator_auth: OperatorAuth,
+    TenantExtension(_tenant): TenantExtension,
+    Path(tenant_id): Path<Uuid>,
+) -> Result<Json<TenantApiResponse<ReprocessSandboxResponseDto>>, ApiError> {
+    let tenant = TenantRepository::new(&state.db)
+        .get_tenant_by_id(tenant_id)
+        .await?
+        .ok_or_else(|| {
+            ApiError::new(
+                StatusCode::NOT_FOUND,
+                "TENANT_NOT_FOUND",
+                "Tenant not found",
+            )
+            .with_details(serde_json::json!({ "tenant_id": tenant_id.to_string() }))
+        })?;
+    if tenant.sandbox_of.is_none() {
+        return Err(ApiError::new(
+            StatusCode::CONFLICT,
+            "CONFLICT",
+            "Only sandbox tenants can be reprocessed",
+        )
+        .with_details(serde_json::json!({ "tenant_id": tenant_id.to_string() })));
+    }
+
+    let engine = WeakSignalEngine::new(
+        std::sync::Arc::new(state.db.clone()),
+        WeakSignalEngineConfig {
+            enable_notifications: false,
+            ..Default::default()
+        },
+    );
+    let summary = engine
+        .reprocess_tenant(tenant_id, MAX_SANDBOX_SIGNAL_SAMPLE)
+        .await?;
+
+    AuditLogRepository::new(state.db.clone())
+        .record_or_log(NewAuditLogEntry {
+            tenant_id: Some(tenant_id),
+            actor: API_AUDIT_ACTOR.to_string(),
+            action: TENANT_SANDBOX_REPROCESSED_AUDIT_ACTION.to_string(),
+            target_type: "tenant".to_string(),
+            target_id: tenant_id.to_string(),
+            reason: None,
+            details: Some(serde_json::json!({
+                "grounded_signals_removed": summary.grounded_signals_removed,
+                "signals_processed": summary.signals_processed,
+                "grounded_signals_created": summary.grounded_signals_created,
+            })),
+        })
+        .await;
+
+    Ok(Json(TenantApiResponse {
+        data: ReprocessSandboxResponseDto {
+            grounded_signals_removed: summary.grounded_signals_removed,
+            signals_processed: summary.signals_processed,
+            grounded_signals_created: summary.grounded_signals_created,
+        },
+        meta: TenantResponseMeta {
+            request_id: Uuid::new_v4().to_string(),
+            timestamp: Utc::now().to_rfc3339(),
+        },
+    }))
+}
+
 /// Get a tenant by ID
 #[utoipa::path(
     get,
@@ -240,48 +831,522 @@ pub async fn get_tenant(
     Ok(Json(response))
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::config::AppConfig;
-    use crate::db::init_pool;
-    use crate::repositories::TenantRepository;
-    use axum::{
-        body::Body,
-        http::{Request, StatusCode},
-    };
-    use serde_json::json;
-    use tower::ServiceExt;
-    use uuid::Uuid;
+/// Request payload for updating a tenant's inherited settings
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateTenantSettingsRequestDto {
+    /// Organization to inherit defaults from; omit to detach the tenant
+    #[schema(value_type = Option<String>)]
+    pub organization_id: Option<Uuid>,
+    /// Tenant-level overrides; settings left out are inherited
+    #[serde(default)]
+    pub settings: ConfigOverrides,
+}
 
-    async fn setup_test_app() -> (AppState, axum::Router) {
-        let config = AppConfig {
-            profile: "test".to_string(),
-            operator_tokens: vec!["test-token".to_string()],
-            ..Default::default()
-        };
+/// A tenant's organization and setting overrides
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TenantSettingsResponseDto {
+    /// Organization the tenant inherits from
+    #[schema(value_type = Option<String>)]
+    pub organization_id: Option<Uuid>,
+    /// Tenant-level overrides
+    pub settings: ConfigOverrides,
+}
 
-        let db = init_pool(&config).await.expect("Failed to init test DB");
-        let state = crate::server::create_test_app_state(config, db.clone());
+/// Set a tenant's organization and setting overrides
+#[utoipa::path(
+    put,
+    path = "/api/v1/tenants/{id}/settings",
+    security(("bearer_auth" = [])),
+    params(
+        ("id" = Uuid, Path, description = "Tenant UUID")
+    ),
+    request_body = UpdateTenantSettingsRequestDto,
+    responses(
+        (status = 200, description = "Tenant settings updated", body = TenantApiResponse<TenantSettingsResponseDto>),
+        (status = 400, description = "Validation failed", body = ApiError),
+        (status = 401, description = "Missing or invalid bearer token", body = ApiError),
+        (status = 404, description = "Tenant or organization not found", body = ApiError)
+    ),
+    tag = "tenants"
+)]
+pub async fn update_tenant_settings(
+    State(state): State<AppState>,
+    _operator_auth: OperatorAuth,
+    TenantExtension(_tenant): TenantExtension,
+    Path(tenant_id): Path<Uuid>,
+    Json(request): Json<UpdateTenantSettingsRequestDto>,
+) -> Result<Json<TenantApiResponse<TenantSettingsResponseDto>>, ApiError> {
+    let tenant = TenantRepository::new(&state.db)
+        .update_settings(tenant_id, request.organization_id, &request.settings)
+        .await?;
 
-        let app = crate::server::create_app(state.clone());
-        (state, app)
-    }
+    AuditLogRepository::new(state.db.clone())
+        .record_or_log(NewAuditLogEntry {
+            tenant_id: Some(tenant.id),
+            actor: API_AUDIT_ACTOR.to_string(),
+            action: TENANT_SETTINGS_UPDATED_AUDIT_ACTION.to_string(),
+            target_type: "tenant".to_string(),
+            target_id: tenant.id.to_string(),
+            reason: None,
+            details: Some(serde_json::json!({
+                "organization_id": tenant.organization_id,
+                "settings": tenant.settings,
+            })),
+        })
+        .await;
 
-    fn create_auth_headers() -> Vec<(&'static str, &'static str)> {
-        vec![
-            ("Authorization", "Bearer test-token"),
-            ("X-Tenant-Id", "550e8400-e29b-41d4-a716-446655440000"),
-            ("Content-Type", "application/json"),
-        ]
-    }
+    Ok(Json(TenantApiResponse {
+        data: TenantSettingsResponseDto {
+            organization_id: tenant.organization_id,
+            settings: ConfigOverrides::from_json(tenant.settings.as_ref()),
+        },
+        meta: TenantResponseMeta {
+            request_id: Uuid::new_v4().to_string(),
+            timestamp: Utc::now().to_rfc3339(),
+        },
+    }))
+}
 
-    #[tokio::test]
-    async fn test_create_tenant_success() {
-        let (_state, app) = setup_test_app().await;
+/// Settings in effect for a tenant and the layer each one comes from
+#[utoipa::path(
+    get,
+    path = "/api/v1/tenants/{id}/effective-config",
+    security(("bearer_auth" = [])),
+    params(
+        ("id" = Uuid, Path, description = "Tenant UUID")
+    ),
+    responses(
+        (status = 200, description = "Resolved tenant settings", body = TenantApiResponse<EffectiveConfig>),
+        (status = 401, description = "Missing or invalid bearer token", body = ApiError),
+        (status = 404, description = "Tenant not found", body = ApiError)
+    ),
+    tag = "tenants"
+)]
+pub async fn get_effective_config(
+    State(state): State<AppState>,
+    _operator_auth: OperatorAuth,
+    TenantExtension(_tenant): TenantExtension,
+    Path(tenant_id): Path<Uuid>,
+) -> Result<Json<TenantApiResponse<EffectiveConfig>>, ApiError> {
+    ensure_tenant_exists(&state, tenant_id).await?;
 
-        let request_body = json!({
-            "name": "Test Tenant",
+    let effective = ConfigLayers::load(&state.db, tenant_id)
+        .await?
+        .resolve(&state.config);
+
+    Ok(Json(TenantApiResponse {
+        data: effective,
+        meta: TenantResponseMeta {
+            request_id: Uuid::new_v4().to_string(),
+            timestamp: Utc::now().to_rfc3339(),
+        },
+    }))
+}
+
+/// A tenant's stored configuration overrides
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct TenantConfigResponseDto {
+    /// Settings overridden for this tenant; settings left out are inherited
+    pub config: TenantConfigSettings,
+    /// When the overrides last changed (RFC3339)
+    #[schema(example = "2024-01-15T10:30:00Z")]
+    pub updated_at: String,
+}
+
+/// Update a tenant's configuration overrides
+///
+/// The body is merged into the stored overrides: keys present replace the
+/// stored value and `null` clears it, so the setting is inherited again. The
+/// scheduler, the executor and mail spam filtering pick up the change on
+/// their next run; `GET /api/v1/tenants/{id}/effective-config` shows the result.
+#[utoipa::path(
+    patch,
+    path = "/api/v1/tenants/{id}/config",
+    security(("bearer_auth" = [])),
+    params(
+        ("id" = Uuid, Path, description = "Tenant UUID")
+    ),
+    request_body = TenantConfigSettings,
+    responses(
+        (status = 200, description = "Tenant config updated", body = TenantApiResponse<TenantConfigResponseDto>),
+        (status = 400, description = "Validation failed", body = ApiError),
+        (status = 401, description = "Missing or invalid bearer token", body = ApiError),
+        (status = 404, description = "Tenant not found", body = ApiError)
+    ),
+    tag = "tenants"
+)]
+pub async fn update_tenant_config(
+    State(state): State<AppState>,
+    _operator_auth: OperatorAuth,
+    TenantExtension(_tenant): TenantExtension,
+    Path(tenant_id): Path<Uuid>,
+    Json(patch): Json<serde_json::Map<String, serde_json::Value>>,
+) -> Result<Json<TenantApiResponse<TenantConfigResponseDto>>, ApiError> {
+    ensure_tenant_exists(&state, tenant_id).await?;
+
+    let stored = TenantConfigRepository::new(&state.db)
+        .patch(tenant_id, &patch)
+        .await?;
+    let config = stored.settings();
+
+    AuditLogRepository::new(state.db.clone())
+        .record_or_log(NewAuditLogEntry {
+            tenant_id: Some(tenant_id),
+            actor: API_AUDIT_ACTOR.to_string(),
+            action: TENANT_CONFIG_UPDATED_AUDIT_ACTION.to_string(),
+            target_type: "tenant".to_string(),
+            target_id: tenant_id.to_string(),
+            reason: None,
+            details: Some(serde_json::json!({
+                "patch": patch,
+                "config": config,
+            })),
+        })
+        .await;
+
+    Ok(Json(TenantApiResponse {
+        data: TenantConfigResponseDto {
+            config,
+            updated_at: stored.updated_at.to_rfc3339(),
+        },
+        meta: TenantResponseMeta {
+            request_id: Uuid::new_v4().to_string(),
+            timestamp: Utc::now().to_rfc3339(),
+        },
+    }))
+}
+
+/// Secret that signs deliveries to a tenant's notification webhook
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct WebhookSigningSecretResponseDto {
+    /// HMAC-SHA256 key for `"{timestamp}.{body}"`
+    #[schema(example = "whsec_3f9a0c...")]
+    pub secret: String,
+    /// Header carrying the `sha256=<hex>` signature
+    #[schema(example = "X-Poblysh-Signature")]
+    pub signature_header: String,
+    /// Header carrying the signed Unix timestamp in seconds
+    #[schema(example = "X-Poblysh-Timestamp")]
+    pub timestamp_header: String,
+}
+
+impl WebhookSigningSecretResponseDto {
+    fn new(secret: String) -> Self {
+        Self {
+            secret,
+            signature_header: crate::webhook_signing::SIGNATURE_HEADER.to_string(),
+            timestamp_header: crate::webhook_signing::TIMESTAMP_HEADER.to_string(),
+        }
+    }
+}
+
+async fn ensure_tenant_exists(state: &AppState, tenant_id: Uuid) -> Result<(), ApiError> {
+    if !state.snapshot.tenant_exists(tenant_id).await? {
+        return Err(ApiError::new(
+            StatusCode::NOT_FOUND,
+            "TENANT_NOT_FOUND",
+            "Tenant not found",
+        )
+        .with_details(serde_json::json!({ "tenant_id": tenant_id.to_string() })));
+    }
+    Ok(())
+}
+
+/// Secret used to sign the tenant's outbound webhook deliveries
+///
+/// Grounded-signal notifications and connection alerts sent to the tenant's
+/// webhook URL carry `X-Poblysh-Timestamp` and `X-Poblysh-Signature` headers
+/// computed with this secret. It is generated on first request.
+#[utoipa::path(
+    get,
+    path = "/api/v1/tenants/{id}/webhook-secret",
+    security(("bearer_auth" = [])),
+    params(
+        ("id" = Uuid, Path, description = "Tenant UUID")
+    ),
+    responses(
+        (status = 200, description = "Current signing secret", body = TenantApiResponse<WebhookSigningSecretResponseDto>),
+        (status = 401, description = "Missing or invalid bearer token", body = ApiError),
+        (status = 404, description = "Tenant not found", body = ApiError)
+    ),
+    tag = "tenants"
+)]
+pub async fn get_webhook_secret(
+    State(state): State<AppState>,
+    _operator_auth: OperatorAuth,
+    TenantExtension(_tenant): TenantExtension,
+    Path(tenant_id): Path<Uuid>,
+) -> Result<Json<TenantApiResponse<WebhookSigningSecretResponseDto>>, ApiError> {
+    ensure_tenant_exists(&state, tenant_id).await?;
+
+    let secret = TenantSignalConfigRepository::new(&state.db)
+        .get_or_create_signing_secret(tenant_id)
+        .await?;
+
+    Ok(Json(TenantApiResponse {
+        data: WebhookSigningSecretResponseDto::new(secret),
+        meta: TenantResponseMeta {
+            request_id: Uuid::new_v4().to_string(),
+            timestamp: Utc::now().to_rfc3339(),
+        },
+    }))
+}
+
+/// Replace the tenant's webhook signing secret
+///
+/// Deliveries are signed with the new secret immediately, so receivers must
+/// switch to it before the next notification.
+#[utoipa::path(
+    post,
+    path = "/api/v1/tenants/{id}/webhook-secret/rotate",
+    security(("bearer_auth" = [])),
+    params(
+        ("id" = Uuid, Path, description = "Tenant UUID")
+    ),
+    responses(
+        (status = 200, description = "New signing secret", body = TenantApiResponse<WebhookSigningSecretResponseDto>),
+        (status = 401, description = "Missing or invalid bearer token", body = ApiError),
+        (status = 404, description = "Tenant not found", body = ApiError)
+    ),
+    tag = "tenants"
+)]
+pub async fn rotate_webhook_secret(
+    State(state): State<AppState>,
+    _operator_auth: OperatorAuth,
+    TenantExtension(_tenant): TenantExtension,
+    Path(tenant_id): Path<Uuid>,
+) -> Result<Json<TenantApiResponse<WebhookSigningSecretResponseDto>>, ApiError> {
+    ensure_tenant_exists(&state, tenant_id).await?;
+
+    let secret = TenantSignalConfigRepository::new(&state.db)
+        .rotate_signing_secret(tenant_id)
+        .await?;
+
+    AuditLogRepository::new(state.db.clone())
+        .record_or_log(NewAuditLogEntry {
+            tenant_id: Some(tenant_id),
+            actor: API_AUDIT_ACTOR.to_string(),
+            action: TENANT_WEBHOOK_SECRET_ROTATED_AUDIT_ACTION.to_string(),
+            target_type: "tenant".to_string(),
+            target_id: tenant_id.to_string(),
+            reason: None,
+            details: None,
+        })
+        .await;
+
+    Ok(Json(TenantApiResponse {
+        data: WebhookSigningSecretResponseDto::new(secret),
+        meta: TenantResponseMeta {
+            request_id: Uuid::new_v4().to_string(),
+            timestamp: Utc::now().to_rfc3339(),
+        },
+    }))
+}
+
+/// Audit actions surfaced in a tenant's event feed.
+///
+/// Break-glass token decryption is an operator audit record rather than a
+/// configuration change, so it stays out of the feed.
+fn tenant_event_actions() -> [&'static str; 15] {
+    [
+        TENANT_CREATED_AUDIT_ACTION,
+        TENANT_SETTINGS_UPDATED_AUDIT_ACTION,
+        TENANT_CONFIG_UPDATED_AUDIT_ACTION,
+        TENANT_WEBHOOK_SECRET_ROTATED_AUDIT_ACTION,
+        TENANT_SANDBOX_CLONED_AUDIT_ACTION,
+        TENANT_SANDBOX_REPROCESSED_AUDIT_ACTION,
+        crate::cli::SHRED_TENANT_KEY_AUDIT_ACTION,
+        SIGNAL_KIND_REGISTERED_AUDIT_ACTION,
+        CONNECTION_CREATED_AUDIT_ACTION,
+        CONNECTION_REAUTHORIZED_AUDIT_ACTION,
+        PauseTransition::NeedsReauth.audit_action(),
+        LEGACY_AUTO_PAUSED_AUDIT_ACTION,
+        PauseTransition::Resumed.audit_action(),
+        CONNECTION_PAUSED_AUDIT_ACTION,
+        CONNECTION_RESUMED_AUDIT_ACTION,
+    ]
+}
+
+/// Query parameters for a tenant's event feed
+#[derive(Debug, Deserialize)]
+pub struct TenantEventsQuery {
+    /// Maximum number of events to return (1-200, default 50)
+    pub limit: Option<u64>,
+    /// Only return events recorded before this RFC3339 timestamp
+    pub before: Option<String>,
+}
+
+/// One configuration or lifecycle event
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct TenantEventDto {
+    /// Audit entry identifier
+    #[schema(value_type = String, example = "7b0f3c9e-3f0a-4a34-9d8e-1c2b3a4d5e6f")]
+    pub id: Uuid,
+    /// Event type (e.g., connection.created, tenant.settings_updated)
+    #[schema(example = "connection.needs_reauth")]
+    pub action: String,
+    /// Who or what made the change
+    #[schema(example = "system:auth-pause-policy")]
+    pub actor: String,
+    /// Kind of object the event concerns
+    #[schema(example = "connection")]
+    pub target_type: String,
+    /// Identifier of that object
+    #[schema(example = "550e8400-e29b-41d4-a716-446655440000")]
+    pub target_id: String,
+    /// Reason recorded with the event, if any
+    pub reason: Option<String>,
+    /// Event-specific details
+    pub details: Option<serde_json::Value>,
+    /// When the event was recorded (RFC3339)
+    #[schema(example = "2024-01-15T10:30:00Z")]
+    pub occurred_at: String,
+}
+
+/// A page of a tenant's event feed
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct TenantEventsResponseDto {
+    /// Events, newest first
+    pub events: Vec<TenantEventDto>,
+    /// Pass as `before` to fetch the next page; absent on the last page
+    #[schema(example = "2024-01-15T10:30:00Z")]
+    pub next_before: Option<String>,
+}
+
+/// Configuration and lifecycle events for a tenant, newest first
+///
+/// Built from the audit log: tenant creation, settings and config changes and
+/// webhook secret rotations, sandbox cloning and reprocessing, custom signal kind
+/// registrations, connections added or re-authorized, and connections paused
+/// or resumed by the auth pause policy.
+#[utoipa::path(
+    get,
+    path = "/api/v1/tenants/{id}/events",
+    security(("bearer_auth" = [])),
+    params(
+        ("id" = Uuid, Path, description = "Tenant UUID"),
+        ("limit" = Option<u64>, Query, description = "Maximum number of events to return (1-200, default 50)"),
+        ("before" = Option<String>, Query, description = "Only return events recorded before this RFC3339 timestamp")
+    ),
+    responses(
+        (status = 200, description = "Tenant event feed", body = TenantApiResponse<TenantEventsResponseDto>),
+        (status = 400, description = "Invalid query parameters", body = ApiError),
+        (status = 401, description = "Missing or invalid bearer token", body = ApiError),
+        (status = 404, description = "Tenant not found", body = ApiError)
+    ),
+    tag = "tenants"
+)]
+pub async fn list_tenant_events(
+    State(state): State<AppState>,
+    _operator_auth: OperatorAuth,
+    TenantExtension(_tenant): TenantExtension,
+    Path(tenant_id): Path<Uuid>,
+    Query(params): Query<TenantEventsQuery>,
+) -> Result<Json<TenantApiResponse<TenantEventsResponseDto>>, ApiError> {
+    let limit = params.limit.unwrap_or(DEFAULT_EVENTS_LIMIT);
+    if !(1..=MAX_EVENTS_LIMIT).contains(&limit) {
+        return Err(validation_error(
+            "Invalid limit",
+            serde_json::json!({ "limit": format!("Must be between 1 and {}", MAX_EVENTS_LIMIT) }),
+        ));
+    }
+    let before = params
+        .before
+        .as_deref()
+        .map(|value| {
+            DateTime::parse_from_rfc3339(value)
+                .map(|dt| dt.with_timezone(&Utc))
+                .map_err(|_| {
+                    validation_error(
+                        "Invalid before",
+                        serde_json::json!({ "before": "Must be an RFC3339 timestamp" }),
+                    )
+                })
+        })
+        .transpose()?;
+
+    ensure_tenant_exists(&state, tenant_id).await?;
+
+    let entries = AuditLogRepository::new(state.db.clone())
+        .list_for_tenant(tenant_id, &tenant_event_actions(), before, limit)
+        .await?;
+
+    let next_before = (entries.len() as u64 == limit)
+        .then(|| entries.last().map(|entry| entry.created_at.to_rfc3339()))
+        .flatten();
+    let events = entries
+        .into_iter()
+        .map(|entry| TenantEventDto {
+            id: entry.id,
+            action: entry.action,
+            actor: entry.actor,
+            target_type: entry.target_type,
+            target_id: entry.target_id,
+            reason: entry.reason,
+            details: entry.details,
+            occurred_at: entry.created_at.to_rfc3339(),
+        })
+        .collect();
+
+    Ok(Json(TenantApiResponse {
+        data: TenantEventsResponseDto {
+            events,
+            next_before,
+        },
+        meta: TenantResponseMeta {
+            request_id: Uuid::new_v4().to_string(),
+            timestamp: Utc::now().to_rfc3339(),
+        },
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::AppConfig;
+    use crate::db::init_pool;
+    use crate::repositories::{
+        OrganizationRepository, TenantKeyRepository, TenantRepository,
+        TenantSignalConfigRepository, TenantSignalKindRepository,
+    };
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+    };
+    use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+    use serde_json::json;
+    use std::sync::Arc;
+    use tower::ServiceExt;
+    use uuid::Uuid;
+
+    async fn setup_test_app() -> (AppState, axum::Router) {
+        let config = AppConfig {
+            profile: "test".to_string(),
+            operator_tokens: vec!["test-token".to_string()],
+            ..Default::default()
+        };
+
+        let db = init_pool(&config).await.expect("Failed to init test DB");
+        let state = crate::server::create_test_app_state(config, db.clone());
+
+        let app = crate::server::create_app(state.clone());
+        (state, app)
+    }
+
+    fn create_auth_headers() -> Vec<(&'static str, &'static str)> {
+        vec![
+            ("Authorization", "Bearer test-token"),
+            ("X-Tenant-Id", "550e8400-e29b-41d4-a716-446655440000"),
+            ("Content-Type", "application/json"),
+        ]
+    }
+
+    #[tokio::test]
+    async fn test_create_tenant_success() {
+        let (_state, app) = setup_test_app().await;
+
+        let request_body = json!({
+            "name": "Test Tenant",
             "metadata": {
                 "environment": "test"
             }
@@ -342,6 +1407,589 @@ mod tests {
         assert_eq!(error_json["code"], "VALIDATION_FAILED");
     }
 
+    #[tokio::test]
+    async fn test_bootstrap_tenant_creates_everything_in_one_call() {
+        let (state, app) = setup_test_app().await;
+
+        let request_body = json!({
+            "name": "Bootstrap Tenant",
+            "signal_config": {
+                "weak_signal_threshold": 0.55,
+                "webhook_url": "https://example.com/hooks/poblysh"
+            },
+            "custom_signal_kinds": [
+                {"kind": "custom:press_mention", "description": "Press coverage"}
+            ]
+        });
+
+        let mut builder = Request::builder()
+            .method("POST")
+            .uri("/api/v1/tenants/bootstrap");
+        for (name, value) in create_auth_headers() {
+            builder = builder.header(name, value);
+        }
+        let request = builder.body(Body::from(request_body.to_string())).unwrap();
+
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let response_json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        let data = &response_json["data"];
+        assert_eq!(data["tenant"]["name"], "Bootstrap Tenant");
+        assert_eq!(data["signal_config"]["weak_signal_threshold"], 0.55);
+        assert_eq!(data["data_key_provisioned"], true);
+        assert_eq!(
+            data["custom_signal_kinds"][0]["kind"],
+            "custom:press_mention"
+        );
+
+        let tenant_id = Uuid::parse_str(data["tenant"]["id"].as_str().unwrap()).unwrap();
+        let config = TenantSignalConfigRepository::new(&state.db)
+            .get(tenant_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            config.webhook_url.as_deref(),
+            Some("https://example.com/hooks/poblysh")
+        );
+        assert!(
+            TenantSignalKindRepository::new(&state.db)
+                .is_registered(tenant_id, "custom:press_mention")
+                .await
+                .unwrap()
+        );
+        assert!(
+            TenantKeyRepository::new(Arc::new(state.db.clone()), state.master_keys.clone())
+                .find(&tenant_id)
+                .await
+                .unwrap()
+                .is_some()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_bootstrap_tenant_rejects_invalid_webhook_without_creating_tenant() {
+        let (state, app) = setup_test_app().await;
+        let name = format!("Rejected Bootstrap {}", Uuid::new_v4().simple());
+
+        let request_body = json!({
+            "name": name,
+            "signal_config": {"webhook_url": "http://example.com/hooks"}
+        });
+
+        let mut builder = Request::builder()
+            .method("POST")
+            .uri("/api/v1/tenants/bootstrap");
+        for (name, value) in create_auth_headers() {
+            builder = builder.header(name, value);
+        }
+        let request = builder.body(Body::from(request_body.to_string())).unwrap();
+
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let tenants = crate::models::tenant::Entity::find()
+            .filter(crate::models::tenant::Column::Name.eq(name))
+            .all(&state.db)
+            .await
+            .unwrap();
+        assert!(tenants.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_effective_config_inherits_from_organization() {
+        let (state, app) = setup_test_app().await;
+
+        let organization = OrganizationRepository::new(&state.db)
+            .create(
+                "Inheritance Org",
+                &ConfigOverrides {
+                    mail_spam_threshold: Some(0.6),
+                    webhook_rate_limit_per_minute: Some(600),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        let tenant = TenantRepository::new(&state.db)
+            .create_tenant(CreateTenantRequest {
+                name: "Inheriting Tenant".to_string(),
+                metadata: None,
+            })
+            .await
+            .unwrap();
+
+        let send = |method: &str, uri: String, body: Body| {
+            let mut builder = Request::builder().method(method).uri(uri);
+            for (name, value) in create_auth_headers() {
+                builder = builder.header(name, value);
+            }
+            app.clone().oneshot(builder.body(body).unwrap())
+        };
+
+        let request_body = json!({
+            "organization_id": organization.id,
+            "settings": {"webhook_rate_limit_per_minute": 50}
+        });
+        let response = send(
+            "PUT",
+            format!("/api/v1/tenants/{}/settings", tenant.id),
+            Body::from(request_body.to_string()),
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = send(
+            "GET",
+            format!("/api/v1/tenants/{}/effective-config", tenant.id),
+            Body::empty(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let data = serde_json::from_slice::<serde_json::Value>(&body).unwrap()["data"].clone();
+        assert_eq!(data["organization_id"], organization.id.to_string());
+        assert_eq!(data["mail_spam_threshold"]["value"], 0.6);
+        assert_eq!(data["mail_spam_threshold"]["source"], "organization");
+        assert_eq!(data["webhook_rate_limit_per_minute"]["value"], 50);
+        assert_eq!(data["webhook_rate_limit_per_minute"]["source"], "tenant");
+        assert_eq!(data["scoring_weights"]["source"], "service");
+
+        let request_body = json!({ "organization_id": Uuid::new_v4() });
+        let response = send(
+            "PUT",
+            format!("/api/v1/tenants/{}/settings", tenant.id),
+            Body::from(request_body.to_string()),
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_tenant_config_patch_overrides_effective_config() {
+        let (state, app) = setup_test_app().await;
+        let tenant = TenantRepository::new(&state.db)
+            .create_tenant(CreateTenantRequest {
+                name: "Configured Tenant".to_string(),
+                metadata: None,
+            })
+            .await
+            .unwrap();
+
+        let send = |method: &str, uri: String, body: Body| {
+            let mut builder = Request::builder().method(method).uri(uri);
+            for (name, value) in create_auth_headers() {
+                builder = builder.header(name, value);
+            }
+            app.clone().oneshot(builder.body(body).unwrap())
+        };
+        let patch = |body: serde_json::Value| {
+            send(
+                "PATCH",
+                format!("/api/v1/tenants/{}/config", tenant.id),
+                Body::from(body.to_string()),
+            )
+        };
+
+        let response = patch(json!({
+            "sync_interval_seconds": 1800,
+            "mail_spam_threshold": 0.5,
+            "rate_limit_overrides": {"github": {"max_attempts": 3}},
+            "notification_urls": ["https://hooks.example.com/poblysh"],
+        }))
+        .await
+        .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = patch(json!({"mail_spam_threshold": null})).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let response = patch(json!({"sync_interval_seconds": 10})).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let response = send(
+            "GET",
+            format!("/api/v1/tenants/{}/effective-config", tenant.id),
+            Body::empty(),
+        )
+        .await
+        .unwrap();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let data = serde_json::from_slice::<serde_json::Value>(&body).unwrap()["data"].clone();
+        assert_eq!(data["sync_interval_seconds"]["value"], 1800);
+        assert_eq!(data["sync_interval_seconds"]["source"], "tenant");
+        assert_eq!(data["mail_spam_threshold"]["source"], "service");
+        assert_eq!(
+            data["rate_limit_overrides"]["value"]["github"]["max_attempts"],
+            3
+        );
+        assert_eq!(
+            data["notification_urls"]["value"],
+            json!(["https://hooks.example.com/poblysh"])
+        );
+
+        let layers = ConfigLayers::load(&state.db, tenant.id).await.unwrap();
+        let policy = layers.rate_limit_policy(&state.config.rate_limit_policy);
+        assert_eq!(policy.max_attempts_for("github"), 3);
+        let scheduler = layers.scheduler(&state.config.scheduler);
+        assert_eq!(scheduler.default_interval_for("jira"), 1800);
+
+        let response = send(
+            "PATCH",
+            format!("/api/v1/tenants/{}/config", Uuid::new_v4()),
+            Body::from("{}"),
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_webhook_secret_is_created_once_and_rotated() {
+        let (state, app) = setup_test_app().await;
+        let tenant = TenantRepository::new(&state.db)
+            .create_tenant(CreateTenantRequest {
+                name: "Signing Tenant".to_string(),
+                metadata: None,
+            })
+            .await
+            .unwrap();
+
+        let send = |method: &str, uri: String| {
+            let mut builder = Request::builder().method(method).uri(uri);
+            for (name, value) in create_auth_headers() {
+                builder = builder.header(name, value);
+            }
+            app.clone().oneshot(builder.body(Body::empty()).unwrap())
+        };
+        let secret_of = |response: axum::response::Response| async move {
+            assert_eq!(response.status(), StatusCode::OK);
+            let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            serde_json::from_slice::<TenantApiResponse<WebhookSigningSecretResponseDto>>(&body)
+                .unwrap()
+                .data
+        };
+
+        let uri = format!("/api/v1/tenants/{}/webhook-secret", tenant.id);
+        let first = secret_of(send("GET", uri.clone()).await.unwrap()).await;
+        assert!(first.secret.starts_with("whsec_"));
+        assert_eq!(first.signature_header, "X-Poblysh-Signature");
+        assert_eq!(first.timestamp_header, "X-Poblysh-Timestamp");
+        let again = secret_of(send("GET", uri.clone()).await.unwrap()).await;
+        assert_eq!(again.secret, first.secret);
+
+        let rotated = secret_of(send("POST", format!("{}/rotate", uri)).await.unwrap()).await;
+        assert_ne!(rotated.secret, first.secret);
+        let current = secret_of(send("GET", uri).await.unwrap()).await;
+        assert_eq!(current.secret, rotated.secret);
+
+        let rotations = AuditLogRepository::new(state.db.clone())
+            .list_for_tenant(
+                tenant.id,
+                &[TENANT_WEBHOOK_SECRET_ROTATED_AUDIT_ACTION],
+                None,
+                10,
+            )
+            .await
+            .unwrap();
+        assert_eq!(rotations.len(), 1);
+
+        let response = send(
+            "GET",
+            format!("/api/v1/tenants/{}/webhook-secret", Uuid::new_v4()),
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_tenant_events_feed_lists_lifecycle_changes_newest_first() {
+        let (state, app) = setup_test_app().await;
+
+        let send = |method: &str, uri: String, body: Body| {
+            let mut builder = Request::builder().method(method).uri(uri);
+            for (name, value) in create_auth_headers() {
+                builder = builder.header(name, value);
+            }
+            app.clone().oneshot(builder.body(body).unwrap())
+        };
+        let events_page = |response: axum::response::Response| async move {
+            let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            serde_json::from_slice::<TenantApiResponse<TenantEventsResponseDto>>(&body)
+                .unwrap()
+                .data
+        };
+
+        let request_body = json!({ "name": "Events Tenant" });
+        let response = send(
+            "POST",
+            "/api/v1/tenants/bootstrap".to_string(),
+            Body::from(request_body.to_string()),
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let data = serde_json::from_slice::<serde_json::Value>(&body).unwrap()["data"].clone();
+        let tenant_id = Uuid::parse_str(data["tenant"]["id"].as_str().unwrap()).unwrap();
+
+        // Break-glass access is audited but is not a configuration change
+        AuditLogRepository::new(state.db.clone())
+            .record(NewAuditLogEntry {
+                tenant_id: Some(tenant_id),
+                actor: "cli:test".to_string(),
+                action: "connection.decrypt_token".to_string(),
+                target_type: "connection".to_string(),
+                target_id: Uuid::new_v4().to_string(),
+                reason: Some("incident".to_string()),
+                details: None,
+            })
+            .await
+            .unwrap();
+
+        let request_body = json!({ "settings": {"webhook_rate_limit_per_minute": 50} });
+        let response = send(
+            "PUT",
+            format!("/api/v1/tenants/{}/settings", tenant_id),
+            Body::from(request_body.to_string()),
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = send(
+            "GET",
+            format!("/api/v1/tenants/{}/events?limit=1", tenant_id),
+            Body::empty(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let page = events_page(response).await;
+        assert_eq!(page.events.len(), 1);
+        assert_eq!(page.events[0].action, TENANT_SETTINGS_UPDATED_AUDIT_ACTION);
+        assert_eq!(page.events[0].actor, API_AUDIT_ACTOR);
+        assert_eq!(
+            page.events[0].details.as_ref().unwrap()["settings"]["webhook_rate_limit_per_minute"],
+            50
+        );
+        let next_before = page.next_before.expect("a full page has a cursor");
+
+        let response = send(
+            "GET",
+            format!(
+                "/api/v1/tenants/{}/events?before={}",
+                tenant_id,
+                url::form_urlencoded::byte_serialize(next_before.as_bytes()).collect::<String>()
+            ),
+            Body::empty(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let page = events_page(response).await;
+        let actions: Vec<_> = page.events.iter().map(|e| e.action.as_str()).collect();
+        assert_eq!(actions, vec![TENANT_CREATED_AUDIT_ACTION]);
+        assert!(page.next_before.is_none());
+
+        let response = send(
+            "GET",
+            format!("/api/v1/tenants/{}/events?limit=0", tenant_id),
+            Body::empty(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let response = send(
+            "GET",
+            format!("/api/v1/tenants/{}/events", Uuid::new_v4()),
+            Body::empty(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_clone_sandbox_copies_configuration_and_sampled_signals() {
+        use crate::models::{connection, signal};
+        use crate::repositories::ProviderRepository;
+        use sea_orm::{ActiveModelTrait, ConnectionTrait, DatabaseBackend, Set, Statement};
+
+        let (state, app) = setup_test_app().await;
+        let source_id = Uuid::new_v4();
+        TenantRepository::new(&state.db)
+            .bootstrap_tenant(TenantBootstrap {
+                tenant_id: source_id,
+                name: "Sandbox Source".to_string(),
+                organization_id: None,
+                weak_signal_threshold: 0.42,
+                webhook_url: Some("https://example.com/hooks/production".to_string()),
+                wrapped_data_key: crate::crypto::wrap_tenant_key(
+                    state.master_keys.current(),
+                    source_id,
+                    &CryptoKey::generate(),
+                )
+                .unwrap(),
+                data_key_version: state.master_keys.current_version(),
+                custom_signal_kinds: vec![("custom:press_mention".to_string(), None)],
+            })
+            .await
+            .unwrap();
+        ProviderRepository::new(Arc::new(state.db.clone()))
+            .upsert("github", "GitHub", "oauth2")
+            .await
+            .unwrap();
+        let connection_id = Uuid::new_v4();
+        connection::ActiveModel {
+            id: Set(connection_id),
+            tenant_id: Set(source_id),
+            provider_slug: Set("github".to_string()),
+            external_id: Set("octocat".to_string()),
+            ..Default::default()
+        }
+        .insert(&state.db)
+        .await
+        .unwrap();
+        let now = Utc::now();
+        for offset in 0..3 {
+            signal::ActiveModel {
+                id: Set(Uuid::new_v4()),
+                tenant_id: Set(source_id),
+                provider_slug: Set("github".to_string()),
+                connection_id: Set(connection_id),
+                kind: Set("issue_created".to_string()),
+                occurred_at: Set((now - chrono::Duration::hours(offset)).into()),
+                received_at: Set(now.into()),
+                payload: Set(json!({"title": format!("Issue {}", offset)})),
+                dedupe_key: Set(Some(format!("github:issue_created:{}", offset))),
+                created_at: Set(now.into()),
+                updated_at: Set(now.into()),
+            }
+            .insert(&state.db)
+            .await
+            .unwrap();
+        }
+
+        let post = |uri: String, body: serde_json::Value| {
+            let mut builder = Request::builder().method("POST").uri(uri);
+            for (name, value) in create_auth_headers() {
+                builder = builder.header(name, value);
+            }
+            builder.body(Body::from(body.to_string())).unwrap()
+        };
+
+        let response = app
+            .clone()
+            .oneshot(post(
+                format!("/api/v1/tenants/{}/clone-sandbox", source_id),
+                json!({"signal_sample": {"size": 2}}),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let data = serde_json::from_slice::<serde_json::Value>(&body).unwrap()["data"].clone();
+        assert_eq!(data["tenant"]["name"], "Sandbox Source sandbox");
+        assert_eq!(data["sandbox_of"], source_id.to_string());
+        assert_eq!(data["signal_config"]["weak_signal_threshold"], 0.42);
+        assert_eq!(
+            data["custom_signal_kinds"][0]["kind"],
+            "custom:press_mention"
+        );
+        assert_eq!(data["connections_copied"], 1);
+        assert_eq!(data["signals_copied"], 2);
+
+        let sandbox_id = Uuid::parse_str(data["tenant"]["id"].as_str().unwrap()).unwrap();
+        let sandbox_config = TenantSignalConfigRepository::new(&state.db)
+            .get(sandbox_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(sandbox_config.webhook_url, None);
+        let sandbox_connections = connection::Entity::find()
+            .filter(connection::Column::TenantId.eq(sandbox_id))
+            .all(&state.db)
+            .await
+            .unwrap();
+        assert_eq!(sandbox_connections.len(), 1);
+        assert_eq!(
+            sandbox_connections[0].status,
+            crate::repositories::tenant::SANDBOX_CONNECTION_STATUS
+        );
+
+        let response = app
+            .clone()
+            .oneshot(post(
+                format!("/api/v1/tenants/{}/clone-sandbox", sandbox_id),
+                json!({}),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+
+        let response = app
+            .clone()
+            .oneshot(post(
+                format!("/api/v1/tenants/{}/reprocess", source_id),
+                json!({}),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+
+        let grounded_signals_table = state
+            .db
+            .query_one(Statement::from_string(
+                DatabaseBackend::Postgres,
+                "SELECT to_regclass('public.grounded_signals') IS NOT NULL AS exists",
+            ))
+            .await
+            .ok()
+            .flatten()
+            .and_then(|row| row.try_get::<bool>("", "exists").ok())
+            .unwrap_or(false);
+        if !grounded_signals_table {
+            return;
+        }
+
+        let response = app
+            .oneshot(post(
+                format!("/api/v1/tenants/{}/reprocess", sandbox_id),
+                json!({}),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let summary: TenantApiResponse<ReprocessSandboxResponseDto> =
+            serde_json::from_slice(&body).unwrap();
+        assert_eq!(summary.data.signals_processed, 2);
+    }
+
     #[tokio::test]
     async fn test_get_tenant_success() {
         let (state, app) = setup_test_app().await;
@@ -356,7 +2004,7 @@ mod tests {
 
         let mut builder = Request::builder()
             .method("GET")
-            .uri(&format!("/api/v1/tenants/{}", tenant.id));
+            .uri(format!("/api/v1/tenants/{}", tenant.id));
 
         for (name, value) in create_auth_headers() {
             builder = builder.header(name, value);
@@ -384,7 +2032,7 @@ mod tests {
         let non_existent_id = Uuid::new_v4();
         let mut builder = Request::builder()
             .method("GET")
-            .uri(&format!("/api/v1/tenants/{}", non_existent_id));
+            .uri(format!("/api/v1/tenants/{}", non_existent_id));
 
         for (name, value) in create_auth_headers() {
             builder = builder.header(name, value);
@@ -15,10 +15,29 @@ use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 
 use crate::auth::{OperatorAuth, TenantExtension, TenantId};
+use crate::connectors::{
+    CIRCLECI_PROVIDER_SLUG, CUSTOM_WEBHOOK_PROVIDER_SLUG, DATADOG_PROVIDER_SLUG,
+    STRIPE_PROVIDER_SLUG, circleci_connection_key, custom_webhook_payload, datadog_external_id,
+    stripe_external_id,
+};
 use crate::error::ApiError;
 use crate::handlers::TenantHeader;
-use crate::repositories::{ConnectionRepository, ProviderRepository, SyncJobRepository};
+use crate::mail::inbound::{
+    INBOUND_EMAIL_PROVIDER_SLUG, InboundEmailError, parse_json as parse_inbound_json, parse_mime,
+};
+use crate::repositories::{ConnectionRepository, SyncJobRepository};
 use crate::server::AppState;
+use crate::telemetry::DebugSampler;
+use crate::webhook_backpressure::check_capacity;
+use crate::webhook_triggers::enqueue_triggered_sync;
+use crate::webhook_verification::{
+    DeliveryReservation, check_operator_auth, reserve_custom_webhook_delivery,
+    verify_custom_webhook,
+};
+
+/// Samplers for per-request debug events (`POBLYSH_LOG_DEBUG_SAMPLE_RATE`)
+static INGEST_DEBUG_SAMPLER: DebugSampler = DebugSampler::new();
+static HEADER_DEBUG_SAMPLER: DebugSampler = DebugSampler::new();
 
 /// Path parameter for provider slug
 #[derive(Debug, Deserialize, ToSchema)]
@@ -297,7 +316,9 @@ fn validate_gmail_webhook_body_size(
         (status = 400, description = "Invalid connection ID header", body = ApiError),
         (status = 401, description = "Missing or invalid operator token", body = ApiError),
         (status = 404, description = "Provider not found or connection not found for tenant/provider", body = ApiError),
-        (status = 500, description = "Internal server error", body = ApiError)
+        (status = 429, description = "Webhook buffer saturated (provider-specific)", body = ApiError),
+        (status = 500, description = "Internal server error", body = ApiError),
+        (status = 503, description = "Webhook buffer saturated (provider-specific)", body = ApiError)
     ),
     tag = "webhooks"
 )]
@@ -311,11 +332,13 @@ pub async fn ingest_webhook(
     let provider_slug = provider_param.provider;
     let tenant_id = tenant.0;
 
-    debug!(
-        provider_slug = %provider_slug,
-        tenant_id = %tenant_id,
-        "Processing webhook ingestion"
-    );
+    if INGEST_DEBUG_SAMPLER.sample() {
+        debug!(
+            provider_slug = %provider_slug,
+            tenant_id = %tenant_id,
+            "Processing webhook ingestion"
+        );
+    }
 
     // Extract headers first, then move request to get body
     let headers = req.headers().clone();
@@ -330,8 +353,9 @@ pub async fn ingest_webhook(
 
     // Jira webhook secret verification if WEBHOOK_JIRA_SECRET is configured
     // Note: For operator-protected route, verification is optional. If configured, enforce; if not, proceed.
-    if provider_slug == "jira" && state.config.webhook_jira_secret.is_some() {
-        let jira_secret = state.config.webhook_jira_secret.as_ref().unwrap();
+    if provider_slug == "jira"
+        && let Some(jira_secret) = state.config.webhook_jira_secret.as_ref()
+    {
         verify_jira_webhook_secret(&headers, &body_bytes, jira_secret)?;
         debug!("Jira webhook secret verification successful");
     }
@@ -361,7 +385,9 @@ pub async fn ingest_webhook(
             let name_lower = name.as_str().to_lowercase();
             // Skip sensitive headers
             if sensitive_headers.contains(&name_lower.as_str()) {
-                tracing::debug!("Filtering sensitive header: {}", name_lower);
+                if HEADER_DEBUG_SAMPLER.sample() {
+                    tracing::debug!("Filtering sensitive header: {}", name_lower);
+                }
                 return None;
             }
 
@@ -375,9 +401,9 @@ pub async fn ingest_webhook(
     // Operator route: do not require Jira secret; optional verification above is sufficient.
 
     // Validate provider exists
-    let provider_repo = ProviderRepository::new(std::sync::Arc::new(state.db.clone()));
-    let _provider = provider_repo
-        .find_by_slug(&provider_slug)
+    let _provider = state
+        .snapshot
+        .provider(&provider_slug)
         .await
         .map_err(|e| {
             error!(error = ?e, "Failed to lookup provider");
@@ -402,6 +428,14 @@ pub async fn ingest_webhook(
     // Extract webhook body from already read bytes
     let body = parse_webhook_body_from_bytes(&body_bytes);
 
+    // Custom webhook payloads name their connection for the mapping rules
+    let body = match connection_id {
+        Some(connection_id) if provider_slug == CUSTOM_WEBHOOK_PROVIDER_SLUG => {
+            Some(custom_webhook_payload(connection_id, body))
+        }
+        _ => body,
+    };
+
     // Gmail-specific synchronous verification (OIDC and body size)
     if provider_slug == "gmail" {
         // Validate body size first to reject oversized payloads early
@@ -415,9 +449,9 @@ pub async fn ingest_webhook(
     if let Some(conn_id) = connection_id {
         let connection_repo = ConnectionRepository::new(
             std::sync::Arc::new(state.db.clone()),
-            state.crypto_key.clone(),
+            state.master_keys.clone(),
         );
-        let _connection = connection_repo
+        let connection = connection_repo
             .find_by_tenant_and_provider(&tenant_id, &provider_slug)
             .await
             .map_err(|e| {
@@ -459,6 +493,9 @@ pub async fn ingest_webhook(
             "received_at": chrono::Utc::now().to_rfc3339()
         }));
 
+        // Ask the provider to redeliver later if the webhook backlog is full
+        check_capacity(&state, &provider_slug).await?;
+
         // Enqueue webhook sync job
         let sync_job_repo = SyncJobRepository::new(state.db.clone());
         sync_job_repo
@@ -479,6 +516,16 @@ pub async fn ingest_webhook(
             connection_id = %conn_id,
             "Webhook sync job enqueued successfully"
         );
+
+        // Change notifications also warrant a debounced incremental sync of the connection
+        enqueue_triggered_sync(
+            &state,
+            &provider_slug,
+            &connection,
+            &webhook_headers,
+            body.as_ref(),
+        )
+        .await;
     } else {
         info!(
             tenant_id = %tenant_id,
@@ -509,12 +556,26 @@ pub async fn ingest_webhook(
 /// **Provider-Specific Requirements**:
 /// - **GitHub**: `X-Hub-Signature-256: sha256=<hex>` header
 /// - **Slack**: `X-Slack-Signature: v0=<hex>` and `X-Slack-Request-Timestamp` headers
-/// - **Jira/Zoho-Cliq**: `Authorization: Bearer <token>` header
+/// - **Linear**: `Linear-Signature: <hex>` header; `webhookTimestamp` in the body must be within a minute
+/// - **GitLab**: `X-Gitlab-Token: <token>` header matching the webhook's secret token
+/// - **Zendesk**: base64 `X-Zendesk-Webhook-Signature` over `X-Zendesk-Webhook-Signature-Timestamp` plus the body; the timestamp must be within five minutes
+/// - **Sentry**: `Sentry-Hook-Signature: <hex>` HMAC of the body with the integration's client secret
+/// - **CircleCI**: `circleci-signature: v1=<hex>` HMAC of the body with the webhook's secret
+/// - **Stripe**: `Stripe-Signature: t=<unix>,v1=<hex>` HMAC of the timestamp and body; the timestamp must be within five minutes
+/// - **Jira/Confluence/Salesforce/Datadog/Zoho-Cliq**: `Authorization: Bearer <token>` header
+/// - **Custom webhook**: `X-Webhook-Signature: sha256=<hex>` HMAC of the body, or the secret itself
+///   as `X-Webhook-Secret` or `Authorization: Bearer <secret>`, checked against the targeted
+///   connection's secret; the connection is named by `X-Connection-Id` or `?connection_id=`
+///
+/// Datadog, CircleCI and Stripe deliveries without `X-Connection-Id` are queued
+/// on the tenant's connection for the payload's organization or account,
+/// created on first delivery.
 ///
 /// **Error Responses**:
 /// - `401 UNAUTHORIZED`: Missing/invalid signature when no operator auth, or missing verification config
 /// - `404 NOT_FOUND`: Unsupported provider
 /// - `429 RATE_LIMIT_EXCEEDED`: Rate limit exceeded
+/// - `429`/`503 WEBHOOK_BACKPRESSURE`: Webhook buffer saturated; status follows the provider's retry semantics
 /// - All errors use `application/problem+json` format with SCREAMING_SNAKE_CASE codes
 #[utoipa::path(
     post,
@@ -525,6 +586,10 @@ pub async fn ingest_webhook(
         ("X-Hub-Signature-256" = Option<String>, Header, description = "GitHub HMAC-SHA256 signature (required for GitHub webhooks without operator auth)"),
         ("X-Slack-Signature" = Option<String>, Header, description = "Slack HMAC-SHA256 signature (required for Slack webhooks without operator auth)"),
         ("X-Slack-Request-Timestamp" = Option<String>, Header, description = "Slack request timestamp (required for Slack webhooks without operator auth)"),
+        ("Linear-Signature" = Option<String>, Header, description = "Linear HMAC-SHA256 signature (required for Linear webhooks without operator auth)"),
+        ("X-Gitlab-Token" = Option<String>, Header, description = "GitLab webhook secret token (required for GitLab webhooks without operator auth)"),
+        ("X-Webhook-Signature" = Option<String>, Header, description = "HMAC-SHA256 of the body with the connection's secret (custom webhooks)"),
+        ("connection_id" = Option<String>, Query, description = "Connection to target when the sender cannot set X-Connection-Id (custom webhooks)"),
         ProviderTenantPath
     ),
     request_body(content = Option<JsonValue>, description = "Webhook payload (opaque to API)", content_type = "application/json"),
@@ -533,8 +598,9 @@ pub async fn ingest_webhook(
         (status = 400, description = "Invalid connection ID header or malformed request", body = ApiError),
         (status = 401, description = "Missing/invalid signature OR webhook verification not configured", body = ApiError),
         (status = 404, description = "Provider not found or unsupported", body = ApiError),
-        (status = 429, description = "Rate limit exceeded", body = ApiError),
-        (status = 500, description = "Internal server error", body = ApiError)
+        (status = 429, description = "Rate limit exceeded, or webhook buffer saturated (provider-specific)", body = ApiError),
+        (status = 500, description = "Internal server error", body = ApiError),
+        (status = 503, description = "Webhook buffer saturated; provider should redeliver (provider-specific)", body = ApiError)
     ),
     tag = "webhooks"
 )]
@@ -553,14 +619,17 @@ pub async fn ingest_public_webhook(
     })?;
     let tenant_id = TenantId(tenant_uuid);
 
-    debug!(
-        provider_slug = %provider_slug,
-        tenant_id = %tenant_id.0,
-        "Processing public webhook ingestion with verified signature"
-    );
+    if INGEST_DEBUG_SAMPLER.sample() {
+        debug!(
+            provider_slug = %provider_slug,
+            tenant_id = %tenant_id.0,
+            "Processing public webhook ingestion with verified signature"
+        );
+    }
 
-    // Extract headers before consuming the request
+    // Extract headers and query before consuming the request
     let headers = req.headers().clone();
+    let query = req.uri().query().map(str::to_string);
 
     // Extract body bytes before consuming the request
     let body_bytes = match axum::body::to_bytes(req.into_body(), 1024 * 1024).await {
@@ -590,6 +659,13 @@ pub async fn ingest_public_webhook(
         "x-hub-signature-256", // Remove signature headers from persisted data
         "x-slack-signature",
         "x-slack-request-timestamp",
+        "linear-signature",
+        "x-zendesk-webhook-signature",
+        "sentry-hook-signature",
+        "circleci-signature",
+        "stripe-signature",
+        "x-webhook-signature",
+        "x-gitlab-token",
         "x-webhook-secret", // Remove webhook secret headers from persisted data
     ]);
 
@@ -604,7 +680,9 @@ pub async fn ingest_public_webhook(
             let name_lower = name.as_str().to_lowercase();
             // Skip sensitive headers and signature headers
             if sensitive_headers.contains(&name_lower.as_str()) {
-                tracing::debug!("Filtering sensitive/signature header: {}", name_lower);
+                if HEADER_DEBUG_SAMPLER.sample() {
+                    tracing::debug!("Filtering sensitive/signature header: {}", name_lower);
+                }
                 return None;
             }
 
@@ -616,9 +694,9 @@ pub async fn ingest_public_webhook(
         .collect();
 
     // Validate provider exists
-    let provider_repo = ProviderRepository::new(std::sync::Arc::new(state.db.clone()));
-    let _provider = provider_repo
-        .find_by_slug(&provider_slug)
+    let _provider = state
+        .snapshot
+        .provider(&provider_slug)
         .await
         .map_err(|e| {
             error!(error = ?e, "Failed to lookup provider");
@@ -643,6 +721,58 @@ pub async fn ingest_public_webhook(
     // Extract webhook body from already read bytes
     let body = parse_webhook_body_from_bytes(&body_bytes);
 
+    // Datadog, CircleCI and Stripe cannot send a per-connection header, so
+    // deliveries target the tenant's connection for the account in the payload
+    let connection_id = match (
+        connection_id,
+        implicit_connection(&provider_slug, body.as_ref()),
+    ) {
+        (None, Some((external_id, display_name, metadata))) => {
+            let connection = webhook_connection(
+                &state,
+                tenant_id.0,
+                &provider_slug,
+                &external_id,
+                display_name,
+                metadata,
+            )
+            .await?;
+            Some(connection.id)
+        }
+        (connection_id, _) => connection_id,
+    };
+
+    // Custom webhooks are verified against the targeted connection's own secret,
+    // and the queued payload names that connection for the mapping rules
+    let mut reservation = None;
+    let (connection_id, body) = if provider_slug == CUSTOM_WEBHOOK_PROVIDER_SLUG {
+        let connection_id = match connection_id {
+            Some(connection_id) => connection_id,
+            None => connection_id_from_query(query.as_deref())?,
+        };
+        reservation = verify_custom_webhook_delivery(
+            &state,
+            tenant_id.0,
+            connection_id,
+            &headers,
+            &body_bytes,
+        )
+        .await?;
+        if !body.as_ref().is_some_and(JsonValue::is_object) {
+            return Err(ApiError::new(
+                StatusCode::BAD_REQUEST,
+                "VALIDATION_FAILED",
+                "custom webhook body must be a JSON object",
+            ));
+        }
+        (
+            Some(connection_id),
+            Some(custom_webhook_payload(connection_id, body)),
+        )
+    } else {
+        (connection_id, body)
+    };
+
     // Gmail-specific synchronous verification (OIDC and body size)
     if provider_slug == "gmail" {
         // Validate body size first to reject oversized payloads early
@@ -656,9 +786,9 @@ pub async fn ingest_public_webhook(
     if let Some(conn_id) = connection_id {
         let connection_repo = ConnectionRepository::new(
             std::sync::Arc::new(state.db.clone()),
-            state.crypto_key.clone(),
+            state.master_keys.clone(),
         );
-        let _connection = connection_repo
+        let connection = connection_repo
             .find_by_tenant_and_provider(&tenant_id.0, &provider_slug)
             .await
             .map_err(|e| {
@@ -701,6 +831,9 @@ pub async fn ingest_public_webhook(
             "verification_method": "signature"
         }));
 
+        // Ask the provider to redeliver later if the webhook backlog is full
+        check_capacity(&state, &provider_slug).await?;
+
         // Enqueue webhook sync job
         let sync_job_repo = SyncJobRepository::new(state.db.clone());
         sync_job_repo
@@ -721,6 +854,16 @@ pub async fn ingest_public_webhook(
             connection_id = %conn_id,
             "Public webhook sync job enqueued successfully"
         );
+
+        // Change notifications also warrant a debounced incremental sync of the connection
+        enqueue_triggered_sync(
+            &state,
+            &provider_slug,
+            &connection,
+            &webhook_headers,
+            body.as_ref(),
+        )
+        .await;
     } else {
         info!(
             tenant_id = %tenant_id.0,
@@ -729,6 +872,11 @@ pub async fn ingest_public_webhook(
         );
     }
 
+    // A delivery rejected above drops its reservation so the retry is accepted
+    if let Some(reservation) = reservation {
+        reservation.keep();
+    }
+
     let response = WebhookAcceptResponse {
         status: "accepted".to_string(),
     };
@@ -736,6 +884,315 @@ pub async fn ingest_public_webhook(
     Ok((StatusCode::ACCEPTED, Json(response)))
 }
 
+/// Inbound email accept response
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct InboundEmailAcceptResponse {
+    /// Acceptance status
+    pub status: String,
+    /// Tenant's inbound email connection the message was queued on
+    pub connection_id: Uuid,
+    /// Message-ID of the accepted message, or a content hash when it has none
+    pub message_id: String,
+}
+
+/// External id of the connection that receives a tenant's forwarded mail
+const INBOUND_EMAIL_EXTERNAL_ID: &str = "inbound";
+
+/// Accept a forwarded email message
+///
+/// Takes mail relayed by an SMTP bridge: a raw RFC 5322 message
+/// (`message/rfc822` or any non-JSON content type), an Amazon SES receipt
+/// notification (optionally inside its SNS envelope), or SendGrid Inbound
+/// Parse fields posted as JSON. The message is queued on the tenant's
+/// `inbound-email` connection, created on first use, and becomes an
+/// `email_received` signal once it passes the shared mail spam filter.
+#[utoipa::path(
+    post,
+    path = "/ingest/email",
+    security(("bearer_auth" = [])),
+    params(TenantHeader),
+    request_body(content = String, description = "Raw MIME message, SES notification or SendGrid Inbound Parse fields", content_type = "message/rfc822"),
+    responses(
+        (status = 202, description = "Message accepted", body = InboundEmailAcceptResponse),
+        (status = 400, description = "Message could not be parsed", body = ApiError),
+        (status = 401, description = "Missing or invalid operator token", body = ApiError),
+        (status = 404, description = "Inbound email provider not registered", body = ApiError),
+        (status = 413, description = "Message exceeds the configured size limit", body = ApiError),
+        (status = 429, description = "Webhook buffer saturated", body = ApiError),
+        (status = 500, description = "Internal server error", body = ApiError)
+    ),
+    tag = "webhooks"
+)]
+pub async fn ingest_email(
+    State(state): State<AppState>,
+    _operator_auth: OperatorAuth,
+    TenantExtension(tenant): TenantExtension,
+    req: Request,
+) -> Result<(StatusCode, Json<InboundEmailAcceptResponse>), ApiError> {
+    let tenant_id = tenant.0;
+    let provider_slug = INBOUND_EMAIL_PROVIDER_SLUG;
+
+    let is_json = req
+        .headers()
+        .get(axum::http::header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.to_ascii_lowercase().contains("json"));
+
+    let max_size_kb = state.config.inbound_email_max_body_kb;
+    let body_bytes = axum::body::to_bytes(req.into_body(), max_size_kb * 1024)
+        .await
+        .map_err(|_| {
+            ApiError::new(
+                StatusCode::PAYLOAD_TOO_LARGE,
+                "PAYLOAD_TOO_LARGE",
+                format!("Message exceeds maximum allowed size {} KB", max_size_kb),
+            )
+        })?;
+
+    let parsed = if is_json {
+        serde_json::from_slice::<JsonValue>(&body_bytes)
+            .map_err(|e| InboundEmailError::Malformed(format!("body is not JSON: {}", e)))
+            .and_then(|value| parse_inbound_json(&value))
+    } else {
+        parse_mime(&body_bytes)
+    };
+    let email = parsed.map_err(|e| {
+        debug!(tenant_id = %tenant_id, error = %e, "Rejected inbound email");
+        ApiError::new(StatusCode::BAD_REQUEST, "VALIDATION_FAILED", e.to_string())
+    })?;
+
+    state
+        .snapshot
+        .provider(provider_slug)
+        .await
+        .map_err(|e| {
+            error!(error = ?e, "Failed to lookup provider");
+            ApiError::new(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "INTERNAL_SERVER_ERROR",
+                "Failed to validate provider",
+            )
+        })?
+        .ok_or_else(|| {
+            ApiError::new(
+                StatusCode::NOT_FOUND,
+                "NOT_FOUND",
+                format!("provider '{}' not found", provider_slug),
+            )
+        })?;
+
+    let connection = webhook_connection(
+        &state,
+        tenant_id,
+        provider_slug,
+        INBOUND_EMAIL_EXTERNAL_ID,
+        "Inbound email",
+        None,
+    )
+    .await?;
+    let message_id = email.stable_id();
+    let cursor = Some(serde_json::json!({
+        "webhook_payload": email,
+        "received_at": chrono::Utc::now().to_rfc3339()
+    }));
+
+    check_capacity(&state, provider_slug).await?;
+
+    SyncJobRepository::new(state.db.clone())
+        .enqueue_webhook_job(tenant_id, provider_slug, connection.id, cursor)
+        .await
+        .map_err(|e| {
+            error!(error = ?e, "Failed to enqueue inbound email job");
+            ApiError::new(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "INTERNAL_SERVER_ERROR",
+                "Failed to enqueue webhook job",
+            )
+        })?;
+
+    info!(
+        tenant_id = %tenant_id,
+        connection_id = %connection.id,
+        message_id = %message_id,
+        source = %email.source,
+        "Inbound email enqueued"
+    );
+
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(InboundEmailAcceptResponse {
+            status: "accepted".to_string(),
+            connection_id: connection.id,
+            message_id,
+        }),
+    ))
+}
+
+/// Connection a push-only provider's delivery belongs to when it names none:
+/// `(external_id, display_name, metadata)`
+fn implicit_connection(
+    provider_slug: &str,
+    body: Option<&JsonValue>,
+) -> Option<(String, &'static str, Option<JsonValue>)> {
+    match provider_slug {
+        DATADOG_PROVIDER_SLUG => Some((datadog_external_id(body), "Datadog", None)),
+        CIRCLECI_PROVIDER_SLUG => {
+            let (external_id, metadata) = circleci_connection_key(body?)?;
+            Some((external_id, "CircleCI", Some(metadata)))
+        }
+        STRIPE_PROVIDER_SLUG => Some((stripe_external_id(body), "Stripe", None)),
+        _ => None,
+    }
+}
+
+/// Connection named by a custom webhook's `connection_id` query parameter
+fn connection_id_from_query(query: Option<&str>) -> Result<Uuid, ApiError> {
+    let value = query
+        .and_then(|query| {
+            url::form_urlencoded::parse(query.as_bytes())
+                .find(|(key, _)| key == "connection_id")
+                .map(|(_, value)| value.into_owned())
+        })
+        .ok_or_else(|| {
+            ApiError::new(
+                StatusCode::BAD_REQUEST,
+                "VALIDATION_FAILED",
+                "custom webhooks require an X-Connection-Id header or connection_id query parameter",
+            )
+        })?;
+    Uuid::parse_str(&value).map_err(|_| {
+        ApiError::new(
+            StatusCode::BAD_REQUEST,
+            "VALIDATION_FAILED",
+            "invalid connection_id query parameter format",
+        )
+    })
+}
+
+/// Check a custom webhook delivery against its connection's secret, then
+/// reserve it against replays to that connection
+///
+/// Operator tokens bypass both checks like they bypass provider signatures.
+async fn verify_custom_webhook_delivery(
+    state: &AppState,
+    tenant_id: Uuid,
+    connection_id: Uuid,
+    headers: &HeaderMap,
+    body: &[u8],
+) -> Result<Option<DeliveryReservation>, ApiError> {
+    if check_operator_auth(&state.config, headers) {
+        return Ok(None);
+    }
+
+    let internal_error = |e: anyhow::Error| {
+        error!(error = ?e, connection_id = %connection_id, "Failed to load custom webhook secret");
+        ApiError::new(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "INTERNAL_SERVER_ERROR",
+            "Failed to verify webhook",
+        )
+    };
+    let repo = ConnectionRepository::new(
+        std::sync::Arc::new(state.db.clone()),
+        state.master_keys.clone(),
+    );
+    let connection = repo
+        .find_by_id(&tenant_id, &connection_id)
+        .await
+        .map_err(internal_error)?
+        .filter(|connection| connection.provider_slug == CUSTOM_WEBHOOK_PROVIDER_SLUG)
+        .ok_or_else(|| {
+            ApiError::new(
+                StatusCode::NOT_FOUND,
+                "NOT_FOUND",
+                "connection not found for tenant/provider",
+            )
+        })?;
+    let connection = repo
+        .with_secrets(connection)
+        .await
+        .map_err(internal_error)?;
+    let (secret, _, _) = repo
+        .decrypt_tokens(&connection)
+        .await
+        .map_err(internal_error)?;
+    let secret = secret.ok_or_else(|| {
+        ApiError::from(
+            crate::webhook_verification::VerificationError::NotConfigured {
+                provider: CUSTOM_WEBHOOK_PROVIDER_SLUG.to_string(),
+            },
+        )
+    })?;
+
+    verify_custom_webhook(body, headers, &secret).map_err(|e| {
+        info!(connection_id = %connection_id, error = %e, "Custom webhook verification failed");
+        ApiError::from(e)
+    })?;
+    Ok(reserve_custom_webhook_delivery(
+        &state.config,
+        tenant_id,
+        connection_id,
+        headers,
+        body,
+    )?)
+}
+
+/// The tenant's connection for a push-only provider, created on first use
+async fn webhook_connection(
+    state: &AppState,
+    tenant_id: Uuid,
+    provider_slug: &str,
+    external_id: &str,
+    display_name: &str,
+    metadata: Option<JsonValue>,
+) -> Result<crate::models::connection::Model, ApiError> {
+    use sea_orm::Set;
+
+    let internal_error = |e: anyhow::Error| {
+        error!(error = ?e, provider_slug = %provider_slug, "Failed to resolve webhook connection");
+        ApiError::new(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "INTERNAL_SERVER_ERROR",
+            "Failed to resolve webhook connection",
+        )
+    };
+    let repo = ConnectionRepository::new(
+        std::sync::Arc::new(state.db.clone()),
+        state.master_keys.clone(),
+    );
+    let find = || repo.find_by_external_id(&tenant_id, provider_slug, external_id);
+
+    if let Some(connection) = find().await.map_err(internal_error)? {
+        return Ok(connection);
+    }
+
+    let now = chrono::Utc::now().fixed_offset();
+    let created = repo
+        .create(crate::models::connection::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            tenant_id: Set(tenant_id),
+            provider_slug: Set(provider_slug.to_string()),
+            external_id: Set(external_id.to_string()),
+            status: Set("active".to_string()),
+            display_name: Set(Some(display_name.to_string())),
+            expires_at: Set(None),
+            scopes: Set(None),
+            metadata: Set(metadata),
+            created_at: Set(now),
+            updated_at: Set(now),
+        })
+        .await;
+
+    match created {
+        Ok(connection) => Ok(connection),
+        // A concurrent request created it first
+        Err(err) => find()
+            .await
+            .map_err(internal_error)?
+            .ok_or_else(|| internal_error(err)),
+    }
+}
+
 /// Generate a GitHub HMAC-SHA256 signature for testing
 #[allow(dead_code)]
 fn generate_github_signature(body: &str, secret: &str) -> String {
@@ -771,6 +1228,7 @@ mod tests {
 
     use crate::config::AppConfig;
     use crate::db::init_pool;
+    use crate::repositories::ProviderRepository;
     use axum::{
         body::Body,
         http::{Request, StatusCode},
@@ -841,6 +1299,7 @@ mod tests {
             id: Set(tenant_id),
             name: Set(Some("Test Tenant".to_string())),
             created_at: Set(chrono::Utc::now().fixed_offset()),
+            ..Default::default()
         };
         tenant.insert(&state.db).await.unwrap();
     }
@@ -862,8 +1321,6 @@ mod tests {
             external_id: Set("test-external-id".to_string()),
             status: Set("active".to_string()),
             display_name: Set(Some("Test Connection".to_string())),
-            access_token_ciphertext: Set(Some(vec![1, 2, 3, 4])),
-            refresh_token_ciphertext: Set(None),
             expires_at: Set(None),
             scopes: Set(None),
             metadata: Set(None),
@@ -990,10 +1447,12 @@ mod tests {
     #[tokio::test]
     async fn test_public_webhook_jira_requires_secret_when_configured() {
         // Configure test profile with Jira webhook secret
-        let mut config = AppConfig::default();
-        config.profile = "test".to_string();
-        config.operator_tokens = vec!["test-token".to_string()];
-        config.webhook_jira_secret = Some("test-secret-123".to_string());
+        let config = AppConfig {
+            profile: "test".to_string(),
+            operator_tokens: vec!["test-token".to_string()],
+            webhook_jira_secret: Some("test-secret-123".to_string()),
+            ..Default::default()
+        };
 
         let (state, app) = setup_test_app_with_config(config).await;
 
@@ -1038,9 +1497,11 @@ mod tests {
     #[tokio::test]
     async fn test_public_webhook_jira_allows_without_secret_in_test_profile() {
         // Configure test profile without Jira webhook secret (allowed for test profile)
-        let mut config = AppConfig::default();
-        config.profile = "test".to_string();
-        config.operator_tokens = vec!["test-token".to_string()];
+        let config = AppConfig {
+            profile: "test".to_string(),
+            operator_tokens: vec!["test-token".to_string()],
+            ..Default::default()
+        };
 
         let (state, app) = setup_test_app_with_config(config).await;
 
@@ -1115,12 +1576,244 @@ mod tests {
         assert!(cursor.get("received_at").is_some());
     }
 
+    #[tokio::test]
+    async fn test_ingest_email_enqueues_mime_and_sendgrid_messages() {
+        let (state, app) = setup_test_app().await;
+        create_test_provider(&state, "inbound-email").await;
+        let tenant_id = Uuid::new_v4();
+        create_test_tenant(&state, tenant_id).await;
+
+        let raw = "From: Ada <ada@example.com>\r\nTo: team@acme.test\r\n\
+Subject: Launch\r\nMessage-ID: <launch@example.com>\r\n\r\nShipping Friday.\r\n";
+        let request = Request::builder()
+            .method("POST")
+            .uri("/ingest/email")
+            .header("Authorization", "Bearer test-token")
+            .header("X-Tenant-Id", tenant_id.to_string())
+            .header("Content-Type", "message/rfc822")
+            .body(Body::from(raw))
+            .unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::ACCEPTED);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let accepted: InboundEmailAcceptResponse = serde_json::from_slice(&body).unwrap();
+        assert_eq!(accepted.message_id, "launch@example.com");
+
+        let request = Request::builder()
+            .method("POST")
+            .uri("/ingest/email")
+            .header("Authorization", "Bearer test-token")
+            .header("X-Tenant-Id", tenant_id.to_string())
+            .header("Content-Type", "application/json")
+            .body(Body::from(
+                r#"{"from": "bob@example.com", "to": "team@acme.test", "subject": "Hi", "text": "Hello"}"#,
+            ))
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::ACCEPTED);
+
+        // Both messages land on the same lazily created connection
+        let jobs = SyncJobRepository::new(state.db.clone())
+            .list_by_tenant(
+                tenant_id,
+                Some("inbound-email".to_string()),
+                None,
+                Some(10),
+                Some(0),
+            )
+            .await
+            .unwrap();
+        assert_eq!(jobs.len(), 2);
+        assert!(jobs.iter().all(|job| job.job_type == "webhook"
+            && job.connection_id == accepted.connection_id));
+        let payloads: Vec<_> = jobs
+            .iter()
+            .map(|job| job.cursor.as_ref().unwrap()["webhook_payload"]["source"].clone())
+            .collect();
+        assert!(payloads.contains(&serde_json::json!("mime")));
+        assert!(payloads.contains(&serde_json::json!("sendgrid")));
+    }
+
+    #[tokio::test]
+    async fn test_ingest_email_rejects_unparseable_and_oversized_messages() {
+        let (state, app) = setup_test_app_with_config(AppConfig {
+            profile: "test".to_string(),
+            operator_tokens: vec!["test-token".to_string()],
+            inbound_email_max_body_kb: 1,
+            ..Default::default()
+        })
+        .await;
+        create_test_provider(&state, "inbound-email").await;
+        let tenant_id = Uuid::new_v4();
+
+        let request = |content_type: &str, body: String| {
+            Request::builder()
+                .method("POST")
+                .uri("/ingest/email")
+                .header("Authorization", "Bearer test-token")
+                .header("X-Tenant-Id", tenant_id.to_string())
+                .header("Content-Type", content_type)
+                .body(Body::from(body))
+                .unwrap()
+        };
+
+        let response = app
+            .clone()
+            .oneshot(request(
+                "application/json",
+                r#"{"hello": "world"}"#.to_string(),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let oversized = format!("From: ada@example.com\r\n\r\n{}", "x".repeat(2048));
+        let response = app
+            .oneshot(request("message/rfc822", oversized))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+    }
+
+    #[tokio::test]
+    async fn test_webhook_ingest_refuses_delivery_when_buffer_saturated() {
+        let (state, app) = setup_test_app_with_config(AppConfig {
+            profile: "test".to_string(),
+            operator_tokens: vec!["test-token".to_string()],
+            webhook_backpressure_max_queued_jobs: 1,
+            webhook_backpressure_retry_after_seconds: 15,
+            ..Default::default()
+        })
+        .await;
+        create_test_provider(&state, "github").await;
+
+        let tenant_id = Uuid::new_v4();
+        let connection_id = create_test_connection(&state, tenant_id, "github").await;
+        SyncJobRepository::new(state.db.clone())
+            .enqueue_webhook_job(tenant_id, "github", connection_id, None)
+            .await
+            .unwrap();
+
+        let request = Request::builder()
+            .method("POST")
+            .uri("/webhooks/github")
+            .header("Authorization", "Bearer test-token")
+            .header("X-Tenant-Id", tenant_id.to_string())
+            .header("X-Connection-Id", connection_id.to_string())
+            .header("Content-Type", "application/json")
+            .body(Body::from(r#"{"event": "push"}"#))
+            .unwrap();
+
+        let response = app.oneshot(request).await.unwrap();
+        // GitHub redelivers 5xx responses and ignores Retry-After
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert!(response.headers().get("retry-after").is_none());
+
+        let jobs = SyncJobRepository::new(state.db.clone())
+            .list_by_tenant(
+                tenant_id,
+                Some("github".to_string()),
+                None,
+                Some(10),
+                Some(0),
+            )
+            .await
+            .unwrap();
+        assert_eq!(jobs.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_webhook_github_push_enqueues_debounced_incremental_sync() {
+        let (state, app) = setup_test_app().await;
+        create_test_provider(&state, "github").await;
+
+        let tenant_id = Uuid::new_v4();
+        let connection_id = create_test_connection(&state, tenant_id, "github").await;
+
+        for _ in 0..2 {
+            let request = Request::builder()
+                .method("POST")
+                .uri("/webhooks/github")
+                .header("Authorization", "Bearer test-token")
+                .header("X-Tenant-Id", tenant_id.to_string())
+                .header("X-Connection-Id", connection_id.to_string())
+                .header("X-GitHub-Event", "push")
+                .header("Content-Type", "application/json")
+                .body(Body::from(r#"{"repository": {"full_name": "octo/repo"}}"#))
+                .unwrap();
+
+            let response = app.clone().oneshot(request).await.unwrap();
+            assert_eq!(response.status(), StatusCode::ACCEPTED);
+        }
+
+        let sync_job_repo = SyncJobRepository::new(state.db.clone());
+        let jobs = sync_job_repo
+            .list_by_tenant(tenant_id, None, None, None, None)
+            .await
+            .unwrap();
+
+        // Each delivery is processed, but the pushes coalesce into one incremental sync
+        assert_eq!(jobs.iter().filter(|j| j.job_type == "webhook").count(), 2);
+        let incremental: Vec<_> = jobs
+            .iter()
+            .filter(|j| j.job_type == "incremental")
+            .collect();
+        assert_eq!(incremental.len(), 1);
+        assert_eq!(incremental[0].connection_id, connection_id);
+        assert_eq!(incremental[0].status, "queued");
+        assert!(incremental[0].cursor.is_none());
+        assert!(incremental[0].scheduled_at > incremental[0].created_at);
+    }
+
+    #[tokio::test]
+    async fn test_webhook_github_push_does_not_sync_paused_connection() {
+        use sea_orm::{ActiveModelTrait, EntityTrait, Set};
+
+        let (state, app) = setup_test_app().await;
+        create_test_provider(&state, "github").await;
+
+        let tenant_id = Uuid::new_v4();
+        let connection_id = create_test_connection(&state, tenant_id, "github").await;
+        let mut connection: crate::models::connection::ActiveModel =
+            crate::models::connection::Entity::find_by_id(connection_id)
+                .one(&state.db)
+                .await
+                .unwrap()
+                .unwrap()
+                .into();
+        connection.status = Set(crate::connection_pause::STATUS_PAUSED.to_string());
+        connection.update(&state.db).await.unwrap();
+
+        let request = Request::builder()
+            .method("POST")
+            .uri("/webhooks/github")
+            .header("Authorization", "Bearer test-token")
+            .header("X-Tenant-Id", tenant_id.to_string())
+            .header("X-Connection-Id", connection_id.to_string())
+            .header("X-GitHub-Event", "push")
+            .header("Content-Type", "application/json")
+            .body(Body::from(r#"{"repository": {"full_name": "octo/repo"}}"#))
+            .unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::ACCEPTED);
+
+        let jobs = SyncJobRepository::new(state.db.clone())
+            .list_by_tenant(tenant_id, None, None, None, None)
+            .await
+            .unwrap();
+        assert!(jobs.iter().all(|j| j.job_type != "incremental"));
+    }
+
     #[tokio::test]
     async fn test_public_webhook_github_valid_signature_accepted() {
-        let mut config = AppConfig::default();
-        config.profile = "test".to_string();
-        config.webhook_github_secret = Some("test-secret-123".to_string());
-        config.operator_tokens = vec!["operator-token".to_string()];
+        let config = AppConfig {
+            profile: "test".to_string(),
+            webhook_github_secret: Some("test-secret-123".to_string()),
+            operator_tokens: vec!["operator-token".to_string()],
+            ..Default::default()
+        };
 
         let (state, app) = setup_test_app_with_config(config).await;
         create_test_provider(&state, "github").await;
@@ -1150,9 +1843,11 @@ mod tests {
 
     #[tokio::test]
     async fn test_public_webhook_github_invalid_signature_rejected() {
-        let mut config = AppConfig::default();
-        config.profile = "test".to_string();
-        config.webhook_github_secret = Some("test-secret-123".to_string());
+        let config = AppConfig {
+            profile: "test".to_string(),
+            webhook_github_secret: Some("test-secret-123".to_string()),
+            ..Default::default()
+        };
 
         let (state, app) = setup_test_app_with_config(config).await;
         create_test_provider(&state, "github").await;
@@ -1181,9 +1876,11 @@ mod tests {
 
     #[tokio::test]
     async fn test_public_webhook_github_missing_signature_rejected() {
-        let mut config = AppConfig::default();
-        config.profile = "test".to_string();
-        config.webhook_github_secret = Some("test-secret-123".to_string());
+        let config = AppConfig {
+            profile: "test".to_string(),
+            webhook_github_secret: Some("test-secret-123".to_string()),
+            ..Default::default()
+        };
 
         let (state, app) = setup_test_app_with_config(config).await;
         create_test_provider(&state, "github").await;
@@ -1211,10 +1908,12 @@ mod tests {
 
     #[tokio::test]
     async fn test_public_webhook_github_operator_auth_overrides_signature() {
-        let mut config = AppConfig::default();
-        config.profile = "test".to_string();
-        config.webhook_github_secret = Some("test-secret-123".to_string());
-        config.operator_tokens = vec!["operator-token".to_string()];
+        let config = AppConfig {
+            profile: "test".to_string(),
+            webhook_github_secret: Some("test-secret-123".to_string()),
+            operator_tokens: vec!["operator-token".to_string()],
+            ..Default::default()
+        };
 
         let (state, app) = setup_test_app_with_config(config).await;
         create_test_provider(&state, "github").await;
@@ -1245,10 +1944,12 @@ mod tests {
 
     #[tokio::test]
     async fn test_public_webhook_slack_valid_signature_accepted() {
-        let mut config = AppConfig::default();
-        config.profile = "test".to_string();
-        config.webhook_slack_signing_secret = Some("test-slack-secret".to_string());
-        config.webhook_slack_tolerance_seconds = 300;
+        let config = AppConfig {
+            profile: "test".to_string(),
+            webhook_slack_signing_secret: Some("test-slack-secret".to_string()),
+            webhook_slack_tolerance_seconds: 300,
+            ..Default::default()
+        };
 
         let (state, app) = setup_test_app_with_config(config).await;
         // Check if slack provider already exists to avoid constraint violation
@@ -1277,10 +1978,12 @@ mod tests {
 
     #[tokio::test]
     async fn test_public_webhook_slack_timestamp_too_old_rejected() {
-        let mut config = AppConfig::default();
-        config.profile = "test".to_string();
-        config.webhook_slack_signing_secret = Some("test-slack-secret".to_string());
-        config.webhook_slack_tolerance_seconds = 300;
+        let config = AppConfig {
+            profile: "test".to_string(),
+            webhook_slack_signing_secret: Some("test-slack-secret".to_string()),
+            webhook_slack_tolerance_seconds: 300,
+            ..Default::default()
+        };
 
         let (state, app) = setup_test_app_with_config(config).await;
         // Check if slack provider already exists to avoid constraint violation
@@ -1315,10 +2018,261 @@ mod tests {
         assert_eq!(error_response["code"], "REPLAY_ATTACK_DETECTED");
     }
 
+    #[tokio::test]
+    async fn test_public_webhook_linear_signature_checked() {
+        let config = AppConfig {
+            profile: "test".to_string(),
+            webhook_linear_secret: Some("test-linear-secret".to_string()),
+            ..Default::default()
+        };
+
+        let (state, app) = setup_test_app_with_config(config).await;
+        let provider_repo = ProviderRepository::new(std::sync::Arc::new(state.db.clone()));
+        if provider_repo
+            .find_by_slug("linear")
+            .await
+            .unwrap()
+            .is_none()
+        {
+            create_test_provider(&state, "linear").await;
+        }
+
+        let tenant_id = Uuid::new_v4();
+        let body = format!(
+            r#"{{"action": "create", "type": "Issue", "data": {{"id": "i-1"}}, "webhookTimestamp": {}}}"#,
+            chrono::Utc::now().timestamp_millis()
+        );
+        // Linear signs the raw body without a prefix
+        let signature = generate_github_signature(&body, "test-linear-secret")
+            .trim_start_matches("sha256=")
+            .to_string();
+
+        let request = |signature: &str| {
+            Request::builder()
+                .method("POST")
+                .uri(format!("/webhooks/linear/{}", tenant_id))
+                .header("Content-Type", "application/json")
+                .header("Linear-Signature", signature)
+                .header("Linear-Delivery", Uuid::new_v4().to_string())
+                .body(Body::from(body.clone()))
+                .unwrap()
+        };
+
+        let response = app.clone().oneshot(request(&signature)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::ACCEPTED);
+
+        let response = app.oneshot(request(&"0".repeat(64))).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_public_webhook_gitlab_token_checked() {
+        let config = AppConfig {
+            profile: "test".to_string(),
+            webhook_gitlab_token: Some("test-gitlab-token".to_string()),
+            ..Default::default()
+        };
+
+        let (state, app) = setup_test_app_with_config(config).await;
+        let provider_repo = ProviderRepository::new(std::sync::Arc::new(state.db.clone()));
+        if provider_repo
+            .find_by_slug("gitlab")
+            .await
+            .unwrap()
+            .is_none()
+        {
+            create_test_provider(&state, "gitlab").await;
+        }
+
+        let tenant_id = Uuid::new_v4();
+        let body = r#"{"object_kind": "issue", "object_attributes": {"id": 1, "action": "open"}}"#;
+
+        let request = |token: &str| {
+            Request::builder()
+                .method("POST")
+                .uri(format!("/webhooks/gitlab/{}", tenant_id))
+                .header("Content-Type", "application/json")
+                .header("X-Gitlab-Token", token)
+                .header("X-Gitlab-Event-UUID", Uuid::new_v4().to_string())
+                .body(Body::from(body))
+                .unwrap()
+        };
+
+        let response = app
+            .clone()
+            .oneshot(request("test-gitlab-token"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::ACCEPTED);
+
+        let response = app.oneshot(request("wrong-token")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_public_webhook_datadog_creates_connection_without_header() {
+        let config = AppConfig {
+            profile: "test".to_string(),
+            webhook_datadog_secret: Some("dd-webhook-token".to_string()),
+            ..Default::default()
+        };
+
+        let (state, app) = setup_test_app_with_config(config).await;
+        create_test_provider(&state, "datadog").await;
+        let tenant_id = Uuid::new_v4();
+        create_test_tenant(&state, tenant_id).await;
+
+        let request = |event_id: &str| {
+            let body = serde_json::json!({
+                "id": event_id,
+                "alert_id": "123456",
+                "transition": "Triggered",
+                "alert_type": "error",
+                "org": {"id": "42", "name": "Acme"}
+            });
+            Request::builder()
+                .method("POST")
+                .uri(format!("/webhooks/datadog/{}", tenant_id))
+                .header("Content-Type", "application/json")
+                .header("Authorization", "Bearer dd-webhook-token")
+                .body(Body::from(body.to_string()))
+                .unwrap()
+        };
+
+        for event_id in ["1001", "1002"] {
+            let response = app.clone().oneshot(request(event_id)).await.unwrap();
+            assert_eq!(response.status(), StatusCode::ACCEPTED);
+        }
+
+        // Both alerts land on the organization's lazily created connection
+        let connection = ConnectionRepository::new(
+            std::sync::Arc::new(state.db.clone()),
+            state.master_keys.clone(),
+        )
+        .find_by_external_id(&tenant_id, "datadog", "42")
+        .await
+        .unwrap()
+        .expect("Datadog connection should be created");
+        let jobs = SyncJobRepository::new(state.db.clone())
+            .list_by_tenant(
+                tenant_id,
+                Some("datadog".to_string()),
+                None,
+                Some(10),
+                Some(0),
+            )
+            .await
+            .unwrap();
+        let webhook_jobs: Vec<_> = jobs
+            .iter()
+            .filter(|job| job.job_type == "webhook")
+            .collect();
+        assert_eq!(webhook_jobs.len(), 2);
+        assert!(
+            webhook_jobs
+                .iter()
+                .all(|job| job.connection_id == connection.id)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_public_webhook_custom_verifies_connection_secret() {
+        use sea_orm::Set;
+
+        let config = AppConfig {
+            profile: "test".to_string(),
+            ..Default::default()
+        };
+
+        let (state, app) = setup_test_app_with_config(config).await;
+        create_test_provider(&state, "custom-webhook").await;
+        let tenant_id = Uuid::new_v4();
+        create_test_tenant(&state, tenant_id).await;
+
+        let now = chrono::Utc::now().fixed_offset();
+        let repo = ConnectionRepository::new(
+            std::sync::Arc::new(state.db.clone()),
+            state.master_keys.clone(),
+        );
+        let connection = repo
+            .create_with_tokens(
+                crate::models::connection::ActiveModel {
+                    id: Set(Uuid::new_v4()),
+                    tenant_id: Set(tenant_id),
+                    provider_slug: Set("custom-webhook".to_string()),
+                    external_id: Set(Uuid::new_v4().to_string()),
+                    status: Set("active".to_string()),
+                    display_name: Set(Some("Deploy bot".to_string())),
+                    expires_at: Set(None),
+                    scopes: Set(None),
+                    metadata: Set(Some(serde_json::json!({"kind": "$.type"}))),
+                    created_at: Set(now),
+                    updated_at: Set(now),
+                },
+                Some("hook-secret"),
+                None,
+            )
+            .await
+            .unwrap();
+
+        let body = serde_json::json!({"type": "ci_run_failed", "id": "run-7"}).to_string();
+        let request = |secret: &str| {
+            Request::builder()
+                .method("POST")
+                .uri(format!(
+                    "/webhooks/custom-webhook/{}?connection_id={}",
+                    tenant_id, connection.id
+                ))
+                .header("Content-Type", "application/json")
+                .header("X-Webhook-Secret", secret)
+                .body(Body::from(body.clone()))
+                .unwrap()
+        };
+
+        // A forged delivery does not reserve the body, so the genuine one still gets through
+        let response = app.clone().oneshot(request("wrong-secret")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let response = app.clone().oneshot(request("hook-secret")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::ACCEPTED);
+
+        let replay = app.clone().oneshot(request("hook-secret")).await.unwrap();
+        assert_eq!(replay.status(), StatusCode::UNAUTHORIZED);
+        let error: serde_json::Value = serde_json::from_slice(
+            &axum::body::to_bytes(replay.into_body(), usize::MAX)
+                .await
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(error["code"], "REPLAY_ATTACK_DETECTED");
+
+        // The queued payload names the connection whose mapping rules apply
+        let jobs = SyncJobRepository::new(state.db.clone())
+            .list_by_tenant(
+                tenant_id,
+                Some("custom-webhook".to_string()),
+                None,
+                Some(10),
+                Some(0),
+            )
+            .await
+            .unwrap();
+        assert_eq!(jobs.len(), 1);
+        assert_eq!(jobs[0].connection_id, connection.id);
+        let payload = &jobs[0].cursor.as_ref().unwrap()["webhook_payload"];
+        assert_eq!(
+            payload["connection_id"],
+            serde_json::json!(connection.id.to_string())
+        );
+        assert_eq!(payload["body"]["id"], serde_json::json!("run-7"));
+    }
+
     #[tokio::test]
     async fn test_public_webhook_github_secret_missing_rejected() {
-        let mut config = AppConfig::default();
-        config.profile = "test".to_string();
+        let config = AppConfig {
+            profile: "test".to_string(),
+            ..Default::default()
+        };
         // No GitHub secret configured
 
         let (state, app) = setup_test_app_with_config(config).await;
@@ -1347,9 +2301,11 @@ mod tests {
 
     #[tokio::test]
     async fn test_public_webhook_unsupported_provider_returns_404() {
-        let mut config = AppConfig::default();
-        config.profile = "test".to_string();
-        config.webhook_github_secret = Some("test-secret-123".to_string());
+        let config = AppConfig {
+            profile: "test".to_string(),
+            webhook_github_secret: Some("test-secret-123".to_string()),
+            ..Default::default()
+        };
 
         let (state, app) = setup_test_app_with_config(config).await;
         // Only create github provider, not the unsupported one
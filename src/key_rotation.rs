@@ -0,0 +1,469 @@
+//! # Master Key Rotation
+//!
+//! Moves every stored secret onto a new master key version so the old version
+//! can be dropped from the configuration. Connections are walked in id order in
+//! batches, each inside one transaction: tokens are decrypted with whatever key
+//! they were written under (legacy plaintext, a master key version or a tenant
+//! data key) and re-encrypted with fresh record keys under the tenant's data
+//! key, which is re-wrapped with the new master key version. Tenant data keys
+//! without any connection are re-wrapped afterwards.
+//!
+//! Rows that cannot be decrypted are left unchanged and reported, so a rerun
+//! only retries them. Connections whose tokens were already written under the
+//! new version are skipped, which makes an interrupted rotation resumable.
+
+use std::collections::HashMap;
+
+use anyhow::{Result, anyhow};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, DatabaseTransaction, EntityTrait,
+    PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, Set, TransactionTrait,
+};
+use uuid::Uuid;
+
+use crate::crypto::{
+    CryptoError, CryptoKey, MasterKeyring, decrypt_connection_tokens_with_keyring,
+    encrypt_connection_tokens_with_tenant_key, uses_record_key,
+};
+use crate::models::connection::{self, Entity as Connection};
+use crate::models::connection_secret::{self, Entity as ConnectionSecret};
+use crate::models::tenant_data_key::{self, Entity as TenantDataKey};
+
+/// Connections re-encrypted per transaction unless overridden
+pub const DEFAULT_ROTATION_BATCH_SIZE: u64 = 100;
+
+/// Running totals of a rotation, reported after every committed batch
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RotationProgress {
+    /// Connections in the database when the rotation started
+    pub connections_total: u64,
+    /// Connections examined so far
+    pub connections_processed: u64,
+    /// Connections whose tokens were re-encrypted under the new version
+    pub connections_rotated: u64,
+    /// Tenant data keys re-wrapped or created with the new version
+    pub tenant_keys_rewrapped: u64,
+    /// Connections whose tokens could not be decrypted; left unchanged
+    pub failed_connections: Vec<Uuid>,
+    /// Tenants whose data key could not be unwrapped; left unchanged
+    pub failed_tenants: Vec<Uuid>,
+}
+
+impl RotationProgress {
+    /// Whether every row is now on the new master key version
+    pub fn is_complete(&self) -> bool {
+        self.failed_connections.is_empty() && self.failed_tenants.is_empty()
+    }
+}
+
+/// Re-encrypts all stored tokens and re-wraps all tenant data keys with the
+/// current version of `keyring`, which must also hold every version the data
+/// was written under. `on_batch` is called after each committed batch.
+pub async fn rotate_master_key(
+    db: &DatabaseConnection,
+    keyring: &MasterKeyring,
+    batch_size: u64,
+    mut on_batch: impl FnMut(&RotationProgress),
+) -> Result<RotationProgress> {
+    let batch_size = batch_size.max(1);
+    let mut progress = RotationProgress {
+        connections_total: Connection::find().count(db).await?,
+        ..Default::default()
+    };
+    // Tenant keys already moved to the new version during this run
+    let mut tenant_keys: HashMap<Uuid, CryptoKey> = HashMap::new();
+
+    let mut after: Option<Uuid> = None;
+    loop {
+        let mut query = Connection::find()
+            .order_by_asc(connection::Column::Id)
+            .limit(batch_size);
+        if let Some(last) = after {
+            query = query.filter(connection::Column::Id.gt(last));
+        }
+        let batch = query.all(db).await?;
+        let Some(last) = batch.last() else {
+            break;
+        };
+        after = Some(last.id);
+
+        let txn = db.begin().await?;
+        for conn in batch {
+            progress.connections_processed += 1;
+            match rotate_connection(&txn, keyring, &mut tenant_keys, conn).await? {
+                Outcome::Rotated {
+                    tenant_key_rewrapped,
+                } => {
+                    progress.connections_rotated += 1;
+                    progress.tenant_keys_rewrapped += u64::from(tenant_key_rewrapped);
+                }
+                Outcome::Unchanged => {}
+                Outcome::Failed(connection_id) => progress.failed_connections.push(connection_id),
+            }
+        }
+        txn.commit().await?;
+        on_batch(&progress);
+    }
+
+    let mut after: Option<Uuid> = None;
+    loop {
+        let mut query = TenantDataKey::find()
+            .filter(tenant_data_key::Column::KeyVersion.ne(keyring.current_version()))
+            .order_by_asc(tenant_data_key::Column::TenantId)
+            .limit(batch_size);
+        if let Some(last) = after {
+            query = query.filter(tenant_data_key::Column::TenantId.gt(last));
+        }
+        let batch = query.all(db).await?;
+        let Some(last) = batch.last() else {
+            break;
+        };
+        after = Some(last.tenant_id);
+
+        let txn = db.begin().await?;
+        for row in batch {
+            let tenant_id = row.tenant_id;
+            match keyring.unwrap_tenant_key(row.key_version, tenant_id, &row.wrapped_key) {
+                Ok(key) => {
+                    rewrap_tenant_key(&txn, keyring, row, &key).await?;
+                    progress.tenant_keys_rewrapped += 1;
+                }
+                Err(e) => {
+                    tracing::warn!(tenant_id = %tenant_id, error = %e, "Failed to unwrap tenant data key");
+                    progress.failed_tenants.push(tenant_id);
+                }
+            }
+        }
+        txn.commit().await?;
+        on_batch(&progress);
+    }
+
+    tracing::info!(
+        key_version = keyring.current_version(),
+        connections_rotated = progress.connections_rotated,
+        tenant_keys_rewrapped = progress.tenant_keys_rewrapped,
+        failed_connections = progress.failed_connections.len(),
+        failed_tenants = progress.failed_tenants.len(),
+        "Master key rotation finished"
+    );
+    Ok(progress)
+}
+
+enum Outcome {
+    /// Tokens re-encrypted; the tenant key was re-wrapped or created on the way
+    Rotated {
+        tenant_key_rewrapped: bool,
+    },
+    Unchanged,
+    Failed(Uuid),
+}
+
+async fn rotate_connection(
+    txn: &DatabaseTransaction,
+    keyring: &MasterKeyring,
+    tenant_keys: &mut HashMap<Uuid, CryptoKey>,
+    mut conn: connection::Model,
+) -> Result<Outcome> {
+    let version = keyring.current_version();
+    // Locked so a concurrent token refresh cannot be overwritten with the old value
+    let Some(secret) = ConnectionSecret::find_by_id(conn.id)
+        .lock_exclusive()
+        .one(txn)
+        .await?
+    else {
+        return Ok(Outcome::Unchanged);
+    };
+    let current = |token: &Option<Vec<u8>>, key_version: Option<i32>| match token {
+        Some(token) => key_version == Some(version) && uses_record_key(token),
+        None => true,
+    };
+    if current(
+        &secret.access_token_ciphertext,
+        secret.access_token_key_version,
+    ) && current(
+        &secret.refresh_token_ciphertext,
+        secret.refresh_token_key_version,
+    ) {
+        return Ok(Outcome::Unchanged);
+    }
+
+    let mut tenant_key_rewrapped = false;
+    let tenant_key = match tenant_keys.get(&conn.tenant_id) {
+        Some(key) => Some(key.clone()),
+        None => match current_tenant_key(txn, keyring, conn.tenant_id).await? {
+            Ok(Some((key, rewrapped))) => {
+                tenant_key_rewrapped = rewrapped;
+                Some(key)
+            }
+            Ok(None) => None,
+            Err(e) => {
+                tracing::warn!(connection_id = %conn.id, error = %e, "Failed to unwrap tenant data key");
+                return Ok(Outcome::Failed(conn.id));
+            }
+        },
+    };
+
+    conn.access_token_ciphertext = secret.access_token_ciphertext.clone();
+    conn.refresh_token_ciphertext = secret.refresh_token_ciphertext.clone();
+    let (access_token, refresh_token) = match decrypt_connection_tokens_with_keyring(
+        keyring,
+        tenant_key.as_ref(),
+        &conn,
+    ) {
+        Ok(tokens) => tokens,
+        Err(e) => {
+            tracing::warn!(connection_id = %conn.id, error = %e, "Failed to decrypt connection tokens");
+            return Ok(Outcome::Failed(conn.id));
+        }
+    };
+
+    // Tokens from before tenant keys existed get one now
+    let tenant_key = match tenant_key {
+        Some(key) => key,
+        None => {
+            tenant_key_rewrapped = true;
+            create_tenant_key(txn, keyring, conn.tenant_id).await?
+        }
+    };
+    tenant_keys.insert(conn.tenant_id, tenant_key.clone());
+
+    let (access_ciphertext, refresh_ciphertext) = encrypt_connection_tokens_with_tenant_key(
+        &tenant_key,
+        &conn,
+        access_token.as_deref(),
+        refresh_token.as_deref(),
+    )
+    .map_err(|e| anyhow!("Failed to encrypt tokens for {}: {}", conn.id, e))?;
+
+    let mut secret: connection_secret::ActiveModel = secret.into();
+    secret.access_token_key_version = Set(access_ciphertext.as_ref().map(|_| version));
+    secret.refresh_token_key_version = Set(refresh_ciphertext.as_ref().map(|_| version));
+    secret.access_token_ciphertext = Set(access_ciphertext);
+    secret.refresh_token_ciphertext = Set(refresh_ciphertext);
+    secret.updated_at = Set(chrono::Utc::now().fixed_offset());
+    secret.update(txn).await?;
+
+    Ok(Outcome::Rotated {
+        tenant_key_rewrapped,
+    })
+}
+
+// Loads a tenant's data key, re-wrapping it with the current version if needed and
+// reporting whether it was; the inner error is a key that cannot be unwrapped
+async fn current_tenant_key(
+    txn: &DatabaseTransaction,
+    keyring: &MasterKeyring,
+    tenant_id: Uuid,
+) -> Result<Result<Option<(CryptoKey, bool)>, CryptoError>> {
+    let Some(row) = TenantDataKey::find_by_id(tenant_id)
+        .lock_exclusive()
+        .one(txn)
+        .await?
+    else {
+        return Ok(Ok(None));
+    };
+    let key = match keyring.unwrap_tenant_key(row.key_version, tenant_id, &row.wrapped_key) {
+        Ok(key) => key,
+        Err(e) => return Ok(Err(e)),
+    };
+    let stale = row.key_version != keyring.current_version();
+    if stale {
+        rewrap_tenant_key(txn, keyring, row, &key).await?;
+    }
+    Ok(Ok(Some((key, stale))))
+}
+
+async fn rewrap_tenant_key(
+    txn: &DatabaseTransaction,
+    keyring: &MasterKeyring,
+    row: tenant_data_key::Model,
+    key: &CryptoKey,
+) -> Result<()> {
+    let (key_version, wrapped_key) = keyring
+        .wrap_tenant_key(row.tenant_id, key)
+        .map_err(|e| anyhow!("Failed to wrap tenant data key: {}", e))?;
+    let mut row: tenant_data_key::ActiveModel = row.into();
+    row.wrapped_key = Set(wrapped_key);
+    row.key_version = Set(key_version);
+    row.update(txn).await?;
+    Ok(())
+}
+
+async fn create_tenant_key(
+    txn: &DatabaseTransaction,
+    keyring: &MasterKeyring,
+    tenant_id: Uuid,
+) -> Result<CryptoKey> {
+    let key = CryptoKey::generate();
+    let (key_version, wrapped_key) = keyring
+        .wrap_tenant_key(tenant_id, &key)
+        .map_err(|e| anyhow!("Failed to wrap tenant data key: {}", e))?;
+    tenant_data_key::ActiveModel {
+        tenant_id: Set(tenant_id),
+        wrapped_key: Set(wrapped_key),
+        key_version: Set(key_version),
+        created_at: Set(chrono::Utc::now().fixed_offset()),
+    }
+    .insert(txn)
+    .await?;
+    Ok(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::AppConfig;
+    use crate::crypto::{encrypt_bytes, encrypt_bytes_with_record_key, wrap_tenant_key};
+    use crate::db::init_pool;
+    use crate::repositories::provider::ProviderRepository;
+    use crate::repositories::{ConnectionRepository, ConnectionSecretRepository};
+    use migration::{Migrator, MigratorTrait};
+    use std::sync::Arc;
+
+    async fn insert_connection(db: &DatabaseConnection, tenant_id: Uuid) -> connection::Model {
+        connection::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            tenant_id: Set(tenant_id),
+            provider_slug: Set("rotation-provider".to_string()),
+            external_id: Set(Uuid::new_v4().to_string()),
+            ..Default::default()
+        }
+        .insert(db)
+        .await
+        .unwrap()
+    }
+
+    async fn insert_tenant(db: &DatabaseConnection) -> Uuid {
+        let tenant_id = Uuid::new_v4();
+        crate::models::tenant::ActiveModel {
+            id: Set(tenant_id),
+            ..Default::default()
+        }
+        .insert(db)
+        .await
+        .unwrap();
+        tenant_id
+    }
+
+    fn aad(conn: &connection::Model) -> String {
+        format!(
+            "{}|{}|{}",
+            conn.tenant_id, conn.provider_slug, conn.external_id
+        )
+    }
+
+    #[tokio::test]
+    async fn test_rotation_moves_every_token_to_the_new_version() {
+        let config = AppConfig {
+            profile: "test".to_string(),
+            ..Default::default()
+        };
+        let db = init_pool(&config).await.expect("Failed to init test DB");
+        Migrator::up(&db, None).await.unwrap();
+        ProviderRepository::new(Arc::new(db.clone()))
+            .upsert("rotation-provider", "Rotation Provider", "oauth")
+            .await
+            .unwrap();
+        let secrets = ConnectionSecretRepository::new(Arc::new(db.clone()));
+
+        // Random keys, so rows written by other tests sharing the database are never decryptable here
+        let old_key = CryptoKey::generate();
+        let new_key = CryptoKey::generate();
+        let old = MasterKeyring::new(1, old_key.clone()).unwrap();
+
+        // A legacy master-key token, and an envelope token under a version 1 tenant key
+        let legacy_tenant = insert_tenant(&db).await;
+        let legacy = insert_connection(&db, legacy_tenant).await;
+        let ciphertext =
+            encrypt_bytes(&old_key, aad(&legacy).as_bytes(), b"legacy-access").unwrap();
+        secrets
+            .upsert(&legacy.id, Some(ciphertext), None)
+            .await
+            .unwrap();
+
+        let enveloped_tenant = insert_tenant(&db).await;
+        let tenant_key = CryptoKey::generate();
+        tenant_data_key::ActiveModel {
+            tenant_id: Set(enveloped_tenant),
+            wrapped_key: Set(wrap_tenant_key(&old_key, enveloped_tenant, &tenant_key).unwrap()),
+            key_version: Set(1),
+            created_at: Set(chrono::Utc::now().fixed_offset()),
+        }
+        .insert(&db)
+        .await
+        .unwrap();
+        let enveloped = insert_connection(&db, enveloped_tenant).await;
+        let seal = |token: &[u8]| {
+            encrypt_bytes_with_record_key(&tenant_key, aad(&enveloped).as_bytes(), token).unwrap()
+        };
+        secrets
+            .upsert_with_key_version(
+                &enveloped.id,
+                Some(seal(b"enveloped-access")),
+                Some(seal(b"enveloped-refresh")),
+                1,
+            )
+            .await
+            .unwrap();
+
+        // A shredded tenant's tokens can never be rotated
+        let shredded_tenant = insert_tenant(&db).await;
+        let shredded = insert_connection(&db, shredded_tenant).await;
+        let orphan = encrypt_bytes_with_record_key(
+            &CryptoKey::generate(),
+            aad(&shredded).as_bytes(),
+            b"gone",
+        )
+        .unwrap();
+        secrets
+            .upsert_with_key_version(&shredded.id, Some(orphan), None, 1)
+            .await
+            .unwrap();
+
+        let rotated = old.rotate(2, new_key.clone()).unwrap();
+        let mut reported = Vec::new();
+        let progress =
+            rotate_master_key(&db, &rotated, 2, |p| reported.push(p.connections_processed))
+                .await
+                .unwrap();
+        assert!(reported.windows(2).all(|w| w[0] <= w[1]));
+        assert!(progress.connections_processed >= 3);
+        assert!(progress.failed_connections.contains(&shredded.id));
+        assert!(!progress.failed_connections.contains(&legacy.id));
+        assert!(!progress.failed_connections.contains(&enveloped.id));
+
+        // Only the new key is needed from now on
+        let repo = ConnectionRepository::new(
+            Arc::new(db.clone()),
+            MasterKeyring::new(2, new_key).unwrap(),
+        );
+        for (conn, access, refresh) in [
+            (&legacy, "legacy-access", None),
+            (&enveloped, "enveloped-access", Some("enveloped-refresh")),
+        ] {
+            let model = repo.with_secrets(conn.clone()).await.unwrap();
+            let (a, r, _) = repo.decrypt_tokens(&model).await.unwrap();
+            assert_eq!(a.as_deref(), Some(access));
+            assert_eq!(r.as_deref(), refresh);
+
+            let secret = secrets.find(&conn.id).await.unwrap().unwrap();
+            assert_eq!(secret.access_token_key_version, Some(2));
+            let tenant_row = TenantDataKey::find_by_id(conn.tenant_id)
+                .one(&db)
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(tenant_row.key_version, 2);
+        }
+
+        // A rerun leaves rotated rows alone and retries only the failed one
+        let before = secrets.find(&enveloped.id).await.unwrap().unwrap();
+        let rerun = rotate_master_key(&db, &rotated, 50, |_| {}).await.unwrap();
+        assert!(rerun.failed_connections.contains(&shredded.id));
+        let after = secrets.find(&enveloped.id).await.unwrap().unwrap();
+        assert_eq!(
+            before.access_token_ciphertext,
+            after.access_token_ciphertext
+        );
+    }
+}
@@ -4,23 +4,49 @@
 //! including handlers, models, and server configuration.
 
 pub mod auth;
+pub mod backoff;
+pub mod billing;
+pub mod circuit_breaker;
+pub mod cli;
+pub mod clock;
 pub mod config;
+pub mod config_reload;
+pub mod config_resolution;
+pub mod connection_pause;
 pub mod connectors;
 pub mod crypto;
 pub mod cursor;
 pub mod db;
+pub mod egress;
 pub mod error;
+pub mod fault_injection;
 pub mod handlers;
+pub mod key_rotation;
 pub mod mail;
 pub mod models;
 pub mod normalization;
+pub mod query_stats;
+pub mod refresh_lock;
 pub mod repositories;
+pub mod request_snapshot;
+pub mod rollups;
 pub mod scheduler;
 pub mod seeds;
 pub mod server;
 pub mod signals;
+pub mod simulation;
+pub mod slo;
+pub mod supervisor;
 pub mod sync_executor;
 pub mod telemetry;
+#[cfg(test)]
+pub(crate) mod test_fixtures;
+pub mod testing;
 pub mod token_refresh;
+pub mod token_vault;
+pub mod webhook_backpressure;
+pub mod webhook_ip_allowlist;
+pub mod webhook_signing;
+pub mod webhook_triggers;
 pub mod webhook_verification;
 pub use migration;
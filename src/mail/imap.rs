@@ -0,0 +1,395 @@
+//! Minimal IMAP client
+//!
+//! Implements the subset of IMAP4rev1 (RFC 3501) the IMAP connector needs:
+//! `LOGIN` or `AUTHENTICATE XOAUTH2`, read-only `EXAMINE`, `UID SEARCH`,
+//! `UID FETCH` of flags and a size-capped message body, and `LOGOUT`.
+//! Connections use implicit TLS unless the connection settings turn it off,
+//! which is meant for local bridges listening on loopback. The egress
+//! allowlist applies to the IMAP host like it does to HTTP calls.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use base64::{Engine as _, engine::general_purpose};
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, BufReader};
+use tokio::net::TcpStream;
+use tokio_rustls::TlsConnector;
+use tokio_rustls::rustls::{ClientConfig, RootCertStore, pki_types::ServerName};
+
+/// Largest literal accepted from the server, in bytes
+const MAX_LITERAL_BYTES: usize = 16 * 1024 * 1024;
+
+/// Longest response line accepted outside literals, in bytes
+const MAX_LINE_BYTES: usize = 1024 * 1024;
+
+/// Reason an IMAP operation failed
+#[derive(Debug, thiserror::Error)]
+pub enum ImapError {
+    #[error("IMAP connection failed: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("IMAP host '{0}' is not on the egress allowlist (POBLYSH_EGRESS_ALLOWLIST)")]
+    EgressDenied(String),
+    #[error("IMAP authentication failed: {0}")]
+    Auth(String),
+    #[error("IMAP command failed: {0}")]
+    Command(String),
+    #[error("IMAP protocol error: {0}")]
+    Protocol(String),
+    #[error("IMAP server did not answer within {0:?}")]
+    Timeout(Duration),
+}
+
+/// Credential used to log in
+#[derive(Debug, Clone, Copy)]
+pub enum ImapAuth<'a> {
+    /// `LOGIN` with a password or app password
+    Password(&'a str),
+    /// `AUTHENTICATE XOAUTH2` with an OAuth access token
+    XOAuth2(&'a str),
+}
+
+/// State of a mailbox opened with `EXAMINE`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MailboxStatus {
+    /// Changes when UIDs are reassigned; cached UIDs are then meaningless
+    pub uid_validity: u32,
+    /// UID the next delivered message will get, when the server reports it
+    pub uid_next: Option<u32>,
+    /// Number of messages in the mailbox
+    pub exists: u32,
+}
+
+/// Message returned by `UID FETCH`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FetchedMessage {
+    pub uid: u32,
+    /// Flags such as `\Seen` or `$Junk`
+    pub flags: Vec<String>,
+    /// Raw RFC 5322 message, cut at the requested size
+    pub body: Vec<u8>,
+}
+
+/// Untagged response with the literals it carried
+#[derive(Debug, Default)]
+struct Response {
+    /// Response text with each literal replaced by `{}`
+    text: String,
+    literals: Vec<Vec<u8>>,
+}
+
+trait Stream: AsyncRead + AsyncWrite + Unpin + Send {}
+impl<T: AsyncRead + AsyncWrite + Unpin + Send> Stream for T {}
+
+/// Authenticated or not-yet-authenticated IMAP session
+pub struct ImapSession {
+    stream: BufReader<Box<dyn Stream>>,
+    next_tag: u32,
+    timeout: Duration,
+}
+
+impl ImapSession {
+    /// Connect to `host:port` and read the server greeting
+    pub async fn connect(
+        host: &str,
+        port: u16,
+        tls: bool,
+        timeout: Duration,
+    ) -> Result<Self, ImapError> {
+        if !crate::egress::current().allows(host) {
+            return Err(ImapError::EgressDenied(host.to_string()));
+        }
+        let tcp = with_timeout(timeout, TcpStream::connect((host, port))).await??;
+        let stream: Box<dyn Stream> = if tls {
+            let mut roots = RootCertStore::empty();
+            roots.extend(webpki_roots::TLS_SERVER_ROOTS.iter().cloned());
+            let config = ClientConfig::builder_with_provider(Arc::new(
+                tokio_rustls::rustls::crypto::ring::default_provider(),
+            ))
+            .with_safe_default_protocol_versions()
+            .map_err(|e| ImapError::Protocol(e.to_string()))?
+            .with_root_certificates(roots)
+            .with_no_client_auth();
+            let server_name = ServerName::try_from(host.to_string())
+                .map_err(|e| ImapError::Protocol(format!("invalid host '{}': {}", host, e)))?;
+            let tls_stream = with_timeout(
+                timeout,
+                TlsConnector::from(Arc::new(config)).connect(server_name, tcp),
+            )
+            .await??;
+            Box::new(tls_stream)
+        } else {
+            Box::new(tcp)
+        };
+
+        let mut session = Self {
+            stream: BufReader::new(stream),
+            next_tag: 1,
+            timeout,
+        };
+        let greeting = with_timeout(timeout, session.read_response()).await??;
+        if !greeting.text.starts_with("* OK") && !greeting.text.starts_with("* PREAUTH") {
+            return Err(ImapError::Protocol(format!(
+                "unexpected greeting: {}",
+                greeting.text
+            )));
+        }
+        Ok(session)
+    }
+
+    /// Log in as `username`
+    pub async fn login(&mut self, username: &str, auth: ImapAuth<'_>) -> Result<(), ImapError> {
+        let command = match auth {
+            ImapAuth::Password(password) => {
+                format!("LOGIN {} {}", quote(username)?, quote(password)?)
+            }
+            ImapAuth::XOAuth2(token) => {
+                let initial = format!("user={}\x01auth=Bearer {}\x01\x01", username, token);
+                format!(
+                    "AUTHENTICATE XOAUTH2 {}",
+                    general_purpose::STANDARD.encode(initial)
+                )
+            }
+        };
+        self.command(&command).await.map_err(|e| match e {
+            ImapError::Command(message) => ImapError::Auth(message),
+            other => other,
+        })?;
+        Ok(())
+    }
+
+    /// Open `mailbox` read-only
+    pub async fn examine(&mut self, mailbox: &str) -> Result<MailboxStatus, ImapError> {
+        let responses = self
+            .command(&format!("EXAMINE {}", quote(mailbox)?))
+            .await?;
+        let mut uid_validity = None;
+        let mut uid_next = None;
+        let mut exists = 0;
+        for response in &responses {
+            let text = response.text.as_str();
+            if let Some(value) = bracketed_number(text, "UIDVALIDITY") {
+                uid_validity = Some(value);
+            } else if let Some(value) = bracketed_number(text, "UIDNEXT") {
+                uid_next = Some(value);
+            } else if let Some(count) = text
+                .strip_prefix("* ")
+                .and_then(|rest| rest.strip_suffix(" EXISTS"))
+            {
+                exists = count.parse().unwrap_or(0);
+            }
+        }
+        Ok(MailboxStatus {
+            uid_validity: uid_validity
+                .ok_or_else(|| ImapError::Protocol("server sent no UIDVALIDITY".to_string()))?,
+            uid_next,
+            exists,
+        })
+    }
+
+    /// UIDs matching `criteria`, e.g. `UID 42:*` or `SINCE 1-May-2024`, ascending
+    pub async fn uid_search(&mut self, criteria: &str) -> Result<Vec<u32>, ImapError> {
+        let responses = self.command(&format!("UID SEARCH {}", criteria)).await?;
+        let mut uids: Vec<u32> = responses
+            .iter()
+            .filter_map(|r| r.text.strip_prefix("* SEARCH"))
+            .flat_map(|rest| rest.split_whitespace())
+            .filter_map(|uid| uid.parse().ok())
+            .collect();
+        uids.sort_unstable();
+        uids.dedup();
+        Ok(uids)
+    }
+
+    /// Flags and the first `max_bytes` of each message in `uids`
+    pub async fn uid_fetch(
+        &mut self,
+        uids: &[u32],
+        max_bytes: usize,
+    ) -> Result<Vec<FetchedMessage>, ImapError> {
+        if uids.is_empty() {
+            return Ok(vec![]);
+        }
+        let set = uids
+            .iter()
+            .map(u32::to_string)
+            .collect::<Vec<_>>()
+            .join(",");
+        let responses = self
+            .command(&format!(
+                "UID FETCH {} (UID FLAGS BODY.PEEK[]<0.{}>)",
+                set, max_bytes
+            ))
+            .await?;
+
+        let mut messages: Vec<FetchedMessage> = responses
+            .into_iter()
+            .filter(|r| r.text.starts_with("* ") && r.text.contains(" FETCH ("))
+            .filter_map(|r| {
+                let uid = number_after(&r.text, "UID ")?;
+                let flags = r
+                    .text
+                    .split_once("FLAGS (")
+                    .and_then(|(_, rest)| rest.split_once(')'))
+                    .map(|(flags, _)| flags.split_whitespace().map(str::to_string).collect())
+                    .unwrap_or_default();
+                Some(FetchedMessage {
+                    uid,
+                    flags,
+                    body: r.literals.into_iter().next().unwrap_or_default(),
+                })
+            })
+            .collect();
+        messages.sort_by_key(|m| m.uid);
+        Ok(messages)
+    }
+
+    /// End the session; errors are ignored since the work is already done
+    pub async fn logout(mut self) {
+        let _ = self.command("LOGOUT").await;
+    }
+
+    /// Send a tagged command and collect untagged responses until its completion
+    async fn command(&mut self, command: &str) -> Result<Vec<Response>, ImapError> {
+        let tag = format!("A{:04}", self.next_tag);
+        self.next_tag += 1;
+        let timeout = self.timeout;
+        with_timeout(timeout, async {
+            self.stream
+                .get_mut()
+                .write_all(format!("{} {}\r\n", tag, command).as_bytes())
+                .await?;
+            self.stream.get_mut().flush().await?;
+
+            let mut responses = Vec::new();
+            loop {
+                let response = self.read_response().await?;
+                if let Some(status) = response.text.strip_prefix(&format!("{} ", tag)) {
+                    if status.starts_with("OK") {
+                        return Ok(responses);
+                    }
+                    return Err(ImapError::Command(status.to_string()));
+                }
+                if response.text.starts_with('+') {
+                    // A failed AUTHENTICATE sends its error as a challenge; cancel it
+                    self.stream.get_mut().write_all(b"\r\n").await?;
+                    self.stream.get_mut().flush().await?;
+                    continue;
+                }
+                if response.text.starts_with("* BYE") && command != "LOGOUT" {
+                    return Err(ImapError::Protocol(response.text));
+                }
+                responses.push(response);
+            }
+        })
+        .await?
+    }
+
+    /// Read one response line, following any `{n}` literals it announces
+    async fn read_response(&mut self) -> Result<Response, ImapError> {
+        let mut response = Response::default();
+        loop {
+            let mut line = Vec::new();
+            let read = (&mut self.stream)
+                .take(MAX_LINE_BYTES as u64)
+                .read_until(b'\n', &mut line)
+                .await?;
+            if read == 0 {
+                return Err(ImapError::Protocol(
+                    "server closed the connection".to_string(),
+                ));
+            }
+            if !line.ends_with(b"\n") {
+                return Err(ImapError::Protocol("response line too long".to_string()));
+            }
+            let line = String::from_utf8_lossy(&line);
+            let line = line.trim_end_matches(['\r', '\n']);
+
+            match literal_length(line) {
+                Some(length) => {
+                    if length > MAX_LITERAL_BYTES {
+                        return Err(ImapError::Protocol(format!(
+                            "literal of {} bytes exceeds the limit",
+                            length
+                        )));
+                    }
+                    let mut literal = vec![0; length];
+                    self.stream.read_exact(&mut literal).await?;
+                    let prefix = &line[..line.rfind('{').unwrap_or(line.len())];
+                    response.text.push_str(prefix);
+                    response.text.push_str("{}");
+                    response.literals.push(literal);
+                }
+                None => {
+                    response.text.push_str(line);
+                    return Ok(response);
+                }
+            }
+        }
+    }
+}
+
+async fn with_timeout<T>(
+    timeout: Duration,
+    future: impl std::future::Future<Output = T>,
+) -> Result<T, ImapError> {
+    tokio::time::timeout(timeout, future)
+        .await
+        .map_err(|_| ImapError::Timeout(timeout))
+}
+
+/// Quoted string for a command argument
+fn quote(value: &str) -> Result<String, ImapError> {
+    if value.contains(['\r', '\n', '\0']) {
+        return Err(ImapError::Protocol(
+            "arguments must not contain line breaks".to_string(),
+        ));
+    }
+    Ok(format!(
+        "\"{}\"",
+        value.replace('\\', "\\\\").replace('"', "\\\"")
+    ))
+}
+
+/// Length of a literal announced at the end of a line, e.g. `{123}`
+fn literal_length(line: &str) -> Option<usize> {
+    let open = line.rfind('{')?;
+    line[open + 1..].strip_suffix('}')?.parse().ok()
+}
+
+/// Number in a response code such as `[UIDVALIDITY 3857529045]`
+fn bracketed_number(text: &str, code: &str) -> Option<u32> {
+    let (_, rest) = text.split_once(&format!("[{} ", code))?;
+    rest.split(']').next()?.trim().parse().ok()
+}
+
+fn number_after(text: &str, marker: &str) -> Option<u32> {
+    let (_, rest) = text.split_once(marker)?;
+    rest.split(|c: char| !c.is_ascii_digit())
+        .next()?
+        .parse()
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_response_parsing_helpers() {
+        assert_eq!(
+            literal_length("* 1 FETCH (UID 7 BODY[]<0> {342}"),
+            Some(342)
+        );
+        assert_eq!(literal_length("* OK [UIDVALIDITY 1] ok"), None);
+        assert_eq!(
+            bracketed_number("* OK [UIDVALIDITY 3857529045] UIDs valid", "UIDVALIDITY"),
+            Some(3857529045)
+        );
+        assert_eq!(
+            number_after("* 3 FETCH (FLAGS (\\Seen) UID 42 {}", "UID "),
+            Some(42)
+        );
+        assert_eq!(quote("pa\"ss\\").unwrap(), "\"pa\\\"ss\\\\\"");
+        assert!(quote("x\r\nA1 LOGOUT").is_err());
+    }
+}
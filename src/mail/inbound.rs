@@ -0,0 +1,804 @@
+//! Inbound email parsing
+//!
+//! Turns mail forwarded by an SMTP bridge into an [`InboundEmail`]. Three
+//! shapes are accepted:
+//!
+//! - a raw RFC 5322 message (`message/rfc822`);
+//! - an Amazon SES receipt notification, optionally wrapped in an SNS envelope;
+//! - SendGrid Inbound Parse fields posted as JSON.
+//!
+//! SES and SendGrid can embed the raw message, which is then parsed in full.
+//! Their own spam verdicts are kept as a `spam` label so the shared spam filter
+//! treats them like a provider label.
+
+use std::collections::HashMap;
+
+use base64::{Engine as _, engine::general_purpose};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+use crate::mail::{MailMetadata, MailProvider};
+
+/// Provider slug used for inbound email connections and signals
+pub const INBOUND_EMAIL_PROVIDER_SLUG: &str = "inbound-email";
+
+/// Longest plain-text body kept, in bytes
+const MAX_TEXT_BYTES: usize = 64 * 1024;
+
+/// Deepest multipart nesting that is walked
+const MAX_MIME_DEPTH: usize = 8;
+
+/// SpamAssassin score at which SendGrid mail is labelled spam
+const SENDGRID_SPAM_SCORE: f64 = 5.0;
+
+/// A received message in the form the inbound email connector consumes
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct InboundEmail {
+    /// `Message-ID` header without angle brackets
+    pub message_id: Option<String>,
+    /// Sender address
+    pub from: Option<String>,
+    /// Recipient addresses
+    pub to: Vec<String>,
+    /// Carbon-copied addresses
+    pub cc: Vec<String>,
+    /// Decoded subject line
+    pub subject: Option<String>,
+    /// `Date` header
+    pub date: Option<DateTime<Utc>>,
+    /// Plain-text body, derived from HTML when there is no text part
+    pub text: Option<String>,
+    /// Top-level headers keyed by lower-case name (first occurrence wins)
+    pub headers: HashMap<String, String>,
+    /// File names of attached parts
+    pub attachment_names: Vec<String>,
+    /// Labels set by the relaying service, e.g. `spam`
+    pub labels: Vec<String>,
+    /// Format the message arrived in: `mime`, `ses` or `sendgrid`
+    pub source: String,
+}
+
+/// Reason an inbound message could not be parsed
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum InboundEmailError {
+    #[error("Unrecognized inbound email payload: {0}")]
+    Unrecognized(String),
+    #[error("Malformed inbound email: {0}")]
+    Malformed(String),
+}
+
+impl InboundEmail {
+    /// Identifier that stays the same when the same message is delivered twice
+    pub fn stable_id(&self) -> String {
+        if let Some(message_id) = self.message_id.as_deref().filter(|id| !id.is_empty()) {
+            return message_id.to_string();
+        }
+        let mut hasher = Sha256::new();
+        for part in [
+            self.from.as_deref().unwrap_or_default(),
+            self.subject.as_deref().unwrap_or_default(),
+            &self.date.map(|d| d.to_rfc3339()).unwrap_or_default(),
+            self.text.as_deref().unwrap_or_default(),
+        ] {
+            hasher.update(part.as_bytes());
+            hasher.update([0]);
+        }
+        format!("sha256:{}", hex::encode(hasher.finalize()))
+    }
+
+    /// Metadata for the shared spam filter
+    pub fn spam_metadata(&self) -> MailMetadata {
+        MailMetadata {
+            provider: MailProvider::from_slug(INBOUND_EMAIL_PROVIDER_SLUG),
+            labels: self.labels.clone(),
+            subject: self.subject.clone(),
+            headers: self.headers.clone(),
+            from: self.from.clone(),
+            to: self.to.clone(),
+            has_attachments: !self.attachment_names.is_empty(),
+            attachment_extensions: self
+                .attachment_names
+                .iter()
+                .filter_map(|name| name.rsplit_once('.'))
+                .map(|(_, ext)| ext.to_ascii_lowercase())
+                .collect(),
+        }
+    }
+}
+
+/// Parse a raw RFC 5322 message
+pub fn parse_mime(raw: &[u8]) -> Result<InboundEmail, InboundEmailError> {
+    let (header_block, body) = split_message(raw);
+    let headers = parse_headers(header_block);
+    if headers.is_empty() {
+        return Err(InboundEmailError::Malformed(
+            "message has no headers".to_string(),
+        ));
+    }
+
+    let mut content = MimeContent::default();
+    walk_part(&headers, body, 0, &mut content);
+
+    let header_map = first_headers(&headers);
+    let text = content
+        .text
+        .or_else(|| content.html.as_deref().map(html_to_text))
+        .map(|text| truncate(text.trim().to_string()));
+
+    Ok(InboundEmail {
+        message_id: header_map.get("message-id").map(|id| strip_angles(id)),
+        from: header_map
+            .get("from")
+            .and_then(|from| parse_addresses(from).into_iter().next()),
+        to: header_map
+            .get("to")
+            .map(|to| parse_addresses(to))
+            .unwrap_or_default(),
+        cc: header_map
+            .get("cc")
+            .map(|cc| parse_addresses(cc))
+            .unwrap_or_default(),
+        subject: header_map.get("subject").map(|s| decode_encoded_words(s)),
+        date: header_map.get("date").and_then(|d| parse_date(d)),
+        text,
+        headers: header_map,
+        attachment_names: content.attachments,
+        labels: Vec::new(),
+        source: "mime".to_string(),
+    })
+}
+
+/// Parse an SES notification or SendGrid Inbound Parse fields
+pub fn parse_json(value: &Value) -> Result<InboundEmail, InboundEmailError> {
+    // SNS delivers the SES notification as a JSON string in `Message`
+    if value.get("Type").and_then(Value::as_str) == Some("Notification")
+        && let Some(message) = value.get("Message").and_then(Value::as_str)
+    {
+        let inner: Value = serde_json::from_str(message)
+            .map_err(|e| InboundEmailError::Malformed(format!("SNS Message is not JSON: {}", e)))?;
+        return parse_json(&inner);
+    }
+
+    if value.get("mail").is_some() && value.get("receipt").is_some() {
+        return parse_ses(value);
+    }
+    if value.get("from").is_some() || value.get("email").is_some() {
+        return parse_sendgrid(value);
+    }
+
+    Err(InboundEmailError::Unrecognized(
+        "expected an SES notification or SendGrid Inbound Parse fields".to_string(),
+    ))
+}
+
+fn parse_ses(value: &Value) -> Result<InboundEmail, InboundEmailError> {
+    let mut email = match value.get("content").and_then(Value::as_str) {
+        Some(content) => parse_mime(content.as_bytes())?,
+        None => {
+            // S3 or Lambda actions omit the content; fall back to the parsed headers
+            let mail = &value["mail"];
+            let common = &mail["commonHeaders"];
+            let headers: HashMap<String, String> = mail
+                .get("headers")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+                .filter_map(|header| {
+                    Some((
+                        header.get("name")?.as_str()?.to_ascii_lowercase(),
+                        header.get("value")?.as_str()?.to_string(),
+                    ))
+                })
+                .fold(HashMap::new(), |mut map, (name, value)| {
+                    map.entry(name).or_insert(value);
+                    map
+                });
+            InboundEmail {
+                message_id: common
+                    .get("messageId")
+                    .or_else(|| mail.get("messageId"))
+                    .and_then(Value::as_str)
+                    .map(strip_angles),
+                from: address_field(common.get("from")).into_iter().next(),
+                to: address_field(common.get("to")),
+                cc: address_field(common.get("cc")),
+                subject: common
+                    .get("subject")
+                    .and_then(Value::as_str)
+                    .map(str::to_string),
+                date: common
+                    .get("date")
+                    .and_then(Value::as_str)
+                    .and_then(parse_date)
+                    .or_else(|| {
+                        mail.get("timestamp")
+                            .and_then(Value::as_str)
+                            .and_then(|ts| DateTime::parse_from_rfc3339(ts).ok())
+                            .map(|dt| dt.with_timezone(&Utc))
+                    }),
+                headers,
+                ..Default::default()
+            }
+        }
+    };
+
+    let receipt = &value["receipt"];
+    let failed = |verdict: &str| {
+        receipt
+            .get(verdict)
+            .and_then(|v| v.get("status"))
+            .and_then(Value::as_str)
+            == Some("FAIL")
+    };
+    if failed("spamVerdict") || failed("virusVerdict") {
+        email.labels.push("spam".to_string());
+    }
+    email.source = "ses".to_string();
+    Ok(email)
+}
+
+fn parse_sendgrid(value: &Value) -> Result<InboundEmail, InboundEmailError> {
+    let field = |name: &str| value.get(name).and_then(Value::as_str);
+
+    let mut email = match field("email") {
+        // "POST the raw, full MIME message" is enabled
+        Some(raw) => parse_mime(raw.as_bytes())?,
+        None => {
+            let headers = first_headers(&parse_headers(
+                field("headers").unwrap_or_default().as_bytes(),
+            ));
+            let text = field("text")
+                .map(str::to_string)
+                .or_else(|| field("html").map(html_to_text))
+                .map(|text| truncate(text.trim().to_string()));
+            let attachment_names = field("attachment-info")
+                .and_then(|info| serde_json::from_str::<Value>(info).ok())
+                .and_then(|info| info.as_object().cloned())
+                .into_iter()
+                .flatten()
+                .map(|(key, attachment)| {
+                    attachment
+                        .get("filename")
+                        .and_then(Value::as_str)
+                        .unwrap_or(&key)
+                        .to_string()
+                })
+                .collect();
+            InboundEmail {
+                message_id: headers.get("message-id").map(|id| strip_angles(id)),
+                from: field("from").and_then(|from| parse_addresses(from).into_iter().next()),
+                to: address_field(value.get("to")),
+                cc: address_field(value.get("cc")),
+                subject: field("subject").map(str::to_string),
+                date: headers.get("date").and_then(|d| parse_date(d)),
+                text,
+                headers,
+                attachment_names,
+                ..Default::default()
+            }
+        }
+    };
+
+    if email.from.is_none() {
+        return Err(InboundEmailError::Malformed(
+            "message has no sender".to_string(),
+        ));
+    }
+    let spam_score = value.get("spam_score").and_then(|score| match score {
+        Value::Number(n) => n.as_f64(),
+        Value::String(s) => s.trim().parse().ok(),
+        _ => None,
+    });
+    if spam_score.is_some_and(|score| score >= SENDGRID_SPAM_SCORE) {
+        email.labels.push("spam".to_string());
+    }
+    email.source = "sendgrid".to_string();
+    Ok(email)
+}
+
+/// Body content gathered while walking MIME parts
+#[derive(Default)]
+struct MimeContent {
+    text: Option<String>,
+    html: Option<String>,
+    attachments: Vec<String>,
+}
+
+fn walk_part(headers: &[(String, String)], body: &[u8], depth: usize, content: &mut MimeContent) {
+    let (mime_type, type_params) = header_value(headers, "content-type")
+        .map(parse_header_params)
+        .unwrap_or_else(|| ("text/plain".to_string(), HashMap::new()));
+    let (disposition, disposition_params) = header_value(headers, "content-disposition")
+        .map(parse_header_params)
+        .unwrap_or_default();
+    let filename = disposition_params
+        .get("filename")
+        .or_else(|| type_params.get("name"))
+        .map(|name| decode_encoded_words(name));
+
+    if let Some(subtype) = mime_type.strip_prefix("multipart/") {
+        let Some(boundary) = type_params.get("boundary") else {
+            return;
+        };
+        if depth >= MAX_MIME_DEPTH {
+            return;
+        }
+        for part in split_multipart(body, boundary) {
+            let (part_headers, part_body) = split_message(part);
+            walk_part(&parse_headers(part_headers), part_body, depth + 1, content);
+            // Alternatives carry the same body; the first readable one is enough
+            if subtype == "alternative" && content.text.is_some() {
+                break;
+            }
+        }
+        return;
+    }
+
+    if disposition == "attachment" || filename.is_some() || mime_type == "message/rfc822" {
+        content
+            .attachments
+            .push(filename.unwrap_or_else(|| "attachment".to_string()));
+        return;
+    }
+
+    let decoded =
+        decode_transfer_encoding(body, header_value(headers, "content-transfer-encoding"));
+    let charset = type_params.get("charset").map(String::as_str);
+    match mime_type.as_str() {
+        "text/plain" if content.text.is_none() => {
+            content.text = Some(decode_charset(&decoded, charset));
+        }
+        "text/html" if content.html.is_none() => {
+            content.html = Some(decode_charset(&decoded, charset));
+        }
+        _ => {}
+    }
+}
+
+/// Split a message or part at the blank line ending its headers
+fn split_message(raw: &[u8]) -> (&[u8], &[u8]) {
+    let crlf = find(raw, b"\r\n\r\n").map(|i| (i, i + 4));
+    let lf = find(raw, b"\n\n").map(|i| (i, i + 2));
+    let split = match (crlf, lf) {
+        (Some(a), Some(b)) => Some(if a.0 <= b.0 { a } else { b }),
+        (a, b) => a.or(b),
+    };
+    match split {
+        Some((end, body_start)) => (&raw[..end], &raw[body_start..]),
+        None => (raw, &[]),
+    }
+}
+
+/// Parse a header block, unfolding continuation lines
+fn parse_headers(block: &[u8]) -> Vec<(String, String)> {
+    let text = String::from_utf8_lossy(block);
+    let mut headers: Vec<(String, String)> = Vec::new();
+    for line in text.split('\n') {
+        let line = line.trim_end_matches('\r');
+        if line.starts_with([' ', '\t']) {
+            if let Some((_, value)) = headers.last_mut() {
+                value.push(' ');
+                value.push_str(line.trim());
+            }
+            continue;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            let name = name.trim();
+            if !name.is_empty() && !name.contains(' ') {
+                headers.push((name.to_ascii_lowercase(), value.trim().to_string()));
+            }
+        }
+    }
+    headers
+}
+
+fn first_headers(headers: &[(String, String)]) -> HashMap<String, String> {
+    let mut map = HashMap::new();
+    for (name, value) in headers {
+        map.entry(name.clone()).or_insert_with(|| value.clone());
+    }
+    map
+}
+
+fn header_value<'a>(headers: &'a [(String, String)], name: &str) -> Option<&'a str> {
+    headers
+        .iter()
+        .find(|(header, _)| header == name)
+        .map(|(_, value)| value.as_str())
+}
+
+/// Split `type/subtype; key=value` into the lower-cased value and its parameters
+fn parse_header_params(value: &str) -> (String, HashMap<String, String>) {
+    let mut parts = value.split(';');
+    let main = parts.next().unwrap_or_default().trim().to_ascii_lowercase();
+    let params = parts
+        .filter_map(|param| {
+            let (key, value) = param.split_once('=')?;
+            Some((
+                key.trim().to_ascii_lowercase(),
+                value.trim().trim_matches('"').to_string(),
+            ))
+        })
+        .collect();
+    (main, params)
+}
+
+/// Bodies of the parts between `--boundary` delimiter lines
+fn split_multipart<'a>(body: &'a [u8], boundary: &str) -> Vec<&'a [u8]> {
+    let delimiter = format!("--{}", boundary);
+    let mut parts = Vec::new();
+    let mut part_start: Option<usize> = None;
+    let mut pos = 0;
+
+    for line in body.split_inclusive(|b| *b == b'\n') {
+        let trimmed = trim_line_end(line);
+        if let Some(rest) = trimmed.strip_prefix(delimiter.as_bytes()) {
+            if let Some(start) = part_start {
+                parts.push(trim_line_end(&body[start..pos]));
+            }
+            if rest.starts_with(b"--") {
+                return parts;
+            }
+            part_start = Some(pos + line.len());
+        }
+        pos += line.len();
+    }
+
+    // Tolerate a missing closing delimiter
+    if let Some(start) = part_start {
+        parts.push(&body[start..]);
+    }
+    parts
+}
+
+fn trim_line_end(line: &[u8]) -> &[u8] {
+    let line = line.strip_suffix(b"\n").unwrap_or(line);
+    line.strip_suffix(b"\r").unwrap_or(line)
+}
+
+fn decode_transfer_encoding(body: &[u8], encoding: Option<&str>) -> Vec<u8> {
+    match encoding.map(|e| e.trim().to_ascii_lowercase()).as_deref() {
+        Some("base64") => {
+            let compact: Vec<u8> = body
+                .iter()
+                .copied()
+                .filter(|b| !b.is_ascii_whitespace())
+                .collect();
+            general_purpose::STANDARD
+                .decode(&compact)
+                .unwrap_or_else(|_| body.to_vec())
+        }
+        Some("quoted-printable") => decode_quoted_printable(body, false),
+        _ => body.to_vec(),
+    }
+}
+
+/// Decode quoted-printable; `underscore_is_space` applies to RFC 2047 `Q` words
+fn decode_quoted_printable(input: &[u8], underscore_is_space: bool) -> Vec<u8> {
+    let mut out = Vec::with_capacity(input.len());
+    let mut i = 0;
+    while i < input.len() {
+        match input[i] {
+            b'=' => {
+                let rest = &input[i + 1..];
+                if rest.starts_with(b"\r\n") {
+                    i += 3;
+                } else if rest.starts_with(b"\n") {
+                    i += 2;
+                } else if let Some(byte) = rest
+                    .get(..2)
+                    .and_then(|hex| std::str::from_utf8(hex).ok())
+                    .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+                {
+                    out.push(byte);
+                    i += 3;
+                } else {
+                    out.push(b'=');
+                    i += 1;
+                }
+            }
+            b'_' if underscore_is_space => {
+                out.push(b' ');
+                i += 1;
+            }
+            byte => {
+                out.push(byte);
+                i += 1;
+            }
+        }
+    }
+    out
+}
+
+fn decode_charset(bytes: &[u8], charset: Option<&str>) -> String {
+    match charset.map(|c| c.trim().to_ascii_lowercase()).as_deref() {
+        Some("iso-8859-1" | "latin1" | "windows-1252" | "cp1252") => {
+            bytes.iter().map(|b| *b as char).collect()
+        }
+        _ => String::from_utf8_lossy(bytes).into_owned(),
+    }
+}
+
+/// Decode RFC 2047 encoded words such as `=?UTF-8?B?SGk=?=`
+fn decode_encoded_words(value: &str) -> String {
+    let mut out = String::new();
+    let mut rest = value;
+    let mut previous_was_word = false;
+
+    while let Some(start) = rest.find("=?") {
+        let (before, candidate) = rest.split_at(start);
+        let decoded = candidate[2..].split_once('?').and_then(|(charset, tail)| {
+            let (encoding, tail) = tail.split_once('?')?;
+            let end = tail.find("?=")?;
+            let text = &tail[..end];
+            let bytes = match encoding.to_ascii_uppercase().as_str() {
+                "B" => general_purpose::STANDARD.decode(text).ok()?,
+                "Q" => decode_quoted_printable(text.as_bytes(), true),
+                _ => return None,
+            };
+            let consumed = 2 + charset.len() + 1 + encoding.len() + 1 + end + 2;
+            Some((decode_charset(&bytes, Some(charset)), consumed))
+        });
+
+        match decoded {
+            Some((word, consumed)) => {
+                // Whitespace between adjacent encoded words is not part of the text
+                if !(previous_was_word && before.trim().is_empty()) {
+                    out.push_str(before);
+                }
+                out.push_str(&word);
+                rest = &candidate[consumed..];
+                previous_was_word = true;
+            }
+            None => {
+                out.push_str(before);
+                out.push_str("=?");
+                rest = &candidate[2..];
+                previous_was_word = false;
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Addresses from an address-list header, ignoring display names
+fn parse_addresses(value: &str) -> Vec<String> {
+    let mut entries = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+    let mut in_angles = false;
+    for c in value.chars() {
+        match c {
+            '"' => in_quotes = !in_quotes,
+            '<' if !in_quotes => in_angles = true,
+            '>' if !in_quotes => in_angles = false,
+            ',' if !in_quotes && !in_angles => {
+                entries.push(std::mem::take(&mut current));
+                continue;
+            }
+            _ => {}
+        }
+        current.push(c);
+    }
+    entries.push(current);
+
+    entries
+        .iter()
+        .filter_map(|entry| {
+            let address = match (entry.rfind('<'), entry.rfind('>')) {
+                (Some(start), Some(end)) if start < end => &entry[start + 1..end],
+                _ => entry.as_str(),
+            };
+            let address = address.trim();
+            address.contains('@').then(|| address.to_string())
+        })
+        .collect()
+}
+
+/// Addresses from a JSON field holding a list string or an array (SES `commonHeaders`)
+fn address_field(value: Option<&Value>) -> Vec<String> {
+    match value {
+        Some(Value::String(list)) => parse_addresses(list),
+        Some(Value::Array(items)) => items
+            .iter()
+            .filter_map(Value::as_str)
+            .flat_map(parse_addresses)
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn parse_date(value: &str) -> Option<DateTime<Utc>> {
+    // Drop a trailing comment such as "(UTC)"
+    let value = match value.find('(') {
+        Some(idx) => &value[..idx],
+        None => value,
+    };
+    DateTime::parse_from_rfc2822(value.trim())
+        .ok()
+        .map(|dt| dt.with_timezone(&Utc))
+}
+
+fn strip_angles(value: &str) -> String {
+    value
+        .trim()
+        .trim_start_matches('<')
+        .trim_end_matches('>')
+        .to_string()
+}
+
+/// Rough plain-text rendering of an HTML body
+fn html_to_text(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => {
+                in_tag = true;
+                text.push(' ');
+            }
+            '>' => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    let text = text
+        .replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&");
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn truncate(mut text: String) -> String {
+    if text.len() > MAX_TEXT_BYTES {
+        let mut end = MAX_TEXT_BYTES;
+        while !text.is_char_boundary(end) {
+            end -= 1;
+        }
+        text.truncate(end);
+    }
+    text
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const MULTIPART: &str = "Received: from mx.example.com by inbound.example.net\r\n\
+From: \"Ada Lovelace\" <ada@example.com>\r\n\
+To: team@acme.test, \"Ops, On Call\" <ops@acme.test>\r\n\
+Subject: =?UTF-8?B?UXVhcnRlcmx5?= =?UTF-8?Q?_r=C3=A9view?=\r\n\
+Date: Tue, 1 Jul 2025 10:00:00 +0000 (UTC)\r\n\
+Message-ID: <q3-review@example.com>\r\n\
+Content-Type: multipart/mixed; boundary=\"outer\"\r\n\
+\r\n\
+--outer\r\n\
+Content-Type: multipart/alternative; boundary=inner\r\n\
+\r\n\
+--inner\r\n\
+Content-Type: text/plain; charset=utf-8\r\n\
+Content-Transfer-Encoding: quoted-printable\r\n\
+\r\n\
+Numbers are up =E2=80=94 see the deck.=\r\n\
+\r\n\
+--inner\r\n\
+Content-Type: text/html\r\n\
+\r\n\
+<p>Numbers are up</p>\r\n\
+--inner--\r\n\
+--outer\r\n\
+Content-Type: application/pdf; name=\"deck.pdf\"\r\n\
+Content-Disposition: attachment; filename=\"deck.pdf\"\r\n\
+Content-Transfer-Encoding: base64\r\n\
+\r\n\
+JVBERi0xLjQK\r\n\
+--outer--\r\n";
+
+    #[test]
+    fn test_parse_mime_multipart_message() {
+        let email = parse_mime(MULTIPART.as_bytes()).unwrap();
+
+        assert_eq!(email.message_id.as_deref(), Some("q3-review@example.com"));
+        assert_eq!(email.from.as_deref(), Some("ada@example.com"));
+        assert_eq!(email.to, vec!["team@acme.test", "ops@acme.test"]);
+        assert_eq!(email.subject.as_deref(), Some("Quarterly réview"));
+        assert_eq!(
+            email.date.map(|d| d.to_rfc3339()).as_deref(),
+            Some("2025-07-01T10:00:00+00:00")
+        );
+        assert_eq!(
+            email.text.as_deref(),
+            Some("Numbers are up — see the deck.")
+        );
+        assert_eq!(email.attachment_names, vec!["deck.pdf"]);
+        assert!(email.headers.contains_key("received"));
+        assert_eq!(email.spam_metadata().attachment_extensions, vec!["pdf"]);
+    }
+
+    #[test]
+    fn test_parse_mime_falls_back_to_html_and_rejects_headerless_input() {
+        let raw = "From: bob@example.com\nContent-Type: text/html\n\n<div>Hello&nbsp;<b>there</b></div>\n";
+        let email = parse_mime(raw.as_bytes()).unwrap();
+        assert_eq!(email.text.as_deref(), Some("Hello there"));
+        assert!(email.message_id.is_none());
+        assert!(email.stable_id().starts_with("sha256:"));
+
+        assert!(parse_mime(b"").is_err());
+    }
+
+    #[test]
+    fn test_parse_ses_notification_in_sns_envelope() {
+        let notification = json!({
+            "notificationType": "Received",
+            "mail": {"messageId": "ses-1"},
+            "receipt": {"spamVerdict": {"status": "FAIL"}, "virusVerdict": {"status": "PASS"}},
+            "content": MULTIPART,
+        });
+        let envelope = json!({
+            "Type": "Notification",
+            "Message": notification.to_string(),
+        });
+
+        let email = parse_json(&envelope).unwrap();
+        assert_eq!(email.source, "ses");
+        assert_eq!(email.message_id.as_deref(), Some("q3-review@example.com"));
+        assert_eq!(email.labels, vec!["spam"]);
+
+        let headers_only = json!({
+            "mail": {
+                "timestamp": "2025-07-01T10:00:00.000Z",
+                "headers": [{"name": "Received", "value": "from mx"}],
+                "commonHeaders": {
+                    "from": ["Ada <ada@example.com>"],
+                    "to": ["team@acme.test"],
+                    "messageId": "<ses-2@example.com>",
+                    "subject": "Hello"
+                }
+            },
+            "receipt": {"spamVerdict": {"status": "PASS"}}
+        });
+        let email = parse_json(&headers_only).unwrap();
+        assert_eq!(email.from.as_deref(), Some("ada@example.com"));
+        assert_eq!(email.message_id.as_deref(), Some("ses-2@example.com"));
+        assert!(email.date.is_some());
+        assert!(email.labels.is_empty());
+    }
+
+    #[test]
+    fn test_parse_sendgrid_fields() {
+        let fields = json!({
+            "from": "Ada Lovelace <ada@example.com>",
+            "to": "team@acme.test",
+            "subject": "Launch plan",
+            "text": "See attached",
+            "headers": "Message-ID: <launch@example.com>\nDate: Tue, 1 Jul 2025 10:00:00 +0000\n",
+            "spam_score": "6.2",
+            "attachment-info": "{\"attachment1\":{\"filename\":\"plan.xlsx\"}}"
+        });
+
+        let email = parse_json(&fields).unwrap();
+        assert_eq!(email.source, "sendgrid");
+        assert_eq!(email.message_id.as_deref(), Some("launch@example.com"));
+        assert_eq!(email.to, vec!["team@acme.test"]);
+        assert_eq!(email.attachment_names, vec!["plan.xlsx"]);
+        assert_eq!(email.labels, vec!["spam"]);
+
+        assert!(matches!(
+            parse_json(&json!({"hello": "world"})),
+            Err(InboundEmailError::Unrecognized(_))
+        ));
+    }
+}
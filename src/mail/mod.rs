@@ -5,6 +5,8 @@
 //! threads to proceed through the signal pipeline.
 
 pub mod default;
+pub mod imap;
+pub mod inbound;
 
 use std::collections::HashMap;
 
@@ -193,7 +195,13 @@ impl MailSpamRuntimeConfig {
 /// Integration helpers for mail connectors
 pub mod integration {
     use super::*;
-    use std::sync::Arc;
+    use std::sync::{Arc, LazyLock, RwLock};
+
+    static INSTALLED_FILTER: LazyLock<RwLock<Arc<dyn MailSpamFilter>>> = LazyLock::new(|| {
+        RwLock::new(Arc::new(default::DefaultMailSpamFilter::new(
+            MailSpamRuntimeConfig::default(),
+        )))
+    });
 
     /// Create a mail spam filter from application configuration
     pub fn create_spam_filter_from_config(
@@ -206,6 +214,49 @@ pub mod integration {
         }))
     }
 
+    /// Replace the process-wide filter behind every [`installed_spam_filter`]
+    pub fn install_spam_filter(config: &crate::config::MailSpamConfig) {
+        let mut current = INSTALLED_FILTER.write().unwrap_or_else(|e| e.into_inner());
+        *current = create_spam_filter_from_config(config);
+    }
+
+    tokio::task_local! {
+        static SCOPED_FILTER: Arc<dyn MailSpamFilter>;
+    }
+
+    /// Run `future` with spam filtering under `config` in place of the installed
+    /// settings, e.g. a tenant's own threshold and lists during its sync job
+    pub async fn with_spam_settings<F: std::future::Future>(
+        config: &crate::config::MailSpamConfig,
+        future: F,
+    ) -> F::Output {
+        SCOPED_FILTER
+            .scope(create_spam_filter_from_config(config), future)
+            .await
+    }
+
+    /// A filter that evaluates with whichever filter is installed at the time,
+    /// so reloaded spam settings reach connectors that are already built.
+    /// Inside [`with_spam_settings`] the scoped settings apply instead.
+    pub fn installed_spam_filter() -> Arc<dyn MailSpamFilter> {
+        Arc::new(InstalledSpamFilter)
+    }
+
+    struct InstalledSpamFilter;
+
+    impl MailSpamFilter for InstalledSpamFilter {
+        fn evaluate(&self, meta: &MailMetadata) -> MailSpamVerdict {
+            if let Ok(verdict) = SCOPED_FILTER.try_with(|filter| filter.evaluate(meta)) {
+                return verdict;
+            }
+            let filter = INSTALLED_FILTER
+                .read()
+                .unwrap_or_else(|e| e.into_inner())
+                .clone();
+            filter.evaluate(meta)
+        }
+    }
+
     pub struct MailMetadataParams {
         pub message_id: String,
         pub labels: Vec<String>,
@@ -332,4 +383,27 @@ mod tests {
         assert!(config_with_lists.is_denied("user@spam.com"));
         assert!(!config_with_lists.is_denied("user@good.com"));
     }
+
+    #[tokio::test]
+    async fn test_scoped_spam_settings_apply_to_installed_filter() {
+        let filter = integration::installed_spam_filter();
+        let meta = MailMetadata {
+            provider: MailProvider::Gmail,
+            labels: Vec::new(),
+            subject: Some("Quarterly update".to_string()),
+            headers: HashMap::new(),
+            from: Some("news@tenant-blocked.example".to_string()),
+            to: Vec::new(),
+            has_attachments: false,
+            attachment_extensions: Vec::new(),
+        };
+        let tenant = crate::config::MailSpamConfig {
+            denylist: vec!["@tenant-blocked.example".to_string()],
+            ..Default::default()
+        };
+
+        let scoped = integration::with_spam_settings(&tenant, async { filter.evaluate(&meta) });
+        assert!(scoped.await.is_spam);
+        assert!(!filter.evaluate(&meta).is_spam);
+    }
 }
@@ -2,13 +2,27 @@
 //!
 //! This is the main entry point for the Connectors API service.
 
+use std::sync::Arc;
+
 use clap::{Parser, Subcommand};
 use connectors::{
-    config::ConfigLoader, connectors::Registry, db, server::run_server,
-    sync_executor::ExecutorConfig, telemetry,
+    cli::{AdminAction, ConnectionsAction, CryptoAction, JobsAction, SimulateArgs},
+    config::{AppConfig, ConfigLoader},
+    config_reload::{ConfigWatcher, ReloadableConfig},
+    connectors::Registry,
+    crypto::{
+        MasterKeyring, key_provider_from_config, load_master_keyring, load_master_keyring_with,
+    },
+    db,
+    repositories::PriorityAging,
+    supervisor::{RestartPolicy, Subsystem, Supervisor},
+    sync_executor::ExecutorConfig,
+    telemetry,
+    token_refresh::TokenRefreshService,
 };
 use migration::{Migrator, MigratorTrait};
 use sea_orm::DatabaseConnection;
+use tokio_util::sync::CancellationToken;
 
 #[derive(Parser)]
 #[command(name = "connectors")]
@@ -27,8 +41,37 @@ enum Commands {
     },
     /// Run the sync executor service
     SyncExecutor,
-    /// Run both API server and sync executor
+    /// Run the API server, token refresh, scheduler, sync executor and signal
+    /// pipeline in one supervised process
     RunAll,
+    /// Inspect and retry sync jobs
+    Jobs {
+        #[command(subcommand)]
+        action: JobsAction,
+    },
+    /// Inspect provider connections
+    Connections {
+        #[command(subcommand)]
+        action: ConnectionsAction,
+    },
+    /// Audited break-glass commands for incident debugging
+    Admin {
+        #[command(subcommand)]
+        action: AdminAction,
+    },
+    /// Master key maintenance
+    Crypto {
+        #[command(subcommand)]
+        action: CryptoAction,
+    },
+    /// Forecast sync cadence and queue depth from the current database (read-only)
+    Simulate(SimulateArgs),
+    /// Write the OpenAPI document without starting the server
+    Openapi {
+        /// File to write; prints to stdout when omitted
+        #[arg(long)]
+        output: Option<std::path::PathBuf>,
+    },
 }
 
 #[derive(Subcommand)]
@@ -45,6 +88,16 @@ enum MigrateAction {
 async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     let cli = Cli::parse();
 
+    // Exporting the spec needs neither configuration nor a database
+    if let Some(Commands::Openapi { output }) = &cli.command {
+        let spec = connectors::server::openapi_json()?;
+        match output {
+            Some(path) => std::fs::write(path, spec)?,
+            None => println!("{}", spec.trim_end()),
+        }
+        return Ok(());
+    }
+
     // Load configuration from layered env files and variables
     let config_loader = ConfigLoader::new();
     let config = config_loader.load()?;
@@ -63,42 +116,43 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
                 return Ok(());
             }
             Commands::SyncExecutor => {
-                handle_sync_executor_command(config, db).await?;
+                println!("Starting sync executor service...");
+                run_subsystems(config, db, RunMode::SyncExecutor).await?;
+                return Ok(());
+            }
+            Commands::Jobs { action } => {
+                let repo = connectors::repositories::SyncJobRepository::new(db);
+                connectors::cli::run_jobs_command(&repo, action, &mut std::io::stdout()).await?;
+                return Ok(());
+            }
+            Commands::Connections { action } => {
+                handle_connections_command(config, db, action).await?;
                 return Ok(());
             }
+            Commands::Admin { action } => {
+                handle_admin_command(config, db, action).await?;
+                return Ok(());
+            }
+            Commands::Crypto { action } => {
+                handle_crypto_command(&config, &db, action).await?;
+                return Ok(());
+            }
+            Commands::Simulate(args) => {
+                connectors::cli::run_simulate_command(&db, &config, args, &mut std::io::stdout())
+                    .await?;
+                return Ok(());
+            }
+            Commands::Openapi { .. } => unreachable!("handled before loading configuration"),
             Commands::RunAll => {
-                println!("Starting both API server and sync executor...");
-
-                // For now, run the server first
-                println!("Starting API server...");
-                run_server(config, db).await?;
+                println!("Starting API server, scheduler, sync executor and token refresh...");
+                run_subsystems(config, db, RunMode::All).await?;
                 return Ok(());
             }
         }
     }
 
-    // Run migrations automatically for local and test profiles
-    if config.profile == "local" || config.profile == "test" {
-        println!(
-            "Running migrations automatically for profile: {}",
-            config.profile
-        );
-        Migrator::up(&db, None).await?;
-        println!("Migrations completed successfully");
-    }
-
-    // Initialize the connector registry
-    Registry::initialize(&config);
-    println!("Connector registry initialized with example provider");
-
-    // Log the loaded configuration (no secrets in current schema)
-    println!("Loaded configuration for profile: {}", config.profile);
-    if let Ok(redacted_json) = config.redacted_json() {
-        println!("Configuration: {}", redacted_json);
-    }
-
-    // Start the server with the loaded configuration
-    run_server(config, db).await
+    // Start the API server
+    run_subsystems(config, db, RunMode::Server).await
 }
 
 async fn handle_migrate_command(
@@ -137,26 +191,403 @@ async fn handle_migrate_command(
     Ok(())
 }
 
-async fn handle_sync_executor_command(
+async fn handle_connections_command(
     config: connectors::config::AppConfig,
     db: DatabaseConnection,
+    action: ConnectionsAction,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    println!("Starting sync executor service...");
+    let master_keys = load_master_keyring(&config)
+        .await
+        .map_err(|e| format!("Failed to load crypto keys: {}", e))?;
+    let connections = connectors::repositories::ConnectionRepository::new(
+        std::sync::Arc::new(db.clone()),
+        master_keys,
+    );
+    let jobs = connectors::repositories::SyncJobRepository::new(db);
 
-    // Run migrations automatically for local and test profiles
-    if config.profile == "local" || config.profile == "test" {
-        println!(
-            "Running migrations for sync executor (profile: {})",
-            config.profile
-        );
-        Migrator::up(&db, None).await?;
-        println!("Migrations completed successfully");
+    connectors::cli::run_connections_command(&connections, &jobs, action, &mut std::io::stdout())
+        .await
+}
+
+async fn handle_admin_command(
+    config: connectors::config::AppConfig,
+    db: DatabaseConnection,
+    action: AdminAction,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let master_keys = load_master_keyring(&config)
+        .await
+        .map_err(|e| format!("Failed to load crypto keys: {}", e))?;
+    let connections = connectors::repositories::ConnectionRepository::new(
+        std::sync::Arc::new(db.clone()),
+        master_keys,
+    );
+    let audit = connectors::repositories::AuditLogRepository::new(db);
+
+    eprintln!("WARNING: break-glass command; this action is recorded in the audit log");
+    connectors::cli::run_admin_command(
+        &connections,
+        &audit,
+        action,
+        connectors::cli::break_glass_enabled(),
+        &connectors::cli::current_actor(),
+        &mut std::io::stdout(),
+    )
+    .await
+}
+
+async fn handle_crypto_command(
+    config: &AppConfig,
+    db: &DatabaseConnection,
+    action: CryptoAction,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let provider = key_provider_from_config(config)
+        .map_err(|e| format!("Failed to load crypto keys: {}", e))?;
+    let master_keys = load_master_keyring_with(provider.as_ref(), config)
+        .await
+        .map_err(|e| format!("Failed to load crypto keys: {}", e))?;
+
+    connectors::cli::run_crypto_command(
+        db,
+        config,
+        &master_keys,
+        provider.as_ref(),
+        action,
+        &mut std::io::stdout(),
+    )
+    .await
+}
+
+/// Long-running subsystems a process hosts
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RunMode {
+    /// API server and token refresh
+    Server,
+    /// Scheduler, executor and the signal pipeline
+    SyncExecutor,
+    /// Everything in one process
+    All,
+}
+
+impl RunMode {
+    fn serves_api(self) -> bool {
+        matches!(self, RunMode::Server | RunMode::All)
+    }
+
+    fn runs_sync(self) -> bool {
+        matches!(self, RunMode::SyncExecutor | RunMode::All)
+    }
+}
+
+/// Resources shared by the subsystems of one process
+struct Runtime {
+    config: Arc<AppConfig>,
+    db: DatabaseConnection,
+    master_keys: MasterKeyring,
+    /// The one token refresh service: its loop runs as a subsystem, and the
+    /// executor and API handlers refresh through the same instance
+    token_refresh_service: Arc<TokenRefreshService>,
+    /// Settings replaced in place when configuration is reloaded on SIGHUP
+    settings: ReloadableConfig,
+    /// The one signal event bus: the outbox relay publishes to it and
+    /// consumers subscribe through the API state
+    signal_events: connectors::signals::SignalEventBus,
+}
+
+/// Resolve on Ctrl+C, or on SIGTERM where the platform has it
+async fn shutdown_signal() -> std::io::Result<()> {
+    #[cfg(unix)]
+    {
+        let mut terminate =
+            tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())?;
+        tokio::select! {
+            result = tokio::signal::ctrl_c() => result,
+            _ = terminate.recv() => Ok(()),
+        }
+    }
+    #[cfg(not(unix))]
+    tokio::signal::ctrl_c().await
+}
+
+/// Start the subsystems for `mode` and run until shutdown or a subsystem failure
+async fn run_subsystems(
+    config: AppConfig,
+    db: DatabaseConnection,
+    mode: RunMode,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    // Log the loaded configuration (no secrets in current schema)
+    println!("Loaded configuration for profile: {}", config.profile);
+    if let Ok(redacted_json) = config.redacted_json() {
+        println!("Configuration: {}", redacted_json);
+    }
+    if mode.runs_sync() {
+        print_executor_config(&config, &ExecutorConfig::from_config(&config));
     }
 
-    // Initialize the connector registry
+    let master_keys = load_master_keyring(&config)
+        .await
+        .map_err(|e| format!("Failed to load crypto keys: {}", e))?;
+    // Connectors are built from configuration alone, so register them before the
+    // services that hold the registry are constructed
     Registry::initialize(&config);
-    println!("Connector registry initialized");
+    let config = Arc::new(config);
+    let token_refresh_service = Arc::new(TokenRefreshService::new(
+        config.clone(),
+        Arc::new(db.clone()),
+        Arc::new(connectors::repositories::ConnectionRepository::new(
+            Arc::new(db.clone()),
+            master_keys.clone(),
+        )),
+        Registry::global().read().unwrap().clone(),
+    ));
+    let runtime = Arc::new(Runtime {
+        settings: ReloadableConfig::from_config(&config),
+        config,
+        db,
+        master_keys,
+        token_refresh_service,
+        signal_events: connectors::signals::SignalEventBus::default(),
+    });
+
+    let shutdown = CancellationToken::new();
+    let shutdown_on_signal = shutdown.clone();
+    tokio::spawn(async move {
+        if shutdown_signal().await.is_ok() {
+            println!("Received shutdown signal, draining");
+            shutdown_on_signal.cancel();
+        }
+    });
+
+    let running = subsystems(runtime, mode)
+        .into_iter()
+        .fold(Supervisor::new(shutdown), Supervisor::with_subsystem)
+        .start()
+        .await?;
+    println!(
+        "Started {} subsystems in {}ms. Press Ctrl+C to stop.",
+        running.report().subsystems.len(),
+        running.report().elapsed_ms
+    );
+    running.wait().await?;
+    Ok(())
+}
+
+/// Subsystems for `mode`, each depending on what it needs to be up first
+fn subsystems(runtime: Arc<Runtime>, mode: RunMode) -> Vec<Subsystem> {
+    let mut subsystems = Vec::new();
+
+    // Check connectivity and run migrations automatically for local and test profiles
+    let rt = runtime.clone();
+    subsystems.push(Subsystem::init("database", move || async move {
+        db::health_check(&rt.db).await?;
+        if rt.config.profile == "local" || rt.config.profile == "test" {
+            println!(
+                "Running migrations automatically for profile: {}",
+                rt.config.profile
+            );
+            Migrator::up(&rt.db, None).await?;
+            println!("Migrations completed successfully");
+        }
+        Ok(())
+    }));
+
+    // Add providers missing from the initialized registry and report drift
+    let rt = runtime.clone();
+    subsystems.push(
+        Subsystem::init("registry", move || async move {
+            #[cfg(feature = "fake-connectors")]
+            connectors::connectors::fake::seed_fake_providers(&rt.db).await?;
+
+            let catalog =
+                connectors::seeds::provider::registry_catalog(&Registry::global().read().unwrap());
+            match connectors::seeds::reconcile_providers(&rt.db, &catalog).await {
+                Ok(report) if report.is_clean() => println!("Provider catalog is in sync"),
+                Ok(report) => println!(
+                    "Provider catalog drift: removed={:?} auth_type_drift={:?} (see /admin/providers/drift)",
+                    report.removed, report.auth_type_drift
+                ),
+                Err(e) => eprintln!("Provider reconciliation failed: {}", e),
+            }
+            Ok(())
+        })
+        .depends_on(&["database"]),
+    );
+
+    // Publish pool gauges and surface database outages/recoveries
+    let rt = runtime.clone();
+    subsystems.push(
+        Subsystem::service("pool-monitor", move |shutdown| {
+            let rt = rt.clone();
+            async move {
+                db::monitor_pool(
+                    rt.db.clone(),
+                    rt.config.db_max_connections,
+                    db::POOL_MONITOR_INTERVAL,
+                    shutdown,
+                )
+                .await;
+                Ok(())
+            }
+        })
+        .depends_on(&["database"])
+        .with_restart(RestartPolicy::background()),
+    );
+
+    // Re-read configuration on SIGHUP and swap in the reloadable settings
+    let rt = runtime.clone();
+    subsystems.push(
+        Subsystem::service("config-watcher", move |shutdown| {
+            let watcher = ConfigWatcher::new(ConfigLoader::new(), rt.settings.clone());
+            async move { Ok(watcher.run(shutdown).await?) }
+        })
+        .with_restart(RestartPolicy::background()),
+    );
+
+    if mode.serves_api() {
+        let rt = runtime.clone();
+        subsystems.push(
+            Subsystem::service("token-refresh", move |shutdown| {
+                let service = rt.token_refresh_service.clone();
+                async move {
+                    service
+                        .run(shutdown)
+                        .await
+                        .map_err(|e| e.message.to_string().into())
+                }
+            })
+            .depends_on(&["registry"])
+            .with_restart(RestartPolicy::background()),
+        );
+    }
+
+    if mode.runs_sync() {
+        let rt = runtime.clone();
+        subsystems.push(
+            Subsystem::service("scheduler", move |shutdown| {
+                let scheduler = connectors::scheduler::SyncScheduler::new(
+                    rt.config.clone(),
+                    Arc::new(rt.db.clone()),
+                )
+                .with_settings(rt.settings.clone());
+                async move {
+                    scheduler
+                        .run(shutdown)
+                        .await
+                        .map_err(|e| e.message.to_string().into())
+                }
+            })
+            .depends_on(&["registry"])
+            .with_restart(RestartPolicy::background()),
+        );
+
+        // Relay committed signals to the event bus and weak engine
+        let rt = runtime.clone();
+        subsystems.push(
+            Subsystem::service("weak-engine", move |shutdown| {
+                let shared_db = Arc::new(rt.db.clone());
+                let relay = connectors::signals::OutboxRelay::new(
+                    shared_db.clone(),
+                    connectors::signals::OutboxRelayConfig::default(),
+                )
+                .with_publisher(
+                    connectors::signals::outbox::TOPIC_SIGNAL_CREATED,
+                    Arc::new(rt.signal_events.clone()),
+                )
+                .with_publisher(
+                    connectors::signals::outbox::TOPIC_WEAK_ENGINE_ENQUEUE,
+                    Arc::new(connectors::signals::WeakSignalEngine::new(
+                        shared_db,
+                        connectors::signals::WeakSignalEngineConfig::default(),
+                    )),
+                );
+                async move { Ok(relay.run(shutdown).await?) }
+            })
+            .depends_on(&["database"])
+            .with_restart(RestartPolicy::background()),
+        );
+
+        // Daily rollups feed the stats endpoints
+        let rt = runtime.clone();
+        subsystems.push(
+            Subsystem::service("rollups", move |shutdown| {
+                let rollup_service = connectors::rollups::RollupService::new(
+                    Arc::new(rt.db.clone()),
+                    connectors::rollups::RollupConfig::default(),
+                );
+                async move { Ok(rollup_service.run(shutdown).await?) }
+            })
+            .depends_on(&["database"])
+            .with_restart(RestartPolicy::background()),
+        );
 
+        // Emit metered usage to the billing system when an endpoint is configured
+        if runtime.config.billing.endpoint_url.is_some() {
+            let rt = runtime.clone();
+            subsystems.push(
+                Subsystem::service("billing", move |shutdown| {
+                    let billing_service = connectors::billing::BillingService::new(
+                        Arc::new(rt.db.clone()),
+                        Arc::new(connectors::billing::HttpBillingSink::new(
+                            rt.config.billing.endpoint_url.clone().unwrap_or_default(),
+                            rt.config.billing.signing_secret.clone(),
+                        )),
+                        rt.config.billing.clone(),
+                    );
+                    async move { Ok(billing_service.run(shutdown).await?) }
+                })
+                .depends_on(&["database"])
+                .with_restart(RestartPolicy::background()),
+            );
+        }
+
+        let rt = runtime.clone();
+        subsystems.push(
+            Subsystem::service("executor", move |shutdown| {
+                let executor = connectors::sync_executor::SyncExecutor::new(
+                    rt.db.clone(),
+                    Registry::global().read().unwrap().clone(),
+                    ExecutorConfig::from_config(&rt.config),
+                    rt.config.rate_limit_policy.clone(),
+                    rt.token_refresh_service.clone(),
+                    connectors::token_vault::TokenVault::new(
+                        std::sync::Arc::new(rt.db.clone()),
+                        rt.master_keys.clone(),
+                    ),
+                    connectors::cursor::CursorSigner::new(rt.master_keys.current()),
+                )
+                .with_settings(rt.settings.clone())
+                .with_shutdown(shutdown);
+                async move { executor.run().await }
+            })
+            .depends_on(&["registry", "weak-engine"])
+            .with_restart(RestartPolicy::background()),
+        );
+    }
+
+    if mode.serves_api() {
+        let rt = runtime.clone();
+        subsystems.push(
+            Subsystem::service("server", move |shutdown| {
+                let rt = rt.clone();
+                async move {
+                    let state = connectors::server::app_state(
+                        rt.config.clone(),
+                        rt.db.clone(),
+                        rt.master_keys.clone(),
+                        rt.token_refresh_service.clone(),
+                        rt.settings.clone(),
+                        rt.signal_events.clone(),
+                    );
+                    connectors::server::serve(state, shutdown).await
+                }
+            })
+            .depends_on(&["registry", "token-refresh"]),
+        );
+    }
+
+    subsystems
+}
+
+fn print_executor_config(config: &AppConfig, executor_config: &ExecutorConfig) {
     // Log rate limit policy configuration
     println!("Rate limit policy:");
     println!("  Base seconds: {}", config.rate_limit_policy.base_seconds);
@@ -174,42 +605,56 @@ async fn handle_sync_executor_command(
         println!("  No provider overrides configured");
     }
 
-    // Create executor configuration
-    let executor_config = ExecutorConfig::default();
     println!("Executor configuration:");
     println!("  Tick interval: {}ms", executor_config.tick_ms);
     println!("  Concurrency: {}", executor_config.concurrency);
     println!("  Claim batch: {}", executor_config.claim_batch);
     println!("  Max run time: {}s", executor_config.max_run_seconds);
     println!("  Max items per run: {}", executor_config.max_items_per_run);
-
-    // Create crypto key and connection repository
-    let crypto_key =
-        connectors::crypto::CryptoKey::new(config.crypto_key.as_ref().unwrap().clone())
-            .map_err(|e| format!("Failed to create crypto key: {}", e))?;
-    // For now, create sync executor without token refresh service due to type issues
-    // TODO: Reintegrate token refresh service once types are resolved
-    let executor = connectors::sync_executor::SyncExecutor::new(
-        db.clone(),
-        Registry::global().read().unwrap().clone(),
-        executor_config,
-        config.rate_limit_policy.clone(),
-        // Temporary placeholder - proper implementation needed
-        std::sync::Arc::new(connectors::token_refresh::TokenRefreshService::new(
-            std::sync::Arc::new(config.clone()),
-            std::sync::Arc::new(db.clone()),
-            std::sync::Arc::new(
-                connectors::repositories::connection::ConnectionRepository::new(
-                    std::sync::Arc::new(db.clone()),
-                    crypto_key,
-                ),
-            ),
-            Registry::global().read().unwrap().clone(),
-        )),
+    println!(
+        "  Auth failure pause threshold: {}",
+        executor_config.auth_failure_pause_threshold
     );
-
-    println!("Sync executor started. Press Ctrl+C to stop.");
-
-    // Run the executor loop (this will block until interrupted)
-    executor.run().await
+    println!(
+        "  Circuit breaker: opens after {} provider failures for {}s",
+        executor_config.circuit_breaker_threshold, executor_config.circuit_breaker_cooldown_seconds
+    );
+    println!(
+        "  Signal payload limit: {} bytes",
+        executor_config.signal_payload.max_bytes
+    );
+    println!(
+        "  Running jobs per tenant: {}",
+        executor_config.max_running_per_tenant
+    );
+    println!(
+        "  Drain timeout: {}s",
+        executor_config.drain_timeout_seconds
+    );
+    println!(
+        "  Lease timeout: {}s",
+        executor_config.lease_timeout_seconds
+    );
+    println!("  Instance: {}", executor_config.instance_id);
+    println!(
+        "  Priority aging: +{} per {}s waiting (max +{})",
+        PriorityAging::STEP,
+        executor_config.priority_aging.interval.num_seconds(),
+        executor_config.priority_aging.max_boost
+    );
+    println!(
+        "  Shard: {} of {} by {}",
+        executor_config.shard.index, executor_config.shard.count, executor_config.shard.key
+    );
+    let caps = &executor_config.provider_concurrency;
+    println!(
+        "  Running jobs per provider: {} (per tenant: {})",
+        caps.max_running, caps.max_running_per_tenant
+    );
+    for (provider, cap) in &caps.provider_max_running {
+        println!("    {}: {}", provider, cap);
+    }
+    for (provider, cap) in &caps.provider_max_running_per_tenant {
+        println!("    {} per tenant: {}", provider, cap);
+    }
 }
@@ -0,0 +1,49 @@
+//! AuditLogEntry entity model
+//!
+//! This module contains the SeaORM entity model for the audit_log_entries table,
+//! which records sensitive operator actions for later review.
+
+use sea_orm::ActiveModelBehavior;
+use sea_orm::entity::prelude::*;
+use sea_orm::prelude::DateTimeWithTimeZone;
+use serde_json::Value as JsonValue;
+use uuid::Uuid;
+
+/// Audit entry describing one sensitive action taken by an operator
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "audit_log_entries")]
+pub struct Model {
+    /// Unique identifier for the audit entry (primary key)
+    #[sea_orm(primary_key)]
+    pub id: Uuid,
+
+    /// Tenant the action affected, if tenant-scoped
+    pub tenant_id: Option<Uuid>,
+
+    /// Who performed the action (e.g., OS user for CLI commands)
+    pub actor: String,
+
+    /// Action identifier (e.g., connection.decrypt_token)
+    pub action: String,
+
+    /// Kind of object the action targeted (e.g., connection)
+    pub target_type: String,
+
+    /// Identifier of the targeted object
+    pub target_id: String,
+
+    /// Justification supplied by the actor, if any
+    pub reason: Option<String>,
+
+    /// Additional structured context; never contains secret values
+    #[sea_orm(column_type = "JsonBinary")]
+    pub details: Option<JsonValue>,
+
+    /// Timestamp when the action was recorded
+    pub created_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
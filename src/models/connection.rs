@@ -2,6 +2,12 @@
 //!
 //! This module contains the SeaORM entity model for the connections table,
 //! which stores tenant-scoped authorizations to external providers.
+//!
+//! Token ciphertexts are stored in `connection_secrets`; the `*_ciphertext` fields
+//! here are not columns and stay empty until hydrated via `ConnectionSecretRepository`.
+//! Connectors see tokens in the separate `access_token` / `refresh_token` fields:
+//! `TokenVault` decrypts into them before a connector runs and encrypts the
+//! plaintext tokens connectors return there.
 
 use super::provider::Entity as Provider;
 use sea_orm::ActiveModelBehavior;
@@ -11,7 +17,7 @@ use serde_json::Value as JsonValue;
 use uuid::Uuid;
 
 /// Connection entity representing tenant-scoped authorizations to external providers
-#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[derive(Clone, PartialEq, Eq, DeriveEntityModel)]
 #[sea_orm(table_name = "connections")]
 pub struct Model {
     /// Unique identifier for the connection (primary key)
@@ -27,18 +33,28 @@ pub struct Model {
     /// External identifier for the connection (unique per tenant & provider)
     pub external_id: String,
 
-    /// Status of the connection (spec: active|revoked|error)
+    /// Status of the connection (spec: active|revoked|error|paused)
     pub status: String,
 
     /// Display name for the connection (optional)
     pub display_name: Option<String>,
 
-    /// Encrypted access token ciphertext (spec; placeholder type)
+    /// Encrypted access token ciphertext, loaded from `connection_secrets` on demand
+    #[sea_orm(ignore)]
     pub access_token_ciphertext: Option<Vec<u8>>,
 
-    /// Encrypted refresh token ciphertext (spec; placeholder type)
+    /// Encrypted refresh token ciphertext, loaded from `connection_secrets` on demand
+    #[sea_orm(ignore)]
     pub refresh_token_ciphertext: Option<Vec<u8>>,
 
+    /// Plaintext access token in models handed to or returned by connectors; never persisted
+    #[sea_orm(ignore)]
+    pub access_token: Option<String>,
+
+    /// Plaintext refresh token in models handed to or returned by connectors; never persisted
+    #[sea_orm(ignore)]
+    pub refresh_token: Option<String>,
+
     /// Expiration timestamp (spec-aligned)
     pub expires_at: Option<DateTimeWithTimeZone>,
 
@@ -57,6 +73,41 @@ pub struct Model {
     pub updated_at: DateTimeWithTimeZone,
 }
 
+// Plaintext tokens and their ciphertexts never appear in logs, even through `{:?}`
+impl std::fmt::Debug for Model {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Connection")
+            .field("id", &self.id)
+            .field("tenant_id", &self.tenant_id)
+            .field("provider_slug", &self.provider_slug)
+            .field("external_id", &self.external_id)
+            .field("status", &self.status)
+            .field("display_name", &self.display_name)
+            .field(
+                "access_token_ciphertext",
+                &self.access_token_ciphertext.as_ref().map(|_| "[REDACTED]"),
+            )
+            .field(
+                "refresh_token_ciphertext",
+                &self.refresh_token_ciphertext.as_ref().map(|_| "[REDACTED]"),
+            )
+            .field(
+                "access_token",
+                &self.access_token.as_ref().map(|_| "[REDACTED]"),
+            )
+            .field(
+                "refresh_token",
+                &self.refresh_token.as_ref().map(|_| "[REDACTED]"),
+            )
+            .field("expires_at", &self.expires_at)
+            .field("scopes", &self.scopes)
+            .field("metadata", &self.metadata)
+            .field("created_at", &self.created_at)
+            .field("updated_at", &self.updated_at)
+            .finish()
+    }
+}
+
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
     #[sea_orm(
@@ -74,3 +125,37 @@ impl Related<Provider> for Entity {
 }
 
 impl ActiveModelBehavior for ActiveModel {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_debug_redacts_tokens() {
+        let now = chrono::Utc::now().fixed_offset();
+        let connection = Model {
+            id: Uuid::new_v4(),
+            tenant_id: Uuid::new_v4(),
+            provider_slug: "github".to_string(),
+            external_id: "octocat".to_string(),
+            status: "active".to_string(),
+            display_name: None,
+            access_token_ciphertext: Some(b"sealed-access".to_vec()),
+            refresh_token_ciphertext: None,
+            access_token: Some("gho_plaintext_access".to_string()),
+            refresh_token: Some("ghr_plaintext_refresh".to_string()),
+            expires_at: None,
+            scopes: None,
+            metadata: None,
+            created_at: now,
+            updated_at: now,
+        };
+
+        let debug = format!("{:?}", connection);
+        assert!(!debug.contains("gho_plaintext_access"));
+        assert!(!debug.contains("ghr_plaintext_refresh"));
+        assert!(debug.contains("access_token: Some(\"[REDACTED]\")"));
+        assert!(debug.contains("refresh_token_ciphertext: None"));
+        assert!(debug.contains("octocat"));
+    }
+}
@@ -0,0 +1,43 @@
+//! ConnectionRefreshLock entity model
+//!
+//! This module contains the SeaORM entity model for the connection_refresh_locks
+//! table, which serializes token refreshes of a connection across workers and
+//! holds its refresh backoff.
+
+use sea_orm::ActiveModelBehavior;
+use sea_orm::entity::prelude::*;
+use sea_orm::prelude::DateTimeWithTimeZone;
+
+/// Refresh lock and backoff state of one connection
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "connection_refresh_locks")]
+pub struct Model {
+    /// Connection whose refresh is guarded (primary key)
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub connection_id: Uuid,
+
+    /// Worker holding the lock, if any
+    #[sea_orm(column_type = "Text", nullable)]
+    pub locked_by: Option<String>,
+
+    /// When the current lock lapses if its holder never releases it
+    pub locked_until: Option<DateTimeWithTimeZone>,
+
+    /// Failed refreshes since the last success
+    pub consecutive_failures: i32,
+
+    /// Earliest time the next refresh may be attempted
+    pub retry_at: Option<DateTimeWithTimeZone>,
+
+    /// Most recent refresh failure, truncated
+    #[sea_orm(column_type = "Text", nullable)]
+    pub last_error: Option<String>,
+
+    /// Timestamp of the last lock change or recorded outcome
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,77 @@
+//! ConnectionSecret entity model
+//!
+//! This module contains the SeaORM entity model for the connection_secrets table,
+//! which holds the token ciphertexts for a connection apart from the connection row.
+//! Only `ConnectionSecretRepository` should query this entity.
+
+use super::connection::Entity as Connection;
+use sea_orm::ActiveModelBehavior;
+use sea_orm::entity::prelude::*;
+use sea_orm::prelude::DateTimeWithTimeZone;
+use uuid::Uuid;
+
+/// Token ciphertexts for one connection
+#[derive(Clone, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "connection_secrets")]
+pub struct Model {
+    /// Connection these secrets belong to (primary key)
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub connection_id: Uuid,
+
+    /// Encrypted access token ciphertext
+    pub access_token_ciphertext: Option<Vec<u8>>,
+
+    /// Encrypted refresh token ciphertext
+    pub refresh_token_ciphertext: Option<Vec<u8>>,
+
+    /// Master key version in force when the access token was encrypted; NULL for older payloads
+    pub access_token_key_version: Option<i32>,
+
+    /// Master key version in force when the refresh token was encrypted; NULL for older payloads
+    pub refresh_token_key_version: Option<i32>,
+
+    /// Timestamp when the secrets were first stored
+    pub created_at: DateTimeWithTimeZone,
+
+    /// Timestamp when the secrets were last rotated
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+// Ciphertexts never appear in logs, even through `{:?}`
+impl std::fmt::Debug for Model {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ConnectionSecret")
+            .field("connection_id", &self.connection_id)
+            .field(
+                "access_token_ciphertext",
+                &self.access_token_ciphertext.as_ref().map(|_| "[REDACTED]"),
+            )
+            .field(
+                "refresh_token_ciphertext",
+                &self.refresh_token_ciphertext.as_ref().map(|_| "[REDACTED]"),
+            )
+            .field("access_token_key_version", &self.access_token_key_version)
+            .field("refresh_token_key_version", &self.refresh_token_key_version)
+            .field("updated_at", &self.updated_at)
+            .finish()
+    }
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "Connection",
+        from = "Column::ConnectionId",
+        to = "super::connection::Column::Id",
+        on_delete = "Cascade"
+    )]
+    Connection,
+}
+
+impl Related<Connection> for Entity {
+    fn to() -> RelationDef {
+        Relation::Connection.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
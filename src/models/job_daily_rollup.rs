@@ -0,0 +1,58 @@
+//! JobDailyRollup entity model
+//!
+//! This module contains the SeaORM entity model for the job_daily_rollups table,
+//! which stores per-day sync job aggregates for a tenant and provider.
+
+use super::tenant::Entity as Tenant;
+use sea_orm::ActiveModelBehavior;
+use sea_orm::entity::prelude::*;
+use sea_orm::prelude::DateTimeWithTimeZone;
+use uuid::Uuid;
+
+/// Daily sync job aggregate for one tenant and provider
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "job_daily_rollups")]
+pub struct Model {
+    /// Unique identifier for the rollup row (primary key)
+    #[sea_orm(primary_key)]
+    pub id: Uuid,
+
+    /// UTC day the aggregate covers
+    pub day: Date,
+
+    /// Tenant identifier for multi-tenancy
+    pub tenant_id: Uuid,
+
+    /// Slug of the provider the jobs ran for
+    pub provider_slug: String,
+
+    /// Number of jobs that finished successfully on this day
+    pub jobs_succeeded: i64,
+
+    /// Number of jobs that finished with a terminal failure on this day
+    pub jobs_failed: i64,
+
+    /// Mean wall-clock duration of finished jobs in milliseconds
+    pub mean_duration_ms: Option<f64>,
+
+    /// Timestamp when the rollup was last computed
+    pub computed_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "Tenant",
+        from = "Column::TenantId",
+        to = "super::tenant::Column::Id"
+    )]
+    Tenant,
+}
+
+impl Related<Tenant> for Entity {
+    fn to() -> RelationDef {
+        Relation::Tenant.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -5,26 +5,54 @@
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
+pub mod audit_log_entry;
 pub mod connection;
+pub mod connection_refresh_lock;
+pub mod connection_secret;
 pub mod grounded_signal;
+pub mod job_daily_rollup;
 pub mod oauth_state;
+pub mod organization;
 pub mod provider;
+pub mod provider_circuit;
 pub mod signal;
+pub mod signal_daily_rollup;
+pub mod signal_outbox;
+pub mod signal_pipeline_event;
 pub mod signal_without_payload;
+pub mod slo_outcome;
 pub mod sync_job;
+pub mod sync_job_run;
 pub mod tenant;
+pub mod tenant_config;
+pub mod tenant_data_key;
 pub mod tenant_signal_config;
+pub mod tenant_signal_kind;
+pub mod token_event;
 
+pub use audit_log_entry::Entity as AuditLogEntry;
 pub use connection::Entity as Connection;
+pub use connection_refresh_lock::Entity as ConnectionRefreshLock;
+pub use connection_secret::Entity as ConnectionSecret;
 pub use grounded_signal::{
     Entity as GroundedSignal, GroundedSignalResponse, GroundedSignalStatus, SignalScores,
 };
+pub use job_daily_rollup::Entity as JobDailyRollup;
 pub use oauth_state::Entity as OAuthState;
+pub use organization::Entity as Organization;
 pub use provider::Entity as Provider;
 pub use signal::Entity as Signal;
+pub use signal_daily_rollup::Entity as SignalDailyRollup;
+pub use signal_outbox::Entity as SignalOutbox;
+pub use signal_pipeline_event::Entity as SignalPipelineEvent;
+pub use slo_outcome::Entity as SloOutcome;
 pub use sync_job::Entity as SyncJob;
 pub use tenant::Entity as Tenant;
+pub use tenant_config::Entity as TenantConfig;
+pub use tenant_data_key::Entity as TenantDataKey;
 pub use tenant_signal_config::{Entity as TenantSignalConfig, ScoringWeights};
+pub use tenant_signal_kind::Entity as TenantSignalKind;
+pub use token_event::Entity as TokenEvent;
 
 /// Basic service information response
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
@@ -0,0 +1,44 @@
+//! Organization entity model
+//!
+//! This module contains the SeaORM entity model for the organizations table,
+//! which groups tenants and stores the default settings they inherit.
+
+use sea_orm::ActiveModelBehavior;
+use sea_orm::entity::prelude::*;
+use sea_orm::prelude::DateTimeWithTimeZone;
+use uuid::Uuid;
+
+/// Organization grouping tenants under shared defaults
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "organizations")]
+pub struct Model {
+    /// Unique identifier for the organization (primary key)
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+
+    /// Display name for the organization
+    pub name: String,
+
+    /// Default settings inherited by member tenants (see `config_resolution::ConfigOverrides`)
+    pub settings: Option<Json>,
+
+    /// Timestamp when the organization was created
+    pub created_at: DateTimeWithTimeZone,
+
+    /// Timestamp when the organization was last updated
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(has_many = "super::tenant::Entity")]
+    Tenant,
+}
+
+impl Related<super::tenant::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Tenant.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,41 @@
+//! ProviderCircuit entity model
+//!
+//! This module contains the SeaORM entity model for the provider_circuits table,
+//! which holds the executor's circuit breaker state for each provider.
+
+use sea_orm::ActiveModelBehavior;
+use sea_orm::entity::prelude::*;
+use sea_orm::prelude::DateTimeWithTimeZone;
+
+/// Circuit breaker state of one provider
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "provider_circuits")]
+pub struct Model {
+    /// Slug of the provider the circuit guards (primary key)
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub provider_slug: String,
+
+    /// Circuit state (closed, open, half_open)
+    pub state: String,
+
+    /// Provider failures (5xx responses, network errors, timeouts) since the last success
+    pub consecutive_failures: i32,
+
+    /// When the circuit last opened
+    pub opened_at: Option<DateTimeWithTimeZone>,
+
+    /// End of the current cooldown (open) or probe window (half_open)
+    pub open_until: Option<DateTimeWithTimeZone>,
+
+    /// Most recent failure message, truncated
+    #[sea_orm(column_type = "Text", nullable)]
+    pub last_error: Option<String>,
+
+    /// Timestamp of the last state change or recorded outcome
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,55 @@
+//! SignalDailyRollup entity model
+//!
+//! This module contains the SeaORM entity model for the signal_daily_rollups table,
+//! which stores per-day signal counts by kind for a tenant and provider.
+
+use super::tenant::Entity as Tenant;
+use sea_orm::ActiveModelBehavior;
+use sea_orm::entity::prelude::*;
+use sea_orm::prelude::DateTimeWithTimeZone;
+use uuid::Uuid;
+
+/// Daily signal count for one tenant, provider and signal kind
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "signal_daily_rollups")]
+pub struct Model {
+    /// Unique identifier for the rollup row (primary key)
+    #[sea_orm(primary_key)]
+    pub id: Uuid,
+
+    /// UTC day the aggregate covers (by signal received_at)
+    pub day: Date,
+
+    /// Tenant identifier for multi-tenancy
+    pub tenant_id: Uuid,
+
+    /// Slug of the provider that emitted the signals
+    pub provider_slug: String,
+
+    /// Normalized signal kind
+    pub kind: String,
+
+    /// Number of signals received on this day
+    pub signal_count: i64,
+
+    /// Timestamp when the rollup was last computed
+    pub computed_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "Tenant",
+        from = "Column::TenantId",
+        to = "super::tenant::Column::Id"
+    )]
+    Tenant,
+}
+
+impl Related<Tenant> for Entity {
+    fn to() -> RelationDef {
+        Relation::Tenant.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
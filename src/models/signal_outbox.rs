@@ -0,0 +1,72 @@
+//! SignalOutbox entity model
+//!
+//! This module contains the SeaORM entity model for the signal_outbox table,
+//! which records pending downstream deliveries for persisted signals.
+
+use super::signal::Entity as Signal;
+use sea_orm::ActiveModelBehavior;
+use sea_orm::entity::prelude::*;
+use sea_orm::prelude::DateTimeWithTimeZone;
+use uuid::Uuid;
+
+/// Outbox entry status values
+pub const STATUS_PENDING: &str = "pending";
+pub const STATUS_PUBLISHED: &str = "published";
+pub const STATUS_FAILED: &str = "failed";
+
+/// Outbox entry representing one delivery of a signal to one downstream topic
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "signal_outbox")]
+pub struct Model {
+    /// Unique identifier for the outbox entry; doubles as the idempotency key for consumers
+    #[sea_orm(primary_key)]
+    pub id: Uuid,
+
+    /// Tenant identifier for multi-tenancy
+    pub tenant_id: Uuid,
+
+    /// Signal this delivery refers to
+    pub signal_id: Uuid,
+
+    /// Downstream topic (e.g., signal.created, weak_engine.enqueue)
+    pub topic: String,
+
+    /// Delivery status (pending, published, failed)
+    pub status: String,
+
+    /// Number of publication attempts made so far
+    pub attempts: i32,
+
+    /// Timestamp when the entry becomes eligible for (re)publication
+    pub available_at: DateTimeWithTimeZone,
+
+    /// Timestamp when the entry was successfully published
+    pub published_at: Option<DateTimeWithTimeZone>,
+
+    /// Last publication error, if any
+    pub last_error: Option<String>,
+
+    /// Timestamp when the entry was created
+    pub created_at: DateTimeWithTimeZone,
+
+    /// Timestamp when the entry was last updated
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "Signal",
+        from = "Column::SignalId",
+        to = "super::signal::Column::Id"
+    )]
+    Signal,
+}
+
+impl Related<Signal> for Entity {
+    fn to() -> RelationDef {
+        Relation::Signal.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
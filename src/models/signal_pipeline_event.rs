@@ -0,0 +1,56 @@
+//! SignalPipelineEvent entity model
+//!
+//! This module contains the SeaORM entity model for the signal_pipeline_events
+//! table, an append-only log of pipeline stages a signal passed through.
+
+use super::signal::Entity as Signal;
+use sea_orm::ActiveModelBehavior;
+use sea_orm::entity::prelude::*;
+use sea_orm::prelude::DateTimeWithTimeZone;
+use uuid::Uuid;
+
+/// Stage recorded when the sync executor persists a signal
+pub const STAGE_INGESTED: &str = "ingested";
+
+/// One pipeline stage recorded for a signal
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "signal_pipeline_events")]
+pub struct Model {
+    /// Unique identifier for the event (primary key)
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+
+    /// Tenant identifier for multi-tenancy
+    pub tenant_id: Uuid,
+
+    /// Signal the event refers to
+    pub signal_id: Uuid,
+
+    /// Pipeline stage (e.g., ingested)
+    pub stage: String,
+
+    /// Stage-specific details such as the sync job id
+    pub detail: Option<Json>,
+
+    /// Timestamp when the stage happened
+    pub occurred_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "Signal",
+        from = "Column::SignalId",
+        to = "super::signal::Column::Id",
+        on_delete = "Cascade"
+    )]
+    Signal,
+}
+
+impl Related<Signal> for Entity {
+    fn to() -> RelationDef {
+        Relation::Signal.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,38 @@
+//! SloOutcome entity model
+//!
+//! This module contains the SeaORM entity model for the slo_outcomes table,
+//! which counts attempts and failures of a tracked operation per provider and hour.
+
+use sea_orm::ActiveModelBehavior;
+use sea_orm::entity::prelude::*;
+use sea_orm::prelude::DateTimeWithTimeZone;
+use uuid::Uuid;
+
+/// Hourly outcome counts for one provider and operation
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "slo_outcomes")]
+pub struct Model {
+    /// Unique identifier for the row (primary key)
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+
+    /// Slug of the provider the operation ran for
+    pub provider_slug: String,
+
+    /// Tracked operation (sync, webhook, notification)
+    pub operation: String,
+
+    /// Start of the UTC hour the counts cover
+    pub hour_start: DateTimeWithTimeZone,
+
+    /// Attempts recorded within the hour
+    pub total: i64,
+
+    /// Failed attempts recorded within the hour
+    pub failed: i64,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -30,7 +30,7 @@ pub struct Model {
     /// Type of job (e.g., full, incremental, webhook)
     pub job_type: String,
 
-    /// Current status of the job (e.g., queued, running, succeeded, failed)
+    /// Current status of the job (e.g., queued, running, cancelling, cancelled, succeeded, failed)
     pub status: String,
 
     /// Job priority for scheduling (higher values = higher priority)
@@ -39,6 +39,9 @@ pub struct Model {
     /// Number of attempts made for this job
     pub attempts: i32,
 
+    /// Attempts that ended rate limited; they have their own retry budget
+    pub rate_limited_attempts: i32,
+
     /// Timestamp when the job is scheduled to run
     pub scheduled_at: DateTimeWithTimeZone,
 
@@ -51,6 +54,16 @@ pub struct Model {
     /// Timestamp when the job finished execution
     pub finished_at: Option<DateTimeWithTimeZone>,
 
+    /// Timestamp when an executor claimed the job and took its lease
+    pub locked_at: Option<DateTimeWithTimeZone>,
+
+    /// Timestamp the lease holder last renewed the lease while running the job
+    pub heartbeat_at: Option<DateTimeWithTimeZone>,
+
+    /// Executor instance holding the lease; only it renews the lease or finishes the job
+    #[sea_orm(column_type = "Text", nullable)]
+    pub locked_by: Option<String>,
+
     /// Opaque provider cursor for incremental sync state
     #[sea_orm(column_type = "JsonBinary")]
     pub cursor: Option<JsonValue>,
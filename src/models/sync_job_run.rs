@@ -0,0 +1,81 @@
+//! SyncJobRun entity model
+//!
+//! This module contains the SeaORM entity model for the sync_job_runs table,
+//! which records every executed run of a sync job.
+
+use super::sync_job::Entity as SyncJob;
+use sea_orm::ActiveModelBehavior;
+use sea_orm::entity::prelude::*;
+use sea_orm::prelude::DateTimeWithTimeZone;
+use uuid::Uuid;
+
+/// Run finished and the job succeeded
+pub const OUTCOME_SUCCEEDED: &str = "succeeded";
+
+/// Run failed; the job was requeued for a retry or ran out of attempts
+pub const OUTCOME_FAILED: &str = "failed";
+
+/// Run stopped by an operator cancel
+pub const OUTCOME_CANCELLED: &str = "cancelled";
+
+/// Run cut short by executor shutdown; the job was requeued without using an attempt
+pub const OUTCOME_INTERRUPTED: &str = "interrupted";
+
+/// One executed run of a sync job
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "sync_job_runs")]
+pub struct Model {
+    /// Unique identifier for the run (primary key)
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+
+    /// Tenant identifier for multi-tenancy
+    pub tenant_id: Uuid,
+
+    /// Connection the job synced
+    pub connection_id: Uuid,
+
+    /// Job the run belongs to
+    pub job_id: Uuid,
+
+    /// Type of the job (e.g., full, incremental, webhook)
+    pub job_type: String,
+
+    /// Attempt number of the job this run was
+    pub attempt: i32,
+
+    /// When the executor started the run
+    pub started_at: DateTimeWithTimeZone,
+
+    /// When the run ended
+    pub finished_at: DateTimeWithTimeZone,
+
+    /// Signals the run persisted
+    pub items_processed: i64,
+
+    /// How the run ended (succeeded, failed, cancelled, interrupted)
+    pub outcome: String,
+
+    /// Error message of a run that did not succeed
+    #[sea_orm(column_type = "Text", nullable)]
+    pub error: Option<String>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "SyncJob",
+        from = "Column::JobId",
+        to = "super::sync_job::Column::Id",
+        on_delete = "Cascade"
+    )]
+    SyncJob,
+}
+
+impl Related<SyncJob> for Entity {
+    fn to() -> RelationDef {
+        Relation::SyncJob.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -20,9 +20,32 @@ pub struct Model {
 
     /// Timestamp when the tenant was created
     pub created_at: DateTimeWithTimeZone,
+
+    /// Organization whose default settings the tenant inherits (optional)
+    pub organization_id: Option<Uuid>,
+
+    /// Tenant-level setting overrides (see `config_resolution::ConfigOverrides`)
+    pub settings: Option<Json>,
+
+    /// Tenant this sandbox was cloned from; `None` for regular tenants
+    pub sandbox_of: Option<Uuid>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
-pub enum Relation {}
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::organization::Entity",
+        from = "Column::OrganizationId",
+        to = "super::organization::Column::Id",
+        on_delete = "SetNull"
+    )]
+    Organization,
+}
+
+impl Related<super::organization::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Organization.def()
+    }
+}
 
 impl ActiveModelBehavior for ActiveModel {}
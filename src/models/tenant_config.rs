@@ -0,0 +1,131 @@
+//! # Tenant Configuration Model
+//!
+//! Per-tenant overrides of runtime settings, one row per tenant. Columns left
+//! NULL inherit the value from the tenant's settings, its organization or the
+//! service configuration.
+
+use std::collections::BTreeMap;
+
+use sea_orm::{
+    ActiveModelBehavior, DeriveEntityModel, EntityTrait, RelationDef, entity::prelude::*,
+};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::config_resolution::{ConfigOverrides, TenantRateLimitOverride};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "tenant_configs")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub tenant_id: Uuid,
+
+    pub sync_interval_seconds: Option<i64>,
+
+    #[sea_orm(column_type = "JsonBinary", nullable)]
+    pub provider_sync_intervals: Option<Json>,
+
+    pub mail_spam_threshold: Option<f32>,
+
+    #[sea_orm(column_type = "JsonBinary", nullable)]
+    pub rate_limit_overrides: Option<Json>,
+
+    #[sea_orm(column_type = "JsonBinary", nullable)]
+    pub notification_urls: Option<Json>,
+
+    pub created_at: DateTimeWithTimeZone,
+
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::tenant::Entity",
+        from = "Column::TenantId",
+        to = "super::tenant::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    Tenant,
+}
+
+impl Related<super::tenant::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Tenant.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+/// Settings a tenant can override in its `tenant_configs` row
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct TenantConfigSettings {
+    /// Sync interval in seconds for connections without an interval of their own
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(example = 1800)]
+    pub sync_interval_seconds: Option<u64>,
+    /// Sync intervals in seconds keyed by provider slug; these win over `sync_interval_seconds`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provider_sync_intervals: Option<BTreeMap<String, u64>>,
+    /// Spam score (0.0 to 1.0) at or above which mail is treated as spam
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(example = 0.6)]
+    pub mail_spam_threshold: Option<f32>,
+    /// Retry backoff and attempt budgets keyed by provider slug
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rate_limit_overrides: Option<BTreeMap<String, TenantRateLimitOverride>>,
+    /// HTTPS URLs that receive signed notification deliveries
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notification_urls: Option<Vec<String>>,
+}
+
+impl TenantConfigSettings {
+    /// The settings as a [`ConfigOverrides`] layer
+    pub fn overrides(&self) -> ConfigOverrides {
+        let mut overrides = ConfigOverrides::default();
+        self.apply_to(&mut overrides);
+        overrides
+    }
+
+    /// Replace the settings this row overrides in `overrides`
+    pub fn apply_to(&self, overrides: &mut ConfigOverrides) {
+        if let Some(interval) = self.sync_interval_seconds {
+            overrides.sync_interval_seconds = Some(interval);
+        }
+        if let Some(intervals) = &self.provider_sync_intervals {
+            overrides.provider_sync_intervals = Some(intervals.clone());
+        }
+        if let Some(threshold) = self.mail_spam_threshold {
+            overrides.mail_spam_threshold = Some(threshold);
+        }
+        if let Some(policies) = &self.rate_limit_overrides {
+            overrides.rate_limit_overrides = Some(policies.clone());
+        }
+        if let Some(urls) = &self.notification_urls {
+            overrides.notification_urls = Some(urls.clone());
+        }
+    }
+}
+
+impl Model {
+    /// The overridden settings stored in this row; malformed columns are ignored
+    pub fn settings(&self) -> TenantConfigSettings {
+        fn parse<T: serde::de::DeserializeOwned>(column: &Option<Json>) -> Option<T> {
+            column
+                .as_ref()
+                .and_then(|json| serde_json::from_value(json.clone()).ok())
+        }
+
+        TenantConfigSettings {
+            sync_interval_seconds: self
+                .sync_interval_seconds
+                .and_then(|interval| u64::try_from(interval).ok()),
+            provider_sync_intervals: parse(&self.provider_sync_intervals),
+            mail_spam_threshold: self.mail_spam_threshold,
+            rate_limit_overrides: parse(&self.rate_limit_overrides),
+            notification_urls: parse(&self.notification_urls),
+        }
+    }
+}
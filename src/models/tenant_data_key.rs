@@ -0,0 +1,60 @@
+//! TenantDataKey entity model
+//!
+//! This module contains the SeaORM entity model for the tenant_data_keys table,
+//! which holds each tenant's token data-encryption key wrapped by the master key.
+//! Only `TenantKeyRepository` should query this entity.
+
+use super::tenant::Entity as Tenant;
+use sea_orm::ActiveModelBehavior;
+use sea_orm::entity::prelude::*;
+use sea_orm::prelude::DateTimeWithTimeZone;
+use uuid::Uuid;
+
+/// Wrapped data-encryption key for one tenant
+#[derive(Clone, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "tenant_data_keys")]
+pub struct Model {
+    /// Tenant this key belongs to (primary key)
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub tenant_id: Uuid,
+
+    /// Data key encrypted with the master key
+    pub wrapped_key: Vec<u8>,
+
+    /// Master key version that wrapped `wrapped_key`
+    pub key_version: i32,
+
+    /// Timestamp when the key was generated
+    pub created_at: DateTimeWithTimeZone,
+}
+
+// Key material never appears in logs, even through `{:?}`
+impl std::fmt::Debug for Model {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TenantDataKey")
+            .field("tenant_id", &self.tenant_id)
+            .field("wrapped_key", &"[REDACTED]")
+            .field("key_version", &self.key_version)
+            .field("created_at", &self.created_at)
+            .finish()
+    }
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "Tenant",
+        from = "Column::TenantId",
+        to = "super::tenant::Column::Id",
+        on_delete = "Cascade"
+    )]
+    Tenant,
+}
+
+impl Related<Tenant> for Entity {
+    fn to() -> RelationDef {
+        Relation::Tenant.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -22,6 +22,11 @@ pub struct Model {
     #[sea_orm(column_type = "Text", nullable)]
     pub webhook_url: Option<String>,
 
+    /// Secret that signs deliveries to `webhook_url`; never serialized
+    #[sea_orm(column_type = "Text", nullable)]
+    #[serde(default, skip_serializing)]
+    pub webhook_signing_secret: Option<String>,
+
     #[serde(skip_serializing_if = "Option::is_none")]
     pub created_at: Option<DateTimeWithTimeZone>,
 
@@ -56,6 +61,7 @@ impl Default for Model {
             weak_signal_threshold: 0.7,
             scoring_weights: None,
             webhook_url: None,
+            webhook_signing_secret: None,
             created_at: None,
             updated_at: None,
         }
@@ -63,7 +69,7 @@ impl Default for Model {
 }
 
 /// Scoring weights configuration
-#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
 pub struct ScoringWeights {
     pub impact: f32,
     pub relevance: f32,
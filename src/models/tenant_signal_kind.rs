@@ -0,0 +1,50 @@
+//! TenantSignalKind entity model
+//!
+//! This module contains the SeaORM entity model for the tenant_signal_kinds table,
+//! which records the custom signal kinds each tenant has registered.
+
+use super::tenant::Entity as Tenant;
+use sea_orm::ActiveModelBehavior;
+use sea_orm::entity::prelude::*;
+use sea_orm::prelude::DateTimeWithTimeZone;
+use uuid::Uuid;
+
+/// A custom signal kind registered by a tenant
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "tenant_signal_kinds")]
+pub struct Model {
+    /// Unique identifier for the registration (primary key)
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+
+    /// Tenant that registered the kind
+    pub tenant_id: Uuid,
+
+    /// Full kind including the `custom:` prefix
+    pub kind: String,
+
+    /// Optional human-readable description
+    pub description: Option<String>,
+
+    /// Timestamp when the kind was registered
+    pub created_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "Tenant",
+        from = "Column::TenantId",
+        to = "super::tenant::Column::Id",
+        on_delete = "Cascade"
+    )]
+    Tenant,
+}
+
+impl Related<Tenant> for Entity {
+    fn to() -> RelationDef {
+        Relation::Tenant.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,72 @@
+//! TokenEvent entity model
+//!
+//! This module contains the SeaORM entity model for the token_events table,
+//! an append-only record of every token refresh attempt of a connection.
+
+use super::connection::Entity as Connection;
+use sea_orm::ActiveModelBehavior;
+use sea_orm::entity::prelude::*;
+use sea_orm::prelude::DateTimeWithTimeZone;
+use uuid::Uuid;
+
+/// The provider issued new tokens
+pub const OUTCOME_SUCCEEDED: &str = "succeeded";
+
+/// The provider rejected the refresh or could not be reached
+pub const OUTCOME_FAILED: &str = "failed";
+
+/// One token refresh attempt of a connection
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "token_events")]
+pub struct Model {
+    /// Unique identifier for the event (primary key)
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+
+    /// Tenant identifier for multi-tenancy
+    pub tenant_id: Uuid,
+
+    /// Connection whose tokens were refreshed
+    pub connection_id: Uuid,
+
+    /// Provider the refresh was sent to
+    pub provider_slug: String,
+
+    /// Access token expiry before the refresh
+    pub previous_expires_at: Option<DateTimeWithTimeZone>,
+
+    /// Access token expiry after a successful refresh
+    pub new_expires_at: Option<DateTimeWithTimeZone>,
+
+    /// Whether the provider issued a new refresh token
+    pub refresh_token_rotated: bool,
+
+    /// How the refresh ended (succeeded, failed)
+    pub outcome: String,
+
+    /// Provider or network error of a failed refresh
+    #[sea_orm(column_type = "Text", nullable)]
+    pub error: Option<String>,
+
+    /// When the refresh finished
+    pub occurred_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "Connection",
+        from = "Column::ConnectionId",
+        to = "super::connection::Column::Id",
+        on_delete = "Cascade"
+    )]
+    Connection,
+}
+
+impl Related<Connection> for Entity {
+    fn to() -> RelationDef {
+        Relation::Connection.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
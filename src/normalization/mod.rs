@@ -1,5 +1,6 @@
 use std::fmt;
 
+use chrono::{DateTime, Utc};
 use serde_json::Value;
 use thiserror::Error;
 
@@ -35,6 +36,34 @@ pub enum SignalKind {
     EmailSent,
     EmailUpdated,
     EmailDeleted,
+    OpportunityCreated,
+    OpportunityUpdated,
+    OpportunityWon,
+    OpportunityLost,
+    OpportunityDeleted,
+    CaseCreated,
+    CaseUpdated,
+    CaseClosed,
+    CaseDeleted,
+    ErrorEventCreated,
+    ErrorEventAlerted,
+    ErrorEventRegressed,
+    ErrorEventResolved,
+    ErrorEventUpdated,
+    IncidentTriggered,
+    IncidentUpdated,
+    IncidentResolved,
+    CiRunSucceeded,
+    CiRunFailed,
+    CiRunCanceled,
+    InvoicePaid,
+    InvoicePaymentFailed,
+    SubscriptionCreated,
+    SubscriptionUpdated,
+    SubscriptionCanceled,
+    DisputeOpened,
+    DisputeUpdated,
+    DisputeClosed,
 }
 
 impl SignalKind {
@@ -70,6 +99,34 @@ impl SignalKind {
             SignalKind::EmailSent => "email_sent",
             SignalKind::EmailUpdated => "email_updated",
             SignalKind::EmailDeleted => "email_deleted",
+            SignalKind::OpportunityCreated => "opportunity_created",
+            SignalKind::OpportunityUpdated => "opportunity_updated",
+            SignalKind::OpportunityWon => "opportunity_won",
+            SignalKind::OpportunityLost => "opportunity_lost",
+            SignalKind::OpportunityDeleted => "opportunity_deleted",
+            SignalKind::CaseCreated => "case_created",
+            SignalKind::CaseUpdated => "case_updated",
+            SignalKind::CaseClosed => "case_closed",
+            SignalKind::CaseDeleted => "case_deleted",
+            SignalKind::ErrorEventCreated => "error_event_created",
+            SignalKind::ErrorEventAlerted => "error_event_alerted",
+            SignalKind::ErrorEventRegressed => "error_event_regressed",
+            SignalKind::ErrorEventResolved => "error_event_resolved",
+            SignalKind::ErrorEventUpdated => "error_event_updated",
+            SignalKind::IncidentTriggered => "incident_triggered",
+            SignalKind::IncidentUpdated => "incident_updated",
+            SignalKind::IncidentResolved => "incident_resolved",
+            SignalKind::CiRunSucceeded => "ci_run_succeeded",
+            SignalKind::CiRunFailed => "ci_run_failed",
+            SignalKind::CiRunCanceled => "ci_run_canceled",
+            SignalKind::InvoicePaid => "invoice_paid",
+            SignalKind::InvoicePaymentFailed => "invoice_payment_failed",
+            SignalKind::SubscriptionCreated => "subscription_created",
+            SignalKind::SubscriptionUpdated => "subscription_updated",
+            SignalKind::SubscriptionCanceled => "subscription_canceled",
+            SignalKind::DisputeOpened => "dispute_opened",
+            SignalKind::DisputeUpdated => "dispute_updated",
+            SignalKind::DisputeClosed => "dispute_closed",
         }
     }
 }
@@ -111,6 +168,34 @@ pub const ALL_SIGNAL_KINDS: &[SignalKind] = &[
     SignalKind::EmailSent,
     SignalKind::EmailUpdated,
     SignalKind::EmailDeleted,
+    SignalKind::OpportunityCreated,
+    SignalKind::OpportunityUpdated,
+    SignalKind::OpportunityWon,
+    SignalKind::OpportunityLost,
+    SignalKind::OpportunityDeleted,
+    SignalKind::CaseCreated,
+    SignalKind::CaseUpdated,
+    SignalKind::CaseClosed,
+    SignalKind::CaseDeleted,
+    SignalKind::ErrorEventCreated,
+    SignalKind::ErrorEventAlerted,
+    SignalKind::ErrorEventRegressed,
+    SignalKind::ErrorEventResolved,
+    SignalKind::ErrorEventUpdated,
+    SignalKind::IncidentTriggered,
+    SignalKind::IncidentUpdated,
+    SignalKind::IncidentResolved,
+    SignalKind::CiRunSucceeded,
+    SignalKind::CiRunFailed,
+    SignalKind::CiRunCanceled,
+    SignalKind::InvoicePaid,
+    SignalKind::InvoicePaymentFailed,
+    SignalKind::SubscriptionCreated,
+    SignalKind::SubscriptionUpdated,
+    SignalKind::SubscriptionCanceled,
+    SignalKind::DisputeOpened,
+    SignalKind::DisputeUpdated,
+    SignalKind::DisputeClosed,
 ];
 
 /// Returns `true` when the provided string matches a canonical kind.
@@ -126,6 +211,58 @@ pub fn parse_signal_kind(kind: &str) -> Option<SignalKind> {
         .find(|k| k.as_str() == kind)
 }
 
+/// Namespace prefix for tenant-defined signal kinds, e.g. `custom:press_mention`.
+pub const CUSTOM_KIND_PREFIX: &str = "custom:";
+
+/// Maximum length of the name after [`CUSTOM_KIND_PREFIX`].
+pub const MAX_CUSTOM_KIND_NAME_LEN: usize = 64;
+
+/// A `Signal.kind` value: a strongly typed canonical kind, or a namespaced custom
+/// kind that a tenant has registered.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum AnySignalKind {
+    Canonical(SignalKind),
+    /// Full custom kind including the `custom:` prefix
+    Custom(String),
+}
+
+impl AnySignalKind {
+    /// Parse a kind string. Custom kinds must be `custom:` followed by lowercase
+    /// letters, digits and underscores, starting with a letter; whether the tenant
+    /// registered it is checked separately.
+    pub fn parse(kind: &str) -> Result<Self, NormalizationError> {
+        if let Some(kind) = parse_signal_kind(kind) {
+            return Ok(AnySignalKind::Canonical(kind));
+        }
+        let Some(name) = kind.strip_prefix(CUSTOM_KIND_PREFIX) else {
+            return Err(NormalizationError::UnknownKind(kind.to_string()));
+        };
+        let valid = name.len() <= MAX_CUSTOM_KIND_NAME_LEN
+            && name.starts_with(|c: char| c.is_ascii_lowercase())
+            && name
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+        if !valid {
+            return Err(NormalizationError::InvalidCustomKind(kind.to_string()));
+        }
+        Ok(AnySignalKind::Custom(kind.to_string()))
+    }
+
+    /// The string stored in `Signal.kind`.
+    pub fn as_str(&self) -> &str {
+        match self {
+            AnySignalKind::Canonical(kind) => kind.as_str(),
+            AnySignalKind::Custom(kind) => kind,
+        }
+    }
+}
+
+impl fmt::Display for AnySignalKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 /// Errors that can occur while mapping provider payloads to canonical kinds.
 #[derive(Debug, Error, PartialEq, Eq)]
 pub enum NormalizationError {
@@ -135,6 +272,10 @@ pub enum NormalizationError {
     MissingField { field: &'static str },
     #[error("unsupported payload variant: {0}")]
     Unsupported(&'static str),
+    #[error("unknown signal kind: {0}")]
+    UnknownKind(String),
+    #[error("invalid custom signal kind '{0}': expected custom:<lowercase_name>")]
+    InvalidCustomKind(String),
 }
 
 /// Normalize the stub example payloads used in fixtures and sample connectors.
@@ -196,11 +337,578 @@ pub fn normalize_zoho_cliq_webhook_kind(payload: &Value) -> Result<SignalKind, N
     }
 }
 
+/// Normalize a Slack message or Events API event into a canonical kind.
+///
+/// Accepts either an entry from `conversations.history` or the `event` object of
+/// an `event_callback`. Membership and topic changes carry no content and are
+/// ignored.
+pub fn normalize_slack_event_kind(event: &Value) -> Option<SignalKind> {
+    match event.get("type").and_then(|v| v.as_str())? {
+        "message" => match event.get("subtype").and_then(|v| v.as_str()) {
+            None | Some("bot_message" | "thread_broadcast" | "file_share" | "me_message") => {
+                Some(SignalKind::MessagePosted)
+            }
+            Some("message_changed") => Some(SignalKind::MessageUpdated),
+            Some("message_deleted") => Some(SignalKind::MessageDeleted),
+            Some(_) => None,
+        },
+        "reaction_added" => Some(SignalKind::ReactionAdded),
+        _ => None,
+    }
+}
+
+/// Normalize a Notion page, database or comment into a canonical kind.
+///
+/// Pages and databases created at or after `since` (or never edited after
+/// creation) count as created, trashed ones as deleted, and everything else as
+/// updated. Comments map to `issue_comment`.
+pub fn normalize_notion_object_kind(
+    object: &Value,
+    since: Option<DateTime<Utc>>,
+) -> Option<SignalKind> {
+    let timestamp = |key: &str| {
+        object
+            .get(key)
+            .and_then(|v| v.as_str())
+            .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+    };
+    match object.get("object").and_then(|v| v.as_str())? {
+        "comment" => Some(SignalKind::IssueComment),
+        "page" | "database" => {
+            let trashed = ["in_trash", "archived"]
+                .iter()
+                .any(|key| object.get(*key).and_then(|v| v.as_bool()) == Some(true));
+            let created = timestamp("created_time");
+            if trashed {
+                Some(SignalKind::FileDeleted)
+            } else if created.is_some_and(|created| {
+                since.is_some_and(|since| created >= since)
+                    || Some(created) == timestamp("last_edited_time")
+            }) {
+                Some(SignalKind::FileCreated)
+            } else {
+                Some(SignalKind::FileUpdated)
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Normalize a Linear issue from the GraphQL API into a canonical kind.
+///
+/// An issue completed or canceled at or after `since` counts as closed; one
+/// created at or after `since` (or never edited after creation) as created;
+/// anything else as updated.
+pub fn normalize_linear_issue_kind(issue: &Value, since: Option<DateTime<Utc>>) -> SignalKind {
+    let timestamp = |key: &str| {
+        issue
+            .get(key)
+            .and_then(|v| v.as_str())
+            .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+    };
+    let after_since = |at: Option<DateTime<chrono::FixedOffset>>| {
+        at.is_some_and(|at| since.is_none_or(|since| at >= since))
+    };
+    let created = timestamp("createdAt");
+    if after_since(timestamp("completedAt")) || after_since(timestamp("canceledAt")) {
+        SignalKind::IssueClosed
+    } else if since.is_some() && after_since(created)
+        || created.is_some_and(|created| Some(created) == timestamp("updatedAt"))
+    {
+        SignalKind::IssueCreated
+    } else {
+        SignalKind::IssueUpdated
+    }
+}
+
+/// Normalize a Linear webhook payload into a canonical kind.
+///
+/// Only issue events are mapped. An update that sets `completedAt` or
+/// `canceledAt` closes the issue; removals are ignored.
+pub fn normalize_linear_webhook_kind(payload: &Value) -> Option<SignalKind> {
+    if payload.get("type").and_then(|v| v.as_str()) != Some("Issue") {
+        return None;
+    }
+    match payload.get("action").and_then(|v| v.as_str())? {
+        "create" => Some(SignalKind::IssueCreated),
+        "update" => {
+            let closed = ["completedAt", "canceledAt"].iter().any(|key| {
+                payload
+                    .get("updatedFrom")
+                    .is_some_and(|from| from.get(*key).is_some())
+                    && payload
+                        .pointer(&format!("/data/{}", key))
+                        .is_some_and(|v| !v.is_null())
+            });
+            Some(if closed {
+                SignalKind::IssueClosed
+            } else {
+                SignalKind::IssueUpdated
+            })
+        }
+        _ => None,
+    }
+}
+
+/// Normalize a GitLab issue or merge request from the REST API into a canonical kind.
+///
+/// Merge requests merged or closed at or after `since` map to `pr_merged` and
+/// `pr_closed`, issues closed then to `issue_closed`. Objects created at or
+/// after `since` (or never edited after creation) count as opened; anything
+/// else as updated.
+pub fn normalize_gitlab_object_kind(
+    object: &Value,
+    merge_request: bool,
+    since: Option<DateTime<Utc>>,
+) -> SignalKind {
+    let timestamp = |key: &str| {
+        object
+            .get(key)
+            .and_then(|v| v.as_str())
+            .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+    };
+    let after_since = |at: Option<DateTime<chrono::FixedOffset>>| {
+        at.is_some_and(|at| since.is_none_or(|since| at >= since))
+    };
+    let state = object.get("state").and_then(|v| v.as_str());
+    let created = timestamp("created_at");
+    let opened = since.is_some() && after_since(created)
+        || created.is_some_and(|created| Some(created) == timestamp("updated_at"));
+
+    match (merge_request, state) {
+        (true, Some("merged")) if after_since(timestamp("merged_at")) => SignalKind::PrMerged,
+        (true, Some("closed")) if after_since(timestamp("closed_at")) => SignalKind::PrClosed,
+        (false, Some("closed")) if after_since(timestamp("closed_at")) => SignalKind::IssueClosed,
+        (true, _) if opened => SignalKind::PrOpened,
+        (false, _) if opened => SignalKind::IssueCreated,
+        (true, _) => SignalKind::PrUpdated,
+        (false, _) => SignalKind::IssueUpdated,
+    }
+}
+
+/// Normalize a GitLab webhook payload into a canonical kind.
+///
+/// Issue, merge request, note and push hooks are mapped; other hooks and
+/// actions are ignored.
+pub fn normalize_gitlab_webhook_kind(payload: &Value) -> Option<SignalKind> {
+    let action = || {
+        payload
+            .pointer("/object_attributes/action")
+            .and_then(|v| v.as_str())
+    };
+    match payload.get("object_kind").and_then(|v| v.as_str())? {
+        "issue" | "work_item" => match action()? {
+            "open" => Some(SignalKind::IssueCreated),
+            "close" => Some(SignalKind::IssueClosed),
+            "reopen" => Some(SignalKind::IssueReopened),
+            "update" => Some(SignalKind::IssueUpdated),
+            _ => None,
+        },
+        "merge_request" => match action()? {
+            "open" => Some(SignalKind::PrOpened),
+            "close" => Some(SignalKind::PrClosed),
+            "reopen" => Some(SignalKind::PrReopened),
+            "merge" => Some(SignalKind::PrMerged),
+            "update" => Some(SignalKind::PrUpdated),
+            "approved" => Some(SignalKind::PrReview),
+            _ => None,
+        },
+        "note" => Some(SignalKind::IssueComment),
+        "push" => Some(SignalKind::CodePushed),
+        _ => None,
+    }
+}
+
+/// Normalize Confluence content from the REST API into a canonical kind.
+///
+/// Pages and blog posts at their first version count as created, later
+/// versions as updated. New comments map to `issue_comment`; edited comments
+/// and other content types are ignored.
+pub fn normalize_confluence_content_kind(content: &Value) -> Option<SignalKind> {
+    let first_version = content
+        .pointer("/version/number")
+        .and_then(|v| v.as_i64())
+        .is_none_or(|number| number <= 1);
+    match content.get("type").and_then(|v| v.as_str())? {
+        "page" | "blogpost" if first_version => Some(SignalKind::FileCreated),
+        "page" | "blogpost" => Some(SignalKind::FileUpdated),
+        "comment" if first_version => Some(SignalKind::IssueComment),
+        _ => None,
+    }
+}
+
+/// Normalize a Confluence webhook payload into a canonical kind.
+///
+/// Page and blog post lifecycle events and new comments are mapped; other
+/// events are ignored.
+pub fn normalize_confluence_webhook_kind(payload: &Value) -> Option<SignalKind> {
+    match payload.get("event").and_then(|v| v.as_str())? {
+        "page_created" | "blog_created" => Some(SignalKind::FileCreated),
+        "page_updated" | "blog_updated" | "page_restored" | "blog_restored" => {
+            Some(SignalKind::FileUpdated)
+        }
+        "page_trashed" | "page_removed" | "blog_trashed" | "blog_removed" => {
+            Some(SignalKind::FileDeleted)
+        }
+        "page_moved" => Some(SignalKind::FileMoved),
+        "comment_created" => Some(SignalKind::IssueComment),
+        _ => None,
+    }
+}
+
+/// Parse a Salesforce datetime such as `2024-05-02T10:00:00.000+0000`.
+///
+/// The REST API omits the colon in the offset, so plain RFC 3339 parsing is
+/// only the fallback for values relayed in that format.
+pub fn parse_salesforce_datetime(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S%.f%z")
+        .or_else(|_| DateTime::parse_from_rfc3339(value))
+        .ok()
+        .map(|dt| dt.with_timezone(&Utc))
+}
+
+/// Normalize an Opportunity or Case record from a SOQL query into a canonical kind.
+///
+/// The object comes from the record's `attributes.type`. Deleted records map
+/// to the `_deleted` kinds. Opportunities whose stage changed into a closed
+/// stage at or after `since` map to `opportunity_won` or `opportunity_lost`,
+/// cases closed then to `case_closed`. Records created at or after `since`
+/// (or never edited after creation) count as created; anything else as
+/// updated. Other objects are ignored.
+pub fn normalize_salesforce_record_kind(
+    record: &Value,
+    since: Option<DateTime<Utc>>,
+) -> Option<SignalKind> {
+    let flag = |key: &str| record.get(key).and_then(|v| v.as_bool()) == Some(true);
+    let timestamp = |key: &str| {
+        record
+            .get(key)
+            .and_then(|v| v.as_str())
+            .and_then(parse_salesforce_datetime)
+    };
+    let after_since =
+        |at: Option<DateTime<Utc>>| at.is_some_and(|at| since.is_none_or(|since| at >= since));
+    let created = timestamp("CreatedDate");
+    let opened = since.is_some() && after_since(created)
+        || created.is_some_and(|created| Some(created) == timestamp("LastModifiedDate"));
+
+    match record
+        .pointer("/attributes/type")
+        .and_then(|v| v.as_str())?
+    {
+        "Opportunity" if flag("IsDeleted") => Some(SignalKind::OpportunityDeleted),
+        "Opportunity" if flag("IsClosed") && after_since(timestamp("LastStageChangeDate")) => {
+            Some(if flag("IsWon") {
+                SignalKind::OpportunityWon
+            } else {
+                SignalKind::OpportunityLost
+            })
+        }
+        "Opportunity" if opened => Some(SignalKind::OpportunityCreated),
+        "Opportunity" => Some(SignalKind::OpportunityUpdated),
+        "Case" if flag("IsDeleted") => Some(SignalKind::CaseDeleted),
+        "Case" if flag("IsClosed") && after_since(timestamp("ClosedDate")) => {
+            Some(SignalKind::CaseClosed)
+        }
+        "Case" if opened => Some(SignalKind::CaseCreated),
+        "Case" => Some(SignalKind::CaseUpdated),
+        _ => None,
+    }
+}
+
+/// Normalize a Salesforce Change Data Capture event payload into a canonical kind.
+///
+/// The payload carries a `ChangeEventHeader` plus the changed fields. Only
+/// Opportunity and Case events are mapped, and gap events count like their
+/// regular change type. An update that sets `IsWon` on a closed opportunity
+/// maps to won or lost; one that closes it without touching `IsWon` to lost,
+/// since the field is only sent when it changes. Cases
+/// closed by an update map to `case_closed`. Undeleted records count as
+/// updated; overflow events are ignored.
+pub fn normalize_salesforce_change_event_kind(payload: &Value) -> Option<SignalKind> {
+    let header = payload.get("ChangeEventHeader")?;
+    let change_type = header.get("changeType").and_then(|v| v.as_str())?;
+    let change_type = change_type.strip_prefix("GAP_").unwrap_or(change_type);
+    let field = |key: &str| payload.get(key).and_then(|v| v.as_bool());
+
+    match (
+        header.get("entityName").and_then(|v| v.as_str())?,
+        change_type,
+    ) {
+        ("Opportunity", "CREATE") => Some(SignalKind::OpportunityCreated),
+        ("Opportunity", "UPDATE") => Some(match (field("IsWon"), field("IsClosed")) {
+            (Some(true), _) => SignalKind::OpportunityWon,
+            // Reopened from a closed stage
+            (_, Some(false)) => SignalKind::OpportunityUpdated,
+            (Some(false), _) | (None, Some(true)) => SignalKind::OpportunityLost,
+            _ => SignalKind::OpportunityUpdated,
+        }),
+        ("Opportunity", "UNDELETE") => Some(SignalKind::OpportunityUpdated),
+        ("Opportunity", "DELETE") => Some(SignalKind::OpportunityDeleted),
+        ("Case", "CREATE") => Some(SignalKind::CaseCreated),
+        ("Case", "UPDATE") if field("IsClosed") == Some(true) => Some(SignalKind::CaseClosed),
+        ("Case", "UPDATE" | "UNDELETE") => Some(SignalKind::CaseUpdated),
+        ("Case", "DELETE") => Some(SignalKind::CaseDeleted),
+        _ => None,
+    }
+}
+
+/// Normalize a ticket from the Zendesk incremental ticket export into a canonical kind.
+///
+/// `metric_set` is the ticket's entry from the export's `metric_sets`, which
+/// carries `solved_at`. Closed tickets map to `issue_closed`; tickets solved
+/// at or after `since` to `issue_resolved`. Tickets created at or after
+/// `since` (or never edited after creation) count as created; anything else
+/// as updated. Deleted tickets are ignored.
+pub fn normalize_zendesk_ticket_kind(
+    ticket: &Value,
+    metric_set: Option<&Value>,
+    since: Option<DateTime<Utc>>,
+) -> Option<SignalKind> {
+    let timestamp = |value: Option<&Value>, key: &str| {
+        value
+            .and_then(|v| v.get(key))
+            .and_then(|v| v.as_str())
+            .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+            .map(|dt| dt.with_timezone(&Utc))
+    };
+    let after_since =
+        |at: Option<DateTime<Utc>>| at.is_some_and(|at| since.is_none_or(|since| at >= since));
+    let created = timestamp(Some(ticket), "created_at");
+
+    match ticket
+        .get("status")
+        .and_then(|v| v.as_str())?
+        .to_ascii_lowercase()
+        .as_str()
+    {
+        "deleted" => None,
+        "closed" => Some(SignalKind::IssueClosed),
+        "solved" if after_since(timestamp(metric_set, "solved_at")) => {
+            Some(SignalKind::IssueResolved)
+        }
+        _ if since.is_some() && after_since(created)
+            || created
+                .is_some_and(|created| Some(created) == timestamp(Some(ticket), "updated_at")) =>
+        {
+            Some(SignalKind::IssueCreated)
+        }
+        _ => Some(SignalKind::IssueUpdated),
+    }
+}
+
+/// Normalize a Zendesk event-subscribed webhook payload into a canonical kind.
+///
+/// Only `zen:event-type:ticket.*` events are mapped. A status change to
+/// solved or closed maps to `issue_resolved` or `issue_closed`, and one from
+/// solved or closed back to an open status to `issue_reopened`. New comments
+/// map to `issue_comment`; deletions are ignored and other ticket events
+/// count as updated.
+pub fn normalize_zendesk_webhook_kind(payload: &Value) -> Option<SignalKind> {
+    let event = payload
+        .get("type")
+        .and_then(|v| v.as_str())?
+        .strip_prefix("zen:event-type:ticket.")?;
+    let status = |key: &str| {
+        payload
+            .pointer(&format!("/event/{}", key))
+            .and_then(|v| v.as_str())
+            .map(str::to_ascii_lowercase)
+    };
+
+    match event {
+        "created" => Some(SignalKind::IssueCreated),
+        "status_changed" => Some(match status("current").as_deref() {
+            Some("solved") => SignalKind::IssueResolved,
+            Some("closed") => SignalKind::IssueClosed,
+            _ if matches!(status("previous").as_deref(), Some("solved" | "closed")) => {
+                SignalKind::IssueReopened
+            }
+            _ => SignalKind::IssueUpdated,
+        }),
+        "comment_added" => Some(SignalKind::IssueComment),
+        "soft_deleted" | "permanently_deleted" => None,
+        _ => Some(SignalKind::IssueUpdated),
+    }
+}
+
+/// Normalize an issue from the Sentry organization issues API into a canonical kind.
+///
+/// Sync only sees issues with events in the requested window. Issues first
+/// seen at or after `since` map to `error_event_created`, regressed issues to
+/// `error_event_regressed` and issues resolved since their last event to
+/// `error_event_resolved`. Ongoing issues are left to webhooks so a noisy
+/// error does not produce a signal on every run.
+pub fn normalize_sentry_issue_kind(issue: &Value, since: DateTime<Utc>) -> Option<SignalKind> {
+    let first_seen = issue
+        .get("firstSeen")
+        .and_then(|v| v.as_str())
+        .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+        .map(|dt| dt.with_timezone(&Utc));
+    let field = |key: &str| issue.get(key).and_then(|v| v.as_str());
+
+    if first_seen.is_some_and(|first_seen| first_seen >= since) {
+        return Some(SignalKind::ErrorEventCreated);
+    }
+    match (field("status"), field("substatus")) {
+        (Some("resolved"), _) => Some(SignalKind::ErrorEventResolved),
+        (_, Some("regressed")) => Some(SignalKind::ErrorEventRegressed),
+        _ => None,
+    }
+}
+
+/// Normalize a Sentry integration webhook payload into a canonical kind.
+///
+/// Issue webhooks (`data.issue`) map `created` to `error_event_created`,
+/// `resolved` to `error_event_resolved` and `unresolved` to
+/// `error_event_regressed`; `assigned`, `archived` and `ignored` count as
+/// updated. Issue alert rules (`data.event` with `triggered_rule`) and
+/// critical or warning metric alerts map to `error_event_alerted`. Other
+/// resources, such as individual error events, are ignored.
+pub fn normalize_sentry_webhook_kind(payload: &Value) -> Option<SignalKind> {
+    let action = payload.get("action").and_then(|v| v.as_str())?;
+    let data = payload.get("data")?;
+
+    if data.get("issue").is_some() {
+        return match action {
+            "created" => Some(SignalKind::ErrorEventCreated),
+            "resolved" => Some(SignalKind::ErrorEventResolved),
+            "unresolved" => Some(SignalKind::ErrorEventRegressed),
+            "assigned" | "archived" | "ignored" => Some(SignalKind::ErrorEventUpdated),
+            _ => None,
+        };
+    }
+    if data.get("triggered_rule").is_some() && data.get("event").is_some() {
+        return (action == "triggered").then_some(SignalKind::ErrorEventAlerted);
+    }
+    if data.get("metric_alert").is_some() {
+        return matches!(action, "critical" | "warning").then_some(SignalKind::ErrorEventAlerted);
+    }
+    None
+}
+
+/// Normalize a Datadog monitor notification into a canonical kind.
+///
+/// Webhook payloads carry the `$ALERT_TRANSITION` template variable as
+/// `transition`. Events API alerts have no such field, so the transition is
+/// read from the bracketed prefix of the title instead, e.g.
+/// `[P1] [Triggered on {host:web-1}] CPU high`. `Triggered`, `Warn` and
+/// `No Data` map to `incident_triggered`, their `Re-` variants and
+/// `Renotify` to `incident_updated` and `Recovered` to `incident_resolved`.
+pub fn normalize_datadog_alert_kind(alert: &Value) -> Option<SignalKind> {
+    let transition = alert
+        .get("transition")
+        .and_then(|v| v.as_str())
+        .filter(|t| !t.trim().is_empty())
+        .map(str::to_string)
+        .or_else(|| {
+            let title = alert.get("title").and_then(|v| v.as_str())?;
+            datadog_title_transition(title)
+        })?;
+
+    match transition.trim().to_ascii_lowercase().as_str() {
+        "triggered" | "warn" | "no data" => Some(SignalKind::IncidentTriggered),
+        "re-triggered" | "re-warn" | "re-no data" | "renotify" => Some(SignalKind::IncidentUpdated),
+        "recovered" => Some(SignalKind::IncidentResolved),
+        _ => None,
+    }
+}
+
+/// Transition named in the leading `[...]` groups of a Datadog alert title
+fn datadog_title_transition(title: &str) -> Option<String> {
+    let mut rest = title.trim_start();
+    while let Some(group) = rest.strip_prefix('[') {
+        let (inner, tail) = group.split_once(']')?;
+        // `Triggered on {host:web-1}` names the scope after the transition
+        let transition = inner.split(" on {").next().unwrap_or(inner).trim();
+        let is_priority = transition.len() == 2
+            && transition.starts_with('P')
+            && transition[1..].chars().all(|c| c.is_ascii_digit());
+        if !is_priority {
+            return Some(transition.to_string());
+        }
+        rest = tail.trim_start();
+    }
+    None
+}
+
+/// Normalize a CircleCI workflow from the API or a webhook into a canonical kind.
+///
+/// `success` maps to `ci_run_succeeded`; `failed`, `error` and
+/// `unauthorized` to `ci_run_failed`; `canceled` to `ci_run_canceled`.
+/// Running, failing and on hold workflows have no result yet, and workflows
+/// that were not run never will.
+pub fn normalize_circleci_workflow_kind(workflow: &Value) -> Option<SignalKind> {
+    match workflow.get("status").and_then(|v| v.as_str())? {
+        "success" => Some(SignalKind::CiRunSucceeded),
+        "failed" | "error" | "unauthorized" => Some(SignalKind::CiRunFailed),
+        "canceled" => Some(SignalKind::CiRunCanceled),
+        _ => None,
+    }
+}
+
+/// Normalize a CircleCI webhook into a canonical kind.
+///
+/// Only `workflow-completed` events produce signals; `job-completed` events
+/// would repeat the result of their workflow.
+pub fn normalize_circleci_webhook_kind(payload: &Value) -> Option<SignalKind> {
+    if payload.get("type").and_then(|v| v.as_str()) != Some("workflow-completed") {
+        return None;
+    }
+    normalize_circleci_workflow_kind(payload.get("workflow")?)
+}
+
+/// Normalize a Stripe webhook event into a canonical commerce kind.
+///
+/// `invoice.paid` and `invoice.payment_failed` map to `invoice_paid` and
+/// `invoice_payment_failed`; `customer.subscription.created`, `.updated` and
+/// `.deleted` to `subscription_created`, `subscription_updated` and
+/// `subscription_canceled`; `charge.dispute.created`, `.updated` and
+/// `.closed` to `dispute_opened`, `dispute_updated` and `dispute_closed`.
+pub fn normalize_stripe_event_kind(event: &Value) -> Option<SignalKind> {
+    match event.get("type").and_then(|v| v.as_str())? {
+        "invoice.paid" => Some(SignalKind::InvoicePaid),
+        "invoice.payment_failed" => Some(SignalKind::InvoicePaymentFailed),
+        "customer.subscription.created" => Some(SignalKind::SubscriptionCreated),
+        "customer.subscription.updated" => Some(SignalKind::SubscriptionUpdated),
+        "customer.subscription.deleted" => Some(SignalKind::SubscriptionCanceled),
+        "charge.dispute.created" => Some(SignalKind::DisputeOpened),
+        "charge.dispute.updated" => Some(SignalKind::DisputeUpdated),
+        "charge.dispute.closed" => Some(SignalKind::DisputeClosed),
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use std::collections::HashSet;
 
+    #[test]
+    fn any_signal_kind_parses_canonical_and_custom() {
+        assert_eq!(
+            AnySignalKind::parse("pr_merged"),
+            Ok(AnySignalKind::Canonical(SignalKind::PrMerged))
+        );
+        let custom = AnySignalKind::parse("custom:press_mention").unwrap();
+        assert_eq!(custom.as_str(), "custom:press_mention");
+
+        assert!(matches!(
+            AnySignalKind::parse("press_mention"),
+            Err(NormalizationError::UnknownKind(_))
+        ));
+        for invalid in ["custom:", "custom:Press", "custom:1st", "custom:a-b"] {
+            assert!(
+                matches!(
+                    AnySignalKind::parse(invalid),
+                    Err(NormalizationError::InvalidCustomKind(_))
+                ),
+                "{}",
+                invalid
+            );
+        }
+    }
+
     #[test]
     fn registry_has_unique_entries() {
         let mut seen = HashSet::new();
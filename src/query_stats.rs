@@ -0,0 +1,311 @@
+//! Query tracing and slow query reporting.
+//!
+//! Every statement executed through the shared pool is observed by a SeaORM
+//! metric callback installed in [`install`]. The callback records the
+//! normalized SQL on the active repository span, publishes a duration
+//! histogram, and keeps statements slower than the configured threshold in an
+//! in-memory log that backs the slow query report endpoint.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{LazyLock, Mutex};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use metrics::histogram;
+use regex::Regex;
+use sea_orm::{DatabaseConnection, metric};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// How long slow query samples are kept for the report
+pub const SLOW_QUERY_WINDOW: Duration = Duration::from_secs(60 * 60);
+
+/// Upper bound on retained samples so a pathological hour cannot grow memory unbounded
+const MAX_SAMPLES: usize = 10_000;
+
+/// Statements at or above this duration are logged; 0 disables the slow log
+static SLOW_QUERY_THRESHOLD_MS: AtomicU64 = AtomicU64::new(0);
+
+static SLOW_QUERIES: LazyLock<SlowQueryLog> = LazyLock::new(SlowQueryLog::default);
+
+static IN_LIST: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\(\s*\?(?:\s*,\s*\?)+\s*\)").expect("valid regex"));
+
+/// Installs the query observer on a freshly created pool.
+pub fn install(db: &mut DatabaseConnection, slow_query_threshold: Duration) {
+    SLOW_QUERY_THRESHOLD_MS.store(slow_query_threshold.as_millis() as u64, Ordering::Relaxed);
+    db.set_metric_callback(observe);
+}
+
+/// Records the number of rows a repository method returned or affected on its span.
+pub fn record_rows(rows: u64) {
+    tracing::Span::current().record("db.rows", rows);
+}
+
+fn observe(info: &metric::Info<'_>) {
+    let query = normalize_sql(&info.statement.sql);
+    let span = tracing::Span::current();
+    span.record("db.statement", query.as_str());
+
+    histogram!(
+        "db_query_duration_seconds",
+        "operation" => statement_operation(&query),
+        "outcome" => if info.failed { "error" } else { "ok" }
+    )
+    .record(info.elapsed.as_secs_f64());
+
+    let threshold_ms = SLOW_QUERY_THRESHOLD_MS.load(Ordering::Relaxed);
+    if threshold_ms == 0 || info.elapsed < Duration::from_millis(threshold_ms) {
+        return;
+    }
+
+    let source = span.metadata().and_then(|metadata| {
+        let module = metadata
+            .target()
+            .strip_prefix("connectors::repositories::")?;
+        Some(format!("{}::{}", module, metadata.name()))
+    });
+    tracing::warn!(
+        query = %query,
+        source = source.as_deref().unwrap_or("unknown"),
+        elapsed_ms = info.elapsed.as_millis() as u64,
+        "Slow database query"
+    );
+    SLOW_QUERIES.record(SlowQuerySample {
+        query,
+        source,
+        elapsed: info.elapsed,
+        failed: info.failed,
+        at: Utc::now(),
+    });
+}
+
+/// Reduces a SQL statement to a stable label.
+///
+/// Literals and bind placeholders become `?`, lists of placeholders collapse to
+/// `(...)`, and whitespace is squeezed, so the same query shape always yields
+/// the same label regardless of its parameters.
+pub fn normalize_sql(sql: &str) -> String {
+    let mut out = String::with_capacity(sql.len());
+    let mut chars = sql.chars().peekable();
+    let mut prev_ident = false;
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' => {
+                // Skip to the closing quote, treating '' as an escaped quote
+                while let Some(next) = chars.next() {
+                    if next == '\'' {
+                        if chars.peek() == Some(&'\'') {
+                            chars.next();
+                        } else {
+                            break;
+                        }
+                    }
+                }
+                out.push('?');
+                prev_ident = false;
+            }
+            '$' if chars.peek().is_some_and(|n| n.is_ascii_digit()) => {
+                while chars.peek().is_some_and(|n| n.is_ascii_digit()) {
+                    chars.next();
+                }
+                out.push('?');
+                prev_ident = false;
+            }
+            c if c.is_ascii_digit() && !prev_ident => {
+                while chars
+                    .peek()
+                    .is_some_and(|n| n.is_ascii_digit() || *n == '.')
+                {
+                    chars.next();
+                }
+                out.push('?');
+                prev_ident = false;
+            }
+            c if c.is_whitespace() => {
+                if !out.is_empty() && !out.ends_with(' ') {
+                    out.push(' ');
+                }
+                prev_ident = false;
+            }
+            c => {
+                out.push(c);
+                prev_ident = c.is_alphanumeric() || c == '_' || c == '"';
+            }
+        }
+    }
+
+    IN_LIST.replace_all(out.trim_end(), "(...)").into_owned()
+}
+
+fn statement_operation(query: &str) -> &'static str {
+    let keyword = query.split_whitespace().next().unwrap_or_default();
+    match keyword.to_ascii_uppercase().as_str() {
+        "SELECT" | "WITH" => "select",
+        "INSERT" => "insert",
+        "UPDATE" => "update",
+        "DELETE" => "delete",
+        _ => "other",
+    }
+}
+
+/// A statement that exceeded the slow query threshold
+#[derive(Debug, Clone)]
+struct SlowQuerySample {
+    query: String,
+    source: Option<String>,
+    elapsed: Duration,
+    failed: bool,
+    at: DateTime<Utc>,
+}
+
+/// Bounded, time-windowed log of slow statements
+#[derive(Debug, Default)]
+struct SlowQueryLog {
+    samples: Mutex<VecDeque<SlowQuerySample>>,
+}
+
+impl SlowQueryLog {
+    fn record(&self, sample: SlowQuerySample) {
+        let mut samples = self.samples.lock().unwrap_or_else(|e| e.into_inner());
+        prune(&mut samples, sample.at);
+        if samples.len() >= MAX_SAMPLES {
+            samples.pop_front();
+        }
+        samples.push_back(sample);
+    }
+
+    fn report(&self, now: DateTime<Utc>, limit: usize) -> Vec<SlowQuerySummary> {
+        let mut samples = self.samples.lock().unwrap_or_else(|e| e.into_inner());
+        prune(&mut samples, now);
+
+        let mut grouped: HashMap<(&str, Option<&str>), SlowQuerySummary> = HashMap::new();
+        for sample in samples.iter() {
+            let elapsed_ms = sample.elapsed.as_secs_f64() * 1000.0;
+            let summary = grouped
+                .entry((sample.query.as_str(), sample.source.as_deref()))
+                .or_insert_with(|| SlowQuerySummary {
+                    query: sample.query.clone(),
+                    source: sample.source.clone(),
+                    count: 0,
+                    failed: 0,
+                    total_ms: 0.0,
+                    mean_ms: 0.0,
+                    max_ms: 0.0,
+                    last_seen_at: sample.at.to_rfc3339(),
+                });
+            summary.count += 1;
+            summary.failed += u64::from(sample.failed);
+            summary.total_ms += elapsed_ms;
+            summary.max_ms = summary.max_ms.max(elapsed_ms);
+            summary.last_seen_at = sample.at.to_rfc3339();
+        }
+
+        let mut report: Vec<SlowQuerySummary> = grouped.into_values().collect();
+        for summary in &mut report {
+            summary.mean_ms = summary.total_ms / summary.count as f64;
+        }
+        report.sort_by(|a, b| b.total_ms.total_cmp(&a.total_ms));
+        report.truncate(limit);
+        report
+    }
+}
+
+fn prune(samples: &mut VecDeque<SlowQuerySample>, now: DateTime<Utc>) {
+    let cutoff = now - chrono::Duration::from_std(SLOW_QUERY_WINDOW).expect("window fits");
+    while samples.front().is_some_and(|s| s.at < cutoff) {
+        samples.pop_front();
+    }
+}
+
+/// Aggregate of slow executions of one normalized statement
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SlowQuerySummary {
+    /// Normalized SQL with literals replaced by `?`
+    #[schema(
+        example = "SELECT \"sync_jobs\".\"id\" FROM \"sync_jobs\" WHERE \"sync_jobs\".\"status\" = ? LIMIT ?"
+    )]
+    pub query: String,
+    /// Repository method that issued the statement, when known
+    #[schema(example = "sync_job::claim_next_jobs")]
+    pub source: Option<String>,
+    /// Slow executions within the window
+    #[schema(example = 12)]
+    pub count: u64,
+    /// Slow executions that returned an error
+    #[schema(example = 0)]
+    pub failed: u64,
+    /// Combined duration of slow executions in milliseconds
+    #[schema(example = 9120.4)]
+    pub total_ms: f64,
+    /// Mean duration of slow executions in milliseconds
+    #[schema(example = 760.0)]
+    pub mean_ms: f64,
+    /// Slowest execution in milliseconds
+    #[schema(example = 1830.2)]
+    pub max_ms: f64,
+    /// When the statement last exceeded the threshold (RFC3339)
+    #[schema(example = "2024-01-15T10:30:00Z")]
+    pub last_seen_at: String,
+}
+
+/// Returns the worst slow statements from the last hour, ordered by total time.
+pub fn slow_query_report(limit: usize) -> Vec<SlowQuerySummary> {
+    SLOW_QUERIES.report(Utc::now(), limit)
+}
+
+/// Currently configured slow query threshold in milliseconds (0 when disabled)
+pub fn slow_query_threshold_ms() -> u64 {
+    SLOW_QUERY_THRESHOLD_MS.load(Ordering::Relaxed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_sql_replaces_literals_and_placeholders() {
+        assert_eq!(
+            normalize_sql(
+                "SELECT \"id\"  FROM \"sync_jobs\"\n WHERE \"status\" = $1 AND \"priority\" > 10 LIMIT $2"
+            ),
+            "SELECT \"id\" FROM \"sync_jobs\" WHERE \"status\" = ? AND \"priority\" > ? LIMIT ?"
+        );
+        assert_eq!(
+            normalize_sql("SELECT * FROM t2 WHERE name = 'o''brien' AND id IN ($1, $2, $3)"),
+            "SELECT * FROM t2 WHERE name = ? AND id IN (...)"
+        );
+    }
+
+    #[test]
+    fn report_groups_by_query_and_drops_old_samples() {
+        let log = SlowQueryLog::default();
+        let now = Utc::now();
+        let sample = |query: &str, ms: u64, age_minutes: i64| SlowQuerySample {
+            query: query.to_string(),
+            source: Some("sync_job::find_by_id".to_string()),
+            elapsed: Duration::from_millis(ms),
+            failed: false,
+            at: now - chrono::Duration::minutes(age_minutes),
+        };
+
+        log.record(sample("SELECT a", 900, 90));
+        log.record(sample("SELECT b", 600, 30));
+        log.record(sample("SELECT a", 700, 20));
+        log.record(sample("SELECT b", 800, 10));
+        log.record(sample("SELECT a", 500, 5));
+
+        let report = log.report(now, 10);
+        assert_eq!(report.len(), 2);
+        assert_eq!(report[0].query, "SELECT b");
+        assert_eq!(report[0].count, 2);
+        assert_eq!(report[0].max_ms, 800.0);
+        assert_eq!(report[0].mean_ms, 700.0);
+        assert_eq!(report[1].query, "SELECT a");
+        assert_eq!(report[1].count, 2);
+
+        assert_eq!(log.report(now, 1).len(), 1);
+    }
+}
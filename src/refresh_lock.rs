@@ -0,0 +1,343 @@
+//! # Token Refresh Locks
+//!
+//! Some providers invalidate a refresh token as soon as it is used, so two
+//! workers refreshing the same connection at once leave one of them holding a
+//! dead token. Before calling the provider a worker takes the connection's
+//! refresh lock; the background refresher and every executor instance go
+//! through the same lock, so at most one refresh per connection is in flight
+//! across the fleet. A lock lapses after its TTL, so a worker that dies while
+//! holding one does not block the connection for long.
+//!
+//! The lock row also carries the connection's refresh backoff: each failed
+//! refresh doubles the wait before the next attempt, up to a maximum, and a
+//! success clears it. State lives in the `connection_refresh_locks` table; a
+//! connection without a row has never been refreshed through it.
+
+use chrono::{DateTime, Duration, Utc};
+use metrics::counter;
+use sea_orm::{
+    ColumnTrait, ConnectionTrait, DbErr, EntityTrait, QueryFilter, Set,
+    sea_query::{Expr, OnConflict},
+};
+use tracing::{debug, warn};
+use uuid::Uuid;
+
+use crate::backoff::ExponentialBackoff;
+use crate::config::TokenRefreshConfig;
+use crate::models::connection_refresh_lock::{ActiveModel, Column, Entity, Model};
+
+/// Longest failure message kept on the lock row
+const MAX_ERROR_CHARS: usize = 500;
+
+/// Result of trying to take a connection's refresh lock
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LockOutcome {
+    /// The caller holds the lock and may refresh
+    Acquired,
+    /// Another worker is refreshing the connection
+    Held {
+        holder: Option<String>,
+        until: Option<DateTime<Utc>>,
+    },
+    /// An earlier refresh failed and the next attempt is not due yet
+    BackingOff { retry_at: DateTime<Utc> },
+}
+
+/// Takes and releases per-connection refresh locks
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RefreshLockPolicy {
+    /// How long a lock is held before other workers may take it over
+    pub ttl: Duration,
+    /// Wait before the next attempt after consecutive failures
+    pub backoff: ExponentialBackoff,
+}
+
+impl RefreshLockPolicy {
+    /// Build the policy from the token refresh settings
+    pub fn from_config(config: &TokenRefreshConfig) -> Self {
+        Self {
+            ttl: Duration::seconds(config.lock_seconds as i64),
+            backoff: config.backoff(),
+        }
+    }
+
+    /// Try to take the refresh lock of `connection_id` for `holder`.
+    ///
+    /// The update is conditional on the lock being free and the backoff having
+    /// passed, so when several workers race only one of them acquires it.
+    pub async fn acquire<C: ConnectionTrait>(
+        &self,
+        db: &C,
+        connection_id: Uuid,
+        holder: &str,
+        now: DateTime<Utc>,
+    ) -> Result<LockOutcome, DbErr> {
+        Entity::insert(ActiveModel {
+            connection_id: Set(connection_id),
+            locked_by: Set(None),
+            locked_until: Set(None),
+            consecutive_failures: Set(0),
+            retry_at: Set(None),
+            last_error: Set(None),
+            updated_at: Set(now.fixed_offset()),
+        })
+        .on_conflict(
+            OnConflict::column(Column::ConnectionId)
+                .do_nothing()
+                .to_owned(),
+        )
+        .exec_without_returning(db)
+        .await?;
+
+        let acquired = Entity::update_many()
+            .col_expr(Column::LockedBy, Expr::value(holder))
+            .col_expr(Column::LockedUntil, Expr::value(now + self.ttl))
+            .col_expr(Column::UpdatedAt, Expr::value(now))
+            .filter(Column::ConnectionId.eq(connection_id))
+            .filter(
+                Column::LockedUntil
+                    .is_null()
+                    .or(Column::LockedUntil.lte(now)),
+            )
+            .filter(Column::RetryAt.is_null().or(Column::RetryAt.lte(now)))
+            .exec(db)
+            .await?
+            .rows_affected
+            > 0;
+        if acquired {
+            return Ok(LockOutcome::Acquired);
+        }
+
+        let lock = find(db, connection_id).await?;
+        let retry_at = lock
+            .as_ref()
+            .and_then(|lock| lock.retry_at)
+            .map(|at| at.with_timezone(&Utc))
+            .filter(|at| *at > now);
+        if let Some(retry_at) = retry_at {
+            debug!(
+                connection_id = %connection_id,
+                retry_at = %retry_at,
+                "Token refresh is backing off"
+            );
+            return Ok(LockOutcome::BackingOff { retry_at });
+        }
+        counter!("token_refresh_lock_contended_total").increment(1);
+        Ok(LockOutcome::Held {
+            holder: lock.as_ref().and_then(|lock| lock.locked_by.clone()),
+            until: lock
+                .and_then(|lock| lock.locked_until)
+                .map(|at| at.with_timezone(&Utc)),
+        })
+    }
+
+    /// Release the lock after a refresh that needs no retry, clearing the backoff
+    pub async fn release<C: ConnectionTrait>(
+        &self,
+        db: &C,
+        connection_id: Uuid,
+        holder: &str,
+        now: DateTime<Utc>,
+    ) -> Result<(), DbErr> {
+        Entity::update_many()
+            .col_expr(Column::LockedBy, Expr::value(Option::<String>::None))
+            .col_expr(
+                Column::LockedUntil,
+                Expr::value(Option::<DateTime<Utc>>::None),
+            )
+            .col_expr(Column::ConsecutiveFailures, Expr::value(0))
+            .col_expr(Column::RetryAt, Expr::value(Option::<DateTime<Utc>>::None))
+            .col_expr(Column::LastError, Expr::value(Option::<String>::None))
+            .col_expr(Column::UpdatedAt, Expr::value(now))
+            .filter(Column::ConnectionId.eq(connection_id))
+            .filter(Column::LockedBy.eq(holder))
+            .exec(db)
+            .await?;
+        Ok(())
+    }
+
+    /// Release the lock after a failed refresh and back off the next attempt.
+    ///
+    /// Returns when the next attempt is allowed, or `None` when `holder` no
+    /// longer held the lock (it lapsed and another worker took it over).
+    pub async fn release_failed<C: ConnectionTrait>(
+        &self,
+        db: &C,
+        connection_id: Uuid,
+        holder: &str,
+        error_msg: &str,
+        now: DateTime<Utc>,
+    ) -> Result<Option<DateTime<Utc>>, DbErr> {
+        let Some(lock) = find(db, connection_id).await? else {
+            return Ok(None);
+        };
+        if lock.locked_by.as_deref() != Some(holder) {
+            return Ok(None);
+        }
+        let failures = lock.consecutive_failures.saturating_add(1);
+        let delay = self.backoff.sample(failures.saturating_sub(1) as u32);
+        let retry_at = now + Duration::from_std(delay).unwrap_or(self.ttl);
+        let error: String = error_msg.chars().take(MAX_ERROR_CHARS).collect();
+
+        Entity::update_many()
+            .col_expr(Column::LockedBy, Expr::value(Option::<String>::None))
+            .col_expr(
+                Column::LockedUntil,
+                Expr::value(Option::<DateTime<Utc>>::None),
+            )
+            .col_expr(Column::ConsecutiveFailures, Expr::value(failures))
+            .col_expr(Column::RetryAt, Expr::value(retry_at))
+            .col_expr(Column::LastError, Expr::value(error))
+            .col_expr(Column::UpdatedAt, Expr::value(now))
+            .filter(Column::ConnectionId.eq(connection_id))
+            .filter(Column::LockedBy.eq(holder))
+            .exec(db)
+            .await?;
+        warn!(
+            connection_id = %connection_id,
+            consecutive_failures = failures,
+            retry_at = %retry_at,
+            "Token refresh failed, backing off"
+        );
+        Ok(Some(retry_at))
+    }
+}
+
+/// Stored refresh lock of `connection_id`, if it was ever taken
+pub async fn find<C: ConnectionTrait>(db: &C, connection_id: Uuid) -> Result<Option<Model>, DbErr> {
+    Entity::find_by_id(connection_id).one(db).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures;
+    use chrono::SubsecRound;
+    use sea_orm::DatabaseConnection;
+
+    async fn setup() -> (DatabaseConnection, Uuid) {
+        let (db, _, connection_id) = test_fixtures::setup_connection().await;
+        (db, connection_id)
+    }
+
+    fn policy() -> RefreshLockPolicy {
+        RefreshLockPolicy {
+            ttl: Duration::seconds(120),
+            backoff: ExponentialBackoff::new(
+                std::time::Duration::from_secs(60),
+                std::time::Duration::from_secs(3600),
+            ),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_only_one_worker_holds_the_lock_until_release_or_expiry() {
+        let (db, connection_id) = setup().await;
+        let policy = policy();
+        let now = Utc::now().trunc_subsecs(0);
+
+        assert_eq!(
+            policy
+                .acquire(&db, connection_id, "refresher", now)
+                .await
+                .unwrap(),
+            LockOutcome::Acquired
+        );
+        let contended = policy
+            .acquire(&db, connection_id, "executor-1", now)
+            .await
+            .unwrap();
+        assert!(matches!(
+            contended,
+            LockOutcome::Held { holder: Some(ref holder), .. } if holder == "refresher"
+        ));
+
+        // Releasing by a worker that does not hold the lock changes nothing
+        policy
+            .release(&db, connection_id, "executor-1", now)
+            .await
+            .unwrap();
+        assert!(matches!(
+            policy
+                .acquire(&db, connection_id, "executor-1", now)
+                .await
+                .unwrap(),
+            LockOutcome::Held { .. }
+        ));
+
+        policy
+            .release(&db, connection_id, "refresher", now)
+            .await
+            .unwrap();
+        assert_eq!(
+            policy
+                .acquire(&db, connection_id, "executor-1", now)
+                .await
+                .unwrap(),
+            LockOutcome::Acquired
+        );
+
+        // A holder that never releases loses the lock once it lapses
+        let after_ttl = now + Duration::seconds(121);
+        assert_eq!(
+            policy
+                .acquire(&db, connection_id, "refresher", after_ttl)
+                .await
+                .unwrap(),
+            LockOutcome::Acquired
+        );
+    }
+
+    #[tokio::test]
+    async fn test_failures_back_off_exponentially_until_success() {
+        let (db, connection_id) = setup().await;
+        let policy = policy();
+        let now = Utc::now().trunc_subsecs(0);
+
+        policy
+            .acquire(&db, connection_id, "refresher", now)
+            .await
+            .unwrap();
+        let first = policy
+            .release_failed(&db, connection_id, "refresher", "HTTP error 503", now)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(first, now + Duration::seconds(60));
+        assert_eq!(
+            policy
+                .acquire(&db, connection_id, "executor-1", now)
+                .await
+                .unwrap(),
+            LockOutcome::BackingOff { retry_at: first }
+        );
+
+        let second = policy
+            .acquire(&db, connection_id, "refresher", first)
+            .await
+            .unwrap();
+        assert_eq!(second, LockOutcome::Acquired);
+        let retry_at = policy
+            .release_failed(&db, connection_id, "refresher", "timeout", first)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(retry_at, first + Duration::seconds(120));
+        let lock = find(&db, connection_id).await.unwrap().unwrap();
+        assert_eq!(lock.consecutive_failures, 2);
+        assert_eq!(lock.last_error.as_deref(), Some("timeout"));
+
+        policy
+            .acquire(&db, connection_id, "refresher", retry_at)
+            .await
+            .unwrap();
+        policy
+            .release(&db, connection_id, "refresher", retry_at)
+            .await
+            .unwrap();
+        let lock = find(&db, connection_id).await.unwrap().unwrap();
+        assert_eq!(lock.consecutive_failures, 0);
+        assert_eq!(lock.retry_at, None);
+        assert_eq!(lock.locked_by, None);
+    }
+}
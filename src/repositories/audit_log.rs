@@ -0,0 +1,131 @@
+//! # Audit Log Repository
+//!
+//! This module provides repository operations for the audit_log_entries table.
+//! Entries are append-only; there is deliberately no update or delete API.
+
+use chrono::{DateTime, Utc};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder,
+    QuerySelect, Set,
+};
+use serde_json::Value as JsonValue;
+use uuid::Uuid;
+
+use crate::error::RepositoryError;
+use crate::models::audit_log_entry::{ActiveModel, Column, Entity, Model};
+use crate::query_stats;
+use tracing::{field::Empty, instrument};
+
+/// Actor recorded for changes made through the operator API
+pub const API_AUDIT_ACTOR: &str = "api:operator";
+
+/// Data for a new audit entry
+#[derive(Debug, Clone)]
+pub struct NewAuditLogEntry {
+    /// Tenant the action affected, if tenant-scoped
+    pub tenant_id: Option<Uuid>,
+    /// Who performed the action
+    pub actor: String,
+    /// Action identifier (e.g., connection.decrypt_token)
+    pub action: String,
+    /// Kind of object the action targeted
+    pub target_type: String,
+    /// Identifier of the targeted object
+    pub target_id: String,
+    /// Justification supplied by the actor
+    pub reason: Option<String>,
+    /// Additional structured context; must not contain secret values
+    pub details: Option<JsonValue>,
+}
+
+/// Repository for audit log database operations
+pub struct AuditLogRepository {
+    db: DatabaseConnection,
+}
+
+impl AuditLogRepository {
+    /// Create a new AuditLogRepository with the given database connection
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    /// Append an entry to the audit log
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
+    pub async fn record(&self, entry: NewAuditLogEntry) -> Result<Model, RepositoryError> {
+        ActiveModel {
+            id: Set(Uuid::new_v4()),
+            tenant_id: Set(entry.tenant_id),
+            actor: Set(entry.actor),
+            action: Set(entry.action),
+            target_type: Set(entry.target_type),
+            target_id: Set(entry.target_id),
+            reason: Set(entry.reason),
+            details: Set(entry.details),
+            created_at: Set(Utc::now().fixed_offset()),
+        }
+        .insert(&self.db)
+        .await
+        .map_err(RepositoryError::database_error)
+    }
+
+    /// Append an entry, logging instead of failing when it cannot be written.
+    ///
+    /// Used after the audited change has committed, so a lost entry never turns a
+    /// successful change into an error response.
+    pub async fn record_or_log(&self, entry: NewAuditLogEntry) {
+        let action = entry.action.clone();
+        let target_id = entry.target_id.clone();
+        if let Err(err) = self.record(entry).await {
+            tracing::error!(
+                action = %action,
+                target_id = %target_id,
+                error = %err,
+                "Failed to write audit log entry"
+            );
+        }
+    }
+
+    /// List a tenant's entries with one of `actions`, newest first.
+    ///
+    /// `before` pages backwards through older entries.
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
+    pub async fn list_for_tenant(
+        &self,
+        tenant_id: Uuid,
+        actions: &[&str],
+        before: Option<DateTime<Utc>>,
+        limit: u64,
+    ) -> Result<Vec<Model>, RepositoryError> {
+        let mut query = Entity::find()
+            .filter(Column::TenantId.eq(tenant_id))
+            .filter(Column::Action.is_in(actions.iter().copied()));
+        if let Some(before) = before {
+            query = query.filter(Column::CreatedAt.lt(before.fixed_offset()));
+        }
+        query
+            .order_by_desc(Column::CreatedAt)
+            .order_by_desc(Column::Id)
+            .limit(limit)
+            .all(&self.db)
+            .await
+            .inspect(|rows| query_stats::record_rows(rows.len() as u64))
+            .map_err(RepositoryError::database_error)
+    }
+
+    /// List entries recorded against a target, newest first
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
+    pub async fn list_for_target(
+        &self,
+        target_type: &str,
+        target_id: &str,
+    ) -> Result<Vec<Model>, RepositoryError> {
+        Entity::find()
+            .filter(Column::TargetType.eq(target_type))
+            .filter(Column::TargetId.eq(target_id))
+            .order_by_desc(Column::CreatedAt)
+            .all(&self.db)
+            .await
+            .inspect(|rows| query_stats::record_rows(rows.len() as u64))
+            .map_err(RepositoryError::database_error)
+    }
+}
@@ -2,66 +2,110 @@
 //!
 //! This module provides the ConnectionRepository struct which encapsulates
 //! SeaORM operations for the connections table with tenant-aware methods
-//! and cursor-based pagination.
+//! and cursor-based pagination. Token ciphertexts are read and written through
+//! [`ConnectionSecretRepository`]; lookups here return connections without them.
+//! New tokens are encrypted with per-record keys wrapped by the tenant's data key
+//! from [`TenantKeyRepository`], and tagged with the current master key version.
 
 use anyhow::{Result, anyhow};
 use chrono::{DateTime, Utc};
 use sea_orm::prelude::DateTimeWithTimeZone;
 use sea_orm::{
     ActiveModelTrait, ColumnTrait, Condition, DatabaseConnection, EntityTrait, QueryFilter,
-    QueryOrder, QuerySelect, Set,
+    QueryOrder, QuerySelect, Set, TransactionTrait,
 };
 use std::sync::Arc;
 use uuid::Uuid;
 
 use crate::crypto::{
-    CryptoKey, decrypt_connection_tokens, encrypt_connection_tokens, is_encrypted_payload,
+    MasterKeyring, decrypt_connection_tokens_with_keyring,
+    encrypt_connection_tokens_with_tenant_key, is_encrypted_payload, uses_tenant_key,
 };
 use crate::cursor::{decode_generic_cursor, encode_generic_cursor};
+use crate::db::with_retry;
 use crate::models::connection::{self, Entity as Connection};
+use crate::query_stats;
+use crate::repositories::{ConnectionSecretRepository, TenantKeyRepository};
+use tracing::{field::Empty, instrument};
 
 /// Repository for connection database operations
 #[derive(Debug, Clone)]
 pub struct ConnectionRepository {
     /// Database connection pool
     pub db: Arc<DatabaseConnection>,
-    /// Crypto key for token encryption
-    pub crypto_key: CryptoKey,
+    /// Master key versions that wrap the tenant data keys
+    pub master_keys: MasterKeyring,
 }
 
 impl ConnectionRepository {
     /// Creates a new ConnectionRepository instance
-    pub fn new(db: Arc<DatabaseConnection>, crypto_key: CryptoKey) -> Self {
-        Self { db, crypto_key }
+    pub fn new(db: Arc<DatabaseConnection>, master_keys: impl Into<MasterKeyring>) -> Self {
+        Self {
+            db,
+            master_keys: master_keys.into(),
+        }
+    }
+
+    /// Repository for the token ciphertexts of these connections
+    pub fn secrets(&self) -> ConnectionSecretRepository {
+        ConnectionSecretRepository::new(self.db.clone())
+    }
+
+    /// Repository for the per-tenant data keys that encrypt these connections' tokens
+    pub fn tenant_keys(&self) -> TenantKeyRepository {
+        TenantKeyRepository::new(self.db.clone(), self.master_keys.clone())
+    }
+
+    /// Returns the connection with its stored token ciphertexts attached
+    pub async fn with_secrets(
+        &self,
+        mut connection: connection::Model,
+    ) -> Result<connection::Model> {
+        self.secrets().hydrate(&mut connection).await?;
+        Ok(connection)
     }
 
-    /// Encrypts tokens and updates connection with encrypted ciphertexts
+    /// Encrypts tokens and updates connection with encrypted ciphertexts and, if given, a new expiry
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
     pub async fn encrypt_and_update_tokens(
         &self,
         connection_id: &Uuid,
         access_token: Option<&str>,
         refresh_token: Option<&str>,
+        expires_at: Option<DateTime<Utc>>,
     ) -> Result<connection::Model> {
         let connection = self
             .get_by_id(connection_id)
             .await?
             .ok_or_else(|| anyhow!("Connection with ID '{}' not found", connection_id))?;
 
+        let tenant_key = self
+            .tenant_keys()
+            .get_or_create(&connection.tenant_id)
+            .await?;
         let (encrypted_access_token, encrypted_refresh_token) =
-            encrypt_connection_tokens(&self.crypto_key, &connection, access_token, refresh_token)
-                .map_err(|e| anyhow!("Token encryption failed: {}", e))?;
-
-        self.update_tokens_status(
-            connection_id,
-            encrypted_access_token,
-            encrypted_refresh_token,
-            None,
-            None,
-        )
-        .await
+            encrypt_connection_tokens_with_tenant_key(
+                &tenant_key,
+                &connection,
+                access_token,
+                refresh_token,
+            )
+            .map_err(|e| anyhow!("Token encryption failed: {}", e))?;
+
+        self.secrets()
+            .upsert_with_key_version(
+                connection_id,
+                encrypted_access_token,
+                encrypted_refresh_token,
+                self.master_keys.current_version(),
+            )
+            .await?;
+        self.update_status_and_expiry(connection_id, None, expires_at)
+            .await
     }
 
     /// Decrypts tokens from a connection model
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
     pub async fn decrypt_tokens(
         &self,
         connection: &connection::Model,
@@ -87,8 +131,26 @@ impl ConnectionRepository {
             );
         }
 
+        let needs_tenant_key = [
+            &connection.access_token_ciphertext,
+            &connection.refresh_token_ciphertext,
+        ]
+        .into_iter()
+        .flatten()
+        .any(|token| uses_tenant_key(token));
+        let tenant_key = if needs_tenant_key {
+            self.tenant_keys().find(&connection.tenant_id).await?
+        } else {
+            None
+        };
+
         let (decrypted_access_token, decrypted_refresh_token) =
-            decrypt_connection_tokens(&self.crypto_key, connection).map_err(|e| {
+            decrypt_connection_tokens_with_keyring(
+                &self.master_keys,
+                tenant_key.as_ref(),
+                connection,
+            )
+            .map_err(|e| {
                 // Log decryption failures as generic auth errors without details
                 tracing::error!(
                     tenant_id = %connection.tenant_id,
@@ -107,9 +169,10 @@ impl ConnectionRepository {
     }
 
     /// Creates a connection with encrypted tokens
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
     pub async fn create_with_tokens(
         &self,
-        mut connection: connection::ActiveModel,
+        connection: connection::ActiveModel,
         access_token: Option<&str>,
         refresh_token: Option<&str>,
     ) -> Result<connection::Model> {
@@ -129,6 +192,8 @@ impl ConnectionRepository {
             display_name: None, // Not needed for AAD generation
             access_token_ciphertext: None,
             refresh_token_ciphertext: None,
+            access_token: None,
+            refresh_token: None,
             expires_at: None, // Not needed for AAD generation
             scopes: None,     // Not needed for AAD generation
             metadata: None,   // Not needed for AAD generation
@@ -136,71 +201,97 @@ impl ConnectionRepository {
             updated_at: chrono::Utc::now().into(),
         };
 
-        // Encrypt tokens
-        let (encrypted_access_token, encrypted_refresh_token) = encrypt_connection_tokens(
-            &self.crypto_key,
-            &temp_connection,
-            access_token,
-            refresh_token,
+        // Encrypt tokens with the tenant's data key
+        let tenant_key = self
+            .tenant_keys()
+            .get_or_create(&temp_connection.tenant_id)
+            .await?;
+        let (encrypted_access_token, encrypted_refresh_token) =
+            encrypt_connection_tokens_with_tenant_key(
+                &tenant_key,
+                &temp_connection,
+                access_token,
+                refresh_token,
+            )
+            .map_err(|e| anyhow!("Token encryption failed: {}", e))?;
+
+        // Save connection and its ciphertexts together, so no connection is left without tokens
+        let txn = self.db.begin().await?;
+        connection.insert(&txn).await?;
+        ConnectionSecretRepository::upsert_with_key_version_on(
+            &txn,
+            &connection_id,
+            encrypted_access_token,
+            encrypted_refresh_token,
+            self.master_keys.current_version(),
         )
-        .map_err(|e| anyhow!("Token encryption failed: {}", e))?;
-
-        // Set encrypted ciphertexts
-        connection.access_token_ciphertext = Set(encrypted_access_token);
-        connection.refresh_token_ciphertext = Set(encrypted_refresh_token);
-
-        // Save connection
-        let active = connection;
-        active.insert(&*self.db).await?;
+        .await?;
+        txn.commit().await?;
 
         // For SQLite, query the record directly since we already know the ID
-        let fetched = Connection::find_by_id(connection_id).one(&*self.db).await?;
-        fetched.ok_or_else(|| anyhow!("connection not persisted"))
+        let fetched = Connection::find_by_id(connection_id)
+            .one(&*self.db)
+            .await?
+            .ok_or_else(|| anyhow!("connection not persisted"))?;
+        self.with_secrets(fetched).await
     }
 
     /// Finds a connection by its ID within a tenant scope
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
     pub async fn find_by_id(
         &self,
         tenant_id: &Uuid,
         id: &Uuid,
     ) -> Result<Option<connection::Model>> {
-        Ok(Connection::find_by_id(*id)
-            .filter(connection::Column::TenantId.eq(*tenant_id))
-            .one(&*self.db)
-            .await?)
+        Ok(with_retry(|| {
+            Connection::find_by_id(*id)
+                .filter(connection::Column::TenantId.eq(*tenant_id))
+                .one(&*self.db)
+        })
+        .await?)
     }
 
     /// Retrieves a connection by its ID without tenant scoping
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
     pub async fn get_by_id(&self, id: &Uuid) -> Result<Option<connection::Model>> {
-        Ok(Connection::find_by_id(*id).one(&*self.db).await?)
+        Ok(with_retry(|| Connection::find_by_id(*id).one(&*self.db)).await?)
     }
 
     /// Lists all connections for a tenant ordered by creation time then ID
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
     pub async fn find_by_tenant(&self, tenant_id: &Uuid) -> Result<Vec<connection::Model>> {
-        Ok(Connection::find()
-            .filter(connection::Column::TenantId.eq(*tenant_id))
-            .order_by_asc(connection::Column::CreatedAt)
-            .order_by_asc(connection::Column::Id)
-            .all(&*self.db)
-            .await?)
+        Ok(with_retry(|| {
+            Connection::find()
+                .filter(connection::Column::TenantId.eq(*tenant_id))
+                .order_by_asc(connection::Column::CreatedAt)
+                .order_by_asc(connection::Column::Id)
+                .all(&*self.db)
+        })
+        .await
+        .inspect(|rows| query_stats::record_rows(rows.len() as u64))?)
     }
 
     /// Lists all connections for a tenant/provider pair ordered by creation time then ID
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
     pub async fn find_by_tenant_and_provider(
         &self,
         tenant_id: &Uuid,
         provider_slug: &str,
     ) -> Result<Vec<connection::Model>> {
-        Ok(Connection::find()
-            .filter(connection::Column::TenantId.eq(*tenant_id))
-            .filter(connection::Column::ProviderSlug.eq(provider_slug))
-            .order_by_asc(connection::Column::CreatedAt)
-            .order_by_asc(connection::Column::Id)
-            .all(&*self.db)
-            .await?)
+        Ok(with_retry(|| {
+            Connection::find()
+                .filter(connection::Column::TenantId.eq(*tenant_id))
+                .filter(connection::Column::ProviderSlug.eq(provider_slug))
+                .order_by_asc(connection::Column::CreatedAt)
+                .order_by_asc(connection::Column::Id)
+                .all(&*self.db)
+        })
+        .await
+        .inspect(|rows| query_stats::record_rows(rows.len() as u64))?)
     }
 
     /// Finds a connection by its unique `(tenant, provider, external_id)` tuple
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
     pub async fn find_by_external_id(
         &self,
         tenant_id: &Uuid,
@@ -216,6 +307,7 @@ impl ConnectionRepository {
     }
 
     /// Alias for spec wording (`find_by_unique`)
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
     pub async fn find_by_unique(
         &self,
         tenant_id: &Uuid,
@@ -227,6 +319,7 @@ impl ConnectionRepository {
     }
 
     /// Creates a new connection record
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
     pub async fn create(&self, connection: connection::ActiveModel) -> Result<connection::Model> {
         let id = connection
             .id
@@ -243,6 +336,7 @@ impl ConnectionRepository {
     }
 
     /// Updates mutable fields on a connection within a tenant scope
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
     pub async fn update_by_id(
         &self,
         tenant_id: &Uuid,
@@ -266,12 +360,6 @@ impl ConnectionRepository {
         if let Some(status) = update.status.clone().take() {
             model.status = Set(status);
         }
-        if let Some(access_cipher) = update.access_token_ciphertext.clone().take() {
-            model.access_token_ciphertext = Set(access_cipher);
-        }
-        if let Some(refresh_cipher) = update.refresh_token_ciphertext.clone().take() {
-            model.refresh_token_ciphertext = Set(refresh_cipher);
-        }
         if let Some(expires_at) = update.expires_at.clone().take() {
             model.expires_at = Set(expires_at);
         }
@@ -285,12 +373,11 @@ impl ConnectionRepository {
         Ok(model.update(&*self.db).await?)
     }
 
-    /// Partial update helper for tokens/status/expiry mutations
-    pub async fn update_tokens_status(
+    /// Partial update helper for status/expiry mutations; returns the connection with secrets attached
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
+    pub async fn update_status_and_expiry(
         &self,
         id: &Uuid,
-        access_token_ciphertext: Option<Vec<u8>>,
-        refresh_token_ciphertext: Option<Vec<u8>>,
         status: Option<String>,
         expires_at: Option<DateTime<Utc>>,
     ) -> Result<connection::Model> {
@@ -300,13 +387,6 @@ impl ConnectionRepository {
             .ok_or_else(|| anyhow!("Connection '{}' not found", id))?;
 
         let mut model: connection::ActiveModel = existing.into();
-
-        if let Some(cipher) = access_token_ciphertext {
-            model.access_token_ciphertext = Set(Some(cipher));
-        }
-        if let Some(cipher) = refresh_token_ciphertext {
-            model.refresh_token_ciphertext = Set(Some(cipher));
-        }
         if let Some(status) = status {
             model.status = Set(status);
         }
@@ -314,17 +394,21 @@ impl ConnectionRepository {
             let fixed: DateTimeWithTimeZone = expires_at.into();
             model.expires_at = Set(Some(fixed));
         }
+        model.updated_at = Set(Utc::now().into());
 
-        Ok(model.update(&*self.db).await?)
+        let updated = model.update(&*self.db).await?;
+        self.with_secrets(updated).await
     }
 
     /// Deletes a connection within a tenant scope
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
     pub async fn delete_by_id(&self, tenant_id: &Uuid, id: &Uuid) -> Result<()> {
         let result = Connection::delete_by_id(*id)
             .filter(connection::Column::TenantId.eq(*tenant_id))
             .exec(&*self.db)
             .await?;
 
+        query_stats::record_rows(result.rows_affected);
         if result.rows_affected == 0 {
             return Err(anyhow!("Connection with ID '{}' not found for tenant", id));
         }
@@ -333,6 +417,7 @@ impl ConnectionRepository {
     }
 
     /// Lists all connections for a tenant with cursor pagination
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
     pub async fn list_by_tenant(
         &self,
         tenant_id: &Uuid,
@@ -363,6 +448,7 @@ impl ConnectionRepository {
         }
 
         let mut rows = query.limit(limit + 1).all(&*self.db).await?;
+        query_stats::record_rows(rows.len() as u64);
 
         let next_cursor = if rows.len() as u64 > limit {
             // Remove overflow row to get only the items to return
@@ -379,6 +465,7 @@ impl ConnectionRepository {
     }
 
     /// Lists connections for a tenant/provider pair with cursor pagination
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
     pub async fn list_by_tenant_provider(
         &self,
         tenant_id: &Uuid,
@@ -411,6 +498,7 @@ impl ConnectionRepository {
         }
 
         let mut rows = query.limit(limit + 1).all(&*self.db).await?;
+        query_stats::record_rows(rows.len() as u64);
 
         let next_cursor = if rows.len() as u64 > limit {
             // Remove overflow row to get only the items to return
@@ -0,0 +1,268 @@
+//! Connection secret repository for token ciphertext storage
+//!
+//! Token ciphertexts live in the `connection_secrets` table, apart from the
+//! connection rows that listings, logs and diagnostics read. This repository is
+//! the only path to them: code that needs tokens hydrates a connection
+//! explicitly, so a plain connection query can never return secrets.
+
+use anyhow::Result;
+use sea_orm::sea_query::{Expr, OnConflict};
+use sea_orm::{
+    ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, QueryFilter, QuerySelect, Set,
+};
+use std::collections::HashMap;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::db::with_retry;
+use crate::models::connection;
+use crate::models::connection_secret::{self, Entity as ConnectionSecret};
+use tracing::{field::Empty, instrument};
+
+/// Whether a connection has an access and a refresh token stored
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TokenPresence {
+    pub has_access_token: bool,
+    pub has_refresh_token: bool,
+}
+
+/// Repository for connection token ciphertexts
+#[derive(Debug, Clone)]
+pub struct ConnectionSecretRepository {
+    db: Arc<DatabaseConnection>,
+}
+
+impl ConnectionSecretRepository {
+    /// Creates a new ConnectionSecretRepository instance
+    pub fn new(db: Arc<DatabaseConnection>) -> Self {
+        Self { db }
+    }
+
+    /// Loads the stored ciphertexts for a connection
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
+    pub async fn find(&self, connection_id: &Uuid) -> Result<Option<connection_secret::Model>> {
+        Ok(with_retry(|| ConnectionSecret::find_by_id(*connection_id).one(&*self.db)).await?)
+    }
+
+    /// Copies the stored ciphertexts onto an in-memory connection model
+    pub async fn hydrate(&self, connection: &mut connection::Model) -> Result<()> {
+        let secret = self.find(&connection.id).await?;
+        connection.access_token_ciphertext = secret
+            .as_ref()
+            .and_then(|s| s.access_token_ciphertext.clone());
+        connection.refresh_token_ciphertext = secret.and_then(|s| s.refresh_token_ciphertext);
+        Ok(())
+    }
+
+    /// Stores ciphertexts for a connection; `None` leaves the existing value unchanged
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
+    pub async fn upsert(
+        &self,
+        connection_id: &Uuid,
+        access_token_ciphertext: Option<Vec<u8>>,
+        refresh_token_ciphertext: Option<Vec<u8>>,
+    ) -> Result<()> {
+        Self::store(
+            &*self.db,
+            connection_id,
+            access_token_ciphertext,
+            refresh_token_ciphertext,
+            None,
+        )
+        .await
+    }
+
+    /// Stores envelope ciphertexts along with the master key version they were written under
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
+    pub async fn upsert_with_key_version(
+        &self,
+        connection_id: &Uuid,
+        access_token_ciphertext: Option<Vec<u8>>,
+        refresh_token_ciphertext: Option<Vec<u8>>,
+        key_version: i32,
+    ) -> Result<()> {
+        Self::store(
+            &*self.db,
+            connection_id,
+            access_token_ciphertext,
+            refresh_token_ciphertext,
+            Some(key_version),
+        )
+        .await
+    }
+
+    /// Stores envelope ciphertexts along with their master key version on `conn`
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
+    pub async fn upsert_with_key_version_on<C: ConnectionTrait>(
+        conn: &C,
+        connection_id: &Uuid,
+        access_token_ciphertext: Option<Vec<u8>>,
+        refresh_token_ciphertext: Option<Vec<u8>>,
+        key_version: i32,
+    ) -> Result<()> {
+        Self::store(
+            conn,
+            connection_id,
+            access_token_ciphertext,
+            refresh_token_ciphertext,
+            Some(key_version),
+        )
+        .await
+    }
+
+    // A replaced ciphertext always takes the given key version, so NULL marks unversioned payloads
+    async fn store<C: ConnectionTrait>(
+        conn: &C,
+        connection_id: &Uuid,
+        access_token_ciphertext: Option<Vec<u8>>,
+        refresh_token_ciphertext: Option<Vec<u8>>,
+        key_version: Option<i32>,
+    ) -> Result<()> {
+        if access_token_ciphertext.is_none() && refresh_token_ciphertext.is_none() {
+            return Ok(());
+        }
+
+        let now = chrono::Utc::now().fixed_offset();
+        let mut update_columns = vec![connection_secret::Column::UpdatedAt];
+        if access_token_ciphertext.is_some() {
+            update_columns.push(connection_secret::Column::AccessTokenCiphertext);
+            update_columns.push(connection_secret::Column::AccessTokenKeyVersion);
+        }
+        if refresh_token_ciphertext.is_some() {
+            update_columns.push(connection_secret::Column::RefreshTokenCiphertext);
+            update_columns.push(connection_secret::Column::RefreshTokenKeyVersion);
+        }
+
+        let secret = connection_secret::ActiveModel {
+            connection_id: Set(*connection_id),
+            access_token_key_version: Set(key_version.filter(|_| access_token_ciphertext.is_some())),
+            refresh_token_key_version: Set(
+                key_version.filter(|_| refresh_token_ciphertext.is_some())
+            ),
+            access_token_ciphertext: Set(access_token_ciphertext),
+            refresh_token_ciphertext: Set(refresh_token_ciphertext),
+            created_at: Set(now),
+            updated_at: Set(now),
+        };
+        ConnectionSecret::insert(secret)
+            .on_conflict(
+                OnConflict::column(connection_secret::Column::ConnectionId)
+                    .update_columns(update_columns)
+                    .to_owned(),
+            )
+            .exec_without_returning(conn)
+            .await?;
+        Ok(())
+    }
+
+    /// Reports which tokens are stored for each connection without reading the ciphertexts
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
+    pub async fn presence(&self, connection_ids: &[Uuid]) -> Result<HashMap<Uuid, TokenPresence>> {
+        if connection_ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let rows: Vec<(Uuid, bool, bool)> = ConnectionSecret::find()
+            .select_only()
+            .column(connection_secret::Column::ConnectionId)
+            .expr(Expr::col(connection_secret::Column::AccessTokenCiphertext).is_not_null())
+            .expr(Expr::col(connection_secret::Column::RefreshTokenCiphertext).is_not_null())
+            .filter(connection_secret::Column::ConnectionId.is_in(connection_ids.iter().copied()))
+            .into_tuple()
+            .all(&*self.db)
+            .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(id, has_access_token, has_refresh_token)| {
+                (
+                    id,
+                    TokenPresence {
+                        has_access_token,
+                        has_refresh_token,
+                    },
+                )
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::connection::Entity as Connection;
+    use crate::test_fixtures;
+
+    async fn setup() -> (Arc<DatabaseConnection>, Uuid) {
+        let (db, _, connection_id) = test_fixtures::setup_connection().await;
+        (Arc::new(db), connection_id)
+    }
+
+    #[tokio::test]
+    async fn test_secrets_are_only_visible_through_repository() {
+        let (db, connection_id) = setup().await;
+        let repo = ConnectionSecretRepository::new(db.clone());
+
+        repo.upsert(
+            &connection_id,
+            Some(b"access-1".to_vec()),
+            Some(b"refresh-1".to_vec()),
+        )
+        .await
+        .unwrap();
+        // Rotating only the access token keeps the stored refresh token
+        repo.upsert(&connection_id, Some(b"access-2".to_vec()), None)
+            .await
+            .unwrap();
+
+        let mut plain = Connection::find_by_id(connection_id)
+            .one(&*db)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(plain.access_token_ciphertext.is_none());
+        assert!(plain.refresh_token_ciphertext.is_none());
+        assert!(format!("{:?}", repo.find(&connection_id).await.unwrap()).contains("[REDACTED]"));
+
+        repo.hydrate(&mut plain).await.unwrap();
+        assert_eq!(plain.access_token_ciphertext, Some(b"access-2".to_vec()));
+        assert_eq!(plain.refresh_token_ciphertext, Some(b"refresh-1".to_vec()));
+
+        let other = Uuid::new_v4();
+        let presence = repo.presence(&[connection_id, other]).await.unwrap();
+        assert_eq!(
+            presence.get(&connection_id),
+            Some(&TokenPresence {
+                has_access_token: true,
+                has_refresh_token: true,
+            })
+        );
+        assert!(!presence.contains_key(&other));
+    }
+
+    #[tokio::test]
+    async fn test_key_versions_follow_their_ciphertexts() {
+        let (db, connection_id) = setup().await;
+        let repo = ConnectionSecretRepository::new(db);
+
+        repo.upsert_with_key_version(
+            &connection_id,
+            Some(b"access-1".to_vec()),
+            Some(b"refresh-1".to_vec()),
+            2,
+        )
+        .await
+        .unwrap();
+        let secret = repo.find(&connection_id).await.unwrap().unwrap();
+        assert_eq!(secret.access_token_key_version, Some(2));
+        assert_eq!(secret.refresh_token_key_version, Some(2));
+
+        // An unversioned write clears only the version of the token it replaces
+        repo.upsert(&connection_id, Some(b"access-2".to_vec()), None)
+            .await
+            .unwrap();
+        let secret = repo.find(&connection_id).await.unwrap().unwrap();
+        assert_eq!(secret.access_token_key_version, None);
+        assert_eq!(secret.refresh_token_key_version, Some(2));
+    }
+}
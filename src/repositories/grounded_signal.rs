@@ -8,11 +8,13 @@ use crate::models::grounded_signal::{
     ActiveModel as GroundedSignalActiveModel, Entity as GroundedSignal, GroundedSignalResponse,
     GroundedSignalStatus, Model as GroundedSignalModel,
 };
+use crate::query_stats;
 use sea_orm::{
     ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, ModelTrait, PaginatorTrait,
     QueryFilter, QueryOrder, QuerySelect, Set,
 };
 use serde::{Deserialize, Serialize};
+use tracing::{field::Empty, instrument};
 use utoipa::ToSchema;
 use uuid::Uuid;
 
@@ -59,6 +61,7 @@ impl<'a> GroundedSignalRepository<'a> {
     ///   with the same tenant_id and idempotency_key and return it if found.
     /// - Otherwise, it will insert a new grounded signal.
     #[allow(clippy::too_many_arguments)]
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
     pub async fn create(
         &self,
         signal_id: Uuid,
@@ -109,6 +112,7 @@ impl<'a> GroundedSignalRepository<'a> {
     }
 
     /// Update grounded signal status and recommendation
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
     pub async fn update_status(
         &self,
         id: Uuid,
@@ -138,6 +142,7 @@ impl<'a> GroundedSignalRepository<'a> {
     }
 
     /// List grounded signals with filters and pagination
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
     pub async fn list(
         &self,
         query: ListGroundedSignalsQuery,
@@ -172,6 +177,7 @@ impl<'a> GroundedSignalRepository<'a> {
             .limit(limit as u64)
             .all(self.db)
             .await
+            .inspect(|rows| query_stats::record_rows(rows.len() as u64))
             .map_err(RepositoryError::database_error)?;
 
         let data: Vec<GroundedSignalResponse> =
@@ -190,6 +196,7 @@ impl<'a> GroundedSignalRepository<'a> {
     }
 
     /// Get grounded signal by ID
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
     pub async fn get_by_id(
         &self,
         id: Uuid,
@@ -203,6 +210,7 @@ impl<'a> GroundedSignalRepository<'a> {
     }
 
     /// Get grounded signals by original signal ID
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
     pub async fn get_by_signal_id(
         &self,
         signal_id: Uuid,
@@ -211,12 +219,33 @@ impl<'a> GroundedSignalRepository<'a> {
             .filter(crate::models::grounded_signal::Column::SignalId.eq(signal_id))
             .all(self.db)
             .await
+            .inspect(|rows| query_stats::record_rows(rows.len() as u64))
             .map_err(RepositoryError::database_error)?;
 
         Ok(results.into_iter().map(|model| model.into()).collect())
     }
 
+    /// List a tenant's grounded signals created at or after `since`, oldest first
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
+    pub async fn list_created_since(
+        &self,
+        tenant_id: Uuid,
+        since: chrono::DateTime<chrono::FixedOffset>,
+        limit: u64,
+    ) -> Result<Vec<GroundedSignalModel>, RepositoryError> {
+        GroundedSignal::find()
+            .filter(crate::models::grounded_signal::Column::TenantId.eq(tenant_id))
+            .filter(crate::models::grounded_signal::Column::CreatedAt.gte(since))
+            .order_by_asc(crate::models::grounded_signal::Column::CreatedAt)
+            .limit(limit)
+            .all(self.db)
+            .await
+            .inspect(|rows| query_stats::record_rows(rows.len() as u64))
+            .map_err(RepositoryError::database_error)
+    }
+
     /// Get pending grounded signals for background processing
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
     pub async fn get_pending_signals(
         &self,
         limit: i64,
@@ -227,12 +256,14 @@ impl<'a> GroundedSignalRepository<'a> {
             .limit(limit as u64)
             .all(self.db)
             .await
+            .inspect(|rows| query_stats::record_rows(rows.len() as u64))
             .map_err(RepositoryError::database_error)?;
 
         Ok(results)
     }
 
     /// Delete grounded signal by ID
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
     pub async fn delete(&self, id: Uuid) -> Result<(), RepositoryError> {
         let grounded_signal = GroundedSignal::find_by_id(id)
             .one(self.db)
@@ -247,6 +278,18 @@ impl<'a> GroundedSignalRepository<'a> {
 
         Ok(())
     }
+
+    /// Delete all of a tenant's grounded signals, returning how many were removed
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
+    pub async fn delete_for_tenant(&self, tenant_id: Uuid) -> Result<u64, RepositoryError> {
+        let result = GroundedSignal::delete_many()
+            .filter(crate::models::grounded_signal::Column::TenantId.eq(tenant_id))
+            .exec(self.db)
+            .await
+            .map_err(RepositoryError::database_error)?;
+
+        Ok(result.rows_affected)
+    }
 }
 
 #[cfg(test)]
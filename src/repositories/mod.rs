@@ -3,24 +3,56 @@
 //! This module contains repository implementations that encapsulate SeaORM operations
 //! for database entities, providing a clean API for data access with tenant-aware methods.
 
+pub mod audit_log;
 pub mod connection;
+pub mod connection_secret;
 pub mod grounded_signal;
 pub mod oauth_state;
+pub mod organization;
 pub mod provider;
+pub mod rollup;
 pub mod signal;
+pub mod signal_outbox;
+pub mod signal_pipeline_event;
+pub mod slo_outcome;
 pub mod sync_job;
+pub mod sync_job_run;
 pub mod sync_metadata;
 pub mod tenant;
+pub mod tenant_config;
+pub mod tenant_key;
 pub mod tenant_signal_config;
+pub mod tenant_signal_kind;
+pub mod token_event;
+pub mod usage;
 
+pub use audit_log::{AuditLogRepository, NewAuditLogEntry};
 pub use connection::ConnectionRepository;
+pub use connection_secret::{ConnectionSecretRepository, TokenPresence};
 pub use grounded_signal::{
     GroundedSignalRepository, ListGroundedSignalsQuery, ListGroundedSignalsResponse, PaginationInfo,
 };
 pub use oauth_state::OAuthStateRepository;
+pub use organization::OrganizationRepository;
 pub use provider::ProviderRepository;
+pub use rollup::{RollupRepository, RollupSummary};
 pub use signal::SignalRepository;
-pub use sync_job::{ListJobsConfig, ListJobsResult, SyncJobRepository};
+pub use signal_outbox::SignalOutboxRepository;
+pub use signal_pipeline_event::SignalPipelineEventRepository;
+pub use slo_outcome::{SloOutcomeRepository, SloOutcomeTotals};
+pub use sync_job::{
+    CoalescePolicy, EnqueueOutcome, ListJobsConfig, ListJobsResult, PriorityAging,
+    SyncJobRepository,
+};
+pub use sync_job_run::{NewSyncJobRun, SyncJobRunRepository};
 pub use sync_metadata::{ConnectionSyncMetadata, MIN_SYNC_INTERVAL_SECONDS};
-pub use tenant::{TenantRepository, CreateTenantRequest};
+pub use tenant::{
+    BootstrappedTenant, ClonedSandbox, CreateTenantRequest, SandboxClone, SandboxSignalSample,
+    TenantBootstrap, TenantRepository,
+};
+pub use tenant_config::TenantConfigRepository;
+pub use tenant_key::TenantKeyRepository;
 pub use tenant_signal_config::TenantSignalConfigRepository;
+pub use tenant_signal_kind::TenantSignalKindRepository;
+pub use token_event::{NewTokenEvent, TokenEventRepository};
+pub use usage::{TenantUsage, UsageRepository};
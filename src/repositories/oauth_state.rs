@@ -2,27 +2,41 @@
 //!
 //! This module provides database operations for OAuth state management.
 
-use chrono::{Duration, Utc};
+use chrono::Duration;
 use sea_orm::{
     ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, PaginatorTrait, QueryFilter, Set,
 };
 use std::sync::Arc;
 use uuid::Uuid;
 
+use crate::clock::{SharedClock, system_clock};
 use crate::models::oauth_state::{self, ActiveModel, Entity, Model};
+use crate::query_stats;
+use tracing::{field::Empty, instrument};
 
 /// Repository for OAuth state database operations
 pub struct OAuthStateRepository {
     db: Arc<DatabaseConnection>,
+    clock: SharedClock,
 }
 
 impl OAuthStateRepository {
     /// Create a new OAuth state repository
     pub fn new(db: Arc<DatabaseConnection>) -> Self {
-        Self { db }
+        Self {
+            db,
+            clock: system_clock(),
+        }
+    }
+
+    /// Replace the time source used to stamp and expire states
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
     }
 
     /// Create a new OAuth state record
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
     pub async fn create(
         &self,
         tenant_id: Uuid,
@@ -31,7 +45,7 @@ impl OAuthStateRepository {
         code_verifier: Option<String>,
         expires_in_minutes: i64,
     ) -> Result<Model, sea_orm::DbErr> {
-        let now = Utc::now();
+        let now = self.clock.now();
         let expires_at = now + Duration::minutes(expires_in_minutes);
 
         let new_state = ActiveModel {
@@ -96,6 +110,7 @@ impl OAuthStateRepository {
     }
 
     /// Find OAuth state by tenant, provider, and state token
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
     pub async fn find_by_tenant_provider_state(
         &self,
         tenant_id: Uuid,
@@ -106,7 +121,7 @@ impl OAuthStateRepository {
             .filter(oauth_state::Column::TenantId.eq(tenant_id))
             .filter(oauth_state::Column::Provider.eq(provider))
             .filter(oauth_state::Column::State.eq(state))
-            .filter(oauth_state::Column::ExpiresAt.gt(Utc::now()))
+            .filter(oauth_state::Column::ExpiresAt.gt(self.clock.now()))
             .one(&*self.db)
             .await?;
 
@@ -114,6 +129,7 @@ impl OAuthStateRepository {
     }
 
     /// Find and consume an OAuth state (delete it after retrieval)
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
     pub async fn find_and_consume_by_tenant_provider_state(
         &self,
         tenant_id: Uuid,
@@ -133,6 +149,7 @@ impl OAuthStateRepository {
     }
 
     /// Find OAuth state by provider and state token (without tenant)
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
     pub async fn find_by_provider_state(
         &self,
         provider: &str,
@@ -142,7 +159,7 @@ impl OAuthStateRepository {
             .filter(oauth_state::Column::Provider.eq(provider))
             .filter(oauth_state::Column::State.eq(state))
             // Temporarily disable expires filter for debugging expired state test
-            .filter(oauth_state::Column::ExpiresAt.gt(Utc::now()))
+            .filter(oauth_state::Column::ExpiresAt.gt(self.clock.now()))
             .one(&*self.db)
             .await?;
 
@@ -150,6 +167,7 @@ impl OAuthStateRepository {
     }
 
     /// Find and consume an OAuth state by provider and state token (without tenant)
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
     pub async fn find_and_consume_by_provider_state(
         &self,
         provider: &str,
@@ -166,39 +184,96 @@ impl OAuthStateRepository {
     }
 
     /// Clean up expired OAuth states
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
     pub async fn cleanup_expired(&self) -> Result<u64, sea_orm::DbErr> {
         let result = Entity::delete_many()
-            .filter(oauth_state::Column::ExpiresAt.lt(Utc::now()))
+            .filter(oauth_state::Column::ExpiresAt.lt(self.clock.now()))
             .exec(&*self.db)
             .await?;
 
+        query_stats::record_rows(result.rows_affected);
         Ok(result.rows_affected)
     }
 
     /// Delete a specific OAuth state by ID
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
     pub async fn delete_by_id(&self, id: Uuid) -> Result<bool, sea_orm::DbErr> {
         let result = Entity::delete_by_id(id).exec(&*self.db).await?;
+        query_stats::record_rows(result.rows_affected);
         Ok(result.rows_affected > 0)
     }
 
     /// Delete all OAuth states for a specific tenant
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
     pub async fn delete_by_tenant(&self, tenant_id: Uuid) -> Result<u64, sea_orm::DbErr> {
         let result = Entity::delete_many()
             .filter(oauth_state::Column::TenantId.eq(tenant_id))
             .exec(&*self.db)
             .await?;
 
+        query_stats::record_rows(result.rows_affected);
         Ok(result.rows_affected)
     }
 
     /// Get count of active OAuth states for a tenant
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
     pub async fn count_by_tenant(&self, tenant_id: Uuid) -> Result<u64, sea_orm::DbErr> {
         let count = Entity::find()
             .filter(oauth_state::Column::TenantId.eq(tenant_id))
-            .filter(oauth_state::Column::ExpiresAt.gt(Utc::now()))
+            .filter(oauth_state::Column::ExpiresAt.gt(self.clock.now()))
             .count(&*self.db)
             .await?;
 
         Ok(count)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::MockClock;
+    use chrono::{TimeZone, Utc};
+    use migration::{Migrator, MigratorTrait};
+    use sea_orm::{Database, Statement};
+
+    #[tokio::test]
+    async fn test_state_expires_on_virtual_clock() {
+        let db = Database::connect("sqlite::memory:")
+            .await
+            .expect("create in-memory db");
+        Migrator::up(&db, None).await.expect("apply migrations");
+
+        let tenant_id = Uuid::new_v4();
+        db.execute(Statement::from_sql_and_values(
+            db.get_database_backend(),
+            "INSERT INTO tenants (id, name) VALUES (?, ?)",
+            vec![tenant_id.into(), "Test Tenant".into()],
+        ))
+        .await
+        .expect("insert tenant");
+
+        let clock = MockClock::new(Utc.with_ymd_and_hms(2024, 1, 15, 10, 0, 0).unwrap());
+        let repo = OAuthStateRepository::new(Arc::new(db)).with_clock(clock.clone());
+        repo.create(tenant_id, "github", "state-1", None, 10)
+            .await
+            .unwrap();
+
+        clock.advance(std::time::Duration::from_secs(9 * 60));
+        assert!(
+            repo.find_by_provider_state("github", "state-1")
+                .await
+                .unwrap()
+                .is_some()
+        );
+        assert_eq!(repo.cleanup_expired().await.unwrap(), 0);
+
+        clock.advance(std::time::Duration::from_secs(2 * 60));
+        assert!(
+            repo.find_by_provider_state("github", "state-1")
+                .await
+                .unwrap()
+                .is_none()
+        );
+        assert_eq!(repo.cleanup_expired().await.unwrap(), 1);
+    }
+}
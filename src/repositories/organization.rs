@@ -0,0 +1,96 @@
+//! Organization repository
+//!
+//! Organizations group tenants and hold the default setting overrides their
+//! tenants inherit; see [`crate::config_resolution`].
+
+use chrono::Utc;
+use sea_orm::{ActiveModelTrait, DatabaseConnection, EntityTrait, IntoActiveModel, Set};
+use tracing::{field::Empty, instrument};
+use uuid::Uuid;
+
+use crate::config_resolution::ConfigOverrides;
+use crate::db::with_retry;
+use crate::error::RepositoryError;
+use crate::models::organization::{self, Entity as Organization, Model as OrganizationModel};
+
+/// Repository for Organization database operations
+pub struct OrganizationRepository<'a> {
+    db: &'a DatabaseConnection,
+}
+
+impl<'a> OrganizationRepository<'a> {
+    /// Create a new OrganizationRepository with the given database connection
+    pub fn new(db: &'a DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    /// Create an organization with its default settings
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
+    pub async fn create(
+        &self,
+        name: &str,
+        settings: &ConfigOverrides,
+    ) -> Result<OrganizationModel, RepositoryError> {
+        validate_name(name)?;
+        settings.validate()?;
+
+        let now = Utc::now().fixed_offset();
+        organization::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            name: Set(name.trim().to_string()),
+            settings: Set(settings.to_json()),
+            created_at: Set(now),
+            updated_at: Set(now),
+        }
+        .insert(self.db)
+        .await
+        .map_err(RepositoryError::database_error)
+    }
+
+    /// Get an organization by ID
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
+    pub async fn get(
+        &self,
+        organization_id: Uuid,
+    ) -> Result<Option<OrganizationModel>, RepositoryError> {
+        with_retry(|| Organization::find_by_id(organization_id).one(self.db))
+            .await
+            .map_err(RepositoryError::database_error)
+    }
+
+    /// Replace an organization's default settings
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
+    pub async fn update_settings(
+        &self,
+        organization_id: Uuid,
+        settings: &ConfigOverrides,
+    ) -> Result<OrganizationModel, RepositoryError> {
+        settings.validate()?;
+
+        let organization = self.get(organization_id).await?.ok_or_else(|| {
+            RepositoryError::NotFound(format!("Organization {}", organization_id))
+        })?;
+
+        let mut active = organization.into_active_model();
+        active.settings = Set(settings.to_json());
+        active.updated_at = Set(Utc::now().fixed_offset());
+        active
+            .update(self.db)
+            .await
+            .map_err(RepositoryError::database_error)
+    }
+}
+
+fn validate_name(name: &str) -> Result<(), RepositoryError> {
+    if name.trim().is_empty() {
+        return Err(RepositoryError::validation_error(
+            "Organization name cannot be empty",
+        ));
+    }
+    if name.len() > 255 {
+        return Err(RepositoryError::validation_error(
+            "Organization name cannot exceed 255 characters",
+        ));
+    }
+    Ok(())
+}
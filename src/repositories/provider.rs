@@ -4,10 +4,16 @@
 //! SeaORM operations for the providers table with tenant-aware methods.
 
 use anyhow::Result;
-use sea_orm::{ActiveModelTrait, DatabaseConnection, DbErr, EntityTrait, QueryOrder, Set};
+use sea_orm::{
+    ActiveModelTrait, DatabaseConnection, DbErr, EntityTrait, QueryOrder, Set,
+    sea_query::OnConflict,
+};
 use std::sync::Arc;
 
+use crate::db::with_retry;
 use crate::models::provider::{self, Entity as Provider};
+use crate::query_stats;
+use tracing::{field::Empty, instrument};
 
 /// Repository for provider database operations
 #[derive(Debug, Clone)]
@@ -39,14 +45,14 @@ impl ProviderRepository {
     /// # Returns
     ///
     /// Returns a Result containing the provider model if found, or an error
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
     pub async fn find_by_slug(&self, slug: &str) -> Result<Option<provider::Model>> {
-        let provider = Provider::find_by_id(slug.to_string())
-            .one(&*self.db)
-            .await?;
+        let provider = with_retry(|| Provider::find_by_id(slug.to_string()).one(&*self.db)).await?;
         Ok(provider)
     }
 
     /// Alias matching spec naming
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
     pub async fn get_by_slug(&self, slug: &str) -> Result<Option<provider::Model>> {
         self.find_by_slug(slug).await
     }
@@ -56,15 +62,18 @@ impl ProviderRepository {
     /// # Returns
     ///
     /// Returns a Result containing a vector of all provider models
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
     pub async fn find_all(&self) -> Result<Vec<provider::Model>> {
         let providers = Provider::find()
             .order_by_asc(provider::Column::Slug)
             .all(&*self.db)
             .await?;
+        query_stats::record_rows(providers.len() as u64);
         Ok(providers)
     }
 
     /// Alias matching spec naming
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
     pub async fn list_all(&self) -> Result<Vec<provider::Model>> {
         self.find_all().await
     }
@@ -78,6 +87,7 @@ impl ProviderRepository {
     /// # Returns
     ///
     /// Returns a Result containing the created provider model
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
     pub async fn create(&self, provider: provider::ActiveModel) -> Result<provider::Model> {
         let slug = provider
             .slug
@@ -97,6 +107,40 @@ impl ProviderRepository {
         fetched.ok_or_else(|| anyhow::anyhow!("provider '{}' not persisted", slug))
     }
 
+    /// Inserts a provider unless one with the same slug exists
+    ///
+    /// Safe to run from several processes at once: a concurrent insert of the
+    /// same slug is ignored instead of failing on the primary key.
+    ///
+    /// # Returns
+    ///
+    /// Returns a Result containing true if this call inserted the row
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
+    pub async fn insert_if_absent(
+        &self,
+        slug: &str,
+        display_name: &str,
+        auth_type: &str,
+    ) -> Result<bool> {
+        let now = chrono::Utc::now().fixed_offset();
+        let rows = Provider::insert(provider::ActiveModel {
+            slug: Set(slug.to_string()),
+            display_name: Set(display_name.to_string()),
+            auth_type: Set(auth_type.to_string()),
+            created_at: Set(now),
+            updated_at: Set(now),
+        })
+        .on_conflict(
+            OnConflict::column(provider::Column::Slug)
+                .do_nothing()
+                .to_owned(),
+        )
+        .exec_without_returning(&*self.db)
+        .await?;
+        query_stats::record_rows(rows);
+        Ok(rows == 1)
+    }
+
     /// Updates a provider by its slug
     ///
     /// # Arguments
@@ -107,6 +151,7 @@ impl ProviderRepository {
     /// # Returns
     ///
     /// Returns a Result containing the updated provider model, or an error if not found
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
     pub async fn update_by_slug(
         &self,
         slug: &str,
@@ -135,6 +180,7 @@ impl ProviderRepository {
     }
 
     /// Upsert a provider by slug (spec requirement)
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
     pub async fn upsert(
         &self,
         slug: &str,
@@ -166,11 +212,13 @@ impl ProviderRepository {
     /// # Returns
     ///
     /// Returns a Result indicating success or failure
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
     pub async fn delete_by_slug(&self, slug: &str) -> Result<()> {
         let delete_result = Provider::delete_by_id(slug.to_string())
             .exec(&*self.db)
             .await?;
 
+        query_stats::record_rows(delete_result.rows_affected);
         if delete_result.rows_affected == 0 {
             return Err(anyhow::anyhow!("Provider with slug '{}' not found", slug));
         }
@@ -0,0 +1,377 @@
+//! # Rollup Repository
+//!
+//! This module computes and reads the daily job and signal aggregates stored in
+//! the job_daily_rollups and signal_daily_rollups tables. Computing a day scans
+//! the raw tables once; readers only ever touch the rollup tables.
+
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use sea_orm::{
+    ColumnTrait, Condition, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, QuerySelect,
+    Set, TransactionTrait, prelude::DateTimeWithTimeZone, sea_query::Expr,
+};
+use uuid::Uuid;
+
+use crate::error::RepositoryError;
+use crate::models::job_daily_rollup::{
+    self, ActiveModel as JobRollupActiveModel, Entity as JobRollup,
+};
+use crate::models::signal::{self, Entity as Signal};
+use crate::models::signal_daily_rollup::{
+    self, ActiveModel as SignalRollupActiveModel, Entity as SignalRollup,
+};
+use crate::models::sync_job::{self, Entity as SyncJob};
+use crate::query_stats;
+use tracing::{field::Empty, instrument};
+
+/// Number of rollup rows written for a day
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RollupSummary {
+    /// Rows written to job_daily_rollups
+    pub job_rows: usize,
+    /// Rows written to signal_daily_rollups
+    pub signal_rows: usize,
+}
+
+#[derive(Default)]
+struct JobAccumulator {
+    succeeded: i64,
+    failed: i64,
+    duration_ms_total: f64,
+    duration_samples: u32,
+}
+
+/// Repository for daily rollup computation and queries
+pub struct RollupRepository {
+    db: DatabaseConnection,
+}
+
+impl RollupRepository {
+    /// Create a new RollupRepository with the given database connection
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    /// Recompute all rollups for one UTC day.
+    ///
+    /// Existing rows for the day are replaced inside a single transaction, so the
+    /// computation is idempotent and safe to re-run for late-arriving data.
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
+    pub async fn compute_day(&self, day: NaiveDate) -> Result<RollupSummary, RepositoryError> {
+        let (start, end) = day_bounds(day);
+        let computed_at = Utc::now().fixed_offset();
+
+        let jobs = SyncJob::find()
+            .select_only()
+            .column(sync_job::Column::TenantId)
+            .column(sync_job::Column::ProviderSlug)
+            .column(sync_job::Column::Status)
+            .column(sync_job::Column::StartedAt)
+            .column(sync_job::Column::FinishedAt)
+            .filter(sync_job::Column::Status.is_in(["succeeded", "failed"]))
+            .filter(
+                Condition::any()
+                    .add(
+                        Condition::all()
+                            .add(sync_job::Column::FinishedAt.gte(start))
+                            .add(sync_job::Column::FinishedAt.lt(end)),
+                    )
+                    // Terminal failures may never record finished_at
+                    .add(
+                        Condition::all()
+                            .add(sync_job::Column::FinishedAt.is_null())
+                            .add(sync_job::Column::UpdatedAt.gte(start))
+                            .add(sync_job::Column::UpdatedAt.lt(end)),
+                    ),
+            )
+            .into_tuple::<(
+                Uuid,
+                String,
+                String,
+                Option<DateTimeWithTimeZone>,
+                Option<DateTimeWithTimeZone>,
+            )>()
+            .all(&self.db)
+            .await
+            .inspect(|rows| query_stats::record_rows(rows.len() as u64))
+            .map_err(RepositoryError::database_error)?;
+
+        let mut job_totals: BTreeMap<(Uuid, String), JobAccumulator> = BTreeMap::new();
+        for (tenant_id, provider_slug, status, started_at, finished_at) in jobs {
+            let acc = job_totals.entry((tenant_id, provider_slug)).or_default();
+            if status == "succeeded" {
+                acc.succeeded += 1;
+            } else {
+                acc.failed += 1;
+            }
+            if let (Some(started), Some(finished)) = (started_at, finished_at) {
+                acc.duration_ms_total += (finished - started).num_milliseconds().max(0) as f64;
+                acc.duration_samples += 1;
+            }
+        }
+
+        let signal_counts = Signal::find()
+            .select_only()
+            .column(signal::Column::TenantId)
+            .column(signal::Column::ProviderSlug)
+            .column(signal::Column::Kind)
+            .column_as(Expr::col(signal::Column::Id).count(), "signal_count")
+            .filter(signal::Column::ReceivedAt.gte(start))
+            .filter(signal::Column::ReceivedAt.lt(end))
+            .group_by(signal::Column::TenantId)
+            .group_by(signal::Column::ProviderSlug)
+            .group_by(signal::Column::Kind)
+            .into_tuple::<(Uuid, String, String, i64)>()
+            .all(&self.db)
+            .await
+            .inspect(|rows| query_stats::record_rows(rows.len() as u64))
+            .map_err(RepositoryError::database_error)?;
+
+        let job_rows: Vec<JobRollupActiveModel> = job_totals
+            .into_iter()
+            .map(|((tenant_id, provider_slug), acc)| JobRollupActiveModel {
+                id: Set(Uuid::new_v4()),
+                day: Set(day),
+                tenant_id: Set(tenant_id),
+                provider_slug: Set(provider_slug),
+                jobs_succeeded: Set(acc.succeeded),
+                jobs_failed: Set(acc.failed),
+                mean_duration_ms: Set((acc.duration_samples > 0)
+                    .then(|| acc.duration_ms_total / acc.duration_samples as f64)),
+                computed_at: Set(computed_at),
+            })
+            .collect();
+
+        let signal_rows: Vec<SignalRollupActiveModel> = signal_counts
+            .into_iter()
+            .map(
+                |(tenant_id, provider_slug, kind, count)| SignalRollupActiveModel {
+                    id: Set(Uuid::new_v4()),
+                    day: Set(day),
+                    tenant_id: Set(tenant_id),
+                    provider_slug: Set(provider_slug),
+                    kind: Set(kind),
+                    signal_count: Set(count),
+                    computed_at: Set(computed_at),
+                },
+            )
+            .collect();
+
+        let summary = RollupSummary {
+            job_rows: job_rows.len(),
+            signal_rows: signal_rows.len(),
+        };
+
+        let txn = self
+            .db
+            .begin()
+            .await
+            .map_err(RepositoryError::database_error)?;
+
+        JobRollup::delete_many()
+            .filter(job_daily_rollup::Column::Day.eq(day))
+            .exec(&txn)
+            .await
+            .map_err(RepositoryError::database_error)?;
+        SignalRollup::delete_many()
+            .filter(signal_daily_rollup::Column::Day.eq(day))
+            .exec(&txn)
+            .await
+            .map_err(RepositoryError::database_error)?;
+
+        if !job_rows.is_empty() {
+            JobRollup::insert_many(job_rows)
+                .exec(&txn)
+                .await
+                .map_err(RepositoryError::database_error)?;
+        }
+        if !signal_rows.is_empty() {
+            SignalRollup::insert_many(signal_rows)
+                .exec(&txn)
+                .await
+                .map_err(RepositoryError::database_error)?;
+        }
+
+        txn.commit()
+            .await
+            .map_err(RepositoryError::database_error)?;
+
+        Ok(summary)
+    }
+
+    /// List job rollups for a tenant within an inclusive day range
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
+    pub async fn list_job_rollups(
+        &self,
+        tenant_id: Uuid,
+        from: NaiveDate,
+        to: NaiveDate,
+        provider_slug: Option<&str>,
+    ) -> Result<Vec<job_daily_rollup::Model>, RepositoryError> {
+        let mut query = JobRollup::find()
+            .filter(job_daily_rollup::Column::TenantId.eq(tenant_id))
+            .filter(job_daily_rollup::Column::Day.gte(from))
+            .filter(job_daily_rollup::Column::Day.lte(to));
+
+        if let Some(provider) = provider_slug {
+            query = query.filter(job_daily_rollup::Column::ProviderSlug.eq(provider));
+        }
+
+        query
+            .order_by_asc(job_daily_rollup::Column::Day)
+            .order_by_asc(job_daily_rollup::Column::ProviderSlug)
+            .all(&self.db)
+            .await
+            .inspect(|rows| query_stats::record_rows(rows.len() as u64))
+            .map_err(RepositoryError::database_error)
+    }
+
+    /// List signal rollups for a tenant within an inclusive day range
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
+    pub async fn list_signal_rollups(
+        &self,
+        tenant_id: Uuid,
+        from: NaiveDate,
+        to: NaiveDate,
+        provider_slug: Option<&str>,
+    ) -> Result<Vec<signal_daily_rollup::Model>, RepositoryError> {
+        let mut query = SignalRollup::find()
+            .filter(signal_daily_rollup::Column::TenantId.eq(tenant_id))
+            .filter(signal_daily_rollup::Column::Day.gte(from))
+            .filter(signal_daily_rollup::Column::Day.lte(to));
+
+        if let Some(provider) = provider_slug {
+            query = query.filter(signal_daily_rollup::Column::ProviderSlug.eq(provider));
+        }
+
+        query
+            .order_by_asc(signal_daily_rollup::Column::Day)
+            .order_by_asc(signal_daily_rollup::Column::ProviderSlug)
+            .order_by_asc(signal_daily_rollup::Column::Kind)
+            .all(&self.db)
+            .await
+            .inspect(|rows| query_stats::record_rows(rows.len() as u64))
+            .map_err(RepositoryError::database_error)
+    }
+}
+
+/// Half-open UTC bounds `[start, end)` of a day
+fn day_bounds(day: NaiveDate) -> (DateTimeWithTimeZone, DateTimeWithTimeZone) {
+    let start = DateTime::<Utc>::from_naive_utc_and_offset(
+        day.and_hms_opt(0, 0, 0).expect("midnight is valid"),
+        Utc,
+    );
+    (
+        start.fixed_offset(),
+        (start + Duration::days(1)).fixed_offset(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::setup_connection;
+    use sea_orm::ActiveModelTrait;
+
+    async fn insert_job(
+        db: &DatabaseConnection,
+        tenant_id: Uuid,
+        connection_id: Uuid,
+        status: &str,
+        started_at: DateTime<Utc>,
+        duration_secs: i64,
+    ) {
+        let finished_at = started_at + Duration::seconds(duration_secs);
+        crate::models::sync_job::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            tenant_id: Set(tenant_id),
+            provider_slug: Set("test-provider".to_string()),
+            connection_id: Set(connection_id),
+            job_type: Set("full".to_string()),
+            status: Set(status.to_string()),
+            priority: Set(10),
+            attempts: Set(1),
+            rate_limited_attempts: Set(0),
+            scheduled_at: Set(started_at.fixed_offset()),
+            retry_after: Set(None),
+            started_at: Set(Some(started_at.fixed_offset())),
+            finished_at: Set(Some(finished_at.fixed_offset())),
+            locked_at: Set(None),
+            heartbeat_at: Set(None),
+            locked_by: Set(None),
+            cursor: Set(None),
+            error: Set(None),
+            created_at: Set(started_at.fixed_offset()),
+            updated_at: Set(finished_at.fixed_offset()),
+        }
+        .insert(db)
+        .await
+        .unwrap();
+    }
+
+    async fn insert_signal(
+        db: &DatabaseConnection,
+        tenant_id: Uuid,
+        connection_id: Uuid,
+        kind: &str,
+        received_at: DateTime<Utc>,
+    ) {
+        crate::models::signal::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            tenant_id: Set(tenant_id),
+            provider_slug: Set("test-provider".to_string()),
+            connection_id: Set(connection_id),
+            kind: Set(kind.to_string()),
+            occurred_at: Set(received_at.fixed_offset()),
+            received_at: Set(received_at.fixed_offset()),
+            payload: Set(serde_json::json!({})),
+            ..Default::default()
+        }
+        .insert(db)
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_compute_day_aggregates_jobs_and_signals() {
+        let (db, tenant_id, connection_id) = setup_connection().await;
+        let day = NaiveDate::from_ymd_opt(2021, 3, 14).unwrap();
+        let (start, _) = day_bounds(day);
+        let noon = start.with_timezone(&Utc) + Duration::hours(12);
+
+        insert_job(&db, tenant_id, connection_id, "succeeded", noon, 10).await;
+        insert_job(&db, tenant_id, connection_id, "succeeded", noon, 30).await;
+        insert_job(&db, tenant_id, connection_id, "failed", noon, 5).await;
+        // Finished on the next day; must not be counted
+        insert_job(&db, tenant_id, connection_id, "succeeded", noon, 86_400).await;
+
+        insert_signal(&db, tenant_id, connection_id, "issue_created", noon).await;
+        insert_signal(&db, tenant_id, connection_id, "issue_created", noon).await;
+        insert_signal(&db, tenant_id, connection_id, "pr_merged", noon).await;
+
+        let repo = RollupRepository::new(db.clone());
+        repo.compute_day(day).await.unwrap();
+        // Re-running replaces rather than duplicates
+        repo.compute_day(day).await.unwrap();
+
+        let jobs = repo
+            .list_job_rollups(tenant_id, day, day, None)
+            .await
+            .unwrap();
+        assert_eq!(jobs.len(), 1);
+        assert_eq!(jobs[0].jobs_succeeded, 2);
+        assert_eq!(jobs[0].jobs_failed, 1);
+        assert_eq!(jobs[0].mean_duration_ms, Some(15_000.0));
+
+        let signals = repo
+            .list_signal_rollups(tenant_id, day, day, Some("test-provider"))
+            .await
+            .unwrap();
+        let counts: Vec<(&str, i64)> = signals
+            .iter()
+            .map(|r| (r.kind.as_str(), r.signal_count))
+            .collect();
+        assert_eq!(counts, vec![("issue_created", 2), ("pr_merged", 1)]);
+    }
+}
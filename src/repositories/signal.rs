@@ -13,6 +13,8 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use crate::models::signal::{Entity as Signal, Model};
+use crate::query_stats;
+use tracing::{field::Empty, instrument};
 
 /// Cursor data structure for pagination
 #[derive(Debug, Clone, Serialize, Deserialize, FromQueryResult)]
@@ -48,6 +50,7 @@ impl<'a> SignalRepository<'a> {
     /// # Returns
     /// A vector of Signal models ordered by occurred_at DESC, id DESC
     #[allow(clippy::too_many_arguments)]
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
     pub async fn list_signals(
         &self,
         tenant_id: Uuid,
@@ -107,10 +110,84 @@ impl<'a> SignalRepository<'a> {
             .limit(limit as u64)
             .all(self.db)
             .await
+            .inspect(|rows| query_stats::record_rows(rows.len() as u64))
             .map_err(RepositoryError::database_error)?;
 
         Ok(signals)
     }
+
+    /// Find a single signal scoped to a tenant
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
+    pub async fn find_for_tenant(
+        &self,
+        tenant_id: Uuid,
+        id: Uuid,
+    ) -> Result<Option<Model>, RepositoryError> {
+        Signal::find_by_id(id)
+            .filter(crate::models::signal::Column::TenantId.eq(tenant_id))
+            .one(self.db)
+            .await
+            .map_err(RepositoryError::database_error)
+    }
+
+    /// Id of the earliest received signal carrying `dedupe_key` for the tenant
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
+    pub async fn first_with_dedupe_key(
+        &self,
+        tenant_id: Uuid,
+        dedupe_key: &str,
+    ) -> Result<Option<Uuid>, RepositoryError> {
+        Signal::find()
+            .select_only()
+            .column(crate::models::signal::Column::Id)
+            .filter(crate::models::signal::Column::TenantId.eq(tenant_id))
+            .filter(crate::models::signal::Column::DedupeKey.eq(dedupe_key))
+            .order_by_asc(crate::models::signal::Column::ReceivedAt)
+            .order_by_asc(crate::models::signal::Column::Id)
+            .into_tuple::<Uuid>()
+            .one(self.db)
+            .await
+            .map_err(RepositoryError::database_error)
+    }
+
+    /// Signals for a tenant that occurred at or after `since`, oldest first
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
+    pub async fn list_since(
+        &self,
+        tenant_id: Uuid,
+        since: DateTime<Utc>,
+        limit: u64,
+    ) -> Result<Vec<Model>, RepositoryError> {
+        Signal::find()
+            .filter(crate::models::signal::Column::TenantId.eq(tenant_id))
+            .filter(crate::models::signal::Column::OccurredAt.gte(since))
+            .order_by_asc(crate::models::signal::Column::OccurredAt)
+            .order_by_asc(crate::models::signal::Column::Id)
+            .limit(limit)
+            .all(self.db)
+            .await
+            .inspect(|rows| query_stats::record_rows(rows.len() as u64))
+            .map_err(RepositoryError::database_error)
+    }
+
+    /// Delete the given signals of a tenant, returning how many were removed
+    ///
+    /// Outbox entries, pipeline events and grounded signals referencing them are
+    /// removed by the foreign key cascades.
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
+    pub async fn delete_for_tenant(
+        &self,
+        tenant_id: Uuid,
+        ids: &[Uuid],
+    ) -> Result<u64, RepositoryError> {
+        Signal::delete_many()
+            .filter(crate::models::signal::Column::TenantId.eq(tenant_id))
+            .filter(crate::models::signal::Column::Id.is_in(ids.iter().copied()))
+            .exec(self.db)
+            .await
+            .map(|result| result.rows_affected)
+            .map_err(RepositoryError::database_error)
+    }
 }
 
 #[cfg(test)]
@@ -273,7 +350,8 @@ mod tests {
         let (db, tenant_id, connection_id, _) = setup_test_data().await;
         let repo = SignalRepository::new(&db);
 
-        let now = Utc::now();
+        // Postgres stores microsecond precision; truncate so comparisons are exact
+        let now = chrono::SubsecRound::trunc_subsecs(Utc::now(), 0);
         let base_time = now - chrono::Duration::hours(2);
 
         // Create signals at different times
@@ -500,4 +578,51 @@ mod tests {
         assert_eq!(tenant2_signals.len(), 1);
         assert_eq!(tenant2_signals[0].kind, "tenant2_event");
     }
+
+    #[tokio::test]
+    async fn test_first_with_dedupe_key_returns_earliest_signal() {
+        let (db, tenant_id, connection_id, _) = setup_test_data().await;
+        let repo = SignalRepository::new(&db);
+
+        let now = Utc::now();
+        let mut ids = Vec::new();
+        for i in 0..2 {
+            let id = Uuid::new_v4();
+            SignalActiveModel {
+                id: sea_orm::Set(id),
+                tenant_id: sea_orm::Set(tenant_id),
+                provider_slug: sea_orm::Set("test-provider".to_string()),
+                connection_id: sea_orm::Set(connection_id),
+                kind: sea_orm::Set("issue_created".to_string()),
+                occurred_at: sea_orm::Set(now.into()),
+                received_at: sea_orm::Set((now + chrono::Duration::seconds(i)).into()),
+                payload: sea_orm::Set(serde_json::json!({})),
+                dedupe_key: sea_orm::Set(Some("issue-1".to_string())),
+                ..Default::default()
+            }
+            .insert(&db)
+            .await
+            .unwrap();
+            ids.push(id);
+        }
+
+        assert_eq!(
+            repo.first_with_dedupe_key(tenant_id, "issue-1")
+                .await
+                .unwrap(),
+            Some(ids[0])
+        );
+        assert_eq!(
+            repo.first_with_dedupe_key(tenant_id, "issue-2")
+                .await
+                .unwrap(),
+            None
+        );
+        assert!(
+            repo.find_for_tenant(Uuid::new_v4(), ids[1])
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
 }
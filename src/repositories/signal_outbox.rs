@@ -0,0 +1,165 @@
+//! # Signal Outbox Repository
+//!
+//! This module provides repository operations for the signal_outbox table. Entries
+//! are written inside the same transaction that persists a signal and drained later
+//! by the outbox relay.
+
+use chrono::Utc;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, QueryFilter,
+    QueryOrder, QuerySelect, Set,
+    sea_query::{Expr, OnConflict},
+};
+use uuid::Uuid;
+
+use crate::error::RepositoryError;
+use crate::models::signal::Model as SignalModel;
+use crate::models::signal_outbox::{
+    ActiveModel, Column, Entity, Model, STATUS_FAILED, STATUS_PENDING, STATUS_PUBLISHED,
+};
+use crate::query_stats;
+use tracing::{field::Empty, instrument};
+
+/// Repository for signal outbox database operations
+pub struct SignalOutboxRepository {
+    db: DatabaseConnection,
+}
+
+impl SignalOutboxRepository {
+    /// Create a new SignalOutboxRepository with the given database connection
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    /// Record outbox entries for a freshly persisted signal.
+    ///
+    /// Accepts any connection so callers can pass the transaction that inserted the
+    /// signal; entries then commit or roll back together with the signal itself.
+    /// Re-enqueueing the same signal/topic pair is a no-op.
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
+    pub async fn enqueue_for_signal<C: ConnectionTrait>(
+        conn: &C,
+        signal: &SignalModel,
+        topics: &[&str],
+    ) -> Result<(), RepositoryError> {
+        if topics.is_empty() {
+            return Ok(());
+        }
+
+        let now = Utc::now().fixed_offset();
+        let entries = topics.iter().map(|topic| ActiveModel {
+            id: Set(Uuid::new_v4()),
+            tenant_id: Set(signal.tenant_id),
+            signal_id: Set(signal.id),
+            topic: Set((*topic).to_string()),
+            status: Set(STATUS_PENDING.to_string()),
+            attempts: Set(0),
+            available_at: Set(now),
+            published_at: Set(None),
+            last_error: Set(None),
+            created_at: Set(now),
+            updated_at: Set(now),
+        });
+
+        Entity::insert_many(entries)
+            .on_conflict(
+                OnConflict::columns([Column::SignalId, Column::Topic])
+                    .do_nothing()
+                    .to_owned(),
+            )
+            .do_nothing()
+            .exec_without_returning(conn)
+            .await
+            .map_err(RepositoryError::database_error)?;
+
+        Ok(())
+    }
+
+    /// Fetch pending entries for a topic that are due for publication, oldest first
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
+    pub async fn list_due(&self, topic: &str, limit: u64) -> Result<Vec<Model>, RepositoryError> {
+        Entity::find()
+            .filter(Column::Topic.eq(topic))
+            .filter(Column::Status.eq(STATUS_PENDING))
+            .filter(Column::AvailableAt.lte(Utc::now().fixed_offset()))
+            .order_by_asc(Column::AvailableAt)
+            .order_by_asc(Column::Id)
+            .limit(limit)
+            .all(&self.db)
+            .await
+            .inspect(|rows| query_stats::record_rows(rows.len() as u64))
+            .map_err(RepositoryError::database_error)
+    }
+
+    /// Mark entries as published
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
+    pub async fn mark_published(&self, ids: Vec<Uuid>) -> Result<u64, RepositoryError> {
+        if ids.is_empty() {
+            return Ok(0);
+        }
+
+        let now = Utc::now().fixed_offset();
+        let result = Entity::update_many()
+            .col_expr(Column::Status, Expr::value(STATUS_PUBLISHED))
+            .col_expr(Column::PublishedAt, Expr::value(now))
+            .col_expr(Column::LastError, Expr::value(Option::<String>::None))
+            .col_expr(Column::UpdatedAt, Expr::value(now))
+            .col_expr(Column::Attempts, Expr::col(Column::Attempts).add(1))
+            .filter(Column::Id.is_in(ids))
+            .filter(Column::Status.eq(STATUS_PENDING))
+            .exec(&self.db)
+            .await
+            .map_err(RepositoryError::database_error)?;
+
+        query_stats::record_rows(result.rows_affected);
+        Ok(result.rows_affected)
+    }
+
+    /// Record a failed publication attempt.
+    ///
+    /// The entry is rescheduled after `retry_in` unless `max_attempts` has been
+    /// reached, in which case it is parked as failed for operator inspection.
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
+    pub async fn mark_attempt_failed(
+        &self,
+        entry: &Model,
+        error: &str,
+        retry_in: chrono::Duration,
+        max_attempts: i32,
+    ) -> Result<Model, RepositoryError> {
+        let now = Utc::now().fixed_offset();
+        let attempts = entry.attempts + 1;
+
+        let mut active: ActiveModel = entry.clone().into();
+        active.attempts = Set(attempts);
+        active.last_error = Set(Some(error.to_string()));
+        active.updated_at = Set(now);
+        if attempts >= max_attempts {
+            active.status = Set(STATUS_FAILED.to_string());
+        } else {
+            active.available_at = Set(now + retry_in);
+        }
+
+        active
+            .update(&self.db)
+            .await
+            .map_err(RepositoryError::database_error)
+    }
+
+    /// List outbox entries for a signal, e.g. for delivery diagnostics
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
+    pub async fn list_for_signal(
+        &self,
+        tenant_id: Uuid,
+        signal_id: Uuid,
+    ) -> Result<Vec<Model>, RepositoryError> {
+        Entity::find()
+            .filter(Column::TenantId.eq(tenant_id))
+            .filter(Column::SignalId.eq(signal_id))
+            .order_by_asc(Column::Topic)
+            .all(&self.db)
+            .await
+            .inspect(|rows| query_stats::record_rows(rows.len() as u64))
+            .map_err(RepositoryError::database_error)
+    }
+}
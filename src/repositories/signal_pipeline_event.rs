@@ -0,0 +1,68 @@
+//! # Signal Pipeline Event Repository
+//!
+//! Append-only log of pipeline stages per signal. Events are written with the
+//! connection that persisted the signal, so they commit together with it.
+
+use chrono::Utc;
+use sea_orm::{
+    ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, Set,
+};
+use serde_json::Value as JsonValue;
+use uuid::Uuid;
+
+use crate::error::RepositoryError;
+use crate::models::signal::Model as SignalModel;
+use crate::models::signal_pipeline_event::{ActiveModel, Column, Entity, Model};
+use crate::query_stats;
+use tracing::{field::Empty, instrument};
+
+/// Repository for signal pipeline events
+pub struct SignalPipelineEventRepository {
+    db: DatabaseConnection,
+}
+
+impl SignalPipelineEventRepository {
+    /// Create a new SignalPipelineEventRepository with the given database connection
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    /// Record a stage for a signal on `conn`, typically the transaction that persisted it
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
+    pub async fn record<C: ConnectionTrait>(
+        conn: &C,
+        signal: &SignalModel,
+        stage: &str,
+        detail: Option<JsonValue>,
+    ) -> Result<(), RepositoryError> {
+        Entity::insert(ActiveModel {
+            id: Set(Uuid::new_v4()),
+            tenant_id: Set(signal.tenant_id),
+            signal_id: Set(signal.id),
+            stage: Set(stage.to_string()),
+            detail: Set(detail),
+            occurred_at: Set(Utc::now().fixed_offset()),
+        })
+        .exec_without_returning(conn)
+        .await
+        .map_err(RepositoryError::database_error)?;
+        Ok(())
+    }
+
+    /// List recorded stages for a signal, oldest first
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
+    pub async fn list_for_signal(
+        &self,
+        tenant_id: Uuid,
+        signal_id: Uuid,
+    ) -> Result<Vec<Model>, RepositoryError> {
+        Entity::find()
+            .filter(Column::TenantId.eq(tenant_id))
+            .filter(Column::SignalId.eq(signal_id))
+            .order_by_asc(Column::OccurredAt)
+            .all(&self.db)
+            .await
+            .inspect(|rows| query_stats::record_rows(rows.len() as u64))
+            .map_err(RepositoryError::database_error)
+    }
+}
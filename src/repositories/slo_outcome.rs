@@ -0,0 +1,195 @@
+//! # SLO Outcome Repository
+//!
+//! This module provides repository operations for the slo_outcomes table. Each
+//! attempt increments the counters of its provider, operation and UTC hour in a
+//! single upsert, so concurrent writers in different processes never lose counts.
+
+use chrono::{DateTime, Utc};
+use sea_orm::{
+    ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, QuerySelect, Set,
+    sea_query::{Alias, Expr, OnConflict},
+};
+use uuid::Uuid;
+
+use crate::error::RepositoryError;
+use crate::models::slo_outcome::{ActiveModel, Column, Entity};
+use crate::query_stats;
+use tracing::{field::Empty, instrument};
+
+/// Attempts and failures of one provider operation summed over a range of hours
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SloOutcomeTotals {
+    /// Provider slug
+    pub provider_slug: String,
+    /// Tracked operation
+    pub operation: String,
+    /// Attempts in the range
+    pub total: i64,
+    /// Failed attempts in the range
+    pub failed: i64,
+}
+
+/// Repository for SLO outcome counters
+pub struct SloOutcomeRepository {
+    db: DatabaseConnection,
+}
+
+impl SloOutcomeRepository {
+    /// Create a new SloOutcomeRepository with the given database connection
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    /// Count one attempt of `operation` for `provider_slug` in the hour containing `at`
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
+    pub async fn record(
+        &self,
+        provider_slug: &str,
+        operation: &str,
+        success: bool,
+        at: DateTime<Utc>,
+    ) -> Result<(), RepositoryError> {
+        let failed = i64::from(!success);
+        Entity::insert(ActiveModel {
+            id: Set(Uuid::new_v4()),
+            provider_slug: Set(provider_slug.to_string()),
+            operation: Set(operation.to_string()),
+            hour_start: Set(hour_start(at).fixed_offset()),
+            total: Set(1),
+            failed: Set(failed),
+        })
+        .on_conflict(
+            OnConflict::columns([Column::ProviderSlug, Column::Operation, Column::HourStart])
+                .value(Column::Total, Expr::col((Entity, Column::Total)).add(1))
+                .value(
+                    Column::Failed,
+                    Expr::col((Entity, Column::Failed)).add(failed),
+                )
+                .to_owned(),
+        )
+        .exec_without_returning(&self.db)
+        .await
+        .map_err(RepositoryError::database_error)?;
+        Ok(())
+    }
+
+    /// Sum counters per provider and operation for hours starting at or after `since`
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
+    pub async fn totals_since(
+        &self,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<SloOutcomeTotals>, RepositoryError> {
+        let rows = Entity::find()
+            .select_only()
+            .column(Column::ProviderSlug)
+            .column(Column::Operation)
+            // SUM over BIGINT is NUMERIC on Postgres
+            .column_as(
+                Expr::col(Column::Total).sum().cast_as(Alias::new("BIGINT")),
+                "total",
+            )
+            .column_as(
+                Expr::col(Column::Failed)
+                    .sum()
+                    .cast_as(Alias::new("BIGINT")),
+                "failed",
+            )
+            .filter(Column::HourStart.gte(hour_start(since).fixed_offset()))
+            .group_by(Column::ProviderSlug)
+            .group_by(Column::Operation)
+            .order_by_asc(Column::ProviderSlug)
+            .order_by_asc(Column::Operation)
+            .into_tuple::<(String, String, i64, i64)>()
+            .all(&self.db)
+            .await
+            .inspect(|rows| query_stats::record_rows(rows.len() as u64))
+            .map_err(RepositoryError::database_error)?;
+
+        Ok(rows
+            .into_iter()
+            .map(
+                |(provider_slug, operation, total, failed)| SloOutcomeTotals {
+                    provider_slug,
+                    operation,
+                    total,
+                    failed,
+                },
+            )
+            .collect())
+    }
+
+    /// Delete counters for hours that started before `cutoff`, returning the rows removed
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
+    pub async fn prune_before(&self, cutoff: DateTime<Utc>) -> Result<u64, RepositoryError> {
+        let result = Entity::delete_many()
+            .filter(Column::HourStart.lt(hour_start(cutoff).fixed_offset()))
+            .exec(&self.db)
+            .await
+            .map_err(RepositoryError::database_error)?;
+        query_stats::record_rows(result.rows_affected);
+        Ok(result.rows_affected)
+    }
+}
+
+/// Start of the UTC hour containing `at`
+fn hour_start(at: DateTime<Utc>) -> DateTime<Utc> {
+    let seconds = at.timestamp();
+    DateTime::from_timestamp(seconds - seconds.rem_euclid(3600), 0).unwrap_or(at)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::AppConfig;
+    use crate::db::init_pool;
+    use migration::{Migrator, MigratorTrait};
+
+    #[tokio::test]
+    async fn test_record_increments_hourly_counters_and_prunes() {
+        let config = AppConfig {
+            profile: "test".to_string(),
+            ..Default::default()
+        };
+        let db = init_pool(&config).await.expect("Failed to init test DB");
+        Migrator::up(&db, None).await.unwrap();
+        let repo = SloOutcomeRepository::new(db);
+
+        let provider = format!("slo-test-{}", Uuid::new_v4());
+        let now = Utc::now();
+        let old = now - chrono::Duration::hours(48);
+        repo.record(&provider, "sync", true, now).await.unwrap();
+        repo.record(&provider, "sync", false, now).await.unwrap();
+        repo.record(&provider, "sync", true, now).await.unwrap();
+        repo.record(&provider, "webhook", false, old).await.unwrap();
+
+        let recent: Vec<_> = repo
+            .totals_since(now - chrono::Duration::hours(24))
+            .await
+            .unwrap()
+            .into_iter()
+            .filter(|totals| totals.provider_slug == provider)
+            .collect();
+        assert_eq!(
+            recent,
+            vec![SloOutcomeTotals {
+                provider_slug: provider.clone(),
+                operation: "sync".to_string(),
+                total: 3,
+                failed: 1,
+            }]
+        );
+
+        assert!(
+            repo.prune_before(now - chrono::Duration::hours(24))
+                .await
+                .unwrap()
+                >= 1
+        );
+        let remaining = repo.totals_since(old).await.unwrap();
+        assert!(
+            remaining
+                .iter()
+                .all(|totals| totals.provider_slug != provider || totals.operation == "sync")
+        );
+    }
+}
@@ -6,15 +6,19 @@
 use crate::models::sync_job::Column;
 use chrono::Utc;
 use sea_orm::{
-    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder,
-    QuerySelect, Set,
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, PaginatorTrait, QueryFilter,
+    QueryOrder, QuerySelect, Set, SqlErr,
+    sea_query::{Expr, SimpleExpr},
 };
 use serde_json::Value as JsonValue;
 use uuid::Uuid;
 
+use crate::db::with_retry;
 use crate::error::ApiError;
 use crate::models::sync_job::{ActiveModel, Entity, Model};
+use crate::query_stats;
 use chrono::DateTime;
+use tracing::{field::Empty, instrument};
 
 /// Configuration for listing jobs with filters
 #[derive(Debug, Default)]
@@ -36,6 +40,76 @@ pub struct ListJobsResult {
     pub next_cursor: Option<(sea_orm::prelude::DateTimeWithTimeZone, Uuid)>,
 }
 
+/// Timing rules for coalescing repeated triggers into a single pending job
+#[derive(Debug, Clone, Copy)]
+pub struct CoalescePolicy {
+    /// Delay applied after each trigger before the job becomes due
+    pub debounce: chrono::Duration,
+    /// Upper bound on how far past its creation a pending job may be pushed back
+    pub max_delay: chrono::Duration,
+}
+
+/// Rules for raising a queued job's priority the longer it waits, so a steady
+/// stream of high-priority jobs cannot starve low-priority ones
+#[derive(Debug, Clone, Copy)]
+pub struct PriorityAging {
+    /// Wait after which a due job gains one aging step (zero disables aging)
+    pub interval: chrono::Duration,
+    /// Upper bound on the priority a job can gain by waiting
+    pub max_boost: i16,
+}
+
+impl PriorityAging {
+    /// Priority gained per `interval` a job waits after becoming due
+    pub const STEP: i16 = 10;
+
+    /// Claim ordering expression: `priority` plus the boost earned by waiting
+    /// since the job became due (its `scheduled_at` or `retry_after`) as of `now`
+    pub fn effective_priority(&self, now: DateTime<Utc>) -> SimpleExpr {
+        let priority = Expr::col(Column::Priority);
+        if self.interval <= chrono::Duration::zero() || self.max_boost <= 0 {
+            return priority.into();
+        }
+        // One CASE arm per step, longest wait first; sticks to comparisons both
+        // backends support instead of date arithmetic
+        let steps = (self.max_boost + Self::STEP - 1) / Self::STEP;
+        let arm = |step: i16| {
+            let due_before = now - self.interval * i32::from(step);
+            let waited = Column::ScheduledAt.lte(due_before).and(
+                Column::RetryAfter
+                    .is_null()
+                    .or(Column::RetryAfter.lte(due_before)),
+            );
+            (waited, (step * Self::STEP).min(self.max_boost))
+        };
+        let (waited, gained) = arm(steps);
+        let mut boost = Expr::case(waited, gained);
+        for step in (1..steps).rev() {
+            let (waited, gained) = arm(step);
+            boost = boost.case(waited, gained);
+        }
+        priority.add(boost.finally(0))
+    }
+}
+
+/// Result of enqueueing a job that may be merged into an existing pending job
+#[derive(Debug)]
+pub enum EnqueueOutcome {
+    /// A new job row was inserted
+    Created(Model),
+    /// The trigger was folded into this already pending job
+    Coalesced(Model),
+}
+
+impl EnqueueOutcome {
+    /// The job that will service the trigger
+    pub fn job(&self) -> &Model {
+        match self {
+            Self::Created(job) | Self::Coalesced(job) => job,
+        }
+    }
+}
+
 /// Repository for sync job database operations
 pub struct SyncJobRepository {
     db: DatabaseConnection,
@@ -48,6 +122,7 @@ impl SyncJobRepository {
     }
 
     /// Enqueue a new webhook sync job
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
     pub async fn enqueue_webhook_job(
         &self,
         tenant_id: Uuid,
@@ -66,10 +141,14 @@ impl SyncJobRepository {
             status: Set("queued".to_string()),
             priority: Set(50), // Default priority for webhook jobs
             attempts: Set(0),
+            rate_limited_attempts: Set(0),
             scheduled_at: Set(now),
             retry_after: Set(None),
             started_at: Set(None),
             finished_at: Set(None),
+            locked_at: Set(None),
+            heartbeat_at: Set(None),
+            locked_by: Set(None),
             cursor: Set(cursor),
             error: Set(None),
             created_at: Set(now),
@@ -97,6 +176,7 @@ impl SyncJobRepository {
     }
 
     /// Enqueue a new regular sync job
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
     pub async fn enqueue_sync_job(
         &self,
         tenant_id: Uuid,
@@ -115,10 +195,14 @@ impl SyncJobRepository {
             status: Set("queued".to_string()),
             priority: Set(10), // Higher priority for triggered sync jobs
             attempts: Set(0),
+            rate_limited_attempts: Set(0),
             scheduled_at: Set(now),
             retry_after: Set(None),
             started_at: Set(None),
             finished_at: Set(None),
+            locked_at: Set(None),
+            heartbeat_at: Set(None),
+            locked_by: Set(None),
             cursor: Set(cursor),
             error: Set(None),
             created_at: Set(now),
@@ -145,15 +229,305 @@ impl SyncJobRepository {
         Ok(result)
     }
 
+    /// Enqueue a job for a connection, coalescing it with an already pending one.
+    ///
+    /// When a queued job of the same type exists for the connection, no new row is
+    /// created; instead the pending job is pushed back to `now + debounce` (but never
+    /// past `created_at + max_delay`, so a chatty source cannot starve it) and its
+    /// priority is raised to the highest requested. A running incremental job also
+    /// absorbs the trigger, since only one may be pending per connection.
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
+    pub async fn enqueue_coalesced_job(
+        &self,
+        tenant_id: Uuid,
+        provider_slug: &str,
+        connection_id: Uuid,
+        job_type: &str,
+        priority: i16,
+        policy: CoalescePolicy,
+    ) -> Result<EnqueueOutcome, ApiError> {
+        let now = Utc::now().fixed_offset();
+
+        let pending = Entity::find()
+            .filter(Column::ConnectionId.eq(connection_id))
+            .filter(Column::JobType.eq(job_type))
+            .filter(Column::Status.is_in(["queued", "running"]))
+            .order_by_asc(Column::CreatedAt)
+            .all(&self.db)
+            .await
+            .inspect(|rows| query_stats::record_rows(rows.len() as u64))
+            .map_err(|e| {
+                tracing::error!("Failed to check pending jobs for coalescing: {}", e);
+                ApiError::new(
+                    axum::http::StatusCode::INTERNAL_SERVER_ERROR,
+                    "INTERNAL_SERVER_ERROR",
+                    "Failed to create sync job",
+                )
+            })?;
+
+        if let Some(queued) = pending.iter().find(|job| job.status == "queued") {
+            let latest_allowed = queued.created_at + policy.max_delay;
+            let scheduled_at = queued
+                .scheduled_at
+                .max(now + policy.debounce)
+                .min(latest_allowed);
+            let priority = queued.priority.max(priority);
+
+            // Guard on status so a job claimed meanwhile is left untouched
+            let result = Entity::update_many()
+                .col_expr(Column::ScheduledAt, Expr::value(scheduled_at))
+                .col_expr(Column::Priority, Expr::value(priority))
+                .col_expr(Column::UpdatedAt, Expr::value(now))
+                .filter(Column::Id.eq(queued.id))
+                .filter(Column::Status.eq("queued"))
+                .exec(&self.db)
+                .await
+                .map_err(|e| {
+                    tracing::error!("Failed to coalesce pending job: {}", e);
+                    ApiError::new(
+                        axum::http::StatusCode::INTERNAL_SERVER_ERROR,
+                        "INTERNAL_SERVER_ERROR",
+                        "Failed to create sync job",
+                    )
+                })?;
+
+            if result.rows_affected > 0 {
+                let mut coalesced = queued.clone();
+                coalesced.scheduled_at = scheduled_at;
+                coalesced.priority = priority;
+                coalesced.updated_at = now;
+                return Ok(EnqueueOutcome::Coalesced(coalesced));
+            }
+        } else if job_type == "incremental"
+            && let Some(running) = pending.into_iter().next()
+        {
+            return Ok(EnqueueOutcome::Coalesced(running));
+        }
+
+        let job = ActiveModel {
+            id: Set(Uuid::new_v4()),
+            tenant_id: Set(tenant_id),
+            provider_slug: Set(provider_slug.to_string()),
+            connection_id: Set(connection_id),
+            job_type: Set(job_type.to_string()),
+            status: Set("queued".to_string()),
+            priority: Set(priority),
+            attempts: Set(0),
+            rate_limited_attempts: Set(0),
+            scheduled_at: Set(now + policy.debounce),
+            retry_after: Set(None),
+            started_at: Set(None),
+            finished_at: Set(None),
+            locked_at: Set(None),
+            heartbeat_at: Set(None),
+            locked_by: Set(None),
+            cursor: Set(None), // Resume from the connection's stored cursor
+            error: Set(None),
+            created_at: Set(now),
+            updated_at: Set(now),
+        };
+
+        match job.insert(&self.db).await {
+            Ok(result) => Ok(EnqueueOutcome::Created(result)),
+            // Lost a race for the single pending incremental slot; the winner covers us
+            Err(e) if matches!(e.sql_err(), Some(SqlErr::UniqueConstraintViolation(_))) => {
+                let existing = Entity::find()
+                    .filter(Column::ConnectionId.eq(connection_id))
+                    .filter(Column::JobType.eq(job_type))
+                    .filter(Column::Status.is_in(["queued", "running"]))
+                    .one(&self.db)
+                    .await
+                    .ok()
+                    .flatten()
+                    .ok_or_else(|| {
+                        ApiError::new(
+                            axum::http::StatusCode::INTERNAL_SERVER_ERROR,
+                            "INTERNAL_SERVER_ERROR",
+                            "Failed to create sync job",
+                        )
+                    })?;
+                Ok(EnqueueOutcome::Coalesced(existing))
+            }
+            Err(e) => {
+                tracing::error!("Failed to create sync job: {}", e);
+                Err(ApiError::new(
+                    axum::http::StatusCode::INTERNAL_SERVER_ERROR,
+                    "INTERNAL_SERVER_ERROR",
+                    "Failed to create sync job",
+                ))
+            }
+        }
+    }
+
+    /// Enqueue an incremental sync that is due immediately, for a manual trigger.
+    ///
+    /// Only one incremental job may be queued or running per connection (the
+    /// `idx_sync_jobs_incremental_pending` guard), so a pending one makes this
+    /// fail with `409 SYNC_ALREADY_PENDING`, naming that job in the details.
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
+    pub async fn enqueue_immediate_sync(
+        &self,
+        tenant_id: Uuid,
+        provider_slug: &str,
+        connection_id: Uuid,
+        priority: i16,
+    ) -> Result<Model, ApiError> {
+        if let Some(pending) = self.pending_incremental(connection_id).await? {
+            return Err(sync_already_pending(&pending));
+        }
+
+        let now = Utc::now().fixed_offset();
+        let job = ActiveModel {
+            id: Set(Uuid::new_v4()),
+            tenant_id: Set(tenant_id),
+            provider_slug: Set(provider_slug.to_string()),
+            connection_id: Set(connection_id),
+            job_type: Set("incremental".to_string()),
+            status: Set("queued".to_string()),
+            priority: Set(priority),
+            attempts: Set(0),
+            rate_limited_attempts: Set(0),
+            scheduled_at: Set(now),
+            retry_after: Set(None),
+            started_at: Set(None),
+            finished_at: Set(None),
+            locked_at: Set(None),
+            heartbeat_at: Set(None),
+            locked_by: Set(None),
+            cursor: Set(None), // Resume from the connection's stored cursor
+            error: Set(None),
+            created_at: Set(now),
+            updated_at: Set(now),
+        };
+
+        match job.insert(&self.db).await {
+            Ok(result) => {
+                tracing::info!(
+                    tenant_id = %tenant_id,
+                    provider_slug = %result.provider_slug,
+                    connection_id = %connection_id,
+                    job_id = %result.id,
+                    "Manual sync job enqueued"
+                );
+                Ok(result)
+            }
+            // Lost a race for the single pending incremental slot
+            Err(e) if matches!(e.sql_err(), Some(SqlErr::UniqueConstraintViolation(_))) => {
+                match self.pending_incremental(connection_id).await? {
+                    Some(pending) => Err(sync_already_pending(&pending)),
+                    None => Err(ApiError::new(
+                        axum::http::StatusCode::CONFLICT,
+                        "SYNC_ALREADY_PENDING",
+                        "A sync is already pending for this connection",
+                    )),
+                }
+            }
+            Err(e) => {
+                tracing::error!("Failed to create sync job: {}", e);
+                Err(ApiError::new(
+                    axum::http::StatusCode::INTERNAL_SERVER_ERROR,
+                    "INTERNAL_SERVER_ERROR",
+                    "Failed to create sync job",
+                ))
+            }
+        }
+    }
+
+    /// The connection's queued or running incremental job, if any
+    async fn pending_incremental(&self, connection_id: Uuid) -> Result<Option<Model>, ApiError> {
+        Entity::find()
+            .filter(Column::ConnectionId.eq(connection_id))
+            .filter(Column::JobType.eq("incremental"))
+            .filter(Column::Status.is_in(["queued", "running"]))
+            .one(&self.db)
+            .await
+            .map_err(|e| {
+                tracing::error!("Failed to check pending incremental jobs: {}", e);
+                ApiError::new(
+                    axum::http::StatusCode::INTERNAL_SERVER_ERROR,
+                    "INTERNAL_SERVER_ERROR",
+                    "Failed to create sync job",
+                )
+            })
+    }
+
     /// Find a sync job by ID, ensuring it belongs to the specified tenant
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
     pub async fn find_by_tenant(
         &self,
         tenant_id: Uuid,
         job_id: Uuid,
     ) -> Result<Option<Model>, ApiError> {
-        let job = Entity::find_by_id(job_id)
-            .filter(Column::TenantId.eq(tenant_id))
-            .one(&self.db)
+        let job = with_retry(|| {
+            Entity::find_by_id(job_id)
+                .filter(Column::TenantId.eq(tenant_id))
+                .one(&self.db)
+        })
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to find sync job: {}", e);
+            ApiError::new(
+                axum::http::StatusCode::INTERNAL_SERVER_ERROR,
+                "INTERNAL_SERVER_ERROR",
+                "Failed to find sync job",
+            )
+        })?;
+
+        Ok(job)
+    }
+
+    /// Most recently finished job of a tenant's connection
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
+    pub async fn latest_finished_for_connection(
+        &self,
+        tenant_id: Uuid,
+        connection_id: Uuid,
+    ) -> Result<Option<Model>, ApiError> {
+        let job = with_retry(|| {
+            Entity::find()
+                .filter(Column::TenantId.eq(tenant_id))
+                .filter(Column::ConnectionId.eq(connection_id))
+                .filter(Column::FinishedAt.is_not_null())
+                .order_by_desc(Column::FinishedAt)
+                .one(&self.db)
+        })
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to find latest sync job: {}", e);
+            ApiError::new(
+                axum::http::StatusCode::INTERNAL_SERVER_ERROR,
+                "INTERNAL_SERVER_ERROR",
+                "Failed to find latest sync job",
+            )
+        })?;
+
+        Ok(job)
+    }
+
+    /// Count webhook jobs still waiting to be processed, across all tenants
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
+    pub async fn count_queued_webhook_jobs(&self) -> Result<u64, ApiError> {
+        with_retry(|| {
+            Entity::find()
+                .filter(Column::Status.eq("queued"))
+                .filter(Column::JobType.eq("webhook"))
+                .count(&self.db)
+        })
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to count queued webhook jobs: {}", e);
+            ApiError::new(
+                axum::http::StatusCode::INTERNAL_SERVER_ERROR,
+                "INTERNAL_SERVER_ERROR",
+                "Failed to count queued webhook jobs",
+            )
+        })
+    }
+
+    /// Find a sync job by ID without tenant scoping (operator tooling only)
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
+    pub async fn find_by_id(&self, job_id: Uuid) -> Result<Option<Model>, ApiError> {
+        with_retry(|| Entity::find_by_id(job_id).one(&self.db))
             .await
             .map_err(|e| {
                 tracing::error!("Failed to find sync job: {}", e);
@@ -162,12 +536,160 @@ impl SyncJobRepository {
                     "INTERNAL_SERVER_ERROR",
                     "Failed to find sync job",
                 )
+            })
+    }
+
+    /// Requeue a failed or backing-off job so it runs as soon as possible.
+    ///
+    /// Attempts are preserved so the job history stays accurate; the last error is
+    /// kept until the next run replaces it.
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
+    pub async fn retry_job(&self, job_id: Uuid) -> Result<Model, ApiError> {
+        let job = self.find_by_id(job_id).await?.ok_or_else(|| {
+            ApiError::new(
+                axum::http::StatusCode::NOT_FOUND,
+                "NOT_FOUND",
+                "Sync job not found",
+            )
+        })?;
+
+        if !matches!(job.status.as_str(), "failed" | "queued") {
+            return Err(ApiError::new(
+                axum::http::StatusCode::CONFLICT,
+                "JOB_NOT_RETRYABLE",
+                format!("Job is {} and cannot be retried", job.status),
+            ));
+        }
+
+        let now = Utc::now().fixed_offset();
+        let result = Entity::update_many()
+            .col_expr(Column::Status, Expr::value("queued"))
+            .col_expr(Column::ScheduledAt, Expr::value(now))
+            .col_expr(
+                Column::RetryAfter,
+                Expr::value(Option::<DateTime<Utc>>::None),
+            )
+            .col_expr(
+                Column::StartedAt,
+                Expr::value(Option::<DateTime<Utc>>::None),
+            )
+            .col_expr(
+                Column::FinishedAt,
+                Expr::value(Option::<DateTime<Utc>>::None),
+            )
+            .col_expr(Column::UpdatedAt, Expr::value(now))
+            .filter(Column::Id.eq(job.id))
+            // Guard on status so a job claimed meanwhile is left untouched
+            .filter(Column::Status.eq(job.status.clone()))
+            .exec(&self.db)
+            .await
+            .map_err(|e| match e.sql_err() {
+                Some(SqlErr::UniqueConstraintViolation(_)) => ApiError::new(
+                    axum::http::StatusCode::CONFLICT,
+                    "JOB_NOT_RETRYABLE",
+                    "Another incremental job is already pending for this connection",
+                ),
+                _ => {
+                    tracing::error!("Failed to retry sync job: {}", e);
+                    ApiError::new(
+                        axum::http::StatusCode::INTERNAL_SERVER_ERROR,
+                        "INTERNAL_SERVER_ERROR",
+                        "Failed to retry sync job",
+                    )
+                }
             })?;
 
-        Ok(job)
+        query_stats::record_rows(result.rows_affected);
+        if result.rows_affected == 0 {
+            return Err(ApiError::new(
+                axum::http::StatusCode::CONFLICT,
+                "JOB_NOT_RETRYABLE",
+                "Job changed state while retrying",
+            ));
+        }
+
+        self.find_by_id(job.id).await?.ok_or_else(|| {
+            ApiError::new(
+                axum::http::StatusCode::NOT_FOUND,
+                "NOT_FOUND",
+                "Sync job not found",
+            )
+        })
+    }
+
+    /// Cancel a tenant's job.
+    ///
+    /// A queued job is cancelled outright. A running job moves to `cancelling`
+    /// and the executor stops it at the connector's next page boundary, keeping
+    /// the signals and cursor of the pages already fetched. Cancelling a job
+    /// that is already `cancelling` is a no-op; any other status is `409`.
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
+    pub async fn cancel_job(&self, tenant_id: Uuid, job_id: Uuid) -> Result<Model, ApiError> {
+        let job = self
+            .find_by_tenant(tenant_id, job_id)
+            .await?
+            .ok_or_else(|| {
+                ApiError::new(
+                    axum::http::StatusCode::NOT_FOUND,
+                    "NOT_FOUND",
+                    "Sync job not found",
+                )
+            })?;
+
+        let now = Utc::now().fixed_offset();
+        let update = match job.status.as_str() {
+            "cancelling" => return Ok(job),
+            "queued" => Entity::update_many()
+                .col_expr(Column::Status, Expr::value("cancelled"))
+                .col_expr(Column::FinishedAt, Expr::value(now)),
+            "running" => Entity::update_many().col_expr(Column::Status, Expr::value("cancelling")),
+            _ => {
+                return Err(ApiError::new(
+                    axum::http::StatusCode::CONFLICT,
+                    "JOB_NOT_CANCELLABLE",
+                    format!("Job is {} and cannot be cancelled", job.status),
+                ));
+            }
+        };
+
+        let result = update
+            .col_expr(Column::UpdatedAt, Expr::value(now))
+            .filter(Column::Id.eq(job.id))
+            // Guard on status so a job claimed or finished meanwhile is left untouched
+            .filter(Column::Status.eq(job.status.clone()))
+            .exec(&self.db)
+            .await
+            .map_err(|e| {
+                tracing::error!("Failed to cancel sync job: {}", e);
+                ApiError::new(
+                    axum::http::StatusCode::INTERNAL_SERVER_ERROR,
+                    "INTERNAL_SERVER_ERROR",
+                    "Failed to cancel sync job",
+                )
+            })?;
+
+        query_stats::record_rows(result.rows_affected);
+        if result.rows_affected == 0 {
+            return Err(ApiError::new(
+                axum::http::StatusCode::CONFLICT,
+                "JOB_NOT_CANCELLABLE",
+                "Job changed state while cancelling",
+            ));
+        }
+
+        self.find_by_tenant(tenant_id, job.id)
+            .await?
+            .ok_or_else(|| {
+                ApiError::new(
+                    axum::http::StatusCode::NOT_FOUND,
+                    "NOT_FOUND",
+                    "Sync job not found",
+                )
+            })
     }
 
     /// List sync jobs for a tenant with optional filtering
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
     pub async fn list_by_tenant(
         &self,
         tenant_id: Uuid,
@@ -197,6 +719,7 @@ impl SyncJobRepository {
         } else {
             query.all(&self.db).await
         }
+        .inspect(|rows| query_stats::record_rows(rows.len() as u64))
         .map_err(|e| {
             tracing::error!("Failed to list sync jobs: {}", e);
             ApiError::new(
@@ -210,6 +733,7 @@ impl SyncJobRepository {
     }
 
     /// Update the status of a sync job
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
     pub async fn update_status(
         &self,
         tenant_id: Uuid,
@@ -217,11 +741,13 @@ impl SyncJobRepository {
         status: String,
         error: Option<JsonValue>,
     ) -> Result<Model, ApiError> {
-        let job = Entity::find_by_id(job_id)
-            .filter(Column::TenantId.eq(tenant_id))
-            .one(&self.db)
-            .await
-            .map_err(|e| {
+        let job = with_retry(|| {
+            Entity::find_by_id(job_id)
+                .filter(Column::TenantId.eq(tenant_id))
+                .one(&self.db)
+        })
+        .await
+        .map_err(|e| {
                 tracing::error!("Failed to find sync job for status update: {}", e);
                 ApiError::new(
                     axum::http::StatusCode::INTERNAL_SERVER_ERROR,
@@ -260,6 +786,7 @@ impl SyncJobRepository {
 
     /// List jobs with cursor pagination and comprehensive filtering
     /// Jobs are ordered by scheduled_at DESC, id DESC for consistent pagination
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
     pub async fn list_jobs(
         &self,
         tenant_id: Uuid,
@@ -317,6 +844,7 @@ impl SyncJobRepository {
             .limit(Some(limit as u64 + 1))
             .all(&self.db)
             .await
+            .inspect(|rows| query_stats::record_rows(rows.len() as u64))
             .map_err(|e| {
                 tracing::error!("Failed to list jobs with cursor: {}", e);
                 ApiError::new(
@@ -345,3 +873,214 @@ impl SyncJobRepository {
         Ok(ListJobsResult { jobs, next_cursor })
     }
 }
+
+/// Conflict returned when a manual sync would duplicate a pending incremental job
+fn sync_already_pending(pending: &Model) -> ApiError {
+    ApiError::new(
+        axum::http::StatusCode::CONFLICT,
+        "SYNC_ALREADY_PENDING",
+        format!(
+            "An incremental sync is already {} for this connection",
+            pending.status
+        ),
+    )
+    .with_details(serde_json::json!({
+        "job_id": pending.id,
+        "status": pending.status,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::setup_connection;
+
+    fn policy(debounce_secs: i64, max_delay_secs: i64) -> CoalescePolicy {
+        CoalescePolicy {
+            debounce: chrono::Duration::seconds(debounce_secs),
+            max_delay: chrono::Duration::seconds(max_delay_secs),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_repeated_triggers_coalesce_into_one_job() {
+        let (db, tenant_id, connection_id) = setup_connection().await;
+        let repo = SyncJobRepository::new(db.clone());
+
+        let first = repo
+            .enqueue_coalesced_job(
+                tenant_id,
+                "test-provider",
+                connection_id,
+                "incremental",
+                20,
+                policy(30, 300),
+            )
+            .await
+            .unwrap();
+        assert!(matches!(first, EnqueueOutcome::Created(_)));
+
+        let second = repo
+            .enqueue_coalesced_job(
+                tenant_id,
+                "test-provider",
+                connection_id,
+                "incremental",
+                40,
+                policy(60, 300),
+            )
+            .await
+            .unwrap();
+        let EnqueueOutcome::Coalesced(coalesced) = second else {
+            panic!("expected the second trigger to coalesce");
+        };
+
+        assert_eq!(coalesced.id, first.job().id);
+        assert_eq!(coalesced.priority, 40);
+        assert!(coalesced.scheduled_at > first.job().scheduled_at);
+
+        let jobs = repo
+            .list_by_tenant(tenant_id, None, None, None, None)
+            .await
+            .unwrap();
+        assert_eq!(jobs.len(), 1);
+        assert_eq!(jobs[0].priority, 40);
+    }
+
+    #[tokio::test]
+    async fn test_coalescing_never_postpones_past_max_delay() {
+        let (db, tenant_id, connection_id) = setup_connection().await;
+        let repo = SyncJobRepository::new(db.clone());
+
+        let created = repo
+            .enqueue_coalesced_job(
+                tenant_id,
+                "test-provider",
+                connection_id,
+                "incremental",
+                40,
+                policy(5, 10),
+            )
+            .await
+            .unwrap();
+
+        let coalesced = repo
+            .enqueue_coalesced_job(
+                tenant_id,
+                "test-provider",
+                connection_id,
+                "incremental",
+                10,
+                policy(3600, 10),
+            )
+            .await
+            .unwrap();
+
+        let job = coalesced.job();
+        assert_eq!(job.id, created.job().id);
+        assert_eq!(job.priority, 40);
+        assert_eq!(
+            job.scheduled_at,
+            created.job().created_at + chrono::Duration::seconds(10)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_running_incremental_job_absorbs_trigger() {
+        let (db, tenant_id, connection_id) = setup_connection().await;
+        let repo = SyncJobRepository::new(db.clone());
+
+        let created = repo
+            .enqueue_coalesced_job(
+                tenant_id,
+                "test-provider",
+                connection_id,
+                "incremental",
+                40,
+                policy(0, 300),
+            )
+            .await
+            .unwrap();
+        repo.update_status(tenant_id, created.job().id, "running".to_string(), None)
+            .await
+            .unwrap();
+
+        let outcome = repo
+            .enqueue_coalesced_job(
+                tenant_id,
+                "test-provider",
+                connection_id,
+                "incremental",
+                40,
+                policy(0, 300),
+            )
+            .await
+            .unwrap();
+
+        assert!(
+            matches!(outcome, EnqueueOutcome::Coalesced(ref job) if job.id == created.job().id)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_retry_job_requeues_failed_jobs_only() {
+        let (db, tenant_id, connection_id) = setup_connection().await;
+        let repo = SyncJobRepository::new(db.clone());
+
+        let job = repo
+            .enqueue_sync_job(tenant_id, "test-provider", connection_id, None)
+            .await
+            .unwrap();
+        repo.update_status(tenant_id, job.id, "running".to_string(), None)
+            .await
+            .unwrap();
+        let err = repo.retry_job(job.id).await.unwrap_err();
+        assert_eq!(err.status, axum::http::StatusCode::CONFLICT);
+
+        repo.update_status(tenant_id, job.id, "failed".to_string(), None)
+            .await
+            .unwrap();
+        let retried = repo.retry_job(job.id).await.unwrap();
+        assert_eq!(retried.status, "queued");
+        assert!(retried.retry_after.is_none());
+        assert!(retried.scheduled_at <= Utc::now().fixed_offset());
+
+        let missing = repo.retry_job(Uuid::new_v4()).await.unwrap_err();
+        assert_eq!(missing.status, axum::http::StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_cancel_job_stops_queued_and_flags_running_jobs() {
+        let (db, tenant_id, connection_id) = setup_connection().await;
+        let repo = SyncJobRepository::new(db.clone());
+
+        let queued = repo
+            .enqueue_sync_job(tenant_id, "test-provider", connection_id, None)
+            .await
+            .unwrap();
+        let cancelled = repo.cancel_job(tenant_id, queued.id).await.unwrap();
+        assert_eq!(cancelled.status, "cancelled");
+        assert!(cancelled.finished_at.is_some());
+        let err = repo.cancel_job(tenant_id, queued.id).await.unwrap_err();
+        assert_eq!(err.code.to_string(), "JOB_NOT_CANCELLABLE");
+
+        let running = repo
+            .enqueue_sync_job(tenant_id, "test-provider", connection_id, None)
+            .await
+            .unwrap();
+        repo.update_status(tenant_id, running.id, "running".to_string(), None)
+            .await
+            .unwrap();
+        let cancelling = repo.cancel_job(tenant_id, running.id).await.unwrap();
+        assert_eq!(cancelling.status, "cancelling");
+        assert!(cancelling.finished_at.is_none());
+        let again = repo.cancel_job(tenant_id, running.id).await.unwrap();
+        assert_eq!(again.status, "cancelling");
+
+        let other_tenant = repo
+            .cancel_job(Uuid::new_v4(), running.id)
+            .await
+            .unwrap_err();
+        assert_eq!(other_tenant.status, axum::http::StatusCode::NOT_FOUND);
+    }
+}
@@ -0,0 +1,91 @@
+//! # Sync Job Run Repository
+//!
+//! Append-only history of executed sync job runs. The executor writes one row
+//! per run once it knows how the run ended; operators read them back per
+//! connection to debug failing or slow syncs.
+
+use chrono::{DateTime, Utc};
+use sea_orm::{
+    ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder,
+    QuerySelect, Set,
+};
+use uuid::Uuid;
+
+use crate::error::RepositoryError;
+use crate::models::sync_job::Model as SyncJobModel;
+use crate::models::sync_job_run::{ActiveModel, Column, Entity, Model};
+use crate::query_stats;
+use tracing::{field::Empty, instrument};
+
+/// How an executed run of a job ended
+#[derive(Debug, Clone)]
+pub struct NewSyncJobRun<'a> {
+    /// Job that ran
+    pub job: &'a SyncJobModel,
+    /// When the executor started the run
+    pub started_at: DateTime<Utc>,
+    /// When the run ended
+    pub finished_at: DateTime<Utc>,
+    /// Signals the run persisted
+    pub items_processed: usize,
+    /// Outcome constant from [`crate::models::sync_job_run`]
+    pub outcome: &'static str,
+    /// Error message of a run that did not succeed
+    pub error: Option<String>,
+}
+
+/// Repository for sync job run history
+pub struct SyncJobRunRepository {
+    db: DatabaseConnection,
+}
+
+impl SyncJobRunRepository {
+    /// Create a new SyncJobRunRepository with the given database connection
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    /// Append a run to the history on `conn`
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
+    pub async fn record<C: ConnectionTrait>(
+        conn: &C,
+        run: NewSyncJobRun<'_>,
+    ) -> Result<(), RepositoryError> {
+        Entity::insert(ActiveModel {
+            id: Set(Uuid::new_v4()),
+            tenant_id: Set(run.job.tenant_id),
+            connection_id: Set(run.job.connection_id),
+            job_id: Set(run.job.id),
+            job_type: Set(run.job.job_type.clone()),
+            attempt: Set(run.job.attempts),
+            started_at: Set(run.started_at.fixed_offset()),
+            finished_at: Set(run.finished_at.fixed_offset()),
+            items_processed: Set(i64::try_from(run.items_processed).unwrap_or(i64::MAX)),
+            outcome: Set(run.outcome.to_string()),
+            error: Set(run.error),
+        })
+        .exec_without_returning(conn)
+        .await
+        .map_err(RepositoryError::database_error)?;
+        Ok(())
+    }
+
+    /// List the most recent runs of a connection's jobs, newest first
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
+    pub async fn list_for_connection(
+        &self,
+        tenant_id: Uuid,
+        connection_id: Uuid,
+        limit: u64,
+    ) -> Result<Vec<Model>, RepositoryError> {
+        Entity::find()
+            .filter(Column::TenantId.eq(tenant_id))
+            .filter(Column::ConnectionId.eq(connection_id))
+            .order_by_desc(Column::StartedAt)
+            .limit(limit)
+            .all(&self.db)
+            .await
+            .inspect(|rows| query_stats::record_rows(rows.len() as u64))
+            .map_err(RepositoryError::database_error)
+    }
+}
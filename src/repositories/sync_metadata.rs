@@ -14,52 +14,55 @@ use crate::config::SchedulerConfig;
 /// Minimum override interval allowed by the scheduler (one minute).
 pub const MIN_SYNC_INTERVAL_SECONDS: u64 = 60;
 
-/// Helper to convert between cursor storage format and Cursor type
-pub fn cursor_from_json(value: Option<&JsonValue>) -> Option<crate::connectors::Cursor> {
-    let value = value?;
-
-    if let JsonValue::Object(map) = value
-        && map.len() == 1
-        && let Some(inner) = map.get("value")
-    {
-        return Some(crate::connectors::Cursor::from_json(inner.clone()));
-    }
-
-    Some(crate::connectors::Cursor::from_json(value.clone()))
-}
-
-/// Helper to convert Cursor to JSON storage format
-pub fn cursor_to_json(cursor: Option<&crate::connectors::Cursor>) -> Option<JsonValue> {
-    cursor.map(|c| c.as_json().clone())
-}
+/// Consecutive busy or idle sync cycles before the adaptive interval moves.
+pub const ADAPTIVE_STREAK_CYCLES: i32 = 3;
 
 /// Metadata stored under `connections.metadata.sync`.
 #[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
 pub struct ConnectionSyncMetadata {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub interval_seconds: Option<u64>,
+    /// Interval adapted to observed volume; `interval_seconds` takes precedence
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub adaptive_interval_seconds: Option<u64>,
+    /// Consecutive cycles that returned a full page (positive) or nothing (negative)
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub activity_streak: i32,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub next_run_at: Option<DateTime<Utc>>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub last_jitter_seconds: Option<u64>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub first_activated_at: Option<DateTime<Utc>>,
-    #[serde(
-        default,
-        skip_serializing_if = "Option::is_none",
-        deserialize_with = "deserialize_cursor_opt"
-    )]
-    pub cursor: Option<crate::connectors::Cursor>,
+    /// Sealed connector cursor; see [`crate::cursor::CursorSigner`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<JsonValue>,
+    /// Windows during which the scheduler enqueues no syncs
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub blackouts: Vec<BlackoutWindow>,
+    /// Run syncs without persisting signals or advancing the cursor
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub dry_run: bool,
+}
+
+fn is_zero(value: &i32) -> bool {
+    *value == 0
 }
 
-fn deserialize_cursor_opt<'de, D>(
-    deserializer: D,
-) -> Result<Option<crate::connectors::Cursor>, D::Error>
-where
-    D: serde::Deserializer<'de>,
-{
-    let value = Option::<JsonValue>::deserialize(deserializer)?;
-    Ok(cursor_from_json(value.as_ref()))
+/// A period during which scheduled syncs are suppressed, e.g. provider maintenance.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BlackoutWindow {
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+impl BlackoutWindow {
+    /// Whether `at` falls inside the window (start inclusive, end exclusive).
+    pub fn contains(&self, at: DateTime<Utc>) -> bool {
+        self.starts_at <= at && at < self.ends_at
+    }
 }
 
 impl ConnectionSyncMetadata {
@@ -141,21 +144,79 @@ impl ConnectionSyncMetadata {
     }
 
     /// Calculate the effective base interval in seconds based on overrides and defaults.
-    pub fn effective_interval_seconds(&self, scheduler: &SchedulerConfig) -> u64 {
+    ///
+    /// An operator override wins over the adaptive interval, which wins over the
+    /// provider's default.
+    pub fn effective_interval_seconds(&self, scheduler: &SchedulerConfig, provider: &str) -> u64 {
+        let in_bounds = |value: &u64| {
+            *value >= MIN_SYNC_INTERVAL_SECONDS
+                && *value <= scheduler.max_overridden_interval_seconds
+        };
         self.interval_seconds
-            .filter(|value| {
-                *value >= MIN_SYNC_INTERVAL_SECONDS
-                    && *value <= scheduler.max_overridden_interval_seconds
-            })
-            .unwrap_or(scheduler.default_interval_seconds)
+            .filter(in_bounds)
+            .or(self.adaptive_interval_seconds.filter(in_bounds))
+            .unwrap_or_else(|| scheduler.default_interval_for(provider))
+    }
+
+    /// Record how a sync cycle went: `full_page` when the provider had more
+    /// pages, `idle` when it returned no signals.
+    ///
+    /// Returns `true` if the metadata was modified.
+    pub fn record_activity(&mut self, full_page: bool, idle: bool) -> bool {
+        let streak = if full_page {
+            self.activity_streak.max(0).saturating_add(1)
+        } else if idle {
+            self.activity_streak.min(0).saturating_sub(1)
+        } else {
+            0
+        };
+        let changed = streak != self.activity_streak;
+        self.activity_streak = streak;
+        changed
+    }
+
+    /// Halve the adaptive interval after a streak of full pages or double it
+    /// after a streak of idle cycles, within the scheduler's interval bounds.
+    ///
+    /// Connections with an interval override keep it. Returns `true` if the
+    /// metadata was modified.
+    pub fn adapt_interval(&mut self, scheduler: &SchedulerConfig, provider: &str) -> bool {
+        if self.activity_streak.abs() < ADAPTIVE_STREAK_CYCLES {
+            return false;
+        }
+        if self.interval_seconds.is_none() {
+            let current = self.effective_interval_seconds(scheduler, provider);
+            let adapted = if self.activity_streak > 0 {
+                current / 2
+            } else {
+                current.saturating_mul(2)
+            }
+            .clamp(
+                MIN_SYNC_INTERVAL_SECONDS,
+                scheduler.max_overridden_interval_seconds,
+            );
+            self.adaptive_interval_seconds =
+                (adapted != scheduler.default_interval_for(provider)).then_some(adapted);
+        }
+        self.activity_streak = 0;
+        true
+    }
+
+    /// The blackout window covering `at`, if any.
+    pub fn blackout_at(&self, at: DateTime<Utc>) -> Option<&BlackoutWindow> {
+        self.blackouts.iter().find(|window| window.contains(at))
     }
 
     fn is_empty(&self) -> bool {
         self.interval_seconds.is_none()
+            && self.adaptive_interval_seconds.is_none()
+            && self.activity_streak == 0
             && self.next_run_at.is_none()
             && self.last_jitter_seconds.is_none()
             && self.first_activated_at.is_none()
             && self.cursor.is_none()
+            && self.blackouts.is_empty()
+            && !self.dry_run
     }
 }
 
@@ -170,6 +231,7 @@ mod tests {
             jitter_pct_min: 0.0,
             jitter_pct_max: 0.2,
             max_overridden_interval_seconds: 86400,
+            provider_intervals: [("google-drive".to_string(), 3600)].into(),
         }
     }
 
@@ -235,7 +297,10 @@ mod tests {
             ..Default::default()
         };
 
-        assert_eq!(metadata.effective_interval_seconds(&test_config()), 1800);
+        assert_eq!(
+            metadata.effective_interval_seconds(&test_config(), "github"),
+            1800
+        );
     }
 
     #[test]
@@ -245,7 +310,14 @@ mod tests {
             ..Default::default()
         };
 
-        assert_eq!(metadata.effective_interval_seconds(&test_config()), 900);
+        assert_eq!(
+            metadata.effective_interval_seconds(&test_config(), "github"),
+            900
+        );
+        assert_eq!(
+            metadata.effective_interval_seconds(&test_config(), "google-drive"),
+            3600
+        );
     }
 
     #[test]
@@ -277,4 +349,44 @@ mod tests {
             &JsonValue::from(60)
         );
     }
+
+    #[test]
+    fn adaptive_interval_follows_activity_streaks() {
+        let config = test_config();
+        let mut metadata = ConnectionSyncMetadata::default();
+
+        // A streak is broken by a cycle of the other kind
+        metadata.record_activity(true, false);
+        metadata.record_activity(true, false);
+        assert!(metadata.record_activity(false, true));
+        assert_eq!(metadata.activity_streak, -1);
+        assert!(!metadata.adapt_interval(&config, "github"));
+
+        for _ in 0..ADAPTIVE_STREAK_CYCLES {
+            metadata.record_activity(true, false);
+        }
+        assert!(metadata.adapt_interval(&config, "github"));
+        assert_eq!(metadata.effective_interval_seconds(&config, "github"), 450);
+        assert_eq!(metadata.activity_streak, 0);
+
+        // Idle cycles lengthen it again, up to the maximum
+        for _ in 0..10 {
+            for _ in 0..ADAPTIVE_STREAK_CYCLES {
+                metadata.record_activity(false, true);
+            }
+            metadata.adapt_interval(&config, "github");
+        }
+        assert_eq!(
+            metadata.effective_interval_seconds(&config, "github"),
+            86400
+        );
+
+        // An operator override is never adapted
+        metadata.interval_seconds = Some(600);
+        for _ in 0..ADAPTIVE_STREAK_CYCLES {
+            metadata.record_activity(true, false);
+        }
+        assert!(metadata.adapt_interval(&config, "github"));
+        assert_eq!(metadata.effective_interval_seconds(&config, "github"), 600);
+    }
 }
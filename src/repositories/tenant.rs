@@ -3,16 +3,30 @@
 //! This module contains the repository implementation for Tenant entities,
 //! providing CRUD operations for tenant management.
 
+use crate::config_resolution::ConfigOverrides;
+use crate::db::with_retry;
 use crate::error::RepositoryError;
 use crate::models::tenant::{
     ActiveModel as TenantActiveModel, Entity as Tenant, Model as TenantModel,
 };
-use chrono::Utc;
+use crate::models::tenant_signal_config::Entity as TenantSignalConfig;
+use crate::models::tenant_signal_config::{
+    ActiveModel as TenantSignalConfigActiveModel, Model as TenantSignalConfigModel,
+};
+use crate::models::tenant_signal_kind::{self, Model as TenantSignalKindModel};
+use crate::models::{connection, signal};
+use crate::models::{tenant_config, tenant_data_key};
+use crate::query_stats;
+use crate::repositories::{OrganizationRepository, TenantSignalConfigRepository};
+use chrono::{DateTime, FixedOffset, Utc};
+use sea_orm::sea_query::Expr;
 use sea_orm::{
-    ActiveModelTrait, Database, DatabaseConnection, EntityTrait, IntoActiveModel, ModelTrait, PaginatorTrait,
-    Set, Statement,
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, IntoActiveModel, ModelTrait,
+    Order, PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, Set, TransactionTrait,
 };
 use serde_json::Value;
+use std::collections::HashMap;
+use tracing::{field::Empty, instrument};
 use uuid::Uuid;
 
 /// Request data for creating a new tenant
@@ -24,6 +38,80 @@ pub struct CreateTenantRequest {
     pub metadata: Option<Value>,
 }
 
+/// Initial configuration created together with a tenant
+#[derive(Debug, Clone)]
+pub struct TenantBootstrap {
+    /// Identifier for the new tenant; chosen up front so its data key can be wrapped for it
+    pub tenant_id: Uuid,
+    /// Display name for the tenant
+    pub name: String,
+    /// Organization whose default settings the tenant inherits
+    pub organization_id: Option<Uuid>,
+    /// Weak signal threshold for the tenant's signal config
+    pub weak_signal_threshold: f32,
+    /// Notification webhook for the tenant's signal config
+    pub webhook_url: Option<String>,
+    /// Tenant data key, already wrapped by the master key
+    pub wrapped_data_key: Vec<u8>,
+    /// Master key version that wrapped `wrapped_data_key`
+    pub data_key_version: i32,
+    /// Custom signal kinds to register, with optional descriptions
+    pub custom_signal_kinds: Vec<(String, Option<String>)>,
+}
+
+/// Rows created by [`TenantRepository::bootstrap_tenant`]
+#[derive(Debug, Clone)]
+pub struct BootstrappedTenant {
+    pub tenant: TenantModel,
+    pub signal_config: TenantSignalConfigModel,
+    pub custom_signal_kinds: Vec<TenantSignalKindModel>,
+}
+
+/// Status given to the placeholder connections of a sandbox tenant.
+///
+/// Only `active` connections are scheduled for sync, so sandbox connections
+/// never call a provider.
+pub const SANDBOX_CONNECTION_STATUS: &str = "sandbox";
+
+/// Maximum number of signals copied into a sandbox tenant
+pub const MAX_SANDBOX_SIGNAL_SAMPLE: u64 = 10_000;
+
+/// Signals sampled from the source tenant when cloning a sandbox
+#[derive(Debug, Clone)]
+pub struct SandboxSignalSample {
+    /// Number of signals to copy, at most [`MAX_SANDBOX_SIGNAL_SAMPLE`]
+    pub size: u64,
+    /// Only signals that occurred at or after this time are sampled
+    pub since: DateTime<FixedOffset>,
+}
+
+/// Sandbox tenant to clone from an existing tenant
+#[derive(Debug, Clone)]
+pub struct SandboxClone {
+    /// Tenant whose configuration is copied
+    pub source_tenant_id: Uuid,
+    /// Identifier for the sandbox; chosen up front so its data key can be wrapped for it
+    pub tenant_id: Uuid,
+    /// Display name for the sandbox
+    pub name: String,
+    /// Sandbox data key, already wrapped by the master key
+    pub wrapped_data_key: Vec<u8>,
+    /// Master key version that wrapped `wrapped_data_key`
+    pub data_key_version: i32,
+    /// Optional random sample of the source tenant's signals
+    pub signal_sample: Option<SandboxSignalSample>,
+}
+
+/// Rows created by [`TenantRepository::clone_sandbox`]
+#[derive(Debug, Clone)]
+pub struct ClonedSandbox {
+    pub tenant: TenantModel,
+    pub signal_config: Option<TenantSignalConfigModel>,
+    pub custom_signal_kinds: Vec<TenantSignalKindModel>,
+    pub connections_copied: u64,
+    pub signals_copied: u64,
+}
+
 /// Repository for Tenant database operations
 pub struct TenantRepository<'a> {
     db: &'a DatabaseConnection,
@@ -36,6 +124,7 @@ impl<'a> TenantRepository<'a> {
     }
 
     /// Create a new tenant
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
     pub async fn create_tenant(
         &self,
         request: CreateTenantRequest,
@@ -50,6 +139,9 @@ impl<'a> TenantRepository<'a> {
             id: Set(tenant_id),
             name: Set(Some(request.name)),
             created_at: Set(now.into()),
+            organization_id: Set(None),
+            settings: Set(None),
+            sandbox_of: Set(None),
         };
 
         let result = tenant
@@ -60,13 +152,310 @@ impl<'a> TenantRepository<'a> {
         Ok(result)
     }
 
+    /// Create a tenant with its signal config, data key and custom signal kinds in one transaction
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
+    pub async fn bootstrap_tenant(
+        &self,
+        bootstrap: TenantBootstrap,
+    ) -> Result<BootstrappedTenant, RepositoryError> {
+        self.validate_tenant_name(&bootstrap.name)?;
+        if let Some(ref url) = bootstrap.webhook_url {
+            TenantSignalConfigRepository::validate_webhook_url(url)?;
+        }
+        if let Some(organization_id) = bootstrap.organization_id
+            && OrganizationRepository::new(self.db)
+                .get(organization_id)
+                .await?
+                .is_none()
+        {
+            return Err(RepositoryError::NotFound(format!(
+                "Organization {}",
+                organization_id
+            )));
+        }
+
+        let now = Utc::now().fixed_offset();
+        let txn = self
+            .db
+            .begin()
+            .await
+            .map_err(RepositoryError::database_error)?;
+
+        let tenant = TenantActiveModel {
+            id: Set(bootstrap.tenant_id),
+            name: Set(Some(bootstrap.name)),
+            created_at: Set(now),
+            organization_id: Set(bootstrap.organization_id),
+            settings: Set(None),
+            sandbox_of: Set(None),
+        }
+        .insert(&txn)
+        .await
+        .map_err(RepositoryError::database_error)?;
+
+        let signal_config = TenantSignalConfigActiveModel {
+            tenant_id: Set(tenant.id),
+            weak_signal_threshold: Set(bootstrap.weak_signal_threshold.clamp(0.0, 1.0)),
+            scoring_weights: Set(None),
+            webhook_url: Set(bootstrap.webhook_url),
+            webhook_signing_secret: Set(None),
+            created_at: Set(Some(now)),
+            updated_at: Set(Some(now)),
+        }
+        .insert(&txn)
+        .await
+        .map_err(RepositoryError::database_error)?;
+
+        tenant_data_key::ActiveModel {
+            tenant_id: Set(tenant.id),
+            wrapped_key: Set(bootstrap.wrapped_data_key),
+            key_version: Set(bootstrap.data_key_version),
+            created_at: Set(now),
+        }
+        .insert(&txn)
+        .await
+        .map_err(RepositoryError::database_error)?;
+
+        let mut custom_signal_kinds = Vec::with_capacity(bootstrap.custom_signal_kinds.len());
+        for (kind, description) in bootstrap.custom_signal_kinds {
+            let registration = tenant_signal_kind::ActiveModel {
+                id: Set(Uuid::new_v4()),
+                tenant_id: Set(tenant.id),
+                kind: Set(kind),
+                description: Set(description),
+                created_at: Set(now),
+            }
+            .insert(&txn)
+            .await
+            .map_err(RepositoryError::database_error)?;
+            custom_signal_kinds.push(registration);
+        }
+
+        txn.commit()
+            .await
+            .map_err(RepositoryError::database_error)?;
+
+        Ok(BootstrappedTenant {
+            tenant,
+            signal_config,
+            custom_signal_kinds,
+        })
+    }
+
+    /// Clone a tenant's configuration into a new sandbox tenant in one transaction.
+    ///
+    /// The tenant settings, signal threshold, scoring weights and custom signal
+    /// kinds are copied. The notification webhook, tokens and sync state are not.
+    /// When a signal sample is requested, each source connection it references
+    /// gets a tokenless placeholder in the sandbox so the copied signals keep
+    /// their provider and connection grouping.
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
+    pub async fn clone_sandbox(
+        &self,
+        clone: SandboxClone,
+    ) -> Result<ClonedSandbox, RepositoryError> {
+        self.validate_tenant_name(&clone.name)?;
+
+        let source = self
+            .get_tenant_by_id(clone.source_tenant_id)
+            .await?
+            .ok_or_else(|| RepositoryError::NotFound("Tenant not found".to_string()))?;
+        if source.sandbox_of.is_some() {
+            return Err(RepositoryError::validation_error(
+                "A sandbox tenant cannot be cloned",
+            ));
+        }
+
+        let source_config = TenantSignalConfig::find_by_id(source.id)
+            .one(self.db)
+            .await
+            .map_err(RepositoryError::database_error)?;
+        let source_tenant_config = tenant_config::Entity::find_by_id(source.id)
+            .one(self.db)
+            .await
+            .map_err(RepositoryError::database_error)?;
+        let source_kinds = tenant_signal_kind::Entity::find()
+            .filter(tenant_signal_kind::Column::TenantId.eq(source.id))
+            .order_by_asc(tenant_signal_kind::Column::Kind)
+            .all(self.db)
+            .await
+            .map_err(RepositoryError::database_error)?;
+        let sampled_signals = match clone.signal_sample {
+            Some(sample) => signal::Entity::find()
+                .filter(signal::Column::TenantId.eq(source.id))
+                .filter(signal::Column::OccurredAt.gte(sample.since))
+                .order_by(Expr::cust("RANDOM()"), Order::Asc)
+                .limit(sample.size.min(MAX_SANDBOX_SIGNAL_SAMPLE))
+                .all(self.db)
+                .await
+                .inspect(|rows| query_stats::record_rows(rows.len() as u64))
+                .map_err(RepositoryError::database_error)?,
+            None => Vec::new(),
+        };
+        let mut source_connection_ids: Vec<Uuid> = sampled_signals
+            .iter()
+            .map(|signal| signal.connection_id)
+            .collect();
+        source_connection_ids.sort_unstable();
+        source_connection_ids.dedup();
+        let source_connections = if source_connection_ids.is_empty() {
+            Vec::new()
+        } else {
+            connection::Entity::find()
+                .filter(connection::Column::Id.is_in(source_connection_ids))
+                .all(self.db)
+                .await
+                .map_err(RepositoryError::database_error)?
+        };
+
+        let now = Utc::now().fixed_offset();
+        let txn = self
+            .db
+            .begin()
+            .await
+            .map_err(RepositoryError::database_error)?;
+
+        // Notification targets stay with the source tenant
+        let settings = ConfigOverrides {
+            notification_urls: None,
+            ..ConfigOverrides::from_json(source.settings.as_ref())
+        };
+        let tenant = TenantActiveModel {
+            id: Set(clone.tenant_id),
+            name: Set(Some(clone.name)),
+            created_at: Set(now),
+            organization_id: Set(source.organization_id),
+            settings: Set(settings.to_json()),
+            sandbox_of: Set(Some(source.id)),
+        }
+        .insert(&txn)
+        .await
+        .map_err(RepositoryError::database_error)?;
+
+        if let Some(config) = source_tenant_config {
+            tenant_config::ActiveModel {
+                tenant_id: Set(tenant.id),
+                sync_interval_seconds: Set(config.sync_interval_seconds),
+                provider_sync_intervals: Set(config.provider_sync_intervals),
+                mail_spam_threshold: Set(config.mail_spam_threshold),
+                rate_limit_overrides: Set(config.rate_limit_overrides),
+                notification_urls: Set(None),
+                created_at: Set(now),
+                updated_at: Set(now),
+            }
+            .insert(&txn)
+            .await
+            .map_err(RepositoryError::database_error)?;
+        }
+
+        let signal_config = match source_config {
+            Some(config) => Some(
+                TenantSignalConfigActiveModel {
+                    tenant_id: Set(tenant.id),
+                    weak_signal_threshold: Set(config.weak_signal_threshold),
+                    scoring_weights: Set(config.scoring_weights),
+                    webhook_url: Set(None),
+                    webhook_signing_secret: Set(None),
+                    created_at: Set(Some(now)),
+                    updated_at: Set(Some(now)),
+                }
+                .insert(&txn)
+                .await
+                .map_err(RepositoryError::database_error)?,
+            ),
+            None => None,
+        };
+
+        tenant_data_key::ActiveModel {
+            tenant_id: Set(tenant.id),
+            wrapped_key: Set(clone.wrapped_data_key),
+            key_version: Set(clone.data_key_version),
+            created_at: Set(now),
+        }
+        .insert(&txn)
+        .await
+        .map_err(RepositoryError::database_error)?;
+
+        let mut custom_signal_kinds = Vec::with_capacity(source_kinds.len());
+        for registration in source_kinds {
+            let copy = tenant_signal_kind::ActiveModel {
+                id: Set(Uuid::new_v4()),
+                tenant_id: Set(tenant.id),
+                kind: Set(registration.kind),
+                description: Set(registration.description),
+                created_at: Set(now),
+            }
+            .insert(&txn)
+            .await
+            .map_err(RepositoryError::database_error)?;
+            custom_signal_kinds.push(copy);
+        }
+
+        let mut connection_ids = HashMap::with_capacity(source_connections.len());
+        for source_connection in source_connections {
+            let placeholder = connection::ActiveModel {
+                id: Set(Uuid::new_v4()),
+                tenant_id: Set(tenant.id),
+                provider_slug: Set(source_connection.provider_slug),
+                external_id: Set(source_connection.external_id),
+                status: Set(SANDBOX_CONNECTION_STATUS.to_string()),
+                display_name: Set(source_connection.display_name),
+                expires_at: Set(None),
+                scopes: Set(None),
+                metadata: Set(None),
+                created_at: Set(now),
+                updated_at: Set(now),
+            }
+            .insert(&txn)
+            .await
+            .map_err(RepositoryError::database_error)?;
+            connection_ids.insert(source_connection.id, placeholder.id);
+        }
+
+        let signals_copied = sampled_signals.len() as u64;
+        for chunk in sampled_signals.chunks(500) {
+            let copies = chunk.iter().filter_map(|source_signal| {
+                let connection_id = connection_ids.get(&source_signal.connection_id)?;
+                Some(signal::ActiveModel {
+                    id: Set(Uuid::new_v4()),
+                    tenant_id: Set(tenant.id),
+                    provider_slug: Set(source_signal.provider_slug.clone()),
+                    connection_id: Set(*connection_id),
+                    kind: Set(source_signal.kind.clone()),
+                    occurred_at: Set(source_signal.occurred_at),
+                    received_at: Set(source_signal.received_at),
+                    payload: Set(source_signal.payload.clone()),
+                    dedupe_key: Set(source_signal.dedupe_key.clone()),
+                    created_at: Set(now),
+                    updated_at: Set(now),
+                })
+            });
+            signal::Entity::insert_many(copies)
+                .exec_without_returning(&txn)
+                .await
+                .map_err(RepositoryError::database_error)?;
+        }
+
+        txn.commit()
+            .await
+            .map_err(RepositoryError::database_error)?;
+
+        Ok(ClonedSandbox {
+            tenant,
+            signal_config,
+            custom_signal_kinds,
+            connections_copied: connection_ids.len() as u64,
+            signals_copied,
+        })
+    }
+
     /// Get tenant by ID
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
     pub async fn get_tenant_by_id(
         &self,
         tenant_id: Uuid,
     ) -> Result<Option<TenantModel>, RepositoryError> {
-        let tenant = Tenant::find_by_id(tenant_id)
-            .one(self.db)
+        let tenant = with_retry(|| Tenant::find_by_id(tenant_id).one(self.db))
             .await
             .map_err(RepositoryError::database_error)?;
 
@@ -74,16 +463,19 @@ impl<'a> TenantRepository<'a> {
     }
 
     /// List all tenants
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
     pub async fn list_tenants(&self) -> Result<Vec<TenantModel>, RepositoryError> {
         let tenants = Tenant::find()
             .all(self.db)
             .await
+            .inspect(|rows| query_stats::record_rows(rows.len() as u64))
             .map_err(RepositoryError::database_error)?;
 
         Ok(tenants)
     }
 
     /// Update tenant name
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
     pub async fn update_tenant_name(
         &self,
         tenant_id: Uuid,
@@ -108,7 +500,47 @@ impl<'a> TenantRepository<'a> {
         Ok(result)
     }
 
+    /// Set the tenant's organization and setting overrides
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
+    pub async fn update_settings(
+        &self,
+        tenant_id: Uuid,
+        organization_id: Option<Uuid>,
+        settings: &ConfigOverrides,
+    ) -> Result<TenantModel, RepositoryError> {
+        settings.validate()?;
+
+        if let Some(organization_id) = organization_id
+            && OrganizationRepository::new(self.db)
+                .get(organization_id)
+                .await?
+                .is_none()
+        {
+            return Err(RepositoryError::NotFound(format!(
+                "Organization {}",
+                organization_id
+            )));
+        }
+
+        let tenant = self
+            .get_tenant_by_id(tenant_id)
+            .await?
+            .ok_or_else(|| RepositoryError::NotFound("Tenant not found".to_string()))?;
+
+        let mut active_tenant = tenant.into_active_model();
+        active_tenant.organization_id = Set(organization_id);
+        active_tenant.settings = Set(settings.to_json());
+
+        let result = active_tenant
+            .update(self.db)
+            .await
+            .map_err(RepositoryError::database_error)?;
+
+        Ok(result)
+    }
+
     /// Delete a tenant
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
     pub async fn delete_tenant(&self, tenant_id: Uuid) -> Result<(), RepositoryError> {
         let tenant = Tenant::find_by_id(tenant_id)
             .one(self.db)
@@ -125,6 +557,7 @@ impl<'a> TenantRepository<'a> {
     }
 
     /// Check if a tenant exists
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
     pub async fn tenant_exists(&self, tenant_id: Uuid) -> Result<bool, RepositoryError> {
         let exists = Tenant::find_by_id(tenant_id)
             .one(self.db)
@@ -136,6 +569,7 @@ impl<'a> TenantRepository<'a> {
     }
 
     /// Get tenant count
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
     pub async fn get_tenant_count(&self) -> Result<i64, RepositoryError> {
         let count = Tenant::find()
             .count(self.db)
@@ -374,8 +808,8 @@ mod tests {
     #[tokio::test]
     async fn test_get_tenant_count() {
         // Create a completely isolated database for this test
-        let db_name = format!("sqlite::memory:");
-        let db = Database::connect(db_name).await.unwrap();
+        let db_name = "sqlite::memory:".to_string();
+        let db = sea_orm::Database::connect(db_name).await.unwrap();
 
         // Run migrations manually on this isolated database
         use migration::MigratorTrait;
@@ -0,0 +1,165 @@
+//! # Tenant Configuration Repository
+//!
+//! Reads and patches a tenant's `tenant_configs` row. Patches are merged into
+//! the stored settings and the result is validated as a whole before it is
+//! written, so a tenant never holds settings the service would reject.
+
+use chrono::Utc;
+use sea_orm::{ActiveModelTrait, DatabaseConnection, EntityTrait, IntoActiveModel, Set};
+use serde_json::{Map, Value as JsonValue};
+use tracing::{field::Empty, instrument};
+use uuid::Uuid;
+
+use crate::error::RepositoryError;
+use crate::models::tenant_config::{ActiveModel, Entity, Model, TenantConfigSettings};
+
+/// Repository for per-tenant configuration overrides
+pub struct TenantConfigRepository<'a> {
+    db: &'a DatabaseConnection,
+}
+
+impl<'a> TenantConfigRepository<'a> {
+    /// Create a new TenantConfigRepository with the given database connection
+    pub fn new(db: &'a DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    /// Get a tenant's configuration row, if it has one
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
+    pub async fn get(&self, tenant_id: Uuid) -> Result<Option<Model>, RepositoryError> {
+        Entity::find_by_id(tenant_id)
+            .one(self.db)
+            .await
+            .map_err(RepositoryError::database_error)
+    }
+
+    /// Merge `patch` into a tenant's settings and store the result.
+    ///
+    /// Keys in `patch` replace the stored value and `null` clears it.
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
+    pub async fn patch(
+        &self,
+        tenant_id: Uuid,
+        patch: &Map<String, JsonValue>,
+    ) -> Result<Model, RepositoryError> {
+        let existing = self.get(tenant_id).await?;
+        let current = existing.as_ref().map(Model::settings).unwrap_or_default();
+
+        let mut merged = match serde_json::to_value(&current) {
+            Ok(JsonValue::Object(map)) => map,
+            _ => Map::new(),
+        };
+        for (key, value) in patch {
+            if value.is_null() {
+                merged.remove(key);
+            } else {
+                merged.insert(key.clone(), value.clone());
+            }
+        }
+        let settings: TenantConfigSettings = serde_json::from_value(JsonValue::Object(merged))
+            .map_err(|e| RepositoryError::Validation(e.to_string()))?;
+        settings.overrides().validate()?;
+
+        let now = Utc::now().fixed_offset();
+        let mut active = match existing {
+            Some(model) => model.into_active_model(),
+            None => ActiveModel {
+                tenant_id: Set(tenant_id),
+                created_at: Set(now),
+                ..Default::default()
+            },
+        };
+        active.sync_interval_seconds = Set(settings
+            .sync_interval_seconds
+            .map(|interval| interval as i64));
+        active.provider_sync_intervals = Set(to_json(settings.provider_sync_intervals));
+        active.mail_spam_threshold = Set(settings.mail_spam_threshold);
+        active.rate_limit_overrides = Set(to_json(settings.rate_limit_overrides));
+        active.notification_urls = Set(to_json(settings.notification_urls));
+        active.updated_at = Set(now);
+
+        let result = if active.tenant_id.is_unchanged() {
+            active.update(self.db).await
+        } else {
+            active.insert(self.db).await
+        };
+        result.map_err(RepositoryError::database_error)
+    }
+}
+
+fn to_json<T: serde::Serialize>(value: Option<T>) -> Option<JsonValue> {
+    value.and_then(|value| serde_json::to_value(value).ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::AppConfig;
+    use crate::db::init_pool;
+    use migration::{Migrator, MigratorTrait};
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn test_patch_merges_validates_and_clears() {
+        let config = AppConfig {
+            profile: "test".to_string(),
+            ..Default::default()
+        };
+        let db = init_pool(&config).await.expect("Failed to init test DB");
+        Migrator::up(&db, None).await.unwrap();
+        let tenant_id = Uuid::new_v4();
+        crate::models::tenant::ActiveModel {
+            id: Set(tenant_id),
+            ..Default::default()
+        }
+        .insert(&db)
+        .await
+        .unwrap();
+        let repo = TenantConfigRepository::new(&db);
+        let patch = |value: JsonValue| value.as_object().unwrap().clone();
+
+        let stored = repo
+            .patch(
+                tenant_id,
+                &patch(json!({
+                    "sync_interval_seconds": 1800,
+                    "rate_limit_overrides": {"github": {"max_attempts": 3}},
+                })),
+            )
+            .await
+            .unwrap();
+        assert_eq!(stored.sync_interval_seconds, Some(1800));
+
+        let stored = repo
+            .patch(
+                tenant_id,
+                &patch(json!({
+                    "sync_interval_seconds": null,
+                    "mail_spam_threshold": 0.5,
+                })),
+            )
+            .await
+            .unwrap();
+        let settings = stored.settings();
+        assert_eq!(settings.sync_interval_seconds, None);
+        assert_eq!(settings.mail_spam_threshold, Some(0.5));
+        assert_eq!(
+            settings.rate_limit_overrides.as_ref().unwrap()["github"].max_attempts,
+            Some(3)
+        );
+
+        for rejected in [
+            json!({"sync_interval_seconds": 5}),
+            json!({"notification_urls": ["http://example.com/hook"]}),
+            json!({"rate_limit_overrides": {"github": {"max_concurrent": 2}}}),
+            json!({"unknown_setting": true}),
+        ] {
+            let err = repo.patch(tenant_id, &patch(rejected)).await.unwrap_err();
+            assert!(matches!(err, RepositoryError::Validation(_)), "{:?}", err);
+        }
+        assert_eq!(
+            repo.get(tenant_id).await.unwrap().unwrap().settings(),
+            settings
+        );
+    }
+}
@@ -0,0 +1,197 @@
+//! Tenant data key repository
+//!
+//! Each tenant's connection tokens are encrypted with a tenant-specific data key
+//! that is stored wrapped by the master key. Keys are created on first use;
+//! shredding a key makes every token encrypted under it permanently unreadable
+//! without affecting other tenants.
+//!
+//! Each row records the master key version that wrapped it. Reads unwrap with
+//! that version; the write path re-wraps a key still on a previous version with
+//! the current one, so rotating the master key never re-encrypts any tokens.
+
+use anyhow::{Result, anyhow};
+use sea_orm::sea_query::OnConflict;
+use sea_orm::{ActiveModelTrait, DatabaseConnection, EntityTrait, Set};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::crypto::{CryptoKey, MasterKeyring};
+use crate::db::with_retry;
+use crate::models::tenant_data_key::{self, Entity as TenantDataKey};
+use tracing::{field::Empty, instrument};
+
+/// Repository for wrapped per-tenant data keys
+#[derive(Debug, Clone)]
+pub struct TenantKeyRepository {
+    db: Arc<DatabaseConnection>,
+    master_keys: MasterKeyring,
+}
+
+impl TenantKeyRepository {
+    /// Creates a new TenantKeyRepository that wraps keys with the current version of `master_keys`
+    pub fn new(db: Arc<DatabaseConnection>, master_keys: impl Into<MasterKeyring>) -> Self {
+        Self {
+            db,
+            master_keys: master_keys.into(),
+        }
+    }
+
+    /// Loads and unwraps a tenant's data key, if one exists
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
+    pub async fn find(&self, tenant_id: &Uuid) -> Result<Option<CryptoKey>> {
+        Ok(self.find_row(tenant_id).await?.map(|(_, key)| key))
+    }
+
+    async fn find_row(
+        &self,
+        tenant_id: &Uuid,
+    ) -> Result<Option<(tenant_data_key::Model, CryptoKey)>> {
+        let row = with_retry(|| TenantDataKey::find_by_id(*tenant_id).one(&*self.db)).await?;
+        row.map(|row| {
+            let key = self
+                .master_keys
+                .unwrap_tenant_key(row.key_version, *tenant_id, &row.wrapped_key)
+                .map_err(|e| anyhow!("Failed to unwrap tenant data key: {}", e))?;
+            Ok((row, key))
+        })
+        .transpose()
+    }
+
+    /// Returns a tenant's data key, generating and storing one on first use.
+    ///
+    /// The returned key is always wrapped by the current master key version.
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
+    pub async fn get_or_create(&self, tenant_id: &Uuid) -> Result<CryptoKey> {
+        if let Some((row, key)) = self.find_row(tenant_id).await? {
+            if row.key_version != self.master_keys.current_version() {
+                self.rewrap(row, &key).await?;
+            }
+            return Ok(key);
+        }
+
+        let key = CryptoKey::generate();
+        let (key_version, wrapped_key) = self
+            .master_keys
+            .wrap_tenant_key(*tenant_id, &key)
+            .map_err(|e| anyhow!("Failed to wrap tenant data key: {}", e))?;
+        let row = tenant_data_key::ActiveModel {
+            tenant_id: Set(*tenant_id),
+            wrapped_key: Set(wrapped_key),
+            key_version: Set(key_version),
+            created_at: Set(chrono::Utc::now().fixed_offset()),
+        };
+        // A concurrent writer may have created the key first; theirs wins
+        TenantDataKey::insert(row)
+            .on_conflict(
+                OnConflict::column(tenant_data_key::Column::TenantId)
+                    .do_nothing()
+                    .to_owned(),
+            )
+            .exec_without_returning(&*self.db)
+            .await?;
+
+        self.find(tenant_id)
+            .await?
+            .ok_or_else(|| anyhow!("tenant data key not persisted"))
+    }
+
+    // Wraps an existing data key with the current master key version; the key itself is unchanged
+    async fn rewrap(&self, row: tenant_data_key::Model, key: &CryptoKey) -> Result<()> {
+        let previous_version = row.key_version;
+        let (key_version, wrapped_key) = self
+            .master_keys
+            .wrap_tenant_key(row.tenant_id, key)
+            .map_err(|e| anyhow!("Failed to wrap tenant data key: {}", e))?;
+        let mut row: tenant_data_key::ActiveModel = row.into();
+        row.wrapped_key = Set(wrapped_key);
+        row.key_version = Set(key_version);
+        row.update(&*self.db).await?;
+        tracing::info!(
+            previous_version,
+            key_version,
+            "Re-wrapped tenant data key with the current master key version"
+        );
+        Ok(())
+    }
+
+    /// Deletes a tenant's data key, crypto-shredding its tokens. Returns whether a key existed.
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
+    pub async fn shred(&self, tenant_id: &Uuid) -> Result<bool> {
+        let result = TenantDataKey::delete_by_id(*tenant_id)
+            .exec(&*self.db)
+            .await?;
+        Ok(result.rows_affected > 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures;
+
+    async fn setup() -> (Arc<DatabaseConnection>, Uuid) {
+        let (db, tenant_id) = test_fixtures::setup_tenant().await;
+        (Arc::new(db), tenant_id)
+    }
+
+    #[tokio::test]
+    async fn test_get_or_create_is_stable_until_shredded() {
+        let (db, tenant_id) = setup().await;
+        let repo = TenantKeyRepository::new(db.clone(), CryptoKey::new(vec![3u8; 32]).unwrap());
+
+        assert!(repo.find(&tenant_id).await.unwrap().is_none());
+        let first = repo.get_or_create(&tenant_id).await.unwrap();
+        let second = repo.get_or_create(&tenant_id).await.unwrap();
+        assert_eq!(first.as_bytes(), second.as_bytes());
+
+        // A different master key cannot unwrap the stored key
+        let other_master =
+            TenantKeyRepository::new(db.clone(), CryptoKey::new(vec![4u8; 32]).unwrap());
+        assert!(other_master.find(&tenant_id).await.is_err());
+
+        assert!(repo.shred(&tenant_id).await.unwrap());
+        assert!(!repo.shred(&tenant_id).await.unwrap());
+        assert!(repo.find(&tenant_id).await.unwrap().is_none());
+        let replacement = repo.get_or_create(&tenant_id).await.unwrap();
+        assert_ne!(replacement.as_bytes(), first.as_bytes());
+    }
+
+    #[tokio::test]
+    async fn test_rotation_rewraps_key_with_current_version() {
+        let (db, tenant_id) = setup().await;
+        let v1 = CryptoKey::new(vec![5u8; 32]).unwrap();
+        let v2 = CryptoKey::new(vec![6u8; 32]).unwrap();
+        let before = TenantKeyRepository::new(db.clone(), v1.clone());
+        let key = before.get_or_create(&tenant_id).await.unwrap();
+
+        let rotated = TenantKeyRepository::new(
+            db.clone(),
+            MasterKeyring::new(2, v2.clone())
+                .unwrap()
+                .with_previous(1, v1)
+                .unwrap(),
+        );
+        // Reads unwrap with the stored version and leave the row alone
+        let found = rotated.find(&tenant_id).await.unwrap().unwrap();
+        assert_eq!(found.as_bytes(), key.as_bytes());
+        let row = TenantDataKey::find_by_id(tenant_id)
+            .one(&*db)
+            .await
+            .unwrap();
+        assert_eq!(row.unwrap().key_version, 1);
+
+        // The write path moves the same key onto the current version
+        let rewrapped = rotated.get_or_create(&tenant_id).await.unwrap();
+        assert_eq!(rewrapped.as_bytes(), key.as_bytes());
+        let row = TenantDataKey::find_by_id(tenant_id)
+            .one(&*db)
+            .await
+            .unwrap();
+        assert_eq!(row.unwrap().key_version, 2);
+
+        // Once re-wrapped, the retired version is no longer needed
+        let current_only = TenantKeyRepository::new(db, MasterKeyring::new(2, v2).unwrap());
+        let found = current_only.find(&tenant_id).await.unwrap().unwrap();
+        assert_eq!(found.as_bytes(), key.as_bytes());
+    }
+}
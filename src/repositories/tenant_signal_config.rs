@@ -11,6 +11,7 @@ use crate::models::tenant_signal_config::{
 use sea_orm::{
     ActiveModelTrait, DatabaseConnection, EntityTrait, IntoActiveModel, ModelTrait, Set,
 };
+use tracing::{field::Empty, instrument};
 use uuid::Uuid;
 
 /// Repository for TenantSignalConfig database operations
@@ -25,6 +26,7 @@ impl<'a> TenantSignalConfigRepository<'a> {
     }
 
     /// Get or create tenant configuration with defaults
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
     pub async fn get_or_create(
         &self,
         tenant_id: Uuid,
@@ -40,6 +42,7 @@ impl<'a> TenantSignalConfigRepository<'a> {
             weak_signal_threshold: Set(0.7),
             scoring_weights: Set(None),
             webhook_url: Set(None),
+            webhook_signing_secret: Set(None),
             created_at: Set(Some(chrono::Utc::now().into())),
             updated_at: Set(Some(chrono::Utc::now().into())),
         };
@@ -53,6 +56,7 @@ impl<'a> TenantSignalConfigRepository<'a> {
     }
 
     /// Get tenant configuration by tenant ID
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
     pub async fn get(&self, tenant_id: Uuid) -> Result<Option<TenantConfigModel>, RepositoryError> {
         let config = TenantConfig::find_by_id(tenant_id)
             .one(self.db)
@@ -63,6 +67,7 @@ impl<'a> TenantSignalConfigRepository<'a> {
     }
 
     /// Update weak signal threshold for tenant
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
     pub async fn update_threshold(
         &self,
         tenant_id: Uuid,
@@ -82,6 +87,7 @@ impl<'a> TenantSignalConfigRepository<'a> {
     }
 
     /// Update scoring weights for tenant
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
     pub async fn update_scoring_weights(
         &self,
         tenant_id: Uuid,
@@ -108,6 +114,7 @@ impl<'a> TenantSignalConfigRepository<'a> {
     }
 
     /// Update webhook URL for tenant
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
     pub async fn update_webhook_url(
         &self,
         tenant_id: Uuid,
@@ -115,7 +122,7 @@ impl<'a> TenantSignalConfigRepository<'a> {
     ) -> Result<TenantConfigModel, RepositoryError> {
         // Validate webhook URL if provided
         if let Some(ref url) = webhook_url {
-            self.validate_webhook_url(url)?;
+            Self::validate_webhook_url(url)?;
         }
 
         let mut config = self.get_or_create(tenant_id).await?.into_active_model();
@@ -132,12 +139,14 @@ impl<'a> TenantSignalConfigRepository<'a> {
     }
 
     /// Get weak signal threshold for tenant (with fallback to default)
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
     pub async fn get_threshold(&self, tenant_id: Uuid) -> Result<f32, RepositoryError> {
         let config = self.get_or_create(tenant_id).await?;
         Ok(config.weak_signal_threshold)
     }
 
     /// Get scoring weights for tenant (with fallback to defaults)
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
     pub async fn get_scoring_weights(
         &self,
         tenant_id: Uuid,
@@ -147,6 +156,7 @@ impl<'a> TenantSignalConfigRepository<'a> {
     }
 
     /// Get webhook URL for tenant
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
     pub async fn get_webhook_url(
         &self,
         tenant_id: Uuid,
@@ -155,7 +165,37 @@ impl<'a> TenantSignalConfigRepository<'a> {
         Ok(config.and_then(|c| c.webhook_url))
     }
 
+    /// Get the tenant's webhook signing secret, generating one on first use
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
+    pub async fn get_or_create_signing_secret(
+        &self,
+        tenant_id: Uuid,
+    ) -> Result<String, RepositoryError> {
+        let config = self.get_or_create(tenant_id).await?;
+        if let Some(secret) = config.webhook_signing_secret {
+            return Ok(secret);
+        }
+        self.rotate_signing_secret(tenant_id).await
+    }
+
+    /// Replace the tenant's webhook signing secret with a new random one
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
+    pub async fn rotate_signing_secret(&self, tenant_id: Uuid) -> Result<String, RepositoryError> {
+        let mut config = self.get_or_create(tenant_id).await?.into_active_model();
+        let secret = crate::webhook_signing::generate_secret();
+
+        config.webhook_signing_secret = Set(Some(secret.clone()));
+        config.updated_at = Set(Some(chrono::Utc::now().into()));
+        config
+            .update(self.db)
+            .await
+            .map_err(RepositoryError::database_error)?;
+
+        Ok(secret)
+    }
+
     /// Delete tenant configuration
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
     pub async fn delete(&self, tenant_id: Uuid) -> Result<(), RepositoryError> {
         let config = TenantConfig::find_by_id(tenant_id)
             .one(self.db)
@@ -172,7 +212,7 @@ impl<'a> TenantSignalConfigRepository<'a> {
     }
 
     /// Validate webhook URL format and security requirements
-    fn validate_webhook_url(&self, url: &str) -> Result<(), RepositoryError> {
+    pub(crate) fn validate_webhook_url(url: &str) -> Result<(), RepositoryError> {
         // Check URL length
         if url.len() > 2048 {
             return Err(RepositoryError::validation_error(
@@ -0,0 +1,153 @@
+//! # Tenant Signal Kind Repository
+//!
+//! Registration table for tenant-defined custom signal kinds. Canonical kinds
+//! are strongly typed in [`crate::normalization::SignalKind`]; a `custom:` kind
+//! is only accepted for a tenant once it has been registered here.
+
+use crate::error::RepositoryError;
+use crate::models::tenant_signal_kind::{self, Entity as TenantSignalKind, Model};
+use sea_orm::sea_query::OnConflict;
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, Set};
+use tracing::{field::Empty, instrument};
+use uuid::Uuid;
+
+/// Repository for tenant custom signal kind registrations
+pub struct TenantSignalKindRepository<'a> {
+    db: &'a DatabaseConnection,
+}
+
+impl<'a> TenantSignalKindRepository<'a> {
+    /// Create a new TenantSignalKindRepository with the given database connection
+    pub fn new(db: &'a DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    /// Register a custom kind for a tenant; re-registering updates the description
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
+    pub async fn register(
+        &self,
+        tenant_id: Uuid,
+        kind: &str,
+        description: Option<String>,
+    ) -> Result<Model, RepositoryError> {
+        let registration = tenant_signal_kind::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            tenant_id: Set(tenant_id),
+            kind: Set(kind.to_string()),
+            description: Set(description),
+            created_at: Set(chrono::Utc::now().fixed_offset()),
+        };
+        TenantSignalKind::insert(registration)
+            .on_conflict(
+                OnConflict::columns([
+                    tenant_signal_kind::Column::TenantId,
+                    tenant_signal_kind::Column::Kind,
+                ])
+                .update_column(tenant_signal_kind::Column::Description)
+                .to_owned(),
+            )
+            .exec_without_returning(self.db)
+            .await
+            .map_err(RepositoryError::database_error)?;
+
+        self.find(tenant_id, kind)
+            .await?
+            .ok_or_else(|| RepositoryError::NotFound(kind.to_string()))
+    }
+
+    /// Look up a single registration
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
+    pub async fn find(
+        &self,
+        tenant_id: Uuid,
+        kind: &str,
+    ) -> Result<Option<Model>, RepositoryError> {
+        TenantSignalKind::find()
+            .filter(tenant_signal_kind::Column::TenantId.eq(tenant_id))
+            .filter(tenant_signal_kind::Column::Kind.eq(kind))
+            .one(self.db)
+            .await
+            .map_err(RepositoryError::database_error)
+    }
+
+    /// Whether the tenant has registered `kind`
+    pub async fn is_registered(
+        &self,
+        tenant_id: Uuid,
+        kind: &str,
+    ) -> Result<bool, RepositoryError> {
+        Ok(self.find(tenant_id, kind).await?.is_some())
+    }
+
+    /// All custom kinds registered by a tenant, ordered by kind
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
+    pub async fn list(&self, tenant_id: Uuid) -> Result<Vec<Model>, RepositoryError> {
+        TenantSignalKind::find()
+            .filter(tenant_signal_kind::Column::TenantId.eq(tenant_id))
+            .order_by_asc(tenant_signal_kind::Column::Kind)
+            .all(self.db)
+            .await
+            .map_err(RepositoryError::database_error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::AppConfig;
+    use crate::db::init_pool;
+    use migration::{Migrator, MigratorTrait};
+    use sea_orm::ActiveModelTrait;
+
+    #[tokio::test]
+    async fn test_registrations_are_tenant_scoped_and_idempotent() {
+        let config = AppConfig {
+            profile: "test".to_string(),
+            ..Default::default()
+        };
+        let db = init_pool(&config).await.expect("Failed to init test DB");
+        Migrator::up(&db, None).await.unwrap();
+
+        let tenant_id = Uuid::new_v4();
+        let other_tenant = Uuid::new_v4();
+        for id in [tenant_id, other_tenant] {
+            crate::models::tenant::ActiveModel {
+                id: Set(id),
+                ..Default::default()
+            }
+            .insert(&db)
+            .await
+            .unwrap();
+        }
+
+        let repo = TenantSignalKindRepository::new(&db);
+        let first = repo
+            .register(tenant_id, "custom:press_mention", None)
+            .await
+            .unwrap();
+        let second = repo
+            .register(
+                tenant_id,
+                "custom:press_mention",
+                Some("Coverage in the press".to_string()),
+            )
+            .await
+            .unwrap();
+        assert_eq!(first.id, second.id);
+        assert_eq!(second.description.as_deref(), Some("Coverage in the press"));
+
+        assert!(
+            repo.is_registered(tenant_id, "custom:press_mention")
+                .await
+                .unwrap()
+        );
+        assert!(
+            !repo
+                .is_registered(other_tenant, "custom:press_mention")
+                .await
+                .unwrap()
+        );
+        assert_eq!(repo.list(tenant_id).await.unwrap().len(), 1);
+        assert!(repo.list(other_tenant).await.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,90 @@
+//! # Token Event Repository
+//!
+//! Append-only history of token refreshes. The token refresh service writes
+//! one row per refresh attempt sent to a provider; operators read them back per
+//! connection to debug expiring, rotating or revoked provider credentials.
+
+use chrono::{DateTime, Utc};
+use sea_orm::{
+    ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder,
+    QuerySelect, Set,
+};
+use uuid::Uuid;
+
+use crate::error::RepositoryError;
+use crate::models::connection::Model as ConnectionModel;
+use crate::models::token_event::{ActiveModel, Column, Entity, Model};
+use crate::query_stats;
+use tracing::{field::Empty, instrument};
+
+/// How one refresh attempt of a connection ended
+#[derive(Debug, Clone)]
+pub struct NewTokenEvent<'a> {
+    /// Connection as it was before the refresh
+    pub connection: &'a ConnectionModel,
+    /// Access token expiry after a successful refresh
+    pub new_expires_at: Option<DateTime<Utc>>,
+    /// Whether the provider issued a new refresh token
+    pub refresh_token_rotated: bool,
+    /// Outcome constant from [`crate::models::token_event`]
+    pub outcome: &'static str,
+    /// Error message of a failed refresh
+    pub error: Option<String>,
+    /// When the refresh finished
+    pub occurred_at: DateTime<Utc>,
+}
+
+/// Repository for token refresh history
+pub struct TokenEventRepository {
+    db: DatabaseConnection,
+}
+
+impl TokenEventRepository {
+    /// Create a new TokenEventRepository with the given database connection
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    /// Append a refresh attempt to the history on `conn`
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
+    pub async fn record<C: ConnectionTrait>(
+        conn: &C,
+        event: NewTokenEvent<'_>,
+    ) -> Result<(), RepositoryError> {
+        Entity::insert(ActiveModel {
+            id: Set(Uuid::new_v4()),
+            tenant_id: Set(event.connection.tenant_id),
+            connection_id: Set(event.connection.id),
+            provider_slug: Set(event.connection.provider_slug.clone()),
+            previous_expires_at: Set(event.connection.expires_at),
+            new_expires_at: Set(event.new_expires_at.map(|at| at.fixed_offset())),
+            refresh_token_rotated: Set(event.refresh_token_rotated),
+            outcome: Set(event.outcome.to_string()),
+            error: Set(event.error),
+            occurred_at: Set(event.occurred_at.fixed_offset()),
+        })
+        .exec_without_returning(conn)
+        .await
+        .map_err(RepositoryError::database_error)?;
+        Ok(())
+    }
+
+    /// List the most recent refresh attempts of a connection, newest first
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
+    pub async fn list_for_connection(
+        &self,
+        tenant_id: Uuid,
+        connection_id: Uuid,
+        limit: u64,
+    ) -> Result<Vec<Model>, RepositoryError> {
+        Entity::find()
+            .filter(Column::TenantId.eq(tenant_id))
+            .filter(Column::ConnectionId.eq(connection_id))
+            .order_by_desc(Column::OccurredAt)
+            .limit(limit)
+            .all(&self.db)
+            .await
+            .inspect(|rows| query_stats::record_rows(rows.len() as u64))
+            .map_err(RepositoryError::database_error)
+    }
+}
@@ -0,0 +1,223 @@
+//! # Usage Repository
+//!
+//! Aggregates per-tenant metered usage over a time window for billing: signals
+//! ingested, connections active and sync job runtime. Queries read the raw
+//! tables, so a window can be recomputed at any time with the same result.
+
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+use sea_orm::{
+    ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QuerySelect,
+    prelude::DateTimeWithTimeZone, sea_query::Expr,
+};
+use uuid::Uuid;
+
+use crate::error::RepositoryError;
+use crate::models::connection::{self, Entity as Connection};
+use crate::models::signal::{self, Entity as Signal};
+use crate::models::sync_job::{self, Entity as SyncJob};
+use crate::query_stats;
+use tracing::{field::Empty, instrument};
+
+/// Metered usage for one tenant over a window
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TenantUsage {
+    /// Signals received during the window
+    pub signals_ingested: i64,
+    /// Active connections created before the end of the window
+    pub connections_active: i64,
+    /// Total runtime of sync jobs that finished during the window, in seconds
+    pub sync_seconds: f64,
+}
+
+/// Repository for metered usage queries
+pub struct UsageRepository {
+    db: DatabaseConnection,
+}
+
+impl UsageRepository {
+    /// Create a new UsageRepository with the given database connection
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    /// Usage per tenant for the half-open window `[start, end)`; tenants with no usage are omitted
+    #[instrument(skip_all, fields(db.statement = Empty, db.rows = Empty))]
+    pub async fn tenant_usage(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<BTreeMap<Uuid, TenantUsage>, RepositoryError> {
+        let (start, end) = (start.fixed_offset(), end.fixed_offset());
+        let mut usage: BTreeMap<Uuid, TenantUsage> = BTreeMap::new();
+
+        let signal_counts = Signal::find()
+            .select_only()
+            .column(signal::Column::TenantId)
+            .column_as(Expr::col(signal::Column::Id).count(), "signal_count")
+            .filter(signal::Column::ReceivedAt.gte(start))
+            .filter(signal::Column::ReceivedAt.lt(end))
+            .group_by(signal::Column::TenantId)
+            .into_tuple::<(Uuid, i64)>()
+            .all(&self.db)
+            .await
+            .inspect(|rows| query_stats::record_rows(rows.len() as u64))
+            .map_err(RepositoryError::database_error)?;
+        for (tenant_id, count) in signal_counts {
+            usage.entry(tenant_id).or_default().signals_ingested = count;
+        }
+
+        let connection_counts = Connection::find()
+            .select_only()
+            .column(connection::Column::TenantId)
+            .column_as(
+                Expr::col(connection::Column::Id).count(),
+                "connection_count",
+            )
+            .filter(connection::Column::Status.eq("active"))
+            .filter(connection::Column::CreatedAt.lt(end))
+            .group_by(connection::Column::TenantId)
+            .into_tuple::<(Uuid, i64)>()
+            .all(&self.db)
+            .await
+            .inspect(|rows| query_stats::record_rows(rows.len() as u64))
+            .map_err(RepositoryError::database_error)?;
+        for (tenant_id, count) in connection_counts {
+            usage.entry(tenant_id).or_default().connections_active = count;
+        }
+
+        let jobs = SyncJob::find()
+            .select_only()
+            .column(sync_job::Column::TenantId)
+            .column(sync_job::Column::StartedAt)
+            .column(sync_job::Column::FinishedAt)
+            .filter(sync_job::Column::StartedAt.is_not_null())
+            .filter(sync_job::Column::FinishedAt.gte(start))
+            .filter(sync_job::Column::FinishedAt.lt(end))
+            .into_tuple::<(
+                Uuid,
+                Option<DateTimeWithTimeZone>,
+                Option<DateTimeWithTimeZone>,
+            )>()
+            .all(&self.db)
+            .await
+            .inspect(|rows| query_stats::record_rows(rows.len() as u64))
+            .map_err(RepositoryError::database_error)?;
+        for (tenant_id, started_at, finished_at) in jobs {
+            if let (Some(started), Some(finished)) = (started_at, finished_at) {
+                usage.entry(tenant_id).or_default().sync_seconds +=
+                    (finished - started).num_milliseconds().max(0) as f64 / 1000.0;
+            }
+        }
+
+        Ok(usage)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::AppConfig;
+    use crate::db::init_pool;
+    use crate::repositories::provider::ProviderRepository;
+    use chrono::{Duration, TimeZone};
+    use migration::{Migrator, MigratorTrait};
+    use sea_orm::{ActiveModelTrait, Set};
+
+    #[tokio::test]
+    async fn test_tenant_usage_counts_window_only() {
+        let config = AppConfig {
+            profile: "test".to_string(),
+            ..Default::default()
+        };
+        let db = init_pool(&config).await.expect("Failed to init test DB");
+        Migrator::up(&db, None).await.unwrap();
+
+        let tenant_id = Uuid::new_v4();
+        crate::models::tenant::ActiveModel {
+            id: Set(tenant_id),
+            ..Default::default()
+        }
+        .insert(&db)
+        .await
+        .unwrap();
+        ProviderRepository::new(std::sync::Arc::new(db.clone()))
+            .upsert("test-provider", "Test Provider", "oauth")
+            .await
+            .unwrap();
+
+        let start = Utc.with_ymd_and_hms(2021, 6, 1, 10, 0, 0).unwrap();
+        let end = start + Duration::hours(1);
+        let connection_id = Uuid::new_v4();
+        crate::models::connection::ActiveModel {
+            id: Set(connection_id),
+            tenant_id: Set(tenant_id),
+            provider_slug: Set("test-provider".to_string()),
+            external_id: Set(format!("usage-{}", connection_id)),
+            status: Set("active".to_string()),
+            created_at: Set((start - Duration::days(1)).fixed_offset()),
+            ..Default::default()
+        }
+        .insert(&db)
+        .await
+        .unwrap();
+
+        for received_at in [start, start + Duration::minutes(59), end] {
+            crate::models::signal::ActiveModel {
+                id: Set(Uuid::new_v4()),
+                tenant_id: Set(tenant_id),
+                provider_slug: Set("test-provider".to_string()),
+                connection_id: Set(connection_id),
+                kind: Set("issue_created".to_string()),
+                occurred_at: Set(received_at.fixed_offset()),
+                received_at: Set(received_at.fixed_offset()),
+                payload: Set(serde_json::json!({})),
+                ..Default::default()
+            }
+            .insert(&db)
+            .await
+            .unwrap();
+        }
+
+        let started_at = start + Duration::minutes(10);
+        crate::models::sync_job::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            tenant_id: Set(tenant_id),
+            provider_slug: Set("test-provider".to_string()),
+            connection_id: Set(connection_id),
+            job_type: Set("incremental".to_string()),
+            status: Set("succeeded".to_string()),
+            priority: Set(10),
+            attempts: Set(1),
+            rate_limited_attempts: Set(0),
+            scheduled_at: Set(started_at.fixed_offset()),
+            retry_after: Set(None),
+            started_at: Set(Some(started_at.fixed_offset())),
+            finished_at: Set(Some((started_at + Duration::seconds(90)).fixed_offset())),
+            locked_at: Set(None),
+            heartbeat_at: Set(None),
+            locked_by: Set(None),
+            cursor: Set(None),
+            error: Set(None),
+            created_at: Set(started_at.fixed_offset()),
+            updated_at: Set(started_at.fixed_offset()),
+        }
+        .insert(&db)
+        .await
+        .unwrap();
+
+        let usage = UsageRepository::new(db.clone())
+            .tenant_usage(start, end)
+            .await
+            .unwrap();
+        assert_eq!(
+            usage.get(&tenant_id),
+            Some(&TenantUsage {
+                signals_ingested: 2,
+                connections_active: 1,
+                sync_seconds: 90.0,
+            })
+        );
+    }
+}
@@ -0,0 +1,220 @@
+//! # Request Snapshot
+//!
+//! Most authenticated requests check that the tenant or provider they name
+//! exists. Both tables change rarely, so their contents are held in memory as a
+//! versioned snapshot shared through [`AppState`](crate::server::AppState).
+//!
+//! Writers in this process call [`SnapshotCache::invalidate`], which bumps the
+//! version so the next read reloads. Changes made by other instances are picked
+//! up once the snapshot is older than the TTL. A lookup that misses the
+//! snapshot is confirmed against the database, so a row created elsewhere is
+//! never reported missing; only removals can be served stale, for at most one TTL.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+
+use sea_orm::{DatabaseConnection, DbErr, EntityTrait, QuerySelect};
+use tracing::debug;
+use uuid::Uuid;
+
+use crate::config::AppConfig;
+use crate::db::with_retry;
+use crate::models::provider::{self, Entity as Provider};
+use crate::models::tenant::{self, Entity as Tenant};
+
+/// Provider catalog and tenant IDs as of one load
+#[derive(Debug)]
+pub struct Snapshot {
+    /// Cache version the snapshot was loaded at
+    pub version: u64,
+    loaded_at: Instant,
+    providers: HashMap<String, provider::Model>,
+    tenants: HashSet<Uuid>,
+}
+
+impl Snapshot {
+    /// Provider row for `slug`, if it was present at load time
+    pub fn provider(&self, slug: &str) -> Option<&provider::Model> {
+        self.providers.get(slug)
+    }
+
+    /// Whether the tenant was present at load time
+    pub fn has_tenant(&self, tenant_id: Uuid) -> bool {
+        self.tenants.contains(&tenant_id)
+    }
+}
+
+/// Versioned, lazily reloaded [`Snapshot`]
+#[derive(Debug)]
+pub struct SnapshotCache {
+    db: DatabaseConnection,
+    ttl: Duration,
+    version: AtomicU64,
+    current: RwLock<Option<Arc<Snapshot>>>,
+    // Serializes reloads so a burst of requests after invalidation loads once
+    reload: tokio::sync::Mutex<()>,
+}
+
+impl SnapshotCache {
+    /// Cache reading from `db`; a zero `ttl` disables caching
+    pub fn new(db: DatabaseConnection, ttl: Duration) -> Self {
+        Self {
+            db,
+            ttl,
+            version: AtomicU64::new(0),
+            current: RwLock::new(None),
+            reload: tokio::sync::Mutex::new(()),
+        }
+    }
+
+    /// Cache using `request_snapshot_ttl_seconds` from the configuration
+    pub fn from_config(db: DatabaseConnection, config: &AppConfig) -> Self {
+        Self::new(db, Duration::from_secs(config.request_snapshot_ttl_seconds))
+    }
+
+    /// Current cache version; snapshots loaded at an older version are stale
+    pub fn version(&self) -> u64 {
+        self.version.load(Ordering::Acquire)
+    }
+
+    /// Mark the snapshot stale after a provider or tenant write
+    pub fn invalidate(&self) {
+        self.version.fetch_add(1, Ordering::AcqRel);
+    }
+
+    /// Current snapshot, reloading it first when stale
+    pub async fn snapshot(&self) -> Result<Arc<Snapshot>, DbErr> {
+        if let Some(snapshot) = self.fresh() {
+            return Ok(snapshot);
+        }
+
+        let _guard = self.reload.lock().await;
+        if let Some(snapshot) = self.fresh() {
+            return Ok(snapshot);
+        }
+
+        // Read the version first so a write racing the load leaves it stale
+        let version = self.version();
+        let providers = with_retry(|| Provider::find().all(&self.db)).await?;
+        let tenants: Vec<Uuid> = with_retry(|| {
+            Tenant::find()
+                .select_only()
+                .column(tenant::Column::Id)
+                .into_tuple()
+                .all(&self.db)
+        })
+        .await?;
+        let snapshot = Arc::new(Snapshot {
+            version,
+            loaded_at: Instant::now(),
+            providers: providers
+                .into_iter()
+                .map(|provider| (provider.slug.clone(), provider))
+                .collect(),
+            tenants: tenants.into_iter().collect(),
+        });
+        debug!(
+            version,
+            providers = snapshot.providers.len(),
+            tenants = snapshot.tenants.len(),
+            "Reloaded request snapshot"
+        );
+
+        *self.current.write().unwrap() = Some(Arc::clone(&snapshot));
+        Ok(snapshot)
+    }
+
+    /// Provider row for `slug`
+    pub async fn provider(&self, slug: &str) -> Result<Option<provider::Model>, DbErr> {
+        if !self.ttl.is_zero()
+            && let Some(provider) = self.snapshot().await?.provider(slug)
+        {
+            return Ok(Some(provider.clone()));
+        }
+
+        let provider = with_retry(|| Provider::find_by_id(slug.to_string()).one(&self.db)).await?;
+        if provider.is_some() && !self.ttl.is_zero() {
+            self.invalidate();
+        }
+        Ok(provider)
+    }
+
+    /// Whether the tenant exists
+    pub async fn tenant_exists(&self, tenant_id: Uuid) -> Result<bool, DbErr> {
+        if !self.ttl.is_zero() && self.snapshot().await?.has_tenant(tenant_id) {
+            return Ok(true);
+        }
+
+        let exists = with_retry(|| Tenant::find_by_id(tenant_id).one(&self.db))
+            .await?
+            .is_some();
+        if exists && !self.ttl.is_zero() {
+            self.invalidate();
+        }
+        Ok(exists)
+    }
+
+    fn fresh(&self) -> Option<Arc<Snapshot>> {
+        self.current
+            .read()
+            .unwrap()
+            .as_ref()
+            .filter(|snapshot| {
+                snapshot.version == self.version() && snapshot.loaded_at.elapsed() < self.ttl
+            })
+            .cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::init_pool;
+    use crate::repositories::ProviderRepository;
+    use migration::{Migrator, MigratorTrait};
+    use sea_orm::{ActiveModelTrait, Set};
+
+    #[tokio::test]
+    async fn test_snapshot_serves_hits_and_confirms_misses() {
+        let config = AppConfig {
+            profile: "test".to_string(),
+            ..Default::default()
+        };
+        let db = init_pool(&config).await.expect("Failed to init test DB");
+        Migrator::up(&db, None).await.unwrap();
+        let providers = ProviderRepository::new(Arc::new(db.clone()));
+        providers
+            .upsert("snapshot-test", "Snapshot Test", "oauth2")
+            .await
+            .unwrap();
+
+        let cache = SnapshotCache::new(db.clone(), Duration::from_secs(60));
+        let loaded = cache.snapshot().await.unwrap();
+        assert!(loaded.provider("snapshot-test").is_some());
+        assert!(Arc::ptr_eq(&loaded, &cache.snapshot().await.unwrap()));
+
+        // Created after the load: the miss is confirmed in the database and
+        // marks the snapshot stale
+        let tenant_id = Uuid::new_v4();
+        tenant::ActiveModel {
+            id: Set(tenant_id),
+            ..Default::default()
+        }
+        .insert(&db)
+        .await
+        .unwrap();
+        assert!(cache.tenant_exists(tenant_id).await.unwrap());
+        let reloaded = cache.snapshot().await.unwrap();
+        assert!(reloaded.version > loaded.version);
+        assert!(reloaded.has_tenant(tenant_id));
+        assert!(!cache.tenant_exists(Uuid::new_v4()).await.unwrap());
+
+        // Removals are visible once the writer invalidates
+        providers.delete_by_slug("snapshot-test").await.unwrap();
+        assert!(cache.provider("snapshot-test").await.unwrap().is_some());
+        cache.invalidate();
+        assert!(cache.provider("snapshot-test").await.unwrap().is_none());
+    }
+}
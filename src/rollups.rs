@@ -0,0 +1,105 @@
+//! # Daily Rollups
+//!
+//! Background task that aggregates finished sync jobs and received signals into
+//! the daily rollup tables. Once a UTC day is over it is rolled up, and the last
+//! few days are recomputed each pass so late-finishing jobs and late-arriving
+//! signals are still reflected. Each pass also drops SLO counters that have
+//! aged out of the longest report window.
+
+use std::sync::Arc;
+
+use chrono::{Duration, NaiveDate, Utc};
+use sea_orm::DatabaseConnection;
+use tokio::time::{Duration as TokioDuration, sleep};
+use tokio_util::sync::CancellationToken;
+use tracing::{error, info, instrument};
+
+use crate::error::RepositoryError;
+use crate::repositories::RollupRepository;
+
+/// Configuration for the rollup service
+#[derive(Debug, Clone)]
+pub struct RollupConfig {
+    /// Seconds between checks for a newly completed day
+    pub tick_seconds: u64,
+    /// Number of completed days recomputed on each pass (including yesterday)
+    pub lookback_days: u32,
+}
+
+impl Default for RollupConfig {
+    fn default() -> Self {
+        Self {
+            tick_seconds: 3600, // 1 hour
+            lookback_days: 3,
+        }
+    }
+}
+
+/// Background service maintaining the daily rollup tables
+pub struct RollupService {
+    db: Arc<DatabaseConnection>,
+    config: RollupConfig,
+}
+
+impl RollupService {
+    /// Create a new rollup service
+    pub fn new(db: Arc<DatabaseConnection>, config: RollupConfig) -> Self {
+        Self { db, config }
+    }
+
+    /// Run until the shutdown token fires, rolling up each day once it completes.
+    #[instrument(skip_all)]
+    pub async fn run(self, shutdown: CancellationToken) -> Result<(), RepositoryError> {
+        info!("Starting daily rollup service");
+        let tick_interval = TokioDuration::from_secs(self.config.tick_seconds);
+        let mut last_rolled_up: Option<NaiveDate> = None;
+
+        loop {
+            let today = Utc::now().date_naive();
+            if last_rolled_up < today.pred_opt() {
+                match self.roll_up_completed_days(today).await {
+                    Ok(days) => {
+                        info!(days, "Daily rollups computed");
+                        last_rolled_up = today.pred_opt();
+                    }
+                    Err(err) => error!(error = ?err, "Daily rollup pass failed"),
+                }
+                match crate::slo::prune(&self.db).await {
+                    Ok(rows) => info!(rows, "Pruned expired SLO counters"),
+                    Err(err) => error!(error = ?err, "Failed to prune SLO counters"),
+                }
+            }
+
+            tokio::select! {
+                _ = shutdown.cancelled() => {
+                    info!("Daily rollup service shutdown requested");
+                    break;
+                }
+                _ = sleep(tick_interval) => {}
+            }
+        }
+
+        info!("Daily rollup service stopped");
+        Ok(())
+    }
+
+    /// Recompute the `lookback_days` completed days preceding `today`.
+    ///
+    /// Returns the number of days processed.
+    pub async fn roll_up_completed_days(&self, today: NaiveDate) -> Result<u32, RepositoryError> {
+        let repo = RollupRepository::new(self.db.as_ref().clone());
+
+        for offset in (1..=self.config.lookback_days).rev() {
+            let day = today - Duration::days(offset as i64);
+            let summary = repo.compute_day(day).await?;
+            info!(
+                day = %day,
+                job_rows = summary.job_rows,
+                signal_rows = summary.signal_rows,
+                "Rolled up day"
+            );
+        }
+
+        Ok(self.config.lookback_days)
+    }
+}
@@ -4,25 +4,34 @@
 //! and enqueues incremental sync jobs while maintaining at-most-once semantics
 //! per connection. The scheduler persists cadence metadata alongside job rows
 //! so multiple instances may coordinate safely.
+//!
+//! Connections without an interval override adapt their interval to observed
+//! volume: the executor records streaks of cycles that returned a full page or
+//! nothing, and the scheduler halves or doubles the interval once a streak is
+//! long enough.
 
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
 use std::sync::Arc;
 
 use axum::http::StatusCode;
 use chrono::{DateTime, Duration, FixedOffset, Utc};
 use metrics::{counter, gauge, histogram};
-use rand::Rng;
 use sea_orm::sea_query::{LockBehavior, LockType};
 use sea_orm::{
     ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, DatabaseTransaction, DbErr,
     EntityTrait, PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, RuntimeErr, Set,
     TransactionTrait,
 };
-use tokio::time::{Duration as TokioDuration, Instant, sleep};
+use tokio::time::{Duration as TokioDuration, Instant};
 use tokio_util::sync::CancellationToken;
 use tracing::{debug, error, info, instrument, warn};
 use uuid::Uuid;
 
-use crate::config::{AppConfig, SchedulerConfig};
+use crate::clock::{SharedClock, system_clock};
+use crate::config::AppConfig;
+use crate::config_reload::ReloadableConfig;
+use crate::config_resolution::{ConfigLayers, defer_past_quiet_hours};
 use crate::error::ApiError;
 use crate::models::connection::{
     ActiveModel as ConnectionActiveModel, Column as ConnectionColumn, Entity as Connection,
@@ -34,16 +43,17 @@ use crate::models::sync_job::{
 use crate::repositories::sync_metadata::{ConnectionSyncMetadata, MIN_SYNC_INTERVAL_SECONDS};
 
 /// Default number of connections evaluated per tick.
-const DEFAULT_BATCH_SIZE: usize = 128;
+pub(crate) const DEFAULT_BATCH_SIZE: usize = 128;
 
 /// Index name for the interval uniqueness guard.
 const INTERVAL_UNIQUE_INDEX: &str = "idx_sync_jobs_incremental_pending";
 
 /// Background scheduler service.
 pub struct SyncScheduler {
-    config: Arc<AppConfig>,
+    settings: ReloadableConfig,
     db: Arc<DatabaseConnection>,
     batch_size: usize,
+    clock: SharedClock,
 }
 
 #[derive(Debug, Default)]
@@ -57,22 +67,35 @@ struct TickStats {
 }
 
 #[derive(Debug, Clone)]
-struct DueComputation {
-    job_due: DateTime<Utc>,
-    next_run_at: DateTime<Utc>,
-    is_overdue: bool,
+pub(crate) struct DueComputation {
+    pub(crate) job_due: DateTime<Utc>,
+    pub(crate) next_run_at: DateTime<Utc>,
+    pub(crate) is_overdue: bool,
 }
 
 impl SyncScheduler {
     /// Create a new scheduler instance.
     pub fn new(config: Arc<AppConfig>, db: Arc<DatabaseConnection>) -> Self {
         Self {
-            config,
+            settings: ReloadableConfig::from_config(&config),
             db,
             batch_size: DEFAULT_BATCH_SIZE,
+            clock: system_clock(),
         }
     }
 
+    /// Replace the time source used for due-time evaluation and tick pacing.
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Follow shared settings so reloaded intervals apply from the next tick.
+    pub fn with_settings(mut self, settings: ReloadableConfig) -> Self {
+        self.settings = settings;
+        self
+    }
+
     /// Override the number of connections processed per tick (primarily for tests).
     #[allow(dead_code)]
     pub fn with_batch_size(mut self, batch_size: usize) -> Self {
@@ -84,15 +107,15 @@ impl SyncScheduler {
     #[instrument(skip_all)]
     pub async fn run(self, shutdown: CancellationToken) -> Result<(), ApiError> {
         info!("Starting sync scheduler");
-        let tick_interval = TokioDuration::from_secs(self.config.scheduler.tick_interval_seconds);
-
         loop {
+            let tick_interval =
+                TokioDuration::from_secs(self.settings.current().scheduler.tick_interval_seconds);
             tokio::select! {
                 _ = shutdown.cancelled() => {
                     info!("Sync scheduler shutdown requested");
                     break;
                 }
-                _ = sleep(tick_interval) => {
+                _ = self.clock.sleep(tick_interval) => {
                     let tick_started = Instant::now();
                     if let Err(err) = self.tick().await {
                         error!(error = ?err, "Scheduler tick failed");
@@ -109,14 +132,29 @@ impl SyncScheduler {
     }
 
     async fn tick(&self) -> Result<(), ApiError> {
-        let now = Utc::now();
+        let now = self.clock.now();
         let mut stats = TickStats::default();
 
-        let candidate_ids = self.load_candidate_ids().await?;
+        let candidates = self.load_candidates().await?;
+        let mut tenant_layers: HashMap<Uuid, ConfigLayers> = HashMap::new();
+
+        for (connection_id, tenant_id) in candidates {
+            if let Entry::Vacant(entry) = tenant_layers.entry(tenant_id) {
+                let layers = ConfigLayers::load(self.db.as_ref(), tenant_id)
+                    .await
+                    .unwrap_or_else(|err| {
+                        warn!(
+                            error = ?err,
+                            tenant_id = %tenant_id,
+                            "Failed to load tenant settings; scheduling with service defaults"
+                        );
+                        ConfigLayers::default()
+                    });
+                entry.insert(layers);
+            }
 
-        for connection_id in candidate_ids {
             match self
-                .process_connection(connection_id, now, &mut stats)
+                .process_connection(connection_id, &tenant_layers[&tenant_id], now, &mut stats)
                 .await
             {
                 Ok(()) => {}
@@ -146,7 +184,8 @@ impl SyncScheduler {
         Ok(())
     }
 
-    async fn load_candidate_ids(&self) -> Result<Vec<Uuid>, ApiError> {
+    /// Connections to evaluate this tick, as `(connection_id, tenant_id)` pairs
+    async fn load_candidates(&self) -> Result<Vec<(Uuid, Uuid)>, ApiError> {
         let mut models = Connection::find()
             .filter(ConnectionColumn::Status.eq("active"))
             .order_by_asc(ConnectionColumn::CreatedAt)
@@ -158,22 +197,20 @@ impl SyncScheduler {
         models.sort_by_key(|connection| {
             let metadata =
                 ConnectionSyncMetadata::from_connection_metadata(connection.metadata.as_ref());
-            metadata
-                .next_run_at
-                .or(metadata.first_activated_at)
-                .unwrap_or_else(|| connection.created_at.with_timezone(&Utc))
+            candidate_sort_key(&metadata, connection.created_at.with_timezone(&Utc))
         });
 
         Ok(models
             .into_iter()
             .take(self.batch_size)
-            .map(|connection| connection.id)
+            .map(|connection| (connection.id, connection.tenant_id))
             .collect())
     }
 
     async fn process_connection(
         &self,
         connection_id: Uuid,
+        layers: &ConfigLayers,
         now: DateTime<Utc>,
         stats: &mut TickStats,
     ) -> Result<(), ApiError> {
@@ -201,14 +238,27 @@ impl SyncScheduler {
 
         let mut metadata =
             ConnectionSyncMetadata::from_connection_metadata(connection.metadata.as_ref());
-        let mut metadata_dirty = metadata.sanitize_interval(&self.config.scheduler);
+        let scheduler = layers.scheduler(&self.settings.current().scheduler);
+        let mut metadata_dirty = metadata.sanitize_interval(&scheduler);
+
+        let streak = metadata.activity_streak;
+        if metadata.adapt_interval(&scheduler, &connection.provider_slug) {
+            metadata_dirty = true;
+            info!(
+                connection_id = %connection.id,
+                activity_streak = streak,
+                adaptive_interval_seconds = ?metadata.adaptive_interval_seconds,
+                "Adapted sync interval to observed volume"
+            );
+        }
 
         if metadata.first_activated_at.is_none() {
             metadata.first_activated_at = Some(connection.created_at.with_timezone(&Utc));
             metadata_dirty = true;
         }
 
-        let base_interval = metadata.effective_interval_seconds(&self.config.scheduler);
+        let base_interval =
+            metadata.effective_interval_seconds(&scheduler, &connection.provider_slug);
         if base_interval < MIN_SYNC_INTERVAL_SECONDS {
             warn!(
                 connection_id = %connection.id,
@@ -255,11 +305,13 @@ impl SyncScheduler {
             return Ok(());
         }
 
-        if now < due.job_due {
+        let blackout_until = metadata.blackout_at(now).map(|window| window.ends_at);
+        if now < due.job_due || blackout_until.is_some() {
             stats.jobs_skipped_not_due += 1;
             debug!(
                 connection_id = %connection.id,
                 due_at = %due.job_due,
+                blackout_until = ?blackout_until,
                 "Connection not yet due for scheduling"
             );
             if metadata_dirty {
@@ -272,11 +324,14 @@ impl SyncScheduler {
             return Ok(());
         }
 
-        let jitter_seconds = sample_jitter_seconds(&self.config.scheduler, base_interval);
-        let scheduled_at = due
-            .job_due
-            .checked_add_signed(Duration::seconds(jitter_seconds as i64))
-            .unwrap_or(now);
+        let jitter_seconds = scheduler.jitter_range().offset_seconds(base_interval);
+        let scheduled_at = defer_past_quiet_hours(
+            &layers.sync_quiet_hours().value,
+            &connection.provider_slug,
+            due.job_due
+                .checked_add_signed(Duration::seconds(jitter_seconds as i64))
+                .unwrap_or(now),
+        );
 
         metadata.next_run_at = Some(due.next_run_at);
         metadata.last_jitter_seconds = Some(jitter_seconds);
@@ -291,10 +346,14 @@ impl SyncScheduler {
             status: Set("queued".to_string()),
             priority: Set(30),
             attempts: Set(0),
+            rate_limited_attempts: Set(0),
             scheduled_at: Set(to_db_time(scheduled_at)),
             retry_after: Set(None),
             started_at: Set(None),
             finished_at: Set(None),
+            locked_at: Set(None),
+            heartbeat_at: Set(None),
+            locked_by: Set(None),
             cursor: Set(None),
             error: Set(None),
             created_at: Set(to_db_time(now)),
@@ -400,7 +459,18 @@ impl SyncScheduler {
     }
 }
 
-fn compute_due_times(
+/// Order in which connections are considered each tick: soonest due first.
+pub(crate) fn candidate_sort_key(
+    metadata: &ConnectionSyncMetadata,
+    created_at: DateTime<Utc>,
+) -> DateTime<Utc> {
+    metadata
+        .next_run_at
+        .or(metadata.first_activated_at)
+        .unwrap_or(created_at)
+}
+
+pub(crate) fn compute_due_times(
     metadata: &ConnectionSyncMetadata,
     base_interval_seconds: u64,
     last_finished: Option<DateTime<Utc>>,
@@ -439,30 +509,43 @@ fn compute_due_times(
     }
 }
 
-fn sample_jitter_seconds(config: &SchedulerConfig, base_interval_seconds: u64) -> u64 {
-    let mut rng = rand::thread_rng();
-    compute_jitter_seconds(config, base_interval_seconds, &mut rng)
-}
-
-fn compute_jitter_seconds<R: Rng + ?Sized>(
-    config: &SchedulerConfig,
+/// Projected times of upcoming scheduled syncs up to `until`, before jitter.
+///
+/// A run that falls inside a blackout window is deferred to the end of the
+/// window, matching how the scheduler catches up once the window closes.
+pub(crate) fn upcoming_runs(
+    metadata: &ConnectionSyncMetadata,
     base_interval_seconds: u64,
-    rng: &mut R,
-) -> u64 {
-    let min = config.jitter_pct_min.max(0.0);
-    let max = config.jitter_pct_max.max(min);
-
-    if min == 0.0 && max == 0.0 {
-        return 0;
+    activation_reference: DateTime<Utc>,
+    now: DateTime<Utc>,
+    until: DateTime<Utc>,
+    limit: usize,
+) -> Vec<DateTime<Utc>> {
+    let base_interval = Duration::seconds(base_interval_seconds as i64);
+    let due = compute_due_times(
+        metadata,
+        base_interval_seconds,
+        None,
+        activation_reference,
+        now,
+    );
+
+    let mut runs = Vec::new();
+    let mut slot = due.job_due;
+    while runs.len() < limit {
+        let mut run = slot.max(now);
+        while let Some(window) = metadata.blackout_at(run) {
+            run = window.ends_at;
+        }
+        if run > until {
+            break;
+        }
+        runs.push(run);
+        while slot <= run {
+            slot += base_interval;
+        }
     }
-
-    let jitter_pct = if (max - min).abs() < f64::EPSILON {
-        min
-    } else {
-        rng.gen_range(min..=max)
-    };
-
-    (base_interval_seconds as f64 * jitter_pct).round() as u64
+    runs
 }
 
 fn is_unique_violation(err: &DbErr) -> bool {
@@ -496,6 +579,10 @@ fn map_db_err(context: &'static str, err: DbErr) -> ApiError {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::clock::MockClock;
+    use crate::config::SchedulerConfig;
+    use crate::repositories::sync_metadata::BlackoutWindow;
+    use chrono::TimeZone;
     use rand::{SeedableRng, rngs::mock::StepRng};
     use std::sync::Arc;
 
@@ -509,6 +596,7 @@ mod tests {
             jitter_pct_min: 0.0,
             jitter_pct_max: 0.2,
             max_overridden_interval_seconds: 86400,
+            provider_intervals: Default::default(),
         }
     }
 
@@ -519,7 +607,9 @@ mod tests {
         let mut rng = rand::rngs::StdRng::seed_from_u64(42);
 
         for _ in 0..100 {
-            let jitter = compute_jitter_seconds(&config, base_interval, &mut rng);
+            let jitter = config
+                .jitter_range()
+                .offset_seconds_with(base_interval, &mut rng);
             assert!(jitter <= (base_interval as f64 * config.jitter_pct_max).round() as u64);
             assert!(jitter >= (base_interval as f64 * config.jitter_pct_min).round() as u64);
         }
@@ -587,6 +677,40 @@ mod tests {
         assert!(due.is_overdue);
     }
 
+    #[test]
+    fn upcoming_runs_defer_past_blackouts() {
+        let at = |s: &str| DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc);
+        let metadata = ConnectionSyncMetadata {
+            next_run_at: Some(at("2025-01-01T10:15:00Z")),
+            blackouts: vec![BlackoutWindow {
+                starts_at: at("2025-01-01T10:40:00Z"),
+                ends_at: at("2025-01-01T11:05:00Z"),
+                reason: Some("provider maintenance".to_string()),
+            }],
+            ..Default::default()
+        };
+
+        let runs = upcoming_runs(
+            &metadata,
+            900,
+            at("2025-01-01T10:00:00Z"),
+            at("2025-01-01T10:16:00Z"),
+            at("2025-01-01T11:30:00Z"),
+            10,
+        );
+
+        assert_eq!(
+            runs,
+            vec![
+                at("2025-01-01T10:16:00Z"),
+                at("2025-01-01T10:30:00Z"),
+                at("2025-01-01T11:05:00Z"),
+                at("2025-01-01T11:15:00Z"),
+                at("2025-01-01T11:30:00Z"),
+            ]
+        );
+    }
+
     #[test]
     fn jitter_zero_when_bounds_zero() {
         let config = SchedulerConfig {
@@ -595,7 +719,7 @@ mod tests {
             ..scheduler_config()
         };
         let mut rng = StepRng::new(0, 1);
-        let jitter = compute_jitter_seconds(&config, 600, &mut rng);
+        let jitter = config.jitter_range().offset_seconds_with(600, &mut rng);
         assert_eq!(jitter, 0);
     }
 
@@ -611,7 +735,8 @@ mod tests {
         let tenant_id = Uuid::new_v4();
         let provider_slug = "github";
         let connection_id = Uuid::new_v4();
-        let now_anchor = Utc::now();
+        let now_anchor = Utc.with_ymd_and_hms(2024, 1, 15, 10, 0, 0).unwrap();
+        let clock = MockClock::new(now_anchor);
 
         db.execute(Statement::from_sql_and_values(
             backend,
@@ -688,7 +813,8 @@ mod tests {
         config.scheduler.jitter_pct_min = 0.0;
         config.scheduler.jitter_pct_max = 0.0;
 
-        let scheduler = SyncScheduler::new(Arc::new(config), Arc::new(db.clone()));
+        let scheduler =
+            SyncScheduler::new(Arc::new(config), Arc::new(db.clone())).with_clock(clock.clone());
         scheduler.tick().await.expect("first tick succeeds");
 
         let queued_jobs = SyncJob::find()
@@ -699,11 +825,7 @@ mod tests {
             .expect("fetch queued jobs");
         assert_eq!(queued_jobs.len(), 1);
         let scheduled_at = queued_jobs[0].scheduled_at.with_timezone(&Utc);
-        assert!(
-            scheduled_at <= Utc::now() + Duration::seconds(1),
-            "scheduled_at too far in future: {}",
-            scheduled_at
-        );
+        assert_eq!(scheduled_at, now_anchor);
 
         let connection = Connection::find_by_id(connection_id)
             .one(&db)
@@ -715,14 +837,10 @@ mod tests {
         let next_run_at = metadata
             .next_run_at
             .expect("next_run_at should be recorded");
-        let diff = next_run_at - scheduled_at;
-        assert!(
-            (diff.num_seconds() - 900).abs() <= 1,
-            "unexpected interval advancement: {} seconds",
-            diff.num_seconds()
-        );
+        assert_eq!(next_run_at, now_anchor + Duration::seconds(900));
         assert_eq!(metadata.last_jitter_seconds, Some(0));
 
+        clock.advance(std::time::Duration::from_secs(900));
         scheduler.tick().await.expect("second tick succeeds");
         let queued_jobs_after = SyncJob::find()
             .filter(SyncJobColumn::ConnectionId.eq(connection_id))
@@ -732,4 +850,74 @@ mod tests {
             .expect("fetch queued jobs after second tick");
         assert_eq!(queued_jobs_after.len(), 1, "no duplicate interval jobs");
     }
+
+    #[tokio::test]
+    async fn tenant_quiet_hours_defer_scheduled_at() {
+        let db = Database::connect("sqlite::memory:")
+            .await
+            .expect("create in-memory db");
+        Migrator::up(&db, None).await.expect("apply migrations");
+
+        let backend = db.get_database_backend();
+        let tenant_id = Uuid::new_v4();
+        let connection_id = Uuid::new_v4();
+        let now_anchor = Utc.with_ymd_and_hms(2024, 1, 15, 1, 30, 0).unwrap();
+        let settings = serde_json::json!({
+            "sync_quiet_hours": [{"start": "01:00", "end": "03:00"}]
+        })
+        .to_string();
+
+        db.execute(Statement::from_sql_and_values(
+            backend,
+            "INSERT INTO tenants (id, name, settings) VALUES (?, ?, ?)",
+            vec![tenant_id.into(), "Test Tenant".into(), settings.into()],
+        ))
+        .await
+        .expect("insert tenant");
+        db.execute(Statement::from_sql_and_values(
+            backend,
+            "INSERT INTO providers (slug, display_name, auth_type) VALUES (?, ?, ?)",
+            vec!["github".into(), "GitHub".into(), "oauth2".into()],
+        ))
+        .await
+        .expect("insert provider");
+
+        let metadata = serde_json::json!({
+            "sync": { "next_run_at": (now_anchor - Duration::minutes(5)).to_rfc3339() }
+        })
+        .to_string();
+        db.execute(Statement::from_sql_and_values(
+            backend,
+            "INSERT INTO connections (id, tenant_id, provider_slug, external_id, status, metadata) \
+             VALUES (?, ?, ?, ?, ?, ?)",
+            vec![
+                Value::from(connection_id),
+                Value::from(tenant_id),
+                Value::from("github"),
+                Value::from("external-1"),
+                Value::from("active"),
+                Value::from(metadata),
+            ],
+        ))
+        .await
+        .expect("insert connection");
+
+        let mut config = AppConfig::default();
+        config.scheduler.jitter_pct_min = 0.0;
+        config.scheduler.jitter_pct_max = 0.0;
+        let scheduler = SyncScheduler::new(Arc::new(config), Arc::new(db.clone()))
+            .with_clock(MockClock::new(now_anchor));
+        scheduler.tick().await.expect("tick succeeds");
+
+        let queued_jobs = SyncJob::find()
+            .filter(SyncJobColumn::ConnectionId.eq(connection_id))
+            .all(&db)
+            .await
+            .expect("fetch queued jobs");
+        assert_eq!(queued_jobs.len(), 1);
+        assert_eq!(
+            queued_jobs[0].scheduled_at.with_timezone(&Utc),
+            Utc.with_ymd_and_hms(2024, 1, 15, 3, 0, 0).unwrap()
+        );
+    }
 }
@@ -6,4 +6,4 @@
 
 pub mod provider;
 
-pub use provider::seed_providers;
+pub use provider::{CatalogReport, detect_drift, reconcile_providers, seed_providers};
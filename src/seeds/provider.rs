@@ -1,21 +1,132 @@
 //! Provider seeding functionality
 //!
-//! This module provides functionality to seed the providers table with
-//! initial data for common OAuth providers.
+//! This module reconciles the providers table with the provider catalog defined
+//! in code. Missing providers are inserted; rows the code no longer defines and
+//! rows whose auth type disagrees with the code are reported rather than
+//! changed, since connections still reference them.
 
 use anyhow::Result;
-use chrono::Utc;
-use sea_orm::{DatabaseConnection, Set};
+use sea_orm::DatabaseConnection;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::sync::Arc;
+use utoipa::ToSchema;
 
-use crate::models::provider;
+use crate::connectors::Registry;
 use crate::repositories::ProviderRepository;
 
-/// Seeds the providers table with common OAuth providers
+/// Provider the code expects to find in the providers table
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CatalogEntry {
+    /// Provider slug
+    pub slug: String,
+    /// Display name stored for new rows
+    pub display_name: String,
+    /// Expected `providers.auth_type`
+    pub auth_type: String,
+}
+
+impl CatalogEntry {
+    fn new(slug: &str, display_name: &str, auth_type: &str) -> Self {
+        Self {
+            slug: slug.to_string(),
+            display_name: display_name.to_string(),
+            auth_type: auth_type.to_string(),
+        }
+    }
+}
+
+/// Provider whose stored auth type differs from the code
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct AuthTypeDrift {
+    /// Provider slug
+    #[schema(example = "jira")]
+    pub slug: String,
+    /// Auth type defined in code
+    #[schema(example = "oauth2")]
+    pub expected: String,
+    /// Auth type stored in the providers table
+    #[schema(example = "api_key")]
+    pub actual: String,
+}
+
+/// Differences between the code catalog and the providers table
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct CatalogReport {
+    /// Providers inserted by this reconciliation
+    pub added: Vec<String>,
+    /// Providers defined in code but absent from the table
+    pub missing: Vec<String>,
+    /// Providers in the table that the code no longer defines
+    pub removed: Vec<String>,
+    /// Providers whose stored auth type differs from the code
+    pub auth_type_drift: Vec<AuthTypeDrift>,
+}
+
+impl CatalogReport {
+    /// Whether the table matches the catalog
+    pub fn is_clean(&self) -> bool {
+        self.missing.is_empty() && self.removed.is_empty() && self.auth_type_drift.is_empty()
+    }
+}
+
+/// Providers seeded regardless of which connectors are configured
+pub fn builtin_catalog() -> Vec<CatalogEntry> {
+    vec![
+        CatalogEntry::new("google", "Google", "oauth2"),
+        CatalogEntry::new("github", "GitHub", "oauth2"),
+        CatalogEntry::new("gitlab", "GitLab", "oauth2"),
+        CatalogEntry::new("jira", "Jira", "oauth2"),
+        CatalogEntry::new("confluence", "Confluence", "oauth2"),
+        CatalogEntry::new("linear", "Linear", "oauth2"),
+        CatalogEntry::new("notion", "Notion", "oauth2"),
+        CatalogEntry::new("microsoft", "Microsoft", "oauth2"),
+        CatalogEntry::new("outlook", "Outlook", "oauth2"),
+        CatalogEntry::new("outlook-calendar", "Outlook Calendar", "oauth2"),
+        CatalogEntry::new("salesforce", "Salesforce", "oauth2"),
+        CatalogEntry::new("zendesk", "Zendesk", "oauth2"),
+        CatalogEntry::new("sentry", "Sentry", "oauth2"),
+    ]
+}
+
+/// Built-in providers plus every connector registered in `registry`
 ///
-/// This function checks if common OAuth providers already exist in the database
-/// and creates them if they don't. It's useful for bootstrapping the system
-/// with initial provider data.
+/// The registry's auth type wins over the built-in entry for the same slug.
+pub fn registry_catalog(registry: &Registry) -> Vec<CatalogEntry> {
+    let mut entries: BTreeMap<String, CatalogEntry> = builtin_catalog()
+        .into_iter()
+        .map(|entry| (entry.slug.clone(), entry))
+        .collect();
+    for metadata in registry.list_metadata() {
+        let auth_type = metadata.auth_type.as_str().to_string();
+        entries
+            .entry(metadata.name.clone())
+            .and_modify(|entry| entry.auth_type = auth_type.clone())
+            .or_insert_with(|| CatalogEntry {
+                display_name: display_name_for(&metadata.name),
+                slug: metadata.name,
+                auth_type,
+            });
+    }
+    entries.into_values().collect()
+}
+
+/// Title-cased slug, e.g. `zoho-cliq` becomes `Zoho Cliq`
+fn display_name_for(slug: &str) -> String {
+    slug.split('-')
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            chars
+                .next()
+                .map(|first| first.to_uppercase().chain(chars).collect::<String>())
+                .unwrap_or_default()
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Seeds the providers table with the built-in providers
 ///
 /// # Arguments
 ///
@@ -23,89 +134,120 @@ use crate::repositories::ProviderRepository;
 ///
 /// # Returns
 ///
-/// Returns a Result indicating success or failure
-pub async fn seed_providers(db: &DatabaseConnection) -> Result<()> {
+/// Returns a Result containing the reconciliation report
+pub async fn seed_providers(db: &DatabaseConnection) -> Result<CatalogReport> {
+    reconcile_providers(db, &builtin_catalog()).await
+}
+
+/// Inserts missing catalog providers and reports the remaining drift
+///
+/// Inserts ignore rows created concurrently by another process, so replicas
+/// starting at the same time can all run this.
+pub async fn reconcile_providers(
+    db: &DatabaseConnection,
+    catalog: &[CatalogEntry],
+) -> Result<CatalogReport> {
     let repo = ProviderRepository::new(Arc::new(db.clone()));
 
-    // Define common OAuth providers with their configurations
-    let providers = vec![
-        ProviderConfig {
-            slug: "google".to_string(),
-            display_name: "Google".to_string(),
-            auth_type: "oauth2".to_string(),
-        },
-        ProviderConfig {
-            slug: "github".to_string(),
-            display_name: "GitHub".to_string(),
-            auth_type: "oauth2".to_string(),
-        },
-        ProviderConfig {
-            slug: "jira".to_string(),
-            display_name: "Jira".to_string(),
-            auth_type: "oauth2".to_string(),
-        },
-        ProviderConfig {
-            slug: "microsoft".to_string(),
-            display_name: "Microsoft".to_string(),
-            auth_type: "oauth2".to_string(),
-        },
-    ];
-
-    for provider_config in providers {
-        // Check if provider already exists
-        match repo.find_by_slug(&provider_config.slug).await {
-            Ok(Some(_)) => {
-                log::info!(
-                    "Provider '{}' already exists, skipping",
-                    provider_config.slug
-                );
-                continue;
-            }
-            Ok(None) => {
-                // Provider doesn't exist, create it
-                log::info!("Creating provider: {}", provider_config.slug);
-
-                let provider = provider::ActiveModel {
-                    slug: Set(provider_config.slug.clone()),
-                    display_name: Set(provider_config.display_name.clone()),
-                    auth_type: Set(provider_config.auth_type.clone()),
-                    created_at: Set(Utc::now().into()),
-                    updated_at: Set(Utc::now().into()),
-                };
-
-                let slug = provider_config.slug.clone();
-                match repo.create(provider).await {
-                    Ok(_) => {
-                        log::info!("Successfully created provider: {}", slug);
-                    }
-                    Err(e) => {
-                        log::error!(
-                            "Failed to create provider '{}': {}",
-                            provider_config.slug,
-                            e
-                        );
-                        return Err(e);
-                    }
-                }
-            }
-            Err(e) => {
-                log::error!(
-                    "Error checking if provider '{}' exists: {}",
-                    provider_config.slug,
-                    e
-                );
-                return Err(e);
-            }
+    let mut added = Vec::new();
+    for entry in catalog {
+        if repo
+            .insert_if_absent(&entry.slug, &entry.display_name, &entry.auth_type)
+            .await?
+        {
+            log::info!("Created provider: {}", entry.slug);
+            added.push(entry.slug.clone());
         }
     }
 
-    log::info!("Provider seeding completed successfully");
-    Ok(())
+    let report = CatalogReport {
+        added,
+        ..detect_drift(db, catalog).await?
+    };
+    for slug in &report.removed {
+        log::warn!(
+            "Provider '{}' exists in the database but is not defined in code",
+            slug
+        );
+    }
+    for drift in &report.auth_type_drift {
+        log::warn!(
+            "Provider '{}' has auth_type '{}' in the database but '{}' in code",
+            drift.slug,
+            drift.actual,
+            drift.expected
+        );
+    }
+
+    log::info!(
+        "Provider reconciliation completed: {} added, {} removed, {} with auth_type drift",
+        report.added.len(),
+        report.removed.len(),
+        report.auth_type_drift.len()
+    );
+    Ok(report)
 }
 
-/// Configuration structure for a provider
-struct ProviderConfig {
-    slug: String,
-    display_name: String,
-    auth_type: String,
+/// Compares the providers table with `catalog` without changing it
+pub async fn detect_drift(
+    db: &DatabaseConnection,
+    catalog: &[CatalogEntry],
+) -> Result<CatalogReport> {
+    let repo = ProviderRepository::new(Arc::new(db.clone()));
+    let stored: BTreeMap<String, String> = repo
+        .list_all()
+        .await?
+        .into_iter()
+        .map(|provider| (provider.slug, provider.auth_type))
+        .collect();
+    let expected: BTreeMap<&str, &str> = catalog
+        .iter()
+        .map(|entry| (entry.slug.as_str(), entry.auth_type.as_str()))
+        .collect();
+
+    let mut report = CatalogReport::default();
+    for (slug, auth_type) in &expected {
+        match stored.get(*slug) {
+            None => report.missing.push(slug.to_string()),
+            Some(actual) if actual != auth_type => report.auth_type_drift.push(AuthTypeDrift {
+                slug: slug.to_string(),
+                expected: auth_type.to_string(),
+                actual: actual.clone(),
+            }),
+            Some(_) => {}
+        }
+    }
+    report.removed = stored
+        .keys()
+        .filter(|slug| !expected.contains_key(slug.as_str()))
+        .cloned()
+        .collect();
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::connectors::{AuthType, ProviderMetadata};
+
+    #[test]
+    fn test_registry_catalog_merges_builtin_and_registered() {
+        let mut registry = Registry::new();
+        let connector = Arc::new(crate::connectors::ExampleConnector);
+        registry.register(
+            connector.clone(),
+            ProviderMetadata::minimal("zoho-cliq".to_string(), AuthType::OAuth2),
+        );
+        registry.register(
+            connector,
+            ProviderMetadata::minimal("jira".to_string(), AuthType::ApiKey),
+        );
+
+        let catalog = registry_catalog(&registry);
+        let jira = catalog.iter().find(|entry| entry.slug == "jira").unwrap();
+        assert_eq!(jira.display_name, "Jira");
+        assert_eq!(jira.auth_type, "api_key");
+        assert!(catalog.contains(&CatalogEntry::new("zoho-cliq", "Zoho Cliq", "oauth2")));
+        assert_eq!(catalog.len(), builtin_catalog().len() + 1);
+    }
 }
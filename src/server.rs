@@ -11,22 +11,24 @@ use axum::{
     http::HeaderValue,
     middleware,
     response::Response,
-    routing::{delete, get, patch, post},
+    routing::{delete, get, patch, post, put},
 };
 use sea_orm::DatabaseConnection;
 use std::time::Duration;
+use tokio_util::sync::CancellationToken;
 use tower_http::cors::{Any, CorsLayer};
 use tower_http::trace::TraceLayer;
 use utoipa::{Modify, OpenApi};
 use utoipa_swagger_ui::SwaggerUi;
 
-use crate::auth::auth_middleware;
+use crate::auth::{admin_auth_middleware, auth_middleware};
 use crate::config::AppConfig;
-use crate::connectors::Registry;
-use crate::crypto::CryptoKey;
+use crate::config_reload::ReloadableConfig;
+use crate::crypto::MasterKeyring;
 use crate::error::ApiError;
 use crate::handlers;
-use crate::repositories::connection::ConnectionRepository;
+use crate::request_snapshot::SnapshotCache;
+use crate::signals::SignalEventBus;
 use crate::telemetry::{self, TraceContext};
 use crate::token_refresh::TokenRefreshService;
 use crate::webhook_verification::webhook_verification_middleware;
@@ -61,8 +63,13 @@ async fn trace_middleware(mut request: Request, next: axum::middleware::Next) ->
 pub struct AppState {
     pub config: Arc<AppConfig>,
     pub db: DatabaseConnection,
-    pub crypto_key: CryptoKey,
+    pub master_keys: MasterKeyring,
     pub token_refresh_service: Arc<TokenRefreshService>,
+    pub snapshot: Arc<SnapshotCache>,
+    /// Settings that config reloads replace while the service runs
+    pub settings: ReloadableConfig,
+    /// Bus the outbox relay publishes committed signals on; call `subscribe()` to consume them
+    pub signal_events: SignalEventBus,
 }
 
 /// Creates and configures the Axum application router
@@ -78,6 +85,18 @@ pub fn create_app(state: AppState) -> Router {
         )
         .route("/config/summary", get(handlers::config::get_config_summary))
         .route("/providers", get(handlers::providers::list_providers))
+        .route(
+            "/providers/{slug}/metadata-schema",
+            get(handlers::providers::get_metadata_schema),
+        )
+        .route(
+            "/providers/{slug}/capabilities",
+            get(handlers::providers::get_capabilities),
+        )
+        .route(
+            "/providers/{slug}/status",
+            get(handlers::providers::get_provider_status),
+        )
         .route(
             "/connect/{provider}/callback",
             get(handlers::connect::oauth_callback),
@@ -96,9 +115,58 @@ pub fn create_app(state: AppState) -> Router {
     // Protected routes (auth required)
     let protected_routes = Router::new()
         .route("/protected/ping", get(handlers::protected_ping))
-        .route("/connections", get(handlers::connections::list_connections))
+        .route(
+            "/connections",
+            get(handlers::connections::list_connections)
+                .post(handlers::connections::create_connection),
+        )
+        .route(
+            "/connections/{id}",
+            patch(handlers::connections::update_connection),
+        )
+        .route(
+            "/connections/{id}/health",
+            get(handlers::connections::get_connection_health),
+        )
+        .route(
+            "/connections/{id}/runs",
+            get(handlers::connections::list_connection_runs),
+        )
+        .route(
+            "/connections/{id}/token-events",
+            get(handlers::connections::list_token_events),
+        )
+        .route(
+            "/connections/{id}/pause",
+            post(handlers::connections::pause_connection),
+        )
+        .route(
+            "/connections/{id}/resume",
+            post(handlers::connections::resume_connection),
+        )
+        .route(
+            "/connections/{id}/sync",
+            post(handlers::connections::trigger_sync),
+        )
         .route("/jobs", get(handlers::jobs::list_jobs))
+        .route("/jobs/{id}/cancel", post(handlers::jobs::cancel_job))
+        .route("/schedule", get(handlers::schedule::get_schedule))
+        .route("/schedule.ics", get(handlers::schedule::get_schedule_ical))
+        .route(
+            "/signal-kinds",
+            get(handlers::signal_kinds::list_signal_kinds)
+                .post(handlers::signal_kinds::register_signal_kind),
+        )
         .route("/signals", get(handlers::signals::list_signals))
+        .route(
+            "/signals/{id}/trace",
+            get(handlers::signals::get_signal_trace),
+        )
+        .route("/stats/daily", get(handlers::stats::get_daily_stats))
+        .route(
+            "/stats/slow-queries",
+            get(handlers::stats::get_slow_queries),
+        )
         .route(
             "/grounded-signals",
             get(handlers::grounded_signals::list_grounded_signals),
@@ -116,21 +184,123 @@ pub fn create_app(state: AppState) -> Router {
             delete(handlers::grounded_signals::delete_grounded_signal),
         )
         .route("/api/v1/tenants", post(handlers::tenants::create_tenant))
+        .route(
+            "/api/v1/tenants/bootstrap",
+            post(handlers::tenants::bootstrap_tenant),
+        )
         .route("/api/v1/tenants/{id}", get(handlers::tenants::get_tenant))
+        .route(
+            "/api/v1/tenants/{id}/clone-sandbox",
+            post(handlers::tenants::clone_sandbox),
+        )
+        .route(
+            "/api/v1/tenants/{id}/reprocess",
+            post(handlers::tenants::reprocess_sandbox),
+        )
+        .route(
+            "/api/v1/tenants/{id}/settings",
+            put(handlers::tenants::update_tenant_settings),
+        )
+        .route(
+            "/api/v1/tenants/{id}/effective-config",
+            get(handlers::tenants::get_effective_config),
+        )
+        .route(
+            "/api/v1/tenants/{id}/config",
+            patch(handlers::tenants::update_tenant_config),
+        )
+        .route(
+            "/api/v1/tenants/{id}/events",
+            get(handlers::tenants::list_tenant_events),
+        )
+        .route(
+            "/api/v1/tenants/{id}/webhook-secret",
+            get(handlers::tenants::get_webhook_secret),
+        )
+        .route(
+            "/api/v1/tenants/{id}/webhook-secret/rotate",
+            post(handlers::tenants::rotate_webhook_secret),
+        )
+        .route(
+            "/api/v1/organizations",
+            post(handlers::organizations::create_organization),
+        )
+        .route(
+            "/api/v1/organizations/{id}",
+            get(handlers::organizations::get_organization),
+        )
+        .route(
+            "/api/v1/organizations/{id}/settings",
+            put(handlers::organizations::update_organization_settings),
+        )
         .route("/connect/{provider}", post(handlers::connect::start_oauth))
+        .route(
+            "/connect/{provider}/preflight",
+            get(handlers::connect::preflight),
+        )
+        .route(
+            "/connect/{provider}/credentials",
+            post(handlers::connect::connect_credentials),
+        )
+        .route(
+            "/connect/{provider}/service-account",
+            post(handlers::connect::connect_service_account),
+        )
         .route(
             "/webhooks/{provider}",
             post(handlers::webhooks::ingest_webhook),
         )
+        .route("/ingest/email", post(handlers::webhooks::ingest_email))
         .layer(middleware::from_fn_with_state(
             Arc::clone(&state.config),
             auth_middleware,
         ));
 
+    // Operator reports plus the embedded dashboard (opt-in), whose API routes reuse the tenant handlers
+    let mut admin_routes = Router::new()
+        .route("/admin/slo", get(handlers::slo::get_slo_report))
+        .route(
+            "/admin/log-level",
+            get(handlers::log_level::get_log_level).put(handlers::log_level::update_log_level),
+        )
+        .route(
+            "/admin/providers/drift",
+            get(handlers::provider_catalog::get_provider_drift),
+        )
+        .route(
+            "/admin/signals/duplicates",
+            get(handlers::signal_duplicates::get_duplicate_report),
+        )
+        .route(
+            "/admin/signals/duplicates/merge",
+            post(handlers::signal_duplicates::merge_duplicates),
+        );
+    if state.config.admin_ui_enabled {
+        admin_routes = admin_routes
+            .route("/admin", get(handlers::admin::index))
+            .route("/admin/", get(handlers::admin::index))
+            .route("/admin/assets/{file}", get(handlers::admin::asset))
+            .route("/admin/api/tenants", get(handlers::admin::list_tenants))
+            .route(
+                "/admin/api/connections",
+                get(handlers::connections::list_connections),
+            )
+            .route("/admin/api/jobs", get(handlers::jobs::list_jobs))
+            .route(
+                "/admin/api/grounded-signals",
+                get(handlers::grounded_signals::list_grounded_signals),
+            );
+    }
+    let admin_routes = admin_routes.layer(middleware::from_fn_with_state(
+        Arc::clone(&state.config),
+        admin_auth_middleware,
+    ));
+
     // Combine all routes with CORS, tracing, and trace ID middleware
     Router::new()
         .merge(public_routes)
         .merge(protected_routes)
+        .merge(admin_routes)
         .with_state(state)
         // CORS: allow frontend dev origin to call backend.
         // For local development we allow:
@@ -213,13 +383,14 @@ pub fn create_app(state: AppState) -> Router {
 
 /// Creates a test AppState with TokenRefreshService for testing purposes
 pub fn create_test_app_state(config: AppConfig, db: DatabaseConnection) -> AppState {
-    let crypto_key =
-        crate::crypto::CryptoKey::new(vec![0u8; 32]).expect("Failed to create crypto key for test");
+    let master_keys = MasterKeyring::from(
+        crate::crypto::CryptoKey::new(vec![0u8; 32]).expect("Failed to create crypto key for test"),
+    );
 
     // Create required dependencies for TokenRefreshService
     let connection_repo = crate::repositories::ConnectionRepository::new(
         std::sync::Arc::new(db.clone()),
-        crypto_key.clone(),
+        master_keys.clone(),
     );
 
     // Create TokenRefreshService
@@ -232,108 +403,60 @@ pub fn create_test_app_state(config: AppConfig, db: DatabaseConnection) -> AppSt
         ));
 
     AppState {
+        snapshot: Arc::new(SnapshotCache::from_config(db.clone(), &config)),
+        settings: ReloadableConfig::from_config(&config),
         config: std::sync::Arc::new(config),
         db,
-        crypto_key,
+        master_keys,
         token_refresh_service,
+        signal_events: SignalEventBus::default(),
     }
 }
 
-/// Starts the server with the given configuration
-pub async fn run_server(
-    config: AppConfig,
+/// Builds the state shared by the API handlers
+pub fn app_state(
+    config: Arc<AppConfig>,
     db: DatabaseConnection,
-) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    let shared_config = Arc::new(config);
-    let shared_db = Arc::new(db);
-
-    // Initialize the connector registry
-    Registry::initialize(shared_config.as_ref());
-    println!("Connector registry initialized");
-
-    // Create crypto key from config
-    let crypto_key = CryptoKey::new(
-        shared_config
-            .crypto_key
-            .as_ref()
-            .ok_or("Crypto key is required")?
-            .clone(),
-    )
-    .map_err(|e| format!("Failed to create crypto key: {}", e))?;
-
-    // Create connection repository for token refresh service
-    let connection_repo = Arc::new(ConnectionRepository::new(
-        shared_db.clone(),
-        crypto_key.clone(),
-    ));
-
-    // Create and start token refresh service
-    let token_refresh_service = Arc::new(TokenRefreshService::new(
-        shared_config.clone(),
-        shared_db.clone(),
-        connection_repo,
-        Registry::global().read().unwrap().clone(),
-    ));
-
-    let shutdown_token = tokio_util::sync::CancellationToken::new();
-    let shutdown_token_for_server = shutdown_token.clone();
-    let shutdown_token_for_refresh = shutdown_token.clone();
-
-    let state = AppState {
-        config: Arc::clone(&shared_config),
-        db: (*shared_db).clone(),
-        crypto_key,
-        token_refresh_service: Arc::clone(&token_refresh_service),
-    };
-    let app = create_app(state);
+    master_keys: MasterKeyring,
+    token_refresh_service: Arc<TokenRefreshService>,
+    settings: ReloadableConfig,
+    signal_events: SignalEventBus,
+) -> AppState {
+    AppState {
+        snapshot: Arc::new(SnapshotCache::from_config(db.clone(), &config)),
+        settings,
+        config,
+        db,
+        master_keys,
+        token_refresh_service,
+        signal_events,
+    }
+}
 
+/// Serves the API until `shutdown` is cancelled, then drains open requests
+pub async fn serve(
+    state: AppState,
+    shutdown: CancellationToken,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     // Resolve the configured bind address
-    let addr = shared_config
+    let addr = state
+        .config
         .bind_addr()
         .map_err(|e| format!("Invalid server address: {}", e))?;
+    let profile = state.config.profile.clone();
+    let app = create_app(state);
 
     let listener = tokio::net::TcpListener::bind(addr).await?;
     println!("Server listening on: {}", addr);
-    println!("Running in profile: {}", shared_config.profile);
-    println!("Token refresh service started");
-
-    // Start token refresh service in background
-    let token_refresh_service_clone = token_refresh_service.clone();
-    let token_refresh_handle = tokio::spawn(async move {
-        if let Err(e) = token_refresh_service_clone
-            .run(shutdown_token_for_refresh)
-            .await
-        {
-            eprintln!("Token refresh service error: {:?}", e);
-        }
-    });
-
-    // Start the server with graceful shutdown
-    let server_handle = tokio::spawn(async move {
-        axum::serve(listener, app)
-            .with_graceful_shutdown(async move {
-                tokio::signal::ctrl_c()
-                    .await
-                    .expect("Failed to install Ctrl+C handler");
-                println!("Received shutdown signal");
-                shutdown_token_for_server.cancel();
-            })
-            .await
-    });
+    println!("Running in profile: {}", profile);
 
-    // Wait for either the server or token refresh service to complete
-    tokio::select! {
-        result = server_handle => {
-            if let Err(e) = result {
-                eprintln!("Server error: {:?}", e);
-            }
-        }
-        result = token_refresh_handle => {
-            if let Err(e) = result {
-                eprintln!("Token refresh service error: {:?}", e);
-            }
-        }
-    }
+    // Peer addresses feed the webhook source IP allowlist
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<std::net::SocketAddr>(),
+    )
+    .with_graceful_shutdown(shutdown.cancelled_owned())
+    .await?;
 
     println!("Server shutdown complete");
     Ok(())
@@ -359,19 +482,60 @@ pub async fn run_server(
         crate::handlers::config::get_rate_limit_policy_config,
         crate::handlers::config::get_config_summary,
         crate::handlers::providers::list_providers,
+        crate::handlers::providers::get_metadata_schema,
+        crate::handlers::providers::get_capabilities,
+        crate::handlers::providers::get_provider_status,
         crate::handlers::connections::list_connections,
+        crate::handlers::connections::create_connection,
+        crate::handlers::connections::update_connection,
+        crate::handlers::connections::get_connection_health,
+        crate::handlers::connections::list_connection_runs,
+        crate::handlers::connections::list_token_events,
+        crate::handlers::connections::pause_connection,
+        crate::handlers::connections::resume_connection,
+        crate::handlers::connections::trigger_sync,
         crate::handlers::jobs::list_jobs,
+        crate::handlers::jobs::cancel_job,
+        crate::handlers::schedule::get_schedule,
+        crate::handlers::schedule::get_schedule_ical,
         crate::handlers::signals::list_signals,
+        crate::handlers::signals::get_signal_trace,
+        crate::handlers::signal_kinds::list_signal_kinds,
+        crate::handlers::signal_kinds::register_signal_kind,
+        crate::handlers::stats::get_daily_stats,
+        crate::handlers::stats::get_slow_queries,
+        crate::handlers::slo::get_slo_report,
+        crate::handlers::log_level::get_log_level,
+        crate::handlers::log_level::update_log_level,
+        crate::handlers::provider_catalog::get_provider_drift,
+        crate::handlers::signal_duplicates::get_duplicate_report,
+        crate::handlers::signal_duplicates::merge_duplicates,
         crate::handlers::grounded_signals::list_grounded_signals,
         crate::handlers::grounded_signals::get_grounded_signal,
         crate::handlers::grounded_signals::update_grounded_signal,
         crate::handlers::grounded_signals::delete_grounded_signal,
         crate::handlers::tenants::create_tenant,
+        crate::handlers::tenants::bootstrap_tenant,
         crate::handlers::tenants::get_tenant,
+        crate::handlers::tenants::clone_sandbox,
+        crate::handlers::tenants::reprocess_sandbox,
+        crate::handlers::tenants::update_tenant_settings,
+        crate::handlers::tenants::get_effective_config,
+        crate::handlers::tenants::update_tenant_config,
+        crate::handlers::tenants::list_tenant_events,
+        crate::handlers::tenants::get_webhook_secret,
+        crate::handlers::tenants::rotate_webhook_secret,
+        crate::handlers::organizations::create_organization,
+        crate::handlers::organizations::get_organization,
+        crate::handlers::organizations::update_organization_settings,
         crate::handlers::connect::start_oauth,
+        crate::handlers::connect::preflight,
+        crate::handlers::connect::connect_credentials,
+        crate::handlers::connect::connect_service_account,
         crate::handlers::connect::oauth_callback,
         crate::handlers::webhooks::ingest_webhook,
         crate::handlers::webhooks::ingest_public_webhook,
+        crate::handlers::webhooks::ingest_email,
             ),
     components(
         schemas(
@@ -380,27 +544,98 @@ pub async fn run_server(
             crate::auth::TenantHeader,
             crate::handlers::ProtectedPingResponse,
             crate::handlers::providers::ProviderInfo,
+            crate::connectors::ProviderCategory,
             crate::handlers::providers::ProvidersResponse,
+            crate::handlers::providers::MetadataSchemaResponse,
+            crate::connectors::ProviderCapabilities,
+            crate::handlers::providers::ProviderStatusResponse,
+            crate::handlers::connect::PreflightStatus,
+            crate::handlers::connect::PreflightCheck,
+            crate::handlers::connect::PreflightResponse,
             crate::handlers::connections::ConnectionInfo,
             crate::handlers::connections::ConnectionsResponse,
+            crate::handlers::connections::CreateConnectionRequest,
+            crate::handlers::connections::UpdateConnectionRequest,
+            crate::handlers::connections::ConnectionHealthResponse,
+            crate::handlers::connections::LastSyncInfo,
+            crate::handlers::connections::SyncRunsResponse,
+            crate::handlers::connections::SyncRunInfo,
+            crate::handlers::connections::ListSyncRunsQuery,
+            crate::handlers::connections::TokenEventsResponse,
+            crate::handlers::connections::TokenEventInfo,
+            crate::handlers::connections::ListTokenEventsQuery,
+            crate::handlers::connections::ConnectionStatusResponse,
+            crate::handlers::connections::DryRunSyncResponse,
+            crate::handlers::connections::DryRunSignal,
             crate::handlers::connections::ListConnectionsQuery,
             crate::handlers::jobs::JobInfo,
             crate::handlers::jobs::JobsResponse,
             crate::handlers::jobs::JobStatusParam,
             crate::handlers::jobs::JobTypeParam,
+            crate::handlers::schedule::BlackoutInfo,
+            crate::handlers::schedule::ConnectionSchedule,
+            crate::handlers::schedule::ScheduleResponse,
             crate::handlers::signals::SignalInfo,
             crate::handlers::signals::SignalsResponse,
             crate::handlers::signals::ListSignalsQuery,
+            crate::handlers::signals::TraceStageStatus,
+            crate::handlers::signals::TraceStage,
+            crate::handlers::signals::SignalTraceResponse,
+            crate::handlers::signal_kinds::SignalKindsResponse,
+            crate::handlers::signal_kinds::CustomSignalKindInfo,
+            crate::handlers::signal_kinds::RegisterSignalKindRequest,
+            crate::handlers::stats::DailyStatsEntry,
+            crate::handlers::stats::DailyStatsResponse,
+            crate::handlers::stats::SlowQueryReport,
+            crate::query_stats::SlowQuerySummary,
+            crate::handlers::slo::SloReport,
+            crate::handlers::log_level::LogLevelState,
+            crate::handlers::log_level::LogLevelUpdate,
+            crate::slo::SloStatus,
+            crate::slo::SloOperation,
+            crate::seeds::CatalogReport,
+            crate::seeds::provider::AuthTypeDrift,
+            crate::handlers::signal_duplicates::DuplicateReport,
+            crate::handlers::signal_duplicates::MergeDuplicatesRequest,
+            crate::handlers::signal_duplicates::MergeDuplicatesResponse,
+            crate::signals::duplicates::DuplicateGroup,
             crate::handlers::tenants::CreateTenantRequestDto,
             crate::handlers::tenants::CreateTenantResponseDto,
+            crate::handlers::tenants::BootstrapTenantRequestDto,
+            crate::handlers::tenants::BootstrapSignalConfigDto,
+            crate::handlers::tenants::BootstrapTenantResponseDto,
+            crate::handlers::tenants::BootstrapSignalConfigResponseDto,
+            crate::handlers::tenants::SandboxSignalSampleDto,
+            crate::handlers::tenants::CloneSandboxRequestDto,
+            crate::handlers::tenants::SandboxSignalConfigDto,
+            crate::handlers::tenants::CloneSandboxResponseDto,
+            crate::handlers::tenants::ReprocessSandboxResponseDto,
+            crate::handlers::tenants::UpdateTenantSettingsRequestDto,
+            crate::handlers::tenants::TenantSettingsResponseDto,
+            crate::handlers::tenants::TenantConfigResponseDto,
+            crate::models::tenant_config::TenantConfigSettings,
+            crate::handlers::tenants::TenantEventDto,
+            crate::handlers::tenants::TenantEventsResponseDto,
+            crate::handlers::tenants::WebhookSigningSecretResponseDto,
+            crate::handlers::organizations::CreateOrganizationRequest,
+            crate::handlers::organizations::OrganizationResponse,
+            crate::config_resolution::ConfigOverrides,
+            crate::config_resolution::QuietHours,
+            crate::config_resolution::TenantRateLimitOverride,
+            crate::config_resolution::SettingSource,
+            crate::config_resolution::EffectiveConfig,
             crate::handlers::tenants::TenantResponseMeta,
             crate::handlers::connect::ProviderPath,
             crate::handlers::connect::OAuthCallbackQuery,
             crate::handlers::connect::ConnectionResponse,
             crate::handlers::connect::ConnectionInfo,
             crate::handlers::connect::AuthorizeUrlResponse,
+            crate::handlers::connect::ConnectCredentialsRequest,
+            crate::handlers::connect::ServiceAccountConnectRequest,
+            crate::handlers::connect::ServiceAccountConnectResponse,
             crate::handlers::ReadinessResponse,
             crate::handlers::webhooks::WebhookAcceptResponse,
+            crate::handlers::webhooks::InboundEmailAcceptResponse,
             crate::handlers::webhooks::ProviderPath,
             crate::handlers::webhooks::GitHubSignatureHeader,
             crate::handlers::webhooks::SlackSignatureHeaders,
@@ -426,8 +661,10 @@ pub async fn run_server(
         (name = "webhooks", description = "Webhook ingest endpoints"),
         (name = "jobs", description = "Jobs listing and management endpoints"),
         (name = "signals", description = "Signals listing and querying endpoints"),
+        (name = "stats", description = "Historical job and signal statistics endpoints"),
         (name = "grounded-signals", description = "Grounded signals management endpoints"),
         (name = "tenants", description = "Tenant management endpoints"),
+        (name = "organizations", description = "Organization defaults inherited by tenants"),
     ),
     security(
         ("bearer_auth" = []),
@@ -435,6 +672,13 @@ pub async fn run_server(
 )]
 pub struct ApiDoc;
 
+/// The OpenAPI document as pretty-printed JSON, as committed in `openapi.json`
+pub fn openapi_json() -> Result<String, serde_json::Error> {
+    let mut json = ApiDoc::openapi().to_pretty_json()?;
+    json.push('\n');
+    Ok(json)
+}
+
 struct SecurityAddon;
 
 impl Modify for SecurityAddon {
@@ -0,0 +1,223 @@
+//! Near-duplicate signal detection
+//!
+//! Historical ingestion bugs occasionally stored the same provider event more
+//! than once, either under an identical dedupe key or under keys that differ
+//! only in a trailing timestamp. This module groups a tenant's signals by
+//! provider, kind, dedupe key family and payload hash, then splits each group
+//! wherever consecutive `occurred_at` values are further apart than the window.
+//! Within a group the earliest received signal is the one to keep, matching the
+//! `duplicate_of` reported by the signal trace.
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::models::signal::Model as Signal;
+
+/// Signals stored for the same event
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct DuplicateGroup {
+    /// Provider that emitted the signals
+    #[schema(example = "github")]
+    pub provider_slug: String,
+    /// Signal kind shared by the group
+    #[schema(example = "issue_updated")]
+    pub kind: String,
+    /// Dedupe key with any trailing timestamp removed; null when the signals have no key
+    #[schema(example = "jira:issue_updated:10001")]
+    pub dedupe_family: Option<String>,
+    /// SHA-256 of the canonical JSON payload
+    pub payload_hash: String,
+    /// Earliest received signal, kept by a merge
+    pub keep_id: Uuid,
+    /// Later copies, removed by a merge
+    pub duplicate_ids: Vec<Uuid>,
+    /// Earliest `occurred_at` in the group (RFC3339)
+    pub first_occurred_at: String,
+    /// Latest `occurred_at` in the group (RFC3339)
+    pub last_occurred_at: String,
+}
+
+/// Dedupe key without a trailing timestamp segment
+///
+/// Keys such as `jira:issue_updated:10001:2024-05-02T10:00:00+00:00` or
+/// `gcal_sync_1714600000` embed when they were produced, so copies of one
+/// event ingested at different times differ only in that suffix. A suffix
+/// counts as a timestamp when it is RFC3339 or a run of at least ten digits
+/// (Unix seconds or milliseconds).
+pub fn dedupe_family(key: &str) -> &str {
+    for (index, separator) in key.char_indices() {
+        if (separator == ':' || separator == '_')
+            && DateTime::parse_from_rfc3339(&key[index + 1..]).is_ok()
+        {
+            return &key[..index];
+        }
+    }
+    match key.rfind([':', '_']) {
+        Some(index)
+            if key.len() - index > 10 && key[index + 1..].bytes().all(|b| b.is_ascii_digit()) =>
+        {
+            &key[..index]
+        }
+        _ => key,
+    }
+}
+
+/// SHA-256 of the payload serialized with sorted object keys
+pub fn payload_hash(payload: &serde_json::Value) -> String {
+    // serde_json maps are ordered by key, so equal payloads serialize identically
+    let bytes = serde_json::to_vec(payload).unwrap_or_default();
+    hex::encode(Sha256::digest(bytes))
+}
+
+/// Groups of two or more signals that describe the same event
+///
+/// Groups are ordered by the time of their earliest signal.
+pub fn find_duplicates(signals: &[Signal], window: Duration) -> Vec<DuplicateGroup> {
+    type GroupKey<'a> = (&'a str, &'a str, Option<&'a str>, String);
+    let mut candidates: BTreeMap<GroupKey<'_>, Vec<&Signal>> = BTreeMap::new();
+    for signal in signals {
+        candidates
+            .entry((
+                signal.provider_slug.as_str(),
+                signal.kind.as_str(),
+                signal.dedupe_key.as_deref().map(dedupe_family),
+                payload_hash(&signal.payload),
+            ))
+            .or_default()
+            .push(signal);
+    }
+
+    let mut groups = Vec::new();
+    for ((provider_slug, kind, family, hash), mut members) in candidates {
+        members.sort_by_key(|signal| (signal.occurred_at, signal.id));
+        let mut cluster: Vec<&Signal> = Vec::new();
+        for signal in members {
+            if let Some(previous) = cluster.last()
+                && signal.occurred_at - previous.occurred_at > window
+            {
+                groups.extend(to_group(provider_slug, kind, family, &hash, &cluster));
+                cluster.clear();
+            }
+            cluster.push(signal);
+        }
+        groups.extend(to_group(provider_slug, kind, family, &hash, &cluster));
+    }
+
+    groups
+        .sort_by(|a, b| (&a.first_occurred_at, a.keep_id).cmp(&(&b.first_occurred_at, b.keep_id)));
+    groups
+}
+
+fn to_group(
+    provider_slug: &str,
+    kind: &str,
+    family: Option<&str>,
+    hash: &str,
+    cluster: &[&Signal],
+) -> Option<DuplicateGroup> {
+    if cluster.len() < 2 {
+        return None;
+    }
+    let mut by_receipt = cluster.to_vec();
+    by_receipt.sort_by_key(|signal| (signal.received_at, signal.id));
+    let occurred = |signal: &Signal| signal.occurred_at.with_timezone(&Utc).to_rfc3339();
+
+    Some(DuplicateGroup {
+        provider_slug: provider_slug.to_string(),
+        kind: kind.to_string(),
+        dedupe_family: family.map(str::to_string),
+        payload_hash: hash.to_string(),
+        keep_id: by_receipt[0].id,
+        duplicate_ids: by_receipt[1..].iter().map(|signal| signal.id).collect(),
+        first_occurred_at: occurred(cluster[0]),
+        last_occurred_at: occurred(cluster[cluster.len() - 1]),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn signal(key: &str, minute: u32, received_minute: u32, payload: serde_json::Value) -> Signal {
+        let at = |minute| {
+            Utc.with_ymd_and_hms(2024, 5, 2, 10, minute, 0)
+                .unwrap()
+                .into()
+        };
+        Signal {
+            id: Uuid::new_v4(),
+            tenant_id: Uuid::nil(),
+            provider_slug: "jira".to_string(),
+            connection_id: Uuid::nil(),
+            kind: "issue_updated".to_string(),
+            occurred_at: at(minute),
+            received_at: at(received_minute),
+            payload,
+            dedupe_key: Some(key.to_string()),
+            created_at: at(received_minute),
+            updated_at: at(received_minute),
+        }
+    }
+
+    #[test]
+    fn test_dedupe_family_strips_trailing_timestamps() {
+        assert_eq!(
+            dedupe_family("jira:issue_updated:10001:2024-05-02T10:00:00+00:00"),
+            "jira:issue_updated:10001"
+        );
+        assert_eq!(dedupe_family("gcal_sync_1714600000"), "gcal_sync");
+        assert_eq!(dedupe_family("github_issue_42"), "github_issue_42");
+        assert_eq!(
+            dedupe_family("linear:issue_created:abc"),
+            "linear:issue_created:abc"
+        );
+    }
+
+    #[test]
+    fn test_find_duplicates_groups_within_window() {
+        let payload = serde_json::json!({"issue": 10001, "status": "Done"});
+        let signals = vec![
+            signal(
+                "jira:issue_updated:10001:2024-05-02T10:00:00+00:00",
+                0,
+                5,
+                payload.clone(),
+            ),
+            signal(
+                "jira:issue_updated:10001:2024-05-02T10:01:00+00:00",
+                1,
+                1,
+                payload.clone(),
+            ),
+            // Same event, but far outside the window
+            signal(
+                "jira:issue_updated:10001:2024-05-02T10:50:00+00:00",
+                50,
+                50,
+                payload.clone(),
+            ),
+            // Different payload is never a duplicate
+            signal(
+                "jira:issue_updated:10001:2024-05-02T10:00:00+00:00",
+                0,
+                6,
+                serde_json::json!({"issue": 10001, "status": "Open"}),
+            ),
+        ];
+
+        let groups = find_duplicates(&signals, Duration::minutes(5));
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].keep_id, signals[1].id);
+        assert_eq!(groups[0].duplicate_ids, vec![signals[0].id]);
+        assert_eq!(
+            groups[0].dedupe_family.as_deref(),
+            Some("jira:issue_updated:10001")
+        );
+        assert_eq!(groups[0].first_occurred_at, "2024-05-02T10:00:00+00:00");
+    }
+}
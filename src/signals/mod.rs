@@ -1,8 +1,15 @@
 //! # Signals Module
 //!
 //! This module contains the signal processing pipeline including the weak signal engine
-//! that processes normalized signals and promotes them to grounded signals.
+//! that processes normalized signals and promotes them to grounded signals, and the
+//! transactional outbox that delivers committed signals downstream. Payload size
+//! limits are applied before signals are persisted, and near-duplicates left by
+//! past ingestion bugs can be detected afterwards.
 
+pub mod duplicates;
+pub mod outbox;
+pub mod payload_limit;
 pub mod weak_engine;
 
-pub use weak_engine::{WeakSignalEngine, WeakSignalEngineConfig};
+pub use outbox::{OutboxPublisher, OutboxRelay, OutboxRelayConfig, SignalEventBus};
+pub use weak_engine::{ReprocessSummary, WeakSignalEngine, WeakSignalEngineConfig};
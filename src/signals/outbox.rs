@@ -0,0 +1,424 @@
+//! # Signal Outbox Relay
+//!
+//! Implements the transactional outbox for the signals pipeline. The sync executor
+//! writes one outbox entry per downstream topic in the same transaction that
+//! persists a signal; the relay then drains committed entries and hands them to
+//! the publisher registered for each topic.
+//!
+//! Delivery is at-least-once: a publisher may see an entry again if the relay
+//! crashes between publishing and marking it published. Every event carries the
+//! outbox entry id so consumers can deduplicate, which together with the
+//! transactional write gives effectively exactly-once processing.
+
+use async_trait::async_trait;
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::broadcast;
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, error, info, warn};
+use uuid::Uuid;
+
+use crate::backoff::ExponentialBackoff;
+use crate::error::RepositoryError;
+use crate::models::signal::{self, Model as SignalModel};
+use crate::models::signal_outbox::Model as OutboxEntry;
+use crate::repositories::SignalOutboxRepository;
+use crate::signals::WeakSignalEngine;
+
+/// Topic for publishing newly persisted signals on the in-process event bus
+pub const TOPIC_SIGNAL_CREATED: &str = "signal.created";
+/// Topic for handing newly persisted signals to the weak signal engine
+pub const TOPIC_WEAK_ENGINE_ENQUEUE: &str = "weak_engine.enqueue";
+
+/// Topics every persisted signal is delivered to
+pub const SIGNAL_TOPICS: &[&str] = &[TOPIC_SIGNAL_CREATED, TOPIC_WEAK_ENGINE_ENQUEUE];
+
+/// Errors raised by outbox publishers
+#[derive(Debug, thiserror::Error)]
+pub enum OutboxError {
+    #[error("Repository error: {0}")]
+    Repository(#[from] RepositoryError),
+    #[error("Publish failed: {0}")]
+    Publish(String),
+}
+
+/// A committed signal delivered through the outbox
+#[derive(Debug, Clone)]
+pub struct OutboxEvent {
+    /// Outbox entry id; stable across redeliveries and usable as an idempotency key
+    pub event_id: Uuid,
+    /// Topic the event was published on
+    pub topic: String,
+    /// Attempt number for this delivery (1-based)
+    pub attempt: i32,
+    /// The committed signal
+    pub signal: SignalModel,
+}
+
+/// Downstream consumer of outbox events for a single topic
+#[async_trait]
+pub trait OutboxPublisher: Send + Sync {
+    /// Publish a batch of events. Returning an error leaves the whole batch pending
+    /// for retry, so implementations must tolerate redelivery.
+    async fn publish(&self, events: &[OutboxEvent]) -> Result<(), OutboxError>;
+}
+
+/// In-process broadcast bus for committed signals
+#[derive(Clone)]
+pub struct SignalEventBus {
+    sender: broadcast::Sender<OutboxEvent>,
+}
+
+impl SignalEventBus {
+    /// Create a new event bus retaining up to `capacity` events per lagging subscriber
+    pub fn new(capacity: usize) -> Self {
+        let (sender, _) = broadcast::channel(capacity.max(1));
+        Self { sender }
+    }
+
+    /// Subscribe to committed signal events
+    pub fn subscribe(&self) -> broadcast::Receiver<OutboxEvent> {
+        self.sender.subscribe()
+    }
+}
+
+impl Default for SignalEventBus {
+    fn default() -> Self {
+        Self::new(1024)
+    }
+}
+
+#[async_trait]
+impl OutboxPublisher for SignalEventBus {
+    async fn publish(&self, events: &[OutboxEvent]) -> Result<(), OutboxError> {
+        for event in events {
+            // No subscribers is not an error: the event is committed and nobody is listening
+            let _ = self.sender.send(event.clone());
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl OutboxPublisher for WeakSignalEngine {
+    async fn publish(&self, events: &[OutboxEvent]) -> Result<(), OutboxError> {
+        let signal_ids: Vec<Uuid> = events.iter().map(|event| event.signal.id).collect();
+        self.process_signal_ids(&signal_ids).await?;
+        Ok(())
+    }
+}
+
+/// Configuration for the outbox relay
+#[derive(Debug, Clone)]
+pub struct OutboxRelayConfig {
+    /// Milliseconds between relay ticks
+    pub tick_ms: u64,
+    /// Maximum number of entries drained per topic per tick
+    pub batch_size: u64,
+    /// Attempts after which an entry is parked as failed
+    pub max_attempts: i32,
+    /// Base retry delay in seconds, doubled per failed attempt
+    pub retry_base_seconds: i64,
+    /// Upper bound for the retry delay in seconds
+    pub retry_max_seconds: i64,
+}
+
+impl Default for OutboxRelayConfig {
+    fn default() -> Self {
+        Self {
+            tick_ms: 1000,
+            batch_size: 100,
+            max_attempts: 10,
+            retry_base_seconds: 5,
+            retry_max_seconds: 900, // 15 minutes
+        }
+    }
+}
+
+/// Background relay draining the signal outbox into registered publishers
+pub struct OutboxRelay {
+    db: Arc<DatabaseConnection>,
+    config: OutboxRelayConfig,
+    publishers: BTreeMap<String, Arc<dyn OutboxPublisher>>,
+}
+
+impl OutboxRelay {
+    /// Create a new relay with no publishers registered
+    pub fn new(db: Arc<DatabaseConnection>, config: OutboxRelayConfig) -> Self {
+        Self {
+            db,
+            config,
+            publishers: BTreeMap::new(),
+        }
+    }
+
+    /// Register the publisher responsible for a topic
+    pub fn with_publisher(mut self, topic: &str, publisher: Arc<dyn OutboxPublisher>) -> Self {
+        self.publishers.insert(topic.to_string(), publisher);
+        self
+    }
+
+    /// Run the relay loop until the shutdown token is cancelled
+    pub async fn run(&self, shutdown: CancellationToken) -> Result<(), OutboxError> {
+        info!(
+            topics = ?self.publishers.keys().collect::<Vec<_>>(),
+            "Starting signal outbox relay"
+        );
+
+        loop {
+            match self.relay_once().await {
+                Ok(count) if count > 0 => debug!("Relayed {} outbox entries", count),
+                Ok(_) => {}
+                Err(e) => error!("Error relaying signal outbox: {}", e),
+            }
+
+            tokio::select! {
+                _ = shutdown.cancelled() => {
+                    info!("Signal outbox relay shutting down");
+                    return Ok(());
+                }
+                _ = tokio::time::sleep(Duration::from_millis(self.config.tick_ms)) => {}
+            }
+        }
+    }
+
+    /// Drain one batch per registered topic, returning the number of entries published
+    pub async fn relay_once(&self) -> Result<usize, OutboxError> {
+        let repo = SignalOutboxRepository::new(self.db.as_ref().clone());
+        let mut published = 0;
+
+        for (topic, publisher) in &self.publishers {
+            let entries = repo.list_due(topic, self.config.batch_size).await?;
+            if entries.is_empty() {
+                continue;
+            }
+
+            let events = self.load_events(&entries).await?;
+            match publisher.publish(&events).await {
+                Ok(()) => {
+                    let ids = entries.iter().map(|entry| entry.id).collect();
+                    published += repo.mark_published(ids).await? as usize;
+                }
+                Err(e) => {
+                    warn!(topic = %topic, error = %e, "Outbox publish failed, scheduling retry");
+                    for entry in &entries {
+                        let retry_in = self.retry_delay(entry.attempts);
+                        repo.mark_attempt_failed(
+                            entry,
+                            &e.to_string(),
+                            retry_in,
+                            self.config.max_attempts,
+                        )
+                        .await?;
+                    }
+                }
+            }
+        }
+
+        Ok(published)
+    }
+
+    async fn load_events(&self, entries: &[OutboxEntry]) -> Result<Vec<OutboxEvent>, OutboxError> {
+        let signal_ids: Vec<Uuid> = entries.iter().map(|entry| entry.signal_id).collect();
+        let signals: BTreeMap<Uuid, SignalModel> = signal::Entity::find()
+            .filter(signal::Column::Id.is_in(signal_ids))
+            .all(self.db.as_ref())
+            .await
+            .map_err(RepositoryError::database_error)?
+            .into_iter()
+            .map(|signal| (signal.id, signal))
+            .collect();
+
+        Ok(entries
+            .iter()
+            .filter_map(|entry| {
+                signals.get(&entry.signal_id).map(|signal| OutboxEvent {
+                    event_id: entry.id,
+                    topic: entry.topic.clone(),
+                    attempt: entry.attempts + 1,
+                    signal: signal.clone(),
+                })
+            })
+            .collect())
+    }
+
+    fn retry_delay(&self, attempts: i32) -> chrono::Duration {
+        let delay = ExponentialBackoff::new(
+            Duration::from_secs(self.config.retry_base_seconds.max(0) as u64),
+            Duration::from_secs(self.config.retry_max_seconds.max(0) as u64),
+        )
+        .delay(attempts.max(0) as u32);
+        chrono::Duration::seconds(delay.as_secs() as i64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::signal::ActiveModel as SignalActiveModel;
+    use crate::models::signal_outbox::{STATUS_PENDING, STATUS_PUBLISHED};
+    use crate::test_fixtures::setup_connection;
+    use chrono::Utc;
+    use sea_orm::{ActiveModelTrait, TransactionTrait};
+    use std::sync::Mutex;
+
+    /// Publisher recording every event it sees, optionally failing
+    struct RecordingPublisher {
+        fail: bool,
+        seen: Mutex<Vec<OutboxEvent>>,
+    }
+
+    impl RecordingPublisher {
+        fn new(fail: bool) -> Self {
+            Self {
+                fail,
+                seen: Mutex::new(Vec::new()),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl OutboxPublisher for RecordingPublisher {
+        async fn publish(&self, events: &[OutboxEvent]) -> Result<(), OutboxError> {
+            self.seen.lock().unwrap().extend(events.iter().cloned());
+            if self.fail {
+                return Err(OutboxError::Publish("downstream unavailable".to_string()));
+            }
+            Ok(())
+        }
+    }
+
+    fn new_signal(tenant_id: Uuid, connection_id: Uuid) -> SignalActiveModel {
+        SignalActiveModel {
+            id: sea_orm::Set(Uuid::new_v4()),
+            tenant_id: sea_orm::Set(tenant_id),
+            provider_slug: sea_orm::Set("test-provider".to_string()),
+            connection_id: sea_orm::Set(connection_id),
+            kind: sea_orm::Set("outbox_test".to_string()),
+            occurred_at: sea_orm::Set(Utc::now().into()),
+            received_at: sea_orm::Set(Utc::now().into()),
+            payload: sea_orm::Set(serde_json::json!({})),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rolled_back_signal_leaves_no_outbox_entries() {
+        let (db, tenant_id, connection_id) = setup_connection().await;
+
+        let txn = db.begin().await.unwrap();
+        let signal = new_signal(tenant_id, connection_id)
+            .insert(&txn)
+            .await
+            .unwrap();
+        SignalOutboxRepository::enqueue_for_signal(&txn, &signal, SIGNAL_TOPICS)
+            .await
+            .unwrap();
+        txn.rollback().await.unwrap();
+
+        let entries = SignalOutboxRepository::new(db.clone())
+            .list_for_signal(tenant_id, signal.id)
+            .await
+            .unwrap();
+        assert!(entries.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_committed_signal_is_published_once() {
+        let (db, tenant_id, connection_id) = setup_connection().await;
+        let topic = format!("test.{}", Uuid::new_v4());
+
+        let txn = db.begin().await.unwrap();
+        let signal = new_signal(tenant_id, connection_id)
+            .insert(&txn)
+            .await
+            .unwrap();
+        SignalOutboxRepository::enqueue_for_signal(&txn, &signal, &[topic.as_str()])
+            .await
+            .unwrap();
+        // Enqueueing the same signal/topic twice must not duplicate the delivery
+        SignalOutboxRepository::enqueue_for_signal(&txn, &signal, &[topic.as_str()])
+            .await
+            .unwrap();
+        txn.commit().await.unwrap();
+
+        let publisher = Arc::new(RecordingPublisher::new(false));
+        let relay = OutboxRelay::new(Arc::new(db.clone()), OutboxRelayConfig::default())
+            .with_publisher(&topic, publisher.clone());
+
+        assert_eq!(relay.relay_once().await.unwrap(), 1);
+        assert_eq!(relay.relay_once().await.unwrap(), 0);
+
+        let seen = publisher.seen.lock().unwrap().clone();
+        assert_eq!(seen.len(), 1);
+        assert_eq!(seen[0].signal.id, signal.id);
+        assert_eq!(seen[0].attempt, 1);
+
+        let entries = SignalOutboxRepository::new(db.clone())
+            .list_for_signal(tenant_id, signal.id)
+            .await
+            .unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].status, STATUS_PUBLISHED);
+        assert!(entries[0].published_at.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_event_bus_subscribers_receive_relayed_signals() {
+        let (db, tenant_id, connection_id) = setup_connection().await;
+        let topic = format!("test.{}", Uuid::new_v4());
+
+        let signal = new_signal(tenant_id, connection_id)
+            .insert(&db)
+            .await
+            .unwrap();
+        SignalOutboxRepository::enqueue_for_signal(&db, &signal, &[topic.as_str()])
+            .await
+            .unwrap();
+
+        // The relay publishes through a clone, as the server and relay share one bus
+        let bus = SignalEventBus::default();
+        let mut events = bus.subscribe();
+        let relay = OutboxRelay::new(Arc::new(db.clone()), OutboxRelayConfig::default())
+            .with_publisher(&topic, Arc::new(bus.clone()));
+
+        assert_eq!(relay.relay_once().await.unwrap(), 1);
+        let event = events.try_recv().unwrap();
+        assert_eq!(event.signal.id, signal.id);
+        assert_eq!(event.topic, topic);
+    }
+
+    #[tokio::test]
+    async fn test_failed_publish_is_rescheduled() {
+        let (db, tenant_id, connection_id) = setup_connection().await;
+        let topic = format!("test.{}", Uuid::new_v4());
+
+        let signal = new_signal(tenant_id, connection_id)
+            .insert(&db)
+            .await
+            .unwrap();
+        SignalOutboxRepository::enqueue_for_signal(&db, &signal, &[topic.as_str()])
+            .await
+            .unwrap();
+
+        let publisher = Arc::new(RecordingPublisher::new(true));
+        let relay = OutboxRelay::new(Arc::new(db.clone()), OutboxRelayConfig::default())
+            .with_publisher(&topic, publisher.clone());
+
+        assert_eq!(relay.relay_once().await.unwrap(), 0);
+        // Entry is backed off, so an immediate second tick does not redeliver
+        assert_eq!(relay.relay_once().await.unwrap(), 0);
+        assert_eq!(publisher.seen.lock().unwrap().len(), 1);
+
+        let entries = SignalOutboxRepository::new(db.clone())
+            .list_for_signal(tenant_id, signal.id)
+            .await
+            .unwrap();
+        assert_eq!(entries[0].status, STATUS_PENDING);
+        assert_eq!(entries[0].attempts, 1);
+        assert!(entries[0].last_error.is_some());
+        assert!(entries[0].available_at > Utc::now().fixed_offset());
+    }
+}
@@ -0,0 +1,222 @@
+//! # Signal Payload Limits
+//!
+//! Connectors copy provider content into signal payloads verbatim, so one
+//! oversized issue description or message body can make every query and scoring
+//! pass over that signal slow. Payloads larger than the configured limit are
+//! shrunk before they are persisted:
+//!
+//! 1. Top-level fields that are numbers, booleans, null or short strings form
+//!    the envelope (IDs, URLs, timestamps) and are never altered.
+//! 2. Every other string is cut to the same byte length, the largest one that
+//!    brings the payload under the limit, so the biggest bodies lose the most.
+//! 3. If the structure alone is still too large, only the envelope is kept.
+//!
+//! Truncated payloads carry `truncated: true` and `original_size_bytes`. The
+//! result depends only on the payload and the limit, so re-delivering the same
+//! content produces the same stored payload.
+
+use serde_json::{Map, Value};
+
+/// Smallest limit accepted by configuration
+pub const MIN_PAYLOAD_BYTES: usize = 1024;
+
+/// Marker set on payloads that were shrunk
+pub const TRUNCATED_FIELD: &str = "truncated";
+
+/// Serialized size of the payload before truncation
+pub const ORIGINAL_SIZE_FIELD: &str = "original_size_bytes";
+
+/// Longest top-level string that still counts as an envelope field
+const ENVELOPE_STRING_MAX_BYTES: usize = 256;
+
+/// Shrink `payload` to at most `max_bytes` of serialized JSON, returning whether it changed
+pub fn enforce_limit(payload: &mut Value, max_bytes: usize) -> bool {
+    let original_size = serialized_len(payload);
+    if original_size <= max_bytes {
+        return false;
+    }
+
+    let Value::Object(fields) = payload else {
+        *payload = Value::Object(markers(original_size));
+        return true;
+    };
+
+    let mut marked = fields.clone();
+    marked.extend(markers(original_size));
+
+    let longest = marked
+        .iter()
+        .filter(|(_, value)| !is_envelope(value))
+        .map(|(_, value)| longest_string(value))
+        .max()
+        .unwrap_or(0);
+
+    // Size only shrinks as the cap shrinks, so search for the largest cap that fits
+    if fits(&clip_fields(&marked, 0), max_bytes) {
+        let (mut low, mut high) = (0, longest);
+        while low < high {
+            let cap = low + (high - low).div_ceil(2);
+            if fits(&clip_fields(&marked, cap), max_bytes) {
+                low = cap;
+            } else {
+                high = cap - 1;
+            }
+        }
+        *payload = Value::Object(clip_fields(&marked, low));
+        return true;
+    }
+
+    // Bodies are mostly structure (e.g. long arrays); keep what fits of the envelope
+    let mut envelope = markers(original_size);
+    for (key, value) in fields.iter() {
+        if is_envelope(value) {
+            envelope.insert(key.clone(), value.clone());
+            if !fits(&envelope, max_bytes) {
+                envelope.remove(key);
+            }
+        }
+    }
+    *payload = Value::Object(envelope);
+    true
+}
+
+fn markers(original_size: usize) -> Map<String, Value> {
+    let mut markers = Map::new();
+    markers.insert(TRUNCATED_FIELD.to_string(), Value::Bool(true));
+    markers.insert(ORIGINAL_SIZE_FIELD.to_string(), Value::from(original_size));
+    markers
+}
+
+fn is_envelope(value: &Value) -> bool {
+    match value {
+        Value::Null | Value::Bool(_) | Value::Number(_) => true,
+        Value::String(s) => s.len() <= ENVELOPE_STRING_MAX_BYTES,
+        _ => false,
+    }
+}
+
+fn serialized_len(value: &impl serde::Serialize) -> usize {
+    serde_json::to_vec(value)
+        .map(|bytes| bytes.len())
+        .unwrap_or(0)
+}
+
+fn fits(fields: &Map<String, Value>, max_bytes: usize) -> bool {
+    serialized_len(fields) <= max_bytes
+}
+
+fn longest_string(value: &Value) -> usize {
+    match value {
+        Value::String(s) => s.len(),
+        Value::Array(items) => items.iter().map(longest_string).max().unwrap_or(0),
+        Value::Object(fields) => fields.values().map(longest_string).max().unwrap_or(0),
+        _ => 0,
+    }
+}
+
+fn clip_fields(fields: &Map<String, Value>, cap: usize) -> Map<String, Value> {
+    fields
+        .iter()
+        .map(|(key, value)| {
+            let value = if is_envelope(value) {
+                value.clone()
+            } else {
+                clip(value, cap)
+            };
+            (key.clone(), value)
+        })
+        .collect()
+}
+
+fn clip(value: &Value, cap: usize) -> Value {
+    match value {
+        Value::String(s) if s.len() > cap => {
+            let mut end = cap;
+            while !s.is_char_boundary(end) {
+                end -= 1;
+            }
+            Value::String(s[..end].to_string())
+        }
+        Value::Array(items) => Value::Array(items.iter().map(|item| clip(item, cap)).collect()),
+        Value::Object(fields) => Value::Object(
+            fields
+                .iter()
+                .map(|(key, value)| (key.clone(), clip(value, cap)))
+                .collect(),
+        ),
+        other => other.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_payload_under_limit_is_untouched() {
+        let mut payload = json!({"issue_key": "ENG-1", "description": "short"});
+        let original = payload.clone();
+        assert!(!enforce_limit(&mut payload, 4096));
+        assert_eq!(payload, original);
+    }
+
+    #[test]
+    fn test_large_body_is_truncated_and_envelope_preserved() {
+        let description = "é".repeat(2_500_000); // 5MB of two-byte characters
+        let mut payload = json!({
+            "issue_key": "ENG-1",
+            "url": "https://example.atlassian.net/browse/ENG-1",
+            "priority": 2,
+            "description": description,
+            "fields": {"summary": "Login broken", "environment": "x".repeat(10_000)},
+        });
+
+        assert!(enforce_limit(&mut payload, 8192));
+        assert!(serde_json::to_vec(&payload).unwrap().len() <= 8192);
+        assert_eq!(payload["truncated"], true);
+        assert!(payload["original_size_bytes"].as_u64().unwrap() > 5_000_000);
+        assert_eq!(payload["issue_key"], "ENG-1");
+        assert_eq!(payload["url"], "https://example.atlassian.net/browse/ENG-1");
+        assert_eq!(payload["priority"], 2);
+        // Both bodies are cut to the same length, on a character boundary
+        let kept = payload["description"].as_str().unwrap();
+        assert!(kept.len() > 1000 && kept.chars().all(|c| c == 'é'));
+        assert_eq!(payload["fields"]["summary"], "Login broken");
+        let environment = payload["fields"]["environment"].as_str().unwrap();
+        assert!(environment.len() - kept.len() <= 1);
+    }
+
+    #[test]
+    fn test_truncation_is_deterministic() {
+        let build = || {
+            json!({
+                "id": "msg-1",
+                "text": "lorem ipsum ".repeat(5000),
+                "blocks": [{"text": "a".repeat(3000)}, {"text": "b".repeat(100)}],
+            })
+        };
+        let (mut first, mut second) = (build(), build());
+        enforce_limit(&mut first, 2048);
+        enforce_limit(&mut second, 2048);
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn test_structure_heavy_payload_keeps_only_envelope() {
+        let mut payload = json!({
+            "id": "page-1",
+            "items": (0..5000).map(|i| json!({"n": i})).collect::<Vec<_>>(),
+        });
+
+        assert!(enforce_limit(&mut payload, 1024));
+        assert_eq!(
+            payload,
+            json!({
+                "id": "page-1",
+                "truncated": true,
+                "original_size_bytes": payload["original_size_bytes"].clone(),
+            })
+        );
+    }
+}
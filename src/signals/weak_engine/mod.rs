@@ -3,12 +3,15 @@
 //! A background service that processes normalized signals, applies scoring models,
 //! and promotes high-confidence candidates to grounded signals with recommendations.
 
+use crate::clock::{SharedClock, system_clock};
+use crate::config_resolution::ConfigLayers;
 use crate::error::RepositoryError;
 use crate::models::signal::Model as Signal;
 use crate::models::{GroundedSignalResponse, ScoringWeights, SignalScores};
 use crate::repositories::{
     GroundedSignalRepository, SignalRepository, TenantSignalConfigRepository,
 };
+use crate::slo::{self, SloOperation};
 use sea_orm::DatabaseConnection;
 use sha2::{Digest, Sha256};
 use std::cmp::Ordering;
@@ -99,6 +102,17 @@ impl Default for WeakSignalEngineConfig {
     }
 }
 
+/// Outcome of [`WeakSignalEngine::reprocess_tenant`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReprocessSummary {
+    /// Grounded signals removed before scoring again
+    pub grounded_signals_removed: u64,
+    /// Signals scored
+    pub signals_processed: usize,
+    /// Grounded signals created by the new run
+    pub grounded_signals_created: usize,
+}
+
 /// Weak Signal Engine that processes signals and creates grounded signals
 pub struct WeakSignalEngine {
     db: Arc<DatabaseConnection>,
@@ -106,6 +120,7 @@ pub struct WeakSignalEngine {
     notifier: Notifier,
     config: WeakSignalEngineConfig,
     vectorizer: TFIDFVectorizer,
+    clock: SharedClock,
 }
 
 impl WeakSignalEngine {
@@ -121,15 +136,23 @@ impl WeakSignalEngine {
             notifier,
             config,
             vectorizer,
+            clock: system_clock(),
         }
     }
 
+    /// Replace the time source used for signal age cutoffs and timeliness scoring
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.scorer = self.scorer.with_clock(clock.clone());
+        self.clock = clock;
+        self
+    }
+
     /// Process new signals and create grounded signals for those that meet thresholds
     pub async fn process_signals(&self) -> Result<(), RepositoryError> {
         info!("Starting weak signal processing cycle");
 
         // Get recent signals that haven't been processed yet
-        let cutoff_time = (chrono::Utc::now()
+        let cutoff_time = (self.clock.now()
             - chrono::Duration::hours(self.config.max_signal_age_hours))
         .naive_utc();
 
@@ -182,12 +205,80 @@ impl WeakSignalEngine {
         Ok(())
     }
 
-    /// Process signals for a specific tenant
+    /// Process a specific set of signals, typically handed over by the outbox relay
+    pub async fn process_signal_ids(&self, signal_ids: &[Uuid]) -> Result<(), RepositoryError> {
+        use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+
+        if signal_ids.is_empty() {
+            return Ok(());
+        }
+
+        let signals = crate::models::Signal::find()
+            .filter(crate::models::signal::Column::Id.is_in(signal_ids.to_vec()))
+            .all(self.db.as_ref())
+            .await
+            .map_err(RepositoryError::database_error)?;
+
+        let mut tenant_signals: std::collections::HashMap<Uuid, Vec<&Signal>> =
+            std::collections::HashMap::new();
+        for signal in &signals {
+            tenant_signals
+                .entry(signal.tenant_id)
+                .or_default()
+                .push(signal);
+        }
+
+        for (tenant_id, signals) in tenant_signals {
+            self.process_tenant_signals(tenant_id, &signals).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Drop a tenant's grounded signals and score up to `limit` of its most recent signals again.
+    ///
+    /// Used to compare scoring settings in sandbox tenants; the tenant's current
+    /// threshold and weights apply, and the signal age cutoff does not.
+    pub async fn reprocess_tenant(
+        &self,
+        tenant_id: Uuid,
+        limit: u64,
+    ) -> Result<ReprocessSummary, RepositoryError> {
+        use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, QueryOrder, QuerySelect};
+
+        let grounded_signals_removed = GroundedSignalRepository::new(&self.db)
+            .delete_for_tenant(tenant_id)
+            .await?;
+
+        let mut signals = crate::models::Signal::find()
+            .filter(crate::models::signal::Column::TenantId.eq(tenant_id))
+            .order_by_desc(crate::models::signal::Column::OccurredAt)
+            .limit(limit)
+            .all(self.db.as_ref())
+            .await
+            .map_err(RepositoryError::database_error)?;
+        signals.reverse();
+
+        let signal_refs: Vec<&Signal> = signals.iter().collect();
+        let grounded_signals_created = if signal_refs.is_empty() {
+            0
+        } else {
+            self.process_tenant_signals(tenant_id, &signal_refs).await?
+        };
+
+        Ok(ReprocessSummary {
+            grounded_signals_removed,
+            signals_processed: signals.len(),
+            grounded_signals_created,
+        })
+    }
+
+    /// Process signals for a specific tenant, returning the number of grounded signals created
     async fn process_tenant_signals(
         &self,
         tenant_id: Uuid,
         signals: &[&Signal],
-    ) -> Result<(), RepositoryError> {
+    ) -> Result<usize, RepositoryError> {
         debug!(
             "Processing {} signals for tenant {}",
             signals.len(),
@@ -204,19 +295,30 @@ impl WeakSignalEngine {
             .await
             .unwrap_or(self.config.default_threshold);
 
-        let scoring_weights = tenant_config_repo
-            .get_scoring_weights(tenant_id)
+        let layers = ConfigLayers::load(&self.db, tenant_id)
             .await
             .unwrap_or_default();
-
-        // Check for webhook configuration
-        let webhook_url = tenant_config_repo
-            .get_webhook_url(tenant_id)
-            .await
-            .ok()
-            .flatten();
+        let scoring_weights = layers.scoring_weights().value;
+
+        // Check for notification targets; deliveries are only sent when they can be signed
+        let notification_urls = layers.notification_urls().value;
+        let signing_secret = if self.config.enable_notifications && !notification_urls.is_empty() {
+            tenant_config_repo
+                .get_or_create_signing_secret(tenant_id)
+                .await
+                .map_err(|e| {
+                    error!(
+                        "Failed to load webhook signing secret for tenant {}: {}",
+                        tenant_id, e
+                    )
+                })
+                .ok()
+        } else {
+            None
+        };
 
         let clusters = self.cluster_signals(signals);
+        let mut created = 0;
 
         for cluster in clusters {
             let grounded_signal = self
@@ -229,6 +331,7 @@ impl WeakSignalEngine {
                 .await?;
 
             if let Some(gs) = grounded_signal {
+                created += 1;
                 info!(
                     "Created grounded signal {} for tenant {} (cluster size {})",
                     gs.id,
@@ -237,25 +340,39 @@ impl WeakSignalEngine {
                 );
 
                 if self.config.enable_notifications
-                    && let Some(ref url) = webhook_url
+                    && let Some(secret) = &signing_secret
                 {
-                    let webhook_url_str: &str = url.as_str();
                     let grounded_signal_ref: &GroundedSignalResponse = &gs;
-                    if let Err(e) = self
-                        .notifier
-                        .send_notification(webhook_url_str, grounded_signal_ref)
-                        .await
-                    {
-                        error!(
-                            "Failed to send notification for grounded signal {}: {}",
-                            gs.id, e
-                        );
+                    let provider = gs
+                        .evidence
+                        .get("source_signal")
+                        .and_then(|s| s.get("provider"))
+                        .and_then(|p| p.as_str())
+                        .unwrap_or("unknown");
+                    for url in &notification_urls {
+                        let result = self
+                            .notifier
+                            .send_notification(url, secret, grounded_signal_ref)
+                            .await;
+                        slo::record(
+                            &self.db,
+                            provider,
+                            SloOperation::Notification,
+                            result.is_ok(),
+                        )
+                        .await;
+                        if let Err(e) = result {
+                            error!(
+                                "Failed to send notification for grounded signal {}: {}",
+                                gs.id, e
+                            );
+                        }
                     }
                 }
             }
         }
 
-        Ok(())
+        Ok(created)
     }
 
     async fn process_signal_cluster(
@@ -1,6 +1,7 @@
 //! # Notification System
 //!
-//! Handles sending notifications when grounded signals are created.
+//! Handles sending notifications when grounded signals are created. Each
+//! attempt is signed with the tenant's secret; see [`crate::webhook_signing`].
 
 use crate::models::GroundedSignalResponse;
 use reqwest::Client;
@@ -10,6 +11,7 @@ use tracing::{error, info, warn};
 use url::Url;
 
 use super::WeakSignalEngineConfig;
+use crate::webhook_signing;
 
 /// Notification system for sending grounded signal alerts
 pub struct Notifier {
@@ -51,10 +53,11 @@ impl Notifier {
         true
     }
 
-    /// Send notification for a grounded signal
+    /// Send notification for a grounded signal, signed with `signing_secret`
     pub async fn send_notification(
         &self,
         webhook_url: &str,
+        signing_secret: &str,
         grounded_signal: &GroundedSignalResponse,
     ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         if !self.validate_webhook_url(webhook_url) {
@@ -67,14 +70,22 @@ impl Notifier {
             self.redacted_target(webhook_url)
         );
 
-        let payload = self.build_webhook_payload(grounded_signal);
+        let body = serde_json::to_vec(&self.build_webhook_payload(grounded_signal))?;
 
         // Implement retry logic with exponential backoff
         let max_retries = 3;
         let mut delay = Duration::from_secs(1);
 
         for attempt in 1..=max_retries {
-            match self.client.post(webhook_url).json(&payload).send().await {
+            match webhook_signing::signed_post(
+                &self.client,
+                webhook_url,
+                signing_secret,
+                body.clone(),
+            )
+            .send()
+            .await
+            {
                 Ok(response) => {
                     if response.status().is_success() {
                         info!(
@@ -3,11 +3,14 @@
 //! Implements the six-dimensional scoring model for evaluating signals and promoting
 //! them to grounded signals.
 
+use crate::clock::{SharedClock, system_clock};
 use crate::models::signal::Model as Signal;
 use crate::models::{ScoringWeights, SignalScores};
 
 /// Signal scorer that applies the six-dimensional scoring model
-pub struct SignalScorer {}
+pub struct SignalScorer {
+    clock: SharedClock,
+}
 
 impl Default for SignalScorer {
     fn default() -> Self {
@@ -18,7 +21,15 @@ impl Default for SignalScorer {
 impl SignalScorer {
     /// Create a new signal scorer
     pub fn new() -> Self {
-        Self {}
+        Self {
+            clock: system_clock(),
+        }
+    }
+
+    /// Replace the time source used to judge signal timeliness
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
     }
 
     /// Score a signal using the six-dimensional model
@@ -151,7 +162,7 @@ impl SignalScorer {
 
     /// Calculate timeliness score - how timely the signal is
     fn calculate_timeliness(&self, signal: &Signal) -> f32 {
-        let now = chrono::Utc::now();
+        let now = self.clock.now();
         let signal_occurred: chrono::DateTime<chrono::Utc> =
             chrono::DateTime::from_naive_utc_and_offset(
                 signal.occurred_at.naive_utc(),
@@ -212,9 +223,21 @@ impl SignalScorer {
             "feature_launch" | "release" => score += 0.2,
             "partnership" | "acquisition" => score += 0.35,
             "customer_issue" | "bug" => score += 0.25,
+            "ci_run_failed" => score += 0.25,
+            "ci_run_succeeded" | "ci_run_canceled" => score += 0.05,
+            "dispute_opened" | "invoice_payment_failed" | "subscription_canceled" => score += 0.3,
+            "subscription_created" | "invoice_paid" => score += 0.2,
             _ => score += 0.1,
         }
 
+        // CI results matter most on the branch that ships
+        if signal.kind.starts_with("ci_run_")
+            && let Some(branch) = signal.payload.get("branch").and_then(|v| v.as_str())
+            && matches!(branch, "main" | "master")
+        {
+            score += 0.15;
+        }
+
         // Scale by audience size if available
         if let Some(audience) = signal.payload.get("audience_size").and_then(|v| v.as_u64()) {
             let audience_score = (audience as f32 / 10000.0).min(0.3); // Cap at 0.3
@@ -277,6 +300,8 @@ impl SignalScorer {
         // Credibility by provider
         match signal.provider_slug.as_str() {
             "github" | "gitlab" => score += 0.3, // High credibility (code changes)
+            "circleci" => score += 0.3,          // High credibility (build results)
+            "stripe" => score += 0.3,            // High credibility (billing records)
             "jira" | "asana" => score += 0.25,   // High credibility (official work tracking)
             "gmail" | "outlook" => score += 0.2, // Medium credibility (email)
             "slack" | "teams" => score += 0.15,  // Medium credibility (chat)
@@ -465,4 +490,69 @@ mod tests {
 
         assert!((scores.total - expected_total).abs() < 0.001);
     }
+
+    fn ci_signal(kind: &str, branch: &str) -> Signal {
+        let now = Utc::now().into();
+        Signal {
+            id: Uuid::new_v4(),
+            tenant_id: Uuid::new_v4(),
+            provider_slug: "circleci".to_string(),
+            connection_id: Uuid::new_v4(),
+            kind: kind.to_string(),
+            occurred_at: now,
+            received_at: now,
+            payload: serde_json::json!({"branch": branch, "workflow_name": "build-and-test"}),
+            dedupe_key: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_ci_failure_on_default_branch_scores_highest_impact() {
+        let scorer = SignalScorer::new();
+        let content = "build-and-test";
+
+        let main_failure = scorer
+            .calculate_impact(&ci_signal("ci_run_failed", "main"), content)
+            .await;
+        let branch_failure = scorer
+            .calculate_impact(&ci_signal("ci_run_failed", "feature/retry"), content)
+            .await;
+        let main_success = scorer
+            .calculate_impact(&ci_signal("ci_run_succeeded", "main"), content)
+            .await;
+
+        assert!(main_failure > branch_failure);
+        assert!(branch_failure > main_success);
+    }
+
+    #[tokio::test]
+    async fn test_revenue_loss_outranks_routine_billing_events() {
+        let scorer = SignalScorer::new();
+        let stripe_signal = |kind: &str| Signal {
+            provider_slug: "stripe".to_string(),
+            payload: serde_json::json!({"customer": "cus_1", "currency": "usd"}),
+            ..ci_signal(kind, "main")
+        };
+
+        let dispute = scorer
+            .calculate_impact(&stripe_signal("dispute_opened"), "")
+            .await;
+        let paid = scorer
+            .calculate_impact(&stripe_signal("invoice_paid"), "")
+            .await;
+        let updated = scorer
+            .calculate_impact(&stripe_signal("subscription_updated"), "")
+            .await;
+
+        assert!(dispute > paid);
+        assert!(paid > updated);
+        assert!(
+            scorer
+                .calculate_credibility(&stripe_signal("invoice_paid"))
+                .await
+                > 0.7
+        );
+    }
 }
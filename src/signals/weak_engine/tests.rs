@@ -33,7 +33,7 @@ async fn test_weak_signal_engine_end_to_end() {
     };
 
     let db = Arc::new(init_pool(&config).await.expect("Failed to init test DB"));
-    if !table_exists(&*db, "grounded_signals").await {
+    if !table_exists(&db, "grounded_signals").await {
         return;
     }
 
@@ -102,7 +102,7 @@ async fn test_weak_signal_engine_end_to_end() {
 
     // Verify that a grounded signal was created
     use crate::repositories::GroundedSignalRepository;
-    let grounded_repo = GroundedSignalRepository::new(&*db);
+    let grounded_repo = GroundedSignalRepository::new(&db);
 
     let grounded_signals = grounded_repo
         .list(crate::repositories::ListGroundedSignalsQuery {
@@ -165,7 +165,7 @@ async fn test_weak_signal_engine_below_threshold() {
     };
 
     let db = Arc::new(init_pool(&config).await.expect("Failed to init test DB"));
-    if !table_exists(&*db, "grounded_signals").await {
+    if !table_exists(&db, "grounded_signals").await {
         return;
     }
 
@@ -233,7 +233,7 @@ async fn test_weak_signal_engine_below_threshold() {
 
     // Verify that no grounded signal was created
     use crate::repositories::GroundedSignalRepository;
-    let grounded_repo = GroundedSignalRepository::new(&*db);
+    let grounded_repo = GroundedSignalRepository::new(&db);
 
     let grounded_signals = grounded_repo
         .list(crate::repositories::ListGroundedSignalsQuery {
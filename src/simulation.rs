@@ -0,0 +1,544 @@
+//! # Capacity Simulation
+//!
+//! Replays the scheduler's cadence rules and the sync executor's claim loop over
+//! a window of virtual time, starting from the connections and jobs currently in
+//! the database. Connectors are stubbed with a fixed run duration and time is
+//! driven by a [`MockClock`], so a day of scheduling is simulated in
+//! milliseconds. Nothing is written back to the database.
+//!
+//! The resulting [`SimulationReport`] lists when each connection would sync and
+//! how the job queue evolves over time, for capacity planning.
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::sync::Arc;
+
+use chrono::{DateTime, Duration, Utc};
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use sea_orm::{ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter, QueryOrder};
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::clock::{Clock, MockClock};
+use crate::config::SchedulerConfig;
+use crate::models::connection::{Column as ConnectionColumn, Entity as Connection};
+use crate::models::sync_job::{Column as SyncJobColumn, Entity as SyncJob};
+use crate::repositories::sync_metadata::ConnectionSyncMetadata;
+use crate::scheduler::{DEFAULT_BATCH_SIZE, candidate_sort_key, compute_due_times};
+use crate::sync_executor::ExecutorConfig;
+
+/// Priority the scheduler assigns to interval jobs
+const INCREMENTAL_PRIORITY: i16 = 30;
+
+/// Parameters for a simulation run
+#[derive(Debug, Clone)]
+pub struct SimulationConfig {
+    /// Length of the simulated window
+    pub duration: Duration,
+    /// How long each stubbed connector run takes
+    pub job_duration: Duration,
+    /// Width of the queue depth report buckets
+    pub bucket: Duration,
+    /// Seed for scheduler jitter, so repeated runs produce the same report
+    pub seed: u64,
+    /// Scheduler settings to simulate
+    pub scheduler: SchedulerConfig,
+    /// Executor settings to simulate
+    pub executor: ExecutorConfig,
+}
+
+/// Forecast for a single connection
+#[derive(Debug, Clone, Serialize)]
+pub struct ConnectionForecast {
+    pub connection_id: Uuid,
+    pub provider_slug: String,
+    pub interval_seconds: u64,
+    /// When the executor would start a sync for this connection
+    pub sync_starts: Vec<DateTime<Utc>>,
+}
+
+/// Queue activity within one report bucket
+#[derive(Debug, Clone, Serialize)]
+pub struct QueueDepthSample {
+    pub bucket_start: DateTime<Utc>,
+    /// Jobs the scheduler enqueued during the bucket
+    pub enqueued: u64,
+    /// Jobs the executor started during the bucket
+    pub started: u64,
+    /// Largest number of queued jobs observed at an executor claim
+    pub peak_queued: u64,
+    /// Longest delay between a job's scheduled time and its start
+    pub max_wait_seconds: i64,
+}
+
+/// Outcome of a simulation run
+#[derive(Debug, Clone, Serialize)]
+pub struct SimulationReport {
+    pub started_at: DateTime<Utc>,
+    pub ended_at: DateTime<Utc>,
+    pub jobs_enqueued: u64,
+    pub jobs_started: u64,
+    /// Jobs still queued when the window closed
+    pub jobs_queued_at_end: u64,
+    pub peak_queued: u64,
+    pub max_wait_seconds: i64,
+    pub connections: Vec<ConnectionForecast>,
+    pub queue_depth: Vec<QueueDepthSample>,
+}
+
+/// In-memory stand-in for a connection row and its scheduling metadata
+struct SimConnection {
+    created_at: DateTime<Utc>,
+    metadata: ConnectionSyncMetadata,
+    last_finished: Option<DateTime<Utc>>,
+    /// Whether a queued or running incremental job exists
+    incremental_pending: bool,
+    forecast: ConnectionForecast,
+}
+
+/// In-memory stand-in for a queued sync job
+struct SimJob {
+    connection: usize,
+    incremental: bool,
+    priority: i16,
+    scheduled_at: DateTime<Utc>,
+}
+
+/// Simulation state advanced by scheduler and executor ticks
+struct Simulation {
+    config: SimulationConfig,
+    clock: Arc<MockClock>,
+    started_at: DateTime<Utc>,
+    ended_at: DateTime<Utc>,
+    rng: StdRng,
+    connections: Vec<SimConnection>,
+    queue: Vec<SimJob>,
+    /// (finish time, connection, incremental) for jobs currently running
+    running: BinaryHeap<Reverse<(DateTime<Utc>, usize, bool)>>,
+    buckets: Vec<QueueDepthSample>,
+    jobs_enqueued: u64,
+    jobs_started: u64,
+}
+
+/// Run a simulation starting at `start` against the current database contents.
+///
+/// Only active connections are simulated. Existing queued jobs are carried into
+/// the simulated queue and running jobs are assumed to finish one
+/// `job_duration` after `start`.
+pub async fn simulate(
+    db: &DatabaseConnection,
+    config: SimulationConfig,
+    start: DateTime<Utc>,
+) -> Result<SimulationReport, DbErr> {
+    let mut simulation = Simulation::load(db, config, start).await?;
+    simulation.run();
+    Ok(simulation.into_report())
+}
+
+impl Simulation {
+    async fn load(
+        db: &DatabaseConnection,
+        config: SimulationConfig,
+        start: DateTime<Utc>,
+    ) -> Result<Self, DbErr> {
+        let rows = Connection::find()
+            .filter(ConnectionColumn::Status.eq("active"))
+            .order_by_asc(ConnectionColumn::CreatedAt)
+            .all(db)
+            .await?;
+
+        let mut connections = Vec::with_capacity(rows.len());
+        for row in &rows {
+            let mut metadata =
+                ConnectionSyncMetadata::from_connection_metadata(row.metadata.as_ref());
+            metadata.sanitize_interval(&config.scheduler);
+            let created_at = row.created_at.with_timezone(&Utc);
+            metadata.first_activated_at.get_or_insert(created_at);
+
+            let last_finished = SyncJob::find()
+                .filter(SyncJobColumn::ConnectionId.eq(row.id))
+                .filter(SyncJobColumn::JobType.eq("incremental"))
+                .filter(SyncJobColumn::Status.eq("succeeded"))
+                .order_by_desc(SyncJobColumn::FinishedAt)
+                .one(db)
+                .await?
+                .and_then(|job| job.finished_at)
+                .map(|at| at.with_timezone(&Utc));
+
+            connections.push(SimConnection {
+                created_at,
+                last_finished,
+                incremental_pending: false,
+                forecast: ConnectionForecast {
+                    connection_id: row.id,
+                    provider_slug: row.provider_slug.clone(),
+                    interval_seconds: metadata
+                        .effective_interval_seconds(&config.scheduler, &row.provider_slug),
+                    sync_starts: Vec::new(),
+                },
+                metadata,
+            });
+        }
+
+        let mut simulation = Self {
+            clock: MockClock::new(start),
+            started_at: start,
+            ended_at: start + config.duration,
+            rng: StdRng::seed_from_u64(config.seed),
+            connections,
+            queue: Vec::new(),
+            running: BinaryHeap::new(),
+            buckets: Vec::new(),
+            jobs_enqueued: 0,
+            jobs_started: 0,
+            config,
+        };
+
+        let pending = SyncJob::find()
+            .filter(SyncJobColumn::Status.is_in(["queued", "running"]))
+            .all(db)
+            .await?;
+        for job in pending {
+            let Some(index) = rows.iter().position(|row| row.id == job.connection_id) else {
+                continue;
+            };
+            let incremental = job.job_type == "incremental";
+            if incremental {
+                simulation.connections[index].incremental_pending = true;
+            }
+            if job.status == "running" {
+                let finish = start + simulation.config.job_duration;
+                simulation
+                    .running
+                    .push(Reverse((finish, index, incremental)));
+            } else {
+                let scheduled_at = job.scheduled_at.with_timezone(&Utc);
+                let retry_after = job.retry_after.map(|at| at.with_timezone(&Utc));
+                simulation.queue.push(SimJob {
+                    connection: index,
+                    incremental,
+                    priority: job.priority,
+                    scheduled_at: retry_after.map_or(scheduled_at, |at| at.max(scheduled_at)),
+                });
+            }
+        }
+
+        Ok(simulation)
+    }
+
+    fn run(&mut self) {
+        let start = self.started_at;
+        let end = self.ended_at;
+        let scheduler_tick = Duration::seconds(self.config.scheduler.tick_interval_seconds as i64);
+        let executor_tick = Duration::milliseconds(self.config.executor.tick_ms as i64);
+
+        // Both services sleep before their first scheduler tick; the executor claims immediately
+        let mut next_scheduler_tick = start + scheduler_tick;
+        let mut next_executor_tick = start;
+
+        loop {
+            let next = next_executor_tick.min(next_scheduler_tick);
+            if next >= end {
+                break;
+            }
+            self.clock.set(next);
+            self.finish_jobs();
+
+            if next_executor_tick <= next_scheduler_tick {
+                let busy_until = self.executor_tick();
+                next_executor_tick = (next + executor_tick).max(busy_until);
+            } else {
+                self.scheduler_tick();
+                next_scheduler_tick = next + scheduler_tick;
+            }
+        }
+        self.clock.set(end);
+        self.finish_jobs();
+    }
+
+    /// Complete stubbed connector runs whose finish time has passed
+    fn finish_jobs(&mut self) {
+        let now = self.clock.now();
+        while let Some(Reverse((finish, index, incremental))) = self.running.peek().copied() {
+            if finish > now {
+                break;
+            }
+            self.running.pop();
+            if incremental {
+                let connection = &mut self.connections[index];
+                connection.incremental_pending = false;
+                connection.last_finished = Some(finish);
+            }
+        }
+    }
+
+    /// Mirror of the scheduler tick: enqueue an interval job for each due connection
+    fn scheduler_tick(&mut self) {
+        let now = self.clock.now();
+
+        let mut candidates: Vec<usize> = (0..self.connections.len())
+            .take(DEFAULT_BATCH_SIZE.saturating_mul(4))
+            .collect();
+        candidates.sort_by_key(|&index| {
+            let connection = &self.connections[index];
+            candidate_sort_key(&connection.metadata, connection.created_at)
+        });
+        candidates.truncate(DEFAULT_BATCH_SIZE);
+
+        for index in candidates {
+            let connection = &mut self.connections[index];
+            if connection.incremental_pending {
+                continue;
+            }
+
+            let base_interval = connection.metadata.effective_interval_seconds(
+                &self.config.scheduler,
+                &connection.forecast.provider_slug,
+            );
+            let due = compute_due_times(
+                &connection.metadata,
+                base_interval,
+                connection.last_finished,
+                connection
+                    .metadata
+                    .first_activated_at
+                    .unwrap_or(connection.created_at),
+                now,
+            );
+            if now < due.job_due {
+                continue;
+            }
+
+            let jitter_seconds = self
+                .config
+                .scheduler
+                .jitter_range()
+                .offset_seconds_with(base_interval, &mut self.rng);
+            connection.metadata.next_run_at = Some(due.next_run_at);
+            connection.metadata.last_jitter_seconds = Some(jitter_seconds);
+            connection.incremental_pending = true;
+
+            self.queue.push(SimJob {
+                connection: index,
+                incremental: true,
+                priority: INCREMENTAL_PRIORITY,
+                scheduled_at: due.job_due + Duration::seconds(jitter_seconds as i64),
+            });
+            self.jobs_enqueued += 1;
+            self.bucket(now).enqueued += 1;
+        }
+    }
+
+    /// Mirror of the executor loop: claim a batch no larger than the concurrency,
+    /// run it, and return when the batch would finish
+    fn executor_tick(&mut self) -> DateTime<Utc> {
+        let now = self.clock.now();
+        let queued = self.queue.len() as u64;
+        let bucket = self.bucket(now);
+        bucket.peak_queued = bucket.peak_queued.max(queued);
+
+        let running_connections: Vec<usize> = self
+            .running
+            .iter()
+            .map(|Reverse((_, index, _))| *index)
+            .collect();
+        let mut eligible: Vec<usize> = (0..self.queue.len())
+            .filter(|&i| {
+                let job = &self.queue[i];
+                job.scheduled_at <= now && !running_connections.contains(&job.connection)
+            })
+            .collect();
+        eligible.sort_by_key(|&i| (Reverse(self.queue[i].priority), self.queue[i].scheduled_at));
+        let concurrency = self.config.executor.concurrency.max(1);
+        eligible.truncate(self.config.executor.claim_batch.min(concurrency));
+        if eligible.is_empty() {
+            return now;
+        }
+
+        let mut slots: Vec<Option<SimJob>> = std::mem::take(&mut self.queue)
+            .into_iter()
+            .map(Some)
+            .collect();
+        let claimed: Vec<SimJob> = eligible.iter().filter_map(|&i| slots[i].take()).collect();
+        self.queue = slots.into_iter().flatten().collect();
+
+        let started = now;
+        let finished = started + self.config.job_duration;
+        for job in claimed {
+            self.running
+                .push(Reverse((finished, job.connection, job.incremental)));
+            if started >= self.ended_at {
+                continue;
+            }
+
+            let wait = (started - job.scheduled_at).num_seconds().max(0);
+            let bucket = self.bucket(started);
+            bucket.started += 1;
+            bucket.max_wait_seconds = bucket.max_wait_seconds.max(wait);
+
+            self.connections[job.connection]
+                .forecast
+                .sync_starts
+                .push(started);
+            self.jobs_started += 1;
+        }
+
+        // The executor waits for the whole batch before its next claim
+        finished
+    }
+
+    /// Report bucket covering `at`, creating empty buckets up to it
+    fn bucket(&mut self, at: DateTime<Utc>) -> &mut QueueDepthSample {
+        let start = self.started_at;
+        let width = self.config.bucket.num_seconds().max(1);
+        let index = ((at - start).num_seconds().max(0) / width) as usize;
+        while self.buckets.len() <= index {
+            let bucket_start = start + Duration::seconds(width * self.buckets.len() as i64);
+            self.buckets.push(QueueDepthSample {
+                bucket_start,
+                enqueued: 0,
+                started: 0,
+                peak_queued: 0,
+                max_wait_seconds: 0,
+            });
+        }
+        &mut self.buckets[index]
+    }
+
+    fn into_report(self) -> SimulationReport {
+        SimulationReport {
+            started_at: self.started_at,
+            ended_at: self.ended_at,
+            jobs_enqueued: self.jobs_enqueued,
+            jobs_started: self.jobs_started,
+            jobs_queued_at_end: self.queue.len() as u64,
+            peak_queued: self
+                .buckets
+                .iter()
+                .map(|bucket| bucket.peak_queued)
+                .max()
+                .unwrap_or(0),
+            max_wait_seconds: self
+                .buckets
+                .iter()
+                .map(|bucket| bucket.max_wait_seconds)
+                .max()
+                .unwrap_or(0),
+            connections: self
+                .connections
+                .into_iter()
+                .map(|connection| connection.forecast)
+                .collect(),
+            queue_depth: self.buckets,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use migration::{Migrator, MigratorTrait};
+    use sea_orm::{ConnectionTrait, Database, PaginatorTrait, Statement, Value};
+    use serde_json::Value as JsonValue;
+
+    async fn insert_connection(
+        db: &DatabaseConnection,
+        tenant_id: Uuid,
+        metadata: JsonValue,
+    ) -> Uuid {
+        let connection_id = Uuid::new_v4();
+        db.execute(Statement::from_sql_and_values(
+            db.get_database_backend(),
+            "INSERT INTO connections (id, tenant_id, provider_slug, external_id, status, metadata) \
+             VALUES (?, ?, ?, ?, ?, ?)",
+            vec![
+                Value::from(connection_id),
+                Value::from(tenant_id),
+                Value::from("github"),
+                Value::from(connection_id.to_string()),
+                Value::from("active"),
+                Value::from(metadata.to_string()),
+            ],
+        ))
+        .await
+        .expect("insert connection");
+        connection_id
+    }
+
+    #[tokio::test]
+    async fn test_simulation_forecasts_interval_syncs_without_writing() {
+        let db = Database::connect("sqlite::memory:")
+            .await
+            .expect("create in-memory db");
+        Migrator::up(&db, None).await.expect("apply migrations");
+        let backend = db.get_database_backend();
+
+        let tenant_id = Uuid::new_v4();
+        db.execute(Statement::from_sql_and_values(
+            backend,
+            "INSERT INTO tenants (id, name) VALUES (?, ?)",
+            vec![tenant_id.into(), "Test Tenant".into()],
+        ))
+        .await
+        .expect("insert tenant");
+        db.execute(Statement::from_sql_and_values(
+            backend,
+            "INSERT INTO providers (slug, display_name, auth_type) VALUES (?, ?, ?)",
+            vec!["github".into(), "GitHub".into(), "oauth2".into()],
+        ))
+        .await
+        .expect("insert provider");
+
+        let start = Utc.with_ymd_and_hms(2024, 1, 15, 10, 0, 0).unwrap();
+        let activated = (start - Duration::hours(1)).to_rfc3339();
+        let fast = insert_connection(
+            &db,
+            tenant_id,
+            serde_json::json!({ "sync": { "first_activated_at": activated } }),
+        )
+        .await;
+        let hourly = insert_connection(
+            &db,
+            tenant_id,
+            serde_json::json!({ "sync": { "first_activated_at": activated, "interval_seconds": 3600 } }),
+        )
+        .await;
+
+        let mut scheduler = crate::config::AppConfig::default().scheduler;
+        scheduler.jitter_pct_min = 0.0;
+        scheduler.jitter_pct_max = 0.0;
+        let config = SimulationConfig {
+            duration: Duration::hours(3),
+            job_duration: Duration::seconds(30),
+            bucket: Duration::hours(1),
+            seed: 0,
+            scheduler,
+            executor: ExecutorConfig::default(),
+        };
+
+        let report = simulate(&db, config, start).await.unwrap();
+
+        let forecast = |id: Uuid| {
+            report
+                .connections
+                .iter()
+                .find(|connection| connection.connection_id == id)
+                .unwrap()
+        };
+        // First scheduler tick runs at 10:01; the executor claims on its next 5s tick
+        assert_eq!(
+            forecast(fast).sync_starts.first(),
+            Some(&(start + Duration::seconds(65)))
+        );
+        assert_eq!(forecast(fast).sync_starts.len(), 12);
+        assert_eq!(forecast(hourly).interval_seconds, 3600);
+        assert_eq!(forecast(hourly).sync_starts.len(), 3);
+        assert_eq!(report.jobs_enqueued, 15);
+        assert_eq!(report.queue_depth.len(), 3);
+        assert_eq!(report.jobs_queued_at_end, 0);
+
+        assert_eq!(SyncJob::find().count(&db).await.unwrap(), 0);
+    }
+}
@@ -0,0 +1,215 @@
+//! Service level objective tracking and error budgets.
+//!
+//! Sync jobs, webhook jobs and tenant notifications report each attempt's
+//! outcome per provider through [`record`], which increments hourly counters in
+//! the slo_outcomes table. [`report`] sums the counters over the configured
+//! window and compares each provider's success rate with its target to derive
+//! how much of the error budget is left.
+
+use chrono::{Duration, Utc};
+use sea_orm::DatabaseConnection;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::config::SloConfig;
+use crate::error::RepositoryError;
+use crate::repositories::{SloOutcomeRepository, SloOutcomeTotals};
+
+/// Longest window a report can cover; older counters are pruned
+pub const MAX_WINDOW_HOURS: u64 = 720;
+
+/// Operation whose success rate is tracked
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SloOperation {
+    /// A scheduled or manual sync job attempt
+    Sync,
+    /// A webhook job attempt
+    Webhook,
+    /// A delivery to a tenant's notification webhook
+    Notification,
+}
+
+impl SloOperation {
+    /// Operation a sync job attempt counts towards, based on its `job_type`
+    pub fn for_job_type(job_type: &str) -> Self {
+        if job_type == "webhook" {
+            Self::Webhook
+        } else {
+            Self::Sync
+        }
+    }
+
+    /// Value stored in the `operation` column
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Sync => "sync",
+            Self::Webhook => "webhook",
+            Self::Notification => "notification",
+        }
+    }
+
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "sync" => Some(Self::Sync),
+            "webhook" => Some(Self::Webhook),
+            "notification" => Some(Self::Notification),
+            _ => None,
+        }
+    }
+}
+
+/// Success rate and error budget of one provider operation over the window
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SloStatus {
+    /// Provider slug
+    #[schema(example = "github")]
+    pub provider: String,
+    /// Tracked operation
+    pub operation: SloOperation,
+    /// Target success rate
+    #[schema(example = 0.99)]
+    pub target: f64,
+    /// Attempts within the window
+    #[schema(example = 1200)]
+    pub total: i64,
+    /// Failed attempts within the window
+    #[schema(example = 6)]
+    pub failed: i64,
+    /// Observed success rate
+    #[schema(example = 0.995)]
+    pub success_rate: f64,
+    /// Failures the target allows for this many attempts
+    #[schema(example = 12.0)]
+    pub allowed_failures: f64,
+    /// Share of the error budget left; negative once the budget is overspent
+    #[schema(example = 0.5)]
+    pub error_budget_remaining: f64,
+    /// Whether failures exceed what the target allows
+    #[schema(example = false)]
+    pub budget_exhausted: bool,
+}
+
+impl SloStatus {
+    fn from_totals(totals: SloOutcomeTotals, operation: SloOperation, config: &SloConfig) -> Self {
+        let target = config.target_for(&totals.provider_slug);
+        let allowed_failures = (1.0 - target) * totals.total as f64;
+        let failed = totals.failed as f64;
+        Self {
+            target,
+            operation,
+            total: totals.total,
+            failed: totals.failed,
+            success_rate: 1.0 - failed / totals.total as f64,
+            allowed_failures,
+            error_budget_remaining: 1.0 - failed / allowed_failures,
+            budget_exhausted: failed > allowed_failures,
+            provider: totals.provider_slug,
+        }
+    }
+}
+
+/// Records the outcome of one attempt of `operation` for `provider`.
+///
+/// Tracking is best effort: a failed write is logged and never affects the
+/// operation being measured.
+pub async fn record(
+    db: &DatabaseConnection,
+    provider: &str,
+    operation: SloOperation,
+    success: bool,
+) {
+    if let Err(err) = SloOutcomeRepository::new(db.clone())
+        .record(provider, operation.as_str(), success, Utc::now())
+        .await
+    {
+        tracing::warn!(
+            provider,
+            operation = operation.as_str(),
+            error = %err,
+            "Failed to record SLO outcome"
+        );
+    }
+}
+
+/// Success rates and error budgets over the configured window, ordered by provider and operation.
+pub async fn report(
+    db: &DatabaseConnection,
+    config: &SloConfig,
+) -> Result<Vec<SloStatus>, RepositoryError> {
+    // The current hour counts as the window's last hour
+    let since = Utc::now() - Duration::hours(config.window_hours as i64 - 1);
+    let totals = SloOutcomeRepository::new(db.clone())
+        .totals_since(since)
+        .await?;
+    Ok(statuses(totals, config))
+}
+
+/// Deletes counters older than [`MAX_WINDOW_HOURS`], returning the rows removed.
+pub async fn prune(db: &DatabaseConnection) -> Result<u64, RepositoryError> {
+    SloOutcomeRepository::new(db.clone())
+        .prune_before(Utc::now() - Duration::hours(MAX_WINDOW_HOURS as i64))
+        .await
+}
+
+fn statuses(totals: Vec<SloOutcomeTotals>, config: &SloConfig) -> Vec<SloStatus> {
+    totals
+        .into_iter()
+        .filter(|totals| totals.total > 0)
+        .filter_map(|totals| {
+            let operation = SloOperation::parse(&totals.operation)?;
+            Some(SloStatus::from_totals(totals, operation, config))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    fn totals(provider: &str, operation: &str, total: i64, failed: i64) -> SloOutcomeTotals {
+        SloOutcomeTotals {
+            provider_slug: provider.to_string(),
+            operation: operation.to_string(),
+            total,
+            failed,
+        }
+    }
+
+    #[test]
+    fn statuses_apply_provider_targets_and_budget() {
+        let config = SloConfig {
+            default_target: 0.9,
+            provider_targets: BTreeMap::from([("jira".to_string(), 0.5)]),
+            window_hours: 24,
+        };
+
+        let report = statuses(
+            vec![
+                totals("github", "sync", 20, 1),
+                totals("github", "webhook", 3, 3),
+                totals("github", "retired", 5, 0),
+                totals("jira", "notification", 1, 0),
+            ],
+            &config,
+        );
+        assert_eq!(report.len(), 3);
+
+        let sync = &report[0];
+        assert_eq!(sync.operation, SloOperation::Sync);
+        assert!((sync.success_rate - 0.95).abs() < 1e-9);
+        assert!((sync.allowed_failures - 2.0).abs() < 1e-9);
+        assert!((sync.error_budget_remaining - 0.5).abs() < 1e-9);
+        assert!(!sync.budget_exhausted);
+
+        let webhook = &report[1];
+        assert!(webhook.budget_exhausted);
+        assert!(webhook.error_budget_remaining < 0.0);
+
+        let notification = &report[2];
+        assert_eq!(notification.provider, "jira");
+        assert_eq!(notification.target, 0.5);
+        assert_eq!(notification.error_budget_remaining, 1.0);
+    }
+}
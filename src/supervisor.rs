@@ -0,0 +1,676 @@
+//! # Subsystem Supervisor
+//!
+//! Starts the service's subsystems in dependency order and keeps them running.
+//! A subsystem is either a one-shot *init step* (database checks, registry
+//! setup), which must finish before anything depending on it starts, or a
+//! long-running *service* (scheduler, executor, API server), which runs until
+//! shutdown. Services that panic are restarted when their [`RestartPolicy`]
+//! allows it; a service that fails, exits early or runs out of restarts stops
+//! every other subsystem.
+//!
+//! Each subsystem's state is published to a [`HealthRegistry`], which
+//! `/readyz` consults so the service only reports ready once every subsystem
+//! is up. When startup completes, a single [`StartupReport`] is logged.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, LazyLock, RwLock};
+use std::time::{Duration, Instant};
+
+use metrics::counter;
+use serde::Serialize;
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+use tracing::{error, info, warn};
+
+/// Error returned by subsystems
+pub type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+type BoxFuture = Pin<Box<dyn Future<Output = Result<(), BoxError>> + Send>>;
+type InitFn = Box<dyn FnOnce() -> BoxFuture + Send>;
+type ServiceFn = Arc<dyn Fn(CancellationToken) -> BoxFuture + Send + Sync>;
+
+static HEALTH: LazyLock<Arc<HealthRegistry>> = LazyLock::new(Arc::default);
+
+/// Process-wide health registry consulted by `/readyz`
+pub fn health() -> Arc<HealthRegistry> {
+    HEALTH.clone()
+}
+
+/// Lifecycle state of a subsystem
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SubsystemState {
+    /// Waiting for its dependencies
+    Pending,
+    /// Init step or service start in progress
+    Starting,
+    /// Init step completed
+    Ready,
+    /// Service running
+    Running,
+    /// Service panicked and is waiting to be restarted
+    Restarting,
+    /// Init step or service failed
+    Failed,
+    /// Service stopped after shutdown
+    Stopped,
+}
+
+impl SubsystemState {
+    /// Whether the subsystem counts as up for readiness
+    pub fn is_up(self) -> bool {
+        matches!(self, SubsystemState::Ready | SubsystemState::Running)
+    }
+}
+
+/// Current state of every registered subsystem
+#[derive(Debug, Default)]
+pub struct HealthRegistry {
+    states: RwLock<BTreeMap<String, SubsystemState>>,
+}
+
+impl HealthRegistry {
+    /// Record a subsystem's state
+    pub fn set(&self, name: &str, state: SubsystemState) {
+        let mut states = self.states.write().unwrap_or_else(|e| e.into_inner());
+        states.insert(name.to_string(), state);
+    }
+
+    /// State of every subsystem by name
+    pub fn snapshot(&self) -> BTreeMap<String, SubsystemState> {
+        self.states
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// Whether no subsystem has been registered, as in tests that build the app directly
+    pub fn is_empty(&self) -> bool {
+        self.states
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .is_empty()
+    }
+
+    /// Whether every registered subsystem is up
+    pub fn is_ready(&self) -> bool {
+        self.states
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .values()
+            .all(|state| state.is_up())
+    }
+}
+
+/// What to do when a service panics
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RestartPolicy {
+    /// Treat a panic as a failure and stop the process
+    Never,
+    /// Restart after `backoff`, up to `max_restarts` times over the process lifetime
+    OnPanic {
+        max_restarts: u32,
+        backoff: Duration,
+    },
+}
+
+impl RestartPolicy {
+    /// Restart policy used for background services
+    pub const fn background() -> Self {
+        RestartPolicy::OnPanic {
+            max_restarts: 5,
+            backoff: Duration::from_secs(5),
+        }
+    }
+}
+
+enum Work {
+    Init(InitFn),
+    Service {
+        run: ServiceFn,
+        restart: RestartPolicy,
+    },
+}
+
+/// A unit the supervisor starts
+pub struct Subsystem {
+    name: &'static str,
+    depends_on: Vec<&'static str>,
+    work: Work,
+}
+
+impl Subsystem {
+    /// One-shot step that must succeed before its dependents start
+    pub fn init<F, Fut>(name: &'static str, step: F) -> Self
+    where
+        F: FnOnce() -> Fut + Send + 'static,
+        Fut: Future<Output = Result<(), BoxError>> + Send + 'static,
+    {
+        Self {
+            name,
+            depends_on: Vec::new(),
+            work: Work::Init(Box::new(move || Box::pin(step()))),
+        }
+    }
+
+    /// Long-running task that runs until the shutdown token is cancelled
+    ///
+    /// `run` is called again for every restart, so it builds the task from
+    /// shared state rather than consuming it.
+    pub fn service<F, Fut>(name: &'static str, run: F) -> Self
+    where
+        F: Fn(CancellationToken) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<(), BoxError>> + Send + 'static,
+    {
+        Self {
+            name,
+            depends_on: Vec::new(),
+            work: Work::Service {
+                run: Arc::new(move |shutdown| Box::pin(run(shutdown))),
+                restart: RestartPolicy::Never,
+            },
+        }
+    }
+
+    /// Subsystems that must be up before this one starts
+    pub fn depends_on(mut self, names: &[&'static str]) -> Self {
+        self.depends_on.extend_from_slice(names);
+        self
+    }
+
+    /// Restart policy of a service; ignored for init steps
+    pub fn with_restart(mut self, policy: RestartPolicy) -> Self {
+        if let Work::Service { restart, .. } = &mut self.work {
+            *restart = policy;
+        }
+        self
+    }
+
+    fn kind(&self) -> SubsystemKind {
+        match self.work {
+            Work::Init(_) => SubsystemKind::Init,
+            Work::Service { .. } => SubsystemKind::Service,
+        }
+    }
+}
+
+/// Whether a subsystem is an init step or a service
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SubsystemKind {
+    Init,
+    Service,
+}
+
+/// Outcome of starting one subsystem
+#[derive(Debug, Clone, Serialize)]
+pub struct SubsystemReport {
+    pub name: String,
+    pub kind: SubsystemKind,
+    pub depends_on: Vec<String>,
+    pub state: SubsystemState,
+    /// Time spent in an init step; services report the time until they were spawned
+    pub elapsed_ms: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Summary of a startup, logged once when it completes or fails
+#[derive(Debug, Clone, Serialize)]
+pub struct StartupReport {
+    /// Subsystems in the order they were started
+    pub subsystems: Vec<SubsystemReport>,
+    pub elapsed_ms: u64,
+    pub ok: bool,
+}
+
+/// Why the supervisor stopped
+#[derive(Debug, thiserror::Error)]
+pub enum SupervisorError {
+    #[error("subsystem '{subsystem}' depends on unknown subsystem '{dependency}'")]
+    UnknownDependency {
+        subsystem: String,
+        dependency: String,
+    },
+    #[error("subsystem '{0}' is registered twice")]
+    DuplicateSubsystem(String),
+    #[error("subsystem dependencies form a cycle through: {}", .0.join(", "))]
+    DependencyCycle(Vec<String>),
+    #[error("subsystem '{subsystem}' failed to start: {reason}")]
+    StartupFailed { subsystem: String, reason: String },
+    #[error("subsystem '{subsystem}' failed: {reason}")]
+    SubsystemFailed { subsystem: String, reason: String },
+}
+
+/// Starts subsystems in dependency order
+pub struct Supervisor {
+    subsystems: Vec<Subsystem>,
+    health: Arc<HealthRegistry>,
+    shutdown: CancellationToken,
+}
+
+impl Supervisor {
+    /// Create a supervisor publishing to the process-wide health registry
+    pub fn new(shutdown: CancellationToken) -> Self {
+        Self::with_health(shutdown, health())
+    }
+
+    /// Create a supervisor publishing to the given health registry
+    pub fn with_health(shutdown: CancellationToken, health: Arc<HealthRegistry>) -> Self {
+        Self {
+            subsystems: Vec::new(),
+            health,
+            shutdown,
+        }
+    }
+
+    /// Add a subsystem
+    pub fn with_subsystem(mut self, subsystem: Subsystem) -> Self {
+        self.subsystems.push(subsystem);
+        self
+    }
+
+    /// Run init steps and spawn services in dependency order
+    ///
+    /// Stops at the first init step that fails, cancelling the shutdown token
+    /// so services already spawned stop too. The startup report is logged
+    /// either way.
+    pub async fn start(self) -> Result<RunningSupervisor, SupervisorError> {
+        let started = Instant::now();
+        let order = startup_order(&self.subsystems)?;
+        for subsystem in &self.subsystems {
+            self.health.set(subsystem.name, SubsystemState::Pending);
+        }
+
+        let mut slots: Vec<Option<Subsystem>> = self.subsystems.into_iter().map(Some).collect();
+        let mut reports = Vec::with_capacity(slots.len());
+        let mut services = Vec::new();
+        let mut failure = None;
+
+        for index in order {
+            let subsystem = slots[index].take().expect("each subsystem starts once");
+            let step_started = Instant::now();
+            let mut report = SubsystemReport {
+                name: subsystem.name.to_string(),
+                kind: subsystem.kind(),
+                depends_on: subsystem.depends_on.iter().map(|s| s.to_string()).collect(),
+                state: SubsystemState::Starting,
+                elapsed_ms: 0,
+                error: None,
+            };
+            self.health.set(subsystem.name, SubsystemState::Starting);
+
+            match subsystem.work {
+                Work::Init(step) => {
+                    let result = step().await;
+                    report.elapsed_ms = step_started.elapsed().as_millis() as u64;
+                    match result {
+                        Ok(()) => report.state = SubsystemState::Ready,
+                        Err(e) => {
+                            report.state = SubsystemState::Failed;
+                            report.error = Some(e.to_string());
+                            failure = Some(SupervisorError::StartupFailed {
+                                subsystem: subsystem.name.to_string(),
+                                reason: e.to_string(),
+                            });
+                        }
+                    }
+                    self.health.set(subsystem.name, report.state);
+                }
+                Work::Service { run, restart } => {
+                    self.health.set(subsystem.name, SubsystemState::Running);
+                    services.push(tokio::spawn(supervise(
+                        subsystem.name,
+                        run,
+                        restart,
+                        self.health.clone(),
+                        self.shutdown.clone(),
+                    )));
+                    report.state = SubsystemState::Running;
+                    report.elapsed_ms = step_started.elapsed().as_millis() as u64;
+                }
+            }
+            reports.push(report);
+            if failure.is_some() {
+                break;
+            }
+        }
+
+        let report = StartupReport {
+            subsystems: reports,
+            elapsed_ms: started.elapsed().as_millis() as u64,
+            ok: failure.is_none(),
+        };
+        let report_json = serde_json::to_string(&report).unwrap_or_default();
+        if let Some(err) = failure {
+            error!(report = %report_json, "Startup failed");
+            self.shutdown.cancel();
+            for service in services {
+                let _ = service.await;
+            }
+            return Err(err);
+        }
+        info!(report = %report_json, "Startup complete");
+
+        Ok(RunningSupervisor {
+            report,
+            services,
+            shutdown: self.shutdown,
+        })
+    }
+}
+
+/// Subsystems started by a [`Supervisor`]
+pub struct RunningSupervisor {
+    report: StartupReport,
+    services: Vec<JoinHandle<Result<(), SupervisorError>>>,
+    shutdown: CancellationToken,
+}
+
+impl RunningSupervisor {
+    /// Report of the completed startup
+    pub fn report(&self) -> &StartupReport {
+        &self.report
+    }
+
+    /// Wait until every service has stopped
+    ///
+    /// Returns the first service failure, if any.
+    pub async fn wait(self) -> Result<(), SupervisorError> {
+        let mut first_error = None;
+        for service in self.services {
+            let result = match service.await {
+                Ok(result) => result,
+                Err(e) => Err(SupervisorError::SubsystemFailed {
+                    subsystem: "supervisor".to_string(),
+                    reason: e.to_string(),
+                }),
+            };
+            if let Err(e) = result {
+                self.shutdown.cancel();
+                first_error.get_or_insert(e);
+            }
+        }
+        first_error.map_or(Ok(()), Err)
+    }
+}
+
+/// Run a service, restarting it after panics as its policy allows
+///
+/// Any failure cancels `shutdown` so the remaining subsystems stop as well.
+async fn supervise(
+    name: &'static str,
+    run: ServiceFn,
+    restart: RestartPolicy,
+    health: Arc<HealthRegistry>,
+    shutdown: CancellationToken,
+) -> Result<(), SupervisorError> {
+    let mut restarts = 0;
+    loop {
+        health.set(name, SubsystemState::Running);
+        let reason = match tokio::spawn(run(shutdown.clone())).await {
+            Ok(Ok(())) if shutdown.is_cancelled() => {
+                health.set(name, SubsystemState::Stopped);
+                return Ok(());
+            }
+            Ok(Ok(())) => "exited before shutdown".to_string(),
+            Ok(Err(e)) => e.to_string(),
+            Err(e) if e.is_panic() => {
+                let panic = panic_message(e.into_panic());
+                match restart {
+                    RestartPolicy::OnPanic {
+                        max_restarts,
+                        backoff,
+                    } if restarts < max_restarts && !shutdown.is_cancelled() => {
+                        restarts += 1;
+                        warn!(
+                            subsystem = name,
+                            panic = %panic,
+                            restart = restarts,
+                            max_restarts,
+                            "Subsystem panicked, restarting"
+                        );
+                        counter!("subsystem_restarts_total", "subsystem" => name).increment(1);
+                        health.set(name, SubsystemState::Restarting);
+                        tokio::select! {
+                            _ = shutdown.cancelled() => {
+                                health.set(name, SubsystemState::Stopped);
+                                return Ok(());
+                            }
+                            _ = tokio::time::sleep(backoff) => continue,
+                        }
+                    }
+                    _ => format!("panicked: {}", panic),
+                }
+            }
+            Err(e) => e.to_string(),
+        };
+
+        error!(subsystem = name, reason = %reason, "Subsystem failed, shutting down");
+        health.set(name, SubsystemState::Failed);
+        shutdown.cancel();
+        return Err(SupervisorError::SubsystemFailed {
+            subsystem: name.to_string(),
+            reason,
+        });
+    }
+}
+
+fn panic_message(panic: Box<dyn std::any::Any + Send>) -> String {
+    panic
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| panic.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".to_string())
+}
+
+/// Indices of `subsystems` with every subsystem after its dependencies
+///
+/// Subsystems without ordering constraints keep their registration order.
+fn startup_order(subsystems: &[Subsystem]) -> Result<Vec<usize>, SupervisorError> {
+    let mut index_of = HashMap::new();
+    for (index, subsystem) in subsystems.iter().enumerate() {
+        if index_of.insert(subsystem.name, index).is_some() {
+            return Err(SupervisorError::DuplicateSubsystem(
+                subsystem.name.to_string(),
+            ));
+        }
+    }
+    for subsystem in subsystems {
+        if let Some(dependency) = subsystem
+            .depends_on
+            .iter()
+            .find(|dependency| !index_of.contains_key(*dependency))
+        {
+            return Err(SupervisorError::UnknownDependency {
+                subsystem: subsystem.name.to_string(),
+                dependency: dependency.to_string(),
+            });
+        }
+    }
+
+    let mut order = Vec::with_capacity(subsystems.len());
+    let mut placed = HashSet::new();
+    while order.len() < subsystems.len() {
+        let next = subsystems.iter().enumerate().find(|(index, subsystem)| {
+            !placed.contains(index)
+                && subsystem
+                    .depends_on
+                    .iter()
+                    .all(|dependency| placed.contains(&index_of[dependency]))
+        });
+        match next {
+            Some((index, _)) => {
+                placed.insert(index);
+                order.push(index);
+            }
+            None => {
+                let stuck = subsystems
+                    .iter()
+                    .enumerate()
+                    .filter(|(index, _)| !placed.contains(index))
+                    .map(|(_, subsystem)| subsystem.name.to_string())
+                    .collect();
+                return Err(SupervisorError::DependencyCycle(stuck));
+            }
+        }
+    }
+    Ok(order)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    fn recorder() -> (
+        Arc<Mutex<Vec<&'static str>>>,
+        impl Fn(&'static str) -> Subsystem,
+    ) {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let for_steps = log.clone();
+        let step = move |name: &'static str| {
+            let log = for_steps.clone();
+            Subsystem::init(name, move || async move {
+                log.lock().unwrap().push(name);
+                Ok(())
+            })
+        };
+        (log, step)
+    }
+
+    #[tokio::test]
+    async fn test_subsystems_start_after_dependencies() {
+        let (log, step) = recorder();
+        let health = Arc::new(HealthRegistry::default());
+        let shutdown = CancellationToken::new();
+
+        let running = Supervisor::with_health(shutdown.clone(), health.clone())
+            .with_subsystem(step("server").depends_on(&["registry", "database"]))
+            .with_subsystem(step("registry").depends_on(&["database"]))
+            .with_subsystem(step("database"))
+            .with_subsystem(
+                Subsystem::service("scheduler", |shutdown: CancellationToken| async move {
+                    shutdown.cancelled().await;
+                    Ok(())
+                })
+                .depends_on(&["registry"]),
+            )
+            .start()
+            .await
+            .unwrap();
+
+        assert_eq!(*log.lock().unwrap(), vec!["database", "registry", "server"]);
+        let names: Vec<_> = running
+            .report()
+            .subsystems
+            .iter()
+            .map(|s| s.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["database", "registry", "server", "scheduler"]);
+        assert!(running.report().ok);
+        assert!(health.is_ready());
+
+        shutdown.cancel();
+        running.wait().await.unwrap();
+        assert_eq!(health.snapshot()["scheduler"], SubsystemState::Stopped);
+        assert!(!health.is_ready());
+    }
+
+    #[tokio::test]
+    async fn test_invalid_dependency_graphs_are_rejected() {
+        let (_, step) = recorder();
+        let start = |supervisor: Supervisor| async move { supervisor.start().await.err().unwrap() };
+        let supervisor = || {
+            Supervisor::with_health(
+                CancellationToken::new(),
+                Arc::new(HealthRegistry::default()),
+            )
+        };
+
+        let err =
+            start(supervisor().with_subsystem(step("server").depends_on(&["database"]))).await;
+        assert!(matches!(err, SupervisorError::UnknownDependency { .. }));
+
+        let err = start(
+            supervisor()
+                .with_subsystem(step("a").depends_on(&["b"]))
+                .with_subsystem(step("b").depends_on(&["a"])),
+        )
+        .await;
+        assert!(matches!(err, SupervisorError::DependencyCycle(names) if names == ["a", "b"]));
+    }
+
+    #[tokio::test]
+    async fn test_failed_init_step_stops_startup() {
+        let (log, step) = recorder();
+        let health = Arc::new(HealthRegistry::default());
+        let shutdown = CancellationToken::new();
+
+        let err = Supervisor::with_health(shutdown.clone(), health.clone())
+            .with_subsystem(Subsystem::init("database", || async {
+                Err::<(), BoxError>("connection refused".into())
+            }))
+            .with_subsystem(step("registry").depends_on(&["database"]))
+            .start()
+            .await
+            .err()
+            .unwrap();
+
+        assert!(
+            matches!(err, SupervisorError::StartupFailed { subsystem, .. } if subsystem == "database")
+        );
+        assert!(log.lock().unwrap().is_empty());
+        assert!(shutdown.is_cancelled());
+        let states = health.snapshot();
+        assert_eq!(states["database"], SubsystemState::Failed);
+        assert_eq!(states["registry"], SubsystemState::Pending);
+    }
+
+    #[tokio::test]
+    async fn test_panicking_service_is_restarted_within_budget() {
+        let health = Arc::new(HealthRegistry::default());
+        let shutdown = CancellationToken::new();
+        let runs = Arc::new(AtomicU32::new(0));
+        let runs_for_service = runs.clone();
+
+        let running = Supervisor::with_health(shutdown.clone(), health.clone())
+            .with_subsystem(
+                Subsystem::service("executor", move |_shutdown: CancellationToken| {
+                    let runs = runs_for_service.clone();
+                    async move {
+                        runs.fetch_add(1, Ordering::SeqCst);
+                        panic!("executor crashed");
+                    }
+                })
+                .with_restart(RestartPolicy::OnPanic {
+                    max_restarts: 2,
+                    backoff: Duration::from_millis(1),
+                }),
+            )
+            .with_subsystem(Subsystem::service(
+                "server",
+                |shutdown: CancellationToken| async move {
+                    shutdown.cancelled().await;
+                    Ok(())
+                },
+            ))
+            .start()
+            .await
+            .unwrap();
+
+        let err = running.wait().await.unwrap_err();
+        assert!(
+            matches!(&err, SupervisorError::SubsystemFailed { subsystem, reason }
+                if subsystem == "executor" && reason.contains("executor crashed"))
+        );
+        assert_eq!(runs.load(Ordering::SeqCst), 3);
+        assert!(shutdown.is_cancelled());
+        let states = health.snapshot();
+        assert_eq!(states["executor"], SubsystemState::Failed);
+        assert_eq!(states["server"], SubsystemState::Stopped);
+    }
+}
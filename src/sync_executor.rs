@@ -4,31 +4,82 @@
 //! connectors, persisting signals, and managing cursor advancement with backoff
 //! and retry logic.
 
-use base64::{Engine as _, engine::general_purpose::STANDARD};
-use chrono::Utc;
 use metrics::{counter, histogram};
-use rand::{Rng, thread_rng};
 use sea_orm::prelude::*;
+use sea_orm::sea_query::{LockBehavior, LockType};
 use sea_orm::{
-    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder,
-    QuerySelect, QueryTrait, Set, TransactionTrait,
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseBackend, DatabaseConnection,
+    EntityTrait, QueryFilter, QueryOrder, QuerySelect, QueryTrait, Set, Statement,
+    TransactionTrait,
 };
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::time::Duration;
-use tokio::time::sleep;
+use tokio_util::sync::CancellationToken;
 use tracing::{debug, error, info, instrument, warn};
 use uuid::Uuid;
 
+use crate::backoff::{ExponentialBackoff, Jitter};
+use crate::circuit_breaker::{self, CircuitBreakerPolicy, CircuitGate};
+use crate::clock::{SharedClock, system_clock};
+use crate::config::RateLimitPolicyConfig;
+use crate::config_reload::{ReloadableConfig, ReloadableSettings};
+use crate::config_resolution::ConfigLayers;
+use crate::connection_pause::{AuthPausePolicy, DEFAULT_PAUSE_THRESHOLD};
 use crate::connectors::{
-    ConnectorError, SyncError, SyncErrorKind, SyncParams, SyncResult, WebhookParams,
-    registry::Registry,
+    ConnectorError, Cursor, SyncError, SyncErrorKind, SyncPage, SyncParams, SyncResult, SyncSink,
+    WebhookParams, registry::Registry,
 };
+use crate::cursor::CursorSigner;
 use crate::models::{
-    connection::{ActiveModel as ConnectionActiveModel, Entity as ConnectionEntity},
-    signal::ActiveModel as SignalActiveModel,
+    connection::{self, ActiveModel as ConnectionActiveModel, Entity as ConnectionEntity},
+    signal::{ActiveModel as SignalActiveModel, Entity as SignalEntity},
+    signal_pipeline_event::STAGE_INGESTED,
     sync_job::{self, ActiveModel as SyncJobActiveModel, Entity as SyncJobEntity},
+    sync_job_run::{OUTCOME_CANCELLED, OUTCOME_FAILED, OUTCOME_INTERRUPTED, OUTCOME_SUCCEEDED},
 };
-use crate::repositories::sync_metadata::{ConnectionSyncMetadata, cursor_from_json};
-use crate::token_refresh::TokenRefreshService;
+use crate::repositories::sync_metadata::ConnectionSyncMetadata;
+use crate::repositories::{
+    NewSyncJobRun, PriorityAging, SignalOutboxRepository, SignalPipelineEventRepository,
+    SyncJobRunRepository,
+};
+use crate::signals::outbox::SIGNAL_TOPICS;
+use crate::signals::payload_limit;
+use crate::slo::{self, SloOperation};
+use crate::telemetry::DebugSampler;
+use crate::token_refresh::SharedTokenRefresher;
+use crate::token_vault::TokenVault;
+
+/// How many more queued jobs than `claim_batch` are scanned when provider caps apply,
+/// so a capped provider at the head of the queue cannot hide other providers' jobs
+const CAPPED_CLAIM_SCAN_FACTOR: usize = 4;
+
+/// Transaction-scoped advisory lock held while a claim checks and takes concurrency
+/// cap slots, so instances cannot both see the last free slot as free
+const CAPPED_CLAIM_LOCK_KEY: i64 = 0x636c_6169_6d5f_6361;
+
+/// Job statuses that hold a running slot; a `cancelling` job runs until its
+/// connector reaches a page boundary
+const ACTIVE_STATUSES: [&str; 2] = ["running", "cancelling"];
+
+/// Signals inserted per statement when persisting a page
+const SIGNAL_INSERT_BATCH: usize = 500;
+
+/// How often a running job's lease is renewed and its status checked for a
+/// cancellation request
+const LEASE_HEARTBEAT_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Samplers for per-tick and per-job debug events (`POBLYSH_LOG_DEBUG_SAMPLE_RATE`)
+static IDLE_DEBUG_SAMPLER: DebugSampler = DebugSampler::new();
+static BATCH_DEBUG_SAMPLER: DebugSampler = DebugSampler::new();
+static JOB_DEBUG_SAMPLER: DebugSampler = DebugSampler::new();
+
+/// Retry schedule for failures that carry no classified [`SyncError`]
+const FALLBACK_BACKOFF: ExponentialBackoff =
+    ExponentialBackoff::new(Duration::from_secs(5), Duration::from_secs(900))
+        .with_jitter(Jitter::Additive(0.1));
+
+/// Error message of a job that ran past `max_run_seconds`
+const JOB_TIMED_OUT: &str = "Job timed out";
 
 /// Configuration for the sync executor
 #[derive(Debug, Clone)]
@@ -37,12 +88,37 @@ pub struct ExecutorConfig {
     pub tick_ms: u64,
     /// Maximum number of concurrent jobs
     pub concurrency: usize,
-    /// Maximum number of jobs to claim in one batch
+    /// Maximum number of jobs to claim in one batch; never more than `concurrency`
+    /// are claimed at once
     pub claim_batch: usize,
     /// Maximum number of seconds a job can run before being timed out
     pub max_run_seconds: u64,
     /// Maximum number of items to process per run
     pub max_items_per_run: usize,
+    /// Consecutive authorization failures before a connection is paused (0 disables)
+    pub auth_failure_pause_threshold: u32,
+    /// Consecutive provider failures before the provider's circuit opens (0 disables)
+    pub circuit_breaker_threshold: u32,
+    /// Seconds an open provider circuit pauses claiming before a probe job runs
+    pub circuit_breaker_cooldown_seconds: u64,
+    /// Size limits applied to signal payloads before they are persisted
+    pub signal_payload: crate::config::SignalPayloadConfig,
+    /// Caps on running jobs per provider, globally and per tenant
+    pub provider_concurrency: crate::config::ProviderConcurrencyConfig,
+    /// Running jobs allowed per tenant across all providers (0 means unlimited)
+    pub max_running_per_tenant: usize,
+    /// Seconds in-flight jobs get to finish or checkpoint after shutdown before
+    /// they are requeued
+    pub drain_timeout_seconds: u64,
+    /// Seconds without a heartbeat after which a running job's lease expires and
+    /// the job is reclaimed
+    pub lease_timeout_seconds: u64,
+    /// Name recorded as `locked_by` on claimed jobs; unique per running instance
+    pub instance_id: String,
+    /// Slice of the job queue this instance claims from
+    pub shard: crate::config::ExecutorShardConfig,
+    /// Priority boost for jobs that have waited in the queue
+    pub priority_aging: PriorityAging,
 }
 
 impl Default for ExecutorConfig {
@@ -53,17 +129,75 @@ impl Default for ExecutorConfig {
             claim_batch: 50,
             max_run_seconds: 300, // 5 minutes
             max_items_per_run: 1000,
+            auth_failure_pause_threshold: DEFAULT_PAUSE_THRESHOLD,
+            circuit_breaker_threshold: circuit_breaker::DEFAULT_FAILURE_THRESHOLD,
+            circuit_breaker_cooldown_seconds: circuit_breaker::DEFAULT_COOLDOWN_SECONDS,
+            signal_payload: crate::config::SignalPayloadConfig::default(),
+            provider_concurrency: crate::config::ProviderConcurrencyConfig::default(),
+            max_running_per_tenant: 0,
+            drain_timeout_seconds: 30,
+            lease_timeout_seconds: 60,
+            instance_id: default_instance_id(),
+            shard: crate::config::ExecutorShardConfig::default(),
+            priority_aging: PriorityAging {
+                interval: chrono::Duration::seconds(300),
+                max_boost: 50,
+            },
+        }
+    }
+}
+
+impl ExecutorConfig {
+    /// Executor settings from the application configuration (`POBLYSH_EXECUTOR_*`
+    /// and the job and provider limits the executor enforces)
+    pub fn from_config(config: &crate::config::AppConfig) -> Self {
+        Self {
+            tick_ms: config.executor_tick_ms,
+            concurrency: config.executor_concurrency,
+            claim_batch: config.executor_claim_batch,
+            max_run_seconds: config.executor_max_run_seconds,
+            max_items_per_run: config.executor_max_items_per_run,
+            auth_failure_pause_threshold: config.connection_auth_pause_threshold,
+            circuit_breaker_threshold: config.circuit_breaker_threshold,
+            circuit_breaker_cooldown_seconds: config.circuit_breaker_cooldown_seconds,
+            signal_payload: config.signal_payload.clone(),
+            provider_concurrency: config.provider_concurrency.clone(),
+            max_running_per_tenant: config.tenant_max_running,
+            drain_timeout_seconds: config.executor_drain_timeout_seconds,
+            lease_timeout_seconds: config.executor_lease_timeout_seconds,
+            instance_id: config
+                .executor_instance_id
+                .clone()
+                .unwrap_or_else(default_instance_id),
+            shard: config.executor_shard.clone(),
+            priority_aging: PriorityAging {
+                interval: chrono::Duration::seconds(config.job_priority_aging_seconds as i64),
+                max_boost: config.job_priority_aging_max_boost,
+            },
         }
     }
 }
 
+/// Instance name derived from the host name and process id, unique per process on a host
+pub fn default_instance_id() -> String {
+    let host = std::env::var("HOSTNAME")
+        .ok()
+        .filter(|host| !host.is_empty())
+        .unwrap_or_else(|| "executor".to_string());
+    format!("{}-{}", host, std::process::id())
+}
+
 /// Sync executor responsible for running background sync jobs
 pub struct SyncExecutor {
     pub db: std::sync::Arc<DatabaseConnection>,
     pub registry: std::sync::Arc<Registry>,
     config: ExecutorConfig,
-    rate_limit_policy: crate::config::RateLimitPolicyConfig,
-    token_refresh_service: std::sync::Arc<TokenRefreshService>,
+    settings: ReloadableConfig,
+    token_refresh_service: SharedTokenRefresher,
+    token_vault: TokenVault,
+    cursor_signer: CursorSigner,
+    clock: SharedClock,
+    shutdown: CancellationToken,
 }
 
 impl SyncExecutor {
@@ -73,70 +207,102 @@ impl SyncExecutor {
         registry: Registry,
         config: ExecutorConfig,
         rate_limit_policy: crate::config::RateLimitPolicyConfig,
-        token_refresh_service: std::sync::Arc<TokenRefreshService>,
+        token_refresh_service: SharedTokenRefresher,
+        token_vault: TokenVault,
+        cursor_signer: CursorSigner,
     ) -> Self {
         Self {
             db: std::sync::Arc::new(db),
             registry: std::sync::Arc::new(registry),
             config,
-            rate_limit_policy,
+            settings: ReloadableConfig::new(ReloadableSettings {
+                rate_limit_policy,
+                ..Default::default()
+            }),
             token_refresh_service,
+            token_vault,
+            cursor_signer,
+            clock: system_clock(),
+            shutdown: CancellationToken::new(),
         }
     }
 
+    /// Replace the time source used for claiming, retry scheduling, and tick pacing
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Follow shared settings so a reloaded rate limit policy applies to the next claim and retry
+    pub fn with_settings(mut self, settings: ReloadableConfig) -> Self {
+        self.settings = settings;
+        self
+    }
+
+    /// Drain on `shutdown`: stop claiming and cancel running jobs' sync tokens so
+    /// connectors checkpoint at their next page boundary
+    pub fn with_shutdown(mut self, shutdown: CancellationToken) -> Self {
+        self.shutdown = shutdown;
+        self
+    }
+
     /// Get the executor configuration
     pub fn config(&self) -> &ExecutorConfig {
         &self.config
     }
 
+    /// Override layers of a tenant, falling back to service settings when they cannot be read
+    async fn tenant_layers(&self, tenant_id: Uuid) -> ConfigLayers {
+        ConfigLayers::load(&self.db, tenant_id)
+            .await
+            .unwrap_or_else(|err| {
+                warn!(
+                    error = ?err,
+                    tenant_id = %tenant_id,
+                    "Failed to load tenant settings; using service defaults"
+                );
+                ConfigLayers::default()
+            })
+    }
+
     /// Calculate retry backoff based on rate limit policy and error
     fn calculate_backoff(
-        &self,
+        rate_limit_policy: &RateLimitPolicyConfig,
         sync_error: &SyncError,
         attempts_completed: i32,
         provider_slug: &str,
     ) -> (f64, bool) {
-        // Get provider-specific override or use default policy
-        let policy = self.rate_limit_policy.provider_overrides.get(provider_slug);
-
-        let base_seconds = policy
-            .and_then(|p| p.base_seconds)
-            .unwrap_or(self.rate_limit_policy.base_seconds) as f64;
-        let max_seconds = policy
-            .and_then(|p| p.max_seconds)
-            .unwrap_or(self.rate_limit_policy.max_seconds) as f64;
-        let jitter_factor = policy
-            .and_then(|p| p.jitter_factor)
-            .unwrap_or(self.rate_limit_policy.jitter_factor);
-
-        let mut backoff = (base_seconds * 2_f64.powi(attempts_completed)).min(max_seconds);
-
-        // If the error provides retry_after_secs, prefer the max of that and our calculated backoff
-        if let SyncErrorKind::RateLimited { retry_after_secs } = &sync_error.kind
-            && let Some(retry_after) = retry_after_secs
-        {
-            backoff = backoff.max(*retry_after as f64);
-        }
+        // Honour the provider's retry-after hint when it exceeds our own schedule
+        let retry_after = match &sync_error.kind {
+            SyncErrorKind::RateLimited {
+                retry_after_secs: Some(retry_after),
+            } => Duration::from_secs(*retry_after),
+            _ => Duration::ZERO,
+        };
 
-        // Apply jitter
-        let jitter = thread_rng().gen_range(0.0..(jitter_factor * backoff));
-        let final_backoff = backoff + jitter;
+        let backoff = rate_limit_policy
+            .backoff_for(provider_slug)
+            .sample_at_least(attempts_completed.max(0) as u32, retry_after);
 
         let is_rate_limited = matches!(sync_error.kind, SyncErrorKind::RateLimited { .. });
 
-        (final_backoff, is_rate_limited)
+        (backoff.as_secs_f64(), is_rate_limited)
     }
 
-    /// Run the executor loop
+    /// Run the executor loop until shutdown, then return once in-flight jobs drained
     pub async fn run(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         info!("Starting sync executor with config: {:?}", self.config);
 
-        loop {
+        while !self.shutdown.is_cancelled() {
             let start = std::time::Instant::now();
 
+            if let Err(e) = self.reap_expired_leases().await {
+                error!("Error reaping expired job leases: {}", e);
+            }
+
             match self.claim_and_run_jobs().await {
                 Ok(count) => {
-                    if count > 0 {
+                    if count > 0 && BATCH_DEBUG_SAMPLER.sample() {
                         debug!("Executed {} sync jobs", count);
                     }
                 }
@@ -149,9 +315,15 @@ impl SyncExecutor {
             let elapsed = start.elapsed();
             let tick_duration = Duration::from_millis(self.config.tick_ms);
             if elapsed < tick_duration {
-                sleep(tick_duration - elapsed).await;
+                tokio::select! {
+                    _ = self.clock.sleep(tick_duration - elapsed) => {}
+                    _ = self.shutdown.cancelled() => {}
+                }
             }
         }
+
+        info!("Sync executor drained and stopped");
+        Ok(())
     }
 
     /// Claim due jobs and execute them
@@ -160,19 +332,22 @@ impl SyncExecutor {
         &self,
     ) -> Result<usize, Box<dyn std::error::Error + Send + Sync>> {
         let timer = std::time::Instant::now();
-        let jobs = self.claim_jobs().await?;
+        // Create a bounded semaphore to limit concurrent jobs
+        let semaphore = std::sync::Arc::new(tokio::sync::Semaphore::new(self.config.concurrency));
+        // A claimed job's lease is only renewed once it runs, so claim no more
+        // jobs than can start right away
+        let jobs = self.claim_jobs(semaphore.available_permits()).await?;
         let count = jobs.len();
 
         if jobs.is_empty() {
-            debug!("No due jobs found to claim");
+            if IDLE_DEBUG_SAMPLER.sample() {
+                debug!("No due jobs found to claim");
+            }
             return Ok(0);
         }
 
         info!("Claimed {} jobs for execution", count);
 
-        // Create a bounded semaphore to limit concurrent jobs
-        let semaphore = std::sync::Arc::new(tokio::sync::Semaphore::new(self.config.concurrency));
-
         // Spawn all jobs with concurrency control
         let mut handles = Vec::new();
         for job in jobs {
@@ -208,17 +383,79 @@ impl SyncExecutor {
         Ok(count)
     }
 
-    /// Claim due jobs from the database using truly atomic approach
+    /// Claim up to `claim_batch` due jobs, but no more than `free_slots`, using truly atomic approach
     async fn claim_jobs(
         &self,
+        free_slots: usize,
     ) -> Result<Vec<sync_job::Model>, Box<dyn std::error::Error + Send + Sync>> {
-        let now = Utc::now();
+        let now = self.clock.now();
+        let batch = self.config.claim_batch.min(free_slots);
+        if batch == 0 {
+            return Ok(Vec::new());
+        }
         let txn = self.db.begin().await?;
 
+        let caps = &self.config.provider_concurrency;
+        let tenant_cap =
+            (self.config.max_running_per_tenant > 0).then_some(self.config.max_running_per_tenant);
+        let mut slots = if caps.is_limited() || tenant_cap.is_some() {
+            // Capped claims take turns so no two instances count the same free slot
+            // (SQLite serializes writers already)
+            if txn.get_database_backend() == DatabaseBackend::Postgres {
+                txn.execute(Statement::from_sql_and_values(
+                    DatabaseBackend::Postgres,
+                    "SELECT pg_advisory_xact_lock($1)",
+                    [CAPPED_CLAIM_LOCK_KEY.into()],
+                ))
+                .await?;
+            }
+            let running = SyncJobEntity::find()
+                .select_only()
+                .column(sync_job::Column::TenantId)
+                .column(sync_job::Column::ProviderSlug)
+                .filter(sync_job::Column::Status.is_in(ACTIVE_STATUSES))
+                .into_tuple::<(Uuid, String)>()
+                .all(&txn)
+                .await?;
+            Some(RunningSlots::new(caps, tenant_cap, running))
+        } else {
+            None
+        };
+        let circuits = if self.circuit_breaker().is_enabled() {
+            self.circuit_breaker().gate(&txn, now).await?
+        } else {
+            CircuitGate::default()
+        };
+        let settings = self.settings.current();
+        let instance_capped =
+            settings.rate_limit_policy.has_concurrency_caps() || !circuits.probing.is_empty();
+        let scan_limit = if slots.is_some() || instance_capped {
+            batch * CAPPED_CLAIM_SCAN_FACTOR
+        } else {
+            batch
+        };
+        let mut saturated = slots
+            .as_ref()
+            .map(RunningSlots::saturated_providers)
+            .unwrap_or_default();
+        saturated.extend(circuits.blocked);
+        let saturated_tenants = slots
+            .as_ref()
+            .map(RunningSlots::saturated_tenants)
+            .unwrap_or_default();
+
+        let shard_column = if self.config.shard.by_connection() {
+            sync_job::Column::ConnectionId
+        } else {
+            sync_job::Column::TenantId
+        };
+
         // First, find eligible jobs with single-flight constraint
-        let eligible_jobs = SyncJobEntity::find()
+        let candidates = SyncJobEntity::find()
             .select_only()
             .column(sync_job::Column::Id)
+            .column(sync_job::Column::TenantId)
+            .column(sync_job::Column::ProviderSlug)
             .filter(
                 sync_job::Column::Status
                     .eq("queued")
@@ -234,27 +471,98 @@ impl SyncExecutor {
                     SyncJobEntity::find()
                         .select_only()
                         .column(sync_job::Column::ConnectionId)
-                        .filter(sync_job::Column::Status.eq("running"))
+                        .filter(sync_job::Column::Status.is_in(ACTIVE_STATUSES))
+                        .into_query(),
+                ),
+            )
+            // Jobs of paused or `needs_reauth` connections wait in the queue, whether
+            // queued before the status changed, as retries or by webhooks
+            .filter(
+                sync_job::Column::ConnectionId.in_subquery(
+                    ConnectionEntity::find()
+                        .select_only()
+                        .column(connection::Column::Id)
+                        .filter(connection::Column::Status.eq("active"))
                         .into_query(),
                 ),
             )
-            .order_by_desc(sync_job::Column::Priority)
+            .apply_if(
+                (!saturated.is_empty()).then_some(saturated),
+                |query, providers| {
+                    query.filter(sync_job::Column::ProviderSlug.is_not_in(providers))
+                },
+            )
+            .apply_if(
+                (!saturated_tenants.is_empty()).then_some(saturated_tenants),
+                |query, tenants| query.filter(sync_job::Column::TenantId.is_not_in(tenants)),
+            )
+            .apply_if(self.config.shard.key_range(), |query, (lower, upper)| {
+                query
+                    .filter(shard_column.gte(lower))
+                    .apply_if(upper, |query, upper| query.filter(shard_column.lt(upper)))
+            })
+            .order_by_desc(self.config.priority_aging.effective_priority(now))
             .order_by_asc(sync_job::Column::ScheduledAt)
-            .limit(Some(self.config.claim_batch as u64))
-            .into_tuple::<Uuid>()
+            .limit(Some(scan_limit as u64))
+            // Rows another instance is claiming are skipped rather than waited on
+            // (SQLite serializes writers and ignores the clause)
+            .lock_with_behavior(LockType::Update, LockBehavior::SkipLocked)
+            .into_tuple::<(Uuid, Uuid, String)>()
             .all(&txn)
             .await?;
 
+        // Keep queue order, skipping jobs whose provider or tenant has no free slot.
+        // A batch runs to completion before the next claim, so the jobs claimed here
+        // are everything this instance runs and per-instance caps apply to the batch.
+        let mut batch_per_provider: std::collections::HashMap<String, usize> =
+            std::collections::HashMap::new();
+        let eligible_jobs: Vec<Uuid> = candidates
+            .into_iter()
+            .filter(|(_, tenant_id, provider_slug)| {
+                let in_batch = batch_per_provider
+                    .get(provider_slug)
+                    .copied()
+                    .unwrap_or_default();
+                if settings
+                    .rate_limit_policy
+                    .max_concurrent_for(provider_slug)
+                    .is_some_and(|cap| in_batch >= cap)
+                {
+                    return false;
+                }
+                // A half-open circuit is probed with a single job
+                if in_batch >= 1 && circuits.probing.contains(provider_slug) {
+                    return false;
+                }
+                if !slots
+                    .as_mut()
+                    .is_none_or(|slots| slots.try_take(*tenant_id, provider_slug))
+                {
+                    return false;
+                }
+                *batch_per_provider.entry(provider_slug.clone()).or_default() += 1;
+                true
+            })
+            .map(|(id, _, _)| id)
+            .take(batch)
+            .collect();
+
         // Atomically claim the jobs in a single UPDATE statement
         let update_result = if !eligible_jobs.is_empty() {
             SyncJobEntity::update_many()
                 .col_expr(sync_job::Column::Status, Expr::value("running"))
                 .col_expr(sync_job::Column::StartedAt, Expr::value(now))
+                .col_expr(sync_job::Column::LockedAt, Expr::value(now))
+                .col_expr(sync_job::Column::HeartbeatAt, Expr::value(now))
+                .col_expr(
+                    sync_job::Column::LockedBy,
+                    Expr::value(self.config.instance_id.clone()),
+                )
                 .col_expr(
                     sync_job::Column::Attempts,
                     Expr::value(Expr::col(sync_job::Column::Attempts).add(1)),
                 )
-                .filter(sync_job::Column::Id.is_in(eligible_jobs))
+                .filter(sync_job::Column::Id.is_in(eligible_jobs.clone()))
                 .filter(sync_job::Column::Status.eq("queued")) // Double-check they're still queued
                 .exec(&txn)
                 .await?
@@ -264,10 +572,12 @@ impl SyncExecutor {
         };
 
         // Fetch only the jobs that were actually claimed (those affected by the UPDATE)
-        // This ensures we only return jobs that we successfully transitioned to "running" status
+        // This ensures we only return jobs that this instance transitioned to "running" status
         let claimed_jobs = if update_result.rows_affected > 0 {
             SyncJobEntity::find()
+                .filter(sync_job::Column::Id.is_in(eligible_jobs))
                 .filter(sync_job::Column::Status.eq("running"))
+                .filter(sync_job::Column::LockedBy.eq(self.config.instance_id.as_str()))
                 .filter(sync_job::Column::StartedAt.eq(now))
                 .all(&txn)
                 .await?
@@ -286,15 +596,76 @@ impl SyncExecutor {
         job: sync_job::Model,
     ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         let start_time = std::time::Instant::now();
+        let started_at = self.clock.now();
+        let slo_operation = SloOperation::for_job_type(&job.job_type);
+        if self.shutdown.is_cancelled() {
+            // Claimed in the same batch as shutdown began; leave it for another instance
+            return self.release_interrupted(&job).await;
+        }
         info!("Starting sync job {} (attempt {})", job.id, job.attempts);
+        // Signals of pages committed while the sync streamed
+        let streamed = AtomicUsize::new(0);
+
+        // Shutdown cancels every running job's token, as does an operator cancel
+        let cancellation = self.shutdown.child_token();
+        let watcher = tokio::spawn(hold_lease(
+            self.db.clone(),
+            self.clock.clone(),
+            job.id,
+            self.config.instance_id.clone(),
+            cancellation.clone(),
+        ));
+        let drain_deadline = async {
+            self.shutdown.cancelled().await;
+            self.clock
+                .sleep(Duration::from_secs(self.config.drain_timeout_seconds))
+                .await;
+        };
+        let outcome = tokio::select! {
+            outcome = self.execute_job(&job, cancellation, &streamed) => outcome,
+            _ = drain_deadline => {
+                watcher.abort();
+                warn!(
+                    "Job {} did not checkpoint within the drain timeout, requeueing",
+                    job.id
+                );
+                self.record_run(
+                    &job,
+                    started_at,
+                    streamed.load(Ordering::Relaxed),
+                    OUTCOME_INTERRUPTED,
+                    Some("Executor shut down before the job checkpointed".to_string()),
+                )
+                .await;
+                return self.release_interrupted(&job).await;
+            }
+        };
+        watcher.abort();
 
-        match self.execute_job(&job).await {
+        match outcome {
             Ok(sync_result) => {
                 let execution_time = start_time.elapsed();
-                debug!("Job {} executed in {:?}", job.id, execution_time);
-
-                match self.handle_success(&job, sync_result).await {
-                    Ok(()) => {
+                if JOB_DEBUG_SAMPLER.sample() {
+                    debug!("Job {} executed in {:?}", job.id, execution_time);
+                }
+                let items = streamed.load(Ordering::Relaxed) + sync_result.signals.len();
+
+                match self.handle_success(&job, sync_result, items).await {
+                    Ok(run_outcome) => {
+                        self.record_run(&job, started_at, items, run_outcome, None)
+                            .await;
+                        slo::record(&self.db, &job.provider_slug, slo_operation, true).await;
+                        self.record_circuit_outcome(&job, None, None).await;
+                        if let Err(e) = self
+                            .auth_pause_policy()
+                            .record_auth_success(job.connection_id)
+                            .await
+                        {
+                            warn!(
+                                "Failed to reset auth failure streak for job {}: {}",
+                                job.id, e
+                            );
+                        }
                         let total_time = start_time.elapsed();
                         info!(
                             "Successfully completed job {} in {:?} (execution: {:?}, total: {:?})",
@@ -304,7 +675,16 @@ impl SyncExecutor {
                     }
                     Err(e) => {
                         error!("Error handling success for job {}: {}", job.id, e);
-                        self.handle_failure(&job, &e.to_string(), None).await?;
+                        slo::record(&self.db, &job.provider_slug, slo_operation, false).await;
+                        let run_outcome = self.handle_failure(&job, &e.to_string(), None).await?;
+                        self.record_run(
+                            &job,
+                            started_at,
+                            streamed.load(Ordering::Relaxed),
+                            run_outcome,
+                            Some(e.to_string()),
+                        )
+                        .await;
                         Err(e)
                     }
                 }
@@ -312,6 +692,7 @@ impl SyncExecutor {
             Err(e) => {
                 let execution_time = start_time.elapsed();
                 warn!("Job {} failed after {:?}: {}", job.id, execution_time, e);
+                slo::record(&self.db, &job.provider_slug, slo_operation, false).await;
 
                 // Try to extract SyncError from the error or convert from ConnectorError
                 let sync_error = e.downcast_ref::<SyncError>().cloned().or_else(|| {
@@ -320,67 +701,368 @@ impl SyncExecutor {
                         .map(|connector_err| SyncError::from(connector_err.clone()))
                 });
 
-                self.handle_failure(&job, &e.to_string(), sync_error.as_ref())
+                let run_outcome = self
+                    .handle_failure(&job, &e.to_string(), sync_error.as_ref())
                     .await?;
+                self.record_run(
+                    &job,
+                    started_at,
+                    streamed.load(Ordering::Relaxed),
+                    run_outcome,
+                    Some(e.to_string()),
+                )
+                .await;
+                self.record_circuit_outcome(&job, sync_error.as_ref(), Some(&e.to_string()))
+                    .await;
+
+                // Token refresh already had its chance; repeated 401s pause the connection
+                if sync_error
+                    .as_ref()
+                    .is_some_and(|sync_err| sync_err.kind == SyncErrorKind::Unauthorized)
+                    && let Err(pause_err) = self
+                        .auth_pause_policy()
+                        .record_auth_failure(job.connection_id, &e.to_string())
+                        .await
+                {
+                    warn!(
+                        "Failed to record auth failure for job {}: {}",
+                        job.id, pause_err
+                    );
+                }
                 Err(e)
             }
         }
     }
 
+    /// Reclaim running jobs whose lease expired because their executor crashed.
+    ///
+    /// A job is requeued, due now, keeping its attempt count and cursor; a job an
+    /// operator was cancelling ends `cancelled`. Returns how many were reclaimed.
+    pub async fn reap_expired_leases(
+        &self,
+    ) -> Result<u64, Box<dyn std::error::Error + Send + Sync>> {
+        let now = self.clock.now();
+        let cutoff = now - chrono::Duration::seconds(self.config.lease_timeout_seconds as i64);
+        // Jobs claimed before leases existed have no heartbeat; their start time stands in
+        let expired = sync_job::Column::HeartbeatAt
+            .lt(cutoff)
+            .or(sync_job::Column::HeartbeatAt
+                .is_null()
+                .and(sync_job::Column::StartedAt.lt(cutoff)));
+
+        let requeued = SyncJobEntity::update_many()
+            .col_expr(sync_job::Column::Status, Expr::value("queued"))
+            .col_expr(sync_job::Column::ScheduledAt, Expr::value(now))
+            .col_expr(
+                sync_job::Column::RetryAfter,
+                Expr::value(Option::<chrono::DateTime<chrono::Utc>>::None),
+            )
+            .col_expr(
+                sync_job::Column::StartedAt,
+                Expr::value(Option::<chrono::DateTime<chrono::Utc>>::None),
+            )
+            .col_expr(
+                sync_job::Column::LockedAt,
+                Expr::value(Option::<chrono::DateTime<chrono::Utc>>::None),
+            )
+            .col_expr(
+                sync_job::Column::HeartbeatAt,
+                Expr::value(Option::<chrono::DateTime<chrono::Utc>>::None),
+            )
+            .col_expr(
+                sync_job::Column::LockedBy,
+                Expr::value(Option::<String>::None),
+            )
+            .col_expr(
+                sync_job::Column::Error,
+                Expr::value(serde_json::json!({
+                    "message": "Lease expired; the executor running the job stopped renewing it",
+                    "timestamp": now.to_rfc3339(),
+                })),
+            )
+            .col_expr(sync_job::Column::UpdatedAt, Expr::value(now))
+            .filter(sync_job::Column::Status.eq("running"))
+            .filter(expired.clone())
+            .exec(&*self.db)
+            .await?
+            .rows_affected;
+        let cancelled = SyncJobEntity::update_many()
+            .col_expr(sync_job::Column::Status, Expr::value("cancelled"))
+            .col_expr(sync_job::Column::FinishedAt, Expr::value(now))
+            .col_expr(sync_job::Column::UpdatedAt, Expr::value(now))
+            .filter(sync_job::Column::Status.eq("cancelling"))
+            .filter(expired)
+            .exec(&*self.db)
+            .await?
+            .rows_affected;
+
+        let reaped = requeued + cancelled;
+        if reaped > 0 {
+            counter!("sync_jobs_reaped_total").increment(reaped);
+            warn!(
+                requeued,
+                cancelled, "Reclaimed jobs whose executor stopped renewing their lease"
+            );
+        }
+        Ok(reaped)
+    }
+
+    /// Requeue a job interrupted by shutdown so it is reclaimed immediately.
+    ///
+    /// The interrupted attempt does not count against the job, and the job resumes
+    /// from its last committed cursor. A job an operator was cancelling ends
+    /// `cancelled` instead.
+    async fn release_interrupted(
+        &self,
+        job: &sync_job::Model,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let now = self.clock.now();
+        SyncJobEntity::update_many()
+            .col_expr(sync_job::Column::Status, Expr::value("queued"))
+            .col_expr(sync_job::Column::ScheduledAt, Expr::value(now))
+            .col_expr(
+                sync_job::Column::RetryAfter,
+                Expr::value(Option::<chrono::DateTime<chrono::Utc>>::None),
+            )
+            .col_expr(
+                sync_job::Column::StartedAt,
+                Expr::value(Option::<chrono::DateTime<chrono::Utc>>::None),
+            )
+            .col_expr(
+                sync_job::Column::LockedAt,
+                Expr::value(Option::<chrono::DateTime<chrono::Utc>>::None),
+            )
+            .col_expr(
+                sync_job::Column::HeartbeatAt,
+                Expr::value(Option::<chrono::DateTime<chrono::Utc>>::None),
+            )
+            .col_expr(
+                sync_job::Column::LockedBy,
+                Expr::value(Option::<String>::None),
+            )
+            .col_expr(
+                sync_job::Column::Attempts,
+                Expr::value(job.attempts.saturating_sub(1).max(0)),
+            )
+            .col_expr(sync_job::Column::UpdatedAt, Expr::value(now))
+            .filter(sync_job::Column::Id.eq(job.id))
+            .filter(sync_job::Column::Status.eq("running"))
+            .filter(sync_job::Column::LockedBy.eq(self.config.instance_id.as_str()))
+            .exec(&*self.db)
+            .await?;
+        SyncJobEntity::update_many()
+            .col_expr(sync_job::Column::Status, Expr::value("cancelled"))
+            .col_expr(sync_job::Column::FinishedAt, Expr::value(now))
+            .col_expr(sync_job::Column::UpdatedAt, Expr::value(now))
+            .filter(sync_job::Column::Id.eq(job.id))
+            .filter(sync_job::Column::Status.eq("cancelling"))
+            .exec(&*self.db)
+            .await?;
+
+        info!("Released job {} for reclaim after shutdown", job.id);
+        Ok(())
+    }
+
+    /// Append the run to the job's history, logging instead of failing the job
+    /// when it cannot be written
+    async fn record_run(
+        &self,
+        job: &sync_job::Model,
+        started_at: chrono::DateTime<chrono::Utc>,
+        items_processed: usize,
+        outcome: &'static str,
+        error: Option<String>,
+    ) {
+        let run = NewSyncJobRun {
+            job,
+            started_at,
+            finished_at: self.clock.now(),
+            items_processed,
+            outcome,
+            error,
+        };
+        if let Err(e) = SyncJobRunRepository::record(&*self.db, run).await {
+            warn!("Failed to record run of job {}: {}", job.id, e);
+        }
+    }
+
+    /// Auth pause policy using this executor's failure threshold
+    fn auth_pause_policy(&self) -> AuthPausePolicy {
+        AuthPausePolicy::new(self.db.clone(), self.config.auth_failure_pause_threshold)
+    }
+
+    /// Circuit breaker using this executor's threshold and cooldown
+    fn circuit_breaker(&self) -> CircuitBreakerPolicy {
+        CircuitBreakerPolicy {
+            threshold: self.config.circuit_breaker_threshold,
+            cooldown: chrono::Duration::seconds(
+                self.config.circuit_breaker_cooldown_seconds as i64,
+            ),
+        }
+    }
+
+    /// Count a finished job towards its provider's circuit.
+    ///
+    /// `error` is the failure message, absent on success. Failures the provider
+    /// had no part in (a missing connection, a database error) leave the circuit
+    /// alone.
+    async fn record_circuit_outcome(
+        &self,
+        job: &sync_job::Model,
+        sync_error: Option<&SyncError>,
+        error: Option<&str>,
+    ) {
+        let breaker = self.circuit_breaker();
+        let now = self.clock.now();
+        let result = match error {
+            Some(message)
+                if circuit_breaker::is_provider_failure(sync_error, message == JOB_TIMED_OUT) =>
+            {
+                breaker
+                    .record_failure(&*self.db, &job.provider_slug, message, now)
+                    .await
+                    .map(|_| ())
+            }
+            Some(_) if sync_error.is_none() => Ok(()),
+            _ => {
+                breaker
+                    .record_success(&*self.db, &job.provider_slug, now)
+                    .await
+            }
+        };
+        if let Err(e) = result {
+            warn!(
+                "Failed to update provider circuit for job {}: {}",
+                job.id, e
+            );
+        }
+    }
+
     /// Execute the actual sync job
     async fn execute_job(
         &self,
         job: &sync_job::Model,
+        cancellation: CancellationToken,
+        streamed: &AtomicUsize,
     ) -> Result<SyncResult, Box<dyn std::error::Error + Send + Sync>> {
-        // Get connection
+        // Get connection with its tokens decrypted for the connector
         let connection = ConnectionEntity::find_by_id(job.connection_id)
             .one(&*self.db)
             .await?
             .ok_or("Connection not found")?;
-
-        // Save connection_id for later use (before we move connection)
-        let connection_id = connection.id;
+        let connection = self.token_vault.open(connection).await?;
 
         // Get connector
         let connector = self.registry.get(&job.provider_slug)?;
 
-        // Resolve cursor: prefer job cursor, then connection metadata cursor
-        let cursor = job
-            .cursor
-            .clone()
-            .and_then(|cursor| cursor_from_json(Some(&cursor)))
-            .or_else(|| {
-                let sync_metadata =
-                    ConnectionSyncMetadata::from_connection_metadata(connection.metadata.as_ref());
-                sync_metadata.cursor
-            });
+        // Mail connectors filter spam with the tenant's settings
+        let mail_spam = self
+            .tenant_layers(job.tenant_id)
+            .await
+            .mail_spam(&self.settings.current().mail_spam);
+        crate::mail::integration::with_spam_settings(
+            &mail_spam,
+            self.run_connector(job, connector.as_ref(), connection, cancellation, streamed),
+        )
+        .await
+    }
+
+    /// Run a claimed job through its connector
+    async fn run_connector(
+        &self,
+        job: &sync_job::Model,
+        connector: &dyn crate::connectors::Connector,
+        connection: crate::models::connection::Model,
+        cancellation: CancellationToken,
+        streamed: &AtomicUsize,
+    ) -> Result<SyncResult, Box<dyn std::error::Error + Send + Sync>> {
+        // Save connection_id for later use (before we move connection)
+        let connection_id = connection.id;
 
         // Execute job based on job type, with 401 retry logic
         let sync_result = if job.job_type == "webhook" {
             tokio::time::timeout(
                 Duration::from_secs(self.config.max_run_seconds),
                 self.execute_webhook_with_retry(
-                    connector.as_ref(),
+                    connector,
                     &connection,
                     job.cursor.as_ref(),
                     &connection_id,
                 ),
             )
             .await
-            .map_err(|_| "Job timed out")??
+            .map_err(|_| JOB_TIMED_OUT)??
         } else {
-            let sync_params = SyncParams { connection, cursor };
-            tokio::time::timeout(
+            // Resolve cursor: prefer job cursor, then connection metadata cursor
+            let stored_cursor = job.cursor.clone().or_else(|| {
+                ConnectionSyncMetadata::from_connection_metadata(connection.metadata.as_ref())
+                    .cursor
+            });
+            let cursor = stored_cursor
+                .and_then(|stored| self.open_cursor(&stored, &job.provider_slug, connection_id));
+            let mut sink = PersistingSink {
+                executor: self,
+                job,
+                started_from: cursor.clone(),
+                checkpoint: None,
+                emitted: streamed,
+                stop: cancellation.clone(),
+            };
+            let sync_params = SyncParams {
+                connection,
+                cursor,
+                cancellation,
+            };
+            let mut sync_result = tokio::time::timeout(
                 Duration::from_secs(self.config.max_run_seconds),
-                self.execute_sync_with_retry(connector.as_ref(), sync_params, &connection_id),
+                self.execute_sync_with_retry(connector, sync_params, &connection_id, &mut sink),
             )
             .await
-            .map_err(|_| "Job timed out")??
+            .map_err(|_| JOB_TIMED_OUT)??;
+
+            // Carry forward windows of resources the connector did not advance,
+            // starting from the last page it emitted
+            sync_result.next_cursor = sync_result
+                .next_cursor
+                .map(|next| next.merged_over(sink.resume_cursor()))
+                .or(sink.checkpoint);
+            sync_result
         };
 
         Ok(sync_result)
     }
 
+    /// Open a stored cursor envelope, falling back to a fresh sync when it is rejected
+    fn open_cursor(
+        &self,
+        stored: &serde_json::Value,
+        provider_slug: &str,
+        connection_id: Uuid,
+    ) -> Option<Cursor> {
+        match self
+            .cursor_signer
+            .open(stored, provider_slug, connection_id)
+        {
+            Ok(cursor) => Some(cursor),
+            Err(rejection) => {
+                warn!(
+                    connection_id = %connection_id,
+                    provider = provider_slug,
+                    reason = rejection.reason(),
+                    "Discarding stored sync cursor ({}); syncing from the initial window",
+                    rejection
+                );
+                counter!(
+                    "sync_cursor_resets_total",
+                    "provider" => provider_slug.to_string(),
+                    "reason" => rejection.reason()
+                )
+                .increment(1);
+                None
+            }
+        }
+    }
+
     /// Execute webhook with automatic retry on 401 unauthorized errors
     async fn execute_webhook_with_retry(
         &self,
@@ -413,69 +1095,26 @@ impl SyncExecutor {
             auth_header,
         };
 
-        // First attempt
-        let webhook_result = connector.handle_webhook(webhook_params.clone()).await;
+        // Connectors cannot tell which connection a webhook was queued for, so the
+        // signals are attributed to the job's connection here
+        let into_sync_result = |mut signals: Vec<crate::models::signal::Model>| {
+            for signal in &mut signals {
+                signal.connection_id = connection.id;
+            }
+            SyncResult {
+                signals,
+                next_cursor: None, // Webhooks don't typically update cursors
+                has_more: false,
+            }
+        };
 
-        // For Gmail webhooks, we need to trigger an incremental sync job after processing the webhook
-        if connection.provider_slug == "gmail" && webhook_result.is_ok() {
-            // Extract history ID from the webhook payload for cursor
-            let history_id = webhook_payload
-                .as_ref()
-                .and_then(|payload| payload.get("data").cloned())
-                .and_then(|data| data.as_str().map(|s| s.to_owned()))
-                .and_then(|data_str| STANDARD.decode(data_str).ok())
-                .and_then(|decoded_vec| {
-                    serde_json::from_slice::<serde_json::Value>(&decoded_vec).ok()
-                })
-                .and_then(|gmail_data| gmail_data.get("historyId").cloned())
-                .and_then(|history_id| history_id.as_u64());
-
-            if let Some(history_id) = history_id {
-                info!(
-                    connection_id = %connection_id,
-                    history_id = history_id,
-                    "Gmail webhook processed, triggering incremental sync job"
-                );
+        crate::fault_injection::before_provider_call(&connection.provider_slug).await?;
 
-                // Create a cursor with the history ID to start incremental sync from this point
-                let sync_cursor = Some(serde_json::json!({
-                    "history_id": history_id
-                }));
-
-                // Enqueue a follow-up sync job for incremental sync
-                let sync_job_repo = crate::repositories::SyncJobRepository::new((*self.db).clone());
-                if let Err(e) = sync_job_repo
-                    .enqueue_sync_job(
-                        connection.tenant_id,
-                        &connection.provider_slug,
-                        connection.id,
-                        sync_cursor,
-                    )
-                    .await
-                {
-                    error!(
-                        connection_id = %connection_id,
-                        error = ?e,
-                        "Failed to enqueue follow-up sync job for Gmail"
-                    );
-                }
-            } else {
-                warn!(
-                    connection_id = %connection_id,
-                    "Gmail webhook processed but could not extract history ID for sync"
-                );
-            }
-        }
+        // First attempt
+        let webhook_result = connector.handle_webhook(webhook_params.clone()).await;
 
         match webhook_result {
-            Ok(signals) => {
-                // Convert webhook signals to sync result format
-                Ok(SyncResult {
-                    signals,
-                    next_cursor: None, // Webhooks don't typically update cursors
-                    has_more: false,
-                })
-            }
+            Ok(signals) => Ok(into_sync_result(signals)),
             Err(e) => {
                 // Check if this is an unauthorized error that might be resolved by token refresh
                 let sync_error = e.downcast_ref::<SyncError>().cloned().or_else(|| {
@@ -504,14 +1143,7 @@ impl SyncExecutor {
 
                                 // Retry the webhook once after successful token refresh
                                 match connector.handle_webhook(webhook_params).await {
-                                    Ok(signals) => {
-                                        // Convert webhook signals to sync result format
-                                        Ok(SyncResult {
-                                            signals,
-                                            next_cursor: None,
-                                            has_more: false,
-                                        })
-                                    }
+                                    Ok(signals) => Ok(into_sync_result(signals)),
                                     Err(e) => {
                                         error!(
                                             connection_id = %connection_id,
@@ -552,11 +1184,14 @@ impl SyncExecutor {
     async fn execute_sync_with_retry(
         &self,
         connector: &dyn crate::connectors::Connector,
-        sync_params: SyncParams,
+        mut sync_params: SyncParams,
         connection_id: &Uuid,
+        sink: &mut PersistingSink<'_>,
     ) -> Result<SyncResult, Box<dyn std::error::Error + Send + Sync>> {
+        crate::fault_injection::before_provider_call(&sync_params.connection.provider_slug).await?;
+
         // First attempt
-        match connector.sync(sync_params.clone()).await {
+        match connector.sync_streaming(sync_params.clone(), sink).await {
             Ok(result) => Ok(result),
             Err(e) => {
                 // Check if this is an unauthorized error that might be resolved by token refresh
@@ -585,8 +1220,12 @@ impl SyncExecutor {
                                 "Token refresh successful, retrying sync operation"
                             );
 
-                            // Retry the sync operation once with refreshed tokens
-                            let retry_result = connector.sync(sync_params).await?;
+                            // Retry the sync operation once with refreshed tokens,
+                            // resuming after any pages already persisted
+                            sync_params.connection =
+                                self.token_vault.open(sync_params.connection).await?;
+                            sync_params.cursor = sink.resume_cursor().cloned();
+                            let retry_result = connector.sync_streaming(sync_params, sink).await?;
                             Ok(retry_result)
                         }
                         Ok(refresh_result) => {
@@ -614,48 +1253,74 @@ impl SyncExecutor {
         }
     }
 
-    /// Handle successful job completion
+    /// Handle successful job completion, returning how the run ended.
+    ///
+    /// `items` counts every signal the run emitted, including streamed pages.
     async fn handle_success(
         &self,
         job: &sync_job::Model,
         sync_result: SyncResult,
-    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        items: usize,
+    ) -> Result<&'static str, Box<dyn std::error::Error + Send + Sync>> {
         let txn = self.db.begin().await?;
-        let now = Utc::now();
+        let now = self.clock.now();
 
-        // Persist signals
-        if !sync_result.signals.is_empty() {
-            for signal in &sync_result.signals {
-                let active_signal: SignalActiveModel = signal.clone().into();
-                active_signal.insert(&txn).await?;
-            }
+        let connection = ConnectionEntity::find_by_id(job.connection_id)
+            .one(&txn)
+            .await?
+            .ok_or("Connection not found")?;
+        let mut sync_metadata =
+            ConnectionSyncMetadata::from_connection_metadata(connection.metadata.as_ref());
+        // Dry-run connections only log what a sync would store
+        let dry_run = sync_metadata.dry_run;
+        // A reaped job may already be running elsewhere; its results are discarded
+        let Some(current) = self.held_job(&txn, job.id).await? else {
+            warn!(
+                "Job {} lost its lease before completing, discarding its results",
+                job.id
+            );
+            return Ok(OUTCOME_INTERRUPTED);
+        };
+        // A job cancelled mid-run keeps the pages it fetched but queues no more
+        let cancelled = current.status == "cancelling";
+
+        if dry_run {
+            log_dry_run(job, &sync_result.signals);
+        } else {
+            self.persist_signals(&txn, job, &sync_result.signals)
+                .await?;
         }
 
         // Update connection cursor if provided
-        if sync_result.next_cursor.is_some() {
-            let connection = ConnectionEntity::find_by_id(job.connection_id)
-                .one(&txn)
-                .await?
-                .ok_or("Connection not found")?;
+        let mut metadata_changed = false;
+        if !dry_run && let Some(next_cursor) = &sync_result.next_cursor {
+            sync_metadata.cursor = Some(self.cursor_signer.seal_json(
+                &job.provider_slug,
+                job.connection_id,
+                next_cursor,
+            ));
+            metadata_changed = true;
+        }
 
-            if let Some(next_cursor) = &sync_result.next_cursor {
-                let mut sync_metadata =
-                    ConnectionSyncMetadata::from_connection_metadata(connection.metadata.as_ref());
-                sync_metadata.cursor = Some(next_cursor.clone());
+        // Cycles start without a job cursor; follow-up pages carry one. The
+        // scheduler adapts the connection's interval to the recorded streak.
+        if !dry_run && !cancelled && job.job_type != "webhook" && job.cursor.is_none() {
+            metadata_changed |= sync_metadata.record_activity(sync_result.has_more, items == 0);
+        }
 
-                let updated_metadata =
-                    sync_metadata.into_connection_metadata(connection.metadata.as_ref());
+        if metadata_changed {
+            let updated_metadata =
+                sync_metadata.into_connection_metadata(connection.metadata.as_ref());
 
-                let mut active_connection: ConnectionActiveModel = connection.into();
-                active_connection.metadata = Set(Some(updated_metadata));
-                active_connection.updated_at = Set(now.into());
-                active_connection.update(&txn).await?;
-            }
+            let mut active_connection: ConnectionActiveModel = connection.into();
+            active_connection.metadata = Set(Some(updated_metadata));
+            active_connection.updated_at = Set(now.into());
+            active_connection.update(&txn).await?;
         }
 
         // Update job status to succeeded
         let mut active_job: SyncJobActiveModel = job.clone().into();
-        active_job.status = Set("succeeded".to_string());
+        active_job.status = Set(if cancelled { "cancelled" } else { "succeeded" }.to_string());
         active_job.finished_at = Set(Some(now.into()));
         active_job.updated_at = Set(now.into());
         active_job.update(&txn).await?;
@@ -664,11 +1329,15 @@ impl SyncExecutor {
         let signal_count = sync_result.signals.len();
 
         // If has_more, create follow-up incremental job
-        if sync_result.has_more
+        if !dry_run
+            && !cancelled
+            && sync_result.has_more
             && sync_result.next_cursor.is_some()
             && let Some(next_cursor) = sync_result.next_cursor
         {
-            let cursor_json = serde_json::to_value(next_cursor)?;
+            let cursor_json =
+                self.cursor_signer
+                    .seal_json(&job.provider_slug, job.connection_id, &next_cursor);
             let follow_up_job = SyncJobActiveModel {
                 id: Set(Uuid::new_v4()),
                 tenant_id: Set(job.tenant_id),
@@ -678,22 +1347,29 @@ impl SyncExecutor {
                 status: Set("queued".to_string()),
                 priority: Set(job.priority),
                 attempts: Set(0),
+                rate_limited_attempts: Set(0),
                 scheduled_at: Set(now.into()),
                 retry_after: Set(None),
                 started_at: Set(None),
                 finished_at: Set(None),
+                locked_at: Set(None),
+                heartbeat_at: Set(None),
+                locked_by: Set(None),
                 cursor: Set(Some(cursor_json)),
                 error: Set(None),
                 created_at: Set(now.into()),
                 updated_at: Set(now.into()),
             };
-            follow_up_job.insert(&txn).await?;
+            SyncJobEntity::insert(follow_up_job)
+                .exec_without_returning(&txn)
+                .await?;
         }
 
         txn.commit().await?;
 
         info!(
-            "Successfully completed job {} with {} signals{}",
+            "Successfully {} job {} with {} signals{}",
+            if cancelled { "cancelled" } else { "completed" },
             job.id,
             signal_count,
             if sync_result.has_more {
@@ -703,18 +1379,142 @@ impl SyncExecutor {
             }
         );
 
+        Ok(if cancelled {
+            OUTCOME_CANCELLED
+        } else {
+            OUTCOME_SUCCEEDED
+        })
+    }
+
+    /// Persist a page a streaming sync emitted, with its cursor as a checkpoint
+    /// so a job that fails later resumes after it. Returns `false` for dry-run
+    /// connections, whose pages are only logged.
+    async fn persist_page(
+        &self,
+        job: &sync_job::Model,
+        signals: &[crate::models::signal::Model],
+        cursor: Option<&Cursor>,
+    ) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
+        let txn = self.db.begin().await?;
+        if self.held_job(&txn, job.id).await?.is_none() {
+            return Err("Job lost its lease while streaming results".into());
+        }
+        let connection = ConnectionEntity::find_by_id(job.connection_id)
+            .one(&txn)
+            .await?
+            .ok_or("Connection not found")?;
+        let mut sync_metadata =
+            ConnectionSyncMetadata::from_connection_metadata(connection.metadata.as_ref());
+        if sync_metadata.dry_run {
+            log_dry_run(job, signals);
+            return Ok(false);
+        }
+
+        self.persist_signals(&txn, job, signals).await?;
+        if let Some(cursor) = cursor {
+            sync_metadata.cursor = Some(self.cursor_signer.seal_json(
+                &job.provider_slug,
+                job.connection_id,
+                cursor,
+            ));
+            let updated_metadata =
+                sync_metadata.into_connection_metadata(connection.metadata.as_ref());
+            let mut active_connection: ConnectionActiveModel = connection.into();
+            active_connection.metadata = Set(Some(updated_metadata));
+            active_connection.updated_at = Set(self.clock.now().into());
+            active_connection.update(&txn).await?;
+        }
+        txn.commit().await?;
+
+        debug!(job_id = %job.id, signals = signals.len(), "Persisted streamed page");
+        Ok(true)
+    }
+
+    /// Insert signals in batches, together with their outbox entries so downstream
+    /// delivery commits or rolls back atomically with the signal itself
+    async fn persist_signals<C: ConnectionTrait>(
+        &self,
+        txn: &C,
+        job: &sync_job::Model,
+        signals: &[crate::models::signal::Model],
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let max_payload_bytes = self.config.signal_payload.max_bytes_for(&job.provider_slug);
+        for batch in signals.chunks(SIGNAL_INSERT_BATCH) {
+            let mut batch = batch.to_vec();
+            for signal in &mut batch {
+                if payload_limit::enforce_limit(&mut signal.payload, max_payload_bytes) {
+                    warn!(
+                        job_id = %job.id,
+                        provider = %job.provider_slug,
+                        signal_kind = %signal.kind,
+                        original_size_bytes = ?signal.payload.get(payload_limit::ORIGINAL_SIZE_FIELD),
+                        max_payload_bytes,
+                        "Signal payload exceeded size limit and was truncated"
+                    );
+                    metrics::counter!("signal_payload_truncated_total", "provider" => job.provider_slug.clone())
+                        .increment(1);
+                }
+            }
+            // Every column is set, so the rows need no read-back (which SQLite
+            // cannot do for UUID keys)
+            SignalEntity::insert_many(batch.iter().cloned().map(SignalActiveModel::from))
+                .exec_without_returning(txn)
+                .await?;
+            for persisted in &batch {
+                SignalOutboxRepository::enqueue_for_signal(txn, persisted, SIGNAL_TOPICS).await?;
+                SignalPipelineEventRepository::record(
+                    txn,
+                    persisted,
+                    STAGE_INGESTED,
+                    Some(serde_json::json!({
+                        "sync_job_id": job.id,
+                        "job_type": job.job_type,
+                    })),
+                )
+                .await?;
+            }
+        }
         Ok(())
     }
 
-    /// Handle job failure
+    /// Handle job failure, returning how the run ended
     async fn handle_failure(
         &self,
         job: &sync_job::Model,
         error_msg: &str,
         sync_error: Option<&SyncError>,
-    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    ) -> Result<&'static str, Box<dyn std::error::Error + Send + Sync>> {
+        let rate_limit_policy = self
+            .tenant_layers(job.tenant_id)
+            .await
+            .rate_limit_policy(&self.settings.current().rate_limit_policy);
         let txn = self.db.begin().await?;
-        let now = Utc::now();
+        let now = self.clock.now();
+
+        let Some(current) = self.held_job(&txn, job.id).await? else {
+            warn!(
+                "Job {} lost its lease before failing, leaving it to its new holder: {}",
+                job.id, error_msg
+            );
+            return Ok(OUTCOME_INTERRUPTED);
+        };
+        // A job cancelled mid-run is not retried
+        if current.status == "cancelling" {
+            let mut active_job: SyncJobActiveModel = job.clone().into();
+            active_job.status = Set("cancelled".to_string());
+            active_job.error = Set(Some(serde_json::json!({
+                "message": error_msg,
+                "attempts": job.attempts,
+                "timestamp": now.to_rfc3339(),
+            })));
+            active_job.finished_at = Set(Some(now.into()));
+            active_job.updated_at = Set(now.into());
+            active_job.update(&txn).await?;
+            txn.commit().await?;
+
+            info!("Job {} cancelled after failing: {}", job.id, error_msg);
+            return Ok(OUTCOME_CANCELLED);
+        }
 
         // job.attempts already includes the current attempt (incremented during claim)
         let attempts_completed = job.attempts.max(0);
@@ -722,23 +1522,40 @@ impl SyncExecutor {
 
         // Calculate backoff using rate limit policy if we have a SyncError
         let (backoff_seconds, is_rate_limited) = if let Some(sync_err) = sync_error {
-            self.calculate_backoff(sync_err, prior_failures, &job.provider_slug)
+            Self::calculate_backoff(
+                &rate_limit_policy,
+                sync_err,
+                prior_failures,
+                &job.provider_slug,
+            )
         } else {
-            // Fallback to old logic for non-SyncError cases
-            let base_seconds = 5.0;
-            let max_seconds = 900.0; // 15 minutes
-            let jitter_factor = 0.1;
-            let exp_backoff = (base_seconds * 2_f64.powi(prior_failures)).min(max_seconds);
-            let jitter = thread_rng().gen_range(0.0..(jitter_factor * exp_backoff));
-            (exp_backoff + jitter, false)
+            // Fallback schedule for failures without a classified SyncError
+            let backoff = FALLBACK_BACKOFF.sample(prior_failures as u32);
+            (backoff.as_secs_f64(), false)
         };
 
         let retry_after = now + chrono::Duration::seconds(backoff_seconds as i64);
 
-        // Build error details
-        let mut error_details = serde_json::json!({
+        // Rate limits and other failures draw on separate retry budgets
+        let rate_limited_attempts = job.rate_limited_attempts + i32::from(is_rate_limited);
+        let (used, budget) = if is_rate_limited {
+            (
+                rate_limited_attempts,
+                rate_limit_policy.max_rate_limited_attempts_for(&job.provider_slug),
+            )
+        } else {
+            (
+                attempts_completed - rate_limited_attempts,
+                rate_limit_policy.max_attempts_for(&job.provider_slug),
+            )
+        };
+        let exhausted = i64::from(used) >= i64::from(budget);
+
+        // Build error details
+        let mut error_details = serde_json::json!({
             "message": error_msg,
             "attempts": attempts_completed,
+            "rate_limited_attempts": rate_limited_attempts,
             "backoff_seconds": backoff_seconds,
             "timestamp": now.to_rfc3339(),
         });
@@ -756,10 +1573,42 @@ impl SyncExecutor {
             }
         }
 
+        if exhausted {
+            error_details["retries_exhausted"] = serde_json::Value::Bool(true);
+            let mut active_job: SyncJobActiveModel = job.clone().into();
+            active_job.status = Set("failed".to_string());
+            active_job.attempts = Set(attempts_completed);
+            active_job.rate_limited_attempts = Set(rate_limited_attempts);
+            active_job.retry_after = Set(None);
+            active_job.error = Set(Some(error_details));
+            active_job.finished_at = Set(Some(now.into()));
+            active_job.updated_at = Set(now.into());
+            active_job.update(&txn).await?;
+            txn.commit().await?;
+
+            let budget_kind = if is_rate_limited {
+                "rate_limited"
+            } else {
+                "failure"
+            };
+            counter!(
+                "sync_jobs_retries_exhausted_total",
+                "provider" => job.provider_slug.clone(),
+                "budget" => budget_kind
+            )
+            .increment(1);
+            error!(
+                "Job {} failed permanently after {} attempts ({} rate limited): {}",
+                job.id, attempts_completed, rate_limited_attempts, error_msg
+            );
+            return Ok(OUTCOME_FAILED);
+        }
+
         // Update job status back to queued with retry_after
         let mut active_job: SyncJobActiveModel = job.clone().into();
         active_job.status = Set("queued".to_string());
         active_job.attempts = Set(attempts_completed);
+        active_job.rate_limited_attempts = Set(rate_limited_attempts);
         active_job.retry_after = Set(Some(retry_after.into()));
         active_job.error = Set(Some(error_details));
         active_job.updated_at = Set(now.into());
@@ -779,10 +1628,140 @@ impl SyncExecutor {
             );
         }
 
+        Ok(OUTCOME_FAILED)
+    }
+
+    /// The job's current row, locked for the transaction, while this instance
+    /// still holds its lease
+    async fn held_job<C: ConnectionTrait>(
+        &self,
+        txn: &C,
+        job_id: Uuid,
+    ) -> Result<Option<sync_job::Model>, DbErr> {
+        Ok(SyncJobEntity::find_by_id(job_id)
+            .lock_exclusive()
+            .one(txn)
+            .await?
+            .filter(|job| {
+                ACTIVE_STATUSES.contains(&job.status.as_str())
+                    && job.locked_by.as_deref() == Some(self.config.instance_id.as_str())
+            }))
+    }
+}
+
+/// Log what a sync would store for a dry-run connection
+fn log_dry_run(job: &sync_job::Model, signals: &[crate::models::signal::Model]) {
+    for signal in signals {
+        info!(
+            job_id = %job.id,
+            connection_id = %job.connection_id,
+            provider = %job.provider_slug,
+            signal_kind = %signal.kind,
+            occurred_at = %signal.occurred_at,
+            dedupe_key = ?signal.dedupe_key,
+            payload = %signal.payload,
+            "Dry run: signal not persisted"
+        );
+    }
+}
+
+/// Sink that persists each page of a streaming sync as soon as it is emitted
+struct PersistingSink<'a> {
+    executor: &'a SyncExecutor,
+    job: &'a sync_job::Model,
+    started_from: Option<Cursor>,
+    /// Cursor stored with the last persisted page
+    checkpoint: Option<Cursor>,
+    /// Signals emitted so far, shared with the run that records them
+    emitted: &'a AtomicUsize,
+    /// Cancelled once `max_items_per_run` signals were emitted, so the connector
+    /// stops at the next page and a follow-up job continues
+    stop: CancellationToken,
+}
+
+impl PersistingSink<'_> {
+    /// Cursor a restarted sync resumes from
+    fn resume_cursor(&self) -> Option<&Cursor> {
+        self.checkpoint.as_ref().or(self.started_from.as_ref())
+    }
+}
+
+#[async_trait::async_trait]
+impl SyncSink for PersistingSink<'_> {
+    async fn emit(
+        &mut self,
+        page: SyncPage,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let cursor = page
+            .next_cursor
+            .map(|next| next.merged_over(self.resume_cursor()));
+        let stored = self
+            .executor
+            .persist_page(self.job, &page.signals, cursor.as_ref())
+            .await?;
+        if stored && cursor.is_some() {
+            self.checkpoint = cursor;
+        }
+        let emitted = self
+            .emitted
+            .fetch_add(page.signals.len(), Ordering::Relaxed)
+            + page.signals.len();
+        if emitted >= self.executor.config.max_items_per_run {
+            self.stop.cancel();
+        }
         Ok(())
     }
 }
 
+/// Renew the job's lease while `instance_id` holds it and cancel `token` once
+/// the job is flagged `cancelling` or the lease was lost; runs until aborted
+async fn hold_lease(
+    db: std::sync::Arc<DatabaseConnection>,
+    clock: SharedClock,
+    job_id: Uuid,
+    instance_id: String,
+    token: CancellationToken,
+) {
+    loop {
+        let renewed = SyncJobEntity::update_many()
+            .col_expr(sync_job::Column::HeartbeatAt, Expr::value(clock.now()))
+            .filter(sync_job::Column::Id.eq(job_id))
+            .filter(sync_job::Column::Status.is_in(ACTIVE_STATUSES))
+            .filter(sync_job::Column::LockedBy.eq(instance_id.as_str()))
+            .exec(&*db)
+            .await;
+        match renewed {
+            Ok(result) if result.rows_affected == 0 => {
+                // Reaped after a stall and possibly claimed by another instance
+                warn!(job_id = %job_id, "Lost the job lease, stopping at the next page");
+                token.cancel();
+                return;
+            }
+            Ok(_) => {}
+            Err(e) => warn!(job_id = %job_id, "Failed to renew job lease: {}", e),
+        }
+
+        match is_cancelling(&*db, job_id).await {
+            Ok(true) => {
+                info!(job_id = %job_id, "Cancellation requested, stopping at the next page");
+                token.cancel();
+                return;
+            }
+            Ok(false) => {}
+            Err(e) => debug!(job_id = %job_id, "Failed to check job for cancellation: {}", e),
+        }
+        clock.sleep(LEASE_HEARTBEAT_INTERVAL).await;
+    }
+}
+
+/// Whether an operator asked to cancel the job while it ran
+async fn is_cancelling<C: ConnectionTrait>(db: &C, job_id: Uuid) -> Result<bool, DbErr> {
+    Ok(SyncJobEntity::find_by_id(job_id)
+        .one(db)
+        .await?
+        .is_some_and(|job| job.status == "cancelling"))
+}
+
 // Implement Clone for the executor to allow it to be used in spawned tasks
 impl Clone for SyncExecutor {
     fn clone(&self) -> Self {
@@ -790,9 +1769,111 @@ impl Clone for SyncExecutor {
             db: self.db.clone(),
             registry: self.registry.clone(),
             config: self.config.clone(),
-            rate_limit_policy: self.rate_limit_policy.clone(),
+            settings: self.settings.clone(),
             token_refresh_service: self.token_refresh_service.clone(),
+            token_vault: self.token_vault.clone(),
+            cursor_signer: self.cursor_signer.clone(),
+            clock: self.clock.clone(),
+            shutdown: self.shutdown.clone(),
+        }
+    }
+}
+
+/// Free running-job slots per provider, per (tenant, provider) and per tenant while
+/// claiming a batch
+struct RunningSlots<'a> {
+    caps: &'a crate::config::ProviderConcurrencyConfig,
+    tenant_cap: Option<usize>,
+    running: std::collections::HashMap<String, usize>,
+    running_per_tenant: std::collections::HashMap<(Uuid, String), usize>,
+    running_by_tenant: std::collections::HashMap<Uuid, usize>,
+}
+
+impl<'a> RunningSlots<'a> {
+    fn new(
+        caps: &'a crate::config::ProviderConcurrencyConfig,
+        tenant_cap: Option<usize>,
+        running_jobs: impl IntoIterator<Item = (Uuid, String)>,
+    ) -> Self {
+        let mut slots = Self {
+            caps,
+            tenant_cap,
+            running: std::collections::HashMap::new(),
+            running_per_tenant: std::collections::HashMap::new(),
+            running_by_tenant: std::collections::HashMap::new(),
+        };
+        for (tenant_id, provider_slug) in running_jobs {
+            slots.record(tenant_id, &provider_slug);
+        }
+        slots
+    }
+
+    fn record(&mut self, tenant_id: Uuid, provider_slug: &str) {
+        *self.running.entry(provider_slug.to_string()).or_default() += 1;
+        *self
+            .running_per_tenant
+            .entry((tenant_id, provider_slug.to_string()))
+            .or_default() += 1;
+        *self.running_by_tenant.entry(tenant_id).or_default() += 1;
+    }
+
+    /// Providers already at their global cap, excluded from the claim query
+    fn saturated_providers(&self) -> Vec<String> {
+        self.running
+            .iter()
+            .filter(|(provider, count)| {
+                self.caps
+                    .max_running_for(provider)
+                    .is_some_and(|cap| **count >= cap)
+            })
+            .map(|(provider, _)| provider.clone())
+            .collect()
+    }
+
+    /// Tenants already at the per-tenant cap, excluded from the claim query
+    fn saturated_tenants(&self) -> Vec<Uuid> {
+        let Some(cap) = self.tenant_cap else {
+            return Vec::new();
+        };
+        self.running_by_tenant
+            .iter()
+            .filter(|(_, count)| **count >= cap)
+            .map(|(tenant_id, _)| *tenant_id)
+            .collect()
+    }
+
+    /// Reserve a slot for a job, returning `false` when a cap is reached
+    fn try_take(&mut self, tenant_id: Uuid, provider_slug: &str) -> bool {
+        let running_by_tenant = self
+            .running_by_tenant
+            .get(&tenant_id)
+            .copied()
+            .unwrap_or_default();
+        if self.tenant_cap.is_some_and(|cap| running_by_tenant >= cap) {
+            return false;
         }
+        let running = self.running.get(provider_slug).copied().unwrap_or_default();
+        if self
+            .caps
+            .max_running_for(provider_slug)
+            .is_some_and(|cap| running >= cap)
+        {
+            return false;
+        }
+        let running_for_tenant = self
+            .running_per_tenant
+            .get(&(tenant_id, provider_slug.to_string()))
+            .copied()
+            .unwrap_or_default();
+        if self
+            .caps
+            .max_running_per_tenant_for(provider_slug)
+            .is_some_and(|cap| running_for_tenant >= cap)
+        {
+            return false;
+        }
+        self.record(tenant_id, provider_slug);
+        true
     }
 }
 
@@ -808,56 +1889,69 @@ mod tests {
             max_seconds: 900,
             jitter_factor: 0.1,
             provider_overrides: BTreeMap::new(),
+            ..Default::default()
         }
     }
 
-    async fn create_test_executor(policy: crate::config::RateLimitPolicyConfig) -> SyncExecutor {
-        let db = sea_orm::Database::connect("sqlite::memory:")
-            .await
-            .expect("Failed to create in-memory database");
+    fn create_test_executor_with(
+        db: DatabaseConnection,
+        config: ExecutorConfig,
+        policy: crate::config::RateLimitPolicyConfig,
+    ) -> SyncExecutor {
         let registry = Registry::new();
-        let config = ExecutorConfig::default();
 
         // Create required dependencies for TokenRefreshService
         let crypto_key = crate::crypto::CryptoKey::new(vec![0u8; 32])
             .expect("Failed to create crypto key for sync executor");
+        let cursor_signer = CursorSigner::new(&crypto_key);
         use crate::repositories::ConnectionRepository;
         let connection_repo =
             ConnectionRepository::new(std::sync::Arc::new(db.clone()), crypto_key);
+        let token_vault = TokenVault::from(connection_repo.clone());
 
         // Create TokenRefreshService
-        let token_refresh_service = std::sync::Arc::new(TokenRefreshService::new(
-            std::sync::Arc::new(crate::config::AppConfig::default()),
-            std::sync::Arc::new(db.clone()),
-            std::sync::Arc::new(connection_repo),
-            registry.clone(),
-        ));
-
-        SyncExecutor::new(db, registry, config, policy, token_refresh_service)
+        let token_refresh_service =
+            std::sync::Arc::new(crate::token_refresh::TokenRefreshService::new(
+                std::sync::Arc::new(crate::config::AppConfig::default()),
+                std::sync::Arc::new(db.clone()),
+                std::sync::Arc::new(connection_repo),
+                registry.clone(),
+            ));
+
+        SyncExecutor::new(
+            db,
+            registry,
+            config,
+            policy,
+            token_refresh_service,
+            token_vault,
+            cursor_signer,
+        )
     }
 
-    #[tokio::test]
-    async fn test_calculate_backoff_default_policy() {
+    #[test]
+    fn test_calculate_backoff_default_policy() {
         let policy = create_test_rate_limit_policy();
-        let executor = create_test_executor(policy).await;
 
         let sync_error = SyncError::rate_limited(None);
 
         // Test exponential backoff with attempts
         let (backoff1, is_rate_limited) =
-            executor.calculate_backoff(&sync_error, 0, "test_provider");
+            SyncExecutor::calculate_backoff(&policy, &sync_error, 0, "test_provider");
         assert!(is_rate_limited);
-        assert!((backoff1 >= 5.0 && backoff1 <= 5.5)); // base * 2^0 = 5, jitter may add 0-0.5
+        assert!((5.0..=5.5).contains(&backoff1)); // base * 2^0 = 5, jitter may add 0-0.5
 
-        let (backoff2, _) = executor.calculate_backoff(&sync_error, 1, "test_provider");
-        assert!((backoff2 >= 10.0 && backoff2 <= 11.0)); // base * 2^1 = 10, jitter may add 0-1
+        let (backoff2, _) =
+            SyncExecutor::calculate_backoff(&policy, &sync_error, 1, "test_provider");
+        assert!((10.0..=11.0).contains(&backoff2)); // base * 2^1 = 10, jitter may add 0-1
 
-        let (backoff3, _) = executor.calculate_backoff(&sync_error, 2, "test_provider");
-        assert!((backoff3 >= 20.0 && backoff3 <= 22.0)); // base * 2^2 = 20, jitter may add 0-2
+        let (backoff3, _) =
+            SyncExecutor::calculate_backoff(&policy, &sync_error, 2, "test_provider");
+        assert!((20.0..=22.0).contains(&backoff3)); // base * 2^2 = 20, jitter may add 0-2
     }
 
-    #[tokio::test]
-    async fn test_calculate_backoff_with_provider_override() {
+    #[test]
+    fn test_calculate_backoff_with_provider_override() {
         let mut provider_overrides = BTreeMap::new();
         provider_overrides.insert(
             "github".to_string(),
@@ -865,6 +1959,8 @@ mod tests {
                 base_seconds: Some(10),
                 max_seconds: Some(1800),
                 jitter_factor: Some(0.2),
+                max_concurrent: None,
+                ..Default::default()
             },
         );
 
@@ -873,45 +1969,46 @@ mod tests {
             max_seconds: 900,
             jitter_factor: 0.1,
             provider_overrides,
+            ..Default::default()
         };
 
-        let executor = create_test_executor(policy).await;
         let sync_error = SyncError::rate_limited(None);
 
         // Test that github provider gets override settings
-        let (backoff, _) = executor.calculate_backoff(&sync_error, 0, "github");
-        assert!((backoff >= 10.0 && backoff <= 12.0)); // override base = 10, jitter 0-2
+        let (backoff, _) = SyncExecutor::calculate_backoff(&policy, &sync_error, 0, "github");
+        assert!((10.0..=12.0).contains(&backoff)); // override base = 10, jitter 0-2
 
         // Test that non-override provider gets default settings
-        let (backoff, _) = executor.calculate_backoff(&sync_error, 0, "jira");
-        assert!((backoff >= 5.0 && backoff <= 5.5)); // default base = 5, jitter 0-0.5
+        let (backoff, _) = SyncExecutor::calculate_backoff(&policy, &sync_error, 0, "jira");
+        assert!((5.0..=5.5).contains(&backoff)); // default base = 5, jitter 0-0.5
     }
 
-    #[tokio::test]
-    async fn test_calculate_backoff_retry_after_precedence() {
+    #[test]
+    fn test_calculate_backoff_retry_after_precedence() {
         let policy = create_test_rate_limit_policy();
-        let executor = create_test_executor(policy).await;
 
         // Test that retry_after_secs takes precedence over calculated backoff when larger
         let sync_error = SyncError::rate_limited(Some(300)); // 5 minutes
-        let (backoff, _) = executor.calculate_backoff(&sync_error, 0, "test_provider");
-        assert!((backoff >= 300.0 && backoff <= 330.0)); // Should use retry_after (300) not calculated (5), jitter up to 30
+        let (backoff, _) =
+            SyncExecutor::calculate_backoff(&policy, &sync_error, 0, "test_provider");
+        assert!((300.0..=330.0).contains(&backoff)); // Should use retry_after (300) not calculated (5), jitter up to 30
 
         // Test that retry_after_secs takes precedence over calculated backoff when smaller
         let sync_error = SyncError::rate_limited(Some(2)); // 2 seconds
-        let (backoff, _) = executor.calculate_backoff(&sync_error, 3, "test_provider"); // 3 attempts = 5*2^3 = 40
-        assert!((backoff >= 40.0 && backoff <= 44.0)); // Should use calculated (40) not retry_after (2), jitter up to 4
+        let (backoff, _) =
+            SyncExecutor::calculate_backoff(&policy, &sync_error, 3, "test_provider"); // 3 attempts = 5*2^3 = 40
+        assert!((40.0..=44.0).contains(&backoff)); // Should use calculated (40) not retry_after (2), jitter up to 4
     }
 
-    #[tokio::test]
-    async fn test_calculate_backoff_max_capping() {
+    #[test]
+    fn test_calculate_backoff_max_capping() {
         let policy = create_test_rate_limit_policy();
-        let executor = create_test_executor(policy).await;
 
         let sync_error = SyncError::rate_limited(None);
 
         // Test with high attempts to exceed max
-        let (backoff, _) = executor.calculate_backoff(&sync_error, 10, "test_provider");
+        let (backoff, _) =
+            SyncExecutor::calculate_backoff(&policy, &sync_error, 10, "test_provider");
         assert!(backoff <= 900.0 + (900.0 * 0.1)); // Should not exceed max + jitter
         assert!(backoff >= 900.0); // Should be at least max
     }
@@ -988,4 +2085,1026 @@ mod tests {
         let sync_error = SyncError::from(network_error);
         matches!(sync_error.kind, SyncErrorKind::Permanent);
     }
+
+    #[test]
+    fn test_running_slots_respect_global_and_tenant_caps() {
+        let caps = crate::config::ProviderConcurrencyConfig {
+            max_running_per_tenant: 2,
+            provider_max_running: BTreeMap::from([("gmail".to_string(), 3)]),
+            ..Default::default()
+        };
+        let (tenant_a, tenant_b) = (Uuid::new_v4(), Uuid::new_v4());
+        let mut slots = RunningSlots::new(&caps, None, [(tenant_a, "gmail".to_string())]);
+
+        assert!(slots.saturated_providers().is_empty());
+        assert!(slots.try_take(tenant_a, "gmail"));
+        // Tenant A has two running gmail jobs
+        assert!(!slots.try_take(tenant_a, "gmail"));
+        assert!(slots.try_take(tenant_b, "gmail"));
+        // Gmail is at its global cap of three
+        assert!(!slots.try_take(tenant_b, "gmail"));
+        assert_eq!(slots.saturated_providers(), vec!["gmail".to_string()]);
+        // Other providers only see the per-tenant default
+        assert!(slots.try_take(tenant_a, "github"));
+        assert!(slots.try_take(tenant_a, "github"));
+        assert!(!slots.try_take(tenant_a, "github"));
+    }
+
+    #[tokio::test]
+    async fn test_claim_jobs_caps_running_jobs_per_provider() {
+        use migration::{Migrator, MigratorTrait};
+
+        let db = sea_orm::Database::connect("sqlite::memory:")
+            .await
+            .expect("Failed to create in-memory database");
+        Migrator::up(&db, None).await.expect("apply migrations");
+
+        let tenant_id = Uuid::new_v4();
+        let now = chrono::Utc::now().fixed_offset();
+        crate::models::tenant::Entity::insert(crate::models::tenant::ActiveModel {
+            id: Set(tenant_id),
+            name: Set(Some("Test Tenant".to_string())),
+            created_at: Set(now),
+            ..Default::default()
+        })
+        .exec_without_returning(&db)
+        .await
+        .unwrap();
+
+        for slug in ["gmail", "github"] {
+            crate::models::provider::Entity::insert(crate::models::provider::ActiveModel {
+                slug: Set(slug.to_string()),
+                display_name: Set(slug.to_string()),
+                auth_type: Set("oauth2".to_string()),
+                created_at: Set(now),
+                updated_at: Set(now),
+            })
+            .exec_without_returning(&db)
+            .await
+            .unwrap();
+        }
+
+        // A gmail backlog queued ahead of one github job
+        let mut queue = vec!["gmail"; 5];
+        queue.push("github");
+        for (position, provider_slug) in queue.into_iter().enumerate() {
+            let connection_id = Uuid::new_v4();
+            ConnectionEntity::insert(ConnectionActiveModel {
+                id: Set(connection_id),
+                tenant_id: Set(tenant_id),
+                provider_slug: Set(provider_slug.to_string()),
+                external_id: Set(format!("{}-{}", provider_slug, position)),
+                status: Set("active".to_string()),
+                display_name: Set(None),
+                expires_at: Set(None),
+                scopes: Set(None),
+                metadata: Set(None),
+                created_at: Set(now),
+                updated_at: Set(now),
+            })
+            .exec_without_returning(&db)
+            .await
+            .unwrap();
+            SyncJobEntity::insert(SyncJobActiveModel {
+                id: Set(Uuid::new_v4()),
+                tenant_id: Set(tenant_id),
+                provider_slug: Set(provider_slug.to_string()),
+                connection_id: Set(connection_id),
+                job_type: Set("incremental".to_string()),
+                status: Set("queued".to_string()),
+                priority: Set(0),
+                attempts: Set(0),
+                rate_limited_attempts: Set(0),
+                scheduled_at: Set(now - chrono::Duration::minutes(10 - position as i64)),
+                retry_after: Set(None),
+                started_at: Set(None),
+                finished_at: Set(None),
+                locked_at: Set(None),
+                heartbeat_at: Set(None),
+                locked_by: Set(None),
+                cursor: Set(None),
+                error: Set(None),
+                created_at: Set(now),
+                updated_at: Set(now),
+            })
+            .exec_without_returning(&db)
+            .await
+            .unwrap();
+        }
+
+        let config = ExecutorConfig {
+            claim_batch: 3,
+            provider_concurrency: crate::config::ProviderConcurrencyConfig {
+                provider_max_running: BTreeMap::from([("gmail".to_string(), 2)]),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let executor = create_test_executor_with(db, config, create_test_rate_limit_policy());
+
+        let mut claimed: Vec<String> = executor
+            .claim_jobs(usize::MAX)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|job| job.provider_slug)
+            .collect();
+        claimed.sort();
+        assert_eq!(claimed, vec!["github", "gmail", "gmail"]);
+
+        // Both gmail slots stay taken until those jobs finish
+        assert!(executor.claim_jobs(usize::MAX).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_claims_respect_provider_cap() {
+        let config = |instance_id: &str| ExecutorConfig {
+            instance_id: instance_id.to_string(),
+            provider_concurrency: crate::config::ProviderConcurrencyConfig {
+                provider_max_running: BTreeMap::from([("gmail".to_string(), 2)]),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // The interleaving varies between runs, so race a few fresh queues
+        for _ in 0..10 {
+            let tenant_id = Uuid::new_v4();
+            let db =
+                seed_queued_jobs_in_postgres("provider_cap_race", &[(tenant_id, "gmail"); 6]).await;
+            let first = create_test_executor_with(
+                db.clone(),
+                config("first"),
+                create_test_rate_limit_policy(),
+            );
+            let second =
+                create_test_executor_with(db, config("second"), create_test_rate_limit_policy());
+
+            // Both instances see two free gmail slots until one of them claims
+            let (a, b) = tokio::join!(first.claim_jobs(usize::MAX), second.claim_jobs(usize::MAX));
+            assert_eq!(a.unwrap().len() + b.unwrap().len(), 2);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_claims_respect_tenant_cap() {
+        let config = |instance_id: &str| ExecutorConfig {
+            instance_id: instance_id.to_string(),
+            max_running_per_tenant: 2,
+            ..Default::default()
+        };
+
+        for _ in 0..10 {
+            let tenant_id = Uuid::new_v4();
+            let mut queue = vec![(tenant_id, "gmail"); 3];
+            queue.extend([(tenant_id, "github"); 3]);
+            let db = seed_queued_jobs_in_postgres("tenant_cap_race", &queue).await;
+            let first = create_test_executor_with(
+                db.clone(),
+                config("first"),
+                create_test_rate_limit_policy(),
+            );
+            let second =
+                create_test_executor_with(db, config("second"), create_test_rate_limit_policy());
+
+            let (a, b) = tokio::join!(first.claim_jobs(usize::MAX), second.claim_jobs(usize::MAX));
+            assert_eq!(a.unwrap().len() + b.unwrap().len(), 2);
+        }
+    }
+
+    /// In-memory database with one queued job per `(tenant, provider)` entry, due in
+    /// the order given
+    async fn seed_queued_jobs(queue: &[(Uuid, &str)]) -> DatabaseConnection {
+        use migration::{Migrator, MigratorTrait};
+
+        let db = sea_orm::Database::connect("sqlite::memory:")
+            .await
+            .expect("Failed to create in-memory database");
+        Migrator::up(&db, None).await.expect("apply migrations");
+        insert_queued_jobs(&db, queue).await;
+        db
+    }
+
+    /// Postgres database of its own holding only the queued jobs given, for claims
+    /// that race across connections
+    async fn seed_queued_jobs_in_postgres(
+        name: &str,
+        queue: &[(Uuid, &str)],
+    ) -> DatabaseConnection {
+        use migration::{Migrator, MigratorTrait};
+
+        let mut database_url = url::Url::parse(&crate::config::AppConfig::default().database_url)
+            .expect("default database URL");
+        database_url.set_path(&format!("/connectors_{}", name));
+        let config = crate::config::AppConfig {
+            profile: "test".to_string(),
+            database_url: database_url.to_string(),
+            ..Default::default()
+        };
+        let db = crate::db::init_pool(&config)
+            .await
+            .expect("Failed to init test DB");
+        Migrator::up(&db, None).await.expect("apply migrations");
+        SyncJobEntity::delete_many().exec(&db).await.unwrap();
+        insert_queued_jobs(&db, queue).await;
+        db
+    }
+
+    async fn insert_queued_jobs(db: &DatabaseConnection, queue: &[(Uuid, &str)]) {
+        let now = chrono::Utc::now().fixed_offset();
+        let mut tenants: Vec<Uuid> = queue.iter().map(|(tenant_id, _)| *tenant_id).collect();
+        tenants.sort();
+        tenants.dedup();
+        for tenant_id in tenants {
+            crate::models::tenant::Entity::insert(crate::models::tenant::ActiveModel {
+                id: Set(tenant_id),
+                name: Set(None),
+                created_at: Set(now),
+                ..Default::default()
+            })
+            .exec_without_returning(db)
+            .await
+            .unwrap();
+        }
+        let mut providers: Vec<&str> = queue.iter().map(|(_, slug)| *slug).collect();
+        providers.sort();
+        providers.dedup();
+        for slug in providers {
+            crate::models::provider::Entity::insert(crate::models::provider::ActiveModel {
+                slug: Set(slug.to_string()),
+                display_name: Set(slug.to_string()),
+                auth_type: Set("oauth2".to_string()),
+                created_at: Set(now),
+                updated_at: Set(now),
+            })
+            .on_conflict(
+                sea_orm::sea_query::OnConflict::column(crate::models::provider::Column::Slug)
+                    .do_nothing()
+                    .to_owned(),
+            )
+            .exec_without_returning(db)
+            .await
+            .unwrap();
+        }
+
+        for (position, (tenant_id, provider_slug)) in queue.iter().enumerate() {
+            let connection_id = Uuid::new_v4();
+            ConnectionEntity::insert(ConnectionActiveModel {
+                id: Set(connection_id),
+                tenant_id: Set(*tenant_id),
+                provider_slug: Set((*provider_slug).to_owned()),
+                external_id: Set(format!("{}-{}", provider_slug, position)),
+                status: Set("active".to_string()),
+                display_name: Set(None),
+                expires_at: Set(None),
+                scopes: Set(None),
+                metadata: Set(None),
+                created_at: Set(now),
+                updated_at: Set(now),
+            })
+            .exec_without_returning(db)
+            .await
+            .unwrap();
+            SyncJobEntity::insert(SyncJobActiveModel {
+                id: Set(Uuid::new_v4()),
+                tenant_id: Set(*tenant_id),
+                provider_slug: Set((*provider_slug).to_owned()),
+                connection_id: Set(connection_id),
+                job_type: Set("incremental".to_string()),
+                status: Set("queued".to_string()),
+                priority: Set(0),
+                attempts: Set(0),
+                rate_limited_attempts: Set(0),
+                scheduled_at: Set(now - chrono::Duration::minutes(60 - position as i64)),
+                retry_after: Set(None),
+                started_at: Set(None),
+                finished_at: Set(None),
+                locked_at: Set(None),
+                heartbeat_at: Set(None),
+                locked_by: Set(None),
+                cursor: Set(None),
+                error: Set(None),
+                created_at: Set(now),
+                updated_at: Set(now),
+            })
+            .exec_without_returning(db)
+            .await
+            .unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn test_claim_jobs_caps_running_jobs_per_tenant() {
+        // One tenant's backlog is queued ahead of the other tenant's single job
+        let (busy_tenant, quiet_tenant) = (Uuid::new_v4(), Uuid::new_v4());
+        let mut queue = vec![(busy_tenant, "github"); 4];
+        queue.push((quiet_tenant, "github"));
+        let db = seed_queued_jobs(&queue).await;
+
+        let config = ExecutorConfig {
+            claim_batch: 3,
+            max_running_per_tenant: 2,
+            ..Default::default()
+        };
+        let executor = create_test_executor_with(db, config, create_test_rate_limit_policy());
+
+        let claimed: Vec<Uuid> = executor
+            .claim_jobs(usize::MAX)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|job| job.tenant_id)
+            .collect();
+        assert_eq!(claimed.len(), 3);
+        assert_eq!(claimed.iter().filter(|id| **id == busy_tenant).count(), 2);
+        assert!(claimed.contains(&quiet_tenant));
+
+        // The busy tenant stays at its cap until its running jobs finish
+        assert!(executor.claim_jobs(usize::MAX).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_claim_jobs_ages_long_waiting_low_priority_jobs() {
+        let tenant_id = Uuid::new_v4();
+        let db = seed_queued_jobs(&[(tenant_id, "github"), (tenant_id, "jira")]).await;
+        // The github job has waited an hour; a higher-priority jira job just became due
+        SyncJobEntity::update_many()
+            .col_expr(sync_job::Column::Priority, Expr::value(40))
+            .col_expr(
+                sync_job::Column::ScheduledAt,
+                Expr::value(chrono::Utc::now()),
+            )
+            .filter(sync_job::Column::ProviderSlug.eq("jira"))
+            .exec(&db)
+            .await
+            .unwrap();
+
+        let claim_first = |priority_aging| {
+            let config = ExecutorConfig {
+                claim_batch: 1,
+                priority_aging,
+                ..Default::default()
+            };
+            create_test_executor_with(db.clone(), config, create_test_rate_limit_policy())
+        };
+        let without_aging = PriorityAging {
+            interval: chrono::Duration::zero(),
+            max_boost: 50,
+        };
+        let claimed = claim_first(without_aging)
+            .claim_jobs(usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(claimed[0].provider_slug, "jira");
+
+        // Put it back and claim again with aging: an hour of waiting earns the full boost
+        SyncJobEntity::update_many()
+            .col_expr(sync_job::Column::Status, Expr::value("queued"))
+            .filter(sync_job::Column::Id.eq(claimed[0].id))
+            .exec(&db)
+            .await
+            .unwrap();
+        let claimed = claim_first(ExecutorConfig::default().priority_aging)
+            .claim_jobs(usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(claimed[0].provider_slug, "github");
+    }
+
+    #[tokio::test]
+    async fn test_sharded_instances_claim_disjoint_jobs() {
+        // Tenants at either end of the UUID space land in different shards
+        let (low_tenant, high_tenant) = (Uuid::from_u128(1 << 64), Uuid::from_u128(u128::MAX - 1));
+        let db = seed_queued_jobs(&[
+            (low_tenant, "github"),
+            (high_tenant, "github"),
+            (low_tenant, "github"),
+        ])
+        .await;
+
+        let instance = |instance_id: &str, index| ExecutorConfig {
+            instance_id: instance_id.to_string(),
+            shard: crate::config::ExecutorShardConfig {
+                index,
+                count: 2,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let first = create_test_executor_with(
+            db.clone(),
+            instance("a", 0),
+            create_test_rate_limit_policy(),
+        );
+        let second =
+            create_test_executor_with(db, instance("b", 1), create_test_rate_limit_policy());
+
+        let claimed = first.claim_jobs(usize::MAX).await.unwrap();
+        assert_eq!(claimed.len(), 2);
+        assert!(
+            claimed
+                .iter()
+                .all(|job| job.tenant_id == low_tenant && job.locked_by.as_deref() == Some("a"))
+        );
+        assert!(first.claim_jobs(usize::MAX).await.unwrap().is_empty());
+
+        let claimed = second.claim_jobs(usize::MAX).await.unwrap();
+        assert_eq!(claimed.len(), 1);
+        assert_eq!(claimed[0].tenant_id, high_tenant);
+        assert_eq!(claimed[0].locked_by.as_deref(), Some("b"));
+    }
+
+    #[tokio::test]
+    async fn test_claim_jobs_applies_per_instance_provider_caps() {
+        let tenant_id = Uuid::new_v4();
+        let mut queue = vec![(tenant_id, "github"); 4];
+        queue.push((tenant_id, "jira"));
+        let db = seed_queued_jobs(&queue).await;
+
+        let mut policy = create_test_rate_limit_policy();
+        policy.provider_overrides.insert(
+            "github".to_string(),
+            RateLimitProviderOverride {
+                max_concurrent: Some(2),
+                ..Default::default()
+            },
+        );
+        let executor = create_test_executor_with(db, ExecutorConfig::default(), policy);
+
+        let mut claimed: Vec<String> = executor
+            .claim_jobs(usize::MAX)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|job| job.provider_slug)
+            .collect();
+        claimed.sort();
+        assert_eq!(claimed, vec!["github", "github", "jira"]);
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_connection_logs_signals_without_persisting() {
+        use crate::testing::{MockConnector, MockPage, SyncHarness};
+        use std::sync::Arc;
+
+        let mock = Arc::new(
+            MockConnector::new("mock-crm").page(
+                MockPage::new()
+                    .signal("issue_created", serde_json::json!({"id": 1}))
+                    .next_cursor("page-2")
+                    .has_more(),
+            ),
+        );
+        let harness = SyncHarness::new([mock.clone()]).await.unwrap();
+        let connection = harness.create_connection("mock-crm").await.unwrap();
+        let mut active_connection: ConnectionActiveModel = connection.clone().into();
+        active_connection.metadata = Set(Some(serde_json::json!({"sync": {"dry_run": true}})));
+        active_connection.update(&harness.db).await.unwrap();
+        harness.enqueue_sync(&connection).await.unwrap();
+
+        assert_eq!(harness.run_until_idle(5).await.unwrap(), 1);
+
+        // Nothing is stored, the cursor stays put and no follow-up page is queued
+        assert!(harness.signals(connection.id).await.unwrap().is_empty());
+        let jobs = harness.jobs(connection.id).await.unwrap();
+        assert_eq!(jobs.len(), 1);
+        assert_eq!(jobs[0].status, "succeeded");
+        let stored = ConnectionEntity::find_by_id(connection.id)
+            .one(&harness.db)
+            .await
+            .unwrap()
+            .unwrap();
+        let sync_metadata =
+            ConnectionSyncMetadata::from_connection_metadata(stored.metadata.as_ref());
+        assert!(sync_metadata.dry_run);
+        assert!(sync_metadata.cursor.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_webhook_signals_are_attributed_to_the_job_connection() {
+        use crate::testing::{MockConnector, SyncHarness};
+        use std::sync::Arc;
+
+        let mock = Arc::new(
+            MockConnector::new("mock-crm")
+                .webhook_signal("issue_created", serde_json::json!({"id": 1})),
+        );
+        let harness = SyncHarness::new([mock.clone()]).await.unwrap();
+        let connection = harness.create_connection("mock-crm").await.unwrap();
+        harness
+            .enqueue_webhook(&connection, serde_json::json!({"action": "opened"}))
+            .await
+            .unwrap();
+
+        assert_eq!(harness.run_until_idle(5).await.unwrap(), 1);
+
+        assert_eq!(mock.webhook_payloads().len(), 1);
+        let jobs = harness.jobs(connection.id).await.unwrap();
+        assert_eq!(jobs[0].status, "succeeded");
+        let signals = harness.signals(connection.id).await.unwrap();
+        assert_eq!(signals.len(), 1);
+        assert_eq!(signals[0].kind, "issue_created");
+    }
+
+    #[tokio::test]
+    async fn test_sync_cycles_record_activity_streaks() {
+        use crate::testing::{MockConnector, MockPage, SyncHarness};
+        use std::sync::Arc;
+
+        let mock = Arc::new(
+            MockConnector::new("mock-crm")
+                .page(
+                    MockPage::new()
+                        .signal("issue_created", serde_json::json!({"id": 1}))
+                        .next_cursor("page-2")
+                        .has_more(),
+                )
+                .page(MockPage::new().next_cursor("page-3")),
+        );
+        let harness = SyncHarness::new([mock.clone()]).await.unwrap();
+        let connection = harness.create_connection("mock-crm").await.unwrap();
+        let streak = || async {
+            let stored = ConnectionEntity::find_by_id(connection.id)
+                .one(&harness.db)
+                .await
+                .unwrap()
+                .unwrap();
+            ConnectionSyncMetadata::from_connection_metadata(stored.metadata.as_ref())
+                .activity_streak
+        };
+
+        // The follow-up page belongs to the same cycle and is not counted
+        harness.enqueue_sync(&connection).await.unwrap();
+        assert_eq!(harness.run_until_idle(5).await.unwrap(), 2);
+        assert_eq!(streak().await, 1);
+
+        // Cycles that find nothing build an idle streak
+        for _ in 0..2 {
+            harness.enqueue_sync(&connection).await.unwrap();
+            harness.run_until_idle(5).await.unwrap();
+        }
+        assert_eq!(streak().await, -2);
+    }
+
+    #[tokio::test]
+    async fn test_streamed_pages_persist_before_a_later_page_fails() {
+        use crate::testing::{MockConnector, MockPage, MockStep, SyncHarness};
+        use std::sync::Arc;
+
+        let page = |id: i64, cursor: &str| {
+            MockStep::Page(
+                MockPage::new()
+                    .signal("issue_created", serde_json::json!({"id": id}))
+                    .next_cursor(cursor),
+            )
+        };
+        let mock = Arc::new(MockConnector::new("mock-crm").stream([
+            page(1, "p1"),
+            page(2, "p2"),
+            MockStep::Error(SyncError::transient("provider went away")),
+        ]));
+        let harness = SyncHarness::new([mock.clone()]).await.unwrap();
+        let connection = harness.create_connection("mock-crm").await.unwrap();
+        harness.enqueue_sync(&connection).await.unwrap();
+
+        assert_eq!(harness.run_once().await.unwrap(), 1);
+        // Both pages were committed as they were emitted, although the job failed
+        assert_eq!(harness.signals(connection.id).await.unwrap().len(), 2);
+        assert_eq!(
+            harness.jobs(connection.id).await.unwrap()[0].status,
+            "queued"
+        );
+
+        // The retry resumes after the last persisted page
+        harness.run_until_idle(5).await.unwrap();
+        assert_eq!(
+            mock.sync_cursors(),
+            vec![None, Some(Cursor::from_string("p2"))]
+        );
+
+        // Each run is kept in the history with what it persisted before ending
+        let runs = harness.runs(connection.id).await.unwrap();
+        let summary: Vec<_> = runs
+            .iter()
+            .map(|run| (run.attempt, run.outcome.as_str(), run.items_processed))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![(1, OUTCOME_FAILED, 2), (2, OUTCOME_SUCCEEDED, 0)]
+        );
+        assert!(
+            runs[0]
+                .error
+                .as_deref()
+                .unwrap()
+                .contains("provider went away")
+        );
+        assert!(runs[1].error.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_streamed_sync_stops_at_max_items_and_queues_follow_up() {
+        use crate::testing::{MockConnector, MockPage, MockStep, SyncHarness};
+        use std::sync::Arc;
+
+        let page = |id: i64, cursor: &str| {
+            MockStep::Page(
+                MockPage::new()
+                    .signal("issue_created", serde_json::json!({"id": id}))
+                    .next_cursor(cursor),
+            )
+        };
+        let mock = Arc::new(MockConnector::new("mock-crm").stream([
+            page(1, "p1"),
+            page(2, "p2"),
+            page(3, "p3"),
+        ]));
+        let config = ExecutorConfig {
+            max_items_per_run: 2,
+            ..Default::default()
+        };
+        let harness = SyncHarness::with_config([mock.clone()], config)
+            .await
+            .unwrap();
+        let connection = harness.create_connection("mock-crm").await.unwrap();
+        harness.enqueue_sync(&connection).await.unwrap();
+
+        assert_eq!(harness.run_once().await.unwrap(), 1);
+        assert_eq!(harness.signals(connection.id).await.unwrap().len(), 2);
+        let jobs = harness.jobs(connection.id).await.unwrap();
+        assert_eq!(jobs.len(), 2);
+        assert!(jobs.iter().any(|job| job.status == "queued"));
+    }
+
+    #[tokio::test]
+    async fn test_open_circuit_pauses_provider_until_a_probe_succeeds() {
+        use crate::testing::{MockConnector, SyncHarness};
+        use std::sync::Arc;
+
+        let mock = Arc::new(
+            MockConnector::new("mock-crm")
+                .error(SyncError::transient("HTTP error 503: unavailable"))
+                .error(SyncError::transient("HTTP error 503: unavailable")),
+        );
+        let config = ExecutorConfig {
+            circuit_breaker_threshold: 2,
+            circuit_breaker_cooldown_seconds: 60,
+            ..Default::default()
+        };
+        let harness = SyncHarness::with_config([mock.clone()], config)
+            .await
+            .unwrap();
+        let mut connections = Vec::new();
+        for _ in 0..3 {
+            connections.push(harness.create_connection("mock-crm").await.unwrap());
+        }
+
+        // Two consecutive 503s open the circuit
+        for connection in &connections[..2] {
+            harness.enqueue_sync(connection).await.unwrap();
+            assert_eq!(harness.run_once().await.unwrap(), 1);
+        }
+        let circuit = circuit_breaker::find(&harness.db, "mock-crm")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(circuit.state, circuit_breaker::STATE_OPEN);
+
+        // While open, due jobs for the provider are not claimed
+        harness.enqueue_sync(&connections[2]).await.unwrap();
+        assert_eq!(harness.run_once().await.unwrap(), 0);
+
+        // After the cooldown a single probe runs; its success closes the circuit
+        harness
+            .clock
+            .set(crate::clock::Clock::now(harness.clock.as_ref()) + chrono::Duration::hours(1));
+        assert_eq!(harness.run_once().await.unwrap(), 1);
+        let circuit = circuit_breaker::find(&harness.db, "mock-crm")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(circuit.state, circuit_breaker::STATE_CLOSED);
+        assert_eq!(circuit.consecutive_failures, 0);
+        assert_eq!(harness.run_once().await.unwrap(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_job_fails_once_its_provider_retry_budget_is_spent() {
+        use crate::testing::{MockConnector, SyncHarness};
+        use std::sync::Arc;
+
+        // Rate limits draw on their own budget, so only the 503s count towards two
+        let mock = Arc::new(
+            MockConnector::new("mock-crm")
+                .rate_limited(None)
+                .error(SyncError::transient("HTTP error 503"))
+                .rate_limited(None)
+                .error(SyncError::transient("HTTP error 503")),
+        );
+        let mut policy = create_test_rate_limit_policy();
+        policy.provider_overrides.insert(
+            "mock-crm".to_string(),
+            RateLimitProviderOverride {
+                max_attempts: Some(2),
+                ..Default::default()
+            },
+        );
+        let harness = SyncHarness::with_policy([mock.clone()], ExecutorConfig::default(), policy)
+            .await
+            .unwrap();
+        let connection = harness.create_connection("mock-crm").await.unwrap();
+        harness.enqueue_sync(&connection).await.unwrap();
+
+        assert_eq!(harness.run_until_idle(10).await.unwrap(), 4);
+        let jobs = harness.jobs(connection.id).await.unwrap();
+        assert_eq!(jobs.len(), 1);
+        assert_eq!(jobs[0].status, "failed");
+        assert_eq!(jobs[0].attempts, 4);
+        assert_eq!(jobs[0].rate_limited_attempts, 2);
+        assert!(jobs[0].finished_at.is_some());
+        assert_eq!(
+            jobs[0].error.as_ref().unwrap()["retries_exhausted"],
+            serde_json::json!(true)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_cancelling_job_commits_fetched_pages_without_follow_up() {
+        use crate::testing::{MockConnector, MockPage, SyncHarness};
+        use std::sync::Arc;
+
+        let mock = Arc::new(
+            MockConnector::new("mock-crm").page(
+                MockPage::new()
+                    .signal("issue_created", serde_json::json!({"id": 1}))
+                    .next_cursor("page-2")
+                    .has_more(),
+            ),
+        );
+        let harness = SyncHarness::new([mock.clone()]).await.unwrap();
+        let connection = harness.create_connection("mock-crm").await.unwrap();
+        let job = harness.enqueue_sync(&connection).await.unwrap();
+
+        // Claimed, then cancelled by an operator before the connector returned
+        let mut active_job: SyncJobActiveModel = job.into();
+        active_job.status = Set("cancelling".to_string());
+        active_job.attempts = Set(1);
+        active_job.locked_by = Set(Some(harness.executor.config().instance_id.clone()));
+        let job = active_job.update(&harness.db).await.unwrap();
+        harness.executor.run_single_job(job).await.unwrap();
+
+        // The fetched page and its cursor are kept, but the backfill stops here
+        assert_eq!(harness.signals(connection.id).await.unwrap().len(), 1);
+        let jobs = harness.jobs(connection.id).await.unwrap();
+        assert_eq!(jobs.len(), 1);
+        assert_eq!(jobs[0].status, "cancelled");
+        assert!(jobs[0].finished_at.is_some());
+        let stored = ConnectionEntity::find_by_id(connection.id)
+            .one(&harness.db)
+            .await
+            .unwrap()
+            .unwrap();
+        let sync_metadata =
+            ConnectionSyncMetadata::from_connection_metadata(stored.metadata.as_ref());
+        assert!(sync_metadata.cursor.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_releases_claimed_jobs_for_immediate_reclaim() {
+        use crate::testing::{MockConnector, MockPage, SyncHarness};
+        use std::sync::Arc;
+
+        let mock = Arc::new(MockConnector::new("mock-crm").page(MockPage::new()));
+        let harness = SyncHarness::new([mock.clone()]).await.unwrap();
+        let connection = harness.create_connection("mock-crm").await.unwrap();
+        harness.enqueue_sync(&connection).await.unwrap();
+
+        let shutdown = CancellationToken::new();
+        let executor = harness.executor.clone().with_shutdown(shutdown.clone());
+        shutdown.cancel();
+
+        // Draining stops the loop, and a job claimed as shutdown began is handed back
+        executor.run().await.unwrap();
+        assert_eq!(executor.claim_and_run_jobs().await.unwrap(), 1);
+        assert_eq!(mock.sync_calls(), 0);
+        let jobs = harness.jobs(connection.id).await.unwrap();
+        assert_eq!(jobs.len(), 1);
+        assert_eq!(jobs[0].status, "queued");
+        assert_eq!(jobs[0].attempts, 0);
+        assert!(jobs[0].started_at.is_none());
+
+        // Another executor picks it up on its next tick
+        assert_eq!(harness.run_once().await.unwrap(), 1);
+        assert_eq!(
+            harness.jobs(connection.id).await.unwrap()[0].status,
+            "succeeded"
+        );
+    }
+    #[tokio::test]
+    async fn test_queued_job_of_connection_needing_reauth_does_not_run() {
+        use crate::connection_pause::STATUS_NEEDS_REAUTH;
+        use crate::testing::{MockConnector, MockPage, SyncHarness};
+        use std::sync::Arc;
+
+        let mock = Arc::new(MockConnector::new("mock-crm").page(MockPage::new()));
+        let harness = SyncHarness::new([mock.clone()]).await.unwrap();
+        let connection = harness.create_connection("mock-crm").await.unwrap();
+        harness.enqueue_sync(&connection).await.unwrap();
+        let mut stored: ConnectionActiveModel = connection.clone().into();
+        stored.status = Set(STATUS_NEEDS_REAUTH.to_string());
+        stored.update(&harness.db).await.unwrap();
+
+        assert_eq!(harness.run_once().await.unwrap(), 0);
+        assert_eq!(mock.sync_calls(), 0);
+        let jobs = harness.jobs(connection.id).await.unwrap();
+        assert_eq!(jobs[0].status, "queued");
+        assert_eq!(jobs[0].attempts, 0);
+    }
+
+    #[tokio::test]
+    async fn test_queued_job_of_paused_connection_waits_for_resume() {
+        use crate::connection_pause::STATUS_PAUSED;
+        use crate::testing::{MockConnector, MockPage, SyncHarness};
+        use std::sync::Arc;
+
+        let mock = Arc::new(MockConnector::new("mock-crm").page(MockPage::new()));
+        let harness = SyncHarness::new([mock.clone()]).await.unwrap();
+        let connection = harness.create_connection("mock-crm").await.unwrap();
+        harness.enqueue_sync(&connection).await.unwrap();
+        let set_status = |status: &str| {
+            let mut stored: ConnectionActiveModel = connection.clone().into();
+            stored.status = Set(status.to_string());
+            stored.update(&harness.db)
+        };
+
+        set_status(STATUS_PAUSED).await.unwrap();
+        assert_eq!(harness.run_once().await.unwrap(), 0);
+        assert_eq!(mock.sync_calls(), 0);
+
+        set_status("active").await.unwrap();
+        assert_eq!(harness.run_once().await.unwrap(), 1);
+        assert_eq!(mock.sync_calls(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_queued_job_does_not_run_once_connection_is_auto_paused() {
+        use crate::connection_pause::STATUS_NEEDS_REAUTH;
+        use crate::testing::{MockConnector, MockPage, SyncHarness};
+        use std::sync::Arc;
+
+        // The sync and its retry after a token refresh are both rejected
+        let mock = Arc::new(
+            MockConnector::new("mock-crm")
+                .error(SyncError::unauthorized("401 token revoked"))
+                .error(SyncError::unauthorized("401 token revoked"))
+                .page(MockPage::new()),
+        );
+        let config = ExecutorConfig {
+            auth_failure_pause_threshold: 1,
+            ..Default::default()
+        };
+        let harness = SyncHarness::with_config([mock.clone()], config)
+            .await
+            .unwrap();
+        let connection = harness.create_connection("mock-crm").await.unwrap();
+        harness.enqueue_sync(&connection).await.unwrap();
+
+        assert_eq!(harness.run_once().await.unwrap(), 1);
+        let stored = ConnectionEntity::find_by_id(connection.id)
+            .one(&harness.db)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(stored.status, STATUS_NEEDS_REAUTH);
+        let calls = mock.sync_calls();
+
+        // Neither a queued job nor a retry calls the provider with the failing credentials
+        harness.enqueue_sync(&connection).await.unwrap();
+        harness.clock.advance(Duration::from_secs(3600));
+        assert_eq!(harness.run_once().await.unwrap(), 0);
+        assert_eq!(mock.sync_calls(), calls);
+        let health = crate::connection_pause::ConnectionAuthHealth::from_connection_metadata(
+            ConnectionEntity::find_by_id(connection.id)
+                .one(&harness.db)
+                .await
+                .unwrap()
+                .unwrap()
+                .metadata
+                .as_ref(),
+        );
+        assert_eq!(health.consecutive_failures, 1);
+    }
+
+    #[tokio::test]
+    async fn test_batch_claims_no_more_jobs_than_can_start() {
+        use crate::testing::{MockConnector, MockPage, SyncHarness};
+        use std::sync::Arc;
+
+        let mock = Arc::new(MockConnector::new("mock-crm").page(MockPage::new()));
+        let config = ExecutorConfig {
+            concurrency: 2,
+            claim_batch: 50,
+            ..Default::default()
+        };
+        let harness = SyncHarness::with_config([mock.clone()], config)
+            .await
+            .unwrap();
+        for _ in 0..3 {
+            let connection = harness.create_connection("mock-crm").await.unwrap();
+            harness.enqueue_sync(&connection).await.unwrap();
+        }
+
+        // Jobs waiting for a permit would not renew their lease, so they stay queued
+        assert_eq!(harness.run_once().await.unwrap(), 2);
+        assert_eq!(harness.run_once().await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_lease_is_renewed_on_the_executor_clock() {
+        use crate::testing::{MockConnector, MockPage, SyncHarness};
+        use std::sync::Arc;
+
+        let mock = Arc::new(MockConnector::new("mock-crm").page(MockPage::new()));
+        let harness = SyncHarness::new([mock.clone()]).await.unwrap();
+        let connection = harness.create_connection("mock-crm").await.unwrap();
+        let job = harness.enqueue_sync(&connection).await.unwrap();
+        let mut active_job: SyncJobActiveModel = job.into();
+        active_job.status = Set("running".to_string());
+        active_job.locked_by = Set(Some(harness.executor.config().instance_id.clone()));
+        let job = active_job.update(&harness.db).await.unwrap();
+
+        let heartbeat = || async {
+            harness.jobs(connection.id).await.unwrap()[0]
+                .heartbeat_at
+                .map(|at| at.with_timezone(&chrono::Utc))
+        };
+        let wait_for_heartbeat = |at: chrono::DateTime<chrono::Utc>| async move {
+            for _ in 0..200 {
+                if heartbeat().await == Some(at) {
+                    return;
+                }
+                tokio::time::sleep(Duration::from_millis(5)).await;
+            }
+            panic!("lease was not renewed at {at}");
+        };
+
+        let start = crate::clock::Clock::now(harness.clock.as_ref());
+        let lease = tokio::spawn(hold_lease(
+            harness.executor.db.clone(),
+            harness.clock.clone(),
+            job.id,
+            harness.executor.config().instance_id.clone(),
+            CancellationToken::new(),
+        ));
+        wait_for_heartbeat(start).await;
+
+        // The next renewal waits for virtual time, not the wall clock
+        harness.clock.advance(LEASE_HEARTBEAT_INTERVAL);
+        wait_for_heartbeat(start + chrono::Duration::from_std(LEASE_HEARTBEAT_INTERVAL).unwrap())
+            .await;
+        lease.abort();
+    }
+
+    #[tokio::test]
+    async fn test_reaper_reclaims_jobs_whose_lease_expired() {
+        use crate::testing::{MockConnector, MockPage, SyncHarness};
+        use std::sync::Arc;
+
+        let mock = Arc::new(MockConnector::new("mock-crm").page(MockPage::new()));
+        let harness = SyncHarness::new([mock.clone()]).await.unwrap();
+        let now = crate::clock::Clock::now(harness.clock.as_ref()).fixed_offset();
+
+        // One worker crashed two minutes ago; another is still renewing its lease
+        let mut leases = Vec::new();
+        for heartbeat_at in [
+            now - chrono::Duration::seconds(120),
+            now - chrono::Duration::seconds(10),
+        ] {
+            let connection = harness.create_connection("mock-crm").await.unwrap();
+            let job = harness.enqueue_sync(&connection).await.unwrap();
+            let mut active_job: SyncJobActiveModel = job.into();
+            active_job.status = Set("running".to_string());
+            active_job.attempts = Set(1);
+            active_job.started_at = Set(Some(heartbeat_at));
+            active_job.locked_at = Set(Some(heartbeat_at));
+            active_job.heartbeat_at = Set(Some(heartbeat_at));
+            leases.push(active_job.update(&harness.db).await.unwrap());
+        }
+
+        assert_eq!(harness.executor.reap_expired_leases().await.unwrap(), 1);
+
+        let crashed = harness.jobs(leases[0].connection_id).await.unwrap();
+        assert_eq!(crashed[0].status, "queued");
+        assert_eq!(crashed[0].attempts, 1);
+        assert!(crashed[0].heartbeat_at.is_none());
+        assert!(crashed[0].error.is_some());
+        let alive = harness.jobs(leases[1].connection_id).await.unwrap();
+        assert_eq!(alive[0].status, "running");
+
+        // The reclaimed job runs again and takes a fresh lease
+        assert_eq!(harness.run_once().await.unwrap(), 1);
+        let rerun = harness.jobs(leases[0].connection_id).await.unwrap();
+        assert_eq!(rerun[0].status, "succeeded");
+        assert_eq!(rerun[0].heartbeat_at, Some(now));
+    }
 }
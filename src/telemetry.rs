@@ -1,16 +1,22 @@
 //! Telemetry utilities for request-scoped tracing metadata and global subscriber management.
+//!
+//! The global filter sits behind a reload handle so operators can change log
+//! levels at runtime (`PUT /admin/log-level`), and [`DebugSampler`] thins out
+//! high-volume debug events on the executor and webhook paths.
 
 use std::any::type_name_of_val;
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
 
 use log::LevelFilter;
 use thiserror::Error;
 use tokio::task_local;
 use tracing_log::LogTracer;
 use tracing_subscriber::{
-    EnvFilter, fmt,
+    EnvFilter, Registry, fmt,
     layer::Layer,
     layer::SubscriberExt,
+    reload,
     util::{SubscriberInitExt, TryInitError},
 };
 
@@ -35,8 +41,22 @@ pub enum TelemetryInitError {
     Subscriber(#[from] TryInitError),
 }
 
+/// Errors returned when changing the log filter at runtime.
+#[derive(Debug, Error)]
+pub enum LogFilterError {
+    #[error("invalid log filter: {0}")]
+    Invalid(String),
+    #[error("log filter cannot be changed: {0}")]
+    Unavailable(String),
+}
+
 static TELEMETRY_INITIALIZED: AtomicBool = AtomicBool::new(false);
 
+static LOG_FILTER_HANDLE: OnceLock<reload::Handle<EnvFilter, Registry>> = OnceLock::new();
+
+/// Emit one in this many sampled debug events; 1 emits all of them
+static DEBUG_SAMPLE_RATE: AtomicU32 = AtomicU32::new(1);
+
 /// Initialize global tracing/logging exactly once, wiring `log::` macros into the tracing pipeline.
 pub fn init_tracing(config: &AppConfig) -> Result<(), TelemetryInitError> {
     if TELEMETRY_INITIALIZED
@@ -62,8 +82,11 @@ pub fn init_tracing(config: &AppConfig) -> Result<(), TelemetryInitError> {
         }
     }
 
+    set_debug_sample_rate(config.log_debug_sample_rate);
+
     let env_filter =
         EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(&config.log_level));
+    let (env_filter, filter_handle) = reload::Layer::new(env_filter);
 
     let fmt_layer = match config.log_format.as_str() {
         "pretty" => fmt::layer().pretty().boxed(),
@@ -80,11 +103,81 @@ pub fn init_tracing(config: &AppConfig) -> Result<(), TelemetryInitError> {
             "Warning: Failed to set global tracing subscriber: {}. Default subscriber remains in effect.",
             err
         );
+    } else {
+        let _ = LOG_FILTER_HANDLE.set(filter_handle);
     }
 
     Ok(())
 }
 
+/// Active global log filter directives, or `None` when the subscriber was not installed here.
+pub fn current_log_filter() -> Option<String> {
+    LOG_FILTER_HANDLE
+        .get()?
+        .with_current(|filter| filter.to_string())
+        .ok()
+}
+
+/// Parse `directives` (`EnvFilter` syntax, e.g. `info,connectors::sync_executor=debug`).
+pub fn parse_log_filter(directives: &str) -> Result<EnvFilter, LogFilterError> {
+    EnvFilter::builder()
+        .parse(directives)
+        .map_err(|e| LogFilterError::Invalid(e.to_string()))
+}
+
+/// Replace the global log filter without restarting, returning the new directives.
+pub fn set_log_filter(directives: &str) -> Result<String, LogFilterError> {
+    let filter = parse_log_filter(directives)?;
+    let handle = LOG_FILTER_HANDLE.get().ok_or_else(|| {
+        LogFilterError::Unavailable("tracing subscriber is not installed".to_string())
+    })?;
+    let applied = filter.to_string();
+    handle
+        .reload(filter)
+        .map_err(|e| LogFilterError::Unavailable(e.to_string()))?;
+    Ok(applied)
+}
+
+/// Current debug sampling rate (one in N events).
+pub fn debug_sample_rate() -> u32 {
+    DEBUG_SAMPLE_RATE.load(Ordering::Relaxed)
+}
+
+/// Set the debug sampling rate; 0 is treated as 1.
+pub fn set_debug_sample_rate(rate: u32) {
+    DEBUG_SAMPLE_RATE.store(rate.max(1), Ordering::Relaxed);
+}
+
+/// Counter-based sampler for a high-volume debug event.
+///
+/// Declare one `static` per call site and guard the event with
+/// [`DebugSampler::sample`]; with a rate of N only every Nth call returns
+/// `true`. The event's own level and target filter still apply afterwards.
+#[derive(Debug, Default)]
+pub struct DebugSampler {
+    calls: AtomicU64,
+}
+
+impl DebugSampler {
+    pub const fn new() -> Self {
+        Self {
+            calls: AtomicU64::new(0),
+        }
+    }
+
+    /// Whether this call should emit its debug event.
+    pub fn sample(&self) -> bool {
+        self.sample_at(debug_sample_rate())
+    }
+
+    fn sample_at(&self, rate: u32) -> bool {
+        let rate = u64::from(rate.max(1));
+        self.calls
+            .fetch_add(1, Ordering::Relaxed)
+            .is_multiple_of(rate)
+    }
+}
+
 /// Execute `future` within the provided trace context, making it available through task-local
 /// storage for the duration of the request.
 pub async fn with_trace_context<Fut, R>(context: TraceContext, future: Fut) -> R
@@ -100,3 +193,27 @@ pub fn current_trace_id() -> Option<String> {
         .try_with(|ctx| ctx.trace_id.clone())
         .ok()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sampler_emits_one_in_n() {
+        let sampler = DebugSampler::new();
+        let emitted = (0..10).filter(|_| sampler.sample_at(5)).count();
+        assert_eq!(emitted, 2);
+
+        let sampler = DebugSampler::new();
+        assert!((0..3).all(|_| sampler.sample_at(0)));
+    }
+
+    #[test]
+    fn test_parse_log_filter_rejects_bad_directives() {
+        assert!(parse_log_filter("info,connectors::sync_executor=debug").is_ok());
+        assert!(matches!(
+            parse_log_filter("connectors=loud"),
+            Err(LogFilterError::Invalid(_))
+        ));
+    }
+}
@@ -0,0 +1,63 @@
+//! Shared database fixtures for unit tests
+//!
+//! Tests share the migrated test-profile Postgres database, so every fixture
+//! creates a fresh tenant and leaves rows of other tests alone.
+
+use sea_orm::{ActiveModelTrait, DatabaseConnection, Set};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::config::AppConfig;
+use crate::db::init_pool;
+use crate::models::{connection, tenant};
+use crate::repositories::provider::ProviderRepository;
+use migration::{Migrator, MigratorTrait};
+
+/// Provider slug of the connection created by [`setup_connection`]
+pub(crate) const TEST_PROVIDER: &str = "test-provider";
+
+/// Migrated test database with a new tenant
+pub(crate) async fn setup_tenant() -> (DatabaseConnection, Uuid) {
+    let config = AppConfig {
+        profile: "test".to_string(),
+        ..Default::default()
+    };
+    let db = init_pool(&config).await.expect("Failed to init test DB");
+    Migrator::up(&db, None).await.unwrap();
+
+    let tenant_id = Uuid::new_v4();
+    tenant::ActiveModel {
+        id: Set(tenant_id),
+        ..Default::default()
+    }
+    .insert(&db)
+    .await
+    .unwrap();
+
+    (db, tenant_id)
+}
+
+/// Migrated test database with a new tenant and an active connection of it to [`TEST_PROVIDER`]
+pub(crate) async fn setup_connection() -> (DatabaseConnection, Uuid, Uuid) {
+    let (db, tenant_id) = setup_tenant().await;
+
+    ProviderRepository::new(Arc::new(db.clone()))
+        .upsert(TEST_PROVIDER, "Test Provider", "oauth")
+        .await
+        .unwrap();
+
+    let connection_id = Uuid::new_v4();
+    connection::ActiveModel {
+        id: Set(connection_id),
+        tenant_id: Set(tenant_id),
+        provider_slug: Set(TEST_PROVIDER.to_string()),
+        external_id: Set(format!("test-{}", connection_id)),
+        status: Set("active".to_string()),
+        ..Default::default()
+    }
+    .insert(&db)
+    .await
+    .unwrap();
+
+    (db, tenant_id, connection_id)
+}
@@ -0,0 +1,751 @@
+//! # Testing Helpers
+//!
+//! Building blocks for testing code that sits on top of connectors without
+//! calling real provider APIs.
+//!
+//! [`MockConnector`] replays a script of sync pages and errors, one step per
+//! `sync` call, and records the cursors it was called with. A streamed step
+//! emits several pages through the executor's sink within one call. [`SyncHarness`]
+//! runs the real [`SyncExecutor`] against an in-memory SQLite database with
+//! migrations applied. Its [`MockClock`] lets a test skip past retry backoff
+//! instead of waiting for it.
+//!
+//! ```no_run
+//! # async fn example() -> anyhow::Result<()> {
+//! use connectors::testing::{MockConnector, MockPage, SyncHarness};
+//! use std::sync::Arc;
+//!
+//! let mock = Arc::new(
+//!     MockConnector::new("mock-crm")
+//!         .page(
+//!             MockPage::new()
+//!                 .signal("issue_created", serde_json::json!({"id": 1}))
+//!                 .next_cursor("p2")
+//!                 .has_more(),
+//!         )
+//!         .rate_limited(Some(30))
+//!         .page(MockPage::new().signal("issue_updated", serde_json::json!({"id": 1}))),
+//! );
+//! let harness = SyncHarness::new([mock.clone()]).await?;
+//! let connection = harness.create_connection("mock-crm").await?;
+//! harness.enqueue_sync(&connection).await?;
+//! harness.run_until_idle(10).await?;
+//!
+//! assert_eq!(harness.signals(connection.id).await?.len(), 2);
+//! assert_eq!(mock.sync_calls(), 3);
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use migration::{Migrator, MigratorTrait};
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, Set};
+use url::Url;
+use uuid::Uuid;
+
+use crate::clock::MockClock;
+use crate::config::{AppConfig, RateLimitPolicyConfig};
+use crate::connectors::{
+    AuthType, Connector, ProviderCategory, ProviderMetadata, Registry, SyncError,
+    trait_::{
+        AuthorizeParams, Cursor, ExchangeTokenParams, SyncPage, SyncParams, SyncResult, SyncSink,
+        WebhookParams,
+    },
+};
+use crate::crypto::CryptoKey;
+use crate::cursor::CursorSigner;
+use crate::models::{connection, signal, sync_job, sync_job_run, tenant};
+use crate::repositories::{ConnectionRepository, ProviderRepository};
+use crate::sync_executor::{ExecutorConfig, SyncExecutor};
+use crate::token_refresh::TokenRefreshService;
+use crate::token_vault::TokenVault;
+
+/// Access token handed out by [`MockConnector`] token exchanges and refreshes
+pub const MOCK_ACCESS_TOKEN: &str = "mock-access-token";
+
+/// Signal emitted by a [`MockPage`]
+#[derive(Debug, Clone)]
+pub struct MockSignal {
+    pub kind: String,
+    pub payload: serde_json::Value,
+    pub dedupe_key: Option<String>,
+}
+
+/// One page returned by a scripted sync
+#[derive(Debug, Clone, Default)]
+pub struct MockPage {
+    pub signals: Vec<MockSignal>,
+    pub next_cursor: Option<Cursor>,
+    pub has_more: bool,
+}
+
+impl MockPage {
+    /// Empty page without a next cursor
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a signal of `kind`
+    pub fn signal(mut self, kind: &str, payload: serde_json::Value) -> Self {
+        self.signals.push(MockSignal {
+            kind: kind.to_string(),
+            payload,
+            dedupe_key: None,
+        });
+        self
+    }
+
+    /// Add a signal of `kind` with a dedupe key
+    pub fn keyed_signal(
+        mut self,
+        kind: &str,
+        dedupe_key: &str,
+        payload: serde_json::Value,
+    ) -> Self {
+        self.signals.push(MockSignal {
+            kind: kind.to_string(),
+            payload,
+            dedupe_key: Some(dedupe_key.to_string()),
+        });
+        self
+    }
+
+    /// Set the cursor the executor stores after this page
+    pub fn next_cursor(mut self, cursor: impl Into<serde_json::Value>) -> Self {
+        self.next_cursor = Some(Cursor::from_json(cursor.into()));
+        self
+    }
+
+    /// Report more pages, so the executor enqueues a follow-up job
+    pub fn has_more(mut self) -> Self {
+        self.has_more = true;
+        self
+    }
+}
+
+/// Outcome of one scripted `sync` call
+#[derive(Debug, Clone)]
+pub enum MockStep {
+    Page(MockPage),
+    Error(SyncError),
+    /// Pages emitted one by one through `sync_streaming`, stopping at the first
+    /// error or, between pages, once the sync is cancelled
+    Stream(Vec<MockStep>),
+}
+
+/// Connector whose syncs replay a script
+///
+/// Each `sync` call takes the next step of the script. Once the script is
+/// exhausted, syncs return an empty page that keeps the cursor they were given.
+/// Webhook payloads are recorded and produce the signals added with
+/// [`MockConnector::webhook_signal`].
+pub struct MockConnector {
+    slug: String,
+    steps: Mutex<VecDeque<MockStep>>,
+    sync_cursors: Mutex<Vec<Option<Cursor>>>,
+    webhook_payloads: Mutex<Vec<serde_json::Value>>,
+    webhook_signals: Mutex<Vec<MockSignal>>,
+}
+
+impl MockConnector {
+    /// Connector registered under `slug` with an empty script
+    pub fn new(slug: &str) -> Self {
+        Self {
+            slug: slug.to_string(),
+            steps: Mutex::new(VecDeque::new()),
+            sync_cursors: Mutex::new(Vec::new()),
+            webhook_payloads: Mutex::new(Vec::new()),
+            webhook_signals: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Append a page to the script
+    pub fn page(self, page: MockPage) -> Self {
+        self.push(MockStep::Page(page));
+        self
+    }
+
+    /// Append an error to the script
+    pub fn error(self, error: SyncError) -> Self {
+        self.push(MockStep::Error(error));
+        self
+    }
+
+    /// Append a rate limit error to the script
+    pub fn rate_limited(self, retry_after_secs: Option<u64>) -> Self {
+        self.error(SyncError::rate_limited(retry_after_secs))
+    }
+
+    /// Append an authorization failure to the script
+    pub fn unauthorized(self) -> Self {
+        self.error(SyncError::unauthorized("mock credentials rejected"))
+    }
+
+    /// Append a call that streams `steps` as pages; the last page's `has_more` is returned
+    pub fn stream(self, steps: impl IntoIterator<Item = MockStep>) -> Self {
+        self.push(MockStep::Stream(steps.into_iter().collect()));
+        self
+    }
+
+    /// Append a step to the script of a connector that is already registered
+    pub fn push(&self, step: MockStep) {
+        self.steps.lock().unwrap().push_back(step);
+    }
+
+    /// Number of `sync` calls so far
+    pub fn sync_calls(&self) -> usize {
+        self.sync_cursors.lock().unwrap().len()
+    }
+
+    /// Cursor passed to each `sync` call, in call order
+    pub fn sync_cursors(&self) -> Vec<Option<Cursor>> {
+        self.sync_cursors.lock().unwrap().clone()
+    }
+
+    /// Emit a signal from every webhook delivery
+    ///
+    /// Like a real connector's webhook signals, it names no connection; the
+    /// executor attributes it to the webhook job's connection.
+    pub fn webhook_signal(self, kind: &str, payload: serde_json::Value) -> Self {
+        self.webhook_signals.lock().unwrap().push(MockSignal {
+            kind: kind.to_string(),
+            payload,
+            dedupe_key: None,
+        });
+        self
+    }
+
+    /// Payload passed to each `handle_webhook` call, in call order
+    pub fn webhook_payloads(&self) -> Vec<serde_json::Value> {
+        self.webhook_payloads.lock().unwrap().clone()
+    }
+
+    /// Provider metadata registered for the connector
+    pub fn metadata(&self) -> ProviderMetadata {
+        ProviderMetadata::new(self.slug.clone(), AuthType::OAuth2, Vec::new(), true)
+            .with_category(ProviderCategory::Other)
+            .with_description("Scripted mock provider for tests")
+    }
+
+    /// Register the connector in `registry` under its slug
+    pub fn register(self: &Arc<Self>, registry: &mut Registry) {
+        registry.register(self.clone(), self.metadata());
+    }
+
+    /// Signals for a scripted page, attributed to the synced connection
+    fn signals(&self, params: &SyncParams, mocks: Vec<MockSignal>) -> Vec<signal::Model> {
+        let connection = &params.connection;
+        let now: DateTime<_> = Utc::now().into();
+        mocks
+            .into_iter()
+            .map(|mock| signal::Model {
+                id: Uuid::new_v4(),
+                tenant_id: connection.tenant_id,
+                provider_slug: self.slug.clone(),
+                connection_id: connection.id,
+                kind: mock.kind,
+                occurred_at: now,
+                received_at: now,
+                payload: mock.payload,
+                dedupe_key: mock.dedupe_key,
+                created_at: now,
+                updated_at: now,
+            })
+            .collect()
+    }
+}
+
+#[async_trait]
+impl Connector for MockConnector {
+    async fn authorize(
+        &self,
+        params: AuthorizeParams,
+    ) -> Result<Url, Box<dyn std::error::Error + Send + Sync>> {
+        let mut url = Url::parse("https://mock.invalid/oauth/authorize")?;
+        url.query_pairs_mut()
+            .append_pair("provider", &self.slug)
+            .append_pair("state", &params.state.unwrap_or_default());
+        Ok(url)
+    }
+
+    async fn exchange_token(
+        &self,
+        params: ExchangeTokenParams,
+    ) -> Result<connection::Model, Box<dyn std::error::Error + Send + Sync>> {
+        let now = DateTime::from(Utc::now());
+        Ok(connection::Model {
+            id: Uuid::new_v4(),
+            tenant_id: params.tenant_id,
+            provider_slug: self.slug.clone(),
+            external_id: format!("{}-{}", self.slug, params.code),
+            status: "active".to_string(),
+            display_name: None,
+            access_token_ciphertext: None,
+            refresh_token_ciphertext: None,
+            access_token: Some(MOCK_ACCESS_TOKEN.to_string()),
+            refresh_token: None,
+            expires_at: Some(now + chrono::Duration::hours(1)),
+            scopes: None,
+            metadata: None,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    async fn refresh_token(
+        &self,
+        connection: connection::Model,
+    ) -> Result<connection::Model, Box<dyn std::error::Error + Send + Sync>> {
+        let now = DateTime::from(Utc::now());
+        Ok(connection::Model {
+            access_token: Some(MOCK_ACCESS_TOKEN.to_string()),
+            expires_at: Some(now + chrono::Duration::hours(1)),
+            updated_at: now,
+            ..connection
+        })
+    }
+
+    async fn sync(
+        &self,
+        params: SyncParams,
+    ) -> Result<SyncResult, Box<dyn std::error::Error + Send + Sync>> {
+        let mut sink = crate::connectors::CollectingSink::default();
+        let result = self.sync_streaming(params, &mut sink).await?;
+        Ok(sink.into_result(result))
+    }
+
+    async fn sync_streaming(
+        &self,
+        params: SyncParams,
+        sink: &mut dyn SyncSink,
+    ) -> Result<SyncResult, Box<dyn std::error::Error + Send + Sync>> {
+        self.sync_cursors
+            .lock()
+            .unwrap()
+            .push(params.cursor.clone());
+        let step = self.steps.lock().unwrap().pop_front();
+        let page = match step {
+            Some(MockStep::Page(page)) => page,
+            Some(MockStep::Error(error)) => return Err(Box::new(error)),
+            Some(MockStep::Stream(steps)) => {
+                let mut has_more = false;
+                for step in steps {
+                    // Stop between pages like a real connector, leaving the rest for a follow-up
+                    if params.cancellation.is_cancelled() {
+                        has_more = true;
+                        break;
+                    }
+                    let page = match step {
+                        MockStep::Page(page) => page,
+                        MockStep::Error(error) => return Err(Box::new(error)),
+                        MockStep::Stream(_) => panic!("streamed steps cannot nest"),
+                    };
+                    has_more = page.has_more;
+                    sink.emit(SyncPage {
+                        signals: self.signals(&params, page.signals),
+                        next_cursor: page.next_cursor,
+                    })
+                    .await?;
+                }
+                return Ok(SyncResult {
+                    signals: Vec::new(),
+                    next_cursor: None,
+                    has_more,
+                });
+            }
+            None => {
+                return Ok(SyncResult {
+                    signals: Vec::new(),
+                    next_cursor: params.cursor,
+                    has_more: false,
+                });
+            }
+        };
+
+        Ok(SyncResult {
+            signals: self.signals(&params, page.signals),
+            next_cursor: page.next_cursor,
+            has_more: page.has_more,
+        })
+    }
+
+    async fn handle_webhook(
+        &self,
+        params: WebhookParams,
+    ) -> Result<Vec<signal::Model>, Box<dyn std::error::Error + Send + Sync>> {
+        self.webhook_payloads.lock().unwrap().push(params.payload);
+        let now: DateTime<_> = Utc::now().into();
+        Ok(self
+            .webhook_signals
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|mock| signal::Model {
+                id: Uuid::new_v4(),
+                tenant_id: params.tenant_id,
+                provider_slug: self.slug.clone(),
+                connection_id: Uuid::nil(),
+                kind: mock.kind.clone(),
+                occurred_at: now,
+                received_at: now,
+                payload: mock.payload.clone(),
+                dedupe_key: mock.dedupe_key.clone(),
+                created_at: now,
+                updated_at: now,
+            })
+            .collect())
+    }
+}
+
+/// Sync executor running against an in-memory database
+///
+/// The database has migrations applied and one tenant. The executor's
+/// registry holds only the connectors passed to [`SyncHarness::new`], and its
+/// time source is [`SyncHarness::clock`].
+pub struct SyncHarness {
+    pub db: DatabaseConnection,
+    pub executor: SyncExecutor,
+    pub clock: Arc<MockClock>,
+    pub tenant_id: Uuid,
+}
+
+impl SyncHarness {
+    /// Harness with the default executor settings
+    pub async fn new(
+        connectors: impl IntoIterator<Item = Arc<MockConnector>>,
+    ) -> anyhow::Result<Self> {
+        Self::with_config(connectors, ExecutorConfig::default()).await
+    }
+
+    /// Harness with custom executor settings
+    pub async fn with_config(
+        connectors: impl IntoIterator<Item = Arc<MockConnector>>,
+        config: ExecutorConfig,
+    ) -> anyhow::Result<Self> {
+        Self::with_policy(connectors, config, RateLimitPolicyConfig::default()).await
+    }
+
+    /// Harness with custom executor settings and retry policy
+    pub async fn with_policy(
+        connectors: impl IntoIterator<Item = Arc<MockConnector>>,
+        config: ExecutorConfig,
+        rate_limit_policy: RateLimitPolicyConfig,
+    ) -> anyhow::Result<Self> {
+        let db = sea_orm::Database::connect("sqlite::memory:").await?;
+        Migrator::up(&db, None).await?;
+
+        let clock = MockClock::new(Utc::now());
+        let tenant_id = Uuid::new_v4();
+        tenant::Entity::insert(tenant::ActiveModel {
+            id: Set(tenant_id),
+            name: Set(Some("Test Tenant".to_string())),
+            created_at: Set(clock_now(&clock)),
+            ..Default::default()
+        })
+        .exec_without_returning(&db)
+        .await?;
+
+        let mut registry = Registry::new();
+        let providers = ProviderRepository::new(Arc::new(db.clone()));
+        for connector in connectors {
+            connector.register(&mut registry);
+            providers
+                .upsert(&connector.slug, &connector.slug, "oauth2")
+                .await?;
+        }
+
+        let crypto_key = CryptoKey::new(vec![0u8; 32])?;
+        let cursor_signer = CursorSigner::new(&crypto_key);
+        let connection_repo = ConnectionRepository::new(Arc::new(db.clone()), crypto_key);
+        let token_vault = TokenVault::from(connection_repo.clone());
+        let token_refresh_service = Arc::new(TokenRefreshService::new(
+            Arc::new(AppConfig::default()),
+            Arc::new(db.clone()),
+            Arc::new(connection_repo),
+            registry.clone(),
+        ));
+        let executor = SyncExecutor::new(
+            db.clone(),
+            registry,
+            config,
+            rate_limit_policy,
+            token_refresh_service,
+            token_vault,
+            cursor_signer,
+        )
+        .with_clock(clock.clone());
+
+        Ok(Self {
+            db,
+            executor,
+            clock,
+            tenant_id,
+        })
+    }
+
+    /// Create an active connection to `provider_slug` for the harness tenant
+    pub async fn create_connection(
+        &self,
+        provider_slug: &str,
+    ) -> anyhow::Result<connection::Model> {
+        let now = clock_now(&self.clock);
+        let id = Uuid::new_v4();
+        connection::Entity::insert(connection::ActiveModel {
+            id: Set(id),
+            tenant_id: Set(self.tenant_id),
+            provider_slug: Set(provider_slug.to_string()),
+            external_id: Set(format!("{}-{}", provider_slug, id)),
+            status: Set("active".to_string()),
+            display_name: Set(None),
+            expires_at: Set(None),
+            scopes: Set(None),
+            metadata: Set(None),
+            created_at: Set(now),
+            updated_at: Set(now),
+        })
+        .exec_without_returning(&self.db)
+        .await?;
+        connection::Entity::find_by_id(id)
+            .one(&self.db)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("connection {} not found after insert", id))
+    }
+
+    /// Queue a sync job for `connection`, due at the harness clock's current time
+    pub async fn enqueue_sync(
+        &self,
+        connection: &connection::Model,
+    ) -> anyhow::Result<sync_job::Model> {
+        self.enqueue(connection, "sync", None).await
+    }
+
+    /// Queue a webhook job delivering `payload` to `connection`, due now
+    pub async fn enqueue_webhook(
+        &self,
+        connection: &connection::Model,
+        payload: serde_json::Value,
+    ) -> anyhow::Result<sync_job::Model> {
+        let cursor = serde_json::json!({ "webhook_payload": payload });
+        self.enqueue(connection, "webhook", Some(cursor)).await
+    }
+
+    async fn enqueue(
+        &self,
+        connection: &connection::Model,
+        job_type: &str,
+        cursor: Option<serde_json::Value>,
+    ) -> anyhow::Result<sync_job::Model> {
+        let now = clock_now(&self.clock);
+        let id = Uuid::new_v4();
+        sync_job::Entity::insert(sync_job::ActiveModel {
+            id: Set(id),
+            tenant_id: Set(connection.tenant_id),
+            provider_slug: Set(connection.provider_slug.clone()),
+            connection_id: Set(connection.id),
+            job_type: Set(job_type.to_string()),
+            status: Set("queued".to_string()),
+            priority: Set(10),
+            attempts: Set(0),
+            rate_limited_attempts: Set(0),
+            scheduled_at: Set(now),
+            retry_after: Set(None),
+            started_at: Set(None),
+            finished_at: Set(None),
+            locked_at: Set(None),
+            heartbeat_at: Set(None),
+            locked_by: Set(None),
+            cursor: Set(cursor),
+            error: Set(None),
+            created_at: Set(now),
+            updated_at: Set(now),
+        })
+        .exec_without_returning(&self.db)
+        .await?;
+        sync_job::Entity::find_by_id(id)
+            .one(&self.db)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("sync job {} not found after insert", id))
+    }
+
+    /// Claim and run the jobs due now; returns how many ran
+    pub async fn run_once(&self) -> anyhow::Result<usize> {
+        self.executor
+            .claim_and_run_jobs()
+            .await
+            .map_err(|e| anyhow::anyhow!("{}", e))
+    }
+
+    /// Run jobs until none are queued, moving the clock to each retry time
+    ///
+    /// Stops after `max_rounds` claims so a script that never succeeds cannot
+    /// loop forever. Returns how many jobs ran.
+    pub async fn run_until_idle(&self, max_rounds: usize) -> anyhow::Result<usize> {
+        let mut total = 0;
+        for _ in 0..max_rounds {
+            let ran = self.run_once().await?;
+            total += ran;
+            if ran > 0 {
+                continue;
+            }
+            let Some(next_due) = self.next_due_at().await? else {
+                break;
+            };
+            self.clock.set(next_due);
+        }
+        Ok(total)
+    }
+
+    /// Earliest time a queued job becomes due, if any job is queued
+    async fn next_due_at(&self) -> anyhow::Result<Option<DateTime<Utc>>> {
+        let queued = sync_job::Entity::find()
+            .filter(sync_job::Column::Status.eq("queued"))
+            .all(&self.db)
+            .await?;
+        Ok(queued
+            .iter()
+            .map(|job| {
+                job.retry_after
+                    .unwrap_or(job.scheduled_at)
+                    .max(job.scheduled_at)
+            })
+            .min()
+            .map(|due| due.with_timezone(&Utc)))
+    }
+
+    /// Signals persisted for `connection_id`, oldest first
+    pub async fn signals(&self, connection_id: Uuid) -> anyhow::Result<Vec<signal::Model>> {
+        Ok(signal::Entity::find()
+            .filter(signal::Column::ConnectionId.eq(connection_id))
+            .order_by_asc(signal::Column::CreatedAt)
+            .all(&self.db)
+            .await?)
+    }
+
+    /// Sync jobs for `connection_id`, oldest first
+    pub async fn jobs(&self, connection_id: Uuid) -> anyhow::Result<Vec<sync_job::Model>> {
+        Ok(sync_job::Entity::find()
+            .filter(sync_job::Column::ConnectionId.eq(connection_id))
+            .order_by_asc(sync_job::Column::CreatedAt)
+            .all(&self.db)
+            .await?)
+    }
+
+    /// Executed runs of `connection_id`'s jobs, oldest first
+    pub async fn runs(&self, connection_id: Uuid) -> anyhow::Result<Vec<sync_job_run::Model>> {
+        Ok(sync_job_run::Entity::find()
+            .filter(sync_job_run::Column::ConnectionId.eq(connection_id))
+            .order_by_asc(sync_job_run::Column::StartedAt)
+            .all(&self.db)
+            .await?)
+    }
+}
+
+fn clock_now(clock: &MockClock) -> sea_orm::prelude::DateTimeWithTimeZone {
+    crate::clock::Clock::now(clock).fixed_offset()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::connectors::SyncErrorKind;
+
+    #[tokio::test]
+    async fn test_harness_runs_pages_and_retries_rate_limits() {
+        let mock = Arc::new(
+            MockConnector::new("mock-crm")
+                .page(
+                    MockPage::new()
+                        .keyed_signal("issue_created", "issue-1", serde_json::json!({"id": 1}))
+                        .next_cursor("page-2")
+                        .has_more(),
+                )
+                .rate_limited(Some(120))
+                .page(
+                    MockPage::new()
+                        .keyed_signal("issue_updated", "issue-1-v2", serde_json::json!({"id": 1}))
+                        .next_cursor("page-3"),
+                ),
+        );
+        let harness = SyncHarness::new([mock.clone()]).await.unwrap();
+        let connection = harness.create_connection("mock-crm").await.unwrap();
+        harness.enqueue_sync(&connection).await.unwrap();
+        let started = crate::clock::Clock::now(harness.clock.as_ref());
+
+        assert_eq!(harness.run_until_idle(10).await.unwrap(), 3);
+
+        let kinds: Vec<_> = harness
+            .signals(connection.id)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|signal| signal.kind)
+            .collect();
+        assert_eq!(kinds, vec!["issue_created", "issue_updated"]);
+        assert_eq!(
+            mock.sync_cursors(),
+            vec![
+                None,
+                Some(Cursor::from_string("page-2")),
+                Some(Cursor::from_string("page-2")),
+            ]
+        );
+
+        let jobs = harness.jobs(connection.id).await.unwrap();
+        assert_eq!(jobs.len(), 2);
+        assert!(jobs.iter().all(|job| job.status == "succeeded"));
+        // The follow-up job was retried once, after the provider's retry-after
+        let mut attempts: Vec<_> = jobs.iter().map(|job| job.attempts).collect();
+        attempts.sort();
+        assert_eq!(attempts, vec![1, 2]);
+        let elapsed = crate::clock::Clock::now(harness.clock.as_ref()) - started;
+        assert!(elapsed >= chrono::Duration::seconds(120));
+    }
+
+    #[tokio::test]
+    async fn test_mock_connector_replays_script_then_idles() {
+        let mock = MockConnector::new("mock-crm")
+            .error(SyncError::transient("flaky"))
+            .page(MockPage::new().signal("issue_created", serde_json::json!({})));
+        let connection = connection::Model {
+            id: Uuid::new_v4(),
+            tenant_id: Uuid::new_v4(),
+            provider_slug: "mock-crm".to_string(),
+            external_id: "acme".to_string(),
+            status: "active".to_string(),
+            display_name: None,
+            access_token_ciphertext: None,
+            refresh_token_ciphertext: None,
+            access_token: None,
+            refresh_token: None,
+            expires_at: None,
+            scopes: None,
+            metadata: None,
+            created_at: DateTime::from(Utc::now()),
+            updated_at: DateTime::from(Utc::now()),
+        };
+        let sync = |cursor: Option<Cursor>| {
+            mock.sync(SyncParams {
+                connection: connection.clone(),
+                cursor,
+                cancellation: Default::default(),
+            })
+        };
+
+        let error = sync(None).await.unwrap_err();
+        assert_eq!(
+            error.downcast_ref::<SyncError>().unwrap().kind,
+            SyncErrorKind::Transient
+        );
+        assert_eq!(sync(None).await.unwrap().signals.len(), 1);
+
+        let cursor = Some(Cursor::from_string("done"));
+        let idle = sync(cursor.clone()).await.unwrap();
+        assert!(idle.signals.is_empty());
+        assert_eq!(idle.next_cursor, cursor);
+        assert_eq!(mock.sync_calls(), 3);
+    }
+}
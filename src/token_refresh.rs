@@ -4,35 +4,50 @@
 //! nearing expiry. Also provides on-demand refresh functionality for sync and webhook
 //! operations when encountering 401 errors.
 
+use async_trait::async_trait;
 use chrono::{DateTime, Duration, Utc};
 use metrics::{counter, gauge, histogram};
-use rand::Rng;
 use sea_orm::{
-    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, Set,
-    prelude::DateTimeWithTimeZone,
+    ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder,
+    prelude::DateTimeWithTimeZone, sea_query::Query,
 };
 use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::Mutex;
-use tokio::time::{Duration as TokioDuration, sleep};
+use tokio::time::Duration as TokioDuration;
 use tokio_util::sync::CancellationToken;
 use tracing::{debug, error, info, instrument, warn};
 use uuid::Uuid;
 
+use crate::clock::{SharedClock, system_clock};
 use crate::config::AppConfig;
+use crate::connection_pause::AuthPausePolicy;
+use crate::connectors::google_service_account::{ServiceAccountKey, delegated_subject};
 use crate::connectors::registry::Registry;
 use crate::error::ApiError;
-use crate::models::connection::{self, ActiveModel as ConnectionActiveModel, Entity as Connection};
+use crate::models::connection::{self, Entity as Connection};
+use crate::models::connection_secret::{self, Entity as ConnectionSecret};
+use crate::models::token_event;
+use crate::refresh_lock::{LockOutcome, RefreshLockPolicy};
 use crate::repositories::connection::ConnectionRepository;
+use crate::repositories::token_event::{NewTokenEvent, TokenEventRepository};
+use crate::sync_executor::default_instance_id;
+use crate::token_vault::TokenVault;
 
 /// Background token refresh service
 pub struct TokenRefreshService {
     config: Arc<AppConfig>,
     db: Arc<DatabaseConnection>,
-    connection_repo: Arc<ConnectionRepository>,
+    /// Decrypts tokens for connectors and encrypts the tokens they return
+    token_vault: TokenVault,
     connector_registry: Registry,
+    /// Serializes refreshes of a connection across workers and backs off failures
+    lock_policy: RefreshLockPolicy,
+    /// Worker name recorded on the refresh locks this service takes
+    lock_holder: String,
     /// Tracks ongoing refresh operations to provide single-flight protection
     in_flight_refreshes: Arc<Mutex<HashMap<Uuid, tokio::task::JoinHandle<()>>>>,
+    clock: SharedClock,
 }
 
 #[derive(Debug, Default)]
@@ -66,6 +81,16 @@ pub struct RefreshResult {
     pub error: Option<String>,
 }
 
+/// On-demand token refresh used by the sync executor when a provider answers 401
+#[async_trait]
+pub trait TokenRefresher: Send + Sync {
+    /// Refresh one connection's tokens, joining a refresh already in flight
+    async fn refresh_on_demand(&self, connection_id: &Uuid) -> Result<RefreshResult, ApiError>;
+}
+
+/// Shared handle to the process's token refresher
+pub type SharedTokenRefresher = Arc<dyn TokenRefresher>;
+
 impl TokenRefreshService {
     /// Create a new token refresh service instance
     pub fn new(
@@ -75,14 +100,23 @@ impl TokenRefreshService {
         connector_registry: Registry,
     ) -> Self {
         Self {
+            lock_policy: RefreshLockPolicy::from_config(&config.token_refresh),
+            lock_holder: default_instance_id(),
             config,
             db,
-            connection_repo,
+            token_vault: TokenVault::from((*connection_repo).clone()),
             connector_registry,
             in_flight_refreshes: Arc::new(Mutex::new(HashMap::new())),
+            clock: system_clock(),
         }
     }
 
+    /// Replace the time source used for expiry windows, jitter waits, and tick pacing
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
     /// Run the token refresh loop until the provided shutdown token fires
     #[instrument(skip_all)]
     pub async fn run(&self, shutdown: CancellationToken) -> Result<(), ApiError> {
@@ -95,7 +129,7 @@ impl TokenRefreshService {
                     info!("Token refresh service shutdown requested");
                     break;
                 }
-                _ = sleep(tick_interval) => {
+                _ = self.clock.sleep(tick_interval) => {
                     let tick_started = std::time::Instant::now();
                     if let Err(err) = self.tick().await {
                         error!(error = ?err, "Token refresh tick failed");
@@ -114,7 +148,7 @@ impl TokenRefreshService {
     /// Execute one tick of the token refresh service
     #[instrument(skip_all)]
     pub async fn tick(&self) -> Result<(), ApiError> {
-        let now = Utc::now();
+        let now = self.clock.now();
         let mut stats = RefreshStats::default();
 
         // Find connections that need refresh
@@ -191,7 +225,15 @@ impl TokenRefreshService {
 
         let connections = Connection::find()
             .filter(connection::Column::Status.eq("active"))
-            .filter(connection::Column::RefreshTokenCiphertext.is_not_null())
+            .filter(
+                connection::Column::Id.in_subquery(
+                    Query::select()
+                        .column(connection_secret::Column::ConnectionId)
+                        .from(ConnectionSecret)
+                        .and_where(connection_secret::Column::RefreshTokenCiphertext.is_not_null())
+                        .to_owned(),
+                ),
+            )
             .filter(
                 connection::Column::ExpiresAt
                     .is_not_null()
@@ -226,7 +268,9 @@ impl TokenRefreshService {
                 jitter_seconds = jitter_seconds,
                 "Applying jitter before token refresh"
             );
-            sleep(TokioDuration::from_secs(jitter_seconds)).await;
+            self.clock
+                .sleep(TokioDuration::from_secs(jitter_seconds))
+                .await;
         }
 
         self.refresh_connection(connection, _now).await
@@ -241,21 +285,124 @@ impl TokenRefreshService {
     ) -> Result<RefreshResult, ApiError> {
         let refresh_start = std::time::Instant::now();
 
-        // Decrypt current tokens
-        let (_access_token, refresh_token, _) = self
-            .connection_repo
-            .decrypt_tokens(&connection)
+        // API keys and client credentials are not OAuth grants; there is nothing to refresh
+        if self
+            .connector_registry
+            .uses_static_credentials(&connection.provider_slug)
+        {
+            debug!(
+                provider_slug = %connection.provider_slug,
+                "Connection uses static credentials, skipping token refresh"
+            );
+            return Ok(RefreshResult {
+                success: false,
+                connection_id: connection.id,
+                new_access_token: None,
+                new_refresh_token: None,
+                new_expires_at: None,
+                error: Some(
+                    "Provider uses static credentials that cannot be refreshed".to_string(),
+                ),
+            });
+        }
+
+        // Refresh only while holding the connection's lock, so no other worker
+        // spends the same refresh token
+        let lock_now = self.clock.now();
+        match self
+            .lock_policy
+            .acquire(self.db.as_ref(), connection.id, &self.lock_holder, lock_now)
             .await
             .map_err(|e| {
-                error!(error = ?e, "Failed to decrypt tokens for connection");
+                error!(error = ?e, "Failed to take token refresh lock");
                 ApiError::new(
                     axum::http::StatusCode::INTERNAL_SERVER_ERROR,
                     "INTERNAL_SERVER_ERROR",
-                    "Failed to decrypt tokens",
+                    "Failed to take token refresh lock",
                 )
-            })?;
+            })? {
+            LockOutcome::Acquired => {}
+            LockOutcome::Held { holder, .. } => {
+                debug!(holder = ?holder, "Token refresh already in progress on another worker");
+                return Ok(RefreshResult {
+                    success: false,
+                    connection_id: connection.id,
+                    new_access_token: None,
+                    new_refresh_token: None,
+                    new_expires_at: None,
+                    error: Some("Token refresh already in progress on another worker".to_string()),
+                });
+            }
+            LockOutcome::BackingOff { retry_at } => {
+                return Ok(RefreshResult {
+                    success: false,
+                    connection_id: connection.id,
+                    new_access_token: None,
+                    new_refresh_token: None,
+                    new_expires_at: None,
+                    error: Some(format!("Token refresh backing off until {}", retry_at)),
+                });
+            }
+        }
+
+        let connection_id = connection.id;
+        let result = self.refresh_locked(connection, refresh_start).await;
+        let failure = match &result {
+            Ok(result) if result.success => None,
+            Ok(result) => result.error.clone(),
+            Err(e) => Some(e.message.to_string()),
+        };
+        let released = match failure {
+            // A connection moved to needs_reauth waits for the tenant, not for a retry
+            Some(error)
+                if self.classify_refresh_error(&error) != RefreshErrorClassification::Permanent =>
+            {
+                self.lock_policy
+                    .release_failed(
+                        self.db.as_ref(),
+                        connection_id,
+                        &self.lock_holder,
+                        &error,
+                        self.clock.now(),
+                    )
+                    .await
+                    .map(|_| ())
+            }
+            _ => {
+                self.lock_policy
+                    .release(
+                        self.db.as_ref(),
+                        connection_id,
+                        &self.lock_holder,
+                        self.clock.now(),
+                    )
+                    .await
+            }
+        };
+        if let Err(e) = released {
+            // The lock lapses on its own after its TTL
+            warn!(error = ?e, "Failed to release token refresh lock");
+        }
+        result
+    }
+
+    /// Refresh a connection whose refresh lock is held by this service
+    async fn refresh_locked(
+        &self,
+        connection: connection::Model,
+        refresh_start: std::time::Instant,
+    ) -> Result<RefreshResult, ApiError> {
+        // Load and decrypt current tokens; connectors only ever see plaintext
+        let connection = self.token_vault.open(connection).await.map_err(|e| {
+            error!(error = ?e, "Failed to decrypt tokens for connection");
+            ApiError::new(
+                axum::http::StatusCode::INTERNAL_SERVER_ERROR,
+                "INTERNAL_SERVER_ERROR",
+                "Failed to decrypt tokens",
+            )
+        })?;
 
-        if refresh_token.is_none() {
+        if connection.refresh_token.is_none() {
             warn!(
                 connection_id = %connection.id,
                 "Connection has no refresh token, cannot refresh"
@@ -290,8 +437,21 @@ impl TokenRefreshService {
                 )
             })?;
 
-        // Perform token refresh via connector
-        match connector.refresh_token(connection.clone()).await {
+        // Service-account connections mint a new delegated token; others refresh via connector
+        let refreshed = match delegated_subject(connection.metadata.as_ref()) {
+            Some(subject) => self.refresh_delegated(&connection, subject).await,
+            None => connector.refresh_token(connection.clone()).await,
+        };
+        // Encrypt and persist the refreshed tokens before reporting success
+        let refreshed = match refreshed {
+            Ok(refreshed) => match self.token_vault.store(&connection.id, &refreshed).await {
+                Ok(_) => Ok(refreshed),
+                Err(e) => Err(e.into()),
+            },
+            Err(e) => Err(e),
+        };
+        self.record_token_event(&connection, &refreshed).await;
+        match refreshed {
             Ok(refreshed_connection) => {
                 let refresh_duration = refresh_start.elapsed();
                 histogram!("token_refresh_latency_ms")
@@ -315,10 +475,10 @@ impl TokenRefreshService {
                     success: true,
                     connection_id: refreshed_connection.id,
                     new_access_token: refreshed_connection
-                        .access_token_ciphertext
+                        .access_token
                         .map(|_| "[REDACTED]".to_string()),
                     new_refresh_token: refreshed_connection
-                        .refresh_token_ciphertext
+                        .refresh_token
                         .map(|_| "[REDACTED]".to_string()),
                     new_expires_at: refreshed_connection
                         .expires_at
@@ -344,12 +504,11 @@ impl TokenRefreshService {
                             connection_id = %connection.id,
                             provider_slug = %connection.provider_slug,
                             error = %error_str,
-                            "Permanent token refresh failure - marking connection as error"
+                            "Permanent token refresh failure - connection needs re-authorization"
                         );
 
-                        // Mark connection as error/revoked status
-                        self.mark_connection_error(&connection.id, &error_str)
-                            .await?;
+                        // Take the connection out of scheduling until the tenant re-authorizes
+                        self.mark_needs_reauth(&connection.id, &error_str).await?;
 
                         counter!("token_refresh_permanent_failure_total").increment(1);
                     }
@@ -394,6 +553,61 @@ impl TokenRefreshService {
         }
     }
 
+    /// Append a refresh sent to the provider to the connection's token history
+    async fn record_token_event(
+        &self,
+        connection: &connection::Model,
+        refreshed: &Result<connection::Model, Box<dyn std::error::Error + Send + Sync>>,
+    ) {
+        let event = match refreshed {
+            Ok(refreshed) => NewTokenEvent {
+                connection,
+                new_expires_at: refreshed.expires_at.map(|at| at.with_timezone(&Utc)),
+                refresh_token_rotated: refreshed.refresh_token.is_some()
+                    && refreshed.refresh_token != connection.refresh_token,
+                outcome: token_event::OUTCOME_SUCCEEDED,
+                error: None,
+                occurred_at: self.clock.now(),
+            },
+            Err(e) => NewTokenEvent {
+                connection,
+                new_expires_at: None,
+                refresh_token_rotated: false,
+                outcome: token_event::OUTCOME_FAILED,
+                error: Some(e.to_string()),
+                occurred_at: self.clock.now(),
+            },
+        };
+        if let Err(e) = TokenEventRepository::record(self.db.as_ref(), event).await {
+            warn!(error = ?e, "Failed to record token event");
+        }
+    }
+
+    /// Mint a delegated token from the service-account key stored as the refresh token
+    async fn refresh_delegated(
+        &self,
+        connection: &connection::Model,
+        subject: &str,
+    ) -> Result<connection::Model, Box<dyn std::error::Error + Send + Sync>> {
+        let key =
+            ServiceAccountKey::parse(connection.refresh_token.as_deref().unwrap_or_default())?;
+        let scopes: Vec<String> = connection
+            .scopes
+            .clone()
+            .and_then(|scopes| serde_json::from_value(scopes).ok())
+            .unwrap_or_default();
+        let token = key
+            .delegated_token(&crate::egress::client(), subject, &scopes)
+            .await?;
+        // The key stays the refresh token; only the access token and expiry change
+        Ok(connection::Model {
+            access_token: Some(token.access_token),
+            refresh_token: None,
+            expires_at: Some(token.expires_at.fixed_offset()),
+            ..connection.clone()
+        })
+    }
+
     /// Classify token refresh errors for appropriate handling strategy
     pub fn classify_refresh_error(&self, error_str: &str) -> RefreshErrorClassification {
         let error_lower = error_str.to_lowercase();
@@ -423,53 +637,38 @@ impl TokenRefreshService {
         RefreshErrorClassification::Transient
     }
 
-    /// Mark a connection as having an error status due to failed refresh
-    async fn mark_connection_error(
+    /// Move a connection whose refresh failed permanently to `needs_reauth`
+    async fn mark_needs_reauth(
         &self,
         connection_id: &Uuid,
         error_msg: &str,
     ) -> Result<(), ApiError> {
-        let updated = ConnectionActiveModel {
-            id: Set(*connection_id),
-            status: Set("error".to_string()),
-            updated_at: Set(Utc::now().into()),
-            ..Default::default()
-        };
-
-        updated.update(self.db.as_ref()).await.map_err(|e| {
-            error!(
-                connection_id = %connection_id,
-                error = ?e,
-                "Failed to mark connection as error status"
-            );
-            ApiError::new(
-                axum::http::StatusCode::INTERNAL_SERVER_ERROR,
-                "INTERNAL_SERVER_ERROR",
-                "Failed to update connection status",
-            )
-        })?;
-
-        warn!(
-            connection_id = %connection_id,
-            error = %error_msg,
-            "Marked connection as error status due to failed token refresh"
-        );
+        AuthPausePolicy::new(self.db.clone(), self.config.connection_auth_pause_threshold)
+            .mark_needs_reauth(*connection_id, error_msg)
+            .await
+            .map_err(|e| {
+                error!(
+                    connection_id = %connection_id,
+                    error = ?e,
+                    "Failed to mark connection as needing re-authorization"
+                );
+                ApiError::new(
+                    axum::http::StatusCode::INTERNAL_SERVER_ERROR,
+                    "INTERNAL_SERVER_ERROR",
+                    "Failed to update connection status",
+                )
+            })?;
 
-        counter!("token_refresh_connections_marked_error_total").increment(1);
+        counter!("token_refresh_connections_needs_reauth_total").increment(1);
         Ok(())
     }
 
     /// Compute jitter delay based on configuration
     fn compute_jitter(&self) -> u64 {
-        if self.config.token_refresh.jitter_factor <= 0.0 {
-            return 0;
-        }
-
-        let max_delay_seconds = (self.config.token_refresh.lead_time_seconds as f64
-            * self.config.token_refresh.jitter_factor) as u64;
-
-        let mut rng = rand::thread_rng();
-        rng.gen_range(0..=max_delay_seconds)
+        let token_refresh = &self.config.token_refresh;
+        token_refresh
+            .jitter_range()
+            .offset_seconds(token_refresh.lead_time_seconds)
     }
 
     /// On-demand refresh for when operations receive a 401 error
@@ -533,7 +732,7 @@ impl TokenRefreshService {
             in_flight.insert(*connection_id, tokio::spawn(async {}));
         }
 
-        let result = self.refresh_connection(connection, Utc::now()).await;
+        let result = self.refresh_connection(connection, self.clock.now()).await;
 
         // Clean up the in-flight entry
         {
@@ -592,14 +791,24 @@ impl TokenRefreshService {
     }
 }
 
+#[async_trait]
+impl TokenRefresher for TokenRefreshService {
+    async fn refresh_on_demand(&self, connection_id: &Uuid) -> Result<RefreshResult, ApiError> {
+        TokenRefreshService::refresh_on_demand(self, connection_id).await
+    }
+}
+
 impl Clone for TokenRefreshService {
     fn clone(&self) -> Self {
         Self {
             config: self.config.clone(),
             db: self.db.clone(),
-            connection_repo: self.connection_repo.clone(),
+            token_vault: self.token_vault.clone(),
             connector_registry: self.connector_registry.clone(),
+            lock_policy: self.lock_policy,
+            lock_holder: self.lock_holder.clone(),
             in_flight_refreshes: self.in_flight_refreshes.clone(),
+            clock: self.clock.clone(),
         }
     }
 }
@@ -0,0 +1,157 @@
+//! # Token Vault
+//!
+//! The single boundary between connectors and stored tokens. Connectors read and
+//! return tokens in plaintext in a connection's `access_token` and `refresh_token`
+//! fields; everything persisted is ciphertext, and the `*_ciphertext` fields only
+//! ever carry encrypted bytes. [`TokenVault::open`] decrypts stored tokens into the
+//! plaintext fields
+//! before a connector runs, and [`TokenVault::create`] / [`TokenVault::store`]
+//! encrypt whatever a connector returns before it reaches the database, so
+//! plaintext tokens are never written.
+
+use std::sync::Arc;
+
+use anyhow::Result;
+use chrono::Utc;
+use sea_orm::DatabaseConnection;
+use uuid::Uuid;
+
+use crate::crypto::MasterKeyring;
+use crate::models::connection;
+use crate::repositories::ConnectionRepository;
+
+/// Encrypts and decrypts connection tokens on behalf of connectors
+#[derive(Debug, Clone)]
+pub struct TokenVault {
+    connections: ConnectionRepository,
+}
+
+impl TokenVault {
+    /// Creates a vault that encrypts with tenant data keys wrapped by `master_keys`
+    pub fn new(db: Arc<DatabaseConnection>, master_keys: impl Into<MasterKeyring>) -> Self {
+        Self {
+            connections: ConnectionRepository::new(db, master_keys),
+        }
+    }
+
+    /// Returns the connection with its stored tokens decrypted into the plaintext token fields
+    pub async fn open(&self, connection: connection::Model) -> Result<connection::Model> {
+        let mut connection = self.connections.with_secrets(connection).await?;
+        let (access_token, refresh_token, _) = self.connections.decrypt_tokens(&connection).await?;
+        connection.access_token = access_token;
+        connection.refresh_token = refresh_token;
+        Ok(connection)
+    }
+
+    /// Persists a connection built by a connector's `exchange_token`, encrypting its tokens
+    pub async fn create(&self, mut connection: connection::Model) -> Result<connection::Model> {
+        let access_token = connection.access_token.take();
+        let refresh_token = connection.refresh_token.take();
+        self.connections
+            .create_with_tokens(
+                connection.into(),
+                access_token.as_deref(),
+                refresh_token.as_deref(),
+            )
+            .await
+    }
+
+    /// Encrypts and stores the tokens and expiry a connector returned for an existing connection.
+    ///
+    /// A token field left empty keeps the stored token.
+    pub async fn store(
+        &self,
+        connection_id: &Uuid,
+        tokens: &connection::Model,
+    ) -> Result<connection::Model> {
+        self.connections
+            .encrypt_and_update_tokens(
+                connection_id,
+                tokens.access_token.as_deref(),
+                tokens.refresh_token.as_deref(),
+                tokens.expires_at.map(|at| at.with_timezone(&Utc)),
+            )
+            .await
+    }
+}
+
+impl From<ConnectionRepository> for TokenVault {
+    fn from(connections: ConnectionRepository) -> Self {
+        Self { connections }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::{CryptoKey, is_encrypted_payload};
+    use crate::repositories::ConnectionSecretRepository;
+    use crate::repositories::provider::ProviderRepository;
+    use crate::test_fixtures;
+
+    #[tokio::test]
+    async fn test_connector_tokens_are_encrypted_at_rest() {
+        let (db, tenant_id) = test_fixtures::setup_tenant().await;
+        ProviderRepository::new(Arc::new(db.clone()))
+            .upsert("vault-provider", "Vault Provider", "oauth")
+            .await
+            .unwrap();
+
+        let db = Arc::new(db);
+        let vault = TokenVault::new(db.clone(), CryptoKey::new(vec![7u8; 32]).unwrap());
+        let now = Utc::now().fixed_offset();
+        // What a connector's exchange_token returns
+        let exchanged = connection::Model {
+            id: Uuid::new_v4(),
+            tenant_id,
+            provider_slug: "vault-provider".to_string(),
+            external_id: "user-1".to_string(),
+            status: "active".to_string(),
+            display_name: None,
+            access_token_ciphertext: None,
+            refresh_token_ciphertext: None,
+            access_token: Some("gho_access".to_string()),
+            refresh_token: Some("ghr_refresh".to_string()),
+            expires_at: None,
+            scopes: None,
+            metadata: None,
+            created_at: now,
+            updated_at: now,
+        };
+        let created = vault.create(exchanged).await.unwrap();
+
+        let secrets = ConnectionSecretRepository::new(db.clone());
+        let stored = secrets.find(&created.id).await.unwrap().unwrap();
+        for token in [
+            &stored.access_token_ciphertext,
+            &stored.refresh_token_ciphertext,
+        ] {
+            let token = token.as_ref().unwrap();
+            assert!(is_encrypted_payload(token));
+            assert!(!token.windows(3).any(|w| w == b"gh"));
+        }
+
+        let opened = vault.open(created.clone()).await.unwrap();
+        assert_eq!(opened.access_token.as_deref(), Some("gho_access"));
+        assert_eq!(opened.refresh_token.as_deref(), Some("ghr_refresh"));
+        assert_eq!(
+            opened.access_token_ciphertext,
+            stored.access_token_ciphertext
+        );
+
+        // What a connector's refresh_token returns: a new access token only
+        let refreshed = connection::Model {
+            access_token: Some("gho_rotated".to_string()),
+            refresh_token: None,
+            ..opened
+        };
+        vault.store(&created.id, &refreshed).await.unwrap();
+        let reopened = vault.open(created).await.unwrap();
+        assert_eq!(reopened.access_token.as_deref(), Some("gho_rotated"));
+        assert_eq!(reopened.refresh_token.as_deref(), Some("ghr_refresh"));
+        let stored = secrets.find(&reopened.id).await.unwrap().unwrap();
+        assert!(is_encrypted_payload(
+            stored.access_token_ciphertext.as_ref().unwrap()
+        ));
+    }
+}
@@ -0,0 +1,113 @@
+//! # Webhook Back-Pressure
+//!
+//! Webhook deliveries are buffered as queued `webhook` sync jobs. When that
+//! backlog reaches `webhook_backpressure_max_queued_jobs`, the webhook routes
+//! stop enqueueing and answer with the status each provider treats as "deliver
+//! again later", so events are redelivered by the provider rather than lost.
+//!
+//! Providers differ in what they honor: GitHub only redelivers failed (5xx)
+//! deliveries and ignores `Retry-After`, while Slack, Jira and Pub/Sub push back
+//! off on `429 Too Many Requests` and respect `Retry-After` where supported.
+
+use axum::http::StatusCode;
+use tracing::warn;
+
+use crate::error::ApiError;
+use crate::repositories::SyncJobRepository;
+use crate::server::AppState;
+
+/// Error code returned when a delivery is refused because the buffer is saturated
+pub const BACKPRESSURE_ERROR_CODE: &str = "WEBHOOK_BACKPRESSURE";
+
+/// How a provider expects to be told to retry a delivery
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BackpressurePolicy {
+    /// Response status the provider treats as retryable
+    pub status: StatusCode,
+    /// Whether to send a `Retry-After` header
+    pub retry_after: bool,
+}
+
+/// Retry semantics for a provider's webhook deliveries
+pub fn policy_for(provider_slug: &str) -> BackpressurePolicy {
+    match provider_slug {
+        // GitHub marks non-2xx deliveries as failed and redelivers 5xx; it has no Retry-After
+        "github" => BackpressurePolicy {
+            status: StatusCode::SERVICE_UNAVAILABLE,
+            retry_after: false,
+        },
+        // Slack Events API, Jira Cloud and Pub/Sub push all back off on 429
+        "slack" | "jira" | "gmail" => BackpressurePolicy {
+            status: StatusCode::TOO_MANY_REQUESTS,
+            retry_after: true,
+        },
+        _ => BackpressurePolicy {
+            status: StatusCode::SERVICE_UNAVAILABLE,
+            retry_after: true,
+        },
+    }
+}
+
+impl BackpressurePolicy {
+    /// Error response refusing a delivery under this policy
+    pub fn rejection(&self, retry_after_seconds: u64) -> ApiError {
+        let error = ApiError::new(
+            self.status,
+            BACKPRESSURE_ERROR_CODE,
+            "webhook buffer is saturated; retry the delivery later",
+        );
+        if self.retry_after {
+            error.with_retry_after(retry_after_seconds)
+        } else {
+            error
+        }
+    }
+}
+
+/// Refuse the delivery with the provider's retry status when the webhook backlog is full.
+///
+/// A limit of 0 disables the check.
+pub async fn check_capacity(state: &AppState, provider_slug: &str) -> Result<(), ApiError> {
+    let limit = state.config.webhook_backpressure_max_queued_jobs;
+    if limit == 0 {
+        return Ok(());
+    }
+
+    let queued = SyncJobRepository::new(state.db.clone())
+        .count_queued_webhook_jobs()
+        .await?;
+    if queued < limit {
+        return Ok(());
+    }
+
+    let policy = policy_for(provider_slug);
+    warn!(
+        provider_slug = %provider_slug,
+        queued,
+        limit,
+        status = policy.status.as_u16(),
+        "Webhook buffer saturated; asking provider to redeliver"
+    );
+    Err(policy.rejection(state.config.webhook_backpressure_retry_after_seconds))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::response::IntoResponse;
+
+    #[test]
+    fn test_rejections_follow_provider_retry_semantics() {
+        let github = policy_for("github").rejection(30).into_response();
+        assert_eq!(github.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert!(github.headers().get("retry-after").is_none());
+
+        let slack = policy_for("slack").rejection(30).into_response();
+        assert_eq!(slack.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(slack.headers().get("retry-after").unwrap(), "30");
+
+        let other = policy_for("zoho-cliq").rejection(5).into_response();
+        assert_eq!(other.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(other.headers().get("retry-after").unwrap(), "5");
+    }
+}
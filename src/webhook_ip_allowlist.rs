@@ -0,0 +1,348 @@
+//! # Webhook Source IP Allowlist
+//!
+//! Optional defense layer for public webhook routes: deliveries are accepted
+//! only from configured CIDR ranges per provider. For GitHub the published
+//! hook ranges can be fetched from the `/meta` API and cached, so deployments
+//! do not have to track GitHub's address changes by hand.
+//!
+//! The source address is the TCP peer by default. Behind load balancers set
+//! `POBLYSH_WEBHOOK_TRUSTED_PROXY_HOPS` so the client address is read from the
+//! matching `X-Forwarded-For` entry instead; headers are never trusted otherwise.
+
+use std::net::IpAddr;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+use axum::http::{HeaderMap, StatusCode};
+use serde::Deserialize;
+use tokio::sync::RwLock;
+use tracing::{debug, warn};
+
+use crate::config::AppConfig;
+
+const DEFAULT_GITHUB_API_BASE: &str = "https://api.github.com";
+
+/// A CIDR block such as `192.30.252.0/22` or `2a0a:a440::/29`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IpRange {
+    network: IpAddr,
+    prefix_len: u8,
+}
+
+impl IpRange {
+    /// Parse CIDR notation; a bare address is treated as a single-host range
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim();
+        let (addr, prefix) = match value.split_once('/') {
+            Some((addr, prefix)) => (addr, Some(prefix)),
+            None => (value, None),
+        };
+        let network: IpAddr = addr.parse().ok()?;
+        let max_len = if network.is_ipv4() { 32 } else { 128 };
+        let prefix_len = match prefix {
+            Some(prefix) => prefix.parse::<u8>().ok().filter(|len| *len <= max_len)?,
+            None => max_len,
+        };
+        Some(Self {
+            network,
+            prefix_len,
+        })
+    }
+
+    /// Whether `ip` falls inside this range; IPv4-mapped IPv6 addresses match IPv4 ranges
+    pub fn contains(&self, ip: IpAddr) -> bool {
+        match (self.network, ip.to_canonical()) {
+            (IpAddr::V4(network), IpAddr::V4(ip)) => {
+                prefix_matches(u32::from(network), u32::from(ip), self.prefix_len, 32)
+            }
+            (IpAddr::V6(network), IpAddr::V6(ip)) => {
+                prefix_matches(u128::from(network), u128::from(ip), self.prefix_len, 128)
+            }
+            _ => false,
+        }
+    }
+}
+
+fn prefix_matches<T>(network: T, ip: T, prefix_len: u8, bits: u8) -> bool
+where
+    T: std::ops::BitXor<Output = T> + std::ops::Shr<u32, Output = T> + PartialEq + From<u8>,
+{
+    if prefix_len == 0 {
+        return true;
+    }
+    ((network ^ ip) >> u32::from(bits - prefix_len)) == T::from(0)
+}
+
+/// Errors raised when a webhook source address is rejected
+#[derive(Debug, thiserror::Error)]
+pub enum IpAllowlistError {
+    #[error("Webhook source IP {ip} is not allowed for provider {provider}")]
+    NotAllowed { provider: String, ip: IpAddr },
+
+    #[error("Webhook source IP could not be determined")]
+    UnknownSource,
+
+    #[error("Allowed IP ranges for provider {provider} are unavailable")]
+    RangesUnavailable { provider: String },
+}
+
+impl IpAllowlistError {
+    /// Returns the appropriate HTTP status code for this error
+    pub fn status_code(&self) -> StatusCode {
+        match self {
+            IpAllowlistError::NotAllowed { .. } => StatusCode::FORBIDDEN,
+            IpAllowlistError::UnknownSource => StatusCode::FORBIDDEN,
+            IpAllowlistError::RangesUnavailable { .. } => StatusCode::SERVICE_UNAVAILABLE,
+        }
+    }
+}
+
+/// Resolve the client address from the peer and, when proxies are trusted, `X-Forwarded-For`.
+///
+/// With `trusted_proxy_hops = n`, the n-th address from the right of the
+/// forwarded chain is used: everything to its left is client-controlled.
+pub fn client_ip(
+    headers: &HeaderMap,
+    peer: Option<IpAddr>,
+    trusted_proxy_hops: usize,
+) -> Option<IpAddr> {
+    if trusted_proxy_hops == 0 {
+        return peer;
+    }
+    let forwarded: Vec<&str> = headers
+        .get_all("x-forwarded-for")
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .collect();
+    forwarded
+        .len()
+        .checked_sub(trusted_proxy_hops)
+        .and_then(|index| forwarded[index].parse().ok())
+}
+
+#[derive(Debug, Deserialize)]
+struct GitHubMeta {
+    #[serde(default)]
+    hooks: Vec<String>,
+}
+
+/// GitHub hook ranges and when they were fetched
+type CachedRanges = Option<(Instant, Vec<IpRange>)>;
+
+static GITHUB_HOOK_RANGES: OnceLock<RwLock<CachedRanges>> = OnceLock::new();
+
+/// GitHub hook ranges from the `/meta` API, refreshed once the cache is older than the configured TTL.
+///
+/// A failed refresh keeps serving the previous ranges; only a cold cache returns `None`.
+async fn github_hook_ranges(config: &AppConfig) -> Option<Vec<IpRange>> {
+    let cache = GITHUB_HOOK_RANGES.get_or_init(|| RwLock::new(None));
+    let ttl = Duration::from_secs(config.webhook_ip_allowlist.github_meta_refresh_seconds);
+
+    if let Some((fetched_at, ranges)) = cache.read().await.as_ref()
+        && fetched_at.elapsed() < ttl
+    {
+        return Some(ranges.clone());
+    }
+
+    let mut guard = cache.write().await;
+    // Another request may have refreshed while we waited for the lock
+    if let Some((fetched_at, ranges)) = guard.as_ref()
+        && fetched_at.elapsed() < ttl
+    {
+        return Some(ranges.clone());
+    }
+
+    let api_base = config
+        .github_api_base
+        .as_deref()
+        .unwrap_or(DEFAULT_GITHUB_API_BASE)
+        .trim_end_matches('/');
+    match fetch_github_hook_ranges(api_base).await {
+        Ok(ranges) => {
+            debug!(count = ranges.len(), "Refreshed GitHub webhook IP ranges");
+            metrics::counter!("webhook_ip_allowlist_refresh_total", "provider" => "github", "outcome" => "success").increment(1);
+            *guard = Some((Instant::now(), ranges.clone()));
+            Some(ranges)
+        }
+        Err(e) => {
+            warn!(error = %e, "Failed to refresh GitHub webhook IP ranges");
+            metrics::counter!("webhook_ip_allowlist_refresh_total", "provider" => "github", "outcome" => "failure").increment(1);
+            guard.as_ref().map(|(_, ranges)| ranges.clone())
+        }
+    }
+}
+
+async fn fetch_github_hook_ranges(api_base: &str) -> Result<Vec<IpRange>, reqwest::Error> {
+    let meta: GitHubMeta = crate::egress::client()
+        .get(format!("{}/meta", api_base))
+        .header("Accept", "application/vnd.github+json")
+        .header("User-Agent", "poblysh-connectors")
+        .timeout(Duration::from_secs(10))
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+    Ok(meta
+        .hooks
+        .iter()
+        .filter_map(|cidr| IpRange::parse(cidr))
+        .collect())
+}
+
+#[cfg(test)]
+async fn reset_github_hook_ranges() {
+    if let Some(cache) = GITHUB_HOOK_RANGES.get() {
+        *cache.write().await = None;
+    }
+}
+
+/// Check a webhook delivery's source address against the provider's allowlist.
+///
+/// Providers without configured ranges are always allowed.
+pub async fn check_source_ip(
+    provider: &str,
+    headers: &HeaderMap,
+    peer: Option<IpAddr>,
+    config: &AppConfig,
+) -> Result<(), IpAllowlistError> {
+    let allowlist = &config.webhook_ip_allowlist;
+    if !allowlist.is_enabled(provider) {
+        return Ok(());
+    }
+
+    let mut ranges: Vec<IpRange> = allowlist
+        .providers
+        .get(provider)
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| IpRange::parse(entry))
+        .collect();
+    if provider == "github" && allowlist.github_meta {
+        match github_hook_ranges(config).await {
+            Some(meta_ranges) => ranges.extend(meta_ranges),
+            None if ranges.is_empty() => {
+                return Err(IpAllowlistError::RangesUnavailable {
+                    provider: provider.to_string(),
+                });
+            }
+            None => {}
+        }
+    }
+
+    let ip = client_ip(headers, peer, allowlist.trusted_proxy_hops)
+        .ok_or(IpAllowlistError::UnknownSource)?;
+    if ranges.iter().any(|range| range.contains(ip)) {
+        Ok(())
+    } else {
+        Err(IpAllowlistError::NotAllowed {
+            provider: provider.to_string(),
+            ip,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn ip(value: &str) -> IpAddr {
+        value.parse().unwrap()
+    }
+
+    #[test]
+    fn test_ip_range_parse_and_contains() {
+        let v4 = IpRange::parse("192.30.252.0/22").unwrap();
+        assert!(v4.contains(ip("192.30.252.1")));
+        assert!(v4.contains(ip("192.30.255.254")));
+        assert!(!v4.contains(ip("192.30.248.1")));
+        assert!(v4.contains(ip("::ffff:192.30.253.7")));
+
+        let v6 = IpRange::parse("2a0a:a440::/29").unwrap();
+        assert!(v6.contains(ip("2a0a:a447::1")));
+        assert!(!v6.contains(ip("2a0a:a448::1")));
+        assert!(!v6.contains(ip("192.30.252.1")));
+
+        let host = IpRange::parse("10.0.0.5").unwrap();
+        assert!(host.contains(ip("10.0.0.5")));
+        assert!(!host.contains(ip("10.0.0.6")));
+
+        assert!(IpRange::parse("0.0.0.0/0").unwrap().contains(ip("8.8.8.8")));
+        assert_eq!(IpRange::parse("10.0.0.0/33"), None);
+        assert_eq!(IpRange::parse("github.com"), None);
+    }
+
+    #[test]
+    fn test_client_ip_only_trusts_configured_hops() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "x-forwarded-for",
+            "1.1.1.1, 2.2.2.2, 3.3.3.3".parse().unwrap(),
+        );
+        let peer = Some(ip("10.0.0.1"));
+
+        assert_eq!(client_ip(&headers, peer, 0), peer);
+        assert_eq!(client_ip(&headers, peer, 1), Some(ip("3.3.3.3")));
+        assert_eq!(client_ip(&headers, peer, 2), Some(ip("2.2.2.2")));
+        assert_eq!(client_ip(&headers, peer, 4), None);
+        assert_eq!(client_ip(&HeaderMap::new(), peer, 1), None);
+    }
+
+    #[tokio::test]
+    async fn test_check_source_ip_uses_static_and_github_meta_ranges() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/meta"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "hooks": ["192.30.252.0/22", "2a0a:a440::/29"],
+                "web": ["140.82.112.0/20"]
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let mut config = AppConfig {
+            github_api_base: Some(server.uri()),
+            ..Default::default()
+        };
+        config.webhook_ip_allowlist.github_meta = true;
+        config.webhook_ip_allowlist.providers =
+            BTreeMap::from([("slack".to_string(), vec!["3.0.0.0/8".to_string()])]);
+        reset_github_hook_ranges().await;
+
+        let headers = HeaderMap::new();
+        assert!(
+            check_source_ip("github", &headers, Some(ip("192.30.252.10")), &config)
+                .await
+                .is_ok()
+        );
+        // Served from cache: the mock expects a single fetch
+        let err = check_source_ip("github", &headers, Some(ip("140.82.112.1")), &config)
+            .await
+            .unwrap_err();
+        assert_eq!(err.status_code(), StatusCode::FORBIDDEN);
+
+        assert!(
+            check_source_ip("slack", &headers, Some(ip("3.4.5.6")), &config)
+                .await
+                .is_ok()
+        );
+        assert!(matches!(
+            check_source_ip("slack", &headers, None, &config).await,
+            Err(IpAllowlistError::UnknownSource)
+        ));
+        // Providers without ranges are unaffected
+        assert!(
+            check_source_ip("jira", &headers, None, &config)
+                .await
+                .is_ok()
+        );
+        reset_github_hook_ranges().await;
+    }
+}
@@ -0,0 +1,120 @@
+//! # Outbound Webhook Signing
+//!
+//! Every delivery to a tenant's notification webhook (grounded-signal
+//! notifications and connection alerts) carries two headers:
+//!
+//! - `X-Poblysh-Timestamp`: Unix time in seconds when the request was signed
+//! - `X-Poblysh-Signature`: `sha256=<hex>` HMAC-SHA256 of `"{timestamp}.{body}"`
+//!   keyed with the tenant's signing secret
+//!
+//! Receivers recompute the HMAC over the raw body, compare it in constant time
+//! and reject timestamps outside a few minutes of their clock.
+//! `poblysh_connectors_client::webhooks::verify_signature` implements that
+//! check. Each retry is signed again with a fresh timestamp.
+
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use rand::RngCore;
+use sha2::Sha256;
+
+/// Header carrying the `sha256=<hex>` signature of a delivery
+pub const SIGNATURE_HEADER: &str = "X-Poblysh-Signature";
+
+/// Header carrying the Unix timestamp included in the signature
+pub const TIMESTAMP_HEADER: &str = "X-Poblysh-Timestamp";
+
+/// Prefix of generated secrets, so they are recognizable when leaked
+const SECRET_PREFIX: &str = "whsec_";
+
+/// Generate a new random signing secret
+pub fn generate_secret() -> String {
+    let mut bytes = [0u8; 32];
+    rand::rngs::OsRng.fill_bytes(&mut bytes);
+    format!("{}{}", SECRET_PREFIX, hex::encode(bytes))
+}
+
+/// `sha256=<hex>` HMAC of `"{timestamp}.{body}"`
+pub fn signature(secret: &str, timestamp: i64, body: &[u8]) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts any key length");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body);
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+/// Build a signed JSON `POST` of `body` to `url`, timestamped now
+pub fn signed_post(
+    client: &reqwest::Client,
+    url: &str,
+    secret: &str,
+    body: Vec<u8>,
+) -> reqwest::RequestBuilder {
+    let timestamp = Utc::now().timestamp();
+    client
+        .post(url)
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .header(TIMESTAMP_HEADER, timestamp.to_string())
+        .header(SIGNATURE_HEADER, signature(secret, timestamp, &body))
+        .body(body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{header_exists, method};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[test]
+    fn test_generated_secrets_are_unique_and_prefixed() {
+        let first = generate_secret();
+        assert!(first.starts_with("whsec_"));
+        assert_eq!(first.len(), "whsec_".len() + 64);
+        assert_ne!(first, generate_secret());
+    }
+
+    #[test]
+    fn test_signature_covers_timestamp_and_body() {
+        let signed = signature("secret", 1_700_000_000, br#"{"a":1}"#);
+        assert!(signed.starts_with("sha256="));
+        assert_ne!(signed, signature("secret", 1_700_000_001, br#"{"a":1}"#));
+        assert_ne!(signed, signature("secret", 1_700_000_000, br#"{"a":2}"#));
+        assert_ne!(signed, signature("other", 1_700_000_000, br#"{"a":1}"#));
+    }
+
+    #[tokio::test]
+    async fn test_signed_post_sends_matching_headers() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(header_exists(SIGNATURE_HEADER))
+            .and(header_exists(TIMESTAMP_HEADER))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let body = br#"{"type":"connection.paused"}"#.to_vec();
+        signed_post(
+            &reqwest::Client::new(),
+            &server.uri(),
+            "secret",
+            body.clone(),
+        )
+        .send()
+        .await
+        .unwrap();
+
+        let request = &server.received_requests().await.unwrap()[0];
+        let timestamp: i64 = request.headers[TIMESTAMP_HEADER]
+            .to_str()
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert!((Utc::now().timestamp() - timestamp).abs() < 60);
+        assert_eq!(
+            request.headers[SIGNATURE_HEADER].to_str().unwrap(),
+            signature("secret", timestamp, &body)
+        );
+        assert_eq!(request.body, body);
+    }
+}
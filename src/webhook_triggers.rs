@@ -0,0 +1,287 @@
+//! # Webhook-Triggered Incremental Syncs
+//!
+//! Some webhooks only tell us that something changed (a GitHub push, a Gmail
+//! Pub/Sub notification) rather than carrying the full data. For those events we
+//! enqueue a connection-scoped incremental sync instead of waiting for the next
+//! scheduled poll. The job is delayed by a debounce window so a burst of
+//! notifications collapses into a single run.
+
+use std::collections::HashMap;
+
+use base64::{Engine as _, engine::general_purpose::STANDARD};
+use chrono::Duration;
+use serde_json::Value as JsonValue;
+use tracing::{debug, error, info};
+
+use crate::models::connection::Model as ConnectionModel;
+use crate::repositories::{CoalescePolicy, EnqueueOutcome, SyncJobRepository};
+use crate::server::AppState;
+
+/// Connection metadata key listing the GitHub repositories (`owner/name`) whose
+/// pushes should trigger a sync. When absent or empty every repository is watched.
+pub const GITHUB_WATCHED_REPOSITORIES_KEY: &str = "watched_repositories";
+
+/// Priority for triggered syncs: ahead of scheduled polls, behind webhook processing
+const TRIGGERED_SYNC_PRIORITY: i16 = 40;
+
+/// A webhook event that warrants an incremental sync of its connection
+#[derive(Debug, Clone, PartialEq)]
+pub struct SyncTrigger {
+    /// Provider event that caused the trigger (e.g., "push", "gmail_notification")
+    pub event: String,
+    /// Provider resource the event refers to, when known (repository, mailbox)
+    pub resource: Option<String>,
+}
+
+/// Decide whether a webhook should trigger an incremental sync for its connection.
+///
+/// `headers` are the lower-cased webhook headers as persisted in the job cursor.
+pub fn detect_sync_trigger(
+    provider_slug: &str,
+    headers: &HashMap<String, String>,
+    payload: Option<&JsonValue>,
+    connection: &ConnectionModel,
+) -> Option<SyncTrigger> {
+    match provider_slug {
+        "github" => detect_github_push(headers, payload, connection),
+        "gmail" => detect_gmail_notification(payload),
+        _ => None,
+    }
+}
+
+fn detect_github_push(
+    headers: &HashMap<String, String>,
+    payload: Option<&JsonValue>,
+    connection: &ConnectionModel,
+) -> Option<SyncTrigger> {
+    if headers.get("x-github-event").map(String::as_str) != Some("push") {
+        return None;
+    }
+
+    let repository = payload
+        .and_then(|p| p.get("repository"))
+        .and_then(|r| r.get("full_name"))
+        .and_then(|name| name.as_str())
+        .map(str::to_string);
+
+    let watched: Vec<&str> = connection
+        .metadata
+        .as_ref()
+        .and_then(|m| m.get(GITHUB_WATCHED_REPOSITORIES_KEY))
+        .and_then(|v| v.as_array())
+        .map(|repos| repos.iter().filter_map(|r| r.as_str()).collect())
+        .unwrap_or_default();
+
+    if !watched.is_empty() {
+        let is_watched = repository
+            .as_deref()
+            .is_some_and(|repo| watched.iter().any(|w| w.eq_ignore_ascii_case(repo)));
+        if !is_watched {
+            debug!(
+                connection_id = %connection.id,
+                repository = ?repository,
+                "Ignoring push to unwatched repository"
+            );
+            return None;
+        }
+    }
+
+    Some(SyncTrigger {
+        event: "push".to_string(),
+        resource: repository,
+    })
+}
+
+fn detect_gmail_notification(payload: Option<&JsonValue>) -> Option<SyncTrigger> {
+    let data = payload?.get("message")?.get("data")?.as_str()?;
+
+    // The mailbox is informational only; any well-formed notification triggers a sync
+    let email_address = STANDARD
+        .decode(data)
+        .ok()
+        .and_then(|decoded| serde_json::from_slice::<JsonValue>(&decoded).ok())
+        .and_then(|push| {
+            push.get("emailAddress")
+                .and_then(|e| e.as_str())
+                .map(str::to_string)
+        });
+
+    Some(SyncTrigger {
+        event: "gmail_notification".to_string(),
+        resource: email_address,
+    })
+}
+
+/// Enqueue a debounced incremental sync when the webhook warrants one.
+///
+/// Connections that are paused or need re-authorization are not synced.
+/// Failures are logged rather than returned: the webhook itself has already been
+/// accepted and the regular scheduler will pick up the changes eventually.
+pub async fn enqueue_triggered_sync(
+    state: &AppState,
+    provider_slug: &str,
+    connection: &ConnectionModel,
+    headers: &HashMap<String, String>,
+    payload: Option<&JsonValue>,
+) {
+    if connection.status != "active" {
+        debug!(
+            connection_id = %connection.id,
+            status = %connection.status,
+            "Connection is not active, skipping webhook-triggered sync"
+        );
+        return;
+    }
+    let Some(trigger) = detect_sync_trigger(provider_slug, headers, payload, connection) else {
+        return;
+    };
+
+    let policy = CoalescePolicy {
+        debounce: Duration::seconds(state.config.webhook_sync_debounce_seconds as i64),
+        max_delay: Duration::seconds(state.config.job_coalesce_window_seconds as i64),
+    };
+    let sync_job_repo = SyncJobRepository::new(state.db.clone());
+    match sync_job_repo
+        .enqueue_coalesced_job(
+            connection.tenant_id,
+            provider_slug,
+            connection.id,
+            "incremental",
+            TRIGGERED_SYNC_PRIORITY,
+            policy,
+        )
+        .await
+    {
+        Ok(EnqueueOutcome::Created(job)) => info!(
+            connection_id = %connection.id,
+            provider_slug = %provider_slug,
+            event = %trigger.event,
+            resource = ?trigger.resource,
+            job_id = %job.id,
+            scheduled_at = %job.scheduled_at,
+            "Webhook-triggered incremental sync enqueued"
+        ),
+        Ok(EnqueueOutcome::Coalesced(job)) => debug!(
+            connection_id = %connection.id,
+            provider_slug = %provider_slug,
+            event = %trigger.event,
+            resource = ?trigger.resource,
+            job_id = %job.id,
+            scheduled_at = %job.scheduled_at,
+            "Webhook trigger coalesced into pending incremental sync"
+        ),
+        Err(e) => error!(
+            connection_id = %connection.id,
+            provider_slug = %provider_slug,
+            error = ?e,
+            "Failed to enqueue webhook-triggered incremental sync"
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use serde_json::json;
+    use uuid::Uuid;
+
+    fn connection(metadata: Option<JsonValue>) -> ConnectionModel {
+        let now = Utc::now().fixed_offset();
+        ConnectionModel {
+            id: Uuid::new_v4(),
+            tenant_id: Uuid::new_v4(),
+            provider_slug: "github".to_string(),
+            external_id: "octocat".to_string(),
+            status: "active".to_string(),
+            display_name: None,
+            access_token_ciphertext: None,
+            refresh_token_ciphertext: None,
+            access_token: None,
+            refresh_token: None,
+            expires_at: None,
+            scopes: None,
+            metadata,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    fn github_headers(event: &str) -> HashMap<String, String> {
+        HashMap::from([("x-github-event".to_string(), event.to_string())])
+    }
+
+    #[test]
+    fn test_github_push_triggers_sync() {
+        let payload = json!({"repository": {"full_name": "octo/repo"}});
+        let trigger = detect_sync_trigger(
+            "github",
+            &github_headers("push"),
+            Some(&payload),
+            &connection(None),
+        )
+        .unwrap();
+
+        assert_eq!(trigger.event, "push");
+        assert_eq!(trigger.resource.as_deref(), Some("octo/repo"));
+    }
+
+    #[test]
+    fn test_github_non_push_event_does_not_trigger() {
+        let payload = json!({"repository": {"full_name": "octo/repo"}});
+        assert!(
+            detect_sync_trigger(
+                "github",
+                &github_headers("issues"),
+                Some(&payload),
+                &connection(None),
+            )
+            .is_none()
+        );
+    }
+
+    #[test]
+    fn test_github_push_respects_watched_repositories() {
+        let conn = connection(Some(json!({"watched_repositories": ["Octo/Watched"]})));
+
+        let watched = json!({"repository": {"full_name": "octo/watched"}});
+        assert!(
+            detect_sync_trigger("github", &github_headers("push"), Some(&watched), &conn).is_some()
+        );
+
+        let other = json!({"repository": {"full_name": "octo/other"}});
+        assert!(
+            detect_sync_trigger("github", &github_headers("push"), Some(&other), &conn).is_none()
+        );
+    }
+
+    #[test]
+    fn test_gmail_notification_triggers_sync() {
+        let data = STANDARD.encode(r#"{"emailAddress":"user@example.com","historyId":42}"#);
+        let payload = json!({"message": {"data": data, "messageId": "1"}, "subscription": "s"});
+
+        let trigger =
+            detect_sync_trigger("gmail", &HashMap::new(), Some(&payload), &connection(None))
+                .unwrap();
+        assert_eq!(trigger.event, "gmail_notification");
+        assert_eq!(trigger.resource.as_deref(), Some("user@example.com"));
+
+        assert!(
+            detect_sync_trigger(
+                "gmail",
+                &HashMap::new(),
+                Some(&json!({})),
+                &connection(None)
+            )
+            .is_none()
+        );
+    }
+
+    #[test]
+    fn test_other_providers_do_not_trigger() {
+        assert!(
+            detect_sync_trigger("jira", &HashMap::new(), Some(&json!({})), &connection(None))
+                .is_none()
+        );
+    }
+}
@@ -1,23 +1,34 @@
 //! # Webhook Signature Verification
 //!
-//! This module provides signature verification for GitHub and Slack webhooks
-//! using HMAC-SHA256 with constant-time comparison to prevent timing attacks.
+//! This module provides signature verification for GitHub, Slack, Linear, Zendesk, Sentry, CircleCI and Stripe webhooks
+//! using HMAC-SHA256 with constant-time comparison to prevent timing attacks, and
+//! shared-token verification for GitLab, Jira, Confluence, Salesforce, Datadog and Zoho Cliq.
+//! Custom webhooks are verified by their handler against each connection's own secret.
+//! Verified deliveries are also checked against a short-lived record of the
+//! signed material and delivery IDs already accepted by the same tenant, so a
+//! captured request cannot be replayed, not even under a fresh delivery ID.
+//! Custom webhook deliveries are recorded per connection, and only once their
+//! handler has authenticated them.
 
 use std::time::{SystemTime, UNIX_EPOCH};
 
 use axum::{
-    extract::{Request, State},
+    extract::{ConnectInfo, Request, State},
     http::{HeaderMap, StatusCode},
     middleware::Next,
     response::{IntoResponse, Response},
 };
 use hmac::{Hmac, Mac};
-use sha2::Sha256;
+use lru::LruCache;
+use sha2::{Digest, Sha256};
 use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::num::NonZeroUsize;
 use std::sync::{Mutex, OnceLock};
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use subtle::ConstantTimeEq;
 use tracing::{debug, error, info, warn};
+use uuid::Uuid;
 
 use crate::config::AppConfig;
 use crate::error::ApiError;
@@ -33,7 +44,7 @@ fn validate_operator_token(config: &AppConfig, token: &str) -> bool {
 }
 
 /// Extracts and validates operator bearer token from headers
-fn check_operator_auth(config: &AppConfig, headers: &HeaderMap) -> bool {
+pub fn check_operator_auth(config: &AppConfig, headers: &HeaderMap) -> bool {
     if let Some(auth_header) = headers.get("authorization").and_then(|h| h.to_str().ok())
         && let Some(token) = auth_header.strip_prefix("Bearer ")
     {
@@ -111,6 +122,153 @@ fn is_rate_limited(
     }
 }
 
+// Accepted delivery nonces per provider with their expiry; bounded so a flood
+// of unique deliveries evicts the oldest entries instead of growing memory
+static WEBHOOK_REPLAY: OnceLock<Mutex<LruCache<String, Instant>>> = OnceLock::new();
+
+const REPLAY_CACHE_CAPACITY: usize = 100_000;
+
+/// Nonces identifying a delivery to `scope` (the tenant, or the tenant and
+/// connection for custom webhooks), so the same delivery sent to two tenants is
+/// accepted by both. The first is derived from material the signature covers, so
+/// a captured delivery cannot be replayed under a fresh delivery ID; the
+/// provider's delivery ID, when sent, is tracked alongside it
+fn replay_keys(provider: &str, scope: &str, headers: &HeaderMap, body: &[u8]) -> Vec<String> {
+    let header = |name: &str| {
+        headers
+            .get(name)
+            .and_then(|h| h.to_str().ok())
+            .filter(|v| !v.is_empty())
+    };
+    let key = |kind: &str, material: &[u8]| {
+        let mut hasher = Sha256::new();
+        hasher.update(provider.as_bytes());
+        hasher.update(b":");
+        hasher.update(scope.as_bytes());
+        hasher.update(b":");
+        hasher.update(kind.as_bytes());
+        hasher.update(b":");
+        hasher.update(material);
+        hex::encode(hasher.finalize())
+    };
+
+    // Slack signs the request timestamp, so the signature is unique per delivery
+    let signed = match (provider, header("x-slack-signature")) {
+        ("slack", Some(signature)) => key("signature", signature.as_bytes()),
+        _ => key("body", body),
+    };
+    let delivery_id = match provider {
+        "github" => header("x-github-delivery"),
+        "jira" | "confluence" => header("x-atlassian-webhook-identifier"),
+        "linear" => header("linear-delivery"),
+        "gitlab" => header("x-gitlab-event-uuid"),
+        "zendesk" => header("x-zendesk-webhook-invocation-id"),
+        "sentry" => header("request-id"),
+        _ => None,
+    };
+
+    let mut keys = vec![signed];
+    keys.extend(delivery_id.map(|id| key("delivery", id.as_bytes())));
+    keys
+}
+
+/// How long an accepted delivery is remembered; zero disables replay protection
+fn replay_ttl(provider: &str, config: &AppConfig) -> Duration {
+    match provider {
+        // Older Slack requests already fail the timestamp check; cover skew in both directions
+        "slack" if config.webhook_replay_ttl_seconds > 0 => {
+            Duration::from_secs(config.webhook_slack_tolerance_seconds * 2)
+        }
+        _ => Duration::from_secs(config.webhook_replay_ttl_seconds),
+    }
+}
+
+/// Record a delivery's nonces, returning false if any was already accepted and has not expired
+fn reserve_delivery(keys: &[String], ttl: Duration) -> bool {
+    let cache = WEBHOOK_REPLAY.get_or_init(|| {
+        Mutex::new(LruCache::new(
+            NonZeroUsize::new(REPLAY_CACHE_CAPACITY).expect("non-zero capacity"),
+        ))
+    });
+    let now = Instant::now();
+    let mut guard = cache.lock().unwrap();
+    if keys
+        .iter()
+        .any(|key| guard.get(key).is_some_and(|expires_at| *expires_at > now))
+    {
+        return false;
+    }
+    for key in keys {
+        guard.put(key.clone(), now + ttl);
+    }
+    true
+}
+
+/// Forget a delivery's nonces so a provider retry of a failed delivery is accepted
+fn release_delivery(keys: &[String]) {
+    if let Some(cache) = WEBHOOK_REPLAY.get() {
+        let mut guard = cache.lock().unwrap();
+        for key in keys {
+            guard.pop(key);
+        }
+    }
+}
+
+/// Reserve a delivery's nonces, rejecting it as a duplicate if any is still recorded
+fn reserve_or_reject(provider: &str, keys: &[String], ttl: Duration) -> VerificationResult<()> {
+    if ttl.is_zero() || reserve_delivery(keys, ttl) {
+        Ok(())
+    } else {
+        metrics::counter!("signature_verification_replay_reject", "provider" => provider.to_string(), "outcome" => "duplicate_delivery").increment(1);
+        Err(VerificationError::DuplicateDelivery {
+            provider: provider.to_string(),
+        })
+    }
+}
+
+/// An authenticated custom webhook delivery's reserved nonces; dropped before
+/// [`DeliveryReservation::keep`], it forgets them so a retry of the failed
+/// delivery is accepted
+#[must_use]
+#[derive(Debug)]
+pub struct DeliveryReservation {
+    keys: Vec<String>,
+}
+
+impl DeliveryReservation {
+    /// Keep the nonces recorded once the delivery was accepted
+    pub fn keep(mut self) {
+        self.keys.clear();
+    }
+}
+
+impl Drop for DeliveryReservation {
+    fn drop(&mut self) {
+        release_delivery(&self.keys);
+    }
+}
+
+/// Reserve a custom webhook delivery to `connection_id` after its handler
+/// verified it against the connection's secret; `None` when replay protection
+/// is disabled
+pub fn reserve_custom_webhook_delivery(
+    config: &AppConfig,
+    tenant_id: Uuid,
+    connection_id: Uuid,
+    headers: &HeaderMap,
+    body: &[u8],
+) -> VerificationResult<Option<DeliveryReservation>> {
+    let provider = crate::connectors::CUSTOM_WEBHOOK_PROVIDER_SLUG;
+    let ttl = replay_ttl(provider, config);
+    if ttl.is_zero() {
+        return Ok(None);
+    }
+    let scope = format!("{}:{}", tenant_id, connection_id);
+    let keys = replay_keys(provider, &scope, headers, body);
+    reserve_or_reject(provider, &keys, ttl)?;
+    Ok(Some(DeliveryReservation { keys }))
+}
+
 /// Errors that can occur during webhook signature verification
 #[derive(Debug, thiserror::Error)]
 pub enum VerificationError {
@@ -140,6 +298,9 @@ pub enum VerificationError {
 
     #[error("Webhook verification not configured for provider: {provider}")]
     NotConfigured { provider: String },
+
+    #[error("Duplicate webhook delivery for provider: {provider}")]
+    DuplicateDelivery { provider: String },
 }
 
 impl VerificationError {
@@ -155,6 +316,7 @@ impl VerificationError {
             VerificationError::TimestampTooFuture { .. } => StatusCode::UNAUTHORIZED,
             VerificationError::UnsupportedProvider { .. } => StatusCode::NOT_FOUND,
             VerificationError::NotConfigured { .. } => StatusCode::UNAUTHORIZED,
+            VerificationError::DuplicateDelivery { .. } => StatusCode::UNAUTHORIZED,
         }
     }
 }
@@ -324,6 +486,483 @@ pub fn verify_slack_signature(
     }
 }
 
+/// Maximum age of a Linear delivery, per Linear's guidance on `webhookTimestamp`
+const LINEAR_TIMESTAMP_TOLERANCE_MS: u64 = 60_000;
+
+/// Verifies Linear webhook signature: hex HMAC-SHA256 of the raw body
+///
+/// The signed body carries `webhookTimestamp` in milliseconds; deliveries older
+/// than a minute are rejected as replays.
+pub fn verify_linear_signature(
+    body: &[u8],
+    signature_header: &str,
+    secret: &str,
+) -> VerificationResult<()> {
+    let start_time = Instant::now();
+    debug!(
+        body_size = body.len(),
+        "Starting Linear signature verification"
+    );
+
+    if signature_header.is_empty() {
+        return Err(VerificationError::MissingSignature {
+            header: "Linear-Signature".to_string(),
+        });
+    }
+
+    let mut mac = HmacSha256::new_from_slice(secret.as_bytes())
+        .map_err(|_| VerificationError::VerificationFailed)?;
+    mac.update(body);
+    let expected_bytes = mac.finalize().into_bytes();
+
+    let provided_bytes =
+        hex::decode(signature_header).map_err(|_| VerificationError::InvalidSignatureFormat {
+            header: "Linear-Signature contains invalid hex".to_string(),
+        })?;
+
+    let expected_bytes_array: &[u8] = expected_bytes.as_ref();
+    if !bool::from(subtle::ConstantTimeEq::ct_eq(
+        expected_bytes_array,
+        &provided_bytes[..],
+    )) {
+        metrics::counter!("signature_verification_failure", "provider" => "linear", "outcome" => "invalid_signature").increment(1);
+        metrics::histogram!("signature_verification_latency_seconds", "provider" => "linear")
+            .record(start_time.elapsed());
+        return Err(VerificationError::VerificationFailed);
+    }
+
+    // Only trust the timestamp once the signature proves it was not tampered with
+    let timestamp = serde_json::from_slice::<serde_json::Value>(body)
+        .ok()
+        .and_then(|payload| payload.get("webhookTimestamp").and_then(|v| v.as_u64()));
+    if let Some(timestamp) = timestamp {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_err(|_| VerificationError::InvalidTimestamp {
+                header: "Failed to get current time".to_string(),
+            })?
+            .as_millis() as u64;
+        let time_diff = now.abs_diff(timestamp);
+        if time_diff > LINEAR_TIMESTAMP_TOLERANCE_MS {
+            metrics::counter!("signature_verification_replay_reject", "provider" => "linear", "outcome" => "timestamp_out_of_window").increment(1);
+            metrics::histogram!("signature_verification_latency_seconds", "provider" => "linear")
+                .record(start_time.elapsed());
+            let (seconds, max_seconds) = (time_diff / 1000, LINEAR_TIMESTAMP_TOLERANCE_MS / 1000);
+            return Err(if now > timestamp {
+                VerificationError::TimestampTooOld {
+                    seconds,
+                    max_seconds,
+                }
+            } else {
+                VerificationError::TimestampTooFuture {
+                    seconds,
+                    max_seconds,
+                }
+            });
+        }
+    }
+
+    metrics::counter!("signature_verification_success", "provider" => "linear").increment(1);
+    metrics::histogram!("signature_verification_latency_seconds", "provider" => "linear")
+        .record(start_time.elapsed());
+    Ok(())
+}
+
+/// Maximum skew of a Zendesk delivery's signed timestamp
+const ZENDESK_TIMESTAMP_TOLERANCE_SECONDS: u64 = 300;
+
+/// Verifies a Zendesk webhook signature
+///
+/// Zendesk signs `timestamp + body` with HMAC-SHA256 and sends the base64
+/// digest in `X-Zendesk-Webhook-Signature`, with the RFC 3339 timestamp in
+/// `X-Zendesk-Webhook-Signature-Timestamp`. Deliveries more than five minutes
+/// away from now are rejected as replays.
+pub fn verify_zendesk_signature(
+    body: &[u8],
+    signature_header: &str,
+    timestamp_header: &str,
+    secret: &str,
+) -> VerificationResult<()> {
+    use base64::{Engine as _, engine::general_purpose};
+
+    let start_time = Instant::now();
+    debug!(
+        body_size = body.len(),
+        "Starting Zendesk signature verification"
+    );
+
+    if signature_header.is_empty() {
+        return Err(VerificationError::MissingSignature {
+            header: "X-Zendesk-Webhook-Signature".to_string(),
+        });
+    }
+
+    if timestamp_header.is_empty() {
+        return Err(VerificationError::MissingTimestamp {
+            header: "X-Zendesk-Webhook-Signature-Timestamp".to_string(),
+        });
+    }
+
+    let timestamp = chrono::DateTime::parse_from_rfc3339(timestamp_header)
+        .map_err(|_| VerificationError::InvalidTimestamp {
+            header: "X-Zendesk-Webhook-Signature-Timestamp must be an RFC 3339 timestamp"
+                .to_string(),
+        })?
+        .timestamp()
+        .max(0) as u64;
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_err(|_| VerificationError::InvalidTimestamp {
+            header: "Failed to get current time".to_string(),
+        })?
+        .as_secs();
+    let time_diff = now.abs_diff(timestamp);
+    if time_diff > ZENDESK_TIMESTAMP_TOLERANCE_SECONDS {
+        metrics::counter!("signature_verification_replay_reject", "provider" => "zendesk", "outcome" => "timestamp_out_of_window").increment(1);
+        metrics::histogram!("signature_verification_latency_seconds", "provider" => "zendesk")
+            .record(start_time.elapsed());
+        let max_seconds = ZENDESK_TIMESTAMP_TOLERANCE_SECONDS;
+        return Err(if now > timestamp {
+            VerificationError::TimestampTooOld {
+                seconds: time_diff,
+                max_seconds,
+            }
+        } else {
+            VerificationError::TimestampTooFuture {
+                seconds: time_diff,
+                max_seconds,
+            }
+        });
+    }
+
+    let provided_bytes = general_purpose::STANDARD
+        .decode(signature_header)
+        .map_err(|_| VerificationError::InvalidSignatureFormat {
+            header: "X-Zendesk-Webhook-Signature contains invalid base64".to_string(),
+        })?;
+
+    let mut mac = HmacSha256::new_from_slice(secret.as_bytes())
+        .map_err(|_| VerificationError::VerificationFailed)?;
+    mac.update(timestamp_header.as_bytes());
+    mac.update(body);
+    let expected_bytes = mac.finalize().into_bytes();
+
+    let expected_bytes_array: &[u8] = expected_bytes.as_ref();
+    if !bool::from(subtle::ConstantTimeEq::ct_eq(
+        expected_bytes_array,
+        &provided_bytes[..],
+    )) {
+        metrics::counter!("signature_verification_failure", "provider" => "zendesk", "outcome" => "invalid_signature").increment(1);
+        metrics::histogram!("signature_verification_latency_seconds", "provider" => "zendesk")
+            .record(start_time.elapsed());
+        return Err(VerificationError::VerificationFailed);
+    }
+
+    metrics::counter!("signature_verification_success", "provider" => "zendesk").increment(1);
+    metrics::histogram!("signature_verification_latency_seconds", "provider" => "zendesk")
+        .record(start_time.elapsed());
+    Ok(())
+}
+
+/// Verifies a Sentry integration webhook signature
+///
+/// Sentry sends the hex HMAC-SHA256 of the raw body, keyed with the
+/// integration's client secret, in `Sentry-Hook-Signature`. Neither
+/// `Sentry-Hook-Timestamp` nor `Request-ID` is signed, so there is no time
+/// window; replays are caught by the replay check on the signed body.
+pub fn verify_sentry_signature(
+    body: &[u8],
+    signature_header: &str,
+    secret: &str,
+) -> VerificationResult<()> {
+    let start_time = Instant::now();
+    debug!(
+        body_size = body.len(),
+        "Starting Sentry signature verification"
+    );
+
+    if signature_header.is_empty() {
+        return Err(VerificationError::MissingSignature {
+            header: "Sentry-Hook-Signature".to_string(),
+        });
+    }
+
+    let mut mac = HmacSha256::new_from_slice(secret.as_bytes())
+        .map_err(|_| VerificationError::VerificationFailed)?;
+    mac.update(body);
+    let expected_bytes = mac.finalize().into_bytes();
+
+    let provided_bytes =
+        hex::decode(signature_header).map_err(|_| VerificationError::InvalidSignatureFormat {
+            header: "Sentry-Hook-Signature contains invalid hex".to_string(),
+        })?;
+
+    let expected_bytes_array: &[u8] = expected_bytes.as_ref();
+    if !bool::from(subtle::ConstantTimeEq::ct_eq(
+        expected_bytes_array,
+        &provided_bytes[..],
+    )) {
+        metrics::counter!("signature_verification_failure", "provider" => "sentry", "outcome" => "invalid_signature").increment(1);
+        metrics::histogram!("signature_verification_latency_seconds", "provider" => "sentry")
+            .record(start_time.elapsed());
+        return Err(VerificationError::VerificationFailed);
+    }
+
+    metrics::counter!("signature_verification_success", "provider" => "sentry").increment(1);
+    metrics::histogram!("signature_verification_latency_seconds", "provider" => "sentry")
+        .record(start_time.elapsed());
+    Ok(())
+}
+
+/// Verifies a CircleCI webhook signature
+///
+/// CircleCI sends `circleci-signature: v1=<hex>`, the HMAC-SHA256 of the raw
+/// body keyed with the webhook's secret. The header may list several
+/// comma-separated signatures; any `v1` entry that matches is accepted.
+pub fn verify_circleci_signature(
+    body: &[u8],
+    signature_header: &str,
+    secret: &str,
+) -> VerificationResult<()> {
+    let start_time = Instant::now();
+    debug!(
+        body_size = body.len(),
+        "Starting CircleCI signature verification"
+    );
+
+    if signature_header.is_empty() {
+        return Err(VerificationError::MissingSignature {
+            header: "circleci-signature".to_string(),
+        });
+    }
+
+    let signatures: Vec<&str> = signature_header
+        .split(',')
+        .filter_map(|part| part.trim().strip_prefix("v1="))
+        .collect();
+    if signatures.is_empty() {
+        return Err(VerificationError::InvalidSignatureFormat {
+            header: "circleci-signature must contain a v1= signature".to_string(),
+        });
+    }
+
+    let mut mac = HmacSha256::new_from_slice(secret.as_bytes())
+        .map_err(|_| VerificationError::VerificationFailed)?;
+    mac.update(body);
+    let expected_bytes = mac.finalize().into_bytes();
+    let expected_bytes_array: &[u8] = expected_bytes.as_ref();
+
+    let matched = signatures.iter().any(|signature| {
+        hex::decode(signature).is_ok_and(|provided| {
+            bool::from(subtle::ConstantTimeEq::ct_eq(
+                expected_bytes_array,
+                &provided[..],
+            ))
+        })
+    });
+    if !matched {
+        metrics::counter!("signature_verification_failure", "provider" => "circleci", "outcome" => "invalid_signature").increment(1);
+        metrics::histogram!("signature_verification_latency_seconds", "provider" => "circleci")
+            .record(start_time.elapsed());
+        return Err(VerificationError::VerificationFailed);
+    }
+
+    metrics::counter!("signature_verification_success", "provider" => "circleci").increment(1);
+    metrics::histogram!("signature_verification_latency_seconds", "provider" => "circleci")
+        .record(start_time.elapsed());
+    Ok(())
+}
+
+/// Maximum age in seconds for Stripe signature timestamps
+const STRIPE_TIMESTAMP_TOLERANCE_SECONDS: u64 = 300;
+
+/// Verifies a Stripe webhook signature
+///
+/// Stripe sends `Stripe-Signature: t=<unix seconds>,v1=<hex>`, where `v1` is
+/// the HMAC-SHA256 of `"{t}.{raw body}"` keyed with the endpoint's signing
+/// secret. During secret rotation several `v1` entries are sent and any one
+/// may match. Deliveries more than five minutes off are rejected.
+pub fn verify_stripe_signature(
+    body: &[u8],
+    signature_header: &str,
+    secret: &str,
+) -> VerificationResult<()> {
+    let start_time = Instant::now();
+    debug!(
+        body_size = body.len(),
+        "Starting Stripe signature verification"
+    );
+
+    if signature_header.is_empty() {
+        return Err(VerificationError::MissingSignature {
+            header: "Stripe-Signature".to_string(),
+        });
+    }
+
+    let mut timestamp_value = None;
+    let mut signatures = Vec::new();
+    for part in signature_header.split(',') {
+        match part.trim().split_once('=') {
+            Some(("t", value)) => timestamp_value = Some(value),
+            Some(("v1", value)) => signatures.push(value),
+            _ => {}
+        }
+    }
+    let timestamp_value = timestamp_value.ok_or_else(|| VerificationError::MissingTimestamp {
+        header: "Stripe-Signature t=".to_string(),
+    })?;
+    if signatures.is_empty() {
+        return Err(VerificationError::InvalidSignatureFormat {
+            header: "Stripe-Signature must contain a v1= signature".to_string(),
+        });
+    }
+
+    let timestamp =
+        timestamp_value
+            .parse::<u64>()
+            .map_err(|_| VerificationError::InvalidTimestamp {
+                header: "Stripe-Signature t= must be Unix seconds".to_string(),
+            })?;
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_err(|_| VerificationError::InvalidTimestamp {
+            header: "Failed to get current time".to_string(),
+        })?
+        .as_secs();
+    let time_diff = now.abs_diff(timestamp);
+    if time_diff > STRIPE_TIMESTAMP_TOLERANCE_SECONDS {
+        metrics::counter!("signature_verification_replay_reject", "provider" => "stripe", "outcome" => "timestamp_out_of_window").increment(1);
+        metrics::histogram!("signature_verification_latency_seconds", "provider" => "stripe")
+            .record(start_time.elapsed());
+        let max_seconds = STRIPE_TIMESTAMP_TOLERANCE_SECONDS;
+        return Err(if now > timestamp {
+            VerificationError::TimestampTooOld {
+                seconds: time_diff,
+                max_seconds,
+            }
+        } else {
+            VerificationError::TimestampTooFuture {
+                seconds: time_diff,
+                max_seconds,
+            }
+        });
+    }
+
+    let mut mac = HmacSha256::new_from_slice(secret.as_bytes())
+        .map_err(|_| VerificationError::VerificationFailed)?;
+    mac.update(timestamp_value.as_bytes());
+    mac.update(b".");
+    mac.update(body);
+    let expected_bytes = mac.finalize().into_bytes();
+    let expected_bytes_array: &[u8] = expected_bytes.as_ref();
+
+    let matched = signatures.iter().any(|signature| {
+        hex::decode(signature).is_ok_and(|provided| {
+            bool::from(subtle::ConstantTimeEq::ct_eq(
+                expected_bytes_array,
+                &provided[..],
+            ))
+        })
+    });
+    if !matched {
+        metrics::counter!("signature_verification_failure", "provider" => "stripe", "outcome" => "invalid_signature").increment(1);
+        metrics::histogram!("signature_verification_latency_seconds", "provider" => "stripe")
+            .record(start_time.elapsed());
+        return Err(VerificationError::VerificationFailed);
+    }
+
+    metrics::counter!("signature_verification_success", "provider" => "stripe").increment(1);
+    metrics::histogram!("signature_verification_latency_seconds", "provider" => "stripe")
+        .record(start_time.elapsed());
+    Ok(())
+}
+
+/// Verifies a GitLab webhook's `X-Gitlab-Token` header
+///
+/// GitLab does not sign payloads; it sends the secret token configured on the
+/// webhook verbatim, so this is a constant-time comparison against that token.
+pub fn verify_gitlab_token(token_header: &str, token: &str) -> VerificationResult<()> {
+    let start_time = Instant::now();
+
+    if token_header.is_empty() {
+        return Err(VerificationError::MissingSignature {
+            header: "X-Gitlab-Token".to_string(),
+        });
+    }
+
+    if !bool::from(subtle::ConstantTimeEq::ct_eq(
+        token_header.as_bytes(),
+        token.as_bytes(),
+    )) {
+        metrics::counter!("signature_verification_failure", "provider" => "gitlab", "outcome" => "invalid_signature").increment(1);
+        metrics::histogram!("signature_verification_latency_seconds", "provider" => "gitlab")
+            .record(start_time.elapsed());
+        return Err(VerificationError::VerificationFailed);
+    }
+
+    metrics::counter!("signature_verification_success", "provider" => "gitlab").increment(1);
+    metrics::histogram!("signature_verification_latency_seconds", "provider" => "gitlab")
+        .record(start_time.elapsed());
+    Ok(())
+}
+
+/// Verifies a delivery to a tenant-registered custom webhook
+///
+/// Tools that can sign requests send `X-Webhook-Signature: sha256=<hex>`, the
+/// HMAC-SHA256 of the raw body keyed with the connection's secret; the
+/// `sha256=` prefix is optional. Tools that can only add a fixed header send
+/// the secret itself as `Authorization: Bearer <secret>` or `X-Webhook-Secret`.
+pub fn verify_custom_webhook(
+    body: &[u8],
+    headers: &HeaderMap,
+    secret: &str,
+) -> VerificationResult<()> {
+    let start_time = Instant::now();
+    let header = |name: &str| {
+        headers
+            .get(name)
+            .and_then(|h| h.to_str().ok())
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+    };
+
+    let verified = if let Some(signature) = header("x-webhook-signature") {
+        let signature = signature.strip_prefix("sha256=").unwrap_or(signature);
+        let provided =
+            hex::decode(signature).map_err(|_| VerificationError::InvalidSignatureFormat {
+                header: "X-Webhook-Signature must be hex, optionally prefixed with sha256="
+                    .to_string(),
+            })?;
+        let mut mac = HmacSha256::new_from_slice(secret.as_bytes())
+            .map_err(|_| VerificationError::VerificationFailed)?;
+        mac.update(body);
+        let expected = mac.finalize().into_bytes();
+        bool::from(ConstantTimeEq::ct_eq(&expected[..], &provided[..]))
+    } else if let Some(token) = header("x-webhook-secret")
+        .or_else(|| header("authorization").and_then(|auth| auth.strip_prefix("Bearer ")))
+    {
+        bool::from(ConstantTimeEq::ct_eq(token.as_bytes(), secret.as_bytes()))
+    } else {
+        return Err(VerificationError::MissingSignature {
+            header: "X-Webhook-Signature, X-Webhook-Secret or Authorization (Bearer)".to_string(),
+        });
+    };
+
+    if !verified {
+        metrics::counter!("signature_verification_failure", "provider" => "custom-webhook", "outcome" => "invalid_signature").increment(1);
+        metrics::histogram!("signature_verification_latency_seconds", "provider" => "custom-webhook")
+            .record(start_time.elapsed());
+        return Err(VerificationError::VerificationFailed);
+    }
+
+    metrics::counter!("signature_verification_success", "provider" => "custom-webhook")
+        .increment(1);
+    metrics::histogram!("signature_verification_latency_seconds", "provider" => "custom-webhook")
+        .record(start_time.elapsed());
+    Ok(())
+}
+
 /// Verifies webhook signature for the given provider
 pub fn verify_webhook_signature(
     provider: &str,
@@ -372,13 +1011,106 @@ pub fn verify_webhook_signature(
                 config.webhook_slack_tolerance_seconds,
             )
         }
-        "jira" => {
-            let secret = config.webhook_jira_secret.as_ref().ok_or_else(|| {
+        "linear" => {
+            let secret = config.webhook_linear_secret.as_ref().ok_or_else(|| {
                 VerificationError::NotConfigured {
-                    provider: "jira".to_string(),
+                    provider: "linear".to_string(),
                 }
             })?;
 
+            let signature_header = headers
+                .get("linear-signature")
+                .and_then(|h| h.to_str().ok())
+                .unwrap_or("");
+
+            verify_linear_signature(body, signature_header, secret)
+        }
+        "zendesk" => {
+            let secret = config.webhook_zendesk_secret.as_ref().ok_or_else(|| {
+                VerificationError::NotConfigured {
+                    provider: "zendesk".to_string(),
+                }
+            })?;
+
+            let signature_header = headers
+                .get("x-zendesk-webhook-signature")
+                .and_then(|h| h.to_str().ok())
+                .unwrap_or("");
+
+            let timestamp_header = headers
+                .get("x-zendesk-webhook-signature-timestamp")
+                .and_then(|h| h.to_str().ok())
+                .unwrap_or("");
+
+            verify_zendesk_signature(body, signature_header, timestamp_header, secret)
+        }
+        "sentry" => {
+            let secret = config.webhook_sentry_secret.as_ref().ok_or_else(|| {
+                VerificationError::NotConfigured {
+                    provider: "sentry".to_string(),
+                }
+            })?;
+
+            let signature_header = headers
+                .get("sentry-hook-signature")
+                .and_then(|h| h.to_str().ok())
+                .unwrap_or("");
+
+            verify_sentry_signature(body, signature_header, secret)
+        }
+        "circleci" => {
+            let secret = config.webhook_circleci_secret.as_ref().ok_or_else(|| {
+                VerificationError::NotConfigured {
+                    provider: "circleci".to_string(),
+                }
+            })?;
+
+            let signature_header = headers
+                .get("circleci-signature")
+                .and_then(|h| h.to_str().ok())
+                .unwrap_or("");
+
+            verify_circleci_signature(body, signature_header, secret)
+        }
+        "stripe" => {
+            let secret = config.webhook_stripe_secret.as_ref().ok_or_else(|| {
+                VerificationError::NotConfigured {
+                    provider: "stripe".to_string(),
+                }
+            })?;
+
+            let signature_header = headers
+                .get("stripe-signature")
+                .and_then(|h| h.to_str().ok())
+                .unwrap_or("");
+
+            verify_stripe_signature(body, signature_header, secret)
+        }
+        "gitlab" => {
+            let token = config.webhook_gitlab_token.as_ref().ok_or_else(|| {
+                VerificationError::NotConfigured {
+                    provider: "gitlab".to_string(),
+                }
+            })?;
+
+            let token_header = headers
+                .get("x-gitlab-token")
+                .and_then(|h| h.to_str().ok())
+                .unwrap_or("");
+
+            verify_gitlab_token(token_header, token)
+        }
+        "jira" | "confluence" | "salesforce" | "datadog" => {
+            let secret = match provider {
+                "jira" => config.webhook_jira_secret.as_ref(),
+                "confluence" => config.webhook_confluence_secret.as_ref(),
+                "datadog" => config.webhook_datadog_secret.as_ref(),
+                _ => config.webhook_salesforce_secret.as_ref(),
+            }
+            .ok_or_else(|| VerificationError::NotConfigured {
+                provider: provider.to_string(),
+            })?;
+
             // Enforce a single method: Authorization: Bearer <secret>
             let provided_auth = headers
                 .get("authorization")
@@ -458,12 +1190,43 @@ pub async fn webhook_verification_middleware(
         return next.run(request).await;
     }
 
+    // Reject deliveries from outside the provider's allowed source ranges
+    let peer = request
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| addr.ip());
+    if let Err(e) =
+        crate::webhook_ip_allowlist::check_source_ip(provider, headers, peer, &config).await
+    {
+        warn!(provider = %provider, tenant_id = %tenant_id, error = %e, "Webhook source IP rejected");
+        metrics::counter!("webhook_ip_rejected_total", "provider" => provider.to_string())
+            .increment(1);
+        let api_error = match e.status_code() {
+            StatusCode::SERVICE_UNAVAILABLE => ApiError::new(
+                StatusCode::SERVICE_UNAVAILABLE,
+                "SERVICE_UNAVAILABLE",
+                "Webhook source IP allowlist unavailable",
+            ),
+            status => ApiError::new(status, "FORBIDDEN", "Webhook source IP not allowed"),
+        };
+        return api_error.into_response();
+    }
+
     // Check if verification is configured for this provider
     // Note: Unsupported providers should proceed to verification to get proper 404 responses
     let verification_enabled = match provider {
         "github" => config.webhook_github_secret.is_some(),
         "slack" => config.webhook_slack_signing_secret.is_some(),
         "jira" => config.webhook_jira_secret.is_some(),
+        "confluence" => config.webhook_confluence_secret.is_some(),
+        "salesforce" => config.webhook_salesforce_secret.is_some(),
+        "linear" => config.webhook_linear_secret.is_some(),
+        "zendesk" => config.webhook_zendesk_secret.is_some(),
+        "sentry" => config.webhook_sentry_secret.is_some(),
+        "datadog" => config.webhook_datadog_secret.is_some(),
+        "circleci" => config.webhook_circleci_secret.is_some(),
+        "stripe" => config.webhook_stripe_secret.is_some(),
+        "gitlab" => config.webhook_gitlab_token.is_some(),
         "zoho-cliq" => config.webhook_zoho_cliq_token.is_some(),
         _ => true, // Allow unsupported providers to proceed to verification for proper 404
     };
@@ -532,8 +1295,24 @@ pub async fn webhook_verification_middleware(
         }
     };
 
-    // Verify the signature
-    match verify_webhook_signature(provider, &body_bytes, &parts.headers, &config) {
+    // Verify the signature, then reject deliveries the tenant already accepted.
+    // Custom webhooks are verified, and their deliveries reserved, by the handler
+    // once it has authenticated them against the targeted connection's secret.
+    let custom = provider == crate::connectors::CUSTOM_WEBHOOK_PROVIDER_SLUG;
+    let replay_ttl = if custom {
+        Duration::ZERO
+    } else {
+        replay_ttl(provider, &config)
+    };
+    let replay_keys = replay_keys(provider, tenant_id, &parts.headers, &body_bytes);
+    let verification = if custom {
+        Ok(())
+    } else {
+        verify_webhook_signature(provider, &body_bytes, &parts.headers, &config)
+    }
+    .and_then(|()| reserve_or_reject(provider, &replay_keys, replay_ttl));
+
+    match verification {
         Ok(()) => {
             info!(
                 provider = %provider,
@@ -543,7 +1322,13 @@ pub async fn webhook_verification_middleware(
 
             // Reconstruct the request with the body
             let request = Request::from_parts(parts, axum::body::Body::from(body_bytes));
-            next.run(request).await
+            let response = next.run(request).await;
+
+            // Failed deliveries are retried by providers with the same ID; let the retry through
+            if !replay_ttl.is_zero() && !response.status().is_success() {
+                release_delivery(&replay_keys);
+            }
+            response
         }
         Err(e) => {
             let error_msg = match &e {
@@ -586,6 +1371,9 @@ pub async fn webhook_verification_middleware(
                         provider
                     )
                 }
+                VerificationError::DuplicateDelivery { provider } => {
+                    format!("Duplicate webhook delivery for provider: {}", provider)
+                }
             };
 
             error!(
@@ -646,6 +1434,306 @@ mod tests {
         assert!(verify_github_signature(body, signature_header, secret).is_err());
     }
 
+    fn linear_signature(body: &[u8], secret: &str) -> String {
+        let mut mac = HmacSha256::new_from_slice(secret.as_bytes()).unwrap();
+        mac.update(body);
+        hex::encode(mac.finalize().into_bytes())
+    }
+
+    #[test]
+    fn test_linear_signature_verification() {
+        let secret = "test_secret";
+        let now_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_millis();
+        let body = format!(
+            r#"{{"action":"create","type":"Issue","webhookTimestamp":{}}}"#,
+            now_ms
+        );
+        let signature = linear_signature(body.as_bytes(), secret);
+
+        assert!(verify_linear_signature(body.as_bytes(), &signature, secret).is_ok());
+        assert!(matches!(
+            verify_linear_signature(body.as_bytes(), &signature, "other_secret"),
+            Err(VerificationError::VerificationFailed)
+        ));
+        assert!(matches!(
+            verify_linear_signature(body.as_bytes(), "", secret),
+            Err(VerificationError::MissingSignature { .. })
+        ));
+        assert!(matches!(
+            verify_linear_signature(body.as_bytes(), "not-hex", secret),
+            Err(VerificationError::InvalidSignatureFormat { .. })
+        ));
+    }
+
+    #[test]
+    fn test_linear_signature_verification_stale_timestamp() {
+        let secret = "test_secret";
+        let old_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_millis()
+            - 120_000;
+        let body = format!(
+            r#"{{"action":"create","type":"Issue","webhookTimestamp":{}}}"#,
+            old_ms
+        );
+        let signature = linear_signature(body.as_bytes(), secret);
+
+        assert!(matches!(
+            verify_linear_signature(body.as_bytes(), &signature, secret),
+            Err(VerificationError::TimestampTooOld { .. })
+        ));
+    }
+
+    fn zendesk_signature(timestamp: &str, body: &[u8], secret: &str) -> String {
+        use base64::{Engine as _, engine::general_purpose};
+
+        let mut mac = HmacSha256::new_from_slice(secret.as_bytes()).unwrap();
+        mac.update(timestamp.as_bytes());
+        mac.update(body);
+        general_purpose::STANDARD.encode(mac.finalize().into_bytes())
+    }
+
+    #[test]
+    fn test_zendesk_signature_verification() {
+        let secret = "zendesk-secret";
+        let body = br#"{"type":"zen:event-type:ticket.created"}"#;
+        let timestamp = chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
+        let signature = zendesk_signature(&timestamp, body, secret);
+
+        let mut headers = HeaderMap::new();
+        headers.insert("x-zendesk-webhook-signature", signature.parse().unwrap());
+        headers.insert(
+            "x-zendesk-webhook-signature-timestamp",
+            timestamp.parse().unwrap(),
+        );
+        let config = AppConfig {
+            webhook_zendesk_secret: Some(secret.to_string()),
+            ..Default::default()
+        };
+        assert!(verify_webhook_signature("zendesk", body, &headers, &config).is_ok());
+
+        assert!(matches!(
+            verify_zendesk_signature(b"{}", &signature, &timestamp, secret),
+            Err(VerificationError::VerificationFailed)
+        ));
+        assert!(matches!(
+            verify_zendesk_signature(body, "", &timestamp, secret),
+            Err(VerificationError::MissingSignature { .. })
+        ));
+        assert!(matches!(
+            verify_zendesk_signature(body, "%%%", &timestamp, secret),
+            Err(VerificationError::InvalidSignatureFormat { .. })
+        ));
+
+        let stale = (chrono::Utc::now() - chrono::Duration::minutes(10))
+            .to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
+        assert!(matches!(
+            verify_zendesk_signature(
+                body,
+                &zendesk_signature(&stale, body, secret),
+                &stale,
+                secret
+            ),
+            Err(VerificationError::TimestampTooOld { .. })
+        ));
+    }
+
+    #[test]
+    fn test_sentry_signature_verification() {
+        let secret = "sentry-client-secret";
+        let body = br#"{"action":"created","data":{"issue":{"id":"42"}}}"#;
+        let mut mac = HmacSha256::new_from_slice(secret.as_bytes()).unwrap();
+        mac.update(body);
+        let signature = hex::encode(mac.finalize().into_bytes());
+
+        let mut headers = HeaderMap::new();
+        headers.insert("sentry-hook-signature", signature.parse().unwrap());
+        let config = AppConfig {
+            webhook_sentry_secret: Some(secret.to_string()),
+            ..Default::default()
+        };
+        assert!(verify_webhook_signature("sentry", body, &headers, &config).is_ok());
+
+        assert!(matches!(
+            verify_sentry_signature(b"{}", &signature, secret),
+            Err(VerificationError::VerificationFailed)
+        ));
+        assert!(matches!(
+            verify_sentry_signature(body, "", secret),
+            Err(VerificationError::MissingSignature { .. })
+        ));
+        assert!(matches!(
+            verify_sentry_signature(body, "not-hex", secret),
+            Err(VerificationError::InvalidSignatureFormat { .. })
+        ));
+    }
+
+    fn stripe_signature(timestamp: u64, body: &[u8], secret: &str) -> String {
+        let mut mac = HmacSha256::new_from_slice(secret.as_bytes()).unwrap();
+        mac.update(format!("{}.", timestamp).as_bytes());
+        mac.update(body);
+        hex::encode(mac.finalize().into_bytes())
+    }
+
+    #[test]
+    fn test_stripe_signature_verification() {
+        let secret = "whsec_test";
+        let body = br#"{"id":"evt_1","type":"invoice.paid"}"#;
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+
+        // A rotated secret's signature may come first
+        let header = format!(
+            "t={},v1={},v1={}",
+            now,
+            stripe_signature(now, body, "whsec_old"),
+            stripe_signature(now, body, secret)
+        );
+        let mut headers = HeaderMap::new();
+        headers.insert("stripe-signature", header.parse().unwrap());
+        let config = AppConfig {
+            webhook_stripe_secret: Some(secret.to_string()),
+            ..Default::default()
+        };
+        assert!(verify_webhook_signature("stripe", body, &headers, &config).is_ok());
+
+        assert!(matches!(
+            verify_stripe_signature(b"{}", &header, secret),
+            Err(VerificationError::VerificationFailed)
+        ));
+        assert!(matches!(
+            verify_stripe_signature(
+                body,
+                &format!("v1={}", stripe_signature(now, body, secret)),
+                secret
+            ),
+            Err(VerificationError::MissingTimestamp { .. })
+        ));
+        let stale = now - 600;
+        assert!(matches!(
+            verify_stripe_signature(
+                body,
+                &format!("t={},v1={}", stale, stripe_signature(stale, body, secret)),
+                secret
+            ),
+            Err(VerificationError::TimestampTooOld { .. })
+        ));
+    }
+
+    #[test]
+    fn test_custom_webhook_verification() {
+        let secret = "tenant-shared-secret";
+        let body = br#"{"event":{"type":"deploy.failed"}}"#;
+        let mut mac = HmacSha256::new_from_slice(secret.as_bytes()).unwrap();
+        mac.update(body);
+        let signature = hex::encode(mac.finalize().into_bytes());
+        let verify = |name: &str, value: &str| {
+            let mut headers = HeaderMap::new();
+            headers.insert(
+                axum::http::HeaderName::from_bytes(name.as_bytes()).unwrap(),
+                value.parse().unwrap(),
+            );
+            verify_custom_webhook(body, &headers, secret)
+        };
+
+        assert!(verify("x-webhook-signature", &format!("sha256={}", signature)).is_ok());
+        assert!(verify("x-webhook-signature", &signature).is_ok());
+        assert!(verify("x-webhook-secret", secret).is_ok());
+        assert!(verify("authorization", &format!("Bearer {}", secret)).is_ok());
+
+        assert!(matches!(
+            verify("x-webhook-signature", &"0".repeat(64)),
+            Err(VerificationError::VerificationFailed)
+        ));
+        assert!(matches!(
+            verify("x-webhook-signature", "not-hex"),
+            Err(VerificationError::InvalidSignatureFormat { .. })
+        ));
+        assert!(matches!(
+            verify("x-webhook-secret", "guess"),
+            Err(VerificationError::VerificationFailed)
+        ));
+        assert!(matches!(
+            verify_custom_webhook(body, &HeaderMap::new(), secret),
+            Err(VerificationError::MissingSignature { .. })
+        ));
+    }
+
+    #[test]
+    fn test_circleci_signature_verification() {
+        let secret = "circleci-webhook-secret";
+        let body = br#"{"type":"workflow-completed","id":"3888f21b"}"#;
+        let mut mac = HmacSha256::new_from_slice(secret.as_bytes()).unwrap();
+        mac.update(body);
+        let signature = hex::encode(mac.finalize().into_bytes());
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "circleci-signature",
+            format!("v2=ignored,v1={}", signature).parse().unwrap(),
+        );
+        let config = AppConfig {
+            webhook_circleci_secret: Some(secret.to_string()),
+            ..Default::default()
+        };
+        assert!(verify_webhook_signature("circleci", body, &headers, &config).is_ok());
+
+        assert!(matches!(
+            verify_circleci_signature(b"{}", &format!("v1={}", signature), secret),
+            Err(VerificationError::VerificationFailed)
+        ));
+        assert!(matches!(
+            verify_circleci_signature(body, "", secret),
+            Err(VerificationError::MissingSignature { .. })
+        ));
+        assert!(matches!(
+            verify_circleci_signature(body, &signature, secret),
+            Err(VerificationError::InvalidSignatureFormat { .. })
+        ));
+    }
+
+    #[test]
+    fn test_datadog_bearer_token_verification() {
+        let body = br#"{"alert_id":"123456","transition":"Triggered"}"#;
+        let config = AppConfig {
+            webhook_datadog_secret: Some("dd-webhook-token".to_string()),
+            ..Default::default()
+        };
+
+        let mut headers = HeaderMap::new();
+        headers.insert("authorization", "Bearer dd-webhook-token".parse().unwrap());
+        assert!(verify_webhook_signature("datadog", body, &headers, &config).is_ok());
+
+        headers.insert("authorization", "Bearer other".parse().unwrap());
+        assert!(matches!(
+            verify_webhook_signature("datadog", body, &headers, &config),
+            Err(VerificationError::VerificationFailed)
+        ));
+        assert!(matches!(
+            verify_webhook_signature("datadog", body, &HeaderMap::new(), &config),
+            Err(VerificationError::MissingSignature { .. })
+        ));
+    }
+
+    #[test]
+    fn test_gitlab_token_verification() {
+        assert!(verify_gitlab_token("gl-secret", "gl-secret").is_ok());
+        assert!(matches!(
+            verify_gitlab_token("other", "gl-secret"),
+            Err(VerificationError::VerificationFailed)
+        ));
+        assert!(matches!(
+            verify_gitlab_token("", "gl-secret"),
+            Err(VerificationError::MissingSignature { .. })
+        ));
+    }
+
     #[test]
     fn test_slack_signature_verification_success() {
         let secret = "test_secret";
@@ -710,8 +1798,10 @@ mod tests {
         let mut headers = HeaderMap::new();
         headers.insert("X-Webhook-Secret", "test-secret-123".parse().unwrap());
 
-        let mut config = AppConfig::default();
-        config.webhook_jira_secret = Some("test-secret-123".to_string());
+        let config = AppConfig {
+            webhook_jira_secret: Some("test-secret-123".to_string()),
+            ..Default::default()
+        };
 
         // Only Authorization: Bearer is accepted now
         assert!(verify_webhook_signature("jira", b"{}", &headers, &config).is_err());
@@ -722,8 +1812,10 @@ mod tests {
         let mut headers = HeaderMap::new();
         headers.insert("authorization", "Bearer test-secret-123".parse().unwrap());
 
-        let mut config = AppConfig::default();
-        config.webhook_jira_secret = Some("test-secret-123".to_string());
+        let config = AppConfig {
+            webhook_jira_secret: Some("test-secret-123".to_string()),
+            ..Default::default()
+        };
 
         assert!(verify_webhook_signature("jira", b"{}", &headers, &config).is_ok());
     }
@@ -731,10 +1823,49 @@ mod tests {
     #[test]
     fn test_jira_secret_verification_missing() {
         let headers = HeaderMap::new();
-        let mut config = AppConfig::default();
-        config.webhook_jira_secret = Some("test-secret-123".to_string());
+        let config = AppConfig {
+            webhook_jira_secret: Some("test-secret-123".to_string()),
+            ..Default::default()
+        };
+
+        assert!(verify_webhook_signature("jira", b"{}", &headers, &config).is_err());
+    }
+
+    #[test]
+    fn test_confluence_secret_verification_uses_own_secret() {
+        let mut headers = HeaderMap::new();
+        headers.insert("authorization", "Bearer confluence-secret".parse().unwrap());
+
+        let config = AppConfig {
+            webhook_jira_secret: Some("test-secret-123".to_string()),
+            webhook_confluence_secret: Some("confluence-secret".to_string()),
+            ..Default::default()
+        };
 
+        assert!(verify_webhook_signature("confluence", b"{}", &headers, &config).is_ok());
         assert!(verify_webhook_signature("jira", b"{}", &headers, &config).is_err());
+        assert!(matches!(
+            verify_webhook_signature("confluence", b"{}", &headers, &AppConfig::default()),
+            Err(VerificationError::NotConfigured { .. })
+        ));
+    }
+
+    #[test]
+    fn test_salesforce_secret_verification() {
+        let mut headers = HeaderMap::new();
+        headers.insert("authorization", "Bearer salesforce-secret".parse().unwrap());
+
+        let config = AppConfig {
+            webhook_salesforce_secret: Some("salesforce-secret".to_string()),
+            ..Default::default()
+        };
+
+        assert!(verify_webhook_signature("salesforce", b"{}", &headers, &config).is_ok());
+        headers.insert("authorization", "Bearer wrong".parse().unwrap());
+        assert!(matches!(
+            verify_webhook_signature("salesforce", b"{}", &headers, &config),
+            Err(VerificationError::VerificationFailed)
+        ));
     }
 
     #[test]
@@ -745,8 +1876,10 @@ mod tests {
             "Bearer zoho-cliq-token-123".parse().unwrap(),
         );
 
-        let mut config = AppConfig::default();
-        config.webhook_zoho_cliq_token = Some("zoho-cliq-token-123".to_string());
+        let config = AppConfig {
+            webhook_zoho_cliq_token: Some("zoho-cliq-token-123".to_string()),
+            ..Default::default()
+        };
 
         assert!(verify_webhook_signature("zoho-cliq", b"{}", &headers, &config).is_ok());
     }
@@ -756,8 +1889,10 @@ mod tests {
         let mut headers = HeaderMap::new();
         headers.insert("authorization", "Bearer wrong-token".parse().unwrap());
 
-        let mut config = AppConfig::default();
-        config.webhook_zoho_cliq_token = Some("zoho-cliq-token-123".to_string());
+        let config = AppConfig {
+            webhook_zoho_cliq_token: Some("zoho-cliq-token-123".to_string()),
+            ..Default::default()
+        };
 
         assert!(verify_webhook_signature("zoho-cliq", b"{}", &headers, &config).is_err());
     }
@@ -765,8 +1900,10 @@ mod tests {
     #[test]
     fn test_zoho_cliq_token_verification_missing() {
         let headers = HeaderMap::new();
-        let mut config = AppConfig::default();
-        config.webhook_zoho_cliq_token = Some("zoho-cliq-token-123".to_string());
+        let config = AppConfig {
+            webhook_zoho_cliq_token: Some("zoho-cliq-token-123".to_string()),
+            ..Default::default()
+        };
 
         assert!(verify_webhook_signature("zoho-cliq", b"{}", &headers, &config).is_err());
     }
@@ -780,4 +1917,170 @@ mod tests {
 
         assert!(verify_webhook_signature("zoho-cliq", b"{}", &headers, &config).is_err());
     }
+
+    #[tokio::test]
+    async fn test_duplicate_delivery_rejected_until_handler_fails() {
+        use axum::{Router, body::Body, routing::post};
+        use tower::ServiceExt;
+
+        let secret = "replay_secret";
+        let config = std::sync::Arc::new(AppConfig {
+            webhook_github_secret: Some(secret.to_string()),
+            ..Default::default()
+        });
+        let app = Router::new()
+            .route(
+                "/webhooks/{provider}/{tenant_id}",
+                post(|body: String| async move {
+                    if body.contains("fail") {
+                        StatusCode::INTERNAL_SERVER_ERROR
+                    } else {
+                        StatusCode::ACCEPTED
+                    }
+                }),
+            )
+            .layer(axum::middleware::from_fn_with_state(
+                config.clone(),
+                webhook_verification_middleware,
+            ));
+
+        let delivery = |body: &'static str, delivery_id: &str| {
+            let mut mac = HmacSha256::new_from_slice(secret.as_bytes()).unwrap();
+            mac.update(body.as_bytes());
+            Request::post("/webhooks/github/replay-tenant")
+                .header(
+                    "x-hub-signature-256",
+                    format!("sha256={}", hex::encode(mac.finalize().into_bytes())),
+                )
+                .header("x-github-delivery", delivery_id)
+                .body(Body::from(body))
+                .unwrap()
+        };
+
+        let first = app.clone().oneshot(delivery("ok", "d-1")).await.unwrap();
+        assert_eq!(first.status(), StatusCode::ACCEPTED);
+        let replay = app.clone().oneshot(delivery("ok", "d-1")).await.unwrap();
+        assert_eq!(replay.status(), StatusCode::UNAUTHORIZED);
+        let next = app.clone().oneshot(delivery("next", "d-2")).await.unwrap();
+        assert_eq!(next.status(), StatusCode::ACCEPTED);
+
+        // A failed delivery is not remembered, so the provider's retry goes through
+        let failed = app.clone().oneshot(delivery("fail", "d-3")).await.unwrap();
+        assert_eq!(failed.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        let retry = app.clone().oneshot(delivery("fail", "d-3")).await.unwrap();
+        assert_eq!(retry.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    }
+
+    #[tokio::test]
+    async fn test_replay_under_fresh_delivery_id_rejected() {
+        use axum::{Router, body::Body, routing::post};
+        use tower::ServiceExt;
+
+        let secret = "fresh_id_secret";
+        let config = std::sync::Arc::new(AppConfig {
+            webhook_github_secret: Some(secret.to_string()),
+            ..Default::default()
+        });
+        let app = Router::new()
+            .route(
+                "/webhooks/{provider}/{tenant_id}",
+                post(|| async { StatusCode::ACCEPTED }),
+            )
+            .layer(axum::middleware::from_fn_with_state(
+                config.clone(),
+                webhook_verification_middleware,
+            ));
+
+        let body = r#"{"action":"opened","replayed":true}"#;
+        let mut mac = HmacSha256::new_from_slice(secret.as_bytes()).unwrap();
+        mac.update(body.as_bytes());
+        let signature = format!("sha256={}", hex::encode(mac.finalize().into_bytes()));
+        let delivery = |delivery_id: &str| {
+            Request::post("/webhooks/github/fresh-id-tenant")
+                .header("x-hub-signature-256", signature.as_str())
+                .header("x-github-delivery", delivery_id)
+                .body(Body::from(body))
+                .unwrap()
+        };
+
+        let first = app.clone().oneshot(delivery("fresh-1")).await.unwrap();
+        assert_eq!(first.status(), StatusCode::ACCEPTED);
+
+        // The delivery ID is not signed, so changing it must not get the body through again
+        let replay = app.clone().oneshot(delivery("fresh-2")).await.unwrap();
+        assert_eq!(replay.status(), StatusCode::UNAUTHORIZED);
+        let body = axum::body::to_bytes(replay.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let error: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(error["code"], "REPLAY_ATTACK_DETECTED");
+    }
+
+    #[tokio::test]
+    async fn test_same_delivery_to_two_tenants_accepted() {
+        use axum::{Router, body::Body, routing::post};
+        use tower::ServiceExt;
+
+        let secret = "shared_repo_secret";
+        let config = std::sync::Arc::new(AppConfig {
+            webhook_github_secret: Some(secret.to_string()),
+            ..Default::default()
+        });
+        let app = Router::new()
+            .route(
+                "/webhooks/{provider}/{tenant_id}",
+                post(|| async { StatusCode::ACCEPTED }),
+            )
+            .layer(axum::middleware::from_fn_with_state(
+                config.clone(),
+                webhook_verification_middleware,
+            ));
+
+        // Two tenants hook the same repository and receive the same signed delivery
+        let body = r#"{"action":"opened","repository":"shared"}"#;
+        let mut mac = HmacSha256::new_from_slice(secret.as_bytes()).unwrap();
+        mac.update(body.as_bytes());
+        let signature = format!("sha256={}", hex::encode(mac.finalize().into_bytes()));
+        let delivery = |tenant: &str| {
+            Request::post(format!("/webhooks/github/{}", tenant))
+                .header("x-hub-signature-256", signature.as_str())
+                .header("x-github-delivery", "shared-delivery")
+                .body(Body::from(body))
+                .unwrap()
+        };
+
+        for tenant in ["shared-tenant-a", "shared-tenant-b"] {
+            let response = app.clone().oneshot(delivery(tenant)).await.unwrap();
+            assert_eq!(response.status(), StatusCode::ACCEPTED);
+        }
+        let replay = app
+            .clone()
+            .oneshot(delivery("shared-tenant-a"))
+            .await
+            .unwrap();
+        assert_eq!(replay.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[test]
+    fn test_custom_webhook_reservation_scoped_to_connection() {
+        let config = AppConfig::default();
+        let tenant_id = Uuid::new_v4();
+        let (first, second) = (Uuid::new_v4(), Uuid::new_v4());
+        let headers = HeaderMap::new();
+        let body = br#"{"event":"custom-replay"}"#;
+        let reserve = |connection_id| {
+            reserve_custom_webhook_delivery(&config, tenant_id, connection_id, &headers, body)
+        };
+
+        // A delivery whose handling failed is forgotten, so the retry is accepted
+        drop(reserve(first).unwrap());
+        reserve(first).unwrap().unwrap().keep();
+        assert!(matches!(
+            reserve(first),
+            Err(VerificationError::DuplicateDelivery { .. })
+        ));
+
+        // Another connection receiving the same body is unaffected
+        reserve(second).unwrap().unwrap().keep();
+    }
 }
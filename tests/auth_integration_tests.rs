@@ -3,6 +3,7 @@
 use anyhow::{Context, Result as AnyhowResult};
 use connectors::connectors::Registry;
 use connectors::repositories::ConnectionRepository;
+use connectors::request_snapshot::SnapshotCache;
 use connectors::token_refresh::TokenRefreshService;
 use connectors::{config::AppConfig, server::create_app};
 use reqwest::StatusCode;
@@ -81,8 +82,11 @@ async fn spawn_test_app(config: AppConfig) -> (String, Arc<DatabaseConnection>,
     let state = connectors::server::AppState {
         config: Arc::new(config.clone()),
         db: db.as_ref().clone(),
-        crypto_key,
+        master_keys: crypto_key.into(),
         token_refresh_service,
+        snapshot: Arc::new(SnapshotCache::from_config(db.as_ref().clone(), &config)),
+        settings: connectors::config_reload::ReloadableConfig::from_config(&config),
+        signal_events: connectors::signals::SignalEventBus::default(),
     };
 
     // Create app
@@ -500,3 +504,76 @@ async fn test_error_response_format() {
 
     handle.shutdown().await.unwrap();
 }
+
+#[tokio::test]
+async fn test_admin_dashboard_requires_operator_auth() {
+    let disabled = AppConfig {
+        operator_tokens: vec!["test-token".to_string()],
+        ..Default::default()
+    };
+    let (server_url, _db, handle) = spawn_test_app(disabled).await;
+    let client = reqwest::Client::new();
+
+    // Dashboard is opt-in and not routed by default
+    let response = client
+        .get(format!("{}/admin", server_url))
+        .basic_auth("operator", Some("test-token"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    handle.shutdown().await.unwrap();
+
+    let enabled = AppConfig {
+        operator_tokens: vec!["test-token".to_string()],
+        admin_ui_enabled: true,
+        ..Default::default()
+    };
+    let (server_url, _db, handle) = spawn_test_app(enabled).await;
+
+    // Browsers get a Basic challenge so they can prompt for the operator token
+    let response = client
+        .get(format!("{}/admin", server_url))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    assert!(response.headers().contains_key("www-authenticate"));
+
+    let response = client
+        .get(format!("{}/admin/", server_url))
+        .basic_auth("operator", Some("test-token"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(
+        response
+            .text()
+            .await
+            .unwrap()
+            .contains("/admin/assets/app.js")
+    );
+
+    let response = client
+        .get(format!("{}/admin/assets/app.js", server_url))
+        .basic_auth("operator", Some("wrong-token"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    // Dashboard API calls reuse the tenant-scoped handlers
+    let response = client
+        .get(format!("{}/admin/api/jobs", server_url))
+        .basic_auth("operator", Some("test-token"))
+        .header("X-Tenant-Id", Uuid::new_v4().to_string())
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body: Value = response.json().await.unwrap();
+    assert!(body["jobs"].as_array().unwrap().is_empty());
+
+    handle.shutdown().await.unwrap();
+}
@@ -133,3 +133,511 @@ fn invalid_bind_addr_returns_error() {
 
     clear_env();
 }
+
+#[test]
+fn provider_concurrency_caps_load_per_provider_overrides() {
+    let _guard = env_guard();
+    clear_env();
+
+    let temp_dir = TempDir::new().unwrap();
+    write_env_file(
+        &temp_dir,
+        ".env",
+        "POBLYSH_CRYPTO_KEY=YWFhYWFhYWFhYWFhYWFhYWFhYWFhYWFhYWFhYWFhYWE=\n\
+         POBLYSH_OPERATOR_TOKEN=test-token-for-provider-caps\n\
+         POBLYSH_PROVIDER_MAX_RUNNING=8\n\
+         POBLYSH_PROVIDER_MAX_RUNNING_PER_TENANT=2\n\
+         POBLYSH_PROVIDER_MAX_RUNNING_GMAIL=3\n\
+         POBLYSH_PROVIDER_MAX_RUNNING_PER_TENANT_ZOHO_MAIL=1\n\
+         POBLYSH_TENANT_MAX_RUNNING=5\n\
+         POBLYSH_EXECUTOR_DRAIN_TIMEOUT_SECONDS=90\n\
+         POBLYSH_EXECUTOR_LEASE_TIMEOUT_SECONDS=120\n\
+         POBLYSH_JOB_PRIORITY_AGING_SECONDS=120\n\
+         POBLYSH_RATE_LIMIT_OVERRIDE_GITHUB_MAX_CONCURRENT=2\n\
+         POBLYSH_RATE_LIMIT_OVERRIDE_ZOHO_MAIL_MAX_SECONDS=600\n",
+    );
+
+    let loader = ConfigLoader::with_base_dir(PathBuf::from(temp_dir.path()));
+    let cfg = loader.load().expect("config loads with provider caps");
+    let caps = &cfg.provider_concurrency;
+
+    assert_eq!(caps.max_running_for("gmail"), Some(3));
+    assert_eq!(caps.max_running_for("github"), Some(8));
+    assert_eq!(caps.max_running_per_tenant_for("zoho-mail"), Some(1));
+    assert_eq!(caps.max_running_per_tenant_for("gmail"), Some(2));
+    assert_eq!(cfg.tenant_max_running, 5);
+    assert_eq!(cfg.executor_drain_timeout_seconds, 90);
+    assert_eq!(cfg.executor_lease_timeout_seconds, 120);
+    assert_eq!(cfg.job_priority_aging_seconds, 120);
+    assert_eq!(cfg.job_priority_aging_max_boost, 50);
+    assert_eq!(cfg.rate_limit_policy.max_concurrent_for("github"), Some(2));
+    assert_eq!(cfg.rate_limit_policy.max_concurrent_for("zoho-mail"), None);
+    assert_eq!(
+        cfg.rate_limit_policy.provider_overrides["zoho-mail"].max_seconds,
+        Some(600)
+    );
+    assert!(
+        !caps
+            .provider_max_running
+            .contains_key("per-tenant-zoho-mail")
+    );
+    clear_env();
+}
+
+#[test]
+fn executor_shard_loads_and_rejects_index_outside_count() {
+    let _guard = env_guard();
+    clear_env();
+
+    let temp_dir = TempDir::new().unwrap();
+    write_env_file(
+        &temp_dir,
+        ".env",
+        "POBLYSH_CRYPTO_KEY=YWFhYWFhYWFhYWFhYWFhYWFhYWFhYWFhYWFhYWFhYWE=\n\
+         POBLYSH_OPERATOR_TOKEN=test-token-for-executor-shards\n\
+         POBLYSH_EXECUTOR_INSTANCE_ID=executor-2\n\
+         POBLYSH_EXECUTOR_SHARD_KEY=Connection\n\
+         POBLYSH_EXECUTOR_SHARD_INDEX=2\n\
+         POBLYSH_EXECUTOR_SHARD_COUNT=3\n",
+    );
+
+    let loader = ConfigLoader::with_base_dir(PathBuf::from(temp_dir.path()));
+    let cfg = loader.load().expect("config loads with executor shard");
+    assert_eq!(cfg.executor_instance_id.as_deref(), Some("executor-2"));
+    assert!(cfg.executor_shard.by_connection());
+    let (lower, upper) = cfg.executor_shard.key_range().unwrap();
+    assert_eq!(lower.as_u128(), u128::MAX / 3 * 2);
+    assert!(upper.is_none());
+
+    write_env_file(&temp_dir, ".env.local", "POBLYSH_EXECUTOR_SHARD_INDEX=3\n");
+    let err = loader
+        .load()
+        .expect_err("shard index outside the count should fail");
+    assert!(format!("{}", err).contains("executor shard index 3"));
+    clear_env();
+}
+
+#[test]
+fn executor_settings_load_and_reject_zero() {
+    let _guard = env_guard();
+    clear_env();
+
+    let temp_dir = TempDir::new().unwrap();
+    write_env_file(
+        &temp_dir,
+        ".env",
+        "POBLYSH_CRYPTO_KEY=YWFhYWFhYWFhYWFhYWFhYWFhYWFhYWFhYWFhYWFhYWE=\n\
+         POBLYSH_OPERATOR_TOKEN=test-token-for-executor-settings\n\
+         POBLYSH_EXECUTOR_TICK_MS=250\n\
+         POBLYSH_EXECUTOR_CONCURRENCY=4\n\
+         POBLYSH_EXECUTOR_CLAIM_BATCH=8\n\
+         POBLYSH_EXECUTOR_MAX_RUN_SECONDS=900\n\
+         POBLYSH_EXECUTOR_MAX_ITEMS_PER_RUN=5000\n",
+    );
+
+    let loader = ConfigLoader::with_base_dir(PathBuf::from(temp_dir.path()));
+    let cfg = loader.load().expect("config loads with executor settings");
+    let executor = connectors::sync_executor::ExecutorConfig::from_config(&cfg);
+    assert_eq!(executor.tick_ms, 250);
+    assert_eq!(executor.concurrency, 4);
+    assert_eq!(executor.claim_batch, 8);
+    assert_eq!(executor.max_run_seconds, 900);
+    assert_eq!(executor.max_items_per_run, 5000);
+
+    write_env_file(&temp_dir, ".env.local", "POBLYSH_EXECUTOR_CONCURRENCY=0\n");
+    let err = loader
+        .load()
+        .expect_err("zero executor concurrency should fail");
+    assert!(format!("{}", err).contains("POBLYSH_EXECUTOR_CONCURRENCY"));
+    clear_env();
+}
+
+#[test]
+fn circuit_breaker_settings_load_and_reject_zero_cooldown() {
+    let _guard = env_guard();
+    clear_env();
+
+    let temp_dir = TempDir::new().unwrap();
+    write_env_file(
+        &temp_dir,
+        ".env",
+        "POBLYSH_CRYPTO_KEY=YWFhYWFhYWFhYWFhYWFhYWFhYWFhYWFhYWFhYWFhYWE=\n\
+         POBLYSH_OPERATOR_TOKEN=test-token-for-circuit-breaker\n\
+         POBLYSH_CIRCUIT_BREAKER_THRESHOLD=3\n\
+         POBLYSH_CIRCUIT_BREAKER_COOLDOWN_SECONDS=120\n",
+    );
+
+    let loader = ConfigLoader::with_base_dir(PathBuf::from(temp_dir.path()));
+    let cfg = loader
+        .load()
+        .expect("config loads with circuit breaker settings");
+    let executor = connectors::sync_executor::ExecutorConfig::from_config(&cfg);
+    assert_eq!(executor.circuit_breaker_threshold, 3);
+    assert_eq!(executor.circuit_breaker_cooldown_seconds, 120);
+
+    write_env_file(
+        &temp_dir,
+        ".env.local",
+        "POBLYSH_CIRCUIT_BREAKER_COOLDOWN_SECONDS=0\n",
+    );
+    let err = loader
+        .load()
+        .expect_err("zero circuit breaker cooldown should fail");
+    assert!(format!("{}", err).contains("POBLYSH_CIRCUIT_BREAKER_COOLDOWN_SECONDS"));
+    clear_env();
+}
+
+#[test]
+fn retry_budgets_load_per_provider_overrides_and_reject_zero() {
+    let _guard = env_guard();
+    clear_env();
+
+    let temp_dir = TempDir::new().unwrap();
+    write_env_file(
+        &temp_dir,
+        ".env",
+        "POBLYSH_CRYPTO_KEY=YWFhYWFhYWFhYWFhYWFhYWFhYWFhYWFhYWFhYWFhYWE=\n\
+         POBLYSH_OPERATOR_TOKEN=test-token-for-retry-budgets\n\
+         POBLYSH_RATE_LIMIT_MAX_ATTEMPTS=4\n\
+         POBLYSH_RATE_LIMIT_OVERRIDE_GITHUB_MAX_ATTEMPTS=2\n\
+         POBLYSH_RATE_LIMIT_OVERRIDE_ZOHO_MAIL_MAX_RATE_LIMITED_ATTEMPTS=50\n",
+    );
+
+    let loader = ConfigLoader::with_base_dir(PathBuf::from(temp_dir.path()));
+    let cfg = loader.load().expect("config loads with retry budgets");
+    let policy = &cfg.rate_limit_policy;
+    assert_eq!(policy.max_attempts_for("github"), 2);
+    assert_eq!(policy.max_attempts_for("zoho-mail"), 4);
+    assert_eq!(policy.max_rate_limited_attempts_for("zoho-mail"), 50);
+    assert_eq!(policy.max_rate_limited_attempts_for("github"), 30);
+
+    write_env_file(
+        &temp_dir,
+        ".env.local",
+        "POBLYSH_RATE_LIMIT_OVERRIDE_GITHUB_MAX_RATE_LIMITED_ATTEMPTS=0\n",
+    );
+    let err = loader.load().expect_err("a zero retry budget should fail");
+    assert!(format!("{}", err).contains("retry budget for provider github"));
+    clear_env();
+}
+
+#[test]
+fn scheduler_loads_provider_intervals_and_rejects_out_of_bounds() {
+    let _guard = env_guard();
+    clear_env();
+
+    let temp_dir = TempDir::new().unwrap();
+    write_env_file(
+        &temp_dir,
+        ".env",
+        "POBLYSH_CRYPTO_KEY=YWFhYWFhYWFhYWFhYWFhYWFhYWFhYWFhYWFhYWFhYWE=\n\
+         POBLYSH_OPERATOR_TOKEN=test-token-for-provider-intervals\n\
+         POBLYSH_SYNC_SCHEDULER_PROVIDER_INTERVAL_SLACK=300\n\
+         POBLYSH_SYNC_SCHEDULER_PROVIDER_INTERVAL_GOOGLE_DRIVE=3600\n",
+    );
+
+    let loader = ConfigLoader::with_base_dir(PathBuf::from(temp_dir.path()));
+    let cfg = loader.load().expect("config loads with provider intervals");
+    assert_eq!(cfg.scheduler.default_interval_for("slack"), 300);
+    assert_eq!(cfg.scheduler.default_interval_for("google-drive"), 3600);
+    assert_eq!(cfg.scheduler.default_interval_for("github"), 900);
+
+    write_env_file(
+        &temp_dir,
+        ".env.local",
+        "POBLYSH_SYNC_SCHEDULER_PROVIDER_INTERVAL_SLACK=30\n",
+    );
+    let err = loader
+        .load()
+        .expect_err("an interval below a minute should fail");
+    assert!(format!("{}", err).contains("interval for provider slack"));
+    clear_env();
+}
+
+#[test]
+fn token_refresh_lock_and_backoff_load_and_reject_inverted_bounds() {
+    let _guard = env_guard();
+    clear_env();
+
+    let temp_dir = TempDir::new().unwrap();
+    write_env_file(
+        &temp_dir,
+        ".env",
+        "POBLYSH_CRYPTO_KEY=YWFhYWFhYWFhYWFhYWFhYWFhYWFhYWFhYWFhYWFhYWE=\n\
+         POBLYSH_OPERATOR_TOKEN=test-token-for-refresh-locks\n\
+         POBLYSH_TOKEN_REFRESH_LOCK_SECONDS=90\n\
+         POBLYSH_TOKEN_REFRESH_BACKOFF_BASE_SECONDS=30\n\
+         POBLYSH_TOKEN_REFRESH_BACKOFF_MAX_SECONDS=1800\n",
+    );
+
+    let loader = ConfigLoader::with_base_dir(PathBuf::from(temp_dir.path()));
+    let cfg = loader
+        .load()
+        .expect("config loads with refresh lock settings");
+    assert_eq!(cfg.token_refresh.lock_seconds, 90);
+    let backoff = cfg.token_refresh.backoff();
+    assert_eq!(backoff.delay(0).as_secs(), 30);
+    assert_eq!(backoff.delay(10).as_secs(), 1800);
+
+    write_env_file(
+        &temp_dir,
+        ".env.local",
+        "POBLYSH_TOKEN_REFRESH_BACKOFF_BASE_SECONDS=3600\n",
+    );
+    let err = loader
+        .load()
+        .expect_err("a backoff base above the max should fail");
+    assert!(format!("{}", err).contains("token refresh backoff base"));
+    clear_env();
+}
+
+#[test]
+fn crypto_key_versions_load_and_reject_previous_versions_not_below_current() {
+    let _guard = env_guard();
+    clear_env();
+
+    let temp_dir = TempDir::new().unwrap();
+    write_env_file(
+        &temp_dir,
+        ".env",
+        "POBLYSH_CRYPTO_KEY=YWFhYWFhYWFhYWFhYWFhYWFhYWFhYWFhYWFhYWFhYWE=\n\
+         POBLYSH_OPERATOR_TOKEN=test-token-for-key-versions\n\
+         POBLYSH_CRYPTO_KEY_VERSION=2\n\
+         POBLYSH_CRYPTO_PREVIOUS_KEYS=1:YmJiYmJiYmJiYmJiYmJiYmJiYmJiYmJiYmJiYmJiYmI=\n",
+    );
+
+    let loader = ConfigLoader::with_base_dir(PathBuf::from(temp_dir.path()));
+    let cfg = loader.load().expect("config loads with key versions");
+    assert_eq!(cfg.crypto_key_version, 2);
+    assert_eq!(cfg.crypto_previous_keys, vec![(1, vec![b'b'; 32])]);
+    let redacted: serde_json::Value = serde_json::from_str(&cfg.redacted_json().unwrap()).unwrap();
+    assert_eq!(
+        redacted["CRYPTO_PREVIOUS_KEYS"][0][1],
+        serde_json::json!(b"[REDACTED]".to_vec())
+    );
+    let keyring = tokio::runtime::Runtime::new()
+        .unwrap()
+        .block_on(connectors::crypto::load_master_keyring(&cfg))
+        .unwrap();
+    assert_eq!(keyring.current_version(), 2);
+    assert!(keyring.get(1).is_some());
+
+    write_env_file(
+        &temp_dir,
+        ".env.local",
+        "POBLYSH_CRYPTO_PREVIOUS_KEYS=2:YmJiYmJiYmJiYmJiYmJiYmJiYmJiYmJiYmJiYmJiYmI=\n",
+    );
+    let err = loader
+        .load()
+        .expect_err("a previous version equal to the current one should fail");
+    assert!(format!("{}", err).contains("previous crypto key version 2"));
+
+    write_env_file(
+        &temp_dir,
+        ".env.local",
+        "POBLYSH_CRYPTO_PREVIOUS_KEYS=YmJiYmJiYmJiYmJiYmJiYmJiYmJiYmJiYmJiYmJiYmI=\n",
+    );
+    let err = loader
+        .load()
+        .expect_err("an entry without a version should fail");
+    assert!(!format!("{}", err).contains("YmJi"));
+    clear_env();
+}
+
+#[test]
+fn kms_key_provider_accepts_wrapped_keys_and_requires_its_settings() {
+    let _guard = env_guard();
+    clear_env();
+
+    let temp_dir = TempDir::new().unwrap();
+    // A KMS ciphertext blob is not 32 bytes; only the unwrapped key must be
+    write_env_file(
+        &temp_dir,
+        ".env",
+        "POBLYSH_CRYPTO_KEY=d3JhcHBlZC1ieS1rbXM=\n\
+         POBLYSH_OPERATOR_TOKEN=test-token-for-kms\n\
+         POBLYSH_CRYPTO_KEY_PROVIDER=aws-kms\n\
+         POBLYSH_AWS_KMS_REGION=eu-west-1\n\
+         POBLYSH_AWS_KMS_KEY_ID=alias/poblysh\n",
+    );
+
+    let loader = ConfigLoader::with_base_dir(PathBuf::from(temp_dir.path()));
+    let cfg = loader
+        .load()
+        .expect("config loads with an AWS KMS provider");
+    assert_eq!(cfg.crypto_key_provider.provider, "aws-kms");
+    assert_eq!(
+        cfg.crypto_key_provider.aws_kms_region.as_deref(),
+        Some("eu-west-1")
+    );
+    assert_eq!(cfg.crypto_key.as_deref(), Some(&b"wrapped-by-kms"[..]));
+
+    write_env_file(
+        &temp_dir,
+        ".env.local",
+        "POBLYSH_CRYPTO_KEY_PROVIDER=gcp-kms\n",
+    );
+    let err = loader
+        .load()
+        .expect_err("gcp-kms without a key name should fail");
+    assert!(format!("{}", err).contains("POBLYSH_GCP_KMS_KEY_NAME"));
+
+    write_env_file(
+        &temp_dir,
+        ".env.local",
+        "POBLYSH_CRYPTO_KEY_PROVIDER=azure\n",
+    );
+    let err = loader.load().expect_err("unknown providers should fail");
+    assert!(format!("{}", err).contains("crypto key provider must be one of"));
+    clear_env();
+}
+
+#[test]
+fn vault_key_provider_keeps_transit_ciphertext_verbatim() {
+    let _guard = env_guard();
+    clear_env();
+
+    let temp_dir = TempDir::new().unwrap();
+    write_env_file(
+        &temp_dir,
+        ".env",
+        "POBLYSH_CRYPTO_KEY=vault:v2:Y3VycmVudA==\n\
+         POBLYSH_CRYPTO_KEY_VERSION=2\n\
+         POBLYSH_CRYPTO_PREVIOUS_KEYS=1:vault:v1:b2xk\n\
+         POBLYSH_OPERATOR_TOKEN=test-token-for-vault\n\
+         POBLYSH_CRYPTO_KEY_PROVIDER=vault\n\
+         POBLYSH_VAULT_ADDR=https://vault.internal:8200\n\
+         POBLYSH_VAULT_TRANSIT_KEY=poblysh-master\n",
+    );
+
+    let loader = ConfigLoader::with_base_dir(PathBuf::from(temp_dir.path()));
+    let cfg = loader.load().expect("config loads with a Vault provider");
+    assert_eq!(
+        cfg.crypto_key.as_deref(),
+        Some(&b"vault:v2:Y3VycmVudA=="[..])
+    );
+    assert_eq!(
+        cfg.crypto_previous_keys,
+        vec![(1, b"vault:v1:b2xk".to_vec())]
+    );
+    assert_eq!(cfg.crypto_key_provider.vault_cache_ttl_seconds, 300);
+
+    write_env_file(&temp_dir, ".env.local", "POBLYSH_VAULT_TRANSIT_KEY=\n");
+    let err = loader
+        .load()
+        .expect_err("vault without a transit key should fail");
+    assert!(format!("{}", err).contains("POBLYSH_VAULT_TRANSIT_KEY"));
+    clear_env();
+}
+
+#[test]
+fn config_watcher_swaps_reloaded_settings_and_keeps_them_when_invalid() {
+    use connectors::config_reload::{ConfigWatcher, ReloadError, ReloadableConfig};
+
+    let _guard = env_guard();
+    clear_env();
+
+    let temp_dir = TempDir::new().unwrap();
+    write_env_file(
+        &temp_dir,
+        ".env",
+        "POBLYSH_CRYPTO_KEY=YWFhYWFhYWFhYWFhYWFhYWFhYWFhYWFhYWFhYWFhYWE=\n\
+         POBLYSH_OPERATOR_TOKEN=test-token-for-config-reload\n\
+         POBLYSH_SYNC_SCHEDULER_TICK_INTERVAL_SECONDS=10\n",
+    );
+    let loader = ConfigLoader::with_base_dir(PathBuf::from(temp_dir.path()));
+    let settings = ReloadableConfig::from_config(&loader.load().unwrap());
+    let before = settings.current();
+    let watcher = ConfigWatcher::new(loader, settings.clone());
+
+    write_env_file(
+        &temp_dir,
+        ".env.local",
+        "POBLYSH_SYNC_SCHEDULER_TICK_INTERVAL_SECONDS=30\n\
+         POBLYSH_MAIL_SPAM_DENYLIST=@spam.example\n",
+    );
+    assert_eq!(watcher.reload().unwrap(), vec!["mail_spam", "scheduler"]);
+    let after = settings.current();
+    assert_eq!(after.scheduler.tick_interval_seconds, 30);
+    assert_eq!(after.mail_spam.denylist, vec!["@spam.example".to_string()]);
+    // Snapshots taken before the reload keep their values
+    assert_eq!(before.scheduler.tick_interval_seconds, 10);
+
+    write_env_file(
+        &temp_dir,
+        ".env.local",
+        "POBLYSH_SYNC_SCHEDULER_TICK_INTERVAL_SECONDS=60\n\
+         POBLYSH_MAIL_SPAM_THRESHOLD=7\n",
+    );
+    assert!(matches!(watcher.reload(), Err(ReloadError::Config(_))));
+    assert_eq!(settings.current(), after);
+    clear_env();
+}
+
+#[test]
+fn config_file_supplies_nested_settings_under_env_layers() {
+    let _guard = env_guard();
+    clear_env();
+
+    let temp_dir = TempDir::new().unwrap();
+    write_env_file(
+        &temp_dir,
+        "poblysh.toml",
+        r#"
+log_level = "debug"
+operator_tokens = ["toml-token-a", "toml-token-b"]
+
+[crypto]
+key = "YWFhYWFhYWFhYWFhYWFhYWFhYWFhYWFhYWFhYWFhYWE="
+
+[sync_scheduler]
+tick_interval_seconds = 20
+
+[sync_scheduler.provider_interval]
+google-drive = 1800
+
+[rate_limit.override.zoho-mail]
+max_seconds = 120
+max_concurrent = 2
+"#,
+    );
+    write_env_file(
+        &temp_dir,
+        ".env",
+        "POBLYSH_CONFIG_FILE=poblysh.toml\nPOBLYSH_LOG_LEVEL=warn\n",
+    );
+
+    let loader = ConfigLoader::with_base_dir(PathBuf::from(temp_dir.path()));
+    let cfg = loader.load().expect("config loads with a TOML file");
+    // The .env file wins over the config file
+    assert_eq!(cfg.log_level, "warn");
+    assert_eq!(cfg.operator_tokens, vec!["toml-token-a", "toml-token-b"]);
+    assert_eq!(cfg.scheduler.tick_interval_seconds, 20);
+    assert_eq!(
+        cfg.scheduler.provider_intervals.get("google-drive"),
+        Some(&1800)
+    );
+    let zoho = &cfg.rate_limit_policy.provider_overrides["zoho-mail"];
+    assert_eq!(zoho.max_seconds, Some(120));
+    assert_eq!(zoho.max_concurrent, Some(2));
+
+    write_env_file(
+        &temp_dir,
+        "poblysh.yaml",
+        "crypto:\n  key: YWFhYWFhYWFhYWFhYWFhYWFhYWFhYWFhYWFhYWFhYWE=\n\
+         operator_token: yaml-token\n\
+         mail_spam:\n  threshold: 0.6\n  denylist:\n    - \"@spam.example\"\n",
+    );
+    write_env_file(&temp_dir, ".env", "POBLYSH_CONFIG_FILE=poblysh.yaml\n");
+    let cfg = loader.load().expect("config loads with a YAML file");
+    assert_eq!(cfg.mail_spam.threshold, 0.6);
+    assert_eq!(cfg.mail_spam.denylist, vec!["@spam.example"]);
+
+    write_env_file(&temp_dir, "poblysh.json", "{}");
+    write_env_file(&temp_dir, ".env", "POBLYSH_CONFIG_FILE=poblysh.json\n");
+    let err = loader
+        .load()
+        .expect_err("unknown config file format should fail");
+    assert!(err.to_string().contains("poblysh.json"));
+    clear_env();
+}
@@ -42,14 +42,8 @@ async fn test_basic_connection_save() -> Result<(), Box<dyn std::error::Error>>
 
     // Create basic connection without tokens first using test_utils direct SQL
     let connection_id = Uuid::new_v4();
-    match test_utils::insert_connection(
-        &*db,
-        connection_id,
-        tenant_id,
-        &provider_slug,
-        &external_id,
-    )
-    .await
+    match test_utils::insert_connection(&db, connection_id, tenant_id, &provider_slug, &external_id)
+        .await
     {
         Ok(_) => {
             println!("✅ Basic connection save succeeded");
@@ -77,9 +71,9 @@ async fn test_connection_token_encryption_roundtrip() -> Result<(), Box<dyn std:
     let refresh_token = "test-refresh-token-67890";
 
     // Create tenant first, then connection using direct SQL
-    test_utils::create_test_tenant(&*db, Some(tenant_id)).await?;
+    test_utils::create_test_tenant(&db, Some(tenant_id)).await?;
     let connection_id = Uuid::new_v4();
-    test_utils::insert_connection(&*db, connection_id, tenant_id, &provider_slug, &external_id)
+    test_utils::insert_connection(&db, connection_id, tenant_id, &provider_slug, &external_id)
         .await?;
 
     // Manually encrypt tokens using direct crypto operations (with correct argument order)
@@ -102,6 +96,8 @@ async fn test_connection_token_encryption_roundtrip() -> Result<(), Box<dyn std:
         display_name: None,
         access_token_ciphertext: Some(encrypted_access.clone()),
         refresh_token_ciphertext: Some(encrypted_refresh.clone()),
+        access_token: None,
+        refresh_token: None,
         expires_at: None,
         scopes: None,
         metadata: None,
@@ -153,6 +149,8 @@ fn test_encrypt_connection_tokens_sets_version_prefix() -> Result<(), Box<dyn st
         display_name: None,
         access_token_ciphertext: None,
         refresh_token_ciphertext: None,
+        access_token: None,
+        refresh_token: None,
         expires_at: None,
         scopes: None,
         metadata: None,
@@ -192,9 +190,9 @@ async fn test_connection_token_aad_isolation() -> Result<(), Box<dyn std::error:
     let conn1_external = "external1";
 
     // Create tenant and connection using direct SQL
-    test_utils::create_test_tenant(&*db, Some(conn1_tenant_id)).await?;
+    test_utils::create_test_tenant(&db, Some(conn1_tenant_id)).await?;
     test_utils::insert_connection(
-        &*db,
+        &db,
         conn1_id,
         conn1_tenant_id,
         conn1_provider,
@@ -215,6 +213,8 @@ async fn test_connection_token_aad_isolation() -> Result<(), Box<dyn std::error:
         display_name: None,
         access_token_ciphertext: Some(encrypted_conn1_token),
         refresh_token_ciphertext: None,
+        access_token: None,
+        refresh_token: None,
         expires_at: None,
         scopes: None,
         metadata: None,
@@ -229,9 +229,9 @@ async fn test_connection_token_aad_isolation() -> Result<(), Box<dyn std::error:
     let conn2_external = "external2";
 
     // Create tenant and connection using direct SQL
-    test_utils::create_test_tenant(&*db, Some(conn2_tenant_id)).await?;
+    test_utils::create_test_tenant(&db, Some(conn2_tenant_id)).await?;
     test_utils::insert_connection(
-        &*db,
+        &db,
         conn2_id,
         conn2_tenant_id,
         conn2_provider,
@@ -252,6 +252,8 @@ async fn test_connection_token_aad_isolation() -> Result<(), Box<dyn std::error:
         display_name: None,
         access_token_ciphertext: Some(encrypted_conn2_token),
         refresh_token_ciphertext: None,
+        access_token: None,
+        refresh_token: None,
         expires_at: None,
         scopes: None,
         metadata: None,
@@ -288,8 +290,8 @@ async fn test_connection_token_update_encryption() -> Result<(), Box<dyn std::er
     let connection_id = Uuid::new_v4();
     let initial_token = "initial-token";
 
-    test_utils::create_test_tenant(&*db, Some(tenant_id)).await?;
-    test_utils::insert_connection(&*db, connection_id, tenant_id, provider_slug, external_id)
+    test_utils::create_test_tenant(&db, Some(tenant_id)).await?;
+    test_utils::insert_connection(&db, connection_id, tenant_id, provider_slug, external_id)
         .await?;
 
     // Manually encrypt initial token
@@ -306,6 +308,8 @@ async fn test_connection_token_update_encryption() -> Result<(), Box<dyn std::er
         display_name: None,
         access_token_ciphertext: Some(encrypted_initial_token),
         refresh_token_ciphertext: None,
+        access_token: None,
+        refresh_token: None,
         expires_at: None,
         scopes: None,
         metadata: None,
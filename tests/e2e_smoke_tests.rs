@@ -278,8 +278,8 @@ fn jittered_backoff(min_ms: u64, max_ms: u64) -> u64 {
 /// - `/openapi.json`
 /// - `/providers`
 /// - `/protected/ping` with:
-///     Authorization: Bearer <operator_token>
-///     X-Tenant-Id: <uuid>
+///   Authorization: Bearer <operator_token>
+///   X-Tenant-Id: <uuid>
 fn run_endpoint_checks(
     client: &Client,
     base_url: &str,
@@ -55,6 +55,7 @@ async fn test_github_oauth_flow_integration() {
         tenant_id,
         redirect_uri: Some(format!("{}/callback", mock_server.uri())),
         state: Some("test_state_123".to_string()),
+        code_challenge: None,
     };
 
     // Test authorize URL generation
@@ -73,14 +74,15 @@ async fn test_github_oauth_flow_integration() {
         code: "test_auth_code_12345".to_string(),
         redirect_uri: Some(format!("{}/callback", mock_server.uri())),
         tenant_id,
+        code_verifier: None,
     };
 
     let connection = connector.exchange_token(exchange_params).await.unwrap();
     assert_eq!(connection.provider_slug, "github");
     assert_eq!(connection.external_id, "123456");
     assert_eq!(connection.display_name, Some("testuser".to_string()));
-    assert!(connection.access_token_ciphertext.is_some());
-    assert!(connection.refresh_token_ciphertext.is_some());
+    assert!(connection.access_token.is_some());
+    assert!(connection.refresh_token.is_some());
 
     // Verify refresh_token_status is present in metadata
     let metadata = connection.metadata.as_ref().unwrap();
@@ -90,7 +92,7 @@ async fn test_github_oauth_flow_integration() {
     // Test token refresh
     let refreshed_connection = connector.refresh_token(connection).await.unwrap();
     assert_eq!(refreshed_connection.provider_slug, "github");
-    assert!(refreshed_connection.access_token_ciphertext.is_some());
+    assert!(refreshed_connection.access_token.is_some());
 
     // Check that metadata was updated
     assert!(refreshed_connection.metadata.is_some());
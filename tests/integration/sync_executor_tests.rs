@@ -6,10 +6,12 @@ use anyhow::Result;
 
 use connectors::config::RateLimitPolicyConfig;
 use connectors::connectors::registry::Registry;
+use connectors::cursor::CursorSigner;
 use connectors::repositories::ConnectionRepository;
 use connectors::seeds::seed_providers;
 use connectors::sync_executor::{ExecutorConfig, SyncExecutor};
 use connectors::token_refresh::TokenRefreshService;
+use connectors::token_vault::TokenVault;
 use test_utils::{create_test_tenant, setup_test_db};
 
 #[tokio::test]
@@ -30,7 +32,9 @@ async fn test_sync_executor_basic_functionality() -> Result<()> {
     // Create required dependencies for TokenRefreshService
     let crypto_key = connectors::crypto::CryptoKey::new(vec![0u8; 32])
         .expect("Failed to create crypto key for test");
+    let cursor_signer = CursorSigner::new(&crypto_key);
     let connection_repo = ConnectionRepository::new(std::sync::Arc::new(db.clone()), crypto_key);
+    let token_vault = TokenVault::from(connection_repo.clone());
 
     // Create TokenRefreshService
     let token_refresh_service = std::sync::Arc::new(TokenRefreshService::new(
@@ -46,6 +50,8 @@ async fn test_sync_executor_basic_functionality() -> Result<()> {
         executor_config,
         rate_limit_policy,
         token_refresh_service,
+        token_vault,
+        cursor_signer,
     );
 
     // Test that executor was created successfully
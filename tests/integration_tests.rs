@@ -2,6 +2,7 @@
 
 use connectors::connectors::Registry;
 use connectors::repositories::ConnectionRepository;
+use connectors::request_snapshot::SnapshotCache;
 use connectors::server::{AppState, create_app};
 use connectors::token_refresh::TokenRefreshService;
 use reqwest::Client;
@@ -49,9 +50,12 @@ async fn start_test_server() -> String {
     ));
 
     let state = AppState {
+        snapshot: Arc::new(SnapshotCache::from_config(db.clone(), &config)),
+        settings: connectors::config_reload::ReloadableConfig::from_config(&config),
+        signal_events: connectors::signals::SignalEventBus::default(),
         config,
         db,
-        crypto_key,
+        master_keys: crypto_key.into(),
         token_refresh_service,
     };
 
@@ -180,9 +184,12 @@ mod signals_tests {
         ));
 
         let state = AppState {
+            snapshot: std::sync::Arc::new(SnapshotCache::from_config(db.clone(), &config)),
+            settings: connectors::config_reload::ReloadableConfig::from_config(&config),
+            signal_events: connectors::signals::SignalEventBus::default(),
             config: std::sync::Arc::new(config),
             db: db.clone(),
-            crypto_key,
+            master_keys: crypto_key.into(),
             token_refresh_service,
         };
 
@@ -1,7 +1,9 @@
 //! Integration tests for Signal.kind normalization with golden fixtures
 
 use connectors::normalization::{
-    ALL_SIGNAL_KINDS, normalize_example_payload, normalize_jira_webhook_kind,
+    ALL_SIGNAL_KINDS, normalize_circleci_webhook_kind, normalize_datadog_alert_kind,
+    normalize_example_payload, normalize_jira_webhook_kind, normalize_salesforce_change_event_kind,
+    normalize_sentry_webhook_kind, normalize_stripe_event_kind, normalize_zendesk_webhook_kind,
     normalize_zoho_cliq_webhook_kind,
 };
 use serde::{Deserialize, Serialize};
@@ -31,18 +33,34 @@ pub struct ExpectedOutput {
 
 /// Providers that must document fixture coverage.
 const ALL_PROVIDER_SLUGS: &[&str] = &[
+    "circleci",
+    "datadog",
     "example",
     "github",
     "gmail",
     "google-calendar",
     "google-drive",
     "jira",
+    "salesforce",
+    "sentry",
+    "stripe",
+    "zendesk",
     "zoho-cliq",
     "zoho-mail",
 ];
 
 /// Providers that have normalization implementations in this harness.
-const NORMALIZED_PROVIDERS: &[&str] = &["example", "jira", "zoho-cliq"];
+const NORMALIZED_PROVIDERS: &[&str] = &[
+    "circleci",
+    "datadog",
+    "example",
+    "jira",
+    "salesforce",
+    "sentry",
+    "stripe",
+    "zendesk",
+    "zoho-cliq",
+];
 
 /// Root directory for normalization fixtures
 const FIXTURE_ROOT: &str = "tests/fixtures/normalization";
@@ -60,12 +78,11 @@ fn discover_fixture_files(fixture_dir: &Path) -> Result<Vec<DirEntry>, Box<dyn s
         .into_iter()
         .filter_map(|e| e.ok())
     {
-        if entry.file_type().is_file() {
-            if let Some(path_str) = entry.path().extension().and_then(|s| s.to_str()) {
-                if path_str == "json" {
-                    fixtures.push(entry);
-                }
-            }
+        if entry.file_type().is_file()
+            && let Some(path_str) = entry.path().extension().and_then(|s| s.to_str())
+            && path_str == "json"
+        {
+            fixtures.push(entry);
         }
     }
 
@@ -137,10 +154,29 @@ fn normalize_fixture_kind(
     fixture: &NormalizationFixture,
 ) -> Result<String, Box<dyn std::error::Error>> {
     let kind = match fixture.provider.as_str() {
+        "circleci" => normalize_circleci_webhook_kind(&fixture.input)
+            .ok_or_else(|| "CircleCI fixture did not contain a completed workflow".to_string())?,
+        "datadog" => normalize_datadog_alert_kind(&fixture.input).ok_or_else(|| {
+            "Datadog fixture did not contain a supported monitor transition".to_string()
+        })?,
         "example" => normalize_example_payload(&fixture.input)
             .map_err(|e| format!("example normalization failed: {}", e))?,
         "jira" => normalize_jira_webhook_kind(&fixture.input)
             .ok_or_else(|| "Jira fixture did not contain a supported webhookEvent".to_string())?,
+        "salesforce" => {
+            normalize_salesforce_change_event_kind(&fixture.input).ok_or_else(|| {
+                "Salesforce fixture did not contain a supported change event".to_string()
+            })?
+        }
+        "sentry" => normalize_sentry_webhook_kind(&fixture.input).ok_or_else(|| {
+            "Sentry fixture did not contain a supported issue or alert event".to_string()
+        })?,
+        "stripe" => normalize_stripe_event_kind(&fixture.input).ok_or_else(|| {
+            "Stripe fixture did not contain a supported billing event".to_string()
+        })?,
+        "zendesk" => normalize_zendesk_webhook_kind(&fixture.input).ok_or_else(|| {
+            "Zendesk fixture did not contain a supported ticket event".to_string()
+        })?,
         "zoho-cliq" => normalize_zoho_cliq_webhook_kind(&fixture.input)
             .map_err(|e| format!("zoho-cliq normalization failed: {}", e))?,
         provider => {
@@ -8,6 +8,7 @@
 use anyhow::{Context, Result as AnyhowResult};
 use connectors::connectors::Registry;
 use connectors::repositories::ConnectionRepository;
+use connectors::request_snapshot::SnapshotCache;
 use connectors::token_refresh::TokenRefreshService;
 use connectors::{
     config::AppConfig, repositories::oauth_state::OAuthStateRepository, server::create_app,
@@ -83,8 +84,11 @@ async fn spawn_test_app(config: AppConfig) -> (String, Arc<DatabaseConnection>,
     let state = connectors::server::AppState {
         config: Arc::new(config.clone()),
         db: db.as_ref().clone(),
-        crypto_key,
+        master_keys: crypto_key.into(),
         token_refresh_service,
+        snapshot: Arc::new(SnapshotCache::from_config(db.as_ref().clone(), &config)),
+        settings: connectors::config_reload::ReloadableConfig::from_config(&config),
+        signal_events: connectors::signals::SignalEventBus::default(),
     };
 
     // Create app
@@ -180,7 +184,7 @@ async fn test_oauth_callback_provider_denied_replay_protection()
     let client = reqwest::Client::new();
 
     // Create test tenant
-    let tenant_id = create_test_tenant(&*db).await?;
+    let tenant_id = create_test_tenant(&db).await?;
     let state_token = "test_replay_state_token_12345";
     println!("Created tenant: {}", tenant_id);
 
@@ -195,12 +199,12 @@ async fn test_oauth_callback_provider_denied_replay_protection()
     );
 
     // Verify the state was actually created
-    let exists = oauth_state_exists(&*db, "example", state_token).await;
+    let exists = oauth_state_exists(&db, "example", state_token).await;
     println!("OAuth state exists immediately after creation: {}", exists);
 
     // First callback attempt - provider denies authorization
     let response = client
-        .get(&format!(
+        .get(format!(
             "{}/connect/example/callback?code=some_code&state={}&error=access_denied",
             server_url, state_token
         ))
@@ -221,13 +225,13 @@ async fn test_oauth_callback_provider_denied_replay_protection()
 
     // Verify state was consumed
     assert!(
-        !oauth_state_exists(&*db, "example", state_token).await,
+        !oauth_state_exists(&db, "example", state_token).await,
         "State should be consumed"
     );
 
     // Second callback attempt with same state (replay attempt)
     let replay_response = client
-        .get(&format!(
+        .get(format!(
             "{}/connect/example/callback?code=another_code&state={}",
             server_url, state_token
         ))
@@ -264,7 +268,7 @@ async fn test_oauth_callback_malformed_response_error_envelope()
     let client = reqwest::Client::new();
 
     // Create test tenant
-    let tenant_id = create_test_tenant(&*db).await?;
+    let tenant_id = create_test_tenant(&db).await?;
     let state_token = "test_malformed_response_state_12345";
 
     // Create OAuth state using the repository (proper timestamp handling)
@@ -275,7 +279,7 @@ async fn test_oauth_callback_malformed_response_error_envelope()
 
     // Callback with malformed response trigger code
     let response = client
-        .get(&format!(
+        .get(format!(
             "{}/connect/example/callback?code=test_malformed_response&state={}",
             server_url, state_token
         ))
@@ -329,7 +333,7 @@ async fn test_oauth_callback_expired_state_validation() -> Result<(), Box<dyn st
     let client = reqwest::Client::new();
 
     // Create test tenant
-    let tenant_id = create_test_tenant(&*db).await?;
+    let tenant_id = create_test_tenant(&db).await?;
     let state_token = "test_expired_state_token_12345";
 
     // Create an expired OAuth state by using repository with negative expiration
@@ -350,7 +354,7 @@ async fn test_oauth_callback_expired_state_validation() -> Result<(), Box<dyn st
     println!("Current time: {}, Is state expired: {}", now, is_expired);
 
     // Verify the expired state was created
-    let exists = oauth_state_exists(&*db, "example", state_token).await;
+    let exists = oauth_state_exists(&db, "example", state_token).await;
     println!(
         "Expired OAuth state exists immediately after creation: {}",
         exists
@@ -358,7 +362,7 @@ async fn test_oauth_callback_expired_state_validation() -> Result<(), Box<dyn st
 
     // Callback with expired state
     let response = client
-        .get(&format!(
+        .get(format!(
             "{}/connect/example/callback?code=some_code&state={}",
             server_url, state_token
         ))
@@ -403,7 +407,7 @@ async fn test_oauth_callback_detailed_http_error_info() -> Result<(), Box<dyn st
     let client = reqwest::Client::new();
 
     // Create test tenant
-    let tenant_id = create_test_tenant(&*db).await?;
+    let tenant_id = create_test_tenant(&db).await?;
     let state_token = "test_http_error_state_12345";
 
     // Create OAuth state using the repository (proper timestamp handling)
@@ -414,7 +418,7 @@ async fn test_oauth_callback_detailed_http_error_info() -> Result<(), Box<dyn st
 
     // Callback with HTTP error trigger code
     let response = client
-        .get(&format!(
+        .get(format!(
             "{}/connect/example/callback?code=test_http_error&state={}",
             server_url, state_token
         ))
@@ -0,0 +1,15 @@
+//! Ensures the committed `openapi.json` matches the spec the server serves.
+//!
+//! The typed client in `client/` is generated from this file, so a stale copy
+//! means a stale client. Regenerate both with `make client`.
+
+#[test]
+fn committed_openapi_json_is_current() {
+    let committed = std::fs::read_to_string(concat!(env!("CARGO_MANIFEST_DIR"), "/openapi.json"))
+        .expect("openapi.json is committed at the repository root");
+    let current = connectors::server::openapi_json().expect("spec serializes");
+    assert!(
+        committed == current,
+        "openapi.json is stale; run `make client` and commit the result"
+    );
+}
@@ -15,12 +15,17 @@ async fn seed_providers_populates_expected_rows() -> Result<()> {
 
     let repo = ProviderRepository::new(std::sync::Arc::new(db));
     let providers = repo.list_all().await?;
-    assert_eq!(providers.len(), 4); // Updated to match actual provider count
+    assert_eq!(providers.len(), 13); // Updated to match actual provider count
     assert!(
         providers
             .iter()
             .any(|p| p.slug == "github" && p.display_name == "GitHub")
     );
+    assert!(
+        providers
+            .iter()
+            .any(|p| p.slug == "gitlab" && p.display_name == "GitLab")
+    );
     assert!(
         providers
             .iter()
@@ -31,11 +36,51 @@ async fn seed_providers_populates_expected_rows() -> Result<()> {
             .iter()
             .any(|p| p.slug == "jira" && p.display_name == "Jira")
     );
+    assert!(
+        providers
+            .iter()
+            .any(|p| p.slug == "confluence" && p.display_name == "Confluence")
+    );
+    assert!(
+        providers
+            .iter()
+            .any(|p| p.slug == "linear" && p.display_name == "Linear")
+    );
+    assert!(
+        providers
+            .iter()
+            .any(|p| p.slug == "notion" && p.display_name == "Notion")
+    );
     assert!(
         providers
             .iter()
             .any(|p| p.slug == "microsoft" && p.display_name == "Microsoft")
     );
+    assert!(
+        providers
+            .iter()
+            .any(|p| p.slug == "outlook" && p.display_name == "Outlook")
+    );
+    assert!(
+        providers
+            .iter()
+            .any(|p| p.slug == "outlook-calendar" && p.display_name == "Outlook Calendar")
+    );
+    assert!(
+        providers
+            .iter()
+            .any(|p| p.slug == "salesforce" && p.display_name == "Salesforce")
+    );
+    assert!(
+        providers
+            .iter()
+            .any(|p| p.slug == "zendesk" && p.display_name == "Zendesk")
+    );
+    assert!(
+        providers
+            .iter()
+            .any(|p| p.slug == "sentry" && p.display_name == "Sentry")
+    );
     Ok(())
 }
 
@@ -47,6 +92,47 @@ async fn seeding_is_idempotent() -> Result<()> {
 
     let repo = ProviderRepository::new(std::sync::Arc::new(db));
     let providers = repo.list_all().await?;
-    assert_eq!(providers.len(), 4); // Updated to match actual provider count
+    assert_eq!(providers.len(), 13); // Updated to match actual provider count
+    Ok(())
+}
+
+#[tokio::test]
+async fn reconciliation_reports_removed_and_drifted_providers() -> Result<()> {
+    let db = setup_test_db().await?;
+    let repo = ProviderRepository::new(std::sync::Arc::new(db.clone()));
+    repo.upsert("jira", "Jira", "api_key").await?;
+    repo.upsert("legacy-crm", "Legacy CRM", "oauth2").await?;
+
+    let report = seed_providers(&db).await?;
+    assert_eq!(
+        report.added,
+        vec![
+            "google",
+            "github",
+            "gitlab",
+            "confluence",
+            "linear",
+            "notion",
+            "microsoft",
+            "outlook",
+            "outlook-calendar",
+            "salesforce",
+            "zendesk",
+            "sentry"
+        ]
+    );
+    assert!(report.missing.is_empty());
+    assert_eq!(report.removed, vec!["legacy-crm"]);
+    assert_eq!(report.auth_type_drift.len(), 1);
+    assert_eq!(report.auth_type_drift[0].slug, "jira");
+    assert_eq!(report.auth_type_drift[0].expected, "oauth2");
+    assert_eq!(report.auth_type_drift[0].actual, "api_key");
+
+    // Drift is reported, not repaired
+    let jira = repo.find_by_slug("jira").await?.unwrap();
+    assert_eq!(jira.auth_type, "api_key");
+    let again = seed_providers(&db).await?;
+    assert!(again.added.is_empty());
+    assert_eq!(again.removed, vec!["legacy-crm"]);
     Ok(())
 }
@@ -108,9 +108,8 @@ pub async fn insert_connection(
         backend,
         "INSERT INTO connections (
             id, tenant_id, provider_slug, external_id, status, display_name,
-            access_token_ciphertext, refresh_token_ciphertext, expires_at, scopes, metadata,
-            created_at, updated_at
-        ) VALUES (?, ?, ?, ?, 'active', ?, NULL, NULL, NULL, ?, ?, CURRENT_TIMESTAMP, CURRENT_TIMESTAMP)",
+            expires_at, scopes, metadata, created_at, updated_at
+        ) VALUES (?, ?, ?, ?, 'active', ?, NULL, ?, ?, CURRENT_TIMESTAMP, CURRENT_TIMESTAMP)",
         vec![
             Value::Uuid(Some(Box::new(id))),
             Value::Uuid(Some(Box::new(tenant_id))),
@@ -251,7 +250,7 @@ pub async fn create_test_sync_job(
 }
 
 /// Creates a test sync job with specific attempt count in the database.
-#[allow(dead_code)]
+#[allow(dead_code, clippy::too_many_arguments)]
 pub async fn create_test_sync_job_with_attempts(
     db: &DatabaseConnection,
     tenant_id: Uuid,
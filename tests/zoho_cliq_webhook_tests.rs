@@ -9,8 +9,10 @@ use connectors::webhook_verification::{VerificationError, verify_webhook_signatu
 /// Test Zoho Cliq webhook verification with valid Bearer token
 #[tokio::test]
 async fn test_zoho_cliq_webhook_verification_integration() {
-    let mut config = AppConfig::default();
-    config.webhook_zoho_cliq_token = Some("test-zoho-cliq-token".to_string());
+    let config = AppConfig {
+        webhook_zoho_cliq_token: Some("test-zoho-cliq-token".to_string()),
+        ..Default::default()
+    };
 
     let mut headers = HeaderMap::new();
     headers.insert(
@@ -27,8 +29,10 @@ async fn test_zoho_cliq_webhook_verification_integration() {
 /// Test Zoho Cliq webhook verification rejection with invalid token
 #[tokio::test]
 async fn test_zoho_cliq_webhook_verification_rejection_integration() {
-    let mut config = AppConfig::default();
-    config.webhook_zoho_cliq_token = Some("correct-token".to_string());
+    let config = AppConfig {
+        webhook_zoho_cliq_token: Some("correct-token".to_string()),
+        ..Default::default()
+    };
 
     let mut headers = HeaderMap::new();
     headers.insert("authorization", "Bearer wrong-token".parse().unwrap());
@@ -64,8 +68,10 @@ async fn test_zoho_cliq_webhook_verification_not_configured_integration() {
 /// Test Zoho Cliq webhook verification with missing Authorization header
 #[tokio::test]
 async fn test_zoho_cliq_webhook_verification_missing_auth_integration() {
-    let mut config = AppConfig::default();
-    config.webhook_zoho_cliq_token = Some("some-token".to_string());
+    let config = AppConfig {
+        webhook_zoho_cliq_token: Some("some-token".to_string()),
+        ..Default::default()
+    };
 
     let headers = HeaderMap::new(); // No Authorization header
 
@@ -82,9 +88,11 @@ async fn test_zoho_cliq_webhook_verification_missing_auth_integration() {
 /// Test that other providers still work correctly
 #[tokio::test]
 async fn test_other_providers_unaffected_integration() {
-    let mut config = AppConfig::default();
-    config.webhook_zoho_cliq_token = Some("zoho-token".to_string());
-    config.webhook_github_secret = Some("github-secret".to_string());
+    let config = AppConfig {
+        webhook_zoho_cliq_token: Some("zoho-token".to_string()),
+        webhook_github_secret: Some("github-secret".to_string()),
+        ..Default::default()
+    };
 
     // Test GitHub still works
     let mut headers = HeaderMap::new();